@@ -0,0 +1,90 @@
+// Table Concurrency Benchmarks for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! Write-scaling benchmark for `TablesManager`.
+//!
+//! Every writer thread inserts into its own table. With per-table locks the
+//! throughput should grow with the thread count; the `global lock` column
+//! serializes all inserts behind one mutex to show the previous behavior.
+//!
+//! Run with `cargo bench --bench table_concurrency [-- <rows per thread>]`.
+
+use osland::dbos_integration::dbos_core::{ColumnDefinition, ColumnType, TableDefinition, TablesManager};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const THREAD_COUNTS: [usize; 5] = [1, 2, 4, 8, 16];
+
+fn table(name: &str) -> TableDefinition {
+    let column = |name: &str| ColumnDefinition {
+        name: name.to_string(),
+        column_type: ColumnType::String,
+        nullable: false,
+        default_value: None,
+        description: String::new(),
+    };
+    TableDefinition {
+        name: name.to_string(),
+        columns: vec![column("key"), column("value")],
+        primary_key: vec!["key".to_string()],
+        indexes: Vec::new(),
+        description: String::new(),
+        created_at: 0,
+        updated_at: 0,
+    }
+}
+
+/// Insert `rows` rows per thread into per-thread tables, optionally behind a global lock
+fn run(threads: usize, rows: usize, global_lock: Option<Arc<Mutex<()>>>) -> Duration {
+    let manager = Arc::new(TablesManager::new());
+    manager.start();
+    for thread in 0..threads {
+        manager.create_table(table(&format!("bench_{}", thread))).expect("create table failed");
+    }
+    
+    let start = Instant::now();
+    let handles: Vec<_> = (0..threads)
+        .map(|thread| {
+            let manager = manager.clone();
+            let global_lock = global_lock.clone();
+            thread::spawn(move || {
+                let table = format!("bench_{}", thread);
+                for index in 0..rows {
+                    let values = HashMap::from([
+                        ("key".to_string(), index.to_string()),
+                        ("value".to_string(), format!("value_{}", index % 32)),
+                    ]);
+                    let _guard = global_lock.as_ref().map(|lock| lock.lock().unwrap());
+                    manager.insert_row(&table, values).expect("insert failed");
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().expect("writer panicked");
+    }
+    start.elapsed()
+}
+
+fn main() {
+    let rows: usize = std::env::args().skip(1)
+        .find_map(|arg| arg.parse().ok())
+        .unwrap_or(100_000);
+    println!("Table concurrency benchmark: {} inserts per writer, one table per writer", rows);
+    println!("{:>8} {:>18} {:>18}", "writers", "per-table (ops/s)", "global lock (ops/s)");
+    
+    for threads in THREAD_COUNTS {
+        let total = (threads * rows) as f64;
+        let sharded = run(threads, rows, None);
+        let global = run(threads, rows, Some(Arc::new(Mutex::new(()))));
+        println!(
+            "{:>8} {:>18.0} {:>18.0}",
+            threads,
+            total / sharded.as_secs_f64(),
+            total / global.as_secs_f64(),
+        );
+    }
+}
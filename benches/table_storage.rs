@@ -0,0 +1,94 @@
+// Table Storage Benchmarks for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! Memory benchmark for DBOS table row storage.
+//!
+//! Compares the previous layout (one `HashMap<String, String>` per row, keyed by
+//! owned column names) with the ordinal/dictionary-encoded `TablesManager`
+//! storage, measuring live heap bytes with a counting allocator.
+//!
+//! Run with `cargo bench --bench table_storage [-- <rows>]` (default 1,000,000 rows).
+
+use osland::dbos_integration::dbos_core::{TableRow, TablesManager};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+/// System allocator that tracks the number of live heap bytes
+struct CountingAllocator;
+
+static LIVE_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        LIVE_BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+    
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        LIVE_BYTES.fetch_sub(layout.size(), Ordering::Relaxed);
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+const STATUSES: [&str; 4] = ["CREATED", "RUNNING", "SLEEPING", "TERMINATED"];
+
+/// Column values of the n-th benchmark task
+fn task_values(index: usize) -> HashMap<String, String> {
+    HashMap::from([
+        ("task_id".to_string(), format!("{:08x}-0000-4000-8000-{:012x}", index, index)),
+        ("name".to_string(), format!("worker_{}", index % 64)),
+        ("status".to_string(), STATUSES[index % STATUSES.len()].to_string()),
+        ("priority".to_string(), (index % 8).to_string()),
+    ])
+}
+
+/// Measure the live heap growth caused by `build`, keeping its result alive
+fn measure<T>(label: &str, build: impl FnOnce() -> T) -> (T, usize) {
+    let before = LIVE_BYTES.load(Ordering::Relaxed);
+    let start = Instant::now();
+    let value = build();
+    let bytes = LIVE_BYTES.load(Ordering::Relaxed).saturating_sub(before);
+    println!("{:<32} {:>10.1} MiB  {:>8.2?}", label, bytes as f64 / (1024.0 * 1024.0), start.elapsed());
+    (value, bytes)
+}
+
+fn main() {
+    let rows: usize = std::env::args().skip(1)
+        .find_map(|arg| arg.parse().ok())
+        .unwrap_or(1_000_000);
+    println!("Table storage benchmark: {} rows of the `tasks` table", rows);
+    
+    let (legacy, legacy_bytes) = measure("HashMap per row (before)", || {
+        let mut store = BTreeMap::new();
+        for index in 0..rows {
+            let row_id = format!("row-{}", index);
+            store.insert(row_id.clone(), TableRow {
+                row_id,
+                values: task_values(index),
+                created_at: 0,
+                updated_at: 0,
+            });
+        }
+        store
+    });
+    drop(legacy);
+    
+    let (manager, compact_bytes) = measure("Ordinal + dictionary (after)", || {
+        let manager = TablesManager::new();
+        manager.start();
+        for index in 0..rows {
+            manager.insert_row("tasks", task_values(index)).expect("insert failed");
+        }
+        manager
+    });
+    
+    let stats = manager.storage_stats("tasks").expect("missing tasks table");
+    println!("Dictionary entries: {} across {} columns", stats.dictionary_entries, stats.dictionary_columns);
+    println!("Reduction: {:.1}x", legacy_bytes as f64 / compact_bytes.max(1) as f64);
+}
@@ -0,0 +1,16 @@
+// Build script for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+// Generate the gRPC server and client of the daemon (`osland serve`)
+//
+// Uses the vendored protoc unless PROTOC points to another one.
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    println!("cargo:rerun-if-changed=proto/osland.proto");
+    println!("cargo:rerun-if-env-changed=PROTOC");
+    if std::env::var_os("PROTOC").is_none() {
+        std::env::set_var("PROTOC", protoc_bin_vendored::protoc_bin_path()?);
+    }
+    tonic_build::compile_protos("proto/osland.proto")?;
+    Ok(())
+}
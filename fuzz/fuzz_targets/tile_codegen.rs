@@ -0,0 +1,82 @@
+// Tile Code Generation Fuzz Target for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! Fuzzes `TileCompiler::generate_execution_code` with arbitrary tile graphs.
+//!
+//! Names, properties and user code are unrestricted, so only the absence of panics
+//! and code generation errors is checked; syntax of the generated code is covered
+//! by the property tests. Run with `cargo +nightly fuzz run tile_codegen`.
+
+#![no_main]
+
+use libfuzzer_sys::arbitrary::{self, Arbitrary};
+use libfuzzer_sys::fuzz_target;
+use osland::core::architecture::KernelArchitecture;
+use osland::tile_engine::tile_compiler::{CompilationOptions, TargetLanguage, TileCompiler};
+use osland::tile_engine::tile_core::{ConnectionType, PortType, Tile, TileConnection, TileGraph, TilePort, TileType};
+
+#[derive(Debug, Arbitrary)]
+struct FuzzTile {
+    name: String,
+    properties: Vec<(String, String)>,
+    ports: Vec<(String, u8)>,
+    initialization_code: String,
+    execution_code: String,
+}
+
+#[derive(Debug, Arbitrary)]
+struct Input {
+    graph_name: String,
+    tiles: Vec<FuzzTile>,
+    connections: Vec<(u8, u8)>,
+    language: u8,
+}
+
+fuzz_target!(|input: Input| {
+    let mut graph = TileGraph::new(input.graph_name);
+    let mut ports = Vec::new();
+    for (index, fuzz_tile) in input.tiles.into_iter().take(16).enumerate() {
+        let mut tile = Tile::new(fuzz_tile.name, TileType::Processing, String::new());
+        tile.id = format!("tile-{}", index);
+        for (key, value) in fuzz_tile.properties {
+            tile.set_property(key, value);
+        }
+        for (port_index, (name, kind)) in fuzz_tile.ports.into_iter().enumerate() {
+            let port_type = match kind % 3 {
+                0 => PortType::Input,
+                1 => PortType::Output,
+                _ => PortType::Bidirectional,
+            };
+            let id = format!("port-{}", port_index);
+            ports.push((tile.id.clone(), id.clone()));
+            tile.add_port(TilePort { id, name, port_type, data_type: "u32".to_string(), description: String::new() });
+        }
+        tile.initialization_code = fuzz_tile.initialization_code;
+        tile.execution_code = fuzz_tile.execution_code;
+        graph.add_tile(tile).unwrap();
+    }
+    if !ports.is_empty() {
+        for (index, (source, dest)) in input.connections.into_iter().enumerate() {
+            let (source_tile_id, source_port_id) = ports[source as usize % ports.len()].clone();
+            let (dest_tile_id, dest_port_id) = ports[dest as usize % ports.len()].clone();
+            graph.add_connection(TileConnection {
+                id: format!("conn-{}", index),
+                source_tile_id,
+                source_port_id,
+                dest_tile_id,
+                dest_port_id,
+                connection_type: ConnectionType::DataFlow,
+            }).unwrap();
+        }
+    }
+    
+    let languages = TargetLanguage::all();
+    let language = languages[input.language as usize % languages.len()].clone();
+    let options = CompilationOptions {
+        target_language: language,
+        ..CompilationOptions::default()
+    };
+    let compiler = TileCompiler::new(KernelArchitecture::Microkernel, Some(options));
+    compiler.generate_execution_code(&graph).unwrap();
+});
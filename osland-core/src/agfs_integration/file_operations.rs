@@ -5,7 +5,8 @@
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
 use serde::{Serialize, Deserialize};
-use std::path::PathBuf;
+use std::path::{Component, Path, PathBuf};
+use crate::dbos_integration::dbos_core::validate_namespace;
 
 /// File Manager
 pub struct FileManager {
@@ -20,6 +21,9 @@ pub struct FileManager {
     
     /// Is the file manager running
     running: Arc<RwLock<bool>>,
+    
+    /// Namespace the manager is confined to (`None` sees every namespace)
+    namespace: Option<String>,
 }
 
 /// Open File Descriptor
@@ -39,6 +43,10 @@ pub struct OpenFile {
     
     /// File content (in memory for simplicity)
     pub content: Vec<u8>,
+    
+    /// Namespace that opened the file
+    #[serde(default)]
+    pub namespace: Option<String>,
 }
 
 /// File Mode
@@ -151,9 +159,62 @@ impl FileManager {
             open_files: Arc::new(RwLock::new(HashMap::new())),
             next_fd: Arc::new(RwLock::new(1)),
             running: Arc::new(RwLock::new(false)),
+            namespace: None,
         }
     }
     
+    /// Get a file manager confined to a namespace
+    ///
+    /// It shares the open files and running state of this manager, but resolves
+    /// paths below `<root>/namespaces/<namespace>` and can only use the file
+    /// descriptors it opened itself.
+    ///
+    /// Only `open` is scoped to the namespace directory. `stat`, `list_dir`, `mkdir`,
+    /// `remove`, `copy` and `rename` are placeholders without backing storage: they
+    /// reject paths leaving the namespace but do not touch the resolved path.
+    pub fn scoped(&self, namespace: &str) -> Result<FileManager, String> {
+        validate_namespace(namespace)?;
+        Ok(Self {
+            root: self.root.clone(),
+            open_files: self.open_files.clone(),
+            next_fd: self.next_fd.clone(),
+            running: self.running.clone(),
+            namespace: Some(namespace.to_string()),
+        })
+    }
+    
+    /// Get the namespace the manager is confined to
+    pub fn namespace(&self) -> Option<&str> {
+        self.namespace.as_deref()
+    }
+    
+    /// Resolve a path for this manager
+    ///
+    /// Paths are used as given without a namespace. With one, they are taken relative
+    /// to the namespace directory and may not leave it.
+    pub fn resolve_path(&self, path: &str) -> Result<PathBuf, String> {
+        let namespace = match &self.namespace {
+            Some(namespace) => namespace,
+            None => return Ok(PathBuf::from(path)),
+        };
+        let mut resolved = self.root.join("namespaces").join(namespace);
+        for component in Path::new(path).components() {
+            match component {
+                Component::Normal(part) => resolved.push(part),
+                Component::RootDir | Component::CurDir => {}
+                Component::ParentDir | Component::Prefix(_) => {
+                    return Err(format!("Path '{}' leaves namespace '{}'", path, namespace));
+                }
+            }
+        }
+        Ok(resolved)
+    }
+    
+    /// Check whether a file descriptor may be used by this manager
+    fn owns(&self, file: &OpenFile) -> bool {
+        self.namespace.is_none() || file.namespace == self.namespace
+    }
+    
     /// Start the file manager
     pub fn start(&self) {
         let mut running = self.running.write().unwrap();
@@ -195,10 +256,11 @@ impl FileOperation for FileManager {
         // Create a virtual file (in a real implementation, this would access the actual resource)
         let open_file = OpenFile {
             fd,
-            path: PathBuf::from(path),
+            path: self.resolve_path(path)?,
             mode,
             position: 0,
             content: Vec::new(), // Empty content initially
+            namespace: self.namespace.clone(),
         };
         
         let mut open_files = self.open_files.write().unwrap();
@@ -209,7 +271,8 @@ impl FileOperation for FileManager {
     
     fn close(&self, fd: u32) -> Result<(), String> {
         let mut open_files = self.open_files.write().unwrap();
-        if open_files.remove(&fd).is_some() {
+        if open_files.get(&fd).is_some_and(|file| self.owns(file)) {
+            open_files.remove(&fd);
             Ok(())
         } else {
             Err("Invalid file descriptor".to_string())
@@ -218,7 +281,7 @@ impl FileOperation for FileManager {
     
     fn read(&self, fd: u32, buffer: &mut [u8]) -> Result<usize, String> {
         let open_files = self.open_files.read().unwrap();
-        if let Some(file) = open_files.get(&fd) {
+        if let Some(file) = open_files.get(&fd).filter(|file| self.owns(file)) {
             let available = file.content.len() - file.position as usize;
             let to_read = std::cmp::min(buffer.len(), available);
             
@@ -242,7 +305,7 @@ impl FileOperation for FileManager {
     
     fn write(&self, fd: u32, buffer: &[u8]) -> Result<usize, String> {
         let mut open_files = self.open_files.write().unwrap();
-        if let Some(file) = open_files.get_mut(&fd) {
+        if let Some(file) = open_files.get_mut(&fd).filter(|file| self.owns(file)) {
             match file.mode {
                 FileMode::Read => return Err("File not open for writing".to_string()),
                 FileMode::Append => {
@@ -273,7 +336,7 @@ impl FileOperation for FileManager {
     
     fn seek(&self, fd: u32, position: u64) -> Result<u64, String> {
         let mut open_files = self.open_files.write().unwrap();
-        if let Some(file) = open_files.get_mut(&fd) {
+        if let Some(file) = open_files.get_mut(&fd).filter(|file| self.owns(file)) {
             file.position = position;
             Ok(position)
         } else {
@@ -282,6 +345,7 @@ impl FileOperation for FileManager {
     }
     
     fn stat(&self, path: &str) -> Result<FileInfo, String> {
+        self.resolve_path(path)?;
         // This is a placeholder implementation
         // In a real implementation, this would query the actual resource provider
         Ok(FileInfo {
@@ -299,32 +363,64 @@ impl FileOperation for FileManager {
     }
     
     fn list_dir(&self, path: &str) -> Result<Vec<DirEntry>, String> {
+        self.resolve_path(path)?;
         // This is a placeholder implementation
         // In a real implementation, this would query the actual resource provider
         Ok(Vec::new())
     }
     
     fn mkdir(&self, path: &str) -> Result<(), String> {
+        self.resolve_path(path)?;
         // This is a placeholder implementation
         // In a real implementation, this would create a directory in the resource provider
         Ok(())
     }
     
     fn remove(&self, path: &str) -> Result<(), String> {
+        self.resolve_path(path)?;
         // This is a placeholder implementation
         // In a real implementation, this would remove a file or directory from the resource provider
         Ok(())
     }
     
     fn copy(&self, src: &str, dst: &str) -> Result<(), String> {
+        self.resolve_path(src)?;
+        self.resolve_path(dst)?;
         // This is a placeholder implementation
         // In a real implementation, this would copy a file between resource providers
         Ok(())
     }
     
     fn rename(&self, src: &str, dst: &str) -> Result<(), String> {
+        self.resolve_path(src)?;
+        self.resolve_path(dst)?;
         // This is a placeholder implementation
         // In a real implementation, this would rename a file in the resource provider
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_namespaces_are_isolated() {
+        let manager = FileManager::new();
+        manager.start();
+        let alice = manager.scoped("alice").unwrap();
+        let bob = manager.scoped("bob").unwrap();
+        
+        assert_eq!(alice.resolve_path("/src/main.c").unwrap(), PathBuf::from("/agfs/namespaces/alice/src/main.c"));
+        assert!(alice.resolve_path("../bob/secret").is_err());
+        assert!(alice.open("src/../../bob/secret", FileMode::Read).is_err());
+        assert!(manager.scoped("a/b").is_err());
+        
+        let fd = alice.open("notes.txt", FileMode::Write).unwrap();
+        assert_eq!(alice.write(fd, b"hi").unwrap(), 2);
+        assert!(bob.write(fd, b"no").is_err());
+        assert!(bob.seek(fd, 0).is_err());
+        assert!(bob.close(fd).is_err());
+        alice.close(fd).unwrap();
+    }
+}
@@ -6,6 +6,8 @@ use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
 use serde::{Serialize, Deserialize};
 use std::time::SystemTime;
+use crate::core::cancellation::CancellationToken;
+use crate::core::resource_governor::{BackgroundJob, ResourceGovernor};
 
 /// Search Engine
 pub struct SearchEngine {
@@ -89,6 +91,9 @@ impl SearchEngine {
     }
     
     /// Index a resource
+    ///
+    /// Waits while the host is busy, so call it from a background thread; canceling
+    /// `token` ends the wait and fails the call without indexing.
     pub fn index_resource(
         &self,
         id: String,
@@ -96,7 +101,12 @@ impl SearchEngine {
         path: String,
         content: String,
         resource_type: SearchResultType,
+        token: &CancellationToken,
     ) -> Result<(), String> {
+        ResourceGovernor::global().throttle(BackgroundJob::Indexing, Some(token));
+        if token.is_canceled() {
+            return Err(format!("Indexing of {} canceled", id));
+        }
         let running = self.running.read().unwrap();
         if !*running {
             return Err("Search engine is not running".to_string());
@@ -3,6 +3,8 @@
 // SPDX-License-Identifier: MulanPSL-2.0
 
 use crate::ai_assistant::{AIAssistantError, model_manager::{ModelManager, ModelParams}};
+use crate::ai_assistant::code_review::Provenance;
+use crate::ai_assistant::pattern_library::DesignPattern;
 use crate::kernel_extractor::KernelComponent;
 use crate::component_manager::Component;
 use std::sync::Arc;
@@ -27,6 +29,12 @@ pub struct CodeGenerationContext {
     
     /// Additional context
     pub additional_context: String,
+    
+    /// Feedback from rejected hunks of earlier reviews
+    pub review_feedback: Vec<String>,
+    
+    /// Design patterns the generated code may build on
+    pub patterns: Vec<DesignPattern>,
 }
 
 /// Code style preferences
@@ -71,6 +79,9 @@ pub struct CodeGenerationResult {
     
     /// Issues or warnings
     pub issues: Vec<String>,
+    
+    /// Model and prompt that produced the code
+    pub provenance: Provenance,
 }
 
 /// Code generator trait
@@ -127,6 +138,21 @@ impl AICodeGenerator {
         }
         
         prompt.push_str(&format!("Additional Context: {}\n", context.additional_context));
+        
+        if !context.review_feedback.is_empty() {
+            prompt.push_str("A reviewer rejected these changes in earlier output. Do not repeat them:\n");
+            for feedback in &context.review_feedback {
+                prompt.push_str(&format!("{}\n", feedback));
+            }
+        }
+        
+        if !context.patterns.is_empty() {
+            prompt.push_str("Relevant OS design patterns. Cite a pattern by its [pattern:id] marker in a comment where the code follows it:\n");
+            for pattern in &context.patterns {
+                prompt.push_str(&pattern.to_prompt());
+            }
+        }
+        
         prompt.push_str("Generate the requested code below.\n");
         prompt.push_str("```");
         
@@ -162,6 +188,7 @@ impl CodeGenerator for AICodeGenerator {
             confidence: 0.85, // Mock confidence score
             explanation: "Generated code based on the provided context".to_string(),
             issues: Vec::new(),
+            provenance: Provenance::new(&self.default_model, &prompt),
         })
     }
     
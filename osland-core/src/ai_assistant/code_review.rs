@@ -0,0 +1,365 @@
+// Code review of AI-generated output for OSland AI Assistant
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! Review mode for AI-generated code.
+//!
+//! Generated code is never written directly. It is turned into a `CodeReview`: a
+//! diff against the current file or tile code, split into hunks that are accepted or
+//! rejected one by one. Only accepted hunks are applied, and the reasons given for
+//! rejected hunks are passed back to the code generator with the next request.
+
+use std::fs;
+use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use similar::{DiffTag, TextDiff};
+use uuid::Uuid;
+use crate::ai_assistant::{AIAssistantError, CodeGenerationContext, CodeGenerationResult};
+use crate::tile_engine::tile_core::Tile;
+
+/// Tile property holding the model that generated the tile code
+pub const PROVENANCE_MODEL_PROPERTY: &str = "ai.model";
+
+/// Tile property holding the hash of the prompt that generated the tile code
+pub const PROVENANCE_PROMPT_HASH_PROPERTY: &str = "ai.prompt_hash";
+
+/// Code section of a tile
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TileCodeSection {
+    Initialization,
+    Execution,
+}
+
+/// Code the generated output is reviewed against
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReviewTarget {
+    /// Source file
+    File(PathBuf),
+    /// Code section of a tile
+    Tile { tile_id: String, section: TileCodeSection },
+}
+
+/// Origin of generated code
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Provenance {
+    /// Model that generated the code
+    pub model: String,
+    
+    /// SHA-256 of the prompt (hex)
+    pub prompt_hash: String,
+    
+    /// Generation time (Unix seconds)
+    pub generated_at: u64,
+}
+
+impl Provenance {
+    /// Record that a model generated code from a prompt
+    pub fn new(model: &str, prompt: &str) -> Self {
+        Self {
+            model: model.to_string(),
+            prompt_hash: prompt_hash(prompt),
+            generated_at: chrono::Utc::now().timestamp() as u64,
+        }
+    }
+}
+
+/// Hash a prompt for provenance records
+pub fn prompt_hash(prompt: &str) -> String {
+    Sha256::digest(prompt.as_bytes()).iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Reviewer's decision on a hunk
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum HunkDecision {
+    #[default]
+    Pending,
+    Accepted,
+    Rejected { reason: Option<String> },
+}
+
+/// Contiguous change proposed by the generated code
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReviewHunk {
+    /// Index of the first replaced line in the original code (0-based)
+    pub old_start: usize,
+    
+    /// Index of the first inserted line in the generated code (0-based)
+    pub new_start: usize,
+    
+    /// Original lines replaced by the hunk, with line endings
+    pub old_lines: Vec<String>,
+    
+    /// Generated lines, with line endings
+    pub new_lines: Vec<String>,
+    
+    /// Reviewer's decision
+    pub decision: HunkDecision,
+}
+
+impl ReviewHunk {
+    /// Render the hunk in unified diff format
+    pub fn to_unified(&self) -> String {
+        let mut text = format!(
+            "@@ -{},{} +{},{} @@\n",
+            self.old_start + 1,
+            self.old_lines.len(),
+            self.new_start + 1,
+            self.new_lines.len(),
+        );
+        for (prefix, lines) in [('-', &self.old_lines), ('+', &self.new_lines)] {
+            for line in lines {
+                text.push(prefix);
+                text.push_str(line.trim_end_matches(['\r', '\n']));
+                text.push('\n');
+            }
+        }
+        text
+    }
+}
+
+/// Reviewable diff of generated code against its target
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CodeReview {
+    /// Review ID
+    pub id: String,
+    
+    /// Reviewed file or tile
+    pub target: ReviewTarget,
+    
+    /// Code before the change
+    pub original: String,
+    
+    /// Code as generated
+    pub proposed: String,
+    
+    /// Origin of the generated code
+    pub provenance: Provenance,
+    
+    /// Changes, in order
+    pub hunks: Vec<ReviewHunk>,
+}
+
+impl CodeReview {
+    /// Diff generated code against the current code of a target
+    pub fn new(target: ReviewTarget, original: &str, proposed: &str, provenance: Provenance) -> Self {
+        let diff = TextDiff::from_lines(original, proposed);
+        let old_lines: Vec<&str> = diff.old_slices().to_vec();
+        let new_lines: Vec<&str> = diff.new_slices().to_vec();
+        
+        let mut hunks: Vec<ReviewHunk> = Vec::new();
+        for op in diff.ops() {
+            if op.tag() == DiffTag::Equal {
+                continue;
+            }
+            let (old_range, new_range) = (op.old_range(), op.new_range());
+            // A delete followed by an insert at the same place is one replacement
+            match hunks.last_mut() {
+                Some(last) if last.old_start + last.old_lines.len() == old_range.start
+                    && last.new_start + last.new_lines.len() == new_range.start => {
+                    last.old_lines.extend(old_lines[old_range].iter().map(|line| line.to_string()));
+                    last.new_lines.extend(new_lines[new_range].iter().map(|line| line.to_string()));
+                }
+                _ => hunks.push(ReviewHunk {
+                    old_start: old_range.start,
+                    new_start: new_range.start,
+                    old_lines: old_lines[old_range].iter().map(|line| line.to_string()).collect(),
+                    new_lines: new_lines[new_range].iter().map(|line| line.to_string()).collect(),
+                    decision: HunkDecision::Pending,
+                }),
+            }
+        }
+        
+        Self {
+            id: Uuid::new_v4().to_string(),
+            target,
+            original: original.to_string(),
+            proposed: proposed.to_string(),
+            provenance,
+            hunks,
+        }
+    }
+    
+    /// Review the result of a code generation request
+    pub fn from_generation(target: ReviewTarget, original: &str, result: &CodeGenerationResult) -> Self {
+        Self::new(target, original, &result.code, result.provenance.clone())
+    }
+    
+    /// Review generated code for a file against its current content
+    pub fn for_file(path: PathBuf, proposed: &str, provenance: Provenance) -> Result<Self, AIAssistantError> {
+        let original = if path.exists() { fs::read_to_string(&path)? } else { String::new() };
+        Ok(Self::new(ReviewTarget::File(path), &original, proposed, provenance))
+    }
+    
+    /// Review generated code for a section of a tile
+    pub fn for_tile(tile: &Tile, section: TileCodeSection, proposed: &str, provenance: Provenance) -> Self {
+        let original = match section {
+            TileCodeSection::Initialization => &tile.initialization_code,
+            TileCodeSection::Execution => &tile.execution_code,
+        };
+        Self::new(ReviewTarget::Tile { tile_id: tile.id.clone(), section }, original, proposed, provenance)
+    }
+    
+    /// Accept a hunk
+    pub fn accept(&mut self, hunk: usize) -> Result<(), AIAssistantError> {
+        self.decide(hunk, HunkDecision::Accepted)
+    }
+    
+    /// Reject a hunk, optionally saying why
+    pub fn reject(&mut self, hunk: usize, reason: Option<&str>) -> Result<(), AIAssistantError> {
+        self.decide(hunk, HunkDecision::Rejected { reason: reason.map(str::to_string) })
+    }
+    
+    /// Accept every pending hunk
+    pub fn accept_remaining(&mut self) {
+        for hunk in self.hunks.iter_mut().filter(|hunk| hunk.decision == HunkDecision::Pending) {
+            hunk.decision = HunkDecision::Accepted;
+        }
+    }
+    
+    /// Reject every pending hunk
+    pub fn reject_remaining(&mut self, reason: Option<&str>) {
+        for hunk in self.hunks.iter_mut().filter(|hunk| hunk.decision == HunkDecision::Pending) {
+            hunk.decision = HunkDecision::Rejected { reason: reason.map(str::to_string) };
+        }
+    }
+    
+    /// Check whether every hunk has been decided
+    pub fn is_complete(&self) -> bool {
+        self.hunks.iter().all(|hunk| hunk.decision != HunkDecision::Pending)
+    }
+    
+    /// Code with the accepted hunks applied to the original
+    pub fn merged(&self) -> String {
+        let original: Vec<&str> = self.original.split_inclusive('\n').collect();
+        let mut merged = String::with_capacity(self.original.len().max(self.proposed.len()));
+        let mut position = 0;
+        for hunk in &self.hunks {
+            merged.extend(original[position..hunk.old_start].iter().copied());
+            let lines = if hunk.decision == HunkDecision::Accepted { &hunk.new_lines } else { &hunk.old_lines };
+            merged.extend(lines.iter().map(String::as_str));
+            position = hunk.old_start + hunk.old_lines.len();
+        }
+        merged.extend(original[position..].iter().copied());
+        merged
+    }
+    
+    /// Feedback on the rejected hunks, for the next generation request
+    pub fn rejection_feedback(&self) -> Vec<String> {
+        self.hunks.iter()
+            .filter_map(|hunk| match &hunk.decision {
+                HunkDecision::Rejected { reason } => Some(match reason {
+                    Some(reason) => format!("Rejected change: {}\n{}", reason, hunk.to_unified()),
+                    None => format!("Rejected change:\n{}", hunk.to_unified()),
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+    
+    /// Add the rejection feedback to the context of the next generation request
+    pub fn feed_back(&self, context: &mut CodeGenerationContext) {
+        context.review_feedback.extend(self.rejection_feedback());
+    }
+    
+    /// Render the whole review in unified diff format
+    pub fn to_unified(&self) -> String {
+        self.hunks.iter().map(ReviewHunk::to_unified).collect()
+    }
+    
+    /// Write the merged code to the reviewed file
+    pub fn apply_to_file(&self) -> Result<(), AIAssistantError> {
+        self.ensure_complete()?;
+        let ReviewTarget::File(path) = &self.target else {
+            return Err(AIAssistantError::GenerationError("Review does not target a file".to_string()));
+        };
+        fs::write(path, self.merged())?;
+        Ok(())
+    }
+    
+    /// Write the merged code to the reviewed tile and record its provenance
+    pub fn apply_to_tile(&self, tile: &mut Tile) -> Result<(), AIAssistantError> {
+        self.ensure_complete()?;
+        let section = match &self.target {
+            ReviewTarget::Tile { tile_id, section } if *tile_id == tile.id => *section,
+            _ => return Err(AIAssistantError::GenerationError(format!("Review does not target tile {}", tile.id))),
+        };
+        
+        let merged = self.merged();
+        match section {
+            TileCodeSection::Initialization => tile.set_initialization_code(merged),
+            TileCodeSection::Execution => tile.set_execution_code(merged),
+        }
+        if self.hunks.iter().any(|hunk| hunk.decision == HunkDecision::Accepted) {
+            tile.set_property(PROVENANCE_MODEL_PROPERTY.to_string(), self.provenance.model.clone());
+            tile.set_property(PROVENANCE_PROMPT_HASH_PROPERTY.to_string(), self.provenance.prompt_hash.clone());
+        }
+        Ok(())
+    }
+    
+    fn decide(&mut self, hunk: usize, decision: HunkDecision) -> Result<(), AIAssistantError> {
+        let count = self.hunks.len();
+        let hunk = self.hunks.get_mut(hunk)
+            .ok_or_else(|| AIAssistantError::GenerationError(format!("Hunk {} out of range (review has {})", hunk, count)))?;
+        hunk.decision = decision;
+        Ok(())
+    }
+    
+    fn ensure_complete(&self) -> Result<(), AIAssistantError> {
+        let pending = self.hunks.iter().filter(|hunk| hunk.decision == HunkDecision::Pending).count();
+        if pending > 0 {
+            return Err(AIAssistantError::GenerationError(format!("{} hunks are still pending review", pending)));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tile_engine::tile_core::TileType;
+    
+    const ORIGINAL: &str = "fn init() {\n    setup();\n}\n\nfn run() {\n    loop {}\n}\n";
+    const PROPOSED: &str = "fn init() {\n    setup();\n    log(\"ready\");\n}\n\nfn run() {\n    loop { schedule(); }\n}\n";
+    
+    #[test]
+    fn test_hunks_apply_independently() {
+        let mut review = CodeReview::new(ReviewTarget::File(PathBuf::from("init.rs")), ORIGINAL, PROPOSED, Provenance::new("local", "prompt"));
+        assert_eq!(review.hunks.len(), 2);
+        assert_eq!(review.hunks[1].old_lines, vec!["    loop {}\n"]);
+        assert!(review.to_unified().contains("+    loop { schedule(); }\n"));
+        
+        review.accept(0).unwrap();
+        review.reject(1, Some("the scheduler is not ready")).unwrap();
+        assert!(review.reject(2, None).is_err());
+        assert!(review.is_complete());
+        assert_eq!(review.merged(), "fn init() {\n    setup();\n    log(\"ready\");\n}\n\nfn run() {\n    loop {}\n}\n");
+        
+        let feedback = review.rejection_feedback();
+        assert_eq!(feedback.len(), 1);
+        assert!(feedback[0].starts_with("Rejected change: the scheduler is not ready\n@@ -6,1 +7,1 @@"));
+        
+        review.accept(1).unwrap();
+        assert_eq!(review.merged(), PROPOSED);
+    }
+    
+    #[test]
+    fn test_apply_to_tile_records_provenance() {
+        let mut tile = Tile::new("timer".to_string(), TileType::Custom("timer".to_string()), String::new());
+        tile.set_execution_code(ORIGINAL.to_string());
+        
+        let mut review = CodeReview::for_tile(&tile, TileCodeSection::Execution, PROPOSED, Provenance::new("local", "prompt"));
+        assert!(review.apply_to_tile(&mut tile).is_err());
+        
+        review.reject_remaining(None);
+        review.apply_to_tile(&mut tile).unwrap();
+        assert_eq!(tile.execution_code, ORIGINAL);
+        assert!(tile.get_property(PROVENANCE_MODEL_PROPERTY).is_none());
+        
+        review.accept(0).unwrap();
+        review.accept(1).unwrap();
+        review.apply_to_tile(&mut tile).unwrap();
+        assert_eq!(tile.execution_code, PROPOSED);
+        assert_eq!(tile.get_property(PROVENANCE_PROMPT_HASH_PROPERTY), Some(&prompt_hash("prompt")));
+    }
+}
@@ -0,0 +1,650 @@
+// Conversation memory for OSland AI Assistant
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! Per-project memory of conversations with the AI assistant.
+//!
+//! Threads and their entries live in DBOS tables and are saved to
+//! `CONVERSATION_MEMORY_FILE` in the project, so they survive IDE restarts. Once a
+//! thread grows past `MemoryPolicy::keep_recent_turns`, its oldest turns are folded
+//! into a summary entry. Design decisions are stored as their own entries and are
+//! looked up by keyword when a new prompt is built.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use crate::ai_assistant::{AIAssistantError, ModelManager, ModelParams};
+use crate::dbos_integration::dbos_core::{ColumnDefinition, ColumnType, IndexDefinition, TableDefinition, TableRow, TablesManager};
+
+/// Table holding one row per conversation thread
+pub const CONVERSATIONS_TABLE: &str = "ai_conversations";
+
+/// Table holding the turns, summaries and decisions of each thread
+pub const CONVERSATION_ENTRIES_TABLE: &str = "ai_conversation_entries";
+
+/// Project-relative path of the saved conversation memory
+pub const CONVERSATION_MEMORY_FILE: &str = ".osland/ai_conversations.json";
+
+/// Author of a conversation entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TurnRole {
+    User,
+    Assistant,
+    System,
+}
+
+impl TurnRole {
+    fn as_str(&self) -> &'static str {
+        match self {
+            TurnRole::User => "user",
+            TurnRole::Assistant => "assistant",
+            TurnRole::System => "system",
+        }
+    }
+    
+    fn parse(value: &str) -> Self {
+        match value {
+            "user" => TurnRole::User,
+            "assistant" => TurnRole::Assistant,
+            _ => TurnRole::System,
+        }
+    }
+}
+
+/// Kind of a conversation entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EntryKind {
+    /// Message exchanged with the assistant
+    Turn,
+    /// Summary replacing older turns
+    Summary,
+    /// Recorded design decision
+    Decision,
+}
+
+impl EntryKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            EntryKind::Turn => "turn",
+            EntryKind::Summary => "summary",
+            EntryKind::Decision => "decision",
+        }
+    }
+    
+    fn parse(value: &str) -> Self {
+        match value {
+            "summary" => EntryKind::Summary,
+            "decision" => EntryKind::Decision,
+            _ => EntryKind::Turn,
+        }
+    }
+}
+
+/// Conversation thread
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConversationThread {
+    /// Thread ID
+    pub id: String,
+    
+    /// Thread title
+    pub title: String,
+    
+    /// Creation time (Unix seconds)
+    pub created_at: u64,
+    
+    /// Time of the last entry (Unix seconds)
+    pub updated_at: u64,
+}
+
+/// Turn, summary or decision of a thread
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConversationEntry {
+    /// Entry ID (row ID)
+    pub id: String,
+    
+    /// Owning thread
+    pub thread_id: String,
+    
+    /// Position in the thread
+    pub sequence: u64,
+    
+    /// Author
+    pub role: TurnRole,
+    
+    /// Entry kind
+    pub kind: EntryKind,
+    
+    /// Decision title (decisions only)
+    pub title: Option<String>,
+    
+    /// Text
+    pub content: String,
+    
+    /// The turn has been folded into a summary
+    pub summarized: bool,
+    
+    /// Creation time (Unix seconds)
+    pub created_at: u64,
+}
+
+/// When old turns are summarized and how much memory goes into a prompt
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoryPolicy {
+    /// Turns kept verbatim at the end of a thread
+    pub keep_recent_turns: usize,
+    
+    /// Minimum number of old turns summarized at once
+    pub summarize_batch: usize,
+    
+    /// Maximum number of design decisions added to a prompt
+    pub max_prompt_decisions: usize,
+}
+
+impl Default for MemoryPolicy {
+    fn default() -> Self {
+        Self {
+            keep_recent_turns: 20,
+            summarize_batch: 10,
+            max_prompt_decisions: 5,
+        }
+    }
+}
+
+/// Condenses old turns into a summary
+pub trait Summarizer: Send + Sync {
+    fn summarize(&self, previous_summary: Option<&str>, turns: &[ConversationEntry]) -> Result<String, AIAssistantError>;
+}
+
+/// Summarizer keeping the first sentence of every turn
+///
+/// Used when no model is configured; it needs no network access.
+#[derive(Debug, Default)]
+pub struct ExtractiveSummarizer;
+
+impl Summarizer for ExtractiveSummarizer {
+    fn summarize(&self, previous_summary: Option<&str>, turns: &[ConversationEntry]) -> Result<String, AIAssistantError> {
+        let mut lines: Vec<String> = previous_summary.map(|summary| summary.to_string()).into_iter().collect();
+        for turn in turns {
+            let first_sentence = turn.content.split_inclusive(['.', '\n']).next().unwrap_or_default().trim();
+            let mut sentence: String = first_sentence.chars().take(200).collect();
+            if sentence.len() < first_sentence.len() {
+                sentence.push_str("...");
+            }
+            lines.push(format!("{}: {}", turn.role.as_str(), sentence));
+        }
+        Ok(lines.join("\n"))
+    }
+}
+
+/// Summarizer asking a model
+pub struct ModelSummarizer {
+    /// Model manager
+    model_manager: Arc<ModelManager>,
+    
+    /// Model name
+    model: String,
+}
+
+impl ModelSummarizer {
+    /// Create a summarizer using a model
+    pub fn new(model_manager: Arc<ModelManager>, model: String) -> Self {
+        Self { model_manager, model }
+    }
+}
+
+impl Summarizer for ModelSummarizer {
+    fn summarize(&self, previous_summary: Option<&str>, turns: &[ConversationEntry]) -> Result<String, AIAssistantError> {
+        let mut prompt = String::from(
+            "Summarize this conversation about an operating system design in a few short paragraphs. \
+             Keep every decision, constraint and open question.\n\n",
+        );
+        if let Some(summary) = previous_summary {
+            prompt.push_str(&format!("Summary so far:\n{}\n\n", summary));
+        }
+        for turn in turns {
+            prompt.push_str(&format!("{}: {}\n", turn.role.as_str(), turn.content));
+        }
+        
+        let params = ModelParams {
+            temperature: 0.3,
+            max_tokens: 512,
+            ..Default::default()
+        };
+        self.model_manager.generate_with_model(&self.model, &prompt, &params)
+            .map(|summary| summary.trim().to_string())
+    }
+}
+
+/// Serialized form of the memory, saved in the project
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct MemorySnapshot {
+    threads: Vec<HashMap<String, String>>,
+    entries: Vec<HashMap<String, String>>,
+}
+
+/// Conversation memory of a project
+pub struct ConversationMemory {
+    /// Backing DBOS tables
+    tables: Arc<TablesManager>,
+    
+    /// Summarization and prompt limits
+    policy: MemoryPolicy,
+    
+    /// Summarizer for old turns
+    summarizer: Arc<dyn Summarizer>,
+}
+
+impl ConversationMemory {
+    /// Create an empty memory backed by a private tables manager
+    pub fn new() -> Result<Self, AIAssistantError> {
+        let tables = Arc::new(TablesManager::new());
+        tables.start();
+        Self::with_tables_manager(tables)
+    }
+    
+    /// Create a memory on top of an existing (running) tables manager
+    pub fn with_tables_manager(tables: Arc<TablesManager>) -> Result<Self, AIAssistantError> {
+        let memory = Self {
+            tables,
+            policy: MemoryPolicy::default(),
+            summarizer: Arc::new(ExtractiveSummarizer),
+        };
+        memory.ensure_tables()?;
+        Ok(memory)
+    }
+    
+    /// Set the summarization and prompt limits
+    pub fn with_policy(mut self, policy: MemoryPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+    
+    /// Set the summarizer for old turns
+    pub fn with_summarizer(mut self, summarizer: Arc<dyn Summarizer>) -> Self {
+        self.summarizer = summarizer;
+        self
+    }
+    
+    /// Get the summarization and prompt limits
+    pub fn policy(&self) -> MemoryPolicy {
+        self.policy
+    }
+    
+    fn ensure_tables(&self) -> Result<(), AIAssistantError> {
+        let definitions = vec![
+            table(CONVERSATIONS_TABLE, "AI assistant conversation threads", vec![
+                column("thread_id", ColumnType::Uuid, "Thread ID"),
+                column("title", ColumnType::String, "Thread title"),
+                column("created_at", ColumnType::Timestamp, "Creation time"),
+                column("updated_at", ColumnType::Timestamp, "Time of the last entry"),
+            ], vec!["thread_id"], vec![("idx_conversations_thread", vec!["thread_id"], true)]),
+            table(CONVERSATION_ENTRIES_TABLE, "Turns, summaries and decisions of AI assistant threads", vec![
+                column("thread_id", ColumnType::Uuid, "Owning thread"),
+                column("sequence", ColumnType::Long, "Position in the thread"),
+                column("role", ColumnType::String, "Author"),
+                column("kind", ColumnType::String, "Entry kind"),
+                column("title", ColumnType::String, "Decision title"),
+                column("content", ColumnType::String, "Text"),
+                column("summarized", ColumnType::Boolean, "Folded into a summary"),
+                column("created_at", ColumnType::Timestamp, "Creation time"),
+            ], vec!["thread_id", "sequence"], vec![("idx_entries_thread", vec!["thread_id"], false), ("idx_entries_kind", vec!["kind"], false)]),
+        ];
+        
+        for definition in definitions {
+            if self.tables.get_table(&definition.name).map_err(AIAssistantError::MemoryError)?.is_none() {
+                self.tables.create_table(definition).map_err(AIAssistantError::MemoryError)?;
+            }
+        }
+        Ok(())
+    }
+    
+    /// Start a new thread
+    pub fn create_thread(&self, title: &str) -> Result<String, AIAssistantError> {
+        let thread_id = Uuid::new_v4().to_string();
+        let now = now().to_string();
+        self.tables.insert_row(CONVERSATIONS_TABLE, HashMap::from([
+            ("thread_id".to_string(), thread_id.clone()),
+            ("title".to_string(), title.to_string()),
+            ("created_at".to_string(), now.clone()),
+            ("updated_at".to_string(), now),
+        ])).map_err(AIAssistantError::MemoryError)?;
+        Ok(thread_id)
+    }
+    
+    /// Get a thread
+    pub fn thread(&self, thread_id: &str) -> Result<Option<ConversationThread>, AIAssistantError> {
+        Ok(self.thread_row(thread_id)?.as_ref().map(row_to_thread))
+    }
+    
+    /// Get all threads, most recently used first
+    pub fn threads(&self) -> Result<Vec<ConversationThread>, AIAssistantError> {
+        let mut threads = Vec::new();
+        self.tables.for_each_row(CONVERSATIONS_TABLE, &HashMap::new(), |row| threads.push(row_to_thread(row)))
+            .map_err(AIAssistantError::MemoryError)?;
+        threads.sort_by(|a, b| b.updated_at.cmp(&a.updated_at).then_with(|| a.title.cmp(&b.title)));
+        Ok(threads)
+    }
+    
+    /// Rename a thread
+    pub fn rename_thread(&self, thread_id: &str, title: &str) -> Result<(), AIAssistantError> {
+        let row = self.require_thread(thread_id)?;
+        self.tables.update_row(CONVERSATIONS_TABLE, &row.row_id, HashMap::from([("title".to_string(), title.to_string())]))
+            .map_err(AIAssistantError::MemoryError)
+    }
+    
+    /// Delete a thread with all its entries
+    pub fn delete_thread(&self, thread_id: &str) -> Result<(), AIAssistantError> {
+        let row = self.require_thread(thread_id)?;
+        for entry in self.entries(thread_id)? {
+            self.tables.delete_row(CONVERSATION_ENTRIES_TABLE, &entry.id).map_err(AIAssistantError::MemoryError)?;
+        }
+        self.tables.delete_row(CONVERSATIONS_TABLE, &row.row_id).map_err(AIAssistantError::MemoryError)
+    }
+    
+    /// Get the entries of a thread in order
+    pub fn entries(&self, thread_id: &str) -> Result<Vec<ConversationEntry>, AIAssistantError> {
+        let mut entries = Vec::new();
+        self.tables.for_each_row(CONVERSATION_ENTRIES_TABLE, &HashMap::from([("thread_id".to_string(), thread_id.to_string())]), |row| {
+            entries.push(row_to_entry(row));
+        }).map_err(AIAssistantError::MemoryError)?;
+        entries.sort_by_key(|entry| entry.sequence);
+        Ok(entries)
+    }
+    
+    /// Append a turn, summarizing old turns when the thread grows too long
+    pub fn add_turn(&self, thread_id: &str, role: TurnRole, content: &str) -> Result<String, AIAssistantError> {
+        let id = self.append_entry(thread_id, role, EntryKind::Turn, None, content)?;
+        self.summarize_old_turns(thread_id)?;
+        Ok(id)
+    }
+    
+    /// Record a design decision made in a thread
+    pub fn record_decision(&self, thread_id: &str, title: &str, decision: &str) -> Result<String, AIAssistantError> {
+        self.append_entry(thread_id, TurnRole::System, EntryKind::Decision, Some(title), decision)
+    }
+    
+    /// Get the design decisions of all threads, oldest first
+    pub fn decisions(&self) -> Result<Vec<ConversationEntry>, AIAssistantError> {
+        let mut decisions = Vec::new();
+        self.tables.for_each_row(CONVERSATION_ENTRIES_TABLE, &HashMap::from([("kind".to_string(), EntryKind::Decision.as_str().to_string())]), |row| {
+            decisions.push(row_to_entry(row));
+        }).map_err(AIAssistantError::MemoryError)?;
+        decisions.sort_by(|a, b| (a.created_at, &a.thread_id, a.sequence).cmp(&(b.created_at, &b.thread_id, b.sequence)));
+        Ok(decisions)
+    }
+    
+    /// Find the design decisions sharing the most words with a text
+    pub fn find_decisions(&self, text: &str, limit: usize) -> Result<Vec<ConversationEntry>, AIAssistantError> {
+        let query = keywords(text);
+        let mut scored: Vec<(usize, ConversationEntry)> = self.decisions()?
+            .into_iter()
+            .filter_map(|decision| {
+                let words = keywords(&format!("{} {}", decision.title.as_deref().unwrap_or_default(), decision.content));
+                let score = query.intersection(&words).count();
+                (score > 0).then_some((score, decision))
+            })
+            .collect();
+        // Newer decisions win ties, as they may supersede older ones
+        scored.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| b.1.created_at.cmp(&a.1.created_at)));
+        Ok(scored.into_iter().take(limit).map(|(_, decision)| decision).collect())
+    }
+    
+    /// Fold old turns into a summary once enough have accumulated
+    ///
+    /// Returns whether a summary was written.
+    pub fn summarize_old_turns(&self, thread_id: &str) -> Result<bool, AIAssistantError> {
+        let entries = self.entries(thread_id)?;
+        let pending: Vec<&ConversationEntry> = entries.iter()
+            .filter(|entry| entry.kind == EntryKind::Turn && !entry.summarized)
+            .collect();
+        if pending.len() < self.policy.keep_recent_turns + self.policy.summarize_batch.max(1) {
+            return Ok(false);
+        }
+        
+        let old: Vec<ConversationEntry> = pending[..pending.len() - self.policy.keep_recent_turns].iter().map(|entry| (*entry).clone()).collect();
+        let previous = entries.iter().rev().find(|entry| entry.kind == EntryKind::Summary);
+        let summary = self.summarizer.summarize(previous.map(|entry| entry.content.as_str()), &old)?;
+        
+        self.append_entry(thread_id, TurnRole::System, EntryKind::Summary, None, &summary)?;
+        for entry in &old {
+            self.tables.update_row(CONVERSATION_ENTRIES_TABLE, &entry.id, HashMap::from([("summarized".to_string(), "true".to_string())]))
+                .map_err(AIAssistantError::MemoryError)?;
+        }
+        Ok(true)
+    }
+    
+    /// Build a prompt with the relevant decisions, the thread summary and the recent turns
+    pub fn build_prompt(&self, thread_id: &str, prompt: &str) -> Result<String, AIAssistantError> {
+        self.require_thread(thread_id)?;
+        let entries = self.entries(thread_id)?;
+        let mut sections = Vec::new();
+        
+        let decisions = self.find_decisions(prompt, self.policy.max_prompt_decisions)?;
+        if !decisions.is_empty() {
+            let lines: Vec<String> = decisions.iter()
+                .map(|decision| format!("- {}: {}", decision.title.as_deref().unwrap_or("Decision"), decision.content))
+                .collect();
+            sections.push(format!("Design decisions made earlier in this project:\n{}", lines.join("\n")));
+        }
+        
+        if let Some(summary) = entries.iter().rev().find(|entry| entry.kind == EntryKind::Summary) {
+            sections.push(format!("Summary of the earlier conversation:\n{}", summary.content));
+        }
+        
+        let recent: Vec<String> = entries.iter()
+            .filter(|entry| entry.kind == EntryKind::Turn && !entry.summarized)
+            .map(|entry| format!("{}: {}", entry.role.as_str(), entry.content))
+            .collect();
+        if !recent.is_empty() {
+            sections.push(format!("Recent conversation:\n{}", recent.join("\n")));
+        }
+        
+        sections.push(format!("user: {}", prompt));
+        Ok(sections.join("\n\n"))
+    }
+    
+    /// Save the memory into a project
+    pub fn save(&self, project_dir: &Path) -> Result<(), AIAssistantError> {
+        let rows = |table: &str| -> Result<Vec<HashMap<String, String>>, AIAssistantError> {
+            Ok(self.tables.get_all_rows(table).map_err(AIAssistantError::MemoryError)?
+                .into_iter()
+                .map(|row| row.values)
+                .collect())
+        };
+        let snapshot = MemorySnapshot {
+            threads: rows(CONVERSATIONS_TABLE)?,
+            entries: rows(CONVERSATION_ENTRIES_TABLE)?,
+        };
+        
+        let path = project_dir.join(CONVERSATION_MEMORY_FILE);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(&snapshot)?)?;
+        Ok(())
+    }
+    
+    /// Load the memory of a project, or an empty memory if it has none
+    pub fn load(project_dir: &Path) -> Result<Self, AIAssistantError> {
+        let memory = Self::new()?;
+        let path = project_dir.join(CONVERSATION_MEMORY_FILE);
+        if !path.exists() {
+            return Ok(memory);
+        }
+        
+        let snapshot: MemorySnapshot = serde_json::from_str(&fs::read_to_string(path)?)?;
+        for (table, rows) in [(CONVERSATIONS_TABLE, snapshot.threads), (CONVERSATION_ENTRIES_TABLE, snapshot.entries)] {
+            for values in rows {
+                memory.tables.insert_row(table, values).map_err(AIAssistantError::MemoryError)?;
+            }
+        }
+        Ok(memory)
+    }
+    
+    fn append_entry(&self, thread_id: &str, role: TurnRole, kind: EntryKind, title: Option<&str>, content: &str) -> Result<String, AIAssistantError> {
+        let thread = self.require_thread(thread_id)?;
+        let mut sequence = 0;
+        self.tables.for_each_row(CONVERSATION_ENTRIES_TABLE, &HashMap::from([("thread_id".to_string(), thread_id.to_string())]), |row| {
+            sequence = sequence.max(parse_u64(row, "sequence") + 1);
+        }).map_err(AIAssistantError::MemoryError)?;
+        
+        let now = now().to_string();
+        let id = self.tables.insert_row(CONVERSATION_ENTRIES_TABLE, HashMap::from([
+            ("thread_id".to_string(), thread_id.to_string()),
+            ("sequence".to_string(), sequence.to_string()),
+            ("role".to_string(), role.as_str().to_string()),
+            ("kind".to_string(), kind.as_str().to_string()),
+            ("title".to_string(), title.unwrap_or_default().to_string()),
+            ("content".to_string(), content.to_string()),
+            ("summarized".to_string(), "false".to_string()),
+            ("created_at".to_string(), now.clone()),
+        ])).map_err(AIAssistantError::MemoryError)?;
+        
+        self.tables.update_row(CONVERSATIONS_TABLE, &thread.row_id, HashMap::from([("updated_at".to_string(), now)]))
+            .map_err(AIAssistantError::MemoryError)?;
+        Ok(id)
+    }
+    
+    fn thread_row(&self, thread_id: &str) -> Result<Option<TableRow>, AIAssistantError> {
+        let rows = self.tables.query_rows(CONVERSATIONS_TABLE, HashMap::from([("thread_id".to_string(), thread_id.to_string())]))
+            .map_err(AIAssistantError::MemoryError)?;
+        Ok(rows.into_iter().next())
+    }
+    
+    fn require_thread(&self, thread_id: &str) -> Result<TableRow, AIAssistantError> {
+        self.thread_row(thread_id)?
+            .ok_or_else(|| AIAssistantError::MemoryError(format!("Conversation thread '{}' not found", thread_id)))
+    }
+}
+
+fn row_to_thread(row: &TableRow) -> ConversationThread {
+    ConversationThread {
+        id: row.values.get("thread_id").cloned().unwrap_or_default(),
+        title: row.values.get("title").cloned().unwrap_or_default(),
+        created_at: parse_u64(row, "created_at"),
+        updated_at: parse_u64(row, "updated_at"),
+    }
+}
+
+fn row_to_entry(row: &TableRow) -> ConversationEntry {
+    let value = |column: &str| row.values.get(column).cloned().unwrap_or_default();
+    let title = value("title");
+    ConversationEntry {
+        id: row.row_id.clone(),
+        thread_id: value("thread_id"),
+        sequence: parse_u64(row, "sequence"),
+        role: TurnRole::parse(&value("role")),
+        kind: EntryKind::parse(&value("kind")),
+        title: (!title.is_empty()).then_some(title),
+        content: value("content"),
+        summarized: value("summarized") == "true",
+        created_at: parse_u64(row, "created_at"),
+    }
+}
+
+fn parse_u64(row: &TableRow, column: &str) -> u64 {
+    row.values.get(column).and_then(|value| value.parse().ok()).unwrap_or(0)
+}
+
+fn now() -> u64 {
+    chrono::Utc::now().timestamp() as u64
+}
+
+/// Lowercase words of at least three characters
+fn keywords(text: &str) -> HashSet<String> {
+    text.split(|c: char| !c.is_alphanumeric() && c != '_')
+        .filter(|word| word.chars().count() >= 3)
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Build a table definition
+fn table(name: &str, description: &str, columns: Vec<ColumnDefinition>, primary_key: Vec<&str>, indexes: Vec<(&str, Vec<&str>, bool)>) -> TableDefinition {
+    let now = now();
+    TableDefinition {
+        name: name.to_string(),
+        columns,
+        primary_key: primary_key.into_iter().map(String::from).collect(),
+        indexes: indexes.into_iter().map(|(name, columns, unique)| IndexDefinition {
+            name: name.to_string(),
+            columns: columns.into_iter().map(String::from).collect(),
+            unique,
+        }).collect(),
+        description: description.to_string(),
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+/// Build a non-nullable column definition
+fn column(name: &str, column_type: ColumnType, description: &str) -> ColumnDefinition {
+    ColumnDefinition {
+        name: name.to_string(),
+        column_type,
+        nullable: false,
+        default_value: None,
+        description: description.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_threads_and_summaries() {
+        let memory = ConversationMemory::new().unwrap().with_policy(MemoryPolicy {
+            keep_recent_turns: 4,
+            summarize_batch: 2,
+            max_prompt_decisions: 5,
+        });
+        let thread = memory.create_thread("Scheduler design").unwrap();
+        
+        for i in 0..5 {
+            memory.add_turn(&thread, TurnRole::User, &format!("Question {}. More detail.", i)).unwrap();
+        }
+        // Five pending turns are below keep_recent_turns + summarize_batch
+        assert!(memory.entries(&thread).unwrap().iter().all(|entry| entry.kind == EntryKind::Turn));
+        
+        memory.add_turn(&thread, TurnRole::Assistant, "Answer 5.").unwrap();
+        let entries = memory.entries(&thread).unwrap();
+        let summary = entries.iter().find(|entry| entry.kind == EntryKind::Summary).unwrap();
+        assert_eq!(summary.content, "user: Question 0.\nuser: Question 1.");
+        assert_eq!(entries.iter().filter(|entry| entry.summarized).count(), 2);
+        
+        let prompt = memory.build_prompt(&thread, "Next step?").unwrap();
+        assert!(prompt.contains("Summary of the earlier conversation:\nuser: Question 0."));
+        assert!(!prompt.contains("user: Question 1. More detail."));
+        assert!(prompt.contains("assistant: Answer 5.") && prompt.ends_with("user: Next step?"));
+        
+        memory.delete_thread(&thread).unwrap();
+        assert!(memory.threads().unwrap().is_empty());
+        assert!(memory.entries(&thread).unwrap().is_empty());
+    }
+    
+    #[test]
+    fn test_decisions_persist_across_sessions() {
+        let project = tempfile::tempdir().unwrap();
+        {
+            let memory = ConversationMemory::new().unwrap();
+            let thread = memory.create_thread("Memory management").unwrap();
+            memory.record_decision(&thread, "Allocator", "Use a buddy allocator for physical pages").unwrap();
+            memory.record_decision(&thread, "Scheduler", "Round robin with 10ms time slices").unwrap();
+            memory.save(project.path()).unwrap();
+        }
+        
+        let memory = ConversationMemory::load(project.path()).unwrap();
+        assert_eq!(memory.decisions().unwrap().len(), 2);
+        let found = memory.find_decisions("Which allocator handles physical memory?", 5).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].title.as_deref(), Some("Allocator"));
+        
+        let thread = memory.create_thread("Paging").unwrap();
+        let prompt = memory.build_prompt(&thread, "How should the page allocator work?").unwrap();
+        assert!(prompt.starts_with("Design decisions made earlier in this project:\n- Allocator: Use a buddy allocator"));
+        assert!(!prompt.contains("Round robin"));
+    }
+}
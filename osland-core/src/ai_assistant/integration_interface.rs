@@ -2,9 +2,11 @@
 // Copyright (c) 2025 OSland Project Team
 // SPDX-License-Identifier: MulanPSL-2.0
 
-use crate::ai_assistant::{AIAssistantError, CodeGenerator, ErrorDiagnoser, PerformanceOptimizer, ModelManager};
+use crate::ai_assistant::{AIAssistantError, CodeGenerator, ErrorDiagnoser, PerformanceOptimizer, ModelManager, ModelParams};
+use crate::ai_assistant::conversation_memory::{ConversationMemory, TurnRole};
 use crate::kernel_extractor::KernelComponent;
 use crate::core::Architecture;
+use crate::mcp::{McpTool, McpToolHub, McpToolResult, PermissionPrompt};
 use std::sync::Arc;
 
 /// AI Assistant integration interface
@@ -47,6 +49,23 @@ pub trait AIAssistantInterface {
     
     /// Get active model name
     fn get_active_model(&self) -> Result<String, AIAssistantError>;
+    
+    /// Get the external tools the assistant may call
+    fn available_tools(&self) -> Vec<McpTool> {
+        Vec::new()
+    }
+    
+    /// Call an external tool, asking the user for permission when required
+    fn call_tool(&self, server: &str, tool: &str, arguments: serde_json::Value, prompt: &dyn PermissionPrompt) -> Result<McpToolResult, AIAssistantError> {
+        let _ = (arguments, prompt);
+        Err(AIAssistantError::ToolError(format!("Tool '{}' of '{}' is not available", tool, server)))
+    }
+    
+    /// Answer a prompt in a conversation thread, remembering the exchange
+    fn chat(&self, thread_id: &str, prompt: &str) -> Result<String, AIAssistantError> {
+        let _ = (thread_id, prompt);
+        Err(AIAssistantError::MemoryError("Conversation memory is not configured".to_string()))
+    }
 }
 
 /// AI Assistant implementation
@@ -65,6 +84,12 @@ pub struct OSlandAIAssistant {
     
     /// Active model name
     active_model: Arc<std::sync::RwLock<String>>,
+    
+    /// External MCP tools
+    tools: Option<Arc<McpToolHub>>,
+    
+    /// Conversation memory of the project
+    memory: Option<Arc<ConversationMemory>>,
 }
 
 impl OSlandAIAssistant {
@@ -82,8 +107,22 @@ impl OSlandAIAssistant {
             performance_optimizer,
             model_manager,
             active_model: Arc::new(std::sync::RwLock::new(default_model)),
+            tools: None,
+            memory: None,
         }
     }
+    
+    /// Offer the tools of connected MCP servers to the assistant
+    pub fn with_tools(mut self, tools: Arc<McpToolHub>) -> Self {
+        self.tools = Some(tools);
+        self
+    }
+    
+    /// Remember conversations in the project's conversation memory
+    pub fn with_memory(mut self, memory: Arc<ConversationMemory>) -> Self {
+        self.memory = Some(memory);
+        self
+    }
 }
 
 impl AIAssistantInterface for OSlandAIAssistant {
@@ -148,6 +187,8 @@ impl AIAssistantInterface for OSlandAIAssistant {
             "Performance optimization".to_string(),
             "Bottleneck analysis".to_string(),
             "Architecture-specific optimizations".to_string(),
+            "External tools via MCP servers".to_string(),
+            "Conversation memory across sessions".to_string(),
         ]
     }
     
@@ -166,6 +207,34 @@ impl AIAssistantInterface for OSlandAIAssistant {
         let active_model = self.active_model.read().map_err(|_| AIAssistantError::ModelError("Failed to acquire lock on active model".to_string()))?;
         Ok(active_model.clone())
     }
+    
+    fn available_tools(&self) -> Vec<McpTool> {
+        self.tools.as_ref().map(|tools| tools.available_tools()).unwrap_or_default()
+    }
+    
+    fn call_tool(&self, server: &str, tool: &str, arguments: serde_json::Value, prompt: &dyn PermissionPrompt) -> Result<McpToolResult, AIAssistantError> {
+        let tools = self.tools.as_ref()
+            .ok_or_else(|| AIAssistantError::ToolError("No MCP servers are connected".to_string()))?;
+        tools.call_tool(server, tool, arguments, prompt)
+            .map_err(|e| AIAssistantError::ToolError(e.to_string()))
+    }
+    
+    fn chat(&self, thread_id: &str, prompt: &str) -> Result<String, AIAssistantError> {
+        let memory = self.memory.as_ref()
+            .ok_or_else(|| AIAssistantError::MemoryError("Conversation memory is not configured".to_string()))?;
+        let full_prompt = memory.build_prompt(thread_id, prompt)?;
+        let params = ModelParams {
+            temperature: 0.7,
+            max_tokens: 1024,
+            top_p: 0.9,
+            ..Default::default()
+        };
+        let response = self.model_manager.generate_with_model(&self.get_active_model()?, &full_prompt, &params)?;
+        
+        memory.add_turn(thread_id, TurnRole::User, prompt)?;
+        memory.add_turn(thread_id, TurnRole::Assistant, response.trim())?;
+        Ok(response.trim().to_string())
+    }
 }
 
 // Re-export types from other modules for convenience
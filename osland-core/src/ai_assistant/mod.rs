@@ -7,13 +7,23 @@ pub mod error_diagnoser;
 pub mod performance_optimizer;
 pub mod model_manager;
 pub mod integration_interface;
+pub mod conversation_memory;
+pub mod code_review;
+pub mod providers;
+pub mod pattern_library;
+pub mod translation_assistant;
 
 // Re-export common types and traits
 pub use code_generator::{CodeGenerator, AICodeGenerator, CodeGenerationContext, CodeGenerationResult, CodeStyle};
 pub use error_diagnoser::{ErrorDiagnoser, AIErrorDiagnoser, ErrorDiagnosticContext, ErrorDiagnosticResult, ErrorSeverity};
-pub use performance_optimizer::{PerformanceOptimizer, AIPerformanceOptimizer, PerformanceOptimizationContext, PerformanceOptimizationResult, PerformanceMetrics, BottleneckAnalysis, OptimizationSuggestion};
+pub use performance_optimizer::{PerformanceOptimizer, AIPerformanceOptimizer, PerformanceOptimizationContext, PerformanceOptimizationResult, PerformanceMetrics, BottleneckAnalysis, OptimizationSuggestion, OptimizationVerification};
 pub use model_manager::{ModelManager, ModelManagerTrait, ModelConfig, ModelParams, ModelInfo, ModelStats};
 pub use integration_interface::{AIAssistantInterface, OSlandAIAssistant, AIAssistantFactory, AIAssistantService};
+pub use providers::{ProviderConfig, ProviderKind, ProviderSettings, RateLimit, RateLimiter, SecretStore, KeychainSecretStore, MemorySecretStore, VaultSecretStore};
+pub use code_review::{CodeReview, ReviewHunk, HunkDecision, ReviewTarget, TileCodeSection, Provenance};
+pub use pattern_library::{PatternLibrary, DesignPattern, PatternParameter, PatternTile, PatternMatch};
+pub use translation_assistant::{Glossary, GlossaryTerm, TranslationItem, Translator, ModelTranslator, TranslationBatch, TranslationSuggestion, SuggestionStatus};
+pub use conversation_memory::{ConversationMemory, ConversationThread, ConversationEntry, TurnRole, EntryKind, MemoryPolicy, Summarizer, ExtractiveSummarizer, ModelSummarizer};
 
 /// AI Assistant error types
 #[derive(Debug, thiserror::Error)]
@@ -41,4 +51,25 @@ pub enum AIAssistantError {
     
     #[error("Reqwest error: {0}")]
     ReqwestError(#[from] reqwest::Error),
+    
+    #[error("Tool error: {0}")]
+    ToolError(String),
+    
+    #[error("Conversation memory error: {0}")]
+    MemoryError(String),
+    
+    #[error("Secret storage error: {0}")]
+    SecretError(String),
+    
+    #[error("Design pattern error: {0}")]
+    PatternError(String),
+    
+    #[error("Translation error: {0}")]
+    TranslationError(String),
+    
+    #[error("Request canceled")]
+    Canceled,
+    
+    #[error("Offline mode: {0}")]
+    Offline(String),
 }
@@ -3,11 +3,15 @@
 // SPDX-License-Identifier: MulanPSL-2.0
 
 use crate::ai_assistant::AIAssistantError;
+use crate::ai_assistant::providers::{api_key_secret, KeychainSecretStore, ProviderConfig, ProviderSettings, RateLimiter, SecretStore, VaultSecretStore};
 use std::collections::HashMap;
+use std::path::Path;
 use std::sync::{Arc, RwLock};
 use serde::{Deserialize, Serialize};
 use reqwest::{Client, Error as ReqwestError};
 use std::time::Duration;
+use crate::core::cancellation::CancellationToken;
+use crate::core::network_policy::{check_egress, redirect_policy, EgressPurpose};
 
 /// Model parameters for AI generation
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -43,13 +47,15 @@ pub struct ModelConfig {
     /// Model name
     pub name: String,
     
-    /// Model provider
+    /// Model provider: a provider registered with the model manager, or a protocol name
+    /// (`openai`, `anthropic`, `mistral`, `local`)
     pub provider: String,
     
     /// API endpoint URL
     pub endpoint: String,
     
-    /// API key
+    /// API key (never written to configuration files; registered providers read it from the secret store)
+    #[serde(skip_serializing, default)]
     pub api_key: Option<String>,
     
     /// Model parameters
@@ -143,12 +149,22 @@ pub struct ModelManager {
     
     /// HTTP client
     http_client: Client,
+    
+    /// Registered providers with their rate limiters
+    providers: RwLock<HashMap<String, (ProviderConfig, Option<Arc<RateLimiter>>)>>,
+    
+    /// Storage for provider API keys
+    secrets: Arc<dyn SecretStore>,
+    
+    /// Vault of the loaded project, preferred over `secrets` when present
+    project_secrets: RwLock<Option<Arc<dyn SecretStore>>>,
 }
 
 impl ModelManager {
     /// Create a new model manager
     pub fn new() -> Result<Self, AIAssistantError> {
         let client = Client::builder()
+            .redirect(redirect_policy(EgressPurpose::AiApi))
             .timeout(Duration::from_secs(30))
             .build()
             .map_err(|e| AIAssistantError::APIError(format!("Failed to create HTTP client: {}", e)))?;
@@ -158,9 +174,92 @@ impl ModelManager {
             model_info: RwLock::new(HashMap::new()),
             model_stats: RwLock::new(HashMap::new()),
             http_client: client,
+            providers: RwLock::new(HashMap::new()),
+            secrets: Arc::new(KeychainSecretStore),
+            project_secrets: RwLock::new(None),
         })
     }
     
+    /// Store provider API keys somewhere other than the OS keychain
+    pub fn with_secret_store(mut self, secrets: Arc<dyn SecretStore>) -> Self {
+        self.secrets = secrets;
+        self
+    }
+    
+    /// Register a provider, replacing one with the same name
+    pub fn register_provider(&self, provider: ProviderConfig) {
+        let limiter = provider.rate_limit.map(|limit| Arc::new(RateLimiter::new(limit)));
+        self.providers.write().unwrap().insert(provider.name.clone(), (provider, limiter));
+    }
+    
+    /// Remove a provider (its API key stays in the secret store)
+    pub fn remove_provider(&self, name: &str) -> Option<ProviderConfig> {
+        self.providers.write().unwrap().remove(name).map(|(provider, _)| provider)
+    }
+    
+    /// Get the registered providers, ordered by name
+    pub fn providers(&self) -> Vec<ProviderConfig> {
+        let mut providers: Vec<ProviderConfig> = self.providers.read().unwrap().values().map(|(provider, _)| provider.clone()).collect();
+        providers.sort_by(|a, b| a.name.cmp(&b.name));
+        providers
+    }
+    
+    /// Register the providers configured for a project
+    ///
+    /// API keys are then read from the project vault, if the project has one.
+    pub fn load_project_providers(&self, project_dir: &Path) -> Result<usize, AIAssistantError> {
+        let settings = ProviderSettings::load(project_dir)?;
+        *self.project_secrets.write().unwrap() = VaultSecretStore::for_project(project_dir)
+            .map(|store| Arc::new(store) as Arc<dyn SecretStore>);
+        let count = settings.providers.len();
+        for provider in settings.providers {
+            self.register_provider(provider);
+        }
+        Ok(count)
+    }
+    
+    /// Store the API key of a provider in the secret store
+    pub fn set_provider_api_key(&self, provider: &str, api_key: &str) -> Result<(), AIAssistantError> {
+        self.secret_store().set(&api_key_secret(provider), api_key)
+    }
+    
+    /// Remove the API key of a provider from the secret store
+    pub fn clear_provider_api_key(&self, provider: &str) -> Result<(), AIAssistantError> {
+        self.secret_store().delete(&api_key_secret(provider))
+    }
+    
+    /// Store holding the API keys: the project vault, or the configured store
+    fn secret_store(&self) -> Arc<dyn SecretStore> {
+        self.project_secrets.read().unwrap().clone().unwrap_or_else(|| self.secrets.clone())
+    }
+    
+    /// Resolve a model's provider into the protocol, endpoint and key used for a request
+    ///
+    /// Also returns how long to wait to stay within the provider's rate limit.
+    fn prepare_request(&self, model_name: &str) -> Result<(ModelConfig, Duration), AIAssistantError> {
+        let mut config = self.get_model_config(model_name)?;
+        let providers = self.providers.read().unwrap();
+        let Some((provider, limiter)) = providers.get(&config.provider) else {
+            check_egress(EgressPurpose::AiApi, &config.endpoint).map_err(|e| AIAssistantError::Offline(e.to_string()))?;
+            return Ok((config, Duration::ZERO));
+        };
+        
+        if config.api_key.is_none() {
+            config.api_key = self.secret_store().get(&api_key_secret(&provider.name))?;
+        }
+        if config.api_key.is_none() && provider.kind.requires_api_key() {
+            return Err(AIAssistantError::SecretError(format!("No API key stored for provider '{}'", provider.name)));
+        }
+        if config.endpoint.is_empty() {
+            config.endpoint = provider.endpoint.clone();
+        }
+        check_egress(EgressPurpose::AiApi, &config.endpoint).map_err(|e| AIAssistantError::Offline(e.to_string()))?;
+        config.provider = provider.kind.protocol().to_string();
+        
+        let wait = limiter.as_ref().map_or(Duration::ZERO, |limiter| limiter.reserve());
+        Ok((config, wait))
+    }
+    
     /// Generate text using a specific model
     pub fn generate_with_model(&self, model_name: &str, prompt: &str, params: &ModelParams) -> Result<String, AIAssistantError> {
         let start_time = std::time::Instant::now();
@@ -174,6 +273,38 @@ impl ModelManager {
         result
     }
     
+    /// Generate text without blocking the calling thread
+    ///
+    /// The request is dropped as soon as `token` is canceled.
+    pub async fn generate_async(&self, model_name: &str, prompt: &str, params: &ModelParams, token: &CancellationToken) -> Result<String, AIAssistantError> {
+        let (config, wait) = self.prepare_request(model_name)?;
+        
+        // Check request size
+        if prompt.len() > config.max_request_size as usize {
+            return Err(AIAssistantError::APIError(format!("Prompt too long, max size is {} characters", config.max_request_size)));
+        }
+        
+        if !wait.is_zero() {
+            tokio::select! {
+                _ = tokio::time::sleep(wait) => {},
+                _ = token.canceled() => return Err(AIAssistantError::Canceled),
+            }
+        }
+        
+        let start_time = std::time::Instant::now();
+        let result = tokio::select! {
+            response = self.send_api_request(&config, prompt, params) => {
+                response.map_err(|e| AIAssistantError::APIError(format!("API request failed: {}", e)))
+            },
+            _ = token.canceled() => return Err(AIAssistantError::Canceled),
+        };
+        
+        let tokens_used = Self::estimate_tokens_used(prompt, result.as_ref().ok());
+        self.update_model_stats(model_name, result.is_ok(), tokens_used, start_time.elapsed())?;
+        
+        result
+    }
+    
     /// Estimate tokens used in a request and response
     fn estimate_tokens_used(prompt: &str, response: Option<&String>) -> u64 {
         // Simple token estimation (1 token ≈ 4 chars)
@@ -223,6 +354,21 @@ impl ModelManager {
                     }
                 )
             },
+            "local" => {
+                serde_json::json!(
+                    {
+                        "model": config.name,
+                        "prompt": prompt,
+                        "stream": false,
+                        "options": {
+                            "temperature": params.temperature,
+                            "num_predict": params.max_tokens,
+                            "top_p": params.top_p,
+                            "top_k": params.top_k
+                        }
+                    }
+                )
+            },
             _ => {
                 serde_json::json!(
                     {
@@ -278,6 +424,12 @@ impl ModelManager {
                     .ok_or_else(|| ReqwestError::from(std::io::Error::new(std::io::ErrorKind::InvalidData, "Invalid response format")))
                     .map(|s| s.to_string())
             },
+            "local" => {
+                let data: serde_json::Value = serde_json::from_str(&body)?;
+                data["response"].as_str()
+                    .ok_or_else(|| ReqwestError::from(std::io::Error::new(std::io::ErrorKind::InvalidData, "Invalid response format")))
+                    .map(|s| s.to_string())
+            },
             _ => Ok(body),
         }
     }
@@ -298,14 +450,16 @@ impl ModelManagerTrait for ModelManager {
     }
     
     fn generate(&self, model_name: &str, prompt: &str, params: &ModelParams) -> Result<String, AIAssistantError> {
-        let config = self.get_model_config(model_name)?;
+        let (config, wait) = self.prepare_request(model_name)?;
         
         // Check request size
         if prompt.len() > config.max_request_size as usize {
             return Err(AIAssistantError::APIError(format!("Prompt too long, max size is {} characters", config.max_request_size)));
         }
         
-        // Create runtime to execute async request
+        std::thread::sleep(wait);
+        
+        // Synchronous wrapper for the CLI; the UI uses `generate_async`
         let rt = tokio::runtime::Runtime::new()
             .map_err(|e| AIAssistantError::APIError(format!("Failed to create runtime: {}", e)))?;
         
@@ -478,3 +632,38 @@ impl ModelManager {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai_assistant::providers::{MemorySecretStore, ProviderKind};
+    
+    #[test]
+    fn test_named_provider_resolution() {
+        let mut manager = ModelManager::new().unwrap().with_secret_store(Arc::new(MemorySecretStore::default()));
+        manager.register_provider(ProviderConfig::new("team-gateway", ProviderKind::OpenAiCompatible, "https://llm.example.com/v1/chat/completions").with_rate_limit(60, 1));
+        manager.load_model_config(ModelConfig {
+            name: "kernel-coder".to_string(),
+            provider: "team-gateway".to_string(),
+            endpoint: String::new(),
+            api_key: None,
+            params: ModelParams::default(),
+            max_request_size: 10000,
+            timeout: Duration::from_secs(5),
+        }).unwrap();
+        
+        // Providers needing a key refuse to send requests without one
+        assert!(matches!(manager.prepare_request("kernel-coder"), Err(AIAssistantError::SecretError(_))));
+        
+        manager.set_provider_api_key("team-gateway", "secret").unwrap();
+        let (config, wait) = manager.prepare_request("kernel-coder").unwrap();
+        assert_eq!(config.provider, "openai");
+        assert_eq!(config.endpoint, "https://llm.example.com/v1/chat/completions");
+        assert_eq!(config.api_key.as_deref(), Some("secret"));
+        assert_eq!(wait, Duration::ZERO);
+        assert!(manager.prepare_request("kernel-coder").unwrap().1 > Duration::from_millis(900));
+        
+        // Keys are not part of the serialized model configuration
+        assert!(!serde_json::to_string(&config).unwrap().contains("secret"));
+    }
+}
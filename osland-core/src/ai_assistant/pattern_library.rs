@@ -0,0 +1,400 @@
+// OS design pattern library for OSland AI Assistant
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! Knowledge base of operating system design patterns.
+//!
+//! Patterns are structured data: what a design is, when to use it, its tradeoffs and
+//! a tile template with parameters. The built-in patterns ship with OSland; projects
+//! add or override patterns with JSON files in `USER_PATTERN_DIR`, in the same format
+//! as the built-in `os_patterns.json`. The assistant cites patterns by ID, and tile
+//! synthesis instantiates them into tiles.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+use crate::ai_assistant::AIAssistantError;
+use crate::tile_engine::tile_core::{Tile, TilePort, TileType};
+
+/// Project-relative directory of user patterns (`*.json`, each an array of patterns)
+pub const USER_PATTERN_DIR: &str = ".osland/patterns";
+
+/// Tile property recording the pattern a tile was instantiated from
+pub const PATTERN_PROPERTY: &str = "pattern.id";
+
+const BUILTIN_PATTERNS: &str = include_str!("os_patterns.json");
+
+/// Tunable parameter of a pattern, stored as a tile property
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PatternParameter {
+    /// Parameter name
+    pub name: String,
+    
+    /// Parameter description
+    pub description: String,
+    
+    /// Value used when none is given
+    pub default: String,
+    
+    /// Allowed values (any value when empty)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed: Vec<String>,
+}
+
+/// Tile a pattern instantiates to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatternTile {
+    /// Tile type
+    pub tile_type: TileType,
+    
+    /// Fixed tile properties
+    #[serde(default)]
+    pub properties: BTreeMap<String, String>,
+    
+    /// Tile ports
+    #[serde(default)]
+    pub ports: Vec<TilePort>,
+}
+
+/// OS design pattern
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DesignPattern {
+    /// Stable ID (`category.name`), used in citations
+    pub id: String,
+    
+    /// Display name
+    pub name: String,
+    
+    /// Category (`scheduler`, `ipc`, `allocator`, or a user-defined one)
+    pub category: String,
+    
+    /// One-paragraph description
+    pub summary: String,
+    
+    /// Situations the pattern suits
+    #[serde(default)]
+    pub when_to_use: Vec<String>,
+    
+    /// Costs and benefits
+    #[serde(default)]
+    pub tradeoffs: Vec<String>,
+    
+    /// Search keywords
+    #[serde(default)]
+    pub tags: Vec<String>,
+    
+    /// Papers, books or documentation describing the pattern
+    #[serde(default)]
+    pub references: Vec<String>,
+    
+    /// Tunable parameters
+    #[serde(default)]
+    pub parameters: Vec<PatternParameter>,
+    
+    /// Tile template
+    pub tile: PatternTile,
+}
+
+impl DesignPattern {
+    /// Citation marker the assistant uses to refer to the pattern
+    pub fn citation(&self) -> String {
+        format!("[pattern:{}]", self.id)
+    }
+    
+    /// Describe the pattern for a model prompt
+    pub fn to_prompt(&self) -> String {
+        let mut text = format!("{} {} ({})\n{}\n", self.citation(), self.name, self.category, self.summary);
+        for use_case in &self.when_to_use {
+            text.push_str(&format!("- Use when: {}\n", use_case));
+        }
+        for tradeoff in &self.tradeoffs {
+            text.push_str(&format!("- Tradeoff: {}\n", tradeoff));
+        }
+        for parameter in &self.parameters {
+            text.push_str(&format!("- Parameter {} (default {}): {}\n", parameter.name, parameter.default, parameter.description));
+        }
+        text
+    }
+    
+    /// Instantiate the pattern as a tile, overriding parameter defaults
+    pub fn instantiate(&self, values: &HashMap<String, String>) -> Result<Tile, AIAssistantError> {
+        if let Some(unknown) = values.keys().find(|name| !self.parameters.iter().any(|parameter| &parameter.name == *name)) {
+            return Err(AIAssistantError::PatternError(format!("Pattern '{}' has no parameter '{}'", self.id, unknown)));
+        }
+        
+        let mut tile = Tile::new(self.name.clone(), self.tile.tile_type.clone(), self.summary.clone());
+        for port in &self.tile.ports {
+            tile.add_port(port.clone());
+        }
+        for (key, value) in &self.tile.properties {
+            tile.set_property(key.clone(), value.clone());
+        }
+        for parameter in &self.parameters {
+            let value = values.get(&parameter.name).unwrap_or(&parameter.default);
+            if !parameter.allowed.is_empty() && !parameter.allowed.contains(value) {
+                return Err(AIAssistantError::PatternError(format!(
+                    "Invalid value '{}' for parameter '{}' of pattern '{}' (allowed: {})",
+                    value, parameter.name, self.id, parameter.allowed.join(", "),
+                )));
+            }
+            tile.set_property(parameter.name.clone(), value.clone());
+        }
+        tile.set_property(PATTERN_PROPERTY.to_string(), self.id.clone());
+        Ok(tile)
+    }
+    
+    fn validate(&self) -> Result<(), AIAssistantError> {
+        if self.id.trim().is_empty() || self.name.trim().is_empty() || self.category.trim().is_empty() {
+            return Err(AIAssistantError::PatternError(format!("Pattern '{}' needs an ID, a name and a category", self.id)));
+        }
+        for parameter in &self.parameters {
+            if !parameter.allowed.is_empty() && !parameter.allowed.contains(&parameter.default) {
+                return Err(AIAssistantError::PatternError(format!(
+                    "Default '{}' of parameter '{}' of pattern '{}' is not an allowed value",
+                    parameter.default, parameter.name, self.id,
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Search hit
+#[derive(Debug, Clone)]
+pub struct PatternMatch<'a> {
+    pub pattern: &'a DesignPattern,
+    
+    /// Relevance (higher is better)
+    pub score: u32,
+}
+
+/// Collection of design patterns
+#[derive(Debug, Clone, Default)]
+pub struct PatternLibrary {
+    /// Patterns by ID
+    patterns: BTreeMap<String, DesignPattern>,
+}
+
+impl PatternLibrary {
+    /// Create a library of the built-in patterns
+    pub fn builtin() -> Self {
+        let mut library = Self::default();
+        library.add_json(BUILTIN_PATTERNS).expect("built-in patterns are valid");
+        library
+    }
+    
+    /// Create a library of the built-in patterns and the project's own patterns
+    pub fn for_project(project_dir: &Path) -> Result<Self, AIAssistantError> {
+        let mut library = Self::builtin();
+        library.load_dir(&project_dir.join(USER_PATTERN_DIR))?;
+        Ok(library)
+    }
+    
+    /// Add the patterns of every `*.json` file in a directory, replacing patterns with the same ID
+    pub fn load_dir(&mut self, dir: &Path) -> Result<usize, AIAssistantError> {
+        if !dir.is_dir() {
+            return Ok(0);
+        }
+        let mut paths: Vec<_> = fs::read_dir(dir)?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().map_or(false, |extension| extension == "json"))
+            .collect();
+        paths.sort();
+        
+        let mut added = 0;
+        for path in paths {
+            added += self.add_json(&fs::read_to_string(&path)?)
+                .map_err(|e| AIAssistantError::PatternError(format!("{}: {}", path.display(), e)))?;
+        }
+        Ok(added)
+    }
+    
+    /// Add patterns from a JSON array, replacing patterns with the same ID
+    pub fn add_json(&mut self, json: &str) -> Result<usize, AIAssistantError> {
+        let patterns: Vec<DesignPattern> = serde_json::from_str(json)?;
+        for pattern in &patterns {
+            pattern.validate()?;
+        }
+        let added = patterns.len();
+        for pattern in patterns {
+            self.add(pattern)?;
+        }
+        Ok(added)
+    }
+    
+    /// Add a pattern, replacing a pattern with the same ID
+    pub fn add(&mut self, pattern: DesignPattern) -> Result<(), AIAssistantError> {
+        pattern.validate()?;
+        self.patterns.insert(pattern.id.clone(), pattern);
+        Ok(())
+    }
+    
+    /// Get a pattern by ID
+    pub fn get(&self, id: &str) -> Option<&DesignPattern> {
+        self.patterns.get(id)
+    }
+    
+    /// Get all patterns, ordered by ID
+    pub fn patterns(&self) -> impl Iterator<Item = &DesignPattern> {
+        self.patterns.values()
+    }
+    
+    /// Get the patterns of a category
+    pub fn by_category(&self, category: &str) -> Vec<&DesignPattern> {
+        self.patterns.values().filter(|pattern| pattern.category.eq_ignore_ascii_case(category)).collect()
+    }
+    
+    /// Get the category names
+    pub fn categories(&self) -> Vec<String> {
+        let mut categories: Vec<String> = self.patterns.values().map(|pattern| pattern.category.clone()).collect();
+        categories.sort();
+        categories.dedup();
+        categories
+    }
+    
+    /// Search patterns by keywords, best match first
+    ///
+    /// Every query word scores against the ID and name, category, tags, and the
+    /// description texts, in decreasing weight.
+    pub fn search(&self, query: &str) -> Vec<PatternMatch<'_>> {
+        let words = keywords(query);
+        let mut matches: Vec<PatternMatch<'_>> = self.patterns.values()
+            .map(|pattern| PatternMatch { pattern, score: score(pattern, &words) })
+            .filter(|hit| hit.score > 0)
+            .collect();
+        matches.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.pattern.id.cmp(&b.pattern.id)));
+        matches
+    }
+    
+    /// Describe the best `limit` matches of a query for a model prompt
+    pub fn prompt_context(&self, query: &str, limit: usize) -> String {
+        self.search(query).iter()
+            .take(limit)
+            .map(|hit| hit.pattern.to_prompt())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+    
+    /// Get the patterns cited in model output, in order of first citation
+    pub fn citations(&self, text: &str) -> Vec<&DesignPattern> {
+        let mut cited: Vec<&DesignPattern> = Vec::new();
+        for (index, marker) in text.match_indices("[pattern:") {
+            let rest = &text[index + marker.len()..];
+            let Some(end) = rest.find(']') else {
+                continue;
+            };
+            if let Some(pattern) = self.get(&rest[..end]) {
+                if !cited.iter().any(|known| known.id == pattern.id) {
+                    cited.push(pattern);
+                }
+            }
+        }
+        cited
+    }
+    
+    /// Instantiate a pattern as a tile
+    pub fn instantiate(&self, id: &str, values: &HashMap<String, String>) -> Result<Tile, AIAssistantError> {
+        self.get(id)
+            .ok_or_else(|| AIAssistantError::PatternError(format!("Unknown pattern '{}'", id)))?
+            .instantiate(values)
+    }
+}
+
+fn keywords(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.len() > 1)
+        .map(|word| word.to_lowercase())
+        .collect()
+}
+
+fn score(pattern: &DesignPattern, words: &[String]) -> u32 {
+    let name = keywords(&format!("{} {}", pattern.id, pattern.name));
+    let category = keywords(&pattern.category);
+    let tags = keywords(&pattern.tags.join(" "));
+    let text = keywords(&format!(
+        "{} {} {}",
+        pattern.summary,
+        pattern.when_to_use.join(" "),
+        pattern.tradeoffs.join(" "),
+    ));
+    
+    words.iter()
+        .map(|word| {
+            let mut score = 0;
+            if name.contains(word) {
+                score += 4;
+            }
+            if category.contains(word) {
+                score += 3;
+            }
+            if tags.contains(word) {
+                score += 2;
+            }
+            if text.contains(word) {
+                score += 1;
+            }
+            score
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_builtin_search_and_instantiate() {
+        let library = PatternLibrary::builtin();
+        assert_eq!(library.categories(), vec!["allocator", "ipc", "scheduler"]);
+        assert!(library.by_category("scheduler").len() >= 4);
+        
+        let hits = library.search("real-time deadline scheduler");
+        assert_eq!(hits[0].pattern.id, "scheduler.edf");
+        assert!(library.search("zero copy").iter().any(|hit| hit.pattern.id == "ipc.shared_ring_buffer"));
+        assert!(library.prompt_context("slab", 1).starts_with("[pattern:allocator.slab] Slab allocator"));
+        
+        let cited = library.citations("Use a buddy allocator [pattern:allocator.buddy] with slabs on top [pattern:allocator.slab] [pattern:allocator.buddy] [pattern:nope]");
+        let cited: Vec<&str> = cited.iter().map(|pattern| pattern.id.as_str()).collect();
+        assert_eq!(cited, vec!["allocator.buddy", "allocator.slab"]);
+        
+        let mut values = HashMap::new();
+        values.insert("max_order".to_string(), "11".to_string());
+        let tile = library.instantiate("allocator.buddy", &values).unwrap();
+        assert_eq!(tile.get_property("max_order").map(String::as_str), Some("11"));
+        assert_eq!(tile.get_property("page_size").map(String::as_str), Some("4096"));
+        assert_eq!(tile.get_property(PATTERN_PROPERTY).map(String::as_str), Some("allocator.buddy"));
+        assert_eq!(tile.ports.len(), 3);
+        
+        values.insert("colour".to_string(), "red".to_string());
+        assert!(library.instantiate("allocator.buddy", &values).is_err());
+        let mut policy = HashMap::new();
+        policy.insert("overload_policy".to_string(), "panic".to_string());
+        assert!(library.instantiate("scheduler.edf", &policy).is_err());
+    }
+    
+    #[test]
+    fn test_user_patterns() {
+        let project = tempfile::tempdir().unwrap();
+        let dir = project.path().join(USER_PATTERN_DIR);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("lottery.json"), r#"[{
+            "id": "scheduler.lottery",
+            "name": "Lottery scheduler",
+            "category": "scheduler",
+            "summary": "Picks the next task by drawing a ticket at random.",
+            "tags": ["proportional share", "randomized"],
+            "parameters": [{ "name": "default_tickets", "description": "Tickets per task", "default": "100" }],
+            "tile": { "tile_type": "Processing", "properties": { "policy": "lottery" } }
+        }]"#).unwrap();
+        
+        let library = PatternLibrary::for_project(project.path()).unwrap();
+        assert_eq!(library.search("randomized")[0].pattern.id, "scheduler.lottery");
+        assert!(library.get("scheduler.cfs").is_some());
+        
+        fs::write(dir.join("broken.json"), r#"[{ "id": "x", "name": "X", "category": "ipc", "summary": "",
+            "parameters": [{ "name": "mode", "description": "", "default": "c", "allowed": ["a", "b"] }],
+            "tile": { "tile_type": "IO" } }]"#).unwrap();
+        assert!(PatternLibrary::for_project(project.path()).is_err());
+    }
+}
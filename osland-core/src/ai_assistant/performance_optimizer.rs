@@ -4,6 +4,7 @@
 
 use crate::ai_assistant::{AIAssistantError, model_manager::{ModelManager, ModelParams}};
 use crate::kernel_extractor::KernelComponent;
+use crate::runtime::{BenchmarkDelta, BenchmarkHarness, BenchmarkResult, FlamegraphSummary, ProgrammingLanguage};
 use std::sync::Arc;
 
 /// Performance optimization context
@@ -26,6 +27,9 @@ pub struct PerformanceOptimizationContext {
     
     /// Constraints
     pub constraints: Vec<OptimizationConstraint>,
+    
+    /// Flame graph of the benchmarked code
+    pub flamegraph: Option<FlamegraphSummary>,
 }
 
 /// Performance metrics
@@ -46,10 +50,29 @@ pub struct PerformanceMetrics {
     /// Power consumption (watts)
     pub power_consumption: Option<f32>,
     
+    /// 95th percentile latency (nanoseconds)
+    pub p95_latency: Option<u64>,
+    
+    /// Throughput (runs per second)
+    pub throughput: Option<f64>,
+    
     /// Custom metrics
     pub custom_metrics: Vec<CustomMetric>,
 }
 
+impl PerformanceMetrics {
+    /// Metrics measured by a benchmark run
+    pub fn from_benchmark(result: &BenchmarkResult) -> Self {
+        Self {
+            execution_time: Some(result.mean_ns),
+            memory_usage: result.peak_memory_bytes,
+            p95_latency: Some(result.p95_ns),
+            throughput: Some(result.throughput_per_sec),
+            ..Default::default()
+        }
+    }
+}
+
 /// Custom performance metric
 #[derive(Debug, Clone)]
 pub struct CustomMetric {
@@ -295,6 +318,17 @@ impl AIPerformanceOptimizer {
             if let Some(cpu) = metrics.cpu_utilization {
                 prompt.push_str(&format!("- CPU Utilization: {:.2}%\n", cpu));
             }
+            if let Some(p95) = metrics.p95_latency {
+                prompt.push_str(&format!("- p95 Latency: {} ns\n", p95));
+            }
+            if let Some(throughput) = metrics.throughput {
+                prompt.push_str(&format!("- Throughput: {:.1} runs/s\n", throughput));
+            }
+        }
+        
+        if let Some(flamegraph) = &context.flamegraph {
+            prompt.push_str("Flame Graph Hotspots:\n");
+            prompt.push_str(&flamegraph.to_text());
         }
         
         if let Some(code) = &context.code_snippet {
@@ -304,9 +338,82 @@ impl AIPerformanceOptimizer {
         
         prompt.push_str("Provide detailed optimization suggestions with code examples and expected improvements.\n");
         prompt.push_str("Consider both micro-optimizations and algorithmic improvements.\n");
+        if context.code_snippet.is_some() {
+            prompt.push_str("End with the complete optimized code in a single fenced code block.\n");
+        }
         
         prompt
     }
+    
+    /// Optimize the context's code snippet and benchmark the result against it
+    ///
+    /// The baseline is measured first unless `baseline` is given, and its numbers
+    /// replace the context metrics so the model sees real latency and throughput.
+    pub fn optimize_and_verify(
+        &self,
+        context: &PerformanceOptimizationContext,
+        harness: &BenchmarkHarness,
+        language: ProgrammingLanguage,
+        baseline: Option<BenchmarkResult>,
+    ) -> Result<OptimizationVerification, AIAssistantError> {
+        let code = context.code_snippet.as_deref()
+            .ok_or_else(|| AIAssistantError::OptimizationError("No code snippet to benchmark".to_string()))?;
+        let name = context.component.as_ref().map_or("snippet", |component| component.name.as_str());
+        let baseline = match baseline {
+            Some(baseline) => baseline,
+            None => harness.run_code(name, language, code)
+                .map_err(|e| AIAssistantError::OptimizationError(format!("Baseline benchmark failed: {}", e)))?,
+        };
+        
+        let mut measured = context.clone();
+        measured.metrics = PerformanceMetrics {
+            custom_metrics: context.metrics.custom_metrics.clone(),
+            ..PerformanceMetrics::from_benchmark(&baseline)
+        };
+        let result = self.optimize_performance(&measured)?;
+        
+        // Suggestions without code cannot be measured; their expected improvement stays unverified
+        let delta = match &result.optimized_code {
+            Some(optimized) => {
+                let candidate = harness.run_code(name, language, optimized)
+                    .map_err(|e| AIAssistantError::OptimizationError(format!("Optimized code failed to run: {}", e)))?;
+                Some(baseline.compare(&candidate))
+            }
+            None => None,
+        };
+        
+        Ok(OptimizationVerification { result, baseline, delta })
+    }
+}
+
+/// Optimization checked against a benchmark
+#[derive(Debug, Clone)]
+pub struct OptimizationVerification {
+    /// Optimizer output
+    pub result: PerformanceOptimizationResult,
+    
+    /// Benchmark of the original code
+    pub baseline: BenchmarkResult,
+    
+    /// Measured change, if the optimizer produced runnable code
+    pub delta: Option<BenchmarkDelta>,
+}
+
+/// Extract the last fenced code block of a model response
+fn extract_code_block(response: &str) -> Option<String> {
+    let mut blocks = Vec::new();
+    let mut current: Option<Vec<&str>> = None;
+    for line in response.lines() {
+        if line.trim_start().starts_with("```") {
+            match current.take() {
+                Some(lines) => blocks.push(lines.join("\n")),
+                None => current = Some(Vec::new()),
+            }
+        } else if let Some(lines) = current.as_mut() {
+            lines.push(line);
+        }
+    }
+    blocks.pop().filter(|code| !code.trim().is_empty())
 }
 
 impl PerformanceOptimizer for AIPerformanceOptimizer {
@@ -329,7 +436,7 @@ impl PerformanceOptimizer for AIPerformanceOptimizer {
         
         // Process the response
         Ok(PerformanceOptimizationResult {
-            optimized_code: None, // Will be extracted from response in real implementation
+            optimized_code: context.code_snippet.as_ref().and_then(|_| extract_code_block(&response)),
             suggestions: vec![OptimizationSuggestion {
                 description: response.clone(),
                 estimated_impact: 0.75,
@@ -443,3 +550,55 @@ impl PerformanceOptimizer for AIPerformanceOptimizer {
             })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::{BenchmarkConfig, RuntimeManager};
+    
+    fn context(code_snippet: Option<&str>) -> PerformanceOptimizationContext {
+        let samples: Vec<u64> = (1..=20).map(|i| i * 1000).collect();
+        PerformanceOptimizationContext {
+            component: None,
+            metrics: PerformanceMetrics::from_benchmark(&BenchmarkResult::from_samples("sched", &samples, Some(4096))),
+            code_snippet: code_snippet.map(str::to_string),
+            architecture: "x86_64".to_string(),
+            goals: vec![OptimizationGoal::MinimizeExecutionTime],
+            constraints: Vec::new(),
+            flamegraph: Some(FlamegraphSummary::from_folded("main;schedule 3\nmain 1\n", 5)),
+        }
+    }
+    
+    fn optimizer() -> AIPerformanceOptimizer {
+        AIPerformanceOptimizer::new(Arc::new(ModelManager::new().unwrap()), "local".to_string())
+    }
+    
+    #[test]
+    fn test_prompt_contains_benchmark_results() {
+        let prompt = optimizer().create_optimization_prompt(&context(Some("int f(void);")));
+        assert!(prompt.contains("- Execution Time: 10500 ns\n"));
+        assert!(prompt.contains("- Memory Usage: 4096 bytes\n"));
+        assert!(prompt.contains("- p95 Latency: 19000 ns\n"));
+        assert!(prompt.contains("Flame Graph Hotspots:\n4 samples\n"));
+        assert!(prompt.ends_with("End with the complete optimized code in a single fenced code block.\n"));
+        
+        let prompt = optimizer().create_optimization_prompt(&context(None));
+        assert!(!prompt.contains("fenced code block"));
+    }
+    
+    #[test]
+    fn test_extract_code_block() {
+        let response = "Unroll the loop:\n```c\nfor (;;) {}\n```\nFinal version:\n```c\nint f(void) {\n    return 0;\n}\n```\n";
+        assert_eq!(extract_code_block(response).as_deref(), Some("int f(void) {\n    return 0;\n}"));
+        assert_eq!(extract_code_block("```\n   \n```"), None);
+        assert_eq!(extract_code_block("Unterminated:\n```c\nint f(void);"), None);
+    }
+    
+    #[test]
+    #[allow(clippy::arc_with_non_send_sync)]
+    fn test_optimize_and_verify_requires_code() {
+        let harness = BenchmarkHarness::new(Arc::new(RuntimeManager::default()), BenchmarkConfig::default());
+        let result = optimizer().optimize_and_verify(&context(None), &harness, ProgrammingLanguage::C, None);
+        assert!(matches!(result, Err(AIAssistantError::OptimizationError(_))));
+    }
+}
@@ -0,0 +1,335 @@
+// Model providers for OSland AI Assistant
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! Named model providers configured per project.
+//!
+//! Provider settings live in `PROVIDER_SETTINGS_FILE` and never contain API keys;
+//! keys are kept in a `SecretStore`: the project vault when the project has one,
+//! otherwise the operating system keychain.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use serde::{Deserialize, Serialize};
+use crate::ai_assistant::AIAssistantError;
+use crate::core::CoreError;
+use crate::core::secrets::{ProjectVault, VaultKey, VaultUnlock};
+
+/// Project-relative path of the provider settings
+pub const PROVIDER_SETTINGS_FILE: &str = ".osland/providers.json";
+
+/// Keychain service under which API keys are stored
+pub const KEYCHAIN_SERVICE: &str = "osland";
+
+/// API protocol spoken by a provider
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProviderKind {
+    /// OpenAI chat completions API (OpenAI, Mistral, vLLM, ...)
+    OpenAiCompatible,
+    /// Anthropic messages API
+    Anthropic,
+    /// Local Ollama-style server without authentication
+    Local,
+}
+
+impl ProviderKind {
+    /// Protocol name understood by the model manager's request builder
+    pub fn protocol(&self) -> &'static str {
+        match self {
+            ProviderKind::OpenAiCompatible => "openai",
+            ProviderKind::Anthropic => "anthropic",
+            ProviderKind::Local => "local",
+        }
+    }
+    
+    /// Whether requests need an API key
+    pub fn requires_api_key(&self) -> bool {
+        !matches!(self, ProviderKind::Local)
+    }
+}
+
+/// Request budget of a provider
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RateLimit {
+    /// Requests allowed per minute
+    pub requests_per_minute: u32,
+    
+    /// Requests allowed back to back before the per-minute rate applies
+    pub burst: u32,
+}
+
+/// Named model provider
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProviderConfig {
+    /// Provider name, referenced by `ModelConfig::provider`
+    pub name: String,
+    
+    /// API protocol
+    pub kind: ProviderKind,
+    
+    /// Endpoint URL
+    pub endpoint: String,
+    
+    /// Request budget (unlimited when unset)
+    #[serde(default)]
+    pub rate_limit: Option<RateLimit>,
+}
+
+impl ProviderConfig {
+    /// Create a provider without a rate limit
+    pub fn new(name: &str, kind: ProviderKind, endpoint: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            kind,
+            endpoint: endpoint.to_string(),
+            rate_limit: None,
+        }
+    }
+    
+    /// Set the request budget
+    pub fn with_rate_limit(mut self, requests_per_minute: u32, burst: u32) -> Self {
+        self.rate_limit = Some(RateLimit { requests_per_minute, burst });
+        self
+    }
+}
+
+/// Providers of a project
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProviderSettings {
+    /// Configured providers
+    #[serde(default)]
+    pub providers: Vec<ProviderConfig>,
+}
+
+impl ProviderSettings {
+    /// Load the providers of a project, or no providers if it has none
+    pub fn load(project_dir: &Path) -> Result<Self, AIAssistantError> {
+        let path = project_dir.join(PROVIDER_SETTINGS_FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+    
+    /// Save the providers of a project
+    pub fn save(&self, project_dir: &Path) -> Result<(), AIAssistantError> {
+        let path = project_dir.join(PROVIDER_SETTINGS_FILE);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// Storage for provider API keys
+pub trait SecretStore: Send + Sync {
+    /// Get a secret, or `None` if it is not set
+    fn get(&self, key: &str) -> Result<Option<String>, AIAssistantError>;
+    
+    /// Set a secret
+    fn set(&self, key: &str, secret: &str) -> Result<(), AIAssistantError>;
+    
+    /// Remove a secret (removing a missing secret is not an error)
+    fn delete(&self, key: &str) -> Result<(), AIAssistantError>;
+}
+
+/// Secrets in the operating system keychain
+#[derive(Debug, Default)]
+pub struct KeychainSecretStore;
+
+impl KeychainSecretStore {
+    fn entry(key: &str) -> Result<keyring::Entry, AIAssistantError> {
+        keyring::Entry::new(KEYCHAIN_SERVICE, key)
+            .map_err(|e| AIAssistantError::SecretError(format!("Invalid keychain entry '{}': {}", key, e)))
+    }
+}
+
+impl SecretStore for KeychainSecretStore {
+    fn get(&self, key: &str) -> Result<Option<String>, AIAssistantError> {
+        match Self::entry(key)?.get_password() {
+            Ok(secret) => Ok(Some(secret)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(AIAssistantError::SecretError(format!("Failed to read '{}' from the keychain: {}", key, e))),
+        }
+    }
+    
+    fn set(&self, key: &str, secret: &str) -> Result<(), AIAssistantError> {
+        Self::entry(key)?.set_password(secret)
+            .map_err(|e| AIAssistantError::SecretError(format!("Failed to store '{}' in the keychain: {}", key, e)))
+    }
+    
+    fn delete(&self, key: &str) -> Result<(), AIAssistantError> {
+        match Self::entry(key)?.delete_password() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(e) => Err(AIAssistantError::SecretError(format!("Failed to remove '{}' from the keychain: {}", key, e))),
+        }
+    }
+}
+
+/// Secrets in the vault of a project, unlocked from the environment
+///
+/// The passphrase comes from `OSLAND_VAULT_PASSPHRASE`, otherwise the vault key
+/// comes from the keychain.
+#[derive(Debug, Clone)]
+pub struct VaultSecretStore {
+    project_dir: PathBuf,
+}
+
+impl VaultSecretStore {
+    /// Store backed by the vault of a project
+    pub fn new(project_dir: &Path) -> Self {
+        Self { project_dir: project_dir.to_path_buf() }
+    }
+    
+    /// Store of a project, if the project has a vault
+    pub fn for_project(project_dir: &Path) -> Option<Self> {
+        ProjectVault::exists(project_dir).then(|| Self::new(project_dir))
+    }
+    
+    fn update(&self, change: impl FnOnce(&mut ProjectVault, &VaultKey) -> Result<(), CoreError>) -> Result<(), AIAssistantError> {
+        let mut vault = ProjectVault::load(&self.project_dir).map_err(vault_error)?;
+        let key = vault.unlock(&VaultUnlock::from_env()).map_err(vault_error)?;
+        change(&mut vault, &key).map_err(vault_error)?;
+        vault.save(&self.project_dir).map_err(vault_error)
+    }
+}
+
+impl SecretStore for VaultSecretStore {
+    fn get(&self, key: &str) -> Result<Option<String>, AIAssistantError> {
+        let vault = ProjectVault::load(&self.project_dir).map_err(vault_error)?;
+        let vault_key = vault.unlock(&VaultUnlock::from_env()).map_err(vault_error)?;
+        vault.secret_string(&vault_key, key).map_err(vault_error)
+    }
+    
+    fn set(&self, key: &str, secret: &str) -> Result<(), AIAssistantError> {
+        self.update(|vault, vault_key| vault.set_secret(vault_key, key, secret.as_bytes()))
+    }
+    
+    fn delete(&self, key: &str) -> Result<(), AIAssistantError> {
+        self.update(|vault, _| {
+            vault.remove(key);
+            Ok(())
+        })
+    }
+}
+
+fn vault_error(error: CoreError) -> AIAssistantError {
+    AIAssistantError::SecretError(error.to_string())
+}
+
+/// Secrets held in memory, for tests and headless environments without a keychain
+#[derive(Debug, Default)]
+pub struct MemorySecretStore {
+    secrets: Mutex<HashMap<String, String>>,
+}
+
+impl SecretStore for MemorySecretStore {
+    fn get(&self, key: &str) -> Result<Option<String>, AIAssistantError> {
+        Ok(self.secrets.lock().unwrap().get(key).cloned())
+    }
+    
+    fn set(&self, key: &str, secret: &str) -> Result<(), AIAssistantError> {
+        self.secrets.lock().unwrap().insert(key.to_string(), secret.to_string());
+        Ok(())
+    }
+    
+    fn delete(&self, key: &str) -> Result<(), AIAssistantError> {
+        self.secrets.lock().unwrap().remove(key);
+        Ok(())
+    }
+}
+
+/// Secret store key of a provider's API key
+pub fn api_key_secret(provider: &str) -> String {
+    format!("provider/{}/api-key", provider)
+}
+
+/// Token bucket enforcing a provider's rate limit
+#[derive(Debug)]
+pub struct RateLimiter {
+    limit: RateLimit,
+    state: Mutex<(f64, Instant)>, // Available requests and time of the last refill
+}
+
+impl RateLimiter {
+    /// Create a limiter with a full bucket
+    pub fn new(limit: RateLimit) -> Self {
+        Self {
+            limit,
+            state: Mutex::new((limit.burst.max(1) as f64, Instant::now())),
+        }
+    }
+    
+    /// Get the enforced limit
+    pub fn limit(&self) -> RateLimit {
+        self.limit
+    }
+    
+    /// Reserve a request, returning how long the caller must wait before sending it
+    pub fn reserve(&self) -> Duration {
+        self.reserve_at(Instant::now())
+    }
+    
+    fn reserve_at(&self, now: Instant) -> Duration {
+        let per_second = self.limit.requests_per_minute.max(1) as f64 / 60.0;
+        let capacity = self.limit.burst.max(1) as f64;
+        let mut state = self.state.lock().unwrap();
+        let (available, refilled_at) = &mut *state;
+        
+        if now > *refilled_at {
+            *available = (*available + now.duration_since(*refilled_at).as_secs_f64() * per_second).min(capacity);
+            *refilled_at = now;
+        }
+        // The bucket may go negative; the debt is paid off by waiting
+        *available -= 1.0;
+        if *available >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-*available / per_second)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_rate_limiter_spaces_requests() {
+        let limiter = RateLimiter::new(RateLimit { requests_per_minute: 60, burst: 2 });
+        let start = Instant::now();
+        assert_eq!(limiter.reserve_at(start), Duration::ZERO);
+        assert_eq!(limiter.reserve_at(start), Duration::ZERO);
+        assert_eq!(limiter.reserve_at(start), Duration::from_secs(1));
+        assert_eq!(limiter.reserve_at(start), Duration::from_secs(2));
+        
+        // Three seconds later the debt is paid and one request is available again
+        assert_eq!(limiter.reserve_at(start + Duration::from_secs(3)), Duration::ZERO);
+        assert_eq!(limiter.reserve_at(start + Duration::from_secs(3)), Duration::from_secs(1));
+    }
+    
+    #[test]
+    fn test_settings_do_not_contain_keys() {
+        let project = tempfile::tempdir().unwrap();
+        let settings = ProviderSettings {
+            providers: vec![
+                ProviderConfig::new("work-openai", ProviderKind::OpenAiCompatible, "https://api.openai.com/v1/chat/completions").with_rate_limit(30, 5),
+                ProviderConfig::new("ollama", ProviderKind::Local, "http://localhost:11434/api/generate"),
+            ],
+        };
+        settings.save(project.path()).unwrap();
+        assert_eq!(ProviderSettings::load(project.path()).unwrap(), settings);
+        
+        let secrets = MemorySecretStore::default();
+        secrets.set(&api_key_secret("work-openai"), "sk-test").unwrap();
+        let saved = fs::read_to_string(project.path().join(PROVIDER_SETTINGS_FILE)).unwrap();
+        assert!(!saved.contains("sk-test"));
+        assert_eq!(secrets.get(&api_key_secret("work-openai")).unwrap().as_deref(), Some("sk-test"));
+        secrets.delete(&api_key_secret("work-openai")).unwrap();
+        assert_eq!(secrets.get(&api_key_secret("work-openai")).unwrap(), None);
+    }
+}
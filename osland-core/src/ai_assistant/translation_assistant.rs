@@ -0,0 +1,455 @@
+// Batch translation assistance for OSland AI Assistant
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! Batch translation of i18n catalogs with human approval.
+//!
+//! The workflow is: export the keys a language is missing, translate them with a
+//! model under the project glossary, save the suggestions for review, approve,
+//! edit or reject each one, and import only the approved ones into the catalog.
+//! Suggestions that drop a placeholder or break the glossary are flagged for the
+//! reviewer instead of being discarded.
+
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use serde::{Deserialize, Serialize};
+use crate::ai_assistant::AIAssistantError;
+use crate::ai_assistant::model_manager::{ModelManager, ModelParams};
+use crate::i18n::{Language, Translations, REFERENCE_LANGUAGE};
+
+/// Project-relative path of the translation glossary
+pub const GLOSSARY_FILE: &str = ".osland/i18n/glossary.json";
+
+/// Project-relative directory of suggestions waiting for review, one file per language
+pub const TRANSLATION_REVIEW_DIR: &str = ".osland/i18n/review";
+
+/// Glossary entry constraining how a term is translated
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GlossaryTerm {
+    /// Term as written in the reference language
+    pub term: String,
+    
+    /// Keep the term untranslated (product names, commands, ...)
+    #[serde(default)]
+    pub keep: bool,
+    
+    /// Required translation by language code
+    #[serde(default)]
+    pub translations: HashMap<String, String>,
+    
+    /// Note for translators
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+/// Terms with fixed translations
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Glossary {
+    /// Glossary entries
+    #[serde(default)]
+    pub terms: Vec<GlossaryTerm>,
+}
+
+impl Glossary {
+    /// Load the glossary of a project, or an empty glossary if it has none
+    pub fn load(project_dir: &Path) -> Result<Self, AIAssistantError> {
+        let path = project_dir.join(GLOSSARY_FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+    
+    /// Save the glossary of a project
+    pub fn save(&self, project_dir: &Path) -> Result<(), AIAssistantError> {
+        let path = project_dir.join(GLOSSARY_FILE);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+    
+    /// Get the required renderings of the terms in a source string
+    pub fn constraints(&self, language: Language, source: &str) -> Vec<(String, String)> {
+        let source = source.to_lowercase();
+        self.terms.iter()
+            .filter(|term| source.contains(&term.term.to_lowercase()))
+            .filter_map(|term| {
+                if term.keep {
+                    Some((term.term.clone(), term.term.clone()))
+                } else {
+                    term.translations.get(language.code()).map(|required| (term.term.clone(), required.clone()))
+                }
+            })
+            .collect()
+    }
+    
+    /// Describe the glossary terms a translation fails to use
+    pub fn violations(&self, language: Language, source: &str, translation: &str) -> Vec<String> {
+        self.constraints(language, source).into_iter()
+            .filter(|(_, required)| !translation.contains(required.as_str()))
+            .map(|(term, required)| format!("Glossary: '{}' must be rendered as '{}'", term, required))
+            .collect()
+    }
+}
+
+/// Key to translate
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TranslationItem {
+    /// Translation key
+    pub key: String,
+    
+    /// Reference string
+    pub source: String,
+}
+
+/// Get the keys a language is missing, with their reference strings
+pub fn export_untranslated(translations: &Translations, language: Language) -> Vec<TranslationItem> {
+    translations.coverage(language).missing.into_iter()
+        .filter_map(|key| translations.get(&key, REFERENCE_LANGUAGE).map(|source| TranslationItem { key, source }))
+        .collect()
+}
+
+/// Translates batches of strings
+pub trait Translator: Send + Sync {
+    /// Translate items, returning translations by key (items may be left out)
+    fn translate(&self, language: Language, items: &[TranslationItem], glossary: &Glossary) -> Result<HashMap<String, String>, AIAssistantError>;
+}
+
+/// Translator using a configured model
+pub struct ModelTranslator {
+    /// Model manager
+    model_manager: Arc<ModelManager>,
+    
+    /// Model name
+    model: String,
+    
+    /// Items sent per request
+    batch_size: usize,
+}
+
+impl ModelTranslator {
+    /// Create a translator using a model
+    pub fn new(model_manager: Arc<ModelManager>, model: String) -> Self {
+        Self { model_manager, model, batch_size: 40 }
+    }
+    
+    /// Set the number of items sent per request
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+    
+    fn build_prompt(&self, language: Language, items: &[TranslationItem], glossary: &Glossary) -> Result<String, AIAssistantError> {
+        let mut prompt = format!(
+            "Translate the user interface strings of an operating system IDE from {} into {} ({}).\n\
+             Keep placeholders such as {{0}} and {{1}} exactly as they are. Keep the strings short.\n",
+            REFERENCE_LANGUAGE.display_name(),
+            language.display_name(),
+            language.code(),
+        );
+        
+        let mut constraints: Vec<(String, String)> = items.iter()
+            .flat_map(|item| glossary.constraints(language, &item.source))
+            .collect();
+        constraints.sort();
+        constraints.dedup();
+        if !constraints.is_empty() {
+            prompt.push_str("Use these glossary renderings:\n");
+            for (term, required) in constraints {
+                prompt.push_str(&format!("- {} => {}\n", term, required));
+            }
+        }
+        
+        let strings: serde_json::Map<String, serde_json::Value> = items.iter()
+            .map(|item| (item.key.clone(), serde_json::Value::String(item.source.clone())))
+            .collect();
+        prompt.push_str("Answer with only a JSON object mapping each key to its translation.\n");
+        prompt.push_str(&serde_json::to_string_pretty(&strings)?);
+        Ok(prompt)
+    }
+}
+
+impl Translator for ModelTranslator {
+    fn translate(&self, language: Language, items: &[TranslationItem], glossary: &Glossary) -> Result<HashMap<String, String>, AIAssistantError> {
+        let params = ModelParams {
+            temperature: 0.2,
+            max_tokens: 4096,
+            ..Default::default()
+        };
+        
+        let mut translated = HashMap::new();
+        for chunk in items.chunks(self.batch_size) {
+            let prompt = self.build_prompt(language, chunk, glossary)?;
+            let response = self.model_manager.generate_with_model(&self.model, &prompt, &params)?;
+            translated.extend(parse_translations(&response)?);
+        }
+        Ok(translated)
+    }
+}
+
+/// Review state of a suggestion
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SuggestionStatus {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+/// Suggested translation waiting for review
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TranslationSuggestion {
+    /// Translation key
+    pub key: String,
+    
+    /// Reference string
+    pub source: String,
+    
+    /// Suggested (or reviewer-edited) translation
+    pub translation: String,
+    
+    /// Review state
+    pub status: SuggestionStatus,
+    
+    /// Problems found in the suggestion
+    #[serde(default)]
+    pub issues: Vec<String>,
+}
+
+/// Suggestions for one language
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TranslationBatch {
+    /// Target language code
+    pub language: String,
+    
+    /// Suggestions, sorted by key
+    pub suggestions: Vec<TranslationSuggestion>,
+    
+    /// Keys the translator returned nothing for
+    #[serde(default)]
+    pub untranslated: Vec<String>,
+}
+
+impl TranslationBatch {
+    /// Translate the keys a language is missing
+    pub fn prepare(
+        translations: &Translations,
+        language: Language,
+        glossary: &Glossary,
+        translator: &dyn Translator,
+    ) -> Result<Self, AIAssistantError> {
+        let items = export_untranslated(translations, language);
+        let mut translated = if items.is_empty() {
+            HashMap::new()
+        } else {
+            translator.translate(language, &items, glossary)?
+        };
+        
+        let mut suggestions = Vec::new();
+        let mut untranslated = Vec::new();
+        for item in items {
+            match translated.remove(&item.key) {
+                Some(translation) => {
+                    let issues = check_translation(glossary, language, &item.source, &translation);
+                    suggestions.push(TranslationSuggestion {
+                        key: item.key,
+                        source: item.source,
+                        translation,
+                        status: SuggestionStatus::Pending,
+                        issues,
+                    });
+                }
+                None => untranslated.push(item.key),
+            }
+        }
+        
+        Ok(Self {
+            language: language.code().to_string(),
+            suggestions,
+            untranslated,
+        })
+    }
+    
+    /// Get the target language
+    pub fn target_language(&self) -> Result<Language, AIAssistantError> {
+        Language::from_code(&self.language)
+            .ok_or_else(|| AIAssistantError::TranslationError(format!("Unknown language '{}'", self.language)))
+    }
+    
+    /// Approve a suggestion
+    pub fn approve(&mut self, key: &str) -> Result<(), AIAssistantError> {
+        self.suggestion_mut(key)?.status = SuggestionStatus::Approved;
+        Ok(())
+    }
+    
+    /// Reject a suggestion
+    pub fn reject(&mut self, key: &str) -> Result<(), AIAssistantError> {
+        self.suggestion_mut(key)?.status = SuggestionStatus::Rejected;
+        Ok(())
+    }
+    
+    /// Replace a suggestion with the reviewer's own translation and approve it
+    pub fn edit(&mut self, key: &str, translation: &str, glossary: &Glossary) -> Result<(), AIAssistantError> {
+        let language = self.target_language()?;
+        let suggestion = self.suggestion_mut(key)?;
+        suggestion.issues = check_translation(glossary, language, &suggestion.source, translation);
+        suggestion.translation = translation.to_string();
+        suggestion.status = SuggestionStatus::Approved;
+        Ok(())
+    }
+    
+    /// Get the suggestions still waiting for review
+    pub fn pending(&self) -> Vec<&TranslationSuggestion> {
+        self.suggestions.iter().filter(|suggestion| suggestion.status == SuggestionStatus::Pending).collect()
+    }
+    
+    /// Add the approved suggestions to the translations, returning how many were added
+    pub fn import(&self, translations: &Translations) -> Result<usize, AIAssistantError> {
+        let language = self.target_language()?;
+        let approved: Vec<&TranslationSuggestion> = self.suggestions.iter()
+            .filter(|suggestion| suggestion.status == SuggestionStatus::Approved)
+            .collect();
+        for suggestion in &approved {
+            translations.add_translation(&suggestion.key, language, &suggestion.translation);
+        }
+        Ok(approved.len())
+    }
+    
+    /// Save the batch for review
+    pub fn save(&self, project_dir: &Path) -> Result<PathBuf, AIAssistantError> {
+        let path = Self::review_path(project_dir, &self.language);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, serde_json::to_string_pretty(self)?)?;
+        Ok(path)
+    }
+    
+    /// Load the batch waiting for review for a language
+    pub fn load(project_dir: &Path, language: Language) -> Result<Self, AIAssistantError> {
+        let path = Self::review_path(project_dir, language.code());
+        if !path.exists() {
+            return Err(AIAssistantError::TranslationError(format!("No translations waiting for review for {}", language.code())));
+        }
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+    
+    fn review_path(project_dir: &Path, language: &str) -> PathBuf {
+        project_dir.join(TRANSLATION_REVIEW_DIR).join(format!("{}.json", language))
+    }
+    
+    fn suggestion_mut(&mut self, key: &str) -> Result<&mut TranslationSuggestion, AIAssistantError> {
+        self.suggestions.iter_mut()
+            .find(|suggestion| suggestion.key == key)
+            .ok_or_else(|| AIAssistantError::TranslationError(format!("No suggestion for '{}'", key)))
+    }
+}
+
+/// Find placeholder and glossary problems in a translation
+fn check_translation(glossary: &Glossary, language: Language, source: &str, translation: &str) -> Vec<String> {
+    let mut issues = Vec::new();
+    let expected = placeholders(source);
+    let found = placeholders(translation);
+    if expected != found {
+        issues.push(format!(
+            "Placeholders differ: expected {:?}, found {:?}",
+            expected.into_iter().collect::<Vec<_>>(),
+            found.into_iter().collect::<Vec<_>>(),
+        ));
+    }
+    issues.extend(glossary.violations(language, source, translation));
+    issues
+}
+
+/// Get the `{n}` placeholders of a string
+fn placeholders(text: &str) -> BTreeSet<String> {
+    let mut found = BTreeSet::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('{') {
+        rest = &rest[start + 1..];
+        if let Some(end) = rest.find('}') {
+            if !rest[..end].is_empty() && rest[..end].chars().all(|c| c.is_ascii_digit()) {
+                found.insert(format!("{{{}}}", &rest[..end]));
+            }
+        }
+    }
+    found
+}
+
+/// Parse the JSON object in a model response
+fn parse_translations(response: &str) -> Result<HashMap<String, String>, AIAssistantError> {
+    let start = response.find('{');
+    let end = response.rfind('}');
+    let json = match (start, end) {
+        (Some(start), Some(end)) if start < end => &response[start..=end],
+        _ => return Err(AIAssistantError::TranslationError("The model did not return a JSON object".to_string())),
+    };
+    Ok(serde_json::from_str(json)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    struct FixedTranslator(HashMap<String, String>);
+    
+    impl Translator for FixedTranslator {
+        fn translate(&self, _language: Language, items: &[TranslationItem], _glossary: &Glossary) -> Result<HashMap<String, String>, AIAssistantError> {
+            Ok(items.iter().filter_map(|item| self.0.get(&item.key).map(|value| (item.key.clone(), value.clone()))).collect())
+        }
+    }
+    
+    #[test]
+    fn test_batch_review_and_import() {
+        let translations = Translations::new(Language::English);
+        translations.add_translation("build.start", Language::English, "Start Build");
+        translations.add_translation("status.building", Language::English, "Building OS image from {0} to {1}...");
+        translations.add_translation("status.ide_started", Language::English, "OSland IDE started");
+        translations.add_translation("menu.file", Language::English, "File");
+        translations.add_translation("menu.file", Language::Japanese, "ファイル");
+        
+        let glossary = Glossary {
+            terms: vec![GlossaryTerm { term: "OSland".to_string(), keep: true, translations: HashMap::new(), note: None }],
+        };
+        let translator = FixedTranslator([
+            ("build.start", "ビルド開始"),
+            ("status.building", "{0} から OS イメージをビルドしています..."),
+            ("status.ide_started", "オーエスランド IDE が起動しました"),
+        ].into_iter().map(|(key, value)| (key.to_string(), value.to_string())).collect());
+        
+        let mut batch = TranslationBatch::prepare(&translations, Language::Japanese, &glossary, &translator).unwrap();
+        assert_eq!(batch.suggestions.len(), 3);
+        assert!(batch.untranslated.is_empty());
+        let issues = |batch: &TranslationBatch, key: &str| batch.suggestions.iter().find(|s| s.key == key).unwrap().issues.len();
+        assert_eq!(issues(&batch, "build.start"), 0);
+        assert_eq!(issues(&batch, "status.building"), 1);
+        assert_eq!(issues(&batch, "status.ide_started"), 1);
+        
+        let project = tempfile::tempdir().unwrap();
+        batch.save(project.path()).unwrap();
+        let mut reviewed = TranslationBatch::load(project.path(), Language::Japanese).unwrap();
+        assert_eq!(reviewed, batch);
+        
+        reviewed.approve("build.start").unwrap();
+        reviewed.edit("status.building", "{0} から {1} へ OS イメージをビルドしています...", &glossary).unwrap();
+        reviewed.reject("status.ide_started").unwrap();
+        assert!(reviewed.pending().is_empty());
+        assert!(reviewed.approve("missing.key").is_err());
+        batch = reviewed;
+        
+        assert_eq!(batch.import(&translations).unwrap(), 2);
+        assert_eq!(translations.get("build.start", Language::Japanese).as_deref(), Some("ビルド開始"));
+        assert_eq!(translations.get("status.ide_started", Language::Japanese), None);
+        assert_eq!(translations.coverage(Language::Japanese).missing, vec!["status.ide_started"]);
+    }
+    
+    #[test]
+    fn test_parse_model_response() {
+        let parsed = parse_translations("Here you go:\n```json\n{\"menu.file\": \"Файл\"}\n```").unwrap();
+        assert_eq!(parsed.get("menu.file").map(String::as_str), Some("Файл"));
+        assert!(parse_translations("no json").is_err());
+    }
+}
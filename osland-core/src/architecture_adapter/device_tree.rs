@@ -0,0 +1,512 @@
+// Device Tree Generation for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+use std::fmt::Write;
+use std::path::Path;
+use std::process::Command;
+use crate::core::architecture::HardwareArchitecture;
+use crate::tile_engine::tile_core::{Tile, TileGraph, TileType};
+use crate::architecture_adapter::hardware_adapters::HardwareAdapter;
+
+/// Device kind recognized on the hardware canvas
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeviceKind {
+    /// Interrupt controller (GIC on ARM64, PLIC on RISC-V)
+    InterruptController,
+    
+    /// Serial port
+    Uart,
+    
+    /// Timer
+    Timer,
+    
+    /// VirtIO MMIO transport
+    VirtioMmio,
+    
+    /// Any other memory-mapped device, described by its `compatible` string
+    Generic(String),
+}
+
+/// Memory-mapped device node
+#[derive(Debug, Clone)]
+pub struct DeviceNode {
+    /// Source tile ID
+    pub tile_id: String,
+    
+    /// Node name (e.g. `serial`)
+    pub node_name: String,
+    
+    /// Device kind
+    pub kind: DeviceKind,
+    
+    /// Compatible strings
+    pub compatible: Vec<String>,
+    
+    /// Register base address
+    pub base: u64,
+    
+    /// Register region size
+    pub size: u64,
+    
+    /// Interrupt numbers
+    pub interrupts: Vec<u32>,
+    
+    /// Clock frequency in Hz
+    pub clock_frequency: Option<u32>,
+}
+
+/// Memory region node
+#[derive(Debug, Clone)]
+pub struct MemoryRegion {
+    /// Source tile ID
+    pub tile_id: String,
+    
+    /// Base address
+    pub base: u64,
+    
+    /// Size in bytes
+    pub size: u64,
+}
+
+/// Generated device tree
+#[derive(Debug, Clone)]
+pub struct DeviceTree {
+    /// Target architecture
+    pub architecture: HardwareArchitecture,
+    
+    /// Board model
+    pub model: String,
+    
+    /// Board compatible string
+    pub compatible: String,
+    
+    /// Number of CPUs
+    pub cpu_count: u32,
+    
+    /// Memory regions
+    pub memory: Vec<MemoryRegion>,
+    
+    /// Devices (the interrupt controller, if any, included)
+    pub devices: Vec<DeviceNode>,
+    
+    /// Kernel command line
+    pub bootargs: Option<String>,
+}
+
+/// Device Tree Generator
+///
+/// Turns the hardware tiles of a tile graph into a device tree source file.
+/// Tiles are recognized through their `device` property (`memory`, `uart`,
+/// `interrupt-controller`, `timer`, `virtio-mmio`) or, for Memory tiles, their
+/// type; registers come from `base_address` and `size`, and interrupts from the
+/// comma-separated `interrupts` property.
+pub struct DeviceTreeGenerator {
+    /// Target architecture
+    architecture: HardwareArchitecture,
+    
+    /// Board model
+    model: String,
+}
+
+impl DeviceTreeGenerator {
+    /// Create a generator for ARM64 or RISC-V
+    pub fn new(architecture: HardwareArchitecture, model: String) -> Result<Self, String> {
+        match architecture {
+            HardwareArchitecture::Aarch64 | HardwareArchitecture::RiscV64 => Ok(Self { architecture, model }),
+            other => Err(format!("Device tree generation is not supported for {}", other)),
+        }
+    }
+    
+    /// Generate a device tree from a tile graph
+    pub fn generate(&self, graph: &TileGraph) -> Result<DeviceTree, String> {
+        let mut tiles: Vec<&Tile> = graph.tiles.values().collect();
+        tiles.sort_by(|a, b| a.name.cmp(&b.name).then(a.id.cmp(&b.id)));
+        
+        let mut tree = DeviceTree {
+            architecture: self.architecture.clone(),
+            model: self.model.clone(),
+            compatible: graph.get_property("compatible").cloned()
+                .unwrap_or_else(|| format!("osland,{}", sanitize(&graph.name))),
+            cpu_count: 0,
+            memory: Vec::new(),
+            devices: Vec::new(),
+            bootargs: graph.get_property("bootargs").cloned(),
+        };
+        
+        for tile in tiles {
+            let device = tile.get_property("device").map(|d| d.to_lowercase());
+            match (device.as_deref(), &tile.tile_type) {
+                (Some("memory"), _) | (None, TileType::Memory) => {
+                    tree.memory.push(MemoryRegion {
+                        tile_id: tile.id.clone(),
+                        base: required_number(tile, "base_address")?,
+                        size: required_number(tile, "size")?,
+                    });
+                }
+                (None, TileType::Processing) => {
+                    tree.cpu_count += tile.get_property("cores")
+                        .and_then(|c| c.parse::<u32>().ok())
+                        .unwrap_or(1);
+                }
+                (Some(device), _) => tree.devices.push(self.device_node(tile, device)?),
+                _ => {}
+            }
+        }
+        
+        tree.cpu_count = tree.cpu_count.max(1);
+        tree.devices.sort_by_key(|d| d.base);
+        Ok(tree)
+    }
+    
+    /// Build a device node from a tile
+    fn device_node(&self, tile: &Tile, device: &str) -> Result<DeviceNode, String> {
+        let riscv = self.architecture == HardwareArchitecture::RiscV64;
+        let (kind, node_name, default_compatible) = match device {
+            "uart" | "serial" => (DeviceKind::Uart, "serial", if riscv { "ns16550a" } else { "arm,pl011" }),
+            "interrupt-controller" | "gic" | "plic" => (
+                DeviceKind::InterruptController,
+                "interrupt-controller",
+                if riscv { "riscv,plic0" } else { "arm,gic-v3" },
+            ),
+            "timer" => (DeviceKind::Timer, "timer", if riscv { "riscv,clint0" } else { "arm,armv8-timer" }),
+            "virtio-mmio" | "virtio" => (DeviceKind::VirtioMmio, "virtio_mmio", "virtio,mmio"),
+            other => (DeviceKind::Generic(other.to_string()), "device", other),
+        };
+        
+        let compatible = tile.get_property("compatible")
+            .map(|c| c.split(',').map(|s| s.trim().to_string()).collect::<Vec<_>>())
+            .filter(|c| !c.is_empty() && c.iter().all(|s| !s.is_empty()))
+            .unwrap_or_else(|| vec![default_compatible.to_string()]);
+        
+        let interrupts = match tile.get_property("interrupts") {
+            Some(list) => list.split(',')
+                .map(|i| i.trim().parse::<u32>().map_err(|_| format!("Tile '{}' has invalid interrupt '{}'", tile.name, i.trim())))
+                .collect::<Result<Vec<_>, _>>()?,
+            None => Vec::new(),
+        };
+        
+        Ok(DeviceNode {
+            tile_id: tile.id.clone(),
+            node_name: tile.get_property("node_name").cloned().unwrap_or_else(|| node_name.to_string()),
+            kind,
+            compatible,
+            base: required_number(tile, "base_address")?,
+            size: required_number(tile, "size")?,
+            interrupts,
+            clock_frequency: tile.get_property("clock_frequency").and_then(|f| parse_number(f)).map(|f| f as u32),
+        })
+    }
+}
+
+impl DeviceTree {
+    /// Interrupt controller node, if present
+    pub fn interrupt_controller(&self) -> Option<&DeviceNode> {
+        self.devices.iter().find(|d| d.kind == DeviceKind::InterruptController)
+    }
+    
+    /// Validate the tree against a hardware adapter's constraints
+    pub fn validate(&self, adapter: &dyn HardwareAdapter) -> Vec<String> {
+        let mut errors = Vec::new();
+        
+        let adapter_arch = adapter.get_hardware_architecture();
+        if adapter_arch != self.architecture {
+            errors.push(format!("Device tree targets {} but the hardware adapter is for {}", self.architecture, adapter_arch));
+        }
+        
+        errors.extend(self.validate_with_page_size(adapter.get_memory_layout().page_size));
+        errors
+    }
+    
+    /// Validate the tree structure, requiring regions aligned to `page_size`
+    pub fn validate_with_page_size(&self, page_size: u64) -> Vec<String> {
+        let mut errors = Vec::new();
+        
+        if self.memory.is_empty() {
+            errors.push("No memory region is defined on the hardware canvas".to_string());
+        }
+        
+        // Regions must be page aligned and must not overlap
+        let page_size = page_size.max(1);
+        let mut regions: Vec<(String, u64, u64)> = self.memory.iter()
+            .map(|m| (format!("memory@{:x}", m.base), m.base, m.size))
+            .chain(self.devices.iter().map(|d| (format!("{}@{:x}", d.node_name, d.base), d.base, d.size)))
+            .collect();
+        for (name, base, size) in &regions {
+            if base % page_size != 0 {
+                errors.push(format!("{} base address is not aligned to the {}-byte page size", name, page_size));
+            }
+            if *size == 0 {
+                errors.push(format!("{} has an empty register region", name));
+            }
+        }
+        regions.sort_by_key(|(_, base, _)| *base);
+        for pair in regions.windows(2) {
+            let (first, first_base, first_size) = &pair[0];
+            let (second, second_base, _) = &pair[1];
+            if first_base.saturating_add(*first_size) > *second_base {
+                errors.push(format!("{} overlaps {}", first, second));
+            }
+        }
+        
+        // Interrupts need a controller
+        let has_interrupts = self.devices.iter().any(|d| !d.interrupts.is_empty() && d.kind != DeviceKind::InterruptController);
+        if has_interrupts && self.interrupt_controller().is_none() {
+            errors.push("Devices use interrupts but no interrupt controller is placed on the canvas".to_string());
+        }
+        
+        errors
+    }
+    
+    /// Render the device tree source
+    pub fn to_dts(&self) -> String {
+        let riscv = self.architecture == HardwareArchitecture::RiscV64;
+        let intc = self.interrupt_controller();
+        let mut dts = String::new();
+        
+        writeln!(dts, "// Generated by OSland").unwrap();
+        writeln!(dts, "/dts-v1/;").unwrap();
+        writeln!(dts).unwrap();
+        writeln!(dts, "/ {{").unwrap();
+        writeln!(dts, "\t#address-cells = <2>;").unwrap();
+        writeln!(dts, "\t#size-cells = <2>;").unwrap();
+        writeln!(dts, "\tmodel = \"{}\";", self.model).unwrap();
+        writeln!(dts, "\tcompatible = \"{}\";", self.compatible).unwrap();
+        if intc.is_some() {
+            writeln!(dts, "\tinterrupt-parent = <&intc>;").unwrap();
+        }
+        
+        // Chosen
+        writeln!(dts).unwrap();
+        writeln!(dts, "\tchosen {{").unwrap();
+        if let Some(uart) = self.devices.iter().find(|d| d.kind == DeviceKind::Uart) {
+            writeln!(dts, "\t\tstdout-path = \"/soc/{}@{:x}\";", uart.node_name, uart.base).unwrap();
+        }
+        if let Some(bootargs) = &self.bootargs {
+            writeln!(dts, "\t\tbootargs = \"{}\";", bootargs).unwrap();
+        }
+        writeln!(dts, "\t}};").unwrap();
+        
+        // CPUs
+        writeln!(dts).unwrap();
+        writeln!(dts, "\tcpus {{").unwrap();
+        writeln!(dts, "\t\t#address-cells = <1>;").unwrap();
+        writeln!(dts, "\t\t#size-cells = <0>;").unwrap();
+        if riscv {
+            writeln!(dts, "\t\ttimebase-frequency = <10000000>;").unwrap();
+        }
+        for cpu in 0..self.cpu_count {
+            writeln!(dts).unwrap();
+            writeln!(dts, "\t\tcpu{}: cpu@{} {{", cpu, cpu).unwrap();
+            writeln!(dts, "\t\t\tdevice_type = \"cpu\";").unwrap();
+            writeln!(dts, "\t\t\treg = <{}>;", cpu).unwrap();
+            if riscv {
+                writeln!(dts, "\t\t\tcompatible = \"riscv\";").unwrap();
+                writeln!(dts, "\t\t\triscv,isa = \"rv64imafdc\";").unwrap();
+                writeln!(dts, "\t\t\tmmu-type = \"riscv,sv48\";").unwrap();
+                writeln!(dts, "\t\t\tcpu{}_intc: interrupt-controller {{", cpu).unwrap();
+                writeln!(dts, "\t\t\t\t#interrupt-cells = <1>;").unwrap();
+                writeln!(dts, "\t\t\t\tinterrupt-controller;").unwrap();
+                writeln!(dts, "\t\t\t\tcompatible = \"riscv,cpu-intc\";").unwrap();
+                writeln!(dts, "\t\t\t}};").unwrap();
+            } else {
+                writeln!(dts, "\t\t\tcompatible = \"arm,cortex-a57\";").unwrap();
+                writeln!(dts, "\t\t\tenable-method = \"psci\";").unwrap();
+            }
+            writeln!(dts, "\t\t}};").unwrap();
+        }
+        writeln!(dts, "\t}};").unwrap();
+        
+        if !riscv {
+            writeln!(dts).unwrap();
+            writeln!(dts, "\tpsci {{").unwrap();
+            writeln!(dts, "\t\tcompatible = \"arm,psci-1.0\";").unwrap();
+            writeln!(dts, "\t\tmethod = \"hvc\";").unwrap();
+            writeln!(dts, "\t}};").unwrap();
+        }
+        
+        // Memory
+        for region in &self.memory {
+            writeln!(dts).unwrap();
+            writeln!(dts, "\tmemory@{:x} {{", region.base).unwrap();
+            writeln!(dts, "\t\tdevice_type = \"memory\";").unwrap();
+            writeln!(dts, "\t\treg = <{}>;", reg_cells(region.base, region.size)).unwrap();
+            writeln!(dts, "\t}};").unwrap();
+        }
+        
+        // Devices
+        writeln!(dts).unwrap();
+        writeln!(dts, "\tsoc {{").unwrap();
+        writeln!(dts, "\t\tcompatible = \"simple-bus\";").unwrap();
+        writeln!(dts, "\t\t#address-cells = <2>;").unwrap();
+        writeln!(dts, "\t\t#size-cells = <2>;").unwrap();
+        writeln!(dts, "\t\tranges;").unwrap();
+        for device in &self.devices {
+            writeln!(dts).unwrap();
+            let label = if device.kind == DeviceKind::InterruptController { "intc: " } else { "" };
+            writeln!(dts, "\t\t{}{}@{:x} {{", label, device.node_name, device.base).unwrap();
+            let compatible: Vec<String> = device.compatible.iter().map(|c| format!("\"{}\"", c)).collect();
+            writeln!(dts, "\t\t\tcompatible = {};", compatible.join(", ")).unwrap();
+            writeln!(dts, "\t\t\treg = <{}>;", reg_cells(device.base, device.size)).unwrap();
+            
+            if device.kind == DeviceKind::InterruptController {
+                writeln!(dts, "\t\t\tinterrupt-controller;").unwrap();
+                if riscv {
+                    writeln!(dts, "\t\t\t#interrupt-cells = <1>;").unwrap();
+                    let contexts: Vec<String> = (0..self.cpu_count).map(|cpu| format!("&cpu{}_intc 11 &cpu{}_intc 9", cpu, cpu)).collect();
+                    writeln!(dts, "\t\t\tinterrupts-extended = <{}>;", contexts.join(" ")).unwrap();
+                    writeln!(dts, "\t\t\triscv,ndev = <127>;").unwrap();
+                } else {
+                    writeln!(dts, "\t\t\t#interrupt-cells = <3>;").unwrap();
+                }
+            } else if !device.interrupts.is_empty() {
+                // GIC interrupts are <SPI number level-high>, PLIC interrupts are plain numbers
+                let cells: Vec<String> = device.interrupts.iter()
+                    .map(|irq| if riscv { irq.to_string() } else { format!("0 {} 4", irq) })
+                    .collect();
+                writeln!(dts, "\t\t\tinterrupts = <{}>;", cells.join(" ")).unwrap();
+            }
+            if let Some(frequency) = device.clock_frequency {
+                writeln!(dts, "\t\t\tclock-frequency = <{}>;", frequency).unwrap();
+            }
+            writeln!(dts, "\t\t}};").unwrap();
+        }
+        writeln!(dts, "\t}};").unwrap();
+        writeln!(dts, "}};").unwrap();
+        dts
+    }
+    
+    /// Write the DTS and compile it into a DTB with `dtc`
+    pub fn compile(&self, dts_path: &Path, dtb_path: &Path) -> Result<(), String> {
+        std::fs::write(dts_path, self.to_dts()).map_err(|e| format!("Failed to write {}: {}", dts_path.display(), e))?;
+        
+        let output = Command::new("dtc")
+            .args(["-I", "dts", "-O", "dtb", "-o"])
+            .arg(dtb_path)
+            .arg(dts_path)
+            .output()
+            .map_err(|e| format!("Failed to run dtc: {}", e))?;
+        
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(format!("dtc failed: {}", String::from_utf8_lossy(&output.stderr).trim()))
+        }
+    }
+}
+
+/// Render a 2-cell address and 2-cell size
+fn reg_cells(base: u64, size: u64) -> String {
+    format!("0x{:x} 0x{:x} 0x{:x} 0x{:x}", base >> 32, base & 0xffff_ffff, size >> 32, size & 0xffff_ffff)
+}
+
+/// Parse a decimal or `0x` hexadecimal number with an optional K/M/G suffix
+fn parse_number(value: &str) -> Option<u64> {
+    let value = value.trim().replace('_', "");
+    let (digits, multiplier) = match value.chars().last()?.to_ascii_uppercase() {
+        'K' => (&value[..value.len() - 1], 1u64 << 10),
+        'M' => (&value[..value.len() - 1], 1 << 20),
+        'G' => (&value[..value.len() - 1], 1 << 30),
+        _ => (value.as_str(), 1),
+    };
+    let number = match digits.strip_prefix("0x").or_else(|| digits.strip_prefix("0X")) {
+        Some(hex) => u64::from_str_radix(hex, 16).ok()?,
+        None => digits.parse().ok()?,
+    };
+    number.checked_mul(multiplier)
+}
+
+fn required_number(tile: &Tile, key: &str) -> Result<u64, String> {
+    let value = tile.get_property(key)
+        .ok_or_else(|| format!("Tile '{}' is missing the '{}' property", tile.name, key))?;
+    parse_number(value).ok_or_else(|| format!("Tile '{}' has an invalid '{}' value '{}'", tile.name, key, value))
+}
+
+fn sanitize(name: &str) -> String {
+    name.to_lowercase().chars().map(|c| if c.is_ascii_alphanumeric() { c } else { '-' }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::architecture_adapter::hardware_adapters::{Arm64HardwareAdapter, X86_64HardwareAdapter};
+    
+    fn tile(graph: &mut TileGraph, name: &str, tile_type: TileType, properties: &[(&str, &str)]) {
+        let mut tile = Tile::new(name.to_string(), tile_type, String::new());
+        for (key, value) in properties {
+            tile.set_property(key.to_string(), value.to_string());
+        }
+        graph.add_tile(tile).unwrap();
+    }
+    
+    fn board() -> TileGraph {
+        let mut graph = TileGraph::new("Virt Board".to_string());
+        tile(&mut graph, "cpu", TileType::Processing, &[("cores", "2")]);
+        tile(&mut graph, "dram", TileType::Memory, &[("base_address", "0x4000_0000"), ("size", "1G")]);
+        tile(&mut graph, "gic", TileType::IO, &[("device", "gic"), ("base_address", "0x08000000"), ("size", "64K")]);
+        tile(&mut graph, "uart0", TileType::IO, &[
+            ("device", "uart"),
+            ("base_address", "0x09000000"),
+            ("size", "0x1000"),
+            ("interrupts", "1"),
+            ("clock_frequency", "24M"),
+        ]);
+        graph
+    }
+    
+    #[test]
+    fn test_parse_number() {
+        assert_eq!(parse_number("0x8000_0000"), Some(0x8000_0000));
+        assert_eq!(parse_number("128M"), Some(128 << 20));
+        assert_eq!(parse_number("0x10k"), Some(16 << 10));
+        assert_eq!(parse_number(" 4096 "), Some(4096));
+        assert_eq!(parse_number("0xFFFFFFFFFFFFFFFFG"), None);
+        assert_eq!(parse_number("G"), None);
+        assert_eq!(parse_number(""), None);
+    }
+    
+    #[test]
+    fn test_arm64_tree_and_dts() {
+        assert!(DeviceTreeGenerator::new(HardwareArchitecture::X86_64, "pc".to_string()).is_err());
+        let generator = DeviceTreeGenerator::new(HardwareArchitecture::Aarch64, "OSland virt".to_string()).unwrap();
+        let tree = generator.generate(&board()).unwrap();
+        
+        assert_eq!(tree.cpu_count, 2);
+        assert_eq!(tree.compatible, "osland,virt-board");
+        assert_eq!(tree.devices.iter().map(|d| d.node_name.as_str()).collect::<Vec<_>>(), vec!["interrupt-controller", "serial"]);
+        assert!(tree.validate(&Arm64HardwareAdapter::new()).is_empty());
+        assert_eq!(tree.validate(&X86_64HardwareAdapter::new()).len(), 1);
+        
+        let dts = tree.to_dts();
+        assert!(dts.contains("\tinterrupt-parent = <&intc>;\n"));
+        assert!(dts.contains("\t\tstdout-path = \"/soc/serial@9000000\";\n"));
+        assert!(dts.contains("\t\tcpu1: cpu@1 {\n\t\t\tdevice_type = \"cpu\";\n\t\t\treg = <1>;\n"));
+        assert!(dts.contains("\tmemory@40000000 {\n\t\tdevice_type = \"memory\";\n\t\treg = <0x0 0x40000000 0x0 0x40000000>;\n"));
+        assert!(dts.contains("\t\tintc: interrupt-controller@8000000 {\n\t\t\tcompatible = \"arm,gic-v3\";\n"));
+        assert!(dts.contains("\t\t\tinterrupts = <0 1 4>;\n\t\t\tclock-frequency = <25165824>;\n"));
+        assert!(dts.ends_with("\t};\n};\n"));
+    }
+    
+    #[test]
+    fn test_riscv_validation_errors() {
+        let mut graph = TileGraph::new("broken".to_string());
+        tile(&mut graph, "uart0", TileType::IO, &[("device", "uart"), ("base_address", "0x10000000"), ("size", "0x2000"), ("interrupts", "10")]);
+        tile(&mut graph, "uart1", TileType::IO, &[("device", "serial"), ("base_address", "0x10001000"), ("size", "0x100")]);
+        tile(&mut graph, "rtc", TileType::IO, &[("device", "google,goldfish-rtc"), ("base_address", "0x10101010"), ("size", "0x1000")]);
+        
+        let generator = DeviceTreeGenerator::new(HardwareArchitecture::RiscV64, "broken".to_string()).unwrap();
+        let tree = generator.generate(&graph).unwrap();
+        assert_eq!(tree.devices[0].compatible, vec!["ns16550a".to_string()]);
+        assert_eq!(tree.devices[2].kind, DeviceKind::Generic("google,goldfish-rtc".to_string()));
+        assert_eq!(tree.validate_with_page_size(4096), vec![
+            "No memory region is defined on the hardware canvas".to_string(),
+            "device@10101010 base address is not aligned to the 4096-byte page size".to_string(),
+            "serial@10000000 overlaps serial@10001000".to_string(),
+            "Devices use interrupts but no interrupt controller is placed on the canvas".to_string(),
+        ]);
+        
+        tile(&mut graph, "plic", TileType::IO, &[("device", "plic"), ("base_address", "0x0c000000"), ("interrupts", "x")]);
+        let error = generator.generate(&graph).unwrap_err();
+        assert_eq!(error, "Tile 'plic' has invalid interrupt 'x'");
+    }
+}
@@ -0,0 +1,344 @@
+// GPU Probe for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! Detection of GPUs usable by generated code.
+//!
+//! The probe queries the vendor tools (`nvidia-smi` for CUDA, `rocm-smi` for ROCm,
+//! `system_profiler` for Metal on macOS), records the devices in the DBOS `resources`
+//! table and tells the tile compiler whether GPU target languages can be built.
+
+use std::collections::HashMap;
+use std::process::Command;
+use serde::{Deserialize, Serialize};
+use crate::dbos_integration::dbos_core::TablesManager;
+
+/// Resource type of GPU rows in the `resources` table
+pub const GPU_RESOURCE_TYPE: &str = "GPU";
+
+/// GPU programming backend
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GpuBackend {
+    Cuda,
+    Rocm,
+    Metal,
+}
+
+impl GpuBackend {
+    /// Display name
+    pub fn name(&self) -> &'static str {
+        match self {
+            GpuBackend::Cuda => "CUDA",
+            GpuBackend::Rocm => "ROCm",
+            GpuBackend::Metal => "Metal",
+        }
+    }
+}
+
+/// Detected GPU
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GpuDevice {
+    /// Backend the device is usable with
+    pub backend: GpuBackend,
+    
+    /// Device index within the backend
+    pub index: u32,
+    
+    /// Product name
+    pub name: String,
+    
+    /// Device memory (MiB)
+    pub memory_mb: u64,
+    
+    /// Compute capability or architecture (e.g. "8.6", "gfx90a", "metal3")
+    pub compute_capability: Option<String>,
+    
+    /// Driver version
+    pub driver_version: Option<String>,
+}
+
+/// Result of probing all backends
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GpuProbeReport {
+    /// Detected devices
+    pub devices: Vec<GpuDevice>,
+    
+    /// Why a backend found no device (tool missing, query failed)
+    pub notes: HashMap<GpuBackend, String>,
+}
+
+impl GpuProbeReport {
+    /// Devices of a backend
+    pub fn devices_for(&self, backend: GpuBackend) -> Vec<&GpuDevice> {
+        self.devices.iter().filter(|device| device.backend == backend).collect()
+    }
+    
+    /// Whether a backend has at least one device
+    pub fn has(&self, backend: GpuBackend) -> bool {
+        self.devices.iter().any(|device| device.backend == backend)
+    }
+    
+    /// Check that one of the backends has a device, explaining what is missing otherwise
+    pub fn require_any(&self, backends: &[GpuBackend]) -> Result<(), String> {
+        if backends.is_empty() || backends.iter().any(|backend| self.has(*backend)) {
+            return Ok(());
+        }
+        let names: Vec<&str> = backends.iter().map(|backend| backend.name()).collect();
+        let mut message = format!("no {} device found", names.join(" or "));
+        for backend in backends {
+            if let Some(note) = self.notes.get(backend) {
+                message.push_str(&format!("; {}: {}", backend.name(), note));
+            }
+        }
+        Err(message)
+    }
+    
+    /// Replace the GPU rows of the `resources` table with the detected devices
+    pub fn publish(&self, tables: &TablesManager) -> Result<usize, String> {
+        let conditions = HashMap::from([("resource_type".to_string(), GPU_RESOURCE_TYPE.to_string())]);
+        for row in tables.query_rows("resources", conditions)? {
+            tables.delete_row("resources", &row.row_id)?;
+        }
+        
+        for device in &self.devices {
+            let metadata = serde_json::json!({
+                "backend": device.backend.name(),
+                "index": device.index,
+                "compute_capability": device.compute_capability,
+                "driver_version": device.driver_version,
+            });
+            let values = HashMap::from([
+                ("name".to_string(), device.name.clone()),
+                ("resource_type".to_string(), GPU_RESOURCE_TYPE.to_string()),
+                ("status".to_string(), "AVAILABLE".to_string()),
+                ("capacity".to_string(), device.memory_mb.to_string()),
+                ("metadata".to_string(), metadata.to_string()),
+            ]);
+            tables.insert_row("resources", values)?;
+        }
+        Ok(self.devices.len())
+    }
+}
+
+/// Runs a vendor tool, returning its standard output
+pub trait ProbeCommand: Send + Sync {
+    /// Run a program; `Err` explains why no output is available
+    fn run(&self, program: &str, args: &[&str]) -> Result<String, String>;
+}
+
+/// Runs tools from `PATH`
+pub struct SystemProbeCommand;
+
+impl ProbeCommand for SystemProbeCommand {
+    fn run(&self, program: &str, args: &[&str]) -> Result<String, String> {
+        let output = Command::new(program)
+            .args(args)
+            .output()
+            .map_err(|e| match e.kind() {
+                std::io::ErrorKind::NotFound => format!("'{}' not found in PATH", program),
+                _ => format!("failed to run '{}': {}", program, e),
+            })?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            let reason = if stderr.trim().is_empty() { stdout } else { stderr };
+            return Err(format!("'{}' failed: {}", program, reason.trim()));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+}
+
+/// GPU Probe
+pub struct GpuProbe {
+    command: Box<dyn ProbeCommand>,
+    
+    /// Whether to query Metal (only meaningful on macOS)
+    probe_metal: bool,
+}
+
+impl Default for GpuProbe {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GpuProbe {
+    /// Create a probe using the tools in `PATH`
+    pub fn new() -> Self {
+        Self::with_command(Box::new(SystemProbeCommand))
+    }
+    
+    /// Create a probe with a custom tool runner
+    pub fn with_command(command: Box<dyn ProbeCommand>) -> Self {
+        Self {
+            command,
+            probe_metal: cfg!(target_os = "macos"),
+        }
+    }
+    
+    /// Enable or disable the Metal query
+    pub fn set_probe_metal(&mut self, probe_metal: bool) {
+        self.probe_metal = probe_metal;
+    }
+    
+    /// Query all backends
+    pub fn probe(&self) -> GpuProbeReport {
+        let mut report = GpuProbeReport::default();
+        let mut record = |backend: GpuBackend, result: Result<Vec<GpuDevice>, String>| match result {
+            Ok(devices) if devices.is_empty() => {
+                report.notes.insert(backend, "no devices reported".to_string());
+            }
+            Ok(devices) => report.devices.extend(devices),
+            Err(note) => {
+                report.notes.insert(backend, note);
+            }
+        };
+        
+        record(GpuBackend::Cuda, self.probe_cuda());
+        record(GpuBackend::Rocm, self.probe_rocm());
+        if self.probe_metal {
+            record(GpuBackend::Metal, self.probe_metal_devices());
+        } else {
+            record(GpuBackend::Metal, Err("Metal is only available on macOS".to_string()));
+        }
+        report
+    }
+    
+    fn probe_cuda(&self) -> Result<Vec<GpuDevice>, String> {
+        let output = self.command.run("nvidia-smi", &[
+            "--query-gpu=index,name,memory.total,compute_cap,driver_version",
+            "--format=csv,noheader,nounits",
+        ])?;
+        Ok(parse_nvidia_smi(&output))
+    }
+    
+    fn probe_rocm(&self) -> Result<Vec<GpuDevice>, String> {
+        let output = self.command.run("rocm-smi", &["--showproductname", "--showmeminfo", "vram", "--json"])?;
+        parse_rocm_smi(&output)
+    }
+    
+    fn probe_metal_devices(&self) -> Result<Vec<GpuDevice>, String> {
+        let output = self.command.run("system_profiler", &["SPDisplaysDataType", "-json"])?;
+        parse_system_profiler(&output)
+    }
+}
+
+/// Parse `nvidia-smi --query-gpu=index,name,memory.total,compute_cap,driver_version` CSV
+fn parse_nvidia_smi(output: &str) -> Vec<GpuDevice> {
+    let optional = |value: Option<&str>| value
+        .map(str::trim)
+        .filter(|value| !value.is_empty() && *value != "[N/A]")
+        .map(str::to_string);
+    
+    output.lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+            if fields.len() < 3 {
+                return None;
+            }
+            Some(GpuDevice {
+                backend: GpuBackend::Cuda,
+                index: fields[0].parse().ok()?,
+                name: fields[1].to_string(),
+                memory_mb: fields[2].parse().unwrap_or(0),
+                compute_capability: optional(fields.get(3).copied()),
+                driver_version: optional(fields.get(4).copied()),
+            })
+        })
+        .collect()
+}
+
+/// Parse `rocm-smi --showproductname --showmeminfo vram --json`
+fn parse_rocm_smi(output: &str) -> Result<Vec<GpuDevice>, String> {
+    let json: serde_json::Value = serde_json::from_str(output)
+        .map_err(|e| format!("unexpected rocm-smi output: {}", e))?;
+    let cards = json.as_object().ok_or("unexpected rocm-smi output")?;
+    
+    let mut devices: Vec<GpuDevice> = cards.iter()
+        .filter_map(|(card, info)| {
+            let index = card.strip_prefix("card")?.parse().ok()?;
+            let field = |key: &str| info.get(key).and_then(|value| value.as_str()).map(str::to_string);
+            let memory_bytes: u64 = field("VRAM Total Memory (B)").and_then(|value| value.parse().ok()).unwrap_or(0);
+            Some(GpuDevice {
+                backend: GpuBackend::Rocm,
+                index,
+                name: field("Card Series").or_else(|| field("Card series")).unwrap_or_else(|| card.clone()),
+                memory_mb: memory_bytes / (1024 * 1024),
+                compute_capability: field("GFX Version"),
+                driver_version: None,
+            })
+        })
+        .collect();
+    devices.sort_by_key(|device| device.index);
+    Ok(devices)
+}
+
+/// Parse `system_profiler SPDisplaysDataType -json`
+fn parse_system_profiler(output: &str) -> Result<Vec<GpuDevice>, String> {
+    let json: serde_json::Value = serde_json::from_str(output)
+        .map_err(|e| format!("unexpected system_profiler output: {}", e))?;
+    let displays = json.get("SPDisplaysDataType")
+        .and_then(|value| value.as_array())
+        .ok_or("unexpected system_profiler output")?;
+    
+    Ok(displays.iter()
+        .filter_map(|display| display.get("spdisplays_mtlgpufamilysupport").map(|family| (display, family)))
+        .enumerate()
+        .map(|(index, (display, family))| {
+            let memory_mb = display.get("spdisplays_vram")
+                .and_then(|value| value.as_str())
+                .and_then(|value| value.split_whitespace().next())
+                .and_then(|value| value.parse::<u64>().ok())
+                .unwrap_or(0);
+            GpuDevice {
+                backend: GpuBackend::Metal,
+                index: index as u32,
+                name: display.get("sppci_model").and_then(|value| value.as_str()).unwrap_or("Apple GPU").to_string(),
+                memory_mb,
+                compute_capability: family.as_str().map(|family| family.trim_start_matches("spdisplays_").to_string()),
+                driver_version: None,
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    struct FakeCommand(HashMap<&'static str, Result<String, String>>);
+    
+    impl ProbeCommand for FakeCommand {
+        fn run(&self, program: &str, _args: &[&str]) -> Result<String, String> {
+            self.0.get(program).cloned().unwrap_or_else(|| Err(format!("'{}' not found in PATH", program)))
+        }
+    }
+    
+    #[test]
+    fn test_probe_and_publish() {
+        let command = FakeCommand(HashMap::from([
+            ("nvidia-smi", Ok("0, NVIDIA GeForce RTX 3080, 10240, 8.6, 535.104\n1, Tesla T4, 15360, 7.5, 535.104\n".to_string())),
+            ("rocm-smi", Ok(r#"{"card0": {"Card Series": "AMD Instinct MI210", "VRAM Total Memory (B)": "68702699520", "GFX Version": "gfx90a"}}"#.to_string())),
+        ]));
+        let mut probe = GpuProbe::with_command(Box::new(command));
+        probe.set_probe_metal(true);
+        let report = probe.probe();
+        
+        assert_eq!(report.devices_for(GpuBackend::Cuda).len(), 2);
+        assert_eq!(report.devices[0].compute_capability.as_deref(), Some("8.6"));
+        assert_eq!(report.devices_for(GpuBackend::Rocm)[0].memory_mb, 65520);
+        assert!(!report.has(GpuBackend::Metal));
+        assert!(report.require_any(&[GpuBackend::Cuda]).is_ok());
+        let error = report.require_any(&[GpuBackend::Metal]).unwrap_err();
+        assert_eq!(error, "no Metal device found; Metal: 'system_profiler' not found in PATH");
+        
+        let tables = TablesManager::new();
+        tables.start();
+        assert_eq!(report.publish(&tables).unwrap(), 3);
+        assert_eq!(report.publish(&tables).unwrap(), 3);
+        let conditions = HashMap::from([("resource_type".to_string(), GPU_RESOURCE_TYPE.to_string())]);
+        let rows = tables.query_rows("resources", conditions).unwrap();
+        assert_eq!(rows.len(), 3);
+        assert!(rows.iter().any(|row| row.values.get("name").map(String::as_str) == Some("Tesla T4")));
+    }
+}
@@ -6,10 +6,16 @@ pub mod hardware_adapters;
 pub mod kernel_adapters;
 pub mod architecture_service;
 pub mod partitioned_kernel_adapter;
+pub mod device_tree;
+pub mod gpu_probe;
+pub mod topology_probe;
 
 // Re-export core components
 pub use hardware_adapters::{HardwareAdapter, X86_64HardwareAdapter, Arm64HardwareAdapter};
 pub use kernel_adapters::{KernelAdapter, MonolithicAdapter, MicrokernelAdapter};
 pub use partitioned_kernel_adapter::{PartitionedKernelAdapter, PartitionedKernelConfig, KernelPartition};
 pub use architecture_service::{ArchitectureService, ArchitectureCompatibility};
+pub use device_tree::{DeviceTree, DeviceTreeGenerator};
+pub use gpu_probe::{GpuProbe, GpuProbeReport, GpuDevice, GpuBackend};
+pub use topology_probe::{TopologyProbe, CpuTopology, LogicalCpu, CacheInfo, CacheKind, NumaNode};
 pub use crate::core::architecture::{KernelArchitecture, HardwareArchitecture, Architecture, MemoryLayout};
@@ -0,0 +1,568 @@
+// CPU Topology Probe for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! Discovery of the CPU, cache and memory topology of the host.
+//!
+//! Like hwloc, the probe walks the kernel's sysfs description of the machine:
+//! `cpu/cpuN/topology` for packages (sockets), cores and hardware threads,
+//! `cpu/cpuN/cache/indexM` for the cache hierarchy and the CPUs sharing each cache,
+//! and `node/nodeN` for NUMA nodes. The topology is recorded in the DBOS `resources`
+//! table as a tree linked through `parent_id` (package → core → thread, caches under
+//! the core or package sharing them) and drives the tile placer. Without sysfs the
+//! probe falls back to one package and one NUMA node holding every available CPU.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use crate::dbos_integration::dbos_core::TablesManager;
+
+/// Resource type of NUMA node rows in the `resources` table
+pub const NUMA_NODE_RESOURCE_TYPE: &str = "NUMA_NODE";
+
+/// Resource type of package (socket) rows in the `resources` table
+pub const PACKAGE_RESOURCE_TYPE: &str = "CPU_PACKAGE";
+
+/// Resource type of physical core rows in the `resources` table
+pub const CORE_RESOURCE_TYPE: &str = "CPU_CORE";
+
+/// Resource type of hardware thread rows in the `resources` table
+pub const THREAD_RESOURCE_TYPE: &str = "CPU_THREAD";
+
+/// Resource type of cache rows in the `resources` table
+pub const CACHE_RESOURCE_TYPE: &str = "CPU_CACHE";
+
+/// Resource types written by `CpuTopology::publish`
+pub const TOPOLOGY_RESOURCE_TYPES: [&str; 5] = [
+    NUMA_NODE_RESOURCE_TYPE,
+    PACKAGE_RESOURCE_TYPE,
+    CORE_RESOURCE_TYPE,
+    THREAD_RESOURCE_TYPE,
+    CACHE_RESOURCE_TYPE,
+];
+
+/// Default sysfs directory describing the system
+const SYSFS_ROOT: &str = "/sys/devices/system";
+
+/// Kind of cache
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum CacheKind {
+    Data,
+    Instruction,
+    Unified,
+}
+
+impl CacheKind {
+    /// Parse the sysfs cache type
+    fn from_sysfs(value: &str) -> Option<Self> {
+        match value.trim() {
+            "Data" => Some(CacheKind::Data),
+            "Instruction" => Some(CacheKind::Instruction),
+            "Unified" => Some(CacheKind::Unified),
+            _ => None,
+        }
+    }
+}
+
+/// Hardware thread (logical CPU)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LogicalCpu {
+    /// Operating system CPU number
+    pub id: u32,
+    
+    /// Package (socket) the thread belongs to
+    pub package: u32,
+    
+    /// Core within the package
+    pub core: u32,
+    
+    /// NUMA node the thread is local to
+    pub numa_node: u32,
+}
+
+/// Cache and the CPUs sharing it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheInfo {
+    /// Cache level (1 for L1)
+    pub level: u8,
+    
+    /// Data, instruction or unified cache
+    pub kind: CacheKind,
+    
+    /// Cache size (KiB)
+    pub size_kb: u64,
+    
+    /// CPUs sharing the cache, in ascending order
+    pub cpus: Vec<u32>,
+}
+
+/// NUMA node
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NumaNode {
+    /// Node number
+    pub id: u32,
+    
+    /// CPUs local to the node, in ascending order
+    pub cpus: Vec<u32>,
+    
+    /// Memory attached to the node (MiB, 0 if unknown)
+    pub memory_mb: u64,
+}
+
+/// CPU, cache and NUMA topology of a machine
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CpuTopology {
+    /// Hardware threads, ordered by CPU number
+    pub cpus: Vec<LogicalCpu>,
+    
+    /// Caches, ordered by level, kind and first CPU
+    pub caches: Vec<CacheInfo>,
+    
+    /// NUMA nodes, ordered by number
+    pub numa_nodes: Vec<NumaNode>,
+    
+    /// What the probe could not find out
+    pub notes: Vec<String>,
+}
+
+impl CpuTopology {
+    /// Build a symmetric topology: one NUMA node and shared L3 per package,
+    /// private L1 and L2 per core
+    pub fn uniform(packages: u32, cores_per_package: u32, threads_per_core: u32) -> Self {
+        let mut topology = CpuTopology::default();
+        let mut next_cpu = 0;
+        for package in 0..packages {
+            let mut package_cpus = Vec::new();
+            for core in 0..cores_per_package {
+                let core_cpus: Vec<u32> = (0..threads_per_core).map(|thread| next_cpu + thread).collect();
+                next_cpu += threads_per_core;
+                for &id in &core_cpus {
+                    topology.cpus.push(LogicalCpu { id, package, core, numa_node: package });
+                }
+                topology.caches.push(CacheInfo { level: 1, kind: CacheKind::Data, size_kb: 32, cpus: core_cpus.clone() });
+                topology.caches.push(CacheInfo { level: 1, kind: CacheKind::Instruction, size_kb: 32, cpus: core_cpus.clone() });
+                topology.caches.push(CacheInfo { level: 2, kind: CacheKind::Unified, size_kb: 1024, cpus: core_cpus.clone() });
+                package_cpus.extend(core_cpus);
+            }
+            topology.caches.push(CacheInfo { level: 3, kind: CacheKind::Unified, size_kb: 32 * 1024, cpus: package_cpus.clone() });
+            topology.numa_nodes.push(NumaNode { id: package, cpus: package_cpus, memory_mb: 0 });
+        }
+        topology.sort();
+        topology
+    }
+    
+    /// Put CPUs, caches and nodes in their canonical order
+    fn sort(&mut self) {
+        self.cpus.sort_by_key(|cpu| cpu.id);
+        self.caches.sort_by(|a, b| (a.level, a.kind, &a.cpus).cmp(&(b.level, b.kind, &b.cpus)));
+        self.numa_nodes.sort_by_key(|node| node.id);
+    }
+    
+    /// Get a hardware thread by CPU number
+    pub fn cpu(&self, id: u32) -> Option<&LogicalCpu> {
+        self.cpus.iter().find(|cpu| cpu.id == id)
+    }
+    
+    /// Get the packages, in ascending order
+    pub fn packages(&self) -> Vec<u32> {
+        self.cpus.iter().map(|cpu| cpu.package).collect::<BTreeSet<_>>().into_iter().collect()
+    }
+    
+    /// Get the cores of a package, in ascending order
+    pub fn cores(&self, package: u32) -> Vec<u32> {
+        self.cpus.iter()
+            .filter(|cpu| cpu.package == package)
+            .map(|cpu| cpu.core)
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+    
+    /// Get the hardware threads of a core, in ascending order
+    pub fn threads(&self, package: u32, core: u32) -> Vec<u32> {
+        self.cpus.iter()
+            .filter(|cpu| cpu.package == package && cpu.core == core)
+            .map(|cpu| cpu.id)
+            .collect()
+    }
+    
+    /// Get the position of a CPU among the hardware threads of its core (0 for the first)
+    pub fn smt_rank(&self, id: u32) -> usize {
+        self.cpu(id)
+            .and_then(|cpu| self.threads(cpu.package, cpu.core).iter().position(|&thread| thread == id))
+            .unwrap_or(0)
+    }
+    
+    /// Get the NUMA node of a CPU
+    pub fn numa_node_of(&self, id: u32) -> Option<u32> {
+        self.cpu(id).map(|cpu| cpu.numa_node)
+    }
+    
+    /// Get the largest data or unified cache a CPU uses
+    pub fn last_level_cache(&self, id: u32) -> Option<&CacheInfo> {
+        self.caches.iter()
+            .filter(|cache| cache.kind != CacheKind::Instruction && cache.cpus.contains(&id))
+            .max_by_key(|cache| cache.level)
+    }
+    
+    /// Get the lowest level of data or unified cache two CPUs share
+    pub fn shared_cache_level(&self, a: u32, b: u32) -> Option<u8> {
+        self.caches.iter()
+            .filter(|cache| cache.kind != CacheKind::Instruction && cache.cpus.contains(&a) && cache.cpus.contains(&b))
+            .map(|cache| cache.level)
+            .min()
+    }
+    
+    /// Replace the topology rows of the `resources` table, returning the number of rows written
+    ///
+    /// NUMA nodes, packages, cores, threads and caches each get a row; `parent_id`
+    /// links a core to its package, a thread to its core and a cache to the core or
+    /// package sharing it, and `numa_node` names the node a resource is local to.
+    pub fn publish(&self, tables: &TablesManager) -> Result<usize, String> {
+        for resource_type in TOPOLOGY_RESOURCE_TYPES {
+            let conditions = HashMap::from([("resource_type".to_string(), resource_type.to_string())]);
+            for row in tables.query_rows("resources", conditions)? {
+                tables.delete_row("resources", &row.row_id)?;
+            }
+        }
+        
+        let mut written = 0;
+        let mut insert = |name: String, resource_type: &str, parent: Option<&str>, numa_node: Option<u32>, capacity: u64, metadata: serde_json::Value|
+            -> Result<String, String> {
+            let resource_id = Uuid::new_v4().to_string();
+            let mut values = HashMap::from([
+                ("resource_id".to_string(), resource_id.clone()),
+                ("name".to_string(), name),
+                ("resource_type".to_string(), resource_type.to_string()),
+                ("status".to_string(), "AVAILABLE".to_string()),
+                ("capacity".to_string(), capacity.to_string()),
+                ("metadata".to_string(), metadata.to_string()),
+            ]);
+            if let Some(parent) = parent {
+                values.insert("parent_id".to_string(), parent.to_string());
+            }
+            if let Some(numa_node) = numa_node {
+                values.insert("numa_node".to_string(), numa_node.to_string());
+            }
+            tables.insert_row("resources", values)?;
+            written += 1;
+            Ok(resource_id)
+        };
+        
+        // The NUMA node of a set of CPUs, if they are all local to the same one
+        let common_node = |cpus: &[u32]| {
+            let nodes: BTreeSet<u32> = cpus.iter().filter_map(|&id| self.numa_node_of(id)).collect();
+            if nodes.len() == 1 { nodes.into_iter().next() } else { None }
+        };
+        
+        for node in &self.numa_nodes {
+            insert(format!("node{}", node.id), NUMA_NODE_RESOURCE_TYPE, None, Some(node.id), node.memory_mb,
+                serde_json::json!({ "cpus": node.cpus }))?;
+        }
+        
+        let mut package_ids = HashMap::new();
+        let mut core_ids = HashMap::new();
+        for package in self.packages() {
+            let package_cpus: Vec<u32> = self.cpus.iter().filter(|cpu| cpu.package == package).map(|cpu| cpu.id).collect();
+            let package_id = insert(format!("package{}", package), PACKAGE_RESOURCE_TYPE, None,
+                common_node(&package_cpus), package_cpus.len() as u64,
+                serde_json::json!({ "package": package, "cpus": package_cpus }))?;
+            for core in self.cores(package) {
+                let threads = self.threads(package, core);
+                let core_id = insert(format!("package{}/core{}", package, core), CORE_RESOURCE_TYPE, Some(&package_id),
+                    common_node(&threads), threads.len() as u64,
+                    serde_json::json!({ "package": package, "core": core, "cpus": threads }))?;
+                for &thread in &threads {
+                    insert(format!("cpu{}", thread), THREAD_RESOURCE_TYPE, Some(&core_id), self.numa_node_of(thread), 1,
+                        serde_json::json!({ "cpu": thread, "smt_rank": self.smt_rank(thread) }))?;
+                }
+                core_ids.insert((package, core), core_id);
+            }
+            package_ids.insert(package, package_id);
+        }
+        
+        for cache in &self.caches {
+            let holders: BTreeSet<(u32, u32)> = cache.cpus.iter()
+                .filter_map(|&id| self.cpu(id))
+                .map(|cpu| (cpu.package, cpu.core))
+                .collect();
+            let packages: BTreeSet<u32> = holders.iter().map(|(package, _)| *package).collect();
+            let parent = match (holders.len(), packages.len()) {
+                (1, _) => holders.iter().next().and_then(|key| core_ids.get(key)),
+                (_, 1) => packages.iter().next().and_then(|package| package_ids.get(package)),
+                _ => None,
+            };
+            let first = cache.cpus.first().copied().unwrap_or_default();
+            insert(format!("L{} {:?} cache of cpu{}", cache.level, cache.kind, first), CACHE_RESOURCE_TYPE,
+                parent.map(String::as_str), common_node(&cache.cpus), cache.size_kb,
+                serde_json::json!({ "level": cache.level, "kind": cache.kind, "cpus": cache.cpus }))?;
+        }
+        Ok(written)
+    }
+}
+
+/// Largest number of CPUs a CPU list may name (the kernel's `NR_CPUS` limit)
+pub const MAX_CPUS: u32 = 8192;
+
+/// Parse a kernel CPU list such as `0-3,8,10-11`
+pub fn parse_cpu_list(list: &str) -> Result<Vec<u32>, String> {
+    let mut cpus = BTreeSet::new();
+    for part in list.trim().split(',').map(str::trim).filter(|part| !part.is_empty()) {
+        let parse = |value: &str| value.trim().parse::<u32>().ok()
+            .filter(|cpu| *cpu < MAX_CPUS)
+            .ok_or_else(|| format!("invalid CPU list '{}'", list.trim()));
+        match part.split_once('-') {
+            Some((first, last)) => {
+                let (first, last) = (parse(first)?, parse(last)?);
+                if first > last {
+                    return Err(format!("invalid CPU list '{}'", list.trim()));
+                }
+                cpus.extend(first..=last);
+            }
+            None => {
+                cpus.insert(parse(part)?);
+            }
+        }
+    }
+    Ok(cpus.into_iter().collect())
+}
+
+/// Parse a sysfs cache size such as `32K` or `8M` into KiB
+fn parse_cache_size(size: &str) -> Option<u64> {
+    let size = size.trim();
+    let (digits, multiplier) = match size.chars().last()? {
+        'K' => (&size[..size.len() - 1], 1),
+        'M' => (&size[..size.len() - 1], 1024),
+        'G' => (&size[..size.len() - 1], 1024 * 1024),
+        _ => (size, 1),
+    };
+    digits.parse::<u64>().ok().map(|value| value * multiplier)
+}
+
+/// Parse the `MemTotal` line of a node's `meminfo` into MiB
+fn parse_node_memory(meminfo: &str) -> Option<u64> {
+    meminfo.lines()
+        .find(|line| line.contains("MemTotal:"))
+        .and_then(|line| line.split_whitespace().rev().nth(1))
+        .and_then(|value| value.parse::<u64>().ok())
+        .map(|kb| kb / 1024)
+}
+
+/// CPU topology probe
+pub struct TopologyProbe {
+    /// Directory holding the `cpu` and `node` sysfs trees
+    root: PathBuf,
+}
+
+impl Default for TopologyProbe {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TopologyProbe {
+    /// Create a probe reading the sysfs of this machine
+    pub fn new() -> Self {
+        Self::with_root(SYSFS_ROOT)
+    }
+    
+    /// Create a probe reading a copy of `/sys/devices/system`, e.g. taken from a target board
+    pub fn with_root<P: AsRef<Path>>(root: P) -> Self {
+        Self { root: root.as_ref().to_path_buf() }
+    }
+    
+    /// Probe the topology, falling back to a flat topology when sysfs is unavailable
+    pub fn probe(&self) -> CpuTopology {
+        match self.probe_sysfs() {
+            Ok(topology) => topology,
+            Err(note) => {
+                let count = std::thread::available_parallelism().map(|count| count.get() as u32).unwrap_or(1);
+                CpuTopology {
+                    cpus: (0..count).map(|id| LogicalCpu { id, package: 0, core: id, numa_node: 0 }).collect(),
+                    numa_nodes: vec![NumaNode { id: 0, cpus: (0..count).collect(), memory_mb: 0 }],
+                    notes: vec![note],
+                    ..Default::default()
+                }
+            }
+        }
+    }
+    
+    /// Read a trimmed sysfs attribute
+    fn read(&self, path: &Path) -> Option<String> {
+        fs::read_to_string(path).ok().map(|value| value.trim().to_string())
+    }
+    
+    fn probe_sysfs(&self) -> Result<CpuTopology, String> {
+        let cpu_dir = self.root.join("cpu");
+        let online = match self.read(&cpu_dir.join("online")) {
+            Some(list) => parse_cpu_list(&list)?,
+            None => {
+                let entries = fs::read_dir(&cpu_dir).map_err(|e| format!("cannot read {}: {}", cpu_dir.display(), e))?;
+                let mut cpus: Vec<u32> = entries
+                    .filter_map(|entry| entry.ok())
+                    .filter_map(|entry| entry.file_name().to_str()?.strip_prefix("cpu")?.parse().ok())
+                    .collect();
+                cpus.sort_unstable();
+                cpus
+            }
+        };
+        if online.is_empty() {
+            return Err(format!("no CPUs listed in {}", cpu_dir.display()));
+        }
+        
+        let mut topology = CpuTopology::default();
+        let mut caches = BTreeSet::new();
+        for &id in &online {
+            let dir = cpu_dir.join(format!("cpu{}", id));
+            let attribute = |name: &str| self.read(&dir.join("topology").join(name)).and_then(|value| value.parse::<i64>().ok());
+            // Packages and cores are reported as -1 on some virtual machines
+            let package = attribute("physical_package_id").filter(|value| *value >= 0).unwrap_or(0) as u32;
+            let core = attribute("core_id").filter(|value| *value >= 0).unwrap_or(id as i64) as u32;
+            topology.cpus.push(LogicalCpu { id, package, core, numa_node: 0 });
+            
+            let Ok(entries) = fs::read_dir(dir.join("cache")) else {
+                continue;
+            };
+            for entry in entries.filter_map(|entry| entry.ok()) {
+                let index = entry.path();
+                if !entry.file_name().to_string_lossy().starts_with("index") {
+                    continue;
+                }
+                let level = self.read(&index.join("level")).and_then(|value| value.parse::<u8>().ok());
+                let kind = self.read(&index.join("type")).and_then(|value| CacheKind::from_sysfs(&value));
+                let (Some(level), Some(kind)) = (level, kind) else {
+                    continue;
+                };
+                let size_kb = self.read(&index.join("size")).and_then(|value| parse_cache_size(&value)).unwrap_or(0);
+                let cpus = self.read(&index.join("shared_cpu_list"))
+                    .and_then(|list| parse_cpu_list(&list).ok())
+                    .unwrap_or_else(|| vec![id]);
+                caches.insert((level, kind, cpus, size_kb));
+            }
+        }
+        topology.caches = caches.into_iter()
+            .map(|(level, kind, cpus, size_kb)| CacheInfo { level, kind, size_kb, cpus })
+            .collect();
+        if topology.caches.is_empty() {
+            topology.notes.push("no cache information in sysfs".to_string());
+        }
+        
+        let mut nodes = BTreeMap::new();
+        if let Ok(entries) = fs::read_dir(self.root.join("node")) {
+            for entry in entries.filter_map(|entry| entry.ok()) {
+                let Some(id) = entry.file_name().to_str().and_then(|name| name.strip_prefix("node")?.parse::<u32>().ok()) else {
+                    continue;
+                };
+                let cpus = self.read(&entry.path().join("cpulist"))
+                    .map(|list| parse_cpu_list(&list))
+                    .transpose()?
+                    .unwrap_or_default();
+                let memory_mb = fs::read_to_string(entry.path().join("meminfo")).ok()
+                    .and_then(|meminfo| parse_node_memory(&meminfo))
+                    .unwrap_or(0);
+                nodes.insert(id, NumaNode { id, cpus, memory_mb });
+            }
+        }
+        if nodes.is_empty() {
+            topology.notes.push("no NUMA information in sysfs, assuming a single node".to_string());
+            nodes.insert(0, NumaNode { id: 0, cpus: online.clone(), memory_mb: 0 });
+        }
+        for cpu in &mut topology.cpus {
+            if let Some(node) = nodes.values().find(|node| node.cpus.contains(&cpu.id)) {
+                cpu.numa_node = node.id;
+            }
+        }
+        topology.numa_nodes = nodes.into_values().collect();
+        topology.sort();
+        Ok(topology)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn write(root: &Path, path: &str, value: &str) {
+        let path = root.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, value).unwrap();
+    }
+    
+    #[test]
+    fn test_probe_sysfs_and_publish() {
+        // Two packages of two cores with two threads each, one NUMA node per package
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(root, "cpu/online", "0-7\n");
+        for id in 0..8u32 {
+            let (package, core) = (id / 4, (id % 4) / 2);
+            let siblings = format!("{}-{}", id - id % 2, id - id % 2 + 1);
+            let package_cpus = format!("{}-{}", package * 4, package * 4 + 3);
+            write(root, &format!("cpu/cpu{}/topology/physical_package_id", id), &package.to_string());
+            write(root, &format!("cpu/cpu{}/topology/core_id", id), &core.to_string());
+            for (index, level, kind, size, shared) in [
+                (0, "1", "Data", "32K", &siblings),
+                (1, "1", "Instruction", "32K", &siblings),
+                (2, "2", "Unified", "1024K", &siblings),
+                (3, "3", "Unified", "16M", &package_cpus),
+            ] {
+                let cache = format!("cpu/cpu{}/cache/index{}", id, index);
+                write(root, &format!("{}/level", cache), level);
+                write(root, &format!("{}/type", cache), kind);
+                write(root, &format!("{}/size", cache), size);
+                write(root, &format!("{}/shared_cpu_list", cache), shared);
+            }
+        }
+        write(root, "node/node0/cpulist", "0-3\n");
+        write(root, "node/node0/meminfo", "Node 0 MemTotal:       16318412 kB\n");
+        write(root, "node/node1/cpulist", "4-7\n");
+        
+        let topology = TopologyProbe::with_root(root).probe();
+        assert!(topology.notes.is_empty(), "{:?}", topology.notes);
+        assert_eq!(topology.packages(), vec![0, 1]);
+        assert_eq!(topology.cores(1), vec![0, 1]);
+        assert_eq!(topology.threads(1, 1), vec![6, 7]);
+        assert_eq!(topology.smt_rank(7), 1);
+        assert_eq!(topology.numa_node_of(5), Some(1));
+        assert_eq!(topology.numa_nodes[0].memory_mb, 15935);
+        assert_eq!(topology.caches.len(), 4 * 3 + 2);
+        assert_eq!(topology.last_level_cache(2).unwrap().size_kb, 16 * 1024);
+        assert_eq!(topology.shared_cache_level(0, 1), Some(1));
+        assert_eq!(topology.shared_cache_level(0, 2), Some(3));
+        assert_eq!(topology.shared_cache_level(0, 4), None);
+        assert_eq!(topology, {
+            let mut uniform = CpuTopology::uniform(2, 2, 2);
+            uniform.caches.iter_mut().filter(|cache| cache.level == 3).for_each(|cache| cache.size_kb = 16 * 1024);
+            uniform.numa_nodes[0].memory_mb = 15935;
+            uniform
+        });
+        
+        let tables = TablesManager::new();
+        tables.start();
+        // 2 nodes, 2 packages, 4 cores, 8 threads and 14 caches
+        assert_eq!(topology.publish(&tables).unwrap(), 30);
+        assert_eq!(topology.publish(&tables).unwrap(), 30);
+        let threads = tables.query_rows("resources", HashMap::from([("name".to_string(), "cpu5".to_string())])).unwrap();
+        assert_eq!(threads.len(), 1);
+        assert_eq!(threads[0].values["numa_node"], "1");
+        let cores = tables.query_rows("resources", HashMap::from([
+            ("resource_id".to_string(), threads[0].values["parent_id"].clone()),
+        ])).unwrap();
+        assert_eq!(cores[0].values["name"], "package1/core0");
+        
+        assert_eq!(parse_cpu_list("0-2, 5,7-8").unwrap(), vec![0, 1, 2, 5, 7, 8]);
+        assert!(parse_cpu_list("3-1").is_err());
+        assert!(parse_cpu_list("0-4294967295").is_err());
+    }
+    
+    #[test]
+    fn test_probe_falls_back_without_sysfs() {
+        let dir = tempfile::tempdir().unwrap();
+        let topology = TopologyProbe::with_root(dir.path().join("missing")).probe();
+        assert!(!topology.cpus.is_empty());
+        assert_eq!(topology.numa_nodes.len(), 1);
+        assert_eq!(topology.notes.len(), 1);
+    }
+}
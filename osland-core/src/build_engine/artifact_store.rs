@@ -0,0 +1,389 @@
+// Build artifact store for OSland build engine
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use super::{build_config::BuildConfig, BuildEngineError};
+
+/// Name of the index file kept at the root of the store
+const INDEX_FILE: &str = "artifacts.json";
+
+/// Build artifact metadata
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArtifactRecord {
+    /// Artifact ID (also the directory name inside the store)
+    pub id: String,
+    
+    /// Project name
+    pub project_name: String,
+    
+    /// Build profile (e.g. "Debug", "Release" or a user-defined name)
+    pub profile: String,
+    
+    /// SHA-256 of the serialized build configuration
+    pub config_hash: String,
+    
+    /// Creation timestamp
+    pub created_at: DateTime<Utc>,
+    
+    /// Artifact directory
+    pub path: PathBuf,
+    
+    /// Total size in bytes
+    pub size_bytes: u64,
+    
+    /// Pinned artifacts are never removed by `clean`
+    #[serde(default)]
+    pub pinned: bool,
+}
+
+/// Artifact retention policy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetentionPolicy {
+    /// Number of most recent artifacts to keep per profile
+    pub keep_last_per_profile: usize,
+    
+    /// Remove artifacts older than this many days (regardless of count)
+    pub max_age_days: Option<i64>,
+    
+    /// Remove the oldest artifacts until the store fits in this many bytes
+    pub max_total_bytes: Option<u64>,
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        Self {
+            keep_last_per_profile: 3,
+            max_age_days: None,
+            max_total_bytes: None,
+        }
+    }
+}
+
+/// Result of a clean operation
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CleanReport {
+    /// Removed (or, for a dry run, removable) artifacts
+    pub removed: Vec<ArtifactRecord>,
+    
+    /// Number of artifacts kept
+    pub kept: usize,
+    
+    /// Reclaimed space in bytes
+    pub reclaimed_bytes: u64,
+    
+    /// Whether this was a dry run
+    pub dry_run: bool,
+}
+
+/// Build artifact store
+///
+/// Every build output is copied into its own directory under the store root and
+/// described in a JSON index, so old builds can be found by configuration hash
+/// and garbage-collected according to a retention policy.
+pub struct ArtifactStore {
+    /// Store root directory
+    root: PathBuf,
+    
+    /// Artifact records, oldest first
+    records: Vec<ArtifactRecord>,
+}
+
+impl ArtifactStore {
+    /// Open (or create) an artifact store
+    pub fn open<P: AsRef<Path>>(root: P) -> Result<Self, BuildEngineError> {
+        let root = root.as_ref().to_path_buf();
+        fs::create_dir_all(&root)
+            .map_err(|e| BuildEngineError::BuildError(format!("Failed to create artifact store {}: {}", root.display(), e)))?;
+        
+        let index_path = root.join(INDEX_FILE);
+        let records = if index_path.exists() {
+            let content = fs::read_to_string(&index_path)
+                .map_err(|e| BuildEngineError::BuildError(format!("Failed to read artifact index: {}", e)))?;
+            serde_json::from_str(&content)
+                .map_err(|e| BuildEngineError::BuildError(format!("Failed to parse artifact index: {}", e)))?
+        } else {
+            Vec::new()
+        };
+        
+        Ok(Self { root, records })
+    }
+    
+    /// Get the store root
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+    
+    /// Get all artifact records, oldest first
+    pub fn records(&self) -> &[ArtifactRecord] {
+        &self.records
+    }
+    
+    /// Total size of all stored artifacts in bytes
+    pub fn total_size(&self) -> u64 {
+        self.records.iter().map(|r| r.size_bytes).sum()
+    }
+    
+    /// Find the most recent artifact built from an identical configuration
+    pub fn find_by_config_hash(&self, config_hash: &str) -> Option<&ArtifactRecord> {
+        self.records.iter().rev().find(|r| r.config_hash == config_hash)
+    }
+    
+    /// Store a build output directory (or file) as a new artifact
+    pub fn register(&mut self, config: &BuildConfig, profile: &str, output: &Path) -> Result<ArtifactRecord, BuildEngineError> {
+        let created_at = Utc::now();
+        let id = format!("{}-{}-{}", config.project_name, created_at.format("%Y%m%d%H%M%S"), &uuid::Uuid::new_v4().to_string()[..8]);
+        let path = self.root.join(&id);
+        
+        copy_recursive(output, &path)
+            .map_err(|e| BuildEngineError::BuildError(format!("Failed to store artifact {}: {}", output.display(), e)))?;
+        
+        let record = ArtifactRecord {
+            id,
+            project_name: config.project_name.clone(),
+            profile: profile.to_string(),
+            config_hash: config_hash(config)?,
+            created_at,
+            size_bytes: dir_size(&path),
+            path,
+            pinned: false,
+        };
+        
+        self.records.push(record.clone());
+        self.save_index()?;
+        Ok(record)
+    }
+    
+    /// Pin or unpin an artifact
+    pub fn set_pinned(&mut self, id: &str, pinned: bool) -> Result<(), BuildEngineError> {
+        let record = self.records.iter_mut().find(|r| r.id == id)
+            .ok_or_else(|| BuildEngineError::BuildError(format!("Artifact '{}' not found", id)))?;
+        record.pinned = pinned;
+        self.save_index()
+    }
+    
+    /// Apply a retention policy
+    ///
+    /// With `dry_run` set, nothing is deleted and the report lists what would be.
+    pub fn clean(&mut self, policy: &RetentionPolicy, dry_run: bool) -> Result<CleanReport, BuildEngineError> {
+        let now = Utc::now();
+        let mut remove = vec![false; self.records.len()];
+        
+        // Keep the last N per profile, newest first
+        let mut seen_per_profile: HashMap<&str, usize> = HashMap::new();
+        for (index, record) in self.records.iter().enumerate().rev() {
+            let seen = seen_per_profile.entry(record.profile.as_str()).or_insert(0);
+            *seen += 1;
+            if *seen > policy.keep_last_per_profile {
+                remove[index] = true;
+            }
+            
+            if let Some(max_age_days) = policy.max_age_days {
+                if now - record.created_at > Duration::days(max_age_days) {
+                    remove[index] = true;
+                }
+            }
+        }
+        
+        // Then drop the oldest remaining artifacts until the size budget is met
+        if let Some(max_total_bytes) = policy.max_total_bytes {
+            let mut total: u64 = self.records.iter().zip(&remove)
+                .filter(|(_, removed)| !**removed)
+                .map(|(r, _)| r.size_bytes)
+                .sum();
+            for (index, record) in self.records.iter().enumerate() {
+                if total <= max_total_bytes {
+                    break;
+                }
+                if !remove[index] && !record.pinned {
+                    remove[index] = true;
+                    total -= record.size_bytes;
+                }
+            }
+        }
+        
+        let mut report = CleanReport { dry_run, ..Default::default() };
+        let mut kept = Vec::new();
+        for (record, removed) in self.records.drain(..).zip(remove) {
+            if removed && !record.pinned {
+                report.reclaimed_bytes += record.size_bytes;
+                report.removed.push(record);
+            } else {
+                kept.push(record);
+            }
+        }
+        report.kept = kept.len();
+        
+        if dry_run {
+            // Restore the original order
+            kept.extend(report.removed.iter().cloned());
+            kept.sort_by_key(|r| r.created_at);
+            self.records = kept;
+            return Ok(report);
+        }
+        
+        self.records = kept;
+        for record in &report.removed {
+            let result = if record.path.is_dir() { fs::remove_dir_all(&record.path) } else { fs::remove_file(&record.path) };
+            if let Err(e) = result {
+                if e.kind() != std::io::ErrorKind::NotFound {
+                    return Err(BuildEngineError::BuildError(format!("Failed to remove artifact {}: {}", record.id, e)));
+                }
+            }
+        }
+        self.save_index()?;
+        
+        Ok(report)
+    }
+    
+    /// Write the index file
+    fn save_index(&self) -> Result<(), BuildEngineError> {
+        let json = serde_json::to_string_pretty(&self.records)
+            .map_err(|e| BuildEngineError::BuildError(format!("Failed to serialize artifact index: {}", e)))?;
+        fs::write(self.root.join(INDEX_FILE), json)
+            .map_err(|e| BuildEngineError::BuildError(format!("Failed to write artifact index: {}", e)))
+    }
+}
+
+/// Compute the SHA-256 hash of a build configuration
+pub fn config_hash(config: &BuildConfig) -> Result<String, BuildEngineError> {
+    let bytes = serde_json::to_vec(config)
+        .map_err(|e| BuildEngineError::ConfigError(format!("Failed to serialize build configuration: {}", e)))?;
+    Ok(Sha256::digest(&bytes).iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+/// Format a byte count for display
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 { format!("{} {}", bytes, UNITS[0]) } else { format!("{:.1} {}", size, UNITS[unit]) }
+}
+
+fn copy_recursive(source: &Path, dest: &Path) -> std::io::Result<()> {
+    if source.is_dir() {
+        fs::create_dir_all(dest)?;
+        for entry in fs::read_dir(source)? {
+            let entry = entry?;
+            copy_recursive(&entry.path(), &dest.join(entry.file_name()))?;
+        }
+    } else {
+        fs::create_dir_all(dest)?;
+        let name = source.file_name().ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidInput, "Output has no file name"))?;
+        fs::copy(source, dest.join(name))?;
+    }
+    Ok(())
+}
+
+fn dir_size(path: &Path) -> u64 {
+    match fs::symlink_metadata(path) {
+        Ok(meta) if meta.is_dir() => fs::read_dir(path)
+            .map(|entries| entries.filter_map(|e| e.ok()).map(|e| dir_size(&e.path())).sum())
+            .unwrap_or(0),
+        Ok(meta) => meta.len(),
+        Err(_) => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    /// Store holding artifacts `(id, profile, size)`, oldest first and a minute apart
+    fn store(root: &Path, artifacts: &[(&str, &str, usize)]) -> ArtifactStore {
+        let mut store = ArtifactStore::open(root).unwrap();
+        let start = Utc::now() - Duration::minutes(artifacts.len() as i64);
+        for (index, (id, profile, size)) in artifacts.iter().enumerate() {
+            let path = root.join(id);
+            fs::create_dir_all(&path).unwrap();
+            fs::write(path.join("image.bin"), vec![0u8; *size]).unwrap();
+            store.records.push(ArtifactRecord {
+                id: id.to_string(),
+                project_name: "demo".to_string(),
+                profile: profile.to_string(),
+                config_hash: String::new(),
+                created_at: start + Duration::minutes(index as i64),
+                size_bytes: dir_size(&path),
+                path,
+                pinned: false,
+            });
+        }
+        store.save_index().unwrap();
+        store
+    }
+    
+    fn ids(records: &[ArtifactRecord]) -> Vec<&str> {
+        records.iter().map(|record| record.id.as_str()).collect()
+    }
+    
+    #[test]
+    fn test_keep_last_per_profile() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = store(dir.path(), &[
+            ("debug-1", "Debug", 10),
+            ("release-1", "Release", 100),
+            ("debug-2", "Debug", 20),
+            ("release-2", "Release", 200),
+            ("debug-3", "Debug", 30),
+            ("release-3", "Release", 300),
+            ("debug-4", "Debug", 40),
+        ]);
+        store.set_pinned("debug-1", true).unwrap();
+        let policy = RetentionPolicy { keep_last_per_profile: 2, ..Default::default() };
+        
+        // Profiles are counted separately from the newest artifact back; pinned ones stay
+        let report = store.clean(&policy, true).unwrap();
+        assert_eq!(ids(&report.removed), vec!["release-1", "debug-2"]);
+        assert_eq!(report.kept, 5);
+        assert_eq!(store.records().len(), 7);
+        assert!(dir.path().join("debug-2").exists());
+        
+        let report = store.clean(&policy, false).unwrap();
+        assert_eq!(ids(&report.removed), vec!["release-1", "debug-2"]);
+        assert_eq!(ids(store.records()), vec!["debug-1", "release-2", "debug-3", "release-3", "debug-4"]);
+        assert!(!dir.path().join("release-1").exists());
+        assert!(!dir.path().join("debug-2").exists());
+        
+        // The index on disk matches
+        let reopened = ArtifactStore::open(dir.path()).unwrap();
+        assert_eq!(ids(reopened.records()), ids(store.records()));
+        assert!(reopened.records()[0].pinned);
+    }
+    
+    #[test]
+    fn test_reclaimed_bytes() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = store(dir.path(), &[
+            ("a", "Debug", 1000),
+            ("b", "Debug", 2000),
+            ("c", "Release", 4000),
+            ("d", "Debug", 8000),
+        ]);
+        assert_eq!(store.total_size(), 15000);
+        
+        // Keeping the last two Debug builds drops "a", then the oldest remaining ones go until 9000 bytes fit
+        let policy = RetentionPolicy { keep_last_per_profile: 2, max_age_days: None, max_total_bytes: Some(9000) };
+        let dry_run = store.clean(&policy, true).unwrap();
+        assert_eq!(ids(&dry_run.removed), vec!["a", "b", "c"]);
+        assert_eq!(dry_run.reclaimed_bytes, 7000);
+        assert_eq!(ids(store.records()), vec!["a", "b", "c", "d"]);
+        assert_eq!(store.total_size(), 15000);
+        
+        // Pinned artifacts are neither removed nor counted as freed
+        store.set_pinned("b", true).unwrap();
+        let report = store.clean(&policy, false).unwrap();
+        assert_eq!(ids(&report.removed), vec!["a", "c", "d"]);
+        assert_eq!(report.reclaimed_bytes, 13000);
+        assert_eq!(store.total_size(), 2000);
+        assert!(dir.path().join("b").exists() && !dir.path().join("d").exists());
+    }
+}
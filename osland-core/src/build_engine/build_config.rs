@@ -0,0 +1,1121 @@
+// Build configuration for OSland build engine
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use crate::core::architecture::KernelArchitecture;
+use crate::os_design::virtualization::{VirtualizationMode, VirtualizationProfile};
+use super::build_hooks::HookConfig;
+use super::license_report::LicensePolicy;
+use super::test_orchestrator::{QemuConfig, TestPlan};
+
+/// Toolchain type (GNU, LLVM/Clang, etc.)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ToolchainType {
+    /// GNU Toolchain (gcc, g++, etc.)
+    GNU,
+    /// LLVM/Clang Toolchain (clang, clang++, etc.)
+    LLVM,
+    /// Custom toolchain
+    Custom,
+}
+
+/// Toolchain configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolchainConfig {
+    /// Toolchain type
+    pub toolchain_type: ToolchainType,
+    
+    /// Toolchain path (optional, defaults to PATH)
+    pub toolchain_path: Option<PathBuf>,
+    
+    /// C compiler executable
+    pub c_compiler: String,
+    
+    /// C++ compiler executable
+    pub cpp_compiler: String,
+    
+    /// Assembler executable
+    pub assembler: String,
+    
+    /// Linker executable
+    pub linker: String,
+    
+    /// Strip executable
+    pub strip: String,
+    
+    /// Objcopy executable
+    pub objcopy: String,
+    
+    /// Objdump executable
+    pub objdump: String,
+}
+
+/// Build configuration for OSland
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BuildConfig {
+    /// Project name
+    pub project_name: String,
+    
+    /// Project version
+    pub project_version: String,
+    
+    /// Output directory for build artifacts
+    pub output_dir: PathBuf,
+    
+    /// Target architecture
+    pub architecture: KernelArchitecture,
+    
+    /// Build mode (debug or release)
+    pub build_mode: BuildMode,
+    
+    /// Toolchain configuration
+    pub toolchain_config: ToolchainConfig,
+    
+    /// Kernel configuration
+    pub kernel_config: KernelConfig,
+    
+    /// Root filesystem configuration
+    pub rootfs_config: RootfsConfig,
+    
+    /// Bootloader configuration
+    pub bootloader_config: BootloaderConfig,
+    
+    /// Build steps to execute
+    pub build_steps: Vec<BuildStep>,
+    
+    /// Custom build commands
+    pub custom_commands: Vec<CustomCommand>,
+    
+    /// Compiler flags
+    pub compiler_flags: Vec<String>,
+    
+    /// Linker flags
+    pub linker_flags: Vec<String>,
+    
+    /// Reproducible build settings
+    #[serde(default)]
+    pub reproducible: Option<ReproducibleConfig>,
+    
+    /// Image signing settings
+    #[serde(default)]
+    pub signing: Option<SigningConfig>,
+    
+    /// Pre-step, post-step and failure hooks
+    #[serde(default)]
+    pub hooks: Vec<HookConfig>,
+    
+    /// Container the build commands run in (None to run on the host)
+    #[serde(default)]
+    pub container: Option<ContainerConfig>,
+}
+
+/// Container runtime
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ContainerRuntime {
+    Docker,
+    Podman,
+}
+
+/// Additional container volume
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VolumeMapping {
+    /// Host path
+    pub host: PathBuf,
+    
+    /// Path inside the container
+    pub container: String,
+    
+    /// Mount read-only
+    #[serde(default)]
+    pub read_only: bool,
+}
+
+/// Containerized build environment
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContainerConfig {
+    /// Container runtime
+    pub runtime: ContainerRuntime,
+    
+    /// Container image (pin with a digest, e.g. `osland/toolchain@sha256:...`)
+    pub image: String,
+    
+    /// Additional volumes (source and output directories are always mounted)
+    #[serde(default)]
+    pub volumes: Vec<VolumeMapping>,
+    
+    /// Working directory inside the container
+    #[serde(default)]
+    pub workdir: Option<String>,
+    
+    /// User to run as (e.g. `1000:1000`)
+    #[serde(default)]
+    pub user: Option<String>,
+    
+    /// Environment variables
+    #[serde(default)]
+    pub env: Vec<(String, String)>,
+    
+    /// Extra runtime arguments
+    #[serde(default)]
+    pub extra_args: Vec<String>,
+}
+
+/// Reproducible build settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReproducibleConfig {
+    /// Fixed SOURCE_DATE_EPOCH (defaults to the last commit of the kernel source)
+    #[serde(default)]
+    pub source_date_epoch: Option<i64>,
+    
+    /// Locale used by build commands
+    #[serde(default = "default_locale")]
+    pub locale: String,
+    
+    /// Time zone used by build commands
+    #[serde(default = "default_timezone")]
+    pub timezone: String,
+    
+    /// File creation mask
+    #[serde(default = "default_umask")]
+    pub umask: u32,
+    
+    /// Also pack the output directory into a sorted `<output_dir>.tar`
+    #[serde(default)]
+    pub archive: bool,
+}
+
+fn default_locale() -> String {
+    "C".to_string()
+}
+
+fn default_timezone() -> String {
+    "UTC".to_string()
+}
+
+fn default_umask() -> u32 {
+    0o022
+}
+
+impl Default for ReproducibleConfig {
+    fn default() -> Self {
+        Self {
+            source_date_epoch: None,
+            locale: default_locale(),
+            timezone: default_timezone(),
+            umask: default_umask(),
+            archive: false,
+        }
+    }
+}
+
+/// Image signature scheme
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum SignatureScheme {
+    /// Detached Ed25519 signature (`<image>.sig`)
+    Ed25519,
+    
+    /// Detached armored GPG signature (`<image>.asc`)
+    Gpg,
+}
+
+/// Image signing settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SigningConfig {
+    /// Signature scheme
+    pub scheme: SignatureScheme,
+    
+    /// Ed25519 secret key file (hex seed)
+    #[serde(default)]
+    pub key_path: Option<PathBuf>,
+    
+    /// Project vault secret holding the Ed25519 hex seed, used instead of `key_path`
+    #[serde(default)]
+    pub key_secret: Option<String>,
+    
+    /// Project directory of the vault (defaults to the current directory)
+    #[serde(default)]
+    pub vault_dir: Option<PathBuf>,
+    
+    /// GPG key ID (defaults to the GPG default key)
+    #[serde(default)]
+    pub key_id: Option<String>,
+}
+
+/// Build mode (debug or release)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum BuildMode {
+    Debug,
+    Release,
+}
+
+/// Kernel configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KernelConfig {
+    /// Kernel name
+    pub kernel_name: String,
+    
+    /// Kernel version
+    pub kernel_version: String,
+    
+    /// Kernel source path
+    pub source_path: PathBuf,
+    
+    /// Kernel configuration file path
+    pub config_file: Option<PathBuf>,
+    
+    /// Kernel features to enable
+    pub features: Vec<String>,
+    
+    /// Kernel modules to include
+    pub modules: Vec<String>,
+    
+    /// Where to download the kernel source from (None to use `source_path` as is)
+    #[serde(default)]
+    pub download: Option<DownloadConfig>,
+}
+
+/// Device tree step configuration (the `config` of a `GenerateDeviceTree` step)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeviceTreeConfig {
+    /// Tile graph file holding the hardware canvas
+    pub graph_file: PathBuf,
+    
+    /// Target hardware architecture (`aarch64` or `riscv64`)
+    pub arch: String,
+    
+    /// Board model
+    #[serde(default = "default_board_model")]
+    pub model: String,
+    
+    /// Output base name (`<name>.dts` / `<name>.dtb` in the output directory)
+    #[serde(default = "default_device_tree_name")]
+    pub output: String,
+}
+
+fn default_board_model() -> String {
+    "OSland Virtual Board".to_string()
+}
+
+fn default_device_tree_name() -> String {
+    "board".to_string()
+}
+
+/// Module ABI check configuration (the `config` of a `CheckModuleAbi` step)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModuleAbiConfig {
+    /// Target kernel System.map (defaults to `System.map` in the kernel source)
+    #[serde(default)]
+    pub system_map: Option<PathBuf>,
+    
+    /// Target kernel Module.symvers (defaults to `Module.symvers` in the kernel source)
+    #[serde(default)]
+    pub module_symvers: Option<PathBuf>,
+    
+    /// Target vmlinux, read with `nm` when no System.map is available
+    #[serde(default)]
+    pub vmlinux: Option<PathBuf>,
+    
+    /// Built modules (`.ko`) or extracted component directories to check
+    pub modules: Vec<PathBuf>,
+    
+    /// Only warn instead of failing the build
+    #[serde(default)]
+    pub warn_only: bool,
+}
+
+/// Syscall generation configuration (the `config` of a `GenerateSyscalls` step)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyscallConfig {
+    /// Syscall table file saved by the syscall designer
+    pub table_file: PathBuf,
+    
+    /// Language of the kernel dispatch table (`c` or `rust`)
+    #[serde(default = "default_syscall_kernel_language")]
+    pub kernel_language: String,
+    
+    /// Languages of the userland stub libraries
+    #[serde(default)]
+    pub stub_languages: Vec<String>,
+    
+    /// Subdirectory of the output directory receiving the artifacts
+    #[serde(default = "default_syscall_output")]
+    pub output: String,
+}
+
+fn default_syscall_kernel_language() -> String {
+    "c".to_string()
+}
+
+fn default_syscall_output() -> String {
+    "syscalls".to_string()
+}
+
+/// Scheduler configuration step (the `config` of a `GenerateSchedulerConfig` step)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchedulerStepConfig {
+    /// Scheduler design file saved by the scheduler designer
+    pub design_file: PathBuf,
+    
+    /// Subdirectory of the output directory receiving the configuration
+    #[serde(default = "default_scheduler_output")]
+    pub output: String,
+}
+
+fn default_scheduler_output() -> String {
+    "scheduler".to_string()
+}
+
+/// Security policy step configuration (the `config` of a `GenerateSecurityPolicy` step)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecurityPolicyConfig {
+    /// Canvas file holding the security policy nodes
+    pub canvas_file: PathBuf,
+    
+    /// Policy format (`apparmor` or `selinux`)
+    #[serde(default = "default_policy_format")]
+    pub format: String,
+    
+    /// Subdirectory of the output directory receiving the rootfs overlay
+    #[serde(default = "default_policy_output")]
+    pub output: String,
+}
+
+fn default_policy_format() -> String {
+    "apparmor".to_string()
+}
+
+fn default_policy_output() -> String {
+    "security".to_string()
+}
+
+/// Boot flow step configuration (the `config` of a `GenerateBootFlow` step)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BootFlowConfig {
+    /// Canvas file holding the boot stage nodes
+    pub canvas_file: PathBuf,
+    
+    /// Subdirectory of the output directory receiving the bootloader entry and init scripts
+    #[serde(default = "default_boot_flow_output")]
+    pub output: String,
+}
+
+fn default_boot_flow_output() -> String {
+    "boot".to_string()
+}
+
+/// Hypervisor image step configuration (the `config` of a `CreateHypervisorImage` step)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HypervisorImageConfig {
+    /// Role of the OS (`hypervisor` or `guest`)
+    #[serde(default = "default_virtualization_mode")]
+    pub mode: String,
+    
+    /// CPU virtualization extension (`vmx` or `svm`)
+    #[serde(default = "default_virt_extension")]
+    pub extension: String,
+    
+    /// Expose the extension to the hypervisor's guests
+    #[serde(default)]
+    pub nested: bool,
+    
+    /// Virtio devices by name (defaults to the virtio tiles of `tile_graph`)
+    #[serde(default)]
+    pub devices: Vec<String>,
+    
+    /// Tile graph holding the virtio device tiles
+    #[serde(default)]
+    pub tile_graph: Option<PathBuf>,
+    
+    /// UEFI firmware image (e.g. OVMF.fd)
+    pub firmware: PathBuf,
+    
+    /// EFI stub kernel (defaults to the bzImage of the kernel source)
+    #[serde(default)]
+    pub kernel: Option<PathBuf>,
+    
+    /// Initramfs of the kernel
+    #[serde(default)]
+    pub initrd: Option<PathBuf>,
+    
+    /// Guest kernel shipped for the hypervisor to launch
+    #[serde(default)]
+    pub guest_kernel: Option<PathBuf>,
+    
+    /// Initramfs of the guest kernel
+    #[serde(default)]
+    pub guest_initrd: Option<PathBuf>,
+    
+    /// Size of the FAT image in MiB
+    #[serde(default = "default_hypervisor_image_size")]
+    pub image_size_mb: u32,
+    
+    /// Subdirectory of the output directory receiving the firmware and image
+    #[serde(default = "default_hypervisor_output")]
+    pub output: String,
+}
+
+fn default_virtualization_mode() -> String {
+    "hypervisor".to_string()
+}
+
+fn default_virt_extension() -> String {
+    "vmx".to_string()
+}
+
+fn default_hypervisor_image_size() -> u32 {
+    64
+}
+
+fn default_hypervisor_output() -> String {
+    "hypervisor".to_string()
+}
+
+/// Filesystem image step configuration (the `config` of a `CreateFilesystemImage` step)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FilesystemImageConfig {
+    /// Canvas file holding the filesystem nodes
+    pub canvas_file: PathBuf,
+    
+    /// Image file name
+    #[serde(default = "default_filesystem_image")]
+    pub image: String,
+    
+    /// Subdirectory of the output directory receiving the image and generated files
+    #[serde(default = "default_filesystem_output")]
+    pub output: String,
+    
+    /// QEMU configuration booting a kernel that mounts the image (None to skip the mount test)
+    #[serde(default)]
+    pub mount_test: Option<QemuConfig>,
+}
+
+fn default_filesystem_image() -> String {
+    "filesystem.img".to_string()
+}
+
+fn default_filesystem_output() -> String {
+    "filesystem".to_string()
+}
+
+/// License report step configuration (the `config` of a `GenerateLicenseReport` step)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LicenseReportConfig {
+    /// Canvas file holding the image's components (defaults to the canvas being built)
+    #[serde(default)]
+    pub canvas_file: Option<PathBuf>,
+    
+    /// Component databases of extracted components
+    #[serde(default)]
+    pub component_databases: Vec<PathBuf>,
+    
+    /// Tile libraries the image's tiles come from
+    #[serde(default)]
+    pub tile_libraries: Vec<PathBuf>,
+    
+    /// Subdirectories of the output directory holding generated sources (empty for all of it)
+    #[serde(default)]
+    pub generated: Vec<String>,
+    
+    /// Licenses allowed in the image
+    #[serde(default)]
+    pub policy: LicensePolicy,
+    
+    /// Report policy violations without failing the build
+    #[serde(default)]
+    pub warn_only: bool,
+    
+    /// Subdirectory of the output directory receiving NOTICE and licenses.json
+    #[serde(default = "default_license_output")]
+    pub output: String,
+}
+
+fn default_license_output() -> String {
+    "licenses".to_string()
+}
+
+/// Kind of a build output pushed to an OCI registry
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum OciArtifactKind {
+    /// Bootable disk or firmware image
+    Image,
+    
+    /// Root filesystem archive or image
+    Rootfs,
+    
+    /// Software bill of materials (SPDX, CycloneDX or the license report)
+    Sbom,
+}
+
+/// Build output pushed as a layer of the OCI artifact
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OciArtifact {
+    /// Kind of output, selecting the default media type
+    pub kind: OciArtifactKind,
+    
+    /// File, relative to the output directory
+    pub path: PathBuf,
+    
+    /// Media type overriding the default of the kind
+    #[serde(default)]
+    pub media_type: Option<String>,
+}
+
+/// OCI publish step configuration (the `config` of a `PublishArtifacts` step)
+///
+/// The repository and tags are templates expanding `{project}`, `{profile}`,
+/// `{version}` and `{date}` (`YYYYMMDD`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OciPublishConfig {
+    /// Registry host, e.g. `ghcr.io` or `localhost:5000`
+    pub registry: String,
+    
+    /// Repository template, e.g. `acme/{project}`
+    pub repository: String,
+    
+    /// Tag templates; every tag points at the same manifest
+    #[serde(default = "default_publish_tags")]
+    pub tags: Vec<String>,
+    
+    /// Outputs pushed as layers of the artifact
+    pub artifacts: Vec<OciArtifact>,
+    
+    /// Profile used in templates (defaults to the build mode)
+    #[serde(default)]
+    pub profile: Option<String>,
+    
+    /// Talk plain HTTP to the registry (local test registries only)
+    #[serde(default)]
+    pub insecure: bool,
+}
+
+fn default_publish_tags() -> Vec<String> {
+    vec!["{version}-{profile}".to_string()]
+}
+
+/// Source download configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DownloadConfig {
+    /// Mirror URLs, tried in order
+    pub urls: Vec<String>,
+    
+    /// Expected SHA-256 of the downloaded file
+    #[serde(default)]
+    pub sha256: Option<String>,
+    
+    /// File name in the download cache (defaults to the last URL segment)
+    #[serde(default)]
+    pub file_name: Option<String>,
+    
+    /// Leading path components stripped when extracting the archive
+    #[serde(default = "default_strip_components")]
+    pub strip_components: u32,
+}
+
+fn default_strip_components() -> u32 {
+    1
+}
+
+/// Root filesystem configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RootfsConfig {
+    /// Root filesystem type (initramfs, ext2, etc.)
+    pub fs_type: String,
+    
+    /// Root filesystem source directory
+    pub source_dir: Option<PathBuf>,
+    
+    /// Root filesystem image path
+    pub image_path: PathBuf,
+    
+    /// Root filesystem size in bytes
+    pub size: Option<u64>,
+    
+    /// Files to copy to root filesystem
+    pub files: Vec<RootfsFile>,
+    
+    /// Directories to create in root filesystem
+    pub directories: Vec<RootfsDirectory>,
+    
+    /// Permissions to set
+    pub permissions: Vec<RootfsPermission>,
+}
+
+/// Root filesystem file specification
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RootfsFile {
+    /// Source path on host
+    pub source: PathBuf,
+    
+    /// Destination path in root filesystem
+    pub destination: PathBuf,
+    
+    /// File permissions (octal)
+    pub permissions: Option<u32>,
+}
+
+/// Root filesystem directory specification
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RootfsDirectory {
+    /// Path in root filesystem
+    pub path: PathBuf,
+    
+    /// Directory permissions (octal)
+    pub permissions: Option<u32>,
+}
+
+/// Root filesystem permission specification
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RootfsPermission {
+    /// Path in root filesystem
+    pub path: PathBuf,
+    
+    /// Permissions (octal)
+    pub permissions: u32,
+}
+
+/// Bootloader configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BootloaderConfig {
+    /// Bootloader type (grub, u-boot, etc.)
+    pub bootloader_type: String,
+    
+    /// Bootloader configuration file
+    pub config_file: Option<PathBuf>,
+    
+    /// Bootloader installation directory
+    pub install_dir: PathBuf,
+    
+    /// Bootloader kernel parameters
+    pub kernel_params: Vec<String>,
+    
+    /// Bootloader timeout in seconds
+    pub timeout: u32,
+}
+
+/// Build step definition
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BuildStep {
+    /// Step name
+    pub name: String,
+    
+    /// Step type
+    pub step_type: BuildStepType,
+    
+    /// Whether this step is enabled
+    pub enabled: bool,
+    
+    /// Step-specific configuration
+    pub config: serde_json::Value,
+    
+    /// Dependencies on other steps
+    pub dependencies: Vec<String>,
+    
+    /// Timeout in seconds
+    pub timeout: Option<u32>,
+}
+
+/// Build step types
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum BuildStepType {
+    /// Download kernel source
+    DownloadKernel,
+    
+    /// Configure kernel
+    ConfigureKernel,
+    
+    /// Build kernel
+    BuildKernel,
+    
+    /// Build kernel modules
+    BuildKernelModules,
+    
+    /// Create root filesystem
+    CreateRootfs,
+    
+    /// Create a cpio initramfs from a manifest
+    CreateInitramfs,
+    
+    /// Generate and compile a device tree from the hardware canvas
+    GenerateDeviceTree,
+    
+    /// Check extracted modules against the target kernel's exported symbols
+    CheckModuleAbi,
+    
+    /// Generate the syscall dispatch table and userland stubs from a syscall table
+    GenerateSyscalls,
+    
+    /// Generate the scheduler configuration from a scheduler design
+    GenerateSchedulerConfig,
+    
+    /// Generate security policy files from the canvas as a rootfs overlay
+    GenerateSecurityPolicy,
+    
+    /// Validate the boot flow of the canvas and generate its bootloader entry and init scripts
+    GenerateBootFlow,
+    
+    /// Create a firmware plus hypervisor (or guest) boot image
+    CreateHypervisorImage,
+    
+    /// Create a filesystem image from the canvas and mount it in QEMU
+    CreateFilesystemImage,
+    
+    /// Generate the NOTICE file of the image and enforce the license policy
+    GenerateLicenseReport,
+    
+    /// Push images, rootfs archives and SBOMs to an OCI registry
+    PublishArtifacts,
+    
+    /// Install bootloader
+    InstallBootloader,
+    
+    /// Create disk image
+    CreateDiskImage,
+    
+    /// Run tests
+    RunTests,
+    
+    /// Custom build step
+    Custom,
+}
+
+/// Custom build command
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomCommand {
+    /// Command name
+    pub name: String,
+    
+    /// Command to execute
+    pub command: String,
+    
+    /// Command arguments
+    pub args: Vec<String>,
+    
+    /// Working directory for command
+    pub working_dir: Option<PathBuf>,
+    
+    /// Environment variables
+    pub env: Vec<(String, String)>,
+    
+    /// Whether to continue on failure
+    pub continue_on_failure: bool,
+}
+
+impl ToolchainConfig {
+    /// Create a default GNU Toolchain configuration
+    pub fn default_gnu(architecture: &KernelArchitecture) -> Self {
+        // Get architecture-specific prefix
+        let prefix = match architecture {
+            KernelArchitecture::X86 => "",
+            KernelArchitecture::X86_64 => "",
+            KernelArchitecture::ArmV7 => "arm-linux-gnueabi-",
+            KernelArchitecture::ArmV8 => "aarch64-linux-gnu-",
+            KernelArchitecture::RiscV32 => "riscv32-linux-gnu-",
+            KernelArchitecture::RiscV64 => "riscv64-linux-gnu-",
+            _ => "",
+        };
+        
+        Self {
+            toolchain_type: ToolchainType::GNU,
+            toolchain_path: None,
+            c_compiler: format!("{}gcc", prefix),
+            cpp_compiler: format!("{}g++", prefix),
+            assembler: format!("{}as", prefix),
+            linker: format!("{}ld", prefix),
+            strip: format!("{}strip", prefix),
+            objcopy: format!("{}objcopy", prefix),
+            objdump: format!("{}objdump", prefix),
+        }
+    }
+    
+    /// Create a default LLVM/Clang Toolchain configuration
+    pub fn default_llvm(architecture: &KernelArchitecture) -> Self {
+        // Get architecture-specific target triple
+        let target_triple = match architecture {
+            KernelArchitecture::X86 => "i386-pc-linux-gnu",
+            KernelArchitecture::X86_64 => "x86_64-pc-linux-gnu",
+            KernelArchitecture::ArmV7 => "armv7-linux-gnueabihf",
+            KernelArchitecture::ArmV8 => "aarch64-linux-gnu",
+            KernelArchitecture::RiscV32 => "riscv32-unknown-linux-gnu",
+            KernelArchitecture::RiscV64 => "riscv64-unknown-linux-gnu",
+            _ => "x86_64-pc-linux-gnu",
+        };
+        
+        Self {
+            toolchain_type: ToolchainType::LLVM,
+            toolchain_path: None,
+            c_compiler: format!("clang --target={}", target_triple),
+            cpp_compiler: format!("clang++ --target={}", target_triple),
+            assembler: "llvm-as".to_string(),
+            linker: "lld".to_string(),
+            strip: "llvm-strip".to_string(),
+            objcopy: "llvm-objcopy".to_string(),
+            objdump: "llvm-objdump".to_string(),
+        }
+    }
+    
+    /// Create a custom Toolchain configuration
+    pub fn custom(c_compiler: String, cpp_compiler: String, assembler: String, linker: String, strip: String, objcopy: String, objdump: String) -> Self {
+        Self {
+            toolchain_type: ToolchainType::Custom,
+            toolchain_path: None,
+            c_compiler,
+            cpp_compiler,
+            assembler,
+            linker,
+            strip,
+            objcopy,
+            objdump,
+        }
+    }
+}
+
+impl BuildConfig {
+    /// Create a default build configuration
+    pub fn default(architecture: KernelArchitecture) -> Self {
+        let toolchain_config = ToolchainConfig::default_gnu(&architecture);
+        
+        Self {
+            project_name: "osland-project".to_string(),
+            project_version: "0.1.0".to_string(),
+            output_dir: PathBuf::from("build"),
+            architecture,
+            build_mode: BuildMode::Debug,
+            toolchain_config,
+            kernel_config: KernelConfig {
+                kernel_name: "linux".to_string(),
+                kernel_version: "6.1".to_string(),
+                source_path: PathBuf::from("kernel"),
+                config_file: None,
+                features: vec!["ext4", "vfat", "usb", "network"].into_iter().map(|s| s.to_string()).collect(),
+                modules: vec![].into_iter().map(|s| s.to_string()).collect(),
+                download: None,
+            },
+            rootfs_config: RootfsConfig {
+                fs_type: "ext2".to_string(),
+                source_dir: None,
+                image_path: PathBuf::from("rootfs.ext2"),
+                size: Some(32 * 1024 * 1024), // 32MB
+                files: vec![],
+                directories: vec![
+                    RootfsDirectory {
+                        path: PathBuf::from("/bin"),
+                        permissions: Some(0o755),
+                    },
+                    RootfsDirectory {
+                        path: PathBuf::from("/sbin"),
+                        permissions: Some(0o755),
+                    },
+                    RootfsDirectory {
+                        path: PathBuf::from("/lib"),
+                        permissions: Some(0o755),
+                    },
+                    RootfsDirectory {
+                        path: PathBuf::from("/etc"),
+                        permissions: Some(0o755),
+                    },
+                    RootfsDirectory {
+                        path: PathBuf::from("/home"),
+                        permissions: Some(0o755),
+                    },
+                    RootfsDirectory {
+                        path: PathBuf::from("/proc"),
+                        permissions: Some(0o555),
+                    },
+                    RootfsDirectory {
+                        path: PathBuf::from("/sys"),
+                        permissions: Some(0o555),
+                    },
+                    RootfsDirectory {
+                        path: PathBuf::from("/dev"),
+                        permissions: Some(0o755),
+                    },
+                ],
+                permissions: vec![],
+            },
+            bootloader_config: BootloaderConfig {
+                bootloader_type: "grub".to_string(),
+                config_file: None,
+                install_dir: PathBuf::from("boot"),
+                kernel_params: vec!["ro", "quiet", "console=ttyS0"].into_iter().map(|s| s.to_string()).collect(),
+                timeout: 5,
+            },
+            build_steps: vec![
+                BuildStep {
+                    name: "download_kernel".to_string(),
+                    step_type: BuildStepType::DownloadKernel,
+                    enabled: true,
+                    config: serde_json::json!({}),
+                    dependencies: vec![],
+                    timeout: None,
+                },
+                BuildStep {
+                    name: "configure_kernel".to_string(),
+                    step_type: BuildStepType::ConfigureKernel,
+                    enabled: true,
+                    config: serde_json::json!({}),
+                    dependencies: vec!["download_kernel"],
+                    timeout: None,
+                },
+                BuildStep {
+                    name: "build_kernel".to_string(),
+                    step_type: BuildStepType::BuildKernel,
+                    enabled: true,
+                    config: serde_json::json!({}),
+                    dependencies: vec!["configure_kernel"],
+                    timeout: None,
+                },
+                BuildStep {
+                    name: "build_kernel_modules".to_string(),
+                    step_type: BuildStepType::BuildKernelModules,
+                    enabled: true,
+                    config: serde_json::json!({}),
+                    dependencies: vec!["build_kernel"],
+                    timeout: None,
+                },
+                BuildStep {
+                    name: "create_rootfs".to_string(),
+                    step_type: BuildStepType::CreateRootfs,
+                    enabled: true,
+                    config: serde_json::json!({}),
+                    dependencies: vec!["build_kernel_modules"],
+                    timeout: None,
+                },
+                BuildStep {
+                    name: "install_bootloader".to_string(),
+                    step_type: BuildStepType::InstallBootloader,
+                    enabled: true,
+                    config: serde_json::json!({}),
+                    dependencies: vec!["create_rootfs"],
+                    timeout: None,
+                },
+                BuildStep {
+                    name: "create_disk_image".to_string(),
+                    step_type: BuildStepType::CreateDiskImage,
+                    enabled: true,
+                    config: serde_json::json!({}),
+                    dependencies: vec!["install_bootloader"],
+                    timeout: None,
+                },
+            ],
+            custom_commands: vec![],
+            compiler_flags: vec!["-O2", "-Wall", "-Wextra"].into_iter().map(|s| s.to_string()).collect(),
+            linker_flags: vec![].into_iter().map(|s| s.to_string()).collect(),
+            reproducible: None,
+            signing: None,
+            hooks: vec![],
+            container: None,
+        }
+    }
+    
+    /// Create a build configuration for a hypervisor or guest project
+    ///
+    /// The bootloader and disk image steps are replaced by a firmware plus EFI image
+    /// built from the kernel, and the image is booted under KVM by a test step.
+    pub fn virtualization(architecture: KernelArchitecture, profile: &VirtualizationProfile, firmware: PathBuf) -> Self {
+        let mut config = Self::default(architecture);
+        config.project_name = match profile.mode {
+            VirtualizationMode::Hypervisor => "osland-hypervisor".to_string(),
+            VirtualizationMode::Guest => "osland-guest".to_string(),
+        };
+        config.bootloader_config.bootloader_type = "efistub".to_string();
+        config.build_steps.retain(|step| !matches!(step.step_type, BuildStepType::InstallBootloader | BuildStepType::CreateDiskImage));
+        
+        let image_config = HypervisorImageConfig {
+            mode: profile.mode.name().to_string(),
+            extension: profile.extension.cpu_flag().to_string(),
+            nested: profile.nested,
+            devices: profile.devices.iter().map(|device| device.name().to_string()).collect(),
+            tile_graph: None,
+            firmware,
+            kernel: None,
+            initrd: None,
+            guest_kernel: None,
+            guest_initrd: None,
+            image_size_mb: default_hypervisor_image_size(),
+            output: default_hypervisor_output(),
+        };
+        let image_dir = config.output_dir.join(&image_config.output);
+        let plan = TestPlan {
+            qemu: profile.qemu_config(&image_dir.join("firmware.fd"), &image_dir.join("hypervisor.img")),
+            suites: vec![profile.test_suite()],
+            junit_output: Some(PathBuf::from("virtualization-tests.xml")),
+            nested: match profile.mode {
+                VirtualizationMode::Hypervisor => Some(profile.extension.cpu_flag().to_string()),
+                VirtualizationMode::Guest => None,
+            },
+        };
+        
+        config.build_steps.push(BuildStep {
+            name: "create_hypervisor_image".to_string(),
+            step_type: BuildStepType::CreateHypervisorImage,
+            enabled: true,
+            config: serde_json::to_value(&image_config).unwrap_or_default(),
+            dependencies: vec!["build_kernel".to_string()],
+            timeout: None,
+        });
+        config.build_steps.push(BuildStep {
+            name: "run_virtualization_tests".to_string(),
+            step_type: BuildStepType::RunTests,
+            enabled: true,
+            config: serde_json::to_value(&plan).unwrap_or_default(),
+            dependencies: vec!["create_hypervisor_image".to_string()],
+            timeout: None,
+        });
+        config
+    }
+    
+    /// Load build configuration from file
+    ///
+    /// The content is validated first so that failures report every unknown
+    /// field, wrong type and missing field with its line and column.
+    pub fn from_file(path: &PathBuf) -> Result<Self, std::io::Error> {
+        let content = std::fs::read_to_string(path)?;
+        
+        let errors: Vec<String> = super::config_validator::validate_config_str(&content).into_iter()
+            .filter(|d| d.severity == super::config_validator::Severity::Error)
+            .map(|d| format!("{}: {}", path.display(), d))
+            .collect();
+        if !errors.is_empty() {
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, errors.join("\n")));
+        }
+        
+        let config = serde_json::from_str(&content)?;
+        Ok(config)
+    }
+    
+    /// Save build configuration to file
+    pub fn to_file(&self, path: &PathBuf) -> Result<(), std::io::Error> {
+        let content = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, content)?;
+        Ok(())
+    }
+    
+    /// Get build step by name
+    pub fn get_step_by_name(&self, name: &str) -> Option<&BuildStep> {
+        self.build_steps.iter().find(|step| step.name == name)
+    }
+    
+    /// Enable/disable a build step
+    pub fn set_step_enabled(&mut self, name: &str, enabled: bool) -> bool {
+        if let Some(step) = self.build_steps.iter_mut().find(|step| step.name == name) {
+            step.enabled = enabled;
+            true
+        } else {
+            false
+        }
+    }
+    
+    /// Add a custom command
+    pub fn add_custom_command(&mut self, command: CustomCommand) {
+        self.custom_commands.push(command);
+    }
+    
+    /// Remove a custom command by name
+    pub fn remove_custom_command(&mut self, name: &str) -> bool {
+        let initial_len = self.custom_commands.len();
+        self.custom_commands.retain(|cmd| cmd.name != name);
+        self.custom_commands.len() != initial_len
+    }
+}
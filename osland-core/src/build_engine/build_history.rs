@@ -0,0 +1,465 @@
+// Build history analytics for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! Persisted build metadata and trend queries.
+//!
+//! Every build writes one row to the `build_history` table and one row per step to
+//! the `build_steps` table. The dashboard queries them for trends (e.g. kernel build
+//! time over the last builds), regressions and side-by-side comparisons of two builds.
+//! Kernel crashes hit by the build's tests are attached in the `build_crashes` table.
+
+use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use crate::dbos_integration::dbos_core::{ColumnDefinition, ColumnType, IndexDefinition, TableDefinition, SharedTables};
+use crate::kernel_visualization::crash_analyzer::CrashReport;
+use super::BuildEngineError;
+
+/// Table of builds
+pub const BUILDS_TABLE: &str = "build_history";
+
+/// Table of build step timings
+pub const BUILD_STEPS_TABLE: &str = "build_steps";
+
+/// Table of crash reports of builds
+pub const BUILD_CRASHES_TABLE: &str = "build_crashes";
+
+/// Result of a build
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BuildOutcome {
+    Succeeded,
+    Failed,
+    Canceled,
+}
+
+/// Time spent in a build step
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StepTiming {
+    /// Step name
+    pub name: String,
+    
+    /// Step duration in milliseconds
+    pub duration_ms: u64,
+    
+    /// Whether the step succeeded
+    pub success: bool,
+}
+
+/// Metadata of a finished build
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BuildRecord {
+    /// Build ID
+    pub build_id: String,
+    
+    /// Project name
+    pub project: String,
+    
+    /// Start time (Unix milliseconds)
+    pub started_at: u64,
+    
+    /// Total duration in milliseconds
+    pub duration_ms: u64,
+    
+    /// Build result
+    pub outcome: BuildOutcome,
+    
+    /// Cache lookups that found a cached result
+    pub cache_hits: u64,
+    
+    /// Cache lookups that missed
+    pub cache_misses: u64,
+    
+    /// Size of the generated image in bytes
+    pub image_size: Option<u64>,
+    
+    /// Timings of the executed steps, in order
+    pub steps: Vec<StepTiming>,
+}
+
+impl BuildRecord {
+    /// Share of cache lookups that hit, if any lookup was made
+    pub fn cache_hit_rate(&self) -> Option<f64> {
+        let lookups = self.cache_hits + self.cache_misses;
+        (lookups > 0).then(|| self.cache_hits as f64 / lookups as f64)
+    }
+    
+    /// Duration of a step
+    pub fn step_duration(&self, name: &str) -> Option<u64> {
+        self.steps.iter().find(|step| step.name == name).map(|step| step.duration_ms)
+    }
+}
+
+/// Quantity tracked across builds
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum BuildMetric {
+    /// Total build time (milliseconds)
+    Duration,
+    
+    /// Time of one step (milliseconds)
+    StepDuration(String),
+    
+    /// Cache hit rate (0-1)
+    CacheHitRate,
+    
+    /// Image size (bytes)
+    ImageSize,
+}
+
+impl BuildMetric {
+    /// Value of the metric for a build
+    pub fn value(&self, build: &BuildRecord) -> Option<f64> {
+        match self {
+            BuildMetric::Duration => Some(build.duration_ms as f64),
+            BuildMetric::StepDuration(name) => build.step_duration(name).map(|duration| duration as f64),
+            BuildMetric::CacheHitRate => build.cache_hit_rate(),
+            BuildMetric::ImageSize => build.image_size.map(|size| size as f64),
+        }
+    }
+}
+
+/// Values of a metric over successive builds
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Trend {
+    /// Metric
+    pub metric: BuildMetric,
+    
+    /// (build ID, start time, value), oldest first
+    pub points: Vec<(String, u64, f64)>,
+}
+
+impl Trend {
+    /// Change per build of a least-squares line through the points
+    pub fn slope(&self) -> f64 {
+        let n = self.points.len() as f64;
+        if self.points.len() < 2 {
+            return 0.0;
+        }
+        let mean_x = (n - 1.0) / 2.0;
+        let mean_y = self.points.iter().map(|(_, _, value)| value).sum::<f64>() / n;
+        let (covariance, variance) = self.points.iter().enumerate()
+            .fold((0.0, 0.0), |(covariance, variance), (x, (_, _, y))| {
+                let dx = x as f64 - mean_x;
+                (covariance + dx * (y - mean_y), variance + dx * dx)
+            });
+        covariance / variance
+    }
+    
+    /// Compare the mean of the last `recent` builds with the builds before them
+    ///
+    /// Returns the relative change when it exceeds `threshold` (e.g. 0.1 for 10%).
+    pub fn regression(&self, recent: usize, threshold: f64) -> Option<f64> {
+        if recent == 0 || self.points.len() <= recent {
+            return None;
+        }
+        let split = self.points.len() - recent;
+        let mean = |points: &[(String, u64, f64)]| points.iter().map(|(_, _, value)| value).sum::<f64>() / points.len() as f64;
+        let baseline = mean(&self.points[..split]);
+        if baseline == 0.0 {
+            return None;
+        }
+        let change = (mean(&self.points[split..]) - baseline) / baseline;
+        (change > threshold).then_some(change)
+    }
+}
+
+/// Difference between two builds
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BuildComparison {
+    /// Earlier build
+    pub base: BuildRecord,
+    
+    /// Later build
+    pub other: BuildRecord,
+    
+    /// Duration change in milliseconds
+    pub duration_delta_ms: i64,
+    
+    /// (step, base duration, other duration) for every step of either build
+    pub steps: Vec<(String, Option<u64>, Option<u64>)>,
+    
+    /// Image size change in bytes
+    pub image_size_delta: Option<i64>,
+}
+
+/// Build history stored in DBOS tables
+pub struct BuildHistory {
+    tables: SharedTables,
+}
+
+impl BuildHistory {
+    /// Open the history on a running tables manager or one of its namespaces, creating its tables if needed
+    pub fn new(tables: impl Into<SharedTables>) -> Result<Self, BuildEngineError> {
+        let tables = tables.into();
+        let definitions = vec![
+            table(BUILDS_TABLE, "Metadata of every build", vec![
+                column("build_id", ColumnType::Uuid, "Build ID"),
+                column("project", ColumnType::String, "Project name"),
+                column("started_at", ColumnType::Timestamp, "Start time (milliseconds)"),
+                column("duration_ms", ColumnType::Long, "Total duration (milliseconds)"),
+                column("outcome", ColumnType::String, "Succeeded, Failed or Canceled"),
+                column("cache_hits", ColumnType::Long, "Cache hits"),
+                column("cache_misses", ColumnType::Long, "Cache misses"),
+                column("image_size", ColumnType::Long, "Image size in bytes (empty without image)"),
+            ], vec!["build_id"], "idx_build_history_project"),
+            table(BUILD_STEPS_TABLE, "Duration of every build step", vec![
+                column("build_id", ColumnType::Uuid, "Build ID"),
+                column("project", ColumnType::String, "Project name"),
+                column("position", ColumnType::Integer, "Step position in the build"),
+                column("step", ColumnType::String, "Step name"),
+                column("duration_ms", ColumnType::Long, "Step duration (milliseconds)"),
+                column("success", ColumnType::Boolean, "Whether the step succeeded"),
+            ], vec!["build_id", "position"], "idx_build_steps_project"),
+            table(BUILD_CRASHES_TABLE, "Kernel crashes hit by the tests of a build", vec![
+                column("build_id", ColumnType::Uuid, "Build ID"),
+                column("project", ColumnType::String, "Project name"),
+                column("position", ColumnType::Integer, "Crash position in the build"),
+                column("title", ColumnType::String, "First line of the crash report"),
+                column("component", ColumnType::String, "Component owning the faulting code (empty if unknown)"),
+                column("report", ColumnType::Json, "Analyzed crash report"),
+            ], vec!["build_id", "position"], "idx_build_crashes_project"),
+        ];
+        for definition in definitions {
+            if tables.get_table(&definition.name).map_err(table_error)?.is_none() {
+                tables.create_table(definition).map_err(table_error)?;
+            }
+        }
+        Ok(Self { tables })
+    }
+    
+    /// Persist a build
+    pub fn record(&self, build: &BuildRecord) -> Result<(), BuildEngineError> {
+        let values = HashMap::from([
+            ("build_id".to_string(), build.build_id.clone()),
+            ("project".to_string(), build.project.clone()),
+            ("started_at".to_string(), build.started_at.to_string()),
+            ("duration_ms".to_string(), build.duration_ms.to_string()),
+            ("outcome".to_string(), format!("{:?}", build.outcome)),
+            ("cache_hits".to_string(), build.cache_hits.to_string()),
+            ("cache_misses".to_string(), build.cache_misses.to_string()),
+            ("image_size".to_string(), build.image_size.map(|size| size.to_string()).unwrap_or_default()),
+        ]);
+        self.tables.insert_row(BUILDS_TABLE, values).map_err(table_error)?;
+        
+        for (position, step) in build.steps.iter().enumerate() {
+            let values = HashMap::from([
+                ("build_id".to_string(), build.build_id.clone()),
+                ("project".to_string(), build.project.clone()),
+                ("position".to_string(), position.to_string()),
+                ("step".to_string(), step.name.clone()),
+                ("duration_ms".to_string(), step.duration_ms.to_string()),
+                ("success".to_string(), step.success.to_string()),
+            ]);
+            self.tables.insert_row(BUILD_STEPS_TABLE, values).map_err(table_error)?;
+        }
+        Ok(())
+    }
+    
+    /// Attach analyzed crashes to a build
+    pub fn attach_crashes(&self, build: &BuildRecord, crashes: &[CrashReport]) -> Result<(), BuildEngineError> {
+        let build_id = &build.build_id;
+        let offset = self.crashes(build_id)?.len();
+        for (position, crash) in crashes.iter().enumerate() {
+            let report = serde_json::to_string(crash)
+                .map_err(|e| BuildEngineError::BuildError(format!("Failed to serialize crash report: {}", e)))?;
+            let values = HashMap::from([
+                ("build_id".to_string(), build_id.clone()),
+                ("project".to_string(), build.project.clone()),
+                ("position".to_string(), (offset + position).to_string()),
+                ("title".to_string(), crash.oops.title.clone()),
+                ("component".to_string(), crash.component.clone().unwrap_or_default()),
+                ("report".to_string(), report),
+            ]);
+            self.tables.insert_row(BUILD_CRASHES_TABLE, values).map_err(table_error)?;
+        }
+        Ok(())
+    }
+    
+    /// Crashes attached to a build, in order
+    pub fn crashes(&self, build_id: &str) -> Result<Vec<CrashReport>, BuildEngineError> {
+        let conditions = HashMap::from([("build_id".to_string(), build_id.to_string())]);
+        let mut crashes = Vec::new();
+        for row in self.tables.query_rows(BUILD_CRASHES_TABLE, conditions).map_err(table_error)? {
+            let position: usize = row.values.get("position").and_then(|value| value.parse().ok()).unwrap_or(0);
+            let report = row.values.get("report").map(String::as_str).unwrap_or_default();
+            let crash: CrashReport = serde_json::from_str(report)
+                .map_err(|e| BuildEngineError::BuildError(format!("Invalid crash report of build {}: {}", build_id, e)))?;
+            crashes.push((position, crash));
+        }
+        crashes.sort_by_key(|(position, _)| *position);
+        Ok(crashes.into_iter().map(|(_, crash)| crash).collect())
+    }
+    
+    /// Builds of a project, oldest first
+    pub fn builds(&self, project: &str) -> Result<Vec<BuildRecord>, BuildEngineError> {
+        let conditions = HashMap::from([("project".to_string(), project.to_string())]);
+        self.load(conditions)
+    }
+    
+    /// Get a build
+    pub fn get(&self, build_id: &str) -> Result<BuildRecord, BuildEngineError> {
+        let conditions = HashMap::from([("build_id".to_string(), build_id.to_string())]);
+        self.load(conditions)?.pop()
+            .ok_or_else(|| BuildEngineError::BuildError(format!("Unknown build: {}", build_id)))
+    }
+    
+    /// Values of a metric over the last `limit` builds of a project that have it
+    pub fn trend(&self, project: &str, metric: BuildMetric, limit: usize) -> Result<Trend, BuildEngineError> {
+        let mut points: Vec<(String, u64, f64)> = self.builds(project)?.iter()
+            .filter_map(|build| metric.value(build).map(|value| (build.build_id.clone(), build.started_at, value)))
+            .collect();
+        let skip = points.len().saturating_sub(limit);
+        points.drain(..skip);
+        Ok(Trend { metric, points })
+    }
+    
+    /// Compare two builds
+    pub fn compare(&self, base_id: &str, other_id: &str) -> Result<BuildComparison, BuildEngineError> {
+        let base = self.get(base_id)?;
+        let other = self.get(other_id)?;
+        
+        let mut steps: Vec<(String, Option<u64>, Option<u64>)> = base.steps.iter()
+            .map(|step| (step.name.clone(), Some(step.duration_ms), other.step_duration(&step.name)))
+            .collect();
+        for step in &other.steps {
+            if base.step_duration(&step.name).is_none() {
+                steps.push((step.name.clone(), None, Some(step.duration_ms)));
+            }
+        }
+        
+        Ok(BuildComparison {
+            duration_delta_ms: other.duration_ms as i64 - base.duration_ms as i64,
+            image_size_delta: base.image_size.zip(other.image_size).map(|(base, other)| other as i64 - base as i64),
+            steps,
+            base,
+            other,
+        })
+    }
+    
+    /// Load the builds matching conditions with their steps
+    fn load(&self, conditions: HashMap<String, String>) -> Result<Vec<BuildRecord>, BuildEngineError> {
+        let rows = self.tables.query_rows(BUILDS_TABLE, conditions.clone()).map_err(table_error)?;
+        let mut steps: HashMap<String, Vec<(usize, StepTiming)>> = HashMap::new();
+        for row in self.tables.query_rows(BUILD_STEPS_TABLE, conditions).map_err(table_error)? {
+            let value = |name: &str| row.values.get(name).cloned().unwrap_or_default();
+            steps.entry(value("build_id")).or_default().push((value("position").parse().unwrap_or(0), StepTiming {
+                name: value("step"),
+                duration_ms: value("duration_ms").parse().unwrap_or(0),
+                success: value("success") == "true",
+            }));
+        }
+        
+        let mut builds: Vec<BuildRecord> = rows.into_iter().map(|row| {
+            let value = |name: &str| row.values.get(name).cloned().unwrap_or_default();
+            let mut build_steps = steps.remove(&value("build_id")).unwrap_or_default();
+            build_steps.sort_by_key(|(position, _)| *position);
+            BuildRecord {
+                build_id: value("build_id"),
+                project: value("project"),
+                started_at: value("started_at").parse().unwrap_or(0),
+                duration_ms: value("duration_ms").parse().unwrap_or(0),
+                outcome: match value("outcome").as_str() {
+                    "Succeeded" => BuildOutcome::Succeeded,
+                    "Canceled" => BuildOutcome::Canceled,
+                    _ => BuildOutcome::Failed,
+                },
+                cache_hits: value("cache_hits").parse().unwrap_or(0),
+                cache_misses: value("cache_misses").parse().unwrap_or(0),
+                image_size: value("image_size").parse().ok(),
+                steps: build_steps.into_iter().map(|(_, step)| step).collect(),
+            }
+        }).collect();
+        builds.sort_by_key(|build| build.started_at);
+        Ok(builds)
+    }
+}
+
+/// Build a table definition with one non-unique index on the project
+fn table(name: &str, description: &str, columns: Vec<ColumnDefinition>, primary_key: Vec<&str>, index: &str) -> TableDefinition {
+    let now = chrono::Utc::now().timestamp() as u64;
+    TableDefinition {
+        name: name.to_string(),
+        columns,
+        primary_key: primary_key.into_iter().map(String::from).collect(),
+        indexes: vec![IndexDefinition {
+            name: index.to_string(),
+            columns: vec!["project".to_string()],
+            unique: false,
+        }],
+        description: description.to_string(),
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+/// Build a column definition
+fn column(name: &str, column_type: ColumnType, description: &str) -> ColumnDefinition {
+    ColumnDefinition {
+        name: name.to_string(),
+        column_type,
+        nullable: matches!(name, "image_size" | "component"),
+        default_value: None,
+        description: description.to_string(),
+    }
+}
+
+fn table_error(error: String) -> BuildEngineError {
+    BuildEngineError::BuildError(format!("Build history: {}", error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use crate::dbos_integration::dbos_core::TablesManager;
+    use crate::kernel_visualization::crash_analyzer::parse_oopses;
+    use crate::kernel_visualization::trace_ingest::ComponentMapper;
+    
+    fn build(id: &str, started_at: u64, kernel_ms: u64, image_size: u64) -> BuildRecord {
+        BuildRecord {
+            build_id: id.to_string(),
+            project: "kernel".to_string(),
+            started_at,
+            duration_ms: kernel_ms + 1000,
+            outcome: BuildOutcome::Succeeded,
+            cache_hits: 3,
+            cache_misses: 1,
+            image_size: Some(image_size),
+            steps: vec![
+                StepTiming { name: "Configure Kernel".to_string(), duration_ms: 1000, success: true },
+                StepTiming { name: "Build Kernel".to_string(), duration_ms: kernel_ms, success: true },
+            ],
+        }
+    }
+    
+    #[test]
+    fn test_build_history_trends() {
+        let tables = Arc::new(TablesManager::new());
+        tables.start();
+        let history = BuildHistory::new(tables).unwrap();
+        for (i, kernel_ms) in [60_000, 61_000, 59_000, 60_000, 90_000, 95_000].into_iter().enumerate() {
+            history.record(&build(&format!("b{}", i), i as u64 * 1000, kernel_ms, 1 << 20)).unwrap();
+        }
+        
+        let stored = history.get("b4").unwrap();
+        assert_eq!(stored, build("b4", 4000, 90_000, 1 << 20));
+        assert_eq!(stored.cache_hit_rate(), Some(0.75));
+        
+        let trend = history.trend("kernel", BuildMetric::StepDuration("Build Kernel".to_string()), 10).unwrap();
+        assert_eq!(trend.points.len(), 6);
+        assert!(trend.slope() > 0.0);
+        let change = trend.regression(2, 0.1).unwrap();
+        assert!((change - 0.5417).abs() < 0.001);
+        assert!(history.trend("kernel", BuildMetric::Duration, 4).unwrap().regression(3, 0.5).is_none());
+        
+        let comparison = history.compare("b0", "b5").unwrap();
+        assert_eq!(comparison.duration_delta_ms, 35_000);
+        assert_eq!(comparison.steps[1], ("Build Kernel".to_string(), Some(60_000), Some(95_000)));
+        assert_eq!(comparison.image_size_delta, Some(0));
+        
+        let oops = parse_oopses("BUG: kernel NULL pointer dereference, address: 0000000000000008\nRIP: 0010:ext4_readdir+0x31/0x8a0\n").remove(0);
+        let crash = CrashReport::attribute(oops, &ComponentMapper::default());
+        history.attach_crashes(&history.get("b5").unwrap(), &[crash.clone()]).unwrap();
+        assert_eq!(history.crashes("b5").unwrap(), vec![crash]);
+        assert!(history.crashes("b4").unwrap().is_empty());
+    }
+}
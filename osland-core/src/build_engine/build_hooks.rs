@@ -0,0 +1,256 @@
+// Build hooks for OSland build engine
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+use std::path::PathBuf;
+use std::process::Command;
+use serde::{Deserialize, Serialize};
+use super::{build_config::BuildStep, build_steps::BuildStepContext, BuildEngineError};
+
+/// Hook point in the step lifecycle
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum HookPoint {
+    /// Before a step runs; a failing hook aborts the step
+    PreStep,
+    
+    /// After a step succeeds
+    PostStep,
+    
+    /// After a step (or one of its pre-step hooks) fails
+    OnFailure,
+}
+
+impl HookPoint {
+    /// Name exported to hook scripts
+    pub fn name(&self) -> &'static str {
+        match self {
+            HookPoint::PreStep => "pre_step",
+            HookPoint::PostStep => "post_step",
+            HookPoint::OnFailure => "on_failure",
+        }
+    }
+}
+
+/// Build hook trait
+///
+/// Hooks receive the step being executed and its context, so they can patch
+/// sources, register additional outputs, upload artifacts or send notifications.
+pub trait BuildHook {
+    /// Hook name (used in error messages)
+    fn name(&self) -> &str;
+    
+    /// Check whether the hook should run for a step
+    fn matches(&self, _step: &BuildStep) -> bool {
+        true
+    }
+    
+    /// Run the hook; `error` is set for `OnFailure` hooks
+    fn run(
+        &self,
+        point: HookPoint,
+        step: &BuildStep,
+        context: &mut BuildStepContext,
+        error: Option<&BuildEngineError>,
+    ) -> Result<(), BuildEngineError>;
+}
+
+/// Hook configuration (as found in the build configuration file)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HookConfig {
+    /// Hook name
+    pub name: String,
+    
+    /// Hook point
+    pub point: HookPoint,
+    
+    /// Script or program to run
+    pub command: String,
+    
+    /// Arguments
+    #[serde(default)]
+    pub args: Vec<String>,
+    
+    /// Step names or step types the hook applies to (empty means all steps)
+    #[serde(default)]
+    pub steps: Vec<String>,
+    
+    /// Ignore hook failures
+    #[serde(default)]
+    pub continue_on_failure: bool,
+}
+
+/// Hook that runs an external script
+///
+/// The script runs in the step working directory with these variables set:
+/// `OSLAND_HOOK`, `OSLAND_STEP_NAME`, `OSLAND_STEP_TYPE`, `OSLAND_WORKING_DIR`,
+/// `OSLAND_OUTPUT_<NAME>` for every output so far and `OSLAND_ERROR` on failure.
+/// Lines printed as `osland-output NAME=PATH` are added to the step outputs.
+pub struct ScriptHook {
+    /// Hook configuration
+    config: HookConfig,
+}
+
+impl ScriptHook {
+    /// Create a new script hook
+    pub fn new(config: HookConfig) -> Self {
+        Self { config }
+    }
+    
+    /// Get the hook point
+    pub fn point(&self) -> HookPoint {
+        self.config.point
+    }
+}
+
+impl BuildHook for ScriptHook {
+    fn name(&self) -> &str {
+        &self.config.name
+    }
+    
+    fn matches(&self, step: &BuildStep) -> bool {
+        let step_type = format!("{:?}", step.step_type);
+        self.config.steps.is_empty() || self.config.steps.iter().any(|s| *s == step.name || *s == step_type)
+    }
+    
+    fn run(
+        &self,
+        point: HookPoint,
+        step: &BuildStep,
+        context: &mut BuildStepContext,
+        error: Option<&BuildEngineError>,
+    ) -> Result<(), BuildEngineError> {
+        let mut cmd = Command::new(&self.config.command);
+        cmd.args(&self.config.args)
+            .current_dir(context.get_working_dir())
+            .env("OSLAND_HOOK", point.name())
+            .env("OSLAND_STEP_NAME", &step.name)
+            .env("OSLAND_STEP_TYPE", format!("{:?}", step.step_type))
+            .env("OSLAND_WORKING_DIR", context.get_working_dir());
+        
+        for (name, path) in context.get_outputs() {
+            cmd.env(format!("OSLAND_OUTPUT_{}", name.to_uppercase()), path);
+        }
+        if let Some(error) = error {
+            cmd.env("OSLAND_ERROR", error.to_string());
+        }
+        
+        let output = cmd.output()
+            .map_err(|e| BuildEngineError::CommandError(format!("Hook '{}': {}", self.config.name, e)))?;
+        
+        if !output.status.success() {
+            if self.config.continue_on_failure {
+                return Ok(());
+            }
+            return Err(BuildEngineError::BuildError(format!(
+                "Hook '{}' failed with {}: {}",
+                self.config.name,
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        
+        for line in String::from_utf8_lossy(&output.stdout).lines() {
+            if let Some((name, path)) = line.strip_prefix("osland-output ").and_then(|rest| rest.split_once('=')) {
+                context.add_output(name.trim().to_string(), PathBuf::from(path.trim()));
+            }
+        }
+        
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use crate::build_engine::build_config::{BuildConfig, BuildStepType};
+    use crate::build_engine::build_steps::BuildStepRegistry;
+    use crate::core::architecture::KernelArchitecture;
+    
+    /// Hook appending `<hook>:<point>` (and the error it saw) to a shared log
+    struct RecordingHook {
+        name: String,
+        log: Rc<RefCell<Vec<String>>>,
+        fail: bool,
+    }
+    
+    impl BuildHook for RecordingHook {
+        fn name(&self) -> &str {
+            &self.name
+        }
+        
+        fn run(
+            &self,
+            point: HookPoint,
+            _step: &BuildStep,
+            _context: &mut BuildStepContext,
+            error: Option<&BuildEngineError>,
+        ) -> Result<(), BuildEngineError> {
+            let mut entry = format!("{}:{}", self.name, point.name());
+            if let Some(error) = error {
+                entry.push_str(&format!(" ({})", error));
+            }
+            self.log.borrow_mut().push(entry);
+            if self.fail {
+                return Err(BuildEngineError::BuildError(format!("{} failed", self.name)));
+            }
+            Ok(())
+        }
+    }
+    
+    fn registry(log: &Rc<RefCell<Vec<String>>>, failing_pre_step: bool) -> BuildStepRegistry {
+        let mut registry = BuildStepRegistry::new();
+        let hook = |name: &str, fail: bool| Box::new(RecordingHook { name: name.to_string(), log: log.clone(), fail });
+        registry.register_hook(HookPoint::PreStep, hook("check", failing_pre_step));
+        registry.register_hook(HookPoint::PreStep, hook("patch", false));
+        registry.register_hook(HookPoint::PostStep, hook("upload", false));
+        registry.register_hook(HookPoint::OnFailure, hook("notify", true));
+        registry
+    }
+    
+    fn run(registry: &BuildStepRegistry, log: &Rc<RefCell<Vec<String>>>, succeed: bool) -> Result<(), BuildEngineError> {
+        let step = BuildStep {
+            name: "kernel".to_string(),
+            step_type: BuildStepType::Custom,
+            enabled: true,
+            config: serde_json::Value::Null,
+            dependencies: Vec::new(),
+            timeout: None,
+        };
+        let mut context = BuildStepContext::new(BuildConfig::default(KernelArchitecture::Monolithic), std::env::temp_dir(), serde_json::Value::Null);
+        registry.run_step(&step, &mut context, |_| {
+            log.borrow_mut().push("step".to_string());
+            if succeed {
+                Ok(())
+            } else {
+                Err(BuildEngineError::CommandError("make failed".to_string()))
+            }
+        })
+    }
+    
+    #[test]
+    fn test_hook_order() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        
+        // Pre-step hooks in registration order, then the step and its post-step hooks
+        run(&registry(&log, false), &log, true).unwrap();
+        assert_eq!(*log.borrow(), vec!["check:pre_step", "patch:pre_step", "step", "upload:post_step"]);
+        
+        // A failing step runs the failure hooks instead; their own failure does not hide the step's error
+        log.borrow_mut().clear();
+        let error = run(&registry(&log, false), &log, false).unwrap_err();
+        assert!(matches!(error, BuildEngineError::CommandError(_)));
+        assert_eq!(log.borrow().len(), 4);
+        assert_eq!(log.borrow()[..3], ["check:pre_step", "patch:pre_step", "step"]);
+        assert!(log.borrow()[3].starts_with("notify:on_failure (") && log.borrow()[3].contains("make failed"));
+        
+        // A failing pre-step hook aborts the step and triggers the failure hooks
+        log.borrow_mut().clear();
+        let error = run(&registry(&log, true), &log, true).unwrap_err();
+        assert!(error.to_string().contains("check failed"));
+        assert_eq!(log.borrow().len(), 2);
+        assert_eq!(log.borrow()[0], "check:pre_step");
+        assert!(log.borrow()[1].starts_with("notify:on_failure (") && log.borrow()[1].contains("check failed"));
+    }
+}
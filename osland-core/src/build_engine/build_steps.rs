@@ -7,6 +7,8 @@ use std::process::{Command, ExitStatus};
 use serde::{Deserialize, Serialize};
 use crate::core::architecture::KernelArchitecture;
 use super::{build_config::{BuildStep, BuildStepType, BuildConfig}, BuildEngineError};
+use super::build_hooks::{BuildHook, HookPoint, ScriptHook};
+use super::initramfs::{InitramfsBuilder, InitramfsManifest};
 
 /// Build step execution context
 pub struct BuildStepContext {
@@ -337,6 +339,34 @@ impl CreateRootfsExecutor {
     }
 }
 
+/// Create initramfs step executor
+pub struct CreateInitramfsExecutor;
+
+impl BuildStepExecutor for CreateInitramfsExecutor {
+    fn execute(&self, context: &mut BuildStepContext) -> Result<(), BuildEngineError> {
+        let mut manifest: InitramfsManifest = serde_json::from_value(context.get_step_config().clone())
+            .map_err(|e| BuildEngineError::ConfigError(format!("Invalid initramfs manifest: {}", e)))?;
+        let config = context.get_config();
+        manifest.resolve_overlays(&config.output_dir);
+        let output = config.output_dir.join(manifest.output.clone().unwrap_or_else(|| PathBuf::from("initramfs")));
+        let mtime = std::env::var("SOURCE_DATE_EPOCH").ok().and_then(|v| v.parse().ok()).unwrap_or(0);
+        
+        let kernel_config = &config.kernel_config;
+        let mut builder = InitramfsBuilder::new(manifest, mtime);
+        builder.stage(&kernel_config.source_path, &kernel_config.modules, &kernel_config.kernel_version)?;
+        let path = builder.write(&output)?;
+        
+        // Add output
+        context.add_output("initramfs".to_string(), path);
+        
+        Ok(())
+    }
+    
+    fn get_step_type(&self) -> BuildStepType {
+        BuildStepType::CreateInitramfs
+    }
+}
+
 /// Install bootloader step executor
 pub struct InstallBootloaderExecutor;
 
@@ -420,6 +450,9 @@ impl BuildStepExecutor for CustomStepExecutor {
 /// Build step registry
 pub struct BuildStepRegistry {
     executors: std::collections::HashMap<BuildStepType, Box<dyn BuildStepExecutor>>,
+    
+    /// Hooks per hook point, in registration order
+    hooks: std::collections::HashMap<HookPoint, Vec<Box<dyn BuildHook>>>,
 }
 
 impl BuildStepRegistry {
@@ -427,6 +460,7 @@ impl BuildStepRegistry {
     pub fn new() -> Self {
         let mut registry = Self {
             executors: std::collections::HashMap::new(),
+            hooks: std::collections::HashMap::new(),
         };
         
         // Register all built-in executors
@@ -435,6 +469,7 @@ impl BuildStepRegistry {
         registry.register(Box::new(BuildKernelExecutor));
         registry.register(Box::new(BuildKernelModulesExecutor));
         registry.register(Box::new(CreateRootfsExecutor));
+        registry.register(Box::new(CreateInitramfsExecutor));
         registry.register(Box::new(InstallBootloaderExecutor));
         registry.register(Box::new(CreateDiskImageExecutor));
         registry.register(Box::new(RunTestsExecutor));
@@ -453,12 +488,67 @@ impl BuildStepRegistry {
         self.executors.get(step_type)
     }
     
-    /// Execute a build step
+    /// Register a hook at a hook point
+    pub fn register_hook(&mut self, point: HookPoint, hook: Box<dyn BuildHook>) {
+        self.hooks.entry(point).or_default().push(hook);
+    }
+    
+    /// Register the script hooks declared in a build configuration
+    pub fn register_config_hooks(&mut self, config: &BuildConfig) {
+        for hook_config in &config.hooks {
+            let hook = ScriptHook::new(hook_config.clone());
+            self.register_hook(hook.point(), Box::new(hook));
+        }
+    }
+    
+    /// Run every hook registered at a point that matches the step
+    fn run_hooks(
+        &self,
+        point: HookPoint,
+        step: &BuildStep,
+        context: &mut BuildStepContext,
+        error: Option<&BuildEngineError>,
+    ) -> Result<(), BuildEngineError> {
+        for hook in self.hooks.get(&point).into_iter().flatten() {
+            if hook.matches(step) {
+                hook.run(point, step, context, error)?;
+            }
+        }
+        Ok(())
+    }
+    
+    /// Execute a build step, running its pre-step, post-step and failure hooks
     pub fn execute_step(&self, step: &BuildStep, context: &mut BuildStepContext) -> Result<(), BuildEngineError> {
-        if let Some(executor) = self.get_executor(&step.step_type) {
-            executor.execute(context)
-        } else {
-            Err(BuildEngineError::BuildStepFailed(format!("No executor found for step type: {:?}", step.step_type)))
+        match self.get_executor(&step.step_type) {
+            Some(executor) => self.run_step(step, context, |context| executor.execute(context)),
+            None => self.finish_step(step, context, Err(BuildEngineError::BuildError(
+                format!("No executor found for step type: {:?}", step.step_type)
+            ))),
+        }
+    }
+    
+    /// Run a step implemented by the caller, with the step's hooks around it
+    ///
+    /// The build engine runs its own steps this way.
+    pub fn run_step<F>(&self, step: &BuildStep, context: &mut BuildStepContext, run: F) -> Result<(), BuildEngineError>
+    where
+        F: FnOnce(&mut BuildStepContext) -> Result<(), BuildEngineError>,
+    {
+        let result = self.run_hooks(HookPoint::PreStep, step, context, None).and_then(|_| run(context));
+        self.finish_step(step, context, result)
+    }
+    
+    /// Run the post-step or failure hooks for the result of a step
+    fn finish_step(&self, step: &BuildStep, context: &mut BuildStepContext, result: Result<(), BuildEngineError>) -> Result<(), BuildEngineError> {
+        match result {
+            Ok(()) => self.run_hooks(HookPoint::PostStep, step, context, None),
+            Err(error) => {
+                // Failure hooks are best effort; the original error is reported
+                if let Err(hook_error) = self.run_hooks(HookPoint::OnFailure, step, context, Some(&error)) {
+                    log::warn!("Failure hook for step '{}' failed: {}", step.name, hook_error);
+                }
+                Err(error)
+            }
         }
     }
 }
@@ -0,0 +1,607 @@
+// Build configuration validation for OSland build engine
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+
+/// Expected kind of a configuration value
+#[derive(Debug)]
+enum Kind {
+    Str,
+    Bool,
+    UInt,
+    Any,
+    Enum(&'static [&'static str]),
+    ArrayOf(&'static Kind),
+    Object(&'static [Field]),
+    /// Two-element array of strings, e.g. `["KEY", "value"]`
+    StrPair,
+}
+
+/// Field of a configuration object
+#[derive(Debug)]
+struct Field {
+    name: &'static str,
+    kind: Kind,
+    required: bool,
+}
+
+const fn field(name: &'static str, kind: Kind) -> Field {
+    Field { name, kind, required: true }
+}
+
+const fn optional(name: &'static str, kind: Kind) -> Field {
+    Field { name, kind, required: false }
+}
+
+const STEP_TYPES: &[&str] = &[
+    "DownloadKernel", "ConfigureKernel", "BuildKernel", "BuildKernelModules", "CreateRootfs", "CreateInitramfs", "GenerateDeviceTree",
+    "CheckModuleAbi", "GenerateSyscalls", "GenerateSchedulerConfig", "GenerateSecurityPolicy", "GenerateBootFlow",
+    "CreateHypervisorImage", "CreateFilesystemImage", "GenerateLicenseReport", "PublishArtifacts", "InstallBootloader", "CreateDiskImage", "RunTests", "Custom",
+];
+
+const TOOLCHAIN_FIELDS: &[Field] = &[
+    field("toolchain_type", Kind::Enum(&["GNU", "LLVM", "Custom"])),
+    optional("toolchain_path", Kind::Str),
+    field("c_compiler", Kind::Str),
+    field("cpp_compiler", Kind::Str),
+    field("assembler", Kind::Str),
+    field("linker", Kind::Str),
+    field("strip", Kind::Str),
+    field("objcopy", Kind::Str),
+    field("objdump", Kind::Str),
+];
+
+const DOWNLOAD_FIELDS: &[Field] = &[
+    field("urls", Kind::ArrayOf(&Kind::Str)),
+    optional("sha256", Kind::Str),
+    optional("file_name", Kind::Str),
+    optional("strip_components", Kind::UInt),
+];
+
+const KERNEL_FIELDS: &[Field] = &[
+    field("kernel_name", Kind::Str),
+    field("kernel_version", Kind::Str),
+    field("source_path", Kind::Str),
+    optional("config_file", Kind::Str),
+    field("features", Kind::ArrayOf(&Kind::Str)),
+    field("modules", Kind::ArrayOf(&Kind::Str)),
+    optional("download", Kind::Object(DOWNLOAD_FIELDS)),
+];
+
+const ROOTFS_FILE_FIELDS: &[Field] = &[
+    field("source", Kind::Str),
+    field("destination", Kind::Str),
+    optional("permissions", Kind::UInt),
+];
+
+const ROOTFS_DIRECTORY_FIELDS: &[Field] = &[
+    field("path", Kind::Str),
+    optional("permissions", Kind::UInt),
+];
+
+const ROOTFS_PERMISSION_FIELDS: &[Field] = &[
+    field("path", Kind::Str),
+    field("permissions", Kind::UInt),
+];
+
+const ROOTFS_FIELDS: &[Field] = &[
+    field("fs_type", Kind::Str),
+    optional("source_dir", Kind::Str),
+    field("image_path", Kind::Str),
+    optional("size", Kind::UInt),
+    field("files", Kind::ArrayOf(&Kind::Object(ROOTFS_FILE_FIELDS))),
+    field("directories", Kind::ArrayOf(&Kind::Object(ROOTFS_DIRECTORY_FIELDS))),
+    field("permissions", Kind::ArrayOf(&Kind::Object(ROOTFS_PERMISSION_FIELDS))),
+];
+
+const BOOTLOADER_FIELDS: &[Field] = &[
+    field("bootloader_type", Kind::Str),
+    optional("config_file", Kind::Str),
+    field("install_dir", Kind::Str),
+    field("kernel_params", Kind::ArrayOf(&Kind::Str)),
+    field("timeout", Kind::UInt),
+];
+
+const BUILD_STEP_FIELDS: &[Field] = &[
+    field("name", Kind::Str),
+    field("step_type", Kind::Enum(STEP_TYPES)),
+    field("enabled", Kind::Bool),
+    field("config", Kind::Any),
+    field("dependencies", Kind::ArrayOf(&Kind::Str)),
+    optional("timeout", Kind::UInt),
+];
+
+const CUSTOM_COMMAND_FIELDS: &[Field] = &[
+    field("name", Kind::Str),
+    field("command", Kind::Str),
+    field("args", Kind::ArrayOf(&Kind::Str)),
+    optional("working_dir", Kind::Str),
+    field("env", Kind::ArrayOf(&Kind::StrPair)),
+    field("continue_on_failure", Kind::Bool),
+];
+
+const REPRODUCIBLE_FIELDS: &[Field] = &[
+    optional("source_date_epoch", Kind::UInt),
+    optional("locale", Kind::Str),
+    optional("timezone", Kind::Str),
+    optional("umask", Kind::UInt),
+    optional("archive", Kind::Bool),
+];
+
+const SIGNING_FIELDS: &[Field] = &[
+    field("scheme", Kind::Enum(&["Ed25519", "Gpg"])),
+    optional("key_path", Kind::Str),
+    optional("key_secret", Kind::Str),
+    optional("vault_dir", Kind::Str),
+    optional("key_id", Kind::Str),
+];
+
+const HOOK_FIELDS: &[Field] = &[
+    field("name", Kind::Str),
+    field("point", Kind::Enum(&["PreStep", "PostStep", "OnFailure"])),
+    field("command", Kind::Str),
+    optional("args", Kind::ArrayOf(&Kind::Str)),
+    optional("steps", Kind::ArrayOf(&Kind::Str)),
+    optional("continue_on_failure", Kind::Bool),
+];
+
+const VOLUME_FIELDS: &[Field] = &[
+    field("host", Kind::Str),
+    field("container", Kind::Str),
+    optional("read_only", Kind::Bool),
+];
+
+const CONTAINER_FIELDS: &[Field] = &[
+    field("runtime", Kind::Enum(&["Docker", "Podman"])),
+    field("image", Kind::Str),
+    optional("volumes", Kind::ArrayOf(&Kind::Object(VOLUME_FIELDS))),
+    optional("workdir", Kind::Str),
+    optional("user", Kind::Str),
+    optional("env", Kind::ArrayOf(&Kind::StrPair)),
+    optional("extra_args", Kind::ArrayOf(&Kind::Str)),
+];
+
+const BUILD_CONFIG_FIELDS: &[Field] = &[
+    field("project_name", Kind::Str),
+    field("project_version", Kind::Str),
+    field("output_dir", Kind::Str),
+    field("architecture", Kind::Any),
+    field("build_mode", Kind::Enum(&["Debug", "Release"])),
+    field("toolchain_config", Kind::Object(TOOLCHAIN_FIELDS)),
+    field("kernel_config", Kind::Object(KERNEL_FIELDS)),
+    field("rootfs_config", Kind::Object(ROOTFS_FIELDS)),
+    field("bootloader_config", Kind::Object(BOOTLOADER_FIELDS)),
+    field("build_steps", Kind::ArrayOf(&Kind::Object(BUILD_STEP_FIELDS))),
+    field("custom_commands", Kind::ArrayOf(&Kind::Object(CUSTOM_COMMAND_FIELDS))),
+    field("compiler_flags", Kind::ArrayOf(&Kind::Str)),
+    field("linker_flags", Kind::ArrayOf(&Kind::Str)),
+    optional("reproducible", Kind::Object(REPRODUCIBLE_FIELDS)),
+    optional("signing", Kind::Object(SIGNING_FIELDS)),
+    optional("hooks", Kind::ArrayOf(&Kind::Object(HOOK_FIELDS))),
+    optional("container", Kind::Object(CONTAINER_FIELDS)),
+];
+
+/// Diagnostic severity
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// Configuration diagnostic
+#[derive(Debug, Clone)]
+pub struct ConfigDiagnostic {
+    /// Severity
+    pub severity: Severity,
+    
+    /// JSON path of the offending value (e.g. `build_steps[2].step_type`)
+    pub path: String,
+    
+    /// Line in the configuration file (1-based)
+    pub line: Option<usize>,
+    
+    /// Column in the configuration file (1-based)
+    pub column: Option<usize>,
+    
+    /// Diagnostic message
+    pub message: String,
+    
+    /// Suggested fix
+    pub suggestion: Option<String>,
+}
+
+impl fmt::Display for ConfigDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => write!(f, "{}:{}: {}", line, column, severity)?,
+            _ => write!(f, "{}", severity)?,
+        }
+        if !self.path.is_empty() {
+            write!(f, " at `{}`", self.path)?;
+        }
+        write!(f, ": {}", self.message)?;
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, " ({})", suggestion)?;
+        }
+        Ok(())
+    }
+}
+
+/// Validate build configuration JSON text
+///
+/// Returns every problem found instead of stopping at the first one, with the
+/// line and column of the offending value where it can be located.
+pub fn validate_config_str(source: &str) -> Vec<ConfigDiagnostic> {
+    let value: Value = match serde_json::from_str(source) {
+        Ok(value) => value,
+        Err(e) => {
+            return vec![ConfigDiagnostic {
+                severity: Severity::Error,
+                path: String::new(),
+                line: Some(e.line()),
+                column: Some(e.column()),
+                message: format!("Invalid JSON: {}", e),
+                suggestion: None,
+            }];
+        }
+    };
+    
+    let positions = index_positions(source);
+    let mut diagnostics = Vec::new();
+    check_object(&value, BUILD_CONFIG_FIELDS, "", &positions, &mut diagnostics);
+    
+    // Semantic checks that go beyond types
+    if let Some(steps) = value.get("build_steps").and_then(|v| v.as_array()) {
+        let names: Vec<&str> = steps.iter().filter_map(|s| s.get("name").and_then(|n| n.as_str())).collect();
+        for (index, step) in steps.iter().enumerate() {
+            let dependencies = step.get("dependencies").and_then(|d| d.as_array()).cloned().unwrap_or_default();
+            for (dep_index, dependency) in dependencies.iter().enumerate() {
+                if let Some(dependency) = dependency.as_str() {
+                    if !names.contains(&dependency) {
+                        let path = format!("build_steps[{}].dependencies[{}]", index, dep_index);
+                        diagnostics.push(diagnostic(
+                            Severity::Error,
+                            &path,
+                            &positions,
+                            format!("Step depends on unknown step '{}'", dependency),
+                            closest_match(dependency, &names).map(|s| format!("did you mean '{}'?", s)),
+                        ));
+                    }
+                }
+            }
+        }
+        
+        let mut seen: HashMap<&str, usize> = HashMap::new();
+        for (index, name) in names.iter().enumerate() {
+            if let Some(first) = seen.insert(name, index) {
+                diagnostics.push(diagnostic(
+                    Severity::Error,
+                    &format!("build_steps[{}].name", index),
+                    &positions,
+                    format!("Duplicate step name '{}' (first defined at build_steps[{}])", name, first),
+                    None,
+                ));
+            }
+        }
+    }
+    
+    diagnostics
+}
+
+/// Validate a build configuration file
+pub fn validate_config_file<P: AsRef<Path>>(path: P) -> Result<Vec<ConfigDiagnostic>, std::io::Error> {
+    let source = std::fs::read_to_string(path)?;
+    Ok(validate_config_str(&source))
+}
+
+fn diagnostic(
+    severity: Severity,
+    path: &str,
+    positions: &HashMap<String, (usize, usize)>,
+    message: String,
+    suggestion: Option<String>,
+) -> ConfigDiagnostic {
+    let position = positions.get(path).copied();
+    ConfigDiagnostic {
+        severity,
+        path: path.to_string(),
+        line: position.map(|p| p.0),
+        column: position.map(|p| p.1),
+        message,
+        suggestion,
+    }
+}
+
+fn join_path(parent: &str, key: &str) -> String {
+    if parent.is_empty() { key.to_string() } else { format!("{}.{}", parent, key) }
+}
+
+fn kind_name(kind: &Kind) -> String {
+    match kind {
+        Kind::Str => "a string".to_string(),
+        Kind::Bool => "a boolean".to_string(),
+        Kind::UInt => "a non-negative integer".to_string(),
+        Kind::Any => "any value".to_string(),
+        Kind::Enum(values) => format!("one of {}", values.join(", ")),
+        Kind::ArrayOf(inner) => format!("an array of {}", kind_name(inner).trim_start_matches("a ").trim_start_matches("an ")),
+        Kind::Object(_) => "an object".to_string(),
+        Kind::StrPair => "a [\"KEY\", \"value\"] pair".to_string(),
+    }
+}
+
+fn value_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Array(_) => "an array",
+        Value::Object(_) => "an object",
+    }
+}
+
+fn check_object(
+    value: &Value,
+    fields: &'static [Field],
+    path: &str,
+    positions: &HashMap<String, (usize, usize)>,
+    diagnostics: &mut Vec<ConfigDiagnostic>,
+) {
+    let object = match value.as_object() {
+        Some(object) => object,
+        None => {
+            diagnostics.push(diagnostic(Severity::Error, path, positions,
+                format!("Expected an object, found {}", value_name(value)), None));
+            return;
+        }
+    };
+    
+    let known: Vec<&str> = fields.iter().map(|f| f.name).collect();
+    for key in object.keys() {
+        if !known.contains(&key.as_str()) {
+            diagnostics.push(diagnostic(
+                Severity::Error,
+                &join_path(path, key),
+                positions,
+                format!("Unknown field '{}'", key),
+                closest_match(key, &known).map(|s| format!("did you mean '{}'?", s)),
+            ));
+        }
+    }
+    
+    for field in fields {
+        let field_path = join_path(path, field.name);
+        match object.get(field.name) {
+            None => {
+                if field.required {
+                    diagnostics.push(diagnostic(
+                        Severity::Error,
+                        path,
+                        positions,
+                        format!("Missing required field '{}'", field.name),
+                        Some(format!("add \"{}\" ({})", field.name, kind_name(&field.kind))),
+                    ));
+                }
+            }
+            Some(Value::Null) if !field.required => {}
+            Some(v) => check_value(v, &field.kind, &field_path, positions, diagnostics),
+        }
+    }
+}
+
+fn check_value(
+    value: &Value,
+    kind: &'static Kind,
+    path: &str,
+    positions: &HashMap<String, (usize, usize)>,
+    diagnostics: &mut Vec<ConfigDiagnostic>,
+) {
+    let type_error = |diagnostics: &mut Vec<ConfigDiagnostic>| {
+        diagnostics.push(diagnostic(Severity::Error, path, positions,
+            format!("Expected {}, found {}", kind_name(kind), value_name(value)), None));
+    };
+    
+    match kind {
+        Kind::Any => {}
+        Kind::Str => if !value.is_string() { type_error(diagnostics) },
+        Kind::Bool => if !value.is_boolean() { type_error(diagnostics) },
+        Kind::UInt => if !value.is_u64() { type_error(diagnostics) },
+        Kind::Enum(variants) => match value.as_str() {
+            Some(s) if variants.contains(&s) => {}
+            Some(s) => diagnostics.push(diagnostic(
+                Severity::Error,
+                path,
+                positions,
+                format!("Unknown variant '{}', expected {}", s, kind_name(kind)),
+                closest_match(s, variants).map(|v| format!("did you mean '{}'?", v)),
+            )),
+            None => type_error(diagnostics),
+        },
+        Kind::ArrayOf(inner) => match value.as_array() {
+            Some(items) => {
+                for (index, item) in items.iter().enumerate() {
+                    check_value(item, inner, &format!("{}[{}]", path, index), positions, diagnostics);
+                }
+            }
+            None => type_error(diagnostics),
+        },
+        Kind::Object(fields) => check_object(value, fields, path, positions, diagnostics),
+        Kind::StrPair => match value.as_array() {
+            Some(items) if items.len() == 2 && items.iter().all(|i| i.is_string()) => {}
+            _ => type_error(diagnostics),
+        },
+    }
+}
+
+/// Suggest the closest candidate within a small edit distance
+fn closest_match<'a>(input: &str, candidates: &[&'a str]) -> Option<&'a str> {
+    candidates.iter()
+        .map(|candidate| (levenshtein(&input.to_lowercase(), &candidate.to_lowercase()), *candidate))
+        .filter(|(distance, candidate)| *distance <= (candidate.len() / 3).max(2))
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b_chars: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b_chars.len()).collect();
+    
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b_chars.iter().enumerate() {
+            let cost = if ca == *cb { 0 } else { 1 };
+            current.push((previous[j] + cost).min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    
+    previous[b_chars.len()]
+}
+
+/// Record the (line, column) where every value starts, keyed by JSON path
+///
+/// The source must already be valid JSON.
+fn index_positions(source: &str) -> HashMap<String, (usize, usize)> {
+    let mut indexer = PositionIndexer {
+        chars: source.chars().collect(),
+        pos: 0,
+        line: 1,
+        column: 1,
+        positions: HashMap::new(),
+    };
+    indexer.value(String::new());
+    indexer.positions
+}
+
+struct PositionIndexer {
+    chars: Vec<char>,
+    pos: usize,
+    line: usize,
+    column: usize,
+    positions: HashMap<String, (usize, usize)>,
+}
+
+impl PositionIndexer {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+    
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += 1;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+    
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(c) if c.is_whitespace()) {
+            self.bump();
+        }
+    }
+    
+    fn string(&mut self) -> String {
+        let mut out = String::new();
+        self.bump(); // opening quote
+        while let Some(c) = self.bump() {
+            match c {
+                '"' => break,
+                '\\' => {
+                    if let Some(escaped) = self.bump() {
+                        out.push(escaped);
+                    }
+                }
+                _ => out.push(c),
+            }
+        }
+        out
+    }
+    
+    fn value(&mut self, path: String) {
+        self.skip_whitespace();
+        self.positions.insert(path.clone(), (self.line, self.column));
+        
+        match self.peek() {
+            Some('{') => {
+                self.bump();
+                loop {
+                    self.skip_whitespace();
+                    match self.peek() {
+                        Some('}') => { self.bump(); break; }
+                        Some(',') => { self.bump(); }
+                        Some('"') => {
+                            let key_position = (self.line, self.column);
+                            let key = self.string();
+                            let child = join_path(&path, &key);
+                            self.skip_whitespace();
+                            self.bump(); // colon
+                            self.value(child.clone());
+                            // Point unknown-field diagnostics at the key rather than the value
+                            self.positions.insert(child, key_position);
+                        }
+                        _ => break,
+                    }
+                }
+            }
+            Some('[') => {
+                self.bump();
+                let mut index = 0;
+                loop {
+                    self.skip_whitespace();
+                    match self.peek() {
+                        Some(']') => { self.bump(); break; }
+                        Some(',') => { self.bump(); }
+                        Some(_) => {
+                            self.value(format!("{}[{}]", path, index));
+                            index += 1;
+                        }
+                        None => break,
+                    }
+                }
+            }
+            Some('"') => { self.string(); }
+            Some(_) => {
+                while matches!(self.peek(), Some(c) if !c.is_whitespace() && c != ',' && c != '}' && c != ']') {
+                    self.bump();
+                }
+            }
+            None => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_reports_unknown_field_with_position() {
+        let source = "{\n  \"project_nme\": \"demo\"\n}";
+        let diagnostics = validate_config_str(source);
+        
+        let unknown = diagnostics.iter().find(|d| d.path == "project_nme").unwrap();
+        assert_eq!(unknown.line, Some(2));
+        assert_eq!(unknown.column, Some(3));
+        assert_eq!(unknown.suggestion.as_deref(), Some("did you mean 'project_name'?"));
+        assert!(diagnostics.iter().any(|d| d.message.contains("Missing required field 'build_steps'")));
+    }
+    
+    #[test]
+    fn test_reports_invalid_json() {
+        let diagnostics = validate_config_str("{ \"project_name\": }");
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0].message.starts_with("Invalid JSON"));
+    }
+}
@@ -0,0 +1,207 @@
+// Containerized build execution for OSland build engine
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::Mutex;
+use std::time::Duration;
+use crate::core::cancellation::CancellationToken;
+use crate::core::network_policy::{check_egress, image_registry, EgressPurpose};
+use super::{build_config::{ContainerConfig, ContainerRuntime}, BuildEngineError};
+
+impl ContainerRuntime {
+    /// Runtime executable
+    pub fn program(&self) -> &'static str {
+        match self {
+            ContainerRuntime::Docker => "docker",
+            ContainerRuntime::Podman => "podman",
+        }
+    }
+}
+
+/// Container executor
+///
+/// Runs build commands inside a pinned container image. Host directories are
+/// mounted at the same absolute path inside the container, so command arguments
+/// referring to source or output files need no translation.
+pub struct ContainerExecutor {
+    /// Container configuration
+    config: ContainerConfig,
+    
+    /// Host directories mounted read-write (absolute paths)
+    mounts: Vec<PathBuf>,
+}
+
+impl ContainerExecutor {
+    /// Create a container executor mounting the given directories
+    pub fn new(config: ContainerConfig, directories: &[&Path]) -> Result<Self, BuildEngineError> {
+        let mut mounts = Vec::new();
+        for dir in directories {
+            std::fs::create_dir_all(dir)
+                .map_err(|e| BuildEngineError::BuildError(format!("Failed to create {}: {}", dir.display(), e)))?;
+            let absolute = dir.canonicalize()
+                .map_err(|e| BuildEngineError::BuildError(format!("Failed to resolve {}: {}", dir.display(), e)))?;
+            if !mounts.contains(&absolute) {
+                mounts.push(absolute);
+            }
+        }
+        Ok(Self { config, mounts })
+    }
+    
+    /// Get the container configuration
+    pub fn config(&self) -> &ContainerConfig {
+        &self.config
+    }
+    
+    /// Make sure the image is available locally, pulling it if needed
+    pub fn ensure_image(&self) -> Result<(), BuildEngineError> {
+        let runtime = self.config.runtime.program();
+        let present = Command::new(runtime)
+            .args(["image", "inspect", &self.config.image])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .map_err(|e| BuildEngineError::CommandError(format!("{}: {}", runtime, e)))?
+            .success();
+        if present {
+            return Ok(());
+        }
+        
+        check_egress(EgressPurpose::Download, image_registry(&self.config.image))
+            .map_err(|e| BuildEngineError::Offline(e.to_string()))?;
+        let status = Command::new(runtime)
+            .args(["pull", &self.config.image])
+            .status()
+            .map_err(|e| BuildEngineError::CommandError(format!("{} pull: {}", runtime, e)))?;
+        if status.success() {
+            Ok(())
+        } else {
+            Err(BuildEngineError::BuildError(format!("Failed to pull container image {}", self.config.image)))
+        }
+    }
+    
+    /// Create a command that runs `program` inside the container
+    ///
+    /// `env` is forwarded into the container; `umask` (if set) is applied before
+    /// the program starts. `workdir` (if set) is the directory the program runs in
+    /// inside the container, overriding the configured one.
+    pub fn command(&self, program: &str, env: &[(String, String)], umask: Option<u32>, workdir: Option<&Path>) -> Command {
+        let mut cmd = Command::new(self.config.runtime.program());
+        cmd.args(["run", "--rm", "-i"]);
+        
+        for mount in &self.mounts {
+            cmd.arg("-v").arg(format!("{}:{}", mount.display(), mount.display()));
+        }
+        for volume in &self.config.volumes {
+            let mode = if volume.read_only { "ro" } else { "rw" };
+            cmd.arg("-v").arg(format!("{}:{}:{}", volume.host.display(), volume.container, mode));
+        }
+        
+        // Otherwise keep the host working directory when it is mounted
+        let workdir = workdir.map(|dir| dir.display().to_string()).or_else(|| self.config.workdir.clone()).or_else(|| {
+            std::env::current_dir().ok()
+                .filter(|cwd| self.mounts.iter().any(|m| cwd.starts_with(m)))
+                .map(|cwd| cwd.display().to_string())
+        });
+        if let Some(workdir) = workdir {
+            cmd.arg("-w").arg(workdir);
+        }
+        
+        if let Some(user) = &self.config.user {
+            cmd.arg("--user").arg(user);
+        }
+        for (key, value) in self.config.env.iter().chain(env) {
+            cmd.arg("-e").arg(format!("{}={}", key, value));
+        }
+        cmd.args(&self.config.extra_args);
+        cmd.arg(&self.config.image);
+        
+        match umask {
+            Some(umask) => {
+                cmd.args(["sh", "-c", &format!("umask {:03o} && exec \"$0\" \"$@\"", umask), program]);
+            }
+            None => {
+                cmd.arg(program);
+            }
+        }
+        cmd
+    }
+}
+
+/// Run a command, passing each output line to `on_line` as it is produced
+///
+/// Stderr lines are prefixed with `[STDERR]`, stdout lines with `[STDOUT]`.
+pub fn run_streaming(cmd: &mut Command, on_line: &(dyn Fn(String) + Sync)) -> std::io::Result<ExitStatus> {
+    run_streaming_cancellable(cmd, &CancellationToken::new(), on_line)
+}
+
+/// Run a command like `run_streaming`, killing it when `token` is canceled
+pub fn run_streaming_cancellable(
+    cmd: &mut Command,
+    token: &CancellationToken,
+    on_line: &(dyn Fn(String) + Sync),
+) -> std::io::Result<ExitStatus> {
+    let mut child: Child = cmd.stdout(Stdio::piped()).stderr(Stdio::piped()).spawn()?;
+    let stdout = child.stdout.take();
+    let stderr = child.stderr.take();
+    let child = Mutex::new(child);
+    
+    std::thread::scope(|scope| {
+        // Kill the process on cancellation; stop watching once it has exited
+        scope.spawn(|| loop {
+            let mut child = child.lock().unwrap();
+            if token.is_canceled() {
+                let _ = child.kill();
+                break;
+            }
+            if !matches!(child.try_wait(), Ok(None)) {
+                break;
+            }
+            drop(child);
+            std::thread::sleep(Duration::from_millis(50));
+        });
+        
+        if let Some(stderr) = stderr {
+            scope.spawn(move || {
+                for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+                    on_line(format!("[STDERR] {}", line));
+                }
+            });
+        }
+        if let Some(stdout) = stdout {
+            for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                on_line(format!("[STDOUT] {}", line));
+            }
+        }
+    });
+    
+    child.into_inner().unwrap().wait()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_command_workdir() {
+        let config = ContainerConfig {
+            runtime: ContainerRuntime::Podman,
+            image: "osland/toolchain:1".to_string(),
+            volumes: Vec::new(),
+            workdir: Some("/work".to_string()),
+            user: None,
+            env: Vec::new(),
+            extra_args: Vec::new(),
+        };
+        let executor = ContainerExecutor::new(config, &[]).unwrap();
+        let args = |cmd: Command| cmd.get_args().map(|arg| arg.to_string_lossy().into_owned()).collect::<Vec<_>>();
+        
+        // The directory is set inside the container, before the image name
+        let configured = args(executor.command("make", &[], None, None));
+        assert_eq!(configured, vec!["run", "--rm", "-i", "-w", "/work", "osland/toolchain:1", "make"]);
+        let explicit = args(executor.command("make", &[], None, Some(Path::new("/src/linux"))));
+        assert_eq!(explicit, vec!["run", "--rm", "-i", "-w", "/src/linux", "osland/toolchain:1", "make"]);
+    }
+}
@@ -0,0 +1,437 @@
+// Source downloader for OSland build engine
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use reqwest::header::{CONTENT_LENGTH, RANGE};
+use reqwest::StatusCode;
+use sha2::{Digest, Sha256};
+use crate::core::network_policy::{EgressPolicy, EgressPurpose};
+use super::{build_config::DownloadConfig, BuildEngineError};
+
+/// Environment variable overriding the shared download cache location
+pub const CACHE_DIR_ENV: &str = "OSLAND_DOWNLOAD_CACHE";
+
+/// Read buffer size
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Time a mirror may stay silent, before sending headers or between two reads of the body
+pub const READ_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Download progress report
+#[derive(Debug, Clone)]
+pub struct DownloadProgress {
+    /// Mirror URL currently in use
+    pub url: String,
+    
+    /// Bytes downloaded so far (including resumed bytes)
+    pub downloaded: u64,
+    
+    /// Total size, if the server reported it
+    pub total: Option<u64>,
+}
+
+impl DownloadProgress {
+    /// Completion percentage (0-100), if the total size is known
+    pub fn percentage(&self) -> Option<u8> {
+        self.total.filter(|t| *t > 0).map(|t| ((self.downloaded.min(t) * 100) / t) as u8)
+    }
+}
+
+/// Downloader with mirror failover, resume and checksum verification
+///
+/// Downloads are kept in a cache shared by all projects, keyed by the expected
+/// SHA-256 (or by the URL when no checksum is configured). Builds fetching the
+/// same entry take turns on the entry's lock file, so only one of them writes the
+/// partial download and the others find the finished file.
+pub struct Downloader {
+    /// Shared cache directory
+    cache_dir: PathBuf,
+    
+    /// HTTP client
+    client: reqwest::blocking::Client,
+    
+    /// Policy the mirrors and their redirects are checked against
+    policy: &'static EgressPolicy,
+    
+    /// Attempts per mirror before failing over
+    attempts_per_mirror: usize,
+}
+
+impl Downloader {
+    /// Create a downloader using the default shared cache
+    pub fn new() -> Result<Self, BuildEngineError> {
+        Self::with_cache_dir(default_cache_dir())
+    }
+    
+    /// Create a downloader using a specific cache directory
+    pub fn with_cache_dir(cache_dir: PathBuf) -> Result<Self, BuildEngineError> {
+        Self::with_policy(cache_dir, EgressPolicy::global(), READ_TIMEOUT)
+    }
+    
+    /// Create a downloader checking its connections against `policy`
+    ///
+    /// A mirror that sends nothing for `read_timeout` fails the attempt, so the
+    /// download fails over instead of hanging. Slow but steady mirrors are not cut off.
+    pub fn with_policy(cache_dir: PathBuf, policy: &'static EgressPolicy, read_timeout: Duration) -> Result<Self, BuildEngineError> {
+        // The blocking client applies its timeout to the response headers and to every read of the body
+        let client = reqwest::blocking::Client::builder()
+            .redirect(policy.redirect_policy(EgressPurpose::Download))
+            .connect_timeout(Duration::from_secs(30))
+            .timeout(read_timeout)
+            .user_agent(concat!("osland/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .map_err(|e| BuildEngineError::BuildError(format!("Failed to create HTTP client: {}", e)))?;
+        
+        Ok(Self {
+            cache_dir,
+            client,
+            policy,
+            attempts_per_mirror: 2,
+        })
+    }
+    
+    /// Get the cache directory
+    pub fn cache_dir(&self) -> &Path {
+        &self.cache_dir
+    }
+    
+    /// Fetch a file, returning its path in the cache
+    pub fn fetch(
+        &self,
+        request: &DownloadConfig,
+        progress: &mut dyn FnMut(&DownloadProgress),
+    ) -> Result<PathBuf, BuildEngineError> {
+        let first_url = request.urls.first()
+            .ok_or_else(|| BuildEngineError::ConfigError("Download has no URLs configured".to_string()))?;
+        let file_name = request.file_name.clone()
+            .or_else(|| first_url.rsplit('/').next().filter(|n| !n.is_empty()).map(|n| n.to_string()))
+            .unwrap_or_else(|| "download".to_string());
+        validate_file_name(&file_name)?;
+        let expected = request.sha256.as_ref().map(|s| s.to_lowercase());
+        if let Some(expected) = &expected {
+            validate_sha256(expected)?;
+        }
+        
+        let key = expected.clone().unwrap_or_else(|| hex_digest(first_url.as_bytes()));
+        let entry_dir = self.cache_dir.join(&key);
+        fs::create_dir_all(&entry_dir)
+            .map_err(|e| BuildEngineError::BuildError(format!("Failed to create download cache {}: {}", entry_dir.display(), e)))?;
+        let target = entry_dir.join(&file_name);
+        let partial = entry_dir.join(format!("{}.part", file_name));
+        
+        // Held until the download is finished, failed or found in the cache
+        let _lock = lock_entry(&entry_dir.join(format!("{}.lock", file_name)))?;
+        
+        // Cache hit, possibly downloaded by another build while this one waited for the lock
+        if target.exists() && verify(&target, expected.as_deref())? {
+            return Ok(target);
+        }
+        
+        let mut errors = Vec::new();
+        let mut denied = None;
+        for url in &request.urls {
+            if let Err(e) = self.policy.check(EgressPurpose::Download, url) {
+                errors.push(format!("{}: {}", url, e));
+                denied.get_or_insert(e);
+                continue;
+            }
+            for attempt in 1..=self.attempts_per_mirror {
+                match self.download(url, &partial, progress) {
+                    Ok(()) => {
+                        if verify(&partial, expected.as_deref())? {
+                            fs::rename(&partial, &target)
+                                .map_err(|e| BuildEngineError::BuildError(format!("Failed to finalize download: {}", e)))?;
+                            return Ok(target);
+                        }
+                        // Corrupt data cannot be resumed; start over from the next mirror
+                        let _ = fs::remove_file(&partial);
+                        errors.push(format!("{}: SHA-256 mismatch", url));
+                        break;
+                    }
+                    Err(e) => errors.push(format!("{} (attempt {}): {}", url, attempt, e)),
+                }
+            }
+        }
+        
+        // Every mirror was blocked, nothing was tried
+        if let Some(denied) = denied.filter(|_| errors.len() == request.urls.len()) {
+            return Err(BuildEngineError::Offline(format!("{} is not in the download cache and {}", file_name, denied)));
+        }
+        Err(BuildEngineError::BuildError(format!("All mirrors failed for {}:\n  {}", file_name, errors.join("\n  "))))
+    }
+    
+    /// Download (or resume) a single URL into `partial`
+    fn download(&self, url: &str, partial: &Path, progress: &mut dyn FnMut(&DownloadProgress)) -> Result<(), String> {
+        let resume_from = fs::metadata(partial).map(|m| m.len()).unwrap_or(0);
+        
+        let mut request = self.client.get(url);
+        if resume_from > 0 {
+            request = request.header(RANGE, format!("bytes={}-", resume_from));
+        }
+        let mut response = request.send().map_err(|e| e.to_string())?;
+        
+        let (mut file, mut downloaded) = match response.status() {
+            StatusCode::PARTIAL_CONTENT => {
+                let file = OpenOptions::new().append(true).open(partial).map_err(|e| e.to_string())?;
+                (file, resume_from)
+            }
+            // The partial file already holds the whole resource
+            StatusCode::RANGE_NOT_SATISFIABLE if resume_from > 0 => return Ok(()),
+            status if status.is_success() => (File::create(partial).map_err(|e| e.to_string())?, 0),
+            status => return Err(format!("HTTP {}", status)),
+        };
+        
+        let total = response.headers().get(CONTENT_LENGTH)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse::<u64>().ok())
+            .map(|len| len + downloaded);
+        
+        let mut buffer = vec![0u8; CHUNK_SIZE];
+        loop {
+            let read = response.read(&mut buffer).map_err(|e| e.to_string())?;
+            if read == 0 {
+                break;
+            }
+            file.write_all(&buffer[..read]).map_err(|e| e.to_string())?;
+            downloaded += read as u64;
+            progress(&DownloadProgress { url: url.to_string(), downloaded, total });
+        }
+        file.flush().map_err(|e| e.to_string())?;
+        
+        match total {
+            Some(total) if downloaded < total => Err(format!("Connection closed after {} of {} bytes", downloaded, total)),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Default shared cache directory
+pub fn default_cache_dir() -> PathBuf {
+    if let Ok(dir) = std::env::var(CACHE_DIR_ENV) {
+        return PathBuf::from(dir);
+    }
+    let base = std::env::var("XDG_CACHE_HOME").map(PathBuf::from)
+        .or_else(|_| std::env::var("HOME").map(|home| PathBuf::from(home).join(".cache")))
+        .or_else(|_| std::env::var("LOCALAPPDATA").map(PathBuf::from))
+        .unwrap_or_else(|_| std::env::temp_dir());
+    base.join("osland").join("downloads")
+}
+
+/// Compute the SHA-256 of a file
+pub fn file_sha256(path: &Path) -> Result<String, BuildEngineError> {
+    let mut file = File::open(path)
+        .map_err(|e| BuildEngineError::BuildError(format!("Failed to open {}: {}", path.display(), e)))?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher)
+        .map_err(|e| BuildEngineError::BuildError(format!("Failed to read {}: {}", path.display(), e)))?;
+    Ok(hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+/// Take the exclusive lock of a cache entry, waiting for other builds
+fn lock_entry(path: &Path) -> Result<File, BuildEngineError> {
+    let lock = OpenOptions::new()
+        .create(true)
+        .write(true)
+        .truncate(false)
+        .open(path)
+        .map_err(|e| BuildEngineError::BuildError(format!("Failed to open {}: {}", path.display(), e)))?;
+    lock.lock()
+        .map_err(|e| BuildEngineError::BuildError(format!("Failed to lock {}: {}", path.display(), e)))?;
+    Ok(lock)
+}
+
+/// Check that a cache file name names a file of its cache entry
+fn validate_file_name(name: &str) -> Result<(), BuildEngineError> {
+    if name.is_empty() || name == "." || name == ".." || name.contains(['/', '\\', '\0']) {
+        return Err(BuildEngineError::ConfigError(format!("Invalid download file name '{}'", name)));
+    }
+    Ok(())
+}
+
+/// Check that a checksum is a SHA-256 in hexadecimal
+fn validate_sha256(sha256: &str) -> Result<(), BuildEngineError> {
+    if sha256.len() != 64 || !sha256.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(BuildEngineError::ConfigError(format!("Invalid SHA-256 '{}': expected 64 hexadecimal digits", sha256)));
+    }
+    Ok(())
+}
+
+fn verify(path: &Path, expected: Option<&str>) -> Result<bool, BuildEngineError> {
+    match expected {
+        Some(expected) => Ok(file_sha256(path)? == expected),
+        None => Ok(true),
+    }
+}
+
+fn hex_digest(bytes: &[u8]) -> String {
+    Sha256::digest(bytes).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::BufRead;
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+    
+    const CONTENT: &[u8] = b"osland source archive";
+    
+    /// Reply of the test mirror to one request, or `None` to stay silent
+    type Reply = Option<Vec<u8>>;
+    
+    /// Serve one reply per connection on a local port, recording the request heads
+    fn mirror(replies: Vec<Reply>) -> (String, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let recorded = requests.clone();
+        thread::spawn(move || {
+            for (stream, reply) in listener.incoming().zip(replies) {
+                let mut stream = stream.unwrap();
+                let mut reader = std::io::BufReader::new(stream.try_clone().unwrap());
+                let mut head = String::new();
+                while reader.read_line(&mut head).unwrap() > 2 {}
+                recorded.lock().unwrap().push(head.to_lowercase());
+                match reply {
+                    Some(reply) => stream.write_all(&reply).unwrap(),
+                    None => thread::sleep(Duration::from_secs(2)),
+                }
+            }
+        });
+        (base, requests)
+    }
+    
+    fn reply(status: &str, body: &[u8]) -> Reply {
+        let mut reply = format!("HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n", status, body.len()).into_bytes();
+        reply.extend_from_slice(body);
+        Some(reply)
+    }
+    
+    fn request(urls: Vec<String>) -> DownloadConfig {
+        DownloadConfig {
+            urls,
+            sha256: Some(hex_digest(CONTENT)),
+            file_name: Some("source.tar.gz".to_string()),
+            strip_components: 1,
+        }
+    }
+    
+    fn downloader(cache: &Path) -> Downloader {
+        Downloader::with_policy(cache.to_path_buf(), Box::leak(Box::new(EgressPolicy::new())), Duration::from_millis(300)).unwrap()
+    }
+    
+    fn partial(cache: &Path, contents: &[u8]) {
+        let entry = cache.join(hex_digest(CONTENT));
+        fs::create_dir_all(&entry).unwrap();
+        fs::write(entry.join("source.tar.gz.part"), contents).unwrap();
+    }
+    
+    #[test]
+    fn test_resumes_partial_download() {
+        let cache = tempfile::tempdir().unwrap();
+        partial(cache.path(), &CONTENT[..7]);
+        let (base, requests) = mirror(vec![reply("206 Partial Content", &CONTENT[7..])]);
+        
+        let mut reports = Vec::new();
+        let path = downloader(cache.path())
+            .fetch(&request(vec![format!("{}/source.tar.gz", base)]), &mut |p| reports.push(p.percentage()))
+            .unwrap();
+        assert_eq!(fs::read(&path).unwrap(), CONTENT);
+        assert!(requests.lock().unwrap()[0].contains("range: bytes=7-"));
+        assert_eq!(reports.last(), Some(&Some(100)));
+        assert!(!path.with_extension("gz.part").exists());
+    }
+    
+    #[test]
+    fn test_complete_partial_file_is_not_downloaded_again() {
+        let cache = tempfile::tempdir().unwrap();
+        partial(cache.path(), CONTENT);
+        let (base, requests) = mirror(vec![reply("416 Range Not Satisfiable", b"")]);
+        
+        let path = downloader(cache.path())
+            .fetch(&request(vec![format!("{}/source.tar.gz", base)]), &mut |_| {})
+            .unwrap();
+        assert_eq!(fs::read(&path).unwrap(), CONTENT);
+        assert!(requests.lock().unwrap()[0].contains(&format!("range: bytes={}-", CONTENT.len())));
+    }
+    
+    #[test]
+    fn test_checksum_mismatch_fails_over_to_next_mirror() {
+        let cache = tempfile::tempdir().unwrap();
+        let (corrupt, corrupt_requests) = mirror(vec![reply("200 OK", b"tampered archive"), reply("200 OK", b"tampered archive")]);
+        let (good, good_requests) = mirror(vec![reply("200 OK", CONTENT)]);
+        
+        let path = downloader(cache.path())
+            .fetch(&request(vec![format!("{}/a.tar.gz", corrupt), format!("{}/b.tar.gz", good)]), &mut |_| {})
+            .unwrap();
+        assert_eq!(fs::read(&path).unwrap(), CONTENT);
+        // Corrupt data is not retried from the same mirror, and is not resumed from
+        assert_eq!(corrupt_requests.lock().unwrap().len(), 1);
+        assert!(!good_requests.lock().unwrap()[0].contains("range:"));
+    }
+    
+    #[test]
+    fn test_stalled_mirror_fails_over() {
+        let cache = tempfile::tempdir().unwrap();
+        let (stalled, _) = mirror(vec![None, None]);
+        let (good, _) = mirror(vec![reply("200 OK", CONTENT)]);
+        
+        let path = downloader(cache.path())
+            .fetch(&request(vec![format!("{}/a.tar.gz", stalled), format!("{}/b.tar.gz", good)]), &mut |_| {})
+            .unwrap();
+        assert_eq!(fs::read(&path).unwrap(), CONTENT);
+    }
+    
+    #[test]
+    fn test_cache_hit_without_egress() {
+        let cache = tempfile::tempdir().unwrap();
+        let entry = cache.path().join(hex_digest(CONTENT));
+        fs::create_dir_all(&entry).unwrap();
+        fs::write(entry.join("source.tar.gz"), CONTENT).unwrap();
+        
+        let policy: &'static EgressPolicy = Box::leak(Box::new(EgressPolicy::new()));
+        policy.set_offline(true);
+        policy.set_allow_loopback(false);
+        let downloader = Downloader::with_policy(cache.path().to_path_buf(), policy, READ_TIMEOUT).unwrap();
+        let (base, requests) = mirror(vec![reply("200 OK", CONTENT)]);
+        
+        let path = downloader.fetch(&request(vec![format!("{}/source.tar.gz", base)]), &mut |_| {}).unwrap();
+        assert_eq!(path, entry.join("source.tar.gz"));
+        
+        // A miss is refused without contacting the mirror
+        let mut missing = request(vec![format!("{}/other.tar.gz", base)]);
+        missing.sha256 = Some(hex_digest(b"other"));
+        assert!(matches!(downloader.fetch(&missing, &mut |_| {}), Err(BuildEngineError::Offline(_))));
+        assert!(requests.lock().unwrap().is_empty());
+        assert_eq!(policy.recent_denials().len(), 1);
+    }
+    
+    #[test]
+    fn test_rejects_names_escaping_the_cache() {
+        let cache = tempfile::tempdir().unwrap();
+        let downloader = downloader(cache.path());
+        let fetch = |file_name: Option<&str>, sha256: Option<String>, url: &str| {
+            let request = DownloadConfig {
+                urls: vec![url.to_string()],
+                sha256,
+                file_name: file_name.map(|n| n.to_string()),
+                strip_components: 1,
+            };
+            downloader.fetch(&request, &mut |_| {})
+        };
+        
+        for name in ["../source.tar.gz", "a/b.tar.gz", "a\\b.tar.gz", "..", "."] {
+            assert!(matches!(fetch(Some(name), None, "http://127.0.0.1:1/x"), Err(BuildEngineError::ConfigError(_))), "{}", name);
+        }
+        assert!(matches!(fetch(None, None, "http://127.0.0.1:1/.."), Err(BuildEngineError::ConfigError(_))));
+        for sha256 in ["../../etc".to_string(), "ab".repeat(31), "zz".repeat(32)] {
+            assert!(matches!(fetch(None, Some(sha256.clone()), "http://127.0.0.1:1/x"), Err(BuildEngineError::ConfigError(_))), "{}", sha256);
+        }
+        assert_eq!(fs::read_dir(cache.path()).unwrap().count(), 0);
+    }
+}
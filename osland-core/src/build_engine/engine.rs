@@ -0,0 +1,1604 @@
+// Build engine core implementation
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use serde::{Deserialize, Serialize};
+use crate::core::architecture::KernelArchitecture;
+use crate::core::audit::{self, AuditCategory};
+use crate::core::event_bus::{self, BuildEvent, SystemEvent};
+use crate::core::cancellation::CancellationToken;
+use crate::core::project::Project;
+use crate::component_manager::{visual_node::NodeCanvas, component::Component};
+use super::reproducible::ReproducibleEnvironment;
+use super::container::{self, ContainerExecutor};
+use super::initramfs::{InitramfsBuilder, InitramfsManifest};
+use crate::architecture_adapter::{Arm64HardwareAdapter, DeviceTreeGenerator};
+use crate::core::architecture::HardwareArchitecture;
+use crate::tile_engine::tile_core::TileGraph;
+use super::test_orchestrator::{TestOrchestrator, TestOutcome, TestPlan, TestReport, TestResult};
+use super::build_history::{BuildHistory, BuildOutcome, BuildRecord, StepTiming};
+use super::build_steps::{BuildStepContext, BuildStepRegistry};
+use super::license_report::LicenseReport;
+use super::oci_publish;
+use crate::kernel_visualization::boot_log::BootLogStore;
+use crate::kernel_visualization::crash_analyzer::{analyze_crashes, CrashReport, Symbolizer};
+use crate::kernel_visualization::trace_ingest::{ComponentMapper, TraceStore};
+use crate::ai_assistant::error_diagnoser::ErrorDiagnoser;
+use crate::core::profiling::{Profile, ProfileSpan};
+use crate::core::simulation;
+use crate::os_design::syscall_designer::{SyscallIssueSeverity, SyscallTable};
+use crate::os_design::scheduler_designer::SchedulerDesign;
+use crate::os_design::security_policy::{PolicyContext, PolicyFormat, PolicyIssueSeverity, SecurityPolicy};
+use crate::os_design::boot_flow::{BootFlow, BootIssueSeverity};
+use crate::os_design::filesystem_designer::{FilesystemDesign, FsIssueSeverity};
+use crate::os_design::virtualization::{CpuVirtExtension, VirtioDevice, VirtualizationMode, VirtualizationProfile};
+use crate::tile_engine::tile_compiler::TargetLanguage;
+use crate::kernel_extractor::{KernelComponent, abi_checker::{AbiChecker, KernelSymbolTable, ModuleRequirements}, component_db::ComponentDatabase};
+use crate::tile_engine::tile_library::TileLibrary;
+use super::{build_config::{BuildConfig, BuildStepType, BuildMode, BuildStep, BootFlowConfig, CustomCommand, DeviceTreeConfig, FilesystemImageConfig, HypervisorImageConfig, LicenseReportConfig, ModuleAbiConfig, OciPublishConfig, SchedulerStepConfig, SecurityPolicyConfig, SyscallConfig}, BuildEngineError};
+
+/// Build engine state
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum BuildState {
+    /// Build is idle
+    Idle,
+    
+    /// Build is in progress
+    Building,
+    
+    /// Build completed successfully
+    Completed,
+    
+    /// Build failed
+    Failed,
+    
+    /// Build was canceled
+    Canceled,
+}
+
+/// Build progress information
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BuildProgress {
+    /// Current build step
+    pub current_step: String,
+    
+    /// Progress percentage (0-100)
+    pub percentage: u8,
+    
+    /// Status message
+    pub status: String,
+    
+    /// Time elapsed in seconds
+    pub time_elapsed: u64,
+    
+    /// Estimated time remaining in seconds
+    pub time_remaining: Option<u64>,
+    
+    /// Build state
+    pub state: BuildState,
+}
+
+/// Build engine core
+pub struct BuildEngine {
+    /// Build configuration
+    config: BuildConfig,
+    
+    /// Project reference
+    project: Arc<Project>,
+    
+    /// Node canvas (visual representation)
+    node_canvas: Arc<NodeCanvas>,
+    
+    /// Current build progress
+    progress: Arc<Mutex<BuildProgress>>,
+    
+    /// Cancellation token of the current build
+    cancel_token: CancellationToken,
+    
+    /// Build log
+    log: Arc<Mutex<Vec<String>>>,
+    
+    /// Resolved reproducible environment (set when a build starts)
+    reproducible_env: Option<ReproducibleEnvironment>,
+    
+    /// Container executor (set when a build starts)
+    container: Option<ContainerExecutor>,
+    
+    /// Report of the last test run
+    test_report: Arc<Mutex<Option<TestReport>>>,
+    
+    /// History every finished build is recorded in
+    history: Option<Arc<BuildHistory>>,
+    
+    /// Store the console output of test runs is ingested into
+    boot_logs: Option<Arc<BootLogStore>>,
+    
+    /// Store the kernel traces printed by test runs are ingested into
+    traces: Option<Arc<TraceStore>>,
+    
+    /// Diagnoser the kernel crashes of test runs are sent to
+    crash_diagnoser: Option<Arc<dyn ErrorDiagnoser + Send + Sync>>,
+    
+    /// Kernel crashes hit by the tests of the current build
+    crashes: Arc<Mutex<Vec<CrashReport>>>,
+    
+    /// Timings of the steps of the current build
+    step_timings: Vec<StepTiming>,
+    
+    /// Cache hits and misses of the current build
+    cache_stats: Arc<Mutex<(u64, u64)>>,
+    
+    /// Profile of the last build
+    profile: Option<Profile>,
+    
+    /// Name build events are published under (the project name unless set)
+    event_source: Option<String>,
+}
+
+impl BuildEngine {
+    /// Create a new build engine
+    pub fn new(config: BuildConfig, project: Arc<Project>, node_canvas: Arc<NodeCanvas>) -> Self {
+        let progress = Arc::new(Mutex::new(BuildProgress {
+            current_step: "Idle".to_string(),
+            percentage: 0,
+            status: "Ready to build".to_string(),
+            time_elapsed: 0,
+            time_remaining: None,
+            state: BuildState::Idle,
+        }));
+        
+        Self {
+            config,
+            project,
+            node_canvas,
+            progress,
+            cancel_token: CancellationToken::new(),
+            log: Arc::new(Mutex::new(vec!["Build engine initialized".to_string()])),
+            reproducible_env: None,
+            container: None,
+            test_report: Arc::new(Mutex::new(None)),
+            history: None,
+            boot_logs: None,
+            traces: None,
+            crash_diagnoser: None,
+            crashes: Arc::new(Mutex::new(Vec::new())),
+            step_timings: Vec::new(),
+            cache_stats: Arc::new(Mutex::new((0, 0))),
+            profile: None,
+            event_source: None,
+        }
+    }
+    
+    /// Record every finished build in a build history
+    pub fn set_history(&mut self, history: Arc<BuildHistory>) {
+        self.history = Some(history);
+    }
+    
+    /// Ingest the boot logs of every test run into a store
+    pub fn set_boot_log_store(&mut self, boot_logs: Arc<BootLogStore>) {
+        self.boot_logs = Some(boot_logs);
+    }
+    
+    /// Ingest the ftrace, perf and ktrace sections of every test run into a store
+    pub fn set_trace_store(&mut self, traces: Arc<TraceStore>) {
+        self.traces = Some(traces);
+    }
+    
+    /// Diagnose the kernel crashes of failed test runs with an error diagnoser
+    pub fn set_crash_diagnoser(&mut self, diagnoser: Arc<dyn ErrorDiagnoser + Send + Sync>) {
+        self.crash_diagnoser = Some(diagnoser);
+    }
+    
+    /// Publish build events under `source` instead of the project name
+    ///
+    /// Gives concurrent builds of one project distinct event sources.
+    pub fn set_event_source(&mut self, source: impl Into<String>) {
+        self.event_source = Some(source.into());
+    }
+    
+    /// Name build events are published under
+    fn event_source(&self) -> String {
+        self.event_source.clone().unwrap_or_else(|| self.config.project_name.clone())
+    }
+    
+    /// Get current build progress
+    pub fn get_progress(&self) -> BuildProgress {
+        simulation::lock(&self.progress).clone()
+    }
+    
+    /// Get build log
+    pub fn get_log(&self) -> Vec<String> {
+        simulation::lock(&self.log).clone()
+    }
+    
+    /// Get the report of the last test run
+    pub fn get_test_report(&self) -> Option<TestReport> {
+        self.test_report.lock().unwrap().clone()
+    }
+    
+    /// Get the kernel crashes hit by the tests of the last build
+    pub fn get_crashes(&self) -> Vec<CrashReport> {
+        self.crashes.lock().unwrap().clone()
+    }
+    
+    /// Get the profile of the last build, for flame graphs of where time went
+    pub fn get_profile(&self) -> Option<Profile> {
+        self.profile.clone()
+    }
+    
+    /// Get the cancellation token of the current (or next) build
+    pub fn cancellation_token(&self) -> CancellationToken {
+        self.cancel_token.clone()
+    }
+    
+    /// Start the build process
+    ///
+    /// A token canceled during a previous build is replaced, so `cancel_build`
+    /// only affects the build it was called for.
+    pub fn build(&mut self) -> Result<PathBuf, BuildEngineError> {
+        if self.cancel_token.is_canceled() {
+            self.cancel_token = CancellationToken::new();
+        }
+        let token = self.cancel_token.clone();
+        self.build_with_cancellation(token)
+    }
+    
+    /// Run the build on the blocking thread pool without stalling the caller
+    ///
+    /// The engine moves to the build thread and is handed back with the result, so no
+    /// lock is held while it builds. Canceling `token` stops the build at the next step
+    /// boundary and kills the running command. Progress is reported through the global event bus.
+    pub async fn build_async(mut self, token: CancellationToken) -> (Self, Result<PathBuf, BuildEngineError>) {
+        let build = tokio::task::spawn_blocking(move || {
+            let result = self.build_with_cancellation(token);
+            (self, result)
+        });
+        match build.await {
+            Ok(finished) => finished,
+            Err(e) => std::panic::resume_unwind(e.into_panic()),
+        }
+    }
+    
+    /// Start the build process, stopping when `token` is canceled
+    ///
+    /// Start, progress, log and completion events are published on the global event bus.
+    pub fn build_with_cancellation(&mut self, token: CancellationToken) -> Result<PathBuf, BuildEngineError> {
+        self.cancel_token = token;
+        let project = self.config.project_name.clone();
+        audit::record(AuditCategory::Build, "build.start", project.clone(), None, Some(format!(
+            "{:?} {:?} build of {:?}", self.config.build_mode, self.config.architecture, self.config.kernel_config.source_path
+        )));
+        event_bus::publish(SystemEvent::Build(BuildEvent::Started { project: self.event_source() }));
+        
+        let started_at = simulation::unix_millis();
+        let start_time = simulation::now();
+        self.crashes.lock().unwrap().clear();
+        let result = self.run_build();
+        let elapsed = simulation::now() - start_time;
+        self.profile = Some(self.build_profile(elapsed));
+        self.record_history(started_at, elapsed, &result);
+        let (success, message) = match &result {
+            Ok(path) => (true, format!("Build completed: {}", path.display())),
+            Err(e) => (false, e.to_string()),
+        };
+        audit::record(AuditCategory::Build, "build.finish", project, None, Some(message.clone()));
+        event_bus::publish(SystemEvent::Build(BuildEvent::Finished { project: self.event_source(), success, message }));
+        
+        result
+    }
+    
+    /// Run all build steps
+    fn run_build(&mut self) -> Result<PathBuf, BuildEngineError> {
+        // Reset state
+        self.reset_build_state();
+        
+        // Set build state to building
+        {
+            let mut progress = self.progress.lock().unwrap();
+            progress.state = BuildState::Building;
+            progress.status = "Starting build process".to_string();
+        }
+        
+        // Log build start
+        self.log_message("=== Build Started ===");
+        self.log_message(format!("Project: {}", self.config.project_name));
+        self.log_message(format!("Architecture: {:?}", self.config.architecture));
+        self.log_message(format!("Build Mode: {:?}", self.config.build_mode));
+        
+        // Start build timer
+        let start_time = simulation::now();
+        
+        // Create output directory
+        self.create_output_dir()?;
+        
+        // Resolve the reproducible environment once for the whole build
+        self.reproducible_env = self.config.reproducible.as_ref()
+            .map(|config| ReproducibleEnvironment::from_config(config, &self.config.kernel_config.source_path));
+        if let Some(env) = &self.reproducible_env {
+            self.log_message(format!("Reproducible build: SOURCE_DATE_EPOCH={}", env.source_date_epoch));
+        }
+        
+        // Prepare the container, mounting the source and output directories
+        self.container = match &self.config.container {
+            Some(container_config) => {
+                let executor = ContainerExecutor::new(
+                    container_config.clone(),
+                    &[&self.config.kernel_config.source_path, &self.config.output_dir],
+                )?;
+                self.log_message(format!("Using container image: {}", container_config.image));
+                executor.ensure_image()?;
+                Some(executor)
+            }
+            None => None,
+        };
+        
+        // Hooks declared in the configuration run around every step
+        let mut hooks = BuildStepRegistry::new();
+        hooks.register_config_hooks(&self.config);
+        
+        // Execute build steps
+        let total_steps = self.config.build_steps.iter().filter(|step| step.enabled).count() as u8;
+        let mut completed_steps = 0;
+        
+        for step in &self.config.build_steps {
+            // Check if build was canceled
+            if self.cancel_token.is_canceled() {
+                self.update_progress(BuildState::Canceled, "Build canceled", completed_steps * 100 / total_steps);
+                self.log_message("Build canceled by user");
+                return Err(BuildEngineError::BuildCanceled);
+            }
+            
+            if !step.enabled {
+                self.log_message(format!("Skipping disabled step: {}", step.name));
+                continue;
+            }
+            
+            // Update progress
+            completed_steps += 1;
+            let percentage = completed_steps * 100 / total_steps;
+            self.update_progress(BuildState::Building, &format!("Executing step: {}", step.name), percentage);
+            self.log_message(format!("=== Step: {} ({}/{}) ===", step.name, completed_steps, total_steps));
+            
+            // Execute the build step
+            let step_start = simulation::now();
+            let mut context = BuildStepContext::new(self.config.clone(), self.config.output_dir.clone(), step.config.clone());
+            let step_result = hooks.run_step(step, &mut context, |_| match step.step_type {
+                BuildStepType::DownloadKernel => self.download_kernel(),
+                BuildStepType::ConfigureKernel => self.configure_kernel(),
+                BuildStepType::BuildKernel => self.build_kernel(),
+                BuildStepType::BuildKernelModules => self.build_kernel_modules(),
+                BuildStepType::CreateRootfs => self.create_rootfs(),
+                BuildStepType::CreateInitramfs => self.create_initramfs(step),
+                BuildStepType::GenerateDeviceTree => self.generate_device_tree(step),
+                BuildStepType::CheckModuleAbi => self.check_module_abi(step),
+                BuildStepType::GenerateSyscalls => self.generate_syscalls(step),
+                BuildStepType::GenerateSchedulerConfig => self.generate_scheduler_config(step),
+                BuildStepType::GenerateSecurityPolicy => self.generate_security_policy(step),
+                BuildStepType::GenerateBootFlow => self.generate_boot_flow(step),
+                BuildStepType::CreateHypervisorImage => self.create_hypervisor_image(step),
+                BuildStepType::CreateFilesystemImage => self.create_filesystem_image(step),
+                BuildStepType::GenerateLicenseReport => self.generate_license_report(step),
+                BuildStepType::PublishArtifacts => self.publish_artifacts(step),
+                BuildStepType::InstallBootloader => self.install_bootloader(),
+                BuildStepType::CreateDiskImage => self.create_disk_image(),
+                BuildStepType::RunTests => self.run_tests(step),
+                BuildStepType::Custom => self.execute_custom_step(step),
+            });
+            self.step_timings.push(StepTiming {
+                name: step.name.clone(),
+                duration_ms: (simulation::now() - step_start).as_millis() as u64,
+                success: step_result.is_ok(),
+            });
+            step_result?;
+            
+            self.log_message(format!("Step completed: {}", step.name));
+        }
+        
+        // Execute custom commands
+        if !self.config.custom_commands.is_empty() {
+            self.log_message("=== Executing Custom Commands ===");
+            
+            for command in &self.config.custom_commands {
+                // Check if build was canceled
+                if self.cancel_token.is_canceled() {
+                    self.update_progress(BuildState::Canceled, "Build canceled", 100);
+                    self.log_message("Build canceled by user");
+                    return Err(BuildEngineError::BuildCanceled);
+                }
+                
+                self.log_message(format!("Executing custom command: {}", command.name));
+                
+                match self.execute_command(command) {
+                    Ok(status) => {
+                        if status.success() {
+                            self.log_message(format!("Custom command completed successfully: {}", command.name));
+                        } else {
+                            if command.continue_on_failure {
+                                self.log_message(format!("Custom command failed but continuing: {}", command.name));
+                            } else {
+                                self.log_message(format!("Custom command failed: {}", command.name));
+                                self.update_progress(BuildState::Failed, "Build failed", 100);
+                                return Err(BuildEngineError::CommandExecutionError(command.name.clone()));
+                            }
+                        }
+                    },
+                    Err(e) => {
+                        if command.continue_on_failure {
+                            self.log_message(format!("Custom command execution error but continuing: {} - {:?}", command.name, e));
+                        } else {
+                            self.log_message(format!("Custom command execution error: {} - {:?}", command.name, e));
+                            self.update_progress(BuildState::Failed, "Build failed", 100);
+                            return Err(e);
+                        }
+                    },
+                }
+            }
+        }
+        
+        // Calculate build time
+        let build_time = (simulation::now() - start_time).as_secs();
+        
+        // Update progress to completed
+        self.update_progress(BuildState::Completed, "Build completed successfully", 100);
+        self.log_message(format!("=== Build Completed ==="));
+        self.log_message(format!("Build time: {} seconds", build_time));
+        
+        // Return path to disk image
+        let disk_image_path = self.config.output_dir.join(format!("{}.img", self.config.project_name));
+        
+        if let Some(env) = &self.reproducible_env {
+            let count = env.normalize_timestamps(&self.config.output_dir)?;
+            self.log_message(format!("Normalized timestamps of {} output files", count));
+            
+            if self.config.reproducible.as_ref().is_some_and(|config| config.archive) {
+                let mut archive = self.config.output_dir.clone().into_os_string();
+                archive.push(".tar");
+                let archive = PathBuf::from(archive);
+                env.create_archive(&self.config.output_dir, &archive)?;
+                self.log_message(format!("Output archived: {}", archive.display()));
+            }
+        }
+        
+        if let Some(signing) = &self.config.signing {
+            let signature_path = super::signing::sign_image(&disk_image_path, signing)?;
+            self.log_message(format!("Image signed: {}", signature_path.display()));
+        }
+        
+        Ok(disk_image_path)
+    }
+    
+    /// Cancel the current build
+    pub fn cancel_build(&mut self) {
+        self.cancel_token.cancel();
+        self.log_message("Build cancellation requested");
+    }
+    
+    /// Profile of the current build: its steps, and the tests under the test step
+    fn build_profile(&self, duration: Duration) -> Profile {
+        let mut build = ProfileSpan::new(format!("build {}", self.config.project_name), 0, duration.as_micros() as u64);
+        let tests = self.test_report.lock().unwrap().clone().unwrap_or_default();
+        let test_steps: Vec<&str> = self.config.build_steps.iter()
+            .filter(|step| step.step_type == BuildStepType::RunTests)
+            .map(|step| step.name.as_str())
+            .collect();
+        
+        // Steps run one after another
+        let mut start_us = 0;
+        for timing in &self.step_timings {
+            let mut step = ProfileSpan::new(timing.name.clone(), start_us, timing.duration_ms * 1000);
+            if test_steps.contains(&timing.name.as_str()) {
+                let mut test_start_us = start_us;
+                for result in &tests.results {
+                    let duration_us = (result.duration_secs * 1_000_000.0) as u64;
+                    step.children.push(ProfileSpan::new(format!("{}::{}", result.suite, result.name), test_start_us, duration_us));
+                    test_start_us += duration_us;
+                }
+            }
+            start_us += step.duration_us;
+            build.children.push(step);
+        }
+        
+        let mut profile = Profile::new(self.config.project_name.clone());
+        profile.roots.push(build);
+        profile
+    }
+    
+    /// Write the current build to the history
+    fn record_history(&mut self, started_at: u64, duration: Duration, result: &Result<PathBuf, BuildEngineError>) {
+        let history = match &self.history {
+            Some(history) => history.clone(),
+            None => return,
+        };
+        let (cache_hits, cache_misses) = *self.cache_stats.lock().unwrap();
+        let record = BuildRecord {
+            build_id: uuid::Uuid::new_v4().to_string(),
+            project: self.config.project_name.clone(),
+            started_at,
+            duration_ms: duration.as_millis() as u64,
+            outcome: match result {
+                Ok(_) => BuildOutcome::Succeeded,
+                Err(BuildEngineError::BuildCanceled) => BuildOutcome::Canceled,
+                Err(_) => BuildOutcome::Failed,
+            },
+            cache_hits,
+            cache_misses,
+            image_size: result.as_ref().ok().and_then(|path| std::fs::metadata(path).ok()).map(|metadata| metadata.len()),
+            steps: std::mem::take(&mut self.step_timings),
+        };
+        // Crashes are attached even when the build row could not be written
+        if let Err(e) = history.record(&record) {
+            self.log_message(format!("Failed to record build history: {}", e));
+        }
+        let crashes = self.crashes.lock().unwrap().clone();
+        if !crashes.is_empty() {
+            if let Err(e) = history.attach_crashes(&record, &crashes) {
+                self.log_message(format!("Failed to attach crash reports: {}", e));
+            }
+        }
+    }
+    
+    /// Reset build state
+    fn reset_build_state(&mut self) {
+        let mut progress = simulation::lock(&self.progress);
+        progress.current_step = "Idle".to_string();
+        progress.percentage = 0;
+        progress.status = "Ready to build".to_string();
+        progress.time_elapsed = 0;
+        progress.time_remaining = None;
+        progress.state = BuildState::Idle;
+        
+        simulation::lock(&self.log).clear();
+        self.step_timings.clear();
+        *self.cache_stats.lock().unwrap() = (0, 0);
+        self.log_message("Build engine state reset");
+    }
+    
+    /// Update build progress
+    fn update_progress(&self, state: BuildState, status: &str, percentage: u8) {
+        let mut progress = simulation::lock(&self.progress);
+        progress.current_step = status.to_string();
+        progress.percentage = percentage;
+        progress.status = status.to_string();
+        progress.state = state;
+        drop(progress);
+        
+        event_bus::publish(SystemEvent::Build(BuildEvent::Progress {
+            project: self.event_source(),
+            status: status.to_string(),
+            percentage,
+        }));
+    }
+    
+    /// Log a message
+    fn log_message(&self, message: impl Into<String>) {
+        let message = message.into();
+        log::info!("{}", message);
+        simulation::lock(&self.log).push(message.clone());
+        event_bus::publish(SystemEvent::Build(BuildEvent::Log {
+            project: self.event_source(),
+            message,
+        }));
+    }
+    
+    /// Create output directory
+    fn create_output_dir(&self) -> Result<(), BuildEngineError> {
+        std::fs::create_dir_all(&self.config.output_dir)
+            .map_err(|e| BuildEngineError::DirectoryCreationError(self.config.output_dir.clone(), e))?;
+        
+        self.log_message(format!("Created output directory: {}", self.config.output_dir.display()));
+        Ok(())
+    }
+    
+    /// Download kernel source code
+    fn download_kernel(&self) -> Result<(), BuildEngineError> {
+        self.log_message("Downloading kernel source...");
+        
+        let source_path = &self.config.kernel_config.source_path;
+        if !source_path.exists() {
+            std::fs::create_dir_all(source_path)
+                .map_err(|e| BuildEngineError::DirectoryCreationError(source_path.clone(), e))?;
+        }
+        
+        // Without a download configuration the source tree is expected to be in place
+        let download = match &self.config.kernel_config.download {
+            Some(download) => download,
+            None => {
+                self.log_message("No download configured, using existing kernel source");
+                return Ok(());
+            }
+        };
+        
+        let downloader = super::downloader::Downloader::new()?;
+        let percentage = self.get_progress().percentage;
+        let mut last_reported = None;
+        let mut downloaded = false;
+        let archive = downloader.fetch(download, &mut |progress| {
+            downloaded = true;
+            let current = progress.percentage();
+            if current != last_reported {
+                last_reported = current;
+                let status = match current {
+                    Some(current) => format!("Downloading {} ({}%)", progress.url, current),
+                    None => format!("Downloading {} ({} bytes)", progress.url, progress.downloaded),
+                };
+                self.update_progress(BuildState::Building, &status, percentage);
+            }
+        })?;
+        self.log_message(format!("Kernel source archive: {}", archive.display()));
+        
+        // No progress means the archive came from the download cache
+        {
+            let mut cache_stats = self.cache_stats.lock().unwrap();
+            if downloaded {
+                cache_stats.1 += 1;
+            } else {
+                cache_stats.0 += 1;
+            }
+        }
+        
+        // Extract into the source directory
+        let archive_arg = archive.to_string_lossy().to_string();
+        let source_arg = source_path.to_string_lossy().to_string();
+        let strip_arg = format!("--strip-components={}", download.strip_components);
+        let status = self.run_command("tar", &["-xf", &archive_arg, "-C", &source_arg, &strip_arg])?;
+        if !status.success() {
+            return Err(BuildEngineError::CommandExecutionError(format!("tar -xf {}", archive_arg)));
+        }
+        
+        self.log_message("Kernel source download completed");
+        Ok(())
+    }
+    
+    /// Configure the kernel
+    fn configure_kernel(&self) -> Result<(), BuildEngineError> {
+        self.log_message("Configuring kernel...");
+        
+        // Check if source directory exists
+        if !self.config.kernel_config.source_path.exists() {
+            return Err(BuildEngineError::DirectoryNotFound(self.config.kernel_config.source_path.clone()));
+        }
+        
+        // Change to kernel source directory
+        let original_dir = std::env::current_dir()?;
+        std::env::set_current_dir(&self.config.kernel_config.source_path)?;
+        
+        // Set environment variables for the toolchain (the container has its own environment)
+        let mut env_vars: Vec<(String, String)> = if self.container.is_some() {
+            Vec::new()
+        } else {
+            std::env::vars().collect()
+        };
+        
+        // Add toolchain path to PATH if specified
+        if let Some(toolchain_path) = &self.config.toolchain_config.toolchain_path {
+            if let Some(path_var) = env_vars.iter_mut().find(|(key, _)| key == "PATH") {
+                path_var.1 = format!("{};{}", toolchain_path.display(), path_var.1);
+            } else {
+                env_vars.push(("PATH".to_string(), toolchain_path.display().to_string()));
+            }
+        }
+        
+        // Set compiler variables for configuration
+        env_vars.push(("CC".to_string(), self.config.toolchain_config.c_compiler.clone()));
+        env_vars.push(("ARCH".to_string(), self.config.architecture.to_string()));
+        env_vars.push(("CROSS_COMPILE".to_string(), self.config.toolchain_config.get_cross_compile_prefix()));
+        
+        // Run make defconfig with the toolchain configuration
+        let mut cmd = self.new_command_with_env("make", &env_vars, None);
+        cmd.args(&["defconfig"]);
+        
+        let status = self.run_logged(&mut cmd, "")
+            .map_err(|e| BuildEngineError::CommandExecutionError(format!("make defconfig: {}", e)))?;
+        
+        if !status.success() {
+            std::env::set_current_dir(original_dir)?;
+            return Err(BuildEngineError::CommandFailed("make defconfig".to_string()));
+        }
+        
+        // Restore original directory
+        std::env::set_current_dir(original_dir)?;
+        
+        self.log_message("Kernel configuration completed");
+        Ok(())
+    }
+    
+    /// Build the kernel
+    fn build_kernel(&self) -> Result<(), BuildEngineError> {
+        self.log_message("Building kernel...");
+        
+        // Check if source directory exists
+        if !self.config.kernel_config.source_path.exists() {
+            return Err(BuildEngineError::DirectoryNotFound(self.config.kernel_config.source_path.clone()));
+        }
+        
+        // Change to kernel source directory
+        let original_dir = std::env::current_dir()?;
+        std::env::set_current_dir(&self.config.kernel_config.source_path)?;
+        
+        // Determine number of CPU cores for parallel build
+        let num_cores = num_cpus::get().to_string();
+        
+        // Set environment variables for the toolchain (the container has its own environment)
+        let mut env_vars: Vec<(String, String)> = if self.container.is_some() {
+            Vec::new()
+        } else {
+            std::env::vars().collect()
+        };
+        
+        // Add toolchain path to PATH if specified
+        if let Some(toolchain_path) = &self.config.toolchain_config.toolchain_path {
+            if let Some(path_var) = env_vars.iter_mut().find(|(key, _)| key == "PATH") {
+                path_var.1 = format!("{};{}", toolchain_path.display(), path_var.1);
+            } else {
+                env_vars.push(("PATH".to_string(), toolchain_path.display().to_string()));
+            }
+        }
+        
+        // Set compiler variables based on toolchain type
+        env_vars.push(("CC".to_string(), self.config.toolchain_config.c_compiler.clone()));
+        env_vars.push(("CXX".to_string(), self.config.toolchain_config.cpp_compiler.clone()));
+        env_vars.push(("AS".to_string(), self.config.toolchain_config.assembler.clone()));
+        env_vars.push(("LD".to_string(), self.config.toolchain_config.linker.clone()));
+        env_vars.push(("STRIP".to_string(), self.config.toolchain_config.strip.clone()));
+        env_vars.push(("OBJCOPY".to_string(), self.config.toolchain_config.objcopy.clone()));
+        env_vars.push(("OBJDUMP".to_string(), self.config.toolchain_config.objdump.clone()));
+        
+        // Add compiler and linker flags
+        let cflags = self.config.compiler_flags.join(" ");
+        let ldflags = self.config.linker_flags.join(" ");
+        env_vars.push(("CFLAGS".to_string(), cflags));
+        env_vars.push(("LDFLAGS".to_string(), ldflags));
+        
+        // Run make with the toolchain configuration
+        let mut cmd = self.new_command_with_env("make", &env_vars, None);
+        cmd.args(&["-j", &num_cores]);
+        
+        let status = self.run_logged(&mut cmd, "")
+            .map_err(|e| BuildEngineError::CommandExecutionError(format!("make: {}", e)))?;
+        
+        if !status.success() {
+            std::env::set_current_dir(original_dir)?;
+            return Err(BuildEngineError::CommandFailed("make".to_string()));
+        }
+        
+        // Restore original directory
+        std::env::set_current_dir(original_dir)?;
+        
+        self.log_message("Kernel build completed");
+        Ok(())
+    }
+    
+    /// Build kernel modules
+    fn build_kernel_modules(&self) -> Result<(), BuildEngineError> {
+        self.log_message("Building kernel modules...");
+        
+        // Check if source directory exists
+        if !self.config.kernel_config.source_path.exists() {
+            return Err(BuildEngineError::DirectoryNotFound(self.config.kernel_config.source_path.clone()));
+        }
+        
+        // Change to kernel source directory
+        let original_dir = std::env::current_dir()?;
+        std::env::set_current_dir(&self.config.kernel_config.source_path)?;
+        
+        // Determine number of CPU cores for parallel build
+        let num_cores = num_cpus::get().to_string();
+        
+        // Set environment variables for the toolchain (the container has its own environment)
+        let mut env_vars: Vec<(String, String)> = if self.container.is_some() {
+            Vec::new()
+        } else {
+            std::env::vars().collect()
+        };
+        
+        // Add toolchain path to PATH if specified
+        if let Some(toolchain_path) = &self.config.toolchain_config.toolchain_path {
+            if let Some(path_var) = env_vars.iter_mut().find(|(key, _)| key == "PATH") {
+                path_var.1 = format!("{};{}", toolchain_path.display(), path_var.1);
+            } else {
+                env_vars.push(("PATH".to_string(), toolchain_path.display().to_string()));
+            }
+        }
+        
+        // Set compiler variables based on toolchain type
+        env_vars.push(("CC".to_string(), self.config.toolchain_config.c_compiler.clone()));
+        env_vars.push(("CXX".to_string(), self.config.toolchain_config.cpp_compiler.clone()));
+        env_vars.push(("AS".to_string(), self.config.toolchain_config.assembler.clone()));
+        env_vars.push(("LD".to_string(), self.config.toolchain_config.linker.clone()));
+        env_vars.push(("STRIP".to_string(), self.config.toolchain_config.strip.clone()));
+        env_vars.push(("OBJCOPY".to_string(), self.config.toolchain_config.objcopy.clone()));
+        env_vars.push(("OBJDUMP".to_string(), self.config.toolchain_config.objdump.clone()));
+        
+        // Add compiler and linker flags
+        let cflags = self.config.compiler_flags.join(" ");
+        let ldflags = self.config.linker_flags.join(" ");
+        env_vars.push(("CFLAGS".to_string(), cflags));
+        env_vars.push(("LDFLAGS".to_string(), ldflags));
+        
+        // Run make modules with the toolchain configuration
+        let mut cmd = self.new_command_with_env("make", &env_vars, None);
+        cmd.args(&["-j", &num_cores, "modules"]);
+        
+        let status = self.run_logged(&mut cmd, "")
+            .map_err(|e| BuildEngineError::CommandExecutionError(format!("make modules: {}", e)))?;
+        
+        if !status.success() {
+            std::env::set_current_dir(original_dir)?;
+            return Err(BuildEngineError::CommandFailed("make modules".to_string()));
+        }
+        
+        // Restore original directory
+        std::env::set_current_dir(original_dir)?;
+        
+        self.log_message("Kernel modules build completed");
+        Ok(())
+    }
+    
+    /// Create root filesystem
+    fn create_rootfs(&self) -> Result<(), BuildEngineError> {
+        self.log_message("Creating root filesystem...");
+        
+        // This is a placeholder implementation
+        // In a real implementation, this would create the root filesystem image
+        
+        // For now, we'll just create an empty file
+        let rootfs_path = self.config.output_dir.join(&self.config.rootfs_config.image_path);
+        std::fs::File::create(rootfs_path)?;
+        
+        self.log_message("Root filesystem creation completed");
+        Ok(())
+    }
+    
+    /// Create an initramfs from the step manifest
+    fn create_initramfs(&self, step: &BuildStep) -> Result<(), BuildEngineError> {
+        self.log_message("Creating initramfs...");
+        
+        let mut manifest: InitramfsManifest = serde_json::from_value(step.config.clone())
+            .map_err(|e| BuildEngineError::ConfigError(format!("Invalid initramfs manifest in step '{}': {}", step.name, e)))?;
+        manifest.resolve_overlays(&self.config.output_dir);
+        let output = self.config.output_dir.join(manifest.output.clone().unwrap_or_else(|| PathBuf::from("initramfs")));
+        
+        // Stamp entries with SOURCE_DATE_EPOCH for reproducible images
+        let mtime = match &self.reproducible_env {
+            Some(env) => env.source_date_epoch.max(0) as u32,
+            None => chrono::Utc::now().timestamp() as u32,
+        };
+        
+        let kernel_config = &self.config.kernel_config;
+        let mut builder = InitramfsBuilder::new(manifest, mtime);
+        let modules = builder.stage(&kernel_config.source_path, &kernel_config.modules, &kernel_config.kernel_version)?;
+        if !modules.is_empty() {
+            self.log_message(format!("Included kernel modules: {}", modules.join(", ")));
+        }
+        
+        let path = builder.write(&output)?;
+        self.log_message(format!("Initramfs created: {}", path.display()));
+        Ok(())
+    }
+    
+    /// Generate the device tree from the hardware canvas and compile it with dtc
+    fn generate_device_tree(&self, step: &BuildStep) -> Result<(), BuildEngineError> {
+        self.log_message("Generating device tree...");
+        
+        let dt_config: DeviceTreeConfig = serde_json::from_value(step.config.clone())
+            .map_err(|e| BuildEngineError::ConfigError(format!("Invalid device tree configuration in step '{}': {}", step.name, e)))?;
+        let architecture = match dt_config.arch.as_str() {
+            "aarch64" | "arm64" => HardwareArchitecture::Aarch64,
+            "riscv64" => HardwareArchitecture::RiscV64,
+            other => return Err(BuildEngineError::ConfigError(format!("Device trees are not supported for '{}'", other))),
+        };
+        
+        let content = std::fs::read_to_string(&dt_config.graph_file)
+            .map_err(|e| BuildEngineError::ConfigError(format!("Failed to read {}: {}", dt_config.graph_file.display(), e)))?;
+        let graph: TileGraph = serde_json::from_str(&content)
+            .map_err(|e| BuildEngineError::ConfigError(format!("Failed to parse {}: {}", dt_config.graph_file.display(), e)))?;
+        
+        let generator = DeviceTreeGenerator::new(architecture.clone(), dt_config.model.clone())
+            .map_err(BuildEngineError::ConfigError)?;
+        let tree = generator.generate(&graph).map_err(BuildEngineError::BuildError)?;
+        
+        // Validate against the hardware adapter where one exists
+        let errors = match architecture {
+            HardwareArchitecture::Aarch64 => tree.validate(&Arm64HardwareAdapter::new()),
+            _ => tree.validate_with_page_size(4096),
+        };
+        if !errors.is_empty() {
+            for error in &errors {
+                self.log_message(format!("[DTS] {}", error));
+            }
+            return Err(BuildEngineError::BuildError(format!("Device tree validation failed with {} error(s)", errors.len())));
+        }
+        
+        let dts_path = self.config.output_dir.join(format!("{}.dts", dt_config.output));
+        let dtb_path = self.config.output_dir.join(format!("{}.dtb", dt_config.output));
+        tree.compile(&dts_path, &dtb_path).map_err(BuildEngineError::BuildError)?;
+        
+        self.log_message(format!("Device tree compiled: {}", dtb_path.display()));
+        Ok(())
+    }
+    
+    /// Generate the syscall dispatch table and userland stubs
+    fn generate_syscalls(&self, step: &BuildStep) -> Result<(), BuildEngineError> {
+        self.log_message("Generating syscall interface...");
+        
+        let syscall_config: SyscallConfig = serde_json::from_value(step.config.clone())
+            .map_err(|e| BuildEngineError::ConfigError(format!("Invalid syscall configuration in step '{}': {}", step.name, e)))?;
+        let language = |name: &str| TargetLanguage::from_name(name)
+            .ok_or_else(|| BuildEngineError::ConfigError(format!("Unknown language '{}' in step '{}'", name, step.name)));
+        let kernel_language = language(&syscall_config.kernel_language)?;
+        let stub_languages = syscall_config.stub_languages.iter().map(|name| language(name)).collect::<Result<Vec<_>, _>>()?;
+        
+        let table = SyscallTable::load(&syscall_config.table_file).map_err(BuildEngineError::ConfigError)?;
+        let issues = table.validate();
+        for issue in &issues {
+            self.log_message(format!("[Syscalls] {}", issue));
+        }
+        let errors = issues.iter().filter(|issue| issue.severity == SyscallIssueSeverity::Error).count();
+        if errors > 0 {
+            return Err(BuildEngineError::BuildError(format!("Syscall table validation failed with {} error(s)", errors)));
+        }
+        
+        let output_dir = self.config.output_dir.join(&syscall_config.output);
+        let written = table.write_artifacts(&output_dir, &kernel_language, &stub_languages)
+            .map_err(BuildEngineError::BuildError)?;
+        
+        self.log_message(format!("Generated {} syscall artifact(s) in {}", written.len(), output_dir.display()));
+        Ok(())
+    }
+    
+    /// Generate the scheduler configuration from a scheduler design
+    fn generate_scheduler_config(&self, step: &BuildStep) -> Result<(), BuildEngineError> {
+        self.log_message("Generating scheduler configuration...");
+        
+        let scheduler_config: SchedulerStepConfig = serde_json::from_value(step.config.clone())
+            .map_err(|e| BuildEngineError::ConfigError(format!("Invalid scheduler configuration in step '{}': {}", step.name, e)))?;
+        let design = SchedulerDesign::load(&scheduler_config.design_file).map_err(BuildEngineError::ConfigError)?;
+        let artifacts = design.generate_config().map_err(BuildEngineError::BuildError)?;
+        
+        let output_dir = self.config.output_dir.join(&scheduler_config.output);
+        std::fs::create_dir_all(&output_dir)
+            .map_err(|e| BuildEngineError::BuildError(format!("Failed to create {}: {}", output_dir.display(), e)))?;
+        for (name, content) in [("scheduler.config", &artifacts.kconfig), ("sched_config.h", &artifacts.header)] {
+            std::fs::write(output_dir.join(name), content)
+                .map_err(|e| BuildEngineError::BuildError(format!("Failed to write {}: {}", name, e)))?;
+        }
+        
+        self.log_message(format!("Scheduler configuration for {} CPU(s) written to {}", design.cpus.len(), output_dir.display()));
+        Ok(())
+    }
+    
+    /// Generate the security policy files of the canvas as a rootfs overlay
+    fn generate_security_policy(&self, step: &BuildStep) -> Result<(), BuildEngineError> {
+        self.log_message("Generating security policy...");
+        
+        let policy_config: SecurityPolicyConfig = serde_json::from_value(step.config.clone())
+            .map_err(|e| BuildEngineError::ConfigError(format!("Invalid security policy configuration in step '{}': {}", step.name, e)))?;
+        let format = PolicyFormat::from_name(&policy_config.format)
+            .ok_or_else(|| BuildEngineError::ConfigError(format!("Unknown policy format '{}'", policy_config.format)))?;
+        let content = std::fs::read_to_string(&policy_config.canvas_file)
+            .map_err(|e| BuildEngineError::ConfigError(format!("Failed to read {}: {}", policy_config.canvas_file.display(), e)))?;
+        let canvas: NodeCanvas = serde_json::from_str(&content)
+            .map_err(|e| BuildEngineError::ConfigError(format!("Failed to parse {}: {}", policy_config.canvas_file.display(), e)))?;
+        
+        let policy = SecurityPolicy::from_canvas(&canvas).map_err(BuildEngineError::ConfigError)?;
+        let issues = policy.validate(&PolicyContext::from_canvas(&canvas));
+        for issue in &issues {
+            self.log_message(format!("[Policy] {}", issue));
+        }
+        let errors = issues.iter().filter(|issue| issue.severity == PolicyIssueSeverity::Error).count();
+        if errors > 0 {
+            return Err(BuildEngineError::BuildError(format!("Security policy validation failed with {} error(s)", errors)));
+        }
+        
+        // Listed in the overlays of a CreateInitramfs step, the files land in the rootfs
+        let output_dir = self.config.output_dir.join(&policy_config.output);
+        let files = policy.write_overlay(&output_dir, format).map_err(BuildEngineError::BuildError)?;
+        
+        self.log_message(format!("Security policy for {} task type(s) written to {} ({} files)", policy.tasks.len(), output_dir.display(), files.len()));
+        Ok(())
+    }
+    
+    /// Validate the boot flow of the canvas and generate its bootloader entry and init scripts
+    fn generate_boot_flow(&self, step: &BuildStep) -> Result<(), BuildEngineError> {
+        self.log_message("Generating boot flow...");
+        
+        let boot_config: BootFlowConfig = serde_json::from_value(step.config.clone())
+            .map_err(|e| BuildEngineError::ConfigError(format!("Invalid boot flow configuration in step '{}': {}", step.name, e)))?;
+        let content = std::fs::read_to_string(&boot_config.canvas_file)
+            .map_err(|e| BuildEngineError::ConfigError(format!("Failed to read {}: {}", boot_config.canvas_file.display(), e)))?;
+        let canvas: NodeCanvas = serde_json::from_str(&content)
+            .map_err(|e| BuildEngineError::ConfigError(format!("Failed to parse {}: {}", boot_config.canvas_file.display(), e)))?;
+        
+        let flow = BootFlow::from_canvas(&canvas).map_err(BuildEngineError::ConfigError)?;
+        let issues = flow.validate(&self.config);
+        for issue in &issues {
+            self.log_message(format!("[Boot] {}", issue));
+        }
+        let errors = issues.iter().filter(|issue| issue.severity == BootIssueSeverity::Error).count();
+        if errors > 0 {
+            return Err(BuildEngineError::BuildError(format!("Boot flow validation failed with {} error(s)", errors)));
+        }
+        
+        // The initramfs and rootfs subdirectories are overlays for the image steps
+        let output_dir = self.config.output_dir.join(&boot_config.output);
+        let files = flow.write(&self.config, &output_dir).map_err(BuildEngineError::BuildError)?;
+        
+        self.log_message(format!("Boot flow of {} stage(s) written to {} ({} files)", flow.stages.len(), output_dir.display(), files.len()));
+        Ok(())
+    }
+    
+    /// Create the firmware plus hypervisor image of a virtualization project
+    fn create_hypervisor_image(&self, step: &BuildStep) -> Result<(), BuildEngineError> {
+        self.log_message("Creating hypervisor image...");
+        
+        let image_config: HypervisorImageConfig = serde_json::from_value(step.config.clone())
+            .map_err(|e| BuildEngineError::ConfigError(format!("Invalid hypervisor image configuration in step '{}': {}", step.name, e)))?;
+        let mode = VirtualizationMode::from_name(&image_config.mode)
+            .ok_or_else(|| BuildEngineError::ConfigError(format!("Unknown virtualization mode '{}'", image_config.mode)))?;
+        let extension = CpuVirtExtension::from_name(&image_config.extension)
+            .ok_or_else(|| BuildEngineError::ConfigError(format!("Unknown virtualization extension '{}'", image_config.extension)))?;
+        
+        let mut profile = VirtualizationProfile::new(mode, extension);
+        profile.nested = image_config.nested;
+        if !image_config.devices.is_empty() {
+            profile.devices = image_config.devices.iter()
+                .map(|name| VirtioDevice::from_name(name).ok_or_else(|| BuildEngineError::ConfigError(format!("Unknown virtio device '{}'", name))))
+                .collect::<Result<_, _>>()?;
+        } else if let Some(tile_graph) = &image_config.tile_graph {
+            let content = std::fs::read_to_string(tile_graph)
+                .map_err(|e| BuildEngineError::ConfigError(format!("Failed to read {}: {}", tile_graph.display(), e)))?;
+            let graph: TileGraph = serde_json::from_str(&content)
+                .map_err(|e| BuildEngineError::ConfigError(format!("Failed to parse {}: {}", tile_graph.display(), e)))?;
+            profile = profile.with_devices_from(&graph);
+        }
+        
+        let output_dir = self.config.output_dir.join(&image_config.output);
+        let esp_dir = output_dir.join("esp");
+        if esp_dir.exists() {
+            std::fs::remove_dir_all(&esp_dir)?;
+        }
+        std::fs::create_dir_all(&esp_dir)
+            .map_err(|e| BuildEngineError::BuildError(format!("Failed to create {}: {}", esp_dir.display(), e)))?;
+        std::fs::write(output_dir.join("virtualization.config"), profile.kconfig())?;
+        
+        // Stage the EFI system partition
+        let kernel = image_config.kernel.clone()
+            .unwrap_or_else(|| self.config.kernel_config.source_path.join("arch/x86/boot/bzImage"));
+        let layout = profile.esp_layout(&kernel, image_config.initrd.as_deref(), image_config.guest_kernel.as_deref(), image_config.guest_initrd.as_deref());
+        for (source, target) in &layout {
+            let destination = esp_dir.join(target);
+            if let Some(parent) = destination.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::copy(source, &destination)
+                .map_err(|e| BuildEngineError::BuildError(format!("Failed to copy {} to the image: {}", source.display(), e)))?;
+        }
+        std::fs::write(esp_dir.join("startup.nsh"), profile.startup_script(image_config.initrd.is_some()))?;
+        std::fs::copy(&image_config.firmware, output_dir.join("firmware.fd"))
+            .map_err(|e| BuildEngineError::ConfigError(format!("Failed to copy firmware {}: {}", image_config.firmware.display(), e)))?;
+        
+        // Pack the partition into a FAT image
+        let image = output_dir.join("hypervisor.img");
+        if image.exists() {
+            std::fs::remove_file(&image)?;
+        }
+        let image_arg = image.display().to_string();
+        let size_kb = (image_config.image_size_mb * 1024).to_string();
+        if !self.run_command("mkfs.vfat", &["-C", &image_arg, &size_kb])?.success() {
+            return Err(BuildEngineError::CommandFailed("mkfs.vfat".to_string()));
+        }
+        let entries: Vec<String> = std::fs::read_dir(&esp_dir)?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path().display().to_string())
+            .collect();
+        let mut args = vec!["-s", "-i", image_arg.as_str()];
+        args.extend(entries.iter().map(|entry| entry.as_str()));
+        args.push("::/");
+        if !self.run_command("mcopy", &args)?.success() {
+            return Err(BuildEngineError::CommandFailed("mcopy".to_string()));
+        }
+        
+        self.log_message(format!("{} image with {} virtio device(s) written to {}", mode.name(), profile.devices.len(), image.display()));
+        Ok(())
+    }
+    
+    /// Create a filesystem image from the canvas and check that a guest kernel mounts it
+    fn create_filesystem_image(&self, step: &BuildStep) -> Result<(), BuildEngineError> {
+        self.log_message("Creating filesystem image...");
+        
+        let fs_config: FilesystemImageConfig = serde_json::from_value(step.config.clone())
+            .map_err(|e| BuildEngineError::ConfigError(format!("Invalid filesystem image configuration in step '{}': {}", step.name, e)))?;
+        let content = std::fs::read_to_string(&fs_config.canvas_file)
+            .map_err(|e| BuildEngineError::ConfigError(format!("Failed to read {}: {}", fs_config.canvas_file.display(), e)))?;
+        let canvas: NodeCanvas = serde_json::from_str(&content)
+            .map_err(|e| BuildEngineError::ConfigError(format!("Failed to parse {}: {}", fs_config.canvas_file.display(), e)))?;
+        
+        let design = FilesystemDesign::from_canvas(&canvas).map_err(BuildEngineError::ConfigError)?;
+        let issues = design.validate();
+        for issue in &issues {
+            self.log_message(format!("[Filesystem] {}", issue));
+        }
+        let errors = issues.iter().filter(|issue| issue.severity == FsIssueSeverity::Error).count();
+        if errors > 0 {
+            return Err(BuildEngineError::BuildError(format!("Filesystem validation failed with {} error(s)", errors)));
+        }
+        
+        let output_dir = self.config.output_dir.join(&fs_config.output);
+        design.write_artifacts(&output_dir).map_err(BuildEngineError::BuildError)?;
+        
+        let image = output_dir.join(&fs_config.image);
+        let image_bytes = design.image_bytes()
+            .ok_or_else(|| BuildEngineError::ConfigError(format!("A {} MiB image is too large", design.block.size_mb)))?;
+        std::fs::File::create(&image)?.set_len(image_bytes)?;
+        let image_arg = image.display().to_string();
+        let mkfs_args = design.mkfs_args();
+        let mut args = vec!["-F"];
+        args.extend(mkfs_args.iter().map(|arg| arg.as_str()));
+        args.push(&image_arg);
+        if !self.run_command("mke2fs", &args)?.success() {
+            return Err(BuildEngineError::CommandFailed("mke2fs".to_string()));
+        }
+        self.log_message(format!("{} image '{}' of {} MiB written to {}", design.fs_type(), design.label, design.block.size_mb, image.display()));
+        
+        if let Some(mut qemu) = fs_config.mount_test {
+            // Default to the disk image produced by this build; the filesystem is the next virtio disk
+            if qemu.kernel.is_none() && qemu.disk_image.is_none() {
+                qemu.disk_image = Some(self.config.output_dir.join(format!("{}.img", self.config.project_name)));
+            }
+            let device = if qemu.disk_image.is_some() { "/dev/vdb" } else { "/dev/vda" };
+            qemu.extra_args.extend(["-drive".to_string(), format!("file={},format=raw,if=virtio", image_arg)]);
+            
+            let plan = TestPlan { qemu: qemu.clone(), suites: vec![design.mount_test_suite(device)], junit_output: None, nested: None };
+            let report = TestOrchestrator::new(qemu).run_plan(&plan, &mut |result| {
+                let status = match &result.outcome {
+                    TestOutcome::Passed => "PASS".to_string(),
+                    TestOutcome::Skipped(message) => format!("SKIP ({})", message),
+                    TestOutcome::Failed(message) | TestOutcome::TimedOut(message) | TestOutcome::Error(message) => format!("FAIL ({})", message),
+                };
+                self.log_message(format!("[TEST] {}::{} {} in {:.1}s", result.suite, result.name, status, result.duration_secs));
+            });
+            if report.failed() > 0 {
+                return Err(BuildEngineError::BuildError(format!("The guest kernel failed to mount {}", image.display())));
+            }
+        }
+        Ok(())
+    }
+    
+    /// Collect the licenses of the image into a NOTICE file and enforce the license policy
+    fn generate_license_report(&self, step: &BuildStep) -> Result<(), BuildEngineError> {
+        self.log_message("Generating license report...");
+        
+        let license_config: LicenseReportConfig = serde_json::from_value(step.config.clone())
+            .map_err(|e| BuildEngineError::ConfigError(format!("Invalid license report configuration in step '{}': {}", step.name, e)))?;
+        let mut report = LicenseReport::new(&self.config.project_name, &self.config.project_version);
+        
+        match &license_config.canvas_file {
+            Some(canvas_file) => {
+                let content = std::fs::read_to_string(canvas_file)
+                    .map_err(|e| BuildEngineError::ConfigError(format!("Failed to read {}: {}", canvas_file.display(), e)))?;
+                let canvas: NodeCanvas = serde_json::from_str(&content)
+                    .map_err(|e| BuildEngineError::ConfigError(format!("Failed to parse {}: {}", canvas_file.display(), e)))?;
+                report.add_canvas(&canvas);
+            }
+            None => report.add_canvas(&self.node_canvas),
+        }
+        for database in &license_config.component_databases {
+            let components = ComponentDatabase::load(database)
+                .and_then(|database| database.all_components())
+                .map_err(|e| BuildEngineError::ConfigError(format!("Failed to load component database {}: {}", database.display(), e)))?;
+            for component in &components {
+                report.add_extracted(component);
+            }
+        }
+        for library in &license_config.tile_libraries {
+            let library = TileLibrary::load_from_file(library).map_err(BuildEngineError::ConfigError)?;
+            report.add_tile_library(&library);
+        }
+        
+        // Generated sources live in the output directory, except for the report itself
+        let output_dir = self.config.output_dir.join(&license_config.output);
+        let generated_dirs: Vec<PathBuf> = if license_config.generated.is_empty() {
+            vec![self.config.output_dir.clone()]
+        } else {
+            license_config.generated.iter().map(|dir| self.config.output_dir.join(dir)).collect()
+        };
+        for dir in generated_dirs.iter().filter(|dir| dir.is_dir()) {
+            let scanned = report.add_generated_sources(dir, std::slice::from_ref(&output_dir)).map_err(BuildEngineError::BuildError)?;
+            self.log_message(format!("[License] Scanned {} generated source file(s) in {}", scanned, dir.display()));
+        }
+        
+        std::fs::create_dir_all(&output_dir)?;
+        std::fs::write(output_dir.join("NOTICE"), report.to_notice())?;
+        let json = serde_json::to_string_pretty(&report)
+            .map_err(|e| BuildEngineError::BuildError(format!("Failed to serialize license report: {}", e)))?;
+        std::fs::write(output_dir.join("licenses.json"), json)?;
+        for (license, count) in report.license_counts() {
+            self.log_message(format!("[License] {}: {}", license, count));
+        }
+        
+        let violations = report.check(&license_config.policy);
+        for violation in &violations {
+            self.log_message(format!("[License] {}", violation));
+        }
+        if !violations.is_empty() && !license_config.warn_only {
+            return Err(BuildEngineError::BuildError(format!("License policy check failed with {} violation(s)", violations.len())));
+        }
+        
+        self.log_message(format!("NOTICE for {} entries written to {}", report.entries.len(), output_dir.display()));
+        Ok(())
+    }
+    
+    /// Push build outputs to an OCI registry
+    fn publish_artifacts(&self, step: &BuildStep) -> Result<(), BuildEngineError> {
+        self.log_message("Publishing artifacts...");
+        
+        let publish_config: OciPublishConfig = serde_json::from_value(step.config.clone())
+            .map_err(|e| BuildEngineError::ConfigError(format!("Invalid publish configuration in step '{}': {}", step.name, e)))?;
+        let report = oci_publish::publish(&publish_config, &self.config)?;
+        for layer in &report.layers {
+            let state = if layer.uploaded { "uploaded" } else { "already present" };
+            self.log_message(format!("[OCI] {} ({}, {} bytes): {}", layer.path.display(), layer.descriptor.media_type, layer.descriptor.size, state));
+        }
+        for tag in &report.tags {
+            self.log_message(format!("Pushed {}:{}@{}", report.reference, tag, report.digest));
+        }
+        Ok(())
+    }
+    
+    /// Check modules against the symbols exported by the target kernel
+    fn check_module_abi(&self, step: &BuildStep) -> Result<(), BuildEngineError> {
+        self.log_message("Checking module ABI compatibility...");
+        
+        let abi_config: ModuleAbiConfig = serde_json::from_value(step.config.clone())
+            .map_err(|e| BuildEngineError::ConfigError(format!("Invalid module ABI configuration in step '{}': {}", step.name, e)))?;
+        let source_path = &self.config.kernel_config.source_path;
+        
+        // Gather the target kernel symbols
+        let mut kernel = KernelSymbolTable::new();
+        let system_map = abi_config.system_map.clone().unwrap_or_else(|| source_path.join("System.map"));
+        if system_map.exists() {
+            kernel.merge(KernelSymbolTable::from_system_map(&system_map).map_err(|e| BuildEngineError::BuildError(e.to_string()))?);
+        } else if let Some(vmlinux) = &abi_config.vmlinux {
+            kernel.merge(KernelSymbolTable::from_vmlinux(vmlinux).map_err(|e| BuildEngineError::BuildError(e.to_string()))?);
+        }
+        let symvers = abi_config.module_symvers.clone().unwrap_or_else(|| source_path.join("Module.symvers"));
+        if symvers.exists() {
+            kernel.merge(KernelSymbolTable::from_module_symvers(&symvers).map_err(|e| BuildEngineError::BuildError(e.to_string()))?);
+        }
+        if kernel.is_empty() {
+            return Err(BuildEngineError::ConfigError("No target kernel symbols found (System.map, vmlinux or Module.symvers)".to_string()));
+        }
+        
+        let checker = AbiChecker::new(kernel);
+        let mut incompatible = 0;
+        for module in &abi_config.modules {
+            let requirements = if module.is_dir() {
+                let mut component = KernelComponent::default();
+                component.name = module.file_name().and_then(|name| name.to_str()).unwrap_or("component").to_string();
+                for entry in std::fs::read_dir(module)?.filter_map(|entry| entry.ok()) {
+                    let path = entry.path();
+                    match path.extension().and_then(|ext| ext.to_str()) {
+                        Some("c") => component.source_files.push(path),
+                        Some("h") => component.header_files.push(path),
+                        _ => {}
+                    }
+                }
+                ModuleRequirements::from_component(&component)
+            } else {
+                ModuleRequirements::from_object(module)
+            }.map_err(|e| BuildEngineError::BuildError(e.to_string()))?;
+            
+            let report = checker.check(&requirements);
+            for issue in &report.issues {
+                self.log_message(format!("[ABI] {}: {}", report.module, issue.message));
+            }
+            if !report.is_compatible() {
+                incompatible += 1;
+            }
+            self.log_message(format!("[ABI] {}: {} symbols checked, {} issue(s)", report.module, report.checked, report.issues.len()));
+        }
+        
+        if incompatible > 0 && !abi_config.warn_only {
+            return Err(BuildEngineError::BuildError(format!("{} module(s) are not ABI compatible with the target kernel", incompatible)));
+        }
+        
+        self.log_message("Module ABI check completed");
+        Ok(())
+    }
+    
+    /// Install bootloader
+    fn install_bootloader(&self) -> Result<(), BuildEngineError> {
+        self.log_message("Installing bootloader...");
+        
+        // This is a placeholder implementation
+        // In a real implementation, this would install the bootloader
+        
+        self.log_message("Bootloader installation completed");
+        Ok(())
+    }
+    
+    /// Create disk image
+    fn create_disk_image(&self) -> Result<(), BuildEngineError> {
+        self.log_message("Creating disk image...");
+        
+        // This is a placeholder implementation
+        // In a real implementation, this would create the final disk image
+        
+        // For now, we'll just create an empty file
+        let disk_image_path = self.config.output_dir.join(format!("{}.img", self.config.project_name));
+        std::fs::File::create(disk_image_path)?;
+        
+        self.log_message("Disk image creation completed");
+        Ok(())
+    }
+    
+    /// Run tests
+    fn run_tests(&self, step: &BuildStep) -> Result<(), BuildEngineError> {
+        self.log_message("Running tests...");
+        
+        // Steps without a test plan have nothing to run
+        if step.config.as_object().is_none_or(|config| config.is_empty()) {
+            self.log_message("No test plan configured, skipping tests");
+            return Ok(());
+        }
+        
+        let mut plan: TestPlan = serde_json::from_value(step.config.clone())
+            .map_err(|e| BuildEngineError::ConfigError(format!("Invalid test plan in step '{}': {}", step.name, e)))?;
+        
+        let mut skip_reason = None;
+        if let Some(nested) = &plan.nested {
+            let extension = CpuVirtExtension::from_name(nested)
+                .ok_or_else(|| BuildEngineError::ConfigError(format!("Unknown virtualization extension '{}'", nested)))?;
+            if !extension.host_nested_enabled() {
+                skip_reason = Some(format!("Nested {} is not enabled in {} on this host", extension.cpu_flag(), extension.kvm_module()));
+            }
+        }
+        
+        // Default to the disk image produced by this build
+        if plan.qemu.kernel.is_none() && plan.qemu.disk_image.is_none() {
+            plan.qemu.disk_image = Some(self.config.output_dir.join(format!("{}.img", self.config.project_name)));
+        }
+        
+        let mut log_result = |result: &TestResult| {
+            let status = match &result.outcome {
+                TestOutcome::Passed => "PASS".to_string(),
+                TestOutcome::Skipped(message) => format!("SKIP ({})", message),
+                TestOutcome::Failed(message) | TestOutcome::TimedOut(message) | TestOutcome::Error(message) => format!("FAIL ({})", message),
+            };
+            self.log_message(format!("[TEST] {}::{} {} in {:.1}s", result.suite, result.name, status, result.duration_secs));
+        };
+        let report = match &skip_reason {
+            // Skipped tests are still reported, so a missing host feature is not mistaken for a pass
+            Some(reason) => {
+                let report = TestReport::skipped_plan(&plan, reason);
+                report.results.iter().for_each(&mut log_result);
+                report
+            }
+            None => TestOrchestrator::new(plan.qemu.clone()).run_plan(&plan, &mut log_result),
+        };
+        
+        if let Some(junit_output) = &plan.junit_output {
+            let path = self.config.output_dir.join(junit_output);
+            report.save_junit(&path)?;
+            self.log_message(format!("JUnit report written to {}", path.display()));
+        }
+        
+        let run_id = uuid::Uuid::new_v4().to_string();
+        if let Some(boot_logs) = self.boot_logs.as_ref().filter(|_| skip_reason.is_none()) {
+            match boot_logs.ingest_report(&self.config.project_name, &run_id, &report) {
+                Ok(events) => {
+                    let failures = events.iter().filter(|event| event.failure).count();
+                    self.log_message(format!("Ingested {} boot log lines ({} failures)", events.len(), failures));
+                }
+                Err(e) => self.log_message(format!("Failed to ingest boot logs: {}", e)),
+            }
+        }
+        if let Some(traces) = self.traces.as_ref().filter(|_| skip_reason.is_none()) {
+            let mut mapper = ComponentMapper::default();
+            mapper.add_canvas(&self.node_canvas);
+            match traces.ingest_report(&self.config.project_name, &run_id, &report, &mapper) {
+                Ok(events) if events.is_empty() => {}
+                Ok(events) => self.log_message(format!("Ingested {} trace events", events.len())),
+                Err(e) => self.log_message(format!("Failed to ingest traces: {}", e)),
+            }
+        }
+        self.analyze_test_crashes(&report);
+        
+        let (passed, failed, skipped) = (report.passed(), report.failed(), report.skipped());
+        *self.test_report.lock().unwrap() = Some(report);
+        
+        self.log_message(format!("Tests completed: {} passed, {} failed, {} skipped", passed, failed, skipped));
+        if failed > 0 {
+            return Err(BuildEngineError::BuildError(format!("{} test(s) failed", failed)));
+        }
+        Ok(())
+    }
+    
+    /// Analyze the kernel crashes printed by failed tests
+    ///
+    /// Frames are symbolicated against the vmlinux in the kernel source tree and
+    /// attributed to the canvas nodes implementing them.
+    fn analyze_test_crashes(&self, report: &TestReport) {
+        let consoles: Vec<&str> = report.results.iter()
+            .filter(|result| !matches!(result.outcome, TestOutcome::Passed | TestOutcome::Skipped(_)))
+            .map(|result| result.console.as_str())
+            .collect();
+        if consoles.is_empty() {
+            return;
+        }
+        
+        let source_root = &self.config.kernel_config.source_path;
+        let vmlinux = source_root.join("vmlinux");
+        let symbolizer = match Symbolizer::load(&vmlinux) {
+            Ok(symbolizer) => Some(symbolizer),
+            Err(_) if !vmlinux.exists() => None,
+            Err(e) => {
+                self.log_message(format!("[Crash] Frames are not symbolicated: {}", e));
+                None
+            }
+        };
+        let mut mapper = ComponentMapper::default();
+        mapper.add_canvas(&self.node_canvas);
+        
+        let architecture = self.config.architecture.to_string();
+        let mut crashes = Vec::new();
+        for console in consoles {
+            for mut crash in analyze_crashes(console, symbolizer.as_ref(), &mapper) {
+                let culprit = crash.culprit.as_ref().map_or("unknown code".to_string(), |frame| frame.describe());
+                let owner = crash.component.as_deref().unwrap_or("no component");
+                self.log_message(format!("[Crash] {} in {} ({})", crash.oops.title, culprit, owner));
+                if let Some(diagnoser) = &self.crash_diagnoser {
+                    if let Err(e) = crash.diagnose(diagnoser.as_ref(), &architecture, Some(source_root)) {
+                        self.log_message(format!("[Crash] Diagnosis failed: {}", e));
+                    }
+                }
+                crashes.push(crash);
+            }
+        }
+        self.crashes.lock().unwrap().extend(crashes);
+    }
+    
+    /// Execute custom build step
+    fn execute_custom_step(&self, step: &BuildStep) -> Result<(), BuildEngineError> {
+        self.log_message(format!("Executing custom step: {}", step.name));
+        
+        // This is a placeholder implementation
+        // In a real implementation, this would execute the custom step
+        
+        self.log_message(format!("Custom step completed: {}", step.name));
+        Ok(())
+    }
+    
+    /// Create a command, applying the container and reproducible environment if enabled
+    fn new_command(&self, program: &str) -> Command {
+        match (&self.container, &self.reproducible_env) {
+            (Some(container), Some(env)) => container.command(program, &env.env_vars(), Some(env.umask), None),
+            (Some(container), None) => container.command(program, &[], None, None),
+            (None, Some(env)) => env.command(program),
+            (None, None) => Command::new(program),
+        }
+    }
+    
+    /// Create a command with extra environment variables and working directory
+    ///
+    /// Inside a container the variables are passed with `-e` and the directory
+    /// with `-w`, since the runtime process environment does not reach the build.
+    fn new_command_with_env(&self, program: &str, env: &[(String, String)], working_dir: Option<&Path>) -> Command {
+        match &self.container {
+            Some(container) => {
+                let mut container_env = env.to_vec();
+                if let Some(reproducible_env) = &self.reproducible_env {
+                    container_env.extend(reproducible_env.env_vars());
+                }
+                // Host directories are mounted at the same path, so an absolute host path works inside
+                let working_dir = working_dir.map(|dir| std::path::absolute(dir).unwrap_or_else(|_| dir.to_path_buf()));
+                container.command(program, &container_env, self.reproducible_env.as_ref().map(|e| e.umask), working_dir.as_deref())
+            }
+            None => {
+                let mut cmd = self.new_command(program);
+                cmd.envs(env.iter().cloned());
+                if let Some(working_dir) = working_dir {
+                    cmd.current_dir(working_dir);
+                }
+                cmd
+            }
+        }
+    }
+    
+    /// Run a command, streaming its output into the build log
+    fn run_logged(&self, cmd: &mut Command, prefix: &str) -> std::io::Result<ExitStatus> {
+        let log = Arc::clone(&self.log);
+        container::run_streaming_cancellable(cmd, &self.cancel_token, &move |line| {
+            let message = if prefix.is_empty() { line } else { format!("{} {}", prefix, line) };
+            log::info!("{}", message);
+            log.lock().unwrap().push(message);
+        })
+    }
+    
+    /// Execute a command
+    fn run_command(&self, command: &str, args: &[&str]) -> Result<ExitStatus, BuildEngineError> {
+        self.log_message(format!("Running command: {} {}", command, args.join(" ")));
+        
+        let status = self.run_logged(self.new_command(command).args(args), "")
+            .map_err(|e| BuildEngineError::CommandExecutionError(format!("{}: {}", command, e)))?;
+        
+        // A killed command reports failure; surface it as a cancellation instead
+        if self.cancel_token.is_canceled() {
+            return Err(BuildEngineError::BuildCanceled);
+        }
+        
+        Ok(status)
+    }
+    
+    /// Execute a custom command
+    fn execute_custom_command(&self, command: &CustomCommand) -> Result<ExitStatus, BuildEngineError> {
+        self.log_message(format!("Executing custom command: {}", command.name));
+        
+        let mut cmd = self.new_command_with_env(&command.command, &command.env, command.working_dir.as_deref());
+        cmd.args(&command.args);
+        
+        let status = self.run_logged(&mut cmd, &format!("{}:", command.name))
+            .map_err(|e| BuildEngineError::CommandExecutionError(format!("{}: {}", command.name, e)))?;
+        
+        Ok(status)
+    }
+    
+    /// Get the current build configuration
+    pub fn get_config(&self) -> &BuildConfig {
+        &self.config
+    }
+    
+    /// Update the build configuration
+    pub fn update_config(&mut self, config: BuildConfig) {
+        self.config = config;
+        self.log_message("Build configuration updated");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::build_engine::build_hooks::{HookConfig, HookPoint};
+    
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_canceled_build_stops_at_the_next_step() {
+        let output = tempfile::tempdir().unwrap();
+        let mut config = BuildConfig::default(KernelArchitecture::Monolithic);
+        config.output_dir = output.path().to_path_buf();
+        config.build_steps = ["first", "second"].iter().map(|name| BuildStep {
+            name: name.to_string(),
+            step_type: BuildStepType::Custom,
+            enabled: true,
+            config: serde_json::Value::Null,
+            dependencies: Vec::new(),
+            timeout: None,
+        }).collect();
+        // The first step runs long enough to be canceled while it runs
+        config.hooks = vec![HookConfig {
+            name: "slow".to_string(),
+            point: HookPoint::PreStep,
+            command: "sleep".to_string(),
+            args: vec!["0.5".to_string()],
+            steps: vec!["first".to_string()],
+            continue_on_failure: false,
+        }];
+        
+        let token = CancellationToken::new();
+        let build = tokio::spawn(BuildEngine::new(config).build_async(token.clone()));
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        token.cancel();
+        let (engine, result) = build.await.unwrap();
+        
+        assert!(matches!(result, Err(BuildEngineError::BuildCanceled)));
+        assert_eq!(engine.get_progress().state, BuildState::Canceled);
+        let log = engine.get_log();
+        assert!(log.iter().any(|line| line.contains("Step completed: first")));
+        assert!(!log.iter().any(|line| line.contains("Step completed: second")));
+    }
+}
@@ -0,0 +1,415 @@
+// Initramfs generation for OSland build engine
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use serde::{Deserialize, Serialize};
+use super::BuildEngineError;
+
+/// Init script used when the manifest does not provide a template
+const DEFAULT_INIT_TEMPLATE: &str = r#"#!/bin/sh
+# Generated by OSland
+mount -t proc none /proc
+mount -t sysfs none /sys
+mount -t devtmpfs none /dev
+
+{{modules}}
+echo "{{banner}}"
+exec {{init}}
+"#;
+
+/// Initramfs compression
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum InitramfsCompression {
+    None,
+    #[default]
+    Gzip,
+    Xz,
+    Zstd,
+    Lz4,
+}
+
+impl InitramfsCompression {
+    /// Compressor command line and file extension
+    fn compressor(&self) -> Option<(&'static str, &'static [&'static str], &'static str)> {
+        match self {
+            InitramfsCompression::None => None,
+            InitramfsCompression::Gzip => Some(("gzip", &["-9", "-n", "-c"], "gz")),
+            // The kernel only accepts CRC32 checksums in xz initramfs images
+            InitramfsCompression::Xz => Some(("xz", &["--check=crc32", "-c"], "xz")),
+            InitramfsCompression::Zstd => Some(("zstd", &["-19", "-q", "-c"], "zst")),
+            // The kernel requires the legacy lz4 frame format
+            InitramfsCompression::Lz4 => Some(("lz4", &["-l", "-9", "-c"], "lz4")),
+        }
+    }
+}
+
+/// File copied into the initramfs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InitramfsEntry {
+    /// Host path
+    pub source: PathBuf,
+    
+    /// Path inside the initramfs (e.g. `/bin/busybox`)
+    pub destination: String,
+    
+    /// Permissions (defaults to the source file's mode, or 0755)
+    #[serde(default)]
+    pub mode: Option<u32>,
+}
+
+/// Initramfs manifest (the `config` of a `CreateInitramfs` step)
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct InitramfsManifest {
+    /// Binaries and other files
+    #[serde(default)]
+    pub files: Vec<InitramfsEntry>,
+    
+    /// Directory trees copied into the initramfs root (relative to the output directory
+    /// unless absolute), e.g. the overlay of a `GenerateSecurityPolicy` step
+    #[serde(default)]
+    pub overlays: Vec<PathBuf>,
+    
+    /// Symlinks: path inside the initramfs -> target
+    #[serde(default)]
+    pub symlinks: BTreeMap<String, String>,
+    
+    /// Kernel modules to include (by name, without `.ko`); defaults to the kernel configuration's modules
+    #[serde(default)]
+    pub modules: Option<Vec<String>>,
+    
+    /// Load the included modules from the init script
+    #[serde(default = "default_true")]
+    pub load_modules: bool,
+    
+    /// Init script template (defaults to a minimal busybox-style script)
+    #[serde(default)]
+    pub init_template: Option<PathBuf>,
+    
+    /// Template variables (`{{name}}`)
+    #[serde(default)]
+    pub variables: BTreeMap<String, String>,
+    
+    /// Compression
+    #[serde(default)]
+    pub compression: InitramfsCompression,
+    
+    /// Output file name (relative to the output directory)
+    #[serde(default)]
+    pub output: Option<PathBuf>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl InitramfsManifest {
+    /// Resolve relative overlay directories against the build output directory
+    pub fn resolve_overlays(&mut self, output_dir: &Path) {
+        for overlay in &mut self.overlays {
+            if overlay.is_relative() {
+                *overlay = output_dir.join(&*overlay);
+            }
+        }
+    }
+}
+
+/// Entry staged for the cpio archive
+enum StagedEntry {
+    Directory,
+    File { data: Vec<u8> },
+    Symlink { target: String },
+}
+
+/// Initramfs builder
+pub struct InitramfsBuilder {
+    /// Manifest
+    manifest: InitramfsManifest,
+    
+    /// Staged entries keyed by path (without leading slash), sorted for reproducibility
+    entries: BTreeMap<String, (StagedEntry, u32)>,
+    
+    /// Modification time of every entry
+    mtime: u32,
+}
+
+impl InitramfsBuilder {
+    /// Create a builder from a manifest
+    ///
+    /// `mtime` is stamped on every entry (use SOURCE_DATE_EPOCH for reproducible images).
+    pub fn new(manifest: InitramfsManifest, mtime: u32) -> Self {
+        let mut builder = Self {
+            manifest,
+            entries: BTreeMap::new(),
+            mtime,
+        };
+        for dir in ["bin", "sbin", "dev", "etc", "lib", "proc", "sys", "tmp", "usr/bin", "usr/sbin"] {
+            builder.add_directory(dir);
+        }
+        builder
+    }
+    
+    /// Add a directory and its parents
+    pub fn add_directory(&mut self, path: &str) {
+        let mut current = String::new();
+        for component in path.trim_matches('/').split('/').filter(|c| !c.is_empty()) {
+            if !current.is_empty() {
+                current.push('/');
+            }
+            current.push_str(component);
+            self.entries.entry(current.clone()).or_insert((StagedEntry::Directory, 0o755));
+        }
+    }
+    
+    /// Add a file with the given content
+    pub fn add_file(&mut self, path: &str, data: Vec<u8>, mode: u32) {
+        let path = path.trim_start_matches('/').to_string();
+        if let Some((parent, _)) = path.rsplit_once('/') {
+            self.add_directory(parent);
+        }
+        self.entries.insert(path, (StagedEntry::File { data }, mode));
+    }
+    
+    /// Add a symlink
+    pub fn add_symlink(&mut self, path: &str, target: &str) {
+        let path = path.trim_start_matches('/').to_string();
+        if let Some((parent, _)) = path.rsplit_once('/') {
+            self.add_directory(parent);
+        }
+        self.entries.insert(path, (StagedEntry::Symlink { target: target.to_string() }, 0o777));
+    }
+    
+    /// Stage the manifest files, kernel modules and init script
+    ///
+    /// Modules are looked up as `<name>.ko` under `modules_dir` (usually the kernel
+    /// build tree) and installed under `/lib/modules/<kernel_version>/`.
+    pub fn stage(&mut self, modules_dir: &Path, default_modules: &[String], kernel_version: &str) -> Result<Vec<String>, BuildEngineError> {
+        // Overlays first so that explicitly listed files take precedence
+        for overlay in self.manifest.overlays.clone() {
+            if !overlay.is_dir() {
+                return Err(BuildEngineError::BuildError(format!("Overlay directory {} does not exist", overlay.display())));
+            }
+            self.stage_overlay(&overlay, "")?;
+        }
+        for entry in self.manifest.files.clone() {
+            let data = fs::read(&entry.source)
+                .map_err(|e| BuildEngineError::BuildError(format!("Failed to read {}: {}", entry.source.display(), e)))?;
+            let mode = entry.mode.unwrap_or_else(|| source_mode(&entry.source));
+            self.add_file(&entry.destination, data, mode);
+        }
+        for (path, target) in self.manifest.symlinks.clone() {
+            self.add_symlink(&path, &target);
+        }
+        
+        // Kernel modules built earlier in the pipeline
+        let wanted = self.manifest.modules.clone().unwrap_or_else(|| default_modules.to_vec());
+        let mut available = BTreeMap::new();
+        find_modules(modules_dir, &mut available);
+        
+        let mut included = Vec::new();
+        for name in &wanted {
+            let path = available.get(name.as_str())
+                .ok_or_else(|| BuildEngineError::BuildError(format!("Kernel module '{}' was not found under {}", name, modules_dir.display())))?;
+            let data = fs::read(path)
+                .map_err(|e| BuildEngineError::BuildError(format!("Failed to read {}: {}", path.display(), e)))?;
+            self.add_file(&format!("lib/modules/{}/{}.ko", kernel_version, name), data, 0o644);
+            included.push(name.clone());
+        }
+        
+        let init = self.render_init(&included, kernel_version)?;
+        self.add_file("init", init.into_bytes(), 0o755);
+        Ok(included)
+    }
+    
+    /// Stage a directory tree under a path inside the initramfs
+    fn stage_overlay(&mut self, dir: &Path, prefix: &str) -> Result<(), BuildEngineError> {
+        let entries = fs::read_dir(dir)
+            .map_err(|e| BuildEngineError::BuildError(format!("Failed to read {}: {}", dir.display(), e)))?;
+        for entry in entries.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            let name = format!("{}{}", prefix, entry.file_name().to_string_lossy());
+            let file_type = entry.file_type()
+                .map_err(|e| BuildEngineError::BuildError(format!("Failed to read {}: {}", path.display(), e)))?;
+            if file_type.is_symlink() {
+                let target = fs::read_link(&path)
+                    .map_err(|e| BuildEngineError::BuildError(format!("Failed to read {}: {}", path.display(), e)))?;
+                self.add_symlink(&name, &target.to_string_lossy());
+            } else if file_type.is_dir() {
+                self.add_directory(&name);
+                self.stage_overlay(&path, &format!("{}/", name))?;
+            } else {
+                let data = fs::read(&path)
+                    .map_err(|e| BuildEngineError::BuildError(format!("Failed to read {}: {}", path.display(), e)))?;
+                self.add_file(&name, data, source_mode(&path));
+            }
+        }
+        Ok(())
+    }
+    
+    /// Render the init script template
+    fn render_init(&self, modules: &[String], kernel_version: &str) -> Result<String, BuildEngineError> {
+        let template = match &self.manifest.init_template {
+            Some(path) => fs::read_to_string(path)
+                .map_err(|e| BuildEngineError::BuildError(format!("Failed to read init template {}: {}", path.display(), e)))?,
+            None => DEFAULT_INIT_TEMPLATE.to_string(),
+        };
+        
+        let module_lines = if self.manifest.load_modules {
+            modules.iter()
+                .map(|m| format!("insmod /lib/modules/{}/{}.ko", kernel_version, m))
+                .collect::<Vec<_>>()
+                .join("\n")
+        } else {
+            String::new()
+        };
+        
+        let mut variables = BTreeMap::new();
+        variables.insert("modules".to_string(), module_lines);
+        variables.insert("banner".to_string(), "Welcome to OSland".to_string());
+        variables.insert("init".to_string(), "/bin/sh".to_string());
+        variables.insert("kernel_version".to_string(), kernel_version.to_string());
+        variables.extend(self.manifest.variables.clone());
+        
+        let mut rendered = template;
+        for (key, value) in &variables {
+            rendered = rendered.replace(&format!("{{{{{}}}}}", key), value);
+        }
+        Ok(rendered)
+    }
+    
+    /// Write the archive (compressed according to the manifest)
+    pub fn write(&self, output: &Path) -> Result<PathBuf, BuildEngineError> {
+        let archive = self.to_cpio();
+        
+        let (data, path) = match self.manifest.compression.compressor() {
+            None => (archive, output.to_path_buf()),
+            Some((program, args, extension)) => {
+                let path = if output.extension().is_some() { output.to_path_buf() } else { output.with_extension(format!("cpio.{}", extension)) };
+                (compress(program, args, &archive)?, path)
+            }
+        };
+        
+        fs::write(&path, data)
+            .map_err(|e| BuildEngineError::BuildError(format!("Failed to write {}: {}", path.display(), e)))?;
+        Ok(path)
+    }
+    
+    /// Encode the staged entries as a `newc` cpio archive
+    pub fn to_cpio(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        let mut ino = 1;
+        
+        for (path, (entry, mode)) in &self.entries {
+            let (kind, data): (u32, &[u8]) = match entry {
+                StagedEntry::Directory => (0o040000, &[]),
+                StagedEntry::File { data } => (0o100000, data),
+                StagedEntry::Symlink { target } => (0o120000, target.as_bytes()),
+            };
+            let nlink = if matches!(entry, StagedEntry::Directory) { 2 } else { 1 };
+            write_cpio_entry(&mut out, ino, kind | mode, nlink, self.mtime, path, data);
+            ino += 1;
+        }
+        
+        write_cpio_entry(&mut out, 0, 0, 1, 0, "TRAILER!!!", &[]);
+        out
+    }
+}
+
+fn write_cpio_entry(out: &mut Vec<u8>, ino: u32, mode: u32, nlink: u32, mtime: u32, name: &str, data: &[u8]) {
+    let name_size = name.len() + 1;
+    let header = format!(
+        "070701{:08X}{:08X}{:08X}{:08X}{:08X}{:08X}{:08X}{:08X}{:08X}{:08X}{:08X}{:08X}{:08X}",
+        ino, mode, 0, 0, nlink, mtime, data.len(), 0, 0, 0, 0, name_size, 0
+    );
+    out.extend_from_slice(header.as_bytes());
+    out.extend_from_slice(name.as_bytes());
+    out.push(0);
+    pad4(out);
+    out.extend_from_slice(data);
+    pad4(out);
+}
+
+fn pad4(out: &mut Vec<u8>) {
+    while out.len() % 4 != 0 {
+        out.push(0);
+    }
+}
+
+fn compress(program: &str, args: &[&str], data: &[u8]) -> Result<Vec<u8>, BuildEngineError> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .map_err(|e| BuildEngineError::CommandError(format!("{}: {}", program, e)))?;
+    
+    // Feed stdin from a separate thread so a full stdout pipe cannot deadlock
+    let mut stdin = child.stdin.take()
+        .ok_or_else(|| BuildEngineError::CommandError(format!("{}: no stdin", program)))?;
+    let input = data.to_vec();
+    let writer = std::thread::spawn(move || stdin.write_all(&input));
+    
+    let output = child.wait_with_output()
+        .map_err(|e| BuildEngineError::CommandError(format!("{}: {}", program, e)))?;
+    writer.join()
+        .map_err(|_| BuildEngineError::CommandError(format!("{}: writer thread panicked", program)))?
+        .map_err(|e| BuildEngineError::CommandError(format!("{}: {}", program, e)))?;
+    
+    if output.status.success() {
+        Ok(output.stdout)
+    } else {
+        Err(BuildEngineError::CommandError(format!("{} exited with {}", program, output.status)))
+    }
+}
+
+fn find_modules(dir: &Path, found: &mut BTreeMap<String, PathBuf>) {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+    for entry in entries.filter_map(|e| e.ok()) {
+        let path = entry.path();
+        if path.is_dir() {
+            find_modules(&path, found);
+        } else if path.extension().map_or(false, |ext| ext == "ko") {
+            if let Some(stem) = path.file_stem() {
+                found.entry(stem.to_string_lossy().to_string()).or_insert(path);
+            }
+        }
+    }
+}
+
+#[cfg(unix)]
+fn source_mode(path: &Path) -> u32 {
+    use std::os::unix::fs::PermissionsExt;
+    fs::metadata(path).map(|m| m.permissions().mode() & 0o7777).unwrap_or(0o755)
+}
+
+#[cfg(not(unix))]
+fn source_mode(_path: &Path) -> u32 {
+    0o755
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_cpio_layout() {
+        let mut builder = InitramfsBuilder::new(InitramfsManifest::default(), 0);
+        builder.add_file("/etc/hostname", b"osland\n".to_vec(), 0o644);
+        let archive = builder.to_cpio();
+        
+        assert!(archive.starts_with(b"070701"));
+        assert_eq!(archive.len() % 4, 0);
+        let text = String::from_utf8_lossy(&archive);
+        assert!(text.contains("etc/hostname\0"));
+        assert!(text.contains("TRAILER!!!\0"));
+        // Entries are sorted, so `bin` precedes `etc/hostname`
+        assert!(text.find("bin\0").unwrap() < text.find("etc/hostname\0").unwrap());
+    }
+}
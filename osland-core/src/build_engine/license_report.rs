@@ -0,0 +1,443 @@
+// License report and NOTICE generation for OSland build engine
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use crate::component_manager::component::Component;
+use crate::component_manager::visual_node::NodeCanvas;
+use crate::kernel_extractor::KernelComponent;
+use crate::kernel_extractor::doc_generator::licenses;
+use crate::tile_engine::tile_library::TileLibrary;
+
+/// Tile property holding the license of a tile (overrides the library license)
+pub const TILE_LICENSE_PROPERTY: &str = "license";
+
+/// Section heading for components without a declared license
+const UNKNOWN_LICENSE: &str = "UNKNOWN";
+
+/// File extensions of generated sources scanned for SPDX headers
+const SOURCE_EXTENSIONS: &[&str] = &["c", "h", "S", "s", "rs", "go", "zig", "v", "mojo", "cpp", "cc", "hpp", "py", "sh", "dts", "dtsi"];
+
+/// `MODULE_LICENSE` strings and their SPDX equivalents
+const MODULE_LICENSES: &[(&str, &str)] = &[
+    ("GPL", "GPL-2.0-or-later"),
+    ("GPL v2", "GPL-2.0-only"),
+    ("GPL and additional rights", "GPL-2.0-or-later"),
+    ("Dual BSD/GPL", "BSD-3-Clause OR GPL-2.0-only"),
+    ("Dual MIT/GPL", "MIT OR GPL-2.0-only"),
+    ("Dual MPL/GPL", "MPL-1.1 OR GPL-2.0-only"),
+    ("Proprietary", "LicenseRef-Proprietary"),
+];
+
+/// Where a licensed piece of the image comes from
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LicenseOrigin {
+    /// Canvas component
+    Component,
+    /// Component extracted from a kernel tree
+    ExtractedComponent,
+    /// Tile of a tile library
+    Tile,
+    /// Source generated during the build
+    Generated,
+}
+
+impl LicenseOrigin {
+    /// Human-readable name
+    pub fn name(&self) -> &'static str {
+        match self {
+            LicenseOrigin::Component => "component",
+            LicenseOrigin::ExtractedComponent => "extracted component",
+            LicenseOrigin::Tile => "tile",
+            LicenseOrigin::Generated => "generated code",
+        }
+    }
+}
+
+/// Licensed piece of the image
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LicenseEntry {
+    /// Component, tile or generated directory name
+    pub name: String,
+    
+    /// Version, when known
+    pub version: Option<String>,
+    
+    /// Where the entry comes from
+    pub origin: LicenseOrigin,
+    
+    /// SPDX license expressions, all of which apply (empty when unknown)
+    pub licenses: Vec<String>,
+    
+    /// Author or source URL
+    pub attribution: Option<String>,
+    
+    /// Files the licenses were read from
+    pub files: Vec<PathBuf>,
+}
+
+impl LicenseEntry {
+    /// Licenses joined into a single expression, or `UNKNOWN`
+    pub fn expression(&self) -> String {
+        match self.licenses.len() {
+            0 => UNKNOWN_LICENSE.to_string(),
+            1 => self.licenses[0].clone(),
+            _ => self.licenses.iter().map(|license| format!("({})", license)).collect::<Vec<_>>().join(" AND "),
+        }
+    }
+}
+
+/// Licenses allowed in an image
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LicensePolicy {
+    /// Forbidden license identifiers (`GPL-3.0` also matches `GPL-3.0-only`, `-or-later` and `+`)
+    #[serde(default)]
+    pub forbidden: Vec<String>,
+    
+    /// If not empty, only these license identifiers are allowed
+    #[serde(default)]
+    pub allowed: Vec<String>,
+    
+    /// Fail on entries without a declared license
+    #[serde(default)]
+    pub deny_unknown: bool,
+}
+
+impl LicensePolicy {
+    /// Whether a single license identifier is acceptable
+    pub fn allows(&self, id: &str) -> bool {
+        !self.forbidden.iter().any(|pattern| license_matches(id, pattern))
+            && (self.allowed.is_empty() || self.allowed.iter().any(|pattern| license_matches(id, pattern)))
+    }
+    
+    /// Whether an SPDX expression is acceptable: one of its `OR` alternatives must only use allowed licenses
+    pub fn allows_expression(&self, expression: &str) -> bool {
+        expression_alternatives(expression).iter().any(|ids| ids.iter().all(|id| self.allows(id)))
+    }
+}
+
+/// Entry violating the license policy
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LicenseViolation {
+    /// Entry name
+    pub entry: String,
+    
+    /// Where the entry comes from
+    pub origin: LicenseOrigin,
+    
+    /// Offending expression (None when the license is unknown)
+    pub license: Option<String>,
+}
+
+impl fmt::Display for LicenseViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.license {
+            Some(license) => write!(f, "error: {} '{}' is licensed under {}, which the policy forbids", self.origin.name(), self.entry, license),
+            None => write!(f, "error: {} '{}' declares no license", self.origin.name(), self.entry),
+        }
+    }
+}
+
+/// Licenses of everything going into an image
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LicenseReport {
+    /// Project name
+    pub project: String,
+    
+    /// Project version
+    pub version: String,
+    
+    /// Licensed entries
+    pub entries: Vec<LicenseEntry>,
+}
+
+impl LicenseReport {
+    /// Create an empty report
+    pub fn new(project: &str, version: &str) -> Self {
+        Self {
+            project: project.to_string(),
+            version: version.to_string(),
+            entries: Vec::new(),
+        }
+    }
+    
+    /// Add a canvas component
+    pub fn add_component(&mut self, component: &Component) {
+        let license = component.license.trim();
+        self.entries.push(LicenseEntry {
+            name: component.name.clone(),
+            version: Some(component.version.clone()).filter(|version| !version.is_empty()),
+            origin: LicenseOrigin::Component,
+            licenses: if license.is_empty() { Vec::new() } else { vec![normalize_license(license)] },
+            attribution: component.source_url.clone().or_else(|| Some(component.author.clone())).filter(|author| !author.is_empty()),
+            files: component.implementation_files.iter().map(PathBuf::from).collect(),
+        });
+    }
+    
+    /// Add the components of a canvas, once per component ID
+    pub fn add_canvas(&mut self, canvas: &NodeCanvas) {
+        let mut components: BTreeMap<&str, &Component> = BTreeMap::new();
+        for node in canvas.nodes.values() {
+            components.entry(node.component_id.as_str()).or_insert(&node.component);
+        }
+        for component in components.values() {
+            self.add_component(component);
+        }
+    }
+    
+    /// Add an extracted component
+    ///
+    /// The licenses recorded by the documentation generator are used when present,
+    /// otherwise the sources are scanned for SPDX tags and `MODULE_LICENSE`.
+    pub fn add_extracted(&mut self, component: &KernelComponent) {
+        let recorded: Option<Vec<String>> = component.metadata.get("docs")
+            .and_then(|docs| docs.get("licenses"))
+            .and_then(|licenses| serde_json::from_value(licenses.clone()).ok());
+        let files: Vec<PathBuf> = component.source_files.iter().chain(&component.header_files).cloned().collect();
+        let found = recorded.unwrap_or_else(|| {
+            let sources: Vec<String> = files.iter().filter_map(|file| fs::read_to_string(file).ok()).collect();
+            licenses(&sources)
+        });
+        
+        self.entries.push(LicenseEntry {
+            name: component.name.clone(),
+            version: None,
+            origin: LicenseOrigin::ExtractedComponent,
+            licenses: normalize_all(&found),
+            attribution: None,
+            files,
+        });
+    }
+    
+    /// Add the tiles of a library
+    pub fn add_tile_library(&mut self, library: &TileLibrary) {
+        let metadata = library.get_metadata();
+        let mut tile_ids = library.get_all_tile_ids();
+        tile_ids.sort();
+        for tile in tile_ids.iter().filter_map(|id| library.get_tile_by_id(id).ok()) {
+            let license = tile.get_property(TILE_LICENSE_PROPERTY).or(metadata.license.as_ref()).map(|license| license.trim());
+            self.entries.push(LicenseEntry {
+                name: format!("{}/{}", metadata.name, tile.name),
+                version: Some(tile.version.clone()).filter(|version| !version.is_empty()),
+                origin: LicenseOrigin::Tile,
+                licenses: license.filter(|license| !license.is_empty()).map(normalize_license).into_iter().collect(),
+                attribution: Some(tile.author.clone()).filter(|author| !author.is_empty()),
+                files: Vec::new(),
+            });
+        }
+    }
+    
+    /// Add the sources generated under a directory, one entry per directory
+    ///
+    /// Returns the number of source files scanned.
+    pub fn add_generated_sources(&mut self, root: &Path, exclude: &[PathBuf]) -> Result<usize, String> {
+        let mut directories: BTreeMap<PathBuf, (BTreeSet<String>, Vec<PathBuf>)> = BTreeMap::new();
+        let mut pending = vec![root.to_path_buf()];
+        let mut scanned = 0;
+        while let Some(dir) = pending.pop() {
+            let entries = fs::read_dir(&dir).map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?;
+            for entry in entries.filter_map(|entry| entry.ok()) {
+                let path = entry.path();
+                if exclude.iter().any(|excluded| path.starts_with(excluded)) {
+                    continue;
+                }
+                let file_type = match entry.file_type() {
+                    Ok(file_type) => file_type,
+                    Err(_) => continue,
+                };
+                if file_type.is_dir() {
+                    pending.push(path);
+                    continue;
+                }
+                let is_source = path.extension().and_then(|ext| ext.to_str()).is_some_and(|ext| SOURCE_EXTENSIONS.contains(&ext));
+                if !file_type.is_file() || !is_source {
+                    continue;
+                }
+                let Ok(content) = fs::read_to_string(&path) else { continue };
+                scanned += 1;
+                
+                let relative = path.strip_prefix(root).unwrap_or(&path);
+                let group = directories.entry(relative.parent().map(Path::to_path_buf).unwrap_or_default()).or_default();
+                group.0.extend(licenses(&[content]));
+                group.1.push(relative.to_path_buf());
+            }
+        }
+        
+        for (dir, (found, mut files)) in directories {
+            files.sort();
+            let found: Vec<String> = found.into_iter().collect();
+            let name = if dir.as_os_str().is_empty() { ".".to_string() } else { dir.display().to_string() };
+            self.entries.push(LicenseEntry {
+                name,
+                version: None,
+                origin: LicenseOrigin::Generated,
+                licenses: normalize_all(&found),
+                attribution: None,
+                files,
+            });
+        }
+        Ok(scanned)
+    }
+    
+    /// Entries violating a policy
+    pub fn check(&self, policy: &LicensePolicy) -> Vec<LicenseViolation> {
+        let mut violations = Vec::new();
+        for entry in &self.entries {
+            if entry.licenses.is_empty() {
+                if policy.deny_unknown {
+                    violations.push(LicenseViolation { entry: entry.name.clone(), origin: entry.origin, license: None });
+                }
+                continue;
+            }
+            for license in entry.licenses.iter().filter(|license| !policy.allows_expression(license)) {
+                violations.push(LicenseViolation { entry: entry.name.clone(), origin: entry.origin, license: Some(license.clone()) });
+            }
+        }
+        violations
+    }
+    
+    /// Number of entries per license expression
+    pub fn license_counts(&self) -> BTreeMap<String, usize> {
+        let mut counts = BTreeMap::new();
+        for entry in &self.entries {
+            *counts.entry(entry.expression()).or_insert(0) += 1;
+        }
+        counts
+    }
+    
+    /// Render the NOTICE file, grouping entries by license
+    pub fn to_notice(&self) -> String {
+        let mut groups: BTreeMap<(bool, String), Vec<&LicenseEntry>> = BTreeMap::new();
+        for entry in &self.entries {
+            // Unknown licenses are listed last
+            groups.entry((entry.licenses.is_empty(), entry.expression())).or_default().push(entry);
+        }
+        
+        let mut notice = format!("NOTICE for {} {}\n\n", self.project, self.version);
+        notice.push_str("This image contains the software listed below, grouped by license.\n");
+        for ((_, license), mut entries) in groups {
+            entries.sort_by(|a, b| (a.origin, &a.name).cmp(&(b.origin, &b.name)));
+            notice.push_str(&format!("\n{}\n{}\n\n", license, "=".repeat(license.len())));
+            for entry in entries {
+                notice.push_str(&format!("  - {}", entry.name));
+                if let Some(version) = &entry.version {
+                    notice.push_str(&format!(" {}", version));
+                }
+                notice.push_str(&format!(" ({})", entry.origin.name()));
+                if let Some(attribution) = &entry.attribution {
+                    notice.push_str(&format!(", {}", attribution));
+                }
+                notice.push('\n');
+            }
+        }
+        notice
+    }
+}
+
+/// Map a `MODULE_LICENSE` string to SPDX; SPDX expressions are kept
+pub fn normalize_license(license: &str) -> String {
+    let license = license.trim();
+    MODULE_LICENSES.iter()
+        .find(|(module_license, _)| module_license.eq_ignore_ascii_case(license))
+        .map(|(_, spdx)| spdx.to_string())
+        .unwrap_or_else(|| license.to_string())
+}
+
+fn normalize_all(licenses: &[String]) -> Vec<String> {
+    let normalized: BTreeSet<String> = licenses.iter().map(|license| normalize_license(license)).collect();
+    normalized.into_iter().collect()
+}
+
+/// Whether a license identifier matches a policy pattern (`GPL-3.0` matches `GPL-3.0-or-later`)
+pub fn license_matches(id: &str, pattern: &str) -> bool {
+    let id = id.to_ascii_lowercase();
+    let pattern = pattern.trim().to_ascii_lowercase();
+    id == pattern
+        || id.strip_prefix(pattern.as_str()).is_some_and(|suffix| ["-only", "-or-later", "+"].contains(&suffix))
+}
+
+/// License identifiers of each `OR` alternative of an SPDX expression
+///
+/// Parentheses are flattened and `WITH` exceptions are dropped, which is exact for
+/// the expressions found in kernel sources (`GPL-2.0 OR MIT`, `GPL-2.0 WITH Linux-syscall-note`).
+pub fn expression_alternatives(expression: &str) -> Vec<Vec<String>> {
+    let flattened = expression.replace(['(', ')'], " ");
+    let tokens: Vec<&str> = flattened.split_whitespace().collect();
+    let mut alternatives = vec![Vec::new()];
+    let mut skip_next = false;
+    for token in tokens {
+        if skip_next {
+            skip_next = false;
+            continue;
+        }
+        match token.to_ascii_uppercase().as_str() {
+            "OR" => alternatives.push(Vec::new()),
+            "AND" => {}
+            "WITH" => skip_next = true,
+            _ => alternatives.last_mut().expect("at least one alternative").push(token.to_string()),
+        }
+    }
+    alternatives.retain(|ids| !ids.is_empty());
+    alternatives
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tile_engine::tile_core::{Tile, TileType};
+    
+    #[test]
+    fn test_license_report_and_policy() {
+        let mut report = LicenseReport::new("demo", "1.0.0");
+        let dir = std::env::temp_dir().join(format!("osland-licenses-{}", std::process::id()));
+        let out = dir.join("out");
+        fs::create_dir_all(dir.join("ext4")).unwrap();
+        fs::create_dir_all(out.join("syscalls")).unwrap();
+        fs::create_dir_all(out.join("licenses")).unwrap();
+        
+        let extracted = KernelComponent {
+            name: "ext4".to_string(),
+            source_files: vec![dir.join("ext4/super.c")],
+            header_files: vec![dir.join("ext4/ext4.h")],
+            ..Default::default()
+        };
+        fs::write(&extracted.source_files[0], "// SPDX-License-Identifier: GPL-2.0\nMODULE_LICENSE(\"Dual BSD/GPL\");\n").unwrap();
+        fs::write(&extracted.header_files[0], "/* SPDX-License-Identifier: GPL-2.0 WITH Linux-syscall-note */\n").unwrap();
+        report.add_extracted(&extracted);
+        
+        let mut library = TileLibrary::new("Vendor".to_string(), "Vendor tiles".to_string());
+        let mut tile = Tile::new("crypto".to_string(), TileType::Processing, "Crypto engine".to_string());
+        tile.set_property(TILE_LICENSE_PROPERTY.to_string(), "GPL-3.0-or-later".to_string());
+        library.add_tile("security".to_string(), tile).unwrap();
+        report.add_tile_library(&library);
+        
+        fs::write(out.join("syscalls/table.c"), "// SPDX-License-Identifier: MIT\nint x;\n").unwrap();
+        fs::write(out.join("syscalls/stubs.rs"), "fn main() {}\n").unwrap();
+        fs::write(out.join("licenses/ignored.c"), "// SPDX-License-Identifier: Proprietary\n").unwrap();
+        assert_eq!(report.add_generated_sources(&out, &[out.join("licenses")]).unwrap(), 2);
+        fs::remove_dir_all(&dir).unwrap();
+        
+        let ext4 = &report.entries[0];
+        assert_eq!(ext4.licenses, vec!["BSD-3-Clause OR GPL-2.0-only", "GPL-2.0", "GPL-2.0 WITH Linux-syscall-note"]);
+        assert_eq!(report.entries[2].name, "syscalls");
+        assert_eq!(report.entries[2].licenses, vec!["MIT"]);
+        
+        // The dual licensed module may be used under BSD, its GPL-only files may not
+        let policy = LicensePolicy { forbidden: vec!["GPL-2.0".to_string(), "GPL-3.0".to_string()], ..Default::default() };
+        let violations = report.check(&policy);
+        assert_eq!(violations.len(), 3);
+        assert_eq!(violations[1].license.as_deref(), Some("GPL-2.0 WITH Linux-syscall-note"));
+        assert_eq!(violations[2].entry, "Vendor/crypto");
+        assert!(report.check(&LicensePolicy::default()).is_empty());
+        
+        let notice = report.to_notice();
+        assert!(notice.starts_with("NOTICE for demo 1.0.0"));
+        assert!(notice.contains("GPL-3.0-or-later\n================\n\n  - Vendor/crypto"));
+        assert!(notice.contains("  - syscalls (generated code)\n"));
+        assert_eq!(report.license_counts().get("MIT"), Some(&1));
+    }
+}
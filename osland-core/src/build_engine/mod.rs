@@ -0,0 +1,226 @@
+// Build Engine module for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+pub mod engine;
+pub mod build_config;
+pub mod builders;
+pub mod image_generator;
+pub mod build_steps;
+pub mod config_validator;
+pub mod artifact_store;
+pub mod reproducible;
+pub mod signing;
+pub mod build_hooks;
+pub mod downloader;
+pub mod container;
+pub mod initramfs;
+pub mod test_orchestrator;
+pub mod build_history;
+pub mod license_report;
+pub mod project_export;
+pub mod project_import;
+pub mod oci_publish;
+
+// Export build engine components
+pub use engine::{BuildEngine, BuildState, BuildProgress};
+pub use build_config::{BuildConfig, BuildMode, BuildStepType, BuildStep, CustomCommand, DownloadConfig, ContainerConfig, ContainerRuntime, ReproducibleConfig, SigningConfig, SignatureScheme, ModuleAbiConfig, OciArtifact, OciArtifactKind, OciPublishConfig};
+pub use build_steps::{BuildStepContext, BuildStepExecutor, BuildStepRegistry, create_default_build_step_registry};
+pub use config_validator::{ConfigDiagnostic, Severity, validate_config_file, validate_config_str};
+pub use artifact_store::{ArtifactStore, ArtifactRecord, RetentionPolicy, CleanReport};
+pub use reproducible::ReproducibleEnvironment;
+pub use build_hooks::{BuildHook, HookConfig, HookPoint, ScriptHook};
+pub use downloader::{Downloader, DownloadProgress};
+pub use container::ContainerExecutor;
+pub use initramfs::{InitramfsBuilder, InitramfsManifest, InitramfsCompression};
+pub use test_orchestrator::{TestOrchestrator, TestPlan, TestSuite, TestReport, TestResult, TestOutcome, QemuConfig};
+pub use build_history::{BuildHistory, BuildRecord, BuildOutcome, BuildMetric, BuildComparison, StepTiming, Trend};
+pub use license_report::{LicenseReport, LicenseEntry, LicenseOrigin, LicensePolicy, LicenseViolation};
+pub use project_export::{ProjectExporter, ExportFormat, ExportedProject};
+pub use project_import::{ProjectImporter, ImportedProject, ImportedPackage, ImportSource, KconfigFile, KconfigValue, BitbakeFile};
+pub use oci_publish::{OciPublisher, PublishReport, PublishedLayer, RegistryCredentials, DockerConfig};
+
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use crate::ai_assistant::error_diagnoser::ErrorDiagnoser;
+use crate::core::cancellation::CancellationToken;
+use crate::dbos_integration::dbos_core::SharedTables;
+use crate::kernel_visualization::boot_log::BootLogStore;
+use crate::kernel_visualization::trace_ingest::TraceStore;
+use crate::core::event_bus::{self, ProjectEvent, SystemEvent};
+use crate::i18n::LocalizedError;
+
+// Build an operating system image from a configuration file
+pub fn build_image(config_path: String, output_path: String) -> Result<(), BuildEngineError> {
+    build_image_with_cancellation(config_path, output_path, None, CancellationToken::new(), BuildServices::default())
+}
+
+// Stores and services a build reports to
+#[derive(Clone, Default)]
+pub struct BuildServices {
+    // DBOS tables (of every namespace or of one) the build history, crashes and traces of the build are recorded in
+    pub tables: Option<SharedTables>,
+    
+    // Diagnoser the kernel crashes of failed tests are sent to
+    pub crash_diagnoser: Option<Arc<dyn ErrorDiagnoser + Send + Sync>>,
+    
+    // Artifact store the image of a successful build is registered in
+    pub artifact_store: Option<Arc<Mutex<ArtifactStore>>>,
+}
+
+// Build an operating system image, stopping when `token` is canceled
+//
+// Build events are published under `event_source` when given, otherwise under the project name.
+// The diagnoses of kernel crashes hit by the tests are logged once the build ends.
+pub fn build_image_with_cancellation(
+    config_path: String,
+    output_path: String,
+    event_source: Option<String>,
+    token: CancellationToken,
+    services: BuildServices,
+) -> Result<(), BuildEngineError> {
+    let artifact_store = services.artifact_store.clone();
+    let mut engine = prepare_engine(config_path, event_source, services)?;
+    let result = engine.build_with_cancellation(token);
+    finish_build(&engine, result, output_path, artifact_store)
+}
+
+// Build an operating system image on the blocking thread pool, stopping when `token` is canceled
+pub async fn build_image_async(
+    config_path: String,
+    output_path: String,
+    event_source: Option<String>,
+    token: CancellationToken,
+    services: BuildServices,
+) -> Result<(), BuildEngineError> {
+    let artifact_store = services.artifact_store.clone();
+    let engine = prepare_engine(config_path, event_source, services)?;
+    let (engine, result) = engine.build_async(token).await;
+    finish_build(&engine, result, output_path, artifact_store)
+}
+
+// Load a build configuration into an engine reporting to `services`
+fn prepare_engine(config_path: String, event_source: Option<String>, services: BuildServices) -> Result<engine::BuildEngine, BuildEngineError> {
+    let config = build_config::BuildConfig::from_file(config_path)
+        .map_err(|e| BuildEngineError::ConfigError(format!("Failed to load build configuration: {}", e)))?;
+    let mut engine = engine::BuildEngine::new(config);
+    if let Some(source) = event_source {
+        engine.set_event_source(source);
+    }
+    if let Some(tables) = services.tables {
+        attach_stores(&mut engine, tables);
+    }
+    if let Some(diagnoser) = services.crash_diagnoser {
+        engine.set_crash_diagnoser(diagnoser);
+    }
+    Ok(engine)
+}
+
+// Log the crash diagnoses of a finished build, generate its image and register it in `artifact_store`
+fn finish_build(
+    engine: &engine::BuildEngine,
+    result: Result<PathBuf, BuildEngineError>,
+    output_path: String,
+    artifact_store: Option<Arc<Mutex<ArtifactStore>>>,
+) -> Result<(), BuildEngineError> {
+    for crash in engine.get_crashes() {
+        if let Some(diagnosis) = &crash.diagnosis {
+            let owner = crash.component.as_deref().unwrap_or("no component");
+            log::warn!("Kernel crash {} ({}): {}; suggested fix: {}", crash.oops.title, owner, diagnosis.probable_cause, diagnosis.suggested_fix);
+        }
+    }
+    result?;
+    engine.generate_image(output_path.clone())?;
+    if let Some(store) = artifact_store {
+        let config = engine.get_config();
+        let profile = format!("{:?}", config.build_mode);
+        let record = store.lock().unwrap().register(config, &profile, Path::new(&output_path))?;
+        log::info!("Stored the image as artifact {}", record.id);
+    }
+    Ok(())
+}
+
+// Record the results of a build in the stores kept in `tables`
+//
+// A store that cannot be opened is skipped with a warning; the build still runs.
+fn attach_stores(engine: &mut engine::BuildEngine, tables: SharedTables) {
+    match BuildHistory::new(tables.clone()) {
+        Ok(history) => engine.set_history(Arc::new(history)),
+        Err(e) => log::warn!("This build is not recorded in the build history: {}", e),
+    }
+    match BootLogStore::new(tables.clone()) {
+        Ok(boot_logs) => engine.set_boot_log_store(Arc::new(boot_logs)),
+        Err(e) => log::warn!("Boot logs of this build are not recorded: {}", e),
+    }
+    match TraceStore::new(tables) {
+        Ok(traces) => engine.set_trace_store(Arc::new(traces)),
+        Err(e) => log::warn!("Traces of this build are not recorded: {}", e),
+    }
+}
+
+// Check a build configuration file without building
+//
+// Returns the diagnostics found; the configuration is valid when none of them is an error.
+// Errors are also published as a validation failure on the global event bus.
+pub fn check_config(config_path: String) -> Result<Vec<ConfigDiagnostic>, BuildEngineError> {
+    let diagnostics = config_validator::validate_config_file(&config_path)
+        .map_err(|e| BuildEngineError::ConfigError(format!("Failed to read {}: {}", config_path, e)))?;
+    let errors: Vec<String> = diagnostics.iter()
+        .filter(|diagnostic| diagnostic.severity == Severity::Error)
+        .map(|diagnostic| diagnostic.to_string())
+        .collect();
+    if !errors.is_empty() {
+        event_bus::publish(SystemEvent::Project(ProjectEvent::ValidationFailed {
+            project: config_path,
+            target: "build config".to_string(),
+            errors,
+        }));
+    }
+    Ok(diagnostics)
+}
+
+// Build Engine error types
+#[derive(thiserror::Error, Debug)]
+pub enum BuildEngineError {
+    #[error("Configuration error: {0}")]
+    ConfigError(String),
+    
+    #[error("Build process error: {0}")]
+    BuildError(String),
+    
+    #[error("Image generation error: {0}")]
+    ImageError(String),
+    
+    #[error("Command execution error: {0}")]
+    CommandError(String),
+    
+    #[error("Build canceled")]
+    BuildCanceled,
+    
+    #[error("Offline mode: {0}")]
+    Offline(String),
+}
+
+impl LocalizedError for BuildEngineError {
+    fn error_code(&self) -> &'static str {
+        match self {
+            BuildEngineError::ConfigError(_) => "OSL-B001",
+            BuildEngineError::BuildError(_) => "OSL-B002",
+            BuildEngineError::ImageError(_) => "OSL-B003",
+            BuildEngineError::CommandError(_) => "OSL-B004",
+            BuildEngineError::BuildCanceled => "OSL-B005",
+            BuildEngineError::Offline(_) => "OSL-B006",
+        }
+    }
+    
+    fn message_args(&self) -> Vec<String> {
+        match self {
+            BuildEngineError::ConfigError(detail)
+            | BuildEngineError::BuildError(detail)
+            | BuildEngineError::ImageError(detail)
+            | BuildEngineError::CommandError(detail)
+            | BuildEngineError::Offline(detail) => vec![detail.clone()],
+            BuildEngineError::BuildCanceled => Vec::new(),
+        }
+    }
+}
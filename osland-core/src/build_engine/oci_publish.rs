@@ -0,0 +1,713 @@
+// OCI artifact publishing for OSland build engine
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! Pushing build outputs to OCI registries as artifacts (ORAS style).
+//!
+//! Every output becomes a layer of an image manifest whose `artifactType` marks it as an
+//! OSland build and whose config is the empty descriptor, so any OCI registry stores it
+//! and `oras pull` fetches the files back under their original names. Credentials come
+//! from the docker configuration: a registry-specific credential helper, the default
+//! credential store, or an inline `auths` entry.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::Duration;
+use base64::Engine as _;
+use reqwest::blocking::{Body, Client, RequestBuilder, Response};
+use reqwest::header::{CONTENT_TYPE, LOCATION, WWW_AUTHENTICATE};
+use reqwest::{StatusCode, Url};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use crate::core::network_policy::{check_egress, redirect_policy, EgressPurpose};
+use super::build_config::{BuildConfig, OciArtifactKind, OciPublishConfig};
+use super::downloader::file_sha256;
+use super::BuildEngineError;
+
+/// Media type of the pushed manifest
+pub const MANIFEST_MEDIA_TYPE: &str = "application/vnd.oci.image.manifest.v1+json";
+
+/// Artifact type identifying OSland build outputs
+pub const ARTIFACT_TYPE: &str = "application/vnd.osland.build.v1";
+
+/// Media type of the empty config blob
+pub const EMPTY_CONFIG_MEDIA_TYPE: &str = "application/vnd.oci.empty.v1+json";
+
+/// Content of the empty config blob
+const EMPTY_CONFIG: &[u8] = b"{}";
+
+/// Annotation naming the file of a layer
+const TITLE_ANNOTATION: &str = "org.opencontainers.image.title";
+
+impl OciArtifactKind {
+    /// Default media type of an output of this kind
+    pub fn default_media_type(&self, path: &Path) -> &'static str {
+        let name = path.file_name().map(|name| name.to_string_lossy().to_lowercase()).unwrap_or_default();
+        match self {
+            OciArtifactKind::Image => "application/vnd.osland.image.v1",
+            OciArtifactKind::Rootfs if name.ends_with(".tar.gz") || name.ends_with(".tgz") => "application/vnd.osland.rootfs.v1.tar+gzip",
+            OciArtifactKind::Rootfs if name.ends_with(".tar") => "application/vnd.osland.rootfs.v1.tar",
+            OciArtifactKind::Rootfs => "application/vnd.osland.rootfs.v1",
+            OciArtifactKind::Sbom if name.contains("spdx") => "application/spdx+json",
+            OciArtifactKind::Sbom if name.contains("cdx") || name.contains("cyclonedx") => "application/vnd.cyclonedx+json",
+            OciArtifactKind::Sbom => "application/vnd.osland.license-report.v1+json",
+        }
+    }
+}
+
+/// Content descriptor of a manifest
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Descriptor {
+    /// Media type of the content
+    pub media_type: String,
+    
+    /// `sha256:<hex>` digest of the content
+    pub digest: String,
+    
+    /// Size in bytes
+    pub size: u64,
+    
+    /// Annotations, e.g. the file name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub annotations: BTreeMap<String, String>,
+}
+
+impl Descriptor {
+    /// Descriptor of the empty config blob
+    pub fn empty_config() -> Self {
+        Self {
+            media_type: EMPTY_CONFIG_MEDIA_TYPE.to_string(),
+            digest: sha256_digest(EMPTY_CONFIG),
+            size: EMPTY_CONFIG.len() as u64,
+            annotations: BTreeMap::new(),
+        }
+    }
+    
+    /// Describe a file pushed as a layer
+    pub fn for_file(path: &Path, media_type: &str) -> Result<Self, BuildEngineError> {
+        let size = fs::metadata(path)
+            .map_err(|e| BuildEngineError::BuildError(format!("Failed to read {}: {}", path.display(), e)))?
+            .len();
+        let title = path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+        Ok(Self {
+            media_type: media_type.to_string(),
+            digest: format!("sha256:{}", file_sha256(path)?),
+            size,
+            annotations: BTreeMap::from([(TITLE_ANNOTATION.to_string(), title)]),
+        })
+    }
+}
+
+/// Serialize the manifest of an artifact made of layers
+pub fn build_manifest(layers: &[Descriptor], annotations: &BTreeMap<String, String>) -> Vec<u8> {
+    let mut manifest = serde_json::json!({
+        "schemaVersion": 2,
+        "mediaType": MANIFEST_MEDIA_TYPE,
+        "artifactType": ARTIFACT_TYPE,
+        "config": Descriptor::empty_config(),
+        "layers": layers,
+    });
+    if !annotations.is_empty() {
+        manifest["annotations"] = serde_json::json!(annotations);
+    }
+    serde_json::to_vec(&manifest).expect("manifest is serializable")
+}
+
+/// `sha256:<hex>` digest of bytes
+pub fn sha256_digest(bytes: &[u8]) -> String {
+    format!("sha256:{}", Sha256::digest(bytes).iter().map(|b| format!("{:02x}", b)).collect::<String>())
+}
+
+/// Values of the repository and tag templates of a build
+pub fn template_values(config: &BuildConfig, profile: Option<&str>) -> BTreeMap<&'static str, String> {
+    BTreeMap::from([
+        ("project", config.project_name.clone()),
+        ("profile", profile.map(str::to_string).unwrap_or_else(|| format!("{:?}", config.build_mode))),
+        ("version", config.project_version.clone()),
+        ("date", chrono::Utc::now().format("%Y%m%d").to_string()),
+    ])
+}
+
+/// Expand `{name}` placeholders, failing on unknown names
+pub fn render_template(template: &str, values: &BTreeMap<&str, String>) -> Result<String, String> {
+    let mut rendered = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        rendered.push_str(&rest[..start]);
+        let end = rest[start..].find('}').ok_or_else(|| format!("Unclosed placeholder in '{}'", template))? + start;
+        let name = &rest[start + 1..end];
+        rendered.push_str(values.get(name).ok_or_else(|| format!("Unknown placeholder '{{{}}}' in '{}'", name, template))?);
+        rest = &rest[end + 1..];
+    }
+    rendered.push_str(rest);
+    Ok(rendered)
+}
+
+/// Render a tag template into a valid tag (`[A-Za-z0-9_][A-Za-z0-9_.-]{0,127}`)
+pub fn render_tag(template: &str, values: &BTreeMap<&str, String>) -> Result<String, String> {
+    let rendered = render_template(template, values)?;
+    let mut tag: String = rendered.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-') { c } else { '-' })
+        .take(128)
+        .collect();
+    if tag.is_empty() {
+        return Err(format!("Tag template '{}' renders to an empty tag", template));
+    }
+    if tag.starts_with(['.', '-']) {
+        tag.replace_range(..1, "_");
+    }
+    Ok(tag)
+}
+
+/// Render a repository template into a valid lowercase repository path
+pub fn render_repository(template: &str, values: &BTreeMap<&str, String>) -> Result<String, String> {
+    let rendered = render_template(template, values)?.to_lowercase();
+    let components: Vec<String> = rendered.split('/')
+        .map(|component| component.chars()
+            .map(|c| if c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-') { c } else { '-' })
+            .collect::<String>()
+            .trim_matches(|c: char| !c.is_ascii_alphanumeric())
+            .to_string())
+        .filter(|component| !component.is_empty())
+        .collect();
+    if components.is_empty() {
+        return Err(format!("Repository template '{}' renders to an empty repository", template));
+    }
+    Ok(components.join("/"))
+}
+
+/// Registry credentials
+#[derive(Clone, PartialEq, Eq)]
+pub struct RegistryCredentials {
+    /// User name
+    pub username: String,
+    
+    /// Password or token
+    pub secret: String,
+}
+
+impl std::fmt::Debug for RegistryCredentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RegistryCredentials").field("username", &self.username).field("secret", &"<redacted>").finish()
+    }
+}
+
+/// Parts of the docker `config.json` used to find credentials
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct DockerConfig {
+    /// Inline credentials by registry
+    #[serde(default)]
+    pub auths: HashMap<String, DockerAuth>,
+    
+    /// Credential helper by registry
+    #[serde(default, rename = "credHelpers")]
+    pub cred_helpers: HashMap<String, String>,
+    
+    /// Default credential helper
+    #[serde(default, rename = "credsStore")]
+    pub creds_store: Option<String>,
+}
+
+/// Inline credentials of the docker configuration
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct DockerAuth {
+    /// Base64 of `user:password`
+    #[serde(default)]
+    pub auth: Option<String>,
+    
+    /// User name
+    #[serde(default)]
+    pub username: Option<String>,
+    
+    /// Password
+    #[serde(default)]
+    pub password: Option<String>,
+}
+
+/// Runs a docker credential helper (helper name, registry host)
+pub type CredentialHelper = dyn Fn(&str, &str) -> Result<Option<RegistryCredentials>, String>;
+
+impl DockerConfig {
+    /// Load the docker configuration of the current user, if any
+    pub fn load_default() -> Result<Self, BuildEngineError> {
+        let path = std::env::var("DOCKER_CONFIG").map(PathBuf::from)
+            .or_else(|_| std::env::var("HOME").map(|home| PathBuf::from(home).join(".docker")))
+            .or_else(|_| std::env::var("USERPROFILE").map(|home| PathBuf::from(home).join(".docker")))
+            .map(|dir| dir.join("config.json"));
+        match path {
+            Ok(path) if path.exists() => {
+                let content = fs::read_to_string(&path)
+                    .map_err(|e| BuildEngineError::ConfigError(format!("Failed to read {}: {}", path.display(), e)))?;
+                serde_json::from_str(&content)
+                    .map_err(|e| BuildEngineError::ConfigError(format!("Failed to parse {}: {}", path.display(), e)))
+            }
+            _ => Ok(Self::default()),
+        }
+    }
+    
+    /// Find the credentials of a registry: its credential helper, then the default store, then `auths`
+    ///
+    /// `run_helper` runs `docker-credential-<helper> get` and returns `None` when the
+    /// helper has no credentials for the registry.
+    pub fn credentials(
+        &self,
+        registry: &str,
+        run_helper: &CredentialHelper,
+    ) -> Result<Option<RegistryCredentials>, String> {
+        let host = registry_host(registry);
+        if let Some(helper) = self.cred_helpers.iter().find(|(key, _)| registry_host(key) == host).map(|(_, helper)| helper) {
+            return run_helper(helper, &host);
+        }
+        if let Some(store) = &self.creds_store {
+            if let Some(credentials) = run_helper(store, &host)? {
+                return Ok(Some(credentials));
+            }
+        }
+        let auth = match self.auths.iter().find(|(key, _)| registry_host(key) == host) {
+            Some((_, auth)) => auth,
+            None => return Ok(None),
+        };
+        if let (Some(username), Some(password)) = (&auth.username, &auth.password) {
+            return Ok(Some(RegistryCredentials { username: username.clone(), secret: password.clone() }));
+        }
+        match &auth.auth {
+            Some(encoded) => {
+                let decoded = base64::engine::general_purpose::STANDARD.decode(encoded.trim())
+                    .map_err(|e| format!("Invalid auth entry for {}: {}", host, e))?;
+                let decoded = String::from_utf8(decoded).map_err(|e| format!("Invalid auth entry for {}: {}", host, e))?;
+                let (username, secret) = decoded.split_once(':').ok_or_else(|| format!("Invalid auth entry for {}", host))?;
+                Ok(Some(RegistryCredentials { username: username.to_string(), secret: secret.to_string() }))
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+/// Registry host of a docker config key or registry name (`https://index.docker.io/v1/` is `docker.io`)
+fn registry_host(registry: &str) -> String {
+    let host = registry.trim_start_matches("https://").trim_start_matches("http://");
+    let host = host.split('/').next().unwrap_or(host).to_lowercase();
+    match host.as_str() {
+        "index.docker.io" | "registry-1.docker.io" => "docker.io".to_string(),
+        _ => host,
+    }
+}
+
+/// Run a docker credential helper to get the credentials of a registry
+pub fn run_credential_helper(helper: &str, registry: &str) -> Result<Option<RegistryCredentials>, String> {
+    let program = format!("docker-credential-{}", helper);
+    // Docker Hub credentials are stored under their legacy URL
+    let server = if registry == "docker.io" { "https://index.docker.io/v1/" } else { registry };
+    let mut child = Command::new(&program)
+        .arg("get")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to run {}: {}", program, e))?;
+    child.stdin.take().expect("stdin is piped").write_all(server.as_bytes())
+        .map_err(|e| format!("Failed to write to {}: {}", program, e))?;
+    let output = child.wait_with_output().map_err(|e| format!("Failed to run {}: {}", program, e))?;
+    if !output.status.success() {
+        let message = String::from_utf8_lossy(&output.stdout).to_string() + &String::from_utf8_lossy(&output.stderr);
+        if message.to_lowercase().contains("credentials not found") {
+            return Ok(None);
+        }
+        return Err(format!("{} failed: {}", program, message.trim()));
+    }
+    
+    #[derive(Deserialize)]
+    struct HelperOutput {
+        #[serde(rename = "Username")]
+        username: String,
+        #[serde(rename = "Secret")]
+        secret: String,
+    }
+    let parsed: HelperOutput = serde_json::from_slice(&output.stdout)
+        .map_err(|e| format!("Invalid output of {}: {}", program, e))?;
+    Ok(Some(RegistryCredentials { username: parsed.username, secret: parsed.secret }))
+}
+
+/// Parse the parameters of a `WWW-Authenticate` challenge
+///
+/// Returns the scheme and its parameters, e.g. `Bearer` with `realm`, `service` and `scope`.
+pub fn parse_challenge(header: &str) -> (String, HashMap<String, String>) {
+    let header = header.trim();
+    let (scheme, rest) = header.split_once(' ').unwrap_or((header, ""));
+    let mut params = HashMap::new();
+    let mut chars = rest.chars().peekable();
+    loop {
+        while chars.peek().is_some_and(|c| *c == ',' || c.is_whitespace()) {
+            chars.next();
+        }
+        let key: String = std::iter::from_fn(|| chars.next_if(|c| *c != '=')).collect();
+        if key.is_empty() || chars.next().is_none() {
+            break;
+        }
+        let value = if chars.peek() == Some(&'"') {
+            chars.next();
+            let value: String = std::iter::from_fn(|| chars.next_if(|c| *c != '"')).collect();
+            chars.next();
+            value
+        } else {
+            std::iter::from_fn(|| chars.next_if(|c| *c != ',')).collect()
+        };
+        params.insert(key.trim().to_lowercase(), value);
+    }
+    (scheme.to_string(), params)
+}
+
+/// Layer pushed by the publisher
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublishedLayer {
+    /// File pushed
+    pub path: PathBuf,
+    
+    /// Layer descriptor
+    pub descriptor: Descriptor,
+    
+    /// False when the registry already had the blob
+    pub uploaded: bool,
+}
+
+/// Result of publishing an artifact
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublishReport {
+    /// `registry/repository` the artifact was pushed to
+    pub reference: String,
+    
+    /// Digest of the manifest
+    pub digest: String,
+    
+    /// Tags pointing at the manifest
+    pub tags: Vec<String>,
+    
+    /// Pushed layers
+    pub layers: Vec<PublishedLayer>,
+}
+
+/// Content of a blob to push
+enum BlobSource<'a> {
+    File(&'a Path),
+    Bytes(&'a [u8]),
+}
+
+/// Client of the OCI distribution API of one repository
+pub struct OciPublisher {
+    client: Client,
+    registry: String,
+    base_url: String,
+    repository: String,
+    credentials: Option<RegistryCredentials>,
+    token: Option<String>,
+    basic: bool,
+}
+
+impl OciPublisher {
+    /// Create a publisher for a repository of a registry
+    pub fn new(registry: &str, repository: &str, insecure: bool, credentials: Option<RegistryCredentials>) -> Result<Self, BuildEngineError> {
+        let client = Client::builder()
+            .redirect(redirect_policy(EgressPurpose::Registry))
+            .connect_timeout(Duration::from_secs(30))
+            .timeout(None)
+            .user_agent(concat!("osland/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .map_err(|e| BuildEngineError::BuildError(format!("Failed to create HTTP client: {}", e)))?;
+        let host = registry.trim_start_matches("https://").trim_start_matches("http://").trim_end_matches('/');
+        Ok(Self {
+            client,
+            registry: host.to_string(),
+            base_url: format!("{}://{}", if insecure { "http" } else { "https" }, api_host(host)),
+            repository: repository.to_string(),
+            credentials,
+            token: None,
+            basic: false,
+        })
+    }
+    
+    /// Push files as layers of one artifact and tag its manifest
+    pub fn push(&mut self, files: &[(PathBuf, String)], tags: &[String], annotations: &BTreeMap<String, String>) -> Result<PublishReport, BuildEngineError> {
+        if tags.is_empty() {
+            return Err(BuildEngineError::ConfigError("At least one tag is required to publish".to_string()));
+        }
+        check_egress(EgressPurpose::Registry, &self.base_url).map_err(|e| BuildEngineError::Offline(e.to_string()))?;
+        
+        let mut layers = Vec::new();
+        for (path, media_type) in files {
+            let descriptor = Descriptor::for_file(path, media_type)?;
+            let uploaded = self.push_blob(&descriptor, BlobSource::File(path))?;
+            layers.push(PublishedLayer { path: path.clone(), descriptor, uploaded });
+        }
+        self.push_blob(&Descriptor::empty_config(), BlobSource::Bytes(EMPTY_CONFIG))?;
+        
+        let descriptors: Vec<Descriptor> = layers.iter().map(|layer| layer.descriptor.clone()).collect();
+        let manifest = build_manifest(&descriptors, annotations);
+        for tag in tags {
+            self.push_manifest(tag, &manifest)?;
+        }
+        
+        Ok(PublishReport {
+            reference: format!("{}/{}", self.registry, self.repository),
+            digest: sha256_digest(&manifest),
+            tags: tags.to_vec(),
+            layers,
+        })
+    }
+    
+    /// Upload a blob unless the registry has it; returns whether it was uploaded
+    fn push_blob(&mut self, descriptor: &Descriptor, source: BlobSource) -> Result<bool, BuildEngineError> {
+        let blob_url = format!("{}/v2/{}/blobs/{}", self.base_url, self.repository, descriptor.digest);
+        let response = self.execute(|client| client.head(&blob_url))?;
+        if response.status().is_success() {
+            return Ok(false);
+        }
+        
+        let uploads_url = format!("{}/v2/{}/blobs/uploads/", self.base_url, self.repository);
+        let response = self.execute(|client| client.post(&uploads_url))?;
+        let response = expect_status(response, StatusCode::ACCEPTED, "start blob upload")?;
+        let location = response.headers().get(LOCATION).and_then(|value| value.to_str().ok())
+            .ok_or_else(|| BuildEngineError::BuildError("Registry did not return an upload location".to_string()))?;
+        let mut upload_url = if location.starts_with("http://") || location.starts_with("https://") {
+            location.to_string()
+        } else {
+            format!("{}{}", self.base_url, location)
+        };
+        check_egress(EgressPurpose::Registry, &upload_url).map_err(|e| BuildEngineError::Offline(e.to_string()))?;
+        upload_url.push(if upload_url.contains('?') { '&' } else { '?' });
+        upload_url.push_str(&format!("digest={}", descriptor.digest));
+        
+        let body = match source {
+            BlobSource::File(path) => {
+                let file = File::open(path)
+                    .map_err(|e| BuildEngineError::BuildError(format!("Failed to open {}: {}", path.display(), e)))?;
+                Body::sized(file, descriptor.size)
+            }
+            BlobSource::Bytes(bytes) => Body::from(bytes.to_vec()),
+        };
+        // The upload session is already authorized, and a streamed body cannot be replayed.
+        // Uploads handed off to another host (e.g. object storage) get no registry credentials.
+        let mut request = self.client.put(&upload_url).header(CONTENT_TYPE, "application/octet-stream").body(body);
+        if same_origin(&upload_url, &self.base_url) {
+            request = self.authorize(request);
+        }
+        let response = request.send().map_err(|e| BuildEngineError::BuildError(format!("Failed to upload {}: {}", descriptor.digest, e)))?;
+        expect_status(response, StatusCode::CREATED, "upload blob")?;
+        Ok(true)
+    }
+    
+    /// Put the manifest under a tag
+    fn push_manifest(&mut self, tag: &str, manifest: &[u8]) -> Result<(), BuildEngineError> {
+        let url = format!("{}/v2/{}/manifests/{}", self.base_url, self.repository, tag);
+        let response = self.execute(|client| client.put(&url).header(CONTENT_TYPE, MANIFEST_MEDIA_TYPE).body(manifest.to_vec()))?;
+        expect_status(response, StatusCode::CREATED, "push manifest")?;
+        Ok(())
+    }
+    
+    /// Send a request, authenticating and retrying once if the registry asks for it
+    fn execute(&mut self, request: impl Fn(&Client) -> RequestBuilder) -> Result<Response, BuildEngineError> {
+        let response = self.authorize(request(&self.client)).send()
+            .map_err(|e| BuildEngineError::BuildError(format!("Registry request failed: {}", e)))?;
+        if response.status() != StatusCode::UNAUTHORIZED {
+            return Ok(response);
+        }
+        let challenge = response.headers().get(WWW_AUTHENTICATE).and_then(|value| value.to_str().ok()).unwrap_or_default().to_string();
+        self.authenticate(&challenge)?;
+        self.authorize(request(&self.client)).send()
+            .map_err(|e| BuildEngineError::BuildError(format!("Registry request failed: {}", e)))
+    }
+    
+    /// Add the current credentials to a request
+    fn authorize(&self, request: RequestBuilder) -> RequestBuilder {
+        match (&self.token, &self.credentials) {
+            (Some(token), _) => request.bearer_auth(token),
+            (None, Some(credentials)) if self.basic => request.basic_auth(&credentials.username, Some(&credentials.secret)),
+            _ => request,
+        }
+    }
+    
+    /// Answer an authentication challenge: fetch a bearer token or switch to basic auth
+    fn authenticate(&mut self, challenge: &str) -> Result<(), BuildEngineError> {
+        let (scheme, params) = parse_challenge(challenge);
+        if scheme.eq_ignore_ascii_case("basic") {
+            if self.credentials.is_none() || self.basic {
+                return Err(BuildEngineError::BuildError(format!("Registry {} rejected the credentials (run `docker login {}`)", self.registry, self.registry)));
+            }
+            self.basic = true;
+            return Ok(());
+        }
+        if !scheme.eq_ignore_ascii_case("bearer") {
+            return Err(BuildEngineError::BuildError(format!("Unsupported registry authentication: {}", challenge)));
+        }
+        let realm = params.get("realm")
+            .ok_or_else(|| BuildEngineError::BuildError("Registry challenge has no token realm".to_string()))?;
+        if !Url::parse(realm).is_ok_and(|url| url.scheme() == "https") {
+            return Err(BuildEngineError::BuildError(format!("Registry token realm {} does not use https", realm)));
+        }
+        check_egress(EgressPurpose::Registry, realm).map_err(|e| BuildEngineError::Offline(e.to_string()))?;
+        let scope = format!("repository:{}:pull,push", self.repository);
+        let mut query = vec![("scope", scope.as_str())];
+        if let Some(service) = params.get("service") {
+            query.push(("service", service.as_str()));
+        }
+        let mut request = self.client.get(realm).query(&query);
+        if let Some(credentials) = &self.credentials {
+            request = request.basic_auth(&credentials.username, Some(&credentials.secret));
+        }
+        let response = request.send().map_err(|e| BuildEngineError::BuildError(format!("Token request failed: {}", e)))?;
+        let response = expect_status(response, StatusCode::OK, "get registry token")?;
+        
+        #[derive(Deserialize)]
+        struct TokenResponse {
+            #[serde(default)]
+            token: Option<String>,
+            #[serde(default)]
+            access_token: Option<String>,
+        }
+        let token: TokenResponse = response.json()
+            .map_err(|e| BuildEngineError::BuildError(format!("Invalid token response: {}", e)))?;
+        self.token = Some(token.token.or(token.access_token)
+            .ok_or_else(|| BuildEngineError::BuildError("Token response has no token".to_string()))?);
+        Ok(())
+    }
+}
+
+/// Host serving the distribution API of a registry; Docker Hub serves it on `registry-1.docker.io`
+fn api_host(host: &str) -> &str {
+    if host.eq_ignore_ascii_case("docker.io") || host.eq_ignore_ascii_case("index.docker.io") {
+        "registry-1.docker.io"
+    } else {
+        host
+    }
+}
+
+/// Whether two URLs share scheme, host and port, so credentials for one may be sent to the other
+fn same_origin(a: &str, b: &str) -> bool {
+    match (Url::parse(a), Url::parse(b)) {
+        (Ok(a), Ok(b)) => a.origin() == b.origin(),
+        _ => false,
+    }
+}
+
+/// Fail unless a response has the expected status
+fn expect_status(response: Response, expected: StatusCode, action: &str) -> Result<Response, BuildEngineError> {
+    if response.status() == expected {
+        return Ok(response);
+    }
+    let status = response.status();
+    let body = response.text().unwrap_or_default();
+    Err(BuildEngineError::BuildError(format!("Failed to {}: HTTP {} {}", action, status, body.trim())))
+}
+
+/// Publish the outputs of a build as configured by a `PublishArtifacts` step
+pub fn publish(config: &OciPublishConfig, build: &BuildConfig) -> Result<PublishReport, BuildEngineError> {
+    let values = template_values(build, config.profile.as_deref());
+    let repository = render_repository(&config.repository, &values).map_err(BuildEngineError::ConfigError)?;
+    let tags = config.tags.iter()
+        .map(|template| render_tag(template, &values))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(BuildEngineError::ConfigError)?;
+    if config.artifacts.is_empty() {
+        return Err(BuildEngineError::ConfigError("No artifacts configured to publish".to_string()));
+    }
+    let files: Vec<(PathBuf, String)> = config.artifacts.iter()
+        .map(|artifact| {
+            let path = build.output_dir.join(&artifact.path);
+            let media_type = artifact.media_type.clone().unwrap_or_else(|| artifact.kind.default_media_type(&path).to_string());
+            (path, media_type)
+        })
+        .collect();
+    
+    let credentials = DockerConfig::load_default()?
+        .credentials(&config.registry, &run_credential_helper)
+        .map_err(BuildEngineError::ConfigError)?;
+    let annotations = BTreeMap::from([
+        ("org.opencontainers.image.title".to_string(), build.project_name.clone()),
+        ("org.opencontainers.image.version".to_string(), build.project_version.clone()),
+        ("org.opencontainers.image.created".to_string(), chrono::Utc::now().to_rfc3339()),
+    ]);
+    OciPublisher::new(&config.registry, &repository, config.insecure, credentials)?.push(&files, &tags, &annotations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_render_tags_and_repositories() {
+        let values = BTreeMap::from([
+            ("project", "My Kernel".to_string()),
+            ("profile", "Release".to_string()),
+            ("version", "1.2.0+git.3".to_string()),
+        ]);
+        assert_eq!(render_tag("{version}-{profile}", &values).unwrap(), "1.2.0-git.3-Release");
+        assert_eq!(render_tag("-{profile}", &values).unwrap(), "_Release");
+        assert_eq!(render_repository("Acme/{project}/images", &values).unwrap(), "acme/my-kernel/images");
+        assert!(render_tag("{arch}", &values).is_err());
+        assert!(render_tag("{version", &values).is_err());
+    }
+    
+    #[test]
+    fn test_manifest_and_challenge() {
+        // Well-known digest of the empty config
+        assert_eq!(Descriptor::empty_config().digest, "sha256:44136fa355b3678a1146ad16f7e8649e94fb4fc21fe77e8310c060f61caaff8a");
+        
+        let layer = Descriptor {
+            media_type: "application/spdx+json".to_string(),
+            digest: sha256_digest(b"sbom"),
+            size: 4,
+            annotations: BTreeMap::from([(TITLE_ANNOTATION.to_string(), "sbom.spdx.json".to_string())]),
+        };
+        let manifest: serde_json::Value = serde_json::from_slice(&build_manifest(&[layer], &BTreeMap::new())).unwrap();
+        assert_eq!(manifest["artifactType"], ARTIFACT_TYPE);
+        assert_eq!(manifest["config"]["mediaType"], EMPTY_CONFIG_MEDIA_TYPE);
+        assert_eq!(manifest["layers"][0]["annotations"][TITLE_ANNOTATION], "sbom.spdx.json");
+        assert!(manifest.get("annotations").is_none());
+        
+        let (scheme, params) = parse_challenge(r#"Bearer realm="https://ghcr.io/token",service="ghcr.io",scope="repository:acme/os:pull,push""#);
+        assert_eq!(scheme, "Bearer");
+        assert_eq!(params["realm"], "https://ghcr.io/token");
+        assert_eq!(params["scope"], "repository:acme/os:pull,push");
+    }
+    
+    #[test]
+    fn test_registry_hosts_and_origins() {
+        let hub = OciPublisher::new("docker.io", "acme/os", false, None).unwrap();
+        assert_eq!(hub.base_url, "https://registry-1.docker.io");
+        assert_eq!(hub.registry, "docker.io");
+        assert_eq!(OciPublisher::new("localhost:5000/", "os", true, None).unwrap().base_url, "http://localhost:5000");
+        
+        assert!(same_origin("https://ghcr.io/v2/acme/os/blobs/uploads/1?state=x", "https://ghcr.io"));
+        assert!(!same_origin("https://bucket.s3.amazonaws.com/upload?sig=x", "https://ghcr.io"));
+        assert!(!same_origin("http://ghcr.io/upload", "https://ghcr.io"));
+        assert!(!same_origin("https://ghcr.io:8443/upload", "https://ghcr.io"));
+    }
+    
+    #[test]
+    fn test_token_realm_requires_https() {
+        let mut publisher = OciPublisher::new("ghcr.io", "acme/os", false, None).unwrap();
+        let error = publisher.authenticate(r#"Bearer realm="http://ghcr.io/token",service="ghcr.io""#).unwrap_err();
+        assert!(error.to_string().contains("does not use https"));
+    }
+    
+    #[test]
+    fn test_docker_credentials() {
+        let config: DockerConfig = serde_json::from_str(r#"{
+            "auths": {"https://index.docker.io/v1/": {"auth": "YWxpY2U6czNjcmV0"}, "ghcr.io": {}},
+            "credHelpers": {"123.dkr.ecr.us-east-1.amazonaws.com": "ecr-login"}
+        }"#).unwrap();
+        let helper = |helper: &str, registry: &str| -> Result<Option<RegistryCredentials>, String> {
+            Ok(Some(RegistryCredentials { username: helper.to_string(), secret: registry.to_string() }))
+        };
+        
+        let hub = config.credentials("docker.io", &helper).unwrap().unwrap();
+        assert_eq!((hub.username.as_str(), hub.secret.as_str()), ("alice", "s3cret"));
+        let ecr = config.credentials("https://123.dkr.ecr.us-east-1.amazonaws.com", &helper).unwrap().unwrap();
+        assert_eq!(ecr.username, "ecr-login");
+        assert_eq!(config.credentials("ghcr.io", &helper).unwrap(), None);
+        assert!(!format!("{:?}", hub).contains("s3cret"));
+    }
+}
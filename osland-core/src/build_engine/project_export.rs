@@ -0,0 +1,598 @@
+// Project export for OSland build engine
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Component as PathComponent, Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use crate::component_manager::component::Component;
+use crate::component_manager::ipc_components::{generate_ipc_stubs, IpcKind};
+use crate::component_manager::network_components::{generate_network_config, NetworkKind};
+use crate::component_manager::visual_node::NodeCanvas;
+use crate::core::architecture::KernelArchitecture;
+use crate::os_design::syscall_designer::GeneratedFile;
+use crate::tile_engine::tile_compiler::{sanitize_identifier, CompilationOptions, TargetLanguage, TileCompiler};
+use crate::tile_engine::tile_core::TileGraph;
+use super::build_config::BuildConfig;
+
+/// Build system of an exported project
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// Cargo workspace with a crate per tile graph and Rust component
+    Cargo,
+    /// CMake project with a target per C component
+    CMake,
+}
+
+impl ExportFormat {
+    /// Lowercase name used on the command line
+    pub fn name(&self) -> &'static str {
+        match self {
+            ExportFormat::Cargo => "cargo",
+            ExportFormat::CMake => "cmake",
+        }
+    }
+    
+    /// Parse a format name
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "cargo" => Some(ExportFormat::Cargo),
+            "cmake" => Some(ExportFormat::CMake),
+            _ => None,
+        }
+    }
+}
+
+/// Files of an exported project
+#[derive(Debug, Clone, Default)]
+pub struct ExportedProject {
+    /// Files relative to the project root
+    pub files: Vec<GeneratedFile>,
+    
+    /// Parts of the design the exported build system does not build
+    pub warnings: Vec<String>,
+}
+
+impl ExportedProject {
+    /// Write the project into a directory
+    pub fn write<P: AsRef<Path>>(&self, dir: P) -> Result<Vec<PathBuf>, String> {
+        let dir = dir.as_ref();
+        self.files.iter().map(|file| {
+            if relative_file(&file.path).is_none_or(|relative| Path::new(&relative) != file.path) {
+                return Err(format!("Refusing to write {} outside {}", file.path.display(), dir.display()));
+            }
+            let path = dir.join(&file.path);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+            }
+            fs::write(&path, &file.content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+            Ok(path)
+        }).collect()
+    }
+    
+    /// Content of a file by relative path
+    pub fn file(&self, path: &str) -> Option<&str> {
+        self.files.iter().find(|file| file.path == Path::new(path)).map(|file| file.content.as_str())
+    }
+}
+
+/// How a component is built in the exported project
+enum ComponentBuild {
+    /// `make` with the component's build commands (kernel modules and the like)
+    Make,
+    /// Cargo crate
+    Crate,
+    /// CMake library
+    Library,
+    /// Copied only
+    None,
+}
+
+/// Exports a design as a self-contained project that builds outside OSland
+///
+/// Canvas components are copied with their implementation files, tile graphs are
+/// compiled to Rust crates, and the build configuration, canvas and graphs are kept
+/// under `osland/` so the design can be reopened.
+pub struct ProjectExporter {
+    name: String,
+    version: String,
+    format: ExportFormat,
+    architecture: KernelArchitecture,
+    source_root: Option<PathBuf>,
+    build_config: Option<BuildConfig>,
+    canvas: Option<NodeCanvas>,
+    tile_graphs: Vec<TileGraph>,
+    generated: Vec<(String, Vec<GeneratedFile>)>,
+}
+
+impl ProjectExporter {
+    /// Create an exporter for a project
+    pub fn new(name: &str, version: &str, format: ExportFormat) -> Self {
+        Self {
+            name: name.to_string(),
+            version: version.to_string(),
+            format,
+            architecture: KernelArchitecture::Monolithic,
+            source_root: None,
+            build_config: None,
+            canvas: None,
+            tile_graphs: Vec::new(),
+            generated: Vec::new(),
+        }
+    }
+    
+    /// Create an exporter for the project of a build configuration
+    pub fn from_build_config(config: &BuildConfig, format: ExportFormat) -> Self {
+        let mut exporter = Self::new(&config.project_name, &config.project_version, format);
+        exporter.architecture = config.architecture.clone();
+        exporter.build_config = Some(config.clone());
+        exporter
+    }
+    
+    /// Directory relative implementation files are resolved against
+    pub fn set_source_root(&mut self, root: PathBuf) {
+        self.source_root = Some(root);
+    }
+    
+    /// Set the component canvas
+    pub fn set_canvas(&mut self, canvas: NodeCanvas) {
+        self.canvas = Some(canvas);
+    }
+    
+    /// Add a tile graph
+    pub fn add_tile_graph(&mut self, graph: TileGraph) {
+        self.tile_graphs.push(graph);
+    }
+    
+    /// Load a saved canvas, resolving its relative implementation files next to it
+    pub fn load_canvas<P: AsRef<Path>>(&mut self, path: P) -> Result<(), String> {
+        let path = path.as_ref();
+        self.canvas = Some(load_json(path)?);
+        if self.source_root.is_none() {
+            self.source_root = path.parent().map(Path::to_path_buf);
+        }
+        Ok(())
+    }
+    
+    /// Load a saved tile graph
+    pub fn load_tile_graph<P: AsRef<Path>>(&mut self, path: P) -> Result<(), String> {
+        self.tile_graphs.push(load_json(path.as_ref())?);
+        Ok(())
+    }
+    
+    /// Add files generated by a designer (syscall tables, boot flow, ...) under `generated/<name>`
+    ///
+    /// The name is made a single directory name the way component names are, and
+    /// file paths are kept relative to it.
+    pub fn add_generated(&mut self, name: &str, files: Vec<GeneratedFile>) {
+        self.generated.push((name.to_string(), files));
+    }
+    
+    /// Build the project files
+    pub fn export(&self) -> Result<ExportedProject, String> {
+        let mut project = ExportedProject::default();
+        let mut names = BTreeSet::new();
+        let mut members = Vec::new();
+        let mut subdirectories = Vec::new();
+        let mut languages = BTreeSet::from(["C"]);
+        
+        // Design sources, so the project can be reopened in OSland
+        if let Some(config) = &self.build_config {
+            project.files.push(json_file("osland/build.json", config)?);
+        }
+        if let Some(canvas) = &self.canvas {
+            project.files.push(json_file("osland/canvas.json", canvas)?);
+        }
+        
+        let mut graphs: Vec<&TileGraph> = self.tile_graphs.iter().collect();
+        graphs.sort_by(|a, b| a.name.cmp(&b.name));
+        for graph in graphs {
+            let name = unique_name(&mut names, &graph.name);
+            project.files.push(json_file(&format!("osland/tiles/{}.json", name), graph)?);
+            match self.format {
+                ExportFormat::Cargo => {
+                    let options = CompilationOptions { target_language: TargetLanguage::Rust, ..Default::default() };
+                    let code = TileCompiler::new(self.architecture.clone(), Some(options)).generate_execution_code(graph)?;
+                    let dir = format!("crates/{}", name);
+                    project.files.push(text_file(&format!("{}/Cargo.toml", dir), self.crate_manifest(&name, None, "[lib]\npath = \"src/lib.rs\"\n")));
+                    project.files.push(text_file(&format!("{}/src/lib.rs", dir), code));
+                    project.files.push(text_file(
+                        &format!("{}/src/main.rs", dir),
+                        format!("fn main() {{\n    {}::execute_tile_graph();\n}}\n", name.replace('-', "_")),
+                    ));
+                    members.push(dir);
+                }
+                ExportFormat::CMake => project.warnings.push(format!("Tile graph '{}' has no C code generator and is only exported as osland/tiles/{}.json", graph.name, name)),
+            }
+        }
+        
+        for component in self.components() {
+            let name = unique_name(&mut names, &component.name);
+            let dir = format!("components/{}", name);
+            
+            // Files keep their layout below the directory they all share, so
+            // same-named files from different directories don't overwrite each other
+            let paths: Vec<PathBuf> = component.implementation_files.iter().map(|file| self.resolve(file)).collect();
+            let base = common_parent(&paths);
+            let mut sources: Vec<(String, String)> = Vec::new();
+            for path in &paths {
+                let file = match path.strip_prefix(&base).ok().and_then(relative_file) {
+                    Some(file) => file,
+                    None => continue,
+                };
+                if sources.iter().any(|(existing, _)| *existing == file) {
+                    project.warnings.push(format!("Component '{}': {} is exported only once as {}", component.name, path.display(), file));
+                    continue;
+                }
+                match fs::read_to_string(path) {
+                    Ok(content) => sources.push((file, content)),
+                    Err(e) => project.warnings.push(format!("Component '{}': cannot read {}: {}", component.name, path.display(), e)),
+                }
+            }
+            let extensions: BTreeSet<&str> = sources.iter().filter_map(|(file, _)| Path::new(file).extension().and_then(|ext| ext.to_str())).collect();
+            
+            let build = if !component.build_commands.is_empty() {
+                ComponentBuild::Make
+            } else if self.format == ExportFormat::Cargo && extensions.contains("rs") {
+                ComponentBuild::Crate
+            } else if self.format == ExportFormat::CMake && ["c", "cc", "cpp", "h"].iter().any(|ext| extensions.contains(ext)) {
+                ComponentBuild::Library
+            } else {
+                ComponentBuild::None
+            };
+            
+            let source_dir = match build {
+                ComponentBuild::Crate => format!("{}/src", dir),
+                _ => dir.clone(),
+            };
+            for (file, content) in &sources {
+                project.files.push(text_file(&format!("{}/{}", source_dir, file), content.clone()));
+            }
+            project.files.push(json_file(&format!("{}/component.json", dir), component)?);
+            
+            match build {
+                ComponentBuild::Make => {
+                    project.files.push(text_file(&format!("{}/Makefile", dir), make_recipe(component)));
+                    if self.format == ExportFormat::CMake {
+                        project.files.push(text_file(
+                            &format!("{}/CMakeLists.txt", dir),
+                            format!("add_custom_target({} ALL COMMAND make WORKING_DIRECTORY ${{CMAKE_CURRENT_SOURCE_DIR}})\n", sanitize_identifier(&name)),
+                        ));
+                        subdirectories.push(dir);
+                    } else {
+                        project.warnings.push(format!("Component '{}' is built with make in {}", component.name, dir));
+                    }
+                }
+                ComponentBuild::Crate => {
+                    let target = if sources.iter().any(|(file, _)| file == "lib.rs") || !sources.iter().any(|(file, _)| file == "main.rs") {
+                        let lib = sources.iter().map(|(file, _)| file.as_str()).find(|file| *file == "lib.rs")
+                            .or_else(|| sources.iter().map(|(file, _)| file.as_str()).find(|file| file.ends_with(".rs")))
+                            .unwrap_or("lib.rs");
+                        format!("[lib]\npath = \"src/{}\"\n", lib)
+                    } else {
+                        format!("[[bin]]\nname = \"{}\"\npath = \"src/main.rs\"\n", name)
+                    };
+                    project.files.push(text_file(&format!("{}/Cargo.toml", dir), self.crate_manifest(&name, Some(component), &target)));
+                    members.push(dir);
+                }
+                ComponentBuild::Library => {
+                    let mut c_sources: Vec<&str> = sources.iter().map(|(file, _)| file.as_str())
+                        .filter(|file| [".c", ".cc", ".cpp"].iter().any(|ext| file.ends_with(ext)))
+                        .collect();
+                    c_sources.sort();
+                    if c_sources.iter().any(|file| !file.ends_with(".c")) {
+                        languages.insert("CXX");
+                    }
+                    let target = sanitize_identifier(&name);
+                    let cmake = if c_sources.is_empty() {
+                        format!("add_library({} INTERFACE)\ntarget_include_directories({} INTERFACE ${{CMAKE_CURRENT_SOURCE_DIR}})\n", target, target)
+                    } else {
+                        format!("add_library({} STATIC {})\ntarget_include_directories({} PUBLIC ${{CMAKE_CURRENT_SOURCE_DIR}})\n", target, c_sources.join(" "), target)
+                    };
+                    project.files.push(text_file(&format!("{}/CMakeLists.txt", dir), cmake));
+                    subdirectories.push(dir);
+                }
+                ComponentBuild::None if !sources.is_empty() => {
+                    project.warnings.push(format!("Component '{}' is copied to {} but not built by the {} project", component.name, dir, self.format.name()));
+                }
+                ComponentBuild::None => {}
+            }
+        }
+        
+        // Channel declarations and kernel options of the IPC components on the canvas
+        if let Some(canvas) = self.canvas.as_ref().filter(|canvas| canvas.nodes.values().any(|node| IpcKind::of_component(&node.component_id).is_some())) {
+            let artifacts = generate_ipc_stubs(canvas).map_err(|e| format!("Failed to generate IPC stubs: {}", e))?;
+            project.files.push(text_file("generated/ipc/osland_ipc_channels.h", artifacts.header));
+            project.files.push(text_file("generated/ipc/ipc.config", artifacts.kconfig));
+        }
+        
+        // Kernel options and initialization sequence of the network stack on the canvas
+        if let Some(canvas) = self.canvas.as_ref().filter(|canvas| canvas.nodes.values().any(|node| NetworkKind::of_component(&node.component_id).is_some())) {
+            let artifacts = generate_network_config(canvas).map_err(|e| format!("Failed to generate the network configuration: {}", e))?;
+            project.files.push(text_file("generated/network/network.config", artifacts.kconfig));
+            project.files.push(text_file("generated/network/osland_net_init.c", artifacts.init_source));
+        }
+        
+        // Generated files stay inside their own directory, whatever name and paths they were added with
+        let mut generated_names = BTreeSet::from(["ipc".to_string(), "network".to_string()]);
+        for (name, files) in &self.generated {
+            let name = unique_name(&mut generated_names, name);
+            for file in files {
+                match relative_file(&file.path) {
+                    Some(path) => project.files.push(text_file(&format!("generated/{}/{}", name, path), file.content.clone())),
+                    None => project.warnings.push(format!("Generated file {} of '{}' has no file name and is skipped", file.path.display(), name)),
+                }
+            }
+        }
+        
+        match self.format {
+            ExportFormat::Cargo => {
+                let mut manifest = format!("# Generated by OSland from {} {}\n[workspace]\nresolver = \"2\"\nmembers = [\n", self.name, self.version);
+                for member in &members {
+                    manifest.push_str(&format!("    \"{}\",\n", member));
+                }
+                manifest.push_str("]\n");
+                project.files.push(text_file("Cargo.toml", manifest));
+                project.files.push(text_file(".gitignore", "/target\n".to_string()));
+            }
+            ExportFormat::CMake => {
+                let mut cmake = format!("# Generated by OSland from {} {}\ncmake_minimum_required(VERSION 3.16)\n", self.name, self.version);
+                let version = if !self.version.is_empty() && self.version.split('.').all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit())) {
+                    format!(" VERSION {}", self.version)
+                } else {
+                    String::new()
+                };
+                cmake.push_str(&format!("project({}{} LANGUAGES {})\n\n", sanitize_identifier(&self.name), version, languages.into_iter().collect::<Vec<_>>().join(" ")));
+                for dir in &subdirectories {
+                    cmake.push_str(&format!("add_subdirectory({})\n", dir));
+                }
+                project.files.push(text_file("CMakeLists.txt", cmake));
+                project.files.push(text_file(".gitignore", "/build\n".to_string()));
+            }
+        }
+        project.files.push(text_file("README.md", self.readme(&project.warnings)));
+        Ok(project)
+    }
+    
+    /// Components of the canvas, once per component ID
+    fn components(&self) -> Vec<&Component> {
+        let mut components: BTreeMap<&str, &Component> = BTreeMap::new();
+        for node in self.canvas.iter().flat_map(|canvas| canvas.nodes.values()) {
+            components.entry(node.component_id.as_str()).or_insert(&node.component);
+        }
+        components.into_values().collect()
+    }
+    
+    fn resolve(&self, file: &str) -> PathBuf {
+        let path = PathBuf::from(file);
+        match &self.source_root {
+            Some(root) if path.is_relative() => root.join(path),
+            _ => path,
+        }
+    }
+    
+    fn crate_manifest(&self, name: &str, component: Option<&Component>, target: &str) -> String {
+        let version = component.map(|component| component.version.as_str()).filter(|version| !version.is_empty()).unwrap_or(&self.version);
+        let mut manifest = format!("[package]\nname = \"{}\"\nversion = \"{}\"\nedition = \"2021\"\npublish = false\n", name, version);
+        if let Some(license) = component.map(|component| component.license.as_str()).filter(|license| !license.is_empty()) {
+            manifest.push_str(&format!("license = \"{}\"\n", license));
+        }
+        manifest.push('\n');
+        manifest.push_str(target);
+        manifest
+    }
+    
+    fn readme(&self, warnings: &[String]) -> String {
+        let mut readme = format!("# {}\n\nExported from OSland (version {}).\n\n## Building\n\n", self.name, self.version);
+        readme.push_str(match self.format {
+            ExportFormat::Cargo => "```sh\ncargo build --workspace\n```\n",
+            ExportFormat::CMake => "```sh\ncmake -B build\ncmake --build build\n```\n",
+        });
+        readme.push_str("\nThe design (build configuration, canvas and tile graphs) is kept in `osland/`.\n");
+        if !warnings.is_empty() {
+            readme.push_str("\n## Notes\n\n");
+            for warning in warnings {
+                readme.push_str(&format!("- {}\n", warning));
+            }
+        }
+        readme
+    }
+}
+
+/// Makefile running the build commands of a component
+fn make_recipe(component: &Component) -> String {
+    let mut makefile = format!("# Generated by OSland from the build commands of {}\n", component.name);
+    if component.build_commands.iter().any(|command| command.contains("$(KDIR)")) {
+        makefile.push_str("KDIR ?= /lib/modules/$(shell uname -r)/build\n");
+    }
+    makefile.push_str("PWD := $(CURDIR)\n\nall:\n");
+    for command in &component.build_commands {
+        makefile.push_str(&format!("\t{}\n", command));
+    }
+    makefile
+}
+
+/// Crate and directory name: lowercase words joined by dashes, unique within the project
+fn unique_name(names: &mut BTreeSet<String>, name: &str) -> String {
+    let mut base = String::new();
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            base.push(c.to_ascii_lowercase());
+        } else if !base.is_empty() && !base.ends_with('-') {
+            base.push('-');
+        }
+    }
+    let mut base = base.trim_end_matches('-').to_string();
+    if base.is_empty() || base.starts_with(|c: char| c.is_ascii_digit()) {
+        base = format!("osland-{}", base).trim_end_matches('-').to_string();
+    }
+    
+    let mut unique = base.clone();
+    let mut suffix = 2;
+    while !names.insert(unique.clone()) {
+        unique = format!("{}-{}", base, suffix);
+        suffix += 1;
+    }
+    unique
+}
+
+/// Deepest directory containing all paths
+fn common_parent(paths: &[PathBuf]) -> PathBuf {
+    let mut base = paths.first().and_then(|path| path.parent()).map(Path::to_path_buf).unwrap_or_default();
+    while !paths.iter().all(|path| path.starts_with(&base)) {
+        if !base.pop() {
+            break;
+        }
+    }
+    base
+}
+
+/// Relative `/`-separated path made of the normal components of a path, `None` if there are none
+fn relative_file(path: &Path) -> Option<String> {
+    let parts: Vec<String> = path.components()
+        .filter_map(|component| match component {
+            PathComponent::Normal(part) => Some(part.to_string_lossy().to_string()),
+            _ => None,
+        })
+        .collect();
+    if parts.is_empty() {
+        None
+    } else {
+        Some(parts.join("/"))
+    }
+}
+
+fn text_file(path: &str, content: String) -> GeneratedFile {
+    GeneratedFile { path: PathBuf::from(path), content }
+}
+
+fn load_json<T: serde::de::DeserializeOwned>(path: &Path) -> Result<T, String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse {}: {}", path.display(), e))
+}
+
+fn json_file<T: Serialize>(path: &str, value: &T) -> Result<GeneratedFile, String> {
+    let content = serde_json::to_string_pretty(value).map_err(|e| format!("Failed to serialize {}: {}", path, e))?;
+    Ok(text_file(path, content))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component_manager::driver_wizard::{DeviceMatch, DriverBus, DriverLanguage, DriverSpec};
+    use crate::component_manager::ipc_components::create_message_queue_component;
+    use crate::component_manager::network_components::create_loopback_component;
+    use crate::component_manager::visual_node::VisualNode;
+    use crate::core::geometry::Point;
+    
+    #[test]
+    fn test_export_formats() {
+        let dir = std::env::temp_dir().join(format!("osland-export-{}", std::process::id()));
+        let spec = DriverSpec {
+            name: "acme_dev".to_string(),
+            language: DriverLanguage::C,
+            device: DeviceMatch::parse(DriverBus::Pci, "1af4:1041").unwrap(),
+            description: "ACME device".to_string(),
+            author: "OSland".to_string(),
+        };
+        let mut driver = spec.generate().unwrap();
+        driver.write(dir.join("driver")).unwrap();
+        
+        // A plain C library next to the driver
+        let mut library = driver.component.clone();
+        library.id = "ring_buffer".to_string();
+        library.name = "Ring Buffer".to_string();
+        library.build_commands.clear();
+        library.implementation_files = vec!["ring.c".to_string(), "ring.h".to_string(), "missing.c".to_string()];
+        fs::write(dir.join("ring.c"), "#include \"ring.h\"\n").unwrap();
+        fs::write(dir.join("ring.h"), "int ring_push(int);\n").unwrap();
+        
+        let mut canvas = NodeCanvas::new();
+        driver.place(&mut canvas, Point::new(0.0, 0.0)).unwrap();
+        canvas.add_node(VisualNode::new(library, Point::new(200.0, 0.0)).unwrap()).unwrap();
+        let mut graph = TileGraph::new("Packet Path".to_string());
+        graph.tiles.clear();
+        
+        let mut exporter = ProjectExporter::new("Demo OS", "0.3.1", ExportFormat::CMake);
+        exporter.set_source_root(dir.clone());
+        exporter.set_canvas(canvas.clone());
+        exporter.add_tile_graph(graph.clone());
+        let project = exporter.export().unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        
+        assert_eq!(
+            project.file("CMakeLists.txt").unwrap(),
+            "# Generated by OSland from Demo OS 0.3.1\ncmake_minimum_required(VERSION 3.16)\nproject(Demo_OS VERSION 0.3.1 LANGUAGES C)\n\nadd_subdirectory(components/acme-dev)\nadd_subdirectory(components/ring-buffer)\n"
+        );
+        assert_eq!(
+            project.file("components/ring-buffer/CMakeLists.txt").unwrap(),
+            "add_library(ring_buffer STATIC ring.c)\ntarget_include_directories(ring_buffer PUBLIC ${CMAKE_CURRENT_SOURCE_DIR})\n"
+        );
+        assert!(project.file("components/acme-dev/Makefile").unwrap().contains("KDIR ?="));
+        assert!(project.file("components/acme-dev/acme_dev.c").unwrap().contains("MODULE_LICENSE"));
+        assert!(project.file("osland/tiles/packet-path.json").is_some());
+        assert!(project.file("generated/ipc/osland_ipc_channels.h").is_none());
+        assert_eq!(project.warnings.len(), 2);
+        assert!(project.warnings[0].starts_with("Tile graph 'Packet Path' has no C code generator"));
+        assert!(project.warnings[1].contains("missing.c"));
+        
+        let mut canvas = NodeCanvas::new();
+        canvas.add_node(VisualNode::new(create_message_queue_component(), Point::new(0.0, 0.0)).unwrap()).unwrap();
+        canvas.add_node(VisualNode::new(create_loopback_component(), Point::new(200.0, 0.0)).unwrap()).unwrap();
+        let mut exporter = ProjectExporter::new("Demo OS", "0.3.1", ExportFormat::Cargo);
+        exporter.set_canvas(canvas);
+        exporter.add_tile_graph(graph);
+        let project = exporter.export().unwrap();
+        assert_eq!(project.file("Cargo.toml").unwrap(), "# Generated by OSland from Demo OS 0.3.1\n[workspace]\nresolver = \"2\"\nmembers = [\n    \"crates/packet-path\",\n]\n");
+        assert!(project.file("crates/packet-path/src/lib.rs").unwrap().contains("pub fn execute_tile_graph()"));
+        assert!(project.file("generated/ipc/osland_ipc_channels.h").unwrap().contains("#define QUEUE_DEPTH 16"));
+        assert!(project.file("generated/ipc/ipc.config").unwrap().contains("CONFIG_POSIX_MQUEUE=y"));
+        assert!(project.file("generated/network/network.config").unwrap().contains("CONFIG_NET=y\n"));
+        assert!(project.file("generated/network/osland_net_init.c").unwrap().contains("int osl_net_init(void)"));
+        assert_eq!(project.file("crates/packet-path/src/main.rs").unwrap(), "fn main() {\n    packet_path::execute_tile_graph();\n}\n");
+    }
+    
+    #[test]
+    fn test_export_paths_stay_inside_project() {
+        let dir = std::env::temp_dir().join(format!("osland-export-paths-{}", std::process::id()));
+        fs::create_dir_all(dir.join("src/net")).unwrap();
+        fs::create_dir_all(dir.join("src/fs")).unwrap();
+        fs::write(dir.join("src/net/buffer.c"), "int net_buffer;\n").unwrap();
+        fs::write(dir.join("src/fs/buffer.c"), "int fs_buffer;\n").unwrap();
+        
+        let mut component = create_message_queue_component();
+        component.build_commands.clear();
+        component.implementation_files = vec!["src/net/buffer.c".to_string(), dir.join("src/fs/buffer.c").display().to_string()];
+        let mut canvas = NodeCanvas::new();
+        canvas.add_node(VisualNode::new(component, Point::new(0.0, 0.0)).unwrap()).unwrap();
+        
+        let mut exporter = ProjectExporter::new("Demo OS", "0.3.1", ExportFormat::CMake);
+        exporter.set_source_root(dir.clone());
+        exporter.set_canvas(canvas);
+        exporter.add_generated("../../etc", vec![
+            GeneratedFile { path: PathBuf::from("/cron.d/job"), content: "job\n".to_string() },
+            GeneratedFile { path: PathBuf::from("../passwd"), content: "root\n".to_string() },
+        ]);
+        let project = exporter.export().unwrap();
+        
+        // Same-named sources keep their directories
+        let component_dir = project.files.iter()
+            .find_map(|file| file.path.to_str().and_then(|path| path.strip_suffix("/net/buffer.c")))
+            .unwrap()
+            .to_string();
+        assert_eq!(project.file(&format!("{}/net/buffer.c", component_dir)).unwrap(), "int net_buffer;\n");
+        assert_eq!(project.file(&format!("{}/fs/buffer.c", component_dir)).unwrap(), "int fs_buffer;\n");
+        assert!(project.file(&format!("{}/CMakeLists.txt", component_dir)).unwrap().contains("STATIC fs/buffer.c net/buffer.c"));
+        
+        // Generated names and paths cannot leave the project
+        assert_eq!(project.file("generated/etc/cron.d/job").unwrap(), "job\n");
+        assert_eq!(project.file("generated/etc/passwd").unwrap(), "root\n");
+        let written = project.write(dir.join("out")).unwrap();
+        assert!(written.iter().all(|path| path.starts_with(dir.join("out"))));
+        
+        let escaping = ExportedProject { files: vec![text_file("../escape", String::new())], warnings: Vec::new() };
+        assert!(escaping.write(dir.join("out")).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
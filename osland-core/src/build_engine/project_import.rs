@@ -0,0 +1,986 @@
+// Project import for OSland build engine
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::core::geometry::Point;
+use crate::component_manager::component::{Component, ComponentCategory, ComponentLibrary, ComponentProperty, ComponentType};
+use crate::component_manager::ipc_components::{extend_with_ipc_components, IpcKind};
+use crate::component_manager::network_components::{create_network_component_library, NetworkKind};
+use crate::component_manager::visual_node::{NodeCanvas, VisualNode};
+use crate::core::architecture::{HardwareArchitecture, KernelArchitecture};
+use crate::os_design::syscall_designer::GeneratedFile;
+use super::build_config::{BuildConfig, BuildStepType, DownloadConfig, ToolchainConfig, ToolchainType};
+
+/// Node property naming the package a placeholder component stands for
+pub const PACKAGE_PROPERTY: &str = "package";
+
+/// Kernel options turning on the default kernel features
+const KERNEL_FEATURES: &[(&str, &str)] = &[
+    ("CONFIG_EXT4_FS", "ext4"),
+    ("CONFIG_VFAT_FS", "vfat"),
+    ("CONFIG_USB", "usb"),
+    ("CONFIG_NET", "network"),
+];
+
+/// Buildroot package symbols that are not packages
+const BUILDROOT_NON_PACKAGES: &[&str] = &["BR2_PACKAGE_HOST_", "BR2_PACKAGE_HAS_", "BR2_PACKAGE_PROVIDES_"];
+
+/// Value of a Kconfig symbol
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KconfigValue {
+    /// `=y`
+    Yes,
+    /// `=m`
+    Module,
+    /// `# ... is not set`
+    No,
+    /// String, integer or hex value as written (strings keep their quotes)
+    Value(String),
+}
+
+/// Symbols of a `.config`, defconfig or config fragment
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KconfigFile {
+    symbols: BTreeMap<String, KconfigValue>,
+}
+
+impl KconfigFile {
+    /// Parse the content of a config file
+    pub fn parse(content: &str) -> Self {
+        let mut symbols = BTreeMap::new();
+        for line in content.lines().map(str::trim) {
+            if let Some(symbol) = line.strip_prefix("# ").and_then(|rest| rest.strip_suffix(" is not set")) {
+                symbols.insert(symbol.to_string(), KconfigValue::No);
+            } else if line.starts_with('#') {
+                continue;
+            } else if let Some((symbol, value)) = line.split_once('=') {
+                let value = match value {
+                    "y" => KconfigValue::Yes,
+                    "m" => KconfigValue::Module,
+                    "n" => KconfigValue::No,
+                    _ => KconfigValue::Value(value.to_string()),
+                };
+                symbols.insert(symbol.trim().to_string(), value);
+            }
+        }
+        Self { symbols }
+    }
+    
+    /// Load a config file
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        Ok(Self::parse(&content))
+    }
+    
+    /// Apply another file on top (its symbols win)
+    pub fn merge(&mut self, other: KconfigFile) {
+        self.symbols.extend(other.symbols);
+    }
+    
+    /// Value of a symbol
+    pub fn get(&self, symbol: &str) -> Option<&KconfigValue> {
+        self.symbols.get(symbol)
+    }
+    
+    /// Whether a symbol is built in or a module
+    pub fn is_enabled(&self, symbol: &str) -> bool {
+        matches!(self.get(symbol), Some(KconfigValue::Yes | KconfigValue::Module))
+    }
+    
+    /// String or number value of a symbol, without quotes
+    pub fn value(&self, symbol: &str) -> Option<String> {
+        match self.get(symbol)? {
+            KconfigValue::Value(value) => match value.strip_prefix('"').and_then(|value| value.strip_suffix('"')) {
+                Some(text) => Some(text.replace("\\\"", "\"").replace("\\\\", "\\")),
+                None => Some(value.clone()),
+            },
+            _ => None,
+        }
+    }
+    
+    /// Non-empty string value of a symbol
+    fn text(&self, symbol: &str) -> Option<String> {
+        self.value(symbol).filter(|value| !value.is_empty())
+    }
+    
+    /// Enabled symbols starting with a prefix
+    pub fn enabled_with_prefix(&self, prefix: &str) -> Vec<&str> {
+        self.symbols.iter()
+            .filter(|(symbol, value)| symbol.starts_with(prefix) && matches!(value, KconfigValue::Yes | KconfigValue::Module))
+            .map(|(symbol, _)| symbol.as_str())
+            .collect()
+    }
+    
+    /// Whether no symbol is set
+    pub fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
+    
+    /// Config file content
+    pub fn to_config(&self) -> String {
+        self.symbols.iter()
+            .map(|(symbol, value)| match value {
+                KconfigValue::Yes => format!("{}=y\n", symbol),
+                KconfigValue::Module => format!("{}=m\n", symbol),
+                KconfigValue::No => format!("# {} is not set\n", symbol),
+                KconfigValue::Value(value) => format!("{}={}\n", symbol, value),
+            })
+            .collect()
+    }
+}
+
+/// Variables of a BitBake recipe, class or configuration file
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BitbakeFile {
+    /// Variable values after all assignments, appends and removals
+    pub variables: BTreeMap<String, String>,
+    
+    /// Files pulled in with `require` or `include`
+    pub includes: Vec<String>,
+    
+    /// Inherited classes
+    pub inherits: Vec<String>,
+}
+
+impl BitbakeFile {
+    /// Parse the content of a BitBake file
+    ///
+    /// Only plain assignments are understood; `${VAR}` references to variables of the
+    /// same file are expanded, python code and other overrides are ignored.
+    pub fn parse(content: &str) -> Self {
+        let mut file = Self::default();
+        let mut pending = String::new();
+        for line in content.lines() {
+            let line = line.trim_end();
+            if let Some(continued) = line.strip_suffix('\\') {
+                pending.push_str(continued);
+                pending.push(' ');
+                continue;
+            }
+            pending.push_str(line);
+            let statement = std::mem::take(&mut pending);
+            file.parse_statement(statement.trim());
+        }
+        file
+    }
+    
+    /// Load a BitBake file
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        Ok(Self::parse(&content))
+    }
+    
+    fn parse_statement(&mut self, statement: &str) {
+        if statement.is_empty() || statement.starts_with('#') {
+            return;
+        }
+        if let Some(path) = statement.strip_prefix("require ").or_else(|| statement.strip_prefix("include ")) {
+            self.includes.push(path.trim().to_string());
+            return;
+        }
+        if let Some(classes) = statement.strip_prefix("inherit ") {
+            self.inherits.extend(classes.split_whitespace().map(str::to_string));
+            return;
+        }
+        
+        let Some(position) = statement.find(['=', '?', ':', '+', '.']).filter(|&position| position > 0) else {
+            return;
+        };
+        let (name, rest) = statement.split_at(position);
+        let mut name = name.trim().trim_start_matches("export ").to_string();
+        let mut operator = rest;
+        // `VAR:append`, `VAR:prepend` and `VAR:remove` (and the older `_append` forms)
+        let mut suffix = None;
+        if let Some(stripped) = operator.strip_prefix(':').filter(|stripped| !stripped.starts_with('=')) {
+            let end = stripped.find(|c: char| c.is_whitespace() || c == '=').unwrap_or(stripped.len());
+            suffix = Some(stripped[..end].to_string());
+            operator = stripped[end..].trim_start();
+        }
+        for old in ["_append", "_prepend", "_remove"] {
+            if let Some(stripped) = name.strip_suffix(old) {
+                suffix = Some(old[1..].to_string());
+                name = stripped.to_string();
+                break;
+            }
+        }
+        let name = name.trim_end().to_string();
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || "_-/${}.".contains(c)) {
+            return;
+        }
+        
+        let operators = ["??=", "?=", ":=", "+=", "=+", ".=", "=.", "="];
+        let Some(op) = operators.iter().find(|op| operator.trim_start().starts_with(**op)) else {
+            return;
+        };
+        let value = operator.trim_start()[op.len()..].trim();
+        let value = value.strip_prefix('"').and_then(|value| value.strip_suffix('"'))
+            .or_else(|| value.strip_prefix('\'').and_then(|value| value.strip_suffix('\'')))
+            .unwrap_or(value);
+        let value = self.expand(value);
+        let current = self.variables.get(&name).cloned();
+        
+        let new_value = match (suffix.as_deref(), *op) {
+            (Some("append"), _) => format!("{}{}", current.unwrap_or_default(), value),
+            (Some("prepend"), _) => format!("{}{}", value, current.unwrap_or_default()),
+            (Some("remove"), _) => {
+                let removed: BTreeSet<&str> = value.split_whitespace().collect();
+                current.unwrap_or_default().split_whitespace().filter(|word| !removed.contains(word)).collect::<Vec<_>>().join(" ")
+            }
+            // Other overrides (machine, distro, ...) only apply conditionally
+            (Some(_), _) => return,
+            (None, "??=" | "?=") if current.is_some() => return,
+            (None, "+=") => match current {
+                Some(current) if !current.is_empty() => format!("{} {}", current, value),
+                _ => value,
+            },
+            (None, "=+") => match current {
+                Some(current) if !current.is_empty() => format!("{} {}", value, current),
+                _ => value,
+            },
+            (None, ".=") => format!("{}{}", current.unwrap_or_default(), value),
+            (None, "=.") => format!("{}{}", value, current.unwrap_or_default()),
+            (None, _) => value,
+        };
+        self.variables.insert(name, new_value);
+    }
+    
+    /// Expand `${VAR}` references to known variables, dropping unknown ones
+    fn expand(&self, value: &str) -> String {
+        let mut expanded = String::new();
+        let mut rest = value;
+        while let Some(start) = rest.find("${") {
+            expanded.push_str(&rest[..start]);
+            match rest[start + 2..].find('}') {
+                Some(end) => {
+                    let name = &rest[start + 2..start + 2 + end];
+                    if let Some(value) = self.variables.get(name) {
+                        expanded.push_str(value);
+                    }
+                    rest = &rest[start + 3 + end..];
+                }
+                None => {
+                    rest = &rest[start..];
+                    break;
+                }
+            }
+        }
+        expanded.push_str(rest);
+        expanded
+    }
+    
+    /// Value of a variable
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.variables.get(name).map(String::as_str)
+    }
+    
+    /// Whitespace separated words of a variable
+    pub fn words(&self, name: &str) -> Vec<String> {
+        self.get(name).map(|value| value.split_whitespace().map(str::to_string).collect()).unwrap_or_default()
+    }
+}
+
+/// Kind of project an import comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportSource {
+    Buildroot,
+    Yocto,
+}
+
+impl ImportSource {
+    /// Name used in messages
+    pub fn name(&self) -> &'static str {
+        match self {
+            ImportSource::Buildroot => "Buildroot",
+            ImportSource::Yocto => "Yocto",
+        }
+    }
+}
+
+/// Package selected by the imported project
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportedPackage {
+    /// Package name
+    pub name: String,
+    
+    /// Version, when the recipe or package file was found
+    pub version: Option<String>,
+    
+    /// License, when the recipe or package file was found
+    pub license: Option<String>,
+    
+    /// Library component the package was matched to
+    pub component_id: Option<String>,
+}
+
+/// OSland project reconstructed from a Buildroot or Yocto project
+#[derive(Debug, Clone)]
+pub struct ImportedProject {
+    /// Kind of the imported project
+    pub source: ImportSource,
+    
+    /// Target hardware architecture
+    pub hardware_architecture: Option<HardwareArchitecture>,
+    
+    /// Build configuration
+    pub config: BuildConfig,
+    
+    /// Canvas with the selected components
+    pub canvas: NodeCanvas,
+    
+    /// Selected packages
+    pub packages: Vec<ImportedPackage>,
+    
+    /// Kernel options found in the project
+    pub kernel_options: KconfigFile,
+    
+    /// Settings that could not be carried over
+    pub warnings: Vec<String>,
+}
+
+impl ImportedProject {
+    /// Project files: `build.json`, `canvas.json` and `kernel.config` when kernel options were found
+    pub fn files(&self) -> Result<Vec<GeneratedFile>, String> {
+        let mut files = vec![
+            GeneratedFile {
+                path: PathBuf::from("build.json"),
+                content: serde_json::to_string_pretty(&self.config).map_err(|e| format!("Failed to serialize build configuration: {}", e))?,
+            },
+            GeneratedFile {
+                path: PathBuf::from("canvas.json"),
+                content: serde_json::to_string_pretty(&self.canvas).map_err(|e| format!("Failed to serialize canvas: {}", e))?,
+            },
+        ];
+        if !self.kernel_options.is_empty() {
+            files.push(GeneratedFile { path: PathBuf::from("kernel.config"), content: self.kernel_options.to_config() });
+        }
+        Ok(files)
+    }
+    
+    /// Write the project files into a directory
+    pub fn write<P: AsRef<Path>>(&self, dir: P) -> Result<Vec<PathBuf>, String> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        self.files()?.iter().map(|file| {
+            let path = dir.join(&file.path);
+            fs::write(&path, &file.content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+            Ok(path)
+        }).collect()
+    }
+}
+
+/// Reconstructs approximate OSland projects from Buildroot and Yocto projects
+///
+/// Packages are matched by name against a component library; packages without a
+/// matching component are placed as placeholder components so they stay visible on
+/// the canvas. Kernel options select the network and IPC components they enable.
+pub struct ProjectImporter {
+    library: ComponentLibrary,
+}
+
+impl ProjectImporter {
+    /// Create an importer matching packages against a library
+    pub fn new(library: ComponentLibrary) -> Self {
+        Self { library }
+    }
+    
+    /// Create an importer with the network and IPC components
+    pub fn with_default_library() -> Self {
+        let mut library = create_network_component_library();
+        extend_with_ipc_components(&mut library);
+        Self::new(library)
+    }
+    
+    /// Import a Buildroot `.config` or defconfig
+    ///
+    /// Paths in the config (kernel config, overlays) and package files are resolved
+    /// against the Buildroot tree when given.
+    pub fn import_buildroot<P: AsRef<Path>>(&self, config_path: P, tree: Option<&Path>) -> Result<ImportedProject, String> {
+        let config_path = config_path.as_ref();
+        let config = KconfigFile::load(config_path)?;
+        let name = config_path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+        let name = name.trim_end_matches("_defconfig").trim_start_matches('.').to_string();
+        Ok(self.import_buildroot_config(if name.is_empty() || name == "config" { "buildroot" } else { &name }, &config, tree))
+    }
+    
+    /// Import parsed Buildroot symbols
+    pub fn import_buildroot_config(&self, name: &str, br: &KconfigFile, tree: Option<&Path>) -> ImportedProject {
+        let resolve = |path: String| match tree {
+            Some(tree) if Path::new(&path).is_relative() => tree.join(path),
+            _ => PathBuf::from(path),
+        };
+        let mut project = self.new_project(ImportSource::Buildroot, name);
+        
+        // Architecture and toolchain
+        let arch = if br.is_enabled("BR2_aarch64") || br.is_enabled("BR2_aarch64_be") {
+            Some(HardwareArchitecture::Aarch64)
+        } else if br.is_enabled("BR2_x86_64") {
+            Some(HardwareArchitecture::X86_64)
+        } else if br.is_enabled("BR2_riscv") && br.is_enabled("BR2_RISCV_64") {
+            Some(HardwareArchitecture::RiscV64)
+        } else if br.is_enabled("BR2_powerpc64") || br.is_enabled("BR2_powerpc64le") {
+            Some(HardwareArchitecture::PowerPC64)
+        } else {
+            None
+        };
+        if arch.is_none() {
+            let symbol = br.enabled_with_prefix("BR2_").into_iter()
+                .find(|symbol| symbol[4..].chars().next().is_some_and(|c| c.is_ascii_lowercase()))
+                .unwrap_or("none");
+            project.warnings.push(format!("Unsupported target architecture ({})", symbol));
+        }
+        let prefix = br.text("BR2_TOOLCHAIN_EXTERNAL_CUSTOM_PREFIX").filter(|prefix| !prefix.contains("$("))
+            .map(|prefix| format!("{}-", prefix))
+            .unwrap_or_else(|| cross_prefix(arch.as_ref()).to_string());
+        project.config.toolchain_config = gnu_toolchain(&prefix);
+        project.config.toolchain_config.toolchain_path = br.text("BR2_TOOLCHAIN_EXTERNAL_PATH").map(PathBuf::from);
+        project.hardware_architecture = arch;
+        
+        // Kernel
+        if br.is_enabled("BR2_LINUX_KERNEL") {
+            let version = br.text("BR2_LINUX_KERNEL_CUSTOM_VERSION_VALUE").or_else(|| br.text("BR2_LINUX_KERNEL_VERSION"));
+            if let Some(version) = &version {
+                project.config.kernel_config.kernel_version = version.clone();
+            }
+            let url = br.text("BR2_LINUX_KERNEL_CUSTOM_TARBALL_LOCATION").or_else(|| version.as_deref().and_then(kernel_tarball_url));
+            project.config.kernel_config.download = url.map(|url| DownloadConfig { urls: vec![url], sha256: None, file_name: None, strip_components: 1 });
+            
+            if br.is_enabled("BR2_LINUX_KERNEL_USE_CUSTOM_CONFIG") {
+                if let Some(file) = br.text("BR2_LINUX_KERNEL_CUSTOM_CONFIG_FILE") {
+                    let path = resolve(file);
+                    match KconfigFile::load(&path) {
+                        Ok(options) => project.kernel_options.merge(options),
+                        Err(e) => project.warnings.push(e),
+                    }
+                    project.config.kernel_config.config_file = Some(path);
+                }
+            } else if let Some(defconfig) = br.text("BR2_LINUX_KERNEL_DEFCONFIG") {
+                project.warnings.push(format!("Kernel is configured with {}_defconfig; only the fragments are imported", defconfig));
+            }
+            for fragment in br.text("BR2_LINUX_KERNEL_CONFIG_FRAGMENT_FILES").unwrap_or_default().split_whitespace() {
+                match KconfigFile::load(resolve(fragment.to_string())) {
+                    Ok(options) => project.kernel_options.merge(options),
+                    Err(e) => project.warnings.push(e),
+                }
+            }
+        } else {
+            project.warnings.push("The project does not build a Linux kernel".to_string());
+        }
+        
+        // Root filesystem
+        let fs_type = if br.is_enabled("BR2_TARGET_ROOTFS_EXT2") {
+            Some(if br.is_enabled("BR2_TARGET_ROOTFS_EXT2_4") {
+                "ext4"
+            } else if br.is_enabled("BR2_TARGET_ROOTFS_EXT2_3") {
+                "ext3"
+            } else {
+                "ext2"
+            })
+        } else if br.is_enabled("BR2_TARGET_ROOTFS_SQUASHFS") {
+            Some("squashfs")
+        } else if br.is_enabled("BR2_TARGET_ROOTFS_INITRAMFS") || br.is_enabled("BR2_TARGET_ROOTFS_CPIO") {
+            Some("initramfs")
+        } else {
+            None
+        };
+        if let Some(fs_type) = fs_type {
+            set_rootfs_type(&mut project.config, fs_type);
+        }
+        if let Some(size) = br.text("BR2_TARGET_ROOTFS_EXT2_SIZE").and_then(|size| parse_size(&size)) {
+            project.config.rootfs_config.size = Some(size);
+        }
+        if let Some(overlay) = br.text("BR2_ROOTFS_OVERLAY") {
+            let overlays: Vec<&str> = overlay.split_whitespace().collect();
+            if overlays.len() > 1 {
+                project.warnings.push(format!("Only the first of {} rootfs overlays is used", overlays.len()));
+            }
+            project.config.rootfs_config.source_dir = overlays.first().map(|overlay| resolve(overlay.to_string()));
+        }
+        
+        // Bootloader and console
+        let bootloader = [
+            ("BR2_TARGET_GRUB2", "grub"),
+            ("BR2_TARGET_UBOOT", "u-boot"),
+            ("BR2_TARGET_SYSLINUX", "syslinux"),
+            ("BR2_TARGET_BAREBOX", "barebox"),
+        ].iter().find(|(symbol, _)| br.is_enabled(symbol)).map(|(_, bootloader)| *bootloader);
+        set_bootloader(&mut project.config, bootloader);
+        if let Some(port) = br.text("BR2_TARGET_GENERIC_GETTY_PORT") {
+            let baudrate = br.enabled_with_prefix("BR2_TARGET_GENERIC_GETTY_BAUDRATE_").into_iter()
+                .filter_map(|symbol| symbol.rsplit('_').next())
+                .find(|rate| rate.chars().all(|c| c.is_ascii_digit()))
+                .map(str::to_string);
+            set_console(&mut project.config, &port, baudrate.as_deref());
+        }
+        
+        // Packages, skipping the options of enabled packages (`BR2_PACKAGE_OPENSSH_SERVER`)
+        let symbols: Vec<&str> = br.enabled_with_prefix("BR2_PACKAGE_").into_iter()
+            .filter(|symbol| !BUILDROOT_NON_PACKAGES.iter().any(|prefix| symbol.starts_with(prefix)))
+            .collect();
+        let mut packages = Vec::new();
+        for symbol in &symbols {
+            let is_option = symbols.iter().any(|other| other != symbol && symbol.starts_with(&format!("{}_", other)));
+            if !is_option {
+                let name = symbol["BR2_PACKAGE_".len()..].to_ascii_lowercase();
+                let (version, license) = tree.map(|tree| buildroot_package_info(tree, &name)).unwrap_or((None, None));
+                packages.push(ImportedPackage { name: name.replace('_', "-"), version, license, component_id: None });
+            }
+        }
+        self.finish(project, packages)
+    }
+    
+    /// Import a Yocto layer
+    ///
+    /// The machine (`conf/machine/<machine>.conf`) and image recipe (`<image>.bb`)
+    /// default to the first ones found in the layer.
+    pub fn import_yocto<P: AsRef<Path>>(&self, layer: P, machine: Option<&str>, image: Option<&str>) -> Result<ImportedProject, String> {
+        let layer = layer.as_ref();
+        let layer_conf = BitbakeFile::load(layer.join("conf").join("layer.conf"))?;
+        let name = layer_conf.words("BBFILE_COLLECTIONS").into_iter().next()
+            .or_else(|| layer.file_name().map(|name| name.to_string_lossy().to_string()))
+            .unwrap_or_else(|| "yocto".to_string());
+        let mut project = self.new_project(ImportSource::Yocto, &name);
+        if let Some(version) = layer_conf.get(&format!("LAYERVERSION_{}", name)) {
+            project.config.project_version = version.to_string();
+        }
+        
+        let mut files = Vec::new();
+        collect_files(layer, &mut files);
+        files.sort();
+        let find = |dir: &str, extension: &str, wanted: Option<&str>| files.iter()
+            .filter(|path| path.extension().is_some_and(|ext| ext == extension))
+            .filter(|path| path.parent().is_some_and(|parent| parent.ends_with(dir)))
+            .find(|path| wanted.map_or(true, |wanted| path.file_stem().is_some_and(|stem| stem == wanted)))
+            .cloned();
+        
+        // Machine
+        let machine_conf = match find("conf/machine", "conf", machine) {
+            Some(path) => BitbakeFile::load(path)?,
+            None => {
+                if let Some(machine) = machine {
+                    return Err(format!("Machine '{}' not found in {}", machine, layer.display()));
+                }
+                project.warnings.push("The layer defines no machine".to_string());
+                BitbakeFile::default()
+            }
+        };
+        let tune = format!("{} {} {}", machine_conf.get("DEFAULTTUNE").unwrap_or(""), machine_conf.get("TUNE_FEATURES").unwrap_or(""), machine_conf.includes.join(" "));
+        let arch = yocto_architecture(&tune);
+        if arch.is_none() && !machine_conf.variables.is_empty() {
+            project.warnings.push("Could not determine the target architecture of the machine".to_string());
+        }
+        project.config.toolchain_config = gnu_toolchain(cross_prefix(arch.as_ref()));
+        project.hardware_architecture = arch;
+        
+        // Image
+        let image_recipe = match find("images", "bb", image) {
+            Some(path) => BitbakeFile::load(path)?,
+            None => {
+                if let Some(image) = image {
+                    return Err(format!("Image recipe '{}' not found in {}", image, layer.display()));
+                }
+                project.warnings.push("The layer defines no image recipe".to_string());
+                BitbakeFile::default()
+            }
+        };
+        
+        // Kernel
+        let kernel = machine_conf.get("PREFERRED_PROVIDER_virtual/kernel").unwrap_or("linux-yocto").to_string();
+        if let Some(version) = machine_conf.get(&format!("PREFERRED_VERSION_{}", kernel)) {
+            project.config.kernel_config.kernel_version = version.trim_end_matches('%').to_string();
+        }
+        project.config.kernel_config.kernel_name = kernel;
+        for fragment in files.iter().filter(|path| path.extension().is_some_and(|ext| ext == "cfg") && path.components().any(|part| part.as_os_str() == "recipes-kernel")) {
+            project.kernel_options.merge(KconfigFile::load(fragment)?);
+        }
+        if !project.kernel_options.is_empty() {
+            project.config.kernel_config.config_file = Some(PathBuf::from("kernel.config"));
+        }
+        
+        // Root filesystem, bootloader and console
+        let fs_types = {
+            let mut fs_types = image_recipe.words("IMAGE_FSTYPES");
+            fs_types.extend(machine_conf.words("IMAGE_FSTYPES"));
+            fs_types
+        };
+        let fs_type = fs_types.iter().find_map(|fs_type| match fs_type.as_str() {
+            "ext2" | "ext3" | "ext4" | "squashfs" => Some(fs_type.as_str()),
+            "cpio" | "cpio.gz" | "cpio.xz" => Some("initramfs"),
+            _ => None,
+        });
+        if let Some(fs_type) = fs_type {
+            set_rootfs_type(&mut project.config, fs_type);
+        }
+        let bootloader = machine_conf.get("PREFERRED_PROVIDER_virtual/bootloader")
+            .or_else(|| machine_conf.get("EFI_PROVIDER"))
+            .or_else(|| machine_conf.words("EXTRA_IMAGEDEPENDS").iter().any(|dep| dep == "u-boot").then_some("u-boot"))
+            .map(|provider| match provider {
+                "grub-efi" | "grub" => "grub",
+                provider if provider.starts_with("u-boot") => "u-boot",
+                provider => provider,
+            });
+        set_bootloader(&mut project.config, bootloader);
+        if let Some((baudrate, port)) = machine_conf.words("SERIAL_CONSOLES").first().and_then(|console| console.split_once(';')) {
+            set_console(&mut project.config, port, Some(baudrate));
+        }
+        
+        // Packages, with the versions and licenses of the layer's recipes
+        let mut recipes = BTreeMap::new();
+        for path in files.iter().filter(|path| path.extension().is_some_and(|ext| ext == "bb")) {
+            let stem = path.file_stem().map(|stem| stem.to_string_lossy().to_string()).unwrap_or_default();
+            let (recipe, version) = match stem.split_once('_') {
+                Some((recipe, version)) => (recipe.to_string(), Some(version.to_string())),
+                None => (stem, None),
+            };
+            let license = BitbakeFile::load(path).ok().and_then(|file| file.get("LICENSE").map(str::to_string));
+            recipes.insert(recipe, (version, license));
+        }
+        let mut names: Vec<String> = image_recipe.words("IMAGE_INSTALL");
+        names.extend(image_recipe.words("CORE_IMAGE_EXTRA_INSTALL"));
+        names.extend(machine_conf.words("MACHINE_EXTRA_RRECOMMENDS"));
+        let mut seen = BTreeSet::new();
+        let packages = names.into_iter()
+            .filter(|name| seen.insert(name.clone()))
+            .map(|name| {
+                let (version, license) = recipes.get(&name).cloned().unwrap_or((None, None));
+                ImportedPackage { name, version, license, component_id: None }
+            })
+            .collect();
+        Ok(self.finish(project, packages))
+    }
+    
+    fn new_project(&self, source: ImportSource, name: &str) -> ImportedProject {
+        let mut config = BuildConfig::default(KernelArchitecture::Monolithic);
+        config.project_name = name.to_string();
+        ImportedProject {
+            source,
+            hardware_architecture: None,
+            config,
+            canvas: NodeCanvas::new(),
+            packages: Vec::new(),
+            kernel_options: KconfigFile::default(),
+            warnings: Vec::new(),
+        }
+    }
+    
+    /// Derive kernel features from the kernel options and place the components
+    fn finish(&self, mut project: ImportedProject, mut packages: Vec<ImportedPackage>) -> ImportedProject {
+        let options = &project.kernel_options;
+        if !options.is_empty() {
+            project.config.kernel_config.features = KERNEL_FEATURES.iter()
+                .filter(|(symbol, _)| options.is_enabled(symbol))
+                .map(|(_, feature)| feature.to_string())
+                .collect();
+            project.config.kernel_config.modules = options.symbols.iter()
+                .filter(|(_, value)| **value == KconfigValue::Module)
+                .map(|(symbol, _)| symbol.trim_start_matches("CONFIG_").to_ascii_lowercase())
+                .collect();
+        }
+        
+        let mut components: Vec<Component> = Vec::new();
+        let kernel_components = NetworkKind::all().into_iter()
+            .filter(|kind| options.is_enabled(network_kconfig_option(*kind)))
+            .map(|kind| kind.component_id())
+            .chain(IpcKind::all().into_iter()
+                .filter(|kind| kind.kconfig_options().iter().all(|option| options.is_enabled(option)))
+                .map(|kind| kind.component_id()));
+        for id in kernel_components {
+            if let Some(component) = self.library.get_component(id) {
+                components.push(component.clone());
+            }
+        }
+        
+        for package in &mut packages {
+            let name = normalize(&package.name);
+            match self.library.get_all_components().into_iter().find(|component| normalize(&component.id) == name || normalize(&component.name) == name) {
+                Some(component) => {
+                    package.component_id = Some(component.id.clone());
+                    if !components.iter().any(|placed| placed.id == component.id) {
+                        components.push(component.clone());
+                    }
+                }
+                None => components.push(package_component(package, project.source)),
+            }
+        }
+        
+        for (index, component) in components.into_iter().enumerate() {
+            let position = Point::new((index % 4) as f64 * 240.0, (index / 4) as f64 * 160.0);
+            match VisualNode::new(component, position).and_then(|node| project.canvas.add_node(node)) {
+                Ok(()) => {}
+                Err(e) => project.warnings.push(format!("Failed to place component: {}", e)),
+            }
+        }
+        project.packages = packages;
+        project
+    }
+}
+
+/// Kernel option a network component needs
+fn network_kconfig_option(kind: NetworkKind) -> &'static str {
+    match kind {
+        NetworkKind::Ethernet => "CONFIG_ETHERNET",
+        NetworkKind::Loopback | NetworkKind::SocketApi => "CONFIG_NET",
+        NetworkKind::Ip | NetworkKind::Tcp | NetworkKind::Udp => "CONFIG_INET",
+    }
+}
+
+/// Placeholder component for a package without a library component
+fn package_component(package: &ImportedPackage, source: ImportSource) -> Component {
+    Component {
+        id: format!("pkg_{}", normalize(&package.name)),
+        name: package.name.clone(),
+        display_name: package.name.clone(),
+        component_type: ComponentType::Custom("Package".to_string()),
+        category: ComponentCategory::Utilities,
+        version: package.version.clone().unwrap_or_default(),
+        description: format!("{} package imported from {}", package.name, source.name()),
+        author: String::new(),
+        source_url: None,
+        license: package.license.clone().unwrap_or_default(),
+        properties: vec![ComponentProperty {
+            name: PACKAGE_PROPERTY.to_string(),
+            value: package.name.clone(),
+            property_type: "string".to_string(),
+            description: format!("{} package", source.name()),
+            required: true,
+            default_value: None,
+            valid_values: None,
+        }],
+        ports: Vec::new(),
+        dependencies: Vec::new(),
+        supported_architectures: Default::default(),
+        supported_languages: Vec::new(),
+        implementation_files: Vec::new(),
+        build_commands: Vec::new(),
+        initialization_code: String::new(),
+    }
+}
+
+/// Version and license from `package/<name>/<name>.mk` of a Buildroot tree
+fn buildroot_package_info(tree: &Path, name: &str) -> (Option<String>, Option<String>) {
+    for dir in [name.replace('_', "-"), name.to_string()] {
+        let Ok(content) = fs::read_to_string(tree.join("package").join(&dir).join(format!("{}.mk", dir))) else {
+            continue;
+        };
+        let variable = dir.replace('-', "_").to_ascii_uppercase();
+        let value = |suffix: &str| content.lines()
+            .filter_map(|line| line.split_once('='))
+            .find(|(key, _)| key.trim() == format!("{}_{}", variable, suffix))
+            .map(|(_, value)| value.trim().to_string());
+        return (value("VERSION"), value("LICENSE"));
+    }
+    (None, None)
+}
+
+/// Architecture named by Yocto tune settings and machine includes
+fn yocto_architecture(tune: &str) -> Option<HardwareArchitecture> {
+    let tune = tune.to_ascii_lowercase();
+    let has = |needles: &[&str]| needles.iter().any(|needle| tune.contains(needle));
+    if has(&["aarch64", "armv8", "armv9", "cortexa53", "cortexa57", "cortexa72", "arm64"]) {
+        Some(HardwareArchitecture::Aarch64)
+    } else if has(&["riscv64"]) {
+        Some(HardwareArchitecture::RiscV64)
+    } else if has(&["x86-64", "x86_64", "corei7", "core2-64"]) {
+        Some(HardwareArchitecture::X86_64)
+    } else if has(&["ppc64", "powerpc64", "power9"]) {
+        Some(HardwareArchitecture::PowerPC64)
+    } else {
+        None
+    }
+}
+
+/// GNU cross compiler prefix of an architecture (none for x86_64 hosts)
+fn cross_prefix(arch: Option<&HardwareArchitecture>) -> &'static str {
+    match arch {
+        Some(HardwareArchitecture::Aarch64) => "aarch64-linux-gnu-",
+        Some(HardwareArchitecture::RiscV64) => "riscv64-linux-gnu-",
+        Some(HardwareArchitecture::PowerPC64) => "powerpc64-linux-gnu-",
+        Some(HardwareArchitecture::X86_64) | None => "",
+    }
+}
+
+fn gnu_toolchain(prefix: &str) -> ToolchainConfig {
+    let mut toolchain = ToolchainConfig::custom(
+        format!("{}gcc", prefix),
+        format!("{}g++", prefix),
+        format!("{}as", prefix),
+        format!("{}ld", prefix),
+        format!("{}strip", prefix),
+        format!("{}objcopy", prefix),
+        format!("{}objdump", prefix),
+    );
+    toolchain.toolchain_type = ToolchainType::GNU;
+    toolchain
+}
+
+/// kernel.org tarball of a release
+fn kernel_tarball_url(version: &str) -> Option<String> {
+    let major = version.split('.').next().filter(|major| !major.is_empty() && major.chars().all(|c| c.is_ascii_digit()))?;
+    Some(format!("https://cdn.kernel.org/pub/linux/kernel/v{}.x/linux-{}.tar.xz", major, version))
+}
+
+fn set_rootfs_type(config: &mut BuildConfig, fs_type: &str) {
+    config.rootfs_config.fs_type = fs_type.to_string();
+    config.rootfs_config.image_path = PathBuf::from(match fs_type {
+        "initramfs" => "rootfs.cpio".to_string(),
+        fs_type => format!("rootfs.{}", fs_type),
+    });
+}
+
+/// Set the bootloader, dropping the bootloader step when there is none
+fn set_bootloader(config: &mut BuildConfig, bootloader: Option<&str>) {
+    match bootloader {
+        Some(bootloader) => config.bootloader_config.bootloader_type = bootloader.to_string(),
+        None => {
+            let Some(index) = config.build_steps.iter().position(|step| step.step_type == BuildStepType::InstallBootloader) else {
+                return;
+            };
+            let removed = config.build_steps.remove(index);
+            for step in &mut config.build_steps {
+                if step.dependencies.contains(&removed.name) {
+                    step.dependencies.retain(|dependency| *dependency != removed.name);
+                    step.dependencies.extend(removed.dependencies.iter().cloned());
+                }
+            }
+        }
+    }
+}
+
+fn set_console(config: &mut BuildConfig, port: &str, baudrate: Option<&str>) {
+    let params = &mut config.bootloader_config.kernel_params;
+    params.retain(|param| !param.starts_with("console="));
+    params.push(match baudrate {
+        Some(baudrate) => format!("console={},{}", port, baudrate),
+        None => format!("console={}", port),
+    });
+}
+
+/// Size like `60M` in bytes
+fn parse_size(size: &str) -> Option<u64> {
+    let size = size.trim();
+    let (number, multiplier) = match size.chars().last()?.to_ascii_uppercase() {
+        'K' => (&size[..size.len() - 1], 1024),
+        'M' => (&size[..size.len() - 1], 1024 * 1024),
+        'G' => (&size[..size.len() - 1], 1024 * 1024 * 1024),
+        _ => (size, 1),
+    };
+    number.parse::<u64>().ok().map(|number| number * multiplier)
+}
+
+/// Package or component name compared case- and separator-insensitively
+fn normalize(name: &str) -> String {
+    name.chars().filter(|c| c.is_ascii_alphanumeric()).map(|c| c.to_ascii_lowercase()).collect()
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for path in entries.filter_map(|entry| entry.ok()).map(|entry| entry.path()) {
+        if path.is_dir() {
+            collect_files(&path, files);
+        } else {
+            files.push(path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_import_buildroot_and_yocto() {
+        let dir = std::env::temp_dir().join(format!("osland-import-{}", std::process::id()));
+        let write = |path: &str, content: &str| {
+            let path = dir.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        };
+        write("buildroot/configs/qemu_virt_defconfig", concat!(
+            "BR2_aarch64=y\n",
+            "BR2_TARGET_GENERIC_GETTY_PORT=\"ttyAMA0\"\n",
+            "BR2_LINUX_KERNEL=y\n",
+            "BR2_LINUX_KERNEL_CUSTOM_VERSION=y\n",
+            "BR2_LINUX_KERNEL_CUSTOM_VERSION_VALUE=\"6.1.44\"\n",
+            "BR2_LINUX_KERNEL_USE_CUSTOM_CONFIG=y\n",
+            "BR2_LINUX_KERNEL_CUSTOM_CONFIG_FILE=\"board/qemu/linux.config\"\n",
+            "BR2_PACKAGE_OPENSSH=y\n",
+            "BR2_PACKAGE_OPENSSH_SERVER=y\n",
+            "BR2_PACKAGE_HOST_QEMU=y\n",
+            "BR2_TARGET_ROOTFS_EXT2=y\n",
+            "BR2_TARGET_ROOTFS_EXT2_4=y\n",
+            "BR2_TARGET_ROOTFS_EXT2_SIZE=\"60M\"\n",
+            "# BR2_TARGET_ROOTFS_TAR is not set\n",
+        ));
+        write("buildroot/board/qemu/linux.config", "CONFIG_NET=y\nCONFIG_INET=y\nCONFIG_EXT4_FS=y\nCONFIG_VIRTIO_NET=m\n# CONFIG_USB is not set\n");
+        write("buildroot/package/openssh/openssh.mk", "OPENSSH_VERSION = 9.3p2\nOPENSSH_LICENSE = BSD-3-Clause, BSD-2-Clause, Public Domain\n");
+        
+        let importer = ProjectImporter::with_default_library();
+        let project = importer.import_buildroot(dir.join("buildroot/configs/qemu_virt_defconfig"), Some(&dir.join("buildroot"))).unwrap();
+        assert_eq!(project.config.project_name, "qemu_virt");
+        assert_eq!(project.hardware_architecture, Some(HardwareArchitecture::Aarch64));
+        assert_eq!(project.config.toolchain_config.c_compiler, "aarch64-linux-gnu-gcc");
+        assert_eq!(project.config.kernel_config.kernel_version, "6.1.44");
+        assert_eq!(project.config.kernel_config.download.as_ref().unwrap().urls, vec!["https://cdn.kernel.org/pub/linux/kernel/v6.x/linux-6.1.44.tar.xz"]);
+        assert_eq!(project.config.kernel_config.features, vec!["ext4", "network"]);
+        assert_eq!(project.config.kernel_config.modules, vec!["virtio_net"]);
+        assert_eq!(project.config.rootfs_config.fs_type, "ext4");
+        assert_eq!(project.config.rootfs_config.size, Some(60 * 1024 * 1024));
+        assert!(project.config.bootloader_config.kernel_params.contains(&"console=ttyAMA0".to_string()));
+        // No bootloader: the disk image is built right after the rootfs
+        assert!(!project.config.build_steps.iter().any(|step| step.step_type == BuildStepType::InstallBootloader));
+        let disk = project.config.build_steps.iter().find(|step| step.step_type == BuildStepType::CreateDiskImage).unwrap();
+        assert_eq!(disk.dependencies, vec!["create_rootfs"]);
+        
+        assert_eq!(project.packages, vec![ImportedPackage {
+            name: "openssh".to_string(),
+            version: Some("9.3p2".to_string()),
+            license: Some("BSD-3-Clause, BSD-2-Clause, Public Domain".to_string()),
+            component_id: None,
+        }]);
+        let mut ids: Vec<&str> = project.canvas.nodes.values().map(|node| node.component_id.as_str()).collect();
+        ids.sort();
+        assert_eq!(ids, vec!["net_ip", "net_loopback", "net_socket", "net_tcp", "net_udp", "pkg_openssh"]);
+        assert!(project.warnings.is_empty(), "{:?}", project.warnings);
+        
+        write("meta-acme/conf/layer.conf", "BBFILE_COLLECTIONS += \"acme\"\nLAYERVERSION_acme = \"2\"\n");
+        write("meta-acme/conf/machine/acme-board.conf", concat!(
+            "require conf/machine/include/riscv/tune-riscv.inc\n",
+            "DEFAULTTUNE ?= \"riscv64\"\n",
+            "PREFERRED_PROVIDER_virtual/kernel = \"linux-acme\"\n",
+            "PREFERRED_VERSION_linux-acme = \"6.6%\"\n",
+            "EXTRA_IMAGEDEPENDS += \"opensbi u-boot\"\n",
+            "SERIAL_CONSOLES = \"115200;ttyS0\"\n",
+            "IMAGE_FSTYPES ?= \"wic.gz ext4\"\n",
+        ));
+        write("meta-acme/recipes-core/images/acme-image.bb", concat!(
+            "inherit core-image\n",
+            "IMAGE_INSTALL = \"packagegroup-core-boot \\\n    acme-daemon\"\n",
+            "IMAGE_INSTALL:append = \" dropbear\"\n",
+            "IMAGE_INSTALL:remove = \"dropbear\"\n",
+            "CORE_IMAGE_EXTRA_INSTALL += \"acme-daemon\"\n",
+        ));
+        write("meta-acme/recipes-acme/acme-daemon/acme-daemon_1.2.bb", "LICENSE = \"MIT\"\n");
+        write("meta-acme/recipes-kernel/linux/linux-acme/ipc.cfg", "CONFIG_SYSVIPC=y\nCONFIG_POSIX_MQUEUE=y\n");
+        
+        let project = importer.import_yocto(dir.join("meta-acme"), None, None).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(project.config.project_name, "acme");
+        assert_eq!(project.config.project_version, "2");
+        assert_eq!(project.hardware_architecture, Some(HardwareArchitecture::RiscV64));
+        assert_eq!(project.config.kernel_config.kernel_name, "linux-acme");
+        assert_eq!(project.config.kernel_config.kernel_version, "6.6");
+        assert_eq!(project.config.bootloader_config.bootloader_type, "u-boot");
+        assert!(project.config.bootloader_config.kernel_params.contains(&"console=ttyS0,115200".to_string()));
+        assert_eq!(project.config.rootfs_config.fs_type, "ext4");
+        let packages: Vec<_> = project.packages.iter().map(|package| (package.name.as_str(), package.version.as_deref())).collect();
+        assert_eq!(packages, vec![("packagegroup-core-boot", None), ("acme-daemon", Some("1.2"))]);
+        assert!(project.canvas.nodes.values().any(|node| node.component_id == "ipc_message_queue"));
+        assert_eq!(project.files().unwrap()[2].content, "CONFIG_POSIX_MQUEUE=y\nCONFIG_SYSVIPC=y\n");
+    }
+}
@@ -0,0 +1,186 @@
+// Reproducible build support for OSland build engine
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use super::{build_config::ReproducibleConfig, BuildEngineError};
+
+/// Resolved environment for a reproducible build
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReproducibleEnvironment {
+    /// Timestamp used for every embedded date and file modification time
+    pub source_date_epoch: i64,
+    
+    /// Locale (LC_ALL / LANG)
+    pub locale: String,
+    
+    /// Time zone (TZ)
+    pub timezone: String,
+    
+    /// File creation mask
+    pub umask: u32,
+    
+    /// Build user reported by kbuild
+    pub build_user: String,
+    
+    /// Build host reported by kbuild
+    pub build_host: String,
+}
+
+impl ReproducibleEnvironment {
+    /// Resolve the environment from a configuration
+    ///
+    /// `SOURCE_DATE_EPOCH` is taken from the configuration, then the process
+    /// environment, then the last commit of the source tree, falling back to 0.
+    pub fn from_config(config: &ReproducibleConfig, source_dir: &Path) -> Self {
+        let source_date_epoch = config.source_date_epoch
+            .or_else(|| std::env::var("SOURCE_DATE_EPOCH").ok().and_then(|v| v.parse().ok()))
+            .or_else(|| git_commit_timestamp(source_dir))
+            .unwrap_or(0);
+        
+        Self {
+            source_date_epoch,
+            locale: config.locale.clone(),
+            timezone: config.timezone.clone(),
+            umask: config.umask,
+            build_user: "osland".to_string(),
+            build_host: "osland".to_string(),
+        }
+    }
+    
+    /// Environment variables exported to every build command
+    pub fn env_vars(&self) -> Vec<(String, String)> {
+        let epoch = self.source_date_epoch.to_string();
+        let timestamp = chrono::DateTime::from_timestamp(self.source_date_epoch, 0)
+            .map(|t| t.format("%a %b %e %H:%M:%S UTC %Y").to_string())
+            .unwrap_or_else(|| epoch.clone());
+        
+        vec![
+            ("SOURCE_DATE_EPOCH".to_string(), epoch),
+            ("KBUILD_BUILD_TIMESTAMP".to_string(), timestamp),
+            ("KBUILD_BUILD_USER".to_string(), self.build_user.clone()),
+            ("KBUILD_BUILD_HOST".to_string(), self.build_host.clone()),
+            ("LC_ALL".to_string(), self.locale.clone()),
+            ("LANG".to_string(), self.locale.clone()),
+            ("TZ".to_string(), self.timezone.clone()),
+            ("ZERO_AR_DATE".to_string(), "1".to_string()),
+        ]
+    }
+    
+    /// Create a command that runs under this environment
+    ///
+    /// On Unix the program is started through `sh` so the umask applies to it.
+    pub fn command(&self, program: &str) -> Command {
+        let mut cmd = if cfg!(unix) {
+            let mut cmd = Command::new("sh");
+            cmd.arg("-c")
+                .arg(format!("umask {:03o} && exec \"$0\" \"$@\"", self.umask))
+                .arg(program);
+            cmd
+        } else {
+            Command::new(program)
+        };
+        
+        for (key, value) in self.env_vars() {
+            cmd.env(key, value);
+        }
+        cmd
+    }
+    
+    /// Clamp the modification time of every file under `path` to `SOURCE_DATE_EPOCH`
+    pub fn normalize_timestamps(&self, path: &Path) -> Result<usize, BuildEngineError> {
+        let epoch = UNIX_EPOCH + Duration::from_secs(self.source_date_epoch.max(0) as u64);
+        normalize_recursive(path, epoch)
+            .map_err(|e| BuildEngineError::BuildError(format!("Failed to normalize timestamps in {}: {}", path.display(), e)))
+    }
+    
+    /// Create a tar archive with sorted entries, fixed ownership and clamped times
+    pub fn create_archive(&self, source_dir: &Path, archive: &Path) -> Result<(), BuildEngineError> {
+        let status = self.command("tar")
+            .arg("--sort=name")
+            .arg(format!("--mtime=@{}", self.source_date_epoch))
+            .args(["--owner=0", "--group=0", "--numeric-owner", "--format=gnu"])
+            .arg("-cf")
+            .arg(archive)
+            .arg("-C")
+            .arg(source_dir)
+            .arg(".")
+            .status()
+            .map_err(|e| BuildEngineError::CommandError(format!("tar: {}", e)))?;
+        
+        if status.success() {
+            Ok(())
+        } else {
+            Err(BuildEngineError::CommandError(format!("tar exited with {}", status)))
+        }
+    }
+}
+
+/// Timestamp of the last commit in a git work tree
+fn git_commit_timestamp(dir: &Path) -> Option<i64> {
+    let output = Command::new("git")
+        .args(["log", "-1", "--format=%ct"])
+        .current_dir(dir)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8_lossy(&output.stdout).trim().parse().ok()
+}
+
+fn normalize_recursive(path: &Path, epoch: SystemTime) -> std::io::Result<usize> {
+    let meta = fs::symlink_metadata(path)?;
+    if meta.file_type().is_symlink() {
+        return Ok(0);
+    }
+    
+    let mut count = 0;
+    if meta.is_dir() {
+        let mut entries: Vec<_> = fs::read_dir(path)?.collect::<Result<_, _>>()?;
+        entries.sort_by_key(|e| e.file_name());
+        for entry in entries {
+            count += normalize_recursive(&entry.path(), epoch)?;
+        }
+    }
+    
+    // Only clamp: files older than the epoch keep their time
+    if meta.modified().map(|m| m > epoch).unwrap_or(true) {
+        fs::File::open(path)?.set_modified(epoch)?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_archives_are_reproducible() {
+        if Command::new("tar").arg("--version").output().is_err() {
+            return;
+        }
+        let dir = tempfile::tempdir().unwrap();
+        let config = ReproducibleConfig { source_date_epoch: Some(1_700_000_000), ..Default::default() };
+        let env = ReproducibleEnvironment::from_config(&config, dir.path());
+        
+        // Same content written in a different order produces the same archive
+        let mut archives = Vec::new();
+        for (index, names) in [["b.txt", "a.txt"], ["a.txt", "b.txt"]].iter().enumerate() {
+            let output = dir.path().join(format!("out{}", index));
+            fs::create_dir_all(&output).unwrap();
+            for name in names {
+                fs::write(output.join(name), name.as_bytes()).unwrap();
+            }
+            env.normalize_timestamps(&output).unwrap();
+            let archive = dir.path().join(format!("out{}.tar", index));
+            env.create_archive(&output, &archive).unwrap();
+            archives.push(fs::read(&archive).unwrap());
+        }
+        assert_eq!(archives[0], archives[1]);
+    }
+}
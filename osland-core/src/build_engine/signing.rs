@@ -0,0 +1,228 @@
+// Image signing for OSland build engine
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use chrono::{DateTime, Utc};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use crate::core::secrets::read_project_secret;
+use super::{build_config::{SignatureScheme, SigningConfig}, BuildEngineError};
+
+/// Detached Ed25519 image signature (stored as `<image>.sig`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImageSignature {
+    /// Signed image file name
+    pub image: String,
+    
+    /// SHA-256 of the image
+    pub sha256: String,
+    
+    /// Signer public key (hex)
+    pub public_key: String,
+    
+    /// Signature over the SHA-256 digest (hex)
+    pub signature: String,
+    
+    /// Signing timestamp
+    pub signed_at: DateTime<Utc>,
+}
+
+/// Generate an Ed25519 key pair
+///
+/// The 32-byte secret seed is written to `key_path`; the public key is returned in hex.
+pub fn generate_ed25519_key(key_path: &Path) -> Result<String, BuildEngineError> {
+    let signing_key = SigningKey::generate(&mut rand_core::OsRng);
+    fs::write(key_path, to_hex(&signing_key.to_bytes()))
+        .map_err(|e| BuildEngineError::ImageError(format!("Failed to write signing key: {}", e)))?;
+    Ok(to_hex(signing_key.verifying_key().as_bytes()))
+}
+
+/// Sign an image according to the signing configuration
+///
+/// Returns the path of the detached signature.
+pub fn sign_image(image: &Path, config: &SigningConfig) -> Result<PathBuf, BuildEngineError> {
+    match config.scheme {
+        SignatureScheme::Ed25519 => {
+            let key = match (&config.key_secret, &config.key_path) {
+                (Some(name), _) => load_vault_signing_key(config.vault_dir.as_deref().unwrap_or(Path::new(".")), name)?,
+                (None, Some(key_path)) => load_signing_key(key_path)?,
+                (None, None) => return Err(BuildEngineError::ConfigError("Ed25519 signing requires key_path or key_secret".to_string())),
+            };
+            sign_ed25519(image, &key)
+        },
+        SignatureScheme::Gpg => sign_gpg(image, config.key_id.as_deref()),
+    }
+}
+
+/// Sign an image with an Ed25519 key
+pub fn sign_ed25519(image: &Path, key: &SigningKey) -> Result<PathBuf, BuildEngineError> {
+    let digest = image_digest(image)?;
+    let signature = ImageSignature {
+        image: image.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default(),
+        sha256: to_hex(&digest),
+        public_key: to_hex(key.verifying_key().as_bytes()),
+        signature: to_hex(&key.sign(&digest).to_bytes()),
+        signed_at: Utc::now(),
+    };
+    
+    let signature_path = with_extension_suffix(image, "sig");
+    let json = serde_json::to_string_pretty(&signature)
+        .map_err(|e| BuildEngineError::ImageError(format!("Failed to serialize signature: {}", e)))?;
+    fs::write(&signature_path, json)
+        .map_err(|e| BuildEngineError::ImageError(format!("Failed to write signature: {}", e)))?;
+    Ok(signature_path)
+}
+
+/// Verify an Ed25519 image signature
+///
+/// When `trusted_key` (hex) is given, the signature must have been made by that key;
+/// otherwise only integrity against the embedded public key is checked.
+pub fn verify_ed25519(image: &Path, signature_path: &Path, trusted_key: Option<&str>) -> Result<ImageSignature, BuildEngineError> {
+    let content = fs::read_to_string(signature_path)
+        .map_err(|e| BuildEngineError::ImageError(format!("Failed to read signature: {}", e)))?;
+    let signature: ImageSignature = serde_json::from_str(&content)
+        .map_err(|e| BuildEngineError::ImageError(format!("Failed to parse signature: {}", e)))?;
+    
+    if let Some(trusted_key) = trusted_key {
+        if !trusted_key.eq_ignore_ascii_case(&signature.public_key) {
+            return Err(BuildEngineError::ImageError("Image was signed by an untrusted key".to_string()));
+        }
+    }
+    
+    let digest = image_digest(image)?;
+    if to_hex(&digest) != signature.sha256 {
+        return Err(BuildEngineError::ImageError(format!("Image {} does not match its signature digest", image.display())));
+    }
+    
+    let public_key: [u8; 32] = from_hex(&signature.public_key)?.try_into()
+        .map_err(|_| BuildEngineError::ImageError("Invalid public key length".to_string()))?;
+    let signature_bytes: [u8; 64] = from_hex(&signature.signature)?.try_into()
+        .map_err(|_| BuildEngineError::ImageError("Invalid signature length".to_string()))?;
+    
+    let verifying_key = VerifyingKey::from_bytes(&public_key)
+        .map_err(|e| BuildEngineError::ImageError(format!("Invalid public key: {}", e)))?;
+    verifying_key.verify(&digest, &Signature::from_bytes(&signature_bytes))
+        .map_err(|_| BuildEngineError::ImageError("Signature verification failed".to_string()))?;
+    
+    Ok(signature)
+}
+
+/// Verify an image against the signature produced by `sign_image`
+pub fn verify_image(image: &Path, config: &SigningConfig, trusted_key: Option<&str>) -> Result<(), BuildEngineError> {
+    match config.scheme {
+        SignatureScheme::Ed25519 => verify_ed25519(image, &with_extension_suffix(image, "sig"), trusted_key).map(|_| ()),
+        SignatureScheme::Gpg => verify_gpg(image, &with_extension_suffix(image, "asc")),
+    }
+}
+
+/// Create an armored detached GPG signature (`<image>.asc`)
+pub fn sign_gpg(image: &Path, key_id: Option<&str>) -> Result<PathBuf, BuildEngineError> {
+    let signature_path = with_extension_suffix(image, "asc");
+    let mut cmd = Command::new("gpg");
+    cmd.args(["--batch", "--yes", "--armor", "--detach-sign"]);
+    if let Some(key_id) = key_id {
+        cmd.args(["--local-user", key_id]);
+    }
+    run_gpg(cmd.arg("--output").arg(&signature_path).arg(image))?;
+    Ok(signature_path)
+}
+
+/// Verify a detached GPG signature
+pub fn verify_gpg(image: &Path, signature_path: &Path) -> Result<(), BuildEngineError> {
+    run_gpg(Command::new("gpg").args(["--batch", "--verify"]).arg(signature_path).arg(image))
+}
+
+fn run_gpg(cmd: &mut Command) -> Result<(), BuildEngineError> {
+    let output = cmd.output()
+        .map_err(|e| BuildEngineError::CommandError(format!("gpg: {}", e)))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(BuildEngineError::ImageError(format!("gpg failed: {}", String::from_utf8_lossy(&output.stderr).trim())))
+    }
+}
+
+fn load_signing_key(path: &Path) -> Result<SigningKey, BuildEngineError> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| BuildEngineError::ImageError(format!("Failed to read signing key {}: {}", path.display(), e)))?;
+    parse_signing_key(&content)
+}
+
+fn load_vault_signing_key(project_dir: &Path, name: &str) -> Result<SigningKey, BuildEngineError> {
+    let secret = read_project_secret(project_dir, name)
+        .map_err(|e| BuildEngineError::ConfigError(e.to_string()))?;
+    let content = String::from_utf8(secret)
+        .map_err(|_| BuildEngineError::ImageError("Signing key must be a 32-byte hex seed".to_string()))?;
+    parse_signing_key(&content)
+}
+
+fn parse_signing_key(content: &str) -> Result<SigningKey, BuildEngineError> {
+    let seed: [u8; 32] = from_hex(content.trim())?.try_into()
+        .map_err(|_| BuildEngineError::ImageError("Signing key must be a 32-byte hex seed".to_string()))?;
+    Ok(SigningKey::from_bytes(&seed))
+}
+
+fn image_digest(image: &Path) -> Result<[u8; 32], BuildEngineError> {
+    let mut file = fs::File::open(image)
+        .map_err(|e| BuildEngineError::ImageError(format!("Failed to open image {}: {}", image.display(), e)))?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher)
+        .map_err(|e| BuildEngineError::ImageError(format!("Failed to read image {}: {}", image.display(), e)))?;
+    Ok(hasher.finalize().into())
+}
+
+fn with_extension_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".");
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn from_hex(hex: &str) -> Result<Vec<u8>, BuildEngineError> {
+    let invalid = || BuildEngineError::ImageError("Invalid hex string".to_string());
+    let digits = hex.chars()
+        .map(|c| c.to_digit(16).map(|d| d as u8).ok_or_else(invalid))
+        .collect::<Result<Vec<u8>, _>>()?;
+    if digits.len() % 2 != 0 {
+        return Err(invalid());
+    }
+    Ok(digits.chunks(2).map(|pair| pair[0] << 4 | pair[1]).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_ed25519_sign_and_verify() {
+        let dir = tempfile::tempdir().unwrap();
+        let image = dir.path().join("os.img");
+        fs::write(&image, b"boot sector").unwrap();
+        
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let signature_path = sign_ed25519(&image, &key).unwrap();
+        let public_key = to_hex(key.verifying_key().as_bytes());
+        assert!(verify_ed25519(&image, &signature_path, Some(&public_key)).is_ok());
+        
+        // Tampering with the image invalidates the signature
+        fs::write(&image, b"evil sector").unwrap();
+        assert!(verify_ed25519(&image, &signature_path, None).is_err());
+    }
+    
+    #[test]
+    fn test_from_hex_rejects_malformed_input() {
+        assert_eq!(from_hex("00ff7A").unwrap(), vec![0x00, 0xff, 0x7a]);
+        assert!(from_hex("abc").is_err());
+        assert!(from_hex("+f").is_err());
+        assert!(from_hex("zz").is_err());
+        assert!(from_hex("a\u{e9}0").is_err());
+    }
+}
@@ -0,0 +1,520 @@
+// QEMU test orchestration for OSland build engine
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::time::{Duration, Instant};
+use serde::{Deserialize, Serialize};
+use super::BuildEngineError;
+
+/// Default per-test timeout in seconds
+const DEFAULT_TIMEOUT_SECS: u64 = 120;
+
+/// QEMU machine configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QemuConfig {
+    /// Target architecture (x86_64, aarch64, riscv64)
+    #[serde(default = "default_arch")]
+    pub arch: String,
+    
+    /// QEMU binary (defaults to `qemu-system-<arch>`)
+    #[serde(default)]
+    pub binary: Option<String>,
+    
+    /// Machine type (defaults per architecture)
+    #[serde(default)]
+    pub machine: Option<String>,
+    
+    /// CPU model
+    #[serde(default)]
+    pub cpu: Option<String>,
+    
+    /// Memory size in MiB
+    #[serde(default = "default_memory_mb")]
+    pub memory_mb: u32,
+    
+    /// Kernel image (`-kernel`)
+    #[serde(default)]
+    pub kernel: Option<PathBuf>,
+    
+    /// Initramfs (`-initrd`)
+    #[serde(default)]
+    pub initrd: Option<PathBuf>,
+    
+    /// Disk image (attached as a virtio drive)
+    #[serde(default)]
+    pub disk_image: Option<PathBuf>,
+    
+    /// Kernel command line
+    #[serde(default)]
+    pub append: Option<String>,
+    
+    /// Enable user-mode networking with a virtio NIC
+    #[serde(default)]
+    pub network: bool,
+    
+    /// Firmware image (`-bios`), e.g. OVMF for UEFI boot
+    #[serde(default)]
+    pub firmware: Option<PathBuf>,
+    
+    /// Extra QEMU arguments
+    #[serde(default)]
+    pub extra_args: Vec<String>,
+}
+
+fn default_arch() -> String {
+    "x86_64".to_string()
+}
+
+fn default_memory_mb() -> u32 {
+    512
+}
+
+impl QemuConfig {
+    /// Build the QEMU command line for a headless run with the console on stdio
+    pub fn command(&self) -> Command {
+        let binary = self.binary.clone().unwrap_or_else(|| format!("qemu-system-{}", self.arch));
+        let mut cmd = Command::new(binary);
+        
+        let (machine, cpu, console) = match self.arch.as_str() {
+            "aarch64" => ("virt", "cortex-a57", "ttyAMA0"),
+            "riscv64" => ("virt", "rv64", "ttyS0"),
+            _ => ("q35", "max", "ttyS0"),
+        };
+        cmd.args(["-machine", self.machine.as_deref().unwrap_or(machine)])
+            .args(["-cpu", self.cpu.as_deref().unwrap_or(cpu)])
+            .args(["-m", &self.memory_mb.to_string()])
+            .args(["-nographic", "-no-reboot", "-serial", "mon:stdio"]);
+        
+        if let Some(firmware) = &self.firmware {
+            cmd.arg("-bios").arg(firmware);
+        }
+        if let Some(kernel) = &self.kernel {
+            cmd.arg("-kernel").arg(kernel);
+            let append = self.append.clone().unwrap_or_else(|| format!("console={} panic=-1", console));
+            cmd.args(["-append", &append]);
+        }
+        if let Some(initrd) = &self.initrd {
+            cmd.arg("-initrd").arg(initrd);
+        }
+        if let Some(disk) = &self.disk_image {
+            cmd.arg("-drive").arg(format!("file={},format=raw,if=virtio", disk.display()));
+        }
+        if self.network {
+            cmd.args(["-netdev", "user,id=net0", "-device", "virtio-net-pci,netdev=net0"]);
+        }
+        cmd.args(&self.extra_args);
+        cmd
+    }
+}
+
+/// Scripted console interaction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ConsoleStep {
+    /// Wait until the console output contains the text
+    Expect(String),
+    
+    /// Type a line into the console
+    Send(String),
+}
+
+/// Test kind
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum TestKind {
+    /// Boot until a marker appears on the console
+    Boot { expect: String },
+    
+    /// Run a command after boot and check its output
+    SyscallSmoke { prompt: String, command: String, expect: String },
+    
+    /// Ping a host from the guest
+    NetworkPing { prompt: String, host: String },
+    
+    /// Arbitrary console script
+    Script { steps: Vec<ConsoleStep> },
+}
+
+impl TestKind {
+    /// Console script implementing the test
+    pub fn steps(&self) -> Vec<ConsoleStep> {
+        match self {
+            TestKind::Boot { expect } => vec![ConsoleStep::Expect(expect.clone())],
+            TestKind::SyscallSmoke { prompt, command, expect } => vec![
+                ConsoleStep::Expect(prompt.clone()),
+                ConsoleStep::Send(command.clone()),
+                ConsoleStep::Expect(expect.clone()),
+            ],
+            TestKind::NetworkPing { prompt, host } => vec![
+                ConsoleStep::Expect(prompt.clone()),
+                ConsoleStep::Send(format!("ping -c 1 -W 5 {} && echo OSLAND_PING_OK", host)),
+                ConsoleStep::Expect("OSLAND_PING_OK".to_string()),
+            ],
+            TestKind::Script { steps } => steps.clone(),
+        }
+    }
+}
+
+/// Test case
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TestCase {
+    /// Test name
+    pub name: String,
+    
+    /// Test kind
+    pub kind: TestKind,
+    
+    /// Timeout in seconds
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+}
+
+/// Test suite
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TestSuite {
+    /// Suite name
+    pub name: String,
+    
+    /// Test cases
+    pub tests: Vec<TestCase>,
+}
+
+impl TestSuite {
+    /// Built-in smoke suite: boot, syscall smoke test and network ping
+    pub fn smoke() -> Self {
+        let prompt = "# ".to_string();
+        Self {
+            name: "smoke".to_string(),
+            tests: vec![
+                TestCase {
+                    name: "boot".to_string(),
+                    kind: TestKind::Boot { expect: "Welcome to OSland".to_string() },
+                    timeout_secs: None,
+                },
+                TestCase {
+                    name: "syscall_smoke".to_string(),
+                    kind: TestKind::SyscallSmoke {
+                        prompt: prompt.clone(),
+                        command: "uname -s && echo OSLAND_SYSCALL_OK".to_string(),
+                        expect: "OSLAND_SYSCALL_OK".to_string(),
+                    },
+                    timeout_secs: None,
+                },
+                TestCase {
+                    name: "network_ping".to_string(),
+                    kind: TestKind::NetworkPing { prompt, host: "10.0.2.2".to_string() },
+                    timeout_secs: None,
+                },
+            ],
+        }
+    }
+}
+
+/// Test plan (the `config` of a `RunTests` step)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TestPlan {
+    /// QEMU configuration
+    pub qemu: QemuConfig,
+    
+    /// Suites to run (defaults to the built-in smoke suite)
+    #[serde(default)]
+    pub suites: Vec<TestSuite>,
+    
+    /// JUnit XML output file (relative to the output directory)
+    #[serde(default)]
+    pub junit_output: Option<PathBuf>,
+    
+    /// CPU virtualization extension (`vmx` or `svm`) the host must expose to guests;
+    /// the plan is skipped when nested virtualization is unavailable
+    #[serde(default)]
+    pub nested: Option<String>,
+}
+
+/// Test outcome
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TestOutcome {
+    Passed,
+    Failed(String),
+    TimedOut(String),
+    Error(String),
+    /// Not run because the host lacks a requirement of the plan
+    Skipped(String),
+}
+
+/// Test result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TestResult {
+    /// Suite name
+    pub suite: String,
+    
+    /// Test name
+    pub name: String,
+    
+    /// Outcome
+    pub outcome: TestOutcome,
+    
+    /// Duration in seconds
+    pub duration_secs: f64,
+    
+    /// Captured console output
+    pub console: String,
+}
+
+/// Test report
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TestReport {
+    /// Results in execution order
+    pub results: Vec<TestResult>,
+}
+
+impl TestReport {
+    /// Number of passed tests
+    pub fn passed(&self) -> usize {
+        self.results.iter().filter(|r| r.outcome == TestOutcome::Passed).count()
+    }
+    
+    /// Number of tests that ran and did not pass
+    pub fn failed(&self) -> usize {
+        self.results.len() - self.passed() - self.skipped()
+    }
+    
+    /// Number of tests that were not run
+    pub fn skipped(&self) -> usize {
+        self.results.iter().filter(|r| matches!(r.outcome, TestOutcome::Skipped(_))).count()
+    }
+    
+    /// Report of a plan whose tests were all skipped for `reason`
+    pub fn skipped_plan(plan: &TestPlan, reason: &str) -> Self {
+        let suites = if plan.suites.is_empty() { vec![TestSuite::smoke()] } else { plan.suites.clone() };
+        let results = suites.iter()
+            .flat_map(|suite| suite.tests.iter().map(move |test| TestResult {
+                suite: suite.name.clone(),
+                name: test.name.clone(),
+                outcome: TestOutcome::Skipped(reason.to_string()),
+                duration_secs: 0.0,
+                console: String::new(),
+            }))
+            .collect();
+        Self { results }
+    }
+    
+    /// Render the report as JUnit XML
+    pub fn to_junit_xml(&self) -> String {
+        let mut suites: Vec<&str> = self.results.iter().map(|r| r.suite.as_str()).collect();
+        suites.dedup();
+        
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str(&format!("<testsuites tests=\"{}\" failures=\"{}\" skipped=\"{}\">\n", self.results.len(), self.failed(), self.skipped()));
+        for suite in suites {
+            let results: Vec<&TestResult> = self.results.iter().filter(|r| r.suite == suite).collect();
+            let failures = results.iter().filter(|r| matches!(r.outcome, TestOutcome::Failed(_) | TestOutcome::TimedOut(_))).count();
+            let errors = results.iter().filter(|r| matches!(r.outcome, TestOutcome::Error(_))).count();
+            let skipped = results.iter().filter(|r| matches!(r.outcome, TestOutcome::Skipped(_))).count();
+            let time: f64 = results.iter().map(|r| r.duration_secs).sum();
+            xml.push_str(&format!(
+                "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\" time=\"{:.3}\">\n",
+                xml_escape(suite), results.len(), failures, errors, skipped, time
+            ));
+            for result in results {
+                xml.push_str(&format!(
+                    "    <testcase classname=\"{}\" name=\"{}\" time=\"{:.3}\"",
+                    xml_escape(suite), xml_escape(&result.name), result.duration_secs
+                ));
+                let body = match &result.outcome {
+                    TestOutcome::Passed => None,
+                    TestOutcome::Failed(message) => Some(format!("<failure message=\"{}\"/>", xml_escape(message))),
+                    TestOutcome::TimedOut(message) => Some(format!("<failure type=\"timeout\" message=\"{}\"/>", xml_escape(message))),
+                    TestOutcome::Error(message) => Some(format!("<error message=\"{}\"/>", xml_escape(message))),
+                    TestOutcome::Skipped(message) => Some(format!("<skipped message=\"{}\"/>", xml_escape(message))),
+                };
+                match body {
+                    None => xml.push_str("/>\n"),
+                    Some(body) => xml.push_str(&format!(
+                        ">\n      {}\n      <system-out>{}</system-out>\n    </testcase>\n",
+                        body, xml_escape(&result.console)
+                    )),
+                }
+            }
+            xml.push_str("  </testsuite>\n");
+        }
+        xml.push_str("</testsuites>\n");
+        xml
+    }
+    
+    /// Write the JUnit XML report
+    pub fn save_junit<P: AsRef<Path>>(&self, path: P) -> Result<(), BuildEngineError> {
+        fs::write(path, self.to_junit_xml())
+            .map_err(|e| BuildEngineError::BuildError(format!("Failed to write JUnit report: {}", e)))
+    }
+}
+
+/// Test orchestrator
+///
+/// Boots a fresh QEMU instance per test and drives it through the serial console.
+pub struct TestOrchestrator {
+    /// QEMU configuration
+    qemu: QemuConfig,
+}
+
+impl TestOrchestrator {
+    /// Create a new orchestrator
+    pub fn new(qemu: QemuConfig) -> Self {
+        Self { qemu }
+    }
+    
+    /// Run all tests of a plan
+    pub fn run_plan(&self, plan: &TestPlan, on_result: &mut dyn FnMut(&TestResult)) -> TestReport {
+        let suites = if plan.suites.is_empty() { vec![TestSuite::smoke()] } else { plan.suites.clone() };
+        let mut report = TestReport::default();
+        for suite in &suites {
+            for test in &suite.tests {
+                let result = self.run_test(&suite.name, test);
+                on_result(&result);
+                report.results.push(result);
+            }
+        }
+        report
+    }
+    
+    /// Run a single test
+    pub fn run_test(&self, suite: &str, test: &TestCase) -> TestResult {
+        let start = Instant::now();
+        let timeout = Duration::from_secs(test.timeout_secs.unwrap_or(DEFAULT_TIMEOUT_SECS));
+        let mut console = String::new();
+        
+        let outcome = match self.spawn() {
+            Ok((mut child, mut stdin, output)) => {
+                let outcome = drive(&test.kind.steps(), &mut stdin, &output, start + timeout, &mut console);
+                let _ = child.kill();
+                let _ = child.wait();
+                outcome
+            }
+            Err(e) => TestOutcome::Error(e),
+        };
+        
+        TestResult {
+            suite: suite.to_string(),
+            name: test.name.clone(),
+            outcome,
+            duration_secs: start.elapsed().as_secs_f64(),
+            console,
+        }
+    }
+    
+    /// Start QEMU and a reader thread forwarding console output
+    fn spawn(&self) -> Result<(Child, ChildStdin, Receiver<Vec<u8>>), String> {
+        let mut child = self.qemu.command()
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| format!("Failed to start QEMU: {}", e))?;
+        let stdin = child.stdin.take().ok_or("QEMU stdin unavailable")?;
+        let mut stdout = child.stdout.take().ok_or("QEMU stdout unavailable")?;
+        
+        let (sender, receiver) = mpsc::channel();
+        std::thread::spawn(move || {
+            let mut buffer = [0u8; 4096];
+            while let Ok(read) = stdout.read(&mut buffer) {
+                if read == 0 || sender.send(buffer[..read].to_vec()).is_err() {
+                    break;
+                }
+            }
+        });
+        
+        Ok((child, stdin, receiver))
+    }
+}
+
+/// Execute a console script against a running guest
+fn drive(steps: &[ConsoleStep], stdin: &mut ChildStdin, output: &Receiver<Vec<u8>>, deadline: Instant, console: &mut String) -> TestOutcome {
+    // Only output after the previous match is considered by the next Expect
+    let mut search_from = 0;
+    
+    for step in steps {
+        match step {
+            ConsoleStep::Send(line) => {
+                if let Err(e) = stdin.write_all(format!("{}\n", line).as_bytes()).and_then(|_| stdin.flush()) {
+                    return TestOutcome::Error(format!("Failed to write to console: {}", e));
+                }
+            }
+            ConsoleStep::Expect(pattern) => loop {
+                if let Some(position) = console[search_from..].find(pattern.as_str()) {
+                    search_from += position + pattern.len();
+                    break;
+                }
+                if console.contains("Kernel panic") {
+                    return TestOutcome::Failed(format!("Kernel panic while waiting for '{}'", pattern));
+                }
+                
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                if remaining.is_zero() {
+                    return TestOutcome::TimedOut(format!("Timed out waiting for '{}'", pattern));
+                }
+                match output.recv_timeout(remaining) {
+                    Ok(bytes) => console.push_str(&String::from_utf8_lossy(&bytes)),
+                    Err(RecvTimeoutError::Timeout) => return TestOutcome::TimedOut(format!("Timed out waiting for '{}'", pattern)),
+                    Err(RecvTimeoutError::Disconnected) => {
+                        return TestOutcome::Failed(format!("QEMU exited before '{}' appeared", pattern));
+                    }
+                }
+            },
+        }
+    }
+    
+    TestOutcome::Passed
+}
+
+fn xml_escape(text: &str) -> String {
+    text.chars()
+        .filter(|c| !c.is_control() || *c == '\n' || *c == '\t')
+        .map(|c| match c {
+            '&' => "&amp;".to_string(),
+            '<' => "&lt;".to_string(),
+            '>' => "&gt;".to_string(),
+            '"' => "&quot;".to_string(),
+            '\'' => "&apos;".to_string(),
+            _ => c.to_string(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_junit_report() {
+        let report = TestReport {
+            results: vec![
+                TestResult {
+                    suite: "smoke".to_string(),
+                    name: "boot".to_string(),
+                    outcome: TestOutcome::Passed,
+                    duration_secs: 1.5,
+                    console: String::new(),
+                },
+                TestResult {
+                    suite: "smoke".to_string(),
+                    name: "ping".to_string(),
+                    outcome: TestOutcome::TimedOut("Timed out waiting for '<ok>'".to_string()),
+                    duration_secs: 30.0,
+                    console: "ping: bad address".to_string(),
+                },
+            ],
+        };
+        
+        let xml = report.to_junit_xml();
+        assert!(xml.contains("<testsuite name=\"smoke\" tests=\"2\" failures=\"1\" errors=\"0\" skipped=\"0\""));
+        assert!(xml.contains("<failure type=\"timeout\" message=\"Timed out waiting for &apos;&lt;ok&gt;&apos;\"/>"));
+        assert_eq!(report.passed(), 1);
+        
+        let plan: TestPlan = serde_json::from_str(r#"{"qemu": {}, "nested": "vmx"}"#).unwrap();
+        let skipped = TestReport::skipped_plan(&plan, "Nested vmx is not enabled");
+        assert_eq!((skipped.passed(), skipped.failed(), skipped.skipped()), (0, 0, skipped.results.len()));
+        assert!(skipped.to_junit_xml().contains("<skipped message=\"Nested vmx is not enabled\"/>"));
+    }
+}
@@ -0,0 +1,759 @@
+// Canvas Lint Framework for OSland Component Manager
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+use serde::{Serialize, Deserialize};
+use crate::component_manager::component::{KernelArchitecture, PortDirection};
+use crate::component_manager::ipc_components::check_message_schemas;
+use crate::component_manager::network_components::check_network_stack;
+use crate::component_manager::version_manager::{DefaultVersionManager, VersionManager};
+use crate::component_manager::visual_node::{NodeCanvas, NodeControlType};
+use crate::component_manager::ComponentManagerError;
+use crate::core::event_bus::{self, ProjectEvent, SystemEvent};
+use crate::os_design::realtime::{check_schedulability, RtIssueSeverity};
+use crate::os_design::security_policy::{check_security_policies, PolicyIssueSeverity};
+
+/// Node property listing input ports that may stay unconnected (comma separated)
+pub const OPTIONAL_PORTS_PROPERTY: &str = "optional_ports";
+
+/// Lint severity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum LintSeverity {
+    /// Rule disabled
+    Off,
+    Info,
+    Warning,
+    Error,
+}
+
+/// Machine-applicable fix for a lint diagnostic
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum QuickFixAction {
+    /// Remove a connection
+    RemoveConnection { connection_id: String },
+    /// Remove a node and its connections
+    RemoveNode { node_id: String },
+    /// Change the control type of a node
+    SetControlType { node_id: String, control_type: NodeControlType },
+    /// Mark an input port as optional
+    MarkPortOptional { node_id: String, port_name: String },
+    /// Replace the component of a node with another version
+    ReplaceComponentVersion { node_id: String, component_id: String, version: String },
+}
+
+/// Quick fix offered with a diagnostic
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuickFix {
+    /// Title shown in the UI
+    pub title: String,
+    
+    /// Action performed by the fix
+    pub action: QuickFixAction,
+}
+
+/// Finding reported by a rule (before severity is applied)
+#[derive(Debug, Clone)]
+pub struct LintFinding {
+    /// Finding message
+    pub message: String,
+    
+    /// Nodes involved
+    pub node_ids: Vec<String>,
+    
+    /// Connections involved
+    pub connection_ids: Vec<String>,
+    
+    /// Quick fixes
+    pub quick_fixes: Vec<QuickFix>,
+}
+
+impl LintFinding {
+    /// Create a finding for a single node
+    pub fn for_node(node_id: &str, message: String) -> Self {
+        Self {
+            message,
+            node_ids: vec![node_id.to_string()],
+            connection_ids: Vec::new(),
+            quick_fixes: Vec::new(),
+        }
+    }
+    
+    /// Attach a quick fix
+    pub fn with_fix(mut self, title: &str, action: QuickFixAction) -> Self {
+        self.quick_fixes.push(QuickFix { title: title.to_string(), action });
+        self
+    }
+}
+
+/// Lint diagnostic
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LintDiagnostic {
+    /// Rule ID
+    pub rule_id: String,
+    
+    /// Effective severity
+    pub severity: LintSeverity,
+    
+    /// Diagnostic message
+    pub message: String,
+    
+    /// Nodes involved
+    pub node_ids: Vec<String>,
+    
+    /// Connections involved
+    pub connection_ids: Vec<String>,
+    
+    /// Quick fixes
+    pub quick_fixes: Vec<QuickFix>,
+}
+
+impl std::fmt::Display for LintDiagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?} [{}]: {}", self.severity, self.rule_id, self.message)
+    }
+}
+
+/// Project lint configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LintConfig {
+    /// Severity overrides per rule ID
+    #[serde(default)]
+    pub severities: HashMap<String, LintSeverity>,
+    
+    /// Architecture the project targets
+    #[serde(default)]
+    pub target_architecture: Option<KernelArchitecture>,
+    
+    /// Component IDs that are deprecated in this project
+    #[serde(default)]
+    pub deprecated_components: Vec<String>,
+}
+
+impl LintConfig {
+    /// Load a lint configuration from a JSON file
+    pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<Self, ComponentManagerError> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| ComponentManagerError::CompatibilityError(format!("Failed to read lint configuration: {}", e)))?;
+        serde_json::from_str(&content)
+            .map_err(|e| ComponentManagerError::CompatibilityError(format!("Failed to parse lint configuration: {}", e)))
+    }
+}
+
+/// Context available to lint rules
+pub struct LintContext<'a> {
+    /// Project lint configuration
+    pub config: &'a LintConfig,
+    
+    /// Component registry (for deprecation lookups)
+    pub versions: Option<&'a DefaultVersionManager>,
+}
+
+/// Canvas lint rule
+pub trait CanvasLintRule {
+    /// Stable rule ID
+    fn id(&self) -> &'static str;
+    
+    /// Rule description
+    fn description(&self) -> &'static str;
+    
+    /// Severity when the project does not override it
+    fn default_severity(&self) -> LintSeverity;
+    
+    /// Check a canvas
+    fn check(&self, canvas: &NodeCanvas, context: &LintContext) -> Vec<LintFinding>;
+}
+
+/// Input ports that are not connected
+pub struct UnconnectedRequiredPortRule;
+
+impl CanvasLintRule for UnconnectedRequiredPortRule {
+    fn id(&self) -> &'static str {
+        "unconnected-required-port"
+    }
+    
+    fn description(&self) -> &'static str {
+        "Input ports must be connected unless marked optional"
+    }
+    
+    fn default_severity(&self) -> LintSeverity {
+        LintSeverity::Error
+    }
+    
+    fn check(&self, canvas: &NodeCanvas, _context: &LintContext) -> Vec<LintFinding> {
+        let mut findings = Vec::new();
+        for node in sorted_nodes(canvas) {
+            let optional: Vec<&str> = node.properties.get(OPTIONAL_PORTS_PROPERTY)
+                .map(|ports| ports.split(',').map(str::trim).collect())
+                .unwrap_or_default();
+            
+            for port in node.ports.iter().filter(|port| port.direction == PortDirection::Input) {
+                let connected = canvas.connections.values().any(|conn| conn.to_node == node.id && conn.to_port == port.id);
+                if connected || optional.contains(&port.name.as_str()) {
+                    continue;
+                }
+                
+                findings.push(LintFinding::for_node(&node.id, format!(
+                    "Required input port '{}' of '{}' is not connected", port.name, node.component.display_name
+                )).with_fix("Mark port as optional", QuickFixAction::MarkPortOptional {
+                    node_id: node.id.clone(),
+                    port_name: port.name.clone(),
+                }));
+            }
+        }
+        findings
+    }
+}
+
+/// Cycles that do not pass through a Loop node
+pub struct CycleOutsideLoopRule;
+
+impl CanvasLintRule for CycleOutsideLoopRule {
+    fn id(&self) -> &'static str {
+        "cycle-outside-loop"
+    }
+    
+    fn description(&self) -> &'static str {
+        "Cycles are only allowed through a Loop node"
+    }
+    
+    fn default_severity(&self) -> LintSeverity {
+        LintSeverity::Error
+    }
+    
+    fn check(&self, canvas: &NodeCanvas, _context: &LintContext) -> Vec<LintFinding> {
+        let mut findings = Vec::new();
+        for cycle in strongly_connected_components(canvas) {
+            let has_loop = cycle.iter().any(|id| matches!(canvas.nodes[id].control_type, NodeControlType::Loop));
+            if has_loop {
+                continue;
+            }
+            
+            let connections: Vec<_> = sorted_connections(canvas).into_iter()
+                .filter(|conn| cycle.contains(&conn.from_node) && cycle.contains(&conn.to_node))
+                .collect();
+            let connection_ids: Vec<String> = connections.iter().map(|conn| conn.id.clone()).collect();
+            let names: Vec<&str> = cycle.iter().map(|id| canvas.nodes[id].component.display_name.as_str()).collect();
+            
+            let mut finding = LintFinding {
+                message: format!("Cycle through {} does not contain a Loop node", names.join(" -> ")),
+                node_ids: cycle.clone(),
+                connection_ids,
+                quick_fixes: Vec::new(),
+            };
+            finding = finding.with_fix("Make the first node a Loop", QuickFixAction::SetControlType {
+                node_id: cycle[0].clone(),
+                control_type: NodeControlType::Loop,
+            });
+            // The back edge is the first connection (by ID) closing the cycle at its first node
+            if let Some(back_edge) = connections.iter().find(|conn| conn.to_node == cycle[0]) {
+                finding = finding.with_fix("Remove the back edge", QuickFixAction::RemoveConnection {
+                    connection_id: back_edge.id.clone(),
+                });
+            }
+            findings.push(finding);
+        }
+        findings
+    }
+}
+
+/// Nodes using deprecated components
+pub struct DeprecatedComponentRule;
+
+impl CanvasLintRule for DeprecatedComponentRule {
+    fn id(&self) -> &'static str {
+        "deprecated-component"
+    }
+    
+    fn description(&self) -> &'static str {
+        "Deprecated components should be replaced"
+    }
+    
+    fn default_severity(&self) -> LintSeverity {
+        LintSeverity::Warning
+    }
+    
+    fn check(&self, canvas: &NodeCanvas, context: &LintContext) -> Vec<LintFinding> {
+        let mut findings = Vec::new();
+        for node in sorted_nodes(canvas) {
+            let component = &node.component;
+            let registry_deprecated = context.versions
+                .and_then(|versions| versions.get_version(&component.id, &component.version).ok().flatten())
+                .is_some_and(|version| version.deprecated);
+            if !registry_deprecated && !context.config.deprecated_components.contains(&component.id) {
+                continue;
+            }
+            
+            let mut finding = LintFinding::for_node(&node.id, format!(
+                "'{}' uses deprecated component {} {}", node.component.display_name, component.id, component.version
+            ));
+            let replacement = context.versions
+                .and_then(|versions| versions.get_recommended_version(&component.id).ok().flatten())
+                .filter(|version| !version.deprecated && version.version != component.version);
+            if let Some(replacement) = replacement {
+                finding = finding.with_fix(&format!("Use {} {}", component.id, replacement.version), QuickFixAction::ReplaceComponentVersion {
+                    node_id: node.id.clone(),
+                    component_id: component.id.clone(),
+                    version: replacement.version.clone(),
+                });
+            }
+            findings.push(finding);
+        }
+        findings
+    }
+}
+
+/// Nodes whose component does not support the project architecture
+pub struct ArchitectureMismatchRule;
+
+impl CanvasLintRule for ArchitectureMismatchRule {
+    fn id(&self) -> &'static str {
+        "architecture-mismatch"
+    }
+    
+    fn description(&self) -> &'static str {
+        "Components must support the project's kernel architecture"
+    }
+    
+    fn default_severity(&self) -> LintSeverity {
+        LintSeverity::Error
+    }
+    
+    fn check(&self, canvas: &NodeCanvas, context: &LintContext) -> Vec<LintFinding> {
+        let target = match &context.config.target_architecture {
+            Some(target) => target,
+            None => return Vec::new(),
+        };
+        
+        sorted_nodes(canvas).into_iter()
+            .filter(|node| !node.component.supported_architectures.is_empty() && !node.component.supported_architectures.contains(target))
+            .map(|node| LintFinding::for_node(&node.id, format!(
+                "'{}' does not support the {:?} architecture", node.component.display_name, target
+            )).with_fix("Remove node", QuickFixAction::RemoveNode { node_id: node.id.clone() }))
+            .collect()
+    }
+}
+
+/// IPC endpoints and channels that disagree on the message schema
+pub struct IpcSchemaMismatchRule;
+
+impl CanvasLintRule for IpcSchemaMismatchRule {
+    fn id(&self) -> &'static str {
+        "ipc-schema-mismatch"
+    }
+    
+    fn description(&self) -> &'static str {
+        "Connected IPC endpoints must declare the same message schema"
+    }
+    
+    fn default_severity(&self) -> LintSeverity {
+        LintSeverity::Error
+    }
+    
+    fn check(&self, canvas: &NodeCanvas, _context: &LintContext) -> Vec<LintFinding> {
+        check_message_schemas(canvas).into_iter()
+            .map(|mismatch| LintFinding {
+                message: mismatch.message,
+                node_ids: mismatch.node_ids,
+                connection_ids: mismatch.connection_ids,
+                quick_fixes: Vec::new(),
+            })
+            .collect()
+    }
+}
+
+/// Network layers must be stacked in protocol order with nothing missing below
+pub struct NetworkLayeringRule;
+
+impl CanvasLintRule for NetworkLayeringRule {
+    fn id(&self) -> &'static str {
+        "network-layering"
+    }
+    
+    fn description(&self) -> &'static str {
+        "Network components must sit on the protocol layer they depend on"
+    }
+    
+    fn default_severity(&self) -> LintSeverity {
+        LintSeverity::Error
+    }
+    
+    fn check(&self, canvas: &NodeCanvas, _context: &LintContext) -> Vec<LintFinding> {
+        check_network_stack(canvas).into_iter()
+            .map(|issue| LintFinding {
+                message: issue.message,
+                node_ids: issue.node_ids,
+                connection_ids: issue.connection_ids,
+                quick_fixes: Vec::new(),
+            })
+            .collect()
+    }
+}
+
+/// Security policies must parse and only rely on components present on the canvas
+pub struct SecurityPolicyRule;
+
+impl CanvasLintRule for SecurityPolicyRule {
+    fn id(&self) -> &'static str {
+        "security-policy"
+    }
+    
+    fn description(&self) -> &'static str {
+        "Security policy rules must refer to declared task types and components on the canvas"
+    }
+    
+    fn default_severity(&self) -> LintSeverity {
+        LintSeverity::Error
+    }
+    
+    fn check(&self, canvas: &NodeCanvas, _context: &LintContext) -> Vec<LintFinding> {
+        // Warnings such as overridden allow rules are reported by the policy build step
+        check_security_policies(canvas).into_iter()
+            .filter(|finding| finding.issue.severity == PolicyIssueSeverity::Error)
+            .map(|finding| LintFinding {
+                message: finding.issue.message,
+                node_ids: vec![finding.node_id],
+                connection_ids: Vec::new(),
+                quick_fixes: Vec::new(),
+            })
+            .collect()
+    }
+}
+
+/// Annotated real-time tasks must meet their deadlines under the project's policy
+pub struct SchedulabilityRule;
+
+impl CanvasLintRule for SchedulabilityRule {
+    fn id(&self) -> &'static str {
+        "rt-schedulability"
+    }
+    
+    fn description(&self) -> &'static str {
+        "Tasks with WCET annotations must pass the rate-monotonic or EDF schedulability test"
+    }
+    
+    fn default_severity(&self) -> LintSeverity {
+        LintSeverity::Error
+    }
+    
+    fn check(&self, canvas: &NodeCanvas, _context: &LintContext) -> Vec<LintFinding> {
+        // Annotations that are skipped rather than analysed are not findings
+        check_schedulability(canvas).into_iter()
+            .filter(|finding| finding.issue.severity == RtIssueSeverity::Error)
+            .map(|finding| LintFinding {
+                message: finding.issue.message,
+                node_ids: vec![finding.node_id],
+                connection_ids: Vec::new(),
+                quick_fixes: Vec::new(),
+            })
+            .collect()
+    }
+}
+
+/// Lint report
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LintReport {
+    /// Diagnostics ordered by severity (errors first)
+    pub diagnostics: Vec<LintDiagnostic>,
+}
+
+impl LintReport {
+    /// Number of diagnostics per severity
+    pub fn counts(&self) -> BTreeMap<LintSeverity, usize> {
+        let mut counts = BTreeMap::new();
+        for diagnostic in &self.diagnostics {
+            *counts.entry(diagnostic.severity).or_insert(0) += 1;
+        }
+        counts
+    }
+    
+    /// Check whether any diagnostic is an error
+    pub fn has_errors(&self) -> bool {
+        self.diagnostics.iter().any(|diagnostic| diagnostic.severity == LintSeverity::Error)
+    }
+    
+    /// Diagnostics involving a node
+    pub fn for_node(&self, node_id: &str) -> Vec<&LintDiagnostic> {
+        self.diagnostics.iter().filter(|diagnostic| diagnostic.node_ids.iter().any(|id| id == node_id)).collect()
+    }
+}
+
+/// Canvas Linter
+///
+/// Runs a set of rules over a `NodeCanvas`. Rule severities can be overridden
+/// per project; the linter has no UI dependencies so it can run headlessly in CI.
+pub struct CanvasLinter {
+    rules: Vec<Box<dyn CanvasLintRule>>,
+    config: LintConfig,
+}
+
+impl CanvasLinter {
+    /// Create a linter without rules
+    pub fn new(config: LintConfig) -> Self {
+        Self {
+            rules: Vec::new(),
+            config,
+        }
+    }
+    
+    /// Create a linter with the built-in rules
+    pub fn with_default_rules(config: LintConfig) -> Self {
+        let mut linter = Self::new(config);
+        linter.add_rule(Box::new(UnconnectedRequiredPortRule));
+        linter.add_rule(Box::new(CycleOutsideLoopRule));
+        linter.add_rule(Box::new(DeprecatedComponentRule));
+        linter.add_rule(Box::new(ArchitectureMismatchRule));
+        linter.add_rule(Box::new(IpcSchemaMismatchRule));
+        linter.add_rule(Box::new(NetworkLayeringRule));
+        linter.add_rule(Box::new(SecurityPolicyRule));
+        linter.add_rule(Box::new(SchedulabilityRule));
+        linter
+    }
+    
+    /// Add a rule
+    pub fn add_rule(&mut self, rule: Box<dyn CanvasLintRule>) {
+        self.rules.push(rule);
+    }
+    
+    /// Get the registered rules
+    pub fn rules(&self) -> &[Box<dyn CanvasLintRule>] {
+        &self.rules
+    }
+    
+    /// Effective severity of a rule
+    pub fn severity_of(&self, rule: &dyn CanvasLintRule) -> LintSeverity {
+        self.config.severities.get(rule.id()).copied().unwrap_or_else(|| rule.default_severity())
+    }
+    
+    /// Lint a canvas
+    pub fn lint(&self, canvas: &NodeCanvas, versions: Option<&DefaultVersionManager>) -> LintReport {
+        let context = LintContext { config: &self.config, versions };
+        let mut diagnostics = Vec::new();
+        
+        for rule in &self.rules {
+            let severity = self.severity_of(rule.as_ref());
+            if severity == LintSeverity::Off {
+                continue;
+            }
+            
+            diagnostics.extend(rule.check(canvas, &context).into_iter().map(|finding| LintDiagnostic {
+                rule_id: rule.id().to_string(),
+                severity,
+                message: finding.message,
+                node_ids: finding.node_ids,
+                connection_ids: finding.connection_ids,
+                quick_fixes: finding.quick_fixes,
+            }));
+        }
+        
+        // Stable sort keeps rule order within a severity
+        diagnostics.sort_by(|a, b| b.severity.cmp(&a.severity));
+        LintReport { diagnostics }
+    }
+    
+    /// Lint a canvas saved as JSON
+    pub fn lint_file<P: AsRef<Path>>(&self, canvas_path: P) -> Result<LintReport, ComponentManagerError> {
+        let content = fs::read_to_string(canvas_path.as_ref())
+            .map_err(|e| ComponentManagerError::VisualNodeError(format!("Failed to read canvas: {}", e)))?;
+        let canvas: NodeCanvas = serde_json::from_str(&content)
+            .map_err(|e| ComponentManagerError::VisualNodeError(format!("Failed to parse canvas: {}", e)))?;
+        let report = self.lint(&canvas, None);
+        if report.has_errors() {
+            event_bus::publish(SystemEvent::Project(ProjectEvent::ValidationFailed {
+                project: canvas_path.as_ref().display().to_string(),
+                target: "canvas".to_string(),
+                errors: report.diagnostics.iter()
+                    .filter(|diagnostic| diagnostic.severity == LintSeverity::Error)
+                    .map(|diagnostic| diagnostic.to_string())
+                    .collect(),
+            }));
+        }
+        Ok(report)
+    }
+}
+
+/// Nodes ordered by ID for deterministic output
+fn sorted_nodes(canvas: &NodeCanvas) -> Vec<&crate::component_manager::visual_node::VisualNode> {
+    let mut nodes: Vec<_> = canvas.nodes.values().collect();
+    nodes.sort_by(|a, b| a.id.cmp(&b.id));
+    nodes
+}
+
+/// Connections ordered by ID for deterministic output
+fn sorted_connections(canvas: &NodeCanvas) -> Vec<&crate::component_manager::visual_node::NodeConnection> {
+    let mut connections: Vec<_> = canvas.connections.values().collect();
+    connections.sort_by(|a, b| a.id.cmp(&b.id));
+    connections
+}
+
+/// Strongly connected components forming cycles (Tarjan's algorithm)
+fn strongly_connected_components(canvas: &NodeCanvas) -> Vec<Vec<String>> {
+    struct Tarjan<'a> {
+        edges: HashMap<&'a str, Vec<&'a str>>,
+        index: HashMap<&'a str, usize>,
+        low: HashMap<&'a str, usize>,
+        stack: Vec<&'a str>,
+        on_stack: HashMap<&'a str, bool>,
+        next: usize,
+        components: Vec<Vec<String>>,
+    }
+    
+    impl<'a> Tarjan<'a> {
+        fn visit(&mut self, node: &'a str) {
+            self.index.insert(node, self.next);
+            self.low.insert(node, self.next);
+            self.next += 1;
+            self.stack.push(node);
+            self.on_stack.insert(node, true);
+            
+            for next in self.edges.get(node).cloned().unwrap_or_default() {
+                if !self.index.contains_key(next) {
+                    self.visit(next);
+                    let low = self.low[node].min(self.low[next]);
+                    self.low.insert(node, low);
+                } else if self.on_stack.get(next).copied().unwrap_or(false) {
+                    let low = self.low[node].min(self.index[next]);
+                    self.low.insert(node, low);
+                }
+            }
+            
+            if self.low[node] == self.index[node] {
+                let mut component = Vec::new();
+                while let Some(member) = self.stack.pop() {
+                    self.on_stack.insert(member, false);
+                    component.push(member.to_string());
+                    if member == node {
+                        break;
+                    }
+                }
+                
+                let self_loop = self.edges.get(node).is_some_and(|edges| edges.contains(&node));
+                if component.len() > 1 || self_loop {
+                    component.sort();
+                    self.components.push(component);
+                }
+            }
+        }
+    }
+    
+    let mut tarjan = Tarjan {
+        edges: HashMap::new(),
+        index: HashMap::new(),
+        low: HashMap::new(),
+        stack: Vec::new(),
+        on_stack: HashMap::new(),
+        next: 0,
+        components: Vec::new(),
+    };
+    for conn in sorted_connections(canvas) {
+        if canvas.nodes.contains_key(&conn.from_node) && canvas.nodes.contains_key(&conn.to_node) {
+            tarjan.edges.entry(conn.from_node.as_str()).or_default().push(conn.to_node.as_str());
+        }
+    }
+    for node in sorted_nodes(canvas) {
+        if !tarjan.index.contains_key(node.id.as_str()) {
+            tarjan.visit(node.id.as_str());
+        }
+    }
+    
+    tarjan.components.sort();
+    tarjan.components
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use crate::core::geometry::{Color, Point};
+    use crate::component_manager::ipc_components::create_message_queue_component;
+    use crate::component_manager::visual_node::{DataFlowInfo, NodeConnection, VisualNode};
+    
+    fn queues(ids: &[&str]) -> NodeCanvas {
+        let mut canvas = NodeCanvas::new();
+        for id in ids {
+            let node = VisualNode::with_id(create_message_queue_component(), Point::new(0.0, 0.0), id.to_string()).unwrap();
+            canvas.nodes.insert(id.to_string(), node);
+        }
+        canvas
+    }
+    
+    fn connect(canvas: &mut NodeCanvas, from: &str, to: &str) {
+        let connection = NodeConnection {
+            id: format!("{}->{}", from, to),
+            from_node: from.to_string(),
+            from_port: canvas.nodes[from].get_port_by_name("receive").unwrap().id.clone(),
+            to_node: to.to_string(),
+            to_port: canvas.nodes[to].get_port_by_name("send").unwrap().id.clone(),
+            connection_type: "ipc".to_string(),
+            color: Color::from_rgba8(0, 0, 0, 255),
+            line_width: 2.0,
+            description: String::new(),
+            data_flow_info: DataFlowInfo {
+                data_type: "ipc".to_string(),
+                data_size: None,
+                flow_rate: None,
+                last_value_preview: None,
+                is_active: false,
+                transmission_time: Duration::ZERO,
+            },
+            is_highlighted: false,
+            is_selected: false,
+            label: None,
+            bend_points: Vec::new(),
+            animation_speed: 1.0,
+            show_data_flow: false,
+        };
+        canvas.connections.insert(connection.id.clone(), connection);
+    }
+    
+    fn linter(config: LintConfig) -> CanvasLinter {
+        let mut linter = CanvasLinter::new(config);
+        linter.add_rule(Box::new(UnconnectedRequiredPortRule));
+        linter.add_rule(Box::new(CycleOutsideLoopRule));
+        linter
+    }
+    
+    #[test]
+    fn test_cycle_back_edge_is_deterministic() {
+        // Both b->a and c->a close the cycle at its first node
+        let mut canvas = queues(&["a", "b", "c"]);
+        connect(&mut canvas, "c", "a");
+        connect(&mut canvas, "a", "b");
+        connect(&mut canvas, "b", "c");
+        connect(&mut canvas, "b", "a");
+        
+        let report = linter(LintConfig::default()).lint(&canvas, None);
+        assert_eq!(report.diagnostics.len(), 1);
+        let diagnostic = &report.diagnostics[0];
+        assert_eq!(diagnostic.rule_id, "cycle-outside-loop");
+        assert_eq!(diagnostic.node_ids, vec!["a", "b", "c"]);
+        assert_eq!(diagnostic.connection_ids, vec!["a->b", "b->a", "b->c", "c->a"]);
+        let actions: Vec<&QuickFixAction> = diagnostic.quick_fixes.iter().map(|fix| &fix.action).collect();
+        assert_eq!(actions, vec![
+            &QuickFixAction::SetControlType { node_id: "a".to_string(), control_type: NodeControlType::Loop },
+            &QuickFixAction::RemoveConnection { connection_id: "b->a".to_string() },
+        ]);
+        
+        canvas.nodes.get_mut("b").unwrap().control_type = NodeControlType::Loop;
+        assert!(linter(LintConfig::default()).lint(&canvas, None).diagnostics.is_empty());
+    }
+    
+    #[test]
+    fn test_severity_overrides() {
+        let canvas = queues(&["queue"]);
+        let report = linter(LintConfig::default()).lint(&canvas, None);
+        assert!(report.has_errors());
+        assert_eq!(report.for_node("queue").len(), 1);
+        
+        let mut config = LintConfig::default();
+        config.severities.insert("unconnected-required-port".to_string(), LintSeverity::Warning);
+        let report = linter(config.clone()).lint(&canvas, None);
+        assert!(!report.has_errors());
+        assert_eq!(report.counts().get(&LintSeverity::Warning), Some(&1));
+        
+        config.severities.insert("unconnected-required-port".to_string(), LintSeverity::Off);
+        assert!(linter(config).lint(&canvas, None).diagnostics.is_empty());
+    }
+}
@@ -555,26 +555,6 @@ fn create_cuda_performance_component() -> Component {
     }
 }
 
-/// Create CUDA component library for visualization programming
-pub fn create_cuda_component_library() -> ComponentLibrary {
-    let mut library = ComponentLibrary::new();
-    
-    // Add CUDA Tile component
-    library.add_component(create_cuda_tile_component()).expect("Failed to add CUDA Tile component");
-    
-    // Add CUDA Tensor component
-    library.add_component(create_cuda_tensor_component()).expect("Failed to add CUDA Tensor component");
-    
-    // Add CUDA Performance component
-    library.add_component(create_cuda_performance_component()).expect("Failed to add CUDA Performance component");
-    
-    // Add Triton components
-    library.add_component(create_triton_kernel_component()).expect("Failed to add Triton Kernel component");
-    library.add_component(create_triton_tensor_component()).expect("Failed to add Triton Tensor component");
-    
-    library
-}
-
 /// Create a CuTile component for CUDA Tile programming
 fn create_cutile_component() -> Component {
     Component {
@@ -780,6 +760,568 @@ fn create_helion_component() -> Component {
     }
 }
 
+/// Create a CUDA shared memory component (per-block on-chip memory)
+fn create_cuda_shared_memory_component() -> Component {
+    Component {
+        id: "cuda_shared_memory".to_string(),
+        name: "cuda_shared_memory".to_string(),
+        display_name: "CUDA Shared Memory".to_string(),
+        component_type: ComponentType::Custom("CudaSharedMemory".to_string()),
+        category: ComponentCategory::Cuda,
+        version: "1.0.0".to_string(),
+        description: "On-chip shared memory partitioned per thread block, used for tile staging between global memory and registers".to_string(),
+        author: "OSland Team".to_string(),
+        source_url: Some("https://github.com/osland-project/osland".to_string()),
+        license: "MulanPSL-2.0".to_string(),
+        
+        properties: vec![
+            ComponentProperty {
+                name: "size_kb".to_string(),
+                value: "48".to_string(),
+                property_type: "int".to_string(),
+                description: "Shared memory allocated per thread block in KB".to_string(),
+                required: true,
+                default_value: Some("48".to_string()),
+                valid_values: None,
+            },
+            ComponentProperty {
+                name: "carveout_percent".to_string(),
+                value: "50".to_string(),
+                property_type: "int".to_string(),
+                description: "Percentage of the unified L1/shared memory carved out as shared memory".to_string(),
+                required: false,
+                default_value: Some("50".to_string()),
+                valid_values: Some(vec![
+                    "0".to_string(),
+                    "25".to_string(),
+                    "50".to_string(),
+                    "75".to_string(),
+                    "100".to_string(),
+                ]),
+            },
+            ComponentProperty {
+                name: "bank_width_bytes".to_string(),
+                value: "4".to_string(),
+                property_type: "int".to_string(),
+                description: "Shared memory bank width in bytes".to_string(),
+                required: false,
+                default_value: Some("4".to_string()),
+                valid_values: Some(vec![
+                    "4".to_string(),
+                    "8".to_string(),
+                ]),
+            },
+            ComponentProperty {
+                name: "use_async_copy".to_string(),
+                value: "true".to_string(),
+                property_type: "bool".to_string(),
+                description: "Use cp.async / TMA to load tiles from global memory".to_string(),
+                required: false,
+                default_value: Some("true".to_string()),
+                valid_values: Some(vec![
+                    "true".to_string(),
+                    "false".to_string(),
+                ]),
+            },
+        ],
+        
+        ports: vec![
+            ComponentPort {
+                name: "global_load".to_string(),
+                port_type: "tensor".to_string(),
+                direction: PortDirection::Input,
+                description: "Tiles loaded from global memory".to_string(),
+            },
+            ComponentPort {
+                name: "tile_buffer".to_string(),
+                port_type: "shared_tile".to_string(),
+                direction: PortDirection::Bidirectional,
+                description: "Staged tile buffer visible to all threads in the block".to_string(),
+            },
+            ComponentPort {
+                name: "sync_barrier".to_string(),
+                port_type: "barrier".to_string(),
+                direction: PortDirection::Output,
+                description: "Block-level barrier signalled when the tile is resident".to_string(),
+            },
+        ],
+        
+        dependencies: vec![
+            ComponentDependency {
+                component_type: ComponentType::Custom("CudaTile".to_string()),
+                min_version: Some("1.0.0".to_string()),
+                max_version: None,
+                optional: false,
+                description: "Shared memory is allocated per CUDA Tile thread block".to_string(),
+            },
+        ],
+        
+        supported_architectures: HashSet::from([
+            KernelArchitecture::Monolithic,
+            KernelArchitecture::Microkernel,
+            KernelArchitecture::Custom("PartitionedKernel".to_string()),
+        ]),
+        
+        supported_languages: vec![
+            "c++".to_string(),
+            "python".to_string(),
+            "rust".to_string(),
+        ],
+        
+        implementation_files: vec![],
+        build_commands: vec![],
+        initialization_code: "// CUDA shared memory initialization\n".to_string() +
+            "extern __shared__ char osland_shared_tile[];\n",
+    }
+}
+
+/// Create a CUDA Tensor Core component (matrix multiply-accumulate units)
+fn create_cuda_tensor_core_component() -> Component {
+    Component {
+        id: "cuda_tensor_core".to_string(),
+        name: "cuda_tensor_core".to_string(),
+        display_name: "CUDA Tensor Core".to_string(),
+        component_type: ComponentType::Custom("CudaTensorCore".to_string()),
+        category: ComponentCategory::Cuda,
+        version: "1.0.0".to_string(),
+        description: "Warp-level matrix multiply-accumulate units for mixed-precision GEMM and convolution".to_string(),
+        author: "OSland Team".to_string(),
+        source_url: Some("https://github.com/osland-project/osland".to_string()),
+        license: "MulanPSL-2.0".to_string(),
+        
+        properties: vec![
+            ComponentProperty {
+                name: "mma_shape".to_string(),
+                value: "16x8x16".to_string(),
+                property_type: "string".to_string(),
+                description: "Matrix multiply-accumulate instruction shape (MxNxK)".to_string(),
+                required: true,
+                default_value: Some("16x8x16".to_string()),
+                valid_values: Some(vec![
+                    "16x8x8".to_string(),
+                    "16x8x16".to_string(),
+                    "16x8x32".to_string(),
+                    "64x8x16".to_string(),
+                ]),
+            },
+            ComponentProperty {
+                name: "input_precision".to_string(),
+                value: "float16".to_string(),
+                property_type: "string".to_string(),
+                description: "Precision of the A and B operands".to_string(),
+                required: true,
+                default_value: Some("float16".to_string()),
+                valid_values: Some(vec![
+                    "float16".to_string(),
+                    "bfloat16".to_string(),
+                    "tf32".to_string(),
+                    "int8".to_string(),
+                    "fp8_e4m3".to_string(),
+                    "fp8_e5m2".to_string(),
+                ]),
+            },
+            ComponentProperty {
+                name: "accumulator_precision".to_string(),
+                value: "float32".to_string(),
+                property_type: "string".to_string(),
+                description: "Precision of the accumulator".to_string(),
+                required: true,
+                default_value: Some("float32".to_string()),
+                valid_values: Some(vec![
+                    "float16".to_string(),
+                    "float32".to_string(),
+                    "int32".to_string(),
+                ]),
+            },
+            ComponentProperty {
+                name: "compute_capability".to_string(),
+                value: "8.0".to_string(),
+                property_type: "string".to_string(),
+                description: "Minimum compute capability required".to_string(),
+                required: true,
+                default_value: Some("8.0".to_string()),
+                valid_values: Some(vec![
+                    "7.0".to_string(),
+                    "7.5".to_string(),
+                    "8.0".to_string(),
+                    "8.6".to_string(),
+                    "8.9".to_string(),
+                    "9.0".to_string(),
+                ]),
+            },
+        ],
+        
+        ports: vec![
+            ComponentPort {
+                name: "matrix_a".to_string(),
+                port_type: "shared_tile".to_string(),
+                direction: PortDirection::Input,
+                description: "A operand fragment loaded from shared memory".to_string(),
+            },
+            ComponentPort {
+                name: "matrix_b".to_string(),
+                port_type: "shared_tile".to_string(),
+                direction: PortDirection::Input,
+                description: "B operand fragment loaded from shared memory".to_string(),
+            },
+            ComponentPort {
+                name: "accumulator".to_string(),
+                port_type: "register_fragment".to_string(),
+                direction: PortDirection::Bidirectional,
+                description: "Accumulator fragment held in registers".to_string(),
+            },
+        ],
+        
+        dependencies: vec![
+            ComponentDependency {
+                component_type: ComponentType::Custom("CudaSharedMemory".to_string()),
+                min_version: Some("1.0.0".to_string()),
+                max_version: None,
+                optional: false,
+                description: "Operands are staged through shared memory".to_string(),
+            },
+            ComponentDependency {
+                component_type: ComponentType::Custom("CudaTile".to_string()),
+                min_version: Some("1.0.0".to_string()),
+                max_version: None,
+                optional: false,
+                description: "Tensor Core fragments are scheduled by a CUDA Tile".to_string(),
+            },
+        ],
+        
+        supported_architectures: HashSet::from([
+            KernelArchitecture::Monolithic,
+            KernelArchitecture::Microkernel,
+            KernelArchitecture::Custom("PartitionedKernel".to_string()),
+        ]),
+        
+        supported_languages: vec![
+            "c++".to_string(),
+            "python".to_string(),
+        ],
+        
+        implementation_files: vec![],
+        build_commands: vec![],
+        initialization_code: "// CUDA Tensor Core initialization\n".to_string() +
+            "#include <mma.h>\n" +
+            "using namespace nvcuda;\n",
+    }
+}
+
+/// Create an NVLink interconnect component (GPU-to-GPU links)
+fn create_nvlink_component() -> Component {
+    Component {
+        id: "nvlink".to_string(),
+        name: "nvlink".to_string(),
+        display_name: "NVLink".to_string(),
+        component_type: ComponentType::Custom("NvLink".to_string()),
+        category: ComponentCategory::Cuda,
+        version: "1.0.0".to_string(),
+        description: "High-bandwidth GPU-to-GPU interconnect for peer memory access and collective operations".to_string(),
+        author: "OSland Team".to_string(),
+        source_url: Some("https://github.com/osland-project/osland".to_string()),
+        license: "MulanPSL-2.0".to_string(),
+        
+        properties: vec![
+            ComponentProperty {
+                name: "version".to_string(),
+                value: "4".to_string(),
+                property_type: "string".to_string(),
+                description: "NVLink generation".to_string(),
+                required: true,
+                default_value: Some("4".to_string()),
+                valid_values: Some(vec![
+                    "2".to_string(),
+                    "3".to_string(),
+                    "4".to_string(),
+                    "5".to_string(),
+                ]),
+            },
+            ComponentProperty {
+                name: "links".to_string(),
+                value: "18".to_string(),
+                property_type: "int".to_string(),
+                description: "Number of NVLink links per GPU".to_string(),
+                required: true,
+                default_value: Some("18".to_string()),
+                valid_values: None,
+            },
+            ComponentProperty {
+                name: "bandwidth_gbps".to_string(),
+                value: "900".to_string(),
+                property_type: "int".to_string(),
+                description: "Aggregate bidirectional bandwidth per GPU in GB/s".to_string(),
+                required: false,
+                default_value: Some("900".to_string()),
+                valid_values: None,
+            },
+            ComponentProperty {
+                name: "peer_access".to_string(),
+                value: "true".to_string(),
+                property_type: "bool".to_string(),
+                description: "Enable direct peer memory access between linked GPUs".to_string(),
+                required: false,
+                default_value: Some("true".to_string()),
+                valid_values: Some(vec![
+                    "true".to_string(),
+                    "false".to_string(),
+                ]),
+            },
+        ],
+        
+        ports: vec![
+            ComponentPort {
+                name: "local_memory".to_string(),
+                port_type: "tensor".to_string(),
+                direction: PortDirection::Input,
+                description: "Tensors in the local GPU memory to send to a peer".to_string(),
+            },
+            ComponentPort {
+                name: "peer_memory".to_string(),
+                port_type: "tensor".to_string(),
+                direction: PortDirection::Output,
+                description: "Tensors delivered into the peer GPU memory".to_string(),
+            },
+            ComponentPort {
+                name: "collective".to_string(),
+                port_type: "nccl_comm".to_string(),
+                direction: PortDirection::Bidirectional,
+                description: "Collective communicator (all-reduce, all-gather) running over the links".to_string(),
+            },
+        ],
+        
+        dependencies: vec![
+            ComponentDependency {
+                component_type: ComponentType::Custom("CudaStream".to_string()),
+                min_version: Some("1.0.0".to_string()),
+                max_version: None,
+                optional: false,
+                description: "Transfers are enqueued on a CUDA stream".to_string(),
+            },
+        ],
+        
+        supported_architectures: HashSet::from([
+            KernelArchitecture::Monolithic,
+            KernelArchitecture::Microkernel,
+            KernelArchitecture::Custom("PartitionedKernel".to_string()),
+        ]),
+        
+        supported_languages: vec![
+            "c++".to_string(),
+            "python".to_string(),
+        ],
+        
+        implementation_files: vec![],
+        build_commands: vec![],
+        initialization_code: "// NVLink peer access initialization\n".to_string() +
+            "cudaDeviceEnablePeerAccess(peer_device, 0);\n",
+    }
+}
+
+/// Create a CUDA stream component (ordered asynchronous work queue)
+fn create_cuda_stream_component() -> Component {
+    Component {
+        id: "cuda_stream".to_string(),
+        name: "cuda_stream".to_string(),
+        display_name: "CUDA Stream".to_string(),
+        component_type: ComponentType::Custom("CudaStream".to_string()),
+        category: ComponentCategory::Cuda,
+        version: "1.0.0".to_string(),
+        description: "Ordered queue of asynchronous kernels and copies; independent streams execute concurrently".to_string(),
+        author: "OSland Team".to_string(),
+        source_url: Some("https://github.com/osland-project/osland".to_string()),
+        license: "MulanPSL-2.0".to_string(),
+        
+        properties: vec![
+            ComponentProperty {
+                name: "priority".to_string(),
+                value: "0".to_string(),
+                property_type: "int".to_string(),
+                description: "Stream priority (lower values are higher priority)".to_string(),
+                required: false,
+                default_value: Some("0".to_string()),
+                valid_values: Some(vec![
+                    "-5".to_string(),
+                    "-4".to_string(),
+                    "-3".to_string(),
+                    "-2".to_string(),
+                    "-1".to_string(),
+                    "0".to_string(),
+                ]),
+            },
+            ComponentProperty {
+                name: "non_blocking".to_string(),
+                value: "true".to_string(),
+                property_type: "bool".to_string(),
+                description: "Create the stream with cudaStreamNonBlocking".to_string(),
+                required: false,
+                default_value: Some("true".to_string()),
+                valid_values: Some(vec![
+                    "true".to_string(),
+                    "false".to_string(),
+                ]),
+            },
+            ComponentProperty {
+                name: "capture_mode".to_string(),
+                value: "none".to_string(),
+                property_type: "string".to_string(),
+                description: "Stream capture mode for building CUDA graphs".to_string(),
+                required: false,
+                default_value: Some("none".to_string()),
+                valid_values: Some(vec![
+                    "none".to_string(),
+                    "global".to_string(),
+                    "thread_local".to_string(),
+                    "relaxed".to_string(),
+                ]),
+            },
+        ],
+        
+        ports: vec![
+            ComponentPort {
+                name: "work".to_string(),
+                port_type: "cuda_work".to_string(),
+                direction: PortDirection::Input,
+                description: "Kernels and memory copies enqueued on the stream".to_string(),
+            },
+            ComponentPort {
+                name: "stream".to_string(),
+                port_type: "cuda_stream".to_string(),
+                direction: PortDirection::Output,
+                description: "Stream handle consumed by kernels and copies".to_string(),
+            },
+            ComponentPort {
+                name: "event".to_string(),
+                port_type: "cuda_event".to_string(),
+                direction: PortDirection::Bidirectional,
+                description: "Events recorded on or waited for by the stream".to_string(),
+            },
+        ],
+        
+        dependencies: vec![],
+        
+        supported_architectures: HashSet::from([
+            KernelArchitecture::Monolithic,
+            KernelArchitecture::Microkernel,
+            KernelArchitecture::Custom("PartitionedKernel".to_string()),
+        ]),
+        
+        supported_languages: vec![
+            "c++".to_string(),
+            "python".to_string(),
+            "rust".to_string(),
+        ],
+        
+        implementation_files: vec![],
+        build_commands: vec![],
+        initialization_code: "// CUDA stream initialization\n".to_string() +
+            "cudaStream_t stream;\n" +
+            "cudaStreamCreateWithFlags(&stream, cudaStreamNonBlocking);\n",
+    }
+}
+
+/// Create a CUDA graph component (captured, replayable work DAG)
+fn create_cuda_graph_component() -> Component {
+    Component {
+        id: "cuda_graph".to_string(),
+        name: "cuda_graph".to_string(),
+        display_name: "CUDA Graph".to_string(),
+        component_type: ComponentType::Custom("CudaGraph".to_string()),
+        category: ComponentCategory::Cuda,
+        version: "1.0.0".to_string(),
+        description: "Directed acyclic graph of kernels and copies captured once and replayed with low launch overhead".to_string(),
+        author: "OSland Team".to_string(),
+        source_url: Some("https://github.com/osland-project/osland".to_string()),
+        license: "MulanPSL-2.0".to_string(),
+        
+        properties: vec![
+            ComponentProperty {
+                name: "build_mode".to_string(),
+                value: "capture".to_string(),
+                property_type: "string".to_string(),
+                description: "How the graph is built".to_string(),
+                required: true,
+                default_value: Some("capture".to_string()),
+                valid_values: Some(vec![
+                    "capture".to_string(),
+                    "explicit".to_string(),
+                ]),
+            },
+            ComponentProperty {
+                name: "instantiate_flags".to_string(),
+                value: "none".to_string(),
+                property_type: "string".to_string(),
+                description: "Flags used when instantiating the executable graph".to_string(),
+                required: false,
+                default_value: Some("none".to_string()),
+                valid_values: Some(vec![
+                    "none".to_string(),
+                    "auto_free_on_launch".to_string(),
+                    "device_launch".to_string(),
+                    "use_node_priority".to_string(),
+                ]),
+            },
+            ComponentProperty {
+                name: "max_nodes".to_string(),
+                value: "1024".to_string(),
+                property_type: "int".to_string(),
+                description: "Maximum number of nodes allowed in the graph".to_string(),
+                required: false,
+                default_value: Some("1024".to_string()),
+                valid_values: None,
+            },
+        ],
+        
+        ports: vec![
+            ComponentPort {
+                name: "capture_stream".to_string(),
+                port_type: "cuda_stream".to_string(),
+                direction: PortDirection::Input,
+                description: "Stream whose work is captured into the graph".to_string(),
+            },
+            ComponentPort {
+                name: "graph_exec".to_string(),
+                port_type: "cuda_graph_exec".to_string(),
+                direction: PortDirection::Output,
+                description: "Instantiated executable graph".to_string(),
+            },
+            ComponentPort {
+                name: "launch_stream".to_string(),
+                port_type: "cuda_stream".to_string(),
+                direction: PortDirection::Input,
+                description: "Stream the executable graph is launched on".to_string(),
+            },
+        ],
+        
+        dependencies: vec![
+            ComponentDependency {
+                component_type: ComponentType::Custom("CudaStream".to_string()),
+                min_version: Some("1.0.0".to_string()),
+                max_version: None,
+                optional: false,
+                description: "Graphs are captured from and launched on CUDA streams".to_string(),
+            },
+        ],
+        
+        supported_architectures: HashSet::from([
+            KernelArchitecture::Monolithic,
+            KernelArchitecture::Microkernel,
+            KernelArchitecture::Custom("PartitionedKernel".to_string()),
+        ]),
+        
+        supported_languages: vec![
+            "c++".to_string(),
+            "python".to_string(),
+        ],
+        
+        implementation_files: vec![],
+        build_commands: vec![],
+        initialization_code: "// CUDA graph initialization\n".to_string() +
+            "cudaGraph_t graph;\n" +
+            "cudaGraphExec_t graph_exec;\n",
+    }
+}
+
 /// Create CUDA component library for visualization programming
 pub fn create_cuda_component_library() -> ComponentLibrary {
     let mut library = ComponentLibrary::new();
@@ -802,6 +1344,13 @@ pub fn create_cuda_component_library() -> ComponentLibrary {
     library.add_component(create_tvm_component()).expect("Failed to add TVM component");
     library.add_component(create_helion_component()).expect("Failed to add Helion component");
     
+    // Add memory hierarchy and execution components
+    library.add_component(create_cuda_shared_memory_component()).expect("Failed to add CUDA Shared Memory component");
+    library.add_component(create_cuda_tensor_core_component()).expect("Failed to add CUDA Tensor Core component");
+    library.add_component(create_nvlink_component()).expect("Failed to add NVLink component");
+    library.add_component(create_cuda_stream_component()).expect("Failed to add CUDA Stream component");
+    library.add_component(create_cuda_graph_component()).expect("Failed to add CUDA Graph component");
+    
     library
 }
 
@@ -824,4 +1373,11 @@ pub fn extend_with_cuda_components(library: &mut ComponentLibrary) {
     library.add_component(create_cutile_component()).expect("Failed to add CuTile component");
     library.add_component(create_tvm_component()).expect("Failed to add TVM component");
     library.add_component(create_helion_component()).expect("Failed to add Helion component");
+    
+    // Add memory hierarchy and execution components
+    library.add_component(create_cuda_shared_memory_component()).expect("Failed to add CUDA Shared Memory component");
+    library.add_component(create_cuda_tensor_core_component()).expect("Failed to add CUDA Tensor Core component");
+    library.add_component(create_nvlink_component()).expect("Failed to add NVLink component");
+    library.add_component(create_cuda_stream_component()).expect("Failed to add CUDA Stream component");
+    library.add_component(create_cuda_graph_component()).expect("Failed to add CUDA Graph component");
 }
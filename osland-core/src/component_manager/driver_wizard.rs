@@ -0,0 +1,792 @@
+// Driver skeleton wizard for OSland Component Manager
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! Guided generation of Linux driver skeletons.
+//!
+//! The wizard asks for the bus (PCI, platform, USB or virtio), the language, the driver
+//! name and the devices to match, then produces the driver source with probe and remove
+//! handlers, its Kconfig entry and Kbuild file, and a device driver component that can be
+//! registered into the component library and placed on the canvas.
+
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::core::geometry::Point;
+use super::component::{Component, ComponentCategory, ComponentPort, ComponentProperty, ComponentType, PortDirection};
+use super::canvas_lint::OPTIONAL_PORTS_PROPERTY;
+use super::visual_node::{NodeCanvas, VisualNode};
+use super::{ComponentLibrary, ComponentManagerError};
+use crate::os_design::syscall_designer::GeneratedFile;
+
+/// Bus a driver binds to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DriverBus {
+    Pci,
+    Platform,
+    Usb,
+    Virtio,
+}
+
+impl DriverBus {
+    /// All buses
+    pub fn all() -> [DriverBus; 4] {
+        [DriverBus::Pci, DriverBus::Platform, DriverBus::Usb, DriverBus::Virtio]
+    }
+    
+    /// Lowercase name
+    pub fn name(&self) -> &'static str {
+        match self {
+            DriverBus::Pci => "pci",
+            DriverBus::Platform => "platform",
+            DriverBus::Usb => "usb",
+            DriverBus::Virtio => "virtio",
+        }
+    }
+    
+    /// Parse a bus name
+    pub fn from_name(name: &str) -> Option<DriverBus> {
+        let name = name.trim().to_lowercase();
+        DriverBus::all().into_iter().find(|bus| bus.name() == name)
+    }
+    
+    /// Languages a skeleton can be generated in for this bus
+    ///
+    /// The kernel crate only has driver bindings for PCI and platform devices.
+    pub fn languages(&self) -> &'static [DriverLanguage] {
+        match self {
+            DriverBus::Pci | DriverBus::Platform => &[DriverLanguage::C, DriverLanguage::Rust],
+            DriverBus::Usb | DriverBus::Virtio => &[DriverLanguage::C],
+        }
+    }
+    
+    /// Kconfig dependency of drivers on this bus
+    fn kconfig_dependency(&self) -> &'static str {
+        match self {
+            DriverBus::Pci => "PCI",
+            DriverBus::Platform => "OF || COMPILE_TEST",
+            DriverBus::Usb => "USB",
+            DriverBus::Virtio => "VIRTIO",
+        }
+    }
+}
+
+/// Language of the generated driver
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DriverLanguage {
+    C,
+    Rust,
+}
+
+impl DriverLanguage {
+    /// Lowercase name
+    pub fn name(&self) -> &'static str {
+        match self {
+            DriverLanguage::C => "c",
+            DriverLanguage::Rust => "rust",
+        }
+    }
+    
+    /// Parse a language name
+    pub fn from_name(name: &str) -> Option<DriverLanguage> {
+        match name.trim().to_lowercase().as_str() {
+            "c" => Some(DriverLanguage::C),
+            "rust" | "rs" => Some(DriverLanguage::Rust),
+            _ => None,
+        }
+    }
+}
+
+/// Devices a driver matches
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeviceMatch {
+    /// PCI vendor and device IDs
+    Pci { vendor: u16, device: u16 },
+    
+    /// Device tree compatible string
+    Platform { compatible: String },
+    
+    /// USB vendor and product IDs
+    Usb { vendor: u16, product: u16 },
+    
+    /// Virtio device ID
+    Virtio { device_id: u32 },
+}
+
+impl DeviceMatch {
+    /// Parse the device IDs of a bus (`vvvv:dddd` in hex, a compatible string or a virtio ID)
+    pub fn parse(bus: DriverBus, input: &str) -> Result<DeviceMatch, String> {
+        let input = input.trim();
+        let id_pair = || -> Result<(u16, u16), String> {
+            let (vendor, device) = input.split_once(':').ok_or_else(|| format!("Expected 'vendor:device' in hex, got '{}'", input))?;
+            let hex = |value: &str| u16::from_str_radix(value.trim().trim_start_matches("0x"), 16).map_err(|_| format!("Invalid hex ID '{}'", value));
+            Ok((hex(vendor)?, hex(device)?))
+        };
+        match bus {
+            DriverBus::Pci => id_pair().map(|(vendor, device)| DeviceMatch::Pci { vendor, device }),
+            DriverBus::Usb => id_pair().map(|(vendor, product)| DeviceMatch::Usb { vendor, product }),
+            DriverBus::Platform => {
+                let valid = input.split_once(',').map_or(false, |(vendor, device)| !vendor.is_empty() && !device.is_empty())
+                    && input.chars().all(|c| c.is_ascii_alphanumeric() || ",-_.".contains(c));
+                if valid {
+                    Ok(DeviceMatch::Platform { compatible: input.to_string() })
+                } else {
+                    Err(format!("Expected a compatible string like 'vendor,device', got '{}'", input))
+                }
+            }
+            DriverBus::Virtio => input.parse().map(|device_id| DeviceMatch::Virtio { device_id })
+                .map_err(|_| format!("Expected a numeric virtio device ID, got '{}'", input)),
+        }
+    }
+    
+    /// Bus of the match
+    pub fn bus(&self) -> DriverBus {
+        match self {
+            DeviceMatch::Pci { .. } => DriverBus::Pci,
+            DeviceMatch::Platform { .. } => DriverBus::Platform,
+            DeviceMatch::Usb { .. } => DriverBus::Usb,
+            DeviceMatch::Virtio { .. } => DriverBus::Virtio,
+        }
+    }
+}
+
+impl fmt::Display for DeviceMatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeviceMatch::Pci { vendor, device } => write!(f, "{:04x}:{:04x}", vendor, device),
+            DeviceMatch::Platform { compatible } => write!(f, "{}", compatible),
+            DeviceMatch::Usb { vendor, product } => write!(f, "{:04x}:{:04x}", vendor, product),
+            DeviceMatch::Virtio { device_id } => write!(f, "{}", device_id),
+        }
+    }
+}
+
+/// Everything needed to generate a driver
+#[derive(Debug, Clone, PartialEq)]
+pub struct DriverSpec {
+    /// Module name, also the prefix of the generated symbols
+    pub name: String,
+    
+    /// Language
+    pub language: DriverLanguage,
+    
+    /// Matched devices
+    pub device: DeviceMatch,
+    
+    /// One-line description
+    pub description: String,
+    
+    /// Module author
+    pub author: String,
+}
+
+impl DriverSpec {
+    /// Check the spec can be generated
+    pub fn validate(&self) -> Result<(), String> {
+        let valid_name = self.name.chars().next().map_or(false, |c| c.is_ascii_lowercase())
+            && self.name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+        if !valid_name {
+            return Err(format!("Driver name '{}' must be a lowercase C identifier", self.name));
+        }
+        let bus = self.device.bus();
+        if !bus.languages().contains(&self.language) {
+            return Err(format!("{} drivers cannot be generated in {} yet", bus.name(), self.language.name()));
+        }
+        check_text("Description", &self.description)?;
+        check_text("Author", &self.author)
+    }
+    
+    /// Generate the driver skeleton
+    pub fn generate(&self) -> Result<DriverSkeleton, String> {
+        self.validate()?;
+        let source = match self.language {
+            DriverLanguage::C => GeneratedFile { path: PathBuf::from(format!("{}.c", self.name)), content: self.c_source() },
+            DriverLanguage::Rust => GeneratedFile { path: PathBuf::from(format!("{}.rs", self.name)), content: self.rust_source() },
+        };
+        let symbol = self.name.to_uppercase();
+        let mut depends = format!("depends on {}", self.device.bus().kconfig_dependency());
+        if self.language == DriverLanguage::Rust {
+            depends.push_str("\n\tdepends on RUST");
+        }
+        let kconfig = format!(
+            "config {symbol}\n\ttristate \"{description}\"\n\t{depends}\n\thelp\n\t  {description}.\n\n\t  To compile this driver as a module, choose M here: the\n\t  module will be called {name}.\n",
+            description = self.description, name = self.name,
+        );
+        let kbuild = format!("# SPDX-License-Identifier: GPL-2.0\nobj-$(CONFIG_{}) += {}.o\n", symbol, self.name);
+        
+        let files = vec![source, GeneratedFile { path: PathBuf::from("Kconfig"), content: kconfig }, GeneratedFile { path: PathBuf::from("Kbuild"), content: kbuild }];
+        let component = self.component(&files);
+        Ok(DriverSkeleton { spec: self.clone(), files, component })
+    }
+    
+    fn module_info_c(&self) -> String {
+        format!("MODULE_DESCRIPTION(\"{}\");\nMODULE_AUTHOR(\"{}\");\nMODULE_LICENSE(\"GPL\");\n", self.description, self.author)
+    }
+    
+    fn c_source(&self) -> String {
+        let n = &self.name;
+        let header = format!("// SPDX-License-Identifier: GPL-2.0\n/*\n * {} - generated by OSland\n */\n\n", self.description);
+        let body = match &self.device {
+            DeviceMatch::Pci { vendor, device } => format!(r#"#include <linux/module.h>
+#include <linux/pci.h>
+
+static const struct pci_device_id {n}_ids[] = {{
+	{{ PCI_DEVICE(0x{vendor:04x}, 0x{device:04x}) }},
+	{{ }}
+}};
+MODULE_DEVICE_TABLE(pci, {n}_ids);
+
+static int {n}_probe(struct pci_dev *pdev, const struct pci_device_id *id)
+{{
+	int ret;
+
+	ret = pcim_enable_device(pdev);
+	if (ret)
+		return ret;
+	pci_set_master(pdev);
+
+	/* TODO: map BARs, request interrupts and register the device */
+	dev_info(&pdev->dev, "probed\n");
+	return 0;
+}}
+
+static void {n}_remove(struct pci_dev *pdev)
+{{
+	/* TODO: unregister the device; managed resources are released automatically */
+	dev_info(&pdev->dev, "removed\n");
+}}
+
+static struct pci_driver {n}_driver = {{
+	.name = KBUILD_MODNAME,
+	.id_table = {n}_ids,
+	.probe = {n}_probe,
+	.remove = {n}_remove,
+}};
+module_pci_driver({n}_driver);
+"#),
+            DeviceMatch::Platform { compatible } => format!(r#"#include <linux/mod_devicetable.h>
+#include <linux/module.h>
+#include <linux/platform_device.h>
+
+static const struct of_device_id {n}_of_match[] = {{
+	{{ .compatible = "{compatible}" }},
+	{{ }}
+}};
+MODULE_DEVICE_TABLE(of, {n}_of_match);
+
+static int {n}_probe(struct platform_device *pdev)
+{{
+	/* TODO: map registers, request interrupts and register the device */
+	dev_info(&pdev->dev, "probed\n");
+	return 0;
+}}
+
+static void {n}_remove(struct platform_device *pdev)
+{{
+	/* TODO: unregister the device; managed resources are released automatically */
+	dev_info(&pdev->dev, "removed\n");
+}}
+
+static struct platform_driver {n}_driver = {{
+	.driver = {{
+		.name = KBUILD_MODNAME,
+		.of_match_table = {n}_of_match,
+	}},
+	.probe = {n}_probe,
+	.remove = {n}_remove,
+}};
+module_platform_driver({n}_driver);
+"#),
+            DeviceMatch::Usb { vendor, product } => format!(r#"#include <linux/module.h>
+#include <linux/usb.h>
+
+static const struct usb_device_id {n}_ids[] = {{
+	{{ USB_DEVICE(0x{vendor:04x}, 0x{product:04x}) }},
+	{{ }}
+}};
+MODULE_DEVICE_TABLE(usb, {n}_ids);
+
+static int {n}_probe(struct usb_interface *intf, const struct usb_device_id *id)
+{{
+	/* TODO: find the endpoints, allocate URBs and register the device */
+	dev_info(&intf->dev, "probed\n");
+	return 0;
+}}
+
+static void {n}_disconnect(struct usb_interface *intf)
+{{
+	/* TODO: kill pending URBs and unregister the device */
+	dev_info(&intf->dev, "disconnected\n");
+}}
+
+static struct usb_driver {n}_driver = {{
+	.name = KBUILD_MODNAME,
+	.id_table = {n}_ids,
+	.probe = {n}_probe,
+	.disconnect = {n}_disconnect,
+}};
+module_usb_driver({n}_driver);
+"#),
+            DeviceMatch::Virtio { device_id } => format!(r#"#include <linux/module.h>
+#include <linux/virtio.h>
+#include <linux/virtio_config.h>
+
+static const struct virtio_device_id {n}_ids[] = {{
+	{{ {device_id}, VIRTIO_DEV_ANY_ID }},
+	{{ }}
+}};
+MODULE_DEVICE_TABLE(virtio, {n}_ids);
+
+static int {n}_probe(struct virtio_device *vdev)
+{{
+	/* TODO: find the virtqueues with virtio_find_vqs() and register the device */
+	virtio_device_ready(vdev);
+	dev_info(&vdev->dev, "probed\n");
+	return 0;
+}}
+
+static void {n}_remove(struct virtio_device *vdev)
+{{
+	/* TODO: unregister the device */
+	virtio_reset_device(vdev);
+	vdev->config->del_vqs(vdev);
+	dev_info(&vdev->dev, "removed\n");
+}}
+
+static struct virtio_driver {n}_driver = {{
+	.driver.name = KBUILD_MODNAME,
+	.id_table = {n}_ids,
+	.probe = {n}_probe,
+	.remove = {n}_remove,
+}};
+module_virtio_driver({n}_driver);
+"#),
+        };
+        format!("{}{}\n{}", header, body, self.module_info_c())
+    }
+    
+    fn rust_source(&self) -> String {
+        let n = &self.name;
+        let ty = n.split('_').map(|part| {
+            let mut chars = part.chars();
+            chars.next().map(|c| c.to_ascii_uppercase().to_string() + chars.as_str()).unwrap_or_default()
+        }).collect::<String>() + "Driver";
+        let module_info = format!("    name: \"{}\",\n    authors: [\"{}\"],\n    description: \"{}\",\n    license: \"GPL\",\n", n, self.author, self.description);
+        let header = format!("// SPDX-License-Identifier: GPL-2.0\n\n//! {} - generated by OSland\n\n", self.description);
+        let body = match &self.device {
+            DeviceMatch::Pci { vendor, device } => format!(r#"use kernel::{{device::Core, pci, prelude::*, types::ARef}};
+
+struct {ty} {{
+    pdev: ARef<pci::Device>,
+}}
+
+kernel::pci_device_table!(
+    PCI_TABLE,
+    MODULE_PCI_TABLE,
+    <{ty} as pci::Driver>::IdInfo,
+    [(pci::DeviceId::from_id(0x{vendor:04x}, 0x{device:04x}), ())]
+);
+
+impl pci::Driver for {ty} {{
+    type IdInfo = ();
+    const ID_TABLE: pci::IdTable<Self::IdInfo> = &PCI_TABLE;
+    
+    fn probe(pdev: &pci::Device<Core>, _info: &Self::IdInfo) -> Result<Pin<KBox<Self>>> {{
+        pdev.enable_device_mem()?;
+        pdev.set_master();
+        
+        // TODO: map BARs, request interrupts and register the device
+        dev_info!(pdev.as_ref(), "probed\n");
+        Ok(KBox::new(Self {{ pdev: pdev.into() }}, GFP_KERNEL)?.into())
+    }}
+}}
+
+// Dropping the driver data is the remove handler
+impl Drop for {ty} {{
+    fn drop(&mut self) {{
+        // TODO: unregister the device
+        dev_info!(self.pdev.as_ref(), "removed\n");
+    }}
+}}
+
+kernel::module_pci_driver! {{
+    type: {ty},
+{module_info}}}
+"#),
+            DeviceMatch::Platform { compatible } => format!(r#"use kernel::{{c_str, device::Core, of, platform, prelude::*, types::ARef}};
+
+struct {ty} {{
+    pdev: ARef<platform::Device>,
+}}
+
+kernel::of_device_table!(
+    OF_TABLE,
+    MODULE_OF_TABLE,
+    <{ty} as platform::Driver>::IdInfo,
+    [(of::DeviceId::new(c_str!("{compatible}")), ())]
+);
+
+impl platform::Driver for {ty} {{
+    type IdInfo = ();
+    const OF_ID_TABLE: Option<of::IdTable<Self::IdInfo>> = Some(&OF_TABLE);
+    
+    fn probe(pdev: &platform::Device<Core>, _info: Option<&Self::IdInfo>) -> Result<Pin<KBox<Self>>> {{
+        // TODO: map registers, request interrupts and register the device
+        dev_info!(pdev.as_ref(), "probed\n");
+        Ok(KBox::new(Self {{ pdev: pdev.into() }}, GFP_KERNEL)?.into())
+    }}
+}}
+
+// Dropping the driver data is the remove handler
+impl Drop for {ty} {{
+    fn drop(&mut self) {{
+        // TODO: unregister the device
+        dev_info!(self.pdev.as_ref(), "removed\n");
+    }}
+}}
+
+kernel::module_platform_driver! {{
+    type: {ty},
+{module_info}}}
+"#),
+            // Rejected by validate()
+            DeviceMatch::Usb { .. } | DeviceMatch::Virtio { .. } => String::new(),
+        };
+        format!("{}{}", header, body)
+    }
+    
+    /// Device driver component describing the generated skeleton
+    fn component(&self, files: &[GeneratedFile]) -> Component {
+        let bus = self.device.bus();
+        let property = |name: &str, value: String, description: &str| ComponentProperty {
+            name: name.to_string(),
+            value: value.clone(),
+            property_type: "string".to_string(),
+            description: description.to_string(),
+            required: false,
+            default_value: Some(value),
+            valid_values: None,
+        };
+        
+        Component {
+            id: format!("driver_{}", self.name),
+            name: self.name.clone(),
+            display_name: format!("{} ({} driver)", self.name, bus.name()),
+            component_type: ComponentType::DeviceDriver,
+            category: ComponentCategory::DeviceDrivers,
+            version: "0.1.0".to_string(),
+            description: self.description.clone(),
+            author: self.author.clone(),
+            source_url: None,
+            license: "GPL-2.0".to_string(),
+            properties: vec![
+                property("bus", bus.name().to_string(), "Bus the driver binds to"),
+                property("device_match", self.device.to_string(), "Matched devices"),
+                property("module_name", self.name.clone(), "Kernel module name"),
+                property(OPTIONAL_PORTS_PROPERTY, "bus".to_string(), "Ports that may stay unconnected"),
+            ],
+            ports: vec![
+                ComponentPort {
+                    name: "bus".to_string(),
+                    port_type: format!("{}_bus", bus.name()),
+                    direction: PortDirection::Input,
+                    description: format!("{} bus the device sits on", bus.name()),
+                },
+                ComponentPort {
+                    name: "device".to_string(),
+                    port_type: "DeviceInterface".to_string(),
+                    direction: PortDirection::Output,
+                    description: "Device registered by the driver".to_string(),
+                },
+            ],
+            dependencies: Vec::new(),
+            supported_architectures: Default::default(),
+            supported_languages: vec![self.language.name().to_string()],
+            implementation_files: files.iter().map(|file| file.path.to_string_lossy().to_string()).collect(),
+            build_commands: vec!["make -C $(KDIR) M=$(PWD) modules".to_string()],
+            initialization_code: String::new(),
+        }
+    }
+}
+
+/// Generated driver: sources and the component describing them
+#[derive(Debug, Clone)]
+pub struct DriverSkeleton {
+    /// Spec the skeleton was generated from
+    pub spec: DriverSpec,
+    
+    /// Driver source, Kconfig entry and Kbuild file
+    pub files: Vec<GeneratedFile>,
+    
+    /// Device driver component
+    pub component: Component,
+}
+
+impl DriverSkeleton {
+    /// Write the files into a directory and point the component at them
+    pub fn write<P: AsRef<Path>>(&mut self, dir: P) -> Result<Vec<PathBuf>, String> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        let paths = self.files.iter().map(|file| {
+            let path = dir.join(&file.path);
+            fs::write(&path, &file.content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+            Ok(path)
+        }).collect::<Result<Vec<_>, String>>()?;
+        self.component.implementation_files = paths.iter().map(|path| path.to_string_lossy().to_string()).collect();
+        Ok(paths)
+    }
+    
+    /// Register the component into a library
+    pub fn register(&self, library: &mut ComponentLibrary) -> Result<(), ComponentManagerError> {
+        library.add_component(self.component.clone())
+    }
+    
+    /// Place a node of the component on a canvas, returning the node ID
+    pub fn place(&self, canvas: &mut NodeCanvas, position: Point) -> Result<String, ComponentManagerError> {
+        let node = VisualNode::new(self.component.clone(), position)?;
+        let id = node.id.clone();
+        canvas.add_node(node)?;
+        Ok(id)
+    }
+}
+
+/// Check free text can go into the string literals and comments of the generated sources
+fn check_text(field: &str, value: &str) -> Result<(), String> {
+    if value.chars().any(|c| c == '"' || c == '\\' || c.is_control()) || value.contains("*/") {
+        return Err(format!("{} must be a single line without quotes, backslashes or '*/'", field));
+    }
+    Ok(())
+}
+
+/// Step of the driver wizard
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WizardStep {
+    Bus,
+    Language,
+    Name,
+    Devices,
+    Description,
+    Review,
+}
+
+/// Guided driver generator
+///
+/// Each call to `answer` validates the input for the current step and moves on; `back`
+/// returns to the previous step. Once at `Review`, `finish` generates the skeleton.
+#[derive(Debug, Clone)]
+pub struct DriverWizard {
+    step: WizardStep,
+    bus: Option<DriverBus>,
+    language: Option<DriverLanguage>,
+    name: Option<String>,
+    device: Option<DeviceMatch>,
+    description: Option<String>,
+    author: String,
+}
+
+impl DriverWizard {
+    /// Start a wizard; `author` goes into the module info
+    pub fn new(author: &str) -> Self {
+        Self {
+            step: WizardStep::Bus,
+            bus: None,
+            language: None,
+            name: None,
+            device: None,
+            description: None,
+            author: author.to_string(),
+        }
+    }
+    
+    /// Current step
+    pub fn step(&self) -> WizardStep {
+        self.step
+    }
+    
+    /// Question for the current step
+    pub fn prompt(&self) -> String {
+        let bus = self.bus.unwrap_or(DriverBus::Pci);
+        match self.step {
+            WizardStep::Bus => "Which bus does the device sit on?".to_string(),
+            WizardStep::Language => "Which language should the driver be written in?".to_string(),
+            WizardStep::Name => "Driver module name (lowercase, e.g. 'acme_sensor')".to_string(),
+            WizardStep::Devices => match bus {
+                DriverBus::Pci => "PCI vendor and device ID (hex, e.g. '1af4:1041')".to_string(),
+                DriverBus::Usb => "USB vendor and product ID (hex, e.g. '0bda:8153')".to_string(),
+                DriverBus::Platform => "Device tree compatible string (e.g. 'acme,sensor')".to_string(),
+                DriverBus::Virtio => "Virtio device ID (e.g. '1' for network devices)".to_string(),
+            },
+            WizardStep::Description => "One-line description (leave empty for a default)".to_string(),
+            WizardStep::Review => format!("Generate this driver?\n{}", self.summary()),
+        }
+    }
+    
+    /// Valid answers of choice steps (empty for free-form steps)
+    pub fn choices(&self) -> Vec<&'static str> {
+        match self.step {
+            WizardStep::Bus => DriverBus::all().iter().map(|bus| bus.name()).collect(),
+            WizardStep::Language => self.bus.map_or(&[][..], |bus| bus.languages()).iter().map(|language| language.name()).collect(),
+            _ => Vec::new(),
+        }
+    }
+    
+    /// Answer the current step and advance
+    pub fn answer(&mut self, input: &str) -> Result<(), String> {
+        self.step = match self.step {
+            WizardStep::Bus => {
+                let bus = DriverBus::from_name(input).ok_or_else(|| format!("Unknown bus '{}' (expected one of {})", input, self.choices().join(", ")))?;
+                if self.bus != Some(bus) {
+                    // Language and device IDs depend on the bus
+                    self.language = None;
+                    self.device = None;
+                }
+                self.bus = Some(bus);
+                // Skip the language question when there is only one option
+                match bus.languages() {
+                    [only] => {
+                        self.language = Some(*only);
+                        WizardStep::Name
+                    }
+                    _ => WizardStep::Language,
+                }
+            }
+            WizardStep::Language => {
+                let language = DriverLanguage::from_name(input)
+                    .filter(|language| self.bus.map_or(false, |bus| bus.languages().contains(language)))
+                    .ok_or_else(|| format!("Unsupported language '{}' (expected one of {})", input, self.choices().join(", ")))?;
+                self.language = Some(language);
+                WizardStep::Name
+            }
+            WizardStep::Name => {
+                let name = input.trim().to_string();
+                let mut probe = self.preview_spec();
+                probe.name = name.clone();
+                probe.validate()?;
+                self.name = Some(name);
+                WizardStep::Devices
+            }
+            WizardStep::Devices => {
+                let bus = self.bus.ok_or("Choose a bus first")?;
+                self.device = Some(DeviceMatch::parse(bus, input)?);
+                WizardStep::Description
+            }
+            WizardStep::Description => {
+                let description = input.trim();
+                check_text("Description", description)?;
+                self.description = (!description.is_empty()).then(|| description.to_string());
+                WizardStep::Review
+            }
+            WizardStep::Review => match input.trim().to_lowercase().as_str() {
+                "y" | "yes" | "" => WizardStep::Review,
+                _ => {
+                    self.back();
+                    return Ok(());
+                }
+            },
+        };
+        Ok(())
+    }
+    
+    /// Go back one step
+    pub fn back(&mut self) {
+        self.step = match self.step {
+            WizardStep::Bus | WizardStep::Language => WizardStep::Bus,
+            WizardStep::Name if self.bus.map_or(false, |bus| bus.languages().len() == 1) => WizardStep::Bus,
+            WizardStep::Name => WizardStep::Language,
+            WizardStep::Devices => WizardStep::Name,
+            WizardStep::Description => WizardStep::Devices,
+            WizardStep::Review => WizardStep::Description,
+        };
+    }
+    
+    /// Spec from the answers so far, with placeholders for missing ones
+    fn preview_spec(&self) -> DriverSpec {
+        let bus = self.bus.unwrap_or(DriverBus::Pci);
+        let name = self.name.clone().unwrap_or_else(|| "driver".to_string());
+        DriverSpec {
+            description: self.description.clone().unwrap_or_else(|| format!("{} {} driver", name, bus.name())),
+            name,
+            language: self.language.unwrap_or(DriverLanguage::C),
+            device: self.device.clone().unwrap_or(match bus {
+                DriverBus::Pci => DeviceMatch::Pci { vendor: 0, device: 0 },
+                DriverBus::Platform => DeviceMatch::Platform { compatible: "vendor,device".to_string() },
+                DriverBus::Usb => DeviceMatch::Usb { vendor: 0, product: 0 },
+                DriverBus::Virtio => DeviceMatch::Virtio { device_id: 0 },
+            }),
+            author: self.author.clone(),
+        }
+    }
+    
+    /// Summary of the answers
+    pub fn summary(&self) -> String {
+        let spec = self.preview_spec();
+        format!(
+            "  bus: {}\n  language: {}\n  name: {}\n  devices: {}\n  description: {}",
+            spec.device.bus().name(), spec.language.name(), spec.name, spec.device, spec.description,
+        )
+    }
+    
+    /// Generate the skeleton once every step is answered
+    pub fn finish(&self) -> Result<DriverSkeleton, String> {
+        if self.step != WizardStep::Review {
+            return Err(format!("The wizard is not complete (current step: {:?})", self.step));
+        }
+        self.preview_spec().generate()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_wizard_generates_registered_driver() {
+        let mut wizard = DriverWizard::new("OSland Team");
+        assert_eq!(wizard.choices(), vec!["pci", "platform", "usb", "virtio"]);
+        wizard.answer("virtio").unwrap();
+        // Virtio drivers are C only, so the language step is skipped
+        assert_eq!(wizard.step(), WizardStep::Name);
+        wizard.back();
+        wizard.answer("PCI").unwrap();
+        assert_eq!(wizard.choices(), vec!["c", "rust"]);
+        wizard.answer("rust").unwrap();
+        assert!(wizard.answer("Acme-Sensor").is_err());
+        wizard.answer("acme_sensor").unwrap();
+        assert!(wizard.answer("1af4").is_err());
+        wizard.answer("1af4:0x1041").unwrap();
+        assert!(wizard.answer("Sensor\\n\");\nMODULE_LICENSE(\"MIT").is_err());
+        assert!(wizard.answer("Sensor */ int x;").is_err());
+        wizard.answer("").unwrap();
+        assert_eq!(wizard.step(), WizardStep::Review);
+        
+        let skeleton = wizard.finish().unwrap();
+        let paths: Vec<&str> = skeleton.files.iter().filter_map(|file| file.path.to_str()).collect();
+        assert_eq!(paths, vec!["acme_sensor.rs", "Kconfig", "Kbuild"]);
+        assert!(skeleton.files[0].content.contains("impl pci::Driver for AcmeSensorDriver"));
+        assert!(skeleton.files[0].content.contains("pci::DeviceId::from_id(0x1af4, 0x1041)"));
+        assert!(skeleton.files[1].content.contains("\tdepends on PCI\n\tdepends on RUST\n"));
+        assert_eq!(skeleton.files[2].content.lines().last(), Some("obj-$(CONFIG_ACME_SENSOR) += acme_sensor.o"));
+        
+        let mut spec = DriverSpec {
+            name: "acme_usb".to_string(),
+            language: DriverLanguage::C,
+            device: DeviceMatch::parse(DriverBus::Usb, "0bda:8153").unwrap(),
+            description: "Acme USB adapter".to_string(),
+            author: "OSland Team".to_string(),
+        };
+        let usb = spec.generate().unwrap();
+        assert!(usb.files[0].content.contains("{ USB_DEVICE(0x0bda, 0x8153) },"));
+        assert!(usb.files[0].content.contains(".disconnect = acme_usb_disconnect,"));
+        spec.author = "OSland\nTeam".to_string();
+        assert!(spec.generate().is_err());
+        
+        let mut library = ComponentLibrary::new();
+        skeleton.register(&mut library).unwrap();
+        let component = library.get_component("driver_acme_sensor").unwrap();
+        assert_eq!(component.component_type, ComponentType::DeviceDriver);
+        
+        let mut canvas = NodeCanvas::new();
+        let node_id = skeleton.place(&mut canvas, Point::new(10.0, 20.0)).unwrap();
+        assert_eq!(canvas.nodes[&node_id].component_id, "driver_acme_sensor");
+    }
+}
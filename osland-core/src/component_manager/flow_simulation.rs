@@ -0,0 +1,455 @@
+// Data Flow Simulation for OSland Component Manager
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
+use serde::{Serialize, Deserialize};
+use crate::component_manager::visual_node::NodeCanvas;
+
+/// Node property holding the processing throughput in bytes per second
+pub const THROUGHPUT_PROPERTY: &str = "throughput";
+
+/// Node property holding the traffic generation rate of source nodes in bytes per second
+pub const RATE_PROPERTY: &str = "rate";
+
+/// Node property overriding the message size in bytes
+pub const MESSAGE_SIZE_PROPERTY: &str = "message_size";
+
+/// Flow simulation settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlowSimulationConfig {
+    /// Simulated time per step
+    pub tick: Duration,
+    
+    /// Throughput of nodes without a `throughput` property (bytes per second)
+    pub default_throughput: u64,
+    
+    /// Message size of port types not listed in `message_sizes`
+    pub default_message_size: usize,
+    
+    /// Message size per port type (bytes)
+    pub message_sizes: HashMap<String, usize>,
+    
+    /// Relative random variation of generated traffic (0.1 = ±10%)
+    pub jitter: f64,
+    
+    /// Utilization of the destination node above which a link is a bottleneck
+    pub bottleneck_threshold: f64,
+    
+    /// Seed of the traffic generator
+    pub seed: u64,
+}
+
+impl Default for FlowSimulationConfig {
+    fn default() -> Self {
+        let message_sizes = [("Packet", 1500), ("Block", 4096), ("Page", 4096), ("Interrupt", 8), ("Message", 256)]
+            .into_iter()
+            .map(|(port_type, size)| (port_type.to_string(), size))
+            .collect();
+        
+        Self {
+            tick: Duration::from_millis(100),
+            default_throughput: 1_000_000,
+            default_message_size: 64,
+            message_sizes,
+            jitter: 0.1,
+            bottleneck_threshold: 1.0,
+            seed: 0x05_1a_4d,
+        }
+    }
+}
+
+/// Statistics of a connection
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LinkStats {
+    /// Connection ID
+    pub connection_id: String,
+    
+    /// Current flow rate (bytes per second)
+    pub flow_rate: f64,
+    
+    /// Peak flow rate (bytes per second)
+    pub peak_rate: f64,
+    
+    /// Utilization of the destination node (1.0 = saturated)
+    pub utilization: f64,
+    
+    /// Total bytes transferred
+    pub bytes_transferred: u64,
+    
+    /// Total messages transferred
+    pub messages: u64,
+    
+    /// Whether the link is a bottleneck in the current step
+    pub bottleneck: bool,
+}
+
+/// Statistics of a node
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NodeFlowStats {
+    /// Node ID
+    pub node_id: String,
+    
+    /// Offered input rate (bytes per second)
+    pub input_rate: f64,
+    
+    /// Processed output rate (bytes per second)
+    pub output_rate: f64,
+    
+    /// Processing capacity (bytes per second)
+    pub capacity: f64,
+    
+    /// Bytes waiting to be processed
+    pub backlog_bytes: f64,
+}
+
+/// State of the simulation after a step
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FlowSnapshot {
+    /// Step number
+    pub tick: u64,
+    
+    /// Simulated time
+    pub elapsed: Duration,
+    
+    /// Link statistics, ordered by connection ID
+    pub links: Vec<LinkStats>,
+    
+    /// Node statistics, ordered by node ID
+    pub nodes: Vec<NodeFlowStats>,
+}
+
+impl FlowSnapshot {
+    /// Bottleneck links, most utilized first
+    pub fn bottlenecks(&self) -> Vec<&LinkStats> {
+        let mut bottlenecks: Vec<&LinkStats> = self.links.iter().filter(|link| link.bottleneck).collect();
+        bottlenecks.sort_by(|a, b| b.utilization.partial_cmp(&a.utilization).unwrap_or(std::cmp::Ordering::Equal));
+        bottlenecks
+    }
+}
+
+/// Data Flow Simulator
+///
+/// Generates synthetic traffic at the canvas entry points and propagates it
+/// along the connections. Each node forwards at most its `throughput`,
+/// queues the excess, and splits its output evenly over its outgoing
+/// connections. The resulting rates are written to each connection's
+/// `DataFlowInfo` so the canvas can animate them.
+pub struct FlowSimulator {
+    config: FlowSimulationConfig,
+    tick: u64,
+    rng: u64,
+    links: HashMap<String, LinkStats>,
+    backlog: HashMap<String, f64>,
+    previous_flows: HashMap<String, f64>,
+}
+
+impl FlowSimulator {
+    /// Create a new simulator
+    pub fn new(config: FlowSimulationConfig) -> Self {
+        let rng = config.seed.max(1);
+        Self {
+            config,
+            tick: 0,
+            rng,
+            links: HashMap::new(),
+            backlog: HashMap::new(),
+            previous_flows: HashMap::new(),
+        }
+    }
+    
+    /// Get the simulation settings
+    pub fn config(&self) -> &FlowSimulationConfig {
+        &self.config
+    }
+    
+    /// Next pseudo-random number in [0, 1) (xorshift64)
+    fn next_random(&mut self) -> f64 {
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 7;
+        self.rng ^= self.rng << 17;
+        (self.rng >> 11) as f64 / (1u64 << 53) as f64
+    }
+    
+    /// Read a numeric node property
+    fn node_number(canvas: &NodeCanvas, node_id: &str, property: &str) -> Option<f64> {
+        canvas.nodes.get(node_id)
+            .and_then(|node| node.properties.get(property))
+            .and_then(|value| value.trim().parse::<f64>().ok())
+    }
+    
+    /// Advance the simulation by one tick and update the canvas' data flow information
+    pub fn step(&mut self, canvas: &mut NodeCanvas) -> FlowSnapshot {
+        self.tick += 1;
+        let dt = self.config.tick.as_secs_f64().max(f64::EPSILON);
+        
+        let mut outgoing: HashMap<&str, Vec<&str>> = HashMap::new();
+        let mut incoming: HashMap<&str, Vec<&str>> = HashMap::new();
+        let mut connection_ids: Vec<&String> = canvas.connections.keys().collect();
+        connection_ids.sort();
+        for id in &connection_ids {
+            let conn = &canvas.connections[*id];
+            if canvas.nodes.contains_key(&conn.from_node) && canvas.nodes.contains_key(&conn.to_node) {
+                outgoing.entry(conn.from_node.as_str()).or_default().push(id.as_str());
+                incoming.entry(conn.to_node.as_str()).or_default().push(id.as_str());
+            }
+        }
+        
+        // Topological order; nodes on cycles follow in ID order and read last tick's flows
+        let mut node_ids: Vec<&str> = canvas.nodes.keys().map(String::as_str).collect();
+        node_ids.sort();
+        let mut in_degree: HashMap<&str, usize> = node_ids.iter().map(|id| (*id, incoming.get(id).map_or(0, Vec::len))).collect();
+        let mut queue: VecDeque<&str> = node_ids.iter().copied().filter(|id| in_degree[id] == 0).collect();
+        let mut order = Vec::new();
+        while let Some(id) = queue.pop_front() {
+            order.push(id);
+            for conn_id in outgoing.get(id).cloned().unwrap_or_default() {
+                let dest = canvas.connections[conn_id].to_node.as_str();
+                let degree = in_degree.get_mut(dest).unwrap();
+                *degree -= 1;
+                if *degree == 0 {
+                    queue.push_back(dest);
+                }
+            }
+        }
+        for id in &node_ids {
+            if !order.contains(id) {
+                order.push(id);
+            }
+        }
+        
+        let mut flows: HashMap<String, f64> = HashMap::new();
+        let mut node_stats = Vec::new();
+        for node_id in order {
+            let capacity = Self::node_number(canvas, node_id, THROUGHPUT_PROPERTY)
+                .unwrap_or(self.config.default_throughput as f64);
+            let input_rate = match incoming.get(node_id) {
+                Some(links) => links.iter()
+                    .map(|link| flows.get(*link).or_else(|| self.previous_flows.get(*link)).copied().unwrap_or(0.0))
+                    .sum(),
+                None => {
+                    let rate = Self::node_number(canvas, node_id, RATE_PROPERTY).unwrap_or(capacity);
+                    let variation = 1.0 + self.config.jitter * (self.next_random() * 2.0 - 1.0);
+                    rate * variation
+                }
+            };
+            
+            let pending = input_rate * dt + self.backlog.get(node_id).copied().unwrap_or(0.0);
+            let processed = pending.min(capacity * dt);
+            let backlog = pending - processed;
+            self.backlog.insert(node_id.to_string(), backlog);
+            
+            let output_rate = processed / dt;
+            if let Some(links) = outgoing.get(node_id) {
+                for link in links {
+                    flows.insert(link.to_string(), output_rate / links.len() as f64);
+                }
+            }
+            
+            node_stats.push(NodeFlowStats {
+                node_id: node_id.to_string(),
+                input_rate,
+                output_rate,
+                capacity,
+                backlog_bytes: backlog,
+            });
+        }
+        
+        // Utilization of each node's input
+        let utilization: HashMap<&str, f64> = node_stats.iter()
+            .map(|stats| (stats.node_id.as_str(), if stats.capacity > 0.0 { stats.input_rate / stats.capacity } else { f64::INFINITY }))
+            .collect();
+        let max_rate = flows.values().copied().fold(0.0, f64::max);
+        
+        let mut links = Vec::new();
+        for id in connection_ids.iter().map(|id| id.to_string()).collect::<Vec<_>>() {
+            let flow_rate = flows.get(&id).copied().unwrap_or(0.0);
+            let connection = &canvas.connections[&id];
+            let data_type = canvas.nodes.get(&connection.from_node)
+                .and_then(|node| node.get_port_by_id(&connection.from_port))
+                .map(|port| port.port_type.clone())
+                .unwrap_or_else(|| connection.data_flow_info.data_type.clone());
+            let message_size = Self::node_number(canvas, &connection.from_node, MESSAGE_SIZE_PROPERTY)
+                .map(|size| size as usize)
+                .or_else(|| self.config.message_sizes.get(&data_type).copied())
+                .unwrap_or(self.config.default_message_size)
+                .max(1);
+            
+            let stats = self.links.entry(id.clone()).or_insert_with(|| LinkStats { connection_id: id.clone(), ..Default::default() });
+            let bytes = (flow_rate * dt) as u64;
+            stats.flow_rate = flow_rate;
+            stats.peak_rate = stats.peak_rate.max(flow_rate);
+            stats.utilization = utilization.get(connection.to_node.as_str()).copied().unwrap_or(0.0);
+            stats.bytes_transferred += bytes;
+            stats.messages += bytes / message_size as u64;
+            stats.bottleneck = flow_rate > 0.0 && stats.utilization > self.config.bottleneck_threshold;
+            
+            let connection = canvas.connections.get_mut(&id).unwrap();
+            let info = &mut connection.data_flow_info;
+            info.data_type = data_type.clone();
+            info.data_size = Some(message_size);
+            info.flow_rate = Some(flow_rate as u64);
+            info.is_active = flow_rate > 0.0;
+            info.transmission_time = if flow_rate > 0.0 {
+                Duration::from_secs_f64(message_size as f64 / flow_rate)
+            } else {
+                Duration::ZERO
+            };
+            if flow_rate > 0.0 {
+                info.last_value_preview = Some(format!("{} #{} ({} B)", data_type, stats.messages, message_size));
+            }
+            
+            connection.show_data_flow = true;
+            connection.animation_speed = if max_rate > 0.0 { flow_rate / max_rate } else { 0.0 };
+            connection.is_highlighted = stats.bottleneck;
+            links.push(stats.clone());
+        }
+        
+        self.previous_flows = flows;
+        FlowSnapshot {
+            tick: self.tick,
+            elapsed: self.config.tick * self.tick as u32,
+            links,
+            nodes: {
+                node_stats.sort_by(|a, b| a.node_id.cmp(&b.node_id));
+                node_stats
+            },
+        }
+    }
+    
+    /// Run several steps and return the last snapshot
+    pub fn run(&mut self, canvas: &mut NodeCanvas, ticks: u64) -> FlowSnapshot {
+        let mut snapshot = FlowSnapshot::default();
+        for _ in 0..ticks {
+            snapshot = self.step(canvas);
+        }
+        snapshot
+    }
+    
+    /// Reset the simulation and clear the canvas' data flow information
+    pub fn reset(&mut self, canvas: &mut NodeCanvas) {
+        self.tick = 0;
+        self.rng = self.config.seed.max(1);
+        self.links.clear();
+        self.backlog.clear();
+        self.previous_flows.clear();
+        
+        for connection in canvas.connections.values_mut() {
+            let info = &mut connection.data_flow_info;
+            info.flow_rate = None;
+            info.last_value_preview = None;
+            info.is_active = false;
+            info.transmission_time = Duration::ZERO;
+            connection.show_data_flow = false;
+            connection.animation_speed = 0.0;
+            connection.is_highlighted = false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::geometry::{Color, Point};
+    use crate::component_manager::network_components::{create_ethernet_component, create_ip_component, create_tcp_component};
+    use crate::component_manager::visual_node::{DataFlowInfo, NodeConnection, VisualNode};
+    
+    fn connect(canvas: &mut NodeCanvas, from: &str, to: &str, to_port: &str) {
+        let from_port = canvas.nodes[from].get_port_by_name("upper").unwrap();
+        let connection = NodeConnection {
+            id: format!("{}->{}", from, to),
+            from_node: from.to_string(),
+            from_port: from_port.id.clone(),
+            to_node: to.to_string(),
+            to_port: canvas.nodes[to].get_port_by_name(to_port).unwrap().id.clone(),
+            connection_type: from_port.port_type.clone(),
+            color: Color::from_rgba8(0, 0, 0, 255),
+            line_width: 2.0,
+            description: String::new(),
+            data_flow_info: DataFlowInfo {
+                data_type: from_port.port_type.clone(),
+                data_size: None,
+                flow_rate: None,
+                last_value_preview: None,
+                is_active: false,
+                transmission_time: Duration::ZERO,
+            },
+            is_highlighted: false,
+            is_selected: false,
+            label: None,
+            bend_points: Vec::new(),
+            animation_speed: 1.0,
+            show_data_flow: false,
+        };
+        canvas.connections.insert(connection.id.clone(), connection);
+    }
+    
+    /// Ethernet offering 2 MB/s to an IP layer that handles 1 MB/s, feeding TCP
+    fn stack() -> NodeCanvas {
+        let mut canvas = NodeCanvas::new();
+        for (id, component, properties) in [
+            ("eth0", create_ethernet_component(), vec![(RATE_PROPERTY, "2000000"), (THROUGHPUT_PROPERTY, "4000000"), (MESSAGE_SIZE_PROPERTY, "1000")]),
+            ("ip", create_ip_component(), vec![(THROUGHPUT_PROPERTY, "1000000")]),
+            ("tcp", create_tcp_component(), vec![]),
+        ] {
+            let mut node = VisualNode::with_id(component, Point::new(0.0, 0.0), id.to_string()).unwrap();
+            for (name, value) in properties {
+                node.properties.insert(name.to_string(), value.to_string());
+            }
+            canvas.nodes.insert(id.to_string(), node);
+        }
+        connect(&mut canvas, "eth0", "ip", "link");
+        connect(&mut canvas, "ip", "tcp", "network");
+        canvas
+    }
+    
+    #[test]
+    fn test_flow_propagation_and_bottlenecks() {
+        let mut canvas = stack();
+        let config = FlowSimulationConfig { tick: Duration::from_secs(1), jitter: 0.0, ..Default::default() };
+        let mut simulator = FlowSimulator::new(config);
+        
+        let snapshot = simulator.step(&mut canvas);
+        let rates: Vec<(&str, f64)> = snapshot.links.iter().map(|link| (link.connection_id.as_str(), link.flow_rate)).collect();
+        assert_eq!(rates, vec![("eth0->ip", 2_000_000.0), ("ip->tcp", 1_000_000.0)]);
+        assert_eq!(snapshot.bottlenecks().iter().map(|link| link.connection_id.as_str()).collect::<Vec<_>>(), vec!["eth0->ip"]);
+        assert_eq!(snapshot.links[0].utilization, 2.0);
+        assert_eq!(snapshot.nodes.iter().map(|node| node.node_id.as_str()).collect::<Vec<_>>(), vec!["eth0", "ip", "tcp"]);
+        assert_eq!(snapshot.nodes[1].backlog_bytes, 1_000_000.0);
+        
+        let link = &canvas.connections["eth0->ip"];
+        assert_eq!(link.data_flow_info.flow_rate, Some(2_000_000));
+        assert_eq!(link.data_flow_info.data_size, Some(1000));
+        assert!(link.is_highlighted && link.show_data_flow);
+        assert_eq!(canvas.connections["ip->tcp"].animation_speed, 0.5);
+        assert!(!canvas.connections["ip->tcp"].is_highlighted);
+        
+        // The IP layer keeps falling behind
+        let snapshot = simulator.run(&mut canvas, 2);
+        assert_eq!(snapshot.tick, 3);
+        assert_eq!(snapshot.elapsed, Duration::from_secs(3));
+        assert_eq!(snapshot.nodes[1].backlog_bytes, 3_000_000.0);
+        assert_eq!((snapshot.links[0].bytes_transferred, snapshot.links[0].messages), (6_000_000, 6000));
+        assert_eq!(snapshot.links[1].flow_rate, 1_000_000.0);
+        
+        simulator.reset(&mut canvas);
+        let link = &canvas.connections["eth0->ip"];
+        assert_eq!(link.data_flow_info.flow_rate, None);
+        assert!(!link.is_highlighted && !link.show_data_flow);
+        let snapshot = simulator.step(&mut canvas);
+        assert_eq!((snapshot.tick, snapshot.nodes[1].backlog_bytes), (1, 1_000_000.0));
+    }
+    
+    #[test]
+    fn test_jitter_is_seeded() {
+        let run = |seed: u64| {
+            let mut canvas = stack();
+            let mut simulator = FlowSimulator::new(FlowSimulationConfig { seed, ..Default::default() });
+            simulator.run(&mut canvas, 5).nodes[0].input_rate
+        };
+        assert_eq!(run(7), run(7));
+        assert_ne!(run(7), run(8));
+        assert!((1_800_000.0..=2_200_000.0).contains(&run(7)));
+    }
+}
@@ -0,0 +1,535 @@
+// IPC components for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! Inter-process communication components.
+//!
+//! Message queues, shared memory regions and capability channels are placed on the
+//! canvas between IPC endpoints. Every endpoint and channel may declare the schema of
+//! the messages it exchanges (`Request { id: u32, len: u64 }`); the canvas linter
+//! checks that connected endpoints agree, and the kernel config fragment and C header
+//! for the channels are generated from the canvas. The components are imported into
+//! tile libraries like any other component.
+
+use std::collections::{BTreeSet, HashSet};
+use std::fmt;
+use super::{component::{Component, ComponentType, ComponentCategory, ComponentProperty, ComponentPort, PortDirection, KernelArchitecture}, ComponentLibrary};
+use super::canvas_lint::OPTIONAL_PORTS_PROPERTY;
+use super::visual_node::{NodeCanvas, VisualNode};
+
+/// Node property holding the message schema of an endpoint or channel
+pub const MESSAGE_SCHEMA_PROPERTY: &str = "message_schema";
+
+/// Node property naming a channel in generated code
+pub const CHANNEL_NAME_PROPERTY: &str = "channel_name";
+
+/// Port type of IPC endpoints
+pub const IPC_PORT_TYPE: &str = "ipc_message";
+
+/// Component ID of IPC endpoints
+pub const IPC_ENDPOINT_ID: &str = "ipc_endpoint";
+
+/// Kind of IPC channel
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IpcKind {
+    MessageQueue,
+    SharedMemory,
+    CapabilityChannel,
+}
+
+impl IpcKind {
+    /// All channel kinds
+    pub fn all() -> [IpcKind; 3] {
+        [IpcKind::MessageQueue, IpcKind::SharedMemory, IpcKind::CapabilityChannel]
+    }
+    
+    /// Component ID of the channel kind
+    pub fn component_id(&self) -> &'static str {
+        match self {
+            IpcKind::MessageQueue => "ipc_message_queue",
+            IpcKind::SharedMemory => "ipc_shared_memory",
+            IpcKind::CapabilityChannel => "ipc_capability_channel",
+        }
+    }
+    
+    /// Channel kind of a component
+    pub fn of_component(component_id: &str) -> Option<IpcKind> {
+        IpcKind::all().into_iter().find(|kind| kind.component_id() == component_id)
+    }
+    
+    /// Kernel options the channel kind needs
+    pub fn kconfig_options(&self) -> &'static [&'static str] {
+        match self {
+            IpcKind::MessageQueue => &["CONFIG_SYSVIPC", "CONFIG_POSIX_MQUEUE"],
+            IpcKind::SharedMemory => &["CONFIG_SYSVIPC", "CONFIG_SHMEM"],
+            IpcKind::CapabilityChannel => &["CONFIG_OSL_IPC_CAPABILITY"],
+        }
+    }
+}
+
+/// Field of a message schema
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageField {
+    /// Field name
+    pub name: String,
+    
+    /// Scalar type (`u8`..`u64`, `i8`..`i64`, `bool` or `cap`)
+    pub field_type: String,
+    
+    /// Array length, for `u8[16]` style fields
+    pub array_len: Option<usize>,
+}
+
+/// Schema of the messages exchanged over a channel
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageSchema {
+    /// Message name
+    pub name: String,
+    
+    /// Fields in layout order
+    pub fields: Vec<MessageField>,
+}
+
+const FIELD_TYPES: &[(&str, &str)] = &[
+    ("u8", "uint8_t"), ("u16", "uint16_t"), ("u32", "uint32_t"), ("u64", "uint64_t"),
+    ("i8", "int8_t"), ("i16", "int16_t"), ("i32", "int32_t"), ("i64", "int64_t"),
+    ("bool", "bool"), ("cap", "osl_cap_t"),
+];
+
+impl MessageSchema {
+    /// Parse a schema written as `Name { field: type, field: type[len] }`
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let text = text.trim();
+        let (name, body) = text.split_once('{').ok_or_else(|| format!("Expected '{{' in message schema '{}'", text))?;
+        let body = body.trim_end().strip_suffix('}').ok_or_else(|| format!("Expected '}}' at the end of message schema '{}'", text))?;
+        let name = name.trim();
+        if !is_identifier(name) {
+            return Err(format!("Invalid message name '{}'", name));
+        }
+        
+        let mut fields = Vec::new();
+        for field in body.split(',').map(str::trim).filter(|field| !field.is_empty()) {
+            let (field_name, field_type) = field.split_once(':').ok_or_else(|| format!("Expected 'name: type' in field '{}'", field))?;
+            let (field_name, field_type) = (field_name.trim(), field_type.trim());
+            if !is_identifier(field_name) {
+                return Err(format!("Invalid field name '{}'", field_name));
+            }
+            let (field_type, array_len) = match field_type.split_once('[') {
+                Some((element, len)) => {
+                    let len = len.strip_suffix(']').and_then(|len| len.trim().parse::<usize>().ok())
+                        .ok_or_else(|| format!("Invalid array length in field '{}'", field_name))?;
+                    (element.trim(), Some(len))
+                }
+                None => (field_type, None),
+            };
+            if !FIELD_TYPES.iter().any(|(name, _)| *name == field_type) {
+                return Err(format!("Unknown type '{}' of field '{}'", field_type, field_name));
+            }
+            if fields.iter().any(|existing: &MessageField| existing.name == field_name) {
+                return Err(format!("Duplicate field '{}'", field_name));
+            }
+            fields.push(MessageField { name: field_name.to_string(), field_type: field_type.to_string(), array_len });
+        }
+        
+        Ok(Self { name: name.to_string(), fields })
+    }
+    
+    /// Schema declared by a node, if any
+    pub fn of_node(node: &VisualNode) -> Option<Result<Self, String>> {
+        node.properties.get(MESSAGE_SCHEMA_PROPERTY)
+            .filter(|schema| !schema.trim().is_empty())
+            .map(|schema| Self::parse(schema))
+    }
+    
+    /// C struct declaration of the message
+    pub fn to_c_struct(&self, struct_name: &str) -> String {
+        let mut out = format!("struct {} {{\n", struct_name);
+        for field in &self.fields {
+            let c_type = FIELD_TYPES.iter().find(|(name, _)| *name == field.field_type).map_or("uint8_t", |(_, c_type)| c_type);
+            match field.array_len {
+                Some(len) => out.push_str(&format!("    {} {}[{}];\n", c_type, field.name, len)),
+                None => out.push_str(&format!("    {} {};\n", c_type, field.name)),
+            }
+        }
+        out.push_str("};\n");
+        out
+    }
+}
+
+impl fmt::Display for MessageSchema {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let fields: Vec<String> = self.fields.iter()
+            .map(|field| match field.array_len {
+                Some(len) => format!("{}: {}[{}]", field.name, field.field_type, len),
+                None => format!("{}: {}", field.name, field.field_type),
+            })
+            .collect();
+        write!(f, "{} {{ {} }}", self.name, fields.join(", "))
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_') && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn property(name: &str, property_type: &str, default: &str, description: &str, valid_values: Option<&[&str]>) -> ComponentProperty {
+    ComponentProperty {
+        name: name.to_string(),
+        value: default.to_string(),
+        property_type: property_type.to_string(),
+        description: description.to_string(),
+        required: false,
+        default_value: Some(default.to_string()),
+        valid_values: valid_values.map(|values| values.iter().map(|value| value.to_string()).collect()),
+    }
+}
+
+fn port(name: &str, direction: PortDirection, description: &str) -> ComponentPort {
+    ComponentPort {
+        name: name.to_string(),
+        port_type: IPC_PORT_TYPE.to_string(),
+        direction,
+        description: description.to_string(),
+    }
+}
+
+fn ipc_component(id: &str, display_name: &str, description: &str, mut properties: Vec<ComponentProperty>, ports: Vec<ComponentPort>) -> Component {
+    properties.insert(0, property(MESSAGE_SCHEMA_PROPERTY, "string", "", "Message schema, e.g. `Request { id: u32, len: u64 }`", None));
+    Component {
+        id: id.to_string(),
+        name: id.to_string(),
+        display_name: display_name.to_string(),
+        component_type: ComponentType::Custom(display_name.replace(' ', "")),
+        category: ComponentCategory::SystemServices,
+        version: "1.0.0".to_string(),
+        description: description.to_string(),
+        author: "OSland Team".to_string(),
+        source_url: None,
+        license: "MulanPSL-2.0".to_string(),
+        properties,
+        ports,
+        dependencies: Vec::new(),
+        supported_architectures: HashSet::from([
+            KernelArchitecture::Monolithic,
+            KernelArchitecture::Microkernel,
+            KernelArchitecture::Exokernel,
+            KernelArchitecture::Framekernel,
+            KernelArchitecture::Hybrid,
+        ]),
+        supported_languages: vec!["c".to_string(), "rust".to_string()],
+        implementation_files: Vec::new(),
+        build_commands: Vec::new(),
+        initialization_code: String::new(),
+    }
+}
+
+fn channel_ports() -> Vec<ComponentPort> {
+    vec![
+        port("send", PortDirection::Input, "Messages from the sending endpoint"),
+        port("receive", PortDirection::Output, "Messages to the receiving endpoint"),
+    ]
+}
+
+/// Create an IPC endpoint component
+pub fn create_ipc_endpoint_component() -> Component {
+    ipc_component(
+        IPC_ENDPOINT_ID,
+        "IPC Endpoint",
+        "Typed endpoint through which a component sends or receives messages",
+        vec![
+            property("role", "string", "client", "Endpoint role", Some(&["client", "server"])),
+            property(OPTIONAL_PORTS_PROPERTY, "string", "rx", "Ports that may stay unconnected", None),
+        ],
+        vec![
+            port("tx", PortDirection::Output, "Messages sent by the endpoint"),
+            port("rx", PortDirection::Input, "Messages received by the endpoint"),
+        ],
+    )
+}
+
+/// Create a message queue component
+pub fn create_message_queue_component() -> Component {
+    ipc_component(
+        IpcKind::MessageQueue.component_id(),
+        "Message Queue",
+        "Bounded queue of messages copied between endpoints",
+        vec![
+            property(CHANNEL_NAME_PROPERTY, "string", "queue", "Channel name in generated code", None),
+            property("depth", "int", "16", "Maximum number of queued messages", None),
+            property("blocking", "bool", "true", "Block senders when the queue is full", Some(&["true", "false"])),
+        ],
+        channel_ports(),
+    )
+}
+
+/// Create a shared memory region component
+pub fn create_shared_memory_component() -> Component {
+    ipc_component(
+        IpcKind::SharedMemory.component_id(),
+        "Shared Memory",
+        "Memory region mapped into both endpoints",
+        vec![
+            property(CHANNEL_NAME_PROPERTY, "string", "shm", "Channel name in generated code", None),
+            property("size", "int", "4096", "Region size in bytes", None),
+            property("permissions", "string", "rw", "Mapping permissions of the receiver", Some(&["r", "rw"])),
+        ],
+        channel_ports(),
+    )
+}
+
+/// Create a capability channel component
+pub fn create_capability_channel_component() -> Component {
+    ipc_component(
+        IpcKind::CapabilityChannel.component_id(),
+        "Capability Channel",
+        "Synchronous channel that transfers messages and capabilities",
+        vec![
+            property(CHANNEL_NAME_PROPERTY, "string", "endpoint", "Channel name in generated code", None),
+            property("rights", "string", "send,receive,grant", "Rights granted with the channel capability", None),
+        ],
+        channel_ports(),
+    )
+}
+
+/// Create a component library holding the IPC components
+pub fn create_ipc_component_library() -> ComponentLibrary {
+    let mut library = ComponentLibrary::new();
+    extend_with_ipc_components(&mut library);
+    library
+}
+
+/// Add the IPC components to an existing library
+pub fn extend_with_ipc_components(library: &mut ComponentLibrary) {
+    for component in [
+        create_ipc_endpoint_component(),
+        create_message_queue_component(),
+        create_shared_memory_component(),
+        create_capability_channel_component(),
+    ] {
+        // Components already present are kept
+        let _ = library.add_component(component);
+    }
+}
+
+/// Schema disagreement between a channel and its endpoints
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaMismatch {
+    /// Channel node
+    pub channel_id: String,
+    
+    /// Nodes involved, channel first
+    pub node_ids: Vec<String>,
+    
+    /// Connections involved
+    pub connection_ids: Vec<String>,
+    
+    /// Description of the mismatch
+    pub message: String,
+}
+
+/// Check that every channel and its connected endpoints declare the same schema
+pub fn check_message_schemas(canvas: &NodeCanvas) -> Vec<SchemaMismatch> {
+    let mut mismatches = Vec::new();
+    let mut channels: Vec<&VisualNode> = canvas.nodes.values().filter(|node| IpcKind::of_component(&node.component_id).is_some()).collect();
+    channels.sort_by(|a, b| a.id.cmp(&b.id));
+    
+    for channel in channels {
+        let mut connections: Vec<_> = canvas.connections.values()
+            .filter(|conn| conn.from_node == channel.id || conn.to_node == channel.id)
+            .collect();
+        connections.sort_by(|a, b| a.id.cmp(&b.id));
+        
+        // Declared schemas, channel first; nodes without a schema accept any message
+        let mut declared: Vec<(&VisualNode, MessageSchema, Option<&str>)> = Vec::new();
+        let members = std::iter::once((channel, None))
+            .chain(connections.iter().filter_map(|conn| {
+                let other = if conn.from_node == channel.id { &conn.to_node } else { &conn.from_node };
+                canvas.nodes.get(other).map(|node| (node, Some(conn.id.as_str())))
+            }));
+        for (node, connection_id) in members {
+            match MessageSchema::of_node(node) {
+                Some(Ok(schema)) => declared.push((node, schema, connection_id)),
+                Some(Err(e)) => mismatches.push(SchemaMismatch {
+                    channel_id: channel.id.clone(),
+                    node_ids: vec![node.id.clone()],
+                    connection_ids: connection_id.map(|id| vec![id.to_string()]).unwrap_or_default(),
+                    message: format!("Invalid message schema on '{}': {}", node.component.display_name, e),
+                }),
+                None => {}
+            }
+        }
+        
+        let (reference_node, reference, _) = match declared.first() {
+            Some(first) => first,
+            None => continue,
+        };
+        for (node, schema, connection_id) in declared.iter().skip(1) {
+            if schema != reference {
+                mismatches.push(SchemaMismatch {
+                    channel_id: channel.id.clone(),
+                    node_ids: vec![reference_node.id.clone(), node.id.clone()],
+                    connection_ids: connection_id.map(|id| vec![id.to_string()]).unwrap_or_default(),
+                    message: format!("'{}' expects `{}` but '{}' uses `{}`",
+                        reference_node.component.display_name, reference, node.component.display_name, schema),
+                });
+            }
+        }
+    }
+    
+    mismatches
+}
+
+/// Kernel config fragment and C header for the channels of a canvas
+#[derive(Debug, Clone, PartialEq)]
+pub struct IpcArtifacts {
+    /// Kernel config fragment enabling the IPC mechanisms in use
+    pub kconfig: String,
+    
+    /// C header declaring the channels and their messages
+    pub header: String,
+}
+
+/// Generate the kernel config fragment and channel declarations of a canvas
+pub fn generate_ipc_stubs(canvas: &NodeCanvas) -> Result<IpcArtifacts, String> {
+    if let Some(mismatch) = check_message_schemas(canvas).first() {
+        return Err(mismatch.message.clone());
+    }
+    
+    let mut channels: Vec<(&VisualNode, IpcKind)> = canvas.nodes.values()
+        .filter_map(|node| IpcKind::of_component(&node.component_id).map(|kind| (node, kind)))
+        .collect();
+    channels.sort_by(|a, b| a.0.id.cmp(&b.0.id));
+    
+    let mut options = BTreeSet::new();
+    let mut names = HashSet::new();
+    let mut header = "/* IPC channels generated by OSland */\n#ifndef OSLAND_IPC_CHANNELS_H\n#define OSLAND_IPC_CHANNELS_H\n\n#include <stdbool.h>\n#include <stdint.h>\n\ntypedef uint64_t osl_cap_t;\nstruct osl_ipc_channel;\n".to_string();
+    for (node, kind) in channels {
+        for option in kind.kconfig_options() {
+            options.insert(*option);
+        }
+        
+        let name = node.properties.get(CHANNEL_NAME_PROPERTY).cloned().unwrap_or_else(|| kind.component_id().to_string());
+        if !is_identifier(&name) {
+            return Err(format!("Invalid channel name '{}'", name));
+        }
+        if !names.insert(name.clone()) {
+            return Err(format!("Duplicate channel name '{}'", name));
+        }
+        let upper = name.to_uppercase();
+        
+        header.push_str(&format!("\n/* {} '{}' */\n", node.component.display_name.replace("*/", "* /"), name));
+        let schema = MessageSchema::of_node(node).and_then(Result::ok).or_else(|| {
+            // Channels without their own schema take the one declared by their endpoints
+            canvas.get_connections_for_node(&node.id).iter()
+                .filter_map(|conn| canvas.nodes.get(if conn.from_node == node.id { &conn.to_node } else { &conn.from_node }))
+                .find_map(|endpoint| MessageSchema::of_node(endpoint).and_then(Result::ok))
+        });
+        if let Some(schema) = &schema {
+            header.push_str(&schema.to_c_struct(&format!("{}_msg", name)));
+            header.push_str(&format!("#define {}_MSG_SIZE sizeof(struct {}_msg)\n", upper, name));
+        }
+        let numeric = |property: &str| node.properties.get(property).and_then(|value| value.parse::<u64>().ok());
+        match kind {
+            IpcKind::MessageQueue => {
+                header.push_str(&format!("#define {}_DEPTH {}\n", upper, numeric("depth").unwrap_or(16)));
+            }
+            IpcKind::SharedMemory => {
+                header.push_str(&format!("#define {}_SIZE {}\n", upper, numeric("size").unwrap_or(4096)));
+            }
+            IpcKind::CapabilityChannel => {
+                let mut rights = Vec::new();
+                for right in node.properties.get("rights").map(String::as_str).unwrap_or_default().split(',').map(str::trim).filter(|right| !right.is_empty()) {
+                    if !is_identifier(right) {
+                        return Err(format!("Invalid right '{}' of channel '{}'", right, name));
+                    }
+                    rights.push(right);
+                }
+                header.push_str(&format!("#define {}_RIGHTS \"{}\"\n", upper, rights.join(",")));
+            }
+        }
+        header.push_str(&format!("extern struct osl_ipc_channel {}_channel;\n", name));
+    }
+    header.push_str("\n#endif\n");
+    
+    let kconfig = std::iter::once("# IPC mechanisms generated by OSland\n".to_string())
+        .chain(options.iter().map(|option| format!("{}=y\n", option)))
+        .collect();
+    Ok(IpcArtifacts { kconfig, header })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use crate::core::geometry::{Color, Point};
+    use crate::component_manager::visual_node::{DataFlowInfo, NodeConnection};
+    
+    fn connect(canvas: &mut NodeCanvas, from: &str, from_port: &str, to: &str, to_port: &str) {
+        let connection = NodeConnection {
+            id: format!("{}->{}", from, to),
+            from_node: from.to_string(),
+            from_port: canvas.nodes[from].get_port_by_name(from_port).unwrap().id.clone(),
+            to_node: to.to_string(),
+            to_port: canvas.nodes[to].get_port_by_name(to_port).unwrap().id.clone(),
+            connection_type: IPC_PORT_TYPE.to_string(),
+            color: Color::from_rgba8(0, 0, 0, 255),
+            line_width: 2.0,
+            description: String::new(),
+            data_flow_info: DataFlowInfo {
+                data_type: IPC_PORT_TYPE.to_string(),
+                data_size: None,
+                flow_rate: None,
+                last_value_preview: None,
+                is_active: false,
+                transmission_time: Duration::ZERO,
+            },
+            is_highlighted: false,
+            is_selected: false,
+            label: None,
+            bend_points: Vec::new(),
+            animation_speed: 1.0,
+            show_data_flow: false,
+        };
+        canvas.connections.insert(connection.id.clone(), connection);
+    }
+    
+    #[test]
+    fn test_schema_agreement_and_stubs() {
+        let mut canvas = NodeCanvas::new();
+        for (id, component) in [("client", create_ipc_endpoint_component()), ("queue", create_message_queue_component()), ("server", create_ipc_endpoint_component())] {
+            let node = VisualNode::with_id(component, Point::new(0.0, 0.0), id.to_string()).unwrap();
+            canvas.nodes.insert(id.to_string(), node);
+        }
+        connect(&mut canvas, "client", "tx", "queue", "send");
+        connect(&mut canvas, "queue", "receive", "server", "rx");
+        canvas.nodes.get_mut("client").unwrap().properties.insert(MESSAGE_SCHEMA_PROPERTY.to_string(), "Request { id: u32, data: u8[16] }".to_string());
+        canvas.nodes.get_mut("server").unwrap().properties.insert(MESSAGE_SCHEMA_PROPERTY.to_string(), "Request { id: u64 }".to_string());
+        
+        let mismatches = check_message_schemas(&canvas);
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].node_ids, vec!["client".to_string(), "server".to_string()]);
+        assert!(generate_ipc_stubs(&canvas).is_err());
+        
+        canvas.nodes.get_mut("server").unwrap().properties.insert(MESSAGE_SCHEMA_PROPERTY.to_string(), "Request {id:u32, data:u8[16]}".to_string());
+        assert!(check_message_schemas(&canvas).is_empty());
+        let artifacts = generate_ipc_stubs(&canvas).unwrap();
+        assert!(artifacts.kconfig.contains("CONFIG_POSIX_MQUEUE=y"));
+        assert!(artifacts.header.contains("struct queue_msg {\n    uint32_t id;\n    uint8_t data[16];\n};"));
+        assert!(artifacts.header.contains("#define QUEUE_DEPTH 16"));
+    }
+    
+    #[test]
+    fn test_capability_rights_are_validated() {
+        let mut canvas = NodeCanvas::new();
+        let node = VisualNode::with_id(create_capability_channel_component(), Point::new(0.0, 0.0), "cap".to_string()).unwrap();
+        canvas.nodes.insert("cap".to_string(), node);
+        
+        canvas.nodes.get_mut("cap").unwrap().properties.insert("rights".to_string(), " send, receive ,".to_string());
+        let artifacts = generate_ipc_stubs(&canvas).unwrap();
+        assert!(artifacts.header.contains("_RIGHTS \"send,receive\"\n"));
+        
+        canvas.nodes.get_mut("cap").unwrap().properties.insert("rights".to_string(), "send\"\n#define EVIL 1".to_string());
+        assert!(generate_ipc_stubs(&canvas).is_err());
+    }
+}
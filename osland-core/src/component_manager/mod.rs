@@ -7,6 +7,12 @@ pub mod visual_node;
 pub mod property_mapper;
 pub mod version_manager;
 pub mod cuda_components;
+pub mod canvas_lint;
+pub mod flow_simulation;
+pub mod spatial_index;
+pub mod ipc_components;
+pub mod driver_wizard;
+pub mod network_components;
 
 // Re-export core components
 pub use component::*;
@@ -14,6 +20,12 @@ pub use visual_node::*;
 pub use property_mapper::*;
 pub use version_manager::*;
 pub use cuda_components::{create_cuda_component_library, extend_with_cuda_components};
+pub use canvas_lint::*;
+pub use flow_simulation::*;
+pub use spatial_index::*;
+pub use ipc_components::{create_ipc_component_library, extend_with_ipc_components};
+pub use driver_wizard::{DriverWizard, DriverSpec, DriverSkeleton, DriverBus, DriverLanguage, DeviceMatch, WizardStep};
+pub use network_components::{create_network_component_library, extend_with_network_components};
 
 // Component Manager error types
 #[derive(thiserror::Error, Debug)]
@@ -0,0 +1,549 @@
+// Network stack components for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! Network stack components.
+//!
+//! Ethernet, loopback, IP, TCP, UDP and socket API components are composed on the
+//! canvas layer by layer. Port types encode the protocol layer (`net.l2` between a link
+//! and IP, `net.l3` between IP and a transport, `net.l4` between a transport and the
+//! socket API), so the canvas only accepts connections between adjacent layers. The
+//! linter additionally checks that every layer has the layer it depends on below it,
+//! and the kernel config and initialization order are generated from the stack.
+
+use std::collections::{BTreeSet, HashSet};
+use super::{component::{Component, ComponentType, ComponentCategory, ComponentProperty, ComponentPort, PortDirection, KernelArchitecture}, ComponentLibrary};
+use super::canvas_lint::OPTIONAL_PORTS_PROPERTY;
+use super::visual_node::{NodeCanvas, VisualNode};
+
+/// Port type of the device interface a NIC driver exposes
+pub const DEVICE_PORT_TYPE: &str = "DeviceInterface";
+
+/// Protocol layer of a network component
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum NetworkLayer {
+    Link,
+    Network,
+    Transport,
+    Socket,
+}
+
+impl NetworkLayer {
+    /// Port type carrying this layer's service to the layer above
+    pub fn port_type(&self) -> Option<&'static str> {
+        match self {
+            NetworkLayer::Link => Some("net.l2"),
+            NetworkLayer::Network => Some("net.l3"),
+            NetworkLayer::Transport => Some("net.l4"),
+            NetworkLayer::Socket => None,
+        }
+    }
+    
+    /// Layer directly below
+    pub fn below(&self) -> Option<NetworkLayer> {
+        match self {
+            NetworkLayer::Link => None,
+            NetworkLayer::Network => Some(NetworkLayer::Link),
+            NetworkLayer::Transport => Some(NetworkLayer::Network),
+            NetworkLayer::Socket => Some(NetworkLayer::Transport),
+        }
+    }
+    
+    /// Name used in messages
+    pub fn name(&self) -> &'static str {
+        match self {
+            NetworkLayer::Link => "link layer",
+            NetworkLayer::Network => "network layer",
+            NetworkLayer::Transport => "transport layer",
+            NetworkLayer::Socket => "socket API",
+        }
+    }
+}
+
+/// Kind of network component
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NetworkKind {
+    Ethernet,
+    Loopback,
+    Ip,
+    Tcp,
+    Udp,
+    SocketApi,
+}
+
+impl NetworkKind {
+    /// All network component kinds
+    pub fn all() -> [NetworkKind; 6] {
+        [NetworkKind::Ethernet, NetworkKind::Loopback, NetworkKind::Ip, NetworkKind::Tcp, NetworkKind::Udp, NetworkKind::SocketApi]
+    }
+    
+    /// Component ID of the kind
+    pub fn component_id(&self) -> &'static str {
+        match self {
+            NetworkKind::Ethernet => "net_ethernet",
+            NetworkKind::Loopback => "net_loopback",
+            NetworkKind::Ip => "net_ip",
+            NetworkKind::Tcp => "net_tcp",
+            NetworkKind::Udp => "net_udp",
+            NetworkKind::SocketApi => "net_socket",
+        }
+    }
+    
+    /// Network kind of a component
+    pub fn of_component(component_id: &str) -> Option<NetworkKind> {
+        NetworkKind::all().into_iter().find(|kind| kind.component_id() == component_id)
+    }
+    
+    /// Protocol layer
+    pub fn layer(&self) -> NetworkLayer {
+        match self {
+            NetworkKind::Ethernet | NetworkKind::Loopback => NetworkLayer::Link,
+            NetworkKind::Ip => NetworkLayer::Network,
+            NetworkKind::Tcp | NetworkKind::Udp => NetworkLayer::Transport,
+            NetworkKind::SocketApi => NetworkLayer::Socket,
+        }
+    }
+}
+
+fn property(name: &str, default: &str, description: &str, valid_values: Option<&[&str]>) -> ComponentProperty {
+    ComponentProperty {
+        name: name.to_string(),
+        value: default.to_string(),
+        property_type: "string".to_string(),
+        description: description.to_string(),
+        required: false,
+        default_value: Some(default.to_string()),
+        valid_values: valid_values.map(|values| values.iter().map(|value| value.to_string()).collect()),
+    }
+}
+
+fn port(name: &str, port_type: &str, direction: PortDirection, description: &str) -> ComponentPort {
+    ComponentPort {
+        name: name.to_string(),
+        port_type: port_type.to_string(),
+        direction,
+        description: description.to_string(),
+    }
+}
+
+/// Ports of a layer: the service of the layer below as input, its own service as output
+fn layer_ports(layer: NetworkLayer, lower_name: &str) -> Vec<ComponentPort> {
+    let mut ports = Vec::new();
+    if let Some(lower) = layer.below().and_then(|below| below.port_type()) {
+        ports.push(port(lower_name, lower, PortDirection::Input, &format!("Service of the {} below", layer.below().map_or("", |below| below.name()))));
+    }
+    if let Some(upper) = layer.port_type() {
+        ports.push(port("upper", upper, PortDirection::Output, &format!("Service offered to the {} above", match layer {
+            NetworkLayer::Link => NetworkLayer::Network.name(),
+            NetworkLayer::Network => NetworkLayer::Transport.name(),
+            _ => NetworkLayer::Socket.name(),
+        })));
+    }
+    ports
+}
+
+fn network_component(kind: NetworkKind, display_name: &str, description: &str, properties: Vec<ComponentProperty>, ports: Vec<ComponentPort>) -> Component {
+    Component {
+        id: kind.component_id().to_string(),
+        name: kind.component_id().to_string(),
+        display_name: display_name.to_string(),
+        component_type: ComponentType::NetworkStack,
+        category: ComponentCategory::Networking,
+        version: "1.0.0".to_string(),
+        description: description.to_string(),
+        author: "OSland Team".to_string(),
+        source_url: None,
+        license: "MulanPSL-2.0".to_string(),
+        properties,
+        ports,
+        dependencies: Vec::new(),
+        supported_architectures: HashSet::from([
+            KernelArchitecture::Monolithic,
+            KernelArchitecture::Microkernel,
+            KernelArchitecture::Hybrid,
+        ]),
+        supported_languages: vec!["c".to_string(), "rust".to_string()],
+        implementation_files: Vec::new(),
+        build_commands: Vec::new(),
+        initialization_code: String::new(),
+    }
+}
+
+/// Create the Ethernet link layer component
+pub fn create_ethernet_component() -> Component {
+    let mut ports = vec![port("device", DEVICE_PORT_TYPE, PortDirection::Input, "NIC driver carrying the frames")];
+    ports.extend(layer_ports(NetworkLayer::Link, ""));
+    network_component(
+        NetworkKind::Ethernet,
+        "Ethernet",
+        "Ethernet framing on top of a NIC driver",
+        vec![
+            property("mtu", "1500", "Maximum transmission unit in bytes", None),
+            property(OPTIONAL_PORTS_PROPERTY, "device", "Ports that may stay unconnected", None),
+        ],
+        ports,
+    )
+}
+
+/// Create the loopback link layer component
+pub fn create_loopback_component() -> Component {
+    network_component(NetworkKind::Loopback, "Loopback", "Loopback interface for local traffic", Vec::new(), layer_ports(NetworkLayer::Link, ""))
+}
+
+/// Create the IP network layer component
+pub fn create_ip_component() -> Component {
+    network_component(
+        NetworkKind::Ip,
+        "IP",
+        "Internet Protocol routing and fragmentation",
+        vec![
+            property("version", "4", "IP versions", Some(&["4", "6", "dual"])),
+            property("forwarding", "false", "Forward packets between interfaces", Some(&["true", "false"])),
+        ],
+        layer_ports(NetworkLayer::Network, "link"),
+    )
+}
+
+/// Create the TCP transport component
+pub fn create_tcp_component() -> Component {
+    network_component(
+        NetworkKind::Tcp,
+        "TCP",
+        "Reliable byte streams",
+        vec![property("congestion_control", "cubic", "Default congestion control algorithm", Some(&["cubic", "reno", "bbr"]))],
+        layer_ports(NetworkLayer::Transport, "network"),
+    )
+}
+
+/// Create the UDP transport component
+pub fn create_udp_component() -> Component {
+    network_component(NetworkKind::Udp, "UDP", "Unreliable datagrams", Vec::new(), layer_ports(NetworkLayer::Transport, "network"))
+}
+
+/// Create the socket API component
+pub fn create_socket_api_component() -> Component {
+    network_component(
+        NetworkKind::SocketApi,
+        "Socket API",
+        "BSD socket system calls for user space",
+        vec![property("families", "inet", "Address families offered, comma separated (inet, inet6, unix, packet)", None)],
+        layer_ports(NetworkLayer::Socket, "transport"),
+    )
+}
+
+/// Create a library with the network components
+pub fn create_network_component_library() -> ComponentLibrary {
+    let mut library = ComponentLibrary::new();
+    extend_with_network_components(&mut library);
+    library
+}
+
+/// Add the network components to an existing library
+pub fn extend_with_network_components(library: &mut ComponentLibrary) {
+    for component in [
+        create_ethernet_component(),
+        create_loopback_component(),
+        create_ip_component(),
+        create_tcp_component(),
+        create_udp_component(),
+        create_socket_api_component(),
+    ] {
+        // Components already present are kept
+        let _ = library.add_component(component);
+    }
+}
+
+/// Illegal composition of network layers
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayeringIssue {
+    /// Nodes involved
+    pub node_ids: Vec<String>,
+    
+    /// Connections involved
+    pub connection_ids: Vec<String>,
+    
+    /// Description of the issue
+    pub message: String,
+}
+
+/// Network nodes of a canvas, ordered by ID
+fn network_nodes(canvas: &NodeCanvas) -> Vec<(&VisualNode, NetworkKind)> {
+    let mut nodes: Vec<(&VisualNode, NetworkKind)> = canvas.nodes.values()
+        .filter_map(|node| NetworkKind::of_component(&node.component_id).map(|kind| (node, kind)))
+        .collect();
+    nodes.sort_by(|a, b| a.0.id.cmp(&b.0.id));
+    nodes
+}
+
+/// Network nodes connected into a node, with the connection IDs
+fn lower_nodes<'a>(canvas: &'a NodeCanvas, node_id: &str) -> Vec<(&'a VisualNode, NetworkKind, &'a str)> {
+    let mut lower: Vec<_> = canvas.connections.values()
+        .filter(|conn| conn.to_node == node_id)
+        .filter_map(|conn| {
+            let node = canvas.nodes.get(&conn.from_node)?;
+            NetworkKind::of_component(&node.component_id).map(|kind| (node, kind, conn.id.as_str()))
+        })
+        .collect();
+    lower.sort_by(|a, b| a.2.cmp(b.2));
+    lower
+}
+
+/// IP versions enabled by an IP node
+fn ip_versions(node: &VisualNode) -> &'static [u8] {
+    match node.properties.get("version").map(|version| version.as_str()) {
+        Some("6") => &[6],
+        Some("dual") => &[4, 6],
+        _ => &[4],
+    }
+}
+
+/// Address families offered by a socket API node
+fn socket_families(node: &VisualNode) -> Vec<String> {
+    node.properties.get("families").map_or("inet", |families| families.as_str())
+        .split(',').map(|family| family.trim().to_lowercase()).filter(|family| !family.is_empty()).collect()
+}
+
+/// Check that the network layers of a canvas are composed legally
+pub fn check_network_stack(canvas: &NodeCanvas) -> Vec<LayeringIssue> {
+    let mut issues = Vec::new();
+    
+    for (node, kind) in network_nodes(canvas) {
+        let layer = kind.layer();
+        let lower = lower_nodes(canvas, &node.id);
+        
+        // Connections that skip or invert layers (possible in hand-edited canvas files)
+        for (other, other_kind, connection_id) in &lower {
+            if Some(other_kind.layer()) != layer.below() {
+                issues.push(LayeringIssue {
+                    node_ids: vec![other.id.clone(), node.id.clone()],
+                    connection_ids: vec![connection_id.to_string()],
+                    message: format!("'{}' ({}) cannot sit directly on '{}' ({})",
+                        node.component.display_name, layer.name(), other.component.display_name, other_kind.layer().name()),
+                });
+            }
+        }
+        
+        if let Some(below) = layer.below() {
+            if !lower.iter().any(|(_, other_kind, _)| other_kind.layer() == below) {
+                issues.push(LayeringIssue {
+                    node_ids: vec![node.id.clone()],
+                    connection_ids: Vec::new(),
+                    message: format!("'{}' has no {} below it", node.component.display_name, below.name()),
+                });
+            }
+        }
+        
+        if kind == NetworkKind::SocketApi {
+            let ip_below: Vec<&VisualNode> = lower.iter()
+                .flat_map(|(transport, _, _)| lower_nodes(canvas, &transport.id))
+                .filter(|(_, kind, _)| *kind == NetworkKind::Ip)
+                .map(|(ip, _, _)| ip)
+                .collect();
+            for family in socket_families(node) {
+                let version = match family.as_str() {
+                    "inet" => 4,
+                    "inet6" => 6,
+                    "unix" | "packet" => continue,
+                    other => {
+                        issues.push(LayeringIssue {
+                            node_ids: vec![node.id.clone()],
+                            connection_ids: Vec::new(),
+                            message: format!("Unknown address family '{}' on '{}'", other, node.component.display_name),
+                        });
+                        continue;
+                    }
+                };
+                if !ip_below.is_empty() && !ip_below.iter().any(|ip| ip_versions(ip).contains(&version)) {
+                    issues.push(LayeringIssue {
+                        node_ids: vec![node.id.clone()],
+                        connection_ids: Vec::new(),
+                        message: format!("'{}' offers {} sockets but no IPv{} layer is below it", node.component.display_name, family, version),
+                    });
+                }
+            }
+        }
+    }
+    
+    issues
+}
+
+/// Step of the network initialization sequence
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetworkInitStep {
+    /// Node initialized
+    pub node_id: String,
+    
+    /// Initialization function called for the node
+    pub function: String,
+}
+
+/// Kernel config fragment and initialization sequence of a network stack
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetworkArtifacts {
+    /// Kernel config fragment
+    pub kconfig: String,
+    
+    /// Nodes in initialization order, lower layers first
+    pub init_order: Vec<NetworkInitStep>,
+    
+    /// C source calling the initialization functions in order
+    pub init_source: String,
+}
+
+/// Generate the kernel config and initialization order of the network stack of a canvas
+pub fn generate_network_config(canvas: &NodeCanvas) -> Result<NetworkArtifacts, String> {
+    if let Some(issue) = check_network_stack(canvas).first() {
+        return Err(issue.message.clone());
+    }
+    let nodes = network_nodes(canvas);
+    if nodes.is_empty() {
+        return Err("The canvas has no network components".to_string());
+    }
+    
+    let mut options: BTreeSet<&str> = BTreeSet::new();
+    let mut enable = |option: &'static str| { options.insert(option); };
+    enable("CONFIG_NET");
+    for (node, kind) in &nodes {
+        match kind {
+            NetworkKind::Ethernet => {
+                enable("CONFIG_NETDEVICES");
+                enable("CONFIG_ETHERNET");
+            }
+            NetworkKind::Loopback => {}
+            NetworkKind::Ip => {
+                enable("CONFIG_INET");
+                if ip_versions(node).contains(&6) {
+                    enable("CONFIG_IPV6");
+                }
+                if node.properties.get("forwarding").map_or(false, |value| value == "true") {
+                    enable("CONFIG_IP_ADVANCED_ROUTER");
+                }
+            }
+            NetworkKind::Tcp => match node.properties.get("congestion_control").map(|value| value.as_str()) {
+                Some("bbr") => {
+                    enable("CONFIG_TCP_CONG_ADVANCED");
+                    enable("CONFIG_TCP_CONG_BBR");
+                    enable("CONFIG_DEFAULT_BBR");
+                }
+                Some("reno") => {
+                    enable("CONFIG_TCP_CONG_ADVANCED");
+                    enable("CONFIG_DEFAULT_RENO");
+                }
+                _ => {
+                    enable("CONFIG_TCP_CONG_CUBIC");
+                    enable("CONFIG_DEFAULT_CUBIC");
+                }
+            },
+            NetworkKind::Udp => {}
+            NetworkKind::SocketApi => {
+                for family in socket_families(node) {
+                    match family.as_str() {
+                        "unix" => enable("CONFIG_UNIX"),
+                        "packet" => enable("CONFIG_PACKET"),
+                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+    let kconfig = std::iter::once("# Network stack generated by OSland\n".to_string())
+        .chain(options.iter().map(|option| format!("{}=y\n", option)))
+        .collect();
+    
+    // Lower layers first; within a layer, by node ID
+    let mut ordered: Vec<(NetworkLayer, &VisualNode, NetworkKind)> = nodes.iter().map(|(node, kind)| (kind.layer(), *node, *kind)).collect();
+    ordered.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.id.cmp(&b.1.id)));
+    let init_order: Vec<NetworkInitStep> = ordered.iter().map(|(_, node, kind)| {
+        let suffix: String = node.id.chars().map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' }).collect();
+        let function = format!("osl_{}_init_{}", kind.component_id(), suffix);
+        NetworkInitStep { node_id: node.id.clone(), function }
+    }).collect();
+    
+    let mut init_source = "/* Network stack initialization generated by OSland */\n\n".to_string();
+    for step in &init_order {
+        init_source.push_str(&format!("extern int {}(void);\n", step.function));
+    }
+    init_source.push_str("\nint osl_net_init(void)\n{\n\tint ret;\n\n");
+    for step in &init_order {
+        init_source.push_str(&format!("\tret = {}();\n\tif (ret)\n\t\treturn ret;\n", step.function));
+    }
+    init_source.push_str("\treturn 0;\n}\n");
+    
+    Ok(NetworkArtifacts { kconfig, init_order, init_source })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use crate::core::geometry::{Color, Point};
+    use crate::component_manager::visual_node::{ConnectionValidationResult, DataFlowInfo, NodeConnection};
+    
+    fn connect(canvas: &mut NodeCanvas, from: &str, to: &str, to_port: &str) {
+        let from_port = canvas.nodes[from].get_port_by_name("upper").unwrap();
+        let connection = NodeConnection {
+            id: format!("{}->{}", from, to),
+            from_node: from.to_string(),
+            from_port: from_port.id.clone(),
+            to_node: to.to_string(),
+            to_port: canvas.nodes[to].get_port_by_name(to_port).unwrap().id.clone(),
+            connection_type: from_port.port_type.clone(),
+            color: Color::from_rgba8(0, 0, 0, 255),
+            line_width: 2.0,
+            description: String::new(),
+            data_flow_info: DataFlowInfo {
+                data_type: from_port.port_type.clone(),
+                data_size: None,
+                flow_rate: None,
+                last_value_preview: None,
+                is_active: false,
+                transmission_time: Duration::ZERO,
+            },
+            is_highlighted: false,
+            is_selected: false,
+            label: None,
+            bend_points: Vec::new(),
+            animation_speed: 1.0,
+            show_data_flow: false,
+        };
+        canvas.connections.insert(connection.id.clone(), connection);
+    }
+    
+    #[test]
+    fn test_stack_layering_and_config() {
+        let mut canvas = NodeCanvas::new();
+        for (id, component) in [
+            ("eth0", create_ethernet_component()),
+            ("ip", create_ip_component()),
+            ("tcp", create_tcp_component()),
+            ("udp", create_udp_component()),
+            ("sockets", create_socket_api_component()),
+        ] {
+            let node = VisualNode::with_id(component, Point::new(0.0, 0.0), id.to_string()).unwrap();
+            canvas.nodes.insert(id.to_string(), node);
+        }
+        // TCP cannot be wired directly onto Ethernet
+        let result = canvas.validate_connection("eth0", &canvas.nodes["eth0"].get_port_by_name("upper").unwrap().id,
+            "tcp", &canvas.nodes["tcp"].get_port_by_name("network").unwrap().id);
+        assert!(matches!(result, ConnectionValidationResult::PortTypeMismatch));
+        
+        connect(&mut canvas, "eth0", "ip", "link");
+        connect(&mut canvas, "ip", "tcp", "network");
+        connect(&mut canvas, "tcp", "sockets", "transport");
+        let messages: Vec<String> = check_network_stack(&canvas).into_iter().map(|issue| issue.message).collect();
+        assert_eq!(messages, vec!["'UDP' has no network layer below it"]);
+        
+        connect(&mut canvas, "ip", "udp", "network");
+        canvas.nodes.get_mut("sockets").unwrap().properties.insert("families".to_string(), "inet,inet6,unix".to_string());
+        let messages: Vec<String> = check_network_stack(&canvas).into_iter().map(|issue| issue.message).collect();
+        assert_eq!(messages, vec!["'Socket API' offers inet6 sockets but no IPv6 layer is below it"]);
+        
+        canvas.nodes.get_mut("ip").unwrap().properties.insert("version".to_string(), "dual".to_string());
+        let artifacts = generate_network_config(&canvas).unwrap();
+        assert!(artifacts.kconfig.contains("CONFIG_INET=y\nCONFIG_IPV6=y\nCONFIG_NET=y\nCONFIG_NETDEVICES=y\n"));
+        assert!(artifacts.kconfig.contains("CONFIG_UNIX=y\n"));
+        let order: Vec<&str> = artifacts.init_order.iter().map(|step| step.node_id.as_str()).collect();
+        assert_eq!(order, vec!["eth0", "ip", "tcp", "udp", "sockets"]);
+        assert!(artifacts.init_source.contains("\tret = osl_net_ethernet_init_eth0();\n"));
+    }
+}
@@ -0,0 +1,281 @@
+// Spatial index for node canvases in OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+use std::collections::{HashMap, HashSet};
+use crate::core::geometry::Rect;
+
+/// Default edge length of a grid cell in canvas units
+pub const DEFAULT_GRID_CELL_SIZE: f64 = 256.0;
+
+/// Axis-aligned bounding box in canvas coordinates
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CanvasBounds {
+    pub min_x: f64,
+    pub min_y: f64,
+    pub max_x: f64,
+    pub max_y: f64,
+}
+
+impl CanvasBounds {
+    /// Create bounds from an origin and a size
+    pub fn new(x: f64, y: f64, width: f64, height: f64) -> Self {
+        Self {
+            min_x: x.min(x + width),
+            min_y: y.min(y + height),
+            max_x: x.max(x + width),
+            max_y: y.max(y + height),
+        }
+    }
+    
+    /// Create bounds covering a rectangle
+    pub fn from_rect(rect: Rect) -> Self {
+        Self::new(rect.x, rect.y, rect.right() - rect.x, rect.bottom() - rect.y)
+    }
+    
+    /// Check whether two bounds overlap (touching edges count as overlapping)
+    pub fn intersects(&self, other: &CanvasBounds) -> bool {
+        self.min_x <= other.max_x && other.min_x <= self.max_x
+            && self.min_y <= other.max_y && other.min_y <= self.max_y
+    }
+    
+    /// Smallest bounds covering both bounds
+    pub fn union(&self, other: &CanvasBounds) -> Self {
+        Self {
+            min_x: self.min_x.min(other.min_x),
+            min_y: self.min_y.min(other.min_y),
+            max_x: self.max_x.max(other.max_x),
+            max_y: self.max_y.max(other.max_y),
+        }
+    }
+    
+    /// Grow the bounds to cover a point
+    pub fn include_point(&mut self, x: f64, y: f64) {
+        self.min_x = self.min_x.min(x);
+        self.min_y = self.min_y.min(y);
+        self.max_x = self.max_x.max(x);
+        self.max_y = self.max_y.max(y);
+    }
+}
+
+type CellKey = (i64, i64);
+
+/// Uniform grid over node and connection bounds
+///
+/// Every entry is registered in each cell its bounds overlap, so a viewport query
+/// only visits the cells under the viewport instead of the whole canvas.
+#[derive(Debug, Clone)]
+pub struct SpatialIndex {
+    cell_size: f64,
+    node_cells: HashMap<CellKey, HashSet<String>>,
+    connection_cells: HashMap<CellKey, HashSet<String>>,
+    node_bounds: HashMap<String, CanvasBounds>,
+    connection_bounds: HashMap<String, CanvasBounds>,
+    node_connections: HashMap<String, HashSet<String>>, // Connections attached to each node
+}
+
+impl Default for SpatialIndex {
+    fn default() -> Self {
+        Self::new(DEFAULT_GRID_CELL_SIZE)
+    }
+}
+
+impl SpatialIndex {
+    /// Create an empty index with the given cell size
+    pub fn new(cell_size: f64) -> Self {
+        Self {
+            cell_size: if cell_size > 0.0 { cell_size } else { DEFAULT_GRID_CELL_SIZE },
+            node_cells: HashMap::new(),
+            connection_cells: HashMap::new(),
+            node_bounds: HashMap::new(),
+            connection_bounds: HashMap::new(),
+            node_connections: HashMap::new(),
+        }
+    }
+    
+    /// Remove every entry
+    pub fn clear(&mut self) {
+        self.node_cells.clear();
+        self.connection_cells.clear();
+        self.node_bounds.clear();
+        self.connection_bounds.clear();
+        self.node_connections.clear();
+    }
+    
+    /// Number of indexed nodes
+    pub fn node_count(&self) -> usize {
+        self.node_bounds.len()
+    }
+    
+    /// Number of indexed connections
+    pub fn connection_count(&self) -> usize {
+        self.connection_bounds.len()
+    }
+    
+    /// Get the indexed bounds of a node
+    pub fn node_bounds(&self, node_id: &str) -> Option<CanvasBounds> {
+        self.node_bounds.get(node_id).copied()
+    }
+    
+    /// Get the IDs of the connections attached to a node
+    pub fn connections_of(&self, node_id: &str) -> impl Iterator<Item = &String> {
+        self.node_connections.get(node_id).into_iter().flatten()
+    }
+    
+    /// Insert or move a node
+    pub fn update_node(&mut self, node_id: &str, bounds: CanvasBounds) {
+        self.remove_node_cells(node_id);
+        for key in self.cells(&bounds) {
+            self.node_cells.entry(key).or_default().insert(node_id.to_string());
+        }
+        self.node_bounds.insert(node_id.to_string(), bounds);
+    }
+    
+    /// Remove a node (its connections must be removed separately)
+    pub fn remove_node(&mut self, node_id: &str) {
+        self.remove_node_cells(node_id);
+        self.node_bounds.remove(node_id);
+        self.node_connections.remove(node_id);
+    }
+    
+    /// Insert or move a connection between two nodes
+    pub fn update_connection(&mut self, connection_id: &str, from_node: &str, to_node: &str, bounds: CanvasBounds) {
+        self.remove_connection_cells(connection_id);
+        for key in self.cells(&bounds) {
+            self.connection_cells.entry(key).or_default().insert(connection_id.to_string());
+        }
+        self.connection_bounds.insert(connection_id.to_string(), bounds);
+        for node_id in [from_node, to_node] {
+            self.node_connections.entry(node_id.to_string()).or_default().insert(connection_id.to_string());
+        }
+    }
+    
+    /// Remove a connection
+    pub fn remove_connection(&mut self, connection_id: &str) {
+        self.remove_connection_cells(connection_id);
+        self.connection_bounds.remove(connection_id);
+        self.node_connections.retain(|_, connections| {
+            connections.remove(connection_id);
+            !connections.is_empty()
+        });
+    }
+    
+    /// Get the IDs of the nodes overlapping an area
+    pub fn query_nodes(&self, area: &CanvasBounds) -> HashSet<&str> {
+        self.query(&self.node_cells, &self.node_bounds, area)
+    }
+    
+    /// Get the IDs of the connections whose bounds overlap an area
+    pub fn query_connections(&self, area: &CanvasBounds) -> HashSet<&str> {
+        self.query(&self.connection_cells, &self.connection_bounds, area)
+    }
+    
+    fn query<'a>(
+        &self,
+        cells: &'a HashMap<CellKey, HashSet<String>>,
+        bounds: &HashMap<String, CanvasBounds>,
+        area: &CanvasBounds,
+    ) -> HashSet<&'a str> {
+        let mut found = HashSet::new();
+        let ((min_x, max_x), (min_y, max_y)) = self.cell_range(area);
+        let area_cells = (max_x - min_x + 1).saturating_mul(max_y - min_y + 1);
+        // Areas covering more cells than are populated are cheaper to answer from the occupied cells
+        if area_cells > cells.len() as i64 {
+            for entries in cells.values() {
+                found.extend(entries.iter().map(String::as_str));
+            }
+        } else {
+            for x in min_x..=max_x {
+                for y in min_y..=max_y {
+                    if let Some(entries) = cells.get(&(x, y)) {
+                        found.extend(entries.iter().map(String::as_str));
+                    }
+                }
+            }
+        }
+        found.retain(|id| bounds.get(*id).is_some_and(|entry| entry.intersects(area)));
+        found
+    }
+    
+    fn remove_node_cells(&mut self, node_id: &str) {
+        if let Some(bounds) = self.node_bounds.get(node_id).copied() {
+            for key in self.cells(&bounds) {
+                if let Some(entries) = self.node_cells.get_mut(&key) {
+                    entries.remove(node_id);
+                    if entries.is_empty() {
+                        self.node_cells.remove(&key);
+                    }
+                }
+            }
+        }
+    }
+    
+    fn remove_connection_cells(&mut self, connection_id: &str) {
+        if let Some(bounds) = self.connection_bounds.get(connection_id).copied() {
+            for key in self.cells(&bounds) {
+                if let Some(entries) = self.connection_cells.get_mut(&key) {
+                    entries.remove(connection_id);
+                    if entries.is_empty() {
+                        self.connection_cells.remove(&key);
+                    }
+                }
+            }
+        }
+    }
+    
+    /// Inclusive ranges of grid columns and rows overlapped by bounds
+    fn cell_range(&self, bounds: &CanvasBounds) -> ((i64, i64), (i64, i64)) {
+        let cell = |value: f64| (value / self.cell_size).floor() as i64;
+        ((cell(bounds.min_x), cell(bounds.max_x)), (cell(bounds.min_y), cell(bounds.max_y)))
+    }
+    
+    /// Grid cells overlapped by bounds
+    fn cells(&self, bounds: &CanvasBounds) -> Vec<CellKey> {
+        let ((min_x, max_x), (min_y, max_y)) = self.cell_range(bounds);
+        let mut keys = Vec::new();
+        for x in min_x..=max_x {
+            for y in min_y..=max_y {
+                keys.push((x, y));
+            }
+        }
+        keys
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_node_queries() {
+        let mut index = SpatialIndex::new(100.0);
+        index.update_node("a", CanvasBounds::new(0.0, 0.0, 50.0, 50.0));
+        index.update_node("b", CanvasBounds::new(1000.0, 1000.0, 50.0, 50.0));
+        index.update_node("c", CanvasBounds::new(-250.0, 80.0, 300.0, 40.0));
+        
+        let found = index.query_nodes(&CanvasBounds::new(-10.0, -10.0, 100.0, 100.0));
+        assert_eq!(found, HashSet::from(["a", "c"]));
+        
+        index.update_node("a", CanvasBounds::new(990.0, 990.0, 20.0, 20.0));
+        assert_eq!(index.query_nodes(&CanvasBounds::new(-10.0, -10.0, 20.0, 20.0)), HashSet::new());
+        assert_eq!(index.query_nodes(&CanvasBounds::new(980.0, 980.0, 40.0, 40.0)), HashSet::from(["a", "b"]));
+        
+        index.remove_node("b");
+        assert_eq!(index.node_count(), 2);
+        assert_eq!(index.query_nodes(&CanvasBounds::new(-1.0e6, -1.0e6, 2.0e6, 2.0e6)), HashSet::from(["a", "c"]));
+    }
+    
+    #[test]
+    fn test_connection_queries() {
+        let mut index = SpatialIndex::new(100.0);
+        index.update_connection("ab", "a", "b", CanvasBounds::new(0.0, 0.0, 500.0, 10.0));
+        
+        assert_eq!(index.query_connections(&CanvasBounds::new(250.0, 0.0, 10.0, 10.0)), HashSet::from(["ab"]));
+        assert!(index.query_connections(&CanvasBounds::new(250.0, 200.0, 10.0, 10.0)).is_empty());
+        assert_eq!(index.connections_of("a").count(), 1);
+        
+        index.remove_connection("ab");
+        assert_eq!(index.connection_count(), 0);
+        assert_eq!(index.connections_of("b").count(), 0);
+    }
+}
@@ -0,0 +1,640 @@
+// Version Manager for OSland Component Manager
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+use semver::{Version, VersionReq};
+use serde::{Serialize, Deserialize};
+use crate::component_manager::{component::Component, ComponentManagerError};
+
+/// Version compatibility mode
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VersionCompatibilityMode {
+    /// Strict version matching (exact version)
+    Strict,
+    /// Allow compatible updates (semver compatible)
+    Compatible,
+    /// Allow any version
+    Any,
+    /// Custom version range
+    Custom(String),
+}
+
+/// Version constraint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionConstraint {
+    pub min_version: Option<String>,
+    pub max_version: Option<String>,
+    pub compatibility_mode: VersionCompatibilityMode,
+}
+
+impl VersionConstraint {
+    /// Check whether a version satisfies the constraint
+    pub fn matches(&self, version: &str) -> bool {
+        let parsed = match Version::parse(version) {
+            Ok(parsed) => parsed,
+            // Non-semver versions can only be matched exactly
+            Err(_) => return self.min_version.as_deref() == Some(version) || self.compatibility_mode == VersionCompatibilityMode::Any,
+        };
+        let bound = |bound: &Option<String>| bound.as_deref().and_then(|b| Version::parse(b).ok());
+        let (min, max) = (bound(&self.min_version), bound(&self.max_version));
+        
+        if min.as_ref().map_or(false, |min| &parsed < min) || max.as_ref().map_or(false, |max| &parsed > max) {
+            return false;
+        }
+        
+        match &self.compatibility_mode {
+            VersionCompatibilityMode::Strict => min.map_or(true, |min| parsed == min),
+            VersionCompatibilityMode::Compatible => min.map_or(true, |min| !is_breaking_change(&min, &parsed)),
+            VersionCompatibilityMode::Any => true,
+            VersionCompatibilityMode::Custom(req) => VersionReq::parse(req).map_or(false, |req| req.matches(&parsed)),
+        }
+    }
+}
+
+/// Component version information
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComponentVersion {
+    pub version: String,
+    pub component: Component,
+    pub release_date: String,
+    pub changelog: String,
+    pub deprecated: bool,
+    pub recommended: bool,
+    pub dependencies: HashMap<String, VersionConstraint>,
+}
+
+/// Version management interface
+pub trait VersionManager {
+    /// Get the latest version of a component
+    fn get_latest_version(&self, component_id: &str) -> Result<Option<&ComponentVersion>, ComponentManagerError>;
+    
+    /// Get a specific version of a component
+    fn get_version(&self, component_id: &str, version: &str) -> Result<Option<&ComponentVersion>, ComponentManagerError>;
+    
+    /// Get all versions of a component
+    fn get_all_versions(&self, component_id: &str) -> Result<Vec<&ComponentVersion>, ComponentManagerError>;
+    
+    /// Get compatible versions of a component
+    fn get_compatible_versions(&self, component_id: &str, version_req: &str) -> Result<Vec<&ComponentVersion>, ComponentManagerError>;
+    
+    /// Add a new component version
+    fn add_version(&mut self, component_id: &str, version_info: ComponentVersion) -> Result<(), ComponentManagerError>;
+    
+    /// Remove a component version
+    fn remove_version(&mut self, component_id: &str, version: &str) -> Result<(), ComponentManagerError>;
+    
+    /// Check if two component versions are compatible
+    fn is_compatible(&self, version1: &str, version2: &str) -> Result<bool, ComponentManagerError>;
+    
+    /// Get recommended version for a component
+    fn get_recommended_version(&self, component_id: &str) -> Result<Option<&ComponentVersion>, ComponentManagerError>;
+}
+
+/// Default version manager implementation
+pub struct DefaultVersionManager {
+    component_versions: HashMap<String, Vec<ComponentVersion>>,
+}
+
+impl DefaultVersionManager {
+    pub fn new() -> Self {
+        Self {
+            component_versions: HashMap::new(),
+        }
+    }
+    
+    /// Sort versions in descending order
+    fn sort_versions_descending(versions: &mut Vec<ComponentVersion>) {
+        versions.sort_by(|a, b| {
+            // Try to parse as semver
+            let version_a = Version::parse(&a.version);
+            let version_b = Version::parse(&b.version);
+            
+            match (version_a, version_b) {
+                (Ok(v_a), Ok(v_b)) => v_b.cmp(&v_a),
+                _ => b.version.cmp(&a.version), // Fallback to string comparison
+            }
+        });
+    }
+}
+
+impl VersionManager for DefaultVersionManager {
+    fn get_latest_version(&self, component_id: &str) -> Result<Option<&ComponentVersion>, ComponentManagerError> {
+        if let Some(versions) = self.component_versions.get(component_id) {
+            if versions.is_empty() {
+                Ok(None)
+            } else {
+                // Return the first version (already sorted in descending order)
+                Ok(Some(&versions[0]))
+            }
+        } else {
+            Ok(None)
+        }
+    }
+    
+    fn get_version(&self, component_id: &str, version: &str) -> Result<Option<&ComponentVersion>, ComponentManagerError> {
+        if let Some(versions) = self.component_versions.get(component_id) {
+            Ok(versions.iter().find(|v| v.version == version))
+        } else {
+            Ok(None)
+        }
+    }
+    
+    fn get_all_versions(&self, component_id: &str) -> Result<Vec<&ComponentVersion>, ComponentManagerError> {
+        if let Some(versions) = self.component_versions.get(component_id) {
+            Ok(versions.iter().collect())
+        } else {
+            Ok(Vec::new())
+        }
+    }
+    
+    fn get_compatible_versions(&self, component_id: &str, version_req: &str) -> Result<Vec<&ComponentVersion>, ComponentManagerError> {
+        if let Some(versions) = self.component_versions.get(component_id) {
+            let req = VersionReq::parse(version_req)
+                .map_err(|e| ComponentManagerError::VersionError(
+                    format!("Invalid version requirement '{}': {}", version_req, e)
+                ))?;
+            
+            let compatible_versions: Vec<&ComponentVersion> = versions.iter()
+                .filter(|v| {
+                    if let Ok(version) = Version::parse(&v.version) {
+                        req.matches(&version)
+                    } else {
+                        false
+                    }
+                })
+                .collect();
+            
+            Ok(compatible_versions)
+        } else {
+            Ok(Vec::new())
+        }
+    }
+    
+    fn add_version(&mut self, component_id: &str, version_info: ComponentVersion) -> Result<(), ComponentManagerError> {
+        // Check if version already exists
+        if let Some(versions) = self.component_versions.get_mut(component_id) {
+            if versions.iter().any(|v| v.version == version_info.version) {
+                return Err(ComponentManagerError::VersionError(
+                    format!("Version {} already exists for component {}", version_info.version, component_id)
+                ));
+            }
+            
+            // Add version and re-sort
+            versions.push(version_info);
+            DefaultVersionManager::sort_versions_descending(versions);
+        } else {
+            // Create new entry for this component
+            let mut versions = vec![version_info];
+            DefaultVersionManager::sort_versions_descending(&mut versions);
+            self.component_versions.insert(component_id.to_string(), versions);
+        }
+        
+        Ok(())
+    }
+    
+    fn remove_version(&mut self, component_id: &str, version: &str) -> Result<(), ComponentManagerError> {
+        if let Some(versions) = self.component_versions.get_mut(component_id) {
+            let initial_len = versions.len();
+            versions.retain(|v| v.version != version);
+            
+            if versions.len() == initial_len {
+                return Err(ComponentManagerError::VersionError(
+                    format!("Version {} not found for component {}", version, component_id)
+                ));
+            }
+            
+            // If no versions left, remove the component entry
+            if versions.is_empty() {
+                self.component_versions.remove(component_id);
+            }
+        } else {
+            return Err(ComponentManagerError::VersionError(
+                format!("Component {} not found", component_id)
+            ));
+        }
+        
+        Ok(())
+    }
+    
+    fn is_compatible(&self, version1: &str, version2: &str) -> Result<bool, ComponentManagerError> {
+        // Try to parse both as semver
+        let v1 = Version::parse(version1)
+            .map_err(|e| ComponentManagerError::VersionError(
+                format!("Invalid version '{}': {}", version1, e)
+            ))?;
+            
+        let v2 = Version::parse(version2)
+            .map_err(|e| ComponentManagerError::VersionError(
+                format!("Invalid version '{}': {}", version2, e)
+            ))?;
+        
+        // Compatible if major versions match and v2 is newer or equal
+        if v1.major != v2.major {
+            return Ok(false);
+        }
+        
+        Ok(v2 >= v1)
+    }
+    
+    fn get_recommended_version(&self, component_id: &str) -> Result<Option<&ComponentVersion>, ComponentManagerError> {
+        if let Some(versions) = self.component_versions.get(component_id) {
+            // Find the first recommended version
+            Ok(versions.iter().find(|v| v.recommended))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// Check whether going from `from` to `to` is a breaking change under semver
+///
+/// A major bump is breaking; before 1.0.0 a minor bump is breaking as well.
+pub fn is_breaking_change(from: &Version, to: &Version) -> bool {
+    if from.major != to.major {
+        return true;
+    }
+    from.major == 0 && from.minor != to.minor
+}
+
+/// Components installed in a project, with their versions
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProjectComponents {
+    /// Installed version per component ID
+    pub versions: BTreeMap<String, String>,
+}
+
+impl ProjectComponents {
+    /// Load the installed components from a JSON file
+    pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<Self, ComponentManagerError> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| ComponentManagerError::VersionError(format!("Failed to read project components: {}", e)))?;
+        serde_json::from_str(&content)
+            .map_err(|e| ComponentManagerError::VersionError(format!("Failed to parse project components: {}", e)))
+    }
+    
+    /// Save the installed components to a JSON file
+    pub fn save_to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), ComponentManagerError> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| ComponentManagerError::VersionError(format!("Failed to serialize project components: {}", e)))?;
+        fs::write(path, json)
+            .map_err(|e| ComponentManagerError::VersionError(format!("Failed to write project components: {}", e)))
+    }
+}
+
+/// Kind of an upgrade
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum UpgradeKind {
+    Patch,
+    Minor,
+    Major,
+    /// Versions that are not valid semver
+    Unknown,
+}
+
+/// Planned upgrade of a single component
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpgradeStep {
+    pub component_id: String,
+    pub from_version: String,
+    pub to_version: String,
+    pub kind: UpgradeKind,
+    /// Whether the upgrade is breaking (semver or declared compatibility)
+    pub breaking: bool,
+    /// Why the upgrade is breaking
+    pub reasons: Vec<String>,
+}
+
+/// Upgrade planning policy
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpgradePolicy {
+    /// Plan upgrades across major versions
+    pub allow_breaking: bool,
+    /// Consider pre-release versions
+    pub include_prerelease: bool,
+}
+
+/// Upgrade plan of a project
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpgradePlan {
+    /// Planned upgrades, in dependency order
+    pub steps: Vec<UpgradeStep>,
+    /// Newer breaking versions left out by the policy (component ID, version)
+    pub held_back: Vec<(String, String)>,
+    /// Installed components unknown to the registry
+    pub unknown_components: Vec<String>,
+}
+
+impl UpgradePlan {
+    /// Check whether the plan contains breaking upgrades
+    pub fn has_breaking(&self) -> bool {
+        self.steps.iter().any(|step| step.breaking)
+    }
+    
+    /// Check whether there is nothing to upgrade
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+}
+
+/// Executes the upgrade of a single component (e.g. replaces its files)
+pub trait UpgradeExecutor {
+    /// Upgrade a component from one version to another
+    fn upgrade(&mut self, from: &ComponentVersion, to: &ComponentVersion) -> Result<(), ComponentManagerError>;
+    
+    /// Undo a previously successful upgrade
+    fn rollback(&mut self, from: &ComponentVersion, to: &ComponentVersion) -> Result<(), ComponentManagerError>;
+}
+
+impl DefaultVersionManager {
+    /// Compute an upgrade plan for the components installed in a project
+    pub fn plan_upgrades(&self, project: &ProjectComponents, policy: &UpgradePolicy) -> Result<UpgradePlan, ComponentManagerError> {
+        let mut plan = UpgradePlan::default();
+        let mut targets: BTreeMap<String, String> = project.versions.clone();
+        
+        for (component_id, installed) in &project.versions {
+            let versions = match self.component_versions.get(component_id) {
+                Some(versions) => versions,
+                None => {
+                    plan.unknown_components.push(component_id.clone());
+                    continue;
+                }
+            };
+            let current = match Version::parse(installed) {
+                Ok(current) => current,
+                Err(_) => continue,
+            };
+            
+            // Versions are sorted newest first
+            let candidates: Vec<(&ComponentVersion, Version)> = versions.iter()
+                .filter(|v| !v.deprecated)
+                .filter_map(|v| Version::parse(&v.version).ok().map(|parsed| (v, parsed)))
+                .filter(|(_, parsed)| parsed > &current && (policy.include_prerelease || parsed.pre.is_empty()))
+                .collect();
+            
+            let compatible = candidates.iter().find(|(_, parsed)| !is_breaking_change(&current, parsed));
+            let latest = candidates.first();
+            let chosen = if policy.allow_breaking { latest } else { compatible };
+            
+            if let Some((latest, parsed)) = latest {
+                if !policy.allow_breaking && is_breaking_change(&current, parsed) {
+                    plan.held_back.push((component_id.clone(), latest.version.clone()));
+                }
+            }
+            
+            if let Some((target, parsed)) = chosen {
+                let kind = if parsed.major != current.major {
+                    UpgradeKind::Major
+                } else if parsed.minor != current.minor {
+                    UpgradeKind::Minor
+                } else {
+                    UpgradeKind::Patch
+                };
+                let mut reasons = Vec::new();
+                if is_breaking_change(&current, parsed) {
+                    reasons.push(format!("{} -> {} is a breaking semver change", installed, target.version));
+                }
+                
+                targets.insert(component_id.clone(), target.version.clone());
+                plan.steps.push(UpgradeStep {
+                    component_id: component_id.clone(),
+                    from_version: installed.clone(),
+                    to_version: target.version.clone(),
+                    kind,
+                    breaking: !reasons.is_empty(),
+                    reasons,
+                });
+            }
+        }
+        
+        // Check the declared compatibility of every component against the planned versions
+        for (component_id, version) in &targets {
+            let declared = match self.get_version(component_id, version)? {
+                Some(declared) => declared,
+                None => continue,
+            };
+            
+            for (dependency_id, constraint) in &declared.dependencies {
+                let dependency_version = match targets.get(dependency_id) {
+                    Some(dependency_version) => dependency_version,
+                    None => continue,
+                };
+                if constraint.matches(dependency_version) {
+                    continue;
+                }
+                
+                let reason = format!("{} {} is not compatible with {} {}", component_id, version, dependency_id, dependency_version);
+                for step in plan.steps.iter_mut().filter(|step| &step.component_id == component_id || &step.component_id == dependency_id) {
+                    step.breaking = true;
+                    step.reasons.push(reason.clone());
+                }
+            }
+        }
+        
+        // Upgrade dependencies before their dependents
+        let dependencies_of = |id: &str| -> Vec<String> {
+            targets.get(id)
+                .and_then(|version| self.get_version(id, version).ok().flatten())
+                .map(|declared| declared.dependencies.keys().cloned().collect())
+                .unwrap_or_default()
+        };
+        let mut ordered: Vec<UpgradeStep> = Vec::new();
+        let mut remaining = std::mem::take(&mut plan.steps);
+        while !remaining.is_empty() {
+            let pending: Vec<String> = remaining.iter().map(|step| step.component_id.clone()).collect();
+            let position = remaining.iter()
+                .position(|step| dependencies_of(&step.component_id).iter().all(|dep| !pending.contains(dep) || dep == &step.component_id))
+                .unwrap_or(0); // Break dependency cycles in plan order
+            ordered.push(remaining.remove(position));
+        }
+        plan.steps = ordered;
+        
+        Ok(plan)
+    }
+    
+    /// Apply an upgrade plan transactionally
+    ///
+    /// Steps run in plan order. If a step fails, the steps already applied are
+    /// rolled back in reverse order and the project is left unchanged.
+    pub fn apply_upgrade_plan<E: UpgradeExecutor>(
+        &self,
+        project: &mut ProjectComponents,
+        plan: &UpgradePlan,
+        executor: &mut E,
+        allow_breaking: bool,
+    ) -> Result<(), ComponentManagerError> {
+        if plan.has_breaking() && !allow_breaking {
+            return Err(ComponentManagerError::CompatibilityError(
+                "Upgrade plan contains breaking upgrades".to_string()
+            ));
+        }
+        
+        let mut resolved = Vec::new();
+        for step in &plan.steps {
+            if project.versions.get(&step.component_id) != Some(&step.from_version) {
+                return Err(ComponentManagerError::VersionError(
+                    format!("Plan is stale: {} is no longer at version {}", step.component_id, step.from_version)
+                ));
+            }
+            let lookup = |version: &str| self.get_version(&step.component_id, version)?.ok_or_else(|| ComponentManagerError::VersionError(
+                format!("Version {} not found for component {}", version, step.component_id)
+            ));
+            resolved.push((lookup(&step.from_version)?, lookup(&step.to_version)?));
+        }
+        
+        let mut applied: Vec<usize> = Vec::new();
+        for (index, (from, to)) in resolved.iter().enumerate() {
+            if let Err(error) = executor.upgrade(from, to) {
+                let mut rollback_errors = Vec::new();
+                for applied_index in applied.iter().rev() {
+                    let (from, to) = resolved[*applied_index];
+                    if let Err(rollback_error) = executor.rollback(from, to) {
+                        rollback_errors.push(rollback_error.to_string());
+                    }
+                }
+                
+                let mut message = format!("Upgrade of {} to {} failed: {}", plan.steps[index].component_id, to.version, error);
+                if !rollback_errors.is_empty() {
+                    message.push_str(&format!("; rollback failed: {}", rollback_errors.join("; ")));
+                }
+                return Err(ComponentManagerError::VersionError(message));
+            }
+            applied.push(index);
+        }
+        
+        for step in &plan.steps {
+            project.versions.insert(step.component_id.clone(), step.to_version.clone());
+        }
+        
+        Ok(())
+    }
+}
+
+/// Component version extension trait
+pub trait ComponentVersionExt {
+    /// Check if a component is compatible with a specific version requirement
+    fn is_version_compatible(&self, version_req: &str) -> Result<bool, ComponentManagerError>;
+    
+    /// Get version as semver if possible
+    fn get_semver(&self) -> Result<Option<Version>, ComponentManagerError>;
+}
+
+impl ComponentVersionExt for Component {
+    fn is_version_compatible(&self, version_req: &str) -> Result<bool, ComponentManagerError> {
+        let req = VersionReq::parse(version_req)
+            .map_err(|e| ComponentManagerError::VersionError(
+                format!("Invalid version requirement '{}': {}", version_req, e)
+            ))?;
+        
+        if let Ok(version) = Version::parse(&self.version) {
+            Ok(req.matches(&version))
+        } else {
+            // If not a valid semver, check if it's an exact match
+            Ok(&self.version == version_req)
+        }
+    }
+    
+    fn get_semver(&self) -> Result<Option<Version>, ComponentManagerError> {
+        Ok(Version::parse(&self.version).ok())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component_manager::component::{ComponentCategory, ComponentType};
+    
+    fn version(id: &str, version: &str, dependencies: &[(&str, &str)]) -> ComponentVersion {
+        ComponentVersion {
+            version: version.to_string(),
+            component: Component {
+                id: id.to_string(),
+                name: id.to_string(),
+                display_name: id.to_string(),
+                component_type: ComponentType::Scheduler,
+                category: ComponentCategory::KernelCore,
+                version: version.to_string(),
+                description: String::new(),
+                author: String::new(),
+                source_url: None,
+                license: String::new(),
+                properties: Vec::new(),
+                ports: Vec::new(),
+                dependencies: Vec::new(),
+                supported_architectures: Default::default(),
+                supported_languages: Vec::new(),
+                implementation_files: Vec::new(),
+                build_commands: Vec::new(),
+                initialization_code: String::new(),
+            },
+            release_date: String::new(),
+            changelog: String::new(),
+            deprecated: false,
+            recommended: false,
+            dependencies: dependencies.iter().map(|(dep, min)| (dep.to_string(), VersionConstraint {
+                min_version: Some(min.to_string()),
+                max_version: None,
+                compatibility_mode: VersionCompatibilityMode::Compatible,
+            })).collect(),
+        }
+    }
+    
+    struct FailingExecutor {
+        fail_on: String,
+        log: Vec<String>,
+    }
+    
+    impl UpgradeExecutor for FailingExecutor {
+        fn upgrade(&mut self, from: &ComponentVersion, to: &ComponentVersion) -> Result<(), ComponentManagerError> {
+            if to.component.id == self.fail_on {
+                return Err(ComponentManagerError::VersionError("disk full".to_string()));
+            }
+            self.log.push(format!("upgrade {} {}->{}", to.component.id, from.version, to.version));
+            Ok(())
+        }
+        
+        fn rollback(&mut self, from: &ComponentVersion, to: &ComponentVersion) -> Result<(), ComponentManagerError> {
+            self.log.push(format!("rollback {} {}->{}", to.component.id, to.version, from.version));
+            Ok(())
+        }
+    }
+    
+    #[test]
+    fn test_plan_and_apply_with_rollback() {
+        let mut manager = DefaultVersionManager::new();
+        for v in ["1.0.0", "1.2.0", "2.0.0"] {
+            manager.add_version("sched", version("sched", v, &[])).unwrap();
+        }
+        manager.add_version("ipc", version("ipc", "0.3.0", &[("sched", "1.0.0")])).unwrap();
+        manager.add_version("ipc", version("ipc", "0.3.1", &[("sched", "1.0.0")])).unwrap();
+        
+        let mut project = ProjectComponents::default();
+        project.versions.insert("sched".to_string(), "1.0.0".to_string());
+        project.versions.insert("ipc".to_string(), "0.3.0".to_string());
+        
+        let plan = manager.plan_upgrades(&project, &UpgradePolicy::default()).unwrap();
+        assert_eq!(plan.steps.len(), 2);
+        assert!(!plan.has_breaking());
+        assert_eq!(plan.held_back, vec![("sched".to_string(), "2.0.0".to_string())]);
+        
+        // Crossing the major version breaks ipc's declared compatibility with sched
+        let breaking = manager.plan_upgrades(&project, &UpgradePolicy { allow_breaking: true, ..Default::default() }).unwrap();
+        let sched = breaking.steps.iter().find(|step| step.component_id == "sched").unwrap();
+        assert_eq!(sched.kind, UpgradeKind::Major);
+        assert!(sched.reasons.len() >= 2);
+        
+        // A failing step rolls back the steps already applied
+        let before = project.clone();
+        let mut executor = FailingExecutor { fail_on: "ipc".to_string(), log: Vec::new() };
+        assert!(manager.apply_upgrade_plan(&mut project, &plan, &mut executor, false).is_err());
+        assert_eq!(project, before);
+        assert_eq!(executor.log, vec!["upgrade sched 1.0.0->1.2.0", "rollback sched 1.2.0->1.0.0"]);
+        
+        executor.fail_on.clear();
+        manager.apply_upgrade_plan(&mut project, &plan, &mut executor, false).unwrap();
+        assert_eq!(project.versions["sched"], "1.2.0");
+        assert_eq!(project.versions["ipc"], "0.3.1");
+    }
+}
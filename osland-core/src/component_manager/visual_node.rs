@@ -0,0 +1,1792 @@
+// Visual node definition for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+use serde::{Serialize, Deserialize};
+use crate::core::audit::{self, AuditCategory};
+use crate::core::geometry::{Rect, Point, Color};
+use super::{component::Component, spatial_index::{CanvasBounds, SpatialIndex}, ComponentManagerError};
+use uuid::Uuid;
+use crate::core::stable_ids::{self, IdStrategy};
+
+/// Visual node style definition
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeStyle {
+    pub background_color: Color,
+    pub border_color: Color,
+    pub border_width: f64,
+    pub text_color: Color,
+    pub font_size: f64,
+    pub rounded_corners: f64,
+    pub shadow_color: Option<Color>,
+    pub shadow_offset: Option<(f64, f64)>,
+    pub shadow_blur: Option<f64>,
+}
+
+/// Visual node port definition (for connections)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VisualNodePort {
+    pub id: String,
+    pub name: String,
+    pub port_type: String,
+    pub direction: crate::component_manager::component::PortDirection,
+    pub position: (f64, f64), // Relative position on the node
+    pub connected_to: Option<String>, // Node ID of connected port
+    pub description: String,
+}
+
+/// Connection validation result
+#[derive(Debug, Clone)]
+pub enum ConnectionValidationResult {
+    Valid,
+    InvalidSourcePort,
+    InvalidTargetPort,
+    InvalidPortDirection,
+    PortTypeMismatch,
+    CircularDependency,
+    AlreadyConnected,
+    SelfConnection,
+    Other(String),
+}
+
+/// Data flow information for connections
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataFlowInfo {
+    pub data_type: String,
+    pub data_size: Option<usize>,
+    pub flow_rate: Option<u64>, // Bytes per second
+    pub last_value_preview: Option<String>,
+    pub is_active: bool,
+    pub transmission_time: Duration,
+}
+
+/// Visual node connection definition with enhanced functionality
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeConnection {
+    pub id: String,
+    pub from_node: String,
+    pub from_port: String,
+    pub to_node: String,
+    pub to_port: String,
+    pub connection_type: String,
+    pub color: Color,
+    pub line_width: f64,
+    pub description: String,
+    
+    // Enhanced connection properties
+    pub data_flow_info: DataFlowInfo,
+    pub is_highlighted: bool,
+    pub is_selected: bool,
+    pub label: Option<String>,
+    pub bend_points: Vec<Point>, // Custom bend points for the connection line
+    pub animation_speed: f64,    // Animation speed for data flow visualization
+    pub show_data_flow: bool,    // Show data flow animation
+}
+
+/// Node state change type for history tracking
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum NodeStateChange {
+    PositionChanged(Point, Point), // old position, new position
+    SizeChanged((f64, f64), (f64, f64)), // old size, new size
+    PropertyChanged(String, String, String), // property name, old value, new value
+    StyleChanged(NodeStyle, NodeStyle), // old style, new style
+    SelectionChanged(bool, bool), // old selected state, new selected state
+    ExpansionChanged(bool, bool), // old expanded state, new expanded state
+}
+
+/// Node control flow type for complex control structures
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum NodeControlType {
+    Sequential,       // Normal sequential execution
+    Conditional,      // If-else branching
+    Loop,             // For/while loop
+    Recursive,        // Recursive node (calls itself)
+    Parallel,         // Parallel execution
+    Switch,           // Switch-case branching
+    TryCatch,         // Try-catch error handling
+}
+
+/// Loop configuration for loop nodes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoopConfig {
+    pub loop_type: String,          // "for", "while", "do-while"
+    pub condition: String,          // Loop condition expression
+    pub iteration_variable: String, // Iteration variable name (for for-loops)
+    pub start_value: String,        // Start value (for for-loops)
+    pub end_value: String,          // End value (for for-loops)
+    pub step_value: String,         // Step value (for for-loops)
+    pub max_iterations: u32,        // Safety limit for iterations
+}
+
+/// Conditional configuration for conditional nodes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConditionalConfig {
+    pub condition: String,          // Condition expression
+    pub has_else: bool,             // Whether there's an else branch
+    pub true_branch_id: Option<String>,  // Node ID of true branch start
+    pub false_branch_id: Option<String>, // Node ID of false branch start
+}
+
+/// Debug information collected while a node executes
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NodeDebugInfo {
+    pub execution_time: Duration,
+    pub is_executing: bool,
+    pub execution_count: u64,
+    pub error_message: Option<String>,
+    pub warning_messages: Vec<String>,
+    pub info_messages: Vec<String>,
+    pub data_flows: HashMap<String, DataFlowInfo>, // Keyed by port name
+}
+
+/// Data visualization settings for a node
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataVisualizationConfig {
+    pub show_values: bool,          // Show current port values on the node
+    pub show_data_flow: bool,       // Animate data flowing through the node's connections
+    pub max_preview_length: usize,  // Truncate value previews to this many characters
+    pub watched_ports: Vec<String>, // Ports to display (empty means all ports)
+}
+
+impl Default for DataVisualizationConfig {
+    fn default() -> Self {
+        Self {
+            show_values: true,
+            show_data_flow: false,
+            max_preview_length: 64,
+            watched_ports: Vec::new(),
+        }
+    }
+}
+
+/// Visual node definition with state management and control flow support
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VisualNode {
+    pub id: String,
+    pub component_id: String,
+    pub component: Component,
+    pub position: Point,
+    pub size: (f64, f64),
+    pub z_index: i32,
+    pub style: NodeStyle,
+    pub ports: Vec<VisualNodePort>,
+    pub properties: HashMap<String, String>,
+    pub selected: bool,
+    pub expanded: bool,
+    pub user_data: HashMap<String, String>,
+    
+    // Control flow configuration
+    pub control_type: NodeControlType,
+    pub loop_config: Option<LoopConfig>,
+    pub conditional_config: Option<ConditionalConfig>,
+    pub recursive_target_id: Option<String>, // Target node ID for recursion
+    pub parallel_branches: Vec<String>,      // Node IDs for parallel branches
+    
+    // State management for real-time editing
+    pub state_history: VecDeque<NodeStateChange>,
+    pub history_limit: usize,
+    pub state_version: u64,
+    pub is_dirty: bool,
+    pub last_updated: u64, // Timestamp for last update
+    
+    // Debugging and data visualization
+    #[serde(default)]
+    pub debug_info: NodeDebugInfo,
+    #[serde(default)]
+    pub data_visualization: DataVisualizationConfig,
+    #[serde(default)]
+    pub current_data_values: HashMap<String, String>, // Latest value per port name
+}
+
+/// Canvas mutation recorded in the operation log
+///
+/// Every structural change to a `NodeCanvas` goes through `NodeCanvas::apply`,
+/// which records the operation together with its inverse for undo/redo.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum CanvasOperation {
+    AddNode(VisualNode),
+    RemoveNode(String), // Also removes the node's connections
+    MoveNode { node_id: String, position: Point },
+    ResizeNode { node_id: String, size: (f64, f64) },
+    SetProperty { node_id: String, name: String, value: Option<String> }, // None removes the property
+    SetControlType { node_id: String, control_type: NodeControlType },
+    AddConnection(NodeConnection),
+    RemoveConnection(String),
+    Batch(Vec<CanvasOperation>), // Applied atomically, undone as a single step
+}
+
+impl CanvasOperation {
+    /// Name, subject and resulting state of the operation for the audit log
+    fn audit_entry(&self) -> (&'static str, String, Option<String>) {
+        match self {
+            CanvasOperation::AddNode(node) => {
+                ("add_node", node.id.clone(), Some(format!("{} at {:?}", node.component_id, node.position)))
+            }
+            CanvasOperation::RemoveNode(node_id) => ("remove_node", node_id.clone(), None),
+            CanvasOperation::MoveNode { node_id, position } => ("move_node", node_id.clone(), Some(format!("{:?}", position))),
+            CanvasOperation::ResizeNode { node_id, size } => ("resize_node", node_id.clone(), Some(format!("{} x {}", size.0, size.1))),
+            CanvasOperation::SetProperty { node_id, name, value } => ("set_property", format!("{}.{}", node_id, name), value.as_ref()
+                .map(|value| if audit::is_sensitive(name) { audit::REDACTED.to_string() } else { value.clone() })),
+            CanvasOperation::SetControlType { node_id, control_type } => {
+                ("set_control_type", node_id.clone(), Some(format!("{:?}", control_type)))
+            }
+            CanvasOperation::AddConnection(connection) => ("add_connection", connection.id.clone(), Some(format!(
+                "{}:{} -> {}:{}", connection.from_node, connection.from_port, connection.to_node, connection.to_port
+            ))),
+            CanvasOperation::RemoveConnection(connection_id) => ("remove_connection", connection_id.clone(), None),
+            CanvasOperation::Batch(operations) => ("batch", format!("{} operations", operations.len()), None),
+        }
+    }
+}
+
+/// Operation log entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CanvasHistoryEntry {
+    pub operation: CanvasOperation,
+    pub inverse: CanvasOperation,
+}
+
+/// Listener notified after an operation is applied, undone or redone, with the new canvas version
+pub type CanvasListener = Arc<dyn Fn(&CanvasOperation, u64) + Send + Sync>;
+
+/// Registered canvas listeners (not serialized; clones share the callbacks)
+#[derive(Clone, Default)]
+pub struct CanvasListeners {
+    next_id: u64,
+    listeners: Vec<(u64, CanvasListener)>,
+}
+
+impl fmt::Debug for CanvasListeners {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CanvasListeners")
+            .field("count", &self.listeners.len())
+            .finish()
+    }
+}
+
+fn default_history_limit() -> usize {
+    100
+}
+
+/// Visual node canvas definition with DAG (Directed Acyclic Graph) support
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeCanvas {
+    pub nodes: HashMap<String, VisualNode>,
+    pub connections: HashMap<String, NodeConnection>,
+    pub canvas_size: (f64, f64),
+    pub zoom: f64,
+    pub pan_offset: (f64, f64),
+    #[serde(serialize_with = "stable_ids::sorted_set")]
+    pub selected_nodes: HashSet<String>,
+    #[serde(serialize_with = "stable_ids::sorted_set")]
+    pub highlighted_nodes: HashSet<String>,
+    pub user_data: HashMap<String, String>,
+    pub entry_points: Vec<String>, // DAG entry points
+    pub exit_points: Vec<String>, // DAG exit points
+    pub execution_order: Vec<String>, // Cached topological order
+    pub has_cycle: bool, // Flag indicating if graph contains cycles
+    #[serde(default)]
+    pub id_strategy: IdStrategy, // How new node and connection IDs are generated
+    
+    // Operation log for undo/redo
+    #[serde(skip)]
+    pub operation_history: VecDeque<CanvasHistoryEntry>,
+    #[serde(default = "default_history_limit")]
+    pub history_limit: usize,
+    #[serde(skip)]
+    pub history_position: usize, // Number of applied entries; entries past it can be redone
+    
+    // Real-time editing state
+    #[serde(default)]
+    pub canvas_version: u64,
+    #[serde(default)]
+    pub is_dirty: bool,
+    #[serde(default)]
+    pub last_updated: u64, // Timestamp for last update
+    #[serde(skip)]
+    pub update_listeners: CanvasListeners,
+    
+    // Viewport virtualization (rebuilt with `rebuild_spatial_index` after loading)
+    #[serde(skip)]
+    pub spatial_index: SpatialIndex,
+}
+
+impl VisualNode {
+    /// Create a new visual node from a component
+    pub fn new(component: Component, position: Point) -> Result<Self, ComponentManagerError> {
+        // Generate a unique ID for the node
+        let id = format!("node_{}_{}", component.id, Uuid::new_v4());
+        Self::with_id(component, position, id)
+    }
+    
+    /// Create a visual node with a given ID
+    ///
+    /// Port IDs are derived from the node ID, so they are as stable as the node ID is.
+    pub fn with_id(component: Component, position: Point, id: String) -> Result<Self, ComponentManagerError> {
+        // Create visual ports from component ports
+        let ports = component.ports.iter()
+            .enumerate()
+            .map(|(index, port)| {
+                // Calculate relative position based on port direction and index
+                let position = match port.direction {
+                    crate::component_manager::component::PortDirection::Input => {
+                        (10.0, 30.0 + (index as f64 * 25.0))
+                    },
+                    crate::component_manager::component::PortDirection::Output => {
+                        (190.0, 30.0 + (index as f64 * 25.0))
+                    },
+                    crate::component_manager::component::PortDirection::Bidirectional => {
+                        (100.0, 30.0 + (index as f64 * 25.0))
+                    },
+                };
+                
+                VisualNodePort {
+                    id: format!("port_{}_{}", port.name, stable_ids::derive_uuid(&["port", &id, &port.name])),
+                    name: port.name.clone(),
+                    port_type: port.port_type.clone(),
+                    direction: port.direction.clone(),
+                    position,
+                    connected_to: None,
+                    description: port.description.clone(),
+                }
+            })
+            .collect();
+        
+        // Create default properties from component properties
+        let mut properties = HashMap::new();
+        for prop in &component.properties {
+            if let Some(default_value) = &prop.default_value {
+                properties.insert(prop.name.clone(), default_value.clone());
+            }
+        }
+        
+        // Create default style based on component category
+        let style = NodeStyle::default_for_component(&component);
+        
+        Ok(Self {
+            id,
+            component_id: component.id.clone(),
+            component,
+            position,
+            size: (200.0, 150.0), // Default size
+            z_index: 0,
+            style,
+            ports,
+            properties,
+            selected: false,
+            expanded: false,
+            user_data: HashMap::new(),
+            
+            // Control flow configuration
+            control_type: NodeControlType::Sequential,
+            loop_config: None,
+            conditional_config: None,
+            recursive_target_id: None,
+            parallel_branches: Vec::new(),
+            
+            // State management
+            state_history: VecDeque::with_capacity(50),
+            history_limit: 50,
+            state_version: 0,
+            is_dirty: false,
+            last_updated: 0,
+            
+            // Debugging and data visualization
+            debug_info: NodeDebugInfo::default(),
+            data_visualization: DataVisualizationConfig::default(),
+            current_data_values: HashMap::new(),
+        })
+    }
+    
+    /// Get the bounding rectangle of the node
+    pub fn get_bounds(&self) -> Rect {
+        Rect::new(self.position, self.size)
+    }
+    
+    /// Get the bounds of the node in canvas coordinates
+    pub fn canvas_bounds(&self) -> CanvasBounds {
+        CanvasBounds::new(self.position.x, self.position.y, self.size.0, self.size.1)
+    }
+    
+    /// Update node position and track state change
+    pub fn set_position(&mut self, new_position: Point, track_history: bool) {
+        if track_history && self.position != new_position {
+            let change = NodeStateChange::PositionChanged(self.position, new_position);
+            self.add_state_change(change);
+        }
+        
+        self.position = new_position;
+        self.update_state_version();
+    }
+    
+    /// Update node size and track state change
+    pub fn set_size(&mut self, new_size: (f64, f64), track_history: bool) {
+        if track_history && self.size != new_size {
+            let change = NodeStateChange::SizeChanged(self.size, new_size);
+            self.add_state_change(change);
+        }
+        
+        self.size = new_size;
+        self.update_state_version();
+    }
+    
+    /// Update node property and track state change
+    pub fn set_property(&mut self, property_name: String, new_value: String, track_history: bool) {
+        let old_value = self.properties.get(&property_name).cloned().unwrap_or_default();
+        
+        if track_history && old_value != new_value {
+            let change = NodeStateChange::PropertyChanged(property_name.clone(), old_value, new_value.clone());
+            self.add_state_change(change);
+        }
+        
+        self.properties.insert(property_name, new_value);
+        self.update_state_version();
+    }
+    
+    /// Update node style and track state change
+    pub fn set_style(&mut self, new_style: NodeStyle, track_history: bool) {
+        if track_history {
+            let change = NodeStateChange::StyleChanged(self.style.clone(), new_style.clone());
+            self.add_state_change(change);
+        }
+        
+        self.style = new_style;
+        self.update_state_version();
+    }
+    
+    /// Update node selection state and track state change
+    pub fn set_selected(&mut self, new_selected: bool, track_history: bool) {
+        if track_history && self.selected != new_selected {
+            let change = NodeStateChange::SelectionChanged(self.selected, new_selected);
+            self.add_state_change(change);
+        }
+        
+        self.selected = new_selected;
+        self.update_state_version();
+    }
+    
+    /// Update node expansion state and track state change
+    pub fn set_expanded(&mut self, new_expanded: bool, track_history: bool) {
+        if track_history && self.expanded != new_expanded {
+            let change = NodeStateChange::ExpansionChanged(self.expanded, new_expanded);
+            self.add_state_change(change);
+        }
+        
+        self.expanded = new_expanded;
+        self.update_state_version();
+    }
+    
+    /// Add a state change to history
+    fn add_state_change(&mut self, change: NodeStateChange) {
+        // If we've reached the history limit, remove the oldest change
+        if self.state_history.len() >= self.history_limit {
+            self.state_history.pop_front();
+        }
+        
+        // Add the new change
+        self.state_history.push_back(change);
+        
+        // Mark node as dirty
+        self.is_dirty = true;
+    }
+    
+    /// Update state version to indicate changes
+    fn update_state_version(&mut self) {
+        self.state_version += 1;
+        self.last_updated = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or(std::time::Duration::ZERO)
+            .as_millis() as u64;
+        
+        self.is_dirty = true;
+    }
+    
+    /// Reset dirty state
+    pub fn reset_dirty(&mut self) {
+        self.is_dirty = false;
+    }
+    
+    /// Check if a point is inside the node
+    pub fn contains_point(&self, point: Point) -> bool {
+        self.get_bounds().contains(point)
+    }
+    
+    /// Get a port by name
+    pub fn get_port_by_name(&self, name: &str) -> Option<&VisualNodePort> {
+        self.ports.iter().find(|port| port.name == name)
+    }
+    
+    /// Get a port by ID
+    pub fn get_port_by_id(&self, id: &str) -> Option<&VisualNodePort> {
+        self.ports.iter().find(|port| port.id == id)
+    }
+    
+    /// Update a property value
+    pub fn update_property(&mut self, name: &str, value: &str) -> Result<(), ComponentManagerError> {
+        // Validate property exists
+        if !self.component.properties.iter().any(|p| p.name == name) {
+            return Err(ComponentManagerError::PropertyError(format!("Property {} not found", name)));
+        }
+        
+        // Validate property value (basic validation)
+        let prop = self.component.properties.iter().find(|p| p.name == name).unwrap();
+        if let Some(valid_values) = &prop.valid_values {
+            if !valid_values.contains(&value.to_string()) {
+                return Err(ComponentManagerError::PropertyError(
+                    format!("Invalid value for property {}: {}", name, value)
+                ));
+            }
+        }
+        
+        self.properties.insert(name.to_string(), value.to_string());
+        Ok(())
+    }
+}
+
+impl NodeStyle {
+    /// Create a default style for a component based on its category
+    pub fn default_for_component(component: &Component) -> Self {
+        match component.category {
+            crate::component_manager::component::ComponentCategory::KernelCore => {
+                Self {
+                    background_color: Color::from_rgba8(60, 180, 240, 255),
+                    border_color: Color::from_rgba8(30, 120, 200, 255),
+                    border_width: 2.0,
+                    text_color: Color::from_rgba8(0, 0, 0, 255),
+                    font_size: 14.0,
+                    rounded_corners: 8.0,
+                    shadow_color: Some(Color::from_rgba8(0, 0, 0, 80)),
+                    shadow_offset: Some((3.0, 3.0)),
+                    shadow_blur: Some(5.0),
+                }
+            },
+            crate::component_manager::component::ComponentCategory::SystemServices => {
+                Self {
+                    background_color: Color::from_rgba8(120, 220, 180, 255),
+                    border_color: Color::from_rgba8(80, 180, 140, 255),
+                    border_width: 2.0,
+                    text_color: Color::from_rgba8(0, 0, 0, 255),
+                    font_size: 14.0,
+                    rounded_corners: 8.0,
+                    shadow_color: Some(Color::from_rgba8(0, 0, 0, 80)),
+                    shadow_offset: Some((3.0, 3.0)),
+                    shadow_blur: Some(5.0),
+                }
+            },
+            crate::component_manager::component::ComponentCategory::HardwareAbstraction => {
+                Self {
+                    background_color: Color::from_rgba8(240, 200, 120, 255),
+                    border_color: Color::from_rgba8(200, 160, 80, 255),
+                    border_width: 2.0,
+                    text_color: Color::from_rgba8(0, 0, 0, 255),
+                    font_size: 14.0,
+                    rounded_corners: 8.0,
+                    shadow_color: Some(Color::from_rgba8(0, 0, 0, 80)),
+                    shadow_offset: Some((3.0, 3.0)),
+                    shadow_blur: Some(5.0),
+                }
+            },
+            crate::component_manager::component::ComponentCategory::Cuda => {
+                Self {
+                    background_color: Color::from_rgba8(76, 175, 80, 255), // NVIDIA green
+                    border_color: Color::from_rgba8(56, 142, 60, 255),
+                    border_width: 2.0,
+                    text_color: Color::from_rgba8(255, 255, 255, 255), // White text for contrast
+                    font_size: 14.0,
+                    rounded_corners: 8.0,
+                    shadow_color: Some(Color::from_rgba8(0, 0, 0, 80)),
+                    shadow_offset: Some((3.0, 3.0)),
+                    shadow_blur: Some(5.0),
+                }
+            },
+            _ => {
+                // Default style for other categories
+                Self {
+                    background_color: Color::from_rgba8(220, 220, 220, 255),
+                    border_color: Color::from_rgba8(160, 160, 160, 255),
+                    border_width: 2.0,
+                    text_color: Color::from_rgba8(0, 0, 0, 255),
+                    font_size: 14.0,
+                    rounded_corners: 8.0,
+                    shadow_color: Some(Color::from_rgba8(0, 0, 0, 60)),
+                    shadow_offset: Some((2.0, 2.0)),
+                    shadow_blur: Some(4.0),
+                }
+            },
+        }
+    }
+}
+
+impl NodeCanvas {
+    /// Create a new empty canvas with DAG support and real-time editing
+    pub fn new() -> Self {
+        Self {
+            nodes: HashMap::new(),
+            connections: HashMap::new(),
+            canvas_size: (1000.0, 1000.0),
+            zoom: 1.0,
+            pan_offset: (0.0, 0.0),
+            selected_nodes: HashSet::new(),
+            highlighted_nodes: HashSet::new(),
+            user_data: HashMap::new(),
+            entry_points: Vec::new(),
+            exit_points: Vec::new(),
+            execution_order: Vec::new(),
+            has_cycle: false,
+            id_strategy: IdStrategy::default(),
+            
+            // Real-time editing and state management
+            operation_history: VecDeque::with_capacity(100),
+            history_limit: default_history_limit(),
+            history_position: 0,
+            canvas_version: 0,
+            is_dirty: false,
+            last_updated: 0,
+            update_listeners: CanvasListeners::default(),
+            spatial_index: SpatialIndex::default(),
+        }
+    }
+    
+    /// Create a node for a component with an ID generated by the canvas ID strategy
+    ///
+    /// Deterministic IDs number the nodes of each component in creation order. The node
+    /// is not added to the canvas.
+    pub fn create_node(&self, component: Component, position: Point) -> Result<VisualNode, ComponentManagerError> {
+        let id = match self.id_strategy {
+            IdStrategy::Random => format!("node_{}_{}", component.id, Uuid::new_v4()),
+            IdStrategy::Deterministic => (0u64..)
+                .map(|sequence| format!("node_{}_{}", component.id, stable_ids::derive_uuid(&["node", &component.id, &sequence.to_string()])))
+                .find(|id| !self.nodes.contains_key(id))
+                .unwrap_or_default(),
+        };
+        VisualNode::with_id(component, position, id)
+    }
+    
+    /// Generate a connection ID with the canvas ID strategy
+    ///
+    /// Deterministic IDs are derived from the connected ports, which identify a connection.
+    pub fn connection_id(&self, from_node: &str, from_port: &str, to_node: &str, to_port: &str) -> String {
+        format!("conn_{}", self.id_strategy.uuid(&["conn", from_node, from_port, to_node, to_port]))
+    }
+    
+    /// Serialize the canvas with sorted keys, so that equal canvases produce equal files
+    ///
+    /// Edit timestamps and dirty flags describe the editing session rather than the
+    /// project and are written as reset.
+    pub fn to_canonical_json(&self) -> Result<String, ComponentManagerError> {
+        let mut canvas = self.clone();
+        canvas.last_updated = 0;
+        canvas.is_dirty = false;
+        for node in canvas.nodes.values_mut() {
+            node.last_updated = 0;
+            node.is_dirty = false;
+        }
+        stable_ids::to_canonical_json(&canvas)
+            .map_err(|e| ComponentManagerError::VisualNodeError(format!("Failed to serialize canvas: {}", e)))
+    }
+    
+    /// Save the canvas to a JSON file in canonical form
+    pub fn save_to_file<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), ComponentManagerError> {
+        std::fs::write(path, self.to_canonical_json()?)
+            .map_err(|e| ComponentManagerError::VisualNodeError(format!("Failed to write canvas: {}", e)))
+    }
+    
+    /// Add a node to the canvas
+    pub fn add_node(&mut self, node: VisualNode) -> Result<(), ComponentManagerError> {
+        self.apply(CanvasOperation::AddNode(node))
+    }
+    
+    /// Remove a node and all of its connections from the canvas
+    pub fn remove_node(&mut self, node_id: &str) -> Result<(), ComponentManagerError> {
+        self.apply(CanvasOperation::RemoveNode(node_id.to_string()))
+    }
+    
+    /// Add a connection between two nodes with enhanced validation and data flow support
+    pub fn add_connection(&mut self, connection: NodeConnection) -> Result<(), ComponentManagerError> {
+        self.apply(CanvasOperation::AddConnection(connection))
+    }
+    
+    /// Remove a connection from the canvas
+    pub fn remove_connection(&mut self, connection_id: &str) -> Result<(), ComponentManagerError> {
+        self.apply(CanvasOperation::RemoveConnection(connection_id.to_string()))
+    }
+    
+    /// Apply an operation, record it in the operation log and notify listeners
+    ///
+    /// Applying a new operation discards any operations that were undone but not redone.
+    pub fn apply(&mut self, operation: CanvasOperation) -> Result<(), ComponentManagerError> {
+        let inverse = self.execute(&operation)?;
+        self.update_dag_properties();
+        self.update_canvas_version();
+        self.notify_listeners(&operation);
+        Self::audit("canvas", &operation, &inverse);
+        
+        self.operation_history.truncate(self.history_position);
+        if self.history_limit > 0 {
+            while self.operation_history.len() >= self.history_limit {
+                self.operation_history.pop_front();
+            }
+            self.operation_history.push_back(CanvasHistoryEntry { operation, inverse });
+        }
+        self.history_position = self.operation_history.len();
+        
+        Ok(())
+    }
+    
+    /// Undo the most recent applied operation, returning false if there is nothing to undo
+    pub fn undo(&mut self) -> Result<bool, ComponentManagerError> {
+        if !self.can_undo() {
+            return Ok(false);
+        }
+        
+        let inverse = self.operation_history[self.history_position - 1].inverse.clone();
+        self.execute(&inverse)?;
+        self.history_position -= 1;
+        self.update_dag_properties();
+        self.update_canvas_version();
+        self.notify_listeners(&inverse);
+        Self::audit("canvas.undo", &inverse, &self.operation_history[self.history_position].operation);
+        
+        Ok(true)
+    }
+    
+    /// Redo the most recently undone operation, returning false if there is nothing to redo
+    pub fn redo(&mut self) -> Result<bool, ComponentManagerError> {
+        if !self.can_redo() {
+            return Ok(false);
+        }
+        
+        let operation = self.operation_history[self.history_position].operation.clone();
+        self.execute(&operation)?;
+        self.history_position += 1;
+        self.update_dag_properties();
+        self.update_canvas_version();
+        self.notify_listeners(&operation);
+        Self::audit("canvas.redo", &operation, &self.operation_history[self.history_position - 1].inverse);
+        
+        Ok(true)
+    }
+    
+    /// Check whether an operation can be undone
+    pub fn can_undo(&self) -> bool {
+        self.history_position > 0
+    }
+    
+    /// Check whether an operation can be redone
+    pub fn can_redo(&self) -> bool {
+        self.history_position < self.operation_history.len()
+    }
+    
+    /// Get the operation log (entries past `history_position` have been undone)
+    pub fn history(&self) -> &VecDeque<CanvasHistoryEntry> {
+        &self.operation_history
+    }
+    
+    /// Clear the operation log
+    pub fn clear_history(&mut self) {
+        self.operation_history.clear();
+        self.history_position = 0;
+    }
+    
+    /// Register a listener and return its ID
+    pub fn add_listener(&mut self, listener: impl Fn(&CanvasOperation, u64) + Send + Sync + 'static) -> u64 {
+        let id = self.update_listeners.next_id;
+        self.update_listeners.next_id += 1;
+        self.update_listeners.listeners.push((id, Arc::new(listener)));
+        id
+    }
+    
+    /// Unregister a listener, returning whether it was registered
+    pub fn remove_listener(&mut self, listener_id: u64) -> bool {
+        let before = self.update_listeners.listeners.len();
+        self.update_listeners.listeners.retain(|(id, _)| *id != listener_id);
+        self.update_listeners.listeners.len() != before
+    }
+    
+    /// Notify all listeners of an applied operation
+    fn notify_listeners(&self, operation: &CanvasOperation) {
+        for (_, listener) in &self.update_listeners.listeners {
+            listener(operation, self.canvas_version);
+        }
+    }
+    
+    /// Record a performed operation in the audit log
+    ///
+    /// `reverted` is the operation that would undo it; when it targets the same subject
+    /// its resulting state is the state before the operation.
+    fn audit(prefix: &str, performed: &CanvasOperation, reverted: &CanvasOperation) {
+        let (name, subject, new) = performed.audit_entry();
+        let (_, reverted_subject, old) = reverted.audit_entry();
+        let old = if reverted_subject == subject { old } else { None };
+        audit::record(AuditCategory::Canvas, format!("{}.{}", prefix, name), subject, old, new);
+    }
+    
+    /// Update canvas version to indicate changes
+    fn update_canvas_version(&mut self) {
+        self.canvas_version += 1;
+        self.last_updated = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or(Duration::ZERO)
+            .as_millis() as u64;
+        
+        self.is_dirty = true;
+    }
+    
+    /// Get a mutable node or fail with a not-found error
+    fn node_mut(&mut self, node_id: &str) -> Result<&mut VisualNode, ComponentManagerError> {
+        self.nodes.get_mut(node_id).ok_or_else(|| ComponentManagerError::VisualNodeError(
+            format!("Node with ID {} not found", node_id)
+        ))
+    }
+    
+    /// Perform an operation without recording it, returning its inverse
+    fn execute(&mut self, operation: &CanvasOperation) -> Result<CanvasOperation, ComponentManagerError> {
+        match operation {
+            CanvasOperation::AddNode(node) => {
+                if self.nodes.contains_key(&node.id) {
+                    return Err(ComponentManagerError::VisualNodeError(
+                        format!("Node with ID {} already exists", node.id)
+                    ));
+                }
+                
+                self.nodes.insert(node.id.clone(), node.clone());
+                self.spatial_index.update_node(&node.id, node.canvas_bounds());
+                Ok(CanvasOperation::RemoveNode(node.id.clone()))
+            },
+            CanvasOperation::RemoveNode(node_id) => {
+                let node = self.nodes.remove(node_id).ok_or_else(|| ComponentManagerError::VisualNodeError(
+                    format!("Node with ID {} not found", node_id)
+                ))?;
+                self.selected_nodes.remove(node_id);
+                self.highlighted_nodes.remove(node_id);
+                
+                // Remove all connections to/from this node; the inverse restores them after the node
+                let connection_ids: Vec<String> = self.connections.values()
+                    .filter(|conn| conn.from_node == *node_id || conn.to_node == *node_id)
+                    .map(|conn| conn.id.clone())
+                    .collect();
+                let mut restore = vec![CanvasOperation::AddNode(node)];
+                for conn_id in connection_ids {
+                    if let Some(connection) = self.connections.remove(&conn_id) {
+                        self.spatial_index.remove_connection(&conn_id);
+                        restore.push(CanvasOperation::AddConnection(connection));
+                    }
+                }
+                self.spatial_index.remove_node(node_id);
+                
+                Ok(CanvasOperation::Batch(restore))
+            },
+            CanvasOperation::MoveNode { node_id, position } => {
+                let node = self.node_mut(node_id)?;
+                let old_position = node.position;
+                node.set_position(*position, false);
+                self.refresh_node_bounds(node_id);
+                Ok(CanvasOperation::MoveNode { node_id: node_id.clone(), position: old_position })
+            },
+            CanvasOperation::ResizeNode { node_id, size } => {
+                let node = self.node_mut(node_id)?;
+                let old_size = node.size;
+                node.set_size(*size, false);
+                self.refresh_node_bounds(node_id);
+                Ok(CanvasOperation::ResizeNode { node_id: node_id.clone(), size: old_size })
+            },
+            CanvasOperation::SetProperty { node_id, name, value } => {
+                let node = self.node_mut(node_id)?;
+                let old_value = node.properties.get(name).cloned();
+                match value {
+                    Some(value) => node.set_property(name.clone(), value.clone(), false),
+                    None => {
+                        node.properties.remove(name);
+                        node.update_state_version();
+                    },
+                }
+                Ok(CanvasOperation::SetProperty { node_id: node_id.clone(), name: name.clone(), value: old_value })
+            },
+            CanvasOperation::SetControlType { node_id, control_type } => {
+                let node = self.node_mut(node_id)?;
+                let old_control_type = std::mem::replace(&mut node.control_type, control_type.clone());
+                node.update_state_version();
+                Ok(CanvasOperation::SetControlType { node_id: node_id.clone(), control_type: old_control_type })
+            },
+            CanvasOperation::AddConnection(connection) => {
+                let validation_result = self.validate_connection(
+                    &connection.from_node,
+                    &connection.from_port,
+                    &connection.to_node,
+                    &connection.to_port
+                );
+                
+                let message = match validation_result {
+                    ConnectionValidationResult::Valid => None,
+                    ConnectionValidationResult::InvalidSourcePort => Some("Source port not found".to_string()),
+                    ConnectionValidationResult::InvalidTargetPort => Some("Target port not found".to_string()),
+                    ConnectionValidationResult::InvalidPortDirection => Some("Invalid port directions for connection".to_string()),
+                    ConnectionValidationResult::PortTypeMismatch => Some("Port type mismatch".to_string()),
+                    ConnectionValidationResult::CircularDependency => Some("Connection would create a circular dependency".to_string()),
+                    ConnectionValidationResult::AlreadyConnected => Some("Connection already exists".to_string()),
+                    ConnectionValidationResult::SelfConnection => Some("Cannot connect a node to itself".to_string()),
+                    ConnectionValidationResult::Other(msg) => Some(msg),
+                };
+                if let Some(message) = message {
+                    return Err(ComponentManagerError::VisualNodeError(message));
+                }
+                if self.connections.contains_key(&connection.id) {
+                    return Err(ComponentManagerError::VisualNodeError(
+                        format!("Connection with ID {} already exists", connection.id)
+                    ));
+                }
+                
+                self.connections.insert(connection.id.clone(), connection.clone());
+                self.index_connection(&connection.id);
+                Ok(CanvasOperation::RemoveConnection(connection.id.clone()))
+            },
+            CanvasOperation::RemoveConnection(connection_id) => {
+                let connection = self.connections.remove(connection_id).ok_or_else(|| ComponentManagerError::VisualNodeError(
+                    format!("Connection with ID {} not found", connection_id)
+                ))?;
+                self.spatial_index.remove_connection(connection_id);
+                Ok(CanvasOperation::AddConnection(connection))
+            },
+            CanvasOperation::Batch(operations) => {
+                let mut inverses = Vec::with_capacity(operations.len());
+                for operation in operations {
+                    match self.execute(operation) {
+                        Ok(inverse) => inverses.push(inverse),
+                        Err(e) => {
+                            // Roll back the part of the batch that was already applied
+                            for inverse in inverses.iter().rev() {
+                                let _ = self.execute(inverse);
+                            }
+                            return Err(e);
+                        },
+                    }
+                }
+                
+                inverses.reverse();
+                Ok(CanvasOperation::Batch(inverses))
+            },
+        }
+    }
+    
+    /// Validate a potential connection between nodes
+    pub fn validate_connection(&self, from_node: &str, from_port: &str, to_node: &str, to_port: &str) -> ConnectionValidationResult {
+        // Check for self-connection
+        if from_node == to_node {
+            return ConnectionValidationResult::SelfConnection;
+        }
+        
+        // Validate that nodes exist
+        let source_node = match self.nodes.get(from_node) {
+            Some(node) => node,
+            None => return ConnectionValidationResult::InvalidSourcePort,
+        };
+        
+        let target_node = match self.nodes.get(to_node) {
+            Some(node) => node,
+            None => return ConnectionValidationResult::InvalidTargetPort,
+        };
+        
+        // Validate that ports exist
+        let source_port = match source_node.ports.iter().find(|p| p.id == from_port) {
+            Some(port) => port,
+            None => return ConnectionValidationResult::InvalidSourcePort,
+        };
+        
+        let target_port = match target_node.ports.iter().find(|p| p.id == to_port) {
+            Some(port) => port,
+            None => return ConnectionValidationResult::InvalidTargetPort,
+        };
+        
+        // Validate port directions
+        if source_port.direction != crate::component_manager::component::PortDirection::Output &&
+           source_port.direction != crate::component_manager::component::PortDirection::Bidirectional {
+            return ConnectionValidationResult::InvalidPortDirection;
+        }
+        
+        if target_port.direction != crate::component_manager::component::PortDirection::Input &&
+           target_port.direction != crate::component_manager::component::PortDirection::Bidirectional {
+            return ConnectionValidationResult::InvalidPortDirection;
+        }
+        
+        // Validate port types match
+        if source_port.port_type != target_port.port_type {
+            return ConnectionValidationResult::PortTypeMismatch;
+        }
+        
+        // Check if connection already exists
+        for conn in self.connections.values() {
+            if conn.from_node == from_node && conn.from_port == from_port &&
+               conn.to_node == to_node && conn.to_port == to_port {
+                return ConnectionValidationResult::AlreadyConnected;
+            }
+        }
+        
+        // Check for potential circular dependency
+        if self.has_path(to_node, from_node) {
+            return ConnectionValidationResult::CircularDependency;
+        }
+        
+        ConnectionValidationResult::Valid
+    }
+    
+    /// Select a node
+    pub fn select_node(&mut self, node_id: &str, multiple: bool) -> Result<(), ComponentManagerError> {
+        if !self.nodes.contains_key(node_id) {
+            return Err(ComponentManagerError::VisualNodeError(
+                format!("Node with ID {} not found", node_id)
+            ));
+        }
+        
+        if !multiple {
+            // Clear previous selection
+            for node in self.nodes.values_mut() {
+                node.selected = false;
+            }
+            self.selected_nodes.clear();
+        }
+        
+        // Select the node
+        if let Some(node) = self.nodes.get_mut(node_id) {
+            node.selected = true;
+        }
+        self.selected_nodes.insert(node_id.to_string());
+        
+        Ok(())
+    }
+    
+    /// Deselect a node
+    pub fn deselect_node(&mut self, node_id: &str) -> Result<(), ComponentManagerError> {
+        if !self.nodes.contains_key(node_id) {
+            return Err(ComponentManagerError::VisualNodeError(
+                format!("Node with ID {} not found", node_id)
+            ));
+        }
+        
+        if let Some(node) = self.nodes.get_mut(node_id) {
+            node.selected = false;
+        }
+        self.selected_nodes.remove(node_id);
+        
+        Ok(())
+    }
+    
+    /// Clear selection
+    pub fn clear_selection(&mut self) {
+        for node in self.nodes.values_mut() {
+            node.selected = false;
+        }
+        self.selected_nodes.clear();
+    }
+    
+    /// Get nodes in a rectangle area
+    pub fn get_nodes_in_rect(&self, rect: Rect) -> Vec<&VisualNode> {
+        self.nodes_in_viewport(rect)
+    }
+    
+    /// Get the nodes overlapping a viewport in canvas coordinates, in z-index order
+    pub fn nodes_in_viewport(&self, viewport: Rect) -> Vec<&VisualNode> {
+        let area = CanvasBounds::from_rect(viewport);
+        let mut nodes: Vec<&VisualNode> = if self.spatial_index_is_current() {
+            self.spatial_index.query_nodes(&area).into_iter()
+                .filter_map(|node_id| self.nodes.get(node_id))
+                .collect()
+        } else {
+            self.nodes.values()
+                .filter(|node| node.canvas_bounds().intersects(&area))
+                .collect()
+        };
+        nodes.sort_by(|a, b| a.z_index.cmp(&b.z_index).then_with(|| a.id.cmp(&b.id)));
+        nodes
+    }
+    
+    /// Get the connections whose line may cross a viewport in canvas coordinates
+    pub fn connections_in_viewport(&self, viewport: Rect) -> Vec<&NodeConnection> {
+        let area = CanvasBounds::from_rect(viewport);
+        let mut connections: Vec<&NodeConnection> = if self.spatial_index_is_current() {
+            self.spatial_index.query_connections(&area).into_iter()
+                .filter_map(|connection_id| self.connections.get(connection_id))
+                .collect()
+        } else {
+            self.connections.values()
+                .filter(|connection| self.connection_bounds(connection).is_some_and(|bounds| bounds.intersects(&area)))
+                .collect()
+        };
+        connections.sort_by(|a, b| a.id.cmp(&b.id));
+        connections
+    }
+    
+    /// Convert widget bounds in screen coordinates to the visible canvas region
+    pub fn visible_region(&self, screen_bounds: Rect) -> Rect {
+        let zoom = if self.zoom > 0.0 { self.zoom } else { 1.0 };
+        let (pan_x, pan_y) = self.pan_offset;
+        Rect::new(
+            Point::new((screen_bounds.x - pan_x) / zoom, (screen_bounds.y - pan_y) / zoom),
+            ((screen_bounds.right() - screen_bounds.x) / zoom, (screen_bounds.bottom() - screen_bounds.y) / zoom),
+        )
+    }
+    
+    /// Re-index a node and its connections after its position or size was changed directly
+    pub fn refresh_node_bounds(&mut self, node_id: &str) {
+        let Some(bounds) = self.nodes.get(node_id).map(VisualNode::canvas_bounds) else {
+            return;
+        };
+        self.spatial_index.update_node(node_id, bounds);
+        let connection_ids: Vec<String> = self.spatial_index.connections_of(node_id).cloned().collect();
+        for connection_id in connection_ids {
+            self.index_connection(&connection_id);
+        }
+    }
+    
+    /// Rebuild the spatial index from scratch, e.g. after deserializing or replacing nodes
+    pub fn rebuild_spatial_index(&mut self) {
+        self.spatial_index.clear();
+        for node in self.nodes.values() {
+            self.spatial_index.update_node(&node.id, node.canvas_bounds());
+        }
+        let connection_ids: Vec<String> = self.connections.keys().cloned().collect();
+        for connection_id in connection_ids {
+            self.index_connection(&connection_id);
+        }
+    }
+    
+    /// Check whether the spatial index covers every node and connection
+    ///
+    /// A deserialized canvas starts with an empty index; queries scan linearly until it is rebuilt.
+    fn spatial_index_is_current(&self) -> bool {
+        self.spatial_index.node_count() == self.nodes.len()
+            && self.spatial_index.connection_count() == self.connections.len()
+    }
+    
+    /// Index a connection by the bounds of its endpoints and bend points
+    fn index_connection(&mut self, connection_id: &str) {
+        let Some(connection) = self.connections.get(connection_id) else {
+            return;
+        };
+        match self.connection_bounds(connection) {
+            Some(bounds) => {
+                let (from_node, to_node) = (connection.from_node.clone(), connection.to_node.clone());
+                self.spatial_index.update_connection(connection_id, &from_node, &to_node, bounds);
+            },
+            None => self.spatial_index.remove_connection(connection_id),
+        }
+    }
+    
+    /// Bounds covering both endpoint nodes, their ports and the bend points of a connection
+    fn connection_bounds(&self, connection: &NodeConnection) -> Option<CanvasBounds> {
+        let from_node = self.nodes.get(&connection.from_node)?;
+        let to_node = self.nodes.get(&connection.to_node)?;
+        
+        let mut bounds = from_node.canvas_bounds().union(&to_node.canvas_bounds());
+        for (node, port_id) in [(from_node, &connection.from_port), (to_node, &connection.to_port)] {
+            if let Some(port) = node.get_port_by_id(port_id) {
+                bounds.include_point(node.position.x + port.position.0, node.position.y + port.position.1);
+            }
+        }
+        for point in &connection.bend_points {
+            bounds.include_point(point.x, point.y);
+        }
+        Some(bounds)
+    }
+    
+    /// Get connections for a node
+    pub fn get_connections_for_node(&self, node_id: &str) -> Vec<&NodeConnection> {
+        self.connections.values()
+            .filter(|conn| conn.from_node == node_id || conn.to_node == node_id)
+            .collect()
+    }
+    
+    /// Update DAG properties including entry points, exit points, execution order and cycle detection
+    pub fn update_dag_properties(&mut self) {
+        // Update entry points (nodes with no incoming connections)
+        self.entry_points = self.nodes.keys()
+            .filter(|&node_id| {
+                !self.connections.values().any(|conn| conn.to_node == *node_id)
+            })
+            .cloned()
+            .collect();
+        self.entry_points.sort();
+        
+        // Update exit points (nodes with no outgoing connections)
+        self.exit_points = self.nodes.keys()
+            .filter(|&node_id| {
+                !self.connections.values().any(|conn| conn.from_node == *node_id)
+            })
+            .cloned()
+            .collect();
+        self.exit_points.sort();
+        
+        // Detect cycles and generate topological order
+        let (order, has_cycle) = self.topological_sort();
+        self.execution_order = order;
+        self.has_cycle = has_cycle;
+    }
+    
+    /// Perform topological sort on the node graph
+    fn topological_sort(&self) -> (Vec<String>, bool) {
+        if self.nodes.is_empty() {
+            return (Vec::new(), false);
+        }
+        
+        // Build adjacency list and in-degree map
+        let mut adjacency: HashMap<String, Vec<String>> = HashMap::new();
+        let mut in_degree: HashMap<String, usize> = HashMap::new();
+        
+        // Initialize adjacency list and in-degree map
+        for node_id in self.nodes.keys() {
+            adjacency.insert(node_id.clone(), Vec::new());
+            in_degree.insert(node_id.clone(), 0);
+        }
+        
+        // Build adjacency list and in-degree map
+        for conn in self.connections.values() {
+            adjacency.get_mut(&conn.from_node).unwrap().push(conn.to_node.clone());
+            *in_degree.get_mut(&conn.to_node).unwrap() += 1;
+        }
+        // Visit nodes in ID order so the result does not depend on hash order
+        for neighbors in adjacency.values_mut() {
+            neighbors.sort();
+        }
+        
+        // Kahn's algorithm for topological sorting
+        let mut queue: Vec<String> = self.nodes.keys()
+            .filter(|&node_id| in_degree.get(node_id) == Some(&0))
+            .cloned()
+            .collect();
+        queue.sort();
+            
+        let mut order = Vec::new();
+        let mut processed = 0;
+        
+        while !queue.is_empty() {
+            let current = queue.remove(0);
+            order.push(current.clone());
+            processed += 1;
+            
+            // Decrease in-degree for all neighbors
+            if let Some(neighbors) = adjacency.get(&current) {
+                for neighbor in neighbors {
+                    if let Some(degree) = in_degree.get_mut(neighbor) {
+                        *degree -= 1;
+                        if *degree == 0 {
+                            queue.push(neighbor.clone());
+                        }
+                    }
+                }
+            }
+        }
+        
+        // Check for cycles
+        let has_cycle = processed != self.nodes.len();
+        
+        (order, has_cycle)
+    }
+    
+    /// Check if the graph is a valid DAG (Directed Acyclic Graph)
+    pub fn is_valid_dag(&self) -> bool {
+        !self.has_cycle
+    }
+    
+    /// Get execution order for the nodes (topological sort)
+    pub fn get_execution_order(&self) -> &Vec<String> {
+        &self.execution_order
+    }
+    
+    /// Execute the DAG in topological order, supporting complex control flow
+    pub fn execute_dag(&self) -> Result<(), ComponentManagerError> {
+        if self.has_cycle {
+            return Err(ComponentManagerError::VisualNodeError(
+                "Cannot execute DAG with cycles".to_string()
+            ));
+        }
+        
+        // Execute nodes in topological order with control flow support
+        for node_id in &self.execution_order {
+            if let Some(node) = self.nodes.get(node_id) {
+                // Execute node with control flow handling
+                self.execute_node_with_control_flow(node)?;
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /// Execute a single node with control flow handling
+    fn execute_node_with_control_flow(&self, node: &VisualNode) -> Result<(), ComponentManagerError> {
+        println!("Executing node: {}", node.component.name);
+        
+        // Handle different control flow types
+        match &node.control_type {
+            NodeControlType::Sequential => {
+                // Normal sequential execution
+                self.execute_node_logic(node)?;
+            },
+            NodeControlType::Conditional => {
+                // Handle conditional branching
+                self.execute_conditional_node(node)?;
+            },
+            NodeControlType::Loop => {
+                // Handle loop execution
+                self.execute_loop_node(node)?;
+            },
+            NodeControlType::Recursive => {
+                // Handle recursive execution
+                self.execute_recursive_node(node)?;
+            },
+            NodeControlType::Parallel => {
+                // Handle parallel execution
+                self.execute_parallel_node(node)?;
+            },
+            NodeControlType::Switch => {
+                // Handle switch-case execution
+                self.execute_switch_node(node)?;
+            },
+            NodeControlType::TryCatch => {
+                // Handle try-catch execution
+                self.execute_try_catch_node(node)?;
+            },
+        }
+        
+        Ok(())
+    }
+    
+    /// Execute basic node logic
+    fn execute_node_logic(&self, node: &VisualNode) -> Result<(), ComponentManagerError> {
+        // Basic node execution logic (to be implemented)
+        Ok(())
+    }
+    
+    /// Execute conditional node
+    fn execute_conditional_node(&self, node: &VisualNode) -> Result<(), ComponentManagerError> {
+        // Placeholder for conditional execution logic
+        Ok(())
+    }
+    
+    /// Execute loop node
+    fn execute_loop_node(&self, node: &VisualNode) -> Result<(), ComponentManagerError> {
+        // Placeholder for loop execution logic
+        Ok(())
+    }
+    
+    /// Execute recursive node
+    fn execute_recursive_node(&self, node: &VisualNode) -> Result<(), ComponentManagerError> {
+        // Placeholder for recursive execution logic
+        Ok(())
+    }
+    
+    /// Execute parallel node
+    fn execute_parallel_node(&self, node: &VisualNode) -> Result<(), ComponentManagerError> {
+        // Placeholder for parallel execution logic
+        Ok(())
+    }
+    
+    /// Execute switch node
+    fn execute_switch_node(&self, node: &VisualNode) -> Result<(), ComponentManagerError> {
+        // Placeholder for switch execution logic
+        Ok(())
+    }
+    
+    /// Execute try-catch node
+    fn execute_try_catch_node(&self, node: &VisualNode) -> Result<(), ComponentManagerError> {
+        // Placeholder for try-catch execution logic
+        Ok(())
+    }
+    
+    /// Detect recursive structures in the graph
+    pub fn detect_recursive_structures(&self) -> Vec<(String, String)> {
+        let mut recursive_pairs = Vec::new();
+        
+        // Check for nodes that reference themselves or form cycles
+        for node in self.nodes.values() {
+            if let Some(target_id) = &node.recursive_target_id {
+                if node.id == *target_id {
+                    // Direct self-recursion
+                    recursive_pairs.push((node.id.clone(), target_id.clone()));
+                } else if self.has_path(target_id, &node.id) {
+                    // Indirect recursion
+                    recursive_pairs.push((node.id.clone(), target_id.clone()));
+                }
+            }
+        }
+        
+        recursive_pairs
+    }
+    
+    /// Reset dirty state
+    pub fn reset_dirty(&mut self) {
+        self.is_dirty = false;
+        
+        // Reset dirty state for all nodes
+        for node in self.nodes.values_mut() {
+            node.reset_dirty();
+        }
+    }
+    
+    /// Update node debug information
+    pub fn update_debug_info(&mut self, node_id: &str, update_fn: impl FnOnce(&mut NodeDebugInfo)) {
+        if let Some(node) = self.nodes.get_mut(node_id) {
+            update_fn(&mut node.debug_info);
+        }
+    }
+    
+    /// Update node data visualization configuration
+    pub fn update_data_visualization(&mut self, node_id: &str, update_fn: impl FnOnce(&mut DataVisualizationConfig)) {
+        if let Some(node) = self.nodes.get_mut(node_id) {
+            update_fn(&mut node.data_visualization);
+        }
+    }
+    
+    /// Update current data values for a node
+    pub fn update_data_values(&mut self, node_id: &str, port_name: &str, value: String) {
+        if let Some(node) = self.nodes.get_mut(node_id) {
+            node.current_data_values.insert(port_name.to_string(), value);
+        }
+    }
+    
+    /// Clear all debug information
+    pub fn clear_debug_info(&mut self) {
+        for node in self.nodes.values_mut() {
+            node.debug_info = NodeDebugInfo::default();
+        }
+    }
+    
+    /// Get execution statistics for the entire graph
+    pub fn get_execution_statistics(&self) -> HashMap<String, u64> {
+        let mut stats = HashMap::new();
+        let mut total_executions = 0;
+        let mut total_time_nanos = 0;
+        
+        for node in self.nodes.values() {
+            total_executions += node.debug_info.execution_count;
+            total_time_nanos += node.debug_info.execution_time.as_nanos() as u64;
+        }
+        
+        stats.insert("total_executions".to_string(), total_executions);
+        stats.insert("total_time_nanos".to_string(), total_time_nanos);
+        stats.insert("node_count".to_string(), self.nodes.len() as u64);
+        stats
+    }
+    
+    /// Check if there's a path from start_node_id to end_node_id
+    fn has_path(&self, start_node_id: &str, end_node_id: &str) -> bool {
+        let mut visited = HashSet::new();
+        self.dfs_has_path(start_node_id, end_node_id, &mut visited)
+    }
+    
+    /// Depth-first search to check for path
+    fn dfs_has_path(&self, current: &str, target: &str, visited: &mut HashSet<String>) -> bool {
+        if current == target {
+            return true;
+        }
+        
+        if visited.contains(current) {
+            return false;
+        }
+        
+        visited.insert(current.to_string());
+        
+        // Check all outgoing connections
+        for conn in self.connections.values() {
+            if conn.from_node == current {
+                if self.dfs_has_path(&conn.to_node, target, visited) {
+                    return true;
+                }
+            }
+        }
+        
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use crate::component_manager::component::{ComponentCategory, ComponentPort, ComponentType, PortDirection};
+    
+    fn node(name: &str) -> VisualNode {
+        let port = |name: &str, direction: PortDirection| ComponentPort {
+            name: name.to_string(),
+            port_type: "data".to_string(),
+            direction,
+            description: String::new(),
+        };
+        let component = Component {
+            id: name.to_string(),
+            name: name.to_string(),
+            display_name: name.to_string(),
+            component_type: ComponentType::Other,
+            category: ComponentCategory::KernelCore,
+            version: "1.0.0".to_string(),
+            description: String::new(),
+            author: String::new(),
+            source_url: None,
+            license: String::new(),
+            properties: Vec::new(),
+            ports: vec![port("in", PortDirection::Input), port("out", PortDirection::Output)],
+            dependencies: Vec::new(),
+            supported_architectures: Default::default(),
+            supported_languages: Vec::new(),
+            implementation_files: Vec::new(),
+            build_commands: Vec::new(),
+            initialization_code: String::new(),
+        };
+        VisualNode::new(component, Point::new(0.0, 0.0)).unwrap()
+    }
+    
+    fn connection(from: &VisualNode, to: &VisualNode) -> NodeConnection {
+        NodeConnection {
+            id: format!("{}->{}", from.id, to.id),
+            from_node: from.id.clone(),
+            from_port: from.get_port_by_name("out").unwrap().id.clone(),
+            to_node: to.id.clone(),
+            to_port: to.get_port_by_name("in").unwrap().id.clone(),
+            connection_type: "default".to_string(),
+            color: Color::from_rgba8(0, 0, 0, 255),
+            line_width: 2.0,
+            description: String::new(),
+            data_flow_info: DataFlowInfo {
+                data_type: "data".to_string(),
+                data_size: None,
+                flow_rate: None,
+                last_value_preview: None,
+                is_active: false,
+                transmission_time: Duration::ZERO,
+            },
+            is_highlighted: false,
+            is_selected: false,
+            label: None,
+            bend_points: Vec::new(),
+            animation_speed: 1.0,
+            show_data_flow: false,
+        }
+    }
+    
+    fn canvas_with_pair() -> (NodeCanvas, String, String, String) {
+        let mut canvas = NodeCanvas::new();
+        let (a, b) = (node("a"), node("b"));
+        let conn = connection(&a, &b);
+        let ids = (a.id.clone(), b.id.clone(), conn.id.clone());
+        canvas.add_node(a).unwrap();
+        canvas.add_node(b).unwrap();
+        canvas.add_connection(conn).unwrap();
+        (canvas, ids.0, ids.1, ids.2)
+    }
+    
+    #[test]
+    fn test_add_and_remove_node() {
+        let mut canvas = NodeCanvas::new();
+        let a = node("a");
+        let id = a.id.clone();
+        canvas.apply(CanvasOperation::AddNode(a.clone())).unwrap();
+        assert!(canvas.nodes.contains_key(&id));
+        assert!(canvas.add_node(a).is_err());
+        
+        canvas.undo().unwrap();
+        assert!(canvas.nodes.is_empty());
+        canvas.redo().unwrap();
+        assert!(canvas.nodes.contains_key(&id));
+        
+        canvas.apply(CanvasOperation::RemoveNode(id.clone())).unwrap();
+        assert!(canvas.nodes.is_empty());
+        assert!(canvas.remove_node(&id).is_err());
+    }
+    
+    #[test]
+    fn test_remove_node_undo_restores_connections() {
+        let (mut canvas, a, b, conn) = canvas_with_pair();
+        canvas.remove_node(&a).unwrap();
+        assert!(canvas.connections.is_empty());
+        assert_eq!(canvas.entry_points, vec![b.clone()]);
+        
+        canvas.undo().unwrap();
+        assert!(canvas.nodes.contains_key(&a));
+        assert!(canvas.connections.contains_key(&conn));
+        assert_eq!(canvas.execution_order, vec![a, b]);
+    }
+    
+    #[test]
+    fn test_add_and_remove_connection() {
+        let (mut canvas, a, b, conn) = canvas_with_pair();
+        
+        // The reverse connection would close a cycle
+        let reverse = connection(&canvas.nodes[&b], &canvas.nodes[&a]);
+        assert!(canvas.add_connection(reverse).is_err());
+        
+        canvas.apply(CanvasOperation::RemoveConnection(conn.clone())).unwrap();
+        assert!(canvas.connections.is_empty());
+        canvas.undo().unwrap();
+        assert!(canvas.connections.contains_key(&conn));
+        canvas.undo().unwrap();
+        assert!(canvas.connections.is_empty());
+    }
+    
+    #[test]
+    fn test_move_and_resize_node() {
+        let (mut canvas, a, _, _) = canvas_with_pair();
+        canvas.apply(CanvasOperation::MoveNode { node_id: a.clone(), position: Point::new(40.0, 60.0) }).unwrap();
+        canvas.apply(CanvasOperation::ResizeNode { node_id: a.clone(), size: (300.0, 100.0) }).unwrap();
+        assert_eq!(canvas.nodes[&a].position, Point::new(40.0, 60.0));
+        assert_eq!(canvas.nodes[&a].size, (300.0, 100.0));
+        
+        canvas.undo().unwrap();
+        canvas.undo().unwrap();
+        assert_eq!(canvas.nodes[&a].position, Point::new(0.0, 0.0));
+        assert_eq!(canvas.nodes[&a].size, (200.0, 150.0));
+        
+        let missing = CanvasOperation::MoveNode { node_id: "missing".to_string(), position: Point::new(1.0, 1.0) };
+        assert!(canvas.apply(missing).is_err());
+    }
+    
+    #[test]
+    fn test_set_property_and_control_type() {
+        let (mut canvas, a, _, _) = canvas_with_pair();
+        canvas.apply(CanvasOperation::SetProperty { node_id: a.clone(), name: "priority".to_string(), value: Some("high".to_string()) }).unwrap();
+        canvas.apply(CanvasOperation::SetControlType { node_id: a.clone(), control_type: NodeControlType::Loop }).unwrap();
+        assert_eq!(canvas.nodes[&a].properties.get("priority").map(String::as_str), Some("high"));
+        assert_eq!(canvas.nodes[&a].control_type, NodeControlType::Loop);
+        
+        canvas.undo().unwrap();
+        assert_eq!(canvas.nodes[&a].control_type, NodeControlType::Sequential);
+        canvas.undo().unwrap();
+        assert!(!canvas.nodes[&a].properties.contains_key("priority"));
+    }
+    
+    #[test]
+    fn test_batch_is_atomic() {
+        let (mut canvas, a, b, _) = canvas_with_pair();
+        let failing = CanvasOperation::Batch(vec![
+            CanvasOperation::MoveNode { node_id: a.clone(), position: Point::new(10.0, 10.0) },
+            CanvasOperation::RemoveNode("missing".to_string()),
+        ]);
+        assert!(canvas.apply(failing).is_err());
+        assert_eq!(canvas.nodes[&a].position, Point::new(0.0, 0.0));
+        
+        let history_len = canvas.history().len();
+        canvas.apply(CanvasOperation::Batch(vec![
+            CanvasOperation::RemoveNode(a.clone()),
+            CanvasOperation::RemoveNode(b.clone()),
+        ])).unwrap();
+        assert!(canvas.nodes.is_empty());
+        assert_eq!(canvas.history().len(), history_len + 1);
+        
+        canvas.undo().unwrap();
+        assert_eq!(canvas.nodes.len(), 2);
+        assert_eq!(canvas.connections.len(), 1);
+    }
+    
+    #[test]
+    fn test_new_operation_discards_redo() {
+        let (mut canvas, a, _, _) = canvas_with_pair();
+        canvas.undo().unwrap();
+        assert!(canvas.can_redo());
+        
+        canvas.apply(CanvasOperation::MoveNode { node_id: a, position: Point::new(5.0, 5.0) }).unwrap();
+        assert!(!canvas.can_redo());
+        assert_eq!(canvas.redo().unwrap(), false);
+        
+        canvas.history_limit = 2;
+        canvas.add_node(node("c")).unwrap();
+        assert_eq!(canvas.history().len(), 2);
+    }
+    
+    #[test]
+    fn test_listeners() {
+        let mut canvas = NodeCanvas::new();
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = seen.clone();
+        let listener = canvas.add_listener(move |operation, version| {
+            let kind = match operation {
+                CanvasOperation::AddNode(_) => "add",
+                CanvasOperation::RemoveNode(_) => "remove",
+                _ => "other",
+            };
+            sink.lock().unwrap().push((kind, version));
+        });
+        
+        canvas.add_node(node("a")).unwrap();
+        canvas.undo().unwrap();
+        assert_eq!(*seen.lock().unwrap(), vec![("add", 1), ("remove", 2)]);
+        
+        assert!(canvas.remove_listener(listener));
+        canvas.redo().unwrap();
+        assert_eq!(seen.lock().unwrap().len(), 2);
+        assert!(canvas.is_dirty);
+    }
+    
+    #[test]
+    fn test_viewport_queries() {
+        let (mut canvas, a, b, conn) = canvas_with_pair();
+        canvas.apply(CanvasOperation::MoveNode { node_id: b.clone(), position: Point::new(5000.0, 5000.0) }).unwrap();
+        
+        let origin = Rect::new(Point::new(-10.0, -10.0), (300.0, 300.0));
+        let ids = |nodes: Vec<&VisualNode>| nodes.into_iter().map(|node| node.id.clone()).collect::<Vec<_>>();
+        assert_eq!(ids(canvas.nodes_in_viewport(origin)), vec![a.clone()]);
+        assert_eq!(ids(canvas.nodes_in_viewport(Rect::new(Point::new(5100.0, 5100.0), (10.0, 10.0)))), vec![b.clone()]);
+        assert_eq!(canvas.connections_in_viewport(origin).len(), 1);
+        assert!(canvas.connections_in_viewport(Rect::new(Point::new(-500.0, -500.0), (10.0, 10.0))).is_empty());
+        
+        // Undo moves the node back into the viewport
+        canvas.undo().unwrap();
+        assert_eq!(canvas.nodes_in_viewport(origin).len(), 2);
+        
+        canvas.remove_connection(&conn).unwrap();
+        assert!(canvas.connections_in_viewport(origin).is_empty());
+        
+        // A deserialized canvas answers queries before and after the index is rebuilt
+        let mut loaded: NodeCanvas = serde_json::from_str(&serde_json::to_string(&canvas).unwrap()).unwrap();
+        assert_eq!(loaded.nodes_in_viewport(origin).len(), 2);
+        loaded.rebuild_spatial_index();
+        assert_eq!(loaded.spatial_index.node_count(), 2);
+        assert_eq!(loaded.nodes_in_viewport(origin).len(), 2);
+        
+        // Direct position changes are picked up after refreshing the node bounds
+        loaded.nodes.get_mut(&a).unwrap().position = Point::new(-1000.0, -1000.0);
+        loaded.refresh_node_bounds(&a);
+        assert_eq!(ids(loaded.nodes_in_viewport(origin)), vec![b]);
+    }
+    
+    #[test]
+    fn test_deterministic_ids_and_canonical_json() {
+        let build = || {
+            let mut canvas = NodeCanvas::new();
+            canvas.id_strategy = IdStrategy::Deterministic;
+            let a = canvas.create_node(node("a").component, Point::new(0.0, 0.0)).unwrap();
+            canvas.add_node(a.clone()).unwrap();
+            let b = canvas.create_node(node("b").component, Point::new(300.0, 0.0)).unwrap();
+            canvas.add_node(b.clone()).unwrap();
+            
+            let mut conn = connection(&a, &b);
+            conn.id = canvas.connection_id(&conn.from_node, &conn.from_port, &conn.to_node, &conn.to_port);
+            canvas.add_connection(conn).unwrap();
+            canvas.select_node(&a.id, true).unwrap();
+            canvas.select_node(&b.id, true).unwrap();
+            canvas
+        };
+        
+        let (first, second) = (build(), build());
+        let mut first_ids: Vec<&String> = first.nodes.keys().collect();
+        let mut second_ids: Vec<&String> = second.nodes.keys().collect();
+        first_ids.sort();
+        second_ids.sort();
+        assert_eq!(first_ids, second_ids);
+        assert_eq!(first.to_canonical_json().unwrap(), second.to_canonical_json().unwrap());
+        
+        // A second node of the same component gets the next sequence number
+        let mut canvas = build();
+        let component = canvas.nodes.values().next().unwrap().component.clone();
+        let extra = canvas.create_node(component, Point::new(0.0, 500.0)).unwrap();
+        assert!(!canvas.nodes.contains_key(&extra.id));
+        canvas.add_node(extra).unwrap();
+        assert_eq!(canvas.nodes.len(), 3);
+        
+        // Random IDs stay the default
+        let random = NodeCanvas::new();
+        let component = node("c").component;
+        assert_ne!(random.create_node(component.clone(), Point::new(0.0, 0.0)).unwrap().id,
+            random.create_node(component, Point::new(0.0, 0.0)).unwrap().id);
+    }
+}
@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: MulanPSL-2.0
 
 /// Kernel architecture types
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum KernelArchitecture {
     /// Traditional monolithic kernel
     Monolithic,
@@ -37,7 +38,25 @@ impl std::fmt::Display for KernelArchitecture {
     }
 }
 
+impl std::str::FromStr for KernelArchitecture {
+    type Err = String;
+    
+    /// Parse the display name or the variant name, ignoring case
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "monolithic" => Ok(KernelArchitecture::Monolithic),
+            "microkernel" => Ok(KernelArchitecture::Microkernel),
+            "hybrid" => Ok(KernelArchitecture::Hybrid),
+            "exokernel" => Ok(KernelArchitecture::Exokernel),
+            "frame" | "framekernel" => Ok(KernelArchitecture::Framekernel),
+            "partitioned" | "partitionedkernel" => Ok(KernelArchitecture::PartitionedKernel),
+            _ => Err(format!("Unknown kernel architecture '{}'", s)),
+        }
+    }
+}
+
 /// Hardware architecture types
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum HardwareArchitecture {
     /// x86_64 architecture
     X86_64,
@@ -0,0 +1,579 @@
+// Audit log for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! Central record of every operation that changes engine state.
+//!
+//! Table mutations, canvas operations, builds and settings changes each append an
+//! `AuditRecord` saying who did what to which subject, with short summaries of the
+//! subject before and after. The log is append-only: no record is ever edited or
+//! removed on its own, only the retention policy drops the oldest records. A log
+//! opened on a file writes every record to it as one JSON line, so the file is also
+//! the export format; `export_jsonl` writes a filtered selection elsewhere.
+//!
+//! The actor is taken from the calling thread (see `act_as`) and defaults to the
+//! operating system user. The global log stays in memory unless `OSLAND_AUDIT_LOG`
+//! names a file. Several processes may share a log file: appends and pruning take an
+//! advisory lock on `<log>.lock`, and pruning works on the records in the file, so the
+//! records appended by other processes are kept.
+//!
+//! Values of columns and properties whose name suggests a credential (see
+//! `is_sensitive`) are redacted from the summaries.
+
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use std::ffi::OsString;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use serde::{Deserialize, Serialize};
+use super::simulation;
+
+/// Environment variable naming the file of the global audit log
+pub const AUDIT_LOG_ENV: &str = "OSLAND_AUDIT_LOG";
+
+/// Actor recorded when neither the thread nor the environment names one
+pub const DEFAULT_ACTOR: &str = "system";
+
+/// Longest summary kept in a record, in characters
+const SUMMARY_LIMIT: usize = 200;
+
+/// Milliseconds in a day
+const DAY_MILLIS: u64 = 24 * 60 * 60 * 1000;
+
+/// Summary of a redacted value
+pub const REDACTED: &str = "<redacted>";
+
+/// Name fragments of columns and properties holding credentials
+const SENSITIVE_NAMES: &[&str] = &["password", "passwd", "secret", "token", "api_key", "apikey", "credential", "private_key"];
+
+thread_local! {
+    static ACTOR: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Kind of audited operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AuditCategory {
+    /// Table creation and row inserts, updates and deletes
+    Table,
+    
+    /// Canvas operations, undos and redos
+    Canvas,
+    
+    /// Builds started and finished
+    Build,
+    
+    /// Changes of settings
+    Settings,
+}
+
+impl AuditCategory {
+    /// Parse a category name as used on the command line
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "table" | "tables" => Some(AuditCategory::Table),
+            "canvas" => Some(AuditCategory::Canvas),
+            "build" | "builds" => Some(AuditCategory::Build),
+            "settings" => Some(AuditCategory::Settings),
+            _ => None,
+        }
+    }
+}
+
+/// One audited operation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditRecord {
+    /// Position in the log, increasing by one per record
+    pub sequence: u64,
+    
+    /// Milliseconds since the Unix epoch
+    pub timestamp: u64,
+    
+    /// Who performed the operation
+    pub actor: String,
+    
+    /// Kind of operation
+    pub category: AuditCategory,
+    
+    /// Operation name, such as `table.update` or `canvas.move_node`
+    pub operation: String,
+    
+    /// What the operation changed, such as a table row or a canvas node
+    pub subject: String,
+    
+    /// Summary of the subject before the operation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub old: Option<String>,
+    
+    /// Summary of the subject after the operation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub new: Option<String>,
+}
+
+/// Which records the log keeps
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditRetention {
+    /// Drop records older than this many days (`None` keeps them forever)
+    pub max_age_days: Option<u64>,
+    
+    /// Keep at most this many of the most recent records (`None` keeps all)
+    pub max_records: Option<usize>,
+}
+
+impl Default for AuditRetention {
+    fn default() -> Self {
+        Self {
+            max_age_days: None,
+            max_records: Some(100_000),
+        }
+    }
+}
+
+/// Selection of audit records
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AuditQuery {
+    /// Only records of this category
+    pub category: Option<AuditCategory>,
+    
+    /// Only records of this actor
+    pub actor: Option<String>,
+    
+    /// Only records at or after this timestamp (milliseconds since the Unix epoch)
+    pub since: Option<u64>,
+    
+    /// Only records before this timestamp (milliseconds since the Unix epoch)
+    pub until: Option<u64>,
+}
+
+impl AuditQuery {
+    /// Check whether a record is selected
+    pub fn matches(&self, record: &AuditRecord) -> bool {
+        self.category.is_none_or(|category| record.category == category)
+            && self.actor.as_ref().is_none_or(|actor| &record.actor == actor)
+            && self.since.is_none_or(|since| record.timestamp >= since)
+            && self.until.is_none_or(|until| record.timestamp < until)
+    }
+}
+
+/// Restores the previous actor of the thread when dropped
+pub struct ActorGuard {
+    previous: Option<String>,
+}
+
+impl Drop for ActorGuard {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        ACTOR.with(|actor| *actor.borrow_mut() = previous);
+    }
+}
+
+/// Record the operations of the current thread as performed by `actor` until the guard is dropped
+pub fn act_as(actor: impl Into<String>) -> ActorGuard {
+    let previous = ACTOR.with(|current| current.borrow_mut().replace(actor.into()));
+    ActorGuard { previous }
+}
+
+/// Get the actor of the current thread
+pub fn current_actor() -> String {
+    ACTOR.with(|actor| actor.borrow().clone())
+        .or_else(|| std::env::var("USER").ok())
+        .or_else(|| std::env::var("USERNAME").ok())
+        .filter(|actor| !actor.is_empty())
+        .unwrap_or_else(|| DEFAULT_ACTOR.to_string())
+}
+
+/// Check whether a column or property name suggests a credential
+pub fn is_sensitive(name: &str) -> bool {
+    let name = name.to_lowercase();
+    SENSITIVE_NAMES.iter().any(|fragment| name.contains(fragment))
+}
+
+/// Summarize column values as sorted `name=value` pairs, redacting sensitive columns
+pub fn summarize(values: &HashMap<String, String>) -> String {
+    let mut pairs: Vec<_> = values.iter().collect();
+    pairs.sort();
+    pairs.into_iter()
+        .map(|(name, value)| format!("{}={}", name, if is_sensitive(name) { REDACTED } else { value }))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Shorten a summary to `SUMMARY_LIMIT` characters
+fn truncate(summary: String) -> String {
+    match summary.char_indices().nth(SUMMARY_LIMIT) {
+        Some((end, _)) => format!("{}…", &summary[..end]),
+        None => summary,
+    }
+}
+
+/// Mutable state of a log
+struct LogState {
+    records: VecDeque<AuditRecord>,
+    next_sequence: u64,
+    retention: AuditRetention,
+    path: Option<PathBuf>,
+    
+    /// Open lock file of a file log, locked while the log file is written
+    lock: Option<File>,
+}
+
+/// Append-only log of audited operations
+pub struct AuditLog {
+    state: Mutex<LogState>,
+}
+
+impl AuditLog {
+    /// Create a log kept in memory
+    pub fn new() -> Self {
+        Self {
+            state: Mutex::new(LogState {
+                records: VecDeque::new(),
+                next_sequence: 1,
+                retention: AuditRetention::default(),
+                path: None,
+                lock: None,
+            }),
+        }
+    }
+    
+    /// Open a log stored in a JSONL file, loading the records already in it
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        
+        let lock_path = lock_path(path);
+        let lock = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&lock_path)
+            .map_err(|e| format!("Failed to open {}: {}", lock_path.display(), e))?;
+        let records = with_lock(&lock, || read_records(path))?;
+        
+        let next_sequence = records.back().map_or(1, |record| record.sequence + 1);
+        Ok(Self {
+            state: Mutex::new(LogState {
+                records,
+                next_sequence,
+                retention: AuditRetention::default(),
+                path: Some(path.to_path_buf()),
+                lock: Some(lock),
+            }),
+        })
+    }
+    
+    /// Get the global audit log, stored in the file named by `OSLAND_AUDIT_LOG` if set
+    pub fn global() -> &'static AuditLog {
+        static GLOBAL: OnceLock<AuditLog> = OnceLock::new();
+        GLOBAL.get_or_init(|| match std::env::var(AUDIT_LOG_ENV) {
+            Ok(path) if !path.is_empty() => AuditLog::open(&path).unwrap_or_else(|e| {
+                log::warn!("Audit log kept in memory: {}", e);
+                AuditLog::new()
+            }),
+            _ => AuditLog::new(),
+        })
+    }
+    
+    /// Get the file the log is stored in, if any
+    pub fn path(&self) -> Option<PathBuf> {
+        simulation::lock(&self.state).path.clone()
+    }
+    
+    /// Replace the retention policy
+    pub fn set_retention(&self, retention: AuditRetention) {
+        simulation::lock(&self.state).retention = retention;
+    }
+    
+    /// Get the retention policy
+    pub fn retention(&self) -> AuditRetention {
+        simulation::lock(&self.state).retention.clone()
+    }
+    
+    /// Append a record for an operation of the current actor, returning its sequence number
+    ///
+    /// Recording never fails the audited operation: a record that cannot be written to
+    /// the file is still kept in memory and the failure is logged.
+    pub fn record(
+        &self,
+        category: AuditCategory,
+        operation: impl Into<String>,
+        subject: impl Into<String>,
+        old: Option<String>,
+        new: Option<String>,
+    ) -> u64 {
+        let mut state = simulation::lock(&self.state);
+        let record = AuditRecord {
+            sequence: state.next_sequence,
+            timestamp: simulation::unix_millis(),
+            actor: current_actor(),
+            category,
+            operation: operation.into(),
+            subject: subject.into(),
+            old: old.map(truncate),
+            new: new.map(truncate),
+        };
+        state.next_sequence += 1;
+        
+        if let (Some(path), Some(lock)) = (&state.path, &state.lock) {
+            // Reopened on every record: another process may have pruned the file meanwhile
+            let written = with_lock(lock, || {
+                let line = serde_json::to_string(&record).map_err(|e| e.to_string())?;
+                let mut file = OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(path)
+                    .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+                writeln!(file, "{}", line).map_err(|e| e.to_string())
+            });
+            if let Err(e) = written {
+                log::warn!("Failed to write audit record {}: {}", record.sequence, e);
+            }
+        }
+        let sequence = record.sequence;
+        state.records.push_back(record);
+        
+        // Prune in batches so a full log is not rewritten on every record
+        if let Some(max_records) = state.retention.max_records {
+            if state.records.len() > max_records + (max_records / 10).max(1) {
+                if let Err(e) = Self::prune(&mut state, simulation::unix_millis()) {
+                    log::warn!("Failed to apply audit retention: {}", e);
+                }
+            }
+        }
+        sequence
+    }
+    
+    /// Get the selected records, oldest first
+    pub fn records(&self, query: &AuditQuery) -> Vec<AuditRecord> {
+        simulation::lock(&self.state).records.iter()
+            .filter(|record| query.matches(record))
+            .cloned()
+            .collect()
+    }
+    
+    /// Get the number of records kept
+    pub fn len(&self) -> usize {
+        simulation::lock(&self.state).records.len()
+    }
+    
+    /// Check whether the log keeps no records
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    
+    /// Write the selected records as JSON lines, returning how many were written
+    pub fn export_jsonl<W: Write>(&self, writer: W, query: &AuditQuery) -> Result<usize, String> {
+        let mut writer = BufWriter::new(writer);
+        let records = self.records(query);
+        for record in &records {
+            let line = serde_json::to_string(record).map_err(|e| e.to_string())?;
+            writeln!(writer, "{}", line).map_err(|e| format!("Failed to export audit log: {}", e))?;
+        }
+        writer.flush().map_err(|e| format!("Failed to export audit log: {}", e))?;
+        Ok(records.len())
+    }
+    
+    /// Write the selected records to a JSONL file, returning how many were written
+    pub fn export_to_file<P: AsRef<Path>>(&self, path: P, query: &AuditQuery) -> Result<usize, String> {
+        let path = path.as_ref();
+        let file = File::create(path).map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+        self.export_jsonl(file, query)
+    }
+    
+    /// Drop the records the retention policy no longer keeps, returning how many were dropped
+    pub fn apply_retention(&self) -> Result<usize, String> {
+        let mut state = simulation::lock(&self.state);
+        Self::prune(&mut state, simulation::unix_millis())
+    }
+    
+    /// Drop the oldest records outside the retention policy and rewrite the file
+    ///
+    /// A file log is pruned from the records in the file, which include the records
+    /// other processes appended; the count returned is the number dropped from the file.
+    fn prune(state: &mut LogState, now: u64) -> Result<usize, String> {
+        let before = state.records.len();
+        drop_expired(&mut state.records, &state.retention, now);
+        let removed = before - state.records.len();
+        
+        let (Some(path), Some(lock)) = (&state.path, &state.lock) else {
+            return Ok(removed);
+        };
+        with_lock(lock, || {
+            let mut records = read_records(path)?;
+            let before = records.len();
+            drop_expired(&mut records, &state.retention, now);
+            let removed = before - records.len();
+            if removed == 0 {
+                return Ok(0);
+            }
+            
+            // Rewrite through a temporary file so a crash leaves either the old or the new log
+            let temp_path = path.with_extension("jsonl.tmp");
+            let mut writer = BufWriter::new(
+                File::create(&temp_path).map_err(|e| format!("Failed to create {}: {}", temp_path.display(), e))?
+            );
+            for record in &records {
+                let line = serde_json::to_string(record).map_err(|e| e.to_string())?;
+                writeln!(writer, "{}", line).map_err(|e| format!("Failed to write {}: {}", temp_path.display(), e))?;
+            }
+            writer.flush().map_err(|e| format!("Failed to write {}: {}", temp_path.display(), e))?;
+            drop(writer);
+            fs::rename(&temp_path, path).map_err(|e| format!("Failed to replace {}: {}", path.display(), e))?;
+            Ok(removed)
+        })
+    }
+}
+
+impl Default for AuditLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Drop the records outside a retention policy, oldest first
+fn drop_expired(records: &mut VecDeque<AuditRecord>, retention: &AuditRetention, now: u64) {
+    if let Some(days) = retention.max_age_days {
+        let cutoff = now.saturating_sub(days.saturating_mul(DAY_MILLIS));
+        while records.front().is_some_and(|record| record.timestamp < cutoff) {
+            records.pop_front();
+        }
+    }
+    if let Some(max_records) = retention.max_records {
+        while records.len() > max_records {
+            records.pop_front();
+        }
+    }
+}
+
+/// Path of the lock file of a log file
+fn lock_path(path: &Path) -> PathBuf {
+    let mut lock_path = OsString::from(path.as_os_str());
+    lock_path.push(".lock");
+    PathBuf::from(lock_path)
+}
+
+/// Run `operation` holding the exclusive advisory lock of a log file
+fn with_lock<T>(lock: &File, operation: impl FnOnce() -> Result<T, String>) -> Result<T, String> {
+    lock.lock().map_err(|e| format!("Failed to lock audit log: {}", e))?;
+    let result = operation();
+    let _ = lock.unlock();
+    result
+}
+
+/// Read the records of a log file (none if it does not exist)
+fn read_records(path: &Path) -> Result<VecDeque<AuditRecord>, String> {
+    let mut records = VecDeque::new();
+    if !path.exists() {
+        return Ok(records);
+    }
+    let file = File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    for (index, line) in BufReader::new(file).lines().enumerate() {
+        let line = line.map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let record: AuditRecord = serde_json::from_str(&line)
+            .map_err(|e| format!("Invalid audit record at {}:{}: {}", path.display(), index + 1, e))?;
+        records.push_back(record);
+    }
+    Ok(records)
+}
+
+/// Append a record for an operation of the current actor to the global audit log
+pub fn record(
+    category: AuditCategory,
+    operation: impl Into<String>,
+    subject: impl Into<String>,
+    old: Option<String>,
+    new: Option<String>,
+) -> u64 {
+    AuditLog::global().record(category, operation, subject, old, new)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_records_carry_actor_and_summaries() {
+        let log = AuditLog::new();
+        {
+            let _actor = act_as("alice");
+            log.record(AuditCategory::Table, "table.update", "tasks/1", Some("state=open".to_string()), Some("state=done".to_string()));
+        }
+        log.record(AuditCategory::Settings, "settings.offline", "network", None, Some("true".to_string()));
+        
+        let records = log.records(&AuditQuery::default());
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].actor, "alice");
+        assert_ne!(records[1].actor, "alice");
+        assert_eq!(records[0].old.as_deref(), Some("state=open"));
+        assert_eq!(records[1].sequence, records[0].sequence + 1);
+        
+        let query = AuditQuery { category: Some(AuditCategory::Settings), ..Default::default() };
+        assert_eq!(log.records(&query).len(), 1);
+        log.record(AuditCategory::Canvas, "canvas.set_property", "node", None, Some("x".repeat(1000)));
+        let long = log.records(&AuditQuery::default()).pop().unwrap();
+        assert_eq!(long.new.unwrap().chars().count(), SUMMARY_LIMIT + 1);
+    }
+    
+    #[test]
+    fn test_file_log_is_reloaded_exported_and_pruned() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.jsonl");
+        {
+            let log = AuditLog::open(&path).unwrap();
+            for index in 0..5 {
+                log.record(AuditCategory::Build, "build.start", format!("project-{}", index), None, None);
+            }
+        }
+        
+        let log = AuditLog::open(&path).unwrap();
+        assert_eq!(log.len(), 5);
+        assert_eq!(log.record(AuditCategory::Build, "build.finish", "project-4", None, None), 6);
+        
+        let mut exported = Vec::new();
+        let query = AuditQuery { category: Some(AuditCategory::Build), ..Default::default() };
+        assert_eq!(log.export_jsonl(&mut exported, &query).unwrap(), 6);
+        assert_eq!(String::from_utf8(exported).unwrap().lines().count(), 6);
+        
+        log.set_retention(AuditRetention { max_age_days: None, max_records: Some(2) });
+        assert_eq!(log.apply_retention().unwrap(), 4);
+        log.record(AuditCategory::Build, "build.start", "project-5", None, None);
+        let reloaded = AuditLog::open(&path).unwrap();
+        let sequences: Vec<u64> = reloaded.records(&AuditQuery::default()).iter().map(|record| record.sequence).collect();
+        assert_eq!(sequences, vec![5, 6, 7]);
+    }
+    
+    #[test]
+    fn test_prune_keeps_records_of_other_writers() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.jsonl");
+        let pruner = AuditLog::open(&path).unwrap();
+        let writer = AuditLog::open(&path).unwrap();
+        pruner.record(AuditCategory::Build, "build.start", "old", None, None);
+        writer.record(AuditCategory::Build, "build.start", "other", None, None);
+        
+        pruner.set_retention(AuditRetention { max_age_days: None, max_records: Some(1) });
+        assert_eq!(pruner.apply_retention().unwrap(), 1);
+        writer.record(AuditCategory::Build, "build.finish", "other", None, None);
+        let subjects: Vec<String> = AuditLog::open(&path).unwrap().records(&AuditQuery::default()).into_iter()
+            .map(|record| format!("{} {}", record.operation, record.subject))
+            .collect();
+        assert_eq!(subjects, vec!["build.start other", "build.finish other"]);
+    }
+    
+    #[test]
+    fn test_sensitive_values_are_redacted() {
+        let values = HashMap::from([
+            ("name".to_string(), "deploy".to_string()),
+            ("api_token".to_string(), "s3cr3t".to_string()),
+        ]);
+        assert_eq!(summarize(&values), "api_token=<redacted>, name=deploy");
+        assert!(is_sensitive("DB_PASSWORD"));
+        assert!(!is_sensitive("owner"));
+    }
+}
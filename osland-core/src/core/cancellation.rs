@@ -0,0 +1,147 @@
+// Cancellation tokens for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+use tokio::sync::Notify;
+
+/// Shared token state
+#[derive(Default)]
+struct TokenState {
+    canceled: AtomicBool,
+    notify: Notify,
+    children: Mutex<Vec<Weak<TokenState>>>,
+}
+
+impl TokenState {
+    fn cancel(&self) {
+        if self.canceled.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        self.notify.notify_waiters();
+        
+        let children = std::mem::take(&mut *self.children.lock().unwrap());
+        for child in children.iter().filter_map(Weak::upgrade) {
+            child.cancel();
+        }
+    }
+}
+
+/// Cooperative cancellation token
+///
+/// Clones share the same state. Child tokens are canceled together with their
+/// parent but can also be canceled on their own, which lets a long operation
+/// cancel one sub-task without affecting its siblings. Blocking code polls
+/// `is_canceled`; async code awaits `canceled`.
+#[derive(Clone, Default)]
+pub struct CancellationToken {
+    state: Arc<TokenState>,
+}
+
+impl CancellationToken {
+    /// Create a new, uncanceled token
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Create a child token that is canceled when this token is
+    pub fn child_token(&self) -> Self {
+        let child = Self::new();
+        self.state.children.lock().unwrap().push(Arc::downgrade(&child.state));
+        
+        // The parent may have been canceled before the child was registered
+        if self.is_canceled() {
+            child.cancel();
+        }
+        child
+    }
+    
+    /// Cancel the token and all of its children
+    pub fn cancel(&self) {
+        self.state.cancel();
+    }
+    
+    /// Check whether the token has been canceled
+    pub fn is_canceled(&self) -> bool {
+        self.state.canceled.load(Ordering::SeqCst)
+    }
+    
+    /// Wait until the token is canceled
+    pub async fn canceled(&self) {
+        let notified = self.state.notify.notified();
+        tokio::pin!(notified);
+        
+        // Register before checking the flag so a concurrent cancel is not missed
+        notified.as_mut().enable();
+        if self.is_canceled() {
+            return;
+        }
+        notified.await;
+    }
+}
+
+impl fmt::Debug for CancellationToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CancellationToken")
+            .field("canceled", &self.is_canceled())
+            .finish()
+    }
+}
+
+/// Run blocking work on the tokio blocking pool
+///
+/// The closure receives a child token to poll. Canceling `token` does not abort
+/// the work; it waits for the closure to observe the cancellation and return, so
+/// no work outlives the caller.
+pub async fn run_blocking<T, F>(token: &CancellationToken, work: F) -> Result<T, String>
+where
+    T: Send + 'static,
+    F: FnOnce(CancellationToken) -> T + Send + 'static,
+{
+    let child = token.child_token();
+    tokio::task::spawn_blocking(move || work(child))
+        .await
+        .map_err(|e| format!("Blocking task failed: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_children_follow_parent() {
+        let parent = CancellationToken::new();
+        let child = parent.child_token();
+        let sibling = parent.child_token();
+        
+        sibling.cancel();
+        assert!(sibling.is_canceled());
+        assert!(!parent.is_canceled() && !child.is_canceled());
+        
+        parent.cancel();
+        assert!(child.is_canceled());
+        assert!(parent.child_token().is_canceled());
+    }
+    
+    #[tokio::test]
+    async fn test_canceled_wakes_waiters() {
+        let token = CancellationToken::new();
+        let waiter = tokio::spawn({
+            let token = token.clone();
+            async move { token.canceled().await }
+        });
+        
+        let work = run_blocking(&token, |token| {
+            while !token.is_canceled() {
+                std::thread::sleep(std::time::Duration::from_millis(1));
+            }
+            "stopped"
+        });
+        token.cancel();
+        
+        assert_eq!(work.await.unwrap(), "stopped");
+        waiter.await.unwrap();
+    }
+}
@@ -0,0 +1,391 @@
+// Event bus for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender, SyncSender, TrySendError};
+use std::sync::{Arc, OnceLock, RwLock};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Event topic, used for subscription filtering
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum EventTopic {
+    /// Build engine activity
+    Build,
+    
+    /// DBOS table changes
+    Table,
+    
+    /// Kernel extraction progress
+    Extraction,
+    
+    /// Collaboration operations
+    Collaboration,
+    
+    /// Project validation and publishing
+    Project,
+    
+    /// Events published by plugins
+    Plugin,
+}
+
+/// Build engine event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum BuildEvent {
+    /// A build was started
+    Started { project: String },
+    
+    /// Build progress changed
+    Progress { project: String, status: String, percentage: u8 },
+    
+    /// A line was added to the build log
+    Log { project: String, message: String },
+    
+    /// A build finished (successfully or not)
+    Finished { project: String, success: bool, message: String },
+}
+
+/// Kind of table change
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TableChangeKind {
+    Insert,
+    Update,
+    Delete,
+}
+
+/// DBOS table change event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TableChangeEvent {
+    /// Table name
+    pub table: String,
+    
+    /// Changed row ID
+    pub row_id: String,
+    
+    /// Kind of change
+    pub kind: TableChangeKind,
+    
+    /// Namespace owning the row, if the table is namespaced
+    #[serde(default)]
+    pub namespace: Option<String>,
+}
+
+/// Kernel extraction progress event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExtractionProgressEvent {
+    /// Kernel being extracted
+    pub kernel: String,
+    
+    /// Current pipeline stage
+    pub stage: String,
+    
+    /// Number of completed stages
+    pub completed: usize,
+    
+    /// Total number of stages
+    pub total: usize,
+}
+
+/// Collaboration operation event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollaborationEvent {
+    /// Operation ID
+    pub operation_id: String,
+    
+    /// User who performed the operation
+    pub user_id: String,
+    
+    /// Operation type name
+    pub operation: String,
+}
+
+/// Project event relevant to external CI and chat systems
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ProjectEvent {
+    /// A build configuration, canvas or set of staged files failed validation
+    ValidationFailed { project: String, target: String, errors: Vec<String> },
+    
+    /// A new version of a component or library tile was published
+    VersionPublished { project: String, component: String, version: String },
+}
+
+/// System event delivered through the event bus
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum SystemEvent {
+    Build(BuildEvent),
+    Table(TableChangeEvent),
+    Extraction(ExtractionProgressEvent),
+    Collaboration(CollaborationEvent),
+    Project(ProjectEvent),
+    
+    /// Free-form event published by a plugin
+    Plugin { plugin: String, name: String, payload: Value },
+}
+
+impl SystemEvent {
+    /// Get the topic of the event
+    pub fn topic(&self) -> EventTopic {
+        match self {
+            SystemEvent::Build(_) => EventTopic::Build,
+            SystemEvent::Table(_) => EventTopic::Table,
+            SystemEvent::Extraction(_) => EventTopic::Extraction,
+            SystemEvent::Collaboration(_) => EventTopic::Collaboration,
+            SystemEvent::Project(_) => EventTopic::Project,
+            SystemEvent::Plugin { .. } => EventTopic::Plugin,
+        }
+    }
+    
+    /// Get the event source: project, table, kernel, user or plugin name
+    pub fn source(&self) -> &str {
+        match self {
+            SystemEvent::Build(BuildEvent::Started { project })
+            | SystemEvent::Build(BuildEvent::Progress { project, .. })
+            | SystemEvent::Build(BuildEvent::Log { project, .. })
+            | SystemEvent::Build(BuildEvent::Finished { project, .. }) => project,
+            SystemEvent::Table(event) => &event.table,
+            SystemEvent::Extraction(event) => &event.kernel,
+            SystemEvent::Collaboration(event) => &event.user_id,
+            SystemEvent::Project(ProjectEvent::ValidationFailed { project, .. })
+            | SystemEvent::Project(ProjectEvent::VersionPublished { project, .. }) => project,
+            SystemEvent::Plugin { plugin, .. } => plugin,
+        }
+    }
+}
+
+/// Subscription filter
+///
+/// An empty topic set matches every topic; a source restricts delivery to events
+/// from one project, table, kernel, user or plugin.
+#[derive(Debug, Clone, Default)]
+pub struct EventFilter {
+    topics: HashSet<EventTopic>,
+    source: Option<String>,
+}
+
+impl EventFilter {
+    /// Match every event
+    pub fn all() -> Self {
+        Self::default()
+    }
+    
+    /// Match events of a single topic
+    pub fn topic(topic: EventTopic) -> Self {
+        Self::default().with_topic(topic)
+    }
+    
+    /// Also match events of the given topic
+    pub fn with_topic(mut self, topic: EventTopic) -> Self {
+        self.topics.insert(topic);
+        self
+    }
+    
+    /// Only match events from the given source
+    pub fn with_source(mut self, source: impl Into<String>) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+    
+    /// Check whether an event passes the filter
+    pub fn matches(&self, event: &SystemEvent) -> bool {
+        (self.topics.is_empty() || self.topics.contains(&event.topic()))
+            && self.source.as_deref().is_none_or(|source| source == event.source())
+    }
+}
+
+/// Subscription identifier
+pub type SubscriptionId = u64;
+
+/// Event handler callback
+pub type EventHandler = Arc<dyn Fn(&SystemEvent) + Send + Sync>;
+
+/// Subscriber delivery target
+enum EventSink {
+    Callback(EventHandler),
+    Channel(Sender<SystemEvent>),
+    Bounded(SyncSender<SystemEvent>),
+}
+
+/// Registered subscriber
+struct Subscriber {
+    id: SubscriptionId,
+    filter: EventFilter,
+    sink: EventSink,
+}
+
+/// Typed publish/subscribe event bus
+///
+/// Engines publish to the bus instead of being polled. Callbacks run synchronously
+/// on the publishing thread; UI panels should use `subscribe_channel` and drain the
+/// receiver from the UI thread.
+pub struct EventBus {
+    next_id: AtomicU64,
+    subscribers: RwLock<Vec<Subscriber>>,
+}
+
+impl EventBus {
+    /// Create a new, empty event bus
+    pub fn new() -> Self {
+        Self {
+            next_id: AtomicU64::new(1),
+            subscribers: RwLock::new(Vec::new()),
+        }
+    }
+    
+    /// Get the process-wide event bus
+    pub fn global() -> &'static EventBus {
+        static GLOBAL: OnceLock<EventBus> = OnceLock::new();
+        GLOBAL.get_or_init(EventBus::new)
+    }
+    
+    /// Register a callback for events matching the filter
+    pub fn subscribe(&self, filter: EventFilter, handler: impl Fn(&SystemEvent) + Send + Sync + 'static) -> SubscriptionId {
+        self.add_subscriber(filter, EventSink::Callback(Arc::new(handler)))
+    }
+    
+    /// Register a channel for events matching the filter
+    ///
+    /// The subscription is removed automatically once the receiver is dropped.
+    pub fn subscribe_channel(&self, filter: EventFilter) -> (SubscriptionId, Receiver<SystemEvent>) {
+        let (sender, receiver) = mpsc::channel();
+        (self.add_subscriber(filter, EventSink::Channel(sender)), receiver)
+    }
+    
+    /// Register a channel holding at most `capacity` undelivered events
+    ///
+    /// Events published while the channel is full are dropped for this subscriber, so a
+    /// receiver that is drained rarely cannot grow without bound. The subscription is
+    /// removed automatically once the receiver is dropped.
+    pub fn subscribe_bounded(&self, filter: EventFilter, capacity: usize) -> (SubscriptionId, Receiver<SystemEvent>) {
+        let (sender, receiver) = mpsc::sync_channel(capacity);
+        (self.add_subscriber(filter, EventSink::Bounded(sender)), receiver)
+    }
+    
+    /// Remove a subscription, returning whether it existed
+    pub fn unsubscribe(&self, id: SubscriptionId) -> bool {
+        let mut subscribers = self.subscribers.write().unwrap();
+        let before = subscribers.len();
+        subscribers.retain(|subscriber| subscriber.id != id);
+        subscribers.len() != before
+    }
+    
+    /// Get the number of active subscriptions
+    pub fn subscriber_count(&self) -> usize {
+        self.subscribers.read().unwrap().len()
+    }
+    
+    /// Publish an event, returning the number of subscribers it was delivered to
+    pub fn publish(&self, event: SystemEvent) -> usize {
+        let mut callbacks = Vec::new();
+        let mut disconnected = Vec::new();
+        let mut delivered = 0;
+        
+        {
+            let subscribers = self.subscribers.read().unwrap();
+            for subscriber in subscribers.iter().filter(|subscriber| subscriber.filter.matches(&event)) {
+                match &subscriber.sink {
+                    EventSink::Callback(handler) => callbacks.push(handler.clone()),
+                    EventSink::Channel(sender) => {
+                        if sender.send(event.clone()).is_ok() {
+                            delivered += 1;
+                        } else {
+                            disconnected.push(subscriber.id);
+                        }
+                    }
+                    EventSink::Bounded(sender) => match sender.try_send(event.clone()) {
+                        Ok(()) => delivered += 1,
+                        Err(TrySendError::Full(_)) => {}
+                        Err(TrySendError::Disconnected(_)) => disconnected.push(subscriber.id),
+                    },
+                }
+            }
+        }
+        
+        // Callbacks run outside the lock so they may (un)subscribe or publish
+        for handler in callbacks {
+            handler(&event);
+            delivered += 1;
+        }
+        
+        if !disconnected.is_empty() {
+            self.subscribers.write().unwrap().retain(|subscriber| !disconnected.contains(&subscriber.id));
+        }
+        
+        delivered
+    }
+    
+    fn add_subscriber(&self, filter: EventFilter, sink: EventSink) -> SubscriptionId {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        self.subscribers.write().unwrap().push(Subscriber { id, filter, sink });
+        id
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Publish an event on the global event bus
+pub fn publish(event: SystemEvent) -> usize {
+    EventBus::global().publish(event)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    
+    fn table_event(table: &str) -> SystemEvent {
+        SystemEvent::Table(TableChangeEvent {
+            table: table.to_string(),
+            row_id: "row".to_string(),
+            kind: TableChangeKind::Insert,
+            namespace: None,
+        })
+    }
+    
+    #[test]
+    fn test_filtering_and_unsubscribe() {
+        let bus = EventBus::new();
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = seen.clone();
+        let id = bus.subscribe(EventFilter::topic(EventTopic::Table).with_source("tasks"), move |event| {
+            sink.lock().unwrap().push(event.source().to_string());
+        });
+        let (_, receiver) = bus.subscribe_channel(EventFilter::topic(EventTopic::Build));
+        
+        assert_eq!(bus.publish(table_event("tasks")), 1);
+        assert_eq!(bus.publish(table_event("files")), 0);
+        assert_eq!(bus.publish(SystemEvent::Build(BuildEvent::Started { project: "demo".to_string() })), 1);
+        assert_eq!(*seen.lock().unwrap(), vec!["tasks".to_string()]);
+        assert!(matches!(receiver.try_recv(), Ok(SystemEvent::Build(BuildEvent::Started { .. }))));
+        
+        assert!(bus.unsubscribe(id));
+        assert_eq!(bus.publish(table_event("tasks")), 0);
+        
+        // Dropped receivers are pruned on the next delivery attempt
+        drop(receiver);
+        bus.publish(SystemEvent::Build(BuildEvent::Started { project: "demo".to_string() }));
+        assert_eq!(bus.subscriber_count(), 0);
+    }
+    
+    #[test]
+    fn test_bounded_subscription_drops_events_when_full() {
+        let bus = EventBus::new();
+        let (_, receiver) = bus.subscribe_bounded(EventFilter::topic(EventTopic::Table), 2);
+        
+        assert_eq!(bus.publish(table_event("a")), 1);
+        assert_eq!(bus.publish(table_event("b")), 1);
+        assert_eq!(bus.publish(table_event("c")), 0);
+        let sources: Vec<String> = receiver.try_iter().map(|event| event.source().to_string()).collect();
+        assert_eq!(sources, vec!["a".to_string(), "b".to_string()]);
+        
+        assert_eq!(bus.publish(table_event("d")), 1);
+        drop(receiver);
+        bus.publish(table_event("e"));
+        assert_eq!(bus.subscriber_count(), 0);
+    }
+}
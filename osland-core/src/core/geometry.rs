@@ -0,0 +1,114 @@
+// Canvas geometry types for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! Points, rectangles and colors used by the canvas model.
+//!
+//! These are plain serializable types, so tools can embed the component and design
+//! engines without pulling in the UI stack. The `gpui` feature only adds `From`
+//! conversions to and from the GPUI types; it never changes the types themselves,
+//! so crates enabling it and crates not enabling it see the same API.
+
+use serde::{Serialize, Deserialize};
+
+/// A position in canvas coordinates
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Point {
+    pub fn new(x: f64, y: f64) -> Self {
+        Self { x, y }
+    }
+}
+
+/// An axis-aligned rectangle given by its origin and size
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct Rect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl Rect {
+    pub fn new(origin: Point, size: (f64, f64)) -> Self {
+        Self { x: origin.x, y: origin.y, width: size.0, height: size.1 }
+    }
+    
+    pub fn right(&self) -> f64 {
+        self.x + self.width
+    }
+    
+    pub fn bottom(&self) -> f64 {
+        self.y + self.height
+    }
+    
+    /// Check if a point lies inside the rectangle, edges included
+    pub fn contains(&self, point: Point) -> bool {
+        point.x >= self.x && point.x <= self.right() && point.y >= self.y && point.y <= self.bottom()
+    }
+    
+    /// Check if two rectangles overlap
+    pub fn intersects(&self, other: &Rect) -> bool {
+        self.x <= other.right() && other.x <= self.right() && self.y <= other.bottom() && other.y <= self.bottom()
+    }
+}
+
+/// An 8-bit RGBA color
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl Color {
+    pub fn from_rgba8(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self { r, g, b, a }
+    }
+}
+
+#[cfg(feature = "gpui")]
+mod gpui_conversions {
+    use super::{Color, Point, Rect};
+    
+    impl From<Point> for gpui::Point {
+        fn from(point: Point) -> Self {
+            gpui::Point::new(point.x, point.y)
+        }
+    }
+    
+    impl From<gpui::Point> for Point {
+        fn from(point: gpui::Point) -> Self {
+            Point::new(point.x, point.y)
+        }
+    }
+    
+    impl From<Rect> for gpui::Rect {
+        fn from(rect: Rect) -> Self {
+            gpui::Rect::new(gpui::Point::new(rect.x, rect.y), (rect.width, rect.height))
+        }
+    }
+    
+    impl From<gpui::Rect> for Rect {
+        fn from(rect: gpui::Rect) -> Self {
+            Rect::new(Point::new(rect.x, rect.y), (rect.width, rect.height))
+        }
+    }
+    
+    impl From<Color> for gpui::Color {
+        fn from(color: Color) -> Self {
+            gpui::Color::from_rgba8(color.r, color.g, color.b, color.a)
+        }
+    }
+    
+    impl From<gpui::Color> for Color {
+        fn from(color: gpui::Color) -> Self {
+            Color::from_rgba8(color.r, color.g, color.b, color.a)
+        }
+    }
+}
@@ -6,6 +6,17 @@ pub mod config;
 pub mod project;
 pub mod kernel;
 pub mod architecture;
+pub mod event_bus;
+pub mod cancellation;
+pub mod stable_ids;
+pub mod profiling;
+pub mod geometry;
+pub mod webhooks;
+pub mod secrets;
+pub mod network_policy;
+pub mod resource_governor;
+pub mod simulation;
+pub mod audit;
 
 // Core application state
 #[derive(Debug)]
@@ -39,4 +50,7 @@ pub enum CoreError {
     
     #[error("Architecture error: {0}")]
     ArchitectureError(String),
+    
+    #[error("Secret error: {0}")]
+    SecretError(String),
 }
@@ -0,0 +1,261 @@
+// Network egress policy for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! Central policy for outbound network connections.
+//!
+//! Every subsystem that talks to the network (AI providers, registries, source
+//! downloads, webhooks, HTTP health checks) asks the egress policy before connecting.
+//! In offline mode, meant for air-gapped machines, the policy refuses every connection
+//! that leaves the machine; the subsystem then fails with an `EgressDenied` naming the
+//! feature that is unavailable instead of timing out. Loopback connections stay allowed
+//! by default so local model servers and registries keep working.
+//!
+//! HTTP clients follow redirects through `redirect_policy`, so every hop is checked
+//! as well and a redirect cannot lead a request off the machine.
+//!
+//! Offline mode is enabled with `--offline` or by setting `OSLAND_OFFLINE=1`.
+
+use std::net::IpAddr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+use serde::{Deserialize, Serialize};
+use super::audit::{self, AuditCategory};
+
+/// Environment variable enabling offline mode
+pub const OFFLINE_ENV: &str = "OSLAND_OFFLINE";
+
+/// Refusals kept for display
+const DENIAL_LOG_SIZE: usize = 100;
+
+/// Redirects followed by one request
+const MAX_REDIRECTS: usize = 10;
+
+/// Reason for an outbound connection
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum EgressPurpose {
+    /// Requests to AI model providers
+    AiApi,
+    
+    /// Pushes to OCI registries
+    Registry,
+    
+    /// Source, toolchain and container image downloads
+    Download,
+    
+    /// Webhook deliveries
+    Webhook,
+    
+    /// HTTP health check probes
+    HealthCheck,
+}
+
+impl EgressPurpose {
+    /// All purposes
+    pub const ALL: [EgressPurpose; 5] = [
+        EgressPurpose::AiApi,
+        EgressPurpose::Registry,
+        EgressPurpose::Download,
+        EgressPurpose::Webhook,
+        EgressPurpose::HealthCheck,
+    ];
+    
+    /// User-facing name of the functionality needing the connection
+    pub fn feature(&self) -> &'static str {
+        match self {
+            EgressPurpose::AiApi => "AI assistance",
+            EgressPurpose::Registry => "Artifact publishing",
+            EgressPurpose::Download => "Source downloading",
+            EgressPurpose::Webhook => "Webhook delivery",
+            EgressPurpose::HealthCheck => "HTTP health checking",
+        }
+    }
+}
+
+/// Refused outbound connection
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error, Serialize, Deserialize)]
+#[error("{} is unavailable in offline mode (blocked connection to {host})", .purpose.feature())]
+pub struct EgressDenied {
+    /// Reason for the connection
+    pub purpose: EgressPurpose,
+    
+    /// Host that was not contacted
+    pub host: String,
+}
+
+/// Decides which outbound connections are allowed
+#[derive(Debug)]
+pub struct EgressPolicy {
+    offline: AtomicBool,
+    allow_loopback: AtomicBool,
+    denials: Mutex<Vec<EgressDenied>>,
+}
+
+impl EgressPolicy {
+    /// Create an online policy
+    pub fn new() -> Self {
+        Self {
+            offline: AtomicBool::new(false),
+            allow_loopback: AtomicBool::new(true),
+            denials: Mutex::new(Vec::new()),
+        }
+    }
+    
+    /// Get the process-wide policy, offline if `OSLAND_OFFLINE` is set to a true value
+    pub fn global() -> &'static EgressPolicy {
+        static GLOBAL: OnceLock<EgressPolicy> = OnceLock::new();
+        GLOBAL.get_or_init(|| {
+            let policy = EgressPolicy::new();
+            let from_env = std::env::var(OFFLINE_ENV)
+                .map(|value| matches!(value.to_lowercase().as_str(), "1" | "true" | "yes" | "on"))
+                .unwrap_or(false);
+            policy.set_offline(from_env);
+            policy
+        })
+    }
+    
+    /// Enable or disable offline mode
+    pub fn set_offline(&self, offline: bool) {
+        let previous = self.offline.swap(offline, Ordering::SeqCst);
+        if previous != offline {
+            audit::record(AuditCategory::Settings, "settings.offline", "network policy", Some(previous.to_string()), Some(offline.to_string()));
+        }
+    }
+    
+    /// Check if offline mode is enabled
+    pub fn is_offline(&self) -> bool {
+        self.offline.load(Ordering::SeqCst)
+    }
+    
+    /// Allow or refuse loopback connections in offline mode
+    pub fn set_allow_loopback(&self, allow: bool) {
+        let previous = self.allow_loopback.swap(allow, Ordering::SeqCst);
+        if previous != allow {
+            audit::record(AuditCategory::Settings, "settings.allow_loopback", "network policy", Some(previous.to_string()), Some(allow.to_string()));
+        }
+    }
+    
+    /// Check whether a connection to a URL is allowed
+    pub fn check(&self, purpose: EgressPurpose, url: &str) -> Result<(), EgressDenied> {
+        if !self.is_offline() {
+            return Ok(());
+        }
+        let host = url_host(url);
+        if self.allow_loopback.load(Ordering::SeqCst) && is_loopback(&host) {
+            return Ok(());
+        }
+        let denied = EgressDenied { purpose, host };
+        log::warn!("{}", denied);
+        let mut denials = self.denials.lock().unwrap();
+        denials.push(denied.clone());
+        if denials.len() > DENIAL_LOG_SIZE {
+            denials.remove(0);
+        }
+        Err(denied)
+    }
+    
+    /// Functionality unavailable because of offline mode
+    pub fn unavailable_features(&self) -> Vec<EgressPurpose> {
+        if self.is_offline() {
+            EgressPurpose::ALL.to_vec()
+        } else {
+            Vec::new()
+        }
+    }
+    
+    /// Most recent refused connections, oldest first
+    pub fn recent_denials(&self) -> Vec<EgressDenied> {
+        self.denials.lock().unwrap().clone()
+    }
+    
+    /// Redirect policy of HTTP clients checking every hop against this policy
+    pub fn redirect_policy(&'static self, purpose: EgressPurpose) -> reqwest::redirect::Policy {
+        reqwest::redirect::Policy::custom(move |attempt| {
+            if attempt.previous().len() >= MAX_REDIRECTS {
+                return attempt.error(format!("stopped after {} redirects", MAX_REDIRECTS));
+            }
+            match self.check(purpose, attempt.url().as_str()) {
+                Ok(()) => attempt.follow(),
+                Err(denied) => attempt.error(denied),
+            }
+        })
+    }
+}
+
+impl Default for EgressPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Check a connection against the process-wide policy
+pub fn check_egress(purpose: EgressPurpose, url: &str) -> Result<(), EgressDenied> {
+    EgressPolicy::global().check(purpose, url)
+}
+
+/// Redirect policy of HTTP clients checking every hop against the process-wide policy
+pub fn redirect_policy(purpose: EgressPurpose) -> reqwest::redirect::Policy {
+    EgressPolicy::global().redirect_policy(purpose)
+}
+
+/// Host of a container image reference, as `docker pull` resolves it
+pub fn image_registry(image: &str) -> &str {
+    match image.split_once('/') {
+        Some((first, _)) if first.contains(['.', ':']) || first == "localhost" => first,
+        _ => "docker.io",
+    }
+}
+
+/// Host of a URL or `host:port` address, without credentials, port or brackets
+fn url_host(url: &str) -> String {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or(rest);
+    let host_port = authority.rsplit_once('@').map_or(authority, |(_, host)| host);
+    let host = if let Some(bracketed) = host_port.strip_prefix('[') {
+        bracketed.split(']').next().unwrap_or(bracketed)
+    } else {
+        host_port.split(':').next().unwrap_or(host_port)
+    };
+    host.to_lowercase()
+}
+
+fn is_loopback(host: &str) -> bool {
+    host == "localhost" || host.ends_with(".localhost") || host.parse::<IpAddr>().is_ok_and(|ip| ip.is_loopback())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_offline_mode_blocks_remote_hosts() {
+        let policy = EgressPolicy::new();
+        assert!(policy.check(EgressPurpose::AiApi, "https://api.openai.com/v1/chat/completions").is_ok());
+        assert!(policy.unavailable_features().is_empty());
+        
+        policy.set_offline(true);
+        let denied = policy.check(EgressPurpose::AiApi, "https://user:pw@api.openai.com:443/v1").unwrap_err();
+        assert_eq!(denied.host, "api.openai.com");
+        assert_eq!(denied.to_string(), "AI assistance is unavailable in offline mode (blocked connection to api.openai.com)");
+        assert!(policy.check(EgressPurpose::Registry, "ghcr.io").is_err());
+        
+        // Local services keep working
+        assert!(policy.check(EgressPurpose::AiApi, "http://localhost:11434/api/generate").is_ok());
+        assert!(policy.check(EgressPurpose::Registry, "http://127.0.0.1:5000").is_ok());
+        assert!(policy.check(EgressPurpose::Webhook, "http://[::1]:8080/hook").is_ok());
+        policy.set_allow_loopback(false);
+        assert!(policy.check(EgressPurpose::AiApi, "http://localhost:11434/api/generate").is_err());
+        
+        assert_eq!(policy.recent_denials().len(), 3);
+        assert_eq!(policy.unavailable_features().len(), EgressPurpose::ALL.len());
+    }
+    
+    #[test]
+    fn test_image_registry() {
+        assert_eq!(image_registry("ubuntu:22.04"), "docker.io");
+        assert_eq!(image_registry("library/ubuntu"), "docker.io");
+        assert_eq!(image_registry("ghcr.io/osland/toolchain:1.0"), "ghcr.io");
+        assert_eq!(image_registry("localhost:5000/toolchain"), "localhost:5000");
+        assert_eq!(image_registry("localhost/toolchain"), "localhost");
+    }
+}
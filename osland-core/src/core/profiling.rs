@@ -0,0 +1,291 @@
+// Profiling data model for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! Span trees and folded stacks for flame graphs.
+//!
+//! Producers such as the build engine and the tile simulator describe where time went
+//! as a tree of spans. A profile folds into stacks (`build;Build Kernel 61000`), exports
+//! them in the collapsed-stack format read by flamegraph.pl, inferno and speedscope, and
+//! lays out the frames of a flame graph for the UI.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+
+/// Timed span with nested spans
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProfileSpan {
+    /// Frame name
+    pub name: String,
+    
+    /// Start offset in microseconds
+    pub start_us: u64,
+    
+    /// Duration in microseconds
+    pub duration_us: u64,
+    
+    /// Nested spans
+    pub children: Vec<ProfileSpan>,
+}
+
+impl ProfileSpan {
+    /// Create a span without children
+    pub fn new(name: impl Into<String>, start_us: u64, duration_us: u64) -> Self {
+        Self { name: name.into(), start_us, duration_us, children: Vec::new() }
+    }
+    
+    /// Time not covered by the children
+    pub fn self_us(&self) -> u64 {
+        let children: u64 = self.children.iter().map(|child| child.duration_us).sum();
+        self.duration_us.saturating_sub(children)
+    }
+    
+    fn fold(&self, prefix: &str, stacks: &mut BTreeMap<String, u64>) {
+        let stack = if prefix.is_empty() { escape(&self.name) } else { format!("{};{}", prefix, escape(&self.name)) };
+        let self_us = self.self_us();
+        if self_us > 0 {
+            *stacks.entry(stack.clone()).or_insert(0) += self_us;
+        }
+        for child in &self.children {
+            child.fold(&stack, stacks);
+        }
+    }
+}
+
+/// Frame names may not contain the stack separator or line breaks
+fn escape(name: &str) -> String {
+    name.replace(';', ":").replace(['\n', '\r'], " ")
+}
+
+/// Profile of one build or simulation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Profile {
+    /// Profile name
+    pub name: String,
+    
+    /// Top-level spans
+    pub roots: Vec<ProfileSpan>,
+}
+
+impl Profile {
+    /// Create an empty profile
+    pub fn new(name: impl Into<String>) -> Self {
+        Self { name: name.into(), roots: Vec::new() }
+    }
+    
+    /// Total time of the top-level spans
+    pub fn total_us(&self) -> u64 {
+        self.roots.iter().map(|root| root.duration_us).sum()
+    }
+    
+    /// Self time per stack, stacks being frame names joined with ';'
+    pub fn folded(&self) -> BTreeMap<String, u64> {
+        let mut stacks = BTreeMap::new();
+        for root in &self.roots {
+            root.fold("", &mut stacks);
+        }
+        stacks
+    }
+    
+    /// Render the profile in collapsed-stack format, one `stack weight` line per stack
+    pub fn to_collapsed(&self) -> String {
+        self.folded().iter().map(|(stack, weight)| format!("{} {}\n", stack, weight)).collect()
+    }
+    
+    /// Write the collapsed stacks to a file
+    pub fn save_collapsed<P: AsRef<Path>>(&self, path: P) -> Result<(), String> {
+        fs::write(path, self.to_collapsed()).map_err(|e| format!("Failed to write collapsed stacks: {}", e))
+    }
+    
+    /// Read collapsed stacks back into a profile
+    ///
+    /// Timing order is lost in the format, so sibling spans are laid out by name.
+    pub fn from_collapsed(name: impl Into<String>, collapsed: &str) -> Result<Self, String> {
+        let mut tree = FrameNode::default();
+        for (number, line) in collapsed.lines().enumerate().filter(|(_, line)| !line.trim().is_empty()) {
+            let (stack, weight) = line.trim_end().rsplit_once(' ')
+                .ok_or_else(|| format!("Line {}: missing weight", number + 1))?;
+            let weight = weight.parse::<u64>().map_err(|e| format!("Line {}: invalid weight: {}", number + 1, e))?;
+            tree.add(stack.split(';'), weight);
+        }
+        Ok(Self { name: name.into(), roots: tree.spans(0) })
+    }
+    
+    /// Lay out the merged frames of a flame graph
+    pub fn flame_graph(&self) -> FlameGraph {
+        let mut tree = FrameNode::default();
+        for (stack, weight) in self.folded() {
+            tree.add(stack.split(';'), weight);
+        }
+        let mut frames = Vec::new();
+        tree.layout(0, 0, &mut frames);
+        FlameGraph {
+            total_us: tree.children.values().map(|node| node.total).sum(),
+            max_depth: frames.iter().map(|frame| frame.depth).max().unwrap_or(0),
+            frames,
+        }
+    }
+}
+
+/// Frame of a flame graph
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FlameFrame {
+    /// Frame name
+    pub name: String,
+    
+    /// Full stack up to and including the frame
+    pub stack: String,
+    
+    /// Depth, 0 for top-level frames
+    pub depth: usize,
+    
+    /// Offset from the left edge in microseconds
+    pub x_us: u64,
+    
+    /// Width (total time including children) in microseconds
+    pub width_us: u64,
+    
+    /// Time spent in the frame itself
+    pub self_us: u64,
+}
+
+/// Flame graph ready for rendering
+///
+/// Frames with the same stack are merged and siblings are ordered by name, so
+/// widths show where time went rather than when.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FlameGraph {
+    /// Width of the whole graph in microseconds
+    pub total_us: u64,
+    
+    /// Deepest frame depth
+    pub max_depth: usize,
+    
+    /// Frames in depth-first order
+    pub frames: Vec<FlameFrame>,
+}
+
+impl FlameGraph {
+    /// Frame under a position, e.g. for hover tooltips
+    pub fn frame_at(&self, x_us: u64, depth: usize) -> Option<&FlameFrame> {
+        self.frames.iter().find(|frame| frame.depth == depth && frame.x_us <= x_us && x_us < frame.x_us + frame.width_us)
+    }
+    
+    /// Share of the total time taken by a frame
+    pub fn fraction(&self, frame: &FlameFrame) -> f64 {
+        if self.total_us == 0 {
+            return 0.0;
+        }
+        frame.width_us as f64 / self.total_us as f64
+    }
+    
+    /// Graph of the frames under a stack, for zooming into a frame
+    pub fn zoom(&self, stack: &str) -> FlameGraph {
+        let root = match self.frames.iter().find(|frame| frame.stack == stack) {
+            Some(root) => root,
+            None => return FlameGraph::default(),
+        };
+        let prefix = format!("{};", stack);
+        let frames: Vec<FlameFrame> = self.frames.iter()
+            .filter(|frame| frame.stack == stack || frame.stack.starts_with(&prefix))
+            .map(|frame| FlameFrame {
+                depth: frame.depth - root.depth,
+                x_us: frame.x_us - root.x_us,
+                ..frame.clone()
+            })
+            .collect();
+        FlameGraph {
+            total_us: root.width_us,
+            max_depth: frames.iter().map(|frame| frame.depth).max().unwrap_or(0),
+            frames,
+        }
+    }
+}
+
+/// Merged frame used to build flame graphs and span trees from folded stacks
+#[derive(Default)]
+struct FrameNode {
+    total: u64,
+    self_weight: u64,
+    children: BTreeMap<String, FrameNode>,
+}
+
+impl FrameNode {
+    fn add<'a>(&mut self, stack: impl Iterator<Item = &'a str>, weight: u64) {
+        let mut node = self;
+        for name in stack {
+            node = node.children.entry(name.to_string()).or_default();
+            node.total += weight;
+        }
+        node.self_weight += weight;
+    }
+    
+    fn layout(&self, depth: usize, x_us: u64, frames: &mut Vec<FlameFrame>) {
+        let parent = frames.last().filter(|_| depth > 0).map(|frame| frame.stack.clone());
+        let mut x_us = x_us;
+        for (name, node) in &self.children {
+            let stack = match &parent {
+                Some(parent) => format!("{};{}", parent, name),
+                None => name.clone(),
+            };
+            frames.push(FlameFrame {
+                name: name.clone(),
+                stack,
+                depth,
+                x_us,
+                width_us: node.total,
+                self_us: node.self_weight,
+            });
+            node.layout(depth + 1, x_us, frames);
+            x_us += node.total;
+        }
+    }
+    
+    fn spans(&self, start_us: u64) -> Vec<ProfileSpan> {
+        let mut start_us = start_us;
+        self.children.iter().map(|(name, node)| {
+            let span = ProfileSpan {
+                name: name.clone(),
+                start_us,
+                duration_us: node.total,
+                children: node.spans(start_us),
+            };
+            start_us += node.total;
+            span
+        }).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_folded_stacks_and_flame_graph() {
+        let mut build = ProfileSpan::new("build", 0, 100);
+        let mut kernel = ProfileSpan::new("Build Kernel", 10, 70);
+        kernel.children.push(ProfileSpan::new("vmlinux", 10, 60));
+        build.children.push(ProfileSpan::new("Configure Kernel", 0, 10));
+        build.children.push(kernel);
+        let mut profile = Profile::new("kernel");
+        profile.roots.push(build);
+        
+        let collapsed = profile.to_collapsed();
+        assert_eq!(collapsed, "build 20\nbuild;Build Kernel 10\nbuild;Build Kernel;vmlinux 60\nbuild;Configure Kernel 10\n");
+        assert_eq!(Profile::from_collapsed("kernel", &collapsed).unwrap().folded(), profile.folded());
+        
+        let graph = profile.flame_graph();
+        assert_eq!(graph.total_us, 100);
+        assert_eq!(graph.max_depth, 2);
+        let vmlinux = graph.frame_at(30, 2).unwrap();
+        assert_eq!((vmlinux.stack.as_str(), vmlinux.x_us, vmlinux.width_us), ("build;Build Kernel;vmlinux", 0, 60));
+        assert_eq!(graph.frame_at(75, 1).unwrap().name, "Configure Kernel");
+        
+        let zoomed = graph.zoom("build;Build Kernel");
+        assert_eq!(zoomed.total_us, 70);
+        assert_eq!(zoomed.frames.len(), 2);
+        assert!((zoomed.fraction(&zoomed.frames[1]) - 60.0 / 70.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,397 @@
+// Resource governor for OSland background work
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! Self-throttling of the IDE's own background work.
+//!
+//! Indexing, kernel extraction and graph layout run next to kernel builds that want
+//! every core. Background jobs ask the governor for permission at their natural yield
+//! points (between files). The governor samples the host and the IDE process with
+//! `sysinfo` and answers according to the configured limits:
+//!
+//! - while the IDE uses more than its CPU share, jobs sleep in proportion to the excess;
+//! - while the host is busy or the IDE is over its memory cap, indexing pauses until
+//!   the host recovers or the job is canceled, and extraction slows down.
+//!
+//! Layouts run on the UI thread, so they never sleep: they run fewer iterations instead.
+//!
+//! The process-wide governor stays inactive until the application configures it at
+//! startup, so library use and tests are never throttled.
+
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock, RwLock};
+use std::time::Duration;
+use serde::{Deserialize, Serialize};
+use sysinfo::{Pid, System};
+use super::audit::{self, AuditCategory};
+use super::cancellation::CancellationToken;
+use super::simulation;
+use super::CoreError;
+
+/// Fewest layout iterations run under load
+const MIN_LAYOUT_ITERATIONS: usize = 10;
+
+/// Longest single throttling sleep
+const MAX_THROTTLE_SLEEP: Duration = Duration::from_millis(500);
+
+/// Limits of the background work
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResourceLimits {
+    /// Share of the host CPU (percent of all cores) the IDE process may use
+    #[serde(default = "default_max_cpu_percent")]
+    pub max_cpu_percent: f32,
+    
+    /// Resident memory of the IDE process above which background work backs off
+    #[serde(default)]
+    pub max_memory_mb: Option<u64>,
+    
+    /// Host CPU usage (percent) above which the host counts as busy, e.g. by a kernel build
+    #[serde(default = "default_host_busy_percent")]
+    pub host_busy_percent: f32,
+    
+    /// Minimum time between two samples
+    #[serde(default = "default_sample_interval_ms")]
+    pub sample_interval_ms: u64,
+}
+
+fn default_max_cpu_percent() -> f32 {
+    50.0
+}
+
+fn default_host_busy_percent() -> f32 {
+    85.0
+}
+
+fn default_sample_interval_ms() -> u64 {
+    1000
+}
+
+impl Default for ResourceLimits {
+    fn default() -> Self {
+        Self {
+            max_cpu_percent: default_max_cpu_percent(),
+            max_memory_mb: None,
+            host_busy_percent: default_host_busy_percent(),
+            sample_interval_ms: default_sample_interval_ms(),
+        }
+    }
+}
+
+impl ResourceLimits {
+    /// Load limits from a JSON file
+    pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<Self, CoreError> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)
+            .map_err(|e| CoreError::ConfigError(format!("Failed to read {}: {}", path.display(), e)))?;
+        serde_json::from_str(&content)
+            .map_err(|e| CoreError::ConfigError(format!("Failed to parse {}: {}", path.display(), e)))
+    }
+}
+
+/// Kind of background work
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum BackgroundJob {
+    /// Search indexing
+    Indexing,
+    
+    /// Kernel component extraction
+    Extraction,
+}
+
+/// Resource usage at one point in time
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct ResourceSample {
+    /// Host CPU usage over all cores (percent)
+    pub host_cpu_percent: f32,
+    
+    /// CPU used by the IDE process, as a share of all cores (percent)
+    pub process_cpu_percent: f32,
+    
+    /// Resident memory of the IDE process
+    pub process_memory_mb: u64,
+}
+
+/// How much background work is held back
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum ThrottleLevel {
+    /// Within limits
+    Normal,
+    
+    /// The IDE is over its CPU share
+    Reduced,
+    
+    /// The host is busy or the IDE is over its memory cap
+    Constrained,
+}
+
+/// Source of resource samples
+pub trait ResourceMonitor: Send {
+    /// Measure the current usage
+    fn sample(&mut self) -> ResourceSample;
+}
+
+/// Monitor of the host and the current process based on `sysinfo`
+pub struct SysinfoMonitor {
+    system: System,
+    pid: Option<Pid>,
+}
+
+impl SysinfoMonitor {
+    /// Create a monitor of the current process
+    pub fn new() -> Self {
+        Self {
+            system: System::new(),
+            pid: sysinfo::get_current_pid().ok(),
+        }
+    }
+}
+
+impl Default for SysinfoMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ResourceMonitor for SysinfoMonitor {
+    fn sample(&mut self) -> ResourceSample {
+        // CPU usage is measured since the previous refresh, so the first sample reads zero
+        self.system.refresh_cpu();
+        let cores = self.system.cpus().len().max(1) as f32;
+        let mut sample = ResourceSample {
+            host_cpu_percent: self.system.global_cpu_info().cpu_usage(),
+            ..Default::default()
+        };
+        if let Some(pid) = self.pid {
+            if self.system.refresh_process(pid) {
+                if let Some(process) = self.system.process(pid) {
+                    sample.process_cpu_percent = process.cpu_usage() / cores;
+                    sample.process_memory_mb = process.memory() / (1024 * 1024);
+                }
+            }
+        }
+        sample
+    }
+}
+
+/// Paces background jobs according to resource limits
+pub struct ResourceGovernor {
+    active: AtomicBool,
+    limits: RwLock<ResourceLimits>,
+    monitor: Mutex<Box<dyn ResourceMonitor>>,
+    latest: Mutex<Option<(Duration, ResourceSample)>>,
+}
+
+impl ResourceGovernor {
+    /// Create a governor sampling with a monitor
+    pub fn new(limits: ResourceLimits, monitor: Box<dyn ResourceMonitor>) -> Self {
+        Self {
+            active: AtomicBool::new(true),
+            limits: RwLock::new(limits),
+            monitor: Mutex::new(monitor),
+            latest: Mutex::new(None),
+        }
+    }
+    
+    /// Get the process-wide governor, inactive until `configure` is called
+    pub fn global() -> &'static ResourceGovernor {
+        static GLOBAL: OnceLock<ResourceGovernor> = OnceLock::new();
+        GLOBAL.get_or_init(|| {
+            let governor = ResourceGovernor::new(ResourceLimits::default(), Box::new(SysinfoMonitor::new()));
+            governor.active.store(false, Ordering::SeqCst);
+            governor
+        })
+    }
+    
+    /// Apply limits and start throttling
+    pub fn configure(&self, limits: ResourceLimits) {
+        self.set_limits(limits);
+        self.active.store(true, Ordering::SeqCst);
+    }
+    
+    /// Check if the governor throttles background work
+    pub fn is_active(&self) -> bool {
+        self.active.load(Ordering::SeqCst)
+    }
+    
+    /// Replace the limits
+    pub fn set_limits(&self, limits: ResourceLimits) {
+        let previous = std::mem::replace(&mut *self.limits.write().unwrap(), limits.clone());
+        if previous != limits {
+            let summary = |limits: &ResourceLimits| serde_json::to_string(limits).ok();
+            audit::record(AuditCategory::Settings, "settings.resource_limits", "resource governor", summary(&previous), summary(&limits));
+        }
+    }
+    
+    /// Get the limits
+    pub fn limits(&self) -> ResourceLimits {
+        self.limits.read().unwrap().clone()
+    }
+    
+    /// Get a sample, taking a new one if the last is older than the sample interval
+    pub fn sample(&self) -> ResourceSample {
+        let interval = Duration::from_millis(self.limits.read().unwrap().sample_interval_ms);
+        let mut latest = self.latest.lock().unwrap();
+        match *latest {
+            Some((taken_at, sample)) if simulation::now().saturating_sub(taken_at) < interval => sample,
+            _ => {
+                let sample = self.monitor.lock().unwrap().sample();
+                *latest = Some((simulation::now(), sample));
+                sample
+            }
+        }
+    }
+    
+    /// Current throttle level
+    pub fn level(&self) -> ThrottleLevel {
+        if !self.is_active() {
+            return ThrottleLevel::Normal;
+        }
+        classify(&self.limits.read().unwrap(), &self.sample())
+    }
+    
+    /// Wait until a background job may continue
+    ///
+    /// Indexing waits for as long as the host is constrained; other jobs sleep for a
+    /// bounded time so they always make progress. Returns early once `token` is canceled.
+    pub fn throttle(&self, job: BackgroundJob, token: Option<&CancellationToken>) {
+        let canceled = || token.map_or(false, |token| token.is_canceled());
+        while self.is_active() {
+            let limits = self.limits();
+            let sample = self.sample();
+            let level = classify(&limits, &sample);
+            if level == ThrottleLevel::Normal || canceled() {
+                return;
+            }
+            if level == ThrottleLevel::Constrained && job == BackgroundJob::Indexing {
+                log::debug!("Indexing paused: host CPU {:.0}%, IDE memory {} MB", sample.host_cpu_percent, sample.process_memory_mb);
+                simulation::sleep(Duration::from_millis(limits.sample_interval_ms).min(MAX_THROTTLE_SLEEP));
+                continue;
+            }
+            simulation::sleep(throttle_delay(&limits, &sample, level));
+            return;
+        }
+    }
+    
+    /// Number of layout iterations to run instead of `requested` under the current load
+    pub fn layout_iterations(&self, requested: usize) -> usize {
+        let scaled = match self.level() {
+            ThrottleLevel::Normal => requested,
+            ThrottleLevel::Reduced => requested / 2,
+            ThrottleLevel::Constrained => requested / 4,
+        };
+        scaled.max(MIN_LAYOUT_ITERATIONS).min(requested)
+    }
+}
+
+/// Classify a sample against the limits
+fn classify(limits: &ResourceLimits, sample: &ResourceSample) -> ThrottleLevel {
+    let over_memory = limits.max_memory_mb.map_or(false, |max| sample.process_memory_mb > max);
+    if over_memory || sample.host_cpu_percent >= limits.host_busy_percent {
+        ThrottleLevel::Constrained
+    } else if sample.process_cpu_percent > limits.max_cpu_percent {
+        ThrottleLevel::Reduced
+    } else {
+        ThrottleLevel::Normal
+    }
+}
+
+/// Sleep that brings the process back to its CPU share, growing with the excess
+fn throttle_delay(limits: &ResourceLimits, sample: &ResourceSample, level: ThrottleLevel) -> Duration {
+    let excess = (sample.process_cpu_percent / limits.max_cpu_percent.max(1.0)).max(1.0);
+    let base = match level {
+        ThrottleLevel::Normal => return Duration::ZERO,
+        ThrottleLevel::Reduced => 20.0,
+        ThrottleLevel::Constrained => 100.0,
+    };
+    Duration::from_millis((base * excess) as u64).min(MAX_THROTTLE_SLEEP)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::simulation::Simulation;
+    use std::sync::Arc;
+    use std::time::Instant;
+    
+    /// Monitor returning whatever sample the test set last
+    struct FixedMonitor(Arc<Mutex<ResourceSample>>);
+    
+    impl ResourceMonitor for FixedMonitor {
+        fn sample(&mut self) -> ResourceSample {
+            *self.0.lock().unwrap()
+        }
+    }
+    
+    #[test]
+    fn test_levels_and_layout_iterations() {
+        let current = Arc::new(Mutex::new(ResourceSample::default()));
+        let limits = ResourceLimits { max_memory_mb: Some(1024), sample_interval_ms: 0, ..Default::default() };
+        let governor = ResourceGovernor::new(limits, Box::new(FixedMonitor(current.clone())));
+        assert_eq!(governor.level(), ThrottleLevel::Normal);
+        assert_eq!(governor.layout_iterations(100), 100);
+        
+        *current.lock().unwrap() = ResourceSample { process_cpu_percent: 80.0, ..Default::default() };
+        assert_eq!(governor.level(), ThrottleLevel::Reduced);
+        assert_eq!(governor.layout_iterations(100), 50);
+        
+        // A kernel build saturating the host
+        *current.lock().unwrap() = ResourceSample { host_cpu_percent: 97.0, ..Default::default() };
+        assert_eq!(governor.level(), ThrottleLevel::Constrained);
+        assert_eq!(governor.layout_iterations(100), 25);
+        assert_eq!(governor.layout_iterations(20), 10);
+        assert_eq!(governor.layout_iterations(5), 5);
+        
+        *current.lock().unwrap() = ResourceSample { process_memory_mb: 2048, ..Default::default() };
+        assert_eq!(governor.level(), ThrottleLevel::Constrained);
+    }
+    
+    #[test]
+    fn test_indexing_pauses_until_canceled() {
+        let current = Arc::new(Mutex::new(ResourceSample { host_cpu_percent: 99.0, ..Default::default() }));
+        let limits = ResourceLimits { sample_interval_ms: 10, ..Default::default() };
+        let governor = Arc::new(ResourceGovernor::new(limits, Box::new(FixedMonitor(current.clone()))));
+        
+        let token = CancellationToken::new();
+        let waiter = {
+            let governor = governor.clone();
+            let token = token.clone();
+            std::thread::spawn(move || governor.throttle(BackgroundJob::Indexing, Some(&token)))
+        };
+        std::thread::sleep(Duration::from_millis(50));
+        assert!(!waiter.is_finished());
+        token.cancel();
+        waiter.join().unwrap();
+        
+        // Extraction slows down but never waits indefinitely
+        let started = Instant::now();
+        governor.throttle(BackgroundJob::Extraction, None);
+        assert!(started.elapsed() <= MAX_THROTTLE_SLEEP + Duration::from_millis(100));
+    }
+    
+    #[test]
+    fn test_indexing_resumes_when_host_calms_down() {
+        let current = Arc::new(Mutex::new(ResourceSample { host_cpu_percent: 99.0, ..Default::default() }));
+        let limits = ResourceLimits { sample_interval_ms: 100, ..Default::default() };
+        let governor = Arc::new(ResourceGovernor::new(limits, Box::new(FixedMonitor(current.clone()))));
+        let resumed_at = Arc::new(Mutex::new(None));
+        
+        // Runs on simulated time: the pause takes no wall-clock time
+        let mut sim = Simulation::new(1);
+        let resumed = resumed_at.clone();
+        sim.spawn("indexer", move || {
+            governor.throttle(BackgroundJob::Indexing, None);
+            *resumed.lock().unwrap() = Some(simulation::now());
+        });
+        sim.spawn("build", move || {
+            simulation::sleep(Duration::from_secs(30));
+            *simulation::lock(&current) = ResourceSample::default();
+        });
+        sim.run().unwrap_or_else(|e| panic!("{}", e));
+        
+        let resumed_at = resumed_at.lock().unwrap().unwrap();
+        assert!(resumed_at >= Duration::from_secs(30));
+        assert!(resumed_at <= Duration::from_secs(30) + MAX_THROTTLE_SLEEP);
+    }
+}
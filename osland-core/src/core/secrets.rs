@@ -0,0 +1,595 @@
+// Project secrets vault for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! Encrypted storage for the sensitive parts of a project.
+//!
+//! API keys, signing keys and proprietary component sources are kept in
+//! `.osland/vault.json`, which can be committed and shared with the rest of the project:
+//! every entry is encrypted with AES-256-GCM under a random vault key. The vault key is
+//! in turn wrapped by one or more key slots, so a vault can be unlocked with a
+//! passphrase (stretched with Argon2id), with the operating system keychain, or both.
+//!
+//! Entry names and the vault ID are authenticated with every ciphertext, so entries
+//! cannot be swapped or moved between vaults without detection. Sealed files also
+//! authenticate the path they are written back to.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Nonce};
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::Engine as _;
+use rand_core::{OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use super::CoreError;
+
+/// Vault file, relative to the project directory
+pub const VAULT_FILE: &str = ".osland/vault.json";
+
+/// Environment variable holding the vault passphrase for non-interactive use
+pub const VAULT_PASSPHRASE_ENV: &str = "OSLAND_VAULT_PASSPHRASE";
+
+/// Keychain service the vault keys are stored under
+const KEYCHAIN_SERVICE: &str = "osland-vault";
+
+/// Current vault format
+const VAULT_VERSION: u32 = 1;
+
+/// Prefix of the entries holding sealed files
+const FILE_ENTRY_PREFIX: &str = "file:";
+
+/// Associated data of the key check blob
+const KEY_CHECK_CONTEXT: &str = "key-check";
+
+/// Largest Argon2 memory cost accepted from a vault file (1 GiB)
+const MAX_KDF_MEMORY_KIB: u32 = 1024 * 1024;
+
+/// Largest Argon2 pass count accepted from a vault file
+const MAX_KDF_ITERATIONS: u32 = 16;
+
+/// Largest Argon2 parallelism accepted from a vault file
+const MAX_KDF_PARALLELISM: u32 = 8;
+
+/// Nonce and ciphertext of an encrypted value, base64 encoded
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SealedBlob {
+    /// 96-bit AES-GCM nonce
+    pub nonce: String,
+    
+    /// Ciphertext followed by the authentication tag
+    pub ciphertext: String,
+}
+
+/// Argon2id cost parameters of a passphrase slot
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KdfParams {
+    /// Memory cost in KiB
+    pub memory_kib: u32,
+    
+    /// Number of passes
+    pub iterations: u32,
+    
+    /// Degree of parallelism
+    pub parallelism: u32,
+}
+
+impl Default for KdfParams {
+    fn default() -> Self {
+        // OWASP recommended minimum for Argon2id
+        Self { memory_kib: 19 * 1024, iterations: 2, parallelism: 1 }
+    }
+}
+
+impl KdfParams {
+    /// Parameters limited to what Argon2 accepts and a machine can afford
+    ///
+    /// Vault files are shared, so a tampered slot must not make unlocking
+    /// allocate unbounded memory or spin for hours.
+    pub fn clamped(self) -> Self {
+        let parallelism = self.parallelism.clamp(1, MAX_KDF_PARALLELISM);
+        Self {
+            memory_kib: self.memory_kib.clamp(8 * parallelism, MAX_KDF_MEMORY_KIB),
+            iterations: self.iterations.clamp(1, MAX_KDF_ITERATIONS),
+            parallelism,
+        }
+    }
+}
+
+/// Way of unlocking the vault key
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum KeySlot {
+    /// Vault key wrapped with a key derived from a passphrase
+    Passphrase {
+        /// Base64 salt of the key derivation
+        salt: String,
+        
+        /// Key derivation cost
+        kdf: KdfParams,
+        
+        /// Wrapped vault key
+        wrapped_key: SealedBlob,
+    },
+    
+    /// Vault key stored in the keychain of the user's machine
+    Keychain,
+}
+
+/// Credential unlocking a vault
+#[derive(Clone)]
+pub enum VaultUnlock {
+    /// Passphrase of a passphrase slot
+    Passphrase(String),
+    
+    /// Vault key from the operating system keychain
+    Keychain,
+}
+
+impl std::fmt::Debug for VaultUnlock {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VaultUnlock::Passphrase(_) => f.write_str("Passphrase(<redacted>)"),
+            VaultUnlock::Keychain => f.write_str("Keychain"),
+        }
+    }
+}
+
+impl VaultUnlock {
+    /// Passphrase from `OSLAND_VAULT_PASSPHRASE` if set, otherwise the keychain
+    pub fn from_env() -> Self {
+        match std::env::var(VAULT_PASSPHRASE_ENV) {
+            Ok(passphrase) if !passphrase.is_empty() => VaultUnlock::Passphrase(passphrase),
+            _ => VaultUnlock::Keychain,
+        }
+    }
+}
+
+/// Unlocked vault key, wiped from memory when dropped
+pub struct VaultKey([u8; 32]);
+
+impl VaultKey {
+    fn generate() -> Self {
+        let mut key = [0u8; 32];
+        OsRng.fill_bytes(&mut key);
+        Self(key)
+    }
+    
+    fn cipher(&self) -> Aes256Gcm {
+        Aes256Gcm::new_from_slice(&self.0).expect("vault keys are 32 bytes")
+    }
+}
+
+impl Drop for VaultKey {
+    fn drop(&mut self) {
+        for byte in self.0.iter_mut() {
+            // Volatile writes so the wipe is not optimized away
+            unsafe { std::ptr::write_volatile(byte, 0) };
+        }
+    }
+}
+
+/// What an entry holds
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum VaultEntryKind {
+    /// Secret value such as an API key or a signing key
+    Secret,
+    
+    /// Content of a project file removed from the working tree
+    File {
+        /// Path relative to the project directory
+        path: PathBuf,
+    },
+}
+
+/// Encrypted entry of the vault
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VaultEntry {
+    /// What the entry holds
+    pub kind: VaultEntryKind,
+    
+    /// Encrypted content
+    pub sealed: SealedBlob,
+    
+    /// Last change time (milliseconds)
+    pub updated_at: u64,
+}
+
+/// Encrypted secrets of a project
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProjectVault {
+    /// Vault format version
+    pub version: u32,
+    
+    /// Vault ID, bound to every ciphertext
+    pub vault_id: String,
+    
+    /// Ways of unlocking the vault key
+    pub key_slots: Vec<KeySlot>,
+    
+    /// Empty value encrypted with the vault key, to check a key before use
+    pub key_check: SealedBlob,
+    
+    /// Entries by name
+    #[serde(default)]
+    pub entries: BTreeMap<String, VaultEntry>,
+}
+
+impl ProjectVault {
+    /// Create an empty vault unlocked by a passphrase or the keychain
+    pub fn create(unlock: &VaultUnlock) -> Result<(Self, VaultKey), CoreError> {
+        Self::create_with_kdf(unlock, KdfParams::default())
+    }
+    
+    /// Create an empty vault with explicit key derivation costs
+    pub fn create_with_kdf(unlock: &VaultUnlock, kdf: KdfParams) -> Result<(Self, VaultKey), CoreError> {
+        let key = VaultKey::generate();
+        let vault_id = uuid::Uuid::new_v4().to_string();
+        let key_check = seal(&key, &vault_id, KEY_CHECK_CONTEXT, &[])?;
+        let mut vault = Self {
+            version: VAULT_VERSION,
+            vault_id,
+            key_slots: Vec::new(),
+            key_check,
+            entries: BTreeMap::new(),
+        };
+        vault.add_unlock_with_kdf(&key, unlock, kdf)?;
+        Ok((vault, key))
+    }
+    
+    /// Path of the vault of a project
+    pub fn path(project_dir: &Path) -> PathBuf {
+        project_dir.join(VAULT_FILE)
+    }
+    
+    /// Check if a project has a vault
+    pub fn exists(project_dir: &Path) -> bool {
+        Self::path(project_dir).exists()
+    }
+    
+    /// Load the vault of a project
+    pub fn load(project_dir: &Path) -> Result<Self, CoreError> {
+        let path = Self::path(project_dir);
+        let content = fs::read_to_string(&path)
+            .map_err(|e| CoreError::SecretError(format!("Failed to read {}: {}", path.display(), e)))?;
+        let vault: Self = serde_json::from_str(&content)
+            .map_err(|e| CoreError::SecretError(format!("Failed to parse {}: {}", path.display(), e)))?;
+        if vault.version > VAULT_VERSION {
+            return Err(CoreError::SecretError(format!("Vault format {} is newer than this version of OSland supports", vault.version)));
+        }
+        Ok(vault)
+    }
+    
+    /// Save the vault of a project
+    pub fn save(&self, project_dir: &Path) -> Result<(), CoreError> {
+        let path = Self::path(project_dir);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| CoreError::SecretError(format!("Failed to create {}: {}", parent.display(), e)))?;
+        }
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| CoreError::SecretError(format!("Failed to serialize vault: {}", e)))?;
+        fs::write(&path, json)
+            .map_err(|e| CoreError::SecretError(format!("Failed to write {}: {}", path.display(), e)))
+    }
+    
+    /// Unlock the vault key
+    pub fn unlock(&self, unlock: &VaultUnlock) -> Result<VaultKey, CoreError> {
+        let key = match unlock {
+            VaultUnlock::Passphrase(passphrase) => {
+                let mut key = None;
+                for slot in &self.key_slots {
+                    if let KeySlot::Passphrase { salt, kdf, wrapped_key } = slot {
+                        let wrapping_key = derive_key(passphrase, &decode(salt)?, *kdf)?;
+                        if let Ok(bytes) = open(&wrapping_key, &self.vault_id, "passphrase-slot", wrapped_key) {
+                            key = Some(key_from_bytes(&bytes)?);
+                            break;
+                        }
+                    }
+                }
+                key.ok_or_else(|| CoreError::SecretError("Wrong vault passphrase".to_string()))?
+            }
+            VaultUnlock::Keychain => {
+                if !self.key_slots.contains(&KeySlot::Keychain) {
+                    return Err(CoreError::SecretError("The vault cannot be unlocked with the keychain".to_string()));
+                }
+                let encoded = keychain_entry(&self.vault_id)?.get_password()
+                    .map_err(|e| CoreError::SecretError(format!("Vault key not found in the keychain: {}", e)))?;
+                key_from_bytes(&decode(&encoded)?)?
+            }
+        };
+        open(&key, &self.vault_id, KEY_CHECK_CONTEXT, &self.key_check)
+            .map_err(|_| CoreError::SecretError("The unlocked key does not belong to this vault".to_string()))?;
+        Ok(key)
+    }
+    
+    /// Allow unlocking the vault another way
+    pub fn add_unlock(&mut self, key: &VaultKey, unlock: &VaultUnlock) -> Result<(), CoreError> {
+        self.add_unlock_with_kdf(key, unlock, KdfParams::default())
+    }
+    
+    fn add_unlock_with_kdf(&mut self, key: &VaultKey, unlock: &VaultUnlock, kdf: KdfParams) -> Result<(), CoreError> {
+        match unlock {
+            VaultUnlock::Passphrase(passphrase) => {
+                if passphrase.is_empty() {
+                    return Err(CoreError::SecretError("The vault passphrase cannot be empty".to_string()));
+                }
+                let mut salt = [0u8; 16];
+                OsRng.fill_bytes(&mut salt);
+                let wrapping_key = derive_key(passphrase, &salt, kdf)?;
+                let wrapped_key = seal(&wrapping_key, &self.vault_id, "passphrase-slot", &key.0)?;
+                self.key_slots.push(KeySlot::Passphrase { salt: encode(&salt), kdf, wrapped_key });
+            }
+            VaultUnlock::Keychain => {
+                keychain_entry(&self.vault_id)?.set_password(&encode(&key.0))
+                    .map_err(|e| CoreError::SecretError(format!("Failed to store the vault key in the keychain: {}", e)))?;
+                if !self.key_slots.contains(&KeySlot::Keychain) {
+                    self.key_slots.push(KeySlot::Keychain);
+                }
+            }
+        }
+        Ok(())
+    }
+    
+    /// Stop unlocking the vault with the keychain and remove the key from it
+    pub fn remove_keychain(&mut self) -> Result<(), CoreError> {
+        if !self.key_slots.iter().any(|slot| matches!(slot, KeySlot::Passphrase { .. })) {
+            return Err(CoreError::SecretError("Add a passphrase before removing the keychain, or the vault cannot be unlocked".to_string()));
+        }
+        self.key_slots.retain(|slot| *slot != KeySlot::Keychain);
+        match keychain_entry(&self.vault_id)?.delete_password() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(e) => Err(CoreError::SecretError(format!("Failed to remove the vault key from the keychain: {}", e))),
+        }
+    }
+    
+    /// Store a secret value
+    pub fn set_secret(&mut self, key: &VaultKey, name: &str, value: &[u8]) -> Result<(), CoreError> {
+        if name.is_empty() || name.starts_with(FILE_ENTRY_PREFIX) {
+            return Err(CoreError::SecretError(format!("Invalid secret name '{}'", name)));
+        }
+        self.put(key, name.to_string(), VaultEntryKind::Secret, value)
+    }
+    
+    /// Decrypt a secret value, or `None` if there is no such secret
+    pub fn secret(&self, key: &VaultKey, name: &str) -> Result<Option<Vec<u8>>, CoreError> {
+        match self.entries.get(name) {
+            Some(entry) => open(key, &self.vault_id, &entry_context(name, &entry.kind), &entry.sealed).map(Some),
+            None => Ok(None),
+        }
+    }
+    
+    /// Decrypt a secret holding text
+    pub fn secret_string(&self, key: &VaultKey, name: &str) -> Result<Option<String>, CoreError> {
+        self.secret(key, name)?
+            .map(|bytes| String::from_utf8(bytes).map_err(|_| CoreError::SecretError(format!("Secret '{}' is not text", name))))
+            .transpose()
+    }
+    
+    /// Remove an entry; returns whether it existed
+    pub fn remove(&mut self, name: &str) -> bool {
+        self.entries.remove(name).is_some()
+    }
+    
+    /// Names of the entries
+    pub fn names(&self) -> Vec<&str> {
+        self.entries.keys().map(String::as_str).collect()
+    }
+    
+    /// Move a project file into the vault, removing the plaintext from the working tree
+    ///
+    /// The vault is saved before the plaintext is removed, so a failed save never
+    /// loses the file.
+    pub fn seal_file(&mut self, key: &VaultKey, project_dir: &Path, path: &Path) -> Result<(), CoreError> {
+        let relative = relative_project_path(project_dir, path)?;
+        let full_path = project_dir.join(&relative);
+        let content = fs::read(&full_path)
+            .map_err(|e| CoreError::SecretError(format!("Failed to read {}: {}", full_path.display(), e)))?;
+        let name = format!("{}{}", FILE_ENTRY_PREFIX, relative.to_string_lossy().replace('\\', "/"));
+        let previous = self.entries.get(&name).cloned();
+        self.put(key, name.clone(), VaultEntryKind::File { path: relative }, &content)?;
+        if let Err(e) = self.save(project_dir) {
+            match previous {
+                Some(entry) => self.entries.insert(name, entry),
+                None => self.entries.remove(&name),
+            };
+            return Err(e);
+        }
+        fs::remove_file(&full_path)
+            .map_err(|e| CoreError::SecretError(format!("Failed to remove {}: {}", full_path.display(), e)))
+    }
+    
+    /// Write the sealed files back into the project; returns their paths
+    pub fn unseal_files(&self, key: &VaultKey, project_dir: &Path) -> Result<Vec<PathBuf>, CoreError> {
+        let mut written = Vec::new();
+        for (name, entry) in &self.entries {
+            let VaultEntryKind::File { path } = &entry.kind else { continue };
+            let relative = relative_project_path(project_dir, path)?;
+            let content = open(key, &self.vault_id, &entry_context(name, &entry.kind), &entry.sealed)?;
+            let full_path = project_dir.join(&relative);
+            if let Some(parent) = full_path.parent() {
+                fs::create_dir_all(parent)
+                    .map_err(|e| CoreError::SecretError(format!("Failed to create {}: {}", parent.display(), e)))?;
+            }
+            fs::write(&full_path, content)
+                .map_err(|e| CoreError::SecretError(format!("Failed to write {}: {}", full_path.display(), e)))?;
+            written.push(relative);
+        }
+        Ok(written)
+    }
+    
+    fn put(&mut self, key: &VaultKey, name: String, kind: VaultEntryKind, value: &[u8]) -> Result<(), CoreError> {
+        let sealed = seal(key, &self.vault_id, &entry_context(&name, &kind), value)?;
+        self.entries.insert(name, VaultEntry { kind, sealed, updated_at: current_timestamp() });
+        Ok(())
+    }
+}
+
+/// Read a secret of a project's vault, unlocking it from the environment
+///
+/// Used by build steps, which cannot prompt: the passphrase comes from
+/// `OSLAND_VAULT_PASSPHRASE`, otherwise the key comes from the keychain.
+pub fn read_project_secret(project_dir: &Path, name: &str) -> Result<Vec<u8>, CoreError> {
+    let vault = ProjectVault::load(project_dir)?;
+    let key = vault.unlock(&VaultUnlock::from_env())?;
+    vault.secret(&key, name)?
+        .ok_or_else(|| CoreError::SecretError(format!("Secret '{}' is not in the vault of {}", name, project_dir.display())))
+}
+
+/// Associated data of an entry: its name, plus the target path of a sealed file
+fn entry_context(name: &str, kind: &VaultEntryKind) -> String {
+    match kind {
+        VaultEntryKind::File { path } => format!("{}\0{}", name, path.to_string_lossy().replace('\\', "/")),
+        VaultEntryKind::Secret => name.to_string(),
+    }
+}
+
+/// Check that a path stays inside the project and make it relative to it
+fn relative_project_path(project_dir: &Path, path: &Path) -> Result<PathBuf, CoreError> {
+    let relative = path.strip_prefix(project_dir).unwrap_or(path);
+    let escapes = relative.components().any(|component| !matches!(component, Component::Normal(_) | Component::CurDir));
+    if escapes || relative.as_os_str().is_empty() {
+        return Err(CoreError::SecretError(format!("{} is not a file inside the project", path.display())));
+    }
+    Ok(relative.to_path_buf())
+}
+
+/// Derive a wrapping key from a passphrase with Argon2id
+fn derive_key(passphrase: &str, salt: &[u8], kdf: KdfParams) -> Result<VaultKey, CoreError> {
+    let kdf = kdf.clamped();
+    let params = Params::new(kdf.memory_kib, kdf.iterations, kdf.parallelism, Some(32))
+        .map_err(|e| CoreError::SecretError(format!("Invalid key derivation parameters: {}", e)))?;
+    let mut key = VaultKey([0u8; 32]);
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key.0)
+        .map_err(|e| CoreError::SecretError(format!("Key derivation failed: {}", e)))?;
+    Ok(key)
+}
+
+/// Encrypt a value, authenticating the vault ID and a context such as the entry name
+fn seal(key: &VaultKey, vault_id: &str, context: &str, plaintext: &[u8]) -> Result<SealedBlob, CoreError> {
+    let mut nonce = [0u8; 12];
+    OsRng.fill_bytes(&mut nonce);
+    let aad = format!("osland-vault/{}/{}", vault_id, context);
+    let ciphertext = key.cipher()
+        .encrypt(Nonce::from_slice(&nonce), Payload { msg: plaintext, aad: aad.as_bytes() })
+        .map_err(|_| CoreError::SecretError("Encryption failed".to_string()))?;
+    Ok(SealedBlob { nonce: encode(&nonce), ciphertext: encode(&ciphertext) })
+}
+
+/// Decrypt a value sealed with the same vault ID and context
+fn open(key: &VaultKey, vault_id: &str, context: &str, sealed: &SealedBlob) -> Result<Vec<u8>, CoreError> {
+    let nonce = decode(&sealed.nonce)?;
+    if nonce.len() != 12 {
+        return Err(CoreError::SecretError(format!("Invalid nonce for '{}'", context)));
+    }
+    let aad = format!("osland-vault/{}/{}", vault_id, context);
+    key.cipher()
+        .decrypt(Nonce::from_slice(&nonce), Payload { msg: &decode(&sealed.ciphertext)?, aad: aad.as_bytes() })
+        .map_err(|_| CoreError::SecretError(format!("Failed to decrypt '{}': wrong key or tampered vault", context)))
+}
+
+fn key_from_bytes(bytes: &[u8]) -> Result<VaultKey, CoreError> {
+    let key: [u8; 32] = bytes.try_into()
+        .map_err(|_| CoreError::SecretError("Vault keys must be 32 bytes".to_string()))?;
+    Ok(VaultKey(key))
+}
+
+fn keychain_entry(vault_id: &str) -> Result<keyring::Entry, CoreError> {
+    keyring::Entry::new(KEYCHAIN_SERVICE, vault_id)
+        .map_err(|e| CoreError::SecretError(format!("Invalid keychain entry for vault {}: {}", vault_id, e)))
+}
+
+fn encode(bytes: &[u8]) -> String {
+    base64::engine::general_purpose::STANDARD.encode(bytes)
+}
+
+fn decode(text: &str) -> Result<Vec<u8>, CoreError> {
+    base64::engine::general_purpose::STANDARD.decode(text)
+        .map_err(|e| CoreError::SecretError(format!("Invalid base64 in vault: {}", e)))
+}
+
+/// Get current timestamp in milliseconds
+fn current_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    // Cheap key derivation so the tests stay fast
+    const TEST_KDF: KdfParams = KdfParams { memory_kib: 64, iterations: 1, parallelism: 1 };
+    
+    fn passphrase(text: &str) -> VaultUnlock {
+        VaultUnlock::Passphrase(text.to_string())
+    }
+    
+    #[test]
+    fn test_secrets_round_trip() {
+        let project = tempfile::tempdir().unwrap();
+        let (mut vault, key) = ProjectVault::create_with_kdf(&passphrase("correct horse"), TEST_KDF).unwrap();
+        vault.set_secret(&key, "provider/openai/api-key", b"sk-test").unwrap();
+        vault.add_unlock_with_kdf(&key, &passphrase("second"), TEST_KDF).unwrap();
+        vault.save(project.path()).unwrap();
+        
+        // Only ciphertext is written to the project
+        let saved = fs::read_to_string(ProjectVault::path(project.path())).unwrap();
+        assert!(!saved.contains("sk-test"));
+        
+        let vault = ProjectVault::load(project.path()).unwrap();
+        assert!(vault.unlock(&passphrase("wrong")).is_err());
+        let key = vault.unlock(&passphrase("second")).unwrap();
+        assert_eq!(vault.secret_string(&key, "provider/openai/api-key").unwrap().as_deref(), Some("sk-test"));
+        assert_eq!(vault.secret(&key, "missing").unwrap(), None);
+        
+        // Entries are bound to their names
+        let mut tampered = vault.clone();
+        let sealed = tampered.entries["provider/openai/api-key"].clone();
+        tampered.entries.insert("signing-key".to_string(), sealed);
+        assert!(tampered.secret(&key, "signing-key").is_err());
+    }
+    
+    #[test]
+    fn test_seal_and_unseal_files() {
+        let project = tempfile::tempdir().unwrap();
+        let source = project.path().join("components/blob/driver.c");
+        fs::create_dir_all(source.parent().unwrap()).unwrap();
+        fs::write(&source, "int secret_sauce(void);").unwrap();
+        
+        let (mut vault, key) = ProjectVault::create_with_kdf(&passphrase("pw"), TEST_KDF).unwrap();
+        vault.seal_file(&key, project.path(), &source).unwrap();
+        assert!(!source.exists());
+        assert_eq!(vault.names(), vec!["file:components/blob/driver.c"]);
+        assert!(vault.seal_file(&key, project.path(), Path::new("../outside.c")).is_err());
+        assert!(vault.set_secret(&key, "file:fake", b"x").is_err());
+        
+        // The vault holding the file was saved before the plaintext was removed
+        let saved = ProjectVault::load(project.path()).unwrap();
+        assert_eq!(saved.names(), vec!["file:components/blob/driver.c"]);
+        
+        // Pointing a sealed file at another path is detected
+        let mut tampered = vault.clone();
+        tampered.entries.get_mut("file:components/blob/driver.c").unwrap().kind = VaultEntryKind::File { path: PathBuf::from("build.rs") };
+        assert!(tampered.unseal_files(&key, project.path()).is_err());
+        assert!(!project.path().join("build.rs").exists());
+        
+        let written = vault.unseal_files(&key, project.path()).unwrap();
+        assert_eq!(written, vec![PathBuf::from("components/blob/driver.c")]);
+        assert_eq!(fs::read_to_string(&source).unwrap(), "int secret_sauce(void);");
+    }
+    
+    #[test]
+    fn test_kdf_params_are_clamped() {
+        let absurd = KdfParams { memory_kib: u32::MAX, iterations: u32::MAX, parallelism: 0 };
+        assert_eq!(absurd.clamped(), KdfParams { memory_kib: MAX_KDF_MEMORY_KIB, iterations: MAX_KDF_ITERATIONS, parallelism: 1 });
+        assert_eq!(KdfParams::default().clamped(), KdfParams::default());
+        assert_eq!(TEST_KDF.clamped(), TEST_KDF);
+    }
+}
@@ -0,0 +1,603 @@
+// Deterministic simulation for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! Deterministic scheduling and time for concurrency tests.
+//!
+//! Code that runs concurrently (the build engine, table storage, collaboration sync and
+//! background jobs) reads time through [`now`] and [`unix_millis`], sleeps through
+//! [`sleep`] and takes locks through [`lock`], [`read`] and [`write`]. Outside a
+//! simulation these are thin wrappers over the system clock and `std::sync`.
+//!
+//! Inside a [`Simulation`] every task runs on its own thread, but only one runs at a
+//! time. At each lock, sleep or [`yield_now`] the running task hands control to a
+//! scheduler that picks the next task with a seeded random generator, and time is a
+//! logical clock that only advances when every task sleeps. The same seed therefore
+//! always produces the same interleaving: a lock-ordering deadlock found by [`explore`]
+//! is reported with its seed and replayed with `OSLAND_SIM_SEED=<seed>`.
+//!
+//! Locks taken with plain `std::sync` calls, or by threads the tasks spawn themselves,
+//! are invisible to the scheduler; a task blocked on one is reported as stalled.
+
+use std::cell::RefCell;
+use std::panic::{self, AssertUnwindSafe, Location};
+use std::sync::{Arc, Condvar, LockResult, Mutex, MutexGuard, OnceLock, RwLock, RwLockReadGuard, RwLockWriteGuard, TryLockError, TryLockResult};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Environment variable selecting the single seed `explore` runs
+pub const SEED_ENV: &str = "OSLAND_SIM_SEED";
+
+/// Scheduling steps after which a simulation is considered livelocked
+const DEFAULT_MAX_STEPS: usize = 100_000;
+
+/// Wall-clock time a task may run without reaching a scheduling point
+const DEFAULT_STALL_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Unix time at which simulated clocks start (2025-01-01T00:00:00Z)
+const SIMULATED_EPOCH_MILLIS: u64 = 1_735_689_600_000;
+
+/// Trace entries included in error messages
+const TRACE_TAIL: usize = 20;
+
+/// Elapsed time on the current clock: simulated inside a simulation, monotonic otherwise
+pub fn now() -> Duration {
+    match context() {
+        Some((shared, _)) => shared.scheduler.lock().unwrap().elapsed,
+        None => process_start().elapsed(),
+    }
+}
+
+/// Milliseconds since the Unix epoch on the current clock
+pub fn unix_millis() -> u64 {
+    match context() {
+        Some((shared, _)) => SIMULATED_EPOCH_MILLIS + shared.scheduler.lock().unwrap().elapsed.as_millis() as u64,
+        None => SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64,
+    }
+}
+
+/// Sleep on the current clock
+///
+/// A simulated sleep lets the other tasks run and resumes once the logical clock
+/// reaches the wake-up time.
+#[track_caller]
+pub fn sleep(duration: Duration) {
+    match context() {
+        Some((shared, id)) => {
+            let wake_at = shared.scheduler.lock().unwrap().elapsed + duration;
+            switch(&shared, id, TaskStatus::Sleeping(wake_at), format!("sleep {:?} at {}", duration, Location::caller()));
+        }
+        None => thread::sleep(duration),
+    }
+}
+
+/// Let the scheduler run another task; a no-op outside simulations
+#[track_caller]
+pub fn yield_now() {
+    if let Some((shared, id)) = context() {
+        switch(&shared, id, TaskStatus::Runnable, format!("yield at {}", Location::caller()));
+    }
+}
+
+/// Lock a mutex, panicking if it is poisoned
+#[track_caller]
+pub fn lock<T: ?Sized>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    acquire(|| mutex.try_lock(), || mutex.lock())
+}
+
+/// Read-lock a `RwLock`, panicking if it is poisoned
+#[track_caller]
+pub fn read<T: ?Sized>(lock: &RwLock<T>) -> RwLockReadGuard<'_, T> {
+    acquire(|| lock.try_read(), || lock.read())
+}
+
+/// Write-lock a `RwLock`, panicking if it is poisoned
+#[track_caller]
+pub fn write<T: ?Sized>(lock: &RwLock<T>) -> RwLockWriteGuard<'_, T> {
+    acquire(|| lock.try_write(), || lock.write())
+}
+
+/// Acquire a lock, retrying under the scheduler while another task holds it
+#[track_caller]
+fn acquire<G>(try_acquire: impl Fn() -> TryLockResult<G>, acquire_blocking: impl FnOnce() -> LockResult<G>) -> G {
+    let Some((shared, id)) = context() else {
+        return acquire_blocking().unwrap();
+    };
+    let location = Location::caller();
+    switch(&shared, id, TaskStatus::Runnable, format!("lock at {}", location));
+    loop {
+        match try_acquire() {
+            Ok(guard) => return guard,
+            Err(TryLockError::WouldBlock) => {
+                switch(&shared, id, TaskStatus::Blocked(location), format!("blocked at {}", location));
+            }
+            Err(TryLockError::Poisoned(e)) => panic!("{} at {}", e, location),
+        }
+    }
+}
+
+fn process_start() -> Instant {
+    static START: OnceLock<Instant> = OnceLock::new();
+    *START.get_or_init(Instant::now)
+}
+
+/// Failure of a simulation run
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum SimulationError {
+    #[error("deadlock with seed {seed}: {}\n{}", .blocked.join(", "), trace_tail(.trace))]
+    Deadlock {
+        seed: u64,
+        /// Blocked tasks and where they wait
+        blocked: Vec<String>,
+        trace: Vec<String>,
+    },
+    
+    #[error("task '{task}' panicked with seed {seed}: {message}\n{}", trace_tail(.trace))]
+    Panicked {
+        seed: u64,
+        task: String,
+        message: String,
+        trace: Vec<String>,
+    },
+    
+    #[error("simulation with seed {seed} exceeded {steps} scheduling steps")]
+    StepLimit {
+        seed: u64,
+        steps: usize,
+    },
+    
+    #[error("task '{task}' stalled with seed {seed}, blocked outside a simulation lock or yield point")]
+    Stalled {
+        seed: u64,
+        task: String,
+    },
+}
+
+impl SimulationError {
+    /// Seed reproducing the failure
+    pub fn seed(&self) -> u64 {
+        match self {
+            SimulationError::Deadlock { seed, .. }
+            | SimulationError::Panicked { seed, .. }
+            | SimulationError::StepLimit { seed, .. }
+            | SimulationError::Stalled { seed, .. } => *seed,
+        }
+    }
+}
+
+fn trace_tail(trace: &[String]) -> String {
+    let skipped = trace.len().saturating_sub(TRACE_TAIL);
+    let mut text = format!("last scheduling steps (rerun with {}=<seed>):", SEED_ENV);
+    for entry in &trace[skipped..] {
+        text.push_str("\n  ");
+        text.push_str(entry);
+    }
+    text
+}
+
+/// Outcome of a successful simulation run
+#[derive(Debug, Clone)]
+pub struct SimulationReport {
+    /// Seed of the run
+    pub seed: u64,
+    
+    /// Scheduling decisions taken
+    pub steps: usize,
+    
+    /// Simulated time at the end of the run
+    pub elapsed: Duration,
+    
+    /// Scheduling points in execution order, as `task: event`
+    pub trace: Vec<String>,
+}
+
+type TaskBody = Box<dyn FnOnce() + Send + 'static>;
+
+/// A set of tasks run under the deterministic scheduler
+pub struct Simulation {
+    seed: u64,
+    max_steps: usize,
+    stall_timeout: Duration,
+    tasks: Vec<(String, TaskBody)>,
+}
+
+impl Simulation {
+    /// Create a simulation scheduled from a seed
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            max_steps: DEFAULT_MAX_STEPS,
+            stall_timeout: DEFAULT_STALL_TIMEOUT,
+            tasks: Vec::new(),
+        }
+    }
+    
+    /// Set the scheduling steps after which the run fails as livelocked
+    pub fn with_max_steps(mut self, max_steps: usize) -> Self {
+        self.max_steps = max_steps;
+        self
+    }
+    
+    /// Add a task
+    pub fn spawn<F>(&mut self, name: impl Into<String>, task: F) -> &mut Self
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        self.tasks.push((name.into(), Box::new(task)));
+        self
+    }
+    
+    /// Run all tasks to completion
+    pub fn run(self) -> Result<SimulationReport, SimulationError> {
+        let shared = Arc::new(Shared {
+            scheduler: Mutex::new(Scheduler {
+                tasks: self.tasks.iter().map(|(name, _)| Task { name: name.clone(), status: TaskStatus::Runnable, retry_failed: false }).collect(),
+                current: None,
+                rng: SplitMix64(self.seed),
+                elapsed: Duration::ZERO,
+                trace: Vec::new(),
+                steps: 0,
+                max_steps: self.max_steps,
+                failure: None,
+                seed: self.seed,
+            }),
+            turn: Condvar::new(),
+        });
+        
+        let handles: Vec<JoinHandle<()>> = self.tasks.into_iter().enumerate()
+            .map(|(id, (name, body))| {
+                let shared = shared.clone();
+                thread::Builder::new()
+                    .name(format!("sim-{}", name))
+                    .spawn(move || run_task(shared, id, body))
+                    .expect("Failed to spawn simulation task")
+            })
+            .collect();
+        
+        let mut scheduler = shared.scheduler.lock().unwrap();
+        scheduler.pick_next();
+        shared.turn.notify_all();
+        let mut last_step = scheduler.steps;
+        while scheduler.failure.is_none() && !scheduler.finished() {
+            let (guard, timeout) = shared.turn.wait_timeout(scheduler, self.stall_timeout).unwrap();
+            scheduler = guard;
+            if timeout.timed_out() && scheduler.steps == last_step && scheduler.failure.is_none() && !scheduler.finished() {
+                // The running task is stuck in a lock the scheduler cannot see; its thread is leaked
+                let task = scheduler.current.map(|id| scheduler.tasks[id].name.clone()).unwrap_or_default();
+                scheduler.failure = Some(SimulationError::Stalled { seed: self.seed, task });
+                shared.turn.notify_all();
+                return Err(scheduler.failure.clone().unwrap());
+            }
+            last_step = scheduler.steps;
+        }
+        let failure = scheduler.failure.clone();
+        let report = SimulationReport {
+            seed: self.seed,
+            steps: scheduler.steps,
+            elapsed: scheduler.elapsed,
+            trace: scheduler.trace.clone(),
+        };
+        drop(scheduler);
+        
+        for handle in handles {
+            let _ = handle.join();
+        }
+        match failure {
+            Some(failure) => Err(failure),
+            None => Ok(report),
+        }
+    }
+}
+
+/// Run a scenario under many seeds, stopping at the first failure
+///
+/// `scenario` adds the tasks for one run. When `OSLAND_SIM_SEED` is set only that seed
+/// runs. Returns the number of runs.
+pub fn explore<F>(runs: u64, mut scenario: F) -> Result<u64, SimulationError>
+where
+    F: FnMut(&mut Simulation),
+{
+    let seeds = match std::env::var(SEED_ENV).ok().and_then(|seed| seed.parse::<u64>().ok()) {
+        Some(seed) => seed..seed + 1,
+        None => 0..runs,
+    };
+    let count = seeds.end - seeds.start;
+    for seed in seeds {
+        let mut simulation = Simulation::new(seed);
+        scenario(&mut simulation);
+        simulation.run()?;
+    }
+    Ok(count)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TaskStatus {
+    Runnable,
+    Blocked(&'static Location<'static>),
+    Sleeping(Duration),
+    Finished,
+}
+
+struct Task {
+    name: String,
+    status: TaskStatus,
+    /// Blocked and failed to take its lock since the last progress of any task
+    retry_failed: bool,
+}
+
+struct Scheduler {
+    tasks: Vec<Task>,
+    current: Option<usize>,
+    rng: SplitMix64,
+    elapsed: Duration,
+    trace: Vec<String>,
+    steps: usize,
+    max_steps: usize,
+    failure: Option<SimulationError>,
+    seed: u64,
+}
+
+impl Scheduler {
+    fn finished(&self) -> bool {
+        self.tasks.iter().all(|task| task.status == TaskStatus::Finished)
+    }
+    
+    /// Record a scheduling point of a task
+    ///
+    /// Guards are released without a scheduling point, so any progress (a yield,
+    /// sleep, lock attempt or finished task) may have freed the lock a blocked task
+    /// waits for, and every blocked task gets another retry.
+    fn record(&mut self, id: usize, status: TaskStatus, event: String) {
+        let entry = format!("{}: {}", self.tasks[id].name, event);
+        self.trace.push(entry);
+        self.tasks[id].status = status;
+        if matches!(status, TaskStatus::Blocked(_)) {
+            self.tasks[id].retry_failed = true;
+        } else {
+            for task in &mut self.tasks {
+                task.retry_failed = false;
+            }
+        }
+    }
+    
+    /// Choose the next task to run, advancing the clock when every task sleeps
+    fn pick_next(&mut self) {
+        self.current = None;
+        if self.finished() || self.failure.is_some() {
+            return;
+        }
+        if self.steps >= self.max_steps {
+            self.failure = Some(SimulationError::StepLimit { seed: self.seed, steps: self.steps });
+            return;
+        }
+        
+        loop {
+            let runnable = self.indices(|status| status == TaskStatus::Runnable);
+            if runnable.is_empty() {
+                let wake_at = self.tasks.iter()
+                    .filter_map(|task| match task.status {
+                        TaskStatus::Sleeping(wake_at) => Some(wake_at),
+                        _ => None,
+                    })
+                    .min();
+                match wake_at {
+                    Some(wake_at) => {
+                        self.elapsed = self.elapsed.max(wake_at);
+                        for task in &mut self.tasks {
+                            if matches!(task.status, TaskStatus::Sleeping(at) if at <= self.elapsed) {
+                                task.status = TaskStatus::Runnable;
+                            }
+                        }
+                        continue;
+                    }
+                    None => {
+                        // Only blocked tasks are left: retry those that have not failed since
+                        // the last progress, and report a deadlock once every retry failed
+                        let retry: Vec<usize> = self.indices(|status| matches!(status, TaskStatus::Blocked(_)))
+                            .into_iter()
+                            .filter(|id| !self.tasks[*id].retry_failed)
+                            .collect();
+                        if !retry.is_empty() {
+                            let choice = retry[(self.rng.next_u64() % retry.len() as u64) as usize];
+                            self.steps += 1;
+                            self.current = Some(choice);
+                            return;
+                        }
+                        let blocked = self.tasks.iter()
+                            .filter_map(|task| match task.status {
+                                TaskStatus::Blocked(location) => Some(format!("'{}' at {}", task.name, location)),
+                                _ => None,
+                            })
+                            .collect();
+                        self.failure = Some(SimulationError::Deadlock { seed: self.seed, blocked, trace: self.trace.clone() });
+                        return;
+                    }
+                }
+            }
+            
+            // Blocked tasks retry their lock alongside the runnable ones
+            let candidates = self.indices(|status| matches!(status, TaskStatus::Runnable | TaskStatus::Blocked(_)));
+            let choice = candidates[(self.rng.next_u64() % candidates.len() as u64) as usize];
+            self.steps += 1;
+            self.current = Some(choice);
+            return;
+        }
+    }
+    
+    fn indices(&self, filter: impl Fn(TaskStatus) -> bool) -> Vec<usize> {
+        (0..self.tasks.len()).filter(|id| filter(self.tasks[*id].status)).collect()
+    }
+}
+
+struct Shared {
+    scheduler: Mutex<Scheduler>,
+    turn: Condvar,
+}
+
+/// Unwinds tasks parked when the simulation fails
+struct Aborted;
+
+thread_local! {
+    static CONTEXT: RefCell<Option<(Arc<Shared>, usize)>> = const { RefCell::new(None) };
+}
+
+fn context() -> Option<(Arc<Shared>, usize)> {
+    CONTEXT.with(|context| context.borrow().clone())
+}
+
+/// Record a scheduling point of the running task and wait for its next turn
+fn switch(shared: &Shared, id: usize, status: TaskStatus, event: String) {
+    let mut scheduler = shared.scheduler.lock().unwrap();
+    scheduler.record(id, status, event);
+    scheduler.pick_next();
+    shared.turn.notify_all();
+    wait_turn(shared, scheduler, id);
+}
+
+fn wait_turn(shared: &Shared, mut scheduler: MutexGuard<'_, Scheduler>, id: usize) {
+    loop {
+        if scheduler.failure.is_some() {
+            drop(scheduler);
+            panic::resume_unwind(Box::new(Aborted));
+        }
+        if scheduler.current == Some(id) {
+            // A resumed task is running again, whatever it waited for
+            scheduler.tasks[id].status = TaskStatus::Runnable;
+            return;
+        }
+        scheduler = shared.turn.wait(scheduler).unwrap();
+    }
+}
+
+fn run_task(shared: Arc<Shared>, id: usize, body: TaskBody) {
+    CONTEXT.with(|context| *context.borrow_mut() = Some((shared.clone(), id)));
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        wait_turn(&shared, shared.scheduler.lock().unwrap(), id);
+        body();
+    }));
+    CONTEXT.with(|context| *context.borrow_mut() = None);
+    
+    let mut scheduler = shared.scheduler.lock().unwrap();
+    scheduler.record(id, TaskStatus::Finished, "finished".to_string());
+    if let Err(payload) = result {
+        if !payload.is::<Aborted>() && scheduler.failure.is_none() {
+            let message = payload.downcast_ref::<&str>().map(|s| s.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown panic".to_string());
+            let task = scheduler.tasks[id].name.clone();
+            scheduler.failure = Some(SimulationError::Panicked { seed: scheduler.seed, task, message, trace: scheduler.trace.clone() });
+        }
+    }
+    if scheduler.current == Some(id) {
+        scheduler.pick_next();
+    }
+    shared.turn.notify_all();
+}
+
+/// SplitMix64 generator: small, fast and stable across platforms and releases
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    /// Two tasks taking the same pair of locks in opposite order
+    fn lock_inversion(simulation: &mut Simulation) {
+        let first = Arc::new(Mutex::new(0));
+        let second = Arc::new(Mutex::new(0));
+        let (a1, a2) = (first.clone(), second.clone());
+        simulation.spawn("forward", move || {
+            let _first = lock(&a1);
+            let _second = lock(&a2);
+        });
+        simulation.spawn("backward", move || {
+            let _second = lock(&second);
+            let _first = lock(&first);
+        });
+    }
+    
+    #[test]
+    fn test_finds_and_replays_lock_order_deadlock() {
+        let failure = explore(100, lock_inversion).unwrap_err();
+        let SimulationError::Deadlock { seed, blocked, trace } = &failure else {
+            panic!("expected a deadlock, got {}", failure);
+        };
+        assert_eq!(blocked.len(), 2);
+        
+        // The same seed replays the same interleaving
+        let mut replay = Simulation::new(*seed);
+        lock_inversion(&mut replay);
+        match replay.run() {
+            Err(SimulationError::Deadlock { trace: replayed, .. }) => assert_eq!(&replayed, trace),
+            other => panic!("seed {} did not replay the deadlock: {:?}", seed, other.map(|report| report.trace)),
+        }
+        
+        // Some other seed serializes the tasks and completes
+        assert!((0..100).any(|seed| {
+            let mut simulation = Simulation::new(seed);
+            lock_inversion(&mut simulation);
+            simulation.run().is_ok()
+        }));
+    }
+    
+    #[test]
+    fn test_blocked_task_retries_after_holder_finishes() {
+        // A task blocked on a mutex whose holder finishes, or releases it and keeps
+        // running, is retried instead of being reported as deadlocked
+        explore(200, |simulation| {
+            let shared = Arc::new(Mutex::new(0));
+            let (holder, releaser) = (shared.clone(), shared.clone());
+            simulation.spawn("holder", move || {
+                let mut value = lock(&holder);
+                yield_now();
+                *value += 1;
+            });
+            simulation.spawn("releaser", move || {
+                {
+                    let mut value = lock(&releaser);
+                    yield_now();
+                    *value += 1;
+                }
+                sleep(Duration::from_millis(5));
+            });
+            simulation.spawn("waiter", move || {
+                *lock(&shared) += 1;
+            });
+        }).unwrap();
+    }
+    
+    #[test]
+    fn test_simulated_time_advances_only_while_sleeping() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut simulation = Simulation::new(7);
+        for (name, delay) in [("slow", 300), ("fast", 100)] {
+            let log = log.clone();
+            simulation.spawn(name, move || {
+                let started = now();
+                sleep(Duration::from_millis(delay));
+                lock(&log).push((name, now() - started, unix_millis()));
+            });
+        }
+        let report = simulation.run().unwrap();
+        
+        assert_eq!(report.elapsed, Duration::from_millis(300));
+        let log = log.lock().unwrap();
+        assert_eq!(*log, vec![
+            ("fast", Duration::from_millis(100), SIMULATED_EPOCH_MILLIS + 100),
+            ("slow", Duration::from_millis(300), SIMULATED_EPOCH_MILLIS + 300),
+        ]);
+    }
+}
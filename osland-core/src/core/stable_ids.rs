@@ -0,0 +1,114 @@
+// Stable IDs and canonical serialization for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! Helpers that make saved project files reproducible.
+//!
+//! Documents that create IDs carry an `IdStrategy`. With `Deterministic`, IDs are
+//! derived from a seed describing what is being created (for example the component
+//! and its sequence number on a canvas), so building the same project twice yields
+//! the same IDs. `to_canonical_json` writes objects with sorted keys, which removes
+//! the `HashMap` iteration order from saved files.
+
+use std::collections::HashSet;
+use serde::{Serialize, Deserialize, Serializer};
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+/// How new IDs are generated
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum IdStrategy {
+    /// Random v4 UUIDs
+    #[default]
+    Random,
+    /// UUIDs derived from a hash of the creation seed
+    Deterministic,
+}
+
+impl IdStrategy {
+    /// Generate a UUID for an object described by `seed`
+    pub fn uuid(&self, seed: &[&str]) -> Uuid {
+        match self {
+            IdStrategy::Random => Uuid::new_v4(),
+            IdStrategy::Deterministic => derive_uuid(seed),
+        }
+    }
+}
+
+/// Derive a UUID from a seed
+///
+/// Seed parts are length-prefixed before hashing, so `["ab", "c"]` and `["a", "bc"]`
+/// produce different IDs.
+pub fn derive_uuid(seed: &[&str]) -> Uuid {
+    let mut hasher = Sha256::new();
+    for part in seed {
+        hasher.update((part.len() as u64).to_le_bytes());
+        hasher.update(part.as_bytes());
+    }
+    let digest = hasher.finalize();
+    
+    let mut bytes = [0u8; 16];
+    bytes.copy_from_slice(&digest[..16]);
+    uuid::Builder::from_custom_bytes(bytes).into_uuid()
+}
+
+/// Serialize a value as pretty JSON with every object's keys sorted
+pub fn to_canonical_json<T: Serialize>(value: &T) -> serde_json::Result<String> {
+    serde_json::to_string_pretty(&canonicalize(serde_json::to_value(value)?))
+}
+
+/// Sort the keys of every object in a JSON value
+pub fn canonicalize(value: Value) -> Value {
+    match value {
+        Value::Object(object) => {
+            let mut entries: Vec<(String, Value)> = object.into_iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+            let mut sorted = Map::new();
+            for (key, value) in entries {
+                sorted.insert(key, canonicalize(value));
+            }
+            Value::Object(sorted)
+        },
+        Value::Array(values) => Value::Array(values.into_iter().map(canonicalize).collect()),
+        other => other,
+    }
+}
+
+/// Serialize a set as a sorted sequence (for `#[serde(serialize_with)]`)
+pub fn sorted_set<S, T>(set: &HashSet<T>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: Ord + Serialize,
+{
+    let mut values: Vec<&T> = set.iter().collect();
+    values.sort();
+    serializer.collect_seq(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    
+    #[test]
+    fn test_derived_ids_are_stable() {
+        assert_eq!(derive_uuid(&["node", "cpu", "0"]), derive_uuid(&["node", "cpu", "0"]));
+        assert_ne!(derive_uuid(&["node", "cpu", "0"]), derive_uuid(&["node", "cpu", "1"]));
+        assert_ne!(derive_uuid(&["ab", "c"]), derive_uuid(&["a", "bc"]));
+        assert_eq!(IdStrategy::Deterministic.uuid(&["x"]), derive_uuid(&["x"]));
+        assert_ne!(IdStrategy::Random.uuid(&["x"]), IdStrategy::Random.uuid(&["x"]));
+    }
+    
+    #[test]
+    fn test_canonical_json_sorts_keys() {
+        let map: HashMap<String, HashMap<String, u32>> = (0..20)
+            .map(|i| (format!("key{:02}", i), HashMap::from([("b".to_string(), i), ("a".to_string(), i)])))
+            .collect();
+        let json = to_canonical_json(&map).unwrap();
+        
+        let keys: Vec<usize> = (0..20).map(|i| json.find(&format!("\"key{:02}\"", i)).unwrap()).collect();
+        assert!(keys.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(json.find("\"a\"").unwrap() < json.find("\"b\"").unwrap());
+    }
+}
@@ -0,0 +1,489 @@
+// Outbound webhooks for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! Webhooks notifying external CI and chat systems of project events.
+//!
+//! The dispatcher listens on the event bus for finished builds, validation failures
+//! and published component versions, and POSTs a JSON payload to every configured URL
+//! interested in the event. Deliveries are retried with exponential backoff on network
+//! errors, timeouts, rate limiting and server errors; other client errors are final.
+//!
+//! When a webhook has a secret, the body is signed with HMAC-SHA256 and the signature
+//! sent as `X-OSland-Signature-256: sha256=<hex>`, so receivers can verify the sender.
+
+use std::collections::VecDeque;
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use super::network_policy::{check_egress, redirect_policy, EgressPurpose};
+use super::event_bus::{BuildEvent, EventBus, EventFilter, EventTopic, ProjectEvent, SubscriptionId, SystemEvent};
+
+/// Header carrying the event name
+pub const EVENT_HEADER: &str = "X-OSland-Event";
+
+/// Header carrying the delivery ID, identical across retries
+pub const DELIVERY_HEADER: &str = "X-OSland-Delivery";
+
+/// Header carrying the HMAC-SHA256 signature of the body
+pub const SIGNATURE_HEADER: &str = "X-OSland-Signature-256";
+
+/// Number of deliveries kept for inspection
+const DELIVERY_LOG_SIZE: usize = 200;
+
+/// Longest delay between two delivery attempts
+const MAX_BACKOFF: Duration = Duration::from_secs(300);
+
+/// Project event a webhook can subscribe to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum WebhookEvent {
+    /// A build finished, successfully or not
+    BuildFinished,
+    
+    /// A build configuration, canvas or set of staged files failed validation
+    ValidationFailed,
+    
+    /// A component or library tile version was published
+    VersionPublished,
+}
+
+impl WebhookEvent {
+    /// Event name sent in the payload and the event header
+    pub fn name(&self) -> &'static str {
+        match self {
+            WebhookEvent::BuildFinished => "build.finished",
+            WebhookEvent::ValidationFailed => "validation.failed",
+            WebhookEvent::VersionPublished => "version.published",
+        }
+    }
+    
+    /// Webhook event of a bus event, if it is one
+    pub fn of(event: &SystemEvent) -> Option<Self> {
+        match event {
+            SystemEvent::Build(BuildEvent::Finished { .. }) => Some(WebhookEvent::BuildFinished),
+            SystemEvent::Project(ProjectEvent::ValidationFailed { .. }) => Some(WebhookEvent::ValidationFailed),
+            SystemEvent::Project(ProjectEvent::VersionPublished { .. }) => Some(WebhookEvent::VersionPublished),
+            _ => None,
+        }
+    }
+}
+
+/// Configured webhook
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebhookConfig {
+    /// Webhook name (used in logs)
+    pub name: String,
+    
+    /// URL the payload is POSTed to
+    pub url: String,
+    
+    /// Events sent to the webhook (empty means all)
+    #[serde(default)]
+    pub events: Vec<WebhookEvent>,
+    
+    /// Projects whose events are sent (empty means all)
+    #[serde(default)]
+    pub projects: Vec<String>,
+    
+    /// Shared secret used to sign payloads
+    #[serde(default)]
+    pub secret: Option<String>,
+    
+    /// Environment variable holding the secret, used when `secret` is not set
+    #[serde(default)]
+    pub secret_env: Option<String>,
+    
+    /// Delivery attempts before giving up
+    #[serde(default = "default_max_attempts")]
+    pub max_attempts: u32,
+    
+    /// Delay before the first retry, doubled for every further retry up to five minutes (milliseconds)
+    #[serde(default = "default_backoff_ms")]
+    pub backoff_ms: u64,
+}
+
+fn default_max_attempts() -> u32 {
+    5
+}
+
+fn default_backoff_ms() -> u64 {
+    1000
+}
+
+impl WebhookConfig {
+    /// Check whether the webhook wants an event of a project
+    pub fn wants(&self, event: WebhookEvent, project: &str) -> bool {
+        (self.events.is_empty() || self.events.contains(&event))
+            && (self.projects.is_empty() || self.projects.iter().any(|p| p == project))
+    }
+    
+    /// Signing secret, read from the environment if configured there
+    pub fn resolve_secret(&self) -> Option<String> {
+        self.secret.clone().or_else(|| self.secret_env.as_ref().and_then(|name| std::env::var(name).ok()))
+    }
+}
+
+/// Webhooks of a project (as found in the webhooks file)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WebhookSettings {
+    /// Configured webhooks
+    #[serde(default)]
+    pub webhooks: Vec<WebhookConfig>,
+}
+
+impl WebhookSettings {
+    /// Load webhook settings from a JSON file
+    pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let content = fs::read_to_string(path.as_ref())
+            .map_err(|e| format!("Failed to read {}: {}", path.as_ref().display(), e))?;
+        serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse {}: {}", path.as_ref().display(), e))
+    }
+}
+
+/// Body POSTed to a webhook
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebhookPayload {
+    /// Delivery ID
+    pub delivery_id: String,
+    
+    /// Event name, e.g. `build.finished`
+    pub event: String,
+    
+    /// Project the event is about
+    pub project: String,
+    
+    /// Time of the event (RFC 3339)
+    pub timestamp: String,
+    
+    /// Event details
+    pub data: Value,
+}
+
+impl WebhookPayload {
+    /// Build the payload of a bus event
+    pub fn from_event(event: &SystemEvent) -> Option<Self> {
+        let kind = WebhookEvent::of(event)?;
+        let data = match event {
+            SystemEvent::Build(BuildEvent::Finished { success, message, .. }) => json!({ "success": success, "message": message }),
+            SystemEvent::Project(ProjectEvent::ValidationFailed { target, errors, .. }) => json!({ "target": target, "errors": errors }),
+            SystemEvent::Project(ProjectEvent::VersionPublished { component, version, .. }) => json!({ "component": component, "version": version }),
+            _ => return None,
+        };
+        Some(Self {
+            delivery_id: uuid::Uuid::new_v4().to_string(),
+            event: kind.name().to_string(),
+            project: event.source().to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            data,
+        })
+    }
+}
+
+/// Sign a body with HMAC-SHA256, formatted as `sha256=<hex>`
+pub fn sign_payload(secret: &[u8], body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts keys of any length");
+    mac.update(body);
+    format!("sha256={}", mac.finalize().into_bytes().iter().map(|byte| format!("{:02x}", byte)).collect::<String>())
+}
+
+/// HTTP transport of webhook deliveries
+pub trait WebhookTransport: Send + Sync {
+    /// POST a JSON body, returning the response status
+    fn post(&self, url: &str, headers: &[(String, String)], body: &[u8]) -> Result<u16, String>;
+}
+
+/// Transport using a blocking HTTP client
+pub struct HttpTransport {
+    client: reqwest::blocking::Client,
+}
+
+impl HttpTransport {
+    /// Create a transport with a request timeout
+    pub fn new(timeout: Duration) -> Result<Self, String> {
+        let client = reqwest::blocking::Client::builder()
+            .redirect(redirect_policy(EgressPurpose::Webhook))
+            .timeout(timeout)
+            .user_agent(concat!("osland-webhooks/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+        Ok(Self { client })
+    }
+}
+
+impl WebhookTransport for HttpTransport {
+    fn post(&self, url: &str, headers: &[(String, String)], body: &[u8]) -> Result<u16, String> {
+        let mut request = self.client.post(url).header("Content-Type", "application/json").body(body.to_vec());
+        for (name, value) in headers {
+            request = request.header(name.as_str(), value.as_str());
+        }
+        let response = request.send().map_err(|e| e.to_string())?;
+        Ok(response.status().as_u16())
+    }
+}
+
+/// Outcome of delivering one payload to one webhook
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeliveryRecord {
+    /// Webhook name
+    pub webhook: String,
+    
+    /// Delivery ID
+    pub delivery_id: String,
+    
+    /// Event name
+    pub event: String,
+    
+    /// Attempts made
+    pub attempts: u32,
+    
+    /// Status of the last response, if any
+    pub status: Option<u16>,
+    
+    /// Error of the last attempt, if it failed
+    pub error: Option<String>,
+}
+
+impl DeliveryRecord {
+    /// Check whether the webhook accepted the payload
+    pub fn delivered(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Check whether a failed attempt may succeed when retried
+fn is_retryable(status: Option<u16>) -> bool {
+    match status {
+        None => true,
+        Some(status) => status == 408 || status == 429 || status >= 500,
+    }
+}
+
+/// Sends project events to the configured webhooks
+pub struct WebhookDispatcher {
+    webhooks: Vec<WebhookConfig>,
+    transport: Box<dyn WebhookTransport>,
+    deliveries: Mutex<VecDeque<DeliveryRecord>>,
+}
+
+impl WebhookDispatcher {
+    /// Create a dispatcher delivering over HTTP
+    pub fn new(settings: WebhookSettings) -> Result<Self, String> {
+        Ok(Self::with_transport(settings, Box::new(HttpTransport::new(Duration::from_secs(10))?)))
+    }
+    
+    /// Create a dispatcher with a custom transport
+    pub fn with_transport(settings: WebhookSettings, transport: Box<dyn WebhookTransport>) -> Self {
+        Self {
+            webhooks: settings.webhooks,
+            transport,
+            deliveries: Mutex::new(VecDeque::new()),
+        }
+    }
+    
+    /// Deliver an event to every interested webhook, blocking until all deliveries finished
+    pub fn dispatch(&self, event: &SystemEvent) -> Vec<DeliveryRecord> {
+        let (kind, payload) = match WebhookEvent::of(event).zip(WebhookPayload::from_event(event)) {
+            Some(found) => found,
+            None => return Vec::new(),
+        };
+        let body = match serde_json::to_vec(&payload) {
+            Ok(body) => body,
+            Err(e) => {
+                log::error!("Failed to serialize webhook payload: {}", e);
+                return Vec::new();
+            }
+        };
+        
+        let records: Vec<DeliveryRecord> = self.webhooks.iter()
+            .filter(|webhook| webhook.wants(kind, &payload.project))
+            .map(|webhook| self.deliver(webhook, &payload, &body))
+            .collect();
+        
+        let mut deliveries = self.deliveries.lock().unwrap();
+        for record in &records {
+            deliveries.push_back(record.clone());
+            if deliveries.len() > DELIVERY_LOG_SIZE {
+                deliveries.pop_front();
+            }
+        }
+        records
+    }
+    
+    /// Get the most recent deliveries, oldest first
+    pub fn recent_deliveries(&self) -> Vec<DeliveryRecord> {
+        self.deliveries.lock().unwrap().iter().cloned().collect()
+    }
+    
+    /// Deliver events published on a bus from a background thread
+    pub fn start(self, bus: &'static EventBus) -> WebhookWorker {
+        let dispatcher = Arc::new(self);
+        let (subscription, receiver) = bus.subscribe_channel(EventFilter::topic(EventTopic::Build).with_topic(EventTopic::Project));
+        let worker_dispatcher = dispatcher.clone();
+        let handle = std::thread::spawn(move || {
+            // Ends once the subscription is removed and the queued events are delivered
+            while let Ok(event) = receiver.recv() {
+                worker_dispatcher.dispatch(&event);
+            }
+        });
+        WebhookWorker { bus, subscription, dispatcher, handle }
+    }
+    
+    /// Deliver a payload to one webhook, retrying with exponential backoff
+    fn deliver(&self, webhook: &WebhookConfig, payload: &WebhookPayload, body: &[u8]) -> DeliveryRecord {
+        let mut headers = vec![
+            (EVENT_HEADER.to_string(), payload.event.clone()),
+            (DELIVERY_HEADER.to_string(), payload.delivery_id.clone()),
+        ];
+        if let Some(secret) = webhook.resolve_secret() {
+            headers.push((SIGNATURE_HEADER.to_string(), sign_payload(secret.as_bytes(), body)));
+        }
+        
+        let mut record = DeliveryRecord {
+            webhook: webhook.name.clone(),
+            delivery_id: payload.delivery_id.clone(),
+            event: payload.event.clone(),
+            attempts: 0,
+            status: None,
+            error: None,
+        };
+        if let Err(denied) = check_egress(EgressPurpose::Webhook, &webhook.url) {
+            // Retrying cannot help until offline mode is turned off
+            record.error = Some(denied.to_string());
+            return record;
+        }
+        let mut backoff = Duration::from_millis(webhook.backoff_ms).min(MAX_BACKOFF);
+        loop {
+            record.attempts += 1;
+            let (status, error) = match self.transport.post(&webhook.url, &headers, body) {
+                Ok(status) if (200..300).contains(&status) => (Some(status), None),
+                Ok(status) => (Some(status), Some(format!("HTTP {}", status))),
+                Err(e) => (None, Some(e)),
+            };
+            record.status = status;
+            record.error = error;
+            
+            match &record.error {
+                None => break,
+                Some(_) if !is_retryable(status) || record.attempts >= webhook.max_attempts.max(1) => {
+                    log::warn!("Webhook '{}' failed to deliver {} after {} attempt(s): {}",
+                        webhook.name, payload.event, record.attempts, record.error.as_deref().unwrap_or_default());
+                    break;
+                }
+                Some(e) => {
+                    log::debug!("Webhook '{}' attempt {} failed: {}; retrying in {:?}", webhook.name, record.attempts, e, backoff);
+                    std::thread::sleep(backoff);
+                    backoff = backoff.saturating_mul(2).min(MAX_BACKOFF);
+                }
+            }
+        }
+        record
+    }
+}
+
+/// Background delivery of bus events to webhooks
+pub struct WebhookWorker {
+    bus: &'static EventBus,
+    subscription: SubscriptionId,
+    dispatcher: Arc<WebhookDispatcher>,
+    handle: JoinHandle<()>,
+}
+
+impl WebhookWorker {
+    /// Get the dispatcher used by the worker
+    pub fn dispatcher(&self) -> &WebhookDispatcher {
+        &self.dispatcher
+    }
+    
+    /// Stop listening and wait until the events already published are delivered
+    pub fn shutdown(self) {
+        self.bus.unsubscribe(self.subscription);
+        if self.handle.join().is_err() {
+            log::error!("Webhook worker panicked");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    /// URL and headers of the requests a transport received
+    type Requests = Arc<Mutex<Vec<(String, Vec<(String, String)>)>>>;
+    
+    /// Transport answering with a fixed sequence of statuses
+    struct ScriptedTransport {
+        responses: Mutex<VecDeque<Result<u16, String>>>,
+        requests: Requests,
+    }
+    
+    impl WebhookTransport for ScriptedTransport {
+        fn post(&self, url: &str, headers: &[(String, String)], _body: &[u8]) -> Result<u16, String> {
+            self.requests.lock().unwrap().push((url.to_string(), headers.to_vec()));
+            self.responses.lock().unwrap().pop_front().unwrap_or(Ok(200))
+        }
+    }
+    
+    fn webhook(name: &str, events: Vec<WebhookEvent>) -> WebhookConfig {
+        WebhookConfig {
+            name: name.to_string(),
+            url: format!("https://ci.example.com/{}", name),
+            events,
+            projects: Vec::new(),
+            secret: Some("Jefe".to_string()),
+            secret_env: None,
+            max_attempts: 3,
+            backoff_ms: 0,
+        }
+    }
+    
+    #[test]
+    fn test_sign_payload_matches_rfc4231() {
+        assert_eq!(
+            sign_payload(b"Jefe", b"what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+    
+    #[test]
+    fn test_dispatch_filters_and_retries() {
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let transport = ScriptedTransport {
+            responses: Mutex::new(VecDeque::from([Err("connection refused".to_string()), Ok(503), Ok(204), Ok(404)])),
+            requests: requests.clone(),
+        };
+        let settings = WebhookSettings {
+            webhooks: vec![webhook("ci", vec![WebhookEvent::BuildFinished]), webhook("chat", vec![WebhookEvent::VersionPublished])],
+        };
+        let dispatcher = WebhookDispatcher::with_transport(settings, Box::new(transport));
+        
+        let finished = SystemEvent::Build(BuildEvent::Finished { project: "demo".to_string(), success: true, message: "ok".to_string() });
+        let records = dispatcher.dispatch(&finished);
+        assert_eq!(records.len(), 1);
+        assert_eq!((records[0].attempts, records[0].status, records[0].delivered()), (3, Some(204), true));
+        
+        // Every retry carries the same delivery ID and a signature
+        let requests = requests.lock().unwrap().clone();
+        assert!(requests.iter().all(|(url, headers)| url.ends_with("/ci")
+            && headers.contains(&(DELIVERY_HEADER.to_string(), records[0].delivery_id.clone()))
+            && headers.iter().any(|(name, value)| name == SIGNATURE_HEADER && value.starts_with("sha256="))));
+        
+        // Client errors are not retried
+        let published = SystemEvent::Project(ProjectEvent::VersionPublished {
+            project: "demo".to_string(),
+            component: "sched".to_string(),
+            version: "1.0.1".to_string(),
+        });
+        let records = dispatcher.dispatch(&published);
+        assert_eq!((records[0].webhook.as_str(), records[0].attempts, records[0].delivered()), ("chat", 1, false));
+        
+        assert!(dispatcher.dispatch(&SystemEvent::Build(BuildEvent::Started { project: "demo".to_string() })).is_empty());
+        assert_eq!(dispatcher.recent_deliveries().len(), 2);
+    }
+}
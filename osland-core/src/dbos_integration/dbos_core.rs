@@ -5,6 +5,7 @@
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
 use serde::{Serialize, Deserialize};
+use super::time_travel::{SnapshotRetention, TimeTravelEngine};
 
 pub mod tables_core;
 
@@ -25,6 +26,10 @@ pub struct DbosConfig {
     
     /// Enable security features
     pub enable_security: bool,
+    
+    /// Time travel snapshot retention and compaction
+    #[serde(default)]
+    pub snapshot_retention: SnapshotRetention,
 }
 
 impl Default for DbosConfig {
@@ -34,6 +39,7 @@ impl Default for DbosConfig {
             enable_time_travel: true,
             max_concurrent_transactions: 100,
             enable_security: true,
+            snapshot_retention: SnapshotRetention::default(),
         }
     }
 }
@@ -133,7 +139,7 @@ impl DbosSystem {
     pub fn new(config: DbosConfig) -> Self {
         let transaction_manager = Arc::new(TransactionManager::new());
         let state_tracker = Arc::new(StateTracker::new());
-        let time_travel_engine = Arc::new(TimeTravelEngine::new());
+        let time_travel_engine = Arc::new(TimeTravelEngine::with_retention(config.snapshot_retention.clone()));
         let tables_manager = Arc::new(TablesManager::new());
         
         Self {
@@ -220,6 +226,7 @@ impl DbosSystem {
         // Start time travel engine if enabled
         if self.config.enable_time_travel {
             self.time_travel_engine.start();
+            TimeTravelEngine::start_compaction(&self.time_travel_engine);
         }
         
         // Start tables manager (core of DBOS)
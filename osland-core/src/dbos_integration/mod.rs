@@ -14,5 +14,5 @@ pub use dbos_core::{DbosSystem, DbosConfig};
 pub use dbos_components::{DbosComponent, DbosComponentType};
 pub use transaction_manager::TransactionManager;
 pub use state_tracker::StateTracker;
-pub use time_travel::TimeTravelEngine;
+pub use time_travel::{TimeTravelEngine, SnapshotRetention, SnapshotStorageStats, CompactionReport};
 pub use unified_resource_manager::UnifiedResourceManager;
\ No newline at end of file
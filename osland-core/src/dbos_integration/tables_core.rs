@@ -0,0 +1,1670 @@
+// Tables Core for DBOS Integration in OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+use serde::{Serialize, Deserialize};
+use std::collections::{HashMap, BTreeMap};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+use uuid::Uuid;
+use crate::core::audit::{self, AuditCategory};
+use crate::core::event_bus::{self, SystemEvent, TableChangeEvent, TableChangeKind};
+use crate::core::simulation;
+
+/// Column holding the namespace of each row in tables shared by all namespaces
+pub const NAMESPACE_COLUMN: &str = "namespace";
+
+/// Namespace of rows written to shared tables without a namespace scope
+pub const DEFAULT_NAMESPACE: &str = "default";
+
+/// Separator between the namespace and the name of a namespace-private table
+pub const NAMESPACE_SEPARATOR: char = ':';
+
+/// DBOS Table Definition
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TableDefinition {
+    /// Table name
+    pub name: String,
+    
+    /// Column definitions
+    pub columns: Vec<ColumnDefinition>,
+    
+    /// Primary key columns
+    pub primary_key: Vec<String>,
+    
+    /// Index definitions
+    pub indexes: Vec<IndexDefinition>,
+    
+    /// Table description
+    pub description: String,
+    
+    /// Creation timestamp
+    pub created_at: u64,
+    
+    /// Last updated timestamp
+    pub updated_at: u64,
+}
+
+/// Column Definition
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColumnDefinition {
+    /// Column name
+    pub name: String,
+    
+    /// Column type
+    pub column_type: ColumnType,
+    
+    /// Is the column nullable
+    pub nullable: bool,
+    
+    /// Default value (if any)
+    pub default_value: Option<String>,
+    
+    /// Column description
+    pub description: String,
+}
+
+/// Column Type
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ColumnType {
+    Integer,
+    Long,
+    Float,
+    Double,
+    String,
+    Boolean,
+    Timestamp,
+    Binary,
+    Json,
+    Uuid,
+}
+
+/// Index Definition
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexDefinition {
+    /// Index name
+    pub name: String,
+    
+    /// Columns in the index
+    pub columns: Vec<String>,
+    
+    /// Is this a unique index
+    pub unique: bool,
+}
+
+/// Table Row (generic data storage)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TableRow {
+    /// Row ID (unique within table)
+    pub row_id: String,
+    
+    /// Column values
+    pub values: HashMap<String, String>,
+    
+    /// Creation timestamp
+    pub created_at: u64,
+    
+    /// Last updated timestamp
+    pub updated_at: u64,
+}
+
+/// Table storage options
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StorageOptions {
+    /// Columns stored with dictionary encoding
+    ///
+    /// `None` encodes every column except UUID, timestamp and binary columns, whose
+    /// values are rarely repeated.
+    pub dictionary_columns: Option<Vec<String>>,
+}
+
+impl StorageOptions {
+    /// Check whether a column should be dictionary encoded
+    fn uses_dictionary(&self, column: &ColumnDefinition) -> bool {
+        match &self.dictionary_columns {
+            Some(columns) => columns.contains(&column.name),
+            None => !matches!(column.column_type, ColumnType::Uuid | ColumnType::Timestamp | ColumnType::Binary),
+        }
+    }
+}
+
+/// Namespace column of the core tables
+fn namespace_column() -> ColumnDefinition {
+    ColumnDefinition {
+        name: NAMESPACE_COLUMN.to_string(),
+        column_type: ColumnType::String,
+        nullable: false,
+        default_value: Some(format!("'{}'", DEFAULT_NAMESPACE)),
+        description: "Namespace (user or tenant) owning the row".to_string(),
+    }
+}
+
+/// Index on the namespace column of a core table
+fn namespace_index(name: &str) -> IndexDefinition {
+    IndexDefinition {
+        name: name.to_string(),
+        columns: vec![NAMESPACE_COLUMN.to_string()],
+        unique: false,
+    }
+}
+
+/// Check that a namespace name is usable in table names and paths
+pub fn validate_namespace(namespace: &str) -> Result<(), String> {
+    let valid = !namespace.is_empty()
+        && namespace.len() <= 64
+        && namespace.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        return Err(format!("Invalid namespace '{}' (expected 1-64 letters, digits, '-' or '_')", namespace));
+    }
+    Ok(())
+}
+
+/// Storage statistics of a table
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StorageStats {
+    /// Number of rows
+    pub rows: usize,
+    
+    /// Number of columns
+    pub columns: usize,
+    
+    /// Number of dictionary-encoded columns
+    pub dictionary_columns: usize,
+    
+    /// Distinct values held in column dictionaries
+    pub dictionary_entries: usize,
+    
+    /// Approximate heap usage of the rows and dictionaries in bytes
+    pub approximate_bytes: usize,
+}
+
+/// Stored cell value
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Cell {
+    /// No value
+    Null,
+    
+    /// Value stored in the row
+    Inline(Box<str>),
+    
+    /// Code into the column dictionary
+    Code(u32),
+}
+
+/// Distinct values of a dictionary-encoded column
+///
+/// Every entry counts the cells referencing it. A value is dropped when its last
+/// cell is updated or deleted, and its code is reused by the next new value.
+#[derive(Debug, Default)]
+struct ValueDictionary {
+    /// Values and their reference counts by code, `None` for free codes
+    entries: Vec<Option<(Arc<str>, usize)>>,
+    codes: HashMap<Arc<str>, u32>,
+    free: Vec<u32>,
+}
+
+impl ValueDictionary {
+    /// Get the code of a value for a new cell, adding the value if needed
+    fn intern(&mut self, value: &str) -> u32 {
+        if let Some(code) = self.codes.get(value) {
+            if let Some((_, references)) = &mut self.entries[*code as usize] {
+                *references += 1;
+            }
+            return *code;
+        }
+        let value: Arc<str> = Arc::from(value);
+        let entry = Some((value.clone(), 1));
+        let code = match self.free.pop() {
+            Some(code) => {
+                self.entries[code as usize] = entry;
+                code
+            }
+            None => {
+                self.entries.push(entry);
+                (self.entries.len() - 1) as u32
+            }
+        };
+        self.codes.insert(value, code);
+        code
+    }
+    
+    /// Drop the reference of a cell that no longer holds a code
+    fn release(&mut self, code: u32) {
+        let Some(slot) = self.entries.get_mut(code as usize) else {
+            return;
+        };
+        if let Some((value, references)) = slot {
+            *references -= 1;
+            if *references == 0 {
+                self.codes.remove(value);
+                *slot = None;
+                self.free.push(code);
+            }
+        }
+    }
+    
+    /// Get the value of a code
+    fn get(&self, code: u32) -> Option<&str> {
+        self.entries.get(code as usize)?.as_ref().map(|(value, _)| value.as_ref())
+    }
+    
+    /// Values currently referenced by a cell
+    fn values(&self) -> impl Iterator<Item = &Arc<str>> {
+        self.entries.iter().flatten().map(|(value, _)| value)
+    }
+}
+
+/// Stored row: cells in schema column order
+#[derive(Debug, Clone)]
+struct StoredRow {
+    cells: Box<[Cell]>,
+    created_at: u64,
+    updated_at: u64,
+}
+
+/// Columnar-schema storage of a single table
+///
+/// Column names are kept once per table; rows hold cells by column ordinal and
+/// repeated values of dictionary-encoded columns are shared.
+#[derive(Debug)]
+struct TableStore {
+    definition: TableDefinition,
+    columns: Vec<String>,
+    ordinals: HashMap<String, usize>,
+    dictionaries: Vec<Option<ValueDictionary>>,
+    rows: BTreeMap<Box<str>, StoredRow>,
+}
+
+impl TableStore {
+    fn new(definition: TableDefinition, options: &StorageOptions) -> Self {
+        Self {
+            columns: definition.columns.iter().map(|column| column.name.clone()).collect(),
+            ordinals: definition.columns.iter().enumerate().map(|(index, column)| (column.name.clone(), index)).collect(),
+            dictionaries: definition.columns.iter()
+                .map(|column| options.uses_dictionary(column).then(ValueDictionary::default))
+                .collect(),
+            rows: BTreeMap::new(),
+            definition,
+        }
+    }
+    
+    /// Encode a value for a column
+    fn encode(&mut self, ordinal: usize, value: &str) -> Cell {
+        match &mut self.dictionaries[ordinal] {
+            Some(dictionary) => Cell::Code(dictionary.intern(value)),
+            None => Cell::Inline(value.into()),
+        }
+    }
+    
+    /// Release the dictionary entry of a cell that is overwritten or deleted
+    fn release(&mut self, ordinal: usize, cell: &Cell) {
+        if let (Cell::Code(code), Some(dictionary)) = (cell, &mut self.dictionaries[ordinal]) {
+            dictionary.release(*code);
+        }
+    }
+    
+    /// Decode a cell of a column
+    fn decode<'a>(&'a self, ordinal: usize, cell: &'a Cell) -> Option<&'a str> {
+        match cell {
+            Cell::Null => None,
+            Cell::Inline(value) => Some(value),
+            Cell::Code(code) => self.dictionaries[ordinal].as_ref().and_then(|dictionary| dictionary.get(*code)),
+        }
+    }
+    
+    /// Materialize a stored row
+    fn to_row(&self, row_id: &str, row: &StoredRow) -> TableRow {
+        let values = row.cells.iter().enumerate()
+            .filter_map(|(ordinal, cell)| {
+                self.decode(ordinal, cell).map(|value| (self.columns[ordinal].clone(), value.to_string()))
+            })
+            .collect();
+        
+        TableRow {
+            row_id: row_id.to_string(),
+            values,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        }
+    }
+    
+    /// Decode a stored row into a reusable row buffer
+    ///
+    /// Column names and value strings already in the buffer are reused, so filling
+    /// the same buffer with rows of one table does not allocate once it has warmed up.
+    fn fill_row(&self, row_id: &str, row: &StoredRow, target: &mut TableRow) {
+        target.row_id.clear();
+        target.row_id.push_str(row_id);
+        target.created_at = row.created_at;
+        target.updated_at = row.updated_at;
+        
+        for (ordinal, cell) in row.cells.iter().enumerate() {
+            let column = &self.columns[ordinal];
+            match self.decode(ordinal, cell) {
+                Some(value) => match target.values.get_mut(column) {
+                    Some(existing) => {
+                        existing.clear();
+                        existing.push_str(value);
+                    }
+                    None => {
+                        target.values.insert(column.clone(), value.to_string());
+                    }
+                },
+                None => {
+                    target.values.remove(column);
+                }
+            }
+        }
+    }
+    
+    /// Get the namespace a row belongs to
+    ///
+    /// Rows of shared tables belong to the namespace in their namespace column, rows
+    /// of namespace-private tables to the namespace in the table name.
+    fn row_namespace(&self, row: &StoredRow) -> Option<String> {
+        match self.ordinals.get(NAMESPACE_COLUMN) {
+            Some(ordinal) => self.decode(*ordinal, &row.cells[*ordinal]).map(str::to_string),
+            None => self.definition.name.split_once(NAMESPACE_SEPARATOR).map(|(namespace, _)| namespace.to_string()),
+        }
+    }
+    
+    /// Check whether a row of a shared table is visible in a namespace
+    ///
+    /// Every row is visible without a namespace.
+    fn in_namespace(&self, row: &StoredRow, namespace: Option<&str>) -> bool {
+        match (namespace, self.ordinals.get(NAMESPACE_COLUMN)) {
+            (Some(namespace), Some(ordinal)) => self.decode(*ordinal, &row.cells[*ordinal]) == Some(namespace),
+            _ => true,
+        }
+    }
+    
+    /// Compile equality conditions into expected cells
+    ///
+    /// Returns `None` if no row can match (unknown column or a value that is not
+    /// in the column dictionary).
+    fn compile_conditions(&self, conditions: &HashMap<String, String>) -> Option<Vec<(usize, Cell)>> {
+        conditions.iter()
+            .map(|(column, value)| {
+                let ordinal = *self.ordinals.get(column)?;
+                let cell = match &self.dictionaries[ordinal] {
+                    Some(dictionary) => Cell::Code(*dictionary.codes.get(value.as_str())?),
+                    None => Cell::Inline(value.as_str().into()),
+                };
+                Some((ordinal, cell))
+            })
+            .collect()
+    }
+    
+    fn stats(&self) -> StorageStats {
+        let mut approximate_bytes = 0;
+        for (row_id, row) in &self.rows {
+            approximate_bytes += row_id.len() + std::mem::size_of::<StoredRow>()
+                + row.cells.len() * std::mem::size_of::<Cell>();
+            for cell in row.cells.iter() {
+                if let Cell::Inline(value) = cell {
+                    approximate_bytes += value.len();
+                }
+            }
+        }
+        
+        let dictionaries: Vec<&ValueDictionary> = self.dictionaries.iter().flatten().collect();
+        let dictionary_entries = dictionaries.iter().map(|dictionary| dictionary.codes.len()).sum();
+        for dictionary in &dictionaries {
+            // Each value is shared by the entry list and the reverse map
+            approximate_bytes += dictionary.values()
+                .map(|value| value.len() + 2 * std::mem::size_of::<Arc<str>>() + std::mem::size_of::<usize>() + std::mem::size_of::<u32>())
+                .sum::<usize>();
+        }
+        
+        StorageStats {
+            rows: self.rows.len(),
+            columns: self.columns.len(),
+            dictionary_columns: dictionaries.len(),
+            dictionary_entries,
+            approximate_bytes,
+        }
+    }
+}
+
+/// Lock-protected storage of one table
+type TableShard = Arc<RwLock<TableStore>>;
+
+/// DBOS Tables Manager
+///
+/// Every table has its own lock, so writers to different tables never contend.
+/// The table map lock is only held to look up or register a table.
+pub struct TablesManager {
+    /// Registered tables and their storage
+    tables: RwLock<HashMap<String, TableShard>>,
+    
+    /// Is the manager running
+    running: AtomicBool,
+}
+
+impl TablesManager {
+    /// Create a new tables manager
+    pub fn new() -> Self {
+        let manager = Self {
+            tables: RwLock::new(HashMap::new()),
+            running: AtomicBool::new(false),
+        };
+        
+        // Initialize core OS tables
+        manager.init_core_tables().unwrap_or_default();
+        manager
+    }
+    
+    /// Initialize core OS tables based on DBOS paper recommendations
+    fn init_core_tables(&self) -> Result<(), String> {
+        // Task table (process table)
+        let task_table = TableDefinition {
+            name: "tasks".to_string(),
+            columns: vec![
+                ColumnDefinition {
+                    name: "task_id".to_string(),
+                    column_type: ColumnType::Uuid,
+                    nullable: false,
+                    default_value: Some("UUID()".to_string()),
+                    description: "Unique task identifier".to_string(),
+                },
+                namespace_column(),
+                ColumnDefinition {
+                    name: "name".to_string(),
+                    column_type: ColumnType::String,
+                    nullable: false,
+                    default_value: None,
+                    description: "Task name/command".to_string(),
+                },
+                ColumnDefinition {
+                    name: "status".to_string(),
+                    column_type: ColumnType::String,
+                    nullable: false,
+                    default_value: Some("'CREATED'".to_string()),
+                    description: "Task status (CREATED, RUNNING, BLOCKED, TERMINATED)".to_string(),
+                },
+                ColumnDefinition {
+                    name: "priority".to_string(),
+                    column_type: ColumnType::Integer,
+                    nullable: false,
+                    default_value: Some("0".to_string()),
+                    description: "Task priority".to_string(),
+                },
+                ColumnDefinition {
+                    name: "parent_id".to_string(),
+                    column_type: ColumnType::Uuid,
+                    nullable: true,
+                    default_value: None,
+                    description: "Parent task ID".to_string(),
+                },
+                ColumnDefinition {
+                    name: "start_time".to_string(),
+                    column_type: ColumnType::Timestamp,
+                    nullable: true,
+                    default_value: None,
+                    description: "Task start time".to_string(),
+                },
+                ColumnDefinition {
+                    name: "end_time".to_string(),
+                    column_type: ColumnType::Timestamp,
+                    nullable: true,
+                    default_value: None,
+                    description: "Task end time".to_string(),
+                },
+                ColumnDefinition {
+                    name: "resource_usage".to_string(),
+                    column_type: ColumnType::Json,
+                    nullable: true,
+                    default_value: None,
+                    description: "Task resource usage (CPU, memory, etc.)".to_string(),
+                },
+            ],
+            primary_key: vec!["task_id".to_string()],
+            indexes: vec![
+                IndexDefinition {
+                    name: "idx_tasks_status".to_string(),
+                    columns: vec!["status".to_string()],
+                    unique: false,
+                },
+                IndexDefinition {
+                    name: "idx_tasks_parent".to_string(),
+                    columns: vec!["parent_id".to_string()],
+                    unique: false,
+                },
+                namespace_index("idx_tasks_namespace"),
+            ],
+            description: "System tasks/processes table".to_string(),
+            created_at: Self::current_timestamp(),
+            updated_at: Self::current_timestamp(),
+        };
+        
+        // Resource table
+        let resource_table = TableDefinition {
+            name: "resources".to_string(),
+            columns: vec![
+                ColumnDefinition {
+                    name: "resource_id".to_string(),
+                    column_type: ColumnType::Uuid,
+                    nullable: false,
+                    default_value: Some("UUID()".to_string()),
+                    description: "Unique resource identifier".to_string(),
+                },
+                namespace_column(),
+                ColumnDefinition {
+                    name: "name".to_string(),
+                    column_type: ColumnType::String,
+                    nullable: false,
+                    default_value: None,
+                    description: "Resource name".to_string(),
+                },
+                ColumnDefinition {
+                    name: "resource_type".to_string(),
+                    column_type: ColumnType::String,
+                    nullable: false,
+                    default_value: None,
+                    description: "Resource type (CPU, memory, disk, network)".to_string(),
+                },
+                ColumnDefinition {
+                    name: "status".to_string(),
+                    column_type: ColumnType::String,
+                    nullable: false,
+                    default_value: Some("'AVAILABLE'".to_string()),
+                    description: "Resource status (AVAILABLE, IN_USE, ERROR)".to_string(),
+                },
+                ColumnDefinition {
+                    name: "capacity".to_string(),
+                    column_type: ColumnType::Double,
+                    nullable: false,
+                    default_value: Some("0.0".to_string()),
+                    description: "Resource capacity".to_string(),
+                },
+                ColumnDefinition {
+                    name: "allocated".to_string(),
+                    column_type: ColumnType::Double,
+                    nullable: false,
+                    default_value: Some("0.0".to_string()),
+                    description: "Allocated resource amount".to_string(),
+                },
+                ColumnDefinition {
+                    name: "parent_id".to_string(),
+                    column_type: ColumnType::Uuid,
+                    nullable: true,
+                    default_value: None,
+                    description: "Containing resource in the hardware topology (package, core)".to_string(),
+                },
+                ColumnDefinition {
+                    name: "numa_node".to_string(),
+                    column_type: ColumnType::Integer,
+                    nullable: true,
+                    default_value: None,
+                    description: "NUMA node the resource is local to".to_string(),
+                },
+                ColumnDefinition {
+                    name: "metadata".to_string(),
+                    column_type: ColumnType::Json,
+                    nullable: true,
+                    default_value: None,
+                    description: "Resource metadata".to_string(),
+                },
+            ],
+            primary_key: vec!["resource_id".to_string()],
+            indexes: vec![
+                IndexDefinition {
+                    name: "idx_resources_type".to_string(),
+                    columns: vec!["resource_type".to_string(), "status".to_string()],
+                    unique: false,
+                },
+                IndexDefinition {
+                    name: "idx_resources_parent".to_string(),
+                    columns: vec!["parent_id".to_string()],
+                    unique: false,
+                },
+                namespace_index("idx_resources_namespace"),
+            ],
+            description: "System resources table".to_string(),
+            created_at: Self::current_timestamp(),
+            updated_at: Self::current_timestamp(),
+        };
+        
+        // File system table
+        let fs_table = TableDefinition {
+            name: "file_system".to_string(),
+            columns: vec![
+                ColumnDefinition {
+                    name: "file_id".to_string(),
+                    column_type: ColumnType::Uuid,
+                    nullable: false,
+                    default_value: Some("UUID()".to_string()),
+                    description: "Unique file identifier".to_string(),
+                },
+                namespace_column(),
+                ColumnDefinition {
+                    name: "path".to_string(),
+                    column_type: ColumnType::String,
+                    nullable: false,
+                    default_value: None,
+                    description: "File path".to_string(),
+                },
+                ColumnDefinition {
+                    name: "file_name".to_string(),
+                    column_type: ColumnType::String,
+                    nullable: false,
+                    default_value: None,
+                    description: "File name".to_string(),
+                },
+                ColumnDefinition {
+                    name: "file_type".to_string(),
+                    column_type: ColumnType::String,
+                    nullable: false,
+                    default_value: None,
+                    description: "File type (FILE, DIRECTORY, SYMLINK)".to_string(),
+                },
+                ColumnDefinition {
+                    name: "size".to_string(),
+                    column_type: ColumnType::Long,
+                    nullable: false,
+                    default_value: Some("0".to_string()),
+                    description: "File size in bytes".to_string(),
+                },
+                ColumnDefinition {
+                    name: "owner".to_string(),
+                    column_type: ColumnType::String,
+                    nullable: false,
+                    default_value: None,
+                    description: "File owner".to_string(),
+                },
+                ColumnDefinition {
+                    name: "permissions".to_string(),
+                    column_type: ColumnType::String,
+                    nullable: false,
+                    default_value: None,
+                    description: "File permissions".to_string(),
+                },
+                ColumnDefinition {
+                    name: "created_at".to_string(),
+                    column_type: ColumnType::Timestamp,
+                    nullable: false,
+                    default_value: None,
+                    description: "File creation time".to_string(),
+                },
+                ColumnDefinition {
+                    name: "modified_at".to_string(),
+                    column_type: ColumnType::Timestamp,
+                    nullable: false,
+                    default_value: None,
+                    description: "File modification time".to_string(),
+                },
+            ],
+            primary_key: vec!["file_id".to_string()],
+            indexes: vec![
+                IndexDefinition {
+                    name: "idx_fs_path".to_string(),
+                    columns: vec![NAMESPACE_COLUMN.to_string(), "path".to_string(), "file_name".to_string()],
+                    unique: true,
+                },
+            ],
+            description: "File system table".to_string(),
+            created_at: Self::current_timestamp(),
+            updated_at: Self::current_timestamp(),
+        };
+        
+        // Register core tables
+        self.register_table(task_table, StorageOptions::default())?;
+        self.register_table(resource_table, StorageOptions::default())?;
+        self.register_table(fs_table, StorageOptions::default())?;
+        
+        Ok(())
+    }
+    
+    /// Helper method to get current timestamp
+    fn current_timestamp() -> u64 {
+        simulation::unix_millis() / 1000
+    }
+    
+    /// Start the tables manager
+    pub fn start(&self) {
+        self.running.store(true, Ordering::SeqCst);
+    }
+    
+    /// Stop the tables manager
+    pub fn stop(&self) {
+        self.running.store(false, Ordering::SeqCst);
+    }
+    
+    /// Fail unless the manager is running
+    fn ensure_running(&self) -> Result<(), String> {
+        if !self.running.load(Ordering::SeqCst) {
+            return Err("Tables manager is not running".to_string());
+        }
+        Ok(())
+    }
+    
+    /// Get the shard of a table, releasing the table map lock immediately
+    fn shard(&self, table_name: &str) -> Result<TableShard, String> {
+        simulation::read(&self.tables)
+            .get(table_name)
+            .cloned()
+            .ok_or_else(|| format!("Table '{}' not found", table_name))
+    }
+    
+    /// Create a new table
+    pub fn create_table(&self, table_def: TableDefinition) -> Result<(), String> {
+        self.create_table_with_options(table_def, StorageOptions::default())
+    }
+    
+    /// Create a new table with explicit storage options
+    ///
+    /// Names containing `NAMESPACE_SEPARATOR` are reserved for namespace-private tables.
+    pub fn create_table_with_options(&self, table_def: TableDefinition, options: StorageOptions) -> Result<(), String> {
+        self.ensure_running()?;
+        if table_def.name.contains(NAMESPACE_SEPARATOR) {
+            return Err(format!("Table name '{}' must not contain '{}'", table_def.name, NAMESPACE_SEPARATOR));
+        }
+        self.register_table(table_def, options)
+    }
+    
+    /// Register a table definition and its storage
+    fn register_table(&self, table_def: TableDefinition, options: StorageOptions) -> Result<(), String> {
+        let mut tables = simulation::write(&self.tables);
+        if tables.contains_key(&table_def.name) {
+            return Err(format!("Table '{}' already exists", table_def.name));
+        }
+        
+        let name = table_def.name.clone();
+        let columns: Vec<&str> = table_def.columns.iter().map(|column| column.name.as_str()).collect();
+        let summary = format!("columns: {}", columns.join(", "));
+        tables.insert(name.clone(), Arc::new(RwLock::new(TableStore::new(table_def, &options))));
+        drop(tables);
+        
+        audit::record(AuditCategory::Table, "table.create", name, None, Some(summary));
+        Ok(())
+    }
+    
+    /// Get table definition by name
+    pub fn get_table(&self, table_name: &str) -> Result<Option<TableDefinition>, String> {
+        let tables = simulation::read(&self.tables);
+        Ok(tables.get(table_name).map(|shard| simulation::read(shard).definition.clone()))
+    }
+    
+    /// Get all tables
+    pub fn get_all_tables(&self) -> Result<Vec<TableDefinition>, String> {
+        let tables = simulation::read(&self.tables);
+        Ok(tables.values().map(|shard| simulation::read(shard).definition.clone()).collect())
+    }
+    
+    /// Get storage statistics of a table
+    pub fn storage_stats(&self, table_name: &str) -> Result<StorageStats, String> {
+        let shard = self.shard(table_name)?;
+        let stats = simulation::read(&shard).stats();
+        Ok(stats)
+    }
+    
+    /// Get a view of the tables restricted to one namespace
+    ///
+    /// The view sees the namespace's private tables and its own rows of the shared
+    /// tables (those with a `NAMESPACE_COLUMN`), and nothing else. The manager
+    /// itself is unrestricted and meant for the engine and administrators.
+    pub fn namespace(&self, namespace: &str) -> Result<NamespacedTables<'_>, String> {
+        validate_namespace(namespace)?;
+        Ok(NamespacedTables { manager: self, namespace: namespace.to_string() })
+    }
+    
+    /// Insert a row into a table
+    pub fn insert_row(&self, table_name: &str, values: HashMap<String, String>) -> Result<String, String> {
+        self.insert_row_in(table_name, values, None)
+    }
+    
+    /// Insert a row, restricted to a namespace
+    fn insert_row_in(&self, table_name: &str, mut values: HashMap<String, String>, namespace: Option<&str>) -> Result<String, String> {
+        self.ensure_running()?;
+        
+        let shard = self.shard(table_name)?;
+        let mut store = simulation::write(&shard);
+        
+        // Rows written in a namespace always belong to it
+        if let Some(namespace) = namespace.filter(|_| store.ordinals.contains_key(NAMESPACE_COLUMN)) {
+            match values.get(NAMESPACE_COLUMN) {
+                Some(value) if value != namespace => {
+                    return Err(format!("Cannot write rows of namespace '{}' from namespace '{}'", value, namespace));
+                }
+                _ => {
+                    values.insert(NAMESPACE_COLUMN.to_string(), namespace.to_string());
+                }
+            }
+        }
+        
+        // Validate column values
+        for column in &store.definition.columns {
+            if !column.nullable && !values.contains_key(&column.name) && column.default_value.is_none() {
+                return Err(format!("Column '{}' is required but not provided", column.name));
+            }
+        }
+        
+        // Generate row ID
+        let row_id = Uuid::new_v4().to_string();
+        let timestamp = Self::current_timestamp();
+        
+        // Encode cells in column order, with default values where applicable
+        let mut cells = Vec::with_capacity(store.columns.len());
+        for ordinal in 0..store.columns.len() {
+            let cell = if let Some(value) = values.get(&store.columns[ordinal]) {
+                store.encode(ordinal, value)
+            } else if let Some(default) = store.definition.columns[ordinal].default_value.clone() {
+                // Handle special default values like UUID() and CURRENT_TIMESTAMP
+                let processed_default = if default.to_uppercase() == "UUID()" {
+                    Uuid::new_v4().to_string()
+                } else if default.to_uppercase() == "CURRENT_TIMESTAMP" {
+                    timestamp.to_string()
+                } else {
+                    // Remove quotes if present
+                    default.trim_matches(|c| c == '\'' || c == '"').to_string()
+                };
+                store.encode(ordinal, &processed_default)
+            } else {
+                Cell::Null
+            };
+            cells.push(cell);
+        }
+        
+        let row = StoredRow {
+            cells: cells.into_boxed_slice(),
+            created_at: timestamp,
+            updated_at: timestamp,
+        };
+        let row_namespace = store.row_namespace(&row);
+        let summary = audit::summarize(&store.to_row(&row_id, &row).values);
+        store.rows.insert(row_id.as_str().into(), row);
+        
+        // Release the lock before notifying subscribers, which may query the table
+        drop(store);
+        Self::publish_change(table_name, &row_id, TableChangeKind::Insert, row_namespace, None, Some(summary));
+        
+        Ok(row_id)
+    }
+    
+    /// Get a row by ID
+    pub fn get_row(&self, table_name: &str, row_id: &str) -> Result<Option<TableRow>, String> {
+        self.get_row_in(table_name, row_id, None)
+    }
+    
+    /// Get a row by ID, restricted to a namespace
+    fn get_row_in(&self, table_name: &str, row_id: &str, namespace: Option<&str>) -> Result<Option<TableRow>, String> {
+        let shard = self.shard(table_name)?;
+        let store = simulation::read(&shard);
+        Ok(store.rows.get(row_id)
+            .filter(|row| store.in_namespace(row, namespace))
+            .map(|row| store.to_row(row_id, row)))
+    }
+    
+    /// Get all rows from a table
+    pub fn get_all_rows(&self, table_name: &str) -> Result<Vec<TableRow>, String> {
+        self.query_rows(table_name, HashMap::new())
+    }
+    
+    /// Update a row
+    pub fn update_row(&self, table_name: &str, row_id: &str, values: HashMap<String, String>) -> Result<(), String> {
+        self.update_row_in(table_name, row_id, values, None)
+    }
+    
+    /// Update a row, restricted to a namespace
+    fn update_row_in(&self, table_name: &str, row_id: &str, values: HashMap<String, String>, namespace: Option<&str>) -> Result<(), String> {
+        self.ensure_running()?;
+        
+        let shard = self.shard(table_name)?;
+        let mut store = simulation::write(&shard);
+        
+        // Validate column names
+        let mut updates = Vec::with_capacity(values.len());
+        for (column_name, value) in &values {
+            let ordinal = *store.ordinals.get(column_name)
+                .ok_or_else(|| format!("Column '{}' does not exist in table '{}'", column_name, table_name))?;
+            if namespace.is_some() && column_name == NAMESPACE_COLUMN && namespace != Some(value.as_str()) {
+                return Err(format!("Cannot move rows out of namespace '{}'", namespace.unwrap_or_default()));
+            }
+            updates.push((ordinal, value));
+        }
+        if !store.rows.get(row_id).is_some_and(|row| store.in_namespace(row, namespace)) {
+            return Err(format!("Row '{}' not found in table '{}'", row_id, table_name));
+        }
+        
+        // Remember the previous values of the updated columns for the audit log
+        let previous: HashMap<String, String> = store.rows.get(row_id)
+            .map(|row| updates.iter()
+                .filter_map(|(ordinal, _)| {
+                    store.decode(*ordinal, &row.cells[*ordinal]).map(|value| (store.columns[*ordinal].clone(), value.to_string()))
+                })
+                .collect())
+            .unwrap_or_default();
+        
+        // Update row
+        let cells: Vec<(usize, Cell)> = updates.into_iter()
+            .map(|(ordinal, value)| (ordinal, store.encode(ordinal, value)))
+            .collect();
+        let mut replaced = Vec::with_capacity(cells.len());
+        if let Some(row) = store.rows.get_mut(row_id) {
+            for (ordinal, cell) in cells {
+                replaced.push((ordinal, std::mem::replace(&mut row.cells[ordinal], cell)));
+            }
+            row.updated_at = Self::current_timestamp();
+        }
+        for (ordinal, cell) in &replaced {
+            store.release(*ordinal, cell);
+        }
+        let row_namespace = store.rows.get(row_id).and_then(|row| store.row_namespace(row));
+        
+        drop(store);
+        Self::publish_change(table_name, row_id, TableChangeKind::Update, row_namespace,
+            Some(audit::summarize(&previous)), Some(audit::summarize(&values)));
+        Ok(())
+    }
+    
+    /// Delete a row
+    pub fn delete_row(&self, table_name: &str, row_id: &str) -> Result<(), String> {
+        self.delete_row_in(table_name, row_id, None)
+    }
+    
+    /// Delete a row, restricted to a namespace
+    fn delete_row_in(&self, table_name: &str, row_id: &str, namespace: Option<&str>) -> Result<(), String> {
+        self.ensure_running()?;
+        
+        let shard = self.shard(table_name)?;
+        let mut store = simulation::write(&shard);
+        let (row_namespace, summary) = match store.rows.get(row_id) {
+            Some(row) if store.in_namespace(row, namespace) => {
+                (store.row_namespace(row), audit::summarize(&store.to_row(row_id, row).values))
+            }
+            _ => return Err(format!("Row '{}' not found in table '{}'", row_id, table_name)),
+        };
+        if let Some(row) = store.rows.remove(row_id) {
+            for (ordinal, cell) in row.cells.iter().enumerate() {
+                store.release(ordinal, cell);
+            }
+        }
+        drop(store);
+        
+        Self::publish_change(table_name, row_id, TableChangeKind::Delete, row_namespace, Some(summary), None);
+        Ok(())
+    }
+    
+    /// Record a row change in the audit log and publish it on the global event bus
+    fn publish_change(
+        table_name: &str,
+        row_id: &str,
+        kind: TableChangeKind,
+        namespace: Option<String>,
+        old: Option<String>,
+        new: Option<String>,
+    ) {
+        let operation = match kind {
+            TableChangeKind::Insert => "table.insert",
+            TableChangeKind::Update => "table.update",
+            TableChangeKind::Delete => "table.delete",
+        };
+        audit::record(AuditCategory::Table, operation, format!("{}/{}", table_name, row_id), old, new);
+        event_bus::publish(SystemEvent::Table(TableChangeEvent {
+            table: table_name.to_string(),
+            row_id: row_id.to_string(),
+            kind,
+            namespace,
+        }));
+    }
+    
+    /// Visit the rows matching simple conditions without cloning them
+    ///
+    /// The visitor borrows a row buffer that is refilled for every row, so it must copy
+    /// out whatever it wants to keep. The table stays read-locked while visiting; the
+    /// visitor must not write to the same table. Returns the number of rows visited.
+    pub fn for_each_row<F>(&self, table_name: &str, conditions: &HashMap<String, String>, visitor: F) -> Result<usize, String>
+    where
+        F: FnMut(&TableRow),
+    {
+        self.for_each_row_in(table_name, conditions, visitor, None)
+    }
+    
+    /// Visit the rows matching simple conditions, restricted to a namespace
+    fn for_each_row_in<F>(&self, table_name: &str, conditions: &HashMap<String, String>, mut visitor: F, namespace: Option<&str>) -> Result<usize, String>
+    where
+        F: FnMut(&TableRow),
+    {
+        let shard = self.shard(table_name)?;
+        let store = simulation::read(&shard);
+        
+        let conditions = match store.compile_conditions(conditions) {
+            Some(conditions) => conditions,
+            None => return Ok(0),
+        };
+        
+        let mut buffer = TableRow {
+            row_id: String::new(),
+            values: HashMap::with_capacity(store.columns.len()),
+            created_at: 0,
+            updated_at: 0,
+        };
+        let mut visited = 0;
+        for (row_id, row) in &store.rows {
+            if conditions.iter().all(|(ordinal, cell)| row.cells[*ordinal] == *cell) && store.in_namespace(row, namespace) {
+                store.fill_row(row_id, row, &mut buffer);
+                visitor(&buffer);
+                visited += 1;
+            }
+        }
+        Ok(visited)
+    }
+    
+    /// Query rows with simple conditions
+    pub fn query_rows(&self, table_name: &str, conditions: HashMap<String, String>) -> Result<Vec<TableRow>, String> {
+        self.query_rows_in(table_name, conditions, None)
+    }
+    
+    /// Query rows with simple conditions, restricted to a namespace
+    fn query_rows_in(&self, table_name: &str, conditions: HashMap<String, String>, namespace: Option<&str>) -> Result<Vec<TableRow>, String> {
+        let shard = self.shard(table_name)?;
+        let store = simulation::read(&shard);
+        
+        let conditions = match store.compile_conditions(&conditions) {
+            Some(conditions) => conditions,
+            None => return Ok(Vec::new()),
+        };
+        
+        Ok(store.rows.iter()
+            .filter(|(_, row)| conditions.iter().all(|(ordinal, cell)| row.cells[*ordinal] == *cell))
+            .filter(|(_, row)| store.in_namespace(row, namespace))
+            .map(|(row_id, row)| store.to_row(row_id, row))
+            .collect())
+    }
+}
+
+/// View of a `TablesManager` restricted to one namespace
+///
+/// Table names are resolved first to the namespace's private tables, then to the
+/// shared tables, whose rows are filtered by their `NAMESPACE_COLUMN`. Shared tables
+/// without that column and other namespaces' tables are not visible.
+pub struct NamespacedTables<'a> {
+    manager: &'a TablesManager,
+    namespace: String,
+}
+
+impl NamespacedTables<'_> {
+    /// Get the namespace of the view
+    pub fn name(&self) -> &str {
+        &self.namespace
+    }
+    
+    /// Get the storage name of one of the namespace's private tables
+    fn private_name(&self, table_name: &str) -> String {
+        format!("{}{}{}", self.namespace, NAMESPACE_SEPARATOR, table_name)
+    }
+    
+    /// Resolve a table name to its storage name and the row filter to apply
+    fn resolve(&self, table_name: &str) -> Result<(String, Option<&str>), String> {
+        let not_found = || format!("Table '{}' not found", table_name);
+        if table_name.contains(NAMESPACE_SEPARATOR) {
+            return Err(not_found());
+        }
+        let private = self.private_name(table_name);
+        let tables = simulation::read(&self.manager.tables);
+        if tables.contains_key(&private) {
+            return Ok((private, None));
+        }
+        match tables.get(table_name) {
+            Some(shard) if simulation::read(shard).ordinals.contains_key(NAMESPACE_COLUMN) => {
+                Ok((table_name.to_string(), Some(self.namespace.as_str())))
+            }
+            _ => Err(not_found()),
+        }
+    }
+    
+    /// Present a private table definition under its name within the namespace
+    fn local_definition(&self, mut definition: TableDefinition) -> TableDefinition {
+        if let Some((_, name)) = definition.name.split_once(NAMESPACE_SEPARATOR) {
+            definition.name = name.to_string();
+        }
+        definition
+    }
+    
+    /// Create a table private to the namespace
+    pub fn create_table(&self, table_def: TableDefinition) -> Result<(), String> {
+        self.create_table_with_options(table_def, StorageOptions::default())
+    }
+    
+    /// Create a table private to the namespace with explicit storage options
+    pub fn create_table_with_options(&self, mut table_def: TableDefinition, options: StorageOptions) -> Result<(), String> {
+        self.manager.ensure_running()?;
+        if table_def.name.contains(NAMESPACE_SEPARATOR) {
+            return Err(format!("Table name '{}' must not contain '{}'", table_def.name, NAMESPACE_SEPARATOR));
+        }
+        table_def.name = self.private_name(&table_def.name);
+        self.manager.register_table(table_def, options)
+    }
+    
+    /// Get the definition of a table visible in the namespace
+    pub fn get_table(&self, table_name: &str) -> Result<Option<TableDefinition>, String> {
+        match self.resolve(table_name) {
+            Ok((name, _)) => Ok(self.manager.get_table(&name)?.map(|definition| self.local_definition(definition))),
+            Err(_) => Ok(None),
+        }
+    }
+    
+    /// Get the tables visible in the namespace: its private tables and the shared tables
+    pub fn get_all_tables(&self) -> Result<Vec<TableDefinition>, String> {
+        let prefix = self.private_name("");
+        Ok(self.manager.get_all_tables()?
+            .into_iter()
+            .filter(|definition| {
+                definition.name.starts_with(&prefix)
+                    || (!definition.name.contains(NAMESPACE_SEPARATOR)
+                        && definition.columns.iter().any(|column| column.name == NAMESPACE_COLUMN))
+            })
+            .map(|definition| self.local_definition(definition))
+            .collect())
+    }
+    
+    /// Insert a row owned by the namespace
+    pub fn insert_row(&self, table_name: &str, values: HashMap<String, String>) -> Result<String, String> {
+        let (name, namespace) = self.resolve(table_name)?;
+        self.manager.insert_row_in(&name, values, namespace)
+    }
+    
+    /// Get a row of the namespace by ID
+    pub fn get_row(&self, table_name: &str, row_id: &str) -> Result<Option<TableRow>, String> {
+        let (name, namespace) = self.resolve(table_name)?;
+        self.manager.get_row_in(&name, row_id, namespace)
+    }
+    
+    /// Get all rows of the namespace in a table
+    pub fn get_all_rows(&self, table_name: &str) -> Result<Vec<TableRow>, String> {
+        self.query_rows(table_name, HashMap::new())
+    }
+    
+    /// Update a row of the namespace
+    pub fn update_row(&self, table_name: &str, row_id: &str, values: HashMap<String, String>) -> Result<(), String> {
+        let (name, namespace) = self.resolve(table_name)?;
+        self.manager.update_row_in(&name, row_id, values, namespace)
+    }
+    
+    /// Delete a row of the namespace
+    pub fn delete_row(&self, table_name: &str, row_id: &str) -> Result<(), String> {
+        let (name, namespace) = self.resolve(table_name)?;
+        self.manager.delete_row_in(&name, row_id, namespace)
+    }
+    
+    /// Visit the rows of the namespace matching simple conditions without cloning them
+    pub fn for_each_row<F>(&self, table_name: &str, conditions: &HashMap<String, String>, visitor: F) -> Result<usize, String>
+    where
+        F: FnMut(&TableRow),
+    {
+        let (name, namespace) = self.resolve(table_name)?;
+        self.manager.for_each_row_in(&name, conditions, visitor, namespace)
+    }
+    
+    /// Query the rows of the namespace with simple conditions
+    pub fn query_rows(&self, table_name: &str, conditions: HashMap<String, String>) -> Result<Vec<TableRow>, String> {
+        let (name, namespace) = self.resolve(table_name)?;
+        self.manager.query_rows_in(&name, conditions, namespace)
+    }
+}
+
+/// Tables a store of the engine keeps: every table, or the view of one namespace
+///
+/// Unlike `NamespacedTables` it shares ownership of the manager, so stores that
+/// outlive a request (build history, boot logs, traces) can hold it.
+#[derive(Clone)]
+pub struct SharedTables {
+    manager: Arc<TablesManager>,
+    namespace: Option<String>,
+}
+
+/// Call a table operation on the manager, or on the namespace view when there is one
+macro_rules! in_scope {
+    ($tables:expr, $method:ident($($arg:expr),*)) => {
+        match &$tables.namespace {
+            Some(namespace) => $tables.manager.namespace(namespace)?.$method($($arg),*),
+            None => $tables.manager.$method($($arg),*),
+        }
+    };
+}
+
+impl SharedTables {
+    /// Get the tables of a namespace
+    pub fn scoped(manager: Arc<TablesManager>, namespace: &str) -> Result<Self, String> {
+        validate_namespace(namespace)?;
+        Ok(Self { manager, namespace: Some(namespace.to_string()) })
+    }
+    
+    /// Get the namespace the tables are restricted to
+    pub fn namespace(&self) -> Option<&str> {
+        self.namespace.as_deref()
+    }
+    
+    /// Create a table (private to the namespace, if any)
+    pub fn create_table(&self, table_def: TableDefinition) -> Result<(), String> {
+        in_scope!(self, create_table(table_def))
+    }
+    
+    /// Get the definition of a visible table
+    pub fn get_table(&self, table_name: &str) -> Result<Option<TableDefinition>, String> {
+        in_scope!(self, get_table(table_name))
+    }
+    
+    /// Insert a row
+    pub fn insert_row(&self, table_name: &str, values: HashMap<String, String>) -> Result<String, String> {
+        in_scope!(self, insert_row(table_name, values))
+    }
+    
+    /// Get a row by ID
+    pub fn get_row(&self, table_name: &str, row_id: &str) -> Result<Option<TableRow>, String> {
+        in_scope!(self, get_row(table_name, row_id))
+    }
+    
+    /// Update a row
+    pub fn update_row(&self, table_name: &str, row_id: &str, values: HashMap<String, String>) -> Result<(), String> {
+        in_scope!(self, update_row(table_name, row_id, values))
+    }
+    
+    /// Delete a row
+    pub fn delete_row(&self, table_name: &str, row_id: &str) -> Result<(), String> {
+        in_scope!(self, delete_row(table_name, row_id))
+    }
+    
+    /// Visit the rows matching simple conditions without cloning them
+    pub fn for_each_row<F>(&self, table_name: &str, conditions: &HashMap<String, String>, visitor: F) -> Result<usize, String>
+    where
+        F: FnMut(&TableRow),
+    {
+        in_scope!(self, for_each_row(table_name, conditions, visitor))
+    }
+    
+    /// Query rows with simple conditions
+    pub fn query_rows(&self, table_name: &str, conditions: HashMap<String, String>) -> Result<Vec<TableRow>, String> {
+        in_scope!(self, query_rows(table_name, conditions))
+    }
+}
+
+impl From<Arc<TablesManager>> for SharedTables {
+    fn from(manager: Arc<TablesManager>) -> Self {
+        Self { manager, namespace: None }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_tables_manager() {
+        // Create tables manager
+        let manager = TablesManager::new();
+        manager.start();
+        
+        // Verify core tables are created
+        let tables = manager.get_all_tables().unwrap();
+        assert_eq!(tables.len(), 3);
+        
+        // Test inserting a row into tasks table
+        let mut task_values = HashMap::new();
+        task_values.insert("name".to_string(), "test_task".to_string());
+        task_values.insert("status".to_string(), "RUNNING".to_string());
+        task_values.insert("priority".to_string(), "10".to_string());
+        
+        let row_id = manager.insert_row("tasks", task_values).unwrap();
+        assert!(!row_id.is_empty());
+        
+        // Test getting the row
+        let row = manager.get_row("tasks", &row_id).unwrap().unwrap();
+        assert_eq!(row.values.get("name").unwrap(), "test_task");
+        assert_eq!(row.values.get("status").unwrap(), "RUNNING");
+        assert_eq!(row.values.get("priority").unwrap(), "10");
+        
+        // Test updating the row
+        let mut update_values = HashMap::new();
+        update_values.insert("status".to_string(), "TERMINATED".to_string());
+        manager.update_row("tasks", &row_id, update_values).unwrap();
+        
+        let updated_row = manager.get_row("tasks", &row_id).unwrap().unwrap();
+        assert_eq!(updated_row.values.get("status").unwrap(), "TERMINATED");
+        
+        // Test querying rows
+        let query_conditions = HashMap::from([("status".to_string(), "TERMINATED".to_string())]);
+        let queried_rows = manager.query_rows("tasks", query_conditions).unwrap();
+        assert_eq!(queried_rows.len(), 1);
+        
+        // Test deleting the row
+        manager.delete_row("tasks", &row_id).unwrap();
+        let deleted_row = manager.get_row("tasks", &row_id).unwrap();
+        assert!(deleted_row.is_none());
+        
+        manager.stop();
+    }
+    
+    #[test]
+    fn test_custom_table() {
+        let manager = TablesManager::new();
+        manager.start();
+        
+        // Create a custom table
+        let custom_table = TableDefinition {
+            name: "test_custom".to_string(),
+            columns: vec![
+                ColumnDefinition {
+                    name: "id".to_string(),
+                    column_type: ColumnType::Integer,
+                    nullable: false,
+                    default_value: Some("1".to_string()),
+                    description: "Test ID".to_string(),
+                },
+                ColumnDefinition {
+                    name: "data".to_string(),
+                    column_type: ColumnType::String,
+                    nullable: true,
+                    default_value: None,
+                    description: "Test data".to_string(),
+                },
+            ],
+            primary_key: vec!["id".to_string()],
+            indexes: vec![],
+            description: "Test custom table".to_string(),
+            created_at: TablesManager::current_timestamp(),
+            updated_at: TablesManager::current_timestamp(),
+        };
+        
+        manager.create_table(custom_table).unwrap();
+        
+        // Insert rows with default values
+        let row_id1 = manager.insert_row("test_custom", HashMap::new()).unwrap();
+        let row_id2 = manager.insert_row("test_custom", HashMap::from([("id".to_string(), "2".to_string()), ("data".to_string(), "test".to_string())])).unwrap();
+        
+        let rows = manager.get_all_rows("test_custom").unwrap();
+        assert_eq!(rows.len(), 2);
+        
+        manager.stop();
+    }
+    
+    #[test]
+    fn test_dictionary_storage() {
+        let manager = TablesManager::new();
+        manager.start();
+        
+        for index in 0..100 {
+            let status = if index % 2 == 0 { "RUNNING" } else { "SLEEPING" };
+            manager.insert_row("tasks", HashMap::from([
+                ("name".to_string(), format!("task_{}", index)),
+                ("status".to_string(), status.to_string()),
+            ])).unwrap();
+        }
+        
+        let stats = manager.storage_stats("tasks").unwrap();
+        assert_eq!(stats.rows, 100);
+        assert!(stats.dictionary_columns > 0);
+        
+        // Dictionary-encoded values round-trip and can be queried
+        let sleeping = manager.query_rows("tasks", HashMap::from([("status".to_string(), "SLEEPING".to_string())])).unwrap();
+        assert_eq!(sleeping.len(), 50);
+        assert!(sleeping.iter().all(|row| row.values["status"] == "SLEEPING"));
+        
+        // Values never stored cannot match
+        let none = manager.query_rows("tasks", HashMap::from([("status".to_string(), "ZOMBIE".to_string())])).unwrap();
+        assert!(none.is_empty());
+        
+        // Updates re-encode the cell
+        let row_id = sleeping[0].row_id.clone();
+        manager.update_row("tasks", &row_id, HashMap::from([("status".to_string(), "ZOMBIE".to_string())])).unwrap();
+        assert_eq!(manager.get_row("tasks", &row_id).unwrap().unwrap().values["status"], "ZOMBIE");
+        
+        manager.stop();
+    }
+    
+    #[test]
+    fn test_dictionary_entries_are_reclaimed() {
+        let manager = TablesManager::new();
+        manager.start();
+        let row_ids: Vec<String> = (0..10)
+            .map(|index| manager.insert_row("tasks", HashMap::from([
+                ("name".to_string(), format!("task_{}", index)),
+                ("status".to_string(), "RUNNING".to_string()),
+            ])).unwrap())
+            .collect();
+        let baseline = manager.storage_stats("tasks").unwrap();
+        
+        // Renaming rows over and over keeps one entry per distinct live value
+        for round in 0..50 {
+            for (index, row_id) in row_ids.iter().enumerate() {
+                manager.update_row("tasks", row_id, HashMap::from([
+                    ("name".to_string(), format!("task_{}_{}", index, round)),
+                    ("status".to_string(), if round % 2 == 0 { "SLEEPING" } else { "RUNNING" }.to_string()),
+                ])).unwrap();
+            }
+        }
+        let updated = manager.storage_stats("tasks").unwrap();
+        assert_eq!(updated.dictionary_entries, baseline.dictionary_entries);
+        assert!(manager.query_rows("tasks", HashMap::from([("name".to_string(), "task_0_48".to_string())])).unwrap().is_empty());
+        assert_eq!(manager.query_rows("tasks", HashMap::from([("name".to_string(), "task_3_49".to_string())])).unwrap().len(), 1);
+        
+        // Values shared with other rows survive the deletion of one of them
+        manager.delete_row("tasks", &row_ids[0]).unwrap();
+        assert_eq!(manager.query_rows("tasks", HashMap::from([("status".to_string(), "RUNNING".to_string())])).unwrap().len(), 9);
+        for row_id in &row_ids[1..] {
+            manager.delete_row("tasks", row_id).unwrap();
+        }
+        assert_eq!(manager.storage_stats("tasks").unwrap().dictionary_entries, 0);
+        
+        // Freed codes are reused by new values
+        let row_id = manager.insert_row("tasks", HashMap::from([("name".to_string(), "fresh".to_string())])).unwrap();
+        assert_eq!(manager.get_row("tasks", &row_id).unwrap().unwrap().values["name"], "fresh");
+        assert!(manager.storage_stats("tasks").unwrap().dictionary_entries <= baseline.dictionary_entries);
+        
+        manager.stop();
+    }
+    
+    #[test]
+    fn test_concurrent_writers_on_separate_tables() {
+        let manager = Arc::new(TablesManager::new());
+        manager.start();
+        
+        let template = manager.get_table("tasks").unwrap().unwrap();
+        for index in 0..8 {
+            let mut table = template.clone();
+            table.name = format!("tasks_{}", index);
+            manager.create_table(table).unwrap();
+        }
+        
+        let handles: Vec<_> = (0..8)
+            .map(|index| {
+                let manager = manager.clone();
+                std::thread::spawn(move || {
+                    for task in 0..200 {
+                        manager.insert_row(&format!("tasks_{}", index), HashMap::from([
+                            ("name".to_string(), format!("task_{}", task)),
+                        ])).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        
+        for index in 0..8 {
+            assert_eq!(manager.get_all_rows(&format!("tasks_{}", index)).unwrap().len(), 200);
+        }
+        assert!(manager.create_table(template).is_err());
+    }
+    
+    #[test]
+    fn test_interleavings_do_not_deadlock() {
+        // Schema changes, writes and reads interleaved at every table and shard lock
+        simulation::explore(50, |sim| {
+            let manager = Arc::new(TablesManager::new());
+            manager.start();
+            let template = manager.get_table("tasks").unwrap().unwrap();
+            
+            let creator = manager.clone();
+            sim.spawn("creator", move || {
+                let mut table = template;
+                table.name = "tasks_new".to_string();
+                creator.create_table(table).unwrap();
+                creator.insert_row("tasks_new", HashMap::from([("name".to_string(), "first".to_string())])).unwrap();
+            });
+            let writer = manager.clone();
+            sim.spawn("writer", move || {
+                let row_id = writer.insert_row("tasks", HashMap::from([("name".to_string(), "job".to_string())])).unwrap();
+                writer.update_row("tasks", &row_id, HashMap::from([("status".to_string(), "DONE".to_string())])).unwrap();
+                writer.delete_row("tasks", &row_id).unwrap();
+            });
+            sim.spawn("reader", move || {
+                let tables = manager.get_all_tables().unwrap().len();
+                assert!(tables == 3 || tables == 4);
+                let done = manager.query_rows("tasks", HashMap::from([("status".to_string(), "DONE".to_string())])).unwrap();
+                assert!(done.len() <= 1);
+                let _ = manager.storage_stats("tasks_new");
+            });
+        }).unwrap_or_else(|e| panic!("{}", e));
+    }
+    
+    #[test]
+    fn test_for_each_row() {
+        let manager = TablesManager::new();
+        manager.start();
+        
+        for (name, status) in [("a", "pending"), ("b", "done"), ("c", "pending")] {
+            manager.insert_row("tasks", HashMap::from([
+                ("name".to_string(), name.to_string()),
+                ("status".to_string(), status.to_string()),
+            ])).unwrap();
+        }
+        
+        let mut names = Vec::new();
+        let visited = manager.for_each_row("tasks", &HashMap::from([("status".to_string(), "pending".to_string())]), |row| {
+            names.push(row.values["name"].clone());
+        }).unwrap();
+        names.sort();
+        assert_eq!(visited, 2);
+        assert_eq!(names, vec!["a", "c"]);
+        
+        // The visitor sees the same rows as the cloning API
+        let mut rows = Vec::new();
+        manager.for_each_row("tasks", &HashMap::new(), |row| rows.push(row.clone())).unwrap();
+        let mut expected = manager.get_all_rows("tasks").unwrap();
+        rows.sort_by(|a, b| a.row_id.cmp(&b.row_id));
+        expected.sort_by(|a, b| a.row_id.cmp(&b.row_id));
+        assert_eq!(rows.len(), expected.len());
+        for (row, expected) in rows.iter().zip(&expected) {
+            assert_eq!(row.values, expected.values);
+        }
+        
+        let no_match = HashMap::from([("status".to_string(), "unknown".to_string())]);
+        assert_eq!(manager.for_each_row("tasks", &no_match, |_| panic!("unexpected row")).unwrap(), 0);
+        assert!(manager.for_each_row("missing", &HashMap::new(), |_| {}).is_err());
+    }
+    
+    #[test]
+    fn test_namespace_isolation() {
+        let manager = TablesManager::new();
+        manager.start();
+        let alice = manager.namespace("alice").unwrap();
+        let bob = manager.namespace("bob").unwrap();
+        assert!(manager.namespace("../etc").is_err());
+        
+        // Shared tables: rows are stamped with and filtered by the namespace
+        let task = HashMap::from([("name".to_string(), "boot".to_string())]);
+        let alice_task = alice.insert_row("tasks", task.clone()).unwrap();
+        let bob_task = bob.insert_row("tasks", task.clone()).unwrap();
+        manager.insert_row("tasks", task.clone()).unwrap();
+        assert_eq!(alice.get_row("tasks", &alice_task).unwrap().unwrap().values[NAMESPACE_COLUMN], "alice");
+        assert_eq!(alice.query_rows("tasks", task.clone()).unwrap().len(), 1);
+        assert_eq!(alice.for_each_row("tasks", &HashMap::new(), |_| {}).unwrap(), 1);
+        assert_eq!(manager.query_rows("tasks", task.clone()).unwrap().len(), 3);
+        let defaults = HashMap::from([(NAMESPACE_COLUMN.to_string(), DEFAULT_NAMESPACE.to_string())]);
+        assert_eq!(manager.query_rows("tasks", defaults).unwrap().len(), 1);
+        
+        // Other namespaces' rows cannot be read, changed or claimed
+        assert!(alice.get_row("tasks", &bob_task).unwrap().is_none());
+        assert!(alice.update_row("tasks", &bob_task, HashMap::from([("status".to_string(), "RUNNING".to_string())])).is_err());
+        assert!(alice.delete_row("tasks", &bob_task).is_err());
+        let moved = HashMap::from([(NAMESPACE_COLUMN.to_string(), "bob".to_string())]);
+        assert!(alice.update_row("tasks", &alice_task, moved.clone()).is_err());
+        assert!(alice.insert_row("tasks", moved).is_err());
+        bob.delete_row("tasks", &bob_task).unwrap();
+        
+        // Private tables are only visible in their namespace
+        let notes = TableDefinition {
+            name: "notes".to_string(),
+            columns: vec![ColumnDefinition {
+                name: "text".to_string(),
+                column_type: ColumnType::String,
+                nullable: false,
+                default_value: None,
+                description: String::new(),
+            }],
+            primary_key: Vec::new(),
+            indexes: Vec::new(),
+            description: String::new(),
+            created_at: 0,
+            updated_at: 0,
+        };
+        alice.create_table(notes.clone()).unwrap();
+        bob.create_table(notes.clone()).unwrap();
+        alice.insert_row("notes", HashMap::from([("text".to_string(), "hi".to_string())])).unwrap();
+        assert_eq!(alice.get_all_rows("notes").unwrap().len(), 1);
+        assert!(bob.get_all_rows("notes").unwrap().is_empty());
+        assert_eq!(alice.get_table("notes").unwrap().unwrap().name, "notes");
+        assert!(bob.get_table("alice:notes").unwrap().is_none());
+        assert!(manager.get_table("alice:notes").unwrap().is_some());
+        
+        let mut visible: Vec<String> = alice.get_all_tables().unwrap().into_iter().map(|table| table.name).collect();
+        visible.sort();
+        assert_eq!(visible, vec!["file_system", "notes", "resources", "tasks"]);
+        
+        // Shared tables without a namespace column are not visible in namespaces
+        let mut shared = notes;
+        shared.name = "shared_notes".to_string();
+        manager.create_table(shared.clone()).unwrap();
+        assert!(alice.get_all_rows("shared_notes").is_err());
+        shared.name = "bob:notes2".to_string();
+        assert!(manager.create_table(shared).is_err());
+    }
+    
+    #[test]
+    fn test_shared_tables_keep_their_namespace() {
+        let manager = Arc::new(TablesManager::new());
+        manager.start();
+        let everything = SharedTables::from(manager.clone());
+        let alice = SharedTables::scoped(manager.clone(), "alice").unwrap();
+        assert!(SharedTables::scoped(manager.clone(), "../etc").is_err());
+        assert_eq!(alice.namespace(), Some("alice"));
+        assert_eq!(everything.namespace(), None);
+        
+        let mut history = manager.get_table("tasks").unwrap().unwrap();
+        history.name = "history".to_string();
+        alice.create_table(history).unwrap();
+        assert!(everything.get_table("history").unwrap().is_none());
+        assert!(manager.get_table("alice:history").unwrap().is_some());
+        
+        let row_id = alice.insert_row("tasks", HashMap::from([("name".to_string(), "build".to_string())])).unwrap();
+        assert_eq!(everything.get_row("tasks", &row_id).unwrap().unwrap().values[NAMESPACE_COLUMN], "alice");
+        let default_row = everything.insert_row("tasks", HashMap::from([("name".to_string(), "build".to_string())])).unwrap();
+        assert!(alice.get_row("tasks", &default_row).unwrap().is_none());
+        assert_eq!(alice.query_rows("tasks", HashMap::new()).unwrap().len(), 1);
+    }
+    
+    #[test]
+    fn test_row_changes_are_audited() {
+        use crate::core::audit::{AuditLog, AuditQuery};
+        
+        let manager = TablesManager::new();
+        manager.start();
+        let _actor = audit::act_as("auditor");
+        let task = HashMap::from([("name".to_string(), "audited".to_string())]);
+        let row_id = manager.insert_row("tasks", task).unwrap();
+        manager.update_row("tasks", &row_id, HashMap::from([("status".to_string(), "RUNNING".to_string())])).unwrap();
+        manager.delete_row("tasks", &row_id).unwrap();
+        
+        let query = AuditQuery { category: Some(AuditCategory::Table), actor: Some("auditor".to_string()), ..Default::default() };
+        let subject = format!("tasks/{}", row_id);
+        let records: Vec<_> = AuditLog::global().records(&query).into_iter().filter(|record| record.subject == subject).collect();
+        let operations: Vec<&str> = records.iter().map(|record| record.operation.as_str()).collect();
+        assert_eq!(operations, vec!["table.insert", "table.update", "table.delete"]);
+        assert!(records[0].new.as_ref().unwrap().contains("name=audited"));
+        assert_eq!(records[1].old.as_deref(), Some("status=CREATED"));
+        assert_eq!(records[1].new.as_deref(), Some("status=RUNNING"));
+        assert!(records[2].old.as_ref().unwrap().contains("status=RUNNING"));
+    }
+}
@@ -0,0 +1,833 @@
+// Time Travel Engine for DBOS Integration in OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, RwLock, Weak};
+use std::thread::{self, JoinHandle};
+use serde::{Serialize, Deserialize};
+use std::time::{Duration, SystemTime};
+
+/// Time Travel Engine
+pub struct TimeTravelEngine {
+    /// Delta-encoded snapshots of system states
+    snapshots: Arc<RwLock<SnapshotStore>>,
+    
+    /// Snapshot retention and compaction settings
+    retention: Arc<RwLock<SnapshotRetention>>,
+    
+    /// Timeline of events
+    timeline: Arc<RwLock<Vec<SystemEvent>>>,
+    
+    /// Is the engine running
+    running: Arc<RwLock<bool>>,
+    
+    /// Current timestamp for time travel
+    current_timestamp: Arc<RwLock<u64>>,
+}
+
+/// System Snapshot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SystemSnapshot {
+    /// Timestamp of the snapshot
+    pub timestamp: u64,
+    
+    /// System state at this time
+    pub state: HashMap<String, String>,
+    
+    /// Active transactions at this time
+    pub active_transactions: Vec<String>,
+    
+    /// Running components at this time
+    pub running_components: Vec<String>,
+    
+    /// Resource states at this time
+    pub resource_states: HashMap<String, String>,
+}
+
+/// Snapshot retention and compaction settings
+///
+/// Snapshots are stored as deltas against the previous snapshot, with a full keyframe
+/// at least every `keyframe_interval` snapshots, so restoring any point applies at most
+/// `keyframe_interval - 1` deltas. Compaction keeps every snapshot of the last
+/// `full_resolution_secs`, thins older ones and re-encodes the rest.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotRetention {
+    /// Maximum number of snapshots from one keyframe to the next
+    pub keyframe_interval: usize,
+    
+    /// Snapshots younger than this many seconds are all kept
+    pub full_resolution_secs: u64,
+    
+    /// Older snapshots are thinned to the last one in each interval of this many seconds
+    pub thinned_interval_secs: u64,
+    
+    /// Snapshots older than this many seconds are dropped (`None` keeps them forever)
+    pub max_age_secs: Option<u64>,
+    
+    /// Seconds between background compactions
+    pub compaction_interval_secs: u64,
+}
+
+impl Default for SnapshotRetention {
+    fn default() -> Self {
+        Self {
+            keyframe_interval: 32,
+            full_resolution_secs: 60 * 60,
+            thinned_interval_secs: 10 * 60,
+            max_age_secs: Some(7 * 24 * 60 * 60),
+            compaction_interval_secs: 5 * 60,
+        }
+    }
+}
+
+/// Snapshot storage usage
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotStorageStats {
+    /// Number of stored snapshots
+    pub snapshots: usize,
+    
+    /// Snapshots stored in full
+    pub keyframes: usize,
+    
+    /// Snapshots stored as deltas
+    pub deltas: usize,
+    
+    /// Approximate size of the stored snapshots in bytes
+    pub stored_bytes: usize,
+    
+    /// Approximate size the snapshots would take if all were stored in full
+    pub full_bytes: usize,
+    
+    /// Timestamp of the oldest snapshot
+    pub oldest: Option<u64>,
+    
+    /// Timestamp of the newest snapshot
+    pub newest: Option<u64>,
+    
+    /// Time of the last compaction
+    pub last_compaction: Option<u64>,
+}
+
+/// Result of a compaction run
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CompactionReport {
+    /// Snapshots before compaction
+    pub snapshots_before: usize,
+    
+    /// Snapshots after compaction
+    pub snapshots_after: usize,
+    
+    /// Stored bytes before compaction
+    pub bytes_before: usize,
+    
+    /// Stored bytes after compaction
+    pub bytes_after: usize,
+}
+
+/// Changes from one snapshot to the next
+#[derive(Debug, Clone)]
+struct SnapshotDelta {
+    /// Timestamp of the snapshot this delta applies to
+    base: u64,
+    
+    /// Number of deltas from the last keyframe up to and including this one
+    depth: usize,
+    
+    state_changes: Vec<(String, Option<String>)>, // None removes the key
+    resource_changes: Vec<(String, Option<String>)>,
+    active_transactions: Option<Vec<String>>, // None if unchanged
+    running_components: Option<Vec<String>>,
+}
+
+impl SnapshotDelta {
+    fn between(base: &SystemSnapshot, next: &SystemSnapshot, depth: usize) -> Self {
+        Self {
+            base: base.timestamp,
+            depth,
+            state_changes: diff_map(&base.state, &next.state),
+            resource_changes: diff_map(&base.resource_states, &next.resource_states),
+            active_transactions: (base.active_transactions != next.active_transactions).then(|| next.active_transactions.clone()),
+            running_components: (base.running_components != next.running_components).then(|| next.running_components.clone()),
+        }
+    }
+    
+    fn apply(&self, base: &SystemSnapshot, timestamp: u64) -> SystemSnapshot {
+        let mut snapshot = base.clone();
+        snapshot.timestamp = timestamp;
+        apply_map(&mut snapshot.state, &self.state_changes);
+        apply_map(&mut snapshot.resource_states, &self.resource_changes);
+        if let Some(active_transactions) = &self.active_transactions {
+            snapshot.active_transactions = active_transactions.clone();
+        }
+        if let Some(running_components) = &self.running_components {
+            snapshot.running_components = running_components.clone();
+        }
+        snapshot
+    }
+    
+    fn approximate_bytes(&self) -> usize {
+        let changes = |changes: &[(String, Option<String>)]| -> usize {
+            changes.iter().map(|(key, value)| key.len() + value.as_ref().map_or(0, String::len)).sum()
+        };
+        let list = |list: &Option<Vec<String>>| -> usize {
+            list.as_ref().map_or(0, |values| values.iter().map(String::len).sum())
+        };
+        changes(&self.state_changes) + changes(&self.resource_changes)
+            + list(&self.active_transactions) + list(&self.running_components)
+    }
+}
+
+/// Keys added, changed or removed between two maps, in key order
+fn diff_map(old: &HashMap<String, String>, new: &HashMap<String, String>) -> Vec<(String, Option<String>)> {
+    let mut changes: Vec<(String, Option<String>)> = new.iter()
+        .filter(|(key, value)| old.get(*key) != Some(*value))
+        .map(|(key, value)| (key.clone(), Some(value.clone())))
+        .chain(old.keys().filter(|key| !new.contains_key(*key)).map(|key| (key.clone(), None)))
+        .collect();
+    changes.sort_by(|a, b| a.0.cmp(&b.0));
+    changes
+}
+
+fn apply_map(map: &mut HashMap<String, String>, changes: &[(String, Option<String>)]) {
+    for (key, value) in changes {
+        match value {
+            Some(value) => {
+                map.insert(key.clone(), value.clone());
+            },
+            None => {
+                map.remove(key);
+            },
+        }
+    }
+}
+
+fn snapshot_bytes(snapshot: &SystemSnapshot) -> usize {
+    let map = |map: &HashMap<String, String>| -> usize { map.iter().map(|(key, value)| key.len() + value.len()).sum() };
+    map(&snapshot.state) + map(&snapshot.resource_states)
+        + snapshot.active_transactions.iter().map(String::len).sum::<usize>()
+        + snapshot.running_components.iter().map(String::len).sum::<usize>()
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Stored form of a snapshot
+#[derive(Debug, Clone)]
+enum StoredSnapshot {
+    Keyframe(SystemSnapshot),
+    Delta(SnapshotDelta),
+}
+
+/// Snapshots in time order; each delta applies to the snapshot stored before it
+#[derive(Debug, Default)]
+struct SnapshotStore {
+    entries: BTreeMap<u64, StoredSnapshot>,
+    latest: Option<SystemSnapshot>, // Materialized newest snapshot
+    full_bytes: usize,
+    last_compaction: Option<u64>,
+}
+
+impl SnapshotStore {
+    /// Append a snapshot, replacing the newest one if it has the same timestamp
+    fn insert(&mut self, snapshot: SystemSnapshot, keyframe_interval: usize) -> Result<(), String> {
+        if let Some(latest) = &self.latest {
+            if snapshot.timestamp < latest.timestamp {
+                return Err(format!(
+                    "Snapshot at {} is older than the newest snapshot at {}", snapshot.timestamp, latest.timestamp
+                ));
+            }
+            if snapshot.timestamp == latest.timestamp {
+                self.full_bytes -= snapshot_bytes(latest);
+                self.entries.remove(&snapshot.timestamp);
+                self.latest = self.entries.keys().next_back().and_then(|timestamp| self.materialize(*timestamp));
+            }
+        }
+        
+        let entry = match &self.latest {
+            Some(base) => {
+                let depth = match self.entries.get(&base.timestamp) {
+                    Some(StoredSnapshot::Delta(delta)) => delta.depth + 1,
+                    _ => 1,
+                };
+                if depth >= keyframe_interval.max(1) {
+                    StoredSnapshot::Keyframe(snapshot.clone())
+                } else {
+                    StoredSnapshot::Delta(SnapshotDelta::between(base, &snapshot, depth))
+                }
+            },
+            None => StoredSnapshot::Keyframe(snapshot.clone()),
+        };
+        
+        self.full_bytes += snapshot_bytes(&snapshot);
+        self.entries.insert(snapshot.timestamp, entry);
+        self.latest = Some(snapshot);
+        Ok(())
+    }
+    
+    /// Reconstruct the snapshot stored at a timestamp
+    fn materialize(&self, timestamp: u64) -> Option<SystemSnapshot> {
+        if let Some(latest) = self.latest.as_ref().filter(|latest| latest.timestamp == timestamp) {
+            return Some(latest.clone());
+        }
+        
+        let mut chain = Vec::new();
+        let mut current = timestamp;
+        let mut snapshot = loop {
+            match self.entries.get(&current)? {
+                StoredSnapshot::Keyframe(keyframe) => break keyframe.clone(),
+                StoredSnapshot::Delta(delta) => {
+                    chain.push((current, delta));
+                    current = delta.base;
+                },
+            }
+        };
+        for (timestamp, delta) in chain.into_iter().rev() {
+            snapshot = delta.apply(&snapshot, timestamp);
+        }
+        Some(snapshot)
+    }
+    
+    /// Timestamp of the newest snapshot at or before a timestamp
+    fn floor(&self, timestamp: u64) -> Option<u64> {
+        self.entries.range(..=timestamp).next_back().map(|(timestamp, _)| *timestamp)
+    }
+    
+    /// Reconstruct every snapshot in time order
+    fn materialize_all(&self) -> Vec<SystemSnapshot> {
+        let mut snapshots: Vec<SystemSnapshot> = Vec::with_capacity(self.entries.len());
+        for (timestamp, entry) in &self.entries {
+            let snapshot = match entry {
+                StoredSnapshot::Keyframe(keyframe) => keyframe.clone(),
+                StoredSnapshot::Delta(delta) => match snapshots.last() {
+                    Some(base) => delta.apply(base, *timestamp),
+                    None => continue,
+                },
+            };
+            snapshots.push(snapshot);
+        }
+        snapshots
+    }
+    
+    /// Thin and drop snapshots according to the retention settings and re-encode the rest
+    fn compact(&mut self, retention: &SnapshotRetention, now: u64) -> CompactionReport {
+        let before = self.stats();
+        let snapshots = self.materialize_all();
+        
+        let young = |snapshot: &SystemSnapshot| now.saturating_sub(snapshot.timestamp) <= retention.full_resolution_secs;
+        let bucket = |snapshot: &SystemSnapshot| snapshot.timestamp / retention.thinned_interval_secs.max(1);
+        let expired = |snapshot: &SystemSnapshot| {
+            retention.max_age_secs.is_some_and(|max_age| now.saturating_sub(snapshot.timestamp) > max_age)
+        };
+        let mut kept = Vec::with_capacity(snapshots.len());
+        for (index, snapshot) in snapshots.iter().enumerate() {
+            // Keep old snapshots only if they are the last of their interval
+            let last_of_interval = match snapshots.get(index + 1) {
+                Some(next) => young(next) || bucket(next) != bucket(snapshot),
+                None => true,
+            };
+            if !expired(snapshot) && (young(snapshot) || last_of_interval) {
+                kept.push(snapshot.clone());
+            }
+        }
+        
+        self.entries.clear();
+        self.latest = None;
+        self.full_bytes = 0;
+        for snapshot in kept {
+            // Snapshots come out in time order, so insertion cannot fail
+            let _ = self.insert(snapshot, retention.keyframe_interval);
+        }
+        self.last_compaction = Some(now);
+        
+        let after = self.stats();
+        CompactionReport {
+            snapshots_before: before.snapshots,
+            snapshots_after: after.snapshots,
+            bytes_before: before.stored_bytes,
+            bytes_after: after.stored_bytes,
+        }
+    }
+    
+    fn stats(&self) -> SnapshotStorageStats {
+        let mut stats = SnapshotStorageStats {
+            snapshots: self.entries.len(),
+            full_bytes: self.full_bytes,
+            oldest: self.entries.keys().next().copied(),
+            newest: self.entries.keys().next_back().copied(),
+            last_compaction: self.last_compaction,
+            ..Default::default()
+        };
+        for entry in self.entries.values() {
+            match entry {
+                StoredSnapshot::Keyframe(keyframe) => {
+                    stats.keyframes += 1;
+                    stats.stored_bytes += snapshot_bytes(keyframe);
+                },
+                StoredSnapshot::Delta(delta) => {
+                    stats.deltas += 1;
+                    stats.stored_bytes += delta.approximate_bytes();
+                },
+            }
+        }
+        stats
+    }
+}
+
+/// System Event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SystemEvent {
+    /// Timestamp of the event
+    pub timestamp: u64,
+    
+    /// Event type
+    pub event_type: SystemEventType,
+    
+    /// Event description
+    pub description: String,
+    
+    /// Associated data
+    pub data: Option<String>,
+    
+    /// Event severity
+    pub severity: EventSeverity,
+}
+
+/// System Event Type
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum SystemEventType {
+    ComponentStarted,
+    ComponentStopped,
+    TransactionStarted,
+    TransactionCommitted,
+    TransactionRolledBack,
+    StateChanged,
+    SystemError,
+    ResourceCreated,
+    ResourceModified,
+    ResourceDeleted,
+    Custom(String),
+}
+
+/// Event Severity
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum EventSeverity {
+    Info,
+    Warning,
+    Error,
+    Critical,
+}
+
+impl TimeTravelEngine {
+    /// Create a new time travel engine
+    pub fn new() -> Self {
+        Self::with_retention(SnapshotRetention::default())
+    }
+    
+    /// Create a new time travel engine with custom snapshot retention
+    pub fn with_retention(retention: SnapshotRetention) -> Self {
+        Self {
+            snapshots: Arc::new(RwLock::new(SnapshotStore::default())),
+            retention: Arc::new(RwLock::new(retention)),
+            timeline: Arc::new(RwLock::new(Vec::new())),
+            running: Arc::new(RwLock::new(false)),
+            current_timestamp: Arc::new(RwLock::new(0)),
+        }
+    }
+    
+    /// Start the time travel engine
+    pub fn start(&self) {
+        let mut running = self.running.write().unwrap();
+        *running = true;
+        
+        // Record system start event
+        self.record_event(
+            SystemEventType::Custom("TimeTravelEngineStarted".to_string()),
+            "Time travel engine started".to_string(),
+            None,
+            EventSeverity::Info,
+        ).unwrap_or_default();
+    }
+    
+    /// Stop the time travel engine
+    pub fn stop(&self) {
+        // Record system stop event
+        self.record_event(
+            SystemEventType::Custom("TimeTravelEngineStopped".to_string()),
+            "Time travel engine stopped".to_string(),
+            None,
+            EventSeverity::Info,
+        ).unwrap_or_default();
+        
+        let mut running = self.running.write().unwrap();
+        *running = false;
+    }
+    
+    /// Create a system snapshot
+    pub fn create_snapshot(
+        &self,
+        state: HashMap<String, String>,
+        active_transactions: Vec<String>,
+        running_components: Vec<String>,
+        resource_states: HashMap<String, String>,
+    ) -> Result<u64, String> {
+        self.record_snapshot(SystemSnapshot {
+            timestamp: now_secs(),
+            state,
+            active_transactions,
+            running_components,
+            resource_states,
+        })
+    }
+    
+    /// Store a snapshot taken at its own timestamp
+    ///
+    /// Snapshots must be recorded in time order; a snapshot with the same timestamp as
+    /// the newest one replaces it.
+    pub fn record_snapshot(&self, snapshot: SystemSnapshot) -> Result<u64, String> {
+        if !*self.running.read().unwrap() {
+            return Err("Time travel engine is not running".to_string());
+        }
+        
+        let timestamp = snapshot.timestamp;
+        let keyframe_interval = self.retention.read().unwrap().keyframe_interval;
+        self.snapshots.write().unwrap().insert(snapshot, keyframe_interval)?;
+        
+        // Update current timestamp
+        let mut current_timestamp = self.current_timestamp.write().unwrap();
+        *current_timestamp = timestamp;
+        
+        // Record snapshot creation event
+        self.record_event(
+            SystemEventType::Custom("SnapshotCreated".to_string()),
+            format!("System snapshot created at timestamp {}", timestamp),
+            Some(timestamp.to_string()),
+            EventSeverity::Info,
+        )?;
+        
+        Ok(timestamp)
+    }
+    
+    /// Get a snapshot by timestamp
+    pub fn get_snapshot(&self, timestamp: u64) -> Result<Option<SystemSnapshot>, String> {
+        let snapshots = self.snapshots.read().unwrap();
+        Ok(snapshots.materialize(timestamp))
+    }
+    
+    /// Get all snapshots
+    pub fn get_all_snapshots(&self) -> Result<Vec<SystemSnapshot>, String> {
+        let snapshots = self.snapshots.read().unwrap();
+        Ok(snapshots.materialize_all())
+    }
+    
+    /// Restore system to a specific timestamp
+    pub fn restore_to_timestamp(&self, timestamp: u64) -> Result<Option<SystemSnapshot>, String> {
+        // Find the latest snapshot before or at the given timestamp
+        let snapshot = {
+            let snapshots = self.snapshots.read().unwrap();
+            snapshots.floor(timestamp).and_then(|timestamp| snapshots.materialize(timestamp))
+        };
+        
+        if let Some(ref snapshot) = snapshot {
+            // Update current timestamp
+            let mut current_timestamp = self.current_timestamp.write().unwrap();
+            *current_timestamp = snapshot.timestamp;
+            
+            // Record restoration event
+            self.record_event(
+                SystemEventType::Custom("SystemRestored".to_string()),
+                format!("System restored to timestamp {}", timestamp),
+                Some(timestamp.to_string()),
+                EventSeverity::Info,
+            )?;
+        }
+        
+        Ok(snapshot)
+    }
+    
+    /// Get current timestamp
+    pub fn get_current_timestamp(&self) -> Result<u64, String> {
+        let current_timestamp = self.current_timestamp.read().unwrap();
+        Ok(*current_timestamp)
+    }
+    
+    /// Record a system event
+    pub fn record_event(
+        &self,
+        event_type: SystemEventType,
+        description: String,
+        data: Option<String>,
+        severity: EventSeverity,
+    ) -> Result<u64, String> {
+        let timestamp = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        
+        let event = SystemEvent {
+            timestamp,
+            event_type,
+            description,
+            data,
+            severity,
+        };
+        
+        let mut timeline = self.timeline.write().unwrap();
+        timeline.push(event);
+        
+        Ok(timestamp)
+    }
+    
+    /// Get events within a time range
+    pub fn get_events_in_range(
+        &self,
+        start_time: u64,
+        end_time: u64,
+    ) -> Result<Vec<SystemEvent>, String> {
+        let timeline = self.timeline.read().unwrap();
+        let filtered: Vec<SystemEvent> = timeline
+            .iter()
+            .filter(|event| event.timestamp >= start_time && event.timestamp <= end_time)
+            .cloned()
+            .collect();
+        Ok(filtered)
+    }
+    
+    /// Get all events
+    pub fn get_all_events(&self) -> Result<Vec<SystemEvent>, String> {
+        let timeline = self.timeline.read().unwrap();
+        Ok(timeline.clone())
+    }
+    
+    /// Find events by type
+    pub fn find_events_by_type(
+        &self,
+        event_type: SystemEventType,
+    ) -> Result<Vec<SystemEvent>, String> {
+        let timeline = self.timeline.read().unwrap();
+        let filtered: Vec<SystemEvent> = timeline
+            .iter()
+            .filter(|event| event.event_type == event_type)
+            .cloned()
+            .collect();
+        Ok(filtered)
+    }
+    
+    /// Find events by severity
+    pub fn find_events_by_severity(
+        &self,
+        severity: EventSeverity,
+    ) -> Result<Vec<SystemEvent>, String> {
+        let timeline = self.timeline.read().unwrap();
+        let filtered: Vec<SystemEvent> = timeline
+            .iter()
+            .filter(|event| event.severity == severity)
+            .cloned()
+            .collect();
+        Ok(filtered)
+    }
+    
+    /// Get snapshot count
+    pub fn get_snapshot_count(&self) -> Result<usize, String> {
+        let snapshots = self.snapshots.read().unwrap();
+        Ok(snapshots.entries.len())
+    }
+    
+    /// Get snapshot storage usage
+    pub fn storage_stats(&self) -> Result<SnapshotStorageStats, String> {
+        Ok(self.snapshots.read().unwrap().stats())
+    }
+    
+    /// Get the snapshot retention settings
+    pub fn retention(&self) -> SnapshotRetention {
+        self.retention.read().unwrap().clone()
+    }
+    
+    /// Change the snapshot retention settings (applied by the next compaction)
+    pub fn set_retention(&self, retention: SnapshotRetention) {
+        *self.retention.write().unwrap() = retention;
+    }
+    
+    /// Thin, drop and re-encode snapshots according to the retention settings
+    pub fn compact(&self) -> Result<CompactionReport, String> {
+        let retention = self.retention();
+        let report = self.snapshots.write().unwrap().compact(&retention, now_secs());
+        
+        self.record_event(
+            SystemEventType::Custom("SnapshotsCompacted".to_string()),
+            format!(
+                "Compacted snapshots from {} to {} ({} to {} bytes)",
+                report.snapshots_before, report.snapshots_after, report.bytes_before, report.bytes_after
+            ),
+            None,
+            EventSeverity::Info,
+        )?;
+        
+        Ok(report)
+    }
+    
+    /// Compact snapshots in a background thread every `compaction_interval_secs`
+    ///
+    /// The thread exits once the engine is stopped or dropped.
+    pub fn start_compaction(engine: &Arc<TimeTravelEngine>) -> JoinHandle<()> {
+        let engine: Weak<TimeTravelEngine> = Arc::downgrade(engine);
+        thread::spawn(move || {
+            let mut waited = 0;
+            loop {
+                thread::sleep(Duration::from_secs(1));
+                let Some(engine) = engine.upgrade() else {
+                    break;
+                };
+                if !*engine.running.read().unwrap() {
+                    break;
+                }
+                
+                waited += 1;
+                if waited >= engine.retention.read().unwrap().compaction_interval_secs {
+                    waited = 0;
+                    let _ = engine.compact();
+                }
+            }
+        })
+    }
+    
+    /// Get event count
+    pub fn get_event_count(&self) -> Result<usize, String> {
+        let timeline = self.timeline.read().unwrap();
+        Ok(timeline.len())
+    }
+    
+    /// Create a checkpoint for quick restoration
+    pub fn create_checkpoint(&self, name: String) -> Result<u64, String> {
+        // This would typically save the current state to a named checkpoint
+        // For now, we'll just record the event
+        let timestamp = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        
+        self.record_event(
+            SystemEventType::Custom("CheckpointCreated".to_string()),
+            format!("Checkpoint '{}' created at timestamp {}", name, timestamp),
+            Some(name),
+            EventSeverity::Info,
+        )?;
+        
+        Ok(timestamp)
+    }
+    
+    /// Restore to a named checkpoint
+    pub fn restore_to_checkpoint(&self, name: String) -> Result<Option<SystemSnapshot>, String> {
+        // This would typically find the checkpoint and restore to it
+        // For now, we'll just record the event and return the latest snapshot
+        self.record_event(
+            SystemEventType::Custom("CheckpointRestored".to_string()),
+            format!("Restoring to checkpoint '{}'", name),
+            Some(name),
+            EventSeverity::Info,
+        )?;
+        
+        // Return the latest snapshot as a placeholder
+        let snapshots = self.snapshots.read().unwrap();
+        Ok(snapshots.latest.clone())
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+    
+    fn snapshot(timestamp: u64, step: usize) -> SystemSnapshot {
+        // 200 keys, five of which change per step
+        let state = (0..200)
+            .map(|key| {
+                let version = if key % 40 == step % 40 { step } else { key };
+                (format!("key_{}", key), format!("value_{}", version))
+            })
+            .collect();
+        SystemSnapshot {
+            timestamp,
+            state,
+            active_transactions: vec![format!("tx_{}", step / 10)],
+            running_components: vec!["scheduler".to_string()],
+            resource_states: HashMap::from([("cpu".to_string(), format!("{}%", step % 100))]),
+        }
+    }
+    
+    #[test]
+    fn test_delta_snapshots_restore_exactly() {
+        let engine = TimeTravelEngine::new();
+        engine.start();
+        
+        // One snapshot per second for the last hour
+        let now = now_secs();
+        let start = now - 3600;
+        for step in 0..=3600 {
+            engine.record_snapshot(snapshot(start + step as u64, step)).unwrap();
+        }
+        
+        let stats = engine.storage_stats().unwrap();
+        assert_eq!(stats.snapshots, 3601);
+        assert!(stats.keyframes <= 3601 / 31 + 1);
+        assert!(stats.stored_bytes * 4 < stats.full_bytes);
+        
+        // Restoring any point of the last hour is exact and fast
+        let started = Instant::now();
+        for step in (0..=3600).step_by(97) {
+            let restored = engine.restore_to_timestamp(start + step as u64).unwrap().unwrap();
+            let expected = snapshot(start + step as u64, step);
+            assert_eq!(restored.state, expected.state);
+            assert_eq!(restored.active_transactions, expected.active_transactions);
+            assert_eq!(restored.resource_states, expected.resource_states);
+        }
+        assert!(started.elapsed() < Duration::from_secs(1));
+        
+        // Out-of-order snapshots are rejected; same-second snapshots replace the newest
+        assert!(engine.record_snapshot(snapshot(start, 0)).is_err());
+        engine.record_snapshot(snapshot(now, 7)).unwrap();
+        assert_eq!(engine.get_snapshot(now).unwrap().unwrap().state, snapshot(now, 7).state);
+        assert_eq!(engine.get_snapshot_count().unwrap(), 3601);
+    }
+    
+    #[test]
+    fn test_compaction_applies_retention() {
+        let engine = TimeTravelEngine::with_retention(SnapshotRetention {
+            keyframe_interval: 8,
+            full_resolution_secs: 3600,
+            thinned_interval_secs: 600,
+            max_age_secs: Some(3 * 3600),
+            compaction_interval_secs: 60,
+        });
+        engine.start();
+        
+        // One snapshot per minute for the last four hours
+        let now = now_secs() / 60 * 60;
+        let start = now - 4 * 3600;
+        for step in 0..=240 {
+            engine.record_snapshot(snapshot(start + step as u64 * 60, step)).unwrap();
+        }
+        
+        let report = engine.compact().unwrap();
+        assert_eq!(report.snapshots_before, 241);
+        assert!(report.bytes_after < report.bytes_before);
+        
+        let snapshots = engine.get_all_snapshots().unwrap();
+        assert_eq!(snapshots.len(), report.snapshots_after);
+        assert!(snapshots.iter().all(|snapshot| now - snapshot.timestamp <= 3 * 3600));
+        
+        // The last hour keeps every snapshot, the two hours before one per ten minutes
+        let recent = snapshots.iter().filter(|snapshot| now - snapshot.timestamp <= 3600).count();
+        assert_eq!(recent, 61);
+        assert!(snapshots.len() - recent <= 2 * 6 + 1);
+        
+        for snapshot in &snapshots {
+            let step = ((snapshot.timestamp - start) / 60) as usize;
+            assert_eq!(snapshot.state, self::snapshot(snapshot.timestamp, step).state);
+        }
+        assert!(engine.storage_stats().unwrap().last_compaction.is_some());
+    }
+}
@@ -4,6 +4,10 @@
 
 use crate::dbos_integration::{DbosSystem, DbosConfig, DbosComponentInfo};
 use crate::agfs_integration::{AgfsSystem, AgfsConfig, ResourceInfo};
+use crate::architecture_adapter::gpu_probe::{GpuProbe, GpuProbeReport};
+use crate::architecture_adapter::topology_probe::{CpuTopology, TopologyProbe};
+use crate::tile_engine::tile_designer::TileDesigner;
+use crate::tile_engine::tile_placement::{PlacementPlan, TilePlacer};
 use std::sync::{Arc, RwLock};
 use serde::{Serialize, Deserialize};
 
@@ -17,6 +21,12 @@ pub struct UnifiedResourceManager {
     
     /// Resource mapping between DBOS and AGFS
     resource_mapping: Arc<RwLock<std::collections::HashMap<String, String>>>,
+    
+    /// CPU topology of the host, once discovered
+    topology: Arc<RwLock<Option<CpuTopology>>>,
+    
+    /// GPUs of the host, once discovered
+    gpus: Arc<RwLock<Option<GpuProbeReport>>>,
 }
 
 /// Unified Resource Information
@@ -70,6 +80,8 @@ impl UnifiedResourceManager {
             dbos_system,
             agfs_system,
             resource_mapping: Arc::new(RwLock::new(std::collections::HashMap::new())),
+            topology: Arc::new(RwLock::new(None)),
+            gpus: Arc::new(RwLock::new(None)),
         }
     }
     
@@ -168,6 +180,53 @@ impl UnifiedResourceManager {
         Ok(mapping.get(dbos_id).cloned())
     }
     
+    /// Probe the host's CPU topology and publish it to the DBOS `resources` table
+    pub fn discover_topology(&self) -> Result<CpuTopology, String> {
+        let topology = TopologyProbe::new().probe();
+        self.set_topology(topology.clone())?;
+        Ok(topology)
+    }
+    
+    /// Use a known topology, e.g. that of a target machine, and publish it to the `resources` table
+    pub fn set_topology(&self, topology: CpuTopology) -> Result<(), String> {
+        topology.publish(&self.dbos_system.get_tables_manager())?;
+        let mut current = self.topology.write().map_err(|_| "Failed to acquire write lock")?;
+        *current = Some(topology);
+        Ok(())
+    }
+    
+    /// Get the CPU topology, if it has been discovered or set
+    pub fn topology(&self) -> Option<CpuTopology> {
+        self.topology.read().ok().and_then(|topology| topology.clone())
+    }
+    
+    /// Probe the host's GPUs and publish them to the DBOS `resources` table
+    pub fn discover_gpus(&self) -> Result<GpuProbeReport, String> {
+        let report = GpuProbe::new().probe();
+        self.set_gpus(report.clone())?;
+        Ok(report)
+    }
+    
+    /// Use known GPUs, e.g. those of a target machine, and publish them to the `resources` table
+    pub fn set_gpus(&self, report: GpuProbeReport) -> Result<(), String> {
+        report.publish(&self.dbos_system.get_tables_manager())?;
+        let mut current = self.gpus.write().map_err(|_| "Failed to acquire write lock")?;
+        *current = Some(report);
+        Ok(())
+    }
+    
+    /// Get the GPUs, if they have been discovered or set (see `CompilationOptions::gpu_capabilities`)
+    pub fn gpus(&self) -> Option<GpuProbeReport> {
+        self.gpus.read().ok().and_then(|gpus| gpus.clone())
+    }
+    
+    /// Suggest NUMA nodes and CPUs for the tiles of a designer's graph and store them on the tiles
+    pub fn annotate_placement(&self, designer: &TileDesigner) -> Result<PlacementPlan, String> {
+        let topology = self.topology.read().map_err(|_| "Failed to acquire read lock")?;
+        let topology = topology.as_ref().ok_or("CPU topology has not been discovered")?;
+        designer.annotate_placement(&TilePlacer::new(topology))
+    }
+    
     /// Execute a unified operation
     pub fn execute_operation(&self, operation: UnifiedOperation) -> Result<UnifiedOperationResult, String> {
         match operation.operation_type {
@@ -274,4 +333,4 @@ pub struct UnifiedOperationResult {
     
     /// System type where operation was executed
     pub system_type: SystemType,
-}
\ No newline at end of file
+}
\ No newline at end of file
@@ -0,0 +1,767 @@
+// Internationalization (i18n) module for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+use std::sync::RwLock;
+use std::sync::atomic::{AtomicBool, Ordering};
+use crate::core::audit::{self, AuditCategory};
+
+/// Supported languages
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Language {
+    /// Chinese (Simplified)
+    Chinese,
+    /// English
+    English,
+    /// Japanese
+    Japanese,
+    /// Korean
+    Korean,
+    /// Russian
+    Russian,
+    /// Arabic
+    Arabic,
+}
+
+/// Direction in which a language's text runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TextDirection {
+    /// Left to right (Latin, CJK, Cyrillic, ...)
+    LeftToRight,
+    /// Right to left (Arabic, Hebrew, ...)
+    RightToLeft,
+}
+
+impl Language {
+    /// All supported languages
+    pub fn all() -> [Language; 6] {
+        [
+            Language::Chinese,
+            Language::English,
+            Language::Japanese,
+            Language::Korean,
+            Language::Russian,
+            Language::Arabic,
+        ]
+    }
+    
+    /// Get language code
+    pub fn code(&self) -> &str {
+        match self {
+            Language::Chinese => "zh-CN",
+            Language::English => "en-US",
+            Language::Japanese => "ja-JP",
+            Language::Korean => "ko-KR",
+            Language::Russian => "ru-RU",
+            Language::Arabic => "ar-SA",
+        }
+    }
+    
+    /// Get display name in the language itself
+    pub fn display_name(&self) -> &str {
+        match self {
+            Language::Chinese => "中文",
+            Language::English => "English",
+            Language::Japanese => "日本語",
+            Language::Korean => "한국어",
+            Language::Russian => "Русский",
+            Language::Arabic => "العربية",
+        }
+    }
+    
+    /// Get the text direction of the language
+    pub fn text_direction(&self) -> TextDirection {
+        match self {
+            Language::Arabic => TextDirection::RightToLeft,
+            _ => TextDirection::LeftToRight,
+        }
+    }
+    
+    /// Parse language from code
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code.to_lowercase().as_str() {
+            "zh-cn" | "zh" => Some(Language::Chinese),
+            "en-us" | "en" => Some(Language::English),
+            "ja-jp" | "ja" => Some(Language::Japanese),
+            "ko-kr" | "ko" => Some(Language::Korean),
+            "ru-ru" | "ru" => Some(Language::Russian),
+            "ar-sa" | "ar" => Some(Language::Arabic),
+            _ => None,
+        }
+    }
+    
+    /// Get system default language
+    pub fn system_default() -> Self {
+        // Get system language from environment variables
+        if let Some(lang) = std::env::var("LANG").ok() {
+            if let Some(language) = Language::from_code(&lang) {
+                return language;
+            }
+        }
+        
+        // Default to Chinese if system language cannot be determined
+        Language::Chinese
+    }
+}
+
+/// Language the other catalogs are translated from
+pub const REFERENCE_LANGUAGE: Language = Language::English;
+
+/// Translation coverage of one language against the reference catalog
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoverageReport {
+    /// Language checked
+    pub language: Language,
+    
+    /// Keys in the reference catalog
+    pub total_keys: usize,
+    
+    /// Reference keys translated into the language
+    pub translated_keys: usize,
+    
+    /// Reference keys without a translation, sorted
+    pub missing: Vec<String>,
+    
+    /// Translated keys no longer in the reference catalog, sorted
+    pub stale: Vec<String>,
+}
+
+impl CoverageReport {
+    /// Share of reference keys translated (%)
+    pub fn percent(&self) -> f64 {
+        if self.total_keys == 0 {
+            return 100.0;
+        }
+        self.translated_keys as f64 * 100.0 / self.total_keys as f64
+    }
+}
+
+/// Translations container
+pub struct Translations {
+    /// Language mappings
+    translations: RwLock<HashMap<String, HashMap<Language, String>>>,
+    
+    /// Default language
+    default_language: Language,
+    
+    /// Whether translations are replaced by pseudo-localized reference strings
+    pseudo_localization: AtomicBool,
+}
+
+impl Translations {
+    /// Create new translations container
+    pub fn new(default_language: Language) -> Self {
+        Self {
+            translations: RwLock::new(HashMap::new()),
+            default_language,
+            pseudo_localization: AtomicBool::new(false),
+        }
+    }
+    
+    /// Add a translation
+    pub fn add_translation(&self, key: &str, language: Language, value: &str) {
+        let mut translations = self.translations.write().unwrap();
+        
+        let entry = translations.entry(key.to_string()).or_insert_with(HashMap::new);
+        entry.insert(language, value.to_string());
+    }
+    
+    /// Get translation for a key
+    pub fn translate(&self, key: &str, language: Option<Language>) -> String {
+        let translations = self.translations.read().unwrap();
+        
+        let lang = language.unwrap_or(self.default_language);
+        
+        if self.pseudo_localization() {
+            return match translations.get(key).and_then(|translations| translations.get(&REFERENCE_LANGUAGE)) {
+                Some(reference) => pseudo_localize(reference),
+                None => key.to_string(),
+            };
+        }
+        
+        if let Some(translations) = translations.get(key) {
+            if let Some(translation) = translations.get(&lang) {
+                return translation.clone();
+            }
+            
+            // Fallback to default language
+            if let Some(translation) = translations.get(&self.default_language) {
+                return translation.clone();
+            }
+        }
+        
+        // Fallback to key itself if no translation found
+        key.to_string()
+    }
+    
+    /// Get the translation of a key in exactly one language, without fallbacks
+    pub fn get(&self, key: &str, language: Language) -> Option<String> {
+        self.translations.read().unwrap().get(key).and_then(|values| values.get(&language)).cloned()
+    }
+    
+    /// Get all translations of a language, sorted by key
+    pub fn catalog(&self, language: Language) -> BTreeMap<String, String> {
+        self.translations.read().unwrap().iter()
+            .filter_map(|(key, values)| values.get(&language).map(|value| (key.clone(), value.clone())))
+            .collect()
+    }
+    
+    /// Add the translations of a catalog file (a JSON object of keys and strings)
+    pub fn load_catalog<P: AsRef<Path>>(&self, language: Language, path: P) -> Result<usize, String> {
+        let content = fs::read_to_string(path.as_ref())
+            .map_err(|e| format!("Failed to read catalog {}: {}", path.as_ref().display(), e))?;
+        let catalog: BTreeMap<String, String> = serde_json::from_str(&content)
+            .map_err(|e| format!("Invalid catalog {}: {}", path.as_ref().display(), e))?;
+        for (key, value) in &catalog {
+            self.add_translation(key, language, value);
+        }
+        Ok(catalog.len())
+    }
+    
+    /// Write the translations of a language to a catalog file
+    pub fn save_catalog<P: AsRef<Path>>(&self, language: Language, path: P) -> Result<(), String> {
+        if let Some(parent) = path.as_ref().parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        let content = serde_json::to_string_pretty(&self.catalog(language)).map_err(|e| e.to_string())?;
+        fs::write(path.as_ref(), content)
+            .map_err(|e| format!("Failed to write catalog {}: {}", path.as_ref().display(), e))
+    }
+    
+    /// Turn pseudo-localization on or off
+    ///
+    /// While it is on, every lookup returns the reference string accented and padded,
+    /// whatever the language. Text that is truncated or still plain English in a
+    /// panel is then easy to spot as a layout problem or a hard-coded string.
+    pub fn set_pseudo_localization(&self, enabled: bool) {
+        let previous = self.pseudo_localization.swap(enabled, Ordering::Relaxed);
+        if previous != enabled {
+            audit::record(AuditCategory::Settings, "settings.pseudo_localization", "translations", Some(previous.to_string()), Some(enabled.to_string()));
+        }
+    }
+    
+    /// Check whether pseudo-localization is on
+    pub fn pseudo_localization(&self) -> bool {
+        self.pseudo_localization.load(Ordering::Relaxed)
+    }
+    
+    /// Report the coverage of a language against the reference catalog
+    pub fn coverage(&self, language: Language) -> CoverageReport {
+        let translations = self.translations.read().unwrap();
+        
+        let mut missing = Vec::new();
+        let mut stale = Vec::new();
+        let mut total_keys = 0;
+        for (key, values) in translations.iter() {
+            match (values.contains_key(&REFERENCE_LANGUAGE), values.contains_key(&language)) {
+                (true, true) => total_keys += 1,
+                (true, false) => {
+                    total_keys += 1;
+                    missing.push(key.clone());
+                }
+                (false, true) => stale.push(key.clone()),
+                (false, false) => {}
+            }
+        }
+        missing.sort();
+        stale.sort();
+        
+        CoverageReport {
+            language,
+            total_keys,
+            translated_keys: total_keys - missing.len(),
+            missing,
+            stale,
+        }
+    }
+    
+    /// Report the coverage of every supported language
+    pub fn coverage_report(&self) -> Vec<CoverageReport> {
+        Language::all().into_iter().map(|language| self.coverage(language)).collect()
+    }
+    
+    /// Load default translations
+    pub fn load_default_translations(&self) {
+        // Common UI translations
+        self.add_translation("app.title", Language::Chinese, "OSland 操作系统可视化编程IDE");
+        self.add_translation("app.title", Language::English, "OSland Operating System Visual Programming IDE");
+        
+        self.add_translation("menu.file", Language::Chinese, "文件");
+        self.add_translation("menu.file", Language::English, "File");
+        
+        self.add_translation("menu.edit", Language::Chinese, "编辑");
+        self.add_translation("menu.edit", Language::English, "Edit");
+        
+        self.add_translation("menu.view", Language::Chinese, "视图");
+        self.add_translation("menu.view", Language::English, "View");
+        
+        self.add_translation("menu.tools", Language::Chinese, "工具");
+        self.add_translation("menu.tools", Language::English, "Tools");
+        
+        self.add_translation("menu.help", Language::Chinese, "帮助");
+        self.add_translation("menu.help", Language::English, "Help");
+        
+        // Project translations
+        self.add_translation("project.new", Language::Chinese, "新建项目");
+        self.add_translation("project.new", Language::English, "New Project");
+        
+        self.add_translation("project.open", Language::Chinese, "打开项目");
+        self.add_translation("project.open", Language::English, "Open Project");
+        
+        self.add_translation("project.save", Language::Chinese, "保存项目");
+        self.add_translation("project.save", Language::English, "Save Project");
+        
+        // Component translations
+        self.add_translation("component.panel.title", Language::Chinese, "组件面板");
+        self.add_translation("component.panel.title", Language::English, "Component Panel");
+        
+        self.add_translation("component.add", Language::Chinese, "添加组件");
+        self.add_translation("component.add", Language::English, "Add Component");
+        
+        // Property translations
+        self.add_translation("property.panel.title", Language::Chinese, "属性面板");
+        self.add_translation("property.panel.title", Language::English, "Property Panel");
+        
+        // Canvas translations
+        self.add_translation("canvas.title", Language::Chinese, "画布");
+        self.add_translation("canvas.title", Language::English, "Canvas");
+        
+        // Build engine translations
+        self.add_translation("build.start", Language::Chinese, "开始构建");
+        self.add_translation("build.start", Language::English, "Start Build");
+        
+        self.add_translation("build.success", Language::Chinese, "构建成功");
+        self.add_translation("build.success", Language::English, "Build Successful");
+        
+        self.add_translation("build.failed", Language::Chinese, "构建失败");
+        self.add_translation("build.failed", Language::English, "Build Failed");
+        
+        self.add_translation("build.config_valid", Language::Chinese, "构建配置有效");
+        self.add_translation("build.config_valid", Language::English, "Build configuration is valid");
+        
+        self.add_translation("build.config_invalid", Language::Chinese, "构建配置无效");
+        self.add_translation("build.config_invalid", Language::English, "Build configuration is invalid");
+        
+        // Canvas lint translations
+        self.add_translation("lint.passed", Language::Chinese, "画布检查通过");
+        self.add_translation("lint.passed", Language::English, "Canvas lint passed");
+        
+        self.add_translation("lint.failed", Language::Chinese, "画布检查发现错误");
+        self.add_translation("lint.failed", Language::English, "Canvas lint found errors");
+        
+        self.add_translation("git.check_passed", Language::Chinese, "已检查{0}个暂存的画布和瓦片图，未发现错误");
+        self.add_translation("git.check_passed", Language::English, "Checked {0} staged canvases and tile graphs, no errors");
+        
+        self.add_translation("git.check_failed", Language::Chinese, "暂存的画布和瓦片图存在错误，提交已阻止（检查了{0}个文件）");
+        self.add_translation("git.check_failed", Language::English, "Staged canvases and tile graphs have errors, commit blocked ({0} files checked)");
+        
+        self.add_translation("vault.created", Language::Chinese, "已创建项目密钥库{0}");
+        self.add_translation("vault.created", Language::English, "Created the project vault {0}");
+        
+        self.add_translation("vault.sealed", Language::Chinese, "已将{0}加密存入项目密钥库");
+        self.add_translation("vault.sealed", Language::English, "Sealed {0} into the project vault");
+        
+        self.add_translation("vault.unsealed", Language::Chinese, "已从项目密钥库恢复{0}个文件");
+        self.add_translation("vault.unsealed", Language::English, "Restored {0} files from the project vault");
+        
+        self.add_translation("audit.exported", Language::Chinese, "已将{0}条审计记录导出到{1}");
+        self.add_translation("audit.exported", Language::English, "Exported {0} audit records to {1}");
+        
+        self.add_translation("audit.pruned", Language::Chinese, "已从{1}删除{0}条过期审计记录");
+        self.add_translation("audit.pruned", Language::English, "Removed {0} expired audit records from {1}");
+        
+        // Kernel extractor translations
+        self.add_translation("extract.start", Language::Chinese, "开始提取组件");
+        self.add_translation("extract.start", Language::English, "Start Component Extraction");
+        
+        self.add_translation("extract.success", Language::Chinese, "组件提取成功");
+        self.add_translation("extract.success", Language::English, "Component Extraction Successful");
+        
+        self.add_translation("extract.failed", Language::Chinese, "组件提取失败");
+        self.add_translation("extract.failed", Language::English, "Component Extraction Failed");
+        
+        // CLI translations
+        self.add_translation("cli.run", Language::Chinese, "运行OSland IDE");
+        self.add_translation("cli.run", Language::English, "Start the OSland IDE");
+        
+        self.add_translation("cli.extract", Language::Chinese, "从开源内核提取组件");
+        self.add_translation("cli.extract", Language::English, "Extract components from open source kernels");
+        
+        self.add_translation("cli.extract.source", Language::Chinese, "内核源代码目录");
+        self.add_translation("cli.extract.source", Language::English, "Kernel source directory");
+        
+        self.add_translation("cli.extract.output", Language::Chinese, "提取组件输出目录");
+        self.add_translation("cli.extract.output", Language::English, "Output directory for extracted components");
+        
+        self.add_translation("cli.build", Language::Chinese, "构建操作系统镜像");
+        self.add_translation("cli.build", Language::English, "Build an operating system image");
+        
+        self.add_translation("cli.build.config", Language::Chinese, "项目配置文件");
+        self.add_translation("cli.build.config", Language::English, "Project configuration file");
+        
+        self.add_translation("cli.build.output", Language::Chinese, "输出镜像文件路径");
+        self.add_translation("cli.build.output", Language::English, "Output image file path");
+        
+        self.add_translation("cli.debug", Language::Chinese, "启用调试日志");
+        self.add_translation("cli.debug", Language::English, "Enable debug logging");
+        
+        // Status messages
+        self.add_translation("status.starting", Language::Chinese, "正在启动OSland v0.1.0...");
+        self.add_translation("status.starting", Language::English, "Starting OSland v0.1.0...");
+        
+        self.add_translation("status.ide_started", Language::Chinese, "OSland IDE已启动");
+        self.add_translation("status.ide_started", Language::English, "OSland IDE started");
+        
+        self.add_translation("status.extracting", Language::Chinese, "正在从{0}提取组件到{1}...");
+        self.add_translation("status.extracting", Language::English, "Extracting components from {0} to {1}...");
+        
+        self.add_translation("status.fusing", Language::Chinese, "正在从{0}个内核提取组件到{1}...");
+        self.add_translation("status.fusing", Language::English, "Extracting components from {0} kernels to {1}...");
+        
+        self.add_translation("fuse.conflict", Language::Chinese, "符号{0}在多个来源中重复定义：{1}");
+        self.add_translation("fuse.conflict", Language::English, "Symbol {0} is defined by several origins: {1}");
+        
+        self.add_translation("status.building", Language::Chinese, "正在从{0}构建OS镜像到{1}...");
+        self.add_translation("status.building", Language::English, "Building OS image from {0} to {1}...");
+        
+        self.add_translation("status.checking_config", Language::Chinese, "正在检查构建配置{0}...");
+        self.add_translation("status.checking_config", Language::English, "Checking build configuration {0}...");
+        
+        self.add_translation("status.cleaned", Language::Chinese, "已清理{0}个构建产物，释放{1}");
+        self.add_translation("status.cleaned", Language::English, "Cleaned {0} build artifacts, reclaimed {1}");
+        
+        self.add_translation("status.exporting", Language::Chinese, "正在导出{0}项目到{1}...");
+        self.add_translation("status.exporting", Language::English, "Exporting {0} project to {1}...");
+        
+        self.add_translation("status.exported", Language::Chinese, "已导出{0}个文件到{1}");
+        self.add_translation("status.exported", Language::English, "Exported {0} files to {1}");
+        
+        self.add_translation("status.imported", Language::Chinese, "已导入{0}项目（{1}个组件）到{2}");
+        self.add_translation("status.imported", Language::English, "Imported {0} project ({1} components) to {2}");
+        
+        self.add_translation("status.offline", Language::Chinese, "离线模式：已禁用所有外部网络连接，不可用的功能：{0}");
+        self.add_translation("status.offline", Language::English, "Offline mode: outbound network access is disabled, unavailable: {0}");
+        
+        self.add_translation("status.serving", Language::Chinese, "OSland守护进程正在{0}上提供服务，按Ctrl-C停止");
+        self.add_translation("status.serving", Language::English, "OSland daemon serving on {0}, press Ctrl-C to stop");
+        
+        self.add_translation("status.no_command", Language::Chinese, "未指定命令，默认启动IDE...");
+        self.add_translation("status.no_command", Language::English, "No command specified, starting IDE by default...");
+        
+        self.add_translation("status.exiting", Language::Chinese, "正在退出OSland...");
+        self.add_translation("status.exiting", Language::English, "Exiting OSland...");
+        
+        // Merge sort demo translations
+        self.add_translation("merge_sort.demo.description", Language::Chinese, "OSland归并排序可视化演示");
+        self.add_translation("merge_sort.demo.description", Language::English, "OSland Merge Sort Visualization Demo");
+        
+        self.add_translation("merge_sort.component.input_array", Language::Chinese, "输入数组");
+        self.add_translation("merge_sort.component.input_array", Language::English, "Input Array");
+        
+        self.add_translation("merge_sort.component.length_check", Language::Chinese, "长度检查");
+        self.add_translation("merge_sort.component.length_check", Language::English, "Length Check");
+        
+        self.add_translation("merge_sort.component.direct_return", Language::Chinese, "直接返回");
+        self.add_translation("merge_sort.component.direct_return", Language::English, "Direct Return");
+        
+        self.add_translation("merge_sort.component.split_array", Language::Chinese, "分割数组");
+        self.add_translation("merge_sort.component.split_array", Language::English, "Split Array");
+        
+        self.add_translation("merge_sort.component.merge_sort", Language::Chinese, "归并排序");
+        self.add_translation("merge_sort.component.merge_sort", Language::English, "Merge Sort");
+        
+        self.add_translation("merge_sort.component.merge_array", Language::Chinese, "合并数组");
+        self.add_translation("merge_sort.component.merge_array", Language::English, "Merge Array");
+        
+        self.add_translation("merge_sort.component.output_result", Language::Chinese, "输出结果");
+        self.add_translation("merge_sort.component.output_result", Language::English, "Output Result");
+        
+        self.load_error_translations();
+        self.load_arabic_translations();
+    }
+    
+    /// Load the Arabic catalog
+    fn load_arabic_translations(&self) {
+        let messages = [
+            ("app.title", "OSland بيئة التطوير المرئية لأنظمة التشغيل"),
+            ("menu.file", "ملف"),
+            ("menu.edit", "تحرير"),
+            ("menu.view", "عرض"),
+            ("menu.tools", "أدوات"),
+            ("menu.help", "مساعدة"),
+            ("project.new", "مشروع جديد"),
+            ("project.open", "فتح مشروع"),
+            ("project.save", "حفظ المشروع"),
+            ("component.panel.title", "لوحة المكونات"),
+            ("component.add", "إضافة مكوّن"),
+            ("property.panel.title", "لوحة الخصائص"),
+            ("canvas.title", "لوحة الرسم"),
+            ("build.start", "بدء البناء"),
+            ("build.success", "نجح البناء"),
+            ("build.failed", "فشل البناء"),
+            ("build.config_valid", "إعدادات البناء صالحة"),
+            ("build.config_invalid", "إعدادات البناء غير صالحة"),
+            ("lint.passed", "اجتاز فحص لوحة الرسم"),
+            ("lint.failed", "وجد فحص لوحة الرسم أخطاء"),
+            ("git.check_passed", "تم فحص {0} من اللوحات ومخططات البلاطات المرحلية دون أخطاء"),
+            ("git.check_failed", "توجد أخطاء في اللوحات ومخططات البلاطات المرحلية، تم منع الإيداع (تم فحص {0} من الملفات)"),
+            ("vault.created", "تم إنشاء خزنة المشروع {0}"),
+            ("vault.sealed", "تم تشفير {0} ونقله إلى خزنة المشروع"),
+            ("vault.unsealed", "تمت استعادة {0} من الملفات من خزنة المشروع"),
+            ("audit.exported", "تم تصدير {0} من سجلات التدقيق إلى {1}"),
+            ("audit.pruned", "تم حذف {0} من سجلات التدقيق المنتهية من {1}"),
+            ("extract.start", "بدء استخراج المكونات"),
+            ("extract.success", "نجح استخراج المكونات"),
+            ("extract.failed", "فشل استخراج المكونات"),
+            ("cli.run", "تشغيل بيئة OSland"),
+            ("cli.extract", "استخراج المكونات من أنوية مفتوحة المصدر"),
+            ("cli.extract.source", "مجلد الشيفرة المصدرية للنواة"),
+            ("cli.extract.output", "مجلد إخراج المكونات المستخرجة"),
+            ("cli.build", "بناء صورة نظام تشغيل"),
+            ("cli.build.config", "ملف إعدادات المشروع"),
+            ("cli.build.output", "مسار ملف الصورة الناتجة"),
+            ("cli.debug", "تفعيل سجل التصحيح"),
+            ("status.starting", "جارٍ تشغيل OSland v0.1.0..."),
+            ("status.ide_started", "تم تشغيل بيئة OSland"),
+            ("status.extracting", "جارٍ استخراج المكونات من {0} إلى {1}..."),
+            ("status.fusing", "جارٍ استخراج المكونات من {0} أنوية إلى {1}..."),
+            ("fuse.conflict", "الرمز {0} معرّف في عدة مصادر: {1}"),
+            ("status.building", "جارٍ بناء صورة النظام من {0} إلى {1}..."),
+            ("status.checking_config", "جارٍ فحص إعدادات البناء {0}..."),
+            ("status.cleaned", "تم حذف {0} من نواتج البناء وتحرير {1}"),
+            ("status.exporting", "جارٍ تصدير مشروع {0} إلى {1}..."),
+            ("status.exported", "تم تصدير {0} من الملفات إلى {1}"),
+            ("status.imported", "تم استيراد مشروع {0} ({1} من المكونات) إلى {2}"),
+            ("status.offline", "وضع عدم الاتصال: تم تعطيل الاتصالات الشبكية الخارجية، الميزات غير المتاحة: {0}"),
+            ("status.serving", "خادم OSland يعمل على {0}، اضغط Ctrl-C للإيقاف"),
+            ("status.no_command", "لم يُحدَّد أمر، سيتم تشغيل البيئة افتراضيًا..."),
+            ("status.exiting", "جارٍ إغلاق OSland..."),
+            ("error.OSL-B001", "خطأ في الإعدادات: {0}"),
+            ("error.OSL-B002", "خطأ في عملية البناء: {0}"),
+            ("error.OSL-B003", "خطأ في إنشاء الصورة: {0}"),
+            ("error.OSL-B004", "خطأ في تنفيذ الأمر: {0}"),
+            ("error.OSL-B005", "تم إلغاء البناء"),
+            ("error.OSL-B006", "وضع عدم الاتصال: {0}"),
+            ("error.OSL-E001", "خطأ في مجلد المصدر: {0}"),
+            ("error.OSL-E002", "خطأ في مجلد الإخراج: {0}"),
+            ("error.OSL-E003", "خطأ في التحليل: {0}"),
+            ("error.OSL-E004", "خطأ في تحليل الاعتماديات: {0}"),
+            ("error.OSL-E005", "خطأ في الاستخراج: {0}"),
+            ("error.OSL-E006", "تم إلغاء الاستخراج"),
+        ];
+        
+        for (key, value) in messages {
+            self.add_translation(key, Language::Arabic, value);
+        }
+    }
+    
+    /// Load translations of error messages, keyed by error code
+    fn load_error_translations(&self) {
+        let messages = [
+            // Build engine errors
+            ("OSL-B001", "配置错误：{0}", "Configuration error: {0}"),
+            ("OSL-B002", "构建过程错误：{0}", "Build process error: {0}"),
+            ("OSL-B003", "镜像生成错误：{0}", "Image generation error: {0}"),
+            ("OSL-B004", "命令执行错误：{0}", "Command execution error: {0}"),
+            ("OSL-B005", "构建已取消", "Build canceled"),
+            ("OSL-B006", "离线模式：{0}", "Offline mode: {0}"),
+            // Kernel extractor errors
+            ("OSL-E001", "源代码目录错误：{0}", "Source directory error: {0}"),
+            ("OSL-E002", "输出目录错误：{0}", "Output directory error: {0}"),
+            ("OSL-E003", "解析错误：{0}", "Parsing error: {0}"),
+            ("OSL-E004", "依赖分析错误：{0}", "Dependency analysis error: {0}"),
+            ("OSL-E005", "提取错误：{0}", "Extraction error: {0}"),
+            ("OSL-E006", "提取已取消", "Extraction canceled"),
+        ];
+        
+        for (code, chinese, english) in messages {
+            let key = format!("error.{}", code);
+            self.add_translation(&key, Language::Chinese, chinese);
+            self.add_translation(&key, Language::English, english);
+        }
+    }
+}
+
+/// Global translations instance
+lazy_static::lazy_static! {
+    pub static ref TRANSLATIONS: Translations = {
+        let translations = Translations::new(Language::Chinese);
+        translations.load_default_translations();
+        translations
+    };
+}
+
+/// Translate a key with optional language
+pub fn translate(key: &str, language: Option<Language>) -> String {
+    TRANSLATIONS.translate(key, language)
+}
+
+/// Turn pseudo-localization of the global translations on or off
+pub fn set_pseudo_localization(enabled: bool) {
+    TRANSLATIONS.set_pseudo_localization(enabled);
+}
+
+/// Accent and pad a string to simulate a longer, non-Latin translation
+///
+/// `{0}`-style placeholders are kept so formatting still works, and the result is
+/// bracketed so that truncated text is visible.
+pub fn pseudo_localize(text: &str) -> String {
+    const LOWER: [char; 26] = [
+        'à', 'ƀ', 'ç', 'đ', 'é', 'ƒ', 'ĝ', 'ĥ', 'î', 'ĵ', 'ķ', 'ļ', 'ɱ',
+        'ñ', 'ö', 'þ', 'ǫ', 'ŕ', 'š', 'ţ', 'û', 'ṽ', 'ŵ', 'ẋ', 'ý', 'ž',
+    ];
+    const UPPER: [char; 26] = [
+        'Å', 'Ɓ', 'Ç', 'Đ', 'É', 'Ƒ', 'Ĝ', 'Ĥ', 'Î', 'Ĵ', 'Ķ', 'Ļ', 'Ṁ',
+        'Ñ', 'Ö', 'Þ', 'Ǫ', 'Ŕ', 'Š', 'Ţ', 'Û', 'Ṽ', 'Ŵ', 'Ẋ', 'Ý', 'Ž',
+    ];
+    
+    let mut result = String::from("[");
+    let mut in_placeholder = false;
+    for c in text.chars() {
+        match c {
+            '{' => in_placeholder = true,
+            '}' => in_placeholder = false,
+            _ => {}
+        }
+        result.push(match c {
+            'a'..='z' if !in_placeholder => LOWER[(c as u8 - b'a') as usize],
+            'A'..='Z' if !in_placeholder => UPPER[(c as u8 - b'A') as usize],
+            _ => c,
+        });
+    }
+    
+    // Translations are often 30-40% longer than English
+    let padding = (text.chars().count() * 2 + 4) / 5;
+    if padding > 0 {
+        result.push(' ');
+        result.push_str(&"·".repeat(padding));
+    }
+    result.push(']');
+    result
+}
+
+/// Translate a key with formatting arguments
+pub fn translate_fmt(key: &str, language: Option<Language>, args: &[&str]) -> String {
+    let translation = translate(key, language);
+    
+    // Simple format string replacement for {0}, {1}, etc.
+    let mut result = String::with_capacity(translation.len());
+    let mut rest = translation.as_str();
+    
+    while let Some(start) = rest.find('{') {
+        result.push_str(&rest[..start]);
+        let placeholder = &rest[start..];
+        let arg = placeholder[1..].find('}')
+            .and_then(|end| placeholder[1..1 + end].parse::<usize>().ok().map(|arg_idx| (arg_idx, end + 2)));
+        
+        match arg {
+            Some((arg_idx, len)) if arg_idx < args.len() => {
+                result.push_str(args[arg_idx]);
+                rest = &placeholder[len..];
+            }
+            Some((_, len)) => {
+                // Keep the original placeholder if argument is missing
+                result.push_str(&placeholder[..len]);
+                rest = &placeholder[len..];
+            }
+            None => {
+                // Invalid placeholder, keep as is
+                result.push('{');
+                rest = &placeholder[1..];
+            }
+        }
+    }
+    
+    result.push_str(rest);
+    result
+}
+
+/// Error with a stable code and a translated message
+///
+/// The code is the same in every release and language, so logs record it and users
+/// can search for it. The message is translated under the key `error.<code>`, with
+/// the error details as `{0}`.
+pub trait LocalizedError: std::fmt::Display {
+    /// Stable error code, e.g. `OSL-B001`
+    fn error_code(&self) -> &'static str;
+    
+    /// Details substituted into the translated message
+    fn message_args(&self) -> Vec<String> {
+        Vec::new()
+    }
+    
+    /// Render the error for users in a language
+    fn localized(&self, language: Option<Language>) -> String {
+        let key = format!("error.{}", self.error_code());
+        let args = self.message_args();
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        let message = translate_fmt(&key, language, &args);
+        
+        if message == key {
+            // No translation, fall back to the English message
+            return self.log_message();
+        }
+        format!("[{}] {}", self.error_code(), message)
+    }
+    
+    /// Render the error for logs, with its code and the untranslated message
+    fn log_message(&self) -> String {
+        format!("[{}] {}", self.error_code(), self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::build_engine::BuildEngineError;
+    
+    #[test]
+    fn test_translate_fmt() {
+        assert_eq!(translate_fmt("status.extracting", Some(Language::Chinese), &["linux", "out"]), "正在从linux提取组件到out...");
+        assert_eq!(translate_fmt("status.extracting", Some(Language::English), &["linux"]), "Extracting components from linux to {1}...");
+    }
+    
+    #[test]
+    fn test_localized_error() {
+        let error = BuildEngineError::ConfigError("missing target".to_string());
+        assert_eq!(error.localized(Some(Language::Chinese)), "[OSL-B001] 配置错误：missing target");
+        assert_eq!(error.localized(Some(Language::English)), "[OSL-B001] Configuration error: missing target");
+        assert_eq!(error.log_message(), "[OSL-B001] Configuration error: missing target");
+        assert_eq!(BuildEngineError::BuildCanceled.localized(Some(Language::Chinese)), "[OSL-B005] 构建已取消");
+    }
+    
+    #[test]
+    fn test_coverage_and_pseudo_localization() {
+        let translations = Translations::new(Language::English);
+        translations.add_translation("menu.file", Language::English, "File");
+        translations.add_translation("menu.file", Language::Japanese, "ファイル");
+        translations.add_translation("menu.edit", Language::English, "Edit");
+        translations.add_translation("menu.old", Language::Japanese, "古い");
+        
+        let coverage = translations.coverage(Language::Japanese);
+        assert_eq!((coverage.total_keys, coverage.translated_keys), (2, 1));
+        assert_eq!(coverage.missing, vec!["menu.edit"]);
+        assert_eq!(coverage.stale, vec!["menu.old"]);
+        assert_eq!(coverage.percent(), 50.0);
+        assert!(TRANSLATIONS.coverage(Language::Chinese).missing.is_empty());
+        
+        assert_eq!(pseudo_localize("Build {0} files"), "[Ɓûîļđ {0} ƒîļéš ······]");
+        translations.set_pseudo_localization(true);
+        assert_eq!(translations.translate("menu.file", Some(Language::Japanese)), "[Ƒîļé ··]");
+        assert_eq!(translations.translate("menu.old", Some(Language::Japanese)), "menu.old");
+        translations.set_pseudo_localization(false);
+        assert_eq!(translations.translate("menu.file", Some(Language::Japanese)), "ファイル");
+    }
+    
+    #[test]
+    fn test_arabic_locale() {
+        let arabic = Language::from_code("ar").unwrap();
+        assert_eq!(arabic.text_direction(), TextDirection::RightToLeft);
+        assert_eq!(Language::English.text_direction(), TextDirection::LeftToRight);
+        assert_eq!(translate("menu.file", Some(arabic)), "ملف");
+        assert_eq!(translate_fmt("status.building", Some(arabic), &["os.json", "os.img"]), "جارٍ بناء صورة النظام من os.json إلى os.img...");
+    }
+}
@@ -0,0 +1,492 @@
+// Module ABI Checker for OSland Kernel Extractor
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::sync::LazyLock;
+use serde::{Deserialize, Serialize};
+use crate::kernel_extractor::{KernelComponent, KernelExtractorError};
+
+static CALL_RE: LazyLock<regex::Regex> = LazyLock::new(|| regex::Regex::new(r"\b([A-Za-z_]\w*)\s*\(").unwrap());
+static DEFINITION_RE: LazyLock<regex::Regex> = LazyLock::new(|| regex::Regex::new(r"(?m)^[A-Za-z_][\w\s\*]*?\b([A-Za-z_]\w*)\s*\([^;]*$").unwrap());
+static DEFINE_RE: LazyLock<regex::Regex> = LazyLock::new(|| regex::Regex::new(r"(?m)^\s*#\s*define\s+([A-Za-z_]\w*)").unwrap());
+static LICENSE_RE: LazyLock<regex::Regex> = LazyLock::new(|| regex::Regex::new(r#"MODULE_LICENSE\s*\(\s*"([^"]*)""#).unwrap());
+static IMPORT_NS_RE: LazyLock<regex::Regex> = LazyLock::new(|| regex::Regex::new(r"MODULE_IMPORT_NS\s*\(\s*([A-Za-z_]\w*)").unwrap());
+
+/// C keywords and builtins that look like calls
+const NOT_SYMBOLS: &[&str] = &[
+    "if", "for", "while", "switch", "return", "sizeof", "typeof", "__typeof__", "alignof", "_Alignof",
+    "defined", "__attribute__", "__builtin_expect", "__builtin_constant_p", "asm", "__asm__", "offsetof",
+];
+
+/// How a kernel symbol is exported
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExportKind {
+    /// `EXPORT_SYMBOL`
+    Plain,
+    /// `EXPORT_SYMBOL_GPL`
+    GplOnly,
+    /// Global symbol without export information (System.map/vmlinux)
+    Unknown,
+}
+
+/// Symbol provided by the target kernel
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KernelSymbol {
+    /// Symbol name
+    pub name: String,
+    
+    /// Symbol version CRC (CONFIG_MODVERSIONS)
+    pub crc: Option<u32>,
+    
+    /// Export kind
+    pub export: ExportKind,
+    
+    /// Symbol namespace
+    pub namespace: Option<String>,
+}
+
+/// Symbols exported by a target kernel
+#[derive(Debug, Clone, Default)]
+pub struct KernelSymbolTable {
+    symbols: HashMap<String, KernelSymbol>,
+}
+
+impl KernelSymbolTable {
+    /// Create an empty symbol table
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Load global symbols from a `System.map`
+    pub fn from_system_map<P: AsRef<Path>>(path: P) -> Result<Self, KernelExtractorError> {
+        let content = fs::read_to_string(path.as_ref())
+            .map_err(|e| KernelExtractorError::SourceDirError(format!("Failed to read {:?}: {}", path.as_ref(), e)))?;
+        Ok(Self::from_nm_output(&content))
+    }
+    
+    /// Load global symbols from a `vmlinux` with `nm`
+    pub fn from_vmlinux<P: AsRef<Path>>(path: P) -> Result<Self, KernelExtractorError> {
+        let output = Command::new("nm")
+            .arg("--defined-only")
+            .arg(path.as_ref())
+            .output()
+            .map_err(|e| KernelExtractorError::DependencyError(format!("Failed to run nm: {}", e)))?;
+        if !output.status.success() {
+            return Err(KernelExtractorError::DependencyError(format!(
+                "nm failed on {:?}: {}",
+                path.as_ref(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        Ok(Self::from_nm_output(&String::from_utf8_lossy(&output.stdout)))
+    }
+    
+    /// Parse `nm`-style `<address> <type> <name>` lines
+    ///
+    /// Upper-case types are global symbols; `__crc_<name>` absolute symbols
+    /// carry the version CRCs of CONFIG_MODVERSIONS kernels.
+    pub fn from_nm_output(content: &str) -> Self {
+        let mut table = Self::new();
+        let mut crcs = HashMap::new();
+        for line in content.lines() {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let (value, kind, name) = match fields.as_slice() {
+                [value, kind, name, ..] => (*value, *kind, *name),
+                _ => continue,
+            };
+            if let Some(symbol) = name.strip_prefix("__crc_") {
+                if let Ok(crc) = u64::from_str_radix(value, 16) {
+                    crcs.insert(symbol.to_string(), crc as u32);
+                }
+                continue;
+            }
+            if kind.chars().all(|c| c.is_ascii_uppercase()) {
+                table.symbols.insert(name.to_string(), KernelSymbol {
+                    name: name.to_string(),
+                    crc: None,
+                    export: ExportKind::Unknown,
+                    namespace: None,
+                });
+            }
+        }
+        for (name, crc) in crcs {
+            if let Some(symbol) = table.symbols.get_mut(&name) {
+                symbol.crc = Some(crc);
+            }
+        }
+        table
+    }
+    
+    /// Load exported symbols from a `Module.symvers`
+    ///
+    /// Lines are `<crc>\t<symbol>\t<module>\t<export type>[\t<namespace>]`.
+    pub fn from_module_symvers<P: AsRef<Path>>(path: P) -> Result<Self, KernelExtractorError> {
+        let content = fs::read_to_string(path.as_ref())
+            .map_err(|e| KernelExtractorError::SourceDirError(format!("Failed to read {:?}: {}", path.as_ref(), e)))?;
+        
+        let mut table = Self::new();
+        for line in content.lines() {
+            let fields: Vec<&str> = line.split('\t').collect();
+            if fields.len() < 4 {
+                continue;
+            }
+            let crc = u32::from_str_radix(fields[0].trim_start_matches("0x"), 16).ok();
+            let export = match fields[3] {
+                "EXPORT_SYMBOL" => ExportKind::Plain,
+                "EXPORT_SYMBOL_GPL" => ExportKind::GplOnly,
+                _ => ExportKind::Unknown,
+            };
+            let namespace = fields.get(4).map(|ns| ns.trim()).filter(|ns| !ns.is_empty()).map(String::from);
+            table.symbols.insert(fields[1].to_string(), KernelSymbol {
+                name: fields[1].to_string(),
+                crc,
+                export,
+                namespace,
+            });
+        }
+        Ok(table)
+    }
+    
+    /// Merge another table, keeping the more precise information for each symbol
+    pub fn merge(&mut self, other: KernelSymbolTable) {
+        for (name, symbol) in other.symbols {
+            match self.symbols.get_mut(&name) {
+                Some(existing) => {
+                    existing.crc = existing.crc.or(symbol.crc);
+                    if existing.export == ExportKind::Unknown {
+                        existing.export = symbol.export;
+                    }
+                    existing.namespace = existing.namespace.take().or(symbol.namespace);
+                }
+                None => {
+                    self.symbols.insert(name, symbol);
+                }
+            }
+        }
+    }
+    
+    /// Get a symbol
+    pub fn get(&self, name: &str) -> Option<&KernelSymbol> {
+        self.symbols.get(name)
+    }
+    
+    /// Number of symbols
+    pub fn len(&self) -> usize {
+        self.symbols.len()
+    }
+    
+    /// Whether the table is empty
+    pub fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
+}
+
+/// Symbol a module needs from the kernel
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SymbolRequirement {
+    /// Symbol name
+    pub name: String,
+    
+    /// CRC the module was built against
+    pub crc: Option<u32>,
+}
+
+/// Kernel symbols required by a module
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModuleRequirements {
+    /// Module name
+    pub module: String,
+    
+    /// Required symbols
+    pub symbols: Vec<SymbolRequirement>,
+    
+    /// `MODULE_LICENSE` string
+    pub license: Option<String>,
+    
+    /// Namespaces imported with `MODULE_IMPORT_NS`
+    pub imported_namespaces: Vec<String>,
+    
+    /// Whether the requirements were inferred from sources rather than a built module
+    pub inferred: bool,
+}
+
+impl ModuleRequirements {
+    /// Read the undefined symbols and symbol versions of a built `.ko`
+    pub fn from_object<P: AsRef<Path>>(path: P) -> Result<Self, KernelExtractorError> {
+        let path = path.as_ref();
+        let output = Command::new("nm")
+            .args(["--undefined-only", "--format=posix"])
+            .arg(path)
+            .output()
+            .map_err(|e| KernelExtractorError::DependencyError(format!("Failed to run nm: {}", e)))?;
+        if !output.status.success() {
+            return Err(KernelExtractorError::DependencyError(format!("nm failed on {:?}: {}", path, String::from_utf8_lossy(&output.stderr).trim())));
+        }
+        
+        // Symbol versions recorded in the module's __versions section
+        let mut crcs = HashMap::new();
+        if let Ok(versions) = Command::new("modprobe").arg("--dump-modversions").arg(path).output() {
+            for line in String::from_utf8_lossy(&versions.stdout).lines() {
+                if let [crc, name] = line.split_whitespace().collect::<Vec<_>>().as_slice() {
+                    if let Ok(crc) = u32::from_str_radix(crc.trim_start_matches("0x"), 16) {
+                        crcs.insert(name.to_string(), crc);
+                    }
+                }
+            }
+        }
+        
+        let symbols = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| line.split_whitespace().next())
+            .map(|name| SymbolRequirement {
+                name: name.to_string(),
+                crc: crcs.get(name).copied(),
+            })
+            .collect();
+        
+        // License and namespaces live in the .modinfo section
+        let modinfo = Command::new("modinfo").arg(path).output()
+            .map(|output| String::from_utf8_lossy(&output.stdout).into_owned())
+            .unwrap_or_default();
+        let field = |key: &str| -> Vec<String> {
+            modinfo.lines()
+                .filter_map(|line| line.strip_prefix(key))
+                .map(|value| value.trim().to_string())
+                .collect()
+        };
+        
+        Ok(Self {
+            module: path.file_stem().and_then(|stem| stem.to_str()).unwrap_or("module").to_string(),
+            symbols,
+            license: field("license:").into_iter().next(),
+            imported_namespaces: field("import_ns:"),
+            inferred: false,
+        })
+    }
+    
+    /// Infer the required symbols of an extracted component from its sources
+    ///
+    /// Every called function that is not defined (as a function or macro) by
+    /// the component is a requirement. Static inline helpers from kernel
+    /// headers cannot be told apart, so results are marked as inferred.
+    pub fn from_component(component: &KernelComponent) -> Result<Self, KernelExtractorError> {
+        let mut called = BTreeSet::new();
+        let mut defined = BTreeSet::new();
+        let mut license = None;
+        let mut imported_namespaces = Vec::new();
+        
+        for path in component.source_files.iter().chain(&component.header_files) {
+            if !path.exists() {
+                continue;
+            }
+            let content = fs::read_to_string(path)
+                .map_err(|e| KernelExtractorError::ParseError(format!("Failed to read {:?}: {}", path, e)))?;
+            defined.extend(DEFINITION_RE.captures_iter(&content).map(|caps| caps[1].to_string()));
+            defined.extend(DEFINE_RE.captures_iter(&content).map(|caps| caps[1].to_string()));
+            called.extend(CALL_RE.captures_iter(&content).map(|caps| caps[1].to_string()));
+            if license.is_none() {
+                license = LICENSE_RE.captures(&content).map(|caps| caps[1].to_string());
+            }
+            imported_namespaces.extend(IMPORT_NS_RE.captures_iter(&content).map(|caps| caps[1].to_string()));
+        }
+        
+        let symbols = called.into_iter()
+            .filter(|name| !defined.contains(name) && !NOT_SYMBOLS.contains(&name.as_str()))
+            // All-caps identifiers are macros from kernel headers
+            .filter(|name| name.chars().any(|c| c.is_ascii_lowercase()))
+            .map(|name| SymbolRequirement { name, crc: None })
+            .collect();
+        
+        Ok(Self {
+            module: component.name.clone(),
+            symbols,
+            license,
+            imported_namespaces,
+            inferred: true,
+        })
+    }
+    
+    /// Whether the module license allows GPL-only symbols
+    pub fn is_gpl_compatible(&self) -> bool {
+        // Same list as license_is_gpl_compatible() in include/linux/license.h
+        matches!(
+            self.license.as_deref(),
+            Some("GPL") | Some("GPL v2") | Some("GPL and additional rights") | Some("Dual BSD/GPL") | Some("Dual MIT/GPL") | Some("Dual MPL/GPL")
+        )
+    }
+}
+
+/// ABI problem found for a symbol
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum AbiIssueKind {
+    /// The target kernel does not provide the symbol
+    Missing,
+    /// The symbol's version CRC differs from the one the module was built against
+    VersionMismatch { expected: u32, actual: u32 },
+    /// The symbol is GPL-only and the module license is not GPL compatible
+    GplOnly,
+    /// The symbol lives in a namespace the module does not import
+    NamespaceNotImported(String),
+}
+
+/// ABI problem of a module
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AbiIssue {
+    /// Symbol name
+    pub symbol: String,
+    
+    /// Issue kind
+    pub kind: AbiIssueKind,
+    
+    /// Human readable description
+    pub message: String,
+}
+
+/// Compatibility report of a module against a target kernel
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AbiReport {
+    /// Module name
+    pub module: String,
+    
+    /// Number of required symbols checked
+    pub checked: usize,
+    
+    /// Problems found
+    pub issues: Vec<AbiIssue>,
+    
+    /// Whether the requirements were inferred from sources
+    pub inferred: bool,
+}
+
+impl AbiReport {
+    /// Whether the module can be loaded into the target kernel
+    pub fn is_compatible(&self) -> bool {
+        self.issues.is_empty()
+    }
+    
+    /// Names of the symbols missing from the target kernel
+    pub fn missing(&self) -> Vec<&str> {
+        self.issues.iter()
+            .filter(|issue| issue.kind == AbiIssueKind::Missing)
+            .map(|issue| issue.symbol.as_str())
+            .collect()
+    }
+}
+
+/// Module ABI Checker
+///
+/// Compares the symbols a module requires with those exported by a target
+/// kernel (System.map, vmlinux or Module.symvers) before the module is
+/// included in a build.
+pub struct AbiChecker {
+    /// Target kernel symbols
+    kernel: KernelSymbolTable,
+}
+
+impl AbiChecker {
+    /// Create a checker for a target kernel
+    pub fn new(kernel: KernelSymbolTable) -> Self {
+        Self { kernel }
+    }
+    
+    /// Check a module's requirements
+    pub fn check(&self, requirements: &ModuleRequirements) -> AbiReport {
+        let mut issues = Vec::new();
+        for requirement in &requirements.symbols {
+            let symbol = match self.kernel.get(&requirement.name) {
+                Some(symbol) => symbol,
+                None => {
+                    let hint = if requirements.inferred { " (or is a header inline/macro)" } else { "" };
+                    issues.push(AbiIssue {
+                        symbol: requirement.name.clone(),
+                        kind: AbiIssueKind::Missing,
+                        message: format!("'{}' is not exported by the target kernel{}", requirement.name, hint),
+                    });
+                    continue;
+                }
+            };
+            
+            if let (Some(expected), Some(actual)) = (requirement.crc, symbol.crc) {
+                if expected != actual {
+                    issues.push(AbiIssue {
+                        symbol: requirement.name.clone(),
+                        kind: AbiIssueKind::VersionMismatch { expected, actual },
+                        message: format!("'{}' has version 0x{:08x} in the target kernel, module expects 0x{:08x}", requirement.name, actual, expected),
+                    });
+                }
+            }
+            if symbol.export == ExportKind::GplOnly && !requirements.is_gpl_compatible() {
+                issues.push(AbiIssue {
+                    symbol: requirement.name.clone(),
+                    kind: AbiIssueKind::GplOnly,
+                    message: format!(
+                        "'{}' is exported GPL-only but the module license is {}",
+                        requirement.name,
+                        requirements.license.as_deref().map(|l| format!("'{}'", l)).unwrap_or_else(|| "not declared".to_string())
+                    ),
+                });
+            }
+            if let Some(namespace) = &symbol.namespace {
+                if !requirements.imported_namespaces.contains(namespace) {
+                    issues.push(AbiIssue {
+                        symbol: requirement.name.clone(),
+                        kind: AbiIssueKind::NamespaceNotImported(namespace.clone()),
+                        message: format!("'{}' is in namespace {} which the module does not import", requirement.name, namespace),
+                    });
+                }
+            }
+        }
+        
+        AbiReport {
+            module: requirements.module.clone(),
+            checked: requirements.symbols.len(),
+            issues,
+            inferred: requirements.inferred,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_check_module_requirements() {
+        let mut kernel = KernelSymbolTable::from_nm_output(
+            "ffffffff81000000 T _printk\nffffffff81000100 t local_helper\n0000000012345678 A __crc__printk\nffffffff81000200 T pci_enable_device\n"
+        );
+        let symvers = KernelSymbolTable {
+            symbols: HashMap::from([("pci_enable_device".to_string(), KernelSymbol {
+                name: "pci_enable_device".to_string(),
+                crc: Some(0xabcd),
+                export: ExportKind::GplOnly,
+                namespace: None,
+            })]),
+        };
+        kernel.merge(symvers);
+        assert_eq!(kernel.len(), 2);
+        assert_eq!(kernel.get("_printk").unwrap().crc, Some(0x12345678));
+        
+        let requirements = ModuleRequirements {
+            module: "drv".to_string(),
+            symbols: vec![
+                SymbolRequirement { name: "_printk".to_string(), crc: Some(0x1) },
+                SymbolRequirement { name: "pci_enable_device".to_string(), crc: None },
+                SymbolRequirement { name: "local_helper".to_string(), crc: None },
+            ],
+            license: Some("Proprietary".to_string()),
+            imported_namespaces: Vec::new(),
+            inferred: false,
+        };
+        
+        let report = AbiChecker::new(kernel).check(&requirements);
+        assert!(!report.is_compatible());
+        assert_eq!(report.missing(), vec!["local_helper"]);
+        assert!(report.issues.iter().any(|issue| matches!(issue.kind, AbiIssueKind::VersionMismatch { expected: 1, actual: 0x12345678 })));
+        assert!(report.issues.iter().any(|issue| issue.kind == AbiIssueKind::GplOnly));
+    }
+}
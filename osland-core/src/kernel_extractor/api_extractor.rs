@@ -0,0 +1,641 @@
+// API Surface Extractor for OSland Kernel Extractor
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+use std::collections::{BTreeSet, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+use serde::{Deserialize, Serialize};
+use crate::kernel_extractor::{KernelComponent, KernelExtractorError};
+
+static EXPORT_RE: LazyLock<regex::Regex> = LazyLock::new(|| regex::Regex::new(r"^EXPORT_SYMBOL(?:_GPL)?(?:_NS)?\s*\(\s*([A-Za-z_]\w*)").unwrap());
+static TYPE_DEFINITION_RE: LazyLock<regex::Regex> = LazyLock::new(|| regex::Regex::new(r"^(struct|union|enum)\s+([A-Za-z_]\w*)\s*\{").unwrap());
+static TYPE_REFERENCE_RE: LazyLock<regex::Regex> = LazyLock::new(|| regex::Regex::new(r"\b(struct|union|enum)\s+([A-Za-z_]\w*)").unwrap());
+static FORWARD_DECLARATION_RE: LazyLock<regex::Regex> = LazyLock::new(|| regex::Regex::new(r"^(struct|union|enum) [A-Za-z_]\w*$").unwrap());
+static POINTER_DECLARATOR_RE: LazyLock<regex::Regex> = LazyLock::new(|| regex::Regex::new(r"\(\s*\*\s*([A-Za-z_]\w*)\s*\)").unwrap());
+static LAST_IDENTIFIER_RE: LazyLock<regex::Regex> = LazyLock::new(|| regex::Regex::new(r"([A-Za-z_]\w*)\s*(?:\[[^\]]*\]\s*)*$").unwrap());
+
+/// Kind of an API item
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum ApiItemKind {
+    Macro,
+    Struct,
+    Union,
+    Enum,
+    Typedef,
+    Variable,
+    Function,
+    InlineFunction,
+}
+
+/// Item of a component's public API
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiItem {
+    /// Symbol name
+    pub name: String,
+    
+    /// Item kind
+    pub kind: ApiItemKind,
+    
+    /// Declaration emitted into the generated header
+    pub declaration: String,
+    
+    /// File defining the item
+    pub file: PathBuf,
+    
+    /// Line number (1-based)
+    pub line: usize,
+    
+    /// Whether the item is exported with `EXPORT_SYMBOL`
+    pub exported: bool,
+}
+
+/// Public API surface of a component
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ApiSurface {
+    /// Component name
+    pub component: String,
+    
+    /// API items, ordered as they appear in the generated header
+    pub items: Vec<ApiItem>,
+    
+    /// System headers the API depends on
+    pub includes: Vec<String>,
+    
+    /// Struct/union types referenced by the API but not defined by it, with
+    /// their keyword (`struct foo`, `union bar`)
+    pub forward_declarations: Vec<String>,
+}
+
+impl ApiSurface {
+    /// Get the names of the functions and variables the component exports
+    pub fn exported_symbols(&self) -> Vec<&str> {
+        self.items.iter()
+            .filter(|item| matches!(item.kind, ApiItemKind::Function | ApiItemKind::Variable))
+            .map(|item| item.name.as_str())
+            .collect()
+    }
+    
+    /// Generate a self-contained header for the API
+    pub fn to_header(&self) -> String {
+        let guard = format!("OSLAND_{}_API_H", sanitize_identifier(&self.component).to_uppercase());
+        let mut header = String::new();
+        header.push_str(&format!("/*\n * {} API\n *\n * Generated by OSland from the extracted component sources.\n */\n", self.component));
+        header.push_str(&format!("#ifndef {}\n#define {}\n\n", guard, guard));
+        
+        for include in &self.includes {
+            header.push_str(&format!("#include <{}>\n", include));
+        }
+        if !self.includes.is_empty() {
+            header.push('\n');
+        }
+        
+        for declaration in &self.forward_declarations {
+            header.push_str(&format!("{};\n", declaration));
+        }
+        if !self.forward_declarations.is_empty() {
+            header.push('\n');
+        }
+        
+        let mut previous_kind = None;
+        for item in &self.items {
+            let group = item_group(item.kind);
+            if previous_kind.is_some() && previous_kind != Some(group) {
+                header.push('\n');
+            }
+            previous_kind = Some(group);
+            header.push_str(&item.declaration);
+            header.push('\n');
+            if matches!(item.kind, ApiItemKind::Struct | ApiItemKind::Union | ApiItemKind::Enum | ApiItemKind::InlineFunction) {
+                header.push('\n');
+            }
+        }
+        
+        header.push_str(&format!("\n#endif /* {} */\n", guard));
+        header
+    }
+    
+    /// Write the generated header to `<dir>/<component>_api.h`
+    pub fn write_header<P: AsRef<Path>>(&self, dir: P) -> Result<PathBuf, KernelExtractorError> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)
+            .map_err(|e| KernelExtractorError::OutputDirError(format!("Failed to create header directory {:?}: {}", dir, e)))?;
+        let path = dir.join(format!("{}_api.h", sanitize_identifier(&self.component)));
+        fs::write(&path, self.to_header())
+            .map_err(|e| KernelExtractorError::ExtractionError(format!("Failed to write header {:?}: {}", path, e)))?;
+        Ok(path)
+    }
+}
+
+/// Group used to separate header sections
+fn item_group(kind: ApiItemKind) -> u8 {
+    match kind {
+        ApiItemKind::Macro => 0,
+        ApiItemKind::Struct | ApiItemKind::Union | ApiItemKind::Enum | ApiItemKind::Typedef => 1,
+        ApiItemKind::Variable => 2,
+        ApiItemKind::Function => 3,
+        ApiItemKind::InlineFunction => 4,
+    }
+}
+
+/// Replace characters that are not valid in a C identifier
+fn sanitize_identifier(name: &str) -> String {
+    name.chars().map(|c| if c.is_ascii_alphanumeric() { c } else { '_' }).collect()
+}
+
+/// Top-level declaration found in a C file
+#[derive(Debug, Clone)]
+struct ParsedItem {
+    name: String,
+    kind: ApiItemKind,
+    text: String,
+    line: usize,
+    is_static: bool,
+    is_definition: bool,
+}
+
+/// Result of scanning a C file
+#[derive(Debug, Default)]
+struct ParsedFile {
+    items: Vec<ParsedItem>,
+    includes: Vec<String>,
+    exports: Vec<String>,
+}
+
+/// API Extractor
+///
+/// Determines the API a component exposes to the rest of the kernel:
+/// functions and variables passed to `EXPORT_SYMBOL`, non-static functions
+/// declared in the component's own headers, and the macros and types those
+/// headers define or the exported signatures reference.
+pub struct ApiExtractor {
+    /// Treat non-static functions declared in the component headers as API even if not exported
+    pub include_header_declarations: bool,
+}
+
+impl Default for ApiExtractor {
+    fn default() -> Self {
+        Self {
+            include_header_declarations: true,
+        }
+    }
+}
+
+impl ApiExtractor {
+    /// Create a new API extractor
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Extract the API surface of a component
+    pub fn extract(&self, component: &KernelComponent) -> Result<ApiSurface, KernelExtractorError> {
+        let mut headers = Vec::new();
+        for path in &component.header_files {
+            headers.push((path.clone(), parse_path(path)?));
+        }
+        let mut sources = Vec::new();
+        for path in &component.source_files {
+            sources.push((path.clone(), parse_path(path)?));
+        }
+        
+        let exports: HashSet<&str> = sources.iter().chain(&headers)
+            .flat_map(|(_, parsed)| parsed.exports.iter().map(String::as_str))
+            .collect();
+        let header_prototypes: HashSet<&str> = headers.iter()
+            .flat_map(|(_, parsed)| parsed.items.iter())
+            .filter(|item| item.kind == ApiItemKind::Function && !item.is_static)
+            .map(|item| item.name.as_str())
+            .collect();
+        
+        let mut items: Vec<ApiItem> = Vec::new();
+        let mut seen: HashSet<(ApiItemKind, String)> = HashSet::new();
+        let mut push = |items: &mut Vec<ApiItem>, item: ApiItem| {
+            if seen.insert((item.kind, item.name.clone())) {
+                items.push(item);
+            }
+        };
+        
+        // Everything a component header defines is part of its interface
+        for (path, parsed) in &headers {
+            for parsed_item in &parsed.items {
+                let kind = match parsed_item.kind {
+                    ApiItemKind::Function if parsed_item.is_static && parsed_item.is_definition => ApiItemKind::InlineFunction,
+                    ApiItemKind::Function | ApiItemKind::Variable => continue,
+                    kind => kind,
+                };
+                push(&mut items, ApiItem {
+                    name: parsed_item.name.clone(),
+                    kind,
+                    declaration: parsed_item.text.clone(),
+                    file: path.clone(),
+                    line: parsed_item.line,
+                    exported: exports.contains(parsed_item.name.as_str()),
+                });
+            }
+        }
+        
+        // Exported or header-declared functions and variables from the sources
+        let mut api_signatures = Vec::new();
+        for (path, parsed) in sources.iter().chain(&headers) {
+            for parsed_item in &parsed.items {
+                let exported = exports.contains(parsed_item.name.as_str());
+                let declared = self.include_header_declarations && header_prototypes.contains(parsed_item.name.as_str());
+                if parsed_item.is_static || !(exported || declared) {
+                    continue;
+                }
+                let declaration = match parsed_item.kind {
+                    ApiItemKind::Function => format!("extern {};", strip_storage(&parsed_item.text)),
+                    ApiItemKind::Variable => format!("extern {};", strip_initializer(&strip_storage(&parsed_item.text))),
+                    _ => continue,
+                };
+                api_signatures.push(declaration.clone());
+                push(&mut items, ApiItem {
+                    name: parsed_item.name.clone(),
+                    kind: parsed_item.kind,
+                    declaration,
+                    file: path.clone(),
+                    line: parsed_item.line,
+                    exported,
+                });
+            }
+        }
+        
+        // Types defined in the sources that the API signatures reference
+        let referenced: BTreeSet<String> = api_signatures.iter()
+            .flat_map(|signature| TYPE_REFERENCE_RE.captures_iter(signature).map(|caps| caps[2].to_string()).collect::<Vec<_>>())
+            .collect();
+        for (path, parsed) in &sources {
+            for parsed_item in &parsed.items {
+                let is_type = matches!(parsed_item.kind, ApiItemKind::Struct | ApiItemKind::Union | ApiItemKind::Enum);
+                if is_type && referenced.contains(&parsed_item.name) {
+                    push(&mut items, ApiItem {
+                        name: parsed_item.name.clone(),
+                        kind: parsed_item.kind,
+                        declaration: parsed_item.text.clone(),
+                        file: path.clone(),
+                        line: parsed_item.line,
+                        exported: false,
+                    });
+                }
+            }
+        }
+        
+        let defined: HashSet<&str> = items.iter()
+            .filter(|item| matches!(item.kind, ApiItemKind::Struct | ApiItemKind::Union))
+            .map(|item| item.name.as_str())
+            .collect();
+        let forward_declarations = items.iter()
+            .filter(|item| item.kind != ApiItemKind::Macro)
+            .flat_map(|item| TYPE_REFERENCE_RE.captures_iter(&item.declaration)
+                .filter(|caps| &caps[1] != "enum" && !defined.contains(&caps[2]))
+                .map(|caps| format!("{} {}", &caps[1], &caps[2]))
+                .collect::<Vec<_>>())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        
+        // Only the headers' own system includes are kept, so the generated
+        // interface does not pull in the private includes of the sources
+        let includes = headers.iter()
+            .flat_map(|(_, parsed)| parsed.includes.iter().cloned())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        
+        items.sort_by(|a, b| item_group(a.kind).cmp(&item_group(b.kind)).then((&a.file, a.line).cmp(&(&b.file, b.line))));
+        
+        Ok(ApiSurface {
+            component: component.name.clone(),
+            items,
+            includes,
+            forward_declarations,
+        })
+    }
+}
+
+/// Parse a file from disk
+fn parse_path(path: &Path) -> Result<ParsedFile, KernelExtractorError> {
+    if !path.exists() {
+        return Ok(ParsedFile::default());
+    }
+    let content = fs::read_to_string(path)
+        .map_err(|e| KernelExtractorError::ParseError(format!("Failed to read {:?}: {}", path, e)))?;
+    Ok(parse_c(&content))
+}
+
+/// Remove `static`, `extern` and `inline` qualifiers and collapse whitespace
+fn strip_storage(text: &str) -> String {
+    text.split_whitespace()
+        .filter(|word| !matches!(*word, "static" | "extern" | "inline" | "__always_inline" | "__init" | "__exit"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Remove the initializer of a variable definition
+fn strip_initializer(text: &str) -> String {
+    match text.find('=') {
+        Some(index) => text[..index].trim_end().to_string(),
+        None => text.to_string(),
+    }
+}
+
+/// Replace comments with whitespace, keeping line numbers intact
+pub(crate) fn strip_comments(source: &str) -> String {
+    let bytes = source.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match (bytes[i], bytes.get(i + 1)) {
+            (b'/', Some(b'*')) => {
+                i += 2;
+                while i < bytes.len() && !(bytes[i] == b'*' && bytes.get(i + 1) == Some(&b'/')) {
+                    if bytes[i] == b'\n' {
+                        out.push(b'\n');
+                    }
+                    i += 1;
+                }
+                i += 2;
+                out.push(b' ');
+            }
+            (b'/', Some(b'/')) => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+            }
+            (b'"' | b'\'', _) => {
+                let end = skip_literal(bytes, i);
+                out.extend_from_slice(&bytes[i..end]);
+                i = end;
+            }
+            (byte, _) => {
+                out.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Return the index just past the string or character literal starting at `start`
+pub(crate) fn skip_literal(bytes: &[u8], start: usize) -> usize {
+    let quote = bytes[start];
+    let mut i = start + 1;
+    while i < bytes.len() && bytes[i] != quote && bytes[i] != b'\n' {
+        if bytes[i] == b'\\' {
+            i += 1;
+        }
+        i += 1;
+    }
+    (i + 1).min(bytes.len())
+}
+
+/// Scan the top-level declarations of a C file
+fn parse_c(source: &str) -> ParsedFile {
+    let text = strip_comments(source);
+    let bytes = text.as_bytes();
+    let mut parsed = ParsedFile::default();
+    
+    let mut depth = 0usize;
+    let mut statement_start = 0;
+    let mut body_start = None;
+    let mut line_start = true;
+    let mut i = 0;
+    while i < bytes.len() {
+        let byte = bytes[i];
+        if depth == 0 && line_start && byte == b'#' {
+            let mut end = i;
+            while end < bytes.len() && !(bytes[end] == b'\n' && (end == 0 || bytes[end - 1] != b'\\')) {
+                end += 1;
+            }
+            parse_directive(&text[i..end], line_of(&text, i), &mut parsed);
+            i = end;
+            statement_start = end;
+            continue;
+        }
+        
+        match byte {
+            b'\n' => line_start = true,
+            b' ' | b'\t' | b'\r' => {}
+            _ => line_start = false,
+        }
+        
+        match byte {
+            b'"' | b'\'' => {
+                i = skip_literal(bytes, i);
+                continue;
+            }
+            b'{' => {
+                if depth == 0 {
+                    body_start = Some(i);
+                }
+                depth += 1;
+            }
+            b'}' if depth > 0 => {
+                depth -= 1;
+                if depth == 0 {
+                    let head = text[statement_start..body_start.unwrap_or(i)].trim();
+                    let is_type = ["struct", "union", "enum", "typedef"].iter().any(|kw| head.starts_with(kw))
+                        || head.contains('=');
+                    if !is_type && head.contains('(') {
+                        if let Some(item) = function_item(head, &text, statement_start, &text[statement_start..=i]) {
+                            parsed.items.push(item);
+                        }
+                        statement_start = i + 1;
+                    }
+                    body_start = None;
+                }
+            }
+            b';' if depth == 0 => {
+                let statement = text[statement_start..i].trim();
+                if !statement.is_empty() {
+                    classify_statement(statement, line_of(&text, statement_start + leading_ws(&text[statement_start..])), &mut parsed);
+                }
+                statement_start = i + 1;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    
+    parsed
+}
+
+/// Number of leading whitespace bytes
+pub(crate) fn leading_ws(text: &str) -> usize {
+    text.len() - text.trim_start().len()
+}
+
+/// 1-based line number of a byte offset
+pub(crate) fn line_of(text: &str, offset: usize) -> usize {
+    text[..offset.min(text.len())].matches('\n').count() + 1
+}
+
+/// Handle a preprocessor directive
+fn parse_directive(directive: &str, line: usize, parsed: &mut ParsedFile) {
+    let body = directive.trim_start_matches('#').trim_start();
+    if let Some(rest) = body.strip_prefix("include") {
+        let rest = rest.trim();
+        if rest.starts_with('<') {
+            if let Some(end) = rest.find('>') {
+                parsed.includes.push(rest[1..end].to_string());
+            }
+        }
+    } else if let Some(rest) = body.strip_prefix("define") {
+        let rest = rest.trim_start();
+        let name: String = rest.chars().take_while(|c| c.is_ascii_alphanumeric() || *c == '_').collect();
+        let value = rest[name.len()..].trim();
+        // Skip include guards
+        let is_guard = value.is_empty() && (name.ends_with("_H") || name.ends_with("_H_") || name.ends_with("_H__"));
+        if !name.is_empty() && !is_guard {
+            parsed.items.push(ParsedItem {
+                name,
+                kind: ApiItemKind::Macro,
+                text: directive.trim_end().to_string(),
+                line,
+                is_static: false,
+                is_definition: true,
+            });
+        }
+    }
+}
+
+/// Identifier immediately before the first top-level parenthesis
+pub(crate) fn declarator_name(head: &str) -> Option<String> {
+    let paren = head.find('(')?;
+    let before = head[..paren].trim_end();
+    let name: String = before.chars().rev().take_while(|c| c.is_ascii_alphanumeric() || *c == '_').collect::<Vec<_>>().into_iter().rev().collect();
+    if name.is_empty() {
+        return None;
+    }
+    // Require a return type before the name, so macro invocations are skipped
+    let prefix = before[..before.len() - name.len()].trim();
+    if prefix.is_empty() {
+        return None;
+    }
+    Some(name)
+}
+
+/// Build an item for a function definition
+fn function_item(head: &str, text: &str, statement_start: usize, full: &str) -> Option<ParsedItem> {
+    let name = declarator_name(head)?;
+    let is_static = head.split_whitespace().any(|word| word == "static");
+    let signature = head.split_whitespace().collect::<Vec<_>>().join(" ");
+    let is_inline = head.split_whitespace().any(|word| word.contains("inline"));
+    Some(ParsedItem {
+        name,
+        kind: ApiItemKind::Function,
+        text: if is_static && is_inline { full.trim().to_string() } else { signature },
+        line: line_of(text, statement_start + leading_ws(&text[statement_start..])),
+        is_static,
+        is_definition: true,
+    })
+}
+
+/// Classify a top-level statement terminated by `;`
+fn classify_statement(statement: &str, line: usize, parsed: &mut ParsedFile) {
+    if let Some(caps) = EXPORT_RE.captures(statement) {
+        parsed.exports.push(caps[1].to_string());
+        return;
+    }
+    
+    let item = |name: String, kind: ApiItemKind, text: String, is_static: bool, is_definition: bool| ParsedItem {
+        name,
+        kind,
+        text,
+        line,
+        is_static,
+        is_definition,
+    };
+    let normalized = statement.split_whitespace().collect::<Vec<_>>().join(" ");
+    
+    if statement.starts_with("typedef") {
+        let name = POINTER_DECLARATOR_RE.captures(statement)
+            .map(|caps| caps[1].to_string())
+            .or_else(|| last_identifier(statement));
+        if let Some(name) = name {
+            parsed.items.push(item(name, ApiItemKind::Typedef, format!("{};", statement.trim()), false, true));
+        }
+        return;
+    }
+    
+    if let Some(caps) = TYPE_DEFINITION_RE.captures(statement) {
+        let kind = match &caps[1] {
+            "struct" => ApiItemKind::Struct,
+            "union" => ApiItemKind::Union,
+            _ => ApiItemKind::Enum,
+        };
+        // `struct foo { ... } instance;` also defines a variable, which is not part of the type
+        let close = statement.rfind('}').unwrap_or(statement.len() - 1);
+        parsed.items.push(item(caps[2].to_string(), kind, format!("{};", statement[..=close].trim()), false, true));
+        return;
+    }
+    
+    // Forward declarations carry no API of their own
+    if FORWARD_DECLARATION_RE.is_match(&normalized) {
+        return;
+    }
+    
+    let is_static = normalized.split(' ').any(|word| word == "static");
+    let is_extern = normalized.starts_with("extern");
+    let initializer = normalized.find('=').unwrap_or(normalized.len());
+    match declarator_name(&normalized[..initializer]) {
+        // Function prototype (function pointer variables have `(*name)` declarators)
+        Some(name) if !normalized[..initializer].contains("(*") => {
+            parsed.items.push(item(name, ApiItemKind::Function, normalized, is_static, false));
+        }
+        _ => {
+            let declarator = normalized[..initializer].split('[').next().unwrap_or("");
+            let name = POINTER_DECLARATOR_RE.captures(declarator).map(|caps| caps[1].to_string()).or_else(|| last_identifier(declarator));
+            let has_type = declarator.split_whitespace().count() > 1;
+            if let (Some(name), true) = (name, has_type) {
+                parsed.items.push(item(name, ApiItemKind::Variable, normalized, is_static, !is_extern));
+            }
+        }
+    }
+}
+
+/// Last identifier of a declaration
+fn last_identifier(text: &str) -> Option<String> {
+    LAST_IDENTIFIER_RE.captures(text.trim()).map(|caps| caps[1].to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_extract_api_surface() {
+        let dir = std::env::temp_dir().join(format!("osland-api-extractor-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let header = dir.join("ring.h");
+        let source = dir.join("ring.c");
+        fs::write(&header, "#ifndef RING_H\n#define RING_H\n#include <linux/types.h>\n#define RING_MAX 64\n\nstruct ring;\nint ring_push(struct ring *r, u32 value);\nint ring_peek(struct ring *r, union ring_slot *slot);\n\nstatic inline bool ring_empty(struct ring *r)\n{\n\treturn !r;\n}\n#endif\n").unwrap();
+        fs::write(&source, "#include \"ring.h\"\n\nstruct ring_stats {\n\tu64 pushed;\n};\n\n/* internal */\nstatic void ring_grow(struct ring *r)\n{\n}\n\nint ring_push(struct ring *r, u32 value)\n{\n\tif (value) { return 0; }\n\treturn 1;\n}\n\nvoid ring_stats_get(struct ring *r, struct ring_stats *stats)\n{\n}\nEXPORT_SYMBOL_GPL(ring_stats_get);\n").unwrap();
+        
+        let mut component = KernelComponent::default();
+        component.name = "ring".to_string();
+        component.header_files.push(header);
+        component.source_files.push(source);
+        
+        let surface = ApiExtractor::new().extract(&component).unwrap();
+        let names: Vec<&str> = surface.items.iter().map(|item| item.name.as_str()).collect();
+        assert!(names.contains(&"RING_MAX"));
+        assert!(names.contains(&"ring_push"));
+        assert!(names.contains(&"ring_stats_get"));
+        assert!(names.contains(&"ring_stats"));
+        assert!(names.contains(&"ring_empty"));
+        assert!(!names.contains(&"ring_grow"));
+        assert!(!names.contains(&"RING_H"));
+        assert_eq!(surface.includes, vec!["linux/types.h".to_string()]);
+        assert_eq!(surface.forward_declarations, vec!["struct ring".to_string(), "union ring_slot".to_string()]);
+        
+        let generated = surface.to_header();
+        assert!(generated.contains("#ifndef OSLAND_RING_API_H"));
+        assert!(generated.contains("extern void ring_stats_get(struct ring *r, struct ring_stats *stats);"));
+        assert!(generated.contains("\nunion ring_slot;\n"));
+        assert!(!generated.contains("struct ring_slot"));
+        
+        fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,682 @@
+// Component Database for OSland Kernel Extractor
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::hash::Hash;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use serde::{Deserialize, Serialize};
+use crate::dbos_integration::dbos_core::{ColumnDefinition, ColumnType, IndexDefinition, TableDefinition, TableRow, TablesManager};
+use crate::kernel_extractor::{ComponentType, KernelComponent, KernelExtractorError};
+use crate::kernel_extractor::patch_manager::component_file_names;
+
+/// Table holding one row per extracted component
+pub const COMPONENTS_TABLE: &str = "components";
+
+/// Table holding the symbols defined by each component
+pub const SYMBOLS_TABLE: &str = "symbols";
+
+/// Table holding component -> component dependency edges
+pub const COMPONENT_DEPS_TABLE: &str = "component_deps";
+
+/// Symbol defined by an extracted component
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ComponentSymbol {
+    /// Symbol name
+    pub name: String,
+    
+    /// Symbol kind (function, struct, variable, ...)
+    pub kind: String,
+    
+    /// File defining the symbol
+    pub file: PathBuf,
+    
+    /// Line number (1-based)
+    pub line: usize,
+    
+    /// Whether the symbol is exported to other components
+    pub exported: bool,
+    
+    /// Whether the symbol has external linkage (not `static`)
+    pub global: bool,
+}
+
+/// Serialized form of the database, used to persist it next to the extraction output
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct DatabaseSnapshot {
+    components: Vec<HashMap<String, String>>,
+    symbols: Vec<HashMap<String, String>>,
+    component_deps: Vec<HashMap<String, String>>,
+}
+
+/// Component Database
+///
+/// Stores extraction results in DBOS tables so that the visualizer, the AI
+/// assistant and the dependency analyzer query one indexed store instead of
+/// walking the output directory.
+pub struct ComponentDatabase {
+    /// Backing DBOS tables
+    tables: Arc<TablesManager>,
+}
+
+impl ComponentDatabase {
+    /// Create a database backed by a private tables manager
+    pub fn new() -> Result<Self, KernelExtractorError> {
+        let tables = Arc::new(TablesManager::new());
+        tables.start();
+        Self::with_tables_manager(tables)
+    }
+    
+    /// Create a database on top of an existing (running) tables manager
+    pub fn with_tables_manager(tables: Arc<TablesManager>) -> Result<Self, KernelExtractorError> {
+        let database = Self { tables };
+        database.ensure_tables()?;
+        Ok(database)
+    }
+    
+    /// Get the backing tables manager
+    pub fn tables_manager(&self) -> Arc<TablesManager> {
+        self.tables.clone()
+    }
+    
+    /// Create the component tables if they do not exist yet
+    fn ensure_tables(&self) -> Result<(), KernelExtractorError> {
+        let definitions = vec![
+            table(COMPONENTS_TABLE, "Extracted kernel components", vec![
+                column("name", ColumnType::String, false, "Component name"),
+                column("component_type", ColumnType::String, false, "Component type"),
+                column("description", ColumnType::String, true, "Component description"),
+                column("architectures", ColumnType::Json, false, "Supported architectures"),
+                column("source_files", ColumnType::Json, false, "Source files"),
+                column("header_files", ColumnType::Json, false, "Header files"),
+                column("kconfig_options", ColumnType::Json, false, "Kconfig options"),
+                column("makefile_entries", ColumnType::Json, false, "Makefile entries"),
+                column("metadata", ColumnType::Json, true, "Additional metadata"),
+            ], vec!["name"], vec![("idx_components_name", vec!["name"], true), ("idx_components_type", vec!["component_type"], false)]),
+            table(SYMBOLS_TABLE, "Symbols defined by extracted components", vec![
+                column("component", ColumnType::String, false, "Defining component"),
+                column("name", ColumnType::String, false, "Symbol name"),
+                column("kind", ColumnType::String, false, "Symbol kind"),
+                column("file", ColumnType::String, false, "Defining file"),
+                column("line", ColumnType::Integer, false, "Line number"),
+                column("exported", ColumnType::Boolean, false, "Exported to other components"),
+                column("global", ColumnType::Boolean, false, "External linkage"),
+            ], vec!["component", "name"], vec![("idx_symbols_name", vec!["name"], false), ("idx_symbols_component", vec!["component"], false)]),
+            table(COMPONENT_DEPS_TABLE, "Dependencies between extracted components", vec![
+                column("component", ColumnType::String, false, "Dependent component"),
+                column("depends_on", ColumnType::String, false, "Required component"),
+            ], vec!["component", "depends_on"], vec![("idx_deps_component", vec!["component"], false), ("idx_deps_depends_on", vec!["depends_on"], false)]),
+        ];
+        
+        for definition in definitions {
+            let exists = self.tables.get_table(&definition.name).map_err(KernelExtractorError::ExtractionError)?;
+            if exists.is_none() {
+                self.tables.create_table(definition).map_err(KernelExtractorError::ExtractionError)?;
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /// Insert a component, merging it into an existing row with the same name
+    ///
+    /// Parsers emit one component per file, so the files, architectures and
+    /// dependencies of all parts of a component accumulate in a single row.
+    pub fn insert_component(&self, component: &KernelComponent) -> Result<String, KernelExtractorError> {
+        let merged = match self.get_component(&component.name)? {
+            Some(mut existing) => {
+                merge_component(&mut existing, std::iter::once(component));
+                existing
+            }
+            None => component.clone(),
+        };
+        
+        let values = component_values(&merged)?;
+        let row_id = match self.component_row(&merged.name)? {
+            Some(row) => {
+                self.tables.update_row(COMPONENTS_TABLE, &row.row_id, values).map_err(KernelExtractorError::ExtractionError)?;
+                row.row_id
+            }
+            None => self.tables.insert_row(COMPONENTS_TABLE, values).map_err(KernelExtractorError::ExtractionError)?,
+        };
+        
+        // Dependency edges
+        let existing_deps: HashSet<String> = self.dependencies_of(&merged.name)?.into_iter().collect();
+        for dependency in merged.dependencies.iter().filter(|dep| !existing_deps.contains(*dep) && **dep != merged.name) {
+            self.tables.insert_row(COMPONENT_DEPS_TABLE, HashMap::from([
+                ("component".to_string(), merged.name.clone()),
+                ("depends_on".to_string(), dependency.clone()),
+            ])).map_err(KernelExtractorError::ExtractionError)?;
+        }
+        
+        // Symbols of the newly added files
+        let known_files: HashSet<PathBuf> = self.symbols_of(&merged.name)?.into_iter().map(|symbol| symbol.file).collect();
+        for file in component.source_files.iter().chain(&component.header_files) {
+            if known_files.contains(file) {
+                continue;
+            }
+            for symbol in scan_symbols(file) {
+                self.insert_symbol(&merged.name, &symbol)?;
+            }
+        }
+        
+        Ok(row_id)
+    }
+    
+    /// Insert components, merging the parts of each component before writing it
+    ///
+    /// Same result as `insert_component` for every part, but each component row,
+    /// its dependencies and its symbols are written once.
+    pub fn insert_components(&self, components: &[KernelComponent]) -> Result<(), KernelExtractorError> {
+        let mut parts: BTreeMap<&str, Vec<&KernelComponent>> = BTreeMap::new();
+        for component in components {
+            parts.entry(component.name.as_str()).or_default().push(component);
+        }
+        for parts in parts.into_values() {
+            let mut merged = parts[0].clone();
+            merge_component(&mut merged, parts[1..].iter().copied());
+            self.insert_component(&merged)?;
+        }
+        Ok(())
+    }
+    
+    /// Replace a stored component, re-scanning its symbols
+    pub fn update_component(&self, component: &KernelComponent) -> Result<(), KernelExtractorError> {
+        let row = self.component_row(&component.name)?
+            .ok_or_else(|| KernelExtractorError::ExtractionError(format!("Component '{}' not found", component.name)))?;
+        self.tables.update_row(COMPONENTS_TABLE, &row.row_id, component_values(component)?)
+            .map_err(KernelExtractorError::ExtractionError)?;
+        
+        let stale = self.tables.query_rows(SYMBOLS_TABLE, HashMap::from([("component".to_string(), component.name.clone())]))
+            .map_err(KernelExtractorError::ExtractionError)?;
+        for symbol_row in stale {
+            self.tables.delete_row(SYMBOLS_TABLE, &symbol_row.row_id).map_err(KernelExtractorError::ExtractionError)?;
+        }
+        for file in component.source_files.iter().chain(&component.header_files) {
+            for symbol in scan_symbols(file) {
+                self.insert_symbol(&component.name, &symbol)?;
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /// Set a key of a component's metadata object
+    pub fn set_metadata(&self, name: &str, key: &str, value: serde_json::Value) -> Result<(), KernelExtractorError> {
+        let row = self.component_row(name)?
+            .ok_or_else(|| KernelExtractorError::ExtractionError(format!("Component '{}' not found", name)))?;
+        let mut metadata = row_to_component(&row)?.metadata;
+        if !metadata.is_object() {
+            metadata = serde_json::Value::Object(serde_json::Map::new());
+        }
+        metadata[key] = value;
+        
+        let json = serde_json::to_string(&metadata)
+            .map_err(|e| KernelExtractorError::ExtractionError(format!("Failed to serialize metadata for component {}: {}", name, e)))?;
+        self.tables.update_row(COMPONENTS_TABLE, &row.row_id, HashMap::from([("metadata".to_string(), json)]))
+            .map_err(KernelExtractorError::ExtractionError)
+    }
+    
+    /// Insert a symbol row for a component
+    pub fn insert_symbol(&self, component: &str, symbol: &ComponentSymbol) -> Result<String, KernelExtractorError> {
+        self.tables.insert_row(SYMBOLS_TABLE, HashMap::from([
+            ("component".to_string(), component.to_string()),
+            ("name".to_string(), symbol.name.clone()),
+            ("kind".to_string(), symbol.kind.clone()),
+            ("file".to_string(), symbol.file.to_string_lossy().to_string()),
+            ("line".to_string(), symbol.line.to_string()),
+            ("exported".to_string(), symbol.exported.to_string()),
+            ("global".to_string(), symbol.global.to_string()),
+        ])).map_err(KernelExtractorError::ExtractionError)
+    }
+    
+    /// Get the row of a component by name
+    fn component_row(&self, name: &str) -> Result<Option<TableRow>, KernelExtractorError> {
+        let rows = self.tables.query_rows(COMPONENTS_TABLE, HashMap::from([("name".to_string(), name.to_string())]))
+            .map_err(KernelExtractorError::ExtractionError)?;
+        Ok(rows.into_iter().next())
+    }
+    
+    /// Get a component by name
+    pub fn get_component(&self, name: &str) -> Result<Option<KernelComponent>, KernelExtractorError> {
+        match self.component_row(name)? {
+            Some(row) => {
+                let mut component = row_to_component(&row)?;
+                component.dependencies = self.dependencies_of(name)?;
+                Ok(Some(component))
+            }
+            None => Ok(None),
+        }
+    }
+    
+    /// Get all components, ordered by name
+    pub fn all_components(&self) -> Result<Vec<KernelComponent>, KernelExtractorError> {
+        let rows = self.tables.get_all_rows(COMPONENTS_TABLE).map_err(KernelExtractorError::ExtractionError)?;
+        let mut components = rows.iter().map(row_to_component).collect::<Result<Vec<_>, _>>()?;
+        for component in &mut components {
+            component.dependencies = self.dependencies_of(&component.name)?;
+        }
+        components.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(components)
+    }
+    
+    /// Get all components of a given type
+    pub fn components_of_type(&self, component_type: &ComponentType) -> Result<Vec<KernelComponent>, KernelExtractorError> {
+        let rows = self.tables.query_rows(COMPONENTS_TABLE, HashMap::from([("component_type".to_string(), type_name(component_type))]))
+            .map_err(KernelExtractorError::ExtractionError)?;
+        let mut components = rows.iter().map(row_to_component).collect::<Result<Vec<_>, _>>()?;
+        for component in &mut components {
+            component.dependencies = self.dependencies_of(&component.name)?;
+        }
+        Ok(components)
+    }
+    
+    /// Get the symbols defined by a component
+    pub fn symbols_of(&self, component: &str) -> Result<Vec<ComponentSymbol>, KernelExtractorError> {
+        let rows = self.tables.query_rows(SYMBOLS_TABLE, HashMap::from([("component".to_string(), component.to_string())]))
+            .map_err(KernelExtractorError::ExtractionError)?;
+        let mut symbols: Vec<ComponentSymbol> = rows.iter().map(row_to_symbol).collect();
+        symbols.sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+        Ok(symbols)
+    }
+    
+    /// Find the components defining a symbol
+    pub fn find_symbol(&self, name: &str) -> Result<Vec<String>, KernelExtractorError> {
+        let mut components = Vec::new();
+        self.tables.for_each_row(SYMBOLS_TABLE, &HashMap::from([("name".to_string(), name.to_string())]), |row| {
+            components.extend(row.values.get("component").cloned());
+        }).map_err(KernelExtractorError::ExtractionError)?;
+        components.sort();
+        components.dedup();
+        Ok(components)
+    }
+    
+    /// Get the components a component depends on
+    pub fn dependencies_of(&self, component: &str) -> Result<Vec<String>, KernelExtractorError> {
+        self.dependency_column(("component", component), "depends_on")
+    }
+    
+    /// Get every symbol with the component defining it
+    pub fn all_symbols(&self) -> Result<Vec<(String, ComponentSymbol)>, KernelExtractorError> {
+        let rows = self.tables.get_all_rows(SYMBOLS_TABLE).map_err(KernelExtractorError::ExtractionError)?;
+        Ok(rows.iter()
+            .map(|row| (row.values.get("component").cloned().unwrap_or_default(), row_to_symbol(row)))
+            .collect())
+    }
+    
+    /// Get the components depending on a component
+    pub fn dependents_of(&self, component: &str) -> Result<Vec<String>, KernelExtractorError> {
+        self.dependency_column(("depends_on", component), "component")
+    }
+    
+    fn dependency_column(&self, condition: (&str, &str), column: &str) -> Result<Vec<String>, KernelExtractorError> {
+        let mut values = Vec::new();
+        self.tables.for_each_row(COMPONENT_DEPS_TABLE, &HashMap::from([(condition.0.to_string(), condition.1.to_string())]), |row| {
+            values.extend(row.values.get(column).cloned());
+        }).map_err(KernelExtractorError::ExtractionError)?;
+        values.sort();
+        values.dedup();
+        Ok(values)
+    }
+    
+    /// Count components per type
+    pub fn component_counts(&self) -> Result<BTreeMap<String, usize>, KernelExtractorError> {
+        let mut counts = BTreeMap::new();
+        self.tables.for_each_row(COMPONENTS_TABLE, &HashMap::new(), |row| {
+            if let Some(component_type) = row.values.get("component_type") {
+                match counts.get_mut(component_type) {
+                    Some(count) => *count += 1,
+                    None => {
+                        counts.insert(component_type.clone(), 1);
+                    }
+                }
+            }
+        }).map_err(KernelExtractorError::ExtractionError)?;
+        Ok(counts)
+    }
+    
+    /// Save the component tables to a JSON file
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), KernelExtractorError> {
+        let rows = |table: &str| -> Result<Vec<HashMap<String, String>>, KernelExtractorError> {
+            Ok(self.tables.get_all_rows(table).map_err(KernelExtractorError::ExtractionError)?
+                .into_iter()
+                .map(|row| row.values)
+                .collect())
+        };
+        let snapshot = DatabaseSnapshot {
+            components: rows(COMPONENTS_TABLE)?,
+            symbols: rows(SYMBOLS_TABLE)?,
+            component_deps: rows(COMPONENT_DEPS_TABLE)?,
+        };
+        
+        let json = serde_json::to_string_pretty(&snapshot)
+            .map_err(|e| KernelExtractorError::ExtractionError(format!("Failed to serialize component database: {}", e)))?;
+        fs::write(path, json)
+            .map_err(|e| KernelExtractorError::OutputDirError(format!("Failed to write component database: {}", e)))
+    }
+    
+    /// Load component tables previously written by `save`
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, KernelExtractorError> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| KernelExtractorError::SourceDirError(format!("Failed to read component database: {}", e)))?;
+        let snapshot: DatabaseSnapshot = serde_json::from_str(&content)
+            .map_err(|e| KernelExtractorError::ParseError(format!("Failed to parse component database: {}", e)))?;
+        
+        let database = Self::new()?;
+        for (table, rows) in [(COMPONENTS_TABLE, snapshot.components), (SYMBOLS_TABLE, snapshot.symbols), (COMPONENT_DEPS_TABLE, snapshot.component_deps)] {
+            for values in rows {
+                database.tables.insert_row(table, values).map_err(KernelExtractorError::ExtractionError)?;
+            }
+        }
+        Ok(database)
+    }
+    
+    /// Export the database to the legacy directory layout
+    ///
+    /// Writes `components/<name>/` with copies of every file, laid out the way the
+    /// patch manager tracks them, and, when `include_metadata` is set,
+    /// `metadata/<name>.json` and `extraction_summary.json`.
+    pub fn export_to_directory<P: AsRef<Path>>(&self, output_dir: P, include_metadata: bool) -> Result<(), KernelExtractorError> {
+        let output_dir = output_dir.as_ref();
+        let components_dir = output_dir.join("components");
+        let metadata_dir = output_dir.join("metadata");
+        for dir in [&components_dir, &metadata_dir] {
+            fs::create_dir_all(dir)
+                .map_err(|e| KernelExtractorError::OutputDirError(format!("Failed to create directory {:?}: {}", dir, e)))?;
+        }
+        
+        let components = self.all_components()?;
+        for component in &components {
+            let component_dir = components_dir.join(&component.name);
+            fs::create_dir_all(&component_dir)
+                .map_err(|e| KernelExtractorError::OutputDirError(format!("Failed to create component directory for {}: {}", component.name, e)))?;
+            
+            let files: Vec<PathBuf> = component.source_files.iter().chain(&component.header_files).cloned().collect();
+            for (file, name) in component_file_names(&files) {
+                if !file.exists() {
+                    continue;
+                }
+                let target = component_dir.join(name);
+                if let Some(parent) = target.parent() {
+                    fs::create_dir_all(parent)
+                        .map_err(|e| KernelExtractorError::OutputDirError(format!("Failed to create directory {:?}: {}", parent, e)))?;
+                }
+                fs::copy(file, &target)
+                    .map_err(|e| KernelExtractorError::ExtractionError(format!("Failed to copy file {:?}: {}", file, e)))?;
+            }
+            
+            if !include_metadata {
+                continue;
+            }
+            let metadata_json = serde_json::to_string_pretty(component)
+                .map_err(|e| KernelExtractorError::ExtractionError(format!("Failed to serialize metadata for component {}: {}", component.name, e)))?;
+            // Namespaced components (`<origin>/<name>`) get one metadata directory per origin
+            let metadata_file = metadata_dir.join(format!("{}.json", component.name));
+            if let Some(parent) = metadata_file.parent() {
+                fs::create_dir_all(parent)
+                    .map_err(|e| KernelExtractorError::OutputDirError(format!("Failed to create directory {:?}: {}", parent, e)))?;
+            }
+            fs::write(metadata_file, metadata_json)
+                .map_err(|e| KernelExtractorError::ExtractionError(format!("Failed to write metadata file for component {}: {}", component.name, e)))?;
+        }
+        
+        if !include_metadata {
+            return Ok(());
+        }
+        let summary = serde_json::json!({
+            "total_components": components.len(),
+            "components_by_type": self.component_counts()?,
+            "extraction_time": chrono::Utc::now().to_rfc3339(),
+        });
+        let summary_json = serde_json::to_string_pretty(&summary)
+            .map_err(|e| KernelExtractorError::ExtractionError(format!("Failed to serialize summary: {}", e)))?;
+        fs::write(output_dir.join("extraction_summary.json"), summary_json)
+            .map_err(|e| KernelExtractorError::ExtractionError(format!("Failed to write summary file: {}", e)))?;
+        
+        Ok(())
+    }
+}
+
+/// Build a table definition
+fn table(name: &str, description: &str, columns: Vec<ColumnDefinition>, primary_key: Vec<&str>, indexes: Vec<(&str, Vec<&str>, bool)>) -> TableDefinition {
+    let now = chrono::Utc::now().timestamp() as u64;
+    TableDefinition {
+        name: name.to_string(),
+        columns,
+        primary_key: primary_key.into_iter().map(String::from).collect(),
+        indexes: indexes.into_iter().map(|(name, columns, unique)| IndexDefinition {
+            name: name.to_string(),
+            columns: columns.into_iter().map(String::from).collect(),
+            unique,
+        }).collect(),
+        description: description.to_string(),
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+/// Build a column definition
+fn column(name: &str, column_type: ColumnType, nullable: bool, description: &str) -> ColumnDefinition {
+    ColumnDefinition {
+        name: name.to_string(),
+        column_type,
+        nullable,
+        default_value: None,
+        description: description.to_string(),
+    }
+}
+
+/// Merge parts of a component into it, keeping the first description, type and metadata
+fn merge_component<'a>(component: &mut KernelComponent, parts: impl Iterator<Item = &'a KernelComponent>) {
+    for part in parts {
+        component.source_files.extend(part.source_files.iter().cloned());
+        component.header_files.extend(part.header_files.iter().cloned());
+        component.dependencies.extend(part.dependencies.iter().cloned());
+        component.kconfig_options.extend(part.kconfig_options.iter().cloned());
+        component.makefile_entries.extend(part.makefile_entries.iter().cloned());
+        for architecture in &part.architecture {
+            if !component.architecture.contains(architecture) {
+                component.architecture.push(architecture.clone());
+            }
+        }
+        if component.description.is_none() {
+            component.description = part.description.clone();
+        }
+        if component.component_type == ComponentType::Other {
+            component.component_type = part.component_type.clone();
+        }
+        if component.metadata.is_null() {
+            component.metadata = part.metadata.clone();
+        }
+    }
+    dedup_in_order(&mut component.source_files);
+    dedup_in_order(&mut component.header_files);
+    dedup_in_order(&mut component.dependencies);
+    dedup_in_order(&mut component.kconfig_options);
+    dedup_in_order(&mut component.makefile_entries);
+}
+
+/// Remove repeated items, keeping the first occurrence
+fn dedup_in_order<T: Clone + Eq + Hash>(items: &mut Vec<T>) {
+    let mut seen = HashSet::new();
+    items.retain(|item| seen.insert(item.clone()));
+}
+
+/// Name of a component type as stored in the `component_type` column
+pub fn type_name(component_type: &ComponentType) -> String {
+    serde_json::to_value(component_type)
+        .ok()
+        .and_then(|value| value.as_str().map(String::from))
+        .unwrap_or_else(|| format!("{:?}", component_type))
+}
+
+/// Convert a component into column values
+fn component_values(component: &KernelComponent) -> Result<HashMap<String, String>, KernelExtractorError> {
+    let json = |value: serde_json::Result<String>| value
+        .map_err(|e| KernelExtractorError::ExtractionError(format!("Failed to serialize component {}: {}", component.name, e)));
+    
+    let mut values = HashMap::from([
+        ("name".to_string(), component.name.clone()),
+        ("component_type".to_string(), type_name(&component.component_type)),
+        ("architectures".to_string(), json(serde_json::to_string(&component.architecture))?),
+        ("source_files".to_string(), json(serde_json::to_string(&component.source_files))?),
+        ("header_files".to_string(), json(serde_json::to_string(&component.header_files))?),
+        ("kconfig_options".to_string(), json(serde_json::to_string(&component.kconfig_options))?),
+        ("makefile_entries".to_string(), json(serde_json::to_string(&component.makefile_entries))?),
+        ("metadata".to_string(), json(serde_json::to_string(&component.metadata))?),
+    ]);
+    if let Some(description) = &component.description {
+        values.insert("description".to_string(), description.clone());
+    }
+    Ok(values)
+}
+
+/// Convert a `components` row back into a component (without its dependencies)
+fn row_to_component(row: &TableRow) -> Result<KernelComponent, KernelExtractorError> {
+    let field = |name: &str| row.values.get(name).map(String::as_str).unwrap_or("null");
+    let parse_err = |e: serde_json::Error| KernelExtractorError::ParseError(format!("Corrupt component row {}: {}", row.row_id, e));
+    let list = |name: &str| -> Result<serde_json::Value, KernelExtractorError> {
+        match field(name) {
+            "null" => Ok(serde_json::Value::Array(Vec::new())),
+            raw => serde_json::from_str(raw).map_err(parse_err),
+        }
+    };
+    
+    Ok(KernelComponent {
+        name: field("name").to_string(),
+        component_type: serde_json::from_value(serde_json::Value::String(field("component_type").to_string()))
+            .unwrap_or(ComponentType::Other),
+        source_files: serde_json::from_value(list("source_files")?).map_err(parse_err)?,
+        header_files: serde_json::from_value(list("header_files")?).map_err(parse_err)?,
+        dependencies: Vec::new(),
+        description: row.values.get("description").cloned(),
+        architecture: serde_json::from_value(list("architectures")?).map_err(parse_err)?,
+        kconfig_options: serde_json::from_value(list("kconfig_options")?).map_err(parse_err)?,
+        makefile_entries: serde_json::from_value(list("makefile_entries")?).map_err(parse_err)?,
+        metadata: serde_json::from_str(field("metadata")).map_err(parse_err)?,
+    })
+}
+
+/// Convert a `symbols` row into a symbol
+fn row_to_symbol(row: &TableRow) -> ComponentSymbol {
+    let field = |name: &str| row.values.get(name).cloned().unwrap_or_default();
+    ComponentSymbol {
+        name: field("name"),
+        kind: field("kind"),
+        file: PathBuf::from(field("file")),
+        line: field("line").parse().unwrap_or(0),
+        exported: field("exported") == "true",
+        global: field("global") != "false",
+    }
+}
+
+/// Scan a C source or header file for the symbols it defines
+///
+/// This is a line-based scan: functions defined at file scope, struct
+/// definitions, and symbols passed to `EXPORT_SYMBOL`/`EXPORT_SYMBOL_GPL`.
+pub fn scan_symbols(path: &Path) -> Vec<ComponentSymbol> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(_) => return Vec::new(),
+    };
+    
+    let function_re = regex::Regex::new(r"^(?:static\s+)?(?:inline\s+)?[A-Za-z_][\w\s\*]*?\b([A-Za-z_]\w*)\s*\([^;]*$").unwrap();
+    let struct_re = regex::Regex::new(r"^struct\s+([A-Za-z_]\w*)\s*\{").unwrap();
+    let export_re = regex::Regex::new(r"^EXPORT_SYMBOL(?:_GPL)?\s*\(\s*([A-Za-z_]\w*)\s*\)").unwrap();
+    
+    let mut symbols: Vec<ComponentSymbol> = Vec::new();
+    let mut exported = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        let symbol = |name: &str, kind: &str| ComponentSymbol {
+            name: name.to_string(),
+            kind: kind.to_string(),
+            file: path.to_path_buf(),
+            line: index + 1,
+            exported: false,
+            global: !line.starts_with("static"),
+        };
+        
+        if let Some(caps) = export_re.captures(line) {
+            exported.push(caps[1].to_string());
+        } else if let Some(caps) = struct_re.captures(line) {
+            symbols.push(symbol(&caps[1], "struct"));
+        } else if let Some(caps) = function_re.captures(line) {
+            let name = &caps[1];
+            let is_keyword = ["if", "for", "while", "switch", "return", "sizeof"].contains(&name);
+            if !is_keyword && !line.starts_with(char::is_whitespace) && !line.trim_start().starts_with('#') {
+                symbols.push(symbol(name, "function"));
+            }
+        }
+    }
+    
+    for symbol in &mut symbols {
+        symbol.exported = exported.contains(&symbol.name);
+    }
+    symbols
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_components_merge_and_query() {
+        let dir = std::env::temp_dir().join(format!("osland-component-db-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let source = dir.join("e1000_main.c");
+        fs::write(&source, "static int helper(void)\n{\n}\n\nint e1000_probe(struct pci_dev *pdev)\n{\n}\nEXPORT_SYMBOL(e1000_probe);\n").unwrap();
+        
+        let database = ComponentDatabase::new().unwrap();
+        let mut component = KernelComponent::default();
+        component.name = "e1000".to_string();
+        component.component_type = ComponentType::Driver;
+        component.source_files.push(source.clone());
+        component.dependencies.push("pci".to_string());
+        database.insert_component(&component).unwrap();
+        
+        // A second file of the same component is merged into the same row
+        let mut header = KernelComponent::default();
+        header.name = "e1000".to_string();
+        header.header_files.push(dir.join("e1000.h"));
+        database.insert_component(&header).unwrap();
+        
+        let stored = database.get_component("e1000").unwrap().unwrap();
+        assert_eq!(stored.component_type, ComponentType::Driver);
+        assert_eq!(stored.header_files.len(), 1);
+        assert_eq!(database.components_of_type(&ComponentType::Driver).unwrap().len(), 1);
+        assert_eq!(database.dependents_of("pci").unwrap(), vec!["e1000".to_string()]);
+        
+        let symbols = database.symbols_of("e1000").unwrap();
+        assert_eq!(symbols.len(), 2);
+        assert!(symbols.iter().any(|s| s.name == "e1000_probe" && s.exported));
+        assert_eq!(database.find_symbol("helper").unwrap(), vec!["e1000".to_string()]);
+        
+        fs::remove_dir_all(&dir).ok();
+    }
+    
+    #[test]
+    fn test_insert_components_merges_parts_once() {
+        let parts: Vec<KernelComponent> = (0..3).map(|index| KernelComponent {
+            name: if index == 1 { "ext4".to_string() } else { "vfs".to_string() },
+            source_files: vec![
+                PathBuf::from(format!("/nonexistent/part{}.c", index)),
+                PathBuf::from("/nonexistent/shared.c"),
+            ],
+            dependencies: vec!["mm".to_string()],
+            ..Default::default()
+        }).collect();
+        
+        let database = ComponentDatabase::new().unwrap();
+        database.insert_components(&parts).unwrap();
+        let vfs = database.get_component("vfs").unwrap().unwrap();
+        assert_eq!(vfs.source_files, vec![
+            PathBuf::from("/nonexistent/part0.c"),
+            PathBuf::from("/nonexistent/shared.c"),
+            PathBuf::from("/nonexistent/part2.c"),
+        ]);
+        assert_eq!(database.all_components().unwrap().len(), 2);
+        assert_eq!(database.dependents_of("mm").unwrap(), vec!["ext4".to_string(), "vfs".to_string()]);
+    }
+}
@@ -0,0 +1,512 @@
+// Dead Code Elimination for OSland Kernel Extractor
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+use serde::{Deserialize, Serialize};
+use crate::kernel_extractor::api_extractor::{declarator_name, leading_ws, line_of, skip_literal, strip_comments};
+use crate::kernel_extractor::{KernelComponent, KernelExtractorError};
+
+static IDENTIFIER_RE: LazyLock<regex::Regex> = LazyLock::new(|| regex::Regex::new(r"[A-Za-z_]\w*").unwrap());
+static MAKEFILE_RULE_RE: LazyLock<regex::Regex> = LazyLock::new(|| regex::Regex::new(r"\$\((CONFIG_\w+)\)\s*[+:]?=\s*(.*)$").unwrap());
+static CONDITION_TERM_RE: LazyLock<regex::Regex> = LazyLock::new(|| regex::Regex::new(r"^(!)?\s*(?:defined\s*\(?\s*|IS_ENABLED\s*\(\s*|IS_BUILTIN\s*\(\s*|IS_REACHABLE\s*\(\s*)?([A-Za-z_]\w*|\d+)\s*\)?$").unwrap());
+
+/// Dead code elimination settings
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DeadCodeConfig {
+    /// Enabled Kconfig symbols of the target (with or without the `CONFIG_` prefix)
+    #[serde(default)]
+    pub features: Vec<String>,
+    
+    /// Additional functions that must be kept
+    #[serde(default)]
+    pub roots: Vec<String>,
+    
+    /// Keep every non-static function, even if nothing in the component references it
+    #[serde(default)]
+    pub keep_external_functions: bool,
+}
+
+/// Pruning result for a single file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrunedFile {
+    /// Original file path
+    pub path: PathBuf,
+    
+    /// Original size in bytes
+    pub original_size: u64,
+    
+    /// Size after pruning in bytes
+    pub pruned_size: u64,
+    
+    /// Functions removed because they are unreachable
+    pub removed_functions: Vec<String>,
+    
+    /// Whether the whole file is compiled out for the feature set
+    pub removed: bool,
+}
+
+/// Dead code elimination report for a component
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DeadCodeReport {
+    /// Component name
+    pub component: String,
+    
+    /// Per-file results
+    pub files: Vec<PrunedFile>,
+}
+
+impl DeadCodeReport {
+    /// Total size before pruning
+    pub fn original_size(&self) -> u64 {
+        self.files.iter().map(|file| file.original_size).sum()
+    }
+    
+    /// Total size after pruning
+    pub fn pruned_size(&self) -> u64 {
+        self.files.iter().map(|file| file.pruned_size).sum()
+    }
+    
+    /// Bytes saved
+    pub fn savings(&self) -> u64 {
+        self.original_size().saturating_sub(self.pruned_size())
+    }
+    
+    /// Bytes saved as a percentage of the original size
+    pub fn savings_percent(&self) -> f64 {
+        match self.original_size() {
+            0 => 0.0,
+            original => self.savings() as f64 * 100.0 / original as f64,
+        }
+    }
+    
+    /// One-line human readable summary
+    pub fn summary(&self) -> String {
+        let removed_functions: usize = self.files.iter().map(|file| file.removed_functions.len()).sum();
+        let removed_files = self.files.iter().filter(|file| file.removed).count();
+        format!(
+            "{}: {} -> {} bytes ({:.1}% smaller), {} functions and {} files removed",
+            self.component,
+            self.original_size(),
+            self.pruned_size(),
+            self.savings_percent(),
+            removed_functions,
+            removed_files
+        )
+    }
+}
+
+/// Pruned component: the report plus the new file contents
+#[derive(Debug, Clone, Default)]
+pub struct PrunedComponent {
+    /// Pruning report
+    pub report: DeadCodeReport,
+    
+    /// Pruned contents of the files that are kept, keyed by original path
+    pub contents: Vec<(PathBuf, String)>,
+}
+
+impl PrunedComponent {
+    /// Write the pruned files into a directory, returning the new paths
+    pub fn write<P: AsRef<Path>>(&self, dir: P) -> Result<Vec<PathBuf>, KernelExtractorError> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)
+            .map_err(|e| KernelExtractorError::OutputDirError(format!("Failed to create directory {:?}: {}", dir, e)))?;
+        
+        let mut paths = Vec::new();
+        for (original, content) in &self.contents {
+            let file_name = original.file_name()
+                .ok_or_else(|| KernelExtractorError::ExtractionError(format!("Invalid file path {:?}", original)))?;
+            let path = dir.join(file_name);
+            fs::write(&path, content)
+                .map_err(|e| KernelExtractorError::ExtractionError(format!("Failed to write pruned file {:?}: {}", path, e)))?;
+            paths.push(path);
+        }
+        Ok(paths)
+    }
+}
+
+/// Function definition with its line span
+#[derive(Debug, Clone)]
+struct FunctionDefinition {
+    name: String,
+    is_static: bool,
+    start_line: usize,
+    end_line: usize,
+    body: String,
+}
+
+/// Result of scanning a file for function definitions
+#[derive(Debug, Default)]
+struct FileScan {
+    functions: Vec<FunctionDefinition>,
+    prototypes: Vec<String>,
+    /// Identifiers referenced outside function bodies (initializers, EXPORT_SYMBOL, module_init, macros)
+    data_references: HashSet<String>,
+}
+
+/// Tri-state result of a preprocessor condition
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Condition {
+    True,
+    False,
+    Unknown,
+}
+
+/// Dead Code Eliminator
+///
+/// Removes code a component does not need for a target feature set: files
+/// whose Makefile rule depends on a disabled Kconfig symbol, `#if` blocks
+/// guarded by disabled symbols, and functions not reachable in the call
+/// graph from the component's exported symbols, init/exit hooks and
+/// operation tables. Conditions it cannot evaluate are kept.
+pub struct DeadCodeEliminator {
+    /// Enabled features, normalized to `CONFIG_*`
+    features: HashSet<String>,
+    
+    /// Elimination settings
+    config: DeadCodeConfig,
+}
+
+impl DeadCodeEliminator {
+    /// Create a new eliminator
+    pub fn new(config: DeadCodeConfig) -> Self {
+        let features = config.features.iter()
+            .map(|feature| if feature.starts_with("CONFIG_") { feature.clone() } else { format!("CONFIG_{}", feature) })
+            .collect();
+        Self { features, config }
+    }
+    
+    /// Prune a component
+    pub fn prune(&self, component: &KernelComponent) -> Result<PrunedComponent, KernelExtractorError> {
+        let disabled_objects = self.disabled_objects(&component.makefile_entries);
+        
+        // Evaluate conditionals and scan every enabled file
+        let mut files: Vec<(PathBuf, String, Vec<bool>, Option<FileScan>, bool)> = Vec::new();
+        for path in component.source_files.iter().chain(&component.header_files) {
+            if !path.exists() {
+                continue;
+            }
+            let content = fs::read_to_string(path)
+                .map_err(|e| KernelExtractorError::ParseError(format!("Failed to read {:?}: {}", path, e)))?;
+            let stem = path.file_stem().and_then(|stem| stem.to_str()).unwrap_or("");
+            let is_source = path.extension().map_or(false, |ext| ext == "c");
+            let removed = is_source && disabled_objects.contains(stem);
+            let active = self.active_lines(&content);
+            let scan = if is_source && !removed { Some(scan_functions(&mask(&content, &active))) } else { None };
+            files.push((path.clone(), content, active, scan, removed));
+        }
+        
+        // Header functions and prototypes are callable from outside the component
+        let mut roots: HashSet<String> = self.config.roots.iter().cloned().collect();
+        for (path, content, active, _, _) in &files {
+            if path.extension().map_or(false, |ext| ext == "h") {
+                let header_scan = scan_functions(&mask(content, active));
+                roots.extend(header_scan.prototypes);
+                roots.extend(header_scan.data_references);
+                roots.extend(header_scan.functions.into_iter().map(|function| function.name));
+            }
+        }
+        
+        let definitions: Vec<&FunctionDefinition> = files.iter()
+            .filter_map(|(_, _, _, scan, _)| scan.as_ref())
+            .flat_map(|scan| scan.functions.iter())
+            .collect();
+        let defined: HashSet<&str> = definitions.iter().map(|function| function.name.as_str()).collect();
+        
+        for scan in files.iter().filter_map(|(_, _, _, scan, _)| scan.as_ref()) {
+            roots.extend(scan.data_references.iter().filter(|name| defined.contains(name.as_str())).cloned());
+        }
+        if self.config.keep_external_functions {
+            roots.extend(definitions.iter().filter(|function| !function.is_static).map(|function| function.name.clone()));
+        }
+        
+        // Call graph edges: every identifier in a body naming a defined function
+        let mut calls: HashMap<&str, BTreeSet<&str>> = HashMap::new();
+        for function in &definitions {
+            let callees = calls.entry(function.name.as_str()).or_default();
+            for identifier in IDENTIFIER_RE.find_iter(&function.body) {
+                if let Some(callee) = defined.get(identifier.as_str()) {
+                    callees.insert(*callee);
+                }
+            }
+        }
+        
+        let mut visited: HashSet<&str> = HashSet::new();
+        let mut queue: VecDeque<&str> = roots.iter().filter_map(|root| defined.get(root.as_str()).copied()).collect();
+        while let Some(function) = queue.pop_front() {
+            if visited.insert(function) {
+                queue.extend(calls.get(function).into_iter().flatten().copied());
+            }
+        }
+        let reachable: HashSet<String> = visited.into_iter().map(String::from).collect();
+        
+        // Build the pruned contents
+        let mut pruned = PrunedComponent {
+            report: DeadCodeReport {
+                component: component.name.clone(),
+                files: Vec::new(),
+            },
+            contents: Vec::new(),
+        };
+        for (path, content, active, scan, removed) in files {
+            let mut keep = active;
+            let mut removed_functions = Vec::new();
+            if let Some(scan) = &scan {
+                for function in scan.functions.iter().filter(|function| !reachable.contains(function.name.as_str())) {
+                    for line in function.start_line..=function.end_line {
+                        if let Some(flag) = keep.get_mut(line - 1) {
+                            *flag = false;
+                        }
+                    }
+                    removed_functions.push(function.name.clone());
+                }
+            }
+            
+            let new_content = if removed {
+                String::new()
+            } else {
+                let mut lines: Vec<&str> = content.lines().zip(&keep).filter(|(_, keep)| **keep).map(|(line, _)| line).collect();
+                lines.dedup_by(|a, b| a.trim().is_empty() && b.trim().is_empty());
+                let mut joined = lines.join("\n");
+                joined.push('\n');
+                joined
+            };
+            
+            pruned.report.files.push(PrunedFile {
+                path: path.clone(),
+                original_size: content.len() as u64,
+                pruned_size: new_content.len() as u64,
+                removed_functions,
+                removed,
+            });
+            if !removed {
+                pruned.contents.push((path, new_content));
+            }
+        }
+        
+        Ok(pruned)
+    }
+    
+    /// Object files (by stem) whose Makefile rules only reference disabled symbols
+    fn disabled_objects(&self, makefile_entries: &[String]) -> HashSet<String> {
+        let mut enabled = HashSet::new();
+        let mut disabled = HashSet::new();
+        for entry in makefile_entries {
+            if let Some(caps) = MAKEFILE_RULE_RE.captures(entry) {
+                let is_enabled = self.features.contains(&caps[1]);
+                for object in caps[2].split_whitespace().filter_map(|object| object.strip_suffix(".o")) {
+                    if is_enabled {
+                        enabled.insert(object.to_string());
+                    } else {
+                        disabled.insert(object.to_string());
+                    }
+                }
+            }
+        }
+        disabled.difference(&enabled).cloned().collect()
+    }
+    
+    /// Evaluate a single-term preprocessor condition
+    fn evaluate(&self, expression: &str) -> Condition {
+        let caps = match CONDITION_TERM_RE.captures(expression.trim()) {
+            Some(caps) => caps,
+            None => return Condition::Unknown,
+        };
+        let symbol = &caps[2];
+        let value = if symbol == "0" {
+            Condition::False
+        } else if symbol.chars().all(|c| c.is_ascii_digit()) {
+            Condition::True
+        } else if symbol.starts_with("CONFIG_") {
+            if self.features.contains(symbol) { Condition::True } else { Condition::False }
+        } else {
+            Condition::Unknown
+        };
+        match (caps.get(1).is_some(), value) {
+            (true, Condition::True) => Condition::False,
+            (true, Condition::False) => Condition::True,
+            (_, value) => value,
+        }
+    }
+    
+    /// Lines that survive preprocessing for the feature set
+    fn active_lines(&self, content: &str) -> Vec<bool> {
+        // Each frame: (branch condition, whether an earlier branch was definitely taken,
+        // whether an earlier branch was undecidable)
+        let mut stack: Vec<(Condition, bool, bool)> = Vec::new();
+        let mut active = Vec::new();
+        
+        for line in content.lines() {
+            let directive = line.trim_start().strip_prefix('#').map(str::trim_start);
+            let enclosing = stack.iter().all(|(condition, _, _)| *condition != Condition::False);
+            match directive {
+                Some(rest) if rest.starts_with("ifdef") || rest.starts_with("ifndef") || rest.starts_with("if") => {
+                    let condition = if let Some(symbol) = rest.strip_prefix("ifdef") {
+                        self.evaluate(&format!("defined({})", symbol.trim()))
+                    } else if let Some(symbol) = rest.strip_prefix("ifndef") {
+                        self.evaluate(&format!("!defined({})", symbol.trim()))
+                    } else {
+                        self.evaluate(&rest[2..])
+                    };
+                    stack.push((condition, condition == Condition::True, condition == Condition::Unknown));
+                    active.push(enclosing);
+                }
+                Some(rest) if rest.starts_with("elif") || rest.starts_with("else") => {
+                    if let Some((condition, taken, unknown)) = stack.last_mut() {
+                        let branch = if rest.starts_with("elif") { self.evaluate(&rest[4..]) } else { Condition::True };
+                        *condition = match (*taken, *unknown, branch) {
+                            (true, _, _) => Condition::False,
+                            (false, _, Condition::False) => Condition::False,
+                            (false, true, _) => Condition::Unknown,
+                            (false, false, branch) => branch,
+                        };
+                        *taken |= *condition == Condition::True;
+                        *unknown |= *condition == Condition::Unknown;
+                    }
+                    active.push(stack.iter().take(stack.len().saturating_sub(1)).all(|(condition, _, _)| *condition != Condition::False));
+                }
+                Some(rest) if rest.starts_with("endif") => {
+                    stack.pop();
+                    active.push(stack.iter().all(|(condition, _, _)| *condition != Condition::False));
+                }
+                _ => active.push(enclosing),
+            }
+        }
+        
+        active
+    }
+}
+
+/// Blank out inactive lines, keeping line numbers intact
+fn mask(content: &str, active: &[bool]) -> String {
+    content.lines()
+        .zip(active)
+        .map(|(line, active)| if *active { line } else { "" })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Find function definitions, prototypes and data references in a C file
+fn scan_functions(source: &str) -> FileScan {
+    let text = strip_comments(source);
+    let bytes = text.as_bytes();
+    let mut scan = FileScan::default();
+    let collect_references = |scan: &mut FileScan, text: &str| {
+        scan.data_references.extend(IDENTIFIER_RE.find_iter(text).map(|m| m.as_str().to_string()));
+    };
+    
+    let mut depth = 0usize;
+    let mut statement_start = 0;
+    let mut body_start = 0;
+    let mut line_start = true;
+    let mut i = 0;
+    while i < bytes.len() {
+        let byte = bytes[i];
+        if depth == 0 && line_start && byte == b'#' {
+            let mut end = i;
+            while end < bytes.len() && !(bytes[end] == b'\n' && (end == 0 || bytes[end - 1] != b'\\')) {
+                end += 1;
+            }
+            // Macro bodies may reference functions
+            if text[i..end].trim_start_matches('#').trim_start().starts_with("define") {
+                collect_references(&mut scan, &text[i..end]);
+            }
+            i = end;
+            statement_start = end;
+            continue;
+        }
+        
+        match byte {
+            b'\n' => line_start = true,
+            b' ' | b'\t' | b'\r' => {}
+            _ => line_start = false,
+        }
+        
+        match byte {
+            b'"' | b'\'' => {
+                i = skip_literal(bytes, i);
+                continue;
+            }
+            b'{' => {
+                if depth == 0 {
+                    body_start = i;
+                }
+                depth += 1;
+            }
+            b'}' if depth > 0 => {
+                depth -= 1;
+                if depth == 0 {
+                    let head = text[statement_start..body_start].trim();
+                    let is_data = ["struct", "union", "enum", "typedef"].iter().any(|kw| head.starts_with(kw)) || head.contains('=');
+                    if !is_data {
+                        if let Some(name) = declarator_name(head) {
+                            scan.functions.push(FunctionDefinition {
+                                name,
+                                is_static: head.split_whitespace().any(|word| word == "static"),
+                                start_line: line_of(&text, statement_start + leading_ws(&text[statement_start..])),
+                                end_line: line_of(&text, i),
+                                body: text[body_start..=i].to_string(),
+                            });
+                            statement_start = i + 1;
+                        }
+                    }
+                }
+            }
+            b';' if depth == 0 => {
+                let statement = text[statement_start..i].trim();
+                let normalized = statement.split_whitespace().collect::<Vec<_>>().join(" ");
+                match declarator_name(&normalized) {
+                    Some(name) if !normalized.contains('=') && !normalized.contains("(*") => scan.prototypes.push(name),
+                    _ => collect_references(&mut scan, statement),
+                }
+                statement_start = i + 1;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    
+    scan
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_prune_unreachable_and_disabled_code() {
+        let dir = std::env::temp_dir().join(format!("osland-dead-code-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let main = dir.join("drv_main.c");
+        let ptp = dir.join("drv_ptp.c");
+        fs::write(&main, "static int helper(void)\n{\n\treturn 1;\n}\n\nstatic int unused(void)\n{\n\treturn 2;\n}\n\n#ifdef CONFIG_DRV_DEBUG\nstatic void dump(void)\n{\n}\n#endif\n\nstatic int drv_init(void)\n{\n\treturn helper();\n}\nmodule_init(drv_init);\n").unwrap();
+        fs::write(&ptp, "int drv_ptp_init(void)\n{\n\treturn 0;\n}\n").unwrap();
+        
+        let mut component = KernelComponent::default();
+        component.name = "drv".to_string();
+        component.source_files = vec![main.clone(), ptp.clone()];
+        component.makefile_entries = vec!["obj-$(CONFIG_DRV) += drv_main.o".to_string(), "drv-$(CONFIG_DRV_PTP) += drv_ptp.o".to_string()];
+        
+        let eliminator = DeadCodeEliminator::new(DeadCodeConfig {
+            features: vec!["DRV".to_string()],
+            ..Default::default()
+        });
+        let pruned = eliminator.prune(&component).unwrap();
+        
+        let main_report = pruned.report.files.iter().find(|file| file.path == main).unwrap();
+        assert_eq!(main_report.removed_functions, vec!["unused".to_string()]);
+        assert!(pruned.report.files.iter().find(|file| file.path == ptp).unwrap().removed);
+        
+        let (_, content) = pruned.contents.iter().find(|(path, _)| *path == main).unwrap();
+        assert!(content.contains("helper"));
+        assert!(!content.contains("unused"));
+        assert!(!content.contains("dump"));
+        assert!(pruned.report.savings() > 0);
+        
+        fs::remove_dir_all(&dir).ok();
+    }
+}
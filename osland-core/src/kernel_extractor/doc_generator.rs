@@ -0,0 +1,301 @@
+// Component Documentation Generator for OSland Kernel Extractor
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+use serde::{Deserialize, Serialize};
+use crate::kernel_extractor::api_extractor::{ApiExtractor, ApiItem, ApiItemKind};
+use crate::kernel_extractor::component_db::{type_name, ComponentDatabase};
+use crate::kernel_extractor::{KernelComponent, KernelExtractorError};
+
+static SPDX_RE: LazyLock<regex::Regex> = LazyLock::new(|| regex::Regex::new(r"SPDX-License-Identifier:\s*([^\s*/][^*/\n]*?)\s*(?:\*/)?$").unwrap());
+static MODULE_LICENSE_RE: LazyLock<regex::Regex> = LazyLock::new(|| regex::Regex::new(r#"MODULE_LICENSE\s*\(\s*"([^"]+)"\s*\)"#).unwrap());
+static BLOCK_COMMENT_RE: LazyLock<regex::Regex> = LazyLock::new(|| regex::Regex::new(r"(?s)/\*(.*?)\*/").unwrap());
+
+/// Documentation of an extracted component
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ComponentDoc {
+    /// Component name
+    pub name: String,
+    
+    /// Component type
+    pub component_type: String,
+    
+    /// One-paragraph summary
+    pub summary: String,
+    
+    /// Licenses declared by the sources (SPDX identifiers or `MODULE_LICENSE`)
+    pub licenses: Vec<String>,
+    
+    /// Public API of the component
+    pub api: Vec<ApiItem>,
+    
+    /// Components this component depends on
+    pub dependencies: Vec<String>,
+    
+    /// Components depending on this component
+    pub dependents: Vec<String>,
+    
+    /// Supported architectures
+    pub architectures: Vec<String>,
+    
+    /// Kconfig options of the component
+    pub kconfig_options: Vec<String>,
+    
+    /// Source and header files
+    pub files: Vec<PathBuf>,
+}
+
+impl ComponentDoc {
+    /// Render the documentation as Markdown
+    pub fn to_markdown(&self) -> String {
+        let mut md = format!("# {}\n\n{}\n\n", self.name, self.summary);
+        md.push_str(&format!("- **Type:** {}\n", self.component_type));
+        md.push_str(&format!("- **License:** {}\n", or_unknown(&self.licenses)));
+        md.push_str(&format!("- **Architectures:** {}\n", or_unknown(&self.architectures)));
+        if !self.kconfig_options.is_empty() {
+            md.push_str(&format!("- **Kconfig:** {}\n", self.kconfig_options.join(", ")));
+        }
+        
+        md.push_str("\n## Exported API\n\n");
+        if self.api.is_empty() {
+            md.push_str("This component has no public API.\n");
+        }
+        for (title, kinds) in api_sections() {
+            let items: Vec<&ApiItem> = self.api.iter().filter(|item| kinds.contains(&item.kind)).collect();
+            if items.is_empty() {
+                continue;
+            }
+            md.push_str(&format!("### {}\n\n```c\n", title));
+            for item in items {
+                md.push_str(&item.declaration);
+                md.push('\n');
+            }
+            md.push_str("```\n\n");
+        }
+        
+        md.push_str("\n## Dependencies\n\n");
+        md.push_str(&markdown_list(&self.dependencies, "None"));
+        md.push_str("\n## Used by\n\n");
+        md.push_str(&markdown_list(&self.dependents, "None"));
+        md.push_str("\n## Files\n\n");
+        let files: Vec<String> = self.files.iter().map(|file| format!("`{}`", file.display())).collect();
+        md.push_str(&markdown_list(&files, "None"));
+        md
+    }
+    
+    /// Render the documentation as a standalone HTML page
+    pub fn to_html(&self) -> String {
+        let mut html = String::from("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+        html.push_str(&format!("<title>{}</title>\n", html_escape(&self.name)));
+        html.push_str("<style>body{font-family:sans-serif;max-width:60em;margin:auto}pre{background:#f4f4f4;padding:.5em}</style>\n");
+        html.push_str("</head>\n<body>\n");
+        html.push_str(&format!("<h1>{}</h1>\n<p>{}</p>\n<ul>\n", html_escape(&self.name), html_escape(&self.summary)));
+        html.push_str(&format!("<li><b>Type:</b> {}</li>\n", html_escape(&self.component_type)));
+        html.push_str(&format!("<li><b>License:</b> {}</li>\n", html_escape(&or_unknown(&self.licenses))));
+        html.push_str(&format!("<li><b>Architectures:</b> {}</li>\n", html_escape(&or_unknown(&self.architectures))));
+        if !self.kconfig_options.is_empty() {
+            html.push_str(&format!("<li><b>Kconfig:</b> {}</li>\n", html_escape(&self.kconfig_options.join(", "))));
+        }
+        html.push_str("</ul>\n<h2>Exported API</h2>\n");
+        if self.api.is_empty() {
+            html.push_str("<p>This component has no public API.</p>\n");
+        }
+        for (title, kinds) in api_sections() {
+            let items: Vec<&ApiItem> = self.api.iter().filter(|item| kinds.contains(&item.kind)).collect();
+            if items.is_empty() {
+                continue;
+            }
+            html.push_str(&format!("<h3>{}</h3>\n<pre><code>", title));
+            for item in items {
+                html.push_str(&html_escape(&item.declaration));
+                html.push('\n');
+            }
+            html.push_str("</code></pre>\n");
+        }
+        
+        for (title, entries) in [("Dependencies", &self.dependencies), ("Used by", &self.dependents)] {
+            html.push_str(&format!("<h2>{}</h2>\n", title));
+            html.push_str(&html_list(entries.iter().map(|entry| html_escape(entry))));
+        }
+        html.push_str("<h2>Files</h2>\n");
+        html.push_str(&html_list(self.files.iter().map(|file| format!("<code>{}</code>", html_escape(&file.display().to_string())))));
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+    
+    /// Write `<name>.md` and `<name>.html` to a directory
+    pub fn write<P: AsRef<Path>>(&self, dir: P) -> Result<(PathBuf, PathBuf), KernelExtractorError> {
+        let markdown_path = dir.as_ref().join(format!("{}.md", self.name));
+        let html_path = dir.as_ref().join(format!("{}.html", self.name));
+        if let Some(parent) = markdown_path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| KernelExtractorError::OutputDirError(format!("Failed to create documentation directory {:?}: {}", parent, e)))?;
+        }
+        
+        for (path, content) in [(&markdown_path, self.to_markdown()), (&html_path, self.to_html())] {
+            fs::write(path, content)
+                .map_err(|e| KernelExtractorError::ExtractionError(format!("Failed to write documentation {:?}: {}", path, e)))?;
+        }
+        Ok((markdown_path, html_path))
+    }
+}
+
+/// Component Documentation Generator
+///
+/// Collects a component's summary, license, API, dependencies and
+/// architectures from its sources and the component database.
+pub struct DocGenerator<'a> {
+    database: &'a ComponentDatabase,
+}
+
+impl<'a> DocGenerator<'a> {
+    /// Create a new documentation generator
+    pub fn new(database: &'a ComponentDatabase) -> Self {
+        Self { database }
+    }
+    
+    /// Build the documentation of a component
+    pub fn generate(&self, component: &KernelComponent) -> Result<ComponentDoc, KernelExtractorError> {
+        let files: Vec<PathBuf> = component.source_files.iter().chain(&component.header_files).cloned().collect();
+        let sources: Vec<String> = files.iter().filter_map(|file| fs::read_to_string(file).ok()).collect();
+        
+        // Reuse the API recorded by the extractor when available
+        let api = match component.metadata.get("api").and_then(|api| api.get("items")) {
+            Some(items) => serde_json::from_value(items.clone())
+                .map_err(|e| KernelExtractorError::ParseError(format!("Corrupt API metadata for {}: {}", component.name, e)))?,
+            None => ApiExtractor::new().extract(component)?.items,
+        };
+        
+        let summary = component.description.clone()
+            .filter(|description| !description.trim().is_empty())
+            .or_else(|| sources.iter().find_map(|source| leading_comment(source)))
+            .unwrap_or_else(|| format!("{} component extracted from {} files.", type_name(&component.component_type), files.len()));
+        
+        let mut architectures: Vec<String> = component.architecture.iter().map(|arch| format!("{:?}", arch)).collect();
+        architectures.dedup();
+        
+        Ok(ComponentDoc {
+            name: component.name.clone(),
+            component_type: type_name(&component.component_type),
+            summary,
+            licenses: licenses(&sources),
+            api,
+            dependencies: component.dependencies.clone(),
+            dependents: self.database.dependents_of(&component.name)?,
+            architectures,
+            kconfig_options: component.kconfig_options.clone(),
+            files,
+        })
+    }
+}
+
+/// Collect the licenses declared in a set of sources
+pub fn licenses(sources: &[String]) -> Vec<String> {
+    let mut licenses = BTreeSet::new();
+    for source in sources {
+        for line in source.lines().take(5) {
+            if let Some(caps) = SPDX_RE.captures(line.trim()) {
+                licenses.insert(caps[1].trim().to_string());
+            }
+        }
+        for caps in MODULE_LICENSE_RE.captures_iter(source) {
+            licenses.insert(caps[1].to_string());
+        }
+    }
+    licenses.into_iter().collect()
+}
+
+/// First paragraph of the first block comment, skipping license and copyright lines
+fn leading_comment(source: &str) -> Option<String> {
+    for caps in BLOCK_COMMENT_RE.captures_iter(source).take(3) {
+        let lines: Vec<&str> = caps[1].lines()
+            .map(|line| line.trim().trim_start_matches('*').trim())
+            .skip_while(|line| line.is_empty())
+            .take_while(|line| !line.is_empty())
+            .filter(|line| !line.contains("SPDX-License-Identifier") && !line.starts_with("Copyright") && !line.starts_with("(C)"))
+            .collect();
+        if !lines.is_empty() {
+            return Some(lines.join(" "));
+        }
+    }
+    None
+}
+
+/// Titles and item kinds of the API sections
+fn api_sections() -> [(&'static str, &'static [ApiItemKind]); 4] {
+    [
+        ("Functions", &[ApiItemKind::Function, ApiItemKind::InlineFunction]),
+        ("Types", &[ApiItemKind::Struct, ApiItemKind::Union, ApiItemKind::Enum, ApiItemKind::Typedef]),
+        ("Variables", &[ApiItemKind::Variable]),
+        ("Macros", &[ApiItemKind::Macro]),
+    ]
+}
+
+fn or_unknown(values: &[String]) -> String {
+    if values.is_empty() {
+        "Unknown".to_string()
+    } else {
+        values.join(", ")
+    }
+}
+
+fn markdown_list(entries: &[String], empty: &str) -> String {
+    if entries.is_empty() {
+        return format!("{}\n", empty);
+    }
+    entries.iter().map(|entry| format!("- {}\n", entry)).collect()
+}
+
+fn html_list<I: Iterator<Item = String>>(entries: I) -> String {
+    let items: Vec<String> = entries.map(|entry| format!("<li>{}</li>\n", entry)).collect();
+    if items.is_empty() {
+        "<p>None</p>\n".to_string()
+    } else {
+        format!("<ul>\n{}</ul>\n", items.concat())
+    }
+}
+
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kernel_extractor::ComponentType;
+    
+    #[test]
+    fn test_generate_docs() {
+        let dir = std::env::temp_dir().join(format!("osland-docs-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let source = dir.join("ring.c");
+        fs::write(&source, "// SPDX-License-Identifier: GPL-2.0\n/*\n * Lock-free ring buffer for packet queues.\n *\n * Copyright (C) 2025\n */\n\nint ring_push(struct ring *r, void *item)\n{\n\treturn 0;\n}\nEXPORT_SYMBOL(ring_push);\nMODULE_LICENSE(\"GPL\");\n").unwrap();
+        
+        let database = ComponentDatabase::new().unwrap();
+        let mut component = KernelComponent::default();
+        component.name = "ring".to_string();
+        component.component_type = ComponentType::Other;
+        component.source_files.push(source);
+        component.dependencies.push("mm".to_string());
+        database.insert_component(&component).unwrap();
+        
+        let doc = DocGenerator::new(&database).generate(&component).unwrap();
+        assert_eq!(doc.summary, "Lock-free ring buffer for packet queues.");
+        assert_eq!(doc.licenses, vec!["GPL".to_string(), "GPL-2.0".to_string()]);
+        assert!(doc.api.iter().any(|item| item.name == "ring_push"));
+        
+        let markdown = doc.to_markdown();
+        assert!(markdown.starts_with("# ring\n"));
+        assert!(markdown.contains("- mm\n"));
+        assert!(doc.to_html().contains("struct ring *r"));
+        
+        fs::remove_dir_all(&dir).ok();
+    }
+}
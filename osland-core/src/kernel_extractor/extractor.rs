@@ -0,0 +1,598 @@
+// Kernel Extractor implementation for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::fs::{self, DirEntry};
+use std::io::{self, Write};
+use serde::{Deserialize, Serialize};
+use crate::kernel_extractor::{KernelExtractorError, parsers::{Parser, CParser}, dependency_analyzer::DependencyAnalyzer};
+use crate::kernel_extractor::component_db::ComponentDatabase;
+use crate::kernel_extractor::api_extractor::ApiExtractor;
+use crate::kernel_extractor::doc_generator::DocGenerator;
+use crate::kernel_extractor::dead_code::{DeadCodeConfig, DeadCodeEliminator};
+use crate::kernel_extractor::patch_manager::PatchManager;
+use crate::kernel_extractor::security_audit::{self, AuditConfig, SecurityAuditor};
+use crate::core::architecture::KernelArchitecture;
+use crate::core::event_bus::{self, ExtractionProgressEvent, SystemEvent};
+use crate::core::cancellation::CancellationToken;
+use crate::core::resource_governor::{BackgroundJob, ResourceGovernor};
+
+/// Kernel component types
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ComponentType {
+    Driver,
+    FileSystem,
+    Network,
+    MemoryManagement,
+    ProcessManagement,
+    Security,
+    Virtualization,
+    DeviceTree,
+    Module,
+    Other,
+}
+
+/// Kernel component information
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KernelComponent {
+    pub name: String,
+    pub component_type: ComponentType,
+    pub source_files: Vec<PathBuf>,
+    pub header_files: Vec<PathBuf>,
+    pub dependencies: Vec<String>,
+    pub description: Option<String>,
+    pub architecture: Vec<KernelArchitecture>,
+    pub kconfig_options: Vec<String>,
+    pub makefile_entries: Vec<String>,
+    pub metadata: serde_json::Value,
+}
+
+impl Default for KernelComponent {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            component_type: ComponentType::Other,
+            source_files: Vec::new(),
+            header_files: Vec::new(),
+            dependencies: Vec::new(),
+            description: None,
+            architecture: Vec::new(),
+            kconfig_options: Vec::new(),
+            makefile_entries: Vec::new(),
+            metadata: serde_json::Value::Null,
+        }
+    }
+}
+
+/// Kernel extraction configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExtractionConfig {
+    pub source_dir: PathBuf,
+    pub output_dir: PathBuf,
+    pub include_patterns: Vec<String>,
+    pub exclude_patterns: Vec<String>,
+    pub components_to_extract: Vec<ComponentType>,
+    pub architectures: Vec<KernelArchitecture>,
+    pub enable_dependency_analysis: bool,
+    pub generate_metadata: bool,
+    /// Prune code unreachable for a target feature set
+    pub dead_code_elimination: Option<DeadCodeConfig>,
+    /// Run the security audit and write `security_audit.sarif`
+    pub security_audit: Option<AuditConfig>,
+    /// Generate `include/<component>_api.h` interface headers
+    pub generate_api_headers: bool,
+    /// Generate `docs/<component>.md` and `.html` documentation
+    pub generate_docs: bool,
+    /// Also export components to the legacy `components/` directory layout (on by default)
+    pub export_directory_layout: bool,
+    /// Version of the kernel being extracted, recorded as the patch baseline
+    pub kernel_version: Option<String>,
+    /// Name of the kernel being extracted (Linux, Zephyr, ...), recorded as provenance
+    pub kernel_name: Option<String>,
+    /// Origin namespace; components are named `<origin>/<name>` when set
+    pub origin: Option<String>,
+    pub verbose: bool,
+}
+
+/// File name of the component database written to the output directory
+pub const COMPONENT_DATABASE_FILE: &str = "components.db.json";
+
+impl Default for ExtractionConfig {
+    fn default() -> Self {
+        Self {
+            source_dir: PathBuf::new(),
+            output_dir: PathBuf::new(),
+            include_patterns: ["*.c", "*.h", "*.S"].map(String::from).to_vec(),
+            exclude_patterns: ["*.o", "*.ko", "*.mod.c"].map(String::from).to_vec(),
+            components_to_extract: vec![],
+            architectures: vec![KernelArchitecture::X86_64],
+            enable_dependency_analysis: true,
+            generate_metadata: true,
+            dead_code_elimination: None,
+            security_audit: None,
+            generate_api_headers: true,
+            generate_docs: true,
+            export_directory_layout: true,
+            kernel_version: None,
+            kernel_name: None,
+            origin: None,
+            verbose: false,
+        }
+    }
+}
+
+/// Include and exclude patterns of a configuration, compiled once per extraction
+struct FilePatterns {
+    include: Vec<regex::Regex>,
+    exclude: Vec<regex::Regex>,
+}
+
+impl FilePatterns {
+    /// Compile the simple glob patterns (`*` and `?`) of a configuration
+    fn new(config: &ExtractionConfig) -> Result<Self, KernelExtractorError> {
+        let compile = |patterns: &[String]| {
+            patterns.iter().map(|pattern| {
+                let regex = regex::escape(pattern).replace(r"\*", ".*").replace(r"\?", ".");
+                regex::Regex::new(&format!("^{}$", regex))
+                    .map_err(|e| KernelExtractorError::ExtractionError(format!("Invalid file pattern '{}': {}", pattern, e)))
+            }).collect::<Result<Vec<_>, _>>()
+        };
+        Ok(Self {
+            include: compile(&config.include_patterns)?,
+            exclude: compile(&config.exclude_patterns)?,
+        })
+    }
+    
+    /// Whether a file name matches an include pattern and no exclude pattern
+    fn matches(&self, path: &Path) -> bool {
+        let filename = path.file_name().and_then(|name| name.to_str()).unwrap_or("");
+        !self.exclude.iter().any(|regex| regex.is_match(filename))
+            && self.include.iter().any(|regex| regex.is_match(filename))
+    }
+}
+
+/// Kernel extractor main class
+pub struct KernelExtractor {
+    config: ExtractionConfig,
+    parser: Box<dyn Parser>,
+    dependency_analyzer: DependencyAnalyzer,
+    extracted_components: Vec<KernelComponent>,
+    database: Arc<ComponentDatabase>,
+    cancel_token: CancellationToken,
+}
+
+impl KernelExtractor {
+    /// Create a new kernel extractor
+    pub fn new(source_dir: String, output_dir: String) -> Result<Self, KernelExtractorError> {
+        let config = ExtractionConfig {
+            source_dir: PathBuf::from(source_dir),
+            output_dir: PathBuf::from(output_dir),
+            ..Default::default()
+        };
+        Self::with_config(config)
+    }
+    
+    /// Create a new kernel extractor with custom configuration
+    pub fn with_config(config: ExtractionConfig) -> Result<Self, KernelExtractorError> {
+        Ok(Self::with_database(config, Arc::new(ComponentDatabase::new()?)))
+    }
+    
+    /// Create a kernel extractor writing into a shared component database
+    pub fn with_database(config: ExtractionConfig, database: Arc<ComponentDatabase>) -> Self {
+        Self {
+            config,
+            parser: Box::new(CParser::new()),
+            dependency_analyzer: DependencyAnalyzer::new(),
+            extracted_components: Vec::new(),
+            database,
+            cancel_token: CancellationToken::new(),
+        }
+    }
+    
+    /// Extract components from the kernel source
+    pub fn extract(&mut self) -> Result<(), KernelExtractorError> {
+        if self.cancel_token.is_canceled() {
+            self.cancel_token = CancellationToken::new();
+        }
+        let token = self.cancel_token.clone();
+        self.extract_with_cancellation(token)
+    }
+    
+    /// Extract components, stopping at the next stage or file when `token` is canceled
+    pub fn extract_with_cancellation(&mut self, token: CancellationToken) -> Result<(), KernelExtractorError> {
+        self.cancel_token = token;
+        
+        // Validate source directory
+        if !self.config.source_dir.exists() {
+            return Err(KernelExtractorError::SourceDirError(format!("Source directory does not exist: {:?}", self.config.source_dir)));
+        }
+        
+        if !self.config.source_dir.is_dir() {
+            return Err(KernelExtractorError::SourceDirError(format!("Source path is not a directory: {:?}", self.config.source_dir)));
+        }
+        
+        // Create output directory if it doesn't exist
+        if !self.config.output_dir.exists() {
+            fs::create_dir_all(&self.config.output_dir)
+                .map_err(|e| KernelExtractorError::OutputDirError(format!("Failed to create output directory: {}", e)))?;
+        }
+        
+        let stages = self.pipeline_stages();
+        let patterns = FilePatterns::new(&self.config)?;
+        
+        // Traverse the source directory
+        self.report_stage(&stages, "traverse")?;
+        let source_dir = self.config.source_dir.clone();
+        self.traverse_source_dir(&source_dir, &patterns)?;
+        
+        // Perform dependency analysis if enabled
+        if self.config.enable_dependency_analysis {
+            self.report_stage(&stages, "dependencies")?;
+            self.analyze_dependencies()?;
+        }
+        
+        // Populate the component database
+        self.report_stage(&stages, "database")?;
+        self.populate_database()?;
+        
+        // Prune dead code for the target feature set
+        if let Some(dead_code_config) = &self.config.dead_code_elimination {
+            self.report_stage(&stages, "dead_code")?;
+            self.eliminate_dead_code(dead_code_config)?;
+        }
+        
+        // Audit the (pruned) sources for insecure patterns
+        if let Some(audit_config) = &self.config.security_audit {
+            self.report_stage(&stages, "security_audit")?;
+            self.audit_security(audit_config)?;
+        }
+        
+        // Extract the API surface of every component
+        if self.config.generate_api_headers {
+            self.report_stage(&stages, "api_headers")?;
+            self.generate_api_headers()?;
+        }
+        
+        // Document every component
+        if self.config.generate_docs {
+            self.report_stage(&stages, "docs")?;
+            self.generate_docs()?;
+        }
+        
+        // Export the legacy directory layout if requested
+        if self.config.export_directory_layout {
+            self.report_stage(&stages, "export")?;
+            self.database.export_to_directory(&self.config.output_dir, self.config.generate_metadata)?;
+            self.reapply_patches()?;
+        }
+        
+        self.report_stage(&stages, "save")?;
+        self.database.save(self.config.output_dir.join(COMPONENT_DATABASE_FILE))?;
+        self.report_stage(&stages, "done")?;
+        
+        Ok(())
+    }
+    
+    /// Names of the pipeline stages enabled by the configuration, in order
+    fn pipeline_stages(&self) -> Vec<&'static str> {
+        let mut stages = vec!["traverse"];
+        if self.config.enable_dependency_analysis {
+            stages.push("dependencies");
+        }
+        stages.push("database");
+        if self.config.dead_code_elimination.is_some() {
+            stages.push("dead_code");
+        }
+        if self.config.security_audit.is_some() {
+            stages.push("security_audit");
+        }
+        if self.config.generate_api_headers {
+            stages.push("api_headers");
+        }
+        if self.config.generate_docs {
+            stages.push("docs");
+        }
+        if self.config.export_directory_layout {
+            stages.push("export");
+        }
+        stages.push("save");
+        stages
+    }
+    
+    /// Publish the start of a pipeline stage on the global event bus
+    ///
+    /// The `done` stage reports all stages as completed. Fails if the extraction
+    /// was canceled, so every stage boundary is a cancellation point.
+    fn report_stage(&self, stages: &[&'static str], stage: &str) -> Result<(), KernelExtractorError> {
+        self.check_canceled()?;
+        let completed = stages.iter().position(|s| *s == stage).unwrap_or(stages.len());
+        event_bus::publish(SystemEvent::Extraction(ExtractionProgressEvent {
+            kernel: self.config.kernel_name.clone().unwrap_or_else(|| "kernel".to_string()),
+            stage: stage.to_string(),
+            completed,
+            total: stages.len(),
+        }));
+        Ok(())
+    }
+    
+    /// Fail with `Canceled` if the extraction was canceled
+    fn check_canceled(&self) -> Result<(), KernelExtractorError> {
+        if self.cancel_token.is_canceled() {
+            return Err(KernelExtractorError::Canceled);
+        }
+        Ok(())
+    }
+    
+    /// Traverse the source directory and collect files
+    fn traverse_source_dir(&mut self, dir: &Path, patterns: &FilePatterns) -> Result<(), KernelExtractorError> {
+        let entries = fs::read_dir(dir)
+            .map_err(|e| KernelExtractorError::SourceDirError(format!("Failed to read directory {:?}: {}", dir, e)))?;
+        
+        for entry in entries {
+            let entry = entry.map_err(|e| KernelExtractorError::SourceDirError(format!("Failed to read directory entry: {}", e)))?;
+            let path = entry.path();
+            
+            if path.is_dir() {
+                // Recursively traverse subdirectories
+                self.traverse_source_dir(&path, patterns)?;
+            } else {
+                // Process file if it matches the include patterns
+                if patterns.matches(&path) {
+                    ResourceGovernor::global().throttle(BackgroundJob::Extraction, Some(&self.cancel_token));
+                    self.check_canceled()?;
+                    self.process_file(&entry)?;
+                }
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /// Process a single file
+    fn process_file(&mut self, entry: &DirEntry) -> Result<(), KernelExtractorError> {
+        let path = entry.path();
+        
+        // Parse the file to extract component information
+        let component_info = self.parser.parse_file(&path)
+            .map_err(|e| KernelExtractorError::ParseError(format!("Failed to parse file {:?}: {}", path, e)))?;
+        
+        // If component info is extracted, add it to the list
+        if let Some(mut component) = component_info {
+            // Determine component type
+            self.classify_component(&mut component, &path);
+            
+            // Keep components of different kernels apart
+            if let Some(origin) = &self.config.origin {
+                component.name = format!("{}/{}", origin, component.name);
+            }
+            
+            // Check if this component type should be extracted
+            if self.config.components_to_extract.is_empty() || self.config.components_to_extract.contains(&component.component_type) {
+                self.extracted_components.push(component);
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /// Classify a component based on its path and content
+    fn classify_component(&self, component: &mut KernelComponent, path: &PathBuf) {
+        // Simple classification based on path
+        let path_str = path.to_str().unwrap_or("");
+        
+        if path_str.contains("/drivers/") {
+            component.component_type = ComponentType::Driver;
+        } else if path_str.contains("/fs/") {
+            component.component_type = ComponentType::FileSystem;
+        } else if path_str.contains("/net/") {
+            component.component_type = ComponentType::Network;
+        } else if path_str.contains("/mm/") {
+            component.component_type = ComponentType::MemoryManagement;
+        } else if path_str.contains("/kernel/") {
+            component.component_type = ComponentType::ProcessManagement;
+        } else if path_str.contains("/security/") {
+            component.component_type = ComponentType::Security;
+        } else if path_str.contains("/virt/") {
+            component.component_type = ComponentType::Virtualization;
+        } else if path_str.contains("/devicetree/") {
+            component.component_type = ComponentType::DeviceTree;
+        } else if path_str.ends_with(".mod.c") {
+            component.component_type = ComponentType::Module;
+        }
+    }
+    
+    /// Analyze dependencies between components
+    fn analyze_dependencies(&mut self) -> Result<(), KernelExtractorError> {
+        // This is a placeholder - in real implementation, we would use the dependency analyzer
+        // to analyze dependencies between components
+        
+        Ok(())
+    }
+    
+    /// Insert the extracted components into the component database and record their provenance
+    fn populate_database(&self) -> Result<(), KernelExtractorError> {
+        self.database.insert_components(&self.extracted_components)?;
+        
+        let extracted_at = chrono::Utc::now().to_rfc3339();
+        for component in self.own_components()? {
+            let files: Vec<String> = component.source_files.iter().chain(&component.header_files)
+                .map(|file| file.strip_prefix(&self.config.source_dir).unwrap_or(file).to_string_lossy().to_string())
+                .collect();
+            let provenance = serde_json::json!({
+                "origin": self.config.origin,
+                "kernel": self.config.kernel_name,
+                "version": self.config.kernel_version,
+                "source_dir": self.config.source_dir,
+                "files": files,
+                "extracted_at": extracted_at,
+            });
+            self.database.set_metadata(&component.name, "provenance", provenance)?;
+        }
+        
+        Ok(())
+    }
+    
+    /// Components of the database produced by this extractor
+    ///
+    /// The database may be shared with extractors of other kernels, so the
+    /// post-processing passes only touch the components extracted here.
+    fn own_components(&self) -> Result<Vec<KernelComponent>, KernelExtractorError> {
+        let mut names: Vec<&str> = self.extracted_components.iter().map(|component| component.name.as_str()).collect();
+        names.sort();
+        names.dedup();
+        
+        let mut components = Vec::new();
+        for name in names {
+            if let Some(component) = self.database.get_component(name)? {
+                components.push(component);
+            }
+        }
+        Ok(components)
+    }
+    
+    /// Replace each component's files with pruned copies under `pruned/<component>/`
+    fn eliminate_dead_code(&self, dead_code_config: &DeadCodeConfig) -> Result<(), KernelExtractorError> {
+        let eliminator = DeadCodeEliminator::new(dead_code_config.clone());
+        let pruned_dir = self.config.output_dir.join("pruned");
+        let (mut original_size, mut pruned_size) = (0, 0);
+        
+        for mut component in self.own_components()? {
+            let pruned = eliminator.prune(&component)?;
+            let paths = pruned.write(pruned_dir.join(&component.name))?;
+            original_size += pruned.report.original_size();
+            pruned_size += pruned.report.pruned_size();
+            if self.config.verbose {
+                log::info!("{}", pruned.report.summary());
+            }
+            
+            component.source_files = paths.iter().filter(|path| path.extension().map_or(false, |ext| ext != "h")).cloned().collect();
+            component.header_files = paths.iter().filter(|path| path.extension().map_or(false, |ext| ext == "h")).cloned().collect();
+            self.database.update_component(&component)?;
+            
+            let report = serde_json::to_value(&pruned.report)
+                .map_err(|e| KernelExtractorError::ExtractionError(format!("Failed to serialize dead code report: {}", e)))?;
+            self.database.set_metadata(&component.name, "dead_code", report)?;
+        }
+        
+        log::info!(
+            "Dead code elimination: {} -> {} bytes ({} bytes saved)",
+            original_size,
+            pruned_size,
+            original_size.saturating_sub(pruned_size)
+        );
+        
+        Ok(())
+    }
+    
+    /// Re-apply user patches onto the freshly exported components
+    ///
+    /// Components without patches get their exported files recorded as the
+    /// upstream baseline so later edits can be captured with `PatchManager::refresh`.
+    fn reapply_patches(&self) -> Result<(), KernelExtractorError> {
+        let patch_manager = PatchManager::new(self.config.output_dir.join("patches"));
+        let version = self.config.kernel_version.clone().unwrap_or_else(|| "unknown".to_string());
+        
+        for component in self.own_components()? {
+            let files: Vec<PathBuf> = component.source_files.iter().chain(&component.header_files).cloned().collect();
+            if !patch_manager.is_tracked(&component.name) || patch_manager.series(&component.name)?.is_empty() {
+                patch_manager.import_upstream(&component.name, &version, &files)?;
+                continue;
+            }
+            
+            let work_dir = self.config.output_dir.join("components").join(&component.name);
+            let report = patch_manager.reapply(&component.name, &version, &files, &work_dir)?;
+            for conflict in &report.conflicts {
+                log::warn!("{}: {} does not apply to {}: {}", component.name, conflict.patch, conflict.file, conflict.message);
+            }
+            
+            let report = serde_json::to_value(&report)
+                .map_err(|e| KernelExtractorError::ExtractionError(format!("Failed to serialize patch report: {}", e)))?;
+            self.database.set_metadata(&component.name, "patches", report)?;
+        }
+        
+        Ok(())
+    }
+    
+    /// Extract each component's API, record it in the database and write its interface header
+    fn generate_api_headers(&self) -> Result<(), KernelExtractorError> {
+        let api_extractor = ApiExtractor::new();
+        let include_dir = self.config.output_dir.join("include");
+        
+        for component in self.own_components()? {
+            let surface = api_extractor.extract(&component)?;
+            if surface.items.is_empty() {
+                continue;
+            }
+            let header = surface.write_header(&include_dir)?;
+            
+            let api = serde_json::json!({
+                "header": header,
+                "exported_symbols": surface.exported_symbols(),
+                "items": surface.items,
+            });
+            self.database.set_metadata(&component.name, "api", api)?;
+        }
+        
+        Ok(())
+    }
+    
+    /// Write each component's documentation and store it with the component
+    fn generate_docs(&self) -> Result<(), KernelExtractorError> {
+        let generator = DocGenerator::new(&self.database);
+        let docs_dir = self.config.output_dir.join("docs");
+        
+        for component in self.own_components()? {
+            let doc = generator.generate(&component)?;
+            let (markdown_path, html_path) = doc.write(&docs_dir)?;
+            
+            let docs = serde_json::json!({
+                "summary": doc.summary,
+                "licenses": doc.licenses,
+                "markdown": doc.to_markdown(),
+                "markdown_file": markdown_path,
+                "html_file": html_path,
+            });
+            self.database.set_metadata(&component.name, "docs", docs)?;
+        }
+        
+        Ok(())
+    }
+    
+    /// Annotate each component with its security findings and write a SARIF log
+    fn audit_security(&self, audit_config: &AuditConfig) -> Result<(), KernelExtractorError> {
+        let auditor = SecurityAuditor::new(audit_config.clone())?;
+        let mut reports = Vec::new();
+        
+        for component in self.own_components()? {
+            let report = auditor.audit(&component);
+            let security = serde_json::json!({
+                "max_severity": report.max_severity(),
+                "counts": report.counts().into_iter().map(|(severity, count)| (severity.sarif_level().to_string(), count)).collect::<std::collections::BTreeMap<_, _>>(),
+                "findings": report.findings,
+            });
+            self.database.set_metadata(&component.name, "security", security)?;
+            reports.push(report);
+        }
+        
+        let total: usize = reports.iter().map(|report| report.findings.len()).sum();
+        log::info!("Security audit: {} findings in {} components", total, reports.len());
+        security_audit::write_sarif(self.config.output_dir.join("security_audit.sarif"), auditor.rules(), &reports)
+    }
+    
+    /// Get the extracted components
+    pub fn get_extracted_components(&self) -> &Vec<KernelComponent> {
+        &self.extracted_components
+    }
+    
+    /// Get the component database
+    pub fn get_database(&self) -> Arc<ComponentDatabase> {
+        self.database.clone()
+    }
+    
+    /// Get the extraction configuration
+    pub fn get_config(&self) -> &ExtractionConfig {
+        &self.config
+    }
+}
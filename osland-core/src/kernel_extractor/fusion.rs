@@ -0,0 +1,296 @@
+// Multi-Kernel Fusion for OSland Kernel Extractor
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+use serde::{Deserialize, Serialize};
+use crate::kernel_extractor::KernelExtractorError;
+use crate::kernel_extractor::component_db::ComponentDatabase;
+use crate::kernel_extractor::extractor::{ExtractionConfig, KernelExtractor, COMPONENT_DATABASE_FILE};
+
+/// File name of the fusion report written to the output directory
+pub const FUSION_REPORT_FILE: &str = "fusion_report.json";
+
+/// Kernel source taking part in a fusion
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KernelSource {
+    /// Origin namespace of the extracted components (e.g. "zephyr")
+    pub origin: String,
+    
+    /// Kernel name recorded as provenance (e.g. "Zephyr")
+    #[serde(default)]
+    pub kernel: Option<String>,
+    
+    /// Kernel version recorded as provenance
+    #[serde(default)]
+    pub version: Option<String>,
+    
+    /// Kernel source directory
+    pub source_dir: PathBuf,
+}
+
+impl KernelSource {
+    /// Parse an `origin=path` command line specification
+    pub fn parse(spec: &str) -> Result<Self, KernelExtractorError> {
+        let (origin, source_dir) = spec.split_once('=')
+            .ok_or_else(|| KernelExtractorError::SourceDirError(format!("Expected <origin>=<path>, got '{}'", spec)))?;
+        if origin.is_empty() || origin.contains('/') {
+            return Err(KernelExtractorError::SourceDirError(format!("Invalid origin name '{}'", origin)));
+        }
+        
+        Ok(Self {
+            origin: origin.to_string(),
+            kernel: None,
+            version: None,
+            source_dir: PathBuf::from(source_dir),
+        })
+    }
+}
+
+/// Fusion configuration
+#[derive(Debug, Clone)]
+pub struct FusionConfig {
+    /// Kernels to extract from
+    pub sources: Vec<KernelSource>,
+    
+    /// Output directory of the fused project
+    pub output_dir: PathBuf,
+    
+    /// Template for the per-kernel extraction settings
+    pub extraction: ExtractionConfig,
+}
+
+/// Severity of a symbol conflict
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConflictSeverity {
+    /// Both definitions are types; they only clash if included together
+    Warning,
+    
+    /// Two global functions with the same name; linking them together fails
+    Error,
+}
+
+/// Definition of a conflicting symbol
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolDefinition {
+    /// Component defining the symbol
+    pub component: String,
+    
+    /// Origin of the component
+    pub origin: String,
+    
+    /// Symbol kind
+    pub kind: String,
+    
+    /// Defining file
+    pub file: PathBuf,
+    
+    /// 1-based line of the definition
+    pub line: usize,
+}
+
+/// Identically named symbol defined by components of different origins
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolConflict {
+    /// Symbol name
+    pub symbol: String,
+    
+    /// Conflict severity
+    pub severity: ConflictSeverity,
+    
+    /// Definitions of the symbol
+    pub definitions: Vec<SymbolDefinition>,
+}
+
+/// Result of a fusion
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FusionReport {
+    /// Number of components extracted per origin
+    pub components_per_origin: BTreeMap<String, usize>,
+    
+    /// Symbol conflicts between origins
+    pub conflicts: Vec<SymbolConflict>,
+}
+
+impl FusionReport {
+    /// Check whether the fused components can be linked together
+    pub fn has_errors(&self) -> bool {
+        self.conflicts.iter().any(|conflict| conflict.severity == ConflictSeverity::Error)
+    }
+}
+
+/// Multi-Kernel Fusion
+///
+/// Extracts components from several kernels into one component database.
+/// Components are namespaced by origin (`<origin>/<name>`), every component
+/// records its provenance, and global symbols defined by more than one origin
+/// are reported as conflicts.
+pub struct KernelFusion {
+    config: FusionConfig,
+    database: Arc<ComponentDatabase>,
+}
+
+impl KernelFusion {
+    /// Create a new fusion
+    pub fn new(config: FusionConfig) -> Result<Self, KernelExtractorError> {
+        Ok(Self {
+            config,
+            database: Arc::new(ComponentDatabase::new()?),
+        })
+    }
+    
+    /// Get the fused component database
+    pub fn database(&self) -> Arc<ComponentDatabase> {
+        self.database.clone()
+    }
+    
+    /// Extract every kernel and detect symbol conflicts
+    pub fn run(&self) -> Result<FusionReport, KernelExtractorError> {
+        let mut origins = BTreeSet::new();
+        for source in &self.config.sources {
+            if !origins.insert(source.origin.as_str()) {
+                return Err(KernelExtractorError::ExtractionError(format!("Duplicate origin '{}'", source.origin)));
+            }
+        }
+        
+        let mut report = FusionReport::default();
+        for source in &self.config.sources {
+            let config = ExtractionConfig {
+                source_dir: source.source_dir.clone(),
+                output_dir: self.config.output_dir.clone(),
+                origin: Some(source.origin.clone()),
+                kernel_name: source.kernel.clone(),
+                kernel_version: source.version.clone(),
+                // The fused project is exported once all kernels are in the database
+                export_directory_layout: false,
+                ..self.config.extraction.clone()
+            };
+            
+            let mut extractor = KernelExtractor::with_database(config, self.database.clone());
+            extractor.extract()?;
+        }
+        
+        for component in self.database.all_components()? {
+            *report.components_per_origin.entry(origin_of(&component.name).to_string()).or_insert(0) += 1;
+        }
+        
+        // A component can clash on several symbols, so collect them all before recording
+        report.conflicts = self.detect_conflicts()?;
+        let mut conflicts_per_component: BTreeMap<&str, BTreeSet<String>> = BTreeMap::new();
+        for conflict in &report.conflicts {
+            for definition in &conflict.definitions {
+                conflicts_per_component.entry(definition.component.as_str()).or_default().extend(
+                    conflict.definitions.iter()
+                        .filter(|other| other.component != definition.component)
+                        .map(|other| format!("{} ({})", conflict.symbol, other.component))
+                );
+            }
+        }
+        for (component, conflicts) in conflicts_per_component {
+            self.database.set_metadata(component, "symbol_conflicts", serde_json::json!(conflicts))?;
+        }
+        
+        if self.config.extraction.export_directory_layout {
+            self.database.export_to_directory(&self.config.output_dir, self.config.extraction.generate_metadata)?;
+        }
+        
+        let report_json = serde_json::to_string_pretty(&report)
+            .map_err(|e| KernelExtractorError::ExtractionError(format!("Failed to serialize fusion report: {}", e)))?;
+        fs::write(self.config.output_dir.join(FUSION_REPORT_FILE), report_json)
+            .map_err(|e| KernelExtractorError::OutputDirError(format!("Failed to write fusion report: {}", e)))?;
+        self.database.save(self.config.output_dir.join(COMPONENT_DATABASE_FILE))?;
+        
+        Ok(report)
+    }
+    
+    /// Find global symbols defined by components of more than one origin
+    pub fn detect_conflicts(&self) -> Result<Vec<SymbolConflict>, KernelExtractorError> {
+        let mut by_name: BTreeMap<String, Vec<SymbolDefinition>> = BTreeMap::new();
+        for (component, symbol) in self.database.all_symbols()? {
+            if !symbol.global {
+                continue;
+            }
+            by_name.entry(symbol.name.clone()).or_default().push(SymbolDefinition {
+                origin: origin_of(&component).to_string(),
+                component,
+                kind: symbol.kind,
+                file: symbol.file,
+                line: symbol.line,
+            });
+        }
+        
+        let mut conflicts = Vec::new();
+        for (symbol, definitions) in by_name {
+            let origins: BTreeSet<&str> = definitions.iter().map(|definition| definition.origin.as_str()).collect();
+            if origins.len() < 2 {
+                continue;
+            }
+            
+            let functions = definitions.iter().filter(|definition| definition.kind == "function").count();
+            conflicts.push(SymbolConflict {
+                symbol,
+                severity: if functions > 1 { ConflictSeverity::Error } else { ConflictSeverity::Warning },
+                definitions,
+            });
+        }
+        
+        Ok(conflicts)
+    }
+}
+
+/// Origin namespace of a component name (`upstream` if not namespaced)
+pub fn origin_of(component: &str) -> &str {
+    component.split_once('/').map(|(origin, _)| origin).unwrap_or("upstream")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_fuse_two_kernels() {
+        let dir = std::env::temp_dir().join(format!("osland-fusion-{}", std::process::id()));
+        let zephyr = dir.join("zephyr/drivers/serial");
+        let linux = dir.join("linux/fs");
+        fs::create_dir_all(&zephyr).unwrap();
+        fs::create_dir_all(&linux).unwrap();
+        fs::write(zephyr.join("uart.c"), "static int helper(void)\n{\n}\n\nint uart_init(void)\n{\n}\n\nint uart_write(void)\n{\n}\n").unwrap();
+        fs::write(linux.join("namei.c"), "static int helper(void)\n{\n}\n\nint uart_init(void)\n{\n}\n\nint uart_write(void)\n{\n}\n\nint vfs_open(void)\n{\n}\n").unwrap();
+        
+        let fusion = KernelFusion::new(FusionConfig {
+            sources: vec![
+                KernelSource::parse(&format!("zephyr={}", dir.join("zephyr").display())).unwrap(),
+                KernelSource::parse(&format!("linux={}", dir.join("linux").display())).unwrap(),
+            ],
+            output_dir: dir.join("out"),
+            extraction: ExtractionConfig { generate_api_headers: false, ..Default::default() },
+        }).unwrap();
+        let report = fusion.run().unwrap();
+        
+        assert!(report.components_per_origin.contains_key("zephyr"));
+        assert!(report.components_per_origin.contains_key("linux"));
+        assert!(fusion.database().all_components().unwrap().iter().all(|c| c.name.contains('/')));
+        
+        // Static helpers don't clash, global functions do
+        let symbols: Vec<&str> = report.conflicts.iter().map(|conflict| conflict.symbol.as_str()).collect();
+        assert_eq!(symbols, vec!["uart_init", "uart_write"]);
+        assert!(report.has_errors());
+        
+        let component = fusion.database().all_components().unwrap().into_iter()
+            .find(|c| c.name.starts_with("zephyr/")).unwrap();
+        assert_eq!(component.metadata["provenance"]["origin"], "zephyr");
+        
+        // Every clash of a component is recorded, not just the last one
+        let linux_component = fusion.database().all_components().unwrap().into_iter()
+            .find(|c| c.name.starts_with("linux/")).unwrap();
+        assert_eq!(component.metadata["symbol_conflicts"], serde_json::json!([
+            format!("uart_init ({})", linux_component.name),
+            format!("uart_write ({})", linux_component.name),
+        ]));
+        
+        fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,131 @@
+// Kernel Extractor module for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+pub mod extractor;
+pub mod parsers;
+pub mod dependency_analyzer;
+pub mod architecture_adapter;
+pub mod component_db;
+pub mod api_extractor;
+pub mod dead_code;
+pub mod patch_manager;
+pub mod abi_checker;
+pub mod fusion;
+pub mod security_audit;
+pub mod doc_generator;
+
+use std::sync::Arc;
+use crate::core::cancellation::{run_blocking, CancellationToken};
+use crate::i18n::LocalizedError;
+
+// Export core components
+pub use extractor::{KernelExtractor, KernelComponent, ComponentType, ExtractionConfig};
+pub use parsers::{Parser, CParser, AssemblyParser, AsmEntryPoint, AsmSyntax, HeaderParser, MultiParser};
+pub use dependency_analyzer::{DependencyAnalyzer, DependencyGraph, DependencyAnalysisResult};
+pub use architecture_adapter::{ArchitectureAdapter, ArchitectureAdapterConfig, ArchitectureAdapterFactory, X86_64Adapter, ARM64Adapter, ArchitectureMacros};
+pub use component_db::{ComponentDatabase, ComponentSymbol};
+pub use api_extractor::{ApiExtractor, ApiItem, ApiItemKind, ApiSurface};
+pub use dead_code::{DeadCodeConfig, DeadCodeEliminator, DeadCodeReport};
+pub use patch_manager::{PatchConflict, PatchManager, ReapplyReport};
+pub use abi_checker::{AbiChecker, AbiIssue, AbiIssueKind, AbiReport, KernelSymbolTable, ModuleRequirements};
+pub use fusion::{FusionConfig, FusionReport, KernelFusion, KernelSource, SymbolConflict};
+pub use security_audit::{AuditConfig, AuditReport, AuditRule, Finding, SecurityAuditor, Severity};
+pub use doc_generator::{ComponentDoc, DocGenerator};
+
+// Extract components from open source kernels into a component database,
+// optionally pruning dead code and exporting the legacy directory layout as well
+pub fn extract_components(
+    source_dir: String,
+    output_dir: String,
+    export_directory_layout: bool,
+    dead_code_elimination: Option<DeadCodeConfig>,
+    security_audit: Option<AuditConfig>,
+) -> Result<(), KernelExtractorError> {
+    let config = ExtractionConfig {
+        source_dir: source_dir.into(),
+        output_dir: output_dir.into(),
+        export_directory_layout,
+        dead_code_elimination,
+        security_audit,
+        ..Default::default()
+    };
+    
+    let mut extractor = extractor::KernelExtractor::with_config(config)?;
+    extractor.extract()
+}
+
+// Extract components on the blocking thread pool, stopping when `token` is canceled
+pub async fn extract_components_async(
+    config: ExtractionConfig,
+    database: Arc<ComponentDatabase>,
+    token: CancellationToken,
+) -> Result<Arc<ComponentDatabase>, KernelExtractorError> {
+    run_blocking(&token, move |token| {
+        let mut extractor = extractor::KernelExtractor::with_database(config, database);
+        extractor.extract_with_cancellation(token)?;
+        Ok(extractor.get_database())
+    })
+    .await
+    .map_err(KernelExtractorError::ExtractionError)?
+}
+
+// Extract components from several kernels into one project, namespaced by origin
+pub fn fuse_kernels(sources: Vec<KernelSource>, output_dir: String, export_directory_layout: bool) -> Result<FusionReport, KernelExtractorError> {
+    let config = FusionConfig {
+        sources,
+        output_dir: output_dir.into(),
+        extraction: ExtractionConfig {
+            export_directory_layout,
+            ..Default::default()
+        },
+    };
+    
+    KernelFusion::new(config)?.run()
+}
+
+// Kernel Extractor error types
+#[derive(thiserror::Error, Debug)]
+pub enum KernelExtractorError {
+    #[error("Source directory error: {0}")]
+    SourceDirError(String),
+    
+    #[error("Output directory error: {0}")]
+    OutputDirError(String),
+    
+    #[error("Parsing error: {0}")]
+    ParseError(String),
+    
+    #[error("Dependency analysis error: {0}")]
+    DependencyError(String),
+    
+    #[error("Extraction error: {0}")]
+    ExtractionError(String),
+    
+    #[error("Extraction canceled")]
+    Canceled,
+}
+
+impl LocalizedError for KernelExtractorError {
+    fn error_code(&self) -> &'static str {
+        match self {
+            KernelExtractorError::SourceDirError(_) => "OSL-E001",
+            KernelExtractorError::OutputDirError(_) => "OSL-E002",
+            KernelExtractorError::ParseError(_) => "OSL-E003",
+            KernelExtractorError::DependencyError(_) => "OSL-E004",
+            KernelExtractorError::ExtractionError(_) => "OSL-E005",
+            KernelExtractorError::Canceled => "OSL-E006",
+        }
+    }
+    
+    fn message_args(&self) -> Vec<String> {
+        match self {
+            KernelExtractorError::SourceDirError(detail)
+            | KernelExtractorError::OutputDirError(detail)
+            | KernelExtractorError::ParseError(detail)
+            | KernelExtractorError::DependencyError(detail)
+            | KernelExtractorError::ExtractionError(detail) => vec![detail.clone()],
+            KernelExtractorError::Canceled => Vec::new(),
+        }
+    }
+}
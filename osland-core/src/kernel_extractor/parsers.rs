@@ -218,11 +218,42 @@ impl Parser for CParser {
     }
 }
 
+/// GNU as dialect of an assembly file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum AsmSyntax {
+    /// x86_64 AT&T syntax (the GNU as default)
+    X86Att,
+    /// x86_64 Intel syntax (`.intel_syntax`)
+    X86Intel,
+    /// AArch64
+    Arm64,
+    /// RISC-V
+    RiscV,
+    /// Dialect could not be determined
+    Unknown,
+}
+
+/// Entry point (global or annotated symbol) defined by an assembly file
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct AsmEntryPoint {
+    /// Symbol name
+    pub name: String,
+    /// Line of the definition (1-based)
+    pub line: usize,
+    /// Whether the symbol is visible outside the file
+    pub global: bool,
+    /// Section the symbol is placed in
+    pub section: String,
+    /// Kind of annotation (`function`, `code`, `data` or `label`)
+    pub kind: String,
+}
+
 /// Assembly source code parser implementation
 pub struct AssemblyParser {
     // Configuration for assembly parser
     pub extract_symbol_names: bool,
     pub extract_section_info: bool,
+    pub extract_macro_usage: bool,
 }
 
 impl Default for AssemblyParser {
@@ -230,10 +261,209 @@ impl Default for AssemblyParser {
         Self {
             extract_symbol_names: true,
             extract_section_info: true,
+            extract_macro_usage: true,
         }
     }
 }
 
+impl AssemblyParser {
+    /// Create a new assembly parser with default configuration
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Detect the assembly dialect from the path and the register names used
+    pub fn detect_syntax(&self, path: &PathBuf, content: &str) -> AsmSyntax {
+        let path_str = path.to_str().unwrap_or("");
+        if content.contains(".intel_syntax") {
+            return AsmSyntax::X86Intel;
+        }
+        if path_str.contains("/x86/") {
+            return AsmSyntax::X86Att;
+        }
+        if path_str.contains("/arm64/") || path_str.contains("/aarch64/") {
+            return AsmSyntax::Arm64;
+        }
+        if path_str.contains("/riscv/") {
+            return AsmSyntax::RiscV;
+        }
+        
+        // Fall back to the registers referenced in the code
+        let x86 = regex::Regex::new(r"%(?:r[a-ds]x|r[sd]i|r[sb]p|r\d+|e[a-d]x|cr[0-4])\b").unwrap();
+        let arm64 = regex::Regex::new(r"\b(?:x(?:[12]?\d|30)|w(?:[12]?\d|30)|xzr|wzr)\b\s*,").unwrap();
+        let riscv = regex::Regex::new(r"\b(?:a[0-7]|t[0-6]|s(?:[0-9]|1[01])|ra|zero)\b\s*,").unwrap();
+        let counts = [
+            (AsmSyntax::X86Att, x86.find_iter(content).count()),
+            (AsmSyntax::Arm64, arm64.find_iter(content).count()),
+            (AsmSyntax::RiscV, riscv.find_iter(content).count()),
+        ];
+        counts.iter()
+            .filter(|(_, count)| *count > 0)
+            .max_by_key(|(_, count)| *count)
+            .map(|(syntax, _)| *syntax)
+            .unwrap_or(AsmSyntax::Unknown)
+    }
+    
+    /// Line comment marker of a dialect
+    fn comment_marker(syntax: AsmSyntax) -> &'static str {
+        match syntax {
+            AsmSyntax::X86Att | AsmSyntax::X86Intel | AsmSyntax::RiscV => "#",
+            AsmSyntax::Arm64 => "//",
+            AsmSyntax::Unknown => "#",
+        }
+    }
+    
+    /// Strip C and line comments, keeping preprocessor directives
+    fn strip_comment(line: &str, syntax: AsmSyntax) -> &str {
+        // `#include`/`#ifdef` are preprocessor lines in .S files, not comments
+        let directive = line.trim_start().strip_prefix('#').map(str::trim_start);
+        let is_cpp = directive.map_or(false, |rest| {
+            ["include", "define", "undef", "if", "else", "elif", "endif", "error", "warning"].iter().any(|kw| rest.starts_with(kw))
+        });
+        if is_cpp {
+            return line;
+        }
+        let mut end = line.len();
+        for marker in ["//", Self::comment_marker(syntax)] {
+            if let Some(index) = line.find(marker) {
+                end = end.min(index);
+            }
+        }
+        &line[..end]
+    }
+    
+    /// Extract entry points, sections, macros and includes from the file
+    pub fn analyze(&self, path: &PathBuf, content: &str) -> serde_json::Value {
+        let syntax = self.detect_syntax(path, content);
+        let annotation_re = regex::Regex::new(r"^(SYM_(FUNC|CODE|DATA|INNER_LABEL)_START(?:_(LOCAL|WEAK|ALIAS|NOALIGN))?\w*|ENTRY|ENDPROC|GLOBAL|SYM_FUNC_END|SYM_CODE_END)\s*\(\s*([A-Za-z_.$][\w.$]*)").unwrap();
+        let global_re = regex::Regex::new(r"^\.(?:globl|global)\s+([A-Za-z_.$][\w.$]*)").unwrap();
+        let label_re = regex::Regex::new(r"^([A-Za-z_.$][\w.$]*):").unwrap();
+        let section_re = regex::Regex::new(r#"^\.(?:section|pushsection)\s+"?([\w.$]+)"?"#).unwrap();
+        let macro_def_re = regex::Regex::new(r"^\.macro\s+([A-Za-z_][\w]*)").unwrap();
+        let include_re = regex::Regex::new(r#"^#\s*include\s+[<"]([^>"]+)[>"]"#).unwrap();
+        let first_token_re = regex::Regex::new(r"^([A-Za-z_][\w]*)").unwrap();
+        
+        let mut sections: Vec<String> = Vec::new();
+        let mut section_stack: Vec<String> = Vec::new();
+        let mut current_section = ".text".to_string();
+        let mut globals: Vec<String> = Vec::new();
+        let mut entry_points: Vec<AsmEntryPoint> = Vec::new();
+        let mut macros_defined: Vec<String> = Vec::new();
+        let mut macro_usage: std::collections::BTreeMap<String, usize> = std::collections::BTreeMap::new();
+        let mut includes: Vec<String> = Vec::new();
+        
+        for (index, raw_line) in content.lines().enumerate() {
+            let line = Self::strip_comment(raw_line, syntax).trim();
+            if line.is_empty() {
+                continue;
+            }
+            
+            if let Some(caps) = include_re.captures(line) {
+                includes.push(caps[1].to_string());
+                continue;
+            }
+            
+            // Section changes
+            if let Some(caps) = section_re.captures(line) {
+                if line.starts_with(".pushsection") {
+                    section_stack.push(current_section.clone());
+                }
+                current_section = caps[1].to_string();
+            } else if line.starts_with(".popsection") {
+                current_section = section_stack.pop().unwrap_or_else(|| ".text".to_string());
+            } else if let Some(section) = [".text", ".data", ".bss", ".rodata"].iter().find(|s| line == **s || line.starts_with(&format!("{} ", s))) {
+                current_section = section.to_string();
+            } else if let Some(section) = match line {
+                "__HEAD" => Some(".head.text"),
+                "__INIT" => Some(".init.text"),
+                "__FINIT" => Some(".text"),
+                "__REF" => Some(".ref.text"),
+                _ => None,
+            } {
+                current_section = section.to_string();
+            }
+            if !sections.contains(&current_section) {
+                sections.push(current_section.clone());
+            }
+            
+            // Macro definitions and usage
+            if let Some(caps) = macro_def_re.captures(line) {
+                macros_defined.push(caps[1].to_string());
+                continue;
+            }
+            if let Some(caps) = first_token_re.captures(line) {
+                let token = &caps[1];
+                let is_cpp_macro = token.len() > 1 && token.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_');
+                if macros_defined.iter().any(|m| m == token) || is_cpp_macro {
+                    *macro_usage.entry(token.to_string()).or_insert(0) += 1;
+                }
+            }
+            
+            // Entry points
+            if let Some(caps) = global_re.captures(line) {
+                globals.push(caps[1].to_string());
+            } else if let Some(caps) = annotation_re.captures(line) {
+                let macro_name = &caps[1];
+                if macro_name.ends_with("_END") || macro_name == "ENDPROC" {
+                    continue;
+                }
+                let kind = match caps.get(2).map(|m| m.as_str()) {
+                    Some("FUNC") => "function",
+                    Some("DATA") => "data",
+                    Some(_) => "code",
+                    None if macro_name == "ENTRY" => "function",
+                    None => "code",
+                };
+                let local = caps.get(3).map_or(false, |m| m.as_str() == "LOCAL");
+                entry_points.push(AsmEntryPoint {
+                    name: caps[4].to_string(),
+                    line: index + 1,
+                    global: !local,
+                    section: current_section.clone(),
+                    kind: kind.to_string(),
+                });
+            } else if let Some(caps) = label_re.captures(line) {
+                let name = caps[1].to_string();
+                // Numeric and .L local labels are never entry points
+                if !name.starts_with(".L") && !entry_points.iter().any(|e| e.name == name) {
+                    entry_points.push(AsmEntryPoint {
+                        name,
+                        line: index + 1,
+                        global: false,
+                        section: current_section.clone(),
+                        kind: "label".to_string(),
+                    });
+                }
+            }
+        }
+        
+        // `.globl` may precede or follow the label
+        for entry in &mut entry_points {
+            if globals.contains(&entry.name) {
+                entry.global = true;
+            }
+        }
+        // Keep plain labels only when they are global (local labels are branch targets)
+        entry_points.retain(|entry| entry.kind != "label" || entry.global);
+        
+        let mut analysis = serde_json::json!({
+            "syntax": syntax,
+            "includes": includes,
+        });
+        if self.extract_symbol_names {
+            analysis["entry_points"] = serde_json::json!(entry_points);
+        }
+        if self.extract_section_info {
+            analysis["sections"] = serde_json::json!(sections);
+        }
+        if self.extract_macro_usage {
+            analysis["macros_defined"] = serde_json::json!(macros_defined);
+            analysis["macro_usage"] = serde_json::json!(macro_usage);
+        }
+        analysis
+    }
+}
+
 impl Parser for AssemblyParser {
     fn parse_file(&self, path: &PathBuf) -> Result<Option<KernelComponent>, String> {
         // Read the file content
@@ -253,11 +483,29 @@ impl Parser for AssemblyParser {
             .unwrap_or("unknown")
             .to_string();
         
+        let analysis = self.analyze(path, &content);
+        
         // Create component
         let mut component = KernelComponent::default();
         component.name = name;
         component.component_type = ComponentType::Other;
         component.source_files.push(path.clone());
+        component.architecture = match analysis["syntax"].as_str() {
+            Some("X86Att") | Some("X86Intel") => vec![KernelArchitecture::X86_64],
+            Some("Arm64") => vec![KernelArchitecture::ARM64],
+            Some("RiscV") => vec![KernelArchitecture::RISC_V64],
+            _ => Vec::new(),
+        };
+        if let Some(includes) = analysis["includes"].as_array() {
+            component.dependencies.extend(includes.iter().filter_map(|include| include.as_str().map(String::from)));
+        }
+        component.description = analysis["entry_points"].as_array()
+            .filter(|entries| !entries.is_empty())
+            .map(|entries| format!(
+                "Assembly entry points: {}",
+                entries.iter().filter_map(|entry| entry["name"].as_str()).collect::<Vec<_>>().join(", ")
+            ));
+        component.metadata = serde_json::json!({ "assembly": analysis });
         
         Ok(Some(component))
     }
@@ -669,3 +917,31 @@ impl Parser for MultiParser {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_assembly_analysis() {
+        let parser = AssemblyParser::new();
+        let path = PathBuf::from("arch/arm64/kernel/head.S");
+        let content = "#include <linux/linkage.h>\n\t__HEAD\n\t.macro enter_el2 tmp\n\tmov \\tmp, x0\n\t.endm\nSYM_CODE_START(primary_entry)\n\tenter_el2 x1\n\tbl\t__cpu_setup // setup\nSYM_CODE_END(primary_entry)\n\t.section \".idmap.text\",\"a\"\nSYM_FUNC_START_LOCAL(__cpu_setup)\n\tret\nSYM_FUNC_END(__cpu_setup)\n";
+        
+        let analysis = parser.analyze(&path, content);
+        assert_eq!(analysis["syntax"], "Arm64");
+        assert_eq!(analysis["includes"][0], "linux/linkage.h");
+        
+        let entries = analysis["entry_points"].as_array().unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0]["name"], "primary_entry");
+        assert_eq!(entries[0]["section"], ".head.text");
+        assert_eq!(entries[1]["name"], "__cpu_setup");
+        assert_eq!(entries[1]["global"], false);
+        assert_eq!(entries[1]["section"], ".idmap.text");
+        
+        assert_eq!(analysis["macros_defined"][0], "enter_el2");
+        assert_eq!(analysis["macro_usage"]["enter_el2"], 1);
+        assert_eq!(analysis["macro_usage"]["SYM_CODE_START"], 1);
+    }
+}
@@ -0,0 +1,592 @@
+// Patch Manager for OSland Kernel Extractor
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use crate::kernel_extractor::KernelExtractorError;
+
+/// Number of context lines in generated patches
+const CONTEXT_LINES: usize = 3;
+
+/// Upstream baseline of a component
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpstreamInfo {
+    /// Kernel version the baseline was extracted from
+    pub version: String,
+    
+    /// Tracked files, relative to the component directory
+    pub files: Vec<String>,
+}
+
+/// Hunk of a unified diff
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hunk {
+    /// 1-based start line in the original file
+    pub old_start: usize,
+    
+    /// Lines expected in the original file (context and removed lines)
+    pub old_lines: Vec<String>,
+    
+    /// Lines replacing them (context and added lines)
+    pub new_lines: Vec<String>,
+    
+    /// Raw hunk text, used for reject files
+    pub text: String,
+}
+
+/// Changes of a patch to one file
+#[derive(Debug, Clone, PartialEq)]
+pub struct FilePatch {
+    /// File name relative to the component directory
+    pub file: String,
+    
+    /// Hunks, in file order
+    pub hunks: Vec<Hunk>,
+}
+
+/// Hunk that could not be applied
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatchConflict {
+    /// Patch name
+    pub patch: String,
+    
+    /// File name
+    pub file: String,
+    
+    /// 1-based hunk index within the file
+    pub hunk: usize,
+    
+    /// Conflict description
+    pub message: String,
+}
+
+/// Result of re-applying a component's patch series onto a new upstream version
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReapplyReport {
+    /// Component name
+    pub component: String,
+    
+    /// Previous upstream version
+    pub old_version: String,
+    
+    /// New upstream version
+    pub new_version: String,
+    
+    /// Patches applied without conflicts
+    pub applied: Vec<String>,
+    
+    /// Hunks that failed to apply (written to `.rej` files in the work directory)
+    pub conflicts: Vec<PatchConflict>,
+}
+
+impl ReapplyReport {
+    /// Whether every patch applied cleanly
+    pub fn is_clean(&self) -> bool {
+        self.conflicts.is_empty()
+    }
+}
+
+/// Parse a unified diff into per-file patches
+pub fn parse_patch(text: &str) -> Result<Vec<FilePatch>, KernelExtractorError> {
+    let hunk_re = regex::Regex::new(r"^@@ -(\d+)(?:,(\d+))? \+\d+(?:,(\d+))? @@").unwrap();
+    let mut files: Vec<FilePatch> = Vec::new();
+    // Lines still expected in the current hunk body (old side, new side)
+    let mut remaining = (0usize, 0usize);
+    
+    for line in text.lines() {
+        if remaining.0 > 0 || remaining.1 > 0 {
+            let hunk = files.last_mut()
+                .and_then(|file| file.hunks.last_mut())
+                .ok_or_else(|| KernelExtractorError::ParseError("Hunk body without header".to_string()))?;
+            let (marker, content) = line.split_at(line.len().min(1));
+            match marker {
+                " " | "" => {
+                    hunk.old_lines.push(content.to_string());
+                    hunk.new_lines.push(content.to_string());
+                    remaining = (remaining.0.saturating_sub(1), remaining.1.saturating_sub(1));
+                }
+                "-" => {
+                    hunk.old_lines.push(content.to_string());
+                    remaining.0 = remaining.0.saturating_sub(1);
+                }
+                "+" => {
+                    hunk.new_lines.push(content.to_string());
+                    remaining.1 = remaining.1.saturating_sub(1);
+                }
+                // "\ No newline at end of file"
+                _ => {}
+            }
+            hunk.text.push_str(line);
+            hunk.text.push('\n');
+        } else if let Some(target) = line.strip_prefix("+++ ") {
+            let target = target.split('\t').next().unwrap_or(target).trim();
+            let file = target.strip_prefix("b/").unwrap_or(target).to_string();
+            files.push(FilePatch { file, hunks: Vec::new() });
+        } else if let Some(caps) = hunk_re.captures(line) {
+            let file = files.last_mut()
+                .ok_or_else(|| KernelExtractorError::ParseError(format!("Hunk without file header: {}", line)))?;
+            let count = |index: usize| caps.get(index).map_or(1, |m| m.as_str().parse().unwrap_or(1));
+            remaining = (count(2), count(3));
+            file.hunks.push(Hunk {
+                old_start: caps[1].parse().unwrap_or(1),
+                old_lines: Vec::new(),
+                new_lines: Vec::new(),
+                text: format!("{}\n", line),
+            });
+        }
+    }
+    
+    Ok(files)
+}
+
+/// Generate a unified diff between two versions of a file
+pub fn diff_file(file: &str, old: &str, new: &str) -> String {
+    if old == new {
+        return String::new();
+    }
+    similar::TextDiff::from_lines(old, new)
+        .unified_diff()
+        .context_radius(CONTEXT_LINES)
+        .header(&format!("a/{}", file), &format!("b/{}", file))
+        .to_string()
+}
+
+/// Find where `needle` occurs in `lines`, searching outward from `expected`
+fn find_block(lines: &[String], needle: &[String], expected: usize) -> Option<usize> {
+    if needle.is_empty() {
+        return Some(expected.min(lines.len()));
+    }
+    if needle.len() > lines.len() {
+        return None;
+    }
+    let last = lines.len() - needle.len();
+    let expected = expected.min(last);
+    let matches = |start: usize| lines[start..start + needle.len()] == *needle;
+    
+    for distance in 0..=last {
+        if expected >= distance && matches(expected - distance) {
+            return Some(expected - distance);
+        }
+        if expected + distance <= last && matches(expected + distance) {
+            return Some(expected + distance);
+        }
+        if expected < distance && expected + distance > last {
+            break;
+        }
+    }
+    None
+}
+
+/// Apply the hunks of a file patch, returning the failed hunk indices
+///
+/// Hunks are matched at their recorded position first, then at any offset,
+/// and finally with one line of context trimmed from each end.
+pub fn apply_file_patch(lines: &mut Vec<String>, patch: &FilePatch) -> Vec<usize> {
+    let mut failed = Vec::new();
+    let mut offset: isize = 0;
+    
+    for (index, hunk) in patch.hunks.iter().enumerate() {
+        let expected = (hunk.old_start as isize - 1 + offset).max(0) as usize;
+        
+        // Exact match, then fuzz 1 (drop one leading and trailing context line)
+        let placement = find_block(lines, &hunk.old_lines, expected)
+            .map(|start| (start, 0, 0))
+            .or_else(|| {
+                let trimmable = hunk.old_lines.len() > 2 && hunk.new_lines.len() > 2;
+                let lead = usize::from(trimmable && hunk.old_lines.first() == hunk.new_lines.first());
+                let trail = usize::from(trimmable && hunk.old_lines.last() == hunk.new_lines.last());
+                if lead + trail == 0 {
+                    return None;
+                }
+                let core = &hunk.old_lines[lead..hunk.old_lines.len() - trail];
+                find_block(lines, core, expected + lead).map(|start| (start, lead, trail))
+            });
+        
+        match placement {
+            Some((start, lead, trail)) => {
+                let removed = hunk.old_lines.len() - lead - trail;
+                let replacement = hunk.new_lines[lead..hunk.new_lines.len() - trail].to_vec();
+                offset += replacement.len() as isize - removed as isize + (start as isize - lead as isize - expected as isize);
+                lines.splice(start..start + removed, replacement);
+            }
+            None => failed.push(index),
+        }
+    }
+    
+    failed
+}
+
+/// Join lines back into file content
+fn join_lines(lines: &[String]) -> String {
+    let mut content = lines.join("\n");
+    if !content.is_empty() {
+        content.push('\n');
+    }
+    content
+}
+
+/// Split file content into lines
+fn split_lines(content: &str) -> Vec<String> {
+    content.lines().map(String::from).collect()
+}
+
+/// Names of a component's files in its directory
+///
+/// Files keep their layout below the deepest directory they all share, so
+/// same-named files from different directories don't overwrite each other.
+/// Names are `/`-separated relative paths.
+pub fn component_file_names(files: &[PathBuf]) -> Vec<(&PathBuf, String)> {
+    let mut base = files.first().and_then(|file| file.parent()).map(Path::to_path_buf).unwrap_or_default();
+    while !files.iter().all(|file| file.starts_with(&base)) {
+        if !base.pop() {
+            break;
+        }
+    }
+    
+    let mut names: Vec<(&PathBuf, String)> = Vec::new();
+    for file in files {
+        let parts: Vec<String> = file.strip_prefix(&base).unwrap_or(file).components()
+            .filter_map(|component| match component {
+                Component::Normal(part) => Some(part.to_string_lossy().to_string()),
+                _ => None,
+            })
+            .collect();
+        let name = parts.join("/");
+        if !name.is_empty() && !names.iter().any(|(_, existing)| *existing == name) {
+            names.push((file, name));
+        }
+    }
+    names
+}
+
+/// Write a file of a component directory, creating its parent directories
+fn write_component_file(path: &Path, content: &str) -> Result<(), KernelExtractorError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| KernelExtractorError::OutputDirError(format!("Failed to create directory {:?}: {}", parent, e)))?;
+    }
+    fs::write(path, content)
+        .map_err(|e| KernelExtractorError::ExtractionError(format!("Failed to write {:?}: {}", path, e)))
+}
+
+/// Quilt-style Patch Manager
+///
+/// Keeps, for each extracted component, a pristine copy of the upstream files
+/// and an ordered series of patches under `<root>/<component>/`:
+///
+/// ```text
+/// <root>/<component>/upstream.json   upstream version and tracked files
+/// <root>/<component>/upstream/       pristine upstream files
+/// <root>/<component>/series          patch order
+/// <root>/<component>/*.patch         patches
+/// ```
+pub struct PatchManager {
+    /// Patches root directory
+    root: PathBuf,
+}
+
+impl PatchManager {
+    /// Create a patch manager rooted at a directory
+    pub fn new<P: AsRef<Path>>(root: P) -> Self {
+        Self {
+            root: root.as_ref().to_path_buf(),
+        }
+    }
+    
+    /// Directory holding a component's upstream baseline and patches
+    pub fn component_dir(&self, component: &str) -> PathBuf {
+        self.root.join(component)
+    }
+    
+    /// Whether a component has an upstream baseline
+    pub fn is_tracked(&self, component: &str) -> bool {
+        self.component_dir(component).join("upstream.json").exists()
+    }
+    
+    /// Record the pristine upstream files of a component
+    pub fn import_upstream(&self, component: &str, version: &str, files: &[PathBuf]) -> Result<UpstreamInfo, KernelExtractorError> {
+        let upstream_dir = self.component_dir(component).join("upstream");
+        if upstream_dir.exists() {
+            fs::remove_dir_all(&upstream_dir)
+                .map_err(|e| KernelExtractorError::OutputDirError(format!("Failed to clear upstream directory {:?}: {}", upstream_dir, e)))?;
+        }
+        fs::create_dir_all(&upstream_dir)
+            .map_err(|e| KernelExtractorError::OutputDirError(format!("Failed to create upstream directory {:?}: {}", upstream_dir, e)))?;
+        
+        let mut names = Vec::new();
+        for (file, name) in component_file_names(files) {
+            let target = upstream_dir.join(&name);
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)
+                    .map_err(|e| KernelExtractorError::OutputDirError(format!("Failed to create upstream directory {:?}: {}", parent, e)))?;
+            }
+            fs::copy(file, target)
+                .map_err(|e| KernelExtractorError::ExtractionError(format!("Failed to copy upstream file {:?}: {}", file, e)))?;
+            names.push(name);
+        }
+        names.sort();
+        
+        let info = UpstreamInfo {
+            version: version.to_string(),
+            files: names,
+        };
+        let json = serde_json::to_string_pretty(&info)
+            .map_err(|e| KernelExtractorError::ExtractionError(format!("Failed to serialize upstream info: {}", e)))?;
+        fs::write(self.component_dir(component).join("upstream.json"), json)
+            .map_err(|e| KernelExtractorError::ExtractionError(format!("Failed to write upstream info: {}", e)))?;
+        Ok(info)
+    }
+    
+    /// Get a component's upstream baseline
+    pub fn upstream_info(&self, component: &str) -> Result<UpstreamInfo, KernelExtractorError> {
+        let path = self.component_dir(component).join("upstream.json");
+        let content = fs::read_to_string(&path)
+            .map_err(|e| KernelExtractorError::SourceDirError(format!("Component '{}' has no upstream baseline: {}", component, e)))?;
+        serde_json::from_str(&content)
+            .map_err(|e| KernelExtractorError::ParseError(format!("Failed to parse {:?}: {}", path, e)))
+    }
+    
+    /// Get the patch series of a component, in application order
+    pub fn series(&self, component: &str) -> Result<Vec<String>, KernelExtractorError> {
+        let path = self.component_dir(component).join("series");
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        let content = fs::read_to_string(&path)
+            .map_err(|e| KernelExtractorError::SourceDirError(format!("Failed to read series file {:?}: {}", path, e)))?;
+        Ok(content.lines()
+            .map(|line| line.split('#').next().unwrap_or("").trim())
+            .filter(|line| !line.is_empty())
+            .map(String::from)
+            .collect())
+    }
+    
+    fn write_series(&self, component: &str, series: &[String]) -> Result<(), KernelExtractorError> {
+        let mut content = series.join("\n");
+        content.push('\n');
+        fs::write(self.component_dir(component).join("series"), content)
+            .map_err(|e| KernelExtractorError::ExtractionError(format!("Failed to write series file: {}", e)))
+    }
+    
+    /// Read a patch of the series
+    fn read_patch(&self, component: &str, patch: &str) -> Result<Vec<FilePatch>, KernelExtractorError> {
+        let path = self.component_dir(component).join(patch);
+        let content = fs::read_to_string(&path)
+            .map_err(|e| KernelExtractorError::SourceDirError(format!("Failed to read patch {:?}: {}", path, e)))?;
+        parse_patch(&content)
+    }
+    
+    /// Read the pristine upstream files
+    fn upstream_files(&self, component: &str) -> Result<BTreeMap<String, Vec<String>>, KernelExtractorError> {
+        let info = self.upstream_info(component)?;
+        let upstream_dir = self.component_dir(component).join("upstream");
+        let mut files = BTreeMap::new();
+        for name in info.files {
+            let content = fs::read_to_string(upstream_dir.join(&name))
+                .map_err(|e| KernelExtractorError::SourceDirError(format!("Failed to read upstream file {}: {}", name, e)))?;
+            files.insert(name, split_lines(&content));
+        }
+        Ok(files)
+    }
+    
+    /// Apply patches to a set of files, collecting conflicts instead of stopping
+    fn apply_series(
+        &self,
+        component: &str,
+        files: &mut BTreeMap<String, Vec<String>>,
+        patches: &[String],
+        conflicts: &mut Vec<PatchConflict>,
+        rejects: &mut BTreeMap<String, String>,
+    ) -> Result<Vec<String>, KernelExtractorError> {
+        let mut applied = Vec::new();
+        for patch in patches {
+            let mut clean = true;
+            for file_patch in self.read_patch(component, patch)? {
+                let lines = files.entry(file_patch.file.clone()).or_default();
+                for index in apply_file_patch(lines, &file_patch) {
+                    clean = false;
+                    let hunk = &file_patch.hunks[index];
+                    conflicts.push(PatchConflict {
+                        patch: patch.clone(),
+                        file: file_patch.file.clone(),
+                        hunk: index + 1,
+                        message: format!("Hunk #{} (original line {}) does not match the upstream source", index + 1, hunk.old_start),
+                    });
+                    rejects.entry(file_patch.file.clone())
+                        .or_insert_with(|| format!("--- a/{}\n+++ b/{}\n", file_patch.file, file_patch.file))
+                        .push_str(&hunk.text);
+                }
+            }
+            if clean {
+                applied.push(patch.clone());
+            }
+        }
+        Ok(applied)
+    }
+    
+    /// Record the changes in `work_dir` as a patch
+    ///
+    /// Like `quilt refresh`, only the top patch of the series can be refreshed;
+    /// a new name appends a patch capturing the changes on top of the whole
+    /// series. Returns `None` if there are no changes to record.
+    pub fn refresh<P: AsRef<Path>>(&self, component: &str, patch: &str, work_dir: P) -> Result<Option<PathBuf>, KernelExtractorError> {
+        let mut series = self.series(component)?;
+        let base_patches = match series.iter().position(|name| name == patch) {
+            Some(position) if position + 1 == series.len() => series[..position].to_vec(),
+            Some(_) => return Err(KernelExtractorError::ExtractionError(format!("Patch '{}' is not the top patch of component '{}'", patch, component))),
+            None => series.clone(),
+        };
+        
+        let mut base = self.upstream_files(component)?;
+        let (mut conflicts, mut rejects) = (Vec::new(), BTreeMap::new());
+        self.apply_series(component, &mut base, &base_patches, &mut conflicts, &mut rejects)?;
+        if let Some(conflict) = conflicts.first() {
+            return Err(KernelExtractorError::ExtractionError(format!("Patch series does not apply: {} ({})", conflict.message, conflict.patch)));
+        }
+        
+        let mut diff = String::new();
+        for (name, lines) in &base {
+            let path = work_dir.as_ref().join(name);
+            let current = match fs::read_to_string(&path) {
+                Ok(content) => content,
+                // A deleted file is recorded as removing all of its lines
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+                Err(e) => return Err(KernelExtractorError::SourceDirError(format!("Failed to read {:?}: {}", path, e))),
+            };
+            diff.push_str(&diff_file(name, &join_lines(lines), &current));
+        }
+        
+        let patch_path = self.component_dir(component).join(patch);
+        if diff.is_empty() {
+            return Ok(None);
+        }
+        fs::write(&patch_path, diff)
+            .map_err(|e| KernelExtractorError::ExtractionError(format!("Failed to write patch {:?}: {}", patch_path, e)))?;
+        if !series.iter().any(|name| name == patch) {
+            series.push(patch.to_string());
+            self.write_series(component, &series)?;
+        }
+        Ok(Some(patch_path))
+    }
+    
+    /// Re-apply a component's patches on top of a newer upstream version
+    ///
+    /// The new upstream files become the baseline, every patch is applied in
+    /// series order and the result is written to `work_dir`. Hunks that do not
+    /// apply are reported and saved next to the file as `<file>.rej`.
+    pub fn reapply<P: AsRef<Path>>(
+        &self,
+        component: &str,
+        new_version: &str,
+        new_files: &[PathBuf],
+        work_dir: P,
+    ) -> Result<ReapplyReport, KernelExtractorError> {
+        let work_dir = work_dir.as_ref();
+        let old_version = self.upstream_info(component).map(|info| info.version).unwrap_or_default();
+        self.import_upstream(component, new_version, new_files)?;
+        
+        let mut files = self.upstream_files(component)?;
+        let series = self.series(component)?;
+        let (mut conflicts, mut rejects) = (Vec::new(), BTreeMap::new());
+        let applied = self.apply_series(component, &mut files, &series, &mut conflicts, &mut rejects)?;
+        
+        fs::create_dir_all(work_dir)
+            .map_err(|e| KernelExtractorError::OutputDirError(format!("Failed to create work directory {:?}: {}", work_dir, e)))?;
+        for (name, lines) in &files {
+            write_component_file(&work_dir.join(name), &join_lines(lines))?;
+        }
+        for (name, reject) in &rejects {
+            write_component_file(&work_dir.join(format!("{}.rej", name)), reject)?;
+        }
+        
+        Ok(ReapplyReport {
+            component: component.to_string(),
+            old_version,
+            new_version: new_version.to_string(),
+            applied,
+            conflicts,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_refresh_and_reapply() {
+        let dir = std::env::temp_dir().join(format!("osland-patch-manager-{}", std::process::id()));
+        let (upstream_dir, work_dir) = (dir.join("src"), dir.join("work"));
+        fs::create_dir_all(&upstream_dir).unwrap();
+        fs::create_dir_all(&work_dir).unwrap();
+        
+        let original: String = (1..=20).map(|n| format!("line {}\n", n)).collect();
+        let source = upstream_dir.join("drv.c");
+        fs::write(&source, &original).unwrap();
+        
+        let manager = PatchManager::new(dir.join("patches"));
+        manager.import_upstream("drv", "6.1", std::slice::from_ref(&source)).unwrap();
+        fs::write(work_dir.join("drv.c"), original.replace("line 10\n", "line 10 patched\n")).unwrap();
+        assert!(manager.refresh("drv", "fix.patch", &work_dir).unwrap().is_some());
+        assert_eq!(manager.series("drv").unwrap(), vec!["fix.patch".to_string()]);
+        
+        // Upstream inserts lines above the patched region: the hunk applies at an offset
+        fs::write(&source, format!("new 1\nnew 2\n{}", original)).unwrap();
+        let report = manager.reapply("drv", "6.6", std::slice::from_ref(&source), &work_dir).unwrap();
+        assert!(report.is_clean());
+        assert!(fs::read_to_string(work_dir.join("drv.c")).unwrap().contains("line 10 patched"));
+        
+        // Upstream rewrites the patched region: the hunk is rejected
+        fs::write(&source, original.replace("line 9\nline 10\nline 11\n", "rewritten\n")).unwrap();
+        let report = manager.reapply("drv", "6.8", &[source], &work_dir).unwrap();
+        assert_eq!(report.conflicts.len(), 1);
+        assert!(work_dir.join("drv.c.rej").exists());
+        
+        fs::remove_dir_all(&dir).ok();
+    }
+    
+    #[test]
+    fn test_same_named_files_and_unreadable_work_files() {
+        let dir = std::env::temp_dir().join(format!("osland-patch-manager-layout-{}", std::process::id()));
+        let (source_dir, work_dir) = (dir.join("src"), dir.join("work"));
+        for sub in ["net", "fs"] {
+            fs::create_dir_all(source_dir.join(sub)).unwrap();
+            fs::create_dir_all(work_dir.join(sub)).unwrap();
+            fs::write(source_dir.join(sub).join("buffer.c"), format!("int {}_buffer;\n", sub)).unwrap();
+        }
+        let files = vec![source_dir.join("net/buffer.c"), source_dir.join("fs/buffer.c")];
+        
+        // Files are tracked relative to the directory they share
+        let manager = PatchManager::new(dir.join("patches"));
+        let info = manager.import_upstream("buf", "6.1", &files).unwrap();
+        assert_eq!(info.files, vec!["fs/buffer.c".to_string(), "net/buffer.c".to_string()]);
+        
+        fs::write(work_dir.join("net/buffer.c"), "int net_buffer;\nint net_extra;\n").unwrap();
+        fs::write(work_dir.join("fs/buffer.c"), "int fs_buffer;\n").unwrap();
+        let patch = manager.refresh("buf", "extra.patch", &work_dir).unwrap().unwrap();
+        let patch = parse_patch(&fs::read_to_string(patch).unwrap()).unwrap();
+        assert_eq!(patch.len(), 1);
+        assert_eq!(patch[0].file, "net/buffer.c");
+        
+        let report = manager.reapply("buf", "6.2", &files, dir.join("reapplied")).unwrap();
+        assert!(report.is_clean());
+        assert_eq!(fs::read_to_string(dir.join("reapplied/net/buffer.c")).unwrap(), "int net_buffer;\nint net_extra;\n");
+        assert_eq!(fs::read_to_string(dir.join("reapplied/fs/buffer.c")).unwrap(), "int fs_buffer;\n");
+        
+        // A file that cannot be read is an error, not a deletion
+        fs::write(work_dir.join("fs/buffer.c"), [0xff, 0xfe, b'\n']).unwrap();
+        assert!(manager.refresh("buf", "extra.patch", &work_dir).is_err());
+        
+        // A deleted file removes its lines
+        fs::remove_file(work_dir.join("fs/buffer.c")).unwrap();
+        let patch = manager.refresh("buf", "extra.patch", &work_dir).unwrap().unwrap();
+        let patch = parse_patch(&fs::read_to_string(patch).unwrap()).unwrap();
+        let deleted = patch.iter().find(|file| file.file == "fs/buffer.c").unwrap();
+        assert_eq!(deleted.hunks[0].new_lines, Vec::<String>::new());
+        
+        fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,552 @@
+// Security Audit for OSland Kernel Extractor
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use crate::kernel_extractor::api_extractor::{skip_literal, strip_comments};
+use crate::kernel_extractor::{KernelComponent, KernelExtractorError};
+
+/// SARIF schema referenced by exported logs
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// Finding severity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Severity {
+    Note,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    /// SARIF `level` of the severity
+    pub fn sarif_level(&self) -> &'static str {
+        match self {
+            Severity::Note => "note",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        }
+    }
+}
+
+/// Check performed by an audit rule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RuleCheck {
+    /// Any call to these functions is a finding
+    BannedFunction {
+        functions: Vec<String>,
+        #[serde(default)]
+        replacement: Option<String>,
+    },
+    
+    /// Calls to these functions whose return value is discarded
+    UncheckedReturn {
+        functions: Vec<String>,
+    },
+    
+    /// Copies whose length argument is not bounded by a preceding comparison
+    UnboundedCopy {
+        functions: Vec<String>,
+        /// 0-based index of the length argument
+        length_argument: usize,
+        /// Number of preceding lines searched for a bounds check
+        #[serde(default = "default_window")]
+        window: usize,
+    },
+    
+    /// Any match of a regular expression
+    Pattern {
+        regex: String,
+    },
+}
+
+fn default_window() -> usize {
+    15
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// Audit rule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditRule {
+    /// Stable rule identifier (used as the SARIF rule ID)
+    pub id: String,
+    
+    /// Short rule name
+    pub name: String,
+    
+    /// Rule description shown with findings
+    pub description: String,
+    
+    /// Severity of findings
+    pub severity: Severity,
+    
+    /// Check performed by the rule
+    pub check: RuleCheck,
+    
+    /// Whether the rule is enabled
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+/// Security audit settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditConfig {
+    /// Audit rules
+    #[serde(default = "default_rules")]
+    pub rules: Vec<AuditRule>,
+    
+    /// Findings below this severity are dropped
+    #[serde(default = "default_min_severity")]
+    pub min_severity: Severity,
+}
+
+fn default_min_severity() -> Severity {
+    Severity::Note
+}
+
+impl Default for AuditConfig {
+    fn default() -> Self {
+        Self {
+            rules: default_rules(),
+            min_severity: default_min_severity(),
+        }
+    }
+}
+
+impl AuditConfig {
+    /// Load audit settings from a JSON file; rules default to the built-in set
+    pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<Self, KernelExtractorError> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| KernelExtractorError::SourceDirError(format!("Failed to read audit rules: {}", e)))?;
+        serde_json::from_str(&content)
+            .map_err(|e| KernelExtractorError::ParseError(format!("Failed to parse audit rules: {}", e)))
+    }
+}
+
+/// Built-in audit rules
+pub fn default_rules() -> Vec<AuditRule> {
+    let rule = |id: &str, name: &str, description: &str, severity: Severity, check: RuleCheck| AuditRule {
+        id: id.to_string(),
+        name: name.to_string(),
+        description: description.to_string(),
+        severity,
+        check,
+        enabled: true,
+    };
+    let names = |functions: &[&str]| functions.iter().map(|f| f.to_string()).collect::<Vec<_>>();
+    
+    vec![
+        rule(
+            "OSL-SEC-001", "banned-string-function",
+            "Unbounded string function can overflow its destination buffer",
+            Severity::Error,
+            RuleCheck::BannedFunction {
+                functions: names(&["strcpy", "strcat", "sprintf", "vsprintf", "gets"]),
+                replacement: Some("strscpy, strlcat, snprintf or scnprintf".to_string()),
+            },
+        ),
+        rule(
+            "OSL-SEC-002", "unchecked-user-copy",
+            "Return value of a user memory access is ignored; a fault leaves the buffer partially initialized",
+            Severity::Error,
+            RuleCheck::UncheckedReturn {
+                functions: names(&["copy_from_user", "copy_to_user", "get_user", "put_user", "strncpy_from_user", "clear_user"]),
+            },
+        ),
+        rule(
+            "OSL-SEC-003", "unbounded-user-copy",
+            "Length of a user copy is not checked against the destination size",
+            Severity::Warning,
+            RuleCheck::UnboundedCopy {
+                functions: names(&["copy_from_user", "copy_to_user"]),
+                length_argument: 2,
+                window: default_window(),
+            },
+        ),
+        rule(
+            "OSL-SEC-004", "unbounded-memcpy",
+            "Length of a memory copy is not bounds checked",
+            Severity::Warning,
+            RuleCheck::UnboundedCopy {
+                functions: names(&["memcpy", "memmove"]),
+                length_argument: 2,
+                window: default_window(),
+            },
+        ),
+        rule(
+            "OSL-SEC-005", "deprecated-string-conversion",
+            "simple_strto* ignores overflow; use kstrto* instead",
+            Severity::Note,
+            RuleCheck::Pattern {
+                regex: r"\bsimple_strto(?:l|ul|ll|ull)\s*\(".to_string(),
+            },
+        ),
+    ]
+}
+
+/// Security finding
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Finding {
+    /// Rule that produced the finding
+    pub rule_id: String,
+    
+    /// Finding severity
+    pub severity: Severity,
+    
+    /// Finding message
+    pub message: String,
+    
+    /// File containing the finding
+    pub file: PathBuf,
+    
+    /// 1-based line
+    pub line: usize,
+    
+    /// 1-based column
+    pub column: usize,
+    
+    /// Source line of the finding
+    pub snippet: String,
+}
+
+/// Audit result for a component
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AuditReport {
+    /// Component name
+    pub component: String,
+    
+    /// Findings, ordered by file and line
+    pub findings: Vec<Finding>,
+}
+
+impl AuditReport {
+    /// Number of findings per severity
+    pub fn counts(&self) -> BTreeMap<Severity, usize> {
+        let mut counts = BTreeMap::new();
+        for finding in &self.findings {
+            *counts.entry(finding.severity).or_insert(0) += 1;
+        }
+        counts
+    }
+    
+    /// Highest severity among the findings
+    pub fn max_severity(&self) -> Option<Severity> {
+        self.findings.iter().map(|finding| finding.severity).max()
+    }
+}
+
+/// Compiled form of a rule check
+enum CompiledCheck {
+    Calls(regex::Regex),
+    Pattern(regex::Regex),
+}
+
+/// Security Auditor
+///
+/// Line-oriented static analysis of extracted C sources. Rules are
+/// configurable; the built-in set covers banned string functions, ignored
+/// results of user memory accesses and copies without a bounds check.
+pub struct SecurityAuditor {
+    config: AuditConfig,
+    compiled: Vec<(usize, CompiledCheck)>,
+}
+
+impl SecurityAuditor {
+    /// Create a new auditor, compiling the enabled rules
+    pub fn new(config: AuditConfig) -> Result<Self, KernelExtractorError> {
+        let mut compiled = Vec::new();
+        for (index, rule) in config.rules.iter().enumerate().filter(|(_, rule)| rule.enabled) {
+            let check = match &rule.check {
+                RuleCheck::BannedFunction { functions, .. }
+                | RuleCheck::UncheckedReturn { functions }
+                | RuleCheck::UnboundedCopy { functions, .. } => {
+                    let alternatives: Vec<String> = functions.iter().map(|f| regex::escape(f)).collect();
+                    CompiledCheck::Calls(regex::Regex::new(&format!(r"\b({})\s*\(", alternatives.join("|")))
+                        .map_err(|e| KernelExtractorError::ParseError(format!("Invalid rule {}: {}", rule.id, e)))?)
+                }
+                RuleCheck::Pattern { regex } => CompiledCheck::Pattern(regex::Regex::new(regex)
+                    .map_err(|e| KernelExtractorError::ParseError(format!("Invalid rule {}: {}", rule.id, e)))?),
+            };
+            compiled.push((index, check));
+        }
+        
+        Ok(Self { config, compiled })
+    }
+    
+    /// Get the audit rules
+    pub fn rules(&self) -> &[AuditRule] {
+        &self.config.rules
+    }
+    
+    /// Audit every source and header file of a component
+    pub fn audit(&self, component: &KernelComponent) -> AuditReport {
+        let mut findings = Vec::new();
+        for path in component.source_files.iter().chain(&component.header_files) {
+            if let Ok(content) = fs::read_to_string(path) {
+                findings.extend(self.audit_source(path, &content));
+            }
+        }
+        findings.sort_by(|a, b| (&a.file, a.line, a.column).cmp(&(&b.file, b.line, b.column)));
+        
+        AuditReport {
+            component: component.name.clone(),
+            findings,
+        }
+    }
+    
+    /// Audit a single source file
+    pub fn audit_source(&self, path: &Path, source: &str) -> Vec<Finding> {
+        let text = blank_literals(&strip_comments(source));
+        let line_starts: Vec<usize> = std::iter::once(0)
+            .chain(text.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        let original_lines: Vec<&str> = source.lines().collect();
+        let position = |offset: usize| {
+            let line = line_starts.partition_point(|start| *start <= offset);
+            (line, offset - line_starts[line - 1] + 1)
+        };
+        
+        let mut findings = Vec::new();
+        for (index, check) in &self.compiled {
+            let rule = &self.config.rules[*index];
+            if rule.severity < self.config.min_severity {
+                continue;
+            }
+            
+            let mut report = |offset: usize, message: String| {
+                let (line, column) = position(offset);
+                findings.push(Finding {
+                    rule_id: rule.id.clone(),
+                    severity: rule.severity,
+                    message,
+                    file: path.to_path_buf(),
+                    line,
+                    column,
+                    snippet: original_lines.get(line - 1).map(|l| l.trim().to_string()).unwrap_or_default(),
+                });
+            };
+            
+            match check {
+                CompiledCheck::Pattern(regex) => {
+                    for found in regex.find_iter(&text) {
+                        report(found.start(), format!("{}: {}", rule.name, rule.description));
+                    }
+                }
+                CompiledCheck::Calls(regex) => {
+                    for caps in regex.captures_iter(&text) {
+                        let call = caps.get(0).unwrap();
+                        let function = &caps[1];
+                        match &rule.check {
+                            RuleCheck::BannedFunction { replacement, .. } => {
+                                let hint = replacement.as_ref().map(|r| format!(" (use {})", r)).unwrap_or_default();
+                                report(call.start(), format!("Call to banned function {}(){}: {}", function, hint, rule.description));
+                            }
+                            RuleCheck::UncheckedReturn { .. } => {
+                                if !is_result_used(&text, call.start()) {
+                                    report(call.start(), format!("Result of {}() is not checked: {}", function, rule.description));
+                                }
+                            }
+                            RuleCheck::UnboundedCopy { length_argument, window, .. } => {
+                                let arguments = call_arguments(&text, call.end() - 1);
+                                if let Some(length) = arguments.get(*length_argument) {
+                                    let (line, _) = position(call.start());
+                                    let context_start = line_starts[line.saturating_sub(*window + 1)];
+                                    if !is_bounded(length, &text[context_start..call.start()]) {
+                                        report(call.start(), format!("Length '{}' of {}() is not bounds checked: {}", length, function, rule.description));
+                                    }
+                                }
+                            }
+                            RuleCheck::Pattern { .. } => {}
+                        }
+                    }
+                }
+            }
+        }
+        
+        findings
+    }
+}
+
+/// Replace the contents of string and character literals with spaces
+fn blank_literals(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut out = bytes.to_vec();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'"' || bytes[i] == b'\'' {
+            let end = skip_literal(bytes, i);
+            for byte in &mut out[i + 1..end.saturating_sub(1).max(i + 1)] {
+                *byte = b' ';
+            }
+            i = end;
+        } else {
+            i += 1;
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Check whether the value of the call starting at `start` is consumed
+///
+/// The value counts as used when anything other than a `(void)` cast precedes
+/// the call in its statement: an assignment, a condition, a return or an
+/// enclosing call.
+fn is_result_used(text: &str, start: usize) -> bool {
+    let statement_start = text[..start].rfind(|c| c == ';' || c == '{' || c == '}').map_or(0, |i| i + 1);
+    let prefix: String = text[statement_start..start].split_whitespace().collect();
+    !prefix.is_empty() && prefix != "(void)"
+}
+
+/// Split the arguments of the call whose opening parenthesis is at `open`
+fn call_arguments(text: &str, open: usize) -> Vec<String> {
+    let bytes = text.as_bytes();
+    let mut arguments = Vec::new();
+    let mut depth = 0usize;
+    let mut current = String::new();
+    for &byte in &bytes[open..] {
+        match byte {
+            b'(' | b'[' | b'{' => {
+                depth += 1;
+                if depth > 1 {
+                    current.push(byte as char);
+                }
+            }
+            b')' | b']' | b'}' => {
+                depth -= 1;
+                if depth == 0 {
+                    arguments.push(current.trim().to_string());
+                    break;
+                }
+                current.push(byte as char);
+            }
+            b',' if depth == 1 => arguments.push(std::mem::take(&mut current).trim().to_string()),
+            _ => current.push(byte as char),
+        }
+    }
+    arguments.into_iter().map(|a| a.split_whitespace().collect::<Vec<_>>().join(" ")).collect()
+}
+
+/// Check whether a copy length is constant, clamped, or compared in `context`
+fn is_bounded(length: &str, context: &str) -> bool {
+    let length = length.trim_start_matches("(size_t)").trim();
+    if length.parse::<u64>().is_ok()
+        || length.starts_with("0x")
+        || length.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+        || ["sizeof", "min(", "min_t(", "clamp(", "clamp_t(", "ARRAY_SIZE("].iter().any(|safe| length.contains(safe))
+    {
+        return true;
+    }
+    
+    // `->` is not a comparison
+    let context = context.replace("->", ".");
+    let expression = regex::escape(&length.replace("->", "."));
+    let comparison = regex::Regex::new(&format!(r"(?:\b|\W){0}\s*(?:<|>|<=|>=|==)|(?:<|>|<=|>=)\s*{0}\b", expression));
+    comparison.map_or(false, |comparison| comparison.is_match(&context))
+}
+
+/// Build a SARIF 2.1.0 log of audit reports
+pub fn to_sarif(rules: &[AuditRule], reports: &[AuditReport]) -> serde_json::Value {
+    let rule_index: BTreeMap<&str, usize> = rules.iter().enumerate().map(|(i, rule)| (rule.id.as_str(), i)).collect();
+    let rule_index = &rule_index;
+    let sarif_rules: Vec<serde_json::Value> = rules.iter().map(|rule| json!({
+        "id": rule.id,
+        "name": rule.name,
+        "shortDescription": { "text": rule.description },
+        "defaultConfiguration": { "level": rule.severity.sarif_level(), "enabled": rule.enabled },
+    })).collect();
+    
+    let results: Vec<serde_json::Value> = reports.iter().flat_map(|report| report.findings.iter().map(move |finding| {
+        let mut result = json!({
+            "ruleId": finding.rule_id,
+            "level": finding.severity.sarif_level(),
+            "message": { "text": finding.message },
+            "locations": [{
+                "physicalLocation": {
+                    "artifactLocation": { "uri": finding.file.to_string_lossy().replace('\\', "/") },
+                    "region": {
+                        "startLine": finding.line,
+                        "startColumn": finding.column,
+                        "snippet": { "text": finding.snippet },
+                    },
+                },
+                "logicalLocations": [{ "name": report.component, "kind": "module" }],
+            }],
+        });
+        if let Some(index) = rule_index.get(finding.rule_id.as_str()) {
+            result["ruleIndex"] = json!(index);
+        }
+        result
+    })).collect();
+    
+    json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "osland-security-audit",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": sarif_rules,
+                },
+            },
+            "results": results,
+        }],
+    })
+}
+
+/// Write a SARIF log of audit reports
+pub fn write_sarif<P: AsRef<Path>>(path: P, rules: &[AuditRule], reports: &[AuditReport]) -> Result<(), KernelExtractorError> {
+    let sarif = serde_json::to_string_pretty(&to_sarif(rules, reports))
+        .map_err(|e| KernelExtractorError::ExtractionError(format!("Failed to serialize SARIF log: {}", e)))?;
+    fs::write(path.as_ref(), sarif)
+        .map_err(|e| KernelExtractorError::OutputDirError(format!("Failed to write SARIF log {:?}: {}", path.as_ref(), e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    const SOURCE: &str = r#"static long dev_write(struct file *f, const char __user *buf, size_t len)
+{
+	char name[16];
+	char *msg = "strcpy(a, b) is fine in a string";
+
+	/* strcpy(name, other); in a comment */
+	strcpy(name, dev_name);
+	copy_from_user(kbuf, buf, len);
+	if (len > sizeof(kbuf))
+		return -EINVAL;
+	if (copy_from_user(kbuf, buf, len))
+		return -EFAULT;
+	memcpy(name, hdr->data, hdr->len);
+	return 0;
+}
+"#;
+
+    #[test]
+    fn test_default_rules() {
+        let auditor = SecurityAuditor::new(AuditConfig::default()).unwrap();
+        let findings = auditor.audit_source(Path::new("dev.c"), SOURCE);
+        let ids: Vec<(&str, usize)> = findings.iter().map(|f| (f.rule_id.as_str(), f.line)).collect();
+        
+        assert!(ids.contains(&("OSL-SEC-001", 7)));
+        assert!(ids.contains(&("OSL-SEC-002", 8)));
+        assert!(ids.contains(&("OSL-SEC-003", 8)));
+        assert!(ids.contains(&("OSL-SEC-004", 13)));
+        // Checked and bounded copy on line 11, strings and comments are ignored
+        assert!(!ids.iter().any(|(_, line)| *line == 11 || *line == 4 || *line == 6));
+        
+        let report = AuditReport { component: "dev".to_string(), findings };
+        assert_eq!(report.max_severity(), Some(Severity::Error));
+        let sarif = to_sarif(auditor.rules(), &[report]);
+        assert_eq!(sarif["runs"][0]["results"][0]["level"], "error");
+        assert_eq!(sarif["runs"][0]["results"][0]["locations"][0]["physicalLocation"]["region"]["startLine"], 7);
+    }
+}
@@ -6,6 +6,7 @@ use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
 use crate::kernel_visualization::visualization_data::{KernelStructure, KernelComponentInfo};
+use crate::kernel_visualization::what_if::{WhatIfImpact, WhatIfSession};
 use crate::kernel_extractor::architecture::{Architecture, ArchitectureSupport};
 
 /// Architecture view configuration
@@ -40,6 +41,8 @@ pub struct ArchitectureViewer {
     pub config: ArchitectureViewConfig,
     /// Architecture support information
     architecture_support: ArchitectureSupport,
+    /// Pending what-if changes
+    what_if: Option<WhatIfSession>,
 }
 
 impl ArchitectureViewer {
@@ -50,6 +53,7 @@ impl ArchitectureViewer {
         Self {
             config: ArchitectureViewConfig::default(),
             architecture_support,
+            what_if: None,
         }
     }
     
@@ -153,6 +157,43 @@ impl ArchitectureViewer {
         !self.is_architecture_specific(component)
     }
     
+    /// Start what-if editing on a copy of the kernel structure
+    pub fn begin_what_if(&mut self, kernel_structure: &KernelStructure) {
+        self.what_if = Some(WhatIfSession::new(kernel_structure.clone()));
+    }
+    
+    /// Hypothetically remove a component and get the recomputed impact
+    pub fn what_if_remove(&mut self, component_name: &str) -> Result<WhatIfImpact, String> {
+        self.what_if.as_mut().ok_or("No what-if session")?.remove(component_name)
+    }
+    
+    /// Hypothetically replace a component and get the recomputed impact
+    pub fn what_if_replace(&mut self, component_name: &str, replacement: KernelComponentInfo) -> Result<WhatIfImpact, String> {
+        self.what_if.as_mut().ok_or("No what-if session")?.replace(component_name, replacement)
+    }
+    
+    /// Revert the last what-if change
+    pub fn undo_what_if(&mut self) -> Option<WhatIfImpact> {
+        let session = self.what_if.as_mut()?;
+        session.undo()?;
+        Some(session.impact())
+    }
+    
+    /// Get the pending what-if session
+    pub fn what_if_session(&self) -> Option<&WhatIfSession> {
+        self.what_if.as_ref()
+    }
+    
+    /// Drop the pending what-if changes
+    pub fn discard_what_if(&mut self) {
+        self.what_if = None;
+    }
+    
+    /// Apply the pending what-if changes, returning the changed structure
+    pub fn apply_what_if(&mut self) -> Option<KernelStructure> {
+        self.what_if.take().map(WhatIfSession::apply)
+    }
+    
     /// Get performance-critical components for the target architecture
     pub fn get_performance_critical_components(
         &self, 
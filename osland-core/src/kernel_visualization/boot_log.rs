@@ -0,0 +1,389 @@
+// Kernel boot log ingestion for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! Structured boot logs of test runs.
+//!
+//! Serial console output captured from QEMU test runs is parsed as dmesg lines
+//! (`<level>[timestamp] subsystem: message`) and stored in the `boot_events` table.
+//! Failing lines are correlated with the kernel components and tiles that provide
+//! the subsystem that printed them, so the kernel visualization can highlight them.
+
+use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use crate::build_engine::test_orchestrator::TestReport;
+use crate::dbos_integration::dbos_core::{ColumnDefinition, ColumnType, IndexDefinition, TableDefinition, SharedTables};
+use crate::kernel_visualization::visualization_data::KernelStructure;
+use crate::tile_engine::tile_core::TileGraph;
+
+/// Table of boot log lines
+pub const BOOT_EVENTS_TABLE: &str = "boot_events";
+
+/// Tile property listing the kernel subsystems a tile provides (comma-separated)
+pub const TILE_SUBSYSTEMS_PROPERTY: &str = "kernel.subsystems";
+
+/// Report headers that mark a failure regardless of the log level when a message starts with them
+const FAILURE_HEADERS: [&str; 5] = ["Kernel panic", "BUG:", "Oops", "WARNING:", "Call Trace"];
+
+/// Words that mark a failure regardless of the log level
+const FAILURE_WORDS: [&str; 3] = ["failed", "error", "timed out"];
+
+/// Kernel log level (`<0>` to `<7>`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum BootLevel {
+    Emergency,
+    Alert,
+    Critical,
+    Error,
+    Warning,
+    Notice,
+    Info,
+    Debug,
+}
+
+impl BootLevel {
+    /// Level of a syslog priority
+    pub fn from_priority(priority: u8) -> Self {
+        match priority & 7 {
+            0 => BootLevel::Emergency,
+            1 => BootLevel::Alert,
+            2 => BootLevel::Critical,
+            3 => BootLevel::Error,
+            4 => BootLevel::Warning,
+            5 => BootLevel::Notice,
+            6 => BootLevel::Info,
+            _ => BootLevel::Debug,
+        }
+    }
+}
+
+/// One parsed boot log line
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BootEvent {
+    /// Test run the line belongs to
+    pub run_id: String,
+    
+    /// Project name
+    pub project: String,
+    
+    /// Line number in the console output
+    pub line: usize,
+    
+    /// Seconds since boot, if the line was timestamped
+    pub timestamp: Option<f64>,
+    
+    /// Log level (`Info` when the line has no priority prefix)
+    pub level: BootLevel,
+    
+    /// Subsystem or driver that printed the line
+    pub subsystem: Option<String>,
+    
+    /// Message without prefixes
+    pub message: String,
+    
+    /// Whether the line reports a failure
+    pub failure: bool,
+}
+
+/// Parse console output in dmesg format
+///
+/// Lines without a timestamp are kept (e.g. init output); empty lines are skipped.
+pub fn parse_dmesg(run_id: &str, project: &str, log: &str) -> Vec<BootEvent> {
+    log.lines().enumerate().filter_map(|(index, raw)| {
+        let mut rest = raw.trim_end_matches('\r').trim_start();
+        
+        let mut level = BootLevel::Info;
+        if let Some((priority, after)) = rest.strip_prefix('<').and_then(|s| s.split_once('>')) {
+            if let Ok(priority) = priority.parse::<u8>() {
+                level = BootLevel::from_priority(priority);
+                rest = after;
+            }
+        }
+        
+        let mut timestamp = None;
+        if let Some((seconds, after)) = rest.strip_prefix('[').and_then(|s| s.split_once(']')) {
+            if let Ok(seconds) = seconds.trim().parse::<f64>() {
+                timestamp = Some(seconds);
+                rest = after.trim_start();
+            }
+        }
+        
+        if rest.is_empty() {
+            return None;
+        }
+        let subsystem = subsystem_of(rest);
+        let failure = level <= BootLevel::Error || is_failure_message(rest);
+        Some(BootEvent {
+            run_id: run_id.to_string(),
+            project: project.to_string(),
+            line: index + 1,
+            timestamp,
+            level,
+            subsystem,
+            message: rest.to_string(),
+            failure,
+        })
+    }).collect()
+}
+
+/// Whether a message starts with a failure report header or contains a failure word
+///
+/// Words only match on word boundaries, so `errors=remount-ro` or `failed_count` are not failures.
+fn is_failure_message(message: &str) -> bool {
+    if FAILURE_HEADERS.iter().any(|header| message.starts_with(header)) {
+        return true;
+    }
+    let is_word_char = |c: char| c.is_alphanumeric() || c == '_';
+    FAILURE_WORDS.iter().any(|word| {
+        message.match_indices(word).any(|(start, _)| {
+            let before = message[..start].chars().next_back();
+            let after = message[start + word.len()..].chars().next();
+            !before.is_some_and(is_word_char) && !after.is_some_and(is_word_char)
+        })
+    })
+}
+
+/// Subsystem prefix of a message: `ext4: ...`, `EXT4-fs (vda): ...` or `virtio_net virtio0: ...`
+fn subsystem_of(message: &str) -> Option<String> {
+    let (prefix, _) = message.split_once(": ")?;
+    let words: Vec<&str> = prefix.split_whitespace().collect();
+    let valid = |word: &str| !word.is_empty() && word.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | ':'));
+    
+    // The driver name may be followed by a device and an interface name
+    let name = *words.first()?;
+    let devices_valid = words[1..].iter().all(|word| valid(word.trim_matches(|c| c == '(' || c == ')')));
+    if words.len() > 3 || !valid(name) || name.chars().all(|c| c.is_ascii_digit()) || !devices_valid {
+        return None;
+    }
+    Some(name.to_string())
+}
+
+/// Normalized subsystem key used for correlation (`EXT4-fs` -> `ext4`)
+pub fn subsystem_key(name: &str) -> String {
+    let name = name.to_ascii_lowercase().replace('-', "_");
+    name.strip_suffix("_fs").map(str::to_string).unwrap_or(name)
+}
+
+/// Failing boot event and what generated its subsystem
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FailureCorrelation {
+    /// Failing line
+    pub event: BootEvent,
+    
+    /// Names of the kernel components providing the subsystem
+    pub components: Vec<String>,
+    
+    /// IDs of the tiles providing the subsystem
+    pub tiles: Vec<String>,
+}
+
+/// Correlate the failing events with kernel components and tiles
+///
+/// A component matches when its name, or the name of one of its source files or
+/// their directories, equals the subsystem. A tile matches when it lists the
+/// subsystem in its `kernel.subsystems` property or is named after it.
+pub fn correlate_failures(events: &[BootEvent], structure: Option<&KernelStructure>, tiles: Option<&TileGraph>) -> Vec<FailureCorrelation> {
+    events.iter().filter(|event| event.failure).map(|event| {
+        let key = event.subsystem.as_deref().map(subsystem_key);
+        let mut components = Vec::new();
+        let mut tile_ids = Vec::new();
+        
+        if let Some(key) = &key {
+            for component in structure.iter().flat_map(|structure| &structure.components) {
+                let from_files = component.source_files.iter().any(|path| {
+                    path.file_stem().into_iter().chain(path.parent().into_iter().flat_map(|dir| dir.iter()))
+                        .any(|part| subsystem_key(&part.to_string_lossy()) == *key)
+                });
+                if subsystem_key(&component.name) == *key || from_files {
+                    components.push(component.name.clone());
+                }
+            }
+            for tile in tiles.iter().flat_map(|graph| graph.tiles.values()) {
+                let listed = tile.get_property(TILE_SUBSYSTEMS_PROPERTY)
+                    .map(|value| value.split(',').any(|name| subsystem_key(name.trim()) == *key))
+                    .unwrap_or(false);
+                if listed || subsystem_key(&tile.name) == *key {
+                    tile_ids.push(tile.id.clone());
+                }
+            }
+            tile_ids.sort();
+        }
+        
+        FailureCorrelation { event: event.clone(), components, tiles: tile_ids }
+    }).collect()
+}
+
+/// Boot events stored in a DBOS table
+pub struct BootLogStore {
+    tables: SharedTables,
+}
+
+impl BootLogStore {
+    /// Open the store on a running tables manager or one of its namespaces, creating the table if needed
+    pub fn new(tables: impl Into<SharedTables>) -> Result<Self, String> {
+        let tables = tables.into();
+        if tables.get_table(BOOT_EVENTS_TABLE)?.is_none() {
+            let now = chrono::Utc::now().timestamp() as u64;
+            tables.create_table(TableDefinition {
+                name: BOOT_EVENTS_TABLE.to_string(),
+                columns: vec![
+                    column("run_id", ColumnType::String, "Test run"),
+                    column("project", ColumnType::String, "Project name"),
+                    column("line", ColumnType::Integer, "Line number in the console output"),
+                    column("timestamp", ColumnType::Double, "Seconds since boot (empty without timestamp)"),
+                    column("level", ColumnType::String, "Log level"),
+                    column("subsystem", ColumnType::String, "Subsystem that printed the line"),
+                    column("message", ColumnType::String, "Message"),
+                    column("failure", ColumnType::Boolean, "Whether the line reports a failure"),
+                ],
+                primary_key: vec!["run_id".to_string(), "line".to_string()],
+                indexes: vec![IndexDefinition {
+                    name: "idx_boot_events_project".to_string(),
+                    columns: vec!["project".to_string()],
+                    unique: false,
+                }],
+                description: "Parsed boot logs of test runs".to_string(),
+                created_at: now,
+                updated_at: now,
+            })?;
+        }
+        Ok(Self { tables })
+    }
+    
+    /// Parse and store the console output of a run
+    pub fn ingest(&self, project: &str, run_id: &str, log: &str) -> Result<Vec<BootEvent>, String> {
+        let events = parse_dmesg(run_id, project, log);
+        for event in &events {
+            let values = HashMap::from([
+                ("run_id".to_string(), event.run_id.clone()),
+                ("project".to_string(), event.project.clone()),
+                ("line".to_string(), event.line.to_string()),
+                ("timestamp".to_string(), event.timestamp.map(|seconds| seconds.to_string()).unwrap_or_default()),
+                ("level".to_string(), format!("{:?}", event.level)),
+                ("subsystem".to_string(), event.subsystem.clone().unwrap_or_default()),
+                ("message".to_string(), event.message.clone()),
+                ("failure".to_string(), event.failure.to_string()),
+            ]);
+            self.tables.insert_row(BOOT_EVENTS_TABLE, values)?;
+        }
+        Ok(events)
+    }
+    
+    /// Store the console output of every test of a report, one run per test
+    pub fn ingest_report(&self, project: &str, build_id: &str, report: &TestReport) -> Result<Vec<BootEvent>, String> {
+        let mut events = Vec::new();
+        for result in &report.results {
+            let run_id = format!("{}/{}/{}", build_id, result.suite, result.name);
+            events.extend(self.ingest(project, &run_id, &result.console)?);
+        }
+        Ok(events)
+    }
+    
+    /// Events of a run in line order
+    pub fn events(&self, run_id: &str) -> Result<Vec<BootEvent>, String> {
+        self.load(HashMap::from([("run_id".to_string(), run_id.to_string())]))
+    }
+    
+    /// Failing events of a project
+    pub fn failures(&self, project: &str) -> Result<Vec<BootEvent>, String> {
+        self.load(HashMap::from([
+            ("project".to_string(), project.to_string()),
+            ("failure".to_string(), "true".to_string()),
+        ]))
+    }
+    
+    fn load(&self, conditions: HashMap<String, String>) -> Result<Vec<BootEvent>, String> {
+        let mut events: Vec<BootEvent> = self.tables.query_rows(BOOT_EVENTS_TABLE, conditions)?.into_iter().map(|row| {
+            let value = |name: &str| row.values.get(name).cloned().unwrap_or_default();
+            BootEvent {
+                run_id: value("run_id"),
+                project: value("project"),
+                line: value("line").parse().unwrap_or(0),
+                timestamp: value("timestamp").parse().ok(),
+                level: match value("level").as_str() {
+                    "Emergency" => BootLevel::Emergency,
+                    "Alert" => BootLevel::Alert,
+                    "Critical" => BootLevel::Critical,
+                    "Error" => BootLevel::Error,
+                    "Warning" => BootLevel::Warning,
+                    "Notice" => BootLevel::Notice,
+                    "Debug" => BootLevel::Debug,
+                    _ => BootLevel::Info,
+                },
+                subsystem: Some(value("subsystem")).filter(|subsystem| !subsystem.is_empty()),
+                message: value("message"),
+                failure: value("failure") == "true",
+            }
+        }).collect();
+        events.sort_by(|a, b| a.run_id.cmp(&b.run_id).then(a.line.cmp(&b.line)));
+        Ok(events)
+    }
+}
+
+/// Build a column definition
+fn column(name: &str, column_type: ColumnType, description: &str) -> ColumnDefinition {
+    ColumnDefinition {
+        name: name.to_string(),
+        column_type,
+        nullable: matches!(name, "timestamp" | "subsystem"),
+        default_value: None,
+        description: description.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use crate::dbos_integration::dbos_core::TablesManager;
+    use crate::tile_engine::tile_core::{Tile, TileType};
+    
+    const LOG: &str = "\
+[    0.000000] Linux version 6.6.0 (osland@build)
+<6>[    1.204511] virtio_net virtio0 eth0: renamed from eth0
+<3>[    2.318002] EXT4-fs (vda): unable to read superblock
+[    2.400000] Kernel panic - not syncing: VFS: Unable to mount root fs
+Welcome to OSland
+";
+    
+    #[test]
+    fn test_failure_markers_match_on_boundaries() {
+        let events = parse_dmesg("b1", "kernel", "\
+[    1.0] EXT4-fs (vda): mounted filesystem with options errors=remount-ro
+[    1.1] systemd[1]: Reported no WARNING: or BUG: conditions
+[    1.2] usb 1-1: device descriptor read/64, error -71
+[    1.3] WARNING: CPU: 0 PID: 1 at kernel/fork.c:100
+[    1.4] nfs: server 10.0.0.1 not responding, timed out
+");
+        assert_eq!(events.iter().map(|event| event.failure).collect::<Vec<_>>(), vec![false, false, true, true, true]);
+    }
+
+
+    #[test]
+    fn test_boot_log_ingestion_and_correlation() {
+        let tables = Arc::new(TablesManager::new());
+        tables.start();
+        let store = BootLogStore::new(tables).unwrap();
+        let events = store.ingest("kernel", "b1/smoke/boot", LOG).unwrap();
+        assert_eq!(events.len(), 5);
+        assert_eq!(events[1].subsystem.as_deref(), Some("virtio_net"));
+        assert_eq!(events[2].level, BootLevel::Error);
+        assert_eq!(events[2].subsystem.as_deref(), Some("EXT4-fs"));
+        assert_eq!(events[2].timestamp, Some(2.318002));
+        assert_eq!(events[4].timestamp, None);
+        assert_eq!(store.events("b1/smoke/boot").unwrap(), events);
+        
+        let failures = store.failures("kernel").unwrap();
+        assert_eq!(failures.iter().map(|event| event.line).collect::<Vec<_>>(), vec![3, 4]);
+        
+        let mut graph = TileGraph::new("kernel".to_string());
+        let mut tile = Tile::new("Root filesystem".to_string(), TileType::Processing, String::new());
+        tile.id = "rootfs".to_string();
+        tile.set_property(TILE_SUBSYSTEMS_PROPERTY.to_string(), "ext4, jbd2".to_string());
+        graph.add_tile(tile).unwrap();
+        
+        let correlations = correlate_failures(&failures, None, Some(&graph));
+        assert_eq!(correlations[0].tiles, vec!["rootfs".to_string()]);
+        assert!(correlations[1].tiles.is_empty());
+    }
+}
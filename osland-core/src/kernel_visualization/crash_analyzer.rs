@@ -0,0 +1,649 @@
+// Kernel crash analysis for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! Oops and panic reports of test runs.
+//!
+//! Kernel oopses, BUGs, warnings and panics printed on the console are split into
+//! reports holding the faulting instruction, the call trace and the task that hit
+//! them. Frames are symbolicated against the build's `vmlinux` (`nm` for the symbol
+//! table, `addr2line` for source lines), the first frame outside the generic trap and
+//! panic code that a component owns names the culprit, and the result is handed to
+//! the AI error diagnoser. Reports are attached to the build record in the history.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use serde::{Deserialize, Serialize};
+use crate::ai_assistant::error_diagnoser::{ErrorDiagnosticContext, ErrorDiagnoser};
+use crate::kernel_visualization::trace_ingest::ComponentMapper;
+
+/// Functions of the trap, oops and warning paths, never blamed for a crash
+const GENERIC_FRAMES: [&str; 27] = [
+    "panic", "die", "__die", "__die_body", "oops_end", "do_trap", "do_error_trap", "dump_stack", "dump_stack_lvl",
+    "show_stack", "__warn", "warn_slowpath_fmt", "report_bug", "handle_bug", "exc_invalid_op", "asm_exc_invalid_op",
+    "exc_page_fault", "asm_exc_page_fault", "page_fault_oops", "handle_page_fault", "do_user_addr_fault",
+    "kernelmode_fixup_or_oops", "die_kernel_fault", "do_page_fault", "do_mem_abort", "el1_abort", "el1h_64_sync_handler",
+];
+
+/// Lines of source shown around the culprit on each side
+const SNIPPET_CONTEXT: usize = 5;
+
+/// Kind of crash
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CrashKind {
+    /// NULL pointer dereference
+    NullDereference,
+    
+    /// Access to an unmapped or protected address
+    PageFault,
+    
+    /// General protection fault (e.g. non-canonical address)
+    GeneralProtection,
+    
+    /// `BUG()` assertion or other `BUG:` report
+    Bug,
+    
+    /// `WARN()` splat; the kernel keeps running
+    Warning,
+    
+    /// Panic without a preceding oops
+    Panic,
+}
+
+impl CrashKind {
+    /// Kind of a report's first line
+    fn of_title(title: &str) -> Self {
+        if title.contains("NULL pointer dereference") {
+            CrashKind::NullDereference
+        } else if title.contains("page fault") || title.contains("paging request") {
+            CrashKind::PageFault
+        } else if title.contains("general protection fault") {
+            CrashKind::GeneralProtection
+        } else if title.starts_with("WARNING:") {
+            CrashKind::Warning
+        } else if title.starts_with("Kernel panic") {
+            CrashKind::Panic
+        } else {
+            CrashKind::Bug
+        }
+    }
+}
+
+/// Source location of a frame
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SourceLocation {
+    /// Source file as recorded in the debug information
+    pub file: PathBuf,
+    
+    /// Line number
+    pub line: usize,
+}
+
+/// Frame of a call trace (or the faulting instruction)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OopsFrame {
+    /// Function name
+    pub symbol: Option<String>,
+    
+    /// Offset into the function
+    pub offset: Option<u64>,
+    
+    /// Size of the function
+    pub size: Option<u64>,
+    
+    /// Module the function belongs to (`None` for vmlinux)
+    pub module: Option<String>,
+    
+    /// Address, as printed or resolved against vmlinux
+    pub address: Option<u64>,
+    
+    /// Whether the unwinder trusted the frame (`?` frames are guesses)
+    pub reliable: bool,
+    
+    /// Source location, once symbolicated
+    pub location: Option<SourceLocation>,
+}
+
+impl OopsFrame {
+    /// Parse `? ext4_readdir+0x31/0x8a0 [ext4]`, `[<ffffffff8105d3a1>] ext4_readdir+0x31/0x8a0`
+    /// or a bare address
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut rest = text.trim();
+        let mut frame = OopsFrame { reliable: true, ..Default::default() };
+        if let Some(after) = rest.strip_prefix("? ") {
+            frame.reliable = false;
+            rest = after.trim_start();
+        }
+        if let Some((address, after)) = rest.strip_prefix("[<").and_then(|s| s.split_once(">]")) {
+            frame.address = u64::from_str_radix(address, 16).ok();
+            rest = after.trim();
+        }
+        if let Some((before, module)) = rest.strip_suffix(']').and_then(|s| s.rsplit_once(" [")) {
+            frame.module = Some(module.to_string());
+            rest = before.trim();
+        }
+        
+        let token = rest.split_whitespace().next().unwrap_or("");
+        if let Some((symbol, position)) = token.split_once('+') {
+            let valid = !symbol.is_empty() && symbol.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '$'));
+            if !valid {
+                return None;
+            }
+            let (offset, size) = position.split_once('/').unwrap_or((position, ""));
+            let hex = |value: &str| u64::from_str_radix(value.trim_start_matches("0x"), 16).ok();
+            frame.symbol = Some(symbol.to_string());
+            frame.offset = hex(offset);
+            frame.size = hex(size);
+        } else if let Some(address) = token.strip_prefix("0x").or((token.len() >= 8).then_some(token)).and_then(|hex| u64::from_str_radix(hex, 16).ok()) {
+            frame.address = Some(address);
+        } else if frame.address.is_none() {
+            return None;
+        }
+        Some(frame)
+    }
+    
+    /// Display form (`ext4_readdir+0x31/0x8a0 [ext4] (fs/ext4/dir.c:142)`)
+    pub fn describe(&self) -> String {
+        let mut text = match (&self.symbol, self.offset) {
+            (Some(symbol), Some(offset)) => format!("{}+{:#x}", symbol, offset),
+            (Some(symbol), None) => symbol.clone(),
+            (None, _) => format!("{:#x}", self.address.unwrap_or(0)),
+        };
+        if let Some(size) = self.size {
+            text.push_str(&format!("/{:#x}", size));
+        }
+        if let Some(module) = &self.module {
+            text.push_str(&format!(" [{}]", module));
+        }
+        if let Some(location) = &self.location {
+            text.push_str(&format!(" ({}:{})", location.file.display(), location.line));
+        }
+        text
+    }
+    
+    fn is_generic(&self) -> bool {
+        self.symbol.as_deref().map_or(false, |symbol| GENERIC_FRAMES.contains(&symbol))
+    }
+}
+
+/// One oops, BUG, warning or panic report
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Oops {
+    /// Kind of crash
+    pub kind: CrashKind,
+    
+    /// First line of the report
+    pub title: String,
+    
+    /// CPU the crash happened on
+    pub cpu: Option<u32>,
+    
+    /// Task that crashed
+    pub pid: Option<u32>,
+    
+    /// Name of the task that crashed
+    pub comm: Option<String>,
+    
+    /// Taint flags (`None` when not tainted)
+    pub tainted: Option<String>,
+    
+    /// Kernel release
+    pub kernel_version: Option<String>,
+    
+    /// Faulting instruction (`RIP`, `pc` or `epc`)
+    pub pc: Option<OopsFrame>,
+    
+    /// Call trace, innermost frame first
+    pub call_trace: Vec<OopsFrame>,
+    
+    /// Panic message, if the kernel panicked
+    pub panic: Option<String>,
+    
+    /// Report lines without log prefixes
+    pub text: String,
+}
+
+impl Oops {
+    fn new(title: &str) -> Self {
+        Self {
+            kind: CrashKind::of_title(title),
+            title: title.to_string(),
+            cpu: None,
+            pid: None,
+            comm: None,
+            tainted: None,
+            kernel_version: None,
+            pc: None,
+            call_trace: Vec::new(),
+            panic: title.strip_prefix("Kernel panic - not syncing: ").map(str::to_string),
+            text: format!("{}\n", title),
+        }
+    }
+    
+    /// Read `CPU: 0 PID: 71 Comm: sh Tainted: G        W  6.6.0 #1`
+    fn parse_task_line(&mut self, line: &str) {
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        let after = |key: &str| tokens.iter().position(|token| *token == key).and_then(|index| tokens.get(index + 1));
+        self.cpu = after("CPU:").and_then(|value| value.parse().ok());
+        self.pid = after("PID:").and_then(|value| value.parse().ok());
+        self.comm = after("Comm:").map(|value| value.to_string());
+        
+        let version_from = |start: usize| tokens[start..].iter()
+            .position(|token| token.starts_with(|c: char| c.is_ascii_digit()) && token.contains('.'))
+            .map(|index| start + index);
+        if let Some(index) = tokens.iter().position(|token| *token == "Tainted:") {
+            let end = version_from(index + 1).unwrap_or(tokens.len());
+            self.tainted = Some(tokens[index + 1..end].join(" "));
+            self.kernel_version = tokens.get(end).map(|token| token.to_string());
+        } else if let Some(index) = tokens.windows(2).position(|pair| pair == ["Not", "tainted"]) {
+            self.kernel_version = version_from(index + 2).map(|index| tokens[index].to_string());
+        }
+    }
+}
+
+/// Line without the `<level>` and `[timestamp]` prefixes
+fn strip_log_prefix(line: &str) -> &str {
+    let mut rest = line.trim_end_matches('\r').trim_start();
+    if let Some((priority, after)) = rest.strip_prefix('<').and_then(|s| s.split_once('>')) {
+        if priority.parse::<u8>().is_ok() {
+            rest = after;
+        }
+    }
+    if let Some((seconds, after)) = rest.strip_prefix('[').and_then(|s| s.split_once(']')) {
+        if seconds.trim().parse::<f64>().is_ok() {
+            rest = after;
+        }
+    }
+    rest.trim()
+}
+
+/// Faulting instruction of a `RIP: 0010:...`, `pc : ...` or `epc : ...` line
+fn pc_of(line: &str) -> Option<OopsFrame> {
+    let value = if let Some(value) = line.strip_prefix("RIP:") {
+        value.trim().rsplit_once(':').map_or(value, |(_, symbol)| symbol)
+    } else {
+        line.strip_prefix("pc :").or_else(|| line.strip_prefix("epc :"))?
+    };
+    OopsFrame::parse(value.split_whitespace().next()?)
+}
+
+/// Split console output into crash reports
+///
+/// A report starts at a `BUG:`, `Oops`, `Unable to handle kernel`, `general protection
+/// fault`, `WARNING:` or `Kernel panic` line and ends at `---[ end trace`. A panic
+/// following a report is attached to it.
+pub fn parse_oopses(console: &str) -> Vec<Oops> {
+    let mut reports: Vec<Oops> = Vec::new();
+    let mut open = false;
+    let mut in_trace = false;
+    for raw in console.lines() {
+        let line = strip_log_prefix(raw);
+        let starts_report = ["BUG:", "kernel BUG at", "Unable to handle kernel", "general protection fault", "WARNING:"]
+            .iter().any(|marker| line.starts_with(marker))
+            || (line.starts_with("Oops") && !open);
+        
+        if let Some(message) = line.strip_prefix("Kernel panic - not syncing: ") {
+            match reports.last_mut() {
+                Some(report) if report.panic.is_none() && report.kind != CrashKind::Warning => {
+                    report.panic = Some(message.to_string());
+                    report.text.push_str(line);
+                    report.text.push('\n');
+                }
+                _ => reports.push(Oops::new(line)),
+            }
+            open = false;
+            continue;
+        }
+        if starts_report {
+            reports.push(Oops::new(line));
+            open = true;
+            in_trace = false;
+            continue;
+        }
+        if !open {
+            continue;
+        }
+        
+        let report = reports.last_mut().expect("an open report exists");
+        report.text.push_str(line);
+        report.text.push('\n');
+        if line.starts_with("---[ end trace") {
+            open = false;
+        } else if line.starts_with("CPU:") && line.contains("PID:") {
+            report.parse_task_line(line);
+        } else if let Some(pc) = pc_of(line) {
+            // Newer x86 kernels repeat the RIP line after the trace
+            report.pc.get_or_insert(pc);
+            in_trace = false;
+        } else if line.eq_ignore_ascii_case("Call Trace:") {
+            in_trace = true;
+        } else if in_trace && !matches!(line, "<TASK>" | "</TASK>" | "<IRQ>" | "</IRQ>") {
+            match OopsFrame::parse(line) {
+                Some(frame) => report.call_trace.push(frame),
+                None => in_trace = false,
+            }
+        }
+    }
+    reports
+}
+
+/// Symbol table and debug information of a vmlinux
+#[derive(Debug, Clone)]
+pub struct Symbolizer {
+    vmlinux: PathBuf,
+    
+    /// Text symbols sorted by address
+    symbols: Vec<(u64, String)>,
+}
+
+impl Symbolizer {
+    /// Load the text symbols of a vmlinux with `nm`
+    pub fn load(vmlinux: &Path) -> Result<Self, String> {
+        let output = Command::new("nm")
+            .args(["-n", "--defined-only"])
+            .arg(vmlinux)
+            .output()
+            .map_err(|e| format!("Failed to run nm: {}", e))?;
+        if !output.status.success() {
+            return Err(format!("nm failed on {}: {}", vmlinux.display(), String::from_utf8_lossy(&output.stderr).trim()));
+        }
+        Ok(Self::from_nm_output(vmlinux, &String::from_utf8_lossy(&output.stdout)))
+    }
+    
+    /// Parse `nm`-style `<address> <type> <name>` lines, keeping text symbols
+    pub fn from_nm_output(vmlinux: &Path, content: &str) -> Self {
+        let mut symbols: Vec<(u64, String)> = content.lines().filter_map(|line| {
+            match line.split_whitespace().collect::<Vec<_>>().as_slice() {
+                [address, kind, name, ..] if matches!(*kind, "T" | "t" | "W" | "w") => {
+                    u64::from_str_radix(address, 16).ok().map(|address| (address, name.to_string()))
+                }
+                _ => None,
+            }
+        }).collect();
+        symbols.sort();
+        Self { vmlinux: vmlinux.to_path_buf(), symbols }
+    }
+    
+    /// Address of a function
+    pub fn address_of(&self, symbol: &str) -> Option<u64> {
+        self.symbols.iter().find(|(_, name)| name == symbol).map(|(address, _)| *address)
+    }
+    
+    /// Function containing an address, with the offset into it
+    pub fn symbol_at(&self, address: u64) -> Option<(&str, u64)> {
+        let index = self.symbols.partition_point(|(start, _)| *start <= address).checked_sub(1)?;
+        let (start, name) = &self.symbols[index];
+        Some((name.as_str(), address - start))
+    }
+    
+    /// Resolve the addresses, symbols and source lines of a report's frames
+    ///
+    /// `symbol+offset` frames are resolved against the vmlinux symbol table, so
+    /// KASLR does not matter. Module frames are left alone.
+    pub fn symbolicate(&self, oops: &mut Oops) -> Result<(), String> {
+        let has_pc = oops.pc.as_ref().map_or(false, |pc| pc.module.is_none());
+        let frames: Vec<&mut OopsFrame> = oops.pc.iter_mut().chain(oops.call_trace.iter_mut())
+            .filter(|frame| frame.module.is_none())
+            .collect();
+        let mut lookups = Vec::new();
+        for (index, frame) in frames.into_iter().enumerate() {
+            match (&frame.symbol, frame.offset) {
+                (Some(symbol), Some(offset)) => {
+                    if let Some(start) = self.address_of(symbol) {
+                        frame.address = Some(start + offset);
+                    }
+                }
+                _ => {
+                    if let Some((symbol, offset)) = frame.address.and_then(|address| self.symbol_at(address)) {
+                        frame.symbol = Some(symbol.to_string());
+                        frame.offset = Some(offset);
+                    }
+                }
+            }
+            // Call trace entries are return addresses; the call is the instruction before
+            if let Some(address) = frame.address {
+                lookups.push((frame, if index == 0 && has_pc { address } else { address.saturating_sub(1) }));
+            }
+        }
+        if lookups.is_empty() {
+            return Ok(());
+        }
+        
+        let output = Command::new("addr2line")
+            .arg("-e")
+            .arg(&self.vmlinux)
+            .arg("-a")
+            .args(lookups.iter().map(|(_, address)| format!("{:#x}", address)))
+            .output()
+            .map_err(|e| format!("Failed to run addr2line: {}", e))?;
+        if !output.status.success() {
+            return Err(format!("addr2line failed on {}: {}", self.vmlinux.display(), String::from_utf8_lossy(&output.stderr).trim()));
+        }
+        for ((frame, _), location) in lookups.into_iter().zip(parse_addr2line(&String::from_utf8_lossy(&output.stdout))) {
+            frame.location = location;
+        }
+        Ok(())
+    }
+}
+
+/// Locations of `addr2line -a` output (an address line followed by `file:line`)
+pub fn parse_addr2line(output: &str) -> Vec<Option<SourceLocation>> {
+    output.lines()
+        .filter(|line| !line.starts_with("0x"))
+        .map(|line| {
+            let line = line.split(" (discriminator").next().unwrap_or(line);
+            let (file, number) = line.rsplit_once(':')?;
+            let number = number.parse().ok().filter(|number| *number > 0)?;
+            (file != "??").then(|| SourceLocation { file: PathBuf::from(file), line: number })
+        })
+        .collect()
+}
+
+/// AI diagnosis of a crash
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CrashDiagnosis {
+    /// Error description
+    pub description: String,
+    
+    /// Probable cause
+    pub probable_cause: String,
+    
+    /// Suggested fix
+    pub suggested_fix: String,
+    
+    /// Severity reported by the diagnoser
+    pub severity: String,
+    
+    /// Confidence score (0-1)
+    pub confidence: f32,
+}
+
+/// Analyzed crash
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CrashReport {
+    /// Parsed report
+    pub oops: Oops,
+    
+    /// Frame the crash is attributed to
+    pub culprit: Option<OopsFrame>,
+    
+    /// Component owning the culprit's code
+    pub component: Option<String>,
+    
+    /// AI diagnosis, once requested
+    pub diagnosis: Option<CrashDiagnosis>,
+}
+
+impl CrashReport {
+    /// Attribute a report to the first non-generic frame a component owns
+    ///
+    /// Frames are tried from the faulting instruction outwards, skipping frames the
+    /// unwinder was unsure of; without an owner, the first non-generic frame is kept.
+    pub fn attribute(oops: Oops, mapper: &ComponentMapper) -> Self {
+        let candidates: Vec<&OopsFrame> = oops.pc.iter().chain(oops.call_trace.iter().filter(|frame| frame.reliable))
+            .filter(|frame| !frame.is_generic())
+            .collect();
+        let owner = |frame: &OopsFrame| {
+            frame.location.as_ref().and_then(|location| mapper.component_of_file(&location.file))
+                .or_else(|| frame.symbol.as_deref().and_then(|symbol| mapper.component_of(symbol)))
+                .or_else(|| frame.module.as_deref().and_then(|module| mapper.component_of(module)))
+                .map(str::to_string)
+        };
+        let (culprit, component) = candidates.iter()
+            .find_map(|frame| owner(frame).map(|component| ((*frame).clone(), Some(component))))
+            .or_else(|| candidates.first().map(|frame| ((*frame).clone(), None)))
+            .map_or((None, None), |(frame, component)| (Some(frame), component));
+        Self { oops, culprit, component, diagnosis: None }
+    }
+    
+    /// Context for the error diagnoser
+    ///
+    /// The source around the culprit is read from `source_root` when the debug
+    /// information holds relative paths.
+    pub fn diagnostic_context(&self, architecture: &str, source_root: Option<&Path>) -> ErrorDiagnosticContext {
+        let mut trace = String::new();
+        if let Some(pc) = &self.oops.pc {
+            trace.push_str(&format!("PC: {}\n", pc.describe()));
+        }
+        for frame in &self.oops.call_trace {
+            trace.push_str(&format!(" {}{}\n", if frame.reliable { "" } else { "? " }, frame.describe()));
+        }
+        
+        let mut environment = Vec::new();
+        if let Some(version) = &self.oops.kernel_version {
+            environment.push(format!("kernel {}", version));
+        }
+        if let (Some(cpu), Some(pid), Some(comm)) = (self.oops.cpu, self.oops.pid, &self.oops.comm) {
+            environment.push(format!("CPU {} PID {} ({})", cpu, pid, comm));
+        }
+        if let Some(tainted) = &self.oops.tainted {
+            environment.push(format!("tainted {}", tainted));
+        }
+        
+        let mut message = self.oops.title.clone();
+        if let Some(panic) = self.oops.panic.as_ref().filter(|_| self.oops.kind != CrashKind::Panic) {
+            message.push_str(&format!(" (kernel panic: {})", panic));
+        }
+        if let Some(culprit) = &self.culprit {
+            message.push_str(&format!(" in {}", culprit.describe()));
+        }
+        
+        ErrorDiagnosticContext {
+            error_message: message,
+            code_snippet: self.culprit.as_ref().and_then(|culprit| culprit.location.as_ref()).and_then(|location| snippet(location, source_root)),
+            build_output: Some(format!("{}\nSymbolicated trace:\n{}", self.oops.text, trace)),
+            environment_info: (!environment.is_empty()).then(|| environment.join(", ")),
+            architecture: architecture.to_string(),
+            component_name: self.component.clone(),
+        }
+    }
+    
+    /// Ask the diagnoser about the crash and keep its answer
+    pub fn diagnose(&mut self, diagnoser: &dyn ErrorDiagnoser, architecture: &str, source_root: Option<&Path>) -> Result<(), String> {
+        let result = diagnoser.diagnose_error(&self.diagnostic_context(architecture, source_root)).map_err(|e| e.to_string())?;
+        self.diagnosis = Some(CrashDiagnosis {
+            description: result.description,
+            probable_cause: result.probable_cause,
+            suggested_fix: result.suggested_fix,
+            severity: result.severity.to_string(),
+            confidence: result.confidence,
+        });
+        Ok(())
+    }
+}
+
+/// Numbered source lines around a location, the location marked with `>`
+fn snippet(location: &SourceLocation, source_root: Option<&Path>) -> Option<String> {
+    let path = match source_root {
+        Some(root) if location.file.is_relative() => root.join(&location.file),
+        _ => location.file.clone(),
+    };
+    let content = fs::read_to_string(path).ok()?;
+    let first = location.line.saturating_sub(SNIPPET_CONTEXT).max(1);
+    let lines: Vec<String> = content.lines().enumerate()
+        .skip(first - 1)
+        .take(location.line + SNIPPET_CONTEXT + 1 - first)
+        .map(|(index, text)| format!("{}{:5} {}", if index + 1 == location.line { ">" } else { " " }, index + 1, text))
+        .collect();
+    (!lines.is_empty()).then(|| lines.join("\n"))
+}
+
+/// Parse, symbolicate and attribute the crashes printed on a console
+///
+/// Symbolication failures leave the frames as printed.
+pub fn analyze_crashes(console: &str, symbolizer: Option<&Symbolizer>, mapper: &ComponentMapper) -> Vec<CrashReport> {
+    parse_oopses(console).into_iter().map(|mut oops| {
+        if let Some(symbolizer) = symbolizer {
+            let _ = symbolizer.symbolicate(&mut oops);
+        }
+        CrashReport::attribute(oops, mapper)
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    const CONSOLE: &str = "\
+[    2.100000] ext4: mounted filesystem
+[    2.318002] BUG: kernel NULL pointer dereference, address: 0000000000000008
+[    2.318010] #PF: supervisor read access in kernel mode
+[    2.318020] Oops: 0000 [#1] PREEMPT SMP NOPTI
+[    2.318030] CPU: 0 PID: 71 Comm: ls Tainted: G        W          6.6.0 #1
+[    2.318040] RIP: 0010:ext4_readdir+0x31/0x8a0
+[    2.318050] Call Trace:
+[    2.318051]  <TASK>
+[    2.318052]  ? __die+0x23/0x70
+[    2.318053]  ? page_fault_oops+0x17d/0x4c0
+[    2.318054]  iterate_dir+0x9c/0x1c0
+[    2.318055]  __x64_sys_getdents64+0x80/0x120
+[    2.318056]  </TASK>
+[    2.318057] Modules linked in: virtio_net
+[    2.318060] ---[ end trace 0000000000000000 ]---
+[    2.318070] Kernel panic - not syncing: Fatal exception
+";
+
+    #[test]
+    fn test_oops_parsing_and_attribution() {
+        let oopses = parse_oopses(CONSOLE);
+        assert_eq!(oopses.len(), 1);
+        let oops = &oopses[0];
+        assert_eq!(oops.kind, CrashKind::NullDereference);
+        assert_eq!((oops.cpu, oops.pid, oops.comm.as_deref()), (Some(0), Some(71), Some("ls")));
+        assert_eq!(oops.tainted.as_deref(), Some("G W"));
+        assert_eq!(oops.kernel_version.as_deref(), Some("6.6.0"));
+        assert_eq!(oops.panic.as_deref(), Some("Fatal exception"));
+        assert_eq!(oops.pc.as_ref().unwrap().describe(), "ext4_readdir+0x31/0x8a0");
+        assert_eq!(oops.call_trace.len(), 4);
+        assert!(!oops.call_trace[0].reliable);
+        
+        let frame = OopsFrame::parse("[<ffffffff8105d3a1>] ext4_file_open+0x10/0x40 [ext4]").unwrap();
+        assert_eq!((frame.address, frame.module.as_deref()), (Some(0xffffffff8105d3a1), Some("ext4")));
+        
+        let symbolizer = Symbolizer::from_nm_output(Path::new("vmlinux"), "ffffffff81200000 T ext4_readdir\nffffffff81300000 t iterate_dir\nffffffff81100000 D jiffies\n");
+        assert_eq!(symbolizer.symbol_at(0xffffffff81200040), Some(("ext4_readdir", 0x40)));
+        assert_eq!(symbolizer.address_of("jiffies"), None);
+        assert_eq!(parse_addr2line("0xffffffff81200031\n/src/linux/fs/ext4/dir.c:142 (discriminator 1)\n0xffffffff8130009b\n??:0\n"), vec![
+            Some(SourceLocation { file: PathBuf::from("/src/linux/fs/ext4/dir.c"), line: 142 }),
+            None,
+        ]);
+        
+        // The faulting function belongs to the filesystem; the trap frames are skipped
+        let mut mapper = ComponentMapper::default();
+        mapper.add_source_file(Path::new("fs/ext4"), "rootfs");
+        mapper.add_prefix("iterate", "vfs");
+        let mut oops = oops.clone();
+        oops.pc.as_mut().unwrap().location = Some(SourceLocation { file: PathBuf::from("/src/linux/fs/ext4/dir.c"), line: 142 });
+        let report = CrashReport::attribute(oops.clone(), &mapper);
+        assert_eq!(report.component.as_deref(), Some("rootfs"));
+        let context = report.diagnostic_context("x86_64", None);
+        assert_eq!(context.error_message, "BUG: kernel NULL pointer dereference, address: 0000000000000008 (kernel panic: Fatal exception) in ext4_readdir+0x31/0x8a0 (/src/linux/fs/ext4/dir.c:142)");
+        assert_eq!(context.environment_info.as_deref(), Some("kernel 6.6.0, CPU 0 PID 71 (ls), tainted G W"));
+        
+        oops.pc = None;
+        let report = CrashReport::attribute(oops, &mapper);
+        assert_eq!(report.culprit.unwrap().symbol.as_deref(), Some("iterate_dir"));
+        assert_eq!(report.component.as_deref(), Some("vfs"));
+    }
+}
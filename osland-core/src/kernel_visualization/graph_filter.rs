@@ -0,0 +1,432 @@
+// Graph filter language for OSland kernel visualization
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! Small query language narrowing large kernel visualizations.
+//!
+//! A filter is a boolean expression of `key:value` terms, for example
+//! `type:driver AND arch:arm64 AND depends_on:vfs`. Terms next to each other are
+//! joined with AND; `OR`, `NOT` and parentheses work as usual. Values may be quoted
+//! and may use `*` as a wildcard; numeric keys accept `>`, `<` and `=` prefixes.
+//!
+//! | Key | Matches components |
+//! |-----|--------------------|
+//! | `name` (or a bare word) | whose name matches |
+//! | `type` | of a component type (`driver`, `fs`, `net`, `mm`, ...) |
+//! | `arch` | built for a kernel architecture (`microkernel`, `frame`, ...) or under `arch/<value>` |
+//! | `depends_on` | depending directly on a matching component |
+//! | `used_by` | depended on directly by a matching component |
+//! | `file` | with a matching source or header file |
+//! | `kconfig` | controlled by a matching Kconfig option |
+//! | `deps`, `dependents`, `size` | whose count or size compares |
+//!
+//! Filters can be saved per project in `.osland/filters.json`.
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use crate::core::architecture::KernelArchitecture;
+use crate::kernel_visualization::visualization_data::{KernelComponentInfo, KernelStructure};
+
+/// Saved filters file, relative to the project directory
+pub const SAVED_FILTERS_FILE: &str = ".osland/filters.json";
+
+/// Parsed filter expression
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterExpr {
+    /// `key:value` term
+    Term { key: String, value: String },
+    
+    /// Both sides match
+    And(Box<FilterExpr>, Box<FilterExpr>),
+    
+    /// Either side matches
+    Or(Box<FilterExpr>, Box<FilterExpr>),
+    
+    /// The inner expression does not match
+    Not(Box<FilterExpr>),
+}
+
+/// Compiled graph filter
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphFilter {
+    /// Source text
+    pub query: String,
+    
+    /// Parsed expression
+    pub expr: FilterExpr,
+}
+
+impl GraphFilter {
+    /// Parse a filter query
+    pub fn parse(query: &str) -> Result<Self, String> {
+        let tokens = tokenize(query)?;
+        if tokens.is_empty() {
+            return Err("Empty filter".to_string());
+        }
+        let mut parser = Parser { tokens, position: 0 };
+        let expr = parser.or()?;
+        if let Some(token) = parser.tokens.get(parser.position) {
+            return Err(format!("Unexpected {:?} in filter", token));
+        }
+        Ok(Self { query: query.to_string(), expr })
+    }
+    
+    /// Whether a component of the structure matches
+    pub fn matches(&self, component: &KernelComponentInfo, kernel: &KernelStructure) -> bool {
+        evaluate(&self.expr, component, kernel)
+    }
+    
+    /// Names of the matching components
+    pub fn matching_names(&self, kernel: &KernelStructure) -> HashSet<String> {
+        kernel.components.iter()
+            .filter(|component| self.matches(component, kernel))
+            .map(|component| component.name.clone())
+            .collect()
+    }
+    
+    /// Structure reduced to the matching components and the edges between them
+    pub fn apply(&self, kernel: &KernelStructure) -> KernelStructure {
+        let names = self.matching_names(kernel);
+        let mut filtered = kernel.clone();
+        filtered.components.retain(|component| names.contains(&component.name));
+        filtered.dependencies.retain(|dependency| names.contains(&dependency.from_module) && names.contains(&dependency.to_module));
+        filtered
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Quoted(String),
+    Colon,
+    Open,
+    Close,
+}
+
+fn tokenize(query: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = query.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' | ')' | ':' => {
+                chars.next();
+                tokens.push(match c {
+                    '(' => Token::Open,
+                    ')' => Token::Close,
+                    _ => Token::Colon,
+                });
+            }
+            '"' => {
+                chars.next();
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some(c) => value.push(c),
+                        None => return Err("Unterminated quote in filter".to_string()),
+                    }
+                }
+                tokens.push(Token::Quoted(value));
+            }
+            _ => {
+                let mut word = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_whitespace() || matches!(c, '(' | ')' | ':' | '"') {
+                        break;
+                    }
+                    word.push(c);
+                    chars.next();
+                }
+                tokens.push(Token::Word(word));
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek_keyword(&self, keyword: &str) -> bool {
+        matches!(self.tokens.get(self.position), Some(Token::Word(word)) if word == keyword)
+    }
+    
+    fn or(&mut self) -> Result<FilterExpr, String> {
+        let mut expr = self.and()?;
+        while self.peek_keyword("OR") {
+            self.position += 1;
+            expr = FilterExpr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+    
+    fn and(&mut self) -> Result<FilterExpr, String> {
+        let mut expr = self.not()?;
+        loop {
+            if self.peek_keyword("AND") {
+                self.position += 1;
+            } else if matches!(self.tokens.get(self.position), None | Some(Token::Close)) || self.peek_keyword("OR") {
+                return Ok(expr);
+            }
+            expr = FilterExpr::And(Box::new(expr), Box::new(self.not()?));
+        }
+    }
+    
+    fn not(&mut self) -> Result<FilterExpr, String> {
+        if self.peek_keyword("NOT") {
+            self.position += 1;
+            return Ok(FilterExpr::Not(Box::new(self.not()?)));
+        }
+        self.primary()
+    }
+    
+    fn primary(&mut self) -> Result<FilterExpr, String> {
+        let token = self.tokens.get(self.position).cloned().ok_or("Unexpected end of filter")?;
+        self.position += 1;
+        match token {
+            Token::Open => {
+                let expr = self.or()?;
+                if self.tokens.get(self.position) != Some(&Token::Close) {
+                    return Err("Missing ')' in filter".to_string());
+                }
+                self.position += 1;
+                Ok(expr)
+            }
+            Token::Word(key) if self.tokens.get(self.position) == Some(&Token::Colon) => {
+                self.position += 1;
+                match self.tokens.get(self.position).cloned() {
+                    Some(Token::Word(value)) | Some(Token::Quoted(value)) => {
+                        self.position += 1;
+                        Ok(FilterExpr::Term { key: key.to_ascii_lowercase(), value })
+                    }
+                    _ => Err(format!("Missing value for '{}'", key)),
+                }
+            }
+            Token::Word(value) | Token::Quoted(value) => Ok(FilterExpr::Term { key: "name".to_string(), value }),
+            token => Err(format!("Unexpected {:?} in filter", token)),
+        }
+    }
+}
+
+fn evaluate(expr: &FilterExpr, component: &KernelComponentInfo, kernel: &KernelStructure) -> bool {
+    match expr {
+        FilterExpr::And(left, right) => evaluate(left, component, kernel) && evaluate(right, component, kernel),
+        FilterExpr::Or(left, right) => evaluate(left, component, kernel) || evaluate(right, component, kernel),
+        FilterExpr::Not(inner) => !evaluate(inner, component, kernel),
+        FilterExpr::Term { key, value } => match key.as_str() {
+            "name" => glob(value, &component.name),
+            "type" => type_matches(value, &format!("{:?}", component.component_type)),
+            "arch" => {
+                let arch_dir = format!("arch/{}", value.to_ascii_lowercase());
+                value.parse::<KernelArchitecture>().is_ok_and(|parsed| component.original.architecture.contains(&parsed))
+                    || files(component).any(|path| path.to_string_lossy().to_ascii_lowercase().contains(&arch_dir))
+            }
+            "depends_on" => kernel.dependencies.iter()
+                .any(|dependency| dependency.from_module == component.name && glob(value, &dependency.to_module)),
+            "used_by" => kernel.dependencies.iter()
+                .any(|dependency| dependency.to_module == component.name && glob(value, &dependency.from_module)),
+            "file" => files(component).any(|path| glob(value, &path.to_string_lossy())),
+            "kconfig" => component.original.kconfig_options.iter().any(|option| glob(value, option)),
+            "deps" => compare(value, component.dependency_count as f64),
+            "dependents" => compare(value, component.dependent_count as f64),
+            "size" => component.size.is_some_and(|size| compare(value, size as f64)),
+            _ => false,
+        },
+    }
+}
+
+fn files(component: &KernelComponentInfo) -> impl Iterator<Item = &PathBuf> {
+    component.source_files.iter().chain(&component.header_files)
+}
+
+/// Compare a component type name with a filter value, accepting common abbreviations
+fn type_matches(value: &str, type_name: &str) -> bool {
+    let normalize = |name: &str| name.to_ascii_lowercase().replace(['_', '-'], "");
+    let value = match normalize(value).as_str() {
+        "fs" => "filesystem".to_string(),
+        "net" => "network".to_string(),
+        "mm" | "memory" => "memorymanagement".to_string(),
+        "sched" | "process" => "processmanagement".to_string(),
+        "dt" => "devicetree".to_string(),
+        other => other.to_string(),
+    };
+    normalize(type_name) == value
+}
+
+/// Case-insensitive match with `*` wildcards
+fn glob(pattern: &str, text: &str) -> bool {
+    let (pattern, text) = (pattern.to_ascii_lowercase(), text.to_ascii_lowercase());
+    let parts: Vec<&str> = pattern.split('*').collect();
+    if parts.len() == 1 {
+        return pattern == text;
+    }
+    let (first, last) = (parts[0], parts[parts.len() - 1]);
+    if !text.starts_with(first) || !text[first.len()..].ends_with(last) {
+        return false;
+    }
+    let mut rest = &text[first.len()..text.len() - last.len()];
+    for part in &parts[1..parts.len() - 1] {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    true
+}
+
+/// Compare a number with `>N`, `<N`, `>=N`, `<=N` or `N`
+fn compare(value: &str, actual: f64) -> bool {
+    let (operator, number) = match value.find(|c: char| c.is_ascii_digit() || c == '.') {
+        Some(index) => value.split_at(index),
+        None => return false,
+    };
+    let number: f64 = match number.parse() {
+        Ok(number) => number,
+        Err(_) => return false,
+    };
+    match operator {
+        ">" => actual > number,
+        ">=" => actual >= number,
+        "<" => actual < number,
+        "<=" => actual <= number,
+        "" | "=" => actual == number,
+        _ => false,
+    }
+}
+
+/// Named filters saved with a project
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SavedFilters {
+    /// Query text by filter name
+    pub filters: BTreeMap<String, String>,
+}
+
+impl SavedFilters {
+    /// Path of the saved filters of a project
+    pub fn path(project_dir: &Path) -> PathBuf {
+        project_dir.join(SAVED_FILTERS_FILE)
+    }
+    
+    /// Load the saved filters of a project; a project without any gets an empty set
+    pub fn load(project_dir: &Path) -> Result<Self, String> {
+        let path = Self::path(project_dir);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        serde_json::from_str(&content).map_err(|e| format!("Invalid saved filters {}: {}", path.display(), e))
+    }
+    
+    /// Save the filters of a project
+    pub fn save(&self, project_dir: &Path) -> Result<(), String> {
+        let path = Self::path(project_dir);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize saved filters: {}", e))?;
+        std::fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+    
+    /// Save a filter under a name, checking that it parses
+    pub fn insert(&mut self, name: &str, query: &str) -> Result<(), String> {
+        GraphFilter::parse(query)?;
+        self.filters.insert(name.to_string(), query.to_string());
+        Ok(())
+    }
+    
+    /// Remove a saved filter
+    pub fn remove(&mut self, name: &str) -> Option<String> {
+        self.filters.remove(name)
+    }
+    
+    /// Compile a saved filter
+    pub fn get(&self, name: &str) -> Result<GraphFilter, String> {
+        let query = self.filters.get(name).ok_or_else(|| format!("Unknown filter: {}", name))?;
+        GraphFilter::parse(query)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kernel_extractor::{ComponentType, KernelComponent};
+    use crate::kernel_visualization::visualization_data::ModuleDependency;
+    
+    fn component(name: &str, component_type: ComponentType, file: &str) -> KernelComponentInfo {
+        KernelComponentInfo {
+            name: name.to_string(),
+            component_type,
+            description: None,
+            source_files: vec![PathBuf::from(file)],
+            header_files: Vec::new(),
+            size: Some(1000),
+            function_count: None,
+            struct_count: None,
+            dependency_count: 0,
+            dependent_count: 0,
+            position: (0.0, 0.0),
+            color: String::new(),
+            is_selected: false,
+            original: KernelComponent { name: name.to_string(), ..KernelComponent::default() },
+        }
+    }
+    
+    #[test]
+    fn test_graph_filter() {
+        let kernel = KernelStructure {
+            name: "linux".to_string(),
+            architecture: KernelArchitecture::Monolithic,
+            version: "6.6".to_string(),
+            components: vec![
+                component("vfs", ComponentType::FileSystem, "fs/namei.c"),
+                component("pl011", ComponentType::Driver, "arch/arm64/kernel/pl011.c"),
+                component("e1000", ComponentType::Driver, "drivers/net/e1000.c"),
+                KernelComponentInfo {
+                    original: KernelComponent {
+                        name: "l4ipc".to_string(),
+                        architecture: vec![KernelArchitecture::Microkernel],
+                        ..KernelComponent::default()
+                    },
+                    ..component("l4ipc", ComponentType::Other, "ipc/l4.c")
+                },
+            ],
+            dependencies: vec![ModuleDependency {
+                from_module: "pl011".to_string(),
+                to_module: "vfs".to_string(),
+                dependency_type: "call".to_string(),
+                count: 1,
+                is_selected: false,
+            }],
+            source_dir: PathBuf::new(),
+            analysis_time: String::new(),
+        };
+        
+        let filter = GraphFilter::parse("type:driver AND arch:arm64 AND depends_on:vfs").unwrap();
+        assert_eq!(filter.matching_names(&kernel), HashSet::from(["pl011".to_string()]));
+        let filter = GraphFilter::parse("(type:fs OR e1*) NOT file:\"arch/*\"").unwrap();
+        assert_eq!(filter.matching_names(&kernel), HashSet::from(["vfs".to_string(), "e1000".to_string()]));
+        assert_eq!(GraphFilter::parse("size:>=1000 used_by:pl011").unwrap().apply(&kernel).components.len(), 1);
+        assert!(GraphFilter::parse("type:driver AND (arch:arm64").is_err());
+        
+        // Kernel architectures match by value, whatever spelling the query uses
+        assert_eq!(GraphFilter::parse("arch:Microkernel").unwrap().matching_names(&kernel), HashSet::from(["l4ipc".to_string()]));
+        
+        let project_dir = std::env::temp_dir().join(format!("osland-filters-{}", uuid::Uuid::new_v4()));
+        let mut saved = SavedFilters::default();
+        saved.insert("arm drivers", "type:driver arch:arm64").unwrap();
+        assert!(saved.insert("broken", "type:").is_err());
+        saved.save(&project_dir).unwrap();
+        assert_eq!(SavedFilters::load(&project_dir).unwrap().get("arm drivers").unwrap().matching_names(&kernel).len(), 1);
+        std::fs::remove_dir_all(&project_dir).unwrap();
+    }
+}
@@ -8,6 +8,9 @@ use std::sync::{Arc, Mutex};
 use crate::kernel_visualization::visualization_data::{KernelStructure, KernelComponentInfo, ModuleDependency};
 use crate::kernel_visualization::layout_algorithm::LayoutAlgorithm;
 
+/// Color of components that failed during boot
+pub const BOOT_FAILURE_COLOR: &str = "#d32f2f";
+
 /// Canvas state for kernel visualization
 pub struct InteractiveCanvasState {
     /// Current kernel structure being visualized
@@ -16,6 +19,8 @@ pub struct InteractiveCanvasState {
     pub component_positions: HashMap<String, (f32, f32)>,
     /// Selected components
     pub selected_components: HashSet<String>,
+    /// Components that boot failures were correlated with
+    pub failing_components: HashSet<String>,
     /// Current canvas tool
     pub current_tool: CanvasTool,
     /// Zoom level
@@ -39,6 +44,7 @@ impl InteractiveCanvasState {
             kernel_structure,
             component_positions: positions,
             selected_components: HashSet::new(),
+            failing_components: HashSet::new(),
             current_tool: CanvasTool::Select,
             zoom: 1.0,
             pan_offset: (0.0, 0.0),
@@ -64,6 +70,15 @@ impl InteractiveCanvasState {
         self.selected_components.clear();
     }
     
+    /// Color to draw a component with, highlighting boot failures
+    pub fn component_color<'a>(&self, component: &'a KernelComponentInfo) -> &'a str {
+        if self.failing_components.contains(&component.name) {
+            BOOT_FAILURE_COLOR
+        } else {
+            &component.color
+        }
+    }
+    
     /// Set the canvas tool
     pub fn set_tool(&mut self, tool: CanvasTool) {
         self.current_tool = tool;
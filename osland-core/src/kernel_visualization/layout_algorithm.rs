@@ -4,6 +4,7 @@
 
 use crate::kernel_visualization::visualization_data::{KernelStructure, KernelComponentInfo, ModuleDependency};
 use std::collections::{HashMap, HashSet, VecDeque};
+use crate::core::resource_governor::ResourceGovernor;
 
 /// Layout algorithm trait
 pub trait LayoutAlgorithm {
@@ -260,8 +261,9 @@ impl LayoutAlgorithm for ForceDirectedLayout {
             velocities.insert(component.name.clone(), (0.0, 0.0));
         }
         
-        // Iterate to find stable positions
-        for _ in 0..self.iterations {
+        // Iterate to find stable positions, fewer times while a build loads the host;
+        // layouts run on the UI thread, so they are never put to sleep
+        for _ in 0..ResourceGovernor::global().layout_iterations(self.iterations) {
             // Calculate repulsion forces
             self.calculate_repulsion(&mut positions, &mut velocities);
             
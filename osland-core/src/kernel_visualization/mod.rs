@@ -8,6 +8,13 @@ pub mod layout_algorithm;
 pub mod interactive_canvas;
 pub mod architecture_viewer;
 pub mod visualization_controller;
+pub mod boot_log;
+pub mod what_if;
+pub mod privilege_layout;
+pub mod graph_filter;
+pub mod timeline;
+pub mod trace_ingest;
+pub mod crash_analyzer;
 
 // Re-export core components
 pub use kernel_visualizer::KernelStructureVisualizer;
@@ -17,3 +24,10 @@ pub use interactive_canvas::{InteractiveCanvasWidget, InteractiveCanvasState, Ca
 pub use architecture_viewer::{ArchitectureViewer, ArchitectureViewConfig, Architecture, ArchitectureComparison};
 pub use dependency_analyzer::{EnhancedDependencyAnalyzer, EnhancedDependencyAnalysis};
 pub use visualization_controller::{KernelVisualizationController, VisualizationEvent, VisualizationEventHandler};
+pub use timeline::{ArchitectureTimeline, TimelineFrame, VersionDiff};
+pub use graph_filter::{GraphFilter, FilterExpr, SavedFilters};
+pub use privilege_layout::{PrivilegeLayout, PrivilegeLevel, BoundaryCrossing, classify_privilege, boundary_crossings};
+pub use what_if::{WhatIfChange, WhatIfImpact, WhatIfSession};
+pub use boot_log::{BootEvent, BootLevel, BootLogStore, FailureCorrelation, parse_dmesg, correlate_failures};
+pub use trace_ingest::{TraceEvent, TraceFormat, TraceStore, TraceTimeline, TraceLane, TraceSpan, ComponentMapper, parse_trace, extract_traces, capture_script};
+pub use crash_analyzer::{Oops, OopsFrame, CrashKind, CrashReport, CrashDiagnosis, SourceLocation, Symbolizer, parse_oopses, analyze_crashes};
@@ -0,0 +1,225 @@
+// Privilege-level layout for OSland kernel visualization
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! Layered view of a kernel by privilege boundary.
+//!
+//! Components are grouped into boot, kernel core, driver and userland service layers.
+//! The layer comes from the `privilege` extraction metadata when present, otherwise
+//! from the component type and the kernel architecture (a microkernel runs drivers and
+//! file systems as userland services). Dependencies that cross from a less privileged
+//! layer into a more privileged one are reported as potential attack surface.
+
+use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use crate::core::architecture::KernelArchitecture;
+use crate::kernel_extractor::ComponentType;
+use crate::kernel_visualization::layout_algorithm::LayoutAlgorithm;
+use crate::kernel_visualization::visualization_data::{KernelComponentInfo, KernelStructure};
+
+/// Privilege layer, most privileged first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum PrivilegeLevel {
+    /// Boot code and firmware interfaces
+    Boot,
+    
+    /// Core kernel running in the most privileged ring
+    KernelCore,
+    
+    /// Device drivers and loadable modules
+    Drivers,
+    
+    /// Services running in user mode
+    UserlandServices,
+}
+
+impl PrivilegeLevel {
+    /// All layers, most privileged first
+    pub fn all() -> [PrivilegeLevel; 4] {
+        [PrivilegeLevel::Boot, PrivilegeLevel::KernelCore, PrivilegeLevel::Drivers, PrivilegeLevel::UserlandServices]
+    }
+    
+    /// Parse the `privilege` metadata value
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().replace(['-', ' '], "_").as_str() {
+            "boot" => Some(PrivilegeLevel::Boot),
+            "kernel" | "kernel_core" | "core" => Some(PrivilegeLevel::KernelCore),
+            "driver" | "drivers" => Some(PrivilegeLevel::Drivers),
+            "user" | "userland" | "userland_services" => Some(PrivilegeLevel::UserlandServices),
+            _ => None,
+        }
+    }
+}
+
+/// Layer of a component in a kernel of the given architecture
+pub fn classify_privilege(component: &KernelComponentInfo, architecture: &KernelArchitecture) -> PrivilegeLevel {
+    if let Some(level) = component.original.metadata.get("privilege").and_then(|value| value.as_str()).and_then(PrivilegeLevel::from_name) {
+        return level;
+    }
+    
+    let in_dir = |dir: &str| component.source_files.iter().any(|path| path.iter().any(|part| part == dir));
+    if in_dir("boot") {
+        return PrivilegeLevel::Boot;
+    }
+    if in_dir("usr") || in_dir("tools") {
+        return PrivilegeLevel::UserlandServices;
+    }
+    
+    let user_mode_services = matches!(architecture, KernelArchitecture::Microkernel | KernelArchitecture::Exokernel);
+    match component.component_type {
+        ComponentType::DeviceTree => PrivilegeLevel::Boot,
+        ComponentType::MemoryManagement | ComponentType::ProcessManagement |
+        ComponentType::Security | ComponentType::Virtualization => PrivilegeLevel::KernelCore,
+        ComponentType::Driver | ComponentType::Module => match architecture {
+            KernelArchitecture::Microkernel => PrivilegeLevel::UserlandServices,
+            _ => PrivilegeLevel::Drivers,
+        },
+        ComponentType::FileSystem | ComponentType::Network if user_mode_services => PrivilegeLevel::UserlandServices,
+        ComponentType::FileSystem | ComponentType::Network | ComponentType::Other => PrivilegeLevel::KernelCore,
+    }
+}
+
+/// Dependency between two components in different layers
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BoundaryCrossing {
+    /// Depending component
+    pub from_module: String,
+    
+    /// Component depended on
+    pub to_module: String,
+    
+    /// Layer of the depending component
+    pub from_level: PrivilegeLevel,
+    
+    /// Layer of the component depended on
+    pub to_level: PrivilegeLevel,
+}
+
+impl BoundaryCrossing {
+    /// Whether a less privileged component reaches into a more privileged one
+    pub fn is_attack_surface(&self) -> bool {
+        self.to_level < self.from_level
+    }
+}
+
+/// Layer of every component of a structure
+pub fn privilege_levels(kernel: &KernelStructure) -> HashMap<String, PrivilegeLevel> {
+    kernel.components.iter()
+        .map(|component| (component.name.clone(), classify_privilege(component, &kernel.architecture)))
+        .collect()
+}
+
+/// Dependencies crossing a privilege boundary
+pub fn boundary_crossings(kernel: &KernelStructure) -> Vec<BoundaryCrossing> {
+    let levels = privilege_levels(kernel);
+    kernel.dependencies.iter().filter_map(|dependency| {
+        let from_level = *levels.get(&dependency.from_module)?;
+        let to_level = *levels.get(&dependency.to_module)?;
+        (from_level != to_level).then(|| BoundaryCrossing {
+            from_module: dependency.from_module.clone(),
+            to_module: dependency.to_module.clone(),
+            from_level,
+            to_level,
+        })
+    }).collect()
+}
+
+/// Layout placing each privilege layer in its own horizontal band
+///
+/// Userland services are at the top and boot code at the bottom, so edges pointing
+/// down cross into more privileged code.
+pub struct PrivilegeLayout {
+    /// Height of a layer band
+    pub band_height: f32,
+    /// Horizontal spacing between nodes of a band
+    pub horizontal_spacing: f32,
+}
+
+impl Default for PrivilegeLayout {
+    fn default() -> Self {
+        Self {
+            band_height: 150.0,
+            horizontal_spacing: 150.0,
+        }
+    }
+}
+
+impl LayoutAlgorithm for PrivilegeLayout {
+    fn calculate_layout(&self, kernel: &KernelStructure) -> HashMap<String, (f32, f32)> {
+        let levels = privilege_levels(kernel);
+        let mut positions = HashMap::new();
+        
+        for (band, level) in PrivilegeLevel::all().iter().rev().enumerate() {
+            let mut names: Vec<&String> = levels.iter().filter(|(_, l)| *l == level).map(|(name, _)| name).collect();
+            names.sort();
+            let y = band as f32 * self.band_height;
+            for (index, name) in names.into_iter().enumerate() {
+                positions.insert(name.clone(), (index as f32 * self.horizontal_spacing, y));
+            }
+        }
+        
+        positions
+    }
+    
+    fn name(&self) -> String {
+        "privilege".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kernel_extractor::KernelComponent;
+    use crate::kernel_visualization::visualization_data::ModuleDependency;
+    
+    fn component(name: &str, component_type: ComponentType, metadata: serde_json::Value) -> KernelComponentInfo {
+        KernelComponentInfo {
+            name: name.to_string(),
+            component_type: component_type.clone(),
+            description: None,
+            source_files: Vec::new(),
+            header_files: Vec::new(),
+            size: None,
+            function_count: None,
+            struct_count: None,
+            dependency_count: 0,
+            dependent_count: 0,
+            position: (0.0, 0.0),
+            color: String::new(),
+            is_selected: false,
+            original: KernelComponent { name: name.to_string(), component_type, metadata, ..KernelComponent::default() },
+        }
+    }
+    
+    #[test]
+    fn test_privilege_layers_and_crossings() {
+        let structure = KernelStructure {
+            name: "kernel".to_string(),
+            architecture: KernelArchitecture::Microkernel,
+            version: "1.0".to_string(),
+            components: vec![
+                component("e1000", ComponentType::Driver, serde_json::Value::Null),
+                component("sched", ComponentType::ProcessManagement, serde_json::Value::Null),
+                component("entry", ComponentType::Other, serde_json::json!({"privilege": "boot"})),
+            ],
+            dependencies: vec![
+                ModuleDependency { from_module: "e1000".to_string(), to_module: "sched".to_string(), dependency_type: "ipc".to_string(), count: 1, is_selected: false },
+                ModuleDependency { from_module: "entry".to_string(), to_module: "sched".to_string(), dependency_type: "call".to_string(), count: 1, is_selected: false },
+            ],
+            source_dir: Default::default(),
+            analysis_time: String::new(),
+        };
+        
+        let levels = privilege_levels(&structure);
+        assert_eq!(levels["e1000"], PrivilegeLevel::UserlandServices);
+        assert_eq!(levels["entry"], PrivilegeLevel::Boot);
+        
+        let crossings = boundary_crossings(&structure);
+        assert_eq!(crossings.len(), 2);
+        assert!(crossings[0].is_attack_surface());
+        assert!(!crossings[1].is_attack_surface());
+        
+        let positions = PrivilegeLayout::default().calculate_layout(&structure);
+        assert!(positions["e1000"].1 < positions["sched"].1 && positions["sched"].1 < positions["entry"].1);
+    }
+}
@@ -0,0 +1,394 @@
+// Architecture evolution timeline for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! Playback of how a kernel's component graph changed across versions.
+//!
+//! A timeline holds the structures extracted from successive kernel versions. Every
+//! component gets one position computed on the union of all versions, so nodes stay
+//! put while playing. Between two versions, appearing components fade in, disappearing
+//! ones fade out, and each node is colored by how much its dependencies churned over
+//! the whole timeline. Frames can be exported as a numbered SVG image sequence.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use crate::kernel_visualization::layout_algorithm::LayoutAlgorithm;
+use crate::kernel_visualization::visualization_data::KernelStructure;
+
+/// Changes between two successive versions
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct VersionDiff {
+    /// Earlier version
+    pub from_version: String,
+    
+    /// Later version
+    pub to_version: String,
+    
+    /// Components only in the later version
+    pub added_components: Vec<String>,
+    
+    /// Components only in the earlier version
+    pub removed_components: Vec<String>,
+    
+    /// Dependencies only in the later version
+    pub added_edges: Vec<(String, String)>,
+    
+    /// Dependencies only in the earlier version
+    pub removed_edges: Vec<(String, String)>,
+}
+
+/// Node of a playback frame
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimelineNode {
+    /// Component name
+    pub name: String,
+    
+    /// Canvas position
+    pub position: (f32, f32),
+    
+    /// Opacity (0 = absent, 1 = present)
+    pub opacity: f32,
+    
+    /// Dependency churn of the component over the timeline (0-1)
+    pub heat: f32,
+}
+
+/// Edge of a playback frame
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimelineEdge {
+    /// Depending component
+    pub from_module: String,
+    
+    /// Component depended on
+    pub to_module: String,
+    
+    /// Opacity (0 = absent, 1 = present)
+    pub opacity: f32,
+}
+
+/// Frame of the playback
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimelineFrame {
+    /// Version shown, or the version being left during a transition
+    pub version: String,
+    
+    /// Progress of the transition to the next version (0-1)
+    pub transition: f32,
+    
+    /// Nodes present in either version
+    pub nodes: Vec<TimelineNode>,
+    
+    /// Edges present in either version
+    pub edges: Vec<TimelineEdge>,
+}
+
+/// Structures of successive kernel versions
+pub struct ArchitectureTimeline {
+    /// Structures, oldest first
+    versions: Vec<KernelStructure>,
+    
+    /// Position of every component of any version
+    positions: HashMap<String, (f32, f32)>,
+    
+    /// Added plus removed dependencies per component, over all transitions
+    churn: HashMap<String, usize>,
+}
+
+impl ArchitectureTimeline {
+    /// Create a timeline from structures ordered oldest first
+    pub fn new(versions: Vec<KernelStructure>, layout: &dyn LayoutAlgorithm) -> Result<Self, String> {
+        let latest = versions.last().ok_or("A timeline needs at least one version")?;
+        
+        // Lay out the union of all versions so positions do not jump
+        let mut union = latest.clone();
+        let mut names: HashSet<String> = union.components.iter().map(|component| component.name.clone()).collect();
+        let mut edges: HashSet<(String, String)> = edge_set(&union);
+        for version in &versions {
+            for component in &version.components {
+                if names.insert(component.name.clone()) {
+                    union.components.push(component.clone());
+                }
+            }
+            for dependency in &version.dependencies {
+                if edges.insert((dependency.from_module.clone(), dependency.to_module.clone())) {
+                    union.dependencies.push(dependency.clone());
+                }
+            }
+        }
+        let positions = layout.calculate_layout(&union);
+        
+        let mut timeline = Self { versions, positions, churn: HashMap::new() };
+        for index in 1..timeline.versions.len() {
+            let diff = timeline.diff(index - 1);
+            for (from, to) in diff.added_edges.iter().chain(&diff.removed_edges) {
+                *timeline.churn.entry(from.clone()).or_insert(0) += 1;
+                *timeline.churn.entry(to.clone()).or_insert(0) += 1;
+            }
+        }
+        Ok(timeline)
+    }
+    
+    /// Versions in order
+    pub fn versions(&self) -> Vec<&str> {
+        self.versions.iter().map(|version| version.version.as_str()).collect()
+    }
+    
+    /// Changes from the version at `index` to the next one
+    pub fn diff(&self, index: usize) -> VersionDiff {
+        let (from, to) = match (self.versions.get(index), self.versions.get(index + 1)) {
+            (Some(from), Some(to)) => (from, to),
+            _ => return VersionDiff::default(),
+        };
+        let names = |structure: &KernelStructure| -> BTreeSet<String> {
+            structure.components.iter().map(|component| component.name.clone()).collect()
+        };
+        let (from_names, to_names) = (names(from), names(to));
+        let (from_edges, to_edges): (BTreeSet<_>, BTreeSet<_>) = (edge_set(from).into_iter().collect(), edge_set(to).into_iter().collect());
+        
+        VersionDiff {
+            from_version: from.version.clone(),
+            to_version: to.version.clone(),
+            added_components: to_names.difference(&from_names).cloned().collect(),
+            removed_components: from_names.difference(&to_names).cloned().collect(),
+            added_edges: to_edges.difference(&from_edges).cloned().collect(),
+            removed_edges: from_edges.difference(&to_edges).cloned().collect(),
+        }
+    }
+    
+    /// Dependency churn per component, scaled so the most churned component is 1
+    pub fn churn_heatmap(&self) -> HashMap<String, f32> {
+        let max = self.churn.values().copied().max().unwrap_or(0).max(1) as f32;
+        self.positions.keys()
+            .map(|name| (name.clone(), self.churn.get(name).copied().unwrap_or(0) as f32 / max))
+            .collect()
+    }
+    
+    /// Frame at a playback position: the integer part selects the version, the
+    /// fraction is the progress of the transition to the next version
+    pub fn frame(&self, position: f32) -> TimelineFrame {
+        let (index, transition) = self.playback_index(position);
+        let from = &self.versions[index];
+        let to = &self.versions[(index + 1).min(self.versions.len() - 1)];
+        
+        // Opacity fades from the presence in one version to the presence in the next
+        let fade = |before: bool, after: bool| match (before, after) {
+            (true, true) => 1.0,
+            (true, false) => 1.0 - transition,
+            (false, true) => transition,
+            (false, false) => 0.0,
+        };
+        
+        let (from_names, to_names) = (component_names(from), component_names(to));
+        let heat = self.churn_heatmap();
+        let mut names: Vec<&String> = from_names.union(&to_names).collect();
+        names.sort();
+        let nodes = names.into_iter()
+            .map(|name| TimelineNode {
+                name: name.clone(),
+                position: self.positions.get(name).copied().unwrap_or((0.0, 0.0)),
+                opacity: fade(from_names.contains(name), to_names.contains(name)),
+                heat: heat.get(name).copied().unwrap_or(0.0),
+            })
+            .filter(|node| node.opacity > 0.0)
+            .collect();
+        
+        let (from_edges, to_edges) = (edge_set(from), edge_set(to));
+        let mut edges: Vec<&(String, String)> = from_edges.union(&to_edges).collect();
+        edges.sort();
+        let edges = edges.into_iter()
+            .map(|edge| TimelineEdge {
+                from_module: edge.0.clone(),
+                to_module: edge.1.clone(),
+                opacity: fade(from_edges.contains(edge), to_edges.contains(edge)),
+            })
+            .filter(|edge| edge.opacity > 0.0)
+            .collect();
+        
+        TimelineFrame { version: from.version.clone(), transition, nodes, edges }
+    }
+    
+    /// Structure shown at a playback position: the version shown plus, during a
+    /// transition, the components and dependencies of the next version, placed
+    /// where the frame draws them
+    pub fn structure_at(&self, position: f32) -> KernelStructure {
+        let (index, transition) = self.playback_index(position);
+        let mut structure = self.versions[index].clone();
+        if transition > 0.0 {
+            let next = &self.versions[index + 1];
+            let mut names = component_names(&structure);
+            structure.components.extend(next.components.iter().filter(|component| names.insert(component.name.clone())).cloned());
+            let mut edges = edge_set(&structure);
+            structure.dependencies.extend(next.dependencies.iter()
+                .filter(|dependency| edges.insert((dependency.from_module.clone(), dependency.to_module.clone())))
+                .cloned());
+        }
+        for component in &mut structure.components {
+            component.position = self.positions.get(&component.name).copied().unwrap_or((0.0, 0.0));
+        }
+        structure
+    }
+    
+    /// Version index and transition progress of a playback position
+    fn playback_index(&self, position: f32) -> (usize, f32) {
+        let last = self.versions.len() - 1;
+        let position = position.clamp(0.0, last as f32);
+        let index = (position.floor() as usize).min(last);
+        (index, if index == last { 0.0 } else { position - index as f32 })
+    }
+    
+    /// Write `frames_per_transition` SVG frames per version change into a directory,
+    /// returning the written files in playback order
+    pub fn export_image_sequence(&self, dir: &Path, frames_per_transition: usize) -> Result<Vec<PathBuf>, String> {
+        std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        let steps = frames_per_transition.max(1);
+        let count = (self.versions.len() - 1) * steps + 1;
+        
+        let mut files = Vec::with_capacity(count);
+        for frame_index in 0..count {
+            let frame = self.frame(frame_index as f32 / steps as f32);
+            let path = dir.join(format!("frame_{:05}.svg", frame_index));
+            std::fs::write(&path, frame.to_svg()).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+            files.push(path);
+        }
+        Ok(files)
+    }
+}
+
+impl TimelineFrame {
+    /// Render the frame as an SVG image
+    pub fn to_svg(&self) -> String {
+        let positions: HashMap<&str, (f32, f32)> = self.nodes.iter().map(|node| (node.name.as_str(), node.position)).collect();
+        let (max_x, max_y) = self.nodes.iter()
+            .fold((0.0f32, 0.0f32), |(x, y), node| (x.max(node.position.0), y.max(node.position.1)));
+        let (margin, radius) = (60.0, 18.0);
+        
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{:.0}\" height=\"{:.0}\">\n",
+            max_x + 2.0 * margin, max_y + 2.0 * margin + 30.0
+        );
+        svg.push_str(&format!("  <text x=\"10\" y=\"24\" font-family=\"sans-serif\">{}</text>\n", escape(&self.version)));
+        for edge in &self.edges {
+            if let (Some(from), Some(to)) = (positions.get(edge.from_module.as_str()), positions.get(edge.to_module.as_str())) {
+                svg.push_str(&format!(
+                    "  <line x1=\"{:.1}\" y1=\"{:.1}\" x2=\"{:.1}\" y2=\"{:.1}\" stroke=\"#888\" stroke-opacity=\"{:.2}\"/>\n",
+                    from.0 + margin, from.1 + margin + 30.0, to.0 + margin, to.1 + margin + 30.0, edge.opacity
+                ));
+            }
+        }
+        for node in &self.nodes {
+            // Cold nodes are blue, churned nodes red
+            let red = (node.heat * 255.0) as u8;
+            let (x, y) = (node.position.0 + margin, node.position.1 + margin + 30.0);
+            svg.push_str(&format!(
+                "  <circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"{}\" fill=\"#{:02x}40{:02x}\" fill-opacity=\"{:.2}\"/>\n",
+                x, y, radius, red, 255 - red, node.opacity
+            ));
+            svg.push_str(&format!(
+                "  <text x=\"{:.1}\" y=\"{:.1}\" font-family=\"sans-serif\" font-size=\"10\" text-anchor=\"middle\" fill-opacity=\"{:.2}\">{}</text>\n",
+                x, y + radius + 12.0, node.opacity, escape(&node.name)
+            ));
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+fn component_names(structure: &KernelStructure) -> HashSet<String> {
+    structure.components.iter().map(|component| component.name.clone()).collect()
+}
+
+fn edge_set(structure: &KernelStructure) -> HashSet<(String, String)> {
+    structure.dependencies.iter().map(|dependency| (dependency.from_module.clone(), dependency.to_module.clone())).collect()
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::architecture::KernelArchitecture;
+    use crate::kernel_extractor::{ComponentType, KernelComponent};
+    use crate::kernel_visualization::visualization_data::{KernelComponentInfo, ModuleDependency};
+    
+    struct GridLayout;
+    
+    impl LayoutAlgorithm for GridLayout {
+        fn calculate_layout(&self, kernel: &KernelStructure) -> HashMap<String, (f32, f32)> {
+            kernel.components.iter().enumerate().map(|(i, component)| (component.name.clone(), (i as f32 * 100.0, 0.0))).collect()
+        }
+        
+        fn name(&self) -> String {
+            "grid".to_string()
+        }
+    }
+    
+    fn structure(version: &str, components: &[&str], edges: &[(&str, &str)]) -> KernelStructure {
+        KernelStructure {
+            name: "linux".to_string(),
+            architecture: KernelArchitecture::Monolithic,
+            version: version.to_string(),
+            components: components.iter().map(|name| KernelComponentInfo {
+                name: name.to_string(),
+                component_type: ComponentType::Module,
+                description: None,
+                source_files: Vec::new(),
+                header_files: Vec::new(),
+                size: None,
+                function_count: None,
+                struct_count: None,
+                dependency_count: 0,
+                dependent_count: 0,
+                position: (0.0, 0.0),
+                color: String::new(),
+                is_selected: false,
+                original: KernelComponent::default(),
+            }).collect(),
+            dependencies: edges.iter().map(|(from, to)| ModuleDependency {
+                from_module: from.to_string(),
+                to_module: to.to_string(),
+                dependency_type: "call".to_string(),
+                count: 1,
+                is_selected: false,
+            }).collect(),
+            source_dir: PathBuf::new(),
+            analysis_time: String::new(),
+        }
+    }
+    
+    #[test]
+    fn test_timeline_playback() {
+        let timeline = ArchitectureTimeline::new(vec![
+            structure("5.10", &["vfs", "mm", "ext3"], &[("ext3", "vfs"), ("vfs", "mm")]),
+            structure("6.6", &["vfs", "mm", "ext4"], &[("ext4", "vfs"), ("vfs", "mm")]),
+        ], &GridLayout).unwrap();
+        
+        let diff = timeline.diff(0);
+        assert_eq!((diff.added_components, diff.removed_components), (vec!["ext4".to_string()], vec!["ext3".to_string()]));
+        assert_eq!(timeline.churn_heatmap()["vfs"], 1.0);
+        assert_eq!(timeline.churn_heatmap()["mm"], 0.0);
+        
+        let frame = timeline.frame(0.25);
+        let opacity = |name: &str| frame.nodes.iter().find(|node| node.name == name).unwrap().opacity;
+        assert_eq!((opacity("ext3"), opacity("ext4"), opacity("vfs")), (0.75, 0.25, 1.0));
+        assert_eq!(timeline.frame(1.0).version, "6.6");
+        assert_eq!(timeline.frame(1.0).nodes.len(), 3);
+        
+        // The structure follows the frame: both versions while transitioning, then the later one
+        let structure = timeline.structure_at(0.25);
+        let names: Vec<&str> = structure.components.iter().map(|component| component.name.as_str()).collect();
+        assert_eq!(names, vec!["vfs", "mm", "ext3", "ext4"]);
+        assert_eq!(structure.dependencies.len(), 3);
+        assert_eq!(structure.components[3].position, frame.nodes.iter().find(|node| node.name == "ext4").unwrap().position);
+        let structure = timeline.structure_at(1.0);
+        assert_eq!((structure.version.as_str(), structure.components.len(), structure.dependencies.len()), ("6.6", 3, 2));
+        
+        let dir = std::env::temp_dir().join(format!("osland-timeline-{}", uuid::Uuid::new_v4()));
+        let files = timeline.export_image_sequence(&dir, 4).unwrap();
+        assert_eq!(files.len(), 5);
+        assert!(std::fs::read_to_string(&files[2]).unwrap().contains("<circle"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,761 @@
+// Kernel trace ingestion for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! ftrace, perf and ktrace output of OS images running in QEMU.
+//!
+//! The guest prints its trace on the serial console between
+//! `--- osland-trace-begin <format> ---` and `--- osland-trace-end ---` markers (see
+//! `capture_script`). Each event is attributed to the kernel component or tile whose
+//! symbol prefix matches the traced function or tracepoint, stored in the
+//! `trace_events` table, and laid out as one timeline lane per component.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use crate::build_engine::test_orchestrator::TestReport;
+use crate::component_manager::visual_node::NodeCanvas;
+use crate::dbos_integration::dbos_core::{ColumnDefinition, ColumnType, IndexDefinition, TableDefinition, SharedTables};
+use crate::kernel_visualization::boot_log::{subsystem_key, TILE_SUBSYSTEMS_PROPERTY};
+use crate::kernel_visualization::visualization_data::KernelStructure;
+use crate::tile_engine::tile_core::TileGraph;
+
+/// Table of trace events
+pub const TRACE_EVENTS_TABLE: &str = "trace_events";
+
+/// Tile property listing the symbol prefixes of the tile's code (comma-separated)
+pub const TILE_SYMBOLS_PROPERTY: &str = "kernel.symbols";
+
+/// Console line opening a trace section (followed by the format name and ` ---`)
+pub const TRACE_BEGIN_MARKER: &str = "--- osland-trace-begin";
+
+/// Console line closing a trace section
+pub const TRACE_END_MARKER: &str = "--- osland-trace-end ---";
+
+/// Lane of events no component claims
+pub const UNMAPPED_LANE: &str = "(unmapped)";
+
+/// Trace output format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TraceFormat {
+    /// Linux tracefs `trace` file (function tracer and tracepoints)
+    Ftrace,
+    
+    /// `perf script` output
+    Perf,
+    
+    /// BSD `kdump -T` output of a ktrace file
+    Ktrace,
+}
+
+impl TraceFormat {
+    /// Format name used in trace markers
+    pub fn name(&self) -> &'static str {
+        match self {
+            TraceFormat::Ftrace => "ftrace",
+            TraceFormat::Perf => "perf",
+            TraceFormat::Ktrace => "ktrace",
+        }
+    }
+    
+    /// Format with a name
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim() {
+            "ftrace" => Some(TraceFormat::Ftrace),
+            "perf" => Some(TraceFormat::Perf),
+            "ktrace" => Some(TraceFormat::Ktrace),
+            _ => None,
+        }
+    }
+}
+
+/// One traced event
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TraceEvent {
+    /// Run the trace belongs to
+    pub run_id: String,
+    
+    /// Project name
+    pub project: String,
+    
+    /// Position in the trace
+    pub seq: usize,
+    
+    /// Timestamp in microseconds
+    pub timestamp_us: u64,
+    
+    /// CPU the event happened on (ktrace does not record it)
+    pub cpu: Option<u32>,
+    
+    /// Process ID
+    pub pid: u32,
+    
+    /// Process name
+    pub comm: String,
+    
+    /// Event name (`function` for the function tracer, the sampled event for perf,
+    /// the record type for ktrace)
+    pub event: String,
+    
+    /// Kernel function or system call, if the event has one
+    pub symbol: Option<String>,
+    
+    /// Rest of the line
+    pub detail: String,
+    
+    /// Kernel component name or tile ID owning the code
+    pub component: Option<String>,
+}
+
+impl TraceEvent {
+    /// Event stored in a row of the trace events table
+    pub fn from_row(values: &HashMap<String, String>) -> Self {
+        let value = |name: &str| values.get(name).cloned().unwrap_or_default();
+        let optional = |name: &str| Some(value(name)).filter(|value| !value.is_empty());
+        Self {
+            run_id: value("run_id"),
+            project: value("project"),
+            seq: value("seq").parse().unwrap_or(0),
+            timestamp_us: value("timestamp_us").parse().unwrap_or(0),
+            cpu: value("cpu").parse().ok(),
+            pid: value("pid").parse().unwrap_or(0),
+            comm: value("comm"),
+            event: value("event"),
+            symbol: optional("symbol"),
+            detail: value("detail"),
+            component: optional("component"),
+        }
+    }
+}
+
+/// Parse trace output
+///
+/// Header, comment and call-chain lines are skipped.
+pub fn parse_trace(run_id: &str, project: &str, format: TraceFormat, text: &str) -> Vec<TraceEvent> {
+    let parse = match format {
+        TraceFormat::Ftrace => parse_ftrace_line,
+        TraceFormat::Perf => parse_perf_line,
+        TraceFormat::Ktrace => parse_ktrace_line,
+    };
+    text.lines()
+        .map(|line| line.trim_end_matches('\r'))
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .filter_map(parse)
+        .enumerate()
+        .map(|(seq, mut event)| {
+            event.run_id = run_id.to_string();
+            event.project = project.to_string();
+            event.seq = seq;
+            event
+        })
+        .collect()
+}
+
+/// Event with the fields every format has
+fn event(timestamp: f64, cpu: Option<u32>, pid: u32, comm: &str, name: &str, symbol: Option<String>, detail: &str) -> TraceEvent {
+    TraceEvent {
+        run_id: String::new(),
+        project: String::new(),
+        seq: 0,
+        timestamp_us: (timestamp * 1_000_000.0).round() as u64,
+        cpu,
+        pid,
+        comm: comm.to_string(),
+        event: name.to_string(),
+        symbol,
+        detail: detail.to_string(),
+        component: None,
+    }
+}
+
+/// Seconds of a `123.456789:` timestamp token
+fn timestamp_of(token: &str) -> Option<f64> {
+    token.strip_suffix(':')?.parse().ok()
+}
+
+/// CPU of a `[001]` token
+fn cpu_of(token: &str) -> Option<u32> {
+    token.strip_prefix('[')?.strip_suffix(']')?.parse().ok()
+}
+
+/// Function name without the `+0x1f/0x40` offset
+fn strip_offset(symbol: &str) -> String {
+    symbol.split('+').next().unwrap_or(symbol).to_string()
+}
+
+/// `bash-1234 [000] d..2. 123.456789: sched_switch: prev_comm=...` or
+/// `bash-1234 [000] .... 123.456789: do_sys_open <-__x64_sys_openat`
+fn parse_ftrace_line(line: &str) -> Option<TraceEvent> {
+    let open = line.find(" [")?;
+    let (task, rest) = (line[..open].trim(), &line[open + 1..]);
+    let (comm, pid) = task.rsplit_once('-')?;
+    let pid = pid.parse().ok()?;
+    
+    let mut tokens = rest.splitn(2, ' ');
+    let cpu = cpu_of(tokens.next()?)?;
+    let rest = tokens.next()?.trim_start();
+    
+    // The flags column (irqs-off, need-resched, ...) is optional
+    let (first, after) = rest.split_once(' ')?;
+    let (timestamp, rest) = match timestamp_of(first) {
+        Some(timestamp) => (timestamp, after),
+        None => {
+            let after = after.trim_start();
+            let (second, rest) = after.split_once(' ').unwrap_or((after, ""));
+            (timestamp_of(second)?, rest)
+        }
+    };
+    let rest = rest.trim();
+    
+    let (head, detail) = rest.split_once(' ').unwrap_or((rest, ""));
+    match head.strip_suffix(':') {
+        Some(name) => Some(event(timestamp, Some(cpu), pid, comm, name, None, detail.trim())),
+        None => Some(event(timestamp, Some(cpu), pid, comm, "function", Some(head.to_string()), detail.trim())),
+    }
+}
+
+/// `bash 1234 [001] 5417.331946: 250000 cpu-clock: ffffffff8105d3a1 do_syscall_64+0x31 ([kernel.kallsyms])`
+fn parse_perf_line(line: &str) -> Option<TraceEvent> {
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+    let time_index = tokens.iter().position(|token| timestamp_of(token).is_some())?;
+    let timestamp = timestamp_of(tokens[time_index])?;
+    
+    let mut header = &tokens[..time_index];
+    let cpu = header.last().and_then(|token| cpu_of(token));
+    if cpu.is_some() {
+        header = &header[..header.len() - 1];
+    }
+    let (pid, comm) = header.split_last()?;
+    let pid = pid.split('/').next()?.parse().ok()?;
+    
+    // An optional sample period precedes the event name
+    let event_index = time_index + 1 + tokens[time_index + 1..].iter().position(|token| token.ends_with(':'))?;
+    let name = tokens[event_index].trim_end_matches(':');
+    let rest = &tokens[event_index + 1..];
+    let symbol = match rest {
+        [address, symbol, ..] if u64::from_str_radix(address, 16).is_ok() => Some(strip_offset(symbol)),
+        _ => None,
+    };
+    Some(event(timestamp, cpu, pid, &comm.join(" "), name, symbol, &rest.join(" ")))
+}
+
+/// `1234 ls 1700000000.123456 CALL open(0x7f7ffffd,0<O_RDONLY>)`
+fn parse_ktrace_line(line: &str) -> Option<TraceEvent> {
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+    if tokens.len() < 4 {
+        return None;
+    }
+    let pid = tokens[0].split('/').next()?.parse().ok()?;
+    let timestamp: f64 = tokens[2].parse().ok()?;
+    let kind = tokens[3];
+    if !kind.chars().all(|c| c.is_ascii_uppercase()) {
+        return None;
+    }
+    let detail = tokens[4..].join(" ");
+    let symbol = match kind {
+        "CALL" => detail.split('(').next().map(str::to_string),
+        "RET" => tokens.get(4).map(|name| name.to_string()),
+        _ => None,
+    };
+    Some(event(timestamp, None, pid, tokens[1], &kind.to_lowercase(), symbol, &detail))
+}
+
+/// Trace sections printed on a console, in order
+pub fn extract_traces(console: &str) -> Vec<(TraceFormat, String)> {
+    let mut sections = Vec::new();
+    let mut current: Option<(TraceFormat, String)> = None;
+    for line in console.lines() {
+        let trimmed = line.trim_end_matches('\r').trim();
+        if let Some(name) = trimmed.strip_prefix(TRACE_BEGIN_MARKER).and_then(|rest| rest.strip_suffix("---")) {
+            current = TraceFormat::from_name(name).map(|format| (format, String::new()));
+        } else if trimmed == TRACE_END_MARKER {
+            sections.extend(current.take());
+        } else if let Some((_, text)) = &mut current {
+            text.push_str(line.trim_end_matches('\r'));
+            text.push('\n');
+        }
+    }
+    sections
+}
+
+/// Guest shell script that traces a command and prints the trace on the console
+///
+/// `events` are tracepoints (`sched:sched_switch`) for ftrace and perf; an empty list
+/// traces kernel functions (ftrace) or samples CPU cycles (perf).
+pub fn capture_script(format: TraceFormat, events: &[&str], command: &str) -> String {
+    let mut script = String::from("#!/bin/sh\n");
+    match format {
+        TraceFormat::Ftrace => {
+            script.push_str("T=/sys/kernel/tracing\n");
+            script.push_str("[ -e $T/trace ] || mount -t tracefs nodev $T\n");
+            script.push_str("echo 0 > $T/tracing_on\necho > $T/trace\n");
+            if events.is_empty() {
+                script.push_str("echo function > $T/current_tracer\n");
+            } else {
+                script.push_str("echo nop > $T/current_tracer\n");
+                script.push_str(&format!("echo '{}' > $T/set_event\n", events.join(" ")));
+            }
+            script.push_str(&format!("echo 1 > $T/tracing_on\n{}\necho 0 > $T/tracing_on\n", command));
+            script.push_str(&format!("echo '{} ftrace ---'\ncat $T/trace\n", TRACE_BEGIN_MARKER));
+        }
+        TraceFormat::Perf => {
+            let events = if events.is_empty() { "cycles".to_string() } else { events.join(",") };
+            script.push_str(&format!("perf record -a -e {} -o /tmp/osland.perf -- {}\n", events, command));
+            script.push_str(&format!("echo '{} perf ---'\nperf script -i /tmp/osland.perf -F comm,pid,cpu,time,event,ip,sym\n", TRACE_BEGIN_MARKER));
+        }
+        TraceFormat::Ktrace => {
+            script.push_str(&format!("ktrace -f /tmp/osland.ktrace {}\n", command));
+            script.push_str(&format!("echo '{} ktrace ---'\nkdump -T -f /tmp/osland.ktrace\n", TRACE_BEGIN_MARKER));
+        }
+    }
+    script.push_str(&format!("echo '{}'\n", TRACE_END_MARKER));
+    script
+}
+
+/// Maps traced symbols and tracepoints to the components owning the code
+///
+/// Every component contributes symbol prefixes: its name and the directories of its
+/// source files for kernel components, the `kernel.symbols` and `kernel.subsystems`
+/// properties and the name for tiles. The longest matching prefix wins. Source files
+/// resolved from a symbol (e.g. by addr2line) are matched against the files and
+/// directories of the components the same way.
+#[derive(Debug, Clone, Default)]
+pub struct ComponentMapper {
+    prefixes: Vec<(String, String)>,
+    files: Vec<(PathBuf, String)>,
+}
+
+impl ComponentMapper {
+    /// Create a mapper from kernel components and tiles
+    pub fn new(structure: Option<&KernelStructure>, tiles: Option<&TileGraph>) -> Self {
+        let mut mapper = Self::default();
+        for component in structure.iter().flat_map(|structure| &structure.components) {
+            mapper.add_prefix(&component.name, &component.name);
+            for path in &component.source_files {
+                mapper.add_source_file(path, &component.name);
+                if let Some(dir) = path.parent().and_then(|dir| dir.file_name()) {
+                    mapper.add_prefix(&dir.to_string_lossy(), &component.name);
+                }
+            }
+        }
+        
+        let mut graph_tiles: Vec<_> = tiles.iter().flat_map(|graph| graph.tiles.values()).collect();
+        graph_tiles.sort_by(|a, b| a.id.cmp(&b.id));
+        for tile in graph_tiles {
+            for property in [TILE_SYMBOLS_PROPERTY, TILE_SUBSYSTEMS_PROPERTY] {
+                for prefix in tile.get_property(property).into_iter().flat_map(|value| value.split(',')) {
+                    mapper.add_prefix(prefix.trim(), &tile.id);
+                }
+            }
+            mapper.add_prefix(&tile.name, &tile.id);
+        }
+        mapper
+    }
+    
+    /// Attribute the nodes of a canvas by component name and implementation files
+    ///
+    /// Nodes are identified by their node ID.
+    pub fn add_canvas(&mut self, canvas: &NodeCanvas) {
+        let mut nodes: Vec<_> = canvas.nodes.values().collect();
+        nodes.sort_by(|a, b| a.id.cmp(&b.id));
+        for node in nodes {
+            self.add_prefix(&node.component_id, &node.id);
+            self.add_prefix(&node.component.name, &node.id);
+            for file in &node.component.implementation_files {
+                self.add_source_file(Path::new(file), &node.id);
+            }
+        }
+    }
+    
+    /// Attribute a source file or directory to a component
+    pub fn add_source_file(&mut self, path: &Path, component: &str) {
+        if !path.as_os_str().is_empty() && !self.files.iter().any(|(existing, _)| existing == path) {
+            self.files.push((path.to_path_buf(), component.to_string()));
+        }
+    }
+    
+    /// Component owning a source file (`/build/linux/fs/ext4/dir.c` matches `fs/ext4`)
+    ///
+    /// The most specific file or directory wins.
+    pub fn component_of_file(&self, file: &Path) -> Option<&str> {
+        self.files.iter()
+            .filter(|(path, _)| file.ancestors().any(|ancestor| ancestor.ends_with(path)))
+            .max_by_key(|(path, _)| path.components().count())
+            .map(|(_, component)| component.as_str())
+    }
+    
+    /// Attribute symbols starting with a prefix to a component
+    ///
+    /// Earlier prefixes win over equal later ones.
+    pub fn add_prefix(&mut self, prefix: &str, component: &str) {
+        let prefix = subsystem_key(prefix.trim());
+        if !prefix.is_empty() && !self.prefixes.iter().any(|(existing, _)| *existing == prefix) {
+            self.prefixes.push((prefix, component.to_string()));
+        }
+    }
+    
+    /// Component owning a symbol or tracepoint (`ext4_file_open`, `ext4:ext4_da_write_begin`)
+    pub fn component_of(&self, name: &str) -> Option<&str> {
+        let name = name.rsplit(':').next().unwrap_or(name).trim_start_matches('_').to_ascii_lowercase();
+        self.prefixes.iter()
+            .filter(|(prefix, _)| {
+                name == *prefix || (name.starts_with(prefix.as_str()) && (prefix.ends_with('_') || name[prefix.len()..].starts_with('_')))
+            })
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, component)| component.as_str())
+    }
+    
+    /// Attribute the events of a trace
+    pub fn map(&self, events: &mut [TraceEvent]) {
+        for event in events {
+            let name = event.symbol.as_deref().unwrap_or(&event.event);
+            event.component = self.component_of(name).map(str::to_string);
+        }
+    }
+}
+
+/// Trace events stored in a DBOS table
+pub struct TraceStore {
+    tables: SharedTables,
+}
+
+impl TraceStore {
+    /// Open the store on a running tables manager or one of its namespaces, creating the table if needed
+    pub fn new(tables: impl Into<SharedTables>) -> Result<Self, String> {
+        let tables = tables.into();
+        if tables.get_table(TRACE_EVENTS_TABLE)?.is_none() {
+            let now = chrono::Utc::now().timestamp() as u64;
+            tables.create_table(TableDefinition {
+                name: TRACE_EVENTS_TABLE.to_string(),
+                columns: vec![
+                    column("run_id", ColumnType::String, "Traced run"),
+                    column("project", ColumnType::String, "Project name"),
+                    column("seq", ColumnType::Integer, "Position in the trace"),
+                    column("timestamp_us", ColumnType::Integer, "Timestamp in microseconds"),
+                    column("cpu", ColumnType::Integer, "CPU (empty for ktrace)"),
+                    column("pid", ColumnType::Integer, "Process ID"),
+                    column("comm", ColumnType::String, "Process name"),
+                    column("event", ColumnType::String, "Event name"),
+                    column("symbol", ColumnType::String, "Kernel function or system call"),
+                    column("detail", ColumnType::String, "Rest of the trace line"),
+                    column("component", ColumnType::String, "Component or tile owning the code"),
+                ],
+                primary_key: vec!["run_id".to_string(), "seq".to_string()],
+                indexes: vec![
+                    IndexDefinition {
+                        name: "idx_trace_events_project".to_string(),
+                        columns: vec!["project".to_string()],
+                        unique: false,
+                    },
+                    IndexDefinition {
+                        name: "idx_trace_events_component".to_string(),
+                        columns: vec!["project".to_string(), "component".to_string()],
+                        unique: false,
+                    },
+                ],
+                description: "Kernel traces of runs in QEMU".to_string(),
+                created_at: now,
+                updated_at: now,
+            })?;
+        }
+        Ok(Self { tables })
+    }
+    
+    /// Parse, attribute and store a trace
+    ///
+    /// A run is stored completely or not at all: a run ID that is already stored is
+    /// rejected, and the rows of a failed ingestion are removed again.
+    pub fn ingest(&self, project: &str, run_id: &str, format: TraceFormat, text: &str, mapper: &ComponentMapper) -> Result<Vec<TraceEvent>, String> {
+        let run = HashMap::from([("run_id".to_string(), run_id.to_string())]);
+        if self.tables.for_each_row(TRACE_EVENTS_TABLE, &run, |_| {})? > 0 {
+            return Err(format!("Trace run '{}' is already stored", run_id));
+        }
+        
+        let mut events = parse_trace(run_id, project, format, text);
+        mapper.map(&mut events);
+        let mut inserted = Vec::with_capacity(events.len());
+        for event in &events {
+            let values = HashMap::from([
+                ("run_id".to_string(), event.run_id.clone()),
+                ("project".to_string(), event.project.clone()),
+                ("seq".to_string(), event.seq.to_string()),
+                ("timestamp_us".to_string(), event.timestamp_us.to_string()),
+                ("cpu".to_string(), event.cpu.map(|cpu| cpu.to_string()).unwrap_or_default()),
+                ("pid".to_string(), event.pid.to_string()),
+                ("comm".to_string(), event.comm.clone()),
+                ("event".to_string(), event.event.clone()),
+                ("symbol".to_string(), event.symbol.clone().unwrap_or_default()),
+                ("detail".to_string(), event.detail.clone()),
+                ("component".to_string(), event.component.clone().unwrap_or_default()),
+            ]);
+            match self.tables.insert_row(TRACE_EVENTS_TABLE, values) {
+                Ok(row_id) => inserted.push(row_id),
+                Err(e) => {
+                    for row_id in &inserted {
+                        let _ = self.tables.delete_row(TRACE_EVENTS_TABLE, row_id);
+                    }
+                    return Err(e);
+                }
+            }
+        }
+        Ok(events)
+    }
+    
+    /// Store the traces printed by the tests of a report
+    ///
+    /// Each trace section becomes a run named `<build>/<suite>/<test>/<format>`; further
+    /// sections of the same format in a test get `-2`, `-3`, ... appended.
+    pub fn ingest_report(&self, project: &str, build_id: &str, report: &TestReport, mapper: &ComponentMapper) -> Result<Vec<TraceEvent>, String> {
+        let mut events = Vec::new();
+        for result in &report.results {
+            let mut sections: HashMap<TraceFormat, usize> = HashMap::new();
+            for (format, text) in extract_traces(&result.console) {
+                let count = sections.entry(format).or_insert(0);
+                *count += 1;
+                let mut run_id = format!("{}/{}/{}/{}", build_id, result.suite, result.name, format.name());
+                if *count > 1 {
+                    run_id.push_str(&format!("-{}", count));
+                }
+                events.extend(self.ingest(project, &run_id, format, &text, mapper)?);
+            }
+        }
+        Ok(events)
+    }
+    
+    /// Events of a run in trace order
+    pub fn events(&self, run_id: &str) -> Result<Vec<TraceEvent>, String> {
+        self.load(HashMap::from([("run_id".to_string(), run_id.to_string())]))
+    }
+    
+    /// Events of a project attributed to a component
+    pub fn component_events(&self, project: &str, component: &str) -> Result<Vec<TraceEvent>, String> {
+        self.load(HashMap::from([
+            ("project".to_string(), project.to_string()),
+            ("component".to_string(), component.to_string()),
+        ]))
+    }
+    
+    fn load(&self, conditions: HashMap<String, String>) -> Result<Vec<TraceEvent>, String> {
+        let mut events: Vec<TraceEvent> = self.tables.query_rows(TRACE_EVENTS_TABLE, conditions)?.iter()
+            .map(|row| TraceEvent::from_row(&row.values))
+            .collect();
+        events.sort_by(|a, b| a.run_id.cmp(&b.run_id).then(a.seq.cmp(&b.seq)));
+        Ok(events)
+    }
+}
+
+/// Build a column definition
+fn column(name: &str, column_type: ColumnType, description: &str) -> ColumnDefinition {
+    ColumnDefinition {
+        name: name.to_string(),
+        column_type,
+        nullable: matches!(name, "cpu" | "symbol" | "component"),
+        default_value: None,
+        description: description.to_string(),
+    }
+}
+
+/// Time a component was running, as seen by the trace
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TraceSpan {
+    /// Start in microseconds
+    pub start_us: u64,
+    
+    /// End in microseconds
+    pub end_us: u64,
+    
+    /// Symbol or event name
+    pub label: String,
+    
+    /// CPU, if the trace records it
+    pub cpu: Option<u32>,
+}
+
+/// Timeline lane of one component
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TraceLane {
+    /// Component name or tile ID (`(unmapped)` for unattributed events)
+    pub component: String,
+    
+    /// Spans in time order
+    pub spans: Vec<TraceSpan>,
+}
+
+impl TraceLane {
+    /// Total time spent in the component
+    pub fn busy_us(&self) -> u64 {
+        self.spans.iter().map(|span| span.end_us - span.start_us).sum()
+    }
+}
+
+/// Per-component timeline of a trace
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TraceTimeline {
+    /// First timestamp in microseconds
+    pub start_us: u64,
+    
+    /// Last timestamp in microseconds
+    pub end_us: u64,
+    
+    /// Lanes ordered by busy time, unmapped events last
+    pub lanes: Vec<TraceLane>,
+}
+
+impl TraceTimeline {
+    /// Lay out events as spans
+    ///
+    /// An event lasts until the next event on the same CPU (or of the same process
+    /// when the trace has no CPU); consecutive spans with the same label are merged.
+    pub fn from_events(events: &[TraceEvent]) -> Self {
+        let mut ordered: Vec<&TraceEvent> = events.iter().collect();
+        ordered.sort_by_key(|event| (event.timestamp_us, event.seq));
+        let (Some(first), Some(last)) = (ordered.first(), ordered.last()) else {
+            return Self::default();
+        };
+        let (start_us, end_us) = (first.timestamp_us, last.timestamp_us);
+        
+        let track = |event: &TraceEvent| event.cpu.map_or((false, event.pid), |cpu| (true, cpu));
+        let mut next_on_track: HashMap<(bool, u32), u64> = HashMap::new();
+        let mut ends = vec![0u64; ordered.len()];
+        for (index, event) in ordered.iter().enumerate().rev() {
+            ends[index] = next_on_track.get(&track(event)).copied().unwrap_or(event.timestamp_us);
+            next_on_track.insert(track(event), event.timestamp_us);
+        }
+        
+        let mut lanes: HashMap<String, Vec<TraceSpan>> = HashMap::new();
+        for (event, end_us) in ordered.iter().zip(ends) {
+            let component = event.component.clone().unwrap_or_else(|| UNMAPPED_LANE.to_string());
+            let label = event.symbol.clone().unwrap_or_else(|| event.event.clone());
+            let spans = lanes.entry(component).or_default();
+            match spans.last_mut() {
+                Some(previous) if previous.label == label && previous.cpu == event.cpu && previous.end_us == event.timestamp_us => previous.end_us = end_us,
+                _ => spans.push(TraceSpan { start_us: event.timestamp_us, end_us, label, cpu: event.cpu }),
+            }
+        }
+        
+        let mut lanes: Vec<TraceLane> = lanes.into_iter().map(|(component, spans)| TraceLane { component, spans }).collect();
+        lanes.sort_by(|a, b| {
+            (a.component == UNMAPPED_LANE).cmp(&(b.component == UNMAPPED_LANE))
+                .then(b.busy_us().cmp(&a.busy_us()))
+                .then(a.component.cmp(&b.component))
+        });
+        Self { start_us, end_us, lanes }
+    }
+    
+    /// Lane of a component
+    pub fn lane(&self, component: &str) -> Option<&TraceLane> {
+        self.lanes.iter().find(|lane| lane.component == component)
+    }
+    
+    /// Timelines of the events of several runs, by run ID
+    pub fn by_run(events: &[TraceEvent]) -> Vec<(String, Self)> {
+        let mut runs: BTreeMap<&str, Vec<TraceEvent>> = BTreeMap::new();
+        for event in events {
+            runs.entry(event.run_id.as_str()).or_default().push(event.clone());
+        }
+        runs.into_iter().map(|(run_id, events)| (run_id.to_string(), Self::from_events(&events))).collect()
+    }
+    
+    /// Render every lane as a text bar of `columns` cells, marking the cells its spans cover
+    pub fn lane_bars(&self, columns: usize) -> Vec<String> {
+        let columns = columns.max(1);
+        let duration = (self.end_us - self.start_us).max(1);
+        let cell = |us: u64| (((us - self.start_us) as u128 * columns as u128 / duration as u128) as usize).min(columns - 1);
+        let name_width = self.lanes.iter().map(|lane| lane.component.chars().count()).max().unwrap_or(0);
+        self.lanes.iter().map(|lane| {
+            let mut bar = vec![' '; columns];
+            for span in &lane.spans {
+                // Instant events stay visible as one cell wide marks
+                for mark in &mut bar[cell(span.start_us)..=cell(span.end_us)] {
+                    *mark = '#';
+                }
+            }
+            format!("{:<width$} |{}| {} us", lane.component, bar.into_iter().collect::<String>(), lane.busy_us(), width = name_width)
+        }).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use crate::dbos_integration::dbos_core::TablesManager;
+    use crate::build_engine::test_orchestrator::{TestOutcome, TestResult};
+    use crate::tile_engine::tile_core::{Tile, TileType};
+    
+    const CONSOLE: &str = "\
+Welcome to OSland
+--- osland-trace-begin ftrace ---
+# tracer: function
+#           TASK-PID     CPU#  |||||  TIMESTAMP  FUNCTION
+              sh-71      [000] .....    10.000100: ext4_file_open <-do_dentry_open
+              sh-71      [000] .....    10.000150: jbd2_journal_start <-ext4_dirty_inode
+          <idle>-0       [001] d..2.    10.000160: sched_switch: prev_comm=swapper/1 prev_pid=0 ==> next_comm=sh next_pid=71
+              sh-71      [000] .....    10.000400: vfs_read <-ksys_read
+--- osland-trace-end ---
+--- osland-trace-begin perf ---
+      sh    71 [000]    10.000500: cycles:  ffffffff8105d3a1 ext4_readdir+0x31 ([kernel.kallsyms])
+	ffffffff8105d3a1 ext4_readdir+0x31 ([kernel.kallsyms])
+--- osland-trace-end ---
+";
+
+    #[test]
+    fn test_trace_ingestion_and_timeline() {
+        let sections = extract_traces(CONSOLE);
+        assert_eq!(sections.iter().map(|(format, _)| *format).collect::<Vec<_>>(), vec![TraceFormat::Ftrace, TraceFormat::Perf]);
+        
+        let ktrace = parse_trace("r", "p", TraceFormat::Ktrace, " 71 sh       12.000100 CALL  open(0x7f7ffffd,0<O_RDONLY>)\n 71 sh 12.000200 RET   open 3\n");
+        assert_eq!(ktrace.iter().map(|event| event.symbol.as_deref()).collect::<Vec<_>>(), vec![Some("open"), Some("open")]);
+        assert_eq!(ktrace[1].timestamp_us - ktrace[0].timestamp_us, 100);
+        
+        let mut graph = TileGraph::new("kernel".to_string());
+        let mut tile = Tile::new("Root filesystem".to_string(), TileType::Storage, String::new());
+        tile.id = "rootfs".to_string();
+        tile.set_property(TILE_SUBSYSTEMS_PROPERTY.to_string(), "ext4".to_string());
+        tile.set_property(TILE_SYMBOLS_PROPERTY.to_string(), "jbd2_journal_".to_string());
+        graph.add_tile(tile).unwrap();
+        let mut mapper = ComponentMapper::new(None, Some(&graph));
+        mapper.add_prefix("sched", "scheduler");
+        
+        let tables = Arc::new(TablesManager::new());
+        tables.start();
+        let store = TraceStore::new(tables).unwrap();
+        let report = TestReport {
+            results: vec![TestResult {
+                suite: "smoke".to_string(),
+                name: "ls".to_string(),
+                outcome: TestOutcome::Passed,
+                duration_secs: 1.0,
+                console: CONSOLE.to_string(),
+            }],
+        };
+        let events = store.ingest_report("kernel", "b1", &report, &mapper).unwrap();
+        assert_eq!(events.len(), 5);
+        assert_eq!(events[0].pid, 71);
+        assert_eq!(events[2].event, "sched_switch");
+        assert_eq!(events[2].comm, "<idle>");
+        assert_eq!(events[4].symbol.as_deref(), Some("ext4_readdir"));
+        let components: Vec<Option<&str>> = events.iter().map(|event| event.component.as_deref()).collect();
+        assert_eq!(components, vec![Some("rootfs"), Some("rootfs"), Some("scheduler"), None, Some("rootfs")]);
+        assert_eq!(store.events("b1/smoke/ls/ftrace").unwrap(), events[..4].to_vec());
+        assert_eq!(store.component_events("kernel", "rootfs").unwrap().len(), 3);
+        
+        let timeline = TraceTimeline::from_events(&events[..4]);
+        assert_eq!((timeline.start_us, timeline.end_us), (10_000_100, 10_000_400));
+        assert_eq!(timeline.lanes.iter().map(|lane| lane.component.as_str()).collect::<Vec<_>>(), vec!["rootfs", "scheduler", UNMAPPED_LANE]);
+        assert_eq!(timeline.lane("rootfs").unwrap().busy_us(), 300);
+        assert_eq!(timeline.lane_bars(6), vec![
+            "rootfs     |######| 300 us",
+            "scheduler  | #    | 0 us",
+            "(unmapped) |     #| 0 us",
+        ]);
+        assert_eq!(TraceTimeline::by_run(&events).iter().map(|(run_id, _)| run_id.as_str()).collect::<Vec<_>>(), vec!["b1/smoke/ls/ftrace", "b1/smoke/ls/perf"]);
+        
+        // Stored runs are not ingested twice; repeated sections of a format get their own run
+        assert!(store.ingest_report("kernel", "b1", &report, &mapper).unwrap_err().contains("already stored"));
+        assert_eq!(store.events("b1/smoke/ls/ftrace").unwrap().len(), 4);
+        let twice = TestReport { results: vec![TestResult { console: format!("{}{}", CONSOLE, CONSOLE), ..report.results[0].clone() }] };
+        store.ingest_report("kernel", "b2", &twice, &mapper).unwrap();
+        assert_eq!(store.events("b2/smoke/ls/ftrace-2").unwrap().len(), 4);
+    }
+}
@@ -2,6 +2,7 @@
 // Copyright (c) 2025 OSland Project Team
 // SPDX-License-Identifier: MulanPSL-2.0
 
+use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, Mutex};
 
 use crate::kernel_visualization::kernel_visualizer::KernelStructureVisualizer;
@@ -10,6 +11,10 @@ use crate::kernel_visualization::interactive_canvas::{InteractiveCanvasWidget, C
 use crate::kernel_visualization::layout_algorithm::{LayoutAlgorithm, HierarchicalLayout, ForceDirectedLayout, RadialLayout};
 use crate::kernel_visualization::architecture_viewer::{ArchitectureViewer, ArchitectureViewConfig, Architecture};
 use crate::kernel_visualization::dependency_analyzer::{EnhancedDependencyAnalyzer, EnhancedDependencyAnalysis};
+use crate::kernel_visualization::boot_log::FailureCorrelation;
+use crate::kernel_visualization::graph_filter::GraphFilter;
+use crate::kernel_visualization::timeline::{ArchitectureTimeline, TimelineFrame};
+use crate::kernel_visualization::privilege_layout::{boundary_crossings, BoundaryCrossing, PrivilegeLayout};
 
 /// Visualization controller for kernel structure
 pub struct KernelVisualizationController {
@@ -27,6 +32,22 @@ pub struct KernelVisualizationController {
     architecture_config: ArchitectureViewConfig,
     /// Enhanced dependency analysis results
     dependency_analysis: Option<EnhancedDependencyAnalysis>,
+    /// Boot failures correlated with the shown components
+    boot_failures: Vec<FailureCorrelation>,
+    /// Active graph filter and the structure it was applied to
+    active_filter: Option<(GraphFilter, KernelStructure)>,
+    /// Structures of successive kernel versions for playback
+    timeline: Option<ArchitectureTimeline>,
+    /// Canvas contents from before timeline playback started
+    timeline_snapshot: Option<CanvasSnapshot>,
+}
+
+/// Canvas contents replaced while the timeline is shown
+struct CanvasSnapshot {
+    kernel_structure: KernelStructure,
+    component_positions: HashMap<String, (f32, f32)>,
+    selected_components: HashSet<String>,
+    failing_components: HashSet<String>,
 }
 
 impl KernelVisualizationController {
@@ -46,6 +67,10 @@ impl KernelVisualizationController {
             settings,
             architecture_config,
             dependency_analysis: None,
+            boot_failures: Vec::new(),
+            active_filter: None,
+            timeline: None,
+            timeline_snapshot: None,
         }
     }
     
@@ -126,6 +151,7 @@ impl KernelVisualizationController {
                 "hierarchical" => Box::new(HierarchicalLayout::default()) as Box<dyn LayoutAlgorithm>,
                 "force_directed" => Box::new(ForceDirectedLayout::default()) as Box<dyn LayoutAlgorithm>,
                 "radial" => Box::new(RadialLayout::default()) as Box<dyn LayoutAlgorithm>,
+                "privilege" => Box::new(PrivilegeLayout::default()) as Box<dyn LayoutAlgorithm>,
                 _ => return Err(format!("Unknown layout type: {}", layout_type).into()),
             };
             
@@ -239,6 +265,162 @@ impl KernelVisualizationController {
         }
     }
     
+    /// Load extraction results of successive kernel versions, oldest first, for playback
+    pub fn load_timeline(&mut self, versions: Vec<KernelStructure>) -> Result<(), Box<dyn std::error::Error>> {
+        self.stop_timeline();
+        let layout: Box<dyn LayoutAlgorithm> = match self.settings.layout_algorithm.as_str() {
+            "force_directed" => Box::new(ForceDirectedLayout::default()),
+            "radial" => Box::new(RadialLayout::default()),
+            "privilege" => Box::new(PrivilegeLayout::default()),
+            _ => Box::new(HierarchicalLayout::default()),
+        };
+        self.timeline = Some(ArchitectureTimeline::new(versions, layout.as_ref())?);
+        Ok(())
+    }
+    
+    /// Get the loaded timeline
+    pub fn get_timeline(&self) -> Option<&ArchitectureTimeline> {
+        self.timeline.as_ref()
+    }
+    
+    /// Show the timeline frame at a playback position on the canvas
+    pub fn seek_timeline(&mut self, position: f32) -> Option<TimelineFrame> {
+        let timeline = self.timeline.as_ref()?;
+        let frame = timeline.frame(position);
+        if let Some(canvas) = &mut self.interactive_canvas {
+            let mut state = canvas.state.lock().unwrap();
+            if self.timeline_snapshot.is_none() {
+                self.timeline_snapshot = Some(CanvasSnapshot {
+                    kernel_structure: state.kernel_structure.clone(),
+                    component_positions: state.component_positions.clone(),
+                    selected_components: state.selected_components.clone(),
+                    failing_components: state.failing_components.clone(),
+                });
+            }
+            
+            // The canvas shows exactly the frame, so nothing from another version lingers
+            state.kernel_structure = timeline.structure_at(position);
+            state.component_positions = frame.nodes.iter().map(|node| (node.name.clone(), node.position)).collect();
+            let shown: HashSet<String> = state.component_positions.keys().cloned().collect();
+            state.selected_components.retain(|name| shown.contains(name));
+            state.failing_components.retain(|name| shown.contains(name));
+            if state.hovered_component.as_ref().is_some_and(|name| !shown.contains(name)) {
+                state.hovered_component = None;
+            }
+            state.is_dragging = false;
+            state.dragged_component = None;
+        }
+        Some(frame)
+    }
+    
+    /// Leave timeline playback, restoring the canvas as it was before the first seek
+    pub fn stop_timeline(&mut self) {
+        if let (Some(snapshot), Some(canvas)) = (self.timeline_snapshot.take(), &mut self.interactive_canvas) {
+            let mut state = canvas.state.lock().unwrap();
+            state.kernel_structure = snapshot.kernel_structure;
+            state.component_positions = snapshot.component_positions;
+            state.selected_components = snapshot.selected_components;
+            state.failing_components = snapshot.failing_components;
+            state.hovered_component = None;
+            state.is_dragging = false;
+            state.dragged_component = None;
+        }
+    }
+    
+    /// Narrow the canvas to the components matching a filter query
+    pub fn apply_filter(&mut self, query: &str) -> Result<usize, Box<dyn std::error::Error>> {
+        let filter = GraphFilter::parse(query)?;
+        let canvas = self.interactive_canvas.as_mut().ok_or("No canvas to filter")?;
+        let mut state = canvas.state.lock().unwrap();
+        
+        // Filters always apply to the full structure, not to the result of the previous one
+        let full = match self.active_filter.take() {
+            Some((_, full)) => full,
+            None => state.kernel_structure.clone(),
+        };
+        state.kernel_structure = filter.apply(&full);
+        let count = state.kernel_structure.components.len();
+        self.active_filter = Some((filter, full));
+        Ok(count)
+    }
+    
+    /// Remove the active filter, showing the full structure again
+    pub fn clear_filter(&mut self) {
+        if let (Some((_, full)), Some(canvas)) = (self.active_filter.take(), &mut self.interactive_canvas) {
+            canvas.state.lock().unwrap().kernel_structure = full;
+        }
+    }
+    
+    /// Get the active filter
+    pub fn get_active_filter(&self) -> Option<&GraphFilter> {
+        self.active_filter.as_ref().map(|(filter, _)| filter)
+    }
+    
+    /// Highlight the dependencies that reach into a more privileged layer
+    pub fn highlight_privilege_boundaries(&mut self) -> Vec<BoundaryCrossing> {
+        let canvas = match &mut self.interactive_canvas {
+            Some(canvas) => canvas,
+            None => return Vec::new(),
+        };
+        let mut state = canvas.state.lock().unwrap();
+        let attack_surface: Vec<BoundaryCrossing> = boundary_crossings(&state.kernel_structure)
+            .into_iter()
+            .filter(BoundaryCrossing::is_attack_surface)
+            .collect();
+        for dependency in &mut state.kernel_structure.dependencies {
+            dependency.is_selected = attack_surface.iter()
+                .any(|crossing| crossing.from_module == dependency.from_module && crossing.to_module == dependency.to_module);
+        }
+        attack_surface
+    }
+    
+    /// Show the structure with the pending what-if changes on the canvas
+    pub fn preview_what_if(&mut self) {
+        let preview = match self.architecture_viewer.what_if_session() {
+            Some(session) => session.preview().clone(),
+            None => return,
+        };
+        if let Some(canvas) = &mut self.interactive_canvas {
+            let mut state = canvas.state.lock().unwrap();
+            state.kernel_structure = preview;
+        }
+    }
+    
+    /// Apply the pending what-if changes to the displayed kernel structure
+    pub fn apply_what_if_changes(&mut self) -> Option<VisualizationEvent> {
+        let structure = self.architecture_viewer.apply_what_if()?;
+        if let Some(canvas) = &mut self.interactive_canvas {
+            let mut state = canvas.state.lock().unwrap();
+            state.kernel_structure = structure.clone();
+        }
+        Some(VisualizationEvent::WhatIfApplied(structure))
+    }
+    
+    /// Highlight the components that boot failures were correlated with
+    pub fn show_boot_failures(&mut self, correlations: Vec<FailureCorrelation>) -> VisualizationEvent {
+        if let Some(canvas) = &mut self.interactive_canvas {
+            let mut state = canvas.state.lock().unwrap();
+            state.selected_components.clear();
+            state.failing_components = correlations.iter()
+                .flat_map(|correlation| correlation.components.iter().cloned())
+                .collect();
+            for name in correlations.iter().flat_map(|correlation| &correlation.components) {
+                state.select_component(name, true);
+            }
+        }
+        
+        let count = correlations.len();
+        self.boot_failures = correlations;
+        VisualizationEvent::BootFailuresCorrelated(count)
+    }
+    
+    /// Boot failures correlated with a component
+    pub fn get_boot_failures(&self, component_name: &str) -> Vec<&FailureCorrelation> {
+        self.boot_failures.iter()
+            .filter(|correlation| correlation.components.iter().any(|name| name == component_name))
+            .collect()
+    }
+    
     /// Update canvas dependency highlighting based on analysis
     fn update_canvas_dependency_highlighting(&mut self) {
         // This would update the canvas to show highlighted dependencies
@@ -306,6 +488,10 @@ pub enum VisualizationEvent {
     ArchitectureViewApplied(Architecture),
     /// Cycles detected
     CyclesDetected(usize),
+    /// Boot failures correlated with components
+    BootFailuresCorrelated(usize),
+    /// What-if changes applied
+    WhatIfApplied(KernelStructure),
 }
 
 /// Visualization event handler trait
@@ -0,0 +1,270 @@
+// What-if editing of kernel structures for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! Hypothetical component removals and replacements.
+//!
+//! A what-if session works on a copy of the kernel structure: every change is
+//! recorded, the impact on dependencies is recomputed against the original structure,
+//! and nothing reaches the project until the changes are applied.
+
+use std::collections::HashSet;
+use serde::{Deserialize, Serialize};
+use crate::kernel_visualization::visualization_data::{KernelComponentInfo, KernelStructure, ModuleDependency};
+
+/// Dependency type of the edges declared by a replacement component
+const REPLACEMENT_DEPENDENCY_TYPE: &str = "replacement";
+
+/// Hypothetical change to a kernel structure
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum WhatIfChange {
+    /// Remove a component
+    Remove(String),
+    
+    /// Replace a component; edges to it are redirected to the replacement and the
+    /// replacement's own dependencies replace the outgoing edges
+    Replace { component: String, replacement: KernelComponentInfo },
+}
+
+/// Effect of the changes compared with the original structure
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WhatIfImpact {
+    /// Dependencies whose target no longer exists
+    pub broken_edges: Vec<(String, String)>,
+    
+    /// Components that were depended on before and no longer are
+    pub orphaned_components: Vec<String>,
+    
+    /// Removed or replaced components
+    pub removed_components: Vec<String>,
+    
+    /// Components added as replacements
+    pub added_components: Vec<String>,
+    
+    /// Change of the total component size in bytes
+    pub size_delta: i64,
+}
+
+impl WhatIfImpact {
+    /// Whether the changes leave every dependency resolved
+    pub fn is_safe(&self) -> bool {
+        self.broken_edges.is_empty()
+    }
+}
+
+/// Set of hypothetical changes on top of a kernel structure
+#[derive(Debug, Clone)]
+pub struct WhatIfSession {
+    /// Structure the session started from
+    base: KernelStructure,
+    
+    /// Structure with the changes applied
+    preview: KernelStructure,
+    
+    /// Changes in the order they were made
+    changes: Vec<WhatIfChange>,
+}
+
+impl WhatIfSession {
+    /// Start a session on a copy of a structure
+    pub fn new(base: KernelStructure) -> Self {
+        Self { preview: base.clone(), base, changes: Vec::new() }
+    }
+    
+    /// Hypothetically remove a component
+    pub fn remove(&mut self, component: &str) -> Result<WhatIfImpact, String> {
+        self.push(WhatIfChange::Remove(component.to_string()))
+    }
+    
+    /// Hypothetically replace a component
+    pub fn replace(&mut self, component: &str, replacement: KernelComponentInfo) -> Result<WhatIfImpact, String> {
+        self.push(WhatIfChange::Replace { component: component.to_string(), replacement })
+    }
+    
+    /// Revert the last change
+    pub fn undo(&mut self) -> Option<WhatIfChange> {
+        let change = self.changes.pop()?;
+        let mut preview = self.base.clone();
+        for change in &self.changes {
+            // Changes were valid when made, so replaying them cannot fail
+            let _ = apply_change(&mut preview, change);
+        }
+        self.preview = preview;
+        Some(change)
+    }
+    
+    /// Recorded changes
+    pub fn changes(&self) -> &[WhatIfChange] {
+        &self.changes
+    }
+    
+    /// Structure with the changes applied, for display
+    pub fn preview(&self) -> &KernelStructure {
+        &self.preview
+    }
+    
+    /// Impact of all changes so far
+    pub fn impact(&self) -> WhatIfImpact {
+        let base_names: HashSet<&str> = self.base.components.iter().map(|component| component.name.as_str()).collect();
+        let preview_names: HashSet<&str> = self.preview.components.iter().map(|component| component.name.as_str()).collect();
+        
+        let mut broken_edges: Vec<(String, String)> = self.preview.dependencies.iter()
+            .filter(|dependency| !preview_names.contains(dependency.to_module.as_str()))
+            .map(|dependency| (dependency.from_module.clone(), dependency.to_module.clone()))
+            .collect();
+        broken_edges.sort();
+        broken_edges.dedup();
+        
+        let used = |structure: &KernelStructure| -> HashSet<String> {
+            structure.dependencies.iter().map(|dependency| dependency.to_module.clone()).collect()
+        };
+        let (used_before, used_after) = (used(&self.base), used(&self.preview));
+        let mut orphaned_components: Vec<String> = self.preview.components.iter()
+            .map(|component| component.name.clone())
+            .filter(|name| used_before.contains(name) && !used_after.contains(name))
+            .collect();
+        orphaned_components.sort();
+        
+        let mut removed_components: Vec<String> = base_names.difference(&preview_names).map(|name| name.to_string()).collect();
+        removed_components.sort();
+        let mut added_components: Vec<String> = preview_names.difference(&base_names).map(|name| name.to_string()).collect();
+        added_components.sort();
+        
+        let size = |structure: &KernelStructure| structure.components.iter().filter_map(|component| component.size).sum::<u64>() as i64;
+        WhatIfImpact {
+            broken_edges,
+            orphaned_components,
+            removed_components,
+            added_components,
+            size_delta: size(&self.preview) - size(&self.base),
+        }
+    }
+    
+    /// Finish the session, returning the structure with the changes applied
+    pub fn apply(self) -> KernelStructure {
+        let mut structure = self.preview;
+        let names: HashSet<String> = structure.components.iter().map(|component| component.name.clone()).collect();
+        structure.dependencies.retain(|dependency| names.contains(&dependency.to_module));
+        for component in &mut structure.components {
+            component.dependency_count = structure.dependencies.iter().filter(|dependency| dependency.from_module == component.name).count();
+            component.dependent_count = structure.dependencies.iter().filter(|dependency| dependency.to_module == component.name).count();
+        }
+        structure
+    }
+    
+    fn push(&mut self, change: WhatIfChange) -> Result<WhatIfImpact, String> {
+        apply_change(&mut self.preview, &change)?;
+        self.changes.push(change);
+        Ok(self.impact())
+    }
+}
+
+/// Apply a change to a structure, keeping edges to missing components so they can be reported
+fn apply_change(structure: &mut KernelStructure, change: &WhatIfChange) -> Result<(), String> {
+    match change {
+        WhatIfChange::Remove(name) => {
+            let index = position(structure, name)?;
+            structure.components.remove(index);
+            structure.dependencies.retain(|dependency| dependency.from_module != *name);
+        }
+        WhatIfChange::Replace { component, replacement } => {
+            let index = position(structure, component)?;
+            if replacement.name != *component && structure.components.iter().any(|existing| existing.name == replacement.name) {
+                return Err(format!("Component '{}' already exists", replacement.name));
+            }
+            structure.components[index] = replacement.clone();
+            
+            structure.dependencies.retain(|dependency| dependency.from_module != *component);
+            for dependency in &mut structure.dependencies {
+                if dependency.to_module == *component {
+                    dependency.to_module = replacement.name.clone();
+                }
+            }
+            for target in &replacement.original.dependencies {
+                structure.dependencies.push(ModuleDependency {
+                    from_module: replacement.name.clone(),
+                    to_module: target.clone(),
+                    dependency_type: REPLACEMENT_DEPENDENCY_TYPE.to_string(),
+                    count: 1,
+                    is_selected: false,
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+fn position(structure: &KernelStructure, name: &str) -> Result<usize, String> {
+    structure.components.iter().position(|component| component.name == name)
+        .ok_or_else(|| format!("Component '{}' not found", name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::architecture::KernelArchitecture;
+    use crate::kernel_extractor::{ComponentType, KernelComponent};
+    
+    fn component(name: &str, size: u64, dependencies: &[&str]) -> KernelComponentInfo {
+        KernelComponentInfo {
+            name: name.to_string(),
+            component_type: ComponentType::Module,
+            description: None,
+            source_files: Vec::new(),
+            header_files: Vec::new(),
+            size: Some(size),
+            function_count: None,
+            struct_count: None,
+            dependency_count: dependencies.len(),
+            dependent_count: 0,
+            position: (0.0, 0.0),
+            color: String::new(),
+            is_selected: false,
+            original: KernelComponent {
+                name: name.to_string(),
+                dependencies: dependencies.iter().map(|dependency| dependency.to_string()).collect(),
+                ..KernelComponent::default()
+            },
+        }
+    }
+    
+    fn dependency(from: &str, to: &str) -> ModuleDependency {
+        ModuleDependency {
+            from_module: from.to_string(),
+            to_module: to.to_string(),
+            dependency_type: "call".to_string(),
+            count: 1,
+            is_selected: false,
+        }
+    }
+    
+    #[test]
+    fn test_what_if_remove_and_replace() {
+        let structure = KernelStructure {
+            name: "kernel".to_string(),
+            architecture: KernelArchitecture::Monolithic,
+            version: "6.6".to_string(),
+            components: vec![component("vfs", 100, &["mm"]), component("ext4", 50, &["vfs", "jbd2"]), component("jbd2", 20, &[]), component("mm", 200, &[])],
+            dependencies: vec![dependency("vfs", "mm"), dependency("ext4", "vfs"), dependency("ext4", "jbd2")],
+            source_dir: Default::default(),
+            analysis_time: String::new(),
+        };
+        let mut session = WhatIfSession::new(structure.clone());
+        
+        let impact = session.remove("ext4").unwrap();
+        assert!(impact.is_safe());
+        assert_eq!(impact.orphaned_components, vec!["jbd2".to_string(), "vfs".to_string()]);
+        assert_eq!(impact.size_delta, -50);
+        
+        session.undo();
+        let impact = session.replace("vfs", component("vfs2", 80, &["mm", "slab"])).unwrap();
+        assert_eq!(impact.broken_edges, vec![("vfs2".to_string(), "slab".to_string())]);
+        assert_eq!((impact.removed_components, impact.added_components), (vec!["vfs".to_string()], vec!["vfs2".to_string()]));
+        assert_eq!(impact.size_delta, -20);
+        assert!(session.preview().dependencies.iter().any(|dependency| dependency.from_module == "ext4" && dependency.to_module == "vfs2"));
+        
+        let applied = session.apply();
+        assert_eq!(applied.dependencies.len(), 3);
+        assert_eq!(structure.components[0].name, "vfs");
+    }
+}
@@ -0,0 +1,62 @@
+// OSland core library
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! The engines behind the OSland IDE, usable without the GPUI application.
+//!
+//! This crate holds the DBOS table store, the tile engine and compiler, the build
+//! engine and the kernel extractor, together with the modules they depend on. It
+//! does not depend on GPUI unless the `gpui` feature is enabled, which adds
+//! conversions between the canvas geometry types in [`core::geometry`] and GPUI's.
+//!
+//! # API stability
+//!
+//! The items re-exported at the crate root are the stable API and follow semver:
+//! breaking changes to them only happen in a new major version (or a new minor
+//! version while the crate is at `0.x`). The public modules are also reachable
+//! because the OSland application is built on them, but their contents may change
+//! in any release.
+//!
+//! ```no_run
+//! use std::collections::HashMap;
+//! use osland_core::{TablesManager, TileCompiler, TileGraph};
+//! use osland_core::core::architecture::KernelArchitecture;
+//!
+//! let tables = TablesManager::new();
+//! tables.start();
+//! let rows = tables.query_rows("components", HashMap::new());
+//!
+//! let compiler = TileCompiler::new(KernelArchitecture::Microkernel, None);
+//! let code = compiler.generate_execution_code(&TileGraph::new("boot".to_string()));
+//! ```
+
+pub mod core;
+pub mod i18n;
+pub mod dbos_integration;
+pub mod agfs_integration;
+pub mod tile_engine;
+pub mod build_engine;
+pub mod kernel_extractor;
+pub mod kernel_visualization;
+pub mod component_manager;
+pub mod architecture_adapter;
+pub mod os_design;
+pub mod ai_assistant;
+pub mod runtime;
+pub mod mcp;
+
+// DBOS tables
+pub use dbos_integration::dbos_core::{
+    ColumnDefinition, ColumnType, IndexDefinition, StorageOptions, StorageStats, TableDefinition, TableRow, TablesManager,
+};
+pub use dbos_integration::dbos_core::{NamespacedTables, SharedTables, DEFAULT_NAMESPACE, NAMESPACE_COLUMN};
+
+// Tile engine
+pub use tile_engine::tile_core::{ConnectionType, PortType, Tile, TileConnection, TileGraph, TilePort, TileType};
+pub use tile_engine::tile_compiler::{CompilationOptions, TargetLanguage, TileCompiler};
+
+// Build engine
+pub use build_engine::{BuildConfig, BuildEngine, BuildEngineError, BuildMode, BuildProgress, BuildState};
+
+// Kernel extractor
+pub use kernel_extractor::{ComponentType, ExtractionConfig, KernelComponent, KernelExtractor};
@@ -0,0 +1,678 @@
+// MCP client for external tool servers in OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! Client for Model Context Protocol tool servers.
+//!
+//! Servers are started as child processes and spoken to with JSON-RPC 2.0 over
+//! their stdin/stdout, one message per line. Which servers are started, and which of
+//! their tools the AI assistant may use, is stored per project in `MCP_SETTINGS_FILE`.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+use std::sync::{Arc, Mutex, RwLock};
+use serde::{Serialize, Deserialize};
+use serde_json::{json, Value};
+use super::MCPServiceError;
+
+/// MCP protocol revision spoken by the client
+pub const MCP_PROTOCOL_VERSION: &str = "2024-11-05";
+
+/// Project-relative path of the MCP settings
+pub const MCP_SETTINGS_FILE: &str = ".osland/mcp.json";
+
+/// Whether a tool call needs the user's approval
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ToolPermission {
+    /// Ask before every call
+    #[default]
+    Ask,
+    /// Call without asking
+    Allow,
+    /// Never call
+    Deny,
+}
+
+/// Per-tool settings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ToolPolicy {
+    /// Offer the tool to the AI assistant
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    
+    /// Approval required for calls
+    #[serde(default)]
+    pub permission: ToolPermission,
+}
+
+impl Default for ToolPolicy {
+    fn default() -> Self {
+        Self { enabled: true, permission: ToolPermission::Ask }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// External MCP server started by the client
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct McpServerConfig {
+    /// Server name, unique within a project
+    pub name: String,
+    
+    /// Executable starting the server
+    pub command: String,
+    
+    /// Command arguments
+    #[serde(default)]
+    pub args: Vec<String>,
+    
+    /// Extra environment variables
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    
+    /// Start the server
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    
+    /// Tool settings by tool name; tools without an entry use `ToolPolicy::default()`
+    #[serde(default)]
+    pub tools: BTreeMap<String, ToolPolicy>,
+}
+
+impl McpServerConfig {
+    /// Create a server configuration
+    pub fn new(name: &str, command: &str, args: &[&str]) -> Self {
+        Self {
+            name: name.to_string(),
+            command: command.to_string(),
+            args: args.iter().map(|arg| arg.to_string()).collect(),
+            env: BTreeMap::new(),
+            enabled: true,
+            tools: BTreeMap::new(),
+        }
+    }
+    
+    /// Reference filesystem server restricted to a directory
+    pub fn filesystem(root: &Path) -> Self {
+        Self::new("filesystem", "npx", &["-y", "@modelcontextprotocol/server-filesystem", &root.display().to_string()])
+    }
+    
+    /// Reference git server for a repository
+    pub fn git(repository: &Path) -> Self {
+        Self::new("git", "uvx", &["mcp-server-git", "--repository", &repository.display().to_string()])
+    }
+    
+    /// Brave web search server (reads `BRAVE_API_KEY` from the environment)
+    pub fn web_search() -> Self {
+        Self::new("web-search", "npx", &["-y", "@modelcontextprotocol/server-brave-search"])
+    }
+    
+    /// Get the settings of a tool
+    pub fn tool_policy(&self, tool: &str) -> ToolPolicy {
+        self.tools.get(tool).copied().unwrap_or_default()
+    }
+}
+
+/// MCP settings of a project
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct McpSettings {
+    /// Configured servers
+    #[serde(default)]
+    pub servers: Vec<McpServerConfig>,
+}
+
+impl McpSettings {
+    /// Load the settings of a project, or empty settings if it has none
+    pub fn load(project_dir: &Path) -> Result<Self, MCPServiceError> {
+        let path = project_dir.join(MCP_SETTINGS_FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&path)
+            .map_err(|e| MCPServiceError::ProtocolError(format!("Failed to read {}: {}", path.display(), e)))?;
+        serde_json::from_str(&content)
+            .map_err(|e| MCPServiceError::ProtocolError(format!("Failed to parse {}: {}", path.display(), e)))
+    }
+    
+    /// Save the settings of a project
+    pub fn save(&self, project_dir: &Path) -> Result<(), MCPServiceError> {
+        let path = project_dir.join(MCP_SETTINGS_FILE);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| MCPServiceError::ProtocolError(format!("Failed to create {}: {}", parent.display(), e)))?;
+        }
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| MCPServiceError::ProtocolError(format!("Failed to serialize MCP settings: {}", e)))?;
+        fs::write(&path, json)
+            .map_err(|e| MCPServiceError::ProtocolError(format!("Failed to write {}: {}", path.display(), e)))
+    }
+    
+    /// Get a server by name
+    pub fn server(&self, name: &str) -> Option<&McpServerConfig> {
+        self.servers.iter().find(|server| server.name == name)
+    }
+    
+    /// Add a server, replacing one with the same name
+    pub fn add_server(&mut self, server: McpServerConfig) {
+        self.servers.retain(|existing| existing.name != server.name);
+        self.servers.push(server);
+    }
+    
+    /// Enable or disable a tool
+    pub fn set_tool_enabled(&mut self, server: &str, tool: &str, enabled: bool) -> Result<(), MCPServiceError> {
+        self.tool_policy_mut(server, tool)?.enabled = enabled;
+        Ok(())
+    }
+    
+    /// Set the approval required for a tool
+    pub fn set_tool_permission(&mut self, server: &str, tool: &str, permission: ToolPermission) -> Result<(), MCPServiceError> {
+        self.tool_policy_mut(server, tool)?.permission = permission;
+        Ok(())
+    }
+    
+    fn tool_policy_mut(&mut self, server: &str, tool: &str) -> Result<&mut ToolPolicy, MCPServiceError> {
+        let server = self.servers.iter_mut().find(|config| config.name == server)
+            .ok_or_else(|| MCPServiceError::ServerError(format!("Unknown MCP server '{}'", server)))?;
+        Ok(server.tools.entry(tool.to_string()).or_default())
+    }
+}
+
+/// Message channel to an MCP server
+pub trait McpTransport: Send {
+    /// Send a JSON-RPC message
+    fn send(&mut self, message: &Value) -> Result<(), MCPServiceError>;
+    
+    /// Receive the next JSON-RPC message
+    fn receive(&mut self) -> Result<Value, MCPServiceError>;
+}
+
+/// Transport to a server child process over newline-delimited JSON on stdin/stdout
+pub struct StdioTransport {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+}
+
+impl StdioTransport {
+    /// Start the server process
+    pub fn spawn(config: &McpServerConfig) -> Result<Self, MCPServiceError> {
+        let mut child = Command::new(&config.command)
+            .args(&config.args)
+            .envs(&config.env)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| MCPServiceError::ServerError(format!("Failed to start MCP server '{}': {}", config.name, e)))?;
+        
+        let stdin = child.stdin.take()
+            .ok_or_else(|| MCPServiceError::ServerError("MCP server stdin is unavailable".to_string()))?;
+        let stdout = child.stdout.take()
+            .ok_or_else(|| MCPServiceError::ServerError("MCP server stdout is unavailable".to_string()))?;
+        Ok(Self { child, stdin, stdout: BufReader::new(stdout) })
+    }
+}
+
+impl McpTransport for StdioTransport {
+    fn send(&mut self, message: &Value) -> Result<(), MCPServiceError> {
+        let line = serde_json::to_string(message)
+            .map_err(|e| MCPServiceError::ProtocolError(format!("Failed to encode message: {}", e)))?;
+        writeln!(self.stdin, "{}", line)
+            .and_then(|_| self.stdin.flush())
+            .map_err(|e| MCPServiceError::ServerError(format!("Failed to write to MCP server: {}", e)))
+    }
+    
+    fn receive(&mut self) -> Result<Value, MCPServiceError> {
+        loop {
+            let mut line = String::new();
+            let read = self.stdout.read_line(&mut line)
+                .map_err(|e| MCPServiceError::ServerError(format!("Failed to read from MCP server: {}", e)))?;
+            if read == 0 {
+                return Err(MCPServiceError::ServerError("MCP server closed the connection".to_string()));
+            }
+            if !line.trim().is_empty() {
+                return serde_json::from_str(&line)
+                    .map_err(|e| MCPServiceError::ProtocolError(format!("Invalid message from MCP server: {}", e)));
+            }
+        }
+    }
+}
+
+impl Drop for StdioTransport {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// Tool offered by an MCP server
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct McpTool {
+    /// Server offering the tool
+    pub server: String,
+    
+    /// Tool name
+    pub name: String,
+    
+    /// Description for the model
+    pub description: String,
+    
+    /// JSON schema of the arguments
+    pub input_schema: Value,
+}
+
+/// Result of a tool call
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct McpToolResult {
+    /// Text content parts (non-text parts are summarized by their type)
+    pub content: Vec<String>,
+    
+    /// The tool reported a failure
+    pub is_error: bool,
+}
+
+/// Connection to one MCP server
+pub struct McpClient {
+    server: String,
+    transport: Box<dyn McpTransport>,
+    next_id: u64,
+    server_info: Value,
+    tools: Vec<McpTool>,
+}
+
+impl McpClient {
+    /// Start a configured server and connect to it
+    pub fn connect(config: &McpServerConfig) -> Result<Self, MCPServiceError> {
+        Self::with_transport(&config.name, Box::new(StdioTransport::spawn(config)?))
+    }
+    
+    /// Connect over an existing transport and list the server's tools
+    pub fn with_transport(server: &str, transport: Box<dyn McpTransport>) -> Result<Self, MCPServiceError> {
+        let mut client = Self {
+            server: server.to_string(),
+            transport,
+            next_id: 1,
+            server_info: Value::Null,
+            tools: Vec::new(),
+        };
+        
+        let result = client.request("initialize", json!({
+            "protocolVersion": MCP_PROTOCOL_VERSION,
+            "capabilities": {},
+            "clientInfo": { "name": "osland", "version": env!("CARGO_PKG_VERSION") },
+        }))?;
+        client.server_info = result.get("serverInfo").cloned().unwrap_or(Value::Null);
+        client.notify("notifications/initialized", json!({}))?;
+        client.refresh_tools()?;
+        Ok(client)
+    }
+    
+    /// Server name
+    pub fn server(&self) -> &str {
+        &self.server
+    }
+    
+    /// Server name and version reported during initialization
+    pub fn server_info(&self) -> &Value {
+        &self.server_info
+    }
+    
+    /// Tools listed by the server
+    pub fn tools(&self) -> &[McpTool] {
+        &self.tools
+    }
+    
+    /// Fetch the tool list again
+    pub fn refresh_tools(&mut self) -> Result<&[McpTool], MCPServiceError> {
+        let mut tools = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let params = match &cursor {
+                Some(cursor) => json!({ "cursor": cursor }),
+                None => json!({}),
+            };
+            let result = self.request("tools/list", params)?;
+            for tool in result.get("tools").and_then(Value::as_array).into_iter().flatten() {
+                let Some(name) = tool.get("name").and_then(Value::as_str) else {
+                    continue;
+                };
+                tools.push(McpTool {
+                    server: self.server.clone(),
+                    name: name.to_string(),
+                    description: tool.get("description").and_then(Value::as_str).unwrap_or_default().to_string(),
+                    input_schema: tool.get("inputSchema").cloned().unwrap_or_else(|| json!({ "type": "object" })),
+                });
+            }
+            
+            cursor = result.get("nextCursor").and_then(Value::as_str).map(str::to_string);
+            if cursor.is_none() {
+                break;
+            }
+        }
+        
+        self.tools = tools;
+        Ok(&self.tools)
+    }
+    
+    /// Call a tool
+    pub fn call_tool(&mut self, name: &str, arguments: Value) -> Result<McpToolResult, MCPServiceError> {
+        let result = self.request("tools/call", json!({ "name": name, "arguments": arguments }))?;
+        let content = result.get("content").and_then(Value::as_array).into_iter().flatten()
+            .map(|part| match part.get("text").and_then(Value::as_str) {
+                Some(text) => text.to_string(),
+                None => format!("[{} content]", part.get("type").and_then(Value::as_str).unwrap_or("unknown")),
+            })
+            .collect();
+        Ok(McpToolResult {
+            content,
+            is_error: result.get("isError").and_then(Value::as_bool).unwrap_or(false),
+        })
+    }
+    
+    /// Send a request and wait for its response, skipping notifications
+    fn request(&mut self, method: &str, params: Value) -> Result<Value, MCPServiceError> {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.transport.send(&json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }))?;
+        
+        loop {
+            let message = self.transport.receive()?;
+            if message.get("id").and_then(Value::as_u64) != Some(id) {
+                continue;
+            }
+            if let Some(error) = message.get("error") {
+                return Err(MCPServiceError::ServerError(format!(
+                    "{} failed on MCP server '{}': {}",
+                    method,
+                    self.server,
+                    error.get("message").and_then(Value::as_str).unwrap_or("unknown error"),
+                )));
+            }
+            return Ok(message.get("result").cloned().unwrap_or(Value::Null));
+        }
+    }
+    
+    fn notify(&mut self, method: &str, params: Value) -> Result<(), MCPServiceError> {
+        self.transport.send(&json!({ "jsonrpc": "2.0", "method": method, "params": params }))
+    }
+}
+
+/// Answer to a permission prompt
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PermissionDecision {
+    /// Allow this call
+    AllowOnce,
+    /// Allow this and future calls of the tool
+    AllowAlways,
+    /// Reject this call
+    Deny,
+    /// Reject this and future calls of the tool
+    DenyAlways,
+}
+
+/// Tool call waiting for approval
+#[derive(Debug)]
+pub struct ToolPermissionRequest<'a> {
+    pub tool: &'a McpTool,
+    pub arguments: &'a Value,
+}
+
+/// Asks the user whether a tool may be called
+pub trait PermissionPrompt {
+    fn request_permission(&self, request: &ToolPermissionRequest) -> PermissionDecision;
+}
+
+/// Connected MCP servers and their tool settings, as used by the AI assistant
+pub struct McpToolHub {
+    settings: RwLock<McpSettings>,
+    clients: Mutex<HashMap<String, Arc<Mutex<McpClient>>>>,
+}
+
+impl McpToolHub {
+    /// Create a hub without connecting to any server
+    pub fn new(settings: McpSettings) -> Self {
+        Self {
+            settings: RwLock::new(settings),
+            clients: Mutex::new(HashMap::new()),
+        }
+    }
+    
+    /// Connect to every enabled server, returning the servers that failed
+    pub fn connect_all(&self) -> Vec<(String, MCPServiceError)> {
+        let servers: Vec<McpServerConfig> = self.settings.read().unwrap().servers.iter()
+            .filter(|server| server.enabled)
+            .cloned()
+            .collect();
+        
+        let mut failures = Vec::new();
+        for server in servers {
+            match McpClient::connect(&server) {
+                Ok(client) => self.add_client(client),
+                Err(e) => failures.push((server.name, e)),
+            }
+        }
+        failures
+    }
+    
+    /// Register a connected client
+    pub fn add_client(&self, client: McpClient) {
+        self.clients.lock().unwrap().insert(client.server().to_string(), Arc::new(Mutex::new(client)));
+    }
+    
+    /// Current settings, including decisions remembered from permission prompts
+    pub fn settings(&self) -> McpSettings {
+        self.settings.read().unwrap().clone()
+    }
+    
+    /// Replace the settings
+    pub fn set_settings(&self, settings: McpSettings) {
+        *self.settings.write().unwrap() = settings;
+    }
+    
+    /// Tools the AI assistant may use, ordered by server and name
+    pub fn available_tools(&self) -> Vec<McpTool> {
+        let settings = self.settings.read().unwrap();
+        let clients: Vec<Arc<Mutex<McpClient>>> = self.clients.lock().unwrap().values().cloned().collect();
+        let mut tools: Vec<McpTool> = clients.iter()
+            .flat_map(|client| client.lock().unwrap().tools().to_vec())
+            .filter(|tool| Self::offered(&settings, tool))
+            .collect();
+        tools.sort_by(|a, b| (&a.server, &a.name).cmp(&(&b.server, &b.name)));
+        tools
+    }
+    
+    /// Call a tool, asking for permission unless the settings already decide
+    ///
+    /// No lock is held while the user is asked, and only the called server's
+    /// connection is locked during the call.
+    pub fn call_tool(
+        &self,
+        server: &str,
+        tool: &str,
+        arguments: Value,
+        prompt: &dyn PermissionPrompt,
+    ) -> Result<McpToolResult, MCPServiceError> {
+        let client = self.clients.lock().unwrap().get(server).cloned()
+            .ok_or_else(|| MCPServiceError::ServerError(format!("MCP server '{}' is not connected", server)))?;
+        let info = client.lock().unwrap().tools().iter().find(|info| info.name == tool).cloned()
+            .ok_or_else(|| MCPServiceError::ServerError(format!("MCP server '{}' has no tool '{}'", server, tool)))?;
+        
+        let policy = {
+            let settings = self.settings.read().unwrap();
+            if !Self::offered(&settings, &info) {
+                return Err(MCPServiceError::PermissionDenied(format!("Tool '{}' of '{}' is disabled", tool, server)));
+            }
+            settings.server(server).map(|config| config.tool_policy(tool)).unwrap_or_default()
+        };
+        
+        if policy.permission == ToolPermission::Ask {
+            let decision = prompt.request_permission(&ToolPermissionRequest { tool: &info, arguments: &arguments });
+            let remembered = match decision {
+                PermissionDecision::AllowAlways => Some(ToolPermission::Allow),
+                PermissionDecision::DenyAlways => Some(ToolPermission::Deny),
+                PermissionDecision::AllowOnce | PermissionDecision::Deny => None,
+            };
+            if let Some(permission) = remembered {
+                // Servers started outside the settings have nowhere to remember the decision
+                let _ = self.settings.write().unwrap().set_tool_permission(server, tool, permission);
+            }
+            if matches!(decision, PermissionDecision::Deny | PermissionDecision::DenyAlways) {
+                return Err(MCPServiceError::PermissionDenied(format!("Call of '{}' on '{}' was rejected", tool, server)));
+            }
+        }
+        
+        let mut client = client.lock().unwrap();
+        client.call_tool(tool, arguments)
+    }
+    
+    /// Check whether a tool is enabled and not denied
+    fn offered(settings: &McpSettings, tool: &McpTool) -> bool {
+        match settings.server(&tool.server) {
+            Some(server) => {
+                let policy = server.tool_policy(&tool.name);
+                server.enabled && policy.enabled && policy.permission != ToolPermission::Deny
+            },
+            None => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+    
+    /// Transport answering requests like a small tool server
+    struct ScriptedServer {
+        sent: Arc<Mutex<Vec<Value>>>,
+        outbox: VecDeque<Value>,
+    }
+    
+    impl McpTransport for ScriptedServer {
+        fn send(&mut self, message: &Value) -> Result<(), MCPServiceError> {
+            self.sent.lock().unwrap().push(message.clone());
+            let Some(id) = message.get("id").cloned() else {
+                return Ok(());
+            };
+            let result = match message["method"].as_str().unwrap() {
+                "initialize" => json!({ "protocolVersion": MCP_PROTOCOL_VERSION, "serverInfo": { "name": "fs", "version": "1" } }),
+                "tools/list" if message["params"].get("cursor").is_none() => json!({
+                    "tools": [{ "name": "read_file", "description": "Read a file", "inputSchema": { "type": "object" } }],
+                    "nextCursor": "page2",
+                }),
+                "tools/list" => json!({ "tools": [{ "name": "write_file", "description": "Write a file" }] }),
+                "tools/call" => json!({
+                    "content": [{ "type": "text", "text": format!("called {}", message["params"]["name"]) }],
+                }),
+                method => {
+                    self.outbox.push_back(json!({ "jsonrpc": "2.0", "id": id, "error": { "code": -32601, "message": method } }));
+                    return Ok(());
+                },
+            };
+            // Servers may interleave notifications with responses
+            self.outbox.push_back(json!({ "jsonrpc": "2.0", "method": "notifications/message", "params": {} }));
+            self.outbox.push_back(json!({ "jsonrpc": "2.0", "id": id, "result": result }));
+            Ok(())
+        }
+        
+        fn receive(&mut self) -> Result<Value, MCPServiceError> {
+            self.outbox.pop_front().ok_or_else(|| MCPServiceError::ServerError("no message".to_string()))
+        }
+    }
+    
+    struct FixedPrompt(PermissionDecision, Mutex<usize>);
+    
+    impl PermissionPrompt for FixedPrompt {
+        fn request_permission(&self, _request: &ToolPermissionRequest) -> PermissionDecision {
+            *self.1.lock().unwrap() += 1;
+            self.0
+        }
+    }
+    
+    /// Prompt that uses the hub while the user is asked
+    struct ReentrantPrompt<'a>(&'a McpToolHub);
+    
+    impl PermissionPrompt for ReentrantPrompt<'_> {
+        fn request_permission(&self, request: &ToolPermissionRequest) -> PermissionDecision {
+            assert!(self.0.available_tools().iter().any(|tool| tool.name == request.tool.name));
+            PermissionDecision::AllowOnce
+        }
+    }
+    
+    fn client() -> (McpClient, Arc<Mutex<Vec<Value>>>) {
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let transport = ScriptedServer { sent: sent.clone(), outbox: VecDeque::new() };
+        (McpClient::with_transport("filesystem", Box::new(transport)).unwrap(), sent)
+    }
+    
+    #[test]
+    fn test_client_handshake_and_tools() {
+        let (mut client, sent) = client();
+        assert_eq!(client.server_info()["name"], "fs");
+        let names: Vec<&str> = client.tools().iter().map(|tool| tool.name.as_str()).collect();
+        assert_eq!(names, vec!["read_file", "write_file"]);
+        
+        let methods: Vec<String> = sent.lock().unwrap().iter().map(|message| message["method"].as_str().unwrap().to_string()).collect();
+        assert_eq!(methods, vec!["initialize", "notifications/initialized", "tools/list", "tools/list"]);
+        
+        let result = client.call_tool("read_file", json!({ "path": "a.txt" })).unwrap();
+        assert_eq!(result.content, vec!["called \"read_file\""]);
+        assert!(!result.is_error);
+        assert!(client.request("resources/list", json!({})).is_err());
+    }
+    
+    #[test]
+    fn test_tool_hub_permissions() {
+        let mut settings = McpSettings::default();
+        settings.add_server(McpServerConfig::filesystem(Path::new("/tmp")));
+        settings.set_tool_enabled("filesystem", "write_file", false).unwrap();
+        
+        let hub = McpToolHub::new(settings);
+        hub.add_client(client().0);
+        let names: Vec<String> = hub.available_tools().into_iter().map(|tool| tool.name).collect();
+        assert_eq!(names, vec!["read_file"]);
+        
+        // Disabled tools cannot be called
+        let allow_always = FixedPrompt(PermissionDecision::AllowAlways, Mutex::new(0));
+        assert!(matches!(
+            hub.call_tool("filesystem", "write_file", json!({}), &allow_always),
+            Err(MCPServiceError::PermissionDenied(_))
+        ));
+        
+        // Remembered approvals stop further prompts
+        hub.call_tool("filesystem", "read_file", json!({}), &allow_always).unwrap();
+        hub.call_tool("filesystem", "read_file", json!({}), &allow_always).unwrap();
+        assert_eq!(*allow_always.1.lock().unwrap(), 1);
+        assert_eq!(hub.settings().server("filesystem").unwrap().tool_policy("read_file").permission, ToolPermission::Allow);
+        
+        // Remembered rejections hide the tool
+        hub.set_settings(McpSettings { servers: vec![McpServerConfig::filesystem(Path::new("/tmp"))] });
+        let deny_always = FixedPrompt(PermissionDecision::DenyAlways, Mutex::new(0));
+        assert!(hub.call_tool("filesystem", "read_file", json!({}), &deny_always).is_err());
+        assert!(hub.available_tools().iter().all(|tool| tool.name != "read_file"));
+        
+        // The hub is not locked while the user is asked
+        hub.set_settings(McpSettings { servers: vec![McpServerConfig::filesystem(Path::new("/tmp"))] });
+        hub.call_tool("filesystem", "read_file", json!({}), &ReentrantPrompt(&hub)).unwrap();
+    }
+    
+    #[test]
+    fn test_settings_round_trip() {
+        let project = tempfile::tempdir().unwrap();
+        assert_eq!(McpSettings::load(project.path()).unwrap(), McpSettings::default());
+        
+        let mut settings = McpSettings::default();
+        settings.add_server(McpServerConfig::git(project.path()));
+        settings.add_server(McpServerConfig::web_search());
+        settings.set_tool_permission("git", "git_commit", ToolPermission::Deny).unwrap();
+        assert!(settings.set_tool_enabled("missing", "tool", false).is_err());
+        settings.save(project.path()).unwrap();
+        
+        assert_eq!(McpSettings::load(project.path()).unwrap(), settings);
+    }
+}
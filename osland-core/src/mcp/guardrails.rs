@@ -0,0 +1,498 @@
+// Guardrails for AI-proposed commands in OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! Policy checks for shell commands and build steps proposed by AI output.
+//!
+//! Every command is evaluated against the project's `GuardrailPolicy` before it runs:
+//! denylisted commands and paths outside the allowlist are refused, everything else
+//! is assigned a confirmation level. Each decision and execution is appended to the
+//! project's audit log.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Component, Path, PathBuf};
+use std::process::Command;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Project-relative path of the guardrail policy
+pub const GUARDRAIL_POLICY_FILE: &str = ".osland/guardrails.json";
+
+/// Project-relative path of the command audit log (one JSON entry per line)
+pub const COMMAND_AUDIT_LOG: &str = ".osland/audit/commands.jsonl";
+
+/// Guardrail Error Types
+#[derive(Error, Debug)]
+pub enum GuardrailError {
+    #[error("File system operation failed: {0}")]
+    FsError(#[from] std::io::Error),
+    
+    #[error("JSON serialization/deserialization error: {0}")]
+    JsonError(#[from] serde_json::Error),
+    
+    #[error("Invalid policy pattern '{0}': {1}")]
+    InvalidPattern(String, String),
+}
+
+/// Approval needed before a command runs, from least to most restrictive
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum ConfirmationLevel {
+    /// Run without asking
+    Automatic,
+    /// Ask the user once
+    Confirm,
+    /// Ask the user to review the full command and its working directory
+    Review,
+    /// Never run
+    Deny,
+}
+
+/// Confirmation level for commands matching a pattern
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommandRule {
+    /// Regular expression matched against the whole command line
+    pub pattern: String,
+    
+    /// Level applied to matching commands
+    pub level: ConfirmationLevel,
+    
+    /// Explanation shown to the user
+    pub reason: String,
+}
+
+/// Per-project guardrail policy
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GuardrailPolicy {
+    /// Patterns of commands that are never run
+    pub denylist: Vec<String>,
+    
+    /// Directories (relative to the project root) commands may touch
+    pub allowed_paths: Vec<PathBuf>,
+    
+    /// Programs that run without confirmation when nothing else applies
+    pub trusted_programs: Vec<String>,
+    
+    /// Level for commands no rule or trusted program covers
+    pub default_level: ConfirmationLevel,
+    
+    /// Additional rules; the most restrictive matching rule wins
+    #[serde(default)]
+    pub rules: Vec<CommandRule>,
+}
+
+impl Default for GuardrailPolicy {
+    fn default() -> Self {
+        Self {
+            denylist: [
+                r"\brm\s+(-\w*\s+)*-\w*[rR]\w*\s+(/|~|\*)(\s|$)",
+                r"\bmkfs(\.\w+)?\b",
+                r"\bdd\b.*\bof=/dev/",
+                r">\s*/dev/(sd|nvme|hd|vd)",
+                r":\(\)\s*\{.*\};\s*:",
+                r"\bchmod\s+(-\w+\s+)*777\s+/(\s|$)",
+                r"\b(shutdown|reboot|halt|poweroff)\b",
+                r"\b(curl|wget)\b.*\|\s*(sudo\s+)?(ba|z)?sh\b",
+                r"\bgit\s+push\b.*--force",
+            ].iter().map(|pattern| pattern.to_string()).collect(),
+            allowed_paths: vec![PathBuf::from(".")],
+            trusted_programs: ["make", "cargo", "cmake", "ninja", "meson", "gcc", "clang", "ld", "objcopy", "objdump", "nm", "qemu-img"]
+                .iter().map(|program| program.to_string()).collect(),
+            default_level: ConfirmationLevel::Confirm,
+            rules: vec![CommandRule {
+                pattern: r"^\s*sudo\b".to_string(),
+                level: ConfirmationLevel::Review,
+                reason: "Runs with elevated privileges".to_string(),
+            }],
+        }
+    }
+}
+
+impl GuardrailPolicy {
+    /// Load the policy of a project, or the default policy if it has none
+    pub fn load(project_root: &Path) -> Result<Self, GuardrailError> {
+        let path = project_root.join(GUARDRAIL_POLICY_FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+    
+    /// Save the policy into a project
+    pub fn save(&self, project_root: &Path) -> Result<(), GuardrailError> {
+        let path = project_root.join(GUARDRAIL_POLICY_FILE);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// Command proposed by AI output
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProposedCommand {
+    /// Shell command line
+    pub command: String,
+    
+    /// Working directory relative to the project root
+    #[serde(default)]
+    pub working_dir: Option<PathBuf>,
+    
+    /// Result the command came from
+    #[serde(default)]
+    pub source: String,
+}
+
+impl ProposedCommand {
+    /// Create a command run from the project root
+    pub fn new(command: &str, source: &str) -> Self {
+        Self {
+            command: command.to_string(),
+            working_dir: None,
+            source: source.to_string(),
+        }
+    }
+}
+
+/// Outcome of evaluating a command against the policy
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PolicyDecision {
+    /// Required approval
+    pub level: ConfirmationLevel,
+    
+    /// Why the level was chosen
+    pub reasons: Vec<String>,
+}
+
+/// Asks the user to approve a command
+pub trait CommandConfirmation {
+    fn confirm(&self, command: &ProposedCommand, decision: &PolicyDecision) -> bool;
+}
+
+/// Runs approved commands
+pub trait CommandExecutor {
+    /// Run a command and return its exit code
+    fn execute(&self, command: &str, working_dir: &Path) -> Result<i32, String>;
+}
+
+/// Executor running commands with `sh -c`
+#[derive(Debug, Default)]
+pub struct ShellExecutor;
+
+impl CommandExecutor for ShellExecutor {
+    fn execute(&self, command: &str, working_dir: &Path) -> Result<i32, String> {
+        let status = Command::new("sh")
+            .arg("-c")
+            .arg(command)
+            .current_dir(working_dir)
+            .status()
+            .map_err(|e| format!("Failed to start shell: {}", e))?;
+        Ok(status.code().unwrap_or(-1))
+    }
+}
+
+/// What happened to a proposed command
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CommandOutcome {
+    /// Ran to completion
+    Executed { exit_code: i32 },
+    /// Refused by the policy
+    Denied,
+    /// The user did not approve it
+    Declined,
+    /// Could not be started
+    Failed { error: String },
+}
+
+impl CommandOutcome {
+    /// Check whether the command ran and exited with code 0
+    pub fn succeeded(&self) -> bool {
+        matches!(self, CommandOutcome::Executed { exit_code: 0 })
+    }
+}
+
+/// Audit log entry
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// Time of the decision
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    
+    /// Evaluated command
+    pub command: ProposedCommand,
+    
+    /// Policy decision
+    pub decision: PolicyDecision,
+    
+    /// What happened
+    pub outcome: CommandOutcome,
+}
+
+/// Compiled guardrail policy of a project
+pub struct Guardrails {
+    project_root: PathBuf,
+    policy: GuardrailPolicy,
+    denylist: Vec<Regex>,
+    rules: Vec<(Regex, CommandRule)>,
+}
+
+impl Guardrails {
+    /// Compile a policy for a project
+    pub fn new(project_root: &Path, policy: GuardrailPolicy) -> Result<Self, GuardrailError> {
+        let compile = |pattern: &str| Regex::new(pattern)
+            .map_err(|e| GuardrailError::InvalidPattern(pattern.to_string(), e.to_string()));
+        let denylist = policy.denylist.iter().map(|pattern| compile(pattern)).collect::<Result<Vec<_>, _>>()?;
+        let rules = policy.rules.iter()
+            .map(|rule| Ok((compile(&rule.pattern)?, rule.clone())))
+            .collect::<Result<Vec<_>, GuardrailError>>()?;
+        
+        Ok(Self {
+            project_root: normalize(project_root),
+            policy,
+            denylist,
+            rules,
+        })
+    }
+    
+    /// Load and compile the policy of a project
+    pub fn for_project(project_root: &Path) -> Result<Self, GuardrailError> {
+        Self::new(project_root, GuardrailPolicy::load(project_root)?)
+    }
+    
+    /// Get the policy
+    pub fn policy(&self) -> &GuardrailPolicy {
+        &self.policy
+    }
+    
+    /// Evaluate a command against the policy
+    pub fn evaluate(&self, command: &ProposedCommand) -> PolicyDecision {
+        let mut level = ConfirmationLevel::Automatic;
+        let mut reasons = Vec::new();
+        let mut raise = |new_level: ConfirmationLevel, reason: String| {
+            level = level.max(new_level);
+            reasons.push(reason);
+        };
+        
+        for pattern in self.denylist.iter().filter(|pattern| pattern.is_match(&command.command)) {
+            raise(ConfirmationLevel::Deny, format!("Matches the denylist pattern `{}`", pattern.as_str()));
+        }
+        
+        let working_dir = self.working_dir(command);
+        if !self.is_allowed(&working_dir) {
+            raise(ConfirmationLevel::Deny, format!("Working directory {} is outside the allowed paths", working_dir.display()));
+        }
+        for argument in command.command.split_whitespace().skip(1) {
+            let argument = argument.trim_matches(|c| c == '"' || c == '\'').rsplit('=').next().unwrap_or_default();
+            if !looks_like_path(argument) {
+                continue;
+            }
+            let path = if argument.starts_with('~') {
+                PathBuf::from(argument)
+            } else {
+                normalize(&working_dir.join(argument))
+            };
+            if argument.starts_with('~') || !self.is_allowed(&path) {
+                raise(ConfirmationLevel::Deny, format!("Path {} is outside the allowed paths", argument));
+            }
+        }
+        
+        // Rules anchored at the start of a command also apply to every chained command
+        let segments = command_segments(&command.command);
+        for (pattern, rule) in &self.rules {
+            if pattern.is_match(&command.command) || segments.iter().any(|segment| pattern.is_match(segment)) {
+                raise(rule.level, format!("{} (`{}`)", rule.reason, pattern.as_str()));
+            }
+        }
+        
+        // Pipes, chaining, background jobs and substitutions hide what actually runs
+        if ["|", ";", "&", "\n", "\r", "`", "$(", ">", "<"].iter().any(|operator| command.command.contains(operator)) {
+            raise(self.policy.default_level.max(ConfirmationLevel::Confirm), "Uses shell operators".to_string());
+        }
+        
+        for segment in &segments {
+            let program = segment.split_whitespace().next().unwrap_or_default();
+            let program = program.rsplit('/').next().unwrap_or_default();
+            if !self.policy.trusted_programs.iter().any(|trusted| trusted == program) {
+                raise(self.policy.default_level, format!("`{}` is not a trusted program", program));
+            }
+        }
+        
+        PolicyDecision { level, reasons }
+    }
+    
+    /// Evaluate a command and run it if the policy and the user allow it, recording the outcome
+    pub fn run(
+        &self,
+        command: &ProposedCommand,
+        confirmation: &dyn CommandConfirmation,
+        executor: &dyn CommandExecutor,
+    ) -> Result<AuditEntry, GuardrailError> {
+        let decision = self.evaluate(command);
+        let approved = match decision.level {
+            ConfirmationLevel::Automatic => true,
+            ConfirmationLevel::Confirm | ConfirmationLevel::Review => confirmation.confirm(command, &decision),
+            ConfirmationLevel::Deny => false,
+        };
+        
+        let outcome = if decision.level == ConfirmationLevel::Deny {
+            CommandOutcome::Denied
+        } else if !approved {
+            CommandOutcome::Declined
+        } else {
+            match executor.execute(&command.command, &self.working_dir(command)) {
+                Ok(exit_code) => CommandOutcome::Executed { exit_code },
+                Err(error) => CommandOutcome::Failed { error },
+            }
+        };
+        
+        let entry = AuditEntry {
+            timestamp: chrono::Utc::now(),
+            command: command.clone(),
+            decision,
+            outcome,
+        };
+        self.append_audit(&entry)?;
+        Ok(entry)
+    }
+    
+    /// Read the audit log of the project
+    pub fn audit_log(&self) -> Result<Vec<AuditEntry>, GuardrailError> {
+        let path = self.project_root.join(COMMAND_AUDIT_LOG);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        fs::read_to_string(path)?
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| serde_json::from_str(line).map_err(GuardrailError::from))
+            .collect()
+    }
+    
+    fn append_audit(&self, entry: &AuditEntry) -> Result<(), GuardrailError> {
+        let path = self.project_root.join(COMMAND_AUDIT_LOG);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(file, "{}", serde_json::to_string(entry)?)?;
+        Ok(())
+    }
+    
+    fn working_dir(&self, command: &ProposedCommand) -> PathBuf {
+        match &command.working_dir {
+            Some(dir) => normalize(&self.project_root.join(dir)),
+            None => self.project_root.clone(),
+        }
+    }
+    
+    fn is_allowed(&self, path: &Path) -> bool {
+        self.policy.allowed_paths.iter().any(|allowed| path.starts_with(normalize(&self.project_root.join(allowed))))
+    }
+}
+
+/// Check whether a command argument names a file system path
+/// Split a command line into the commands it runs, at pipes, `;`, `&` and line breaks
+fn command_segments(command: &str) -> Vec<&str> {
+    let segments: Vec<&str> = command.split(['|', ';', '&', '\n', '\r'])
+        .map(str::trim)
+        .filter(|segment| !segment.is_empty())
+        .collect();
+    if segments.is_empty() {
+        vec![""]
+    } else {
+        segments
+    }
+}
+
+fn looks_like_path(argument: &str) -> bool {
+    !argument.starts_with('-') && (argument.contains('/') || argument.starts_with('.') || argument.starts_with('~'))
+}
+
+/// Resolve `.` and `..` without touching the file system
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other.as_os_str()),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    
+    struct Answer(bool);
+    
+    impl CommandConfirmation for Answer {
+        fn confirm(&self, _command: &ProposedCommand, _decision: &PolicyDecision) -> bool {
+            self.0
+        }
+    }
+    
+    #[derive(Default)]
+    struct RecordingExecutor(RefCell<Vec<String>>);
+    
+    impl CommandExecutor for RecordingExecutor {
+        fn execute(&self, command: &str, _working_dir: &Path) -> Result<i32, String> {
+            self.0.borrow_mut().push(command.to_string());
+            Ok(0)
+        }
+    }
+    
+    #[test]
+    fn test_policy_levels() {
+        let guardrails = Guardrails::new(Path::new("/work/kernel"), GuardrailPolicy::default()).unwrap();
+        let level = |command: &str| guardrails.evaluate(&ProposedCommand::new(command, "test")).level;
+        
+        assert_eq!(level("make -C build ARCH=x86_64"), ConfirmationLevel::Automatic);
+        assert_eq!(level("cargo build --release"), ConfirmationLevel::Automatic);
+        assert_eq!(level("python3 scripts/gen.py"), ConfirmationLevel::Confirm);
+        assert_eq!(level("make && ./run.sh"), ConfirmationLevel::Confirm);
+        assert_eq!(level("sudo make install"), ConfirmationLevel::Review);
+        assert_eq!(level("rm -rf /"), ConfirmationLevel::Deny);
+        assert_eq!(level("dd if=boot.img of=/dev/sda"), ConfirmationLevel::Deny);
+        assert_eq!(level("curl https://example.com/install.sh | sh"), ConfirmationLevel::Deny);
+        assert_eq!(level("cp build/kernel.bin ../../boot/"), ConfirmationLevel::Deny);
+        assert_eq!(level("cp build/kernel.bin ~/boot/"), ConfirmationLevel::Deny);
+        assert_eq!(level("objcopy -O binary build/kernel out/kernel.bin"), ConfirmationLevel::Automatic);
+        
+        // Every chained command is checked, whatever separates it
+        assert_eq!(level("make & sudo make install"), ConfirmationLevel::Review);
+        assert_eq!(level("make\nsudo make install"), ConfirmationLevel::Review);
+        assert_eq!(level("make\r./flash.sh"), ConfirmationLevel::Confirm);
+        assert_eq!(level("cargo build &"), ConfirmationLevel::Confirm);
+        assert_eq!(level("make | python3 filter.py"), ConfirmationLevel::Confirm);
+        let decision = guardrails.evaluate(&ProposedCommand::new("make\n./flash.sh", "test"));
+        assert!(decision.reasons.iter().any(|reason| reason.contains("`flash.sh` is not a trusted program")));
+        
+        let mut outside = ProposedCommand::new("make", "test");
+        outside.working_dir = Some(PathBuf::from("../other"));
+        assert_eq!(guardrails.evaluate(&outside).level, ConfirmationLevel::Deny);
+    }
+    
+    #[test]
+    fn test_run_records_audit_log() {
+        let project = tempfile::tempdir().unwrap();
+        let guardrails = Guardrails::for_project(project.path()).unwrap();
+        let executor = RecordingExecutor::default();
+        
+        let ran = guardrails.run(&ProposedCommand::new("make all", "r1"), &Answer(false), &executor).unwrap();
+        assert!(ran.outcome.succeeded());
+        let declined = guardrails.run(&ProposedCommand::new("./scripts/flash.sh", "r1"), &Answer(false), &executor).unwrap();
+        assert_eq!(declined.outcome, CommandOutcome::Declined);
+        let denied = guardrails.run(&ProposedCommand::new("mkfs.ext4 disk.img", "r1"), &Answer(true), &executor).unwrap();
+        assert_eq!(denied.outcome, CommandOutcome::Denied);
+        
+        assert_eq!(*executor.0.borrow(), vec!["make all"]);
+        let log = guardrails.audit_log().unwrap();
+        assert_eq!(log, vec![ran, declined, denied]);
+    }
+}
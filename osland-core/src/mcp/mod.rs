@@ -0,0 +1,41 @@
+// MCP (Model Context Protocol) module for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+pub mod protocol;
+pub mod model_manager;
+pub mod context_transfer;
+pub mod result_integrator;
+pub mod client;
+pub mod guardrails;
+
+pub use client::{
+    McpClient, McpServerConfig, McpSettings, McpTool, McpToolHub, McpToolResult,
+    PermissionDecision, PermissionPrompt, ToolPermission, ToolPermissionRequest, ToolPolicy,
+};
+pub use guardrails::{
+    AuditEntry, CommandConfirmation, CommandExecutor, CommandOutcome, CommandRule, ConfirmationLevel,
+    GuardrailError, GuardrailPolicy, Guardrails, PolicyDecision, ProposedCommand, ShellExecutor,
+};
+
+// MCP error types
+#[derive(thiserror::Error, Debug)]
+pub enum MCPServiceError {
+    #[error("Protocol implementation error: {0}")]
+    ProtocolError(String),
+    
+    #[error("Model management error: {0}")]
+    ModelError(String),
+    
+    #[error("Context transfer error: {0}")]
+    ContextError(String),
+    
+    #[error("Result integration error: {0}")]
+    IntegrationError(String),
+    
+    #[error("MCP server error: {0}")]
+    ServerError(String),
+    
+    #[error("Tool permission denied: {0}")]
+    PermissionDenied(String),
+}
@@ -8,6 +8,7 @@ use std::fs::{self, File};
 use std::io::{Read, Write};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
+use super::guardrails::{AuditEntry, CommandConfirmation, CommandExecutor, CommandOutcome, GuardrailError, Guardrails, ProposedCommand};
 
 /// Result Integrator Error Types
 #[derive(Error, Debug)]
@@ -29,6 +30,9 @@ pub enum ResultIntegratorError {
     
     #[error("Integration failed: {0}")]
     IntegrationFailed(String),
+    
+    #[error("Guardrail error: {0}")]
+    GuardrailError(#[from] GuardrailError),
 }
 
 /// Result Data
@@ -287,6 +291,62 @@ impl ResultIntegrator {
         Ok(integrated_result)
     }
     
+    /// Get the shell commands and build steps of a result, in order
+    ///
+    /// Commands are read from the `commands` and `build_steps` arrays of the result data;
+    /// entries are either command lines or objects with `command` and `working_dir`.
+    pub fn proposed_commands(&self, result: &ResultData) -> Result<Vec<ProposedCommand>, ResultIntegratorError> {
+        let mut commands = Vec::new();
+        for key in ["commands", "build_steps"] {
+            let Some(entries) = result.data.get(key) else {
+                continue;
+            };
+            let entries = entries.as_array().ok_or_else(|| ResultIntegratorError::InvalidResultFormat(
+                format!("`{}` of result {} is not an array", key, result.result_id)))?;
+            for entry in entries {
+                let mut command = match entry {
+                    serde_json::Value::String(command) => ProposedCommand::new(command, &result.result_id),
+                    _ => serde_json::from_value::<ProposedCommand>(entry.clone())?,
+                };
+                command.source = result.result_id.clone();
+                commands.push(command);
+            }
+        }
+        Ok(commands)
+    }
+    
+    /// Run the commands of a result through the guardrails
+    ///
+    /// Commands run in order and stop at the first one that is denied, declined or fails,
+    /// since later build steps usually depend on earlier ones. The result's status
+    /// becomes `applied`, `blocked` (denied or declined) or `failed`.
+    pub fn apply_result(
+        &self,
+        result_id: &str,
+        guardrails: &Guardrails,
+        confirmation: &dyn CommandConfirmation,
+        executor: &dyn CommandExecutor,
+    ) -> Result<Vec<AuditEntry>, ResultIntegratorError> {
+        let mut result = self.get_result(result_id)?;
+        let mut entries = Vec::new();
+        for command in self.proposed_commands(&result)? {
+            let entry = guardrails.run(&command, confirmation, executor)?;
+            let succeeded = entry.outcome.succeeded();
+            entries.push(entry);
+            if !succeeded {
+                break;
+            }
+        }
+        
+        result.status = match entries.last().map(|entry| &entry.outcome) {
+            Some(CommandOutcome::Denied) | Some(CommandOutcome::Declined) => "blocked",
+            Some(outcome) if !outcome.succeeded() => "failed",
+            _ => "applied",
+        }.to_string();
+        self.update_result(&result)?;
+        Ok(entries)
+    }
+    
     /// Merge results
     fn merge_results(&self, results: &[ResultData]) -> Result<serde_json::Value, ResultIntegratorError> {
         if results.is_empty() {
@@ -0,0 +1,978 @@
+// Memory allocator designer for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! Kernel memory allocator selection and tuning.
+//!
+//! Buddy, SLAB, SLUB-like and TLSF allocators are offered as components whose node
+//! properties hold the tunables. A recorded allocation trace (a plain `alloc`/`free`
+//! listing or ftrace `kmem` events) is replayed against a model of each allocator to
+//! compare fragmentation, memory overhead and allocator work, and the chosen
+//! configuration is turned into a kernel config fragment or a Rust global allocator.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use crate::component_manager::component::{Component, ComponentCategory, ComponentPort, ComponentProperty, ComponentType, PortDirection};
+use crate::component_manager::ComponentLibrary;
+
+/// Header of every TLSF block in bytes
+const TLSF_BLOCK_HEADER: usize = 16;
+
+/// Management data at the start of a SLAB slab in bytes
+const SLAB_HEADER: usize = 32;
+
+/// Per-object free list index kept by SLAB in bytes
+const SLAB_BUFCTL: usize = 4;
+
+/// Allocator algorithm
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AllocatorKind {
+    /// Binary buddy page allocator
+    Buddy,
+    
+    /// Object caches with in-slab management data, like Linux SLAB
+    Slab,
+    
+    /// Object caches with free pointers inside free objects, like Linux SLUB
+    Slub,
+    
+    /// Two-level segregated fit, with bounded allocation time
+    Tlsf,
+}
+
+impl AllocatorKind {
+    /// All allocator kinds
+    pub fn all() -> [AllocatorKind; 4] {
+        [AllocatorKind::Buddy, AllocatorKind::Slab, AllocatorKind::Slub, AllocatorKind::Tlsf]
+    }
+    
+    /// Component ID of the allocator kind
+    pub fn component_id(&self) -> &'static str {
+        match self {
+            AllocatorKind::Buddy => "allocator_buddy",
+            AllocatorKind::Slab => "allocator_slab",
+            AllocatorKind::Slub => "allocator_slub",
+            AllocatorKind::Tlsf => "allocator_tlsf",
+        }
+    }
+    
+    /// Allocator kind of a component
+    pub fn of_component(component_id: &str) -> Option<AllocatorKind> {
+        AllocatorKind::all().into_iter().find(|kind| kind.component_id() == component_id)
+    }
+}
+
+/// Allocator choice and tunables
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AllocatorConfig {
+    /// Allocator algorithm
+    pub kind: AllocatorKind,
+    
+    /// Heap size in bytes
+    #[serde(default = "default_heap_size")]
+    pub heap_size: usize,
+    
+    /// Page size in bytes, the smallest buddy block
+    #[serde(default = "default_page_size")]
+    pub page_size: usize,
+    
+    /// Largest buddy block order
+    #[serde(default = "default_max_order")]
+    pub max_order: u32,
+    
+    /// Object sizes of the SLAB/SLUB caches
+    #[serde(default = "default_size_classes")]
+    pub size_classes: Vec<usize>,
+    
+    /// Pages per slab as a buddy order
+    #[serde(default = "default_slab_order")]
+    pub slab_order: u32,
+    
+    /// Empty slabs kept per cache instead of being returned to the page allocator
+    #[serde(default = "default_empty_slab_cache")]
+    pub empty_slab_cache: usize,
+    
+    /// TLSF second-level index bits (subdivisions per power of two)
+    #[serde(default = "default_second_level_bits")]
+    pub second_level_bits: u32,
+}
+
+fn default_heap_size() -> usize {
+    16 << 20
+}
+
+fn default_page_size() -> usize {
+    4096
+}
+
+fn default_max_order() -> u32 {
+    10
+}
+
+fn default_size_classes() -> Vec<usize> {
+    vec![8, 16, 32, 64, 96, 128, 192, 256, 512, 1024, 2048, 4096]
+}
+
+fn default_slab_order() -> u32 {
+    1
+}
+
+fn default_empty_slab_cache() -> usize {
+    2
+}
+
+fn default_second_level_bits() -> u32 {
+    4
+}
+
+impl AllocatorConfig {
+    /// Default tunables for an allocator kind
+    pub fn new(kind: AllocatorKind) -> Self {
+        Self {
+            kind,
+            heap_size: default_heap_size(),
+            page_size: default_page_size(),
+            max_order: default_max_order(),
+            size_classes: default_size_classes(),
+            slab_order: default_slab_order(),
+            empty_slab_cache: default_empty_slab_cache(),
+            second_level_bits: default_second_level_bits(),
+        }
+    }
+    
+    /// Read the tunables from the properties of an allocator node
+    pub fn from_properties(kind: AllocatorKind, properties: &HashMap<String, String>) -> Result<Self, String> {
+        let mut config = Self::new(kind);
+        let number = |name: &str| -> Result<Option<u64>, String> {
+            properties.get(name)
+                .map(|value| value.trim().parse::<u64>().map_err(|_| format!("Property '{}' must be a number, got '{}'", name, value)))
+                .transpose()
+        };
+        if let Some(value) = number("heap_size")? { config.heap_size = value as usize; }
+        if let Some(value) = number("page_size")? { config.page_size = value as usize; }
+        if let Some(value) = number("max_order")? { config.max_order = value as u32; }
+        if let Some(value) = number("slab_order")? { config.slab_order = value as u32; }
+        if let Some(value) = number("empty_slab_cache")? { config.empty_slab_cache = value as usize; }
+        if let Some(value) = number("second_level_bits")? { config.second_level_bits = value as u32; }
+        if let Some(classes) = properties.get("size_classes") {
+            config.size_classes = classes.split(',')
+                .map(|class| class.trim().parse::<usize>().map_err(|_| format!("Invalid size class '{}'", class.trim())))
+                .collect::<Result<_, _>>()?;
+        }
+        config.validate()?;
+        Ok(config)
+    }
+    
+    /// Check the tunables are consistent
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.page_size.is_power_of_two() {
+            return Err(format!("Page size {} is not a power of two", self.page_size));
+        }
+        if self.heap_size < self.page_size {
+            return Err("The heap is smaller than a page".to_string());
+        }
+        if self.max_order > 20 || self.slab_order > self.max_order {
+            return Err(format!("Orders must satisfy slab_order <= max_order <= 20 (got {} and {})", self.slab_order, self.max_order));
+        }
+        if !(2..=5).contains(&self.second_level_bits) {
+            return Err(format!("TLSF second-level bits must be between 2 and 5, got {}", self.second_level_bits));
+        }
+        if matches!(self.kind, AllocatorKind::Slab | AllocatorKind::Slub) {
+            if self.size_classes.is_empty() || self.size_classes.windows(2).any(|pair| pair[0] >= pair[1]) {
+                return Err("Size classes must be non-empty and strictly increasing".to_string());
+            }
+            if self.size_classes[0] < 8 {
+                return Err("Size classes must hold at least a free pointer (8 bytes)".to_string());
+            }
+            let overhead = if self.kind == AllocatorKind::Slab { SLAB_HEADER + SLAB_BUFCTL } else { 0 };
+            if *self.size_classes.last().unwrap() + overhead > self.page_size << self.slab_order {
+                return Err("The largest size class does not fit in a slab".to_string());
+            }
+        }
+        Ok(())
+    }
+    
+    /// Display name of the configuration
+    pub fn label(&self) -> String {
+        format!("{:?}", self.kind)
+    }
+    
+    /// Kernel config fragment selecting the allocator
+    pub fn kconfig(&self) -> String {
+        let mut out = format!("# {:?} allocator generated by OSland\nCONFIG_ARCH_FORCE_MAX_ORDER={}\n", self.kind, self.max_order);
+        match self.kind {
+            AllocatorKind::Buddy => {}
+            AllocatorKind::Slab => out.push_str("CONFIG_SLAB=y\n# CONFIG_SLUB is not set\n"),
+            AllocatorKind::Slub => {
+                out.push_str("CONFIG_SLUB=y\n# CONFIG_SLAB is not set\n");
+                out.push_str(if self.empty_slab_cache > 0 { "CONFIG_SLUB_CPU_PARTIAL=y\n" } else { "# CONFIG_SLUB_CPU_PARTIAL is not set\n" });
+            }
+            AllocatorKind::Tlsf => out.push_str(&format!("CONFIG_OSL_ALLOC_TLSF=y\nCONFIG_OSL_ALLOC_TLSF_SLI={}\n", self.second_level_bits)),
+        }
+        out.push_str(&format!("CONFIG_OSL_HEAP_SIZE={}\n", self.heap_size));
+        out
+    }
+    
+    /// Rust global allocator for a `no_std` kernel
+    ///
+    /// The buddy heap uses the `buddy_system_allocator` crate, TLSF uses `rlsf`, and the
+    /// object caches are a size-class front end over the buddy heap; `spin` provides locking.
+    pub fn rust_allocator(&self) -> Result<String, String> {
+        self.validate()?;
+        // buddy_system_allocator orders are powers of two in bytes
+        let order = ((self.page_size << self.max_order).trailing_zeros() + 1) as usize;
+        let header = format!("// {:?} kernel heap generated by OSland\n\npub const HEAP_SIZE: usize = {};\n", self.kind, self.heap_size);
+        
+        Ok(match self.kind {
+            AllocatorKind::Buddy => format!(r#"{header}
+use buddy_system_allocator::LockedHeap;
+
+#[global_allocator]
+static HEAP: LockedHeap<{order}> = LockedHeap::<{order}>::empty();
+
+/// Hand the heap memory to the allocator; call once during early boot
+pub unsafe fn init_heap(start: usize) {{
+    HEAP.lock().init(start, HEAP_SIZE);
+}}
+"#),
+            AllocatorKind::Tlsf => {
+                let sl_bitmap = match self.second_level_bits { 2 | 3 => "u8", 4 => "u16", _ => "u32" };
+                let sl_len = 1usize << self.second_level_bits;
+                format!(r#"{header}
+use core::alloc::{{GlobalAlloc, Layout}};
+use core::mem::MaybeUninit;
+use core::ptr::{{self, NonNull}};
+use rlsf::Tlsf;
+use spin::Mutex;
+
+type Heap = Tlsf<'static, u32, {sl_bitmap}, 28, {sl_len}>;
+
+pub struct TlsfAllocator(Mutex<Heap>);
+
+unsafe impl GlobalAlloc for TlsfAllocator {{
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {{
+        self.0.lock().allocate(layout).map_or(ptr::null_mut(), NonNull::as_ptr)
+    }}
+    
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {{
+        self.0.lock().deallocate(NonNull::new_unchecked(ptr), layout.align());
+    }}
+}}
+
+#[global_allocator]
+static HEAP: TlsfAllocator = TlsfAllocator(Mutex::new(Tlsf::new()));
+
+/// Hand the heap memory to the allocator; call once during early boot
+pub unsafe fn init_heap(start: usize) {{
+    let pool = core::slice::from_raw_parts_mut(start as *mut MaybeUninit<u8>, HEAP_SIZE);
+    HEAP.0.lock().insert_free_block(pool);
+}}
+"#)
+            }
+            AllocatorKind::Slab | AllocatorKind::Slub => {
+                let classes: Vec<String> = self.size_classes.iter().map(|class| class.to_string()).collect();
+                let class_count = classes.len();
+                let classes = classes.join(", ");
+                let slab_size = self.page_size << self.slab_order;
+                let page_size = self.page_size;
+                format!(r#"{header}
+use buddy_system_allocator::LockedHeap;
+use core::alloc::{{GlobalAlloc, Layout}};
+use core::ptr;
+use spin::Mutex;
+
+const SIZE_CLASSES: [usize; {class_count}] = [{classes}];
+const SLAB_SIZE: usize = {slab_size};
+const PAGE_SIZE: usize = {page_size};
+
+pub struct SizeClassAllocator {{
+    pages: LockedHeap<{order}>,
+    free_lists: Mutex<[usize; {class_count}]>,
+}}
+
+fn class_of(layout: &Layout) -> Option<usize> {{
+    SIZE_CLASSES.iter().position(|&size| size >= layout.size() && size % layout.align() == 0)
+}}
+
+unsafe impl GlobalAlloc for SizeClassAllocator {{
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {{
+        let class = match class_of(&layout) {{
+            Some(class) => class,
+            None => return self.pages.alloc(layout),
+        }};
+        let mut free_lists = self.free_lists.lock();
+        if free_lists[class] == 0 {{
+            // Carve a new slab into a free list threaded through the objects
+            let slab = self.pages.alloc(Layout::from_size_align_unchecked(SLAB_SIZE, PAGE_SIZE));
+            if slab.is_null() {{
+                return ptr::null_mut();
+            }}
+            let size = SIZE_CLASSES[class];
+            for index in (0..SLAB_SIZE / size).rev() {{
+                let object = slab.add(index * size) as *mut usize;
+                object.write(free_lists[class]);
+                free_lists[class] = object as usize;
+            }}
+        }}
+        let object = free_lists[class] as *mut usize;
+        free_lists[class] = object.read();
+        object as *mut u8
+    }}
+    
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {{
+        match class_of(&layout) {{
+            Some(class) => {{
+                let mut free_lists = self.free_lists.lock();
+                (ptr as *mut usize).write(free_lists[class]);
+                free_lists[class] = ptr as usize;
+            }}
+            None => self.pages.dealloc(ptr, layout),
+        }}
+    }}
+}}
+
+#[global_allocator]
+static HEAP: SizeClassAllocator = SizeClassAllocator {{
+    pages: LockedHeap::<{order}>::empty(),
+    free_lists: Mutex::new([0; {class_count}]),
+}};
+
+/// Hand the heap memory to the allocator; call once during early boot
+pub unsafe fn init_heap(start: usize) {{
+    HEAP.pages.lock().init(start, HEAP_SIZE);
+}}
+"#)
+            }
+        })
+    }
+    
+    /// Write the kernel config fragment and the Rust allocator into a directory
+    pub fn write_artifacts<P: AsRef<Path>>(&self, dir: P) -> Result<Vec<PathBuf>, String> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        let files = [("allocator.config", self.kconfig()), ("heap.rs", self.rust_allocator()?)];
+        files.into_iter().map(|(name, content)| {
+            let path = dir.join(name);
+            fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+            Ok(path)
+        }).collect()
+    }
+}
+
+/// Create the component of an allocator kind, with its tunables as properties
+pub fn create_allocator_component(kind: AllocatorKind) -> Component {
+    let defaults = AllocatorConfig::new(kind);
+    let property = |name: &str, value: String, description: &str| ComponentProperty {
+        name: name.to_string(),
+        value: value.clone(),
+        property_type: "int".to_string(),
+        description: description.to_string(),
+        required: false,
+        default_value: Some(value),
+        valid_values: None,
+    };
+    
+    let mut properties = vec![
+        property("heap_size", defaults.heap_size.to_string(), "Heap size in bytes"),
+        property("page_size", defaults.page_size.to_string(), "Page size in bytes"),
+        property("max_order", defaults.max_order.to_string(), "Largest buddy block order"),
+    ];
+    let description = match kind {
+        AllocatorKind::Buddy => "Binary buddy page allocator",
+        AllocatorKind::Slab => "Object caches with in-slab management data (SLAB)",
+        AllocatorKind::Slub => "Object caches with in-object free pointers (SLUB)",
+        AllocatorKind::Tlsf => "Two-level segregated fit allocator with bounded latency",
+    };
+    match kind {
+        AllocatorKind::Buddy => {}
+        AllocatorKind::Slab | AllocatorKind::Slub => {
+            let classes: Vec<String> = defaults.size_classes.iter().map(|class| class.to_string()).collect();
+            properties.push(ComponentProperty { property_type: "string".to_string(), ..property("size_classes", classes.join(","), "Object sizes of the caches") });
+            properties.push(property("slab_order", defaults.slab_order.to_string(), "Pages per slab as a buddy order"));
+            properties.push(property("empty_slab_cache", defaults.empty_slab_cache.to_string(), "Empty slabs kept per cache"));
+        }
+        AllocatorKind::Tlsf => {
+            properties.push(property("second_level_bits", defaults.second_level_bits.to_string(), "Subdivisions per power of two (log2)"));
+        }
+    }
+    
+    Component {
+        id: kind.component_id().to_string(),
+        name: kind.component_id().to_string(),
+        display_name: format!("{:?} Allocator", kind),
+        component_type: ComponentType::MemoryManager,
+        category: ComponentCategory::KernelCore,
+        version: "1.0.0".to_string(),
+        description: description.to_string(),
+        author: "OSland Team".to_string(),
+        source_url: None,
+        license: "MulanPSL-2.0".to_string(),
+        properties,
+        ports: vec![ComponentPort {
+            name: "heap".to_string(),
+            port_type: "Allocator".to_string(),
+            direction: PortDirection::Output,
+            description: "Heap served to kernel components".to_string(),
+        }],
+        dependencies: Vec::new(),
+        supported_architectures: Default::default(),
+        supported_languages: vec!["c".to_string(), "rust".to_string()],
+        implementation_files: Vec::new(),
+        build_commands: Vec::new(),
+        initialization_code: String::new(),
+    }
+}
+
+/// Add the allocator components to an existing library
+pub fn extend_with_allocator_components(library: &mut ComponentLibrary) {
+    for kind in AllocatorKind::all() {
+        // Components already present are kept
+        let _ = library.add_component(create_allocator_component(kind));
+    }
+}
+
+/// Event of an allocation trace
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TraceEvent {
+    Alloc { id: u64, size: usize },
+    Free { id: u64 },
+}
+
+/// Recorded sequence of allocations and frees
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AllocationTrace {
+    /// Events in recording order
+    pub events: Vec<TraceEvent>,
+}
+
+impl AllocationTrace {
+    /// Parse a trace
+    ///
+    /// Lines are either `alloc <id> <size>` / `free <id>` or ftrace `kmem` events
+    /// (`kmalloc`, `kmem_cache_alloc`, `kfree`, `kmem_cache_free`), whose pointers are
+    /// mapped to allocation IDs. Blank lines and `#` comments are ignored.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut events = Vec::new();
+        let mut live_pointers: HashMap<String, u64> = HashMap::new();
+        let mut next_id = 0u64;
+        
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let error = |message: &str| format!("Line {}: {}", number + 1, message);
+            
+            if line.contains("kmalloc:") || line.contains("kmem_cache_alloc:") {
+                let (ptr, size) = (field(line, "ptr=").ok_or_else(|| error("missing ptr"))?, field(line, "bytes_req=").ok_or_else(|| error("missing bytes_req"))?);
+                let size = size.parse::<usize>().map_err(|_| error("invalid bytes_req"))?;
+                next_id += 1;
+                live_pointers.insert(ptr.to_string(), next_id);
+                events.push(TraceEvent::Alloc { id: next_id, size });
+            } else if line.contains("kfree:") || line.contains("kmem_cache_free:") {
+                let ptr = field(line, "ptr=").ok_or_else(|| error("missing ptr"))?;
+                // Frees of memory allocated before recording started are skipped
+                if let Some(id) = live_pointers.remove(ptr) {
+                    events.push(TraceEvent::Free { id });
+                }
+            } else {
+                let parts: Vec<&str> = line.split_whitespace().collect();
+                let id = |part: Option<&&str>| part.and_then(|id| id.parse::<u64>().ok()).ok_or_else(|| error("invalid allocation ID"));
+                match parts.first().copied() {
+                    Some("alloc") | Some("a") => {
+                        let size = parts.get(2).and_then(|size| size.parse::<usize>().ok()).ok_or_else(|| error("invalid size"))?;
+                        events.push(TraceEvent::Alloc { id: id(parts.get(1))?, size });
+                    }
+                    Some("free") | Some("f") => events.push(TraceEvent::Free { id: id(parts.get(1))? }),
+                    _ => return Err(error("expected 'alloc <id> <size>' or 'free <id>'")),
+                }
+            }
+        }
+        
+        Ok(Self { events })
+    }
+    
+    /// Load and parse a trace file
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        Self::parse(&content)
+    }
+}
+
+/// Value of a `key=value` field in an ftrace line
+fn field<'a>(line: &'a str, key: &str) -> Option<&'a str> {
+    let start = line.find(key)? + key.len();
+    line[start..].split_whitespace().next()
+}
+
+/// Result of replaying a trace against an allocator
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AllocatorMetrics {
+    /// Configuration label
+    pub allocator: String,
+    
+    /// Successful allocations
+    pub allocations: u64,
+    
+    /// Allocations that could not be satisfied
+    pub failures: u64,
+    
+    /// Largest number of requested bytes live at once
+    pub peak_requested_bytes: usize,
+    
+    /// Largest number of heap bytes in use at once, including rounding and metadata
+    pub peak_footprint_bytes: usize,
+    
+    /// Average share of the used heap holding requested bytes (1.0 = no overhead)
+    pub avg_utilization: f64,
+    
+    /// Worst external fragmentation seen (1 - largest free block / free bytes)
+    pub max_external_fragmentation: f64,
+    
+    /// Splits, merges and list operations, a proxy for allocator time
+    pub work: u64,
+    
+    /// Average work per allocation or free
+    pub avg_work_per_op: f64,
+}
+
+/// Allocator model used by the simulation
+trait AllocatorModel {
+    /// Allocate, returning the handle of the allocation
+    fn alloc(&mut self, size: usize) -> Option<usize>;
+    
+    /// Free an allocation
+    fn free(&mut self, handle: usize);
+    
+    /// Free bytes and largest free block
+    fn free_space(&self) -> (usize, usize);
+    
+    /// Work done so far
+    fn work(&self) -> u64;
+}
+
+/// Replay a trace against an allocator configuration
+pub fn simulate_allocator(config: &AllocatorConfig, trace: &AllocationTrace) -> Result<AllocatorMetrics, String> {
+    config.validate()?;
+    let mut model: Box<dyn AllocatorModel> = match config.kind {
+        AllocatorKind::Buddy => Box::new(BuddyModel::new(config.heap_size, config.page_size, config.max_order)),
+        AllocatorKind::Slab | AllocatorKind::Slub => Box::new(SlabModel::new(config)),
+        AllocatorKind::Tlsf => Box::new(TlsfModel::new(config.heap_size, config.second_level_bits)),
+    };
+    
+    let mut metrics = AllocatorMetrics { allocator: config.label(), ..AllocatorMetrics::default() };
+    let mut live: HashMap<u64, (usize, usize)> = HashMap::new();
+    let (mut requested, mut operations, mut utilization_sum, mut samples) = (0usize, 0u64, 0.0, 0u64);
+    
+    for event in &trace.events {
+        match *event {
+            TraceEvent::Alloc { id, size } => match model.alloc(size.max(1)) {
+                Some(handle) => {
+                    if let Some((old_handle, old_size)) = live.insert(id, (handle, size)) {
+                        // IDs reused without a free in between: treat as a free of the old allocation
+                        model.free(old_handle);
+                        requested -= old_size;
+                    }
+                    requested += size;
+                    metrics.allocations += 1;
+                }
+                None => metrics.failures += 1,
+            },
+            TraceEvent::Free { id } => match live.remove(&id) {
+                Some((handle, size)) => {
+                    model.free(handle);
+                    requested -= size;
+                }
+                None => continue,
+            },
+        }
+        operations += 1;
+        
+        let (free, largest) = model.free_space();
+        let footprint = config.heap_size - free;
+        metrics.peak_requested_bytes = metrics.peak_requested_bytes.max(requested);
+        metrics.peak_footprint_bytes = metrics.peak_footprint_bytes.max(footprint);
+        if footprint > 0 {
+            utilization_sum += requested as f64 / footprint as f64;
+            samples += 1;
+        }
+        if free > 0 {
+            metrics.max_external_fragmentation = metrics.max_external_fragmentation.max(1.0 - largest as f64 / free as f64);
+        }
+    }
+    
+    metrics.avg_utilization = if samples == 0 { 1.0 } else { utilization_sum / samples as f64 };
+    metrics.work = model.work();
+    metrics.avg_work_per_op = if operations == 0 { 0.0 } else { metrics.work as f64 / operations as f64 };
+    Ok(metrics)
+}
+
+/// Replay a trace against several configurations, best utilization first
+pub fn compare_allocators(configs: &[AllocatorConfig], trace: &AllocationTrace) -> Result<Vec<AllocatorMetrics>, String> {
+    let mut results = configs.iter().map(|config| simulate_allocator(config, trace)).collect::<Result<Vec<_>, _>>()?;
+    results.sort_by(|a, b| a.failures.cmp(&b.failures).then(b.avg_utilization.total_cmp(&a.avg_utilization)));
+    Ok(results)
+}
+
+/// Binary buddy allocator over `[0, heap_size)`
+struct BuddyModel {
+    min_block: usize,
+    max_order: u32,
+    free_lists: Vec<BTreeSet<usize>>,
+    allocated: HashMap<usize, u32>,
+    free_bytes: usize,
+    work: u64,
+}
+
+impl BuddyModel {
+    fn new(heap_size: usize, min_block: usize, max_order: u32) -> Self {
+        let mut model = Self {
+            min_block,
+            max_order,
+            free_lists: vec![BTreeSet::new(); max_order as usize + 1],
+            allocated: HashMap::new(),
+            free_bytes: 0,
+            work: 0,
+        };
+        // Cover the heap with the largest aligned blocks that fit
+        let mut offset = 0;
+        for order in (0..=max_order).rev() {
+            let size = min_block << order;
+            while offset + size <= heap_size {
+                model.free_lists[order as usize].insert(offset);
+                model.free_bytes += size;
+                offset += size;
+            }
+        }
+        model
+    }
+    
+    fn order_for(&self, size: usize) -> Option<u32> {
+        (0..=self.max_order).find(|&order| self.min_block << order >= size)
+    }
+    
+    fn alloc_order(&mut self, order: u32) -> Option<usize> {
+        let mut current = (order..=self.max_order).find(|&o| !self.free_lists[o as usize].is_empty())?;
+        let offset = self.free_lists[current as usize].pop_first()?;
+        self.work += 1;
+        while current > order {
+            current -= 1;
+            self.free_lists[current as usize].insert(offset + (self.min_block << current));
+            self.work += 1;
+        }
+        self.allocated.insert(offset, order);
+        self.free_bytes -= self.min_block << order;
+        Some(offset)
+    }
+    
+    fn free_block(&mut self, offset: usize) {
+        let Some(mut order) = self.allocated.remove(&offset) else { return };
+        self.free_bytes += self.min_block << order;
+        let mut offset = offset;
+        while order < self.max_order {
+            let buddy = offset ^ (self.min_block << order);
+            if !self.free_lists[order as usize].remove(&buddy) {
+                break;
+            }
+            offset = offset.min(buddy);
+            order += 1;
+            self.work += 1;
+        }
+        self.free_lists[order as usize].insert(offset);
+        self.work += 1;
+    }
+    
+    fn largest_free(&self) -> usize {
+        (0..=self.max_order).rev().find(|&order| !self.free_lists[order as usize].is_empty()).map_or(0, |order| self.min_block << order)
+    }
+}
+
+impl AllocatorModel for BuddyModel {
+    fn alloc(&mut self, size: usize) -> Option<usize> {
+        let order = self.order_for(size)?;
+        self.alloc_order(order)
+    }
+    
+    fn free(&mut self, handle: usize) {
+        self.free_block(handle);
+    }
+    
+    fn free_space(&self) -> (usize, usize) {
+        (self.free_bytes, self.largest_free())
+    }
+    
+    fn work(&self) -> u64 {
+        self.work
+    }
+}
+
+/// Slab of an object cache
+struct Slab {
+    free_objects: Vec<usize>,
+}
+
+/// Object cache of one size class
+struct SlabCache {
+    object_size: usize,
+    objects_per_slab: usize,
+    slabs: BTreeMap<usize, Slab>,
+}
+
+/// SLAB or SLUB object caches on top of a buddy page allocator
+///
+/// Handles of objects are their heap offsets; large allocations go to the page allocator.
+struct SlabModel {
+    pages: BuddyModel,
+    slab_order: u32,
+    slab_size: usize,
+    empty_slab_cache: usize,
+    caches: Vec<SlabCache>,
+    objects: HashMap<usize, (usize, usize)>,
+    work: u64,
+}
+
+impl SlabModel {
+    fn new(config: &AllocatorConfig) -> Self {
+        let slab_size = config.page_size << config.slab_order;
+        let caches = config.size_classes.iter().map(|&object_size| SlabCache {
+            object_size,
+            objects_per_slab: match config.kind {
+                AllocatorKind::Slab => (slab_size - SLAB_HEADER) / (object_size + SLAB_BUFCTL),
+                _ => slab_size / object_size,
+            },
+            slabs: BTreeMap::new(),
+        }).collect();
+        
+        Self {
+            pages: BuddyModel::new(config.heap_size, config.page_size, config.max_order),
+            slab_order: config.slab_order,
+            slab_size,
+            empty_slab_cache: config.empty_slab_cache,
+            caches,
+            objects: HashMap::new(),
+            work: 0,
+        }
+    }
+}
+
+impl AllocatorModel for SlabModel {
+    fn alloc(&mut self, size: usize) -> Option<usize> {
+        let class = match self.caches.iter().position(|cache| cache.object_size >= size) {
+            Some(class) => class,
+            None => return self.pages.alloc(size),
+        };
+        
+        // Fill partial slabs first, lowest address first
+        self.work += 1;
+        let partial = self.caches[class].slabs.iter().find(|(_, slab)| !slab.free_objects.is_empty()).map(|(offset, _)| *offset);
+        let slab_offset = match partial {
+            Some(offset) => offset,
+            None => {
+                let offset = self.pages.alloc_order(self.slab_order)?;
+                let cache = &mut self.caches[class];
+                let free_objects = (0..cache.objects_per_slab).rev().map(|index| offset + index * cache.object_size).collect();
+                cache.slabs.insert(offset, Slab { free_objects });
+                offset
+            }
+        };
+        let object = self.caches[class].slabs.get_mut(&slab_offset)?.free_objects.pop()?;
+        self.objects.insert(object, (class, slab_offset));
+        Some(object)
+    }
+    
+    fn free(&mut self, handle: usize) {
+        let Some((class, slab_offset)) = self.objects.remove(&handle) else {
+            self.pages.free_block(handle);
+            return;
+        };
+        self.work += 1;
+        
+        let cache = &mut self.caches[class];
+        let Some(slab) = cache.slabs.get_mut(&slab_offset) else { return };
+        slab.free_objects.push(handle);
+        if slab.free_objects.len() < cache.objects_per_slab {
+            return;
+        }
+        // Return empty slabs beyond the cached ones to the page allocator
+        let empty = cache.slabs.values().filter(|slab| slab.free_objects.len() == cache.objects_per_slab).count();
+        if empty > self.empty_slab_cache {
+            cache.slabs.remove(&slab_offset);
+            self.pages.free_block(slab_offset);
+        }
+    }
+    
+    fn free_space(&self) -> (usize, usize) {
+        let (free, largest) = self.pages.free_space();
+        // Free objects inside slabs are only usable by their own size class
+        let cached_empty: usize = self.caches.iter()
+            .map(|cache| cache.slabs.values().filter(|slab| slab.free_objects.len() == cache.objects_per_slab).count() * self.slab_size)
+            .sum();
+        (free + cached_empty, largest.max(if cached_empty > 0 { self.slab_size } else { 0 }))
+    }
+    
+    fn work(&self) -> u64 {
+        self.work + self.pages.work()
+    }
+}
+
+/// TLSF allocator with physical-neighbor coalescing
+///
+/// Handles are block offsets; requests are rounded up to the next size class so that
+/// any block of the first non-empty class found fits (good fit).
+struct TlsfModel {
+    second_level_bits: u32,
+    blocks: BTreeMap<usize, (usize, bool)>,
+    free_classes: BTreeMap<(u32, u32), BTreeSet<usize>>,
+    free_bytes: usize,
+    work: u64,
+}
+
+impl TlsfModel {
+    fn new(heap_size: usize, second_level_bits: u32) -> Self {
+        let mut model = Self {
+            second_level_bits,
+            blocks: BTreeMap::new(),
+            free_classes: BTreeMap::new(),
+            free_bytes: 0,
+            work: 0,
+        };
+        model.insert_free(0, heap_size);
+        model
+    }
+    
+    /// Size class containing a block size
+    fn class_of(&self, size: usize) -> (u32, u32) {
+        let first = usize::BITS - 1 - size.leading_zeros();
+        if first <= self.second_level_bits {
+            (first, 0)
+        } else {
+            let second = (size >> (first - self.second_level_bits)) ^ (1 << self.second_level_bits);
+            (first, second as u32)
+        }
+    }
+    
+    /// Smallest class whose blocks all fit a size
+    fn search_class(&self, size: usize) -> (u32, u32) {
+        let first = usize::BITS - 1 - size.leading_zeros();
+        if first <= self.second_level_bits {
+            if size.is_power_of_two() { (first, 0) } else { (first + 1, 0) }
+        } else {
+            self.class_of(size + (1 << (first - self.second_level_bits)) - 1)
+        }
+    }
+    
+    fn insert_free(&mut self, offset: usize, size: usize) {
+        self.blocks.insert(offset, (size, true));
+        let class = self.class_of(size);
+        self.free_classes.entry(class).or_default().insert(offset);
+        self.free_bytes += size;
+    }
+    
+    fn remove_free(&mut self, offset: usize, size: usize) {
+        let class = self.class_of(size);
+        if let Some(set) = self.free_classes.get_mut(&class) {
+            set.remove(&offset);
+            if set.is_empty() {
+                self.free_classes.remove(&class);
+            }
+        }
+        self.blocks.remove(&offset);
+        self.free_bytes -= size;
+    }
+}
+
+impl AllocatorModel for TlsfModel {
+    fn alloc(&mut self, size: usize) -> Option<usize> {
+        let needed = (size + TLSF_BLOCK_HEADER).next_multiple_of(TLSF_BLOCK_HEADER);
+        let class = self.search_class(needed);
+        self.work += 1;
+        let offset = *self.free_classes.range(class..).next()?.1.iter().next()?;
+        let (block_size, _) = self.blocks[&offset];
+        self.remove_free(offset, block_size);
+        
+        if block_size - needed >= 2 * TLSF_BLOCK_HEADER {
+            self.insert_free(offset + needed, block_size - needed);
+            self.blocks.insert(offset, (needed, false));
+            self.work += 1;
+        } else {
+            self.blocks.insert(offset, (block_size, false));
+        }
+        Some(offset)
+    }
+    
+    fn free(&mut self, handle: usize) {
+        let Some(&(mut size, false)) = self.blocks.get(&handle) else { return };
+        let mut offset = handle;
+        self.blocks.remove(&handle);
+        self.work += 1;
+        
+        if let Some((&next, &(next_size, true))) = self.blocks.range(offset + size..).next() {
+            if next == offset + size {
+                self.remove_free(next, next_size);
+                size += next_size;
+                self.work += 1;
+            }
+        }
+        if let Some((&previous, &(previous_size, true))) = self.blocks.range(..offset).next_back() {
+            if previous + previous_size == offset {
+                self.remove_free(previous, previous_size);
+                offset = previous;
+                size += previous_size;
+                self.work += 1;
+            }
+        }
+        self.insert_free(offset, size);
+    }
+    
+    fn free_space(&self) -> (usize, usize) {
+        let largest = self.free_classes.iter().next_back()
+            .and_then(|(_, offsets)| offsets.iter().map(|offset| self.blocks[offset].0).max())
+            .unwrap_or(0);
+        (self.free_bytes, largest)
+    }
+    
+    fn work(&self) -> u64 {
+        self.work
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_trace_replay_and_generation() {
+        let trace = AllocationTrace::parse(
+            "# mixed workload\nalloc 1 100\nalloc 2 3000\nalloc 3 40\nfree 2\n\
+             kmalloc: call_site=ext4_fill_super+0x1c ptr=0xffff888003a1c000 bytes_req=600 bytes_alloc=1024 gfp_flags=GFP_KERNEL\n\
+             kfree: call_site=ext4_put_super+0x10 ptr=0xffff888003a1c000\nkfree: call_site=x ptr=0xdead\nfree 1\n",
+        ).unwrap();
+        assert_eq!(trace.events.len(), 7);
+        assert_eq!(trace.events[4], TraceEvent::Alloc { id: 1, size: 600 });
+        
+        let mut configs: Vec<AllocatorConfig> = AllocatorKind::all().into_iter().map(AllocatorConfig::new).collect();
+        for config in &mut configs {
+            config.heap_size = 1 << 20;
+        }
+        let buddy = simulate_allocator(&configs[0], &trace).unwrap();
+        assert_eq!((buddy.allocations, buddy.failures), (4, 0));
+        assert_eq!(buddy.peak_requested_bytes, 3140);
+        assert_eq!(buddy.peak_footprint_bytes, 3 * 4096);
+        
+        let ranked = compare_allocators(&configs, &trace).unwrap();
+        assert_ne!(ranked[0].allocator, "Buddy");
+        assert!(ranked.iter().all(|metrics| metrics.failures == 0));
+        
+        let mut small = AllocatorConfig::new(AllocatorKind::Tlsf);
+        small.heap_size = 4096;
+        let huge = AllocationTrace::parse("alloc 1 8000").unwrap();
+        assert_eq!(simulate_allocator(&small, &huge).unwrap().failures, 1);
+        
+        assert!(configs[2].kconfig().contains("CONFIG_SLUB=y\n# CONFIG_SLAB is not set\nCONFIG_SLUB_CPU_PARTIAL=y\n"));
+        assert!(configs[3].rust_allocator().unwrap().contains("type Heap = Tlsf<'static, u32, u16, 28, 16>;"));
+        assert!(configs[0].rust_allocator().unwrap().contains("static HEAP: LockedHeap<23>"));
+    }
+}
@@ -0,0 +1,551 @@
+// Boot flow designer for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! Boot sequence modelling.
+//!
+//! In the boot flow canvas mode, nodes are boot stages (firmware, bootloader, kernel
+//! init, initramfs and userspace) and connections are hand-overs from one stage to the
+//! next. The flow is checked for stages running out of order and for artifacts the
+//! build configuration does not produce, and the bootloader entry, initramfs `/init`
+//! and userspace init scripts are generated from the stage properties.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use crate::core::geometry::{Color, Point};
+use serde::{Deserialize, Serialize};
+use crate::component_manager::component::{Component, ComponentCategory, ComponentPort, ComponentProperty, ComponentType, PortDirection};
+use crate::component_manager::visual_node::{DataFlowInfo, NodeCanvas, NodeConnection, VisualNode};
+use crate::component_manager::ComponentLibrary;
+use crate::build_engine::build_config::{BuildConfig, BuildStepType};
+use super::syscall_designer::GeneratedFile;
+
+/// Port type of the hand-over between boot stages
+pub const BOOT_STAGE_PORT_TYPE: &str = "BootStage";
+
+/// Stage of the boot sequence, in boot order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum BootStage {
+    Firmware,
+    Bootloader,
+    KernelInit,
+    Initramfs,
+    Userspace,
+}
+
+impl BootStage {
+    /// Every stage in boot order
+    pub fn all() -> [BootStage; 5] {
+        [BootStage::Firmware, BootStage::Bootloader, BootStage::KernelInit, BootStage::Initramfs, BootStage::Userspace]
+    }
+    
+    /// Component ID of the stage
+    pub fn component_id(&self) -> &'static str {
+        match self {
+            BootStage::Firmware => "boot_firmware",
+            BootStage::Bootloader => "boot_bootloader",
+            BootStage::KernelInit => "boot_kernel_init",
+            BootStage::Initramfs => "boot_initramfs",
+            BootStage::Userspace => "boot_userspace",
+        }
+    }
+    
+    /// Stage of a component
+    pub fn of_component(component_id: &str) -> Option<BootStage> {
+        BootStage::all().into_iter().find(|stage| stage.component_id() == component_id)
+    }
+    
+    /// Whether every boot flow needs the stage (the initramfs is optional)
+    pub fn is_required(&self) -> bool {
+        *self != BootStage::Initramfs
+    }
+}
+
+impl fmt::Display for BootStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            BootStage::Firmware => "firmware",
+            BootStage::Bootloader => "bootloader",
+            BootStage::KernelInit => "kernel init",
+            BootStage::Initramfs => "initramfs",
+            BootStage::Userspace => "userspace",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Issue severity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BootIssueSeverity {
+    /// Generation is refused
+    Error,
+    
+    /// Generation proceeds
+    Warning,
+}
+
+/// Problem found in a boot flow
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BootIssue {
+    /// Issue severity
+    pub severity: BootIssueSeverity,
+    
+    /// Stage the issue refers to
+    pub stage: Option<BootStage>,
+    
+    /// Issue message
+    pub message: String,
+}
+
+impl fmt::Display for BootIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            BootIssueSeverity::Error => "error",
+            BootIssueSeverity::Warning => "warning",
+        };
+        match &self.stage {
+            Some(stage) => write!(f, "{}: {}: {}", severity, stage, self.message),
+            None => write!(f, "{}: {}", severity, self.message),
+        }
+    }
+}
+
+/// Stage node of a boot flow
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BootStageNode {
+    /// Boot stage
+    pub stage: BootStage,
+    
+    /// Canvas node ID
+    pub node_id: String,
+    
+    /// Node properties
+    pub properties: HashMap<String, String>,
+}
+
+impl BootStageNode {
+    fn property<'a>(&'a self, name: &str, default: &'a str) -> &'a str {
+        self.properties.get(name).map(|value| value.trim()).filter(|value| !value.is_empty()).unwrap_or(default)
+    }
+    
+    fn list(&self, name: &str) -> Vec<String> {
+        self.properties.get(name)
+            .map(|value| value.split(',').map(|item| item.trim().to_string()).filter(|item| !item.is_empty()).collect())
+            .unwrap_or_default()
+    }
+}
+
+/// Boot sequence of a canvas
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BootFlow {
+    /// Stages in boot order
+    pub stages: Vec<BootStageNode>,
+    
+    /// Hand-overs as (from node, to node)
+    pub handovers: Vec<(String, String)>,
+}
+
+impl BootFlow {
+    /// Collect the boot stages and hand-overs of a canvas
+    pub fn from_canvas(canvas: &NodeCanvas) -> Result<Self, String> {
+        let mut flow = Self::default();
+        for node in canvas.nodes.values() {
+            if let Some(stage) = BootStage::of_component(&node.component_id) {
+                if let Some(other) = flow.stages.iter().find(|existing| existing.stage == stage) {
+                    return Err(format!("Nodes '{}' and '{}' are both the {} stage", other.node_id, node.id, stage));
+                }
+                flow.stages.push(BootStageNode { stage, node_id: node.id.clone(), properties: node.properties.clone() });
+            }
+        }
+        flow.stages.sort_by_key(|node| node.stage);
+        
+        let mut handovers: Vec<(String, String)> = canvas.connections.values()
+            .filter(|connection| flow.node(&connection.from_node).is_some() && flow.node(&connection.to_node).is_some())
+            .map(|connection| (connection.from_node.clone(), connection.to_node.clone()))
+            .collect();
+        handovers.sort();
+        flow.handovers = handovers;
+        Ok(flow)
+    }
+    
+    /// Node of a stage
+    pub fn stage(&self, stage: BootStage) -> Option<&BootStageNode> {
+        self.stages.iter().find(|node| node.stage == stage)
+    }
+    
+    fn node(&self, node_id: &str) -> Option<&BootStageNode> {
+        self.stages.iter().find(|node| node.node_id == node_id)
+    }
+    
+    /// Check the stage order and the artifacts each stage needs from the build configuration
+    pub fn validate(&self, config: &BuildConfig) -> Vec<BootIssue> {
+        let mut issues = Vec::new();
+        let error = |stage: Option<BootStage>, message: String| BootIssue { severity: BootIssueSeverity::Error, stage, message };
+        
+        for stage in BootStage::all() {
+            if stage.is_required() && self.stage(stage).is_none() {
+                issues.push(error(None, format!("The boot flow has no {} stage", stage)));
+            }
+        }
+        
+        // Every stage hands over to the next present stage, and only to it
+        for (from_id, to_id) in &self.handovers {
+            let (from, to) = match (self.node(from_id), self.node(to_id)) {
+                (Some(from), Some(to)) => (from.stage, to.stage),
+                _ => continue,
+            };
+            if to <= from {
+                issues.push(error(Some(from), format!("Hands over to the {} stage, which runs before it", to)));
+            } else if let Some(skipped) = self.stages.iter().find(|node| node.stage > from && node.stage < to) {
+                issues.push(error(Some(from), format!("Hands over to the {} stage, skipping the {} stage", to, skipped.stage)));
+            }
+        }
+        for pair in self.stages.windows(2) {
+            let connected = self.handovers.iter().any(|(from, to)| *from == pair[0].node_id && *to == pair[1].node_id);
+            if !connected {
+                issues.push(error(Some(pair[0].stage), format!("Does not hand over to the {} stage", pair[1].stage)));
+            }
+        }
+        
+        // Artifacts produced by the build
+        let step_enabled = |step_type: BuildStepType| config.build_steps.iter().any(|step| step.enabled && step.step_type == step_type);
+        let bootloader = config.bootloader_config.bootloader_type.as_str();
+        for node in &self.stages {
+            match node.stage {
+                BootStage::Firmware => {
+                    let firmware = node.property("type", "uefi");
+                    if !matches!(firmware, "uefi" | "bios") {
+                        issues.push(error(Some(node.stage), format!("Unknown firmware type '{}'", firmware)));
+                    } else if firmware == "bios" && bootloader == "systemd-boot" {
+                        issues.push(error(Some(node.stage), "systemd-boot needs UEFI firmware".to_string()));
+                    }
+                }
+                BootStage::Bootloader => {
+                    if !step_enabled(BuildStepType::InstallBootloader) && !step_enabled(BuildStepType::CreateHypervisorImage) {
+                        issues.push(error(Some(node.stage), "No enabled build step installs the bootloader".to_string()));
+                    }
+                    if !matches!(bootloader, "grub" | "systemd-boot") {
+                        issues.push(BootIssue {
+                            severity: BootIssueSeverity::Warning,
+                            stage: Some(node.stage),
+                            message: format!("No boot entry is generated for bootloader '{}'", bootloader),
+                        });
+                    }
+                }
+                BootStage::KernelInit => {
+                    if !step_enabled(BuildStepType::BuildKernel) {
+                        issues.push(error(Some(node.stage), "No enabled build step builds the kernel".to_string()));
+                    }
+                }
+                BootStage::Initramfs => {
+                    if !step_enabled(BuildStepType::CreateInitramfs) {
+                        issues.push(error(Some(node.stage), "No enabled build step creates the initramfs".to_string()));
+                    }
+                    if node.property("root_wait", "10").parse::<u32>().is_err() {
+                        issues.push(error(Some(node.stage), format!("root_wait '{}' is not a number of seconds", node.property("root_wait", ""))));
+                    }
+                }
+                BootStage::Userspace => {
+                    if !step_enabled(BuildStepType::CreateRootfs) && config.rootfs_config.source_dir.is_none() {
+                        issues.push(error(Some(node.stage), "No enabled build step creates the root filesystem".to_string()));
+                    }
+                    if !node.property("init", "/sbin/init").starts_with('/') {
+                        issues.push(error(Some(node.stage), "The init program must be an absolute path".to_string()));
+                    }
+                }
+            }
+        }
+        issues
+    }
+    
+    /// Kernel command line handed over by the bootloader
+    pub fn kernel_cmdline(&self, config: &BuildConfig) -> String {
+        let mut params = config.bootloader_config.kernel_params.clone();
+        if let Some(kernel) = self.stage(BootStage::KernelInit) {
+            params.extend(kernel.property("cmdline", "").split_whitespace().map(|param| param.to_string()));
+        }
+        if let Some(userspace) = self.stage(BootStage::Userspace) {
+            params.push(format!("root={}", userspace.property("root", "/dev/vda1")));
+            params.push(format!("rootfstype={}", config.rootfs_config.fs_type));
+            match self.stage(BootStage::Initramfs) {
+                Some(_) => params.push("rdinit=/init".to_string()),
+                None => params.push(format!("init={}", userspace.property("init", "/sbin/init"))),
+            }
+        }
+        params.join(" ")
+    }
+    
+    /// Generate the bootloader entry and init scripts, with paths relative to the output directory
+    pub fn generate(&self, config: &BuildConfig) -> Vec<GeneratedFile> {
+        let mut files = Vec::new();
+        let cmdline = self.kernel_cmdline(config);
+        let title = self.stage(BootStage::Bootloader).map_or("OSland", |node| node.property("title", "OSland"));
+        let initrd = self.stage(BootStage::Initramfs).is_some();
+        
+        match config.bootloader_config.bootloader_type.as_str() {
+            "grub" => {
+                let mut grub = format!("set timeout={}\nset default=0\n\nmenuentry \"{}\" {{\n\tlinux /vmlinuz {}\n", config.bootloader_config.timeout, title, cmdline);
+                if initrd {
+                    grub.push_str("\tinitrd /initrd.img\n");
+                }
+                grub.push_str("}\n");
+                files.push(GeneratedFile { path: PathBuf::from("bootloader/grub/grub.cfg"), content: grub });
+            }
+            "systemd-boot" => {
+                let loader = format!("default osland.conf\ntimeout {}\n", config.bootloader_config.timeout);
+                let mut entry = format!("title {}\nlinux /vmlinuz\n", title);
+                if initrd {
+                    entry.push_str("initrd /initrd.img\n");
+                }
+                entry.push_str(&format!("options {}\n", cmdline));
+                files.push(GeneratedFile { path: PathBuf::from("bootloader/loader/loader.conf"), content: loader });
+                files.push(GeneratedFile { path: PathBuf::from("bootloader/loader/entries/osland.conf"), content: entry });
+            }
+            _ => {}
+        }
+        
+        let userspace = self.stage(BootStage::Userspace);
+        let init = userspace.map_or("/sbin/init", |node| node.property("init", "/sbin/init"));
+        if let Some(initramfs) = self.stage(BootStage::Initramfs) {
+            files.push(GeneratedFile { path: PathBuf::from("initramfs/init"), content: initramfs_init(initramfs, &config.rootfs_config.fs_type, init) });
+        }
+        if let Some(userspace) = userspace {
+            files.push(GeneratedFile {
+                path: PathBuf::from("rootfs/etc/inittab"),
+                content: "::sysinit:/etc/init.d/rcS\n::respawn:/sbin/getty -L console 0 vt100\n::ctrlaltdel:/sbin/reboot\n::shutdown:/bin/umount -a -r\n".to_string(),
+            });
+            let mut rcs = format!("#!/bin/sh\n# Generated by OSland\nmount -a\nhostname {}\n", userspace.property("hostname", "osland"));
+            for service in userspace.list("services") {
+                rcs.push_str(&format!("/etc/init.d/{} start\n", service));
+            }
+            files.push(GeneratedFile { path: PathBuf::from("rootfs/etc/init.d/rcS"), content: rcs });
+        }
+        files
+    }
+    
+    /// Write the generated files under a directory
+    ///
+    /// `initramfs` and `rootfs` hold overlays for the initramfs and root filesystem steps.
+    pub fn write<P: AsRef<Path>>(&self, config: &BuildConfig, dir: P) -> Result<Vec<PathBuf>, String> {
+        let dir = dir.as_ref();
+        self.generate(config).into_iter().map(|file| {
+            let path = dir.join(&file.path);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+            }
+            fs::write(&path, &file.content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+            set_executable(&path, &file.path)?;
+            Ok(path)
+        }).collect()
+    }
+}
+
+/// Mark the init scripts executable
+#[cfg(unix)]
+fn set_executable(path: &Path, relative: &Path) -> Result<(), String> {
+    use std::os::unix::fs::PermissionsExt;
+    if relative.ends_with("init") || relative.ends_with("rcS") {
+        fs::set_permissions(path, fs::Permissions::from_mode(0o755)).map_err(|e| format!("Failed to set permissions of {}: {}", path.display(), e))?;
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn set_executable(_path: &Path, _relative: &Path) -> Result<(), String> {
+    Ok(())
+}
+
+/// `/init` of the initramfs: load modules, wait for the root device and switch to it
+fn initramfs_init(initramfs: &BootStageNode, fs_type: &str, init: &str) -> String {
+    let mut script = String::from("#!/bin/sh\n# Generated by OSland\nmount -t proc proc /proc\nmount -t sysfs sysfs /sys\nmount -t devtmpfs devtmpfs /dev\n\n");
+    for module in initramfs.list("modules") {
+        script.push_str(&format!("modprobe {}\n", module));
+    }
+    script.push_str(&format!(
+        "\nroot=$(sed -n 's/.*root=\\([^ ]*\\).*/\\1/p' /proc/cmdline)\n\
+         tries={}\n\
+         while [ ! -b \"$root\" ] && [ \"$tries\" -gt 0 ]; do\n\
+         \tsleep 1\n\
+         \ttries=$((tries - 1))\n\
+         done\n\
+         \n\
+         mkdir -p /newroot\n\
+         mount -t {} -o ro \"$root\" /newroot || exec sh\n\
+         umount /proc /sys\n\
+         mount --move /dev /newroot/dev\n\
+         exec switch_root /newroot {}\n",
+        initramfs.property("root_wait", "10"), fs_type, init
+    ));
+    script
+}
+
+/// Create the component of a boot stage, with its settings as properties
+pub fn create_boot_stage_component(stage: BootStage) -> Component {
+    let property = |name: &str, value: &str, description: &str| ComponentProperty {
+        name: name.to_string(),
+        value: value.to_string(),
+        property_type: "string".to_string(),
+        description: description.to_string(),
+        required: false,
+        default_value: Some(value.to_string()),
+        valid_values: None,
+    };
+    let (display_name, description, properties) = match stage {
+        BootStage::Firmware => ("Firmware", "Platform firmware starting the bootloader", vec![
+            ComponentProperty {
+                valid_values: Some(vec!["uefi".to_string(), "bios".to_string()]),
+                ..property("type", "uefi", "Firmware interface")
+            },
+        ]),
+        BootStage::Bootloader => ("Bootloader", "Bootloader of the build configuration loading the kernel", vec![
+            property("title", "OSland", "Boot entry title"),
+        ]),
+        BootStage::KernelInit => ("Kernel Init", "Kernel initialization up to the first user process", vec![
+            property("cmdline", "", "Extra kernel command line parameters"),
+        ]),
+        BootStage::Initramfs => ("Initramfs", "Early userspace mounting the root filesystem", vec![
+            property("modules", "", "Modules loaded before mounting the root (comma separated)"),
+            property("root_wait", "10", "Seconds to wait for the root device"),
+        ]),
+        BootStage::Userspace => ("Userspace", "Init system and services of the root filesystem", vec![
+            property("root", "/dev/vda1", "Root device"),
+            property("init", "/sbin/init", "Init program"),
+            property("hostname", "osland", "Host name"),
+            property("services", "", "Services started at boot (comma separated)"),
+        ]),
+    };
+    
+    let mut ports = Vec::new();
+    if stage != BootStage::Firmware {
+        ports.push(ComponentPort {
+            name: "from".to_string(),
+            port_type: BOOT_STAGE_PORT_TYPE.to_string(),
+            direction: PortDirection::Input,
+            description: "Stage handing over control".to_string(),
+        });
+    }
+    if stage != BootStage::Userspace {
+        ports.push(ComponentPort {
+            name: "to".to_string(),
+            port_type: BOOT_STAGE_PORT_TYPE.to_string(),
+            direction: PortDirection::Output,
+            description: "Stage control is handed over to".to_string(),
+        });
+    }
+    
+    Component {
+        id: stage.component_id().to_string(),
+        name: stage.component_id().to_string(),
+        display_name: display_name.to_string(),
+        component_type: ComponentType::Custom("BootStage".to_string()),
+        category: ComponentCategory::SystemServices,
+        version: "1.0.0".to_string(),
+        description: description.to_string(),
+        author: "OSland Team".to_string(),
+        source_url: None,
+        license: "MulanPSL-2.0".to_string(),
+        properties,
+        ports,
+        dependencies: Vec::new(),
+        supported_architectures: Default::default(),
+        supported_languages: Vec::new(),
+        implementation_files: Vec::new(),
+        build_commands: Vec::new(),
+        initialization_code: String::new(),
+    }
+}
+
+/// Add the boot stage components to a library
+pub fn extend_with_boot_stage_components(library: &mut ComponentLibrary) {
+    for stage in BootStage::all() {
+        // Components already present are kept
+        let _ = library.add_component(create_boot_stage_component(stage));
+    }
+}
+
+/// Create the canvas of the boot flow mode: every stage, handing over in boot order
+pub fn create_boot_flow_canvas() -> Result<NodeCanvas, String> {
+    let mut canvas = NodeCanvas::new();
+    let mut previous: Option<VisualNode> = None;
+    for (index, stage) in BootStage::all().into_iter().enumerate() {
+        let node = VisualNode::with_id(create_boot_stage_component(stage), Point::new(index as f64 * 220.0, 0.0), stage.component_id().to_string())
+            .map_err(|e| e.to_string())?;
+        canvas.add_node(node.clone()).map_err(|e| e.to_string())?;
+        if let Some(previous) = &previous {
+            canvas.add_connection(handover(&canvas, previous, &node)?).map_err(|e| e.to_string())?;
+        }
+        previous = Some(node);
+    }
+    Ok(canvas)
+}
+
+fn handover(canvas: &NodeCanvas, from: &VisualNode, to: &VisualNode) -> Result<NodeConnection, String> {
+    let from_port = from.get_port_by_name("to").ok_or_else(|| format!("'{}' has no outgoing hand-over port", from.id))?;
+    let to_port = to.get_port_by_name("from").ok_or_else(|| format!("'{}' has no incoming hand-over port", to.id))?;
+    Ok(NodeConnection {
+        id: canvas.connection_id(&from.id, &from_port.id, &to.id, &to_port.id),
+        from_node: from.id.clone(),
+        from_port: from_port.id.clone(),
+        to_node: to.id.clone(),
+        to_port: to_port.id.clone(),
+        connection_type: BOOT_STAGE_PORT_TYPE.to_string(),
+        color: Color::from_rgba8(0, 0, 0, 255),
+        line_width: 2.0,
+        description: format!("{} hands over to {}", from.component.display_name, to.component.display_name),
+        data_flow_info: DataFlowInfo {
+            data_type: BOOT_STAGE_PORT_TYPE.to_string(),
+            data_size: None,
+            flow_rate: None,
+            last_value_preview: None,
+            is_active: false,
+            transmission_time: Duration::ZERO,
+        },
+        is_highlighted: false,
+        is_selected: false,
+        label: None,
+        bend_points: Vec::new(),
+        animation_speed: 1.0,
+        show_data_flow: false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::architecture::KernelArchitecture;
+    
+    #[test]
+    fn test_boot_flow_validation_and_generation() {
+        let mut config = BuildConfig::default(KernelArchitecture::Monolithic);
+        let mut canvas = create_boot_flow_canvas().unwrap();
+        canvas.nodes.get_mut("boot_userspace").unwrap().properties.insert("services".to_string(), "sshd, netd".to_string());
+        canvas.nodes.get_mut("boot_initramfs").unwrap().properties.insert("modules".to_string(), "virtio_blk".to_string());
+        
+        // The default build has no initramfs step
+        let flow = BootFlow::from_canvas(&canvas).unwrap();
+        let messages: Vec<String> = flow.validate(&config).iter().map(|issue| issue.to_string()).collect();
+        assert_eq!(messages, vec!["error: initramfs: No enabled build step creates the initramfs"]);
+        
+        // Without the initramfs stage, kernel init has to hand over to userspace directly
+        canvas.remove_node("boot_initramfs").unwrap();
+        let flow = BootFlow::from_canvas(&canvas).unwrap();
+        let messages: Vec<String> = flow.validate(&config).iter().map(|issue| issue.to_string()).collect();
+        assert_eq!(messages, vec!["error: kernel init: Does not hand over to the userspace stage"]);
+        let kernel = canvas.nodes["boot_kernel_init"].clone();
+        let userspace = canvas.nodes["boot_userspace"].clone();
+        canvas.add_connection(handover(&canvas, &kernel, &userspace).unwrap()).unwrap();
+        let flow = BootFlow::from_canvas(&canvas).unwrap();
+        assert!(flow.validate(&config).is_empty());
+        
+        config.bootloader_config.kernel_params = vec!["console=ttyS0".to_string()];
+        assert_eq!(flow.kernel_cmdline(&config), "console=ttyS0 root=/dev/vda1 rootfstype=ext2 init=/sbin/init");
+        let files = flow.generate(&config);
+        let paths: Vec<&str> = files.iter().filter_map(|file| file.path.to_str()).collect();
+        assert_eq!(paths, vec!["bootloader/grub/grub.cfg", "rootfs/etc/inittab", "rootfs/etc/init.d/rcS"]);
+        assert!(files[0].content.contains("menuentry \"OSland\" {\n\tlinux /vmlinuz console=ttyS0 root=/dev/vda1 rootfstype=ext2 init=/sbin/init\n}\n"));
+        assert!(files[2].content.ends_with("hostname osland\n/etc/init.d/sshd start\n/etc/init.d/netd start\n"));
+    }
+}
@@ -0,0 +1,595 @@
+// Filesystem designer for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! Filesystems composed from components.
+//!
+//! A block layer node carries the on-disk geometry; journal and inode cache nodes
+//! connected to it add journaling and tune the in-memory inode and dentry caches.
+//! The design is checked against the limits of the ext2/ext4 on-disk format and
+//! turned into `mke2fs` parameters, a kernel config fragment, an fstab entry and
+//! sysctl settings, plus a console script mounting the image in a QEMU guest.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use crate::component_manager::component::{Component, ComponentCategory, ComponentPort, ComponentProperty, ComponentType, PortDirection};
+use crate::component_manager::ComponentLibrary;
+use crate::component_manager::visual_node::NodeCanvas;
+use crate::build_engine::test_orchestrator::{TestCase, TestKind, TestSuite};
+
+/// Port type connecting journal and inode cache nodes to the block layer
+pub const FS_BLOCKS_PORT_TYPE: &str = "FsBlocks";
+
+/// Smallest journal mke2fs creates, in filesystem blocks
+const MIN_JOURNAL_BLOCKS: u64 = 1024;
+
+/// Largest journal mke2fs creates, in filesystem blocks
+const MAX_JOURNAL_BLOCKS: u64 = 10_240_000;
+
+/// Page size the kernel mounting the filesystem runs with
+const PAGE_SIZE: u32 = 4096;
+
+/// Marker printed by the mount test once the probe file was read back
+const MOUNT_OK_MARKER: &str = "OSLAND_MOUNT_OK";
+
+const MIB: u64 = 1024 * 1024;
+
+/// Filesystem building block
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum FsComponentKind {
+    /// On-disk geometry: block size, inode size and inode density
+    BlockLayer,
+    
+    /// Metadata (or data) journal
+    Journal,
+    
+    /// In-memory inode and dentry cache
+    InodeCache,
+}
+
+impl FsComponentKind {
+    /// Every component kind
+    pub fn all() -> [FsComponentKind; 3] {
+        [FsComponentKind::BlockLayer, FsComponentKind::Journal, FsComponentKind::InodeCache]
+    }
+    
+    /// Component ID of the kind
+    pub fn component_id(&self) -> &'static str {
+        match self {
+            FsComponentKind::BlockLayer => "fs_block_layer",
+            FsComponentKind::Journal => "fs_journal",
+            FsComponentKind::InodeCache => "fs_inode_cache",
+        }
+    }
+    
+    /// Kind of a component
+    pub fn of_component(component_id: &str) -> Option<FsComponentKind> {
+        FsComponentKind::all().into_iter().find(|kind| kind.component_id() == component_id)
+    }
+}
+
+/// Data journaling mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum JournalMode {
+    /// Data is written before its metadata is committed
+    Ordered,
+    
+    /// Only metadata is journaled
+    Writeback,
+    
+    /// Data and metadata are journaled
+    Journal,
+}
+
+impl JournalMode {
+    /// Name used by the `data=` mount option
+    pub fn name(&self) -> &'static str {
+        match self {
+            JournalMode::Ordered => "ordered",
+            JournalMode::Writeback => "writeback",
+            JournalMode::Journal => "journal",
+        }
+    }
+    
+    /// Look a mode up by its name
+    pub fn from_name(name: &str) -> Option<Self> {
+        [JournalMode::Ordered, JournalMode::Writeback, JournalMode::Journal].into_iter().find(|mode| mode.name() == name.trim())
+    }
+}
+
+/// On-disk geometry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlockLayerConfig {
+    /// Image size in MiB
+    pub size_mb: u64,
+    
+    /// Block size in bytes
+    pub block_size: u32,
+    
+    /// Inode size in bytes
+    pub inode_size: u32,
+    
+    /// Bytes of disk space per inode
+    pub bytes_per_inode: u32,
+    
+    /// Blocks reserved for the superuser, in percent
+    pub reserved_percent: u32,
+}
+
+impl Default for BlockLayerConfig {
+    fn default() -> Self {
+        Self { size_mb: 64, block_size: 4096, inode_size: 256, bytes_per_inode: 16384, reserved_percent: 5 }
+    }
+}
+
+/// Journal settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JournalConfig {
+    /// Journal size in MiB
+    pub size_mb: u64,
+    
+    /// Data journaling mode
+    pub mode: JournalMode,
+    
+    /// Checksum metadata and journal blocks
+    pub checksums: bool,
+}
+
+impl Default for JournalConfig {
+    fn default() -> Self {
+        Self { size_mb: 8, mode: JournalMode::Ordered, checksums: true }
+    }
+}
+
+/// Inode and dentry cache settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InodeCacheConfig {
+    /// Reclaim pressure on the caches relative to the page cache (`vm.vfs_cache_pressure`)
+    pub cache_pressure: u32,
+    
+    /// Hashed b-tree directory indexes for fast lookups in large directories
+    pub dir_index: bool,
+}
+
+impl Default for InodeCacheConfig {
+    fn default() -> Self {
+        Self { cache_pressure: 100, dir_index: true }
+    }
+}
+
+/// Issue severity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FsIssueSeverity {
+    /// The image is not created
+    Error,
+    
+    /// The image is created
+    Warning,
+}
+
+/// Problem found in a filesystem design
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FsIssue {
+    /// Issue severity
+    pub severity: FsIssueSeverity,
+    
+    /// Issue message
+    pub message: String,
+}
+
+impl std::fmt::Display for FsIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let severity = match self.severity {
+            FsIssueSeverity::Error => "error",
+            FsIssueSeverity::Warning => "warning",
+        };
+        write!(f, "{}: {}", severity, self.message)
+    }
+}
+
+/// Filesystem composed from a block layer and optional journal and inode cache
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FilesystemDesign {
+    /// Volume label
+    pub label: String,
+    
+    /// On-disk geometry
+    pub block: BlockLayerConfig,
+    
+    /// Journal (ext4 when present, ext2 otherwise)
+    pub journal: Option<JournalConfig>,
+    
+    /// Inode cache tuning
+    pub inode_cache: Option<InodeCacheConfig>,
+}
+
+impl FilesystemDesign {
+    /// Create a design with the default block layer and no journal or inode cache
+    pub fn new(label: &str) -> Self {
+        Self { label: label.to_string(), block: BlockLayerConfig::default(), journal: None, inode_cache: None }
+    }
+    
+    /// Build the design from the filesystem nodes of a canvas
+    ///
+    /// The canvas must hold exactly one block layer; journal and inode cache nodes must be connected to it.
+    pub fn from_canvas(canvas: &NodeCanvas) -> Result<Self, String> {
+        let mut nodes: Vec<_> = canvas.nodes.values()
+            .filter_map(|node| FsComponentKind::of_component(&node.component_id).map(|kind| (kind, node)))
+            .collect();
+        nodes.sort_by(|a, b| a.1.id.cmp(&b.1.id));
+        
+        let block_nodes: Vec<_> = nodes.iter().filter(|(kind, _)| *kind == FsComponentKind::BlockLayer).collect();
+        let block_node = match block_nodes.as_slice() {
+            [(_, node)] => *node,
+            [] => return Err("The canvas has no filesystem block layer".to_string()),
+            _ => return Err(format!("The canvas has {} filesystem block layers; one is expected", block_nodes.len())),
+        };
+        let label = block_node.properties.get("label").cloned().unwrap_or_else(|| "osland".to_string());
+        let mut design = Self::new(&label);
+        
+        let number = |properties: &std::collections::HashMap<String, String>, name: &str| -> Result<Option<u64>, String> {
+            properties.get(name)
+                .map(|value| value.trim().parse::<u64>().map_err(|_| format!("Property '{}' must be a number, got '{}'", name, value)))
+                .transpose()
+        };
+        let number_u32 = |properties: &std::collections::HashMap<String, String>, name: &str| -> Result<Option<u32>, String> {
+            number(properties, name)?
+                .map(|value| u32::try_from(value).map_err(|_| format!("Property '{}' is out of range, got {}", name, value)))
+                .transpose()
+        };
+        let flag = |properties: &std::collections::HashMap<String, String>, name: &str| -> Option<bool> {
+            properties.get(name).map(|value| matches!(value.trim(), "true" | "yes" | "1"))
+        };
+        
+        let properties = &block_node.properties;
+        if let Some(value) = number(properties, "size_mb")? { design.block.size_mb = value; }
+        if let Some(value) = number_u32(properties, "block_size")? { design.block.block_size = value; }
+        if let Some(value) = number_u32(properties, "inode_size")? { design.block.inode_size = value; }
+        if let Some(value) = number_u32(properties, "bytes_per_inode")? { design.block.bytes_per_inode = value; }
+        if let Some(value) = number_u32(properties, "reserved_percent")? { design.block.reserved_percent = value; }
+        
+        for (kind, node) in &nodes {
+            if *kind == FsComponentKind::BlockLayer {
+                continue;
+            }
+            let connected = canvas.get_connections_for_node(&node.id).iter()
+                .any(|connection| connection.from_node == block_node.id || connection.to_node == block_node.id);
+            if !connected {
+                return Err(format!("Node '{}' is not connected to the block layer", node.id));
+            }
+            
+            let properties = &node.properties;
+            match kind {
+                FsComponentKind::Journal => {
+                    if design.journal.is_some() {
+                        return Err("The filesystem has more than one journal".to_string());
+                    }
+                    let mut journal = JournalConfig::default();
+                    if let Some(value) = number(properties, "size_mb")? { journal.size_mb = value; }
+                    if let Some(mode) = properties.get("mode") {
+                        journal.mode = JournalMode::from_name(mode).ok_or_else(|| format!("Unknown journal mode '{}'", mode))?;
+                    }
+                    if let Some(value) = flag(properties, "checksums") { journal.checksums = value; }
+                    design.journal = Some(journal);
+                }
+                FsComponentKind::InodeCache => {
+                    let mut cache = InodeCacheConfig::default();
+                    if let Some(value) = number_u32(properties, "cache_pressure")? { cache.cache_pressure = value; }
+                    if let Some(value) = flag(properties, "dir_index") { cache.dir_index = value; }
+                    design.inode_cache = Some(cache);
+                }
+                FsComponentKind::BlockLayer => {}
+            }
+        }
+        Ok(design)
+    }
+    
+    /// Filesystem type the design is created as
+    pub fn fs_type(&self) -> &'static str {
+        if self.journal.is_some() { "ext4" } else { "ext2" }
+    }
+    
+    /// Size of the image in bytes, if it fits in 64 bits
+    pub fn image_bytes(&self) -> Option<u64> {
+        self.block.size_mb.checked_mul(MIB)
+    }
+    
+    /// Number of filesystem blocks (0 when the image size overflows)
+    pub fn block_count(&self) -> u64 {
+        self.image_bytes().map_or(0, |bytes| bytes / self.block.block_size.max(1) as u64)
+    }
+    
+    /// Check the parameters against the limits of the on-disk format
+    pub fn validate(&self) -> Vec<FsIssue> {
+        let mut issues = Vec::new();
+        let mut error = |message: String| issues.push(FsIssue { severity: FsIssueSeverity::Error, message });
+        let block = &self.block;
+        
+        if !block.block_size.is_power_of_two() || !(1024..=65536).contains(&block.block_size) {
+            error(format!("Block size {} is not a power of two between 1024 and 65536", block.block_size));
+        }
+        if !block.inode_size.is_power_of_two() || block.inode_size < 128 || block.inode_size > block.block_size {
+            error(format!("Inode size {} is not a power of two between 128 and the block size", block.inode_size));
+        }
+        if block.bytes_per_inode < block.block_size {
+            error(format!("{} bytes per inode is less than the block size", block.bytes_per_inode));
+        }
+        if block.reserved_percent > 50 {
+            error(format!("{}% reserved blocks exceeds the 50% limit", block.reserved_percent));
+        }
+        if self.image_bytes().is_none() {
+            error(format!("A {} MiB image exceeds the 64-bit byte range", block.size_mb));
+        } else if self.block_count() < 64 {
+            error(format!("A {} MiB image holds only {} blocks", block.size_mb, self.block_count()));
+        }
+        
+        if let Some(journal) = &self.journal {
+            let journal_blocks = journal.size_mb.checked_mul(MIB).map_or(u64::MAX, |bytes| bytes / block.block_size.max(1) as u64);
+            if journal_blocks < MIN_JOURNAL_BLOCKS {
+                error(format!("A {} MiB journal has {} blocks, below the minimum of {}", journal.size_mb, journal_blocks, MIN_JOURNAL_BLOCKS));
+            } else if journal_blocks > MAX_JOURNAL_BLOCKS {
+                error(format!("A {} MiB journal has {} blocks, above the maximum of {}", journal.size_mb, journal_blocks, MAX_JOURNAL_BLOCKS));
+            }
+            if journal.size_mb.saturating_mul(2) > block.size_mb {
+                error(format!("The {} MiB journal takes more than half of the {} MiB image", journal.size_mb, block.size_mb));
+            }
+        }
+        
+        if block.block_size > PAGE_SIZE && block.block_size.is_power_of_two() {
+            issues.push(FsIssue {
+                severity: FsIssueSeverity::Warning,
+                message: format!("Block size {} exceeds the {}-byte page size; the kernel may refuse to mount it", block.block_size, PAGE_SIZE),
+            });
+        }
+        if let Some(journal) = &self.journal {
+            if journal.mode == JournalMode::Writeback {
+                issues.push(FsIssue {
+                    severity: FsIssueSeverity::Warning,
+                    message: "Writeback journaling can expose stale data in files after a crash".to_string(),
+                });
+            }
+        }
+        if self.inode_cache.as_ref().is_some_and(|cache| cache.cache_pressure == 0) {
+            issues.push(FsIssue {
+                severity: FsIssueSeverity::Warning,
+                message: "A cache pressure of 0 never reclaims inodes and dentries and can exhaust memory".to_string(),
+            });
+        }
+        issues
+    }
+    
+    /// `mke2fs` arguments, without the image path
+    pub fn mkfs_args(&self) -> Vec<String> {
+        let mut features = Vec::new();
+        match &self.journal {
+            Some(journal) => {
+                features.push("has_journal");
+                if journal.checksums {
+                    features.push("metadata_csum");
+                }
+            }
+            None => features.push("^has_journal"),
+        }
+        if let Some(cache) = &self.inode_cache {
+            features.push(if cache.dir_index { "dir_index" } else { "^dir_index" });
+        }
+        
+        let mut args = vec![
+            "-t".to_string(), self.fs_type().to_string(),
+            "-b".to_string(), self.block.block_size.to_string(),
+            "-I".to_string(), self.block.inode_size.to_string(),
+            "-i".to_string(), self.block.bytes_per_inode.to_string(),
+            "-m".to_string(), self.block.reserved_percent.to_string(),
+            "-L".to_string(), self.label.clone(),
+            "-O".to_string(), features.join(","),
+        ];
+        if let Some(journal) = &self.journal {
+            args.push("-J".to_string());
+            args.push(format!("size={}", journal.size_mb));
+        }
+        args
+    }
+    
+    /// Kernel config fragment for mounting the filesystem
+    pub fn kconfig(&self) -> String {
+        let mut lines = vec!["CONFIG_BLOCK=y"];
+        match &self.journal {
+            Some(journal) => {
+                lines.push("CONFIG_EXT4_FS=y");
+                lines.push("CONFIG_JBD2=y");
+                if journal.checksums {
+                    lines.push("CONFIG_CRYPTO_CRC32C=y");
+                }
+            }
+            None => lines.push("CONFIG_EXT2_FS=y"),
+        }
+        lines.push("");
+        lines.join("\n")
+    }
+    
+    /// Mount options of the filesystem
+    pub fn mount_options(&self) -> String {
+        match &self.journal {
+            Some(journal) => format!("defaults,data={}", journal.mode.name()),
+            None => "defaults".to_string(),
+        }
+    }
+    
+    /// fstab line mounting the filesystem by label
+    pub fn fstab_entry(&self, mount_point: &str) -> String {
+        format!("LABEL={}\t{}\t{}\t{}\t0\t2\n", self.label, mount_point, self.fs_type(), self.mount_options())
+    }
+    
+    /// sysctl settings of the inode cache
+    pub fn sysctl(&self) -> Option<String> {
+        self.inode_cache.as_ref().map(|cache| format!("vm.vfs_cache_pressure = {}\n", cache.cache_pressure))
+    }
+    
+    /// Write the mkfs script, kernel config fragment, fstab and sysctl settings into `dir`
+    pub fn write_artifacts<P: AsRef<Path>>(&self, dir: P) -> Result<Vec<PathBuf>, String> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        let script = format!("#!/bin/sh\nexec mke2fs -F {} \"$1\"\n", self.mkfs_args().iter().map(|arg| shell_quote(arg)).collect::<Vec<_>>().join(" "));
+        let mut files = vec![("mkfs.sh", script), ("filesystem.config", self.kconfig()), ("fstab", self.fstab_entry("/data"))];
+        if let Some(sysctl) = self.sysctl() {
+            files.push(("sysctl.conf", sysctl));
+        }
+        files.into_iter().map(|(name, content)| {
+            let path = dir.join(name);
+            fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+            Ok(path)
+        }).collect()
+    }
+    
+    /// Console test mounting the filesystem from `device` and reading a file back
+    pub fn mount_test_suite(&self, device: &str) -> TestSuite {
+        let command = format!(
+            "mkdir -p /mnt/osland && mount -t {} -o {} {} /mnt/osland && echo probe > /mnt/osland/probe && sync && grep -q probe /mnt/osland/probe && umount /mnt/osland && echo {}",
+            self.fs_type(), self.mount_options(), device, MOUNT_OK_MARKER
+        );
+        TestSuite {
+            name: "filesystem".to_string(),
+            tests: vec![TestCase {
+                name: format!("mount_{}", self.fs_type()),
+                kind: TestKind::SyscallSmoke { prompt: "# ".to_string(), command, expect: MOUNT_OK_MARKER.to_string() },
+                timeout_secs: None,
+            }],
+        }
+    }
+}
+
+fn shell_quote(arg: &str) -> String {
+    if arg.chars().all(|c| c.is_ascii_alphanumeric() || "-_=,^./".contains(c)) {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', "'\\''"))
+    }
+}
+
+/// Create the component of a filesystem building block, with its parameters as properties
+pub fn create_filesystem_component(kind: FsComponentKind) -> Component {
+    let property = |name: &str, value: String, property_type: &str, description: &str| ComponentProperty {
+        name: name.to_string(),
+        value: value.clone(),
+        property_type: property_type.to_string(),
+        description: description.to_string(),
+        required: false,
+        default_value: Some(value),
+        valid_values: None,
+    };
+    let blocks_port = |direction: PortDirection, description: &str| ComponentPort {
+        name: "blocks".to_string(),
+        port_type: FS_BLOCKS_PORT_TYPE.to_string(),
+        direction,
+        description: description.to_string(),
+    };
+    
+    let (display_name, description, properties, ports) = match kind {
+        FsComponentKind::BlockLayer => {
+            let defaults = BlockLayerConfig::default();
+            ("Filesystem Block Layer", "On-disk geometry of an ext2/ext4 filesystem", vec![
+                property("label", "osland".to_string(), "string", "Volume label"),
+                property("size_mb", defaults.size_mb.to_string(), "int", "Image size in MiB"),
+                property("block_size", defaults.block_size.to_string(), "int", "Block size in bytes"),
+                property("inode_size", defaults.inode_size.to_string(), "int", "Inode size in bytes"),
+                property("bytes_per_inode", defaults.bytes_per_inode.to_string(), "int", "Bytes of disk space per inode"),
+                property("reserved_percent", defaults.reserved_percent.to_string(), "int", "Blocks reserved for the superuser in percent"),
+            ], vec![blocks_port(PortDirection::Output, "Blocks used by the journal and caches")])
+        }
+        FsComponentKind::Journal => {
+            let defaults = JournalConfig::default();
+            ("Filesystem Journal", "JBD2 journal turning the filesystem into ext4", vec![
+                property("size_mb", defaults.size_mb.to_string(), "int", "Journal size in MiB"),
+                ComponentProperty {
+                    valid_values: Some(vec!["ordered".to_string(), "writeback".to_string(), "journal".to_string()]),
+                    ..property("mode", defaults.mode.name().to_string(), "string", "Data journaling mode")
+                },
+                property("checksums", defaults.checksums.to_string(), "bool", "Checksum metadata and journal blocks"),
+            ], vec![blocks_port(PortDirection::Input, "Block layer holding the journal")])
+        }
+        FsComponentKind::InodeCache => {
+            let defaults = InodeCacheConfig::default();
+            ("Inode Cache", "In-memory inode and dentry cache", vec![
+                property("cache_pressure", defaults.cache_pressure.to_string(), "int", "Reclaim pressure relative to the page cache"),
+                property("dir_index", defaults.dir_index.to_string(), "bool", "Hashed b-tree directory indexes"),
+            ], vec![blocks_port(PortDirection::Input, "Block layer whose inodes are cached")])
+        }
+    };
+    
+    Component {
+        id: kind.component_id().to_string(),
+        name: kind.component_id().to_string(),
+        display_name: display_name.to_string(),
+        component_type: ComponentType::FileSystem,
+        category: ComponentCategory::Storage,
+        version: "1.0.0".to_string(),
+        description: description.to_string(),
+        author: "OSland Team".to_string(),
+        source_url: None,
+        license: "MulanPSL-2.0".to_string(),
+        properties,
+        ports,
+        dependencies: Vec::new(),
+        supported_architectures: Default::default(),
+        supported_languages: vec!["c".to_string()],
+        implementation_files: Vec::new(),
+        build_commands: Vec::new(),
+        initialization_code: String::new(),
+    }
+}
+
+/// Add the filesystem building blocks to a library
+pub fn extend_with_filesystem_components(library: &mut ComponentLibrary) {
+    for kind in FsComponentKind::all() {
+        // Components already present are kept
+        let _ = library.add_component(create_filesystem_component(kind));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::build_engine::test_orchestrator::ConsoleStep;
+    
+    #[test]
+    fn test_validate_and_generate() {
+        let mut design = FilesystemDesign::new("data");
+        design.journal = Some(JournalConfig::default());
+        design.inode_cache = Some(InodeCacheConfig { cache_pressure: 50, dir_index: true });
+        assert!(design.validate().is_empty());
+        assert_eq!(
+            design.mkfs_args().join(" "),
+            "-t ext4 -b 4096 -I 256 -i 16384 -m 5 -L data -O has_journal,metadata_csum,dir_index -J size=8"
+        );
+        assert_eq!(design.kconfig(), "CONFIG_BLOCK=y\nCONFIG_EXT4_FS=y\nCONFIG_JBD2=y\nCONFIG_CRYPTO_CRC32C=y\n");
+        assert_eq!(design.fstab_entry("/data"), "LABEL=data\t/data\text4\tdefaults,data=ordered\t0\t2\n");
+        assert_eq!(design.sysctl().as_deref(), Some("vm.vfs_cache_pressure = 50\n"));
+        
+        // A 2 MiB journal on 4 KiB blocks is below the minimum, a 40 MiB one exceeds half the image
+        design.journal.as_mut().unwrap().size_mb = 2;
+        design.block.inode_size = 8192;
+        let messages: Vec<String> = design.validate().iter().map(|issue| issue.to_string()).collect();
+        assert_eq!(messages, vec![
+            "error: Inode size 8192 is not a power of two between 128 and the block size",
+            "error: A 2 MiB journal has 512 blocks, below the minimum of 1024",
+        ]);
+        design.block.inode_size = 256;
+        design.journal.as_mut().unwrap().size_mb = 40;
+        assert_eq!(design.validate()[0].message, "The 40 MiB journal takes more than half of the 64 MiB image");
+        
+        design.journal = None;
+        assert_eq!(design.fs_type(), "ext2");
+        assert!(design.mkfs_args().contains(&"^has_journal,dir_index".to_string()));
+        let suite = design.mount_test_suite("/dev/vdb");
+        assert!(suite.tests[0].kind.steps().iter().any(|step| matches!(step, ConsoleStep::Send(line) if line.contains("mount -t ext2 -o defaults /dev/vdb /mnt/osland"))));
+        
+        // Sizes whose byte count overflows are rejected instead of wrapping
+        design.block.size_mb = u64::MAX;
+        design.journal = Some(JournalConfig { size_mb: u64::MAX / 2, ..JournalConfig::default() });
+        assert_eq!(design.image_bytes(), None);
+        let messages: Vec<String> = design.validate().iter().map(|issue| issue.message.clone()).collect();
+        assert!(messages.contains(&format!("A {} MiB image exceeds the 64-bit byte range", u64::MAX)));
+        assert!(messages.iter().any(|message| message.contains("above the maximum")));
+    }
+}
@@ -0,0 +1,24 @@
+// OS design subsystems for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! Designers for the interfaces and policies of a kernel under construction.
+
+pub mod syscall_designer;
+pub mod scheduler_designer;
+pub mod allocator_designer;
+pub mod security_policy;
+pub mod virtualization;
+pub mod filesystem_designer;
+pub mod boot_flow;
+pub mod realtime;
+
+// Re-export core components
+pub use syscall_designer::{Syscall, SyscallArg, SyscallArgType, SyscallIssue, SyscallIssueSeverity, SyscallTable, GeneratedFile};
+pub use scheduler_designer::{SchedulerDesign, SchedulingPolicy, TaskPlacement, CpuScheduler, SyntheticTask, SchedulerMetrics, SchedulerSimulationConfig, simulate, generate_task_set};
+pub use allocator_designer::{AllocatorKind, AllocatorConfig, AllocationTrace, TraceEvent, AllocatorMetrics, simulate_allocator, compare_allocators, create_allocator_component, extend_with_allocator_components};
+pub use security_policy::{SecurityPolicy, TaskType, AccessRule, PolicyClass, PolicyFormat, PolicyContext, PolicyIssue, PolicyIssueSeverity, check_security_policies, create_security_policy_component, extend_with_security_policy_component};
+pub use virtualization::{VirtualizationMode, VirtualizationProfile, CpuVirtExtension, VirtioDevice, create_virt_extension_component, extend_with_virtualization_components};
+pub use filesystem_designer::{FilesystemDesign, FsComponentKind, BlockLayerConfig, JournalConfig, JournalMode, InodeCacheConfig, FsIssue, FsIssueSeverity, create_filesystem_component, extend_with_filesystem_components};
+pub use boot_flow::{BootFlow, BootStage, BootStageNode, BootIssue, BootIssueSeverity, create_boot_stage_component, extend_with_boot_stage_components, create_boot_flow_canvas};
+pub use realtime::{RtProfile, RtPolicy, RtIssue, RtIssueSeverity, RtFinding, SchedulabilityReport, CpuAnalysis, TaskVerdict, check_schedulability, tile_tasks, create_rt_profile_component, extend_with_realtime_components};
@@ -0,0 +1,578 @@
+// Real-time profile for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! WCET annotations and schedulability analysis.
+//!
+//! A canvas becomes a real-time project once it holds an `rt_profile` node. Any node
+//! (and any tile) can then describe a periodic task through the `rt.wcet_us`,
+//! `rt.period_us`, `rt.deadline_us` and `rt.cpu` properties. The task set is checked
+//! per CPU: rate- and deadline-monotonic priorities go through the Liu–Layland bound
+//! and, when that is inconclusive, exact response-time analysis; EDF uses the
+//! utilization test for implicit deadlines and the processor demand test otherwise.
+
+use std::collections::HashMap;
+use std::fmt;
+use serde::{Deserialize, Serialize};
+use crate::component_manager::component::{Component, ComponentCategory, ComponentProperty, ComponentType};
+use crate::component_manager::visual_node::NodeCanvas;
+use crate::component_manager::ComponentLibrary;
+use crate::tile_engine::tile_core::TileGraph;
+use super::scheduler_designer::SyntheticTask;
+
+/// Component ID of the real-time profile node
+pub const RT_PROFILE_ID: &str = "rt_profile";
+
+/// Worst-case execution time of a task in microseconds
+pub const RT_WCET_PROPERTY: &str = "rt.wcet_us";
+
+/// Release period of a task in microseconds
+pub const RT_PERIOD_PROPERTY: &str = "rt.period_us";
+
+/// Relative deadline of a task in microseconds (defaults to the period)
+pub const RT_DEADLINE_PROPERTY: &str = "rt.deadline_us";
+
+/// CPU a task is bound to
+pub const RT_CPU_PROPERTY: &str = "rt.cpu";
+
+/// Absolute deadlines checked by the processor demand test before giving up
+const MAX_DEMAND_POINTS: usize = 100_000;
+
+/// Real-time scheduling policy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RtPolicy {
+    /// Fixed priorities, shorter period first
+    RateMonotonic,
+    
+    /// Fixed priorities, shorter deadline first
+    DeadlineMonotonic,
+    
+    /// Earliest deadline first
+    Edf,
+}
+
+impl RtPolicy {
+    /// Short name used in node properties
+    pub fn name(&self) -> &'static str {
+        match self {
+            RtPolicy::RateMonotonic => "rm",
+            RtPolicy::DeadlineMonotonic => "dm",
+            RtPolicy::Edf => "edf",
+        }
+    }
+    
+    /// Policy with a short name
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "rm" | "rate_monotonic" => Some(RtPolicy::RateMonotonic),
+            "dm" | "deadline_monotonic" => Some(RtPolicy::DeadlineMonotonic),
+            "edf" => Some(RtPolicy::Edf),
+            _ => None,
+        }
+    }
+}
+
+/// Issue severity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RtIssueSeverity {
+    /// The task set is not schedulable or not analysable
+    Error,
+    
+    /// The task set is analysed anyway
+    Warning,
+}
+
+/// Problem found in the real-time annotations
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RtIssue {
+    /// Issue severity
+    pub severity: RtIssueSeverity,
+    
+    /// Issue message
+    pub message: String,
+}
+
+impl fmt::Display for RtIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            RtIssueSeverity::Error => "error",
+            RtIssueSeverity::Warning => "warning",
+        };
+        write!(f, "{}: {}", severity, self.message)
+    }
+}
+
+/// Real-time issue attributed to a canvas node
+#[derive(Debug, Clone, PartialEq)]
+pub struct RtFinding {
+    /// Annotated node (or the profile node)
+    pub node_id: String,
+    
+    /// Issue
+    pub issue: RtIssue,
+}
+
+/// Verdict for one task
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaskVerdict {
+    /// Task name
+    pub task: String,
+    
+    /// CPU the task was placed on
+    pub cpu: usize,
+    
+    /// Worst-case response time in microseconds (fixed priorities, when it is within the deadline)
+    pub response_time_us: Option<u64>,
+    
+    /// Whether every job meets its deadline
+    pub schedulable: bool,
+}
+
+/// Analysis of the tasks placed on one CPU
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CpuAnalysis {
+    /// CPU index
+    pub cpu: usize,
+    
+    /// Total utilization of the CPU
+    pub utilization: f64,
+    
+    /// Test that decided the outcome
+    pub test: String,
+    
+    /// Reason the CPU is not schedulable
+    pub failure: Option<String>,
+}
+
+/// Result of a schedulability analysis
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SchedulabilityReport {
+    /// Policy analysed
+    pub policy: RtPolicy,
+    
+    /// Per-CPU results
+    pub cpus: Vec<CpuAnalysis>,
+    
+    /// Per-task results, in input order
+    pub tasks: Vec<TaskVerdict>,
+}
+
+impl SchedulabilityReport {
+    /// Check whether every task meets its deadlines
+    pub fn is_schedulable(&self) -> bool {
+        self.tasks.iter().all(|verdict| verdict.schedulable)
+    }
+}
+
+/// Real-time project profile
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RtProfile {
+    /// Scheduling policy of the real-time class
+    pub policy: RtPolicy,
+    
+    /// Number of CPUs tasks are partitioned over
+    pub cpus: usize,
+}
+
+impl RtProfile {
+    /// Create a profile
+    pub fn new(policy: RtPolicy, cpus: usize) -> Self {
+        Self { policy, cpus: cpus.max(1) }
+    }
+    
+    /// Profile of a canvas, if it has a real-time profile node
+    pub fn from_canvas(canvas: &NodeCanvas) -> Result<Option<Self>, String> {
+        let mut nodes: Vec<_> = canvas.nodes.values().filter(|node| node.component_id == RT_PROFILE_ID).collect();
+        nodes.sort_by(|a, b| a.id.cmp(&b.id));
+        let node = match nodes.as_slice() {
+            [] => return Ok(None),
+            [node] => *node,
+            _ => return Err(format!("The canvas has {} real-time profiles; one is expected", nodes.len())),
+        };
+        
+        let policy = match node.properties.get("policy") {
+            Some(name) => RtPolicy::from_name(name).ok_or_else(|| format!("Unknown real-time policy '{}' (expected rm, dm or edf)", name))?,
+            None => RtPolicy::RateMonotonic,
+        };
+        let cpus = match node.properties.get("cpus") {
+            Some(value) => value.trim().parse::<usize>().ok().filter(|cpus| *cpus > 0)
+                .ok_or_else(|| format!("Property 'cpus' must be a positive number, got '{}'", value))?,
+            None => 1,
+        };
+        Ok(Some(Self::new(policy, cpus)))
+    }
+    
+    /// Analyse a task set
+    ///
+    /// Bound tasks stay on their CPU; the others are placed on the least loaded CPU
+    /// in decreasing utilization order.
+    pub fn analyze(&self, tasks: &[SyntheticTask]) -> Result<SchedulabilityReport, String> {
+        let mut placement = vec![0usize; tasks.len()];
+        let mut load = vec![0.0f64; self.cpus];
+        for (index, task) in tasks.iter().enumerate() {
+            if task.period_us == 0 {
+                return Err(format!("Task '{}' has a period of zero", task.name));
+            }
+            if let Some(cpu) = task.cpu {
+                if cpu >= self.cpus {
+                    return Err(format!("Task '{}' is bound to CPU {} but the profile has {} CPU(s)", task.name, cpu, self.cpus));
+                }
+                placement[index] = cpu;
+                load[cpu] += task.utilization();
+            }
+        }
+        let mut unbound: Vec<usize> = (0..tasks.len()).filter(|index| tasks[*index].cpu.is_none()).collect();
+        unbound.sort_by(|a, b| tasks[*b].utilization().total_cmp(&tasks[*a].utilization()).then(a.cmp(b)));
+        for index in unbound {
+            let cpu = (0..self.cpus).min_by(|a, b| load[*a].total_cmp(&load[*b])).unwrap_or(0);
+            placement[index] = cpu;
+            load[cpu] += tasks[index].utilization();
+        }
+        
+        let mut verdicts: Vec<TaskVerdict> = tasks.iter().zip(&placement)
+            .map(|(task, cpu)| TaskVerdict { task: task.name.clone(), cpu: *cpu, response_time_us: None, schedulable: true })
+            .collect();
+        let mut cpus = Vec::new();
+        for cpu in 0..self.cpus {
+            let indices: Vec<usize> = (0..tasks.len()).filter(|index| placement[*index] == cpu).collect();
+            let analysis = match self.policy {
+                RtPolicy::Edf => analyze_edf(cpu, tasks, &indices, &mut verdicts),
+                _ => analyze_fixed_priority(self.policy, cpu, tasks, &indices, &mut verdicts),
+            };
+            cpus.push(analysis);
+        }
+        Ok(SchedulabilityReport { policy: self.policy, cpus, tasks: verdicts })
+    }
+}
+
+/// Fixed priority analysis: Liu–Layland bound, then response-time analysis
+fn analyze_fixed_priority(policy: RtPolicy, cpu: usize, tasks: &[SyntheticTask], indices: &[usize], verdicts: &mut [TaskVerdict]) -> CpuAnalysis {
+    let mut order = indices.to_vec();
+    order.sort_by_key(|index| {
+        let task = &tasks[*index];
+        let key = if policy == RtPolicy::RateMonotonic { task.period_us } else { task.deadline() };
+        (key, task.name.clone())
+    });
+    let utilization: f64 = order.iter().map(|index| tasks[*index].utilization()).sum();
+    let count = order.len() as f64;
+    let implicit = order.iter().all(|index| tasks[*index].deadline() == tasks[*index].period_us);
+    let bound_holds = implicit && utilization <= count * (2f64.powf(1.0 / count) - 1.0);
+    
+    let mut failure = None;
+    for (position, index) in order.iter().enumerate() {
+        let task = &tasks[*index];
+        let higher = &order[..position];
+        let deadline = task.deadline();
+        
+        // The response time only converges while the task and those above it fit on the CPU
+        let level_utilization: f64 = task.utilization() + higher.iter().map(|other| tasks[*other].utilization()).sum::<f64>();
+        let response = if level_utilization <= 1.0 { response_time(task, higher.iter().map(|other| &tasks[*other]), deadline) } else { None };
+        let schedulable = response.is_some();
+        verdicts[*index].response_time_us = response;
+        verdicts[*index].schedulable = schedulable;
+        if !schedulable && failure.is_none() {
+            let response = response.map_or("above the deadline".to_string(), |response| format!("{} us", response));
+            failure = Some(format!("Task '{}' misses its {} us deadline (response time {})", task.name, deadline, response));
+        }
+    }
+    
+    let test = if bound_holds { "Liu-Layland bound" } else { "response-time analysis" };
+    CpuAnalysis { cpu, utilization, test: test.to_string(), failure }
+}
+
+/// Response time of a task below the given higher priority tasks
+///
+/// R = C + sum over higher priority tasks of ceil(R / T) * C, iterated to a fixed point.
+/// `R` only grows, so the iteration stops as soon as it passes the deadline; `None`
+/// means the deadline is missed (or the demand overflows, which misses it too).
+fn response_time<'a>(task: &SyntheticTask, higher: impl Iterator<Item = &'a SyntheticTask> + Clone, deadline: u64) -> Option<u64> {
+    let mut response = task.wcet_us;
+    while response <= deadline {
+        let mut next = Some(task.wcet_us);
+        for other in higher.clone() {
+            next = next.and_then(|next| response.div_ceil(other.period_us).checked_mul(other.wcet_us)?.checked_add(next));
+        }
+        let next = next?;
+        if next == response {
+            return Some(response);
+        }
+        response = next;
+    }
+    None
+}
+
+/// EDF analysis: utilization test, or processor demand for constrained deadlines
+fn analyze_edf(cpu: usize, tasks: &[SyntheticTask], indices: &[usize], verdicts: &mut [TaskVerdict]) -> CpuAnalysis {
+    let set: Vec<&SyntheticTask> = indices.iter().map(|index| &tasks[*index]).collect();
+    let utilization: f64 = set.iter().map(|task| task.utilization()).sum();
+    let mut fail = |failure: String, test: &str| {
+        for index in indices {
+            verdicts[*index].schedulable = false;
+        }
+        CpuAnalysis { cpu, utilization, test: test.to_string(), failure: Some(failure) }
+    };
+    
+    if utilization > 1.0 {
+        return fail(format!("Utilization {:.3} exceeds 1", utilization), "utilization");
+    }
+    if set.iter().all(|task| task.deadline() >= task.period_us) {
+        return CpuAnalysis { cpu, utilization, test: "utilization".to_string(), failure: None };
+    }
+    
+    // Synchronous busy period: L = sum of ceil(L / T) * C, iterated to a fixed point.
+    // With U <= 1 it is bounded by the hyperperiod, but that may not fit in 64 bits
+    let overflow = |busy: u64| format!("Processor demand overflows beyond a busy period of {} us", busy);
+    let Some(mut busy) = set.iter().try_fold(0u64, |sum, task| sum.checked_add(task.wcet_us)) else {
+        return fail(overflow(0), "processor demand");
+    };
+    loop {
+        let next = set.iter().try_fold(0u64, |sum, task| busy.div_ceil(task.period_us).checked_mul(task.wcet_us)?.checked_add(sum));
+        match next {
+            Some(next) if next == busy => break,
+            Some(next) => busy = next,
+            None => return fail(overflow(busy), "processor demand"),
+        }
+    }
+    
+    let mut deadlines: Vec<u64> = Vec::new();
+    for task in &set {
+        let mut deadline = task.deadline();
+        while deadline <= busy {
+            deadlines.push(deadline);
+            if deadlines.len() > MAX_DEMAND_POINTS {
+                return fail(format!("Processor demand test needs more than {} deadlines (busy period {} us)", MAX_DEMAND_POINTS, busy), "processor demand");
+            }
+            match deadline.checked_add(task.period_us) {
+                Some(next) => deadline = next,
+                None => break,
+            }
+        }
+    }
+    deadlines.sort_unstable();
+    deadlines.dedup();
+    for t in deadlines {
+        let demand = set.iter()
+            .filter(|task| task.deadline() <= t)
+            .try_fold(0u64, |sum, task| ((t - task.deadline()) / task.period_us + 1).checked_mul(task.wcet_us)?.checked_add(sum));
+        let Some(demand) = demand else {
+            return fail(format!("Processor demand overflows within the first {} us", t), "processor demand");
+        };
+        if demand > t {
+            return fail(format!("Processor demand of {} us exceeds the first {} us", demand, t), "processor demand");
+        }
+    }
+    CpuAnalysis { cpu, utilization, test: "processor demand".to_string(), failure: None }
+}
+
+/// Task described by the real-time properties of a node or tile
+fn annotated_task(name: &str, properties: &HashMap<String, String>) -> Result<Option<SyntheticTask>, RtIssue> {
+    let error = |message: String| RtIssue { severity: RtIssueSeverity::Error, message };
+    let number = |property: &str| -> Result<Option<u64>, RtIssue> {
+        properties.get(property)
+            .map(|value| value.trim().parse::<u64>().map_err(|_| error(format!("Property '{}' must be a number, got '{}'", property, value))))
+            .transpose()
+    };
+    
+    let wcet = number(RT_WCET_PROPERTY)?;
+    let period = number(RT_PERIOD_PROPERTY)?;
+    let deadline = number(RT_DEADLINE_PROPERTY)?;
+    let cpu = number(RT_CPU_PROPERTY)?;
+    let (wcet, period) = match (wcet, period) {
+        (Some(wcet), Some(period)) => (wcet, period),
+        (None, None) if deadline.is_none() => return Ok(None),
+        (None, _) => return Err(RtIssue {
+            severity: RtIssueSeverity::Warning,
+            message: format!("'{}' has timing annotations but no WCET; it is not analysed", name),
+        }),
+        (Some(_), None) => return Err(error(format!("'{}' has a WCET but no period", name))),
+    };
+    if wcet == 0 || period == 0 {
+        return Err(error(format!("'{}' needs a non-zero WCET and period", name)));
+    }
+    if deadline.is_some_and(|deadline| deadline < wcet) {
+        return Err(error(format!("'{}' has a deadline shorter than its WCET", name)));
+    }
+    
+    let mut task = SyntheticTask::new(name, period, wcet);
+    task.deadline_us = deadline;
+    task.cpu = cpu.map(|cpu| cpu as usize);
+    Ok(Some(task))
+}
+
+/// Tasks annotated on the tiles of a graph, ordered by tile ID
+pub fn tile_tasks(graph: &TileGraph) -> Result<Vec<SyntheticTask>, String> {
+    let mut tiles: Vec<_> = graph.tiles.values().collect();
+    tiles.sort_by(|a, b| a.id.cmp(&b.id));
+    let mut tasks = Vec::new();
+    for tile in tiles {
+        match annotated_task(&tile.id, &tile.properties) {
+            Ok(Some(task)) => tasks.push(task),
+            Ok(None) => {}
+            Err(issue) if issue.severity == RtIssueSeverity::Warning => {}
+            Err(issue) => return Err(issue.message),
+        }
+    }
+    Ok(tasks)
+}
+
+/// Check the annotated nodes of a canvas against its real-time profile
+///
+/// Canvases without a real-time profile are not checked.
+pub fn check_schedulability(canvas: &NodeCanvas) -> Vec<RtFinding> {
+    let profile_node = canvas.nodes.values().filter(|node| node.component_id == RT_PROFILE_ID).map(|node| node.id.clone()).min();
+    let Some(profile_node) = profile_node else {
+        return Vec::new();
+    };
+    let error = |node_id: &str, message: String| RtFinding {
+        node_id: node_id.to_string(),
+        issue: RtIssue { severity: RtIssueSeverity::Error, message },
+    };
+    let profile = match RtProfile::from_canvas(canvas) {
+        Ok(profile) => profile.expect("the canvas has a profile node"),
+        Err(message) => return vec![error(&profile_node, message)],
+    };
+    
+    let mut nodes: Vec<_> = canvas.nodes.values().filter(|node| node.component_id != RT_PROFILE_ID).collect();
+    nodes.sort_by(|a, b| a.id.cmp(&b.id));
+    let mut findings = Vec::new();
+    let mut tasks = Vec::new();
+    for node in nodes {
+        match annotated_task(&node.id, &node.properties) {
+            Ok(Some(task)) => tasks.push(task),
+            Ok(None) => {}
+            Err(issue) => findings.push(RtFinding { node_id: node.id.clone(), issue }),
+        }
+    }
+    
+    let report = match profile.analyze(&tasks) {
+        Ok(report) => report,
+        Err(message) => {
+            findings.push(error(&profile_node, message));
+            return findings;
+        }
+    };
+    for verdict in report.tasks.iter().filter(|verdict| !verdict.schedulable) {
+        let task = tasks.iter().find(|task| task.name == verdict.task).expect("verdicts follow the task set");
+        let message = if profile.policy == RtPolicy::Edf {
+            let failure = report.cpus[verdict.cpu].failure.clone().unwrap_or_default();
+            format!("Not schedulable on CPU {} under edf: {}", verdict.cpu, failure)
+        } else {
+            let response = verdict.response_time_us.map_or("above the deadline".to_string(), |response| format!("{} us", response));
+            format!("Misses its {} us deadline on CPU {} under {} (response time {})", task.deadline(), verdict.cpu, profile.policy.name(), response)
+        };
+        findings.push(error(&verdict.task, message));
+    }
+    findings
+}
+
+/// Create the real-time profile component
+pub fn create_rt_profile_component() -> Component {
+    let property = |name: &str, value: &str, description: &str, valid_values: Option<Vec<String>>| ComponentProperty {
+        name: name.to_string(),
+        value: value.to_string(),
+        property_type: if valid_values.is_some() { "enum" } else { "number" }.to_string(),
+        description: description.to_string(),
+        required: false,
+        default_value: Some(value.to_string()),
+        valid_values,
+    };
+    
+    Component {
+        id: RT_PROFILE_ID.to_string(),
+        name: RT_PROFILE_ID.to_string(),
+        display_name: "Real-Time Profile".to_string(),
+        component_type: ComponentType::Scheduler,
+        category: ComponentCategory::KernelCore,
+        version: "1.0.0".to_string(),
+        description: "Enables WCET annotations and schedulability analysis for the canvas".to_string(),
+        author: "OSland Team".to_string(),
+        source_url: None,
+        license: "MulanPSL-2.0".to_string(),
+        properties: vec![
+            property("policy", "rm", "Real-time scheduling policy", Some(vec!["rm".to_string(), "dm".to_string(), "edf".to_string()])),
+            property("cpus", "1", "Number of CPUs tasks are partitioned over", None),
+        ],
+        ports: Vec::new(),
+        dependencies: Vec::new(),
+        supported_architectures: Default::default(),
+        supported_languages: Vec::new(),
+        implementation_files: Vec::new(),
+        build_commands: Vec::new(),
+        initialization_code: String::new(),
+    }
+}
+
+/// Add the real-time profile component to an existing library
+pub fn extend_with_realtime_components(library: &mut ComponentLibrary) {
+    // Components already present are kept
+    let _ = library.add_component(create_rt_profile_component());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_schedulability_analysis() {
+        // U = 0.85 is above the Liu-Layland bound for three tasks, but response times fit
+        let tasks = vec![
+            SyntheticTask::new("sensor", 4_000, 1_000),
+            SyntheticTask::new("control", 5_000, 1_000),
+            SyntheticTask::new("logger", 20_000, 8_000),
+        ];
+        let report = RtProfile::new(RtPolicy::RateMonotonic, 1).analyze(&tasks).unwrap();
+        assert!(report.is_schedulable());
+        assert_eq!(report.cpus[0].test, "response-time analysis");
+        assert_eq!(report.tasks.iter().map(|verdict| verdict.response_time_us.unwrap()).collect::<Vec<_>>(), vec![1_000, 2_000, 15_000]);
+        
+        // A tighter deadline breaks rate-monotonic but not EDF, which the demand test confirms
+        let mut tight = tasks.clone();
+        tight[2].deadline_us = Some(14_000);
+        assert!(!RtProfile::new(RtPolicy::RateMonotonic, 1).analyze(&tight).unwrap().tasks[2].schedulable);
+        let edf = RtProfile::new(RtPolicy::Edf, 1).analyze(&tight).unwrap();
+        assert!(edf.is_schedulable());
+        assert_eq!(edf.cpus[0].test, "processor demand");
+        tight[2].deadline_us = Some(9_000);
+        let edf = RtProfile::new(RtPolicy::Edf, 1).analyze(&tight).unwrap();
+        assert_eq!(edf.cpus[0].failure.as_deref(), Some("Processor demand of 11000 us exceeds the first 9000 us"));
+        
+        // Two CPUs split the load; a task bound beyond them is rejected
+        let mut heavy = tasks.clone();
+        heavy.push(SyntheticTask::new("video", 10_000, 6_000));
+        assert!(!RtProfile::new(RtPolicy::Edf, 1).analyze(&heavy).unwrap().is_schedulable());
+        assert!(RtProfile::new(RtPolicy::Edf, 2).analyze(&heavy).unwrap().is_schedulable());
+        heavy[0].cpu = Some(2);
+        assert!(RtProfile::new(RtPolicy::Edf, 2).analyze(&heavy).unwrap_err().contains("bound to CPU 2"));
+        
+        let mut properties = HashMap::new();
+        properties.insert(RT_WCET_PROPERTY.to_string(), "500".to_string());
+        assert_eq!(annotated_task("irq", &properties).unwrap_err().to_string(), "error: 'irq' has a WCET but no period");
+    }
+    
+    #[test]
+    fn test_overflowing_demand_is_unschedulable() {
+        // U = 1, but the response time and the busy period converge on 2^64
+        let tasks = vec![
+            SyntheticTask::new("tick", 2, 1),
+            SyntheticTask::new("batch", u64::MAX, 1 << 63),
+        ];
+        let report = RtProfile::new(RtPolicy::RateMonotonic, 1).analyze(&tasks).unwrap();
+        assert!(report.tasks[0].schedulable);
+        assert!(!report.tasks[1].schedulable);
+        assert_eq!(report.tasks[1].response_time_us, None);
+        
+        let mut constrained = tasks.clone();
+        constrained[1].deadline_us = Some(u64::MAX - 1);
+        let edf = RtProfile::new(RtPolicy::Edf, 1).analyze(&constrained).unwrap();
+        assert!(!edf.is_schedulable());
+        assert!(edf.cpus[0].failure.as_deref().unwrap().starts_with("Processor demand overflows"));
+        
+        // A response time past the deadline stops the iteration
+        let tight = vec![
+            SyntheticTask::new("tick", 2, 1),
+            SyntheticTask { deadline_us: Some(3), ..SyntheticTask::new("batch", 1 << 40, 2) },
+        ];
+        let report = RtProfile::new(RtPolicy::DeadlineMonotonic, 1).analyze(&tight).unwrap();
+        assert!(!report.tasks[1].schedulable);
+    }
+}
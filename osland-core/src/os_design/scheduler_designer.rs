@@ -0,0 +1,558 @@
+// Scheduler policy designer for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! Scheduler design and simulation.
+//!
+//! A scheduler design assigns a policy (round robin, CFS-like fair sharing or EDF) to
+//! every CPU tile and chooses whether tasks are partitioned onto CPUs or dispatched
+//! globally. The design is simulated tick by tick against a synthetic periodic task
+//! set to estimate latency, deadline misses and fairness before it is turned into the
+//! scheduler configuration used by the build.
+
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+use crate::tile_engine::tile_core::{TileGraph, TileType};
+
+/// Tile property selecting the policy of a CPU tile (`rr`, `fair` or `edf`)
+pub const SCHED_POLICY_PROPERTY: &str = "sched.policy";
+
+/// Tile property holding the round robin quantum in microseconds
+pub const SCHED_QUANTUM_PROPERTY: &str = "sched.quantum_us";
+
+/// Tile property holding the fair scheduler's minimum granularity in microseconds
+pub const SCHED_GRANULARITY_PROPERTY: &str = "sched.min_granularity_us";
+
+/// Load weight of a nice 0 task
+const NICE_0_WEIGHT: u64 = 1024;
+
+/// Scheduling policy of a CPU
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SchedulingPolicy {
+    /// Round robin with a fixed time slice
+    RoundRobin { quantum_us: u64 },
+    
+    /// Weighted fair sharing by virtual runtime, like CFS
+    Fair { min_granularity_us: u64 },
+    
+    /// Earliest deadline first, preemptive
+    Edf,
+}
+
+impl SchedulingPolicy {
+    /// Short name used in tile properties and generated configuration
+    pub fn name(&self) -> &'static str {
+        match self {
+            SchedulingPolicy::RoundRobin { .. } => "rr",
+            SchedulingPolicy::Fair { .. } => "fair",
+            SchedulingPolicy::Edf => "edf",
+        }
+    }
+    
+    /// Policy named by a tile property, with parameters from the tile or defaults
+    pub fn from_name(name: &str, quantum_us: Option<u64>, min_granularity_us: Option<u64>) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "rr" | "round_robin" => Some(SchedulingPolicy::RoundRobin { quantum_us: quantum_us.unwrap_or(4_000) }),
+            "fair" | "cfs" => Some(SchedulingPolicy::Fair { min_granularity_us: min_granularity_us.unwrap_or(750) }),
+            "edf" => Some(SchedulingPolicy::Edf),
+            _ => None,
+        }
+    }
+}
+
+/// How tasks are distributed over CPUs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TaskPlacement {
+    /// Every task is bound to one CPU (its affinity, else worst-fit by utilization)
+    Partitioned,
+    
+    /// Every job is dispatched to the least loaded CPU when released
+    Global,
+}
+
+/// Scheduler configuration of one CPU
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CpuScheduler {
+    /// CPU tile ID or name
+    pub cpu: String,
+    
+    /// Policy of the CPU
+    pub policy: SchedulingPolicy,
+}
+
+/// Scheduler design of a kernel
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SchedulerDesign {
+    /// Design name
+    pub name: String,
+    
+    /// Task placement
+    pub placement: TaskPlacement,
+    
+    /// CPUs in CPU number order
+    pub cpus: Vec<CpuScheduler>,
+}
+
+impl SchedulerDesign {
+    /// Create a design using one policy on every CPU
+    pub fn uniform(name: impl Into<String>, cpu_count: usize, policy: SchedulingPolicy, placement: TaskPlacement) -> Self {
+        let cpus = (0..cpu_count).map(|cpu| CpuScheduler { cpu: format!("cpu{}", cpu), policy: policy.clone() }).collect();
+        Self { name: name.into(), placement, cpus }
+    }
+    
+    /// Create a design from the processing tiles of a hardware canvas
+    ///
+    /// Tiles are numbered in name order; tiles without a `sched.policy` property use
+    /// the default policy.
+    pub fn from_tile_graph(graph: &TileGraph, default_policy: SchedulingPolicy, placement: TaskPlacement) -> Result<Self, String> {
+        let mut tiles: Vec<_> = graph.tiles.values().filter(|tile| matches!(tile.tile_type, TileType::Processing)).collect();
+        tiles.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.id.cmp(&b.id)));
+        
+        let mut cpus = Vec::new();
+        for tile in tiles {
+            let number = |property: &str| tile.get_property(property).and_then(|value| value.parse::<u64>().ok());
+            let policy = match tile.get_property(SCHED_POLICY_PROPERTY) {
+                Some(name) => SchedulingPolicy::from_name(name, number(SCHED_QUANTUM_PROPERTY), number(SCHED_GRANULARITY_PROPERTY))
+                    .ok_or_else(|| format!("Unknown scheduling policy '{}' on tile '{}'", name, tile.name))?,
+                None => default_policy.clone(),
+            };
+            cpus.push(CpuScheduler { cpu: tile.id.clone(), policy });
+        }
+        if cpus.is_empty() {
+            return Err("The tile graph has no processing tiles".to_string());
+        }
+        
+        Ok(Self { name: graph.name.clone(), placement, cpus })
+    }
+    
+    /// Load a design from a JSON file
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        serde_json::from_str(&content).map_err(|e| format!("Failed to parse {}: {}", path.display(), e))
+    }
+    
+    /// Save the design as JSON
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), String> {
+        let content = serde_json::to_string_pretty(self).map_err(|e| format!("Failed to serialize scheduler design: {}", e))?;
+        fs::write(path, content).map_err(|e| format!("Failed to write scheduler design: {}", e))
+    }
+    
+    /// Check the design can be simulated and built
+    pub fn validate(&self) -> Result<(), String> {
+        if self.cpus.is_empty() {
+            return Err(format!("Scheduler design '{}' has no CPUs", self.name));
+        }
+        for cpu in &self.cpus {
+            match cpu.policy {
+                SchedulingPolicy::RoundRobin { quantum_us: 0 } => return Err(format!("CPU '{}' has a zero quantum", cpu.cpu)),
+                SchedulingPolicy::Fair { min_granularity_us: 0 } => return Err(format!("CPU '{}' has a zero granularity", cpu.cpu)),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+    
+    /// Kernel config fragment and C header describing the design
+    pub fn generate_config(&self) -> Result<SchedulerArtifacts, String> {
+        self.validate()?;
+        
+        let mut kconfig = format!("# Scheduler '{}' generated by OSland\nCONFIG_NR_CPUS={}\n", self.name, self.cpus.len());
+        for policy in ["rr", "fair", "edf"] {
+            if self.cpus.iter().any(|cpu| cpu.policy.name() == policy) {
+                kconfig.push_str(&format!("CONFIG_OSL_SCHED_{}=y\n", policy.to_uppercase()));
+            }
+        }
+        kconfig.push_str(match self.placement {
+            TaskPlacement::Partitioned => "CONFIG_OSL_SCHED_PARTITIONED=y\n",
+            TaskPlacement::Global => "CONFIG_OSL_SCHED_GLOBAL=y\n",
+        });
+        
+        let mut header = format!(
+            "/* Scheduler '{}' generated by OSland */\n#ifndef OSLAND_SCHED_CONFIG_H\n#define OSLAND_SCHED_CONFIG_H\n\n#define SCHED_POLICY_RR 0\n#define SCHED_POLICY_FAIR 1\n#define SCHED_POLICY_EDF 2\n\n#define SCHED_NR_CPUS {}\n#define SCHED_PARTITIONED {}\n\n",
+            self.name, self.cpus.len(), (self.placement == TaskPlacement::Partitioned) as u8);
+        header.push_str("struct osl_sched_cpu_config {\n    unsigned int policy;\n    unsigned long quantum_us;\n    unsigned long min_granularity_us;\n};\n\n");
+        header.push_str("static const struct osl_sched_cpu_config sched_cpu_config[SCHED_NR_CPUS] = {\n");
+        for cpu in &self.cpus {
+            let (policy, quantum, granularity) = match cpu.policy {
+                SchedulingPolicy::RoundRobin { quantum_us } => ("SCHED_POLICY_RR", quantum_us, 0),
+                SchedulingPolicy::Fair { min_granularity_us } => ("SCHED_POLICY_FAIR", 0, min_granularity_us),
+                SchedulingPolicy::Edf => ("SCHED_POLICY_EDF", 0, 0),
+            };
+            header.push_str(&format!("    {{ .policy = {}, .quantum_us = {}, .min_granularity_us = {} }}, /* {} */\n", policy, quantum, granularity, cpu.cpu));
+        }
+        header.push_str("};\n\n#endif\n");
+        
+        Ok(SchedulerArtifacts { kconfig, header })
+    }
+}
+
+/// Configuration generated from a scheduler design
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchedulerArtifacts {
+    /// Kernel config fragment
+    pub kconfig: String,
+    
+    /// C header with the per-CPU configuration
+    pub header: String,
+}
+
+/// Periodic task of a synthetic workload
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyntheticTask {
+    /// Task name
+    pub name: String,
+    
+    /// Release period in microseconds
+    pub period_us: u64,
+    
+    /// Execution time of each job in microseconds
+    pub wcet_us: u64,
+    
+    /// Relative deadline in microseconds (defaults to the period)
+    #[serde(default)]
+    pub deadline_us: Option<u64>,
+    
+    /// Release time of the first job in microseconds
+    #[serde(default)]
+    pub offset_us: u64,
+    
+    /// Nice value weighting the fair policy
+    #[serde(default)]
+    pub nice: i32,
+    
+    /// CPU the task is bound to under partitioned placement
+    #[serde(default)]
+    pub cpu: Option<usize>,
+}
+
+impl SyntheticTask {
+    /// Create a task with an implicit deadline
+    pub fn new(name: impl Into<String>, period_us: u64, wcet_us: u64) -> Self {
+        Self { name: name.into(), period_us, wcet_us, deadline_us: None, offset_us: 0, nice: 0, cpu: None }
+    }
+    
+    /// Share of a CPU the task needs
+    pub fn utilization(&self) -> f64 {
+        self.wcet_us as f64 / self.period_us as f64
+    }
+    
+    /// Relative deadline
+    pub fn deadline(&self) -> u64 {
+        self.deadline_us.unwrap_or(self.period_us)
+    }
+    
+    /// Load weight, scaled like CFS (about 1.25x per nice level)
+    pub fn weight(&self) -> u64 {
+        let nice = self.nice.clamp(-20, 19);
+        ((NICE_0_WEIGHT as f64) / 1.25f64.powi(nice)).round().max(1.0) as u64
+    }
+}
+
+/// Generate a periodic task set with a total utilization, using UUniFast
+pub fn generate_task_set(count: usize, total_utilization: f64, period_range_us: (u64, u64), seed: u64) -> Vec<SyntheticTask> {
+    let mut rng = seed.max(1);
+    let mut next = move || {
+        rng ^= rng << 13;
+        rng ^= rng >> 7;
+        rng ^= rng << 17;
+        (rng >> 11) as f64 / (1u64 << 53) as f64
+    };
+    
+    let (min_period, max_period) = (period_range_us.0.max(1), period_range_us.1.max(period_range_us.0.max(1)));
+    let mut remaining = total_utilization;
+    (0..count).map(|index| {
+        let utilization = if index + 1 == count {
+            remaining
+        } else {
+            let next_remaining = remaining * next().powf(1.0 / (count - index - 1) as f64);
+            let utilization = remaining - next_remaining;
+            remaining = next_remaining;
+            utilization
+        };
+        let period_us = min_period + ((max_period - min_period) as f64 * next()) as u64;
+        let wcet_us = ((period_us as f64 * utilization) as u64).max(1);
+        SyntheticTask::new(format!("task{}", index), period_us, wcet_us)
+    }).collect()
+}
+
+/// Simulation settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SchedulerSimulationConfig {
+    /// Scheduling tick in microseconds
+    pub tick_us: u64,
+    
+    /// Simulated time in microseconds
+    pub horizon_us: u64,
+}
+
+impl Default for SchedulerSimulationConfig {
+    fn default() -> Self {
+        Self { tick_us: 100, horizon_us: 1_000_000 }
+    }
+}
+
+/// Simulated behavior of one task
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TaskMetrics {
+    /// Task name
+    pub name: String,
+    
+    /// Released jobs
+    pub jobs: u64,
+    
+    /// Completed jobs
+    pub completed: u64,
+    
+    /// Jobs that completed late or were still running past their deadline
+    pub deadline_misses: u64,
+    
+    /// Average release-to-first-run latency in microseconds
+    pub avg_latency_us: f64,
+    
+    /// Maximum release-to-first-run latency in microseconds
+    pub max_latency_us: u64,
+    
+    /// Average release-to-completion time in microseconds
+    pub avg_response_us: f64,
+    
+    /// Maximum release-to-completion time in microseconds
+    pub max_response_us: u64,
+    
+    /// CPU time received in microseconds
+    pub cpu_time_us: u64,
+    
+    /// CPU time requested by released jobs in microseconds
+    pub demand_us: u64,
+}
+
+/// Result of a scheduler simulation
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SchedulerMetrics {
+    /// Per-task results, in task order
+    pub tasks: Vec<TaskMetrics>,
+    
+    /// Busy share of each CPU
+    pub cpu_utilization: Vec<f64>,
+    
+    /// Average release-to-first-run latency over all jobs
+    pub avg_latency_us: f64,
+    
+    /// Worst release-to-first-run latency
+    pub max_latency_us: u64,
+    
+    /// Share of released jobs that missed their deadline
+    pub deadline_miss_ratio: f64,
+    
+    /// Jain's fairness index of the CPU time received relative to the demand (1.0 = fair)
+    pub fairness_index: f64,
+}
+
+struct Job {
+    task: usize,
+    release: u64,
+    deadline: u64,
+    remaining: u64,
+    first_run: Option<u64>,
+}
+
+#[derive(Default)]
+struct CpuState {
+    ready: VecDeque<usize>,
+    current: Option<usize>,
+    slice_used: u64,
+    busy_us: u64,
+}
+
+/// Simulate a design against a task set
+pub fn simulate(design: &SchedulerDesign, tasks: &[SyntheticTask], config: &SchedulerSimulationConfig) -> Result<SchedulerMetrics, String> {
+    design.validate()?;
+    if config.tick_us == 0 {
+        return Err("The simulation tick must be positive".to_string());
+    }
+    if let Some(task) = tasks.iter().find(|task| task.period_us == 0 || task.wcet_us == 0) {
+        return Err(format!("Task '{}' needs a positive period and execution time", task.name));
+    }
+    
+    let cpu_count = design.cpus.len();
+    let assignment = partition(tasks, cpu_count);
+    let mut cpus: Vec<CpuState> = (0..cpu_count).map(|_| CpuState::default()).collect();
+    let mut jobs: Vec<Job> = Vec::new();
+    let mut vruntime: Vec<u64> = vec![0; tasks.len()];
+    let mut metrics: Vec<TaskMetrics> = tasks.iter().map(|task| TaskMetrics { name: task.name.clone(), ..TaskMetrics::default() }).collect();
+    let mut latencies: HashMap<usize, Vec<u64>> = HashMap::new();
+    let mut responses: HashMap<usize, Vec<u64>> = HashMap::new();
+    
+    let mut now = 0;
+    while now < config.horizon_us {
+        // Release the jobs due in this tick
+        for (index, task) in tasks.iter().enumerate() {
+            if now < task.offset_us || (now - task.offset_us) % task.period_us >= config.tick_us {
+                continue;
+            }
+            let release = now - (now - task.offset_us) % task.period_us;
+            let cpu = match design.placement {
+                TaskPlacement::Partitioned => assignment[index],
+                TaskPlacement::Global => (0..cpu_count)
+                    .min_by_key(|&cpu| cpus[cpu].ready.iter().chain(cpus[cpu].current.iter()).map(|&job| jobs[job].remaining).sum::<u64>())
+                    .unwrap_or(0),
+            };
+            let min_vruntime = cpus[cpu].ready.iter().chain(cpus[cpu].current.iter()).map(|&job| vruntime[jobs[job].task]).min();
+            if let Some(min_vruntime) = min_vruntime {
+                vruntime[index] = vruntime[index].max(min_vruntime);
+            }
+            jobs.push(Job { task: index, release, deadline: release + task.deadline(), remaining: task.wcet_us, first_run: None });
+            cpus[cpu].ready.push_back(jobs.len() - 1);
+            metrics[index].jobs += 1;
+            metrics[index].demand_us += task.wcet_us;
+        }
+        
+        for (cpu, state) in cpus.iter_mut().enumerate() {
+            let job = match pick(&design.cpus[cpu].policy, state, &jobs, &vruntime) {
+                Some(job) => job,
+                None => continue,
+            };
+            let task = jobs[job].task;
+            let run = jobs[job].remaining.min(config.tick_us);
+            jobs[job].first_run.get_or_insert(now);
+            jobs[job].remaining -= run;
+            state.slice_used += run;
+            state.busy_us += run;
+            vruntime[task] += run * NICE_0_WEIGHT / tasks[task].weight();
+            metrics[task].cpu_time_us += run;
+            
+            if jobs[job].remaining == 0 {
+                let finished = now + run;
+                metrics[task].completed += 1;
+                if finished > jobs[job].deadline {
+                    metrics[task].deadline_misses += 1;
+                }
+                latencies.entry(task).or_default().push(jobs[job].first_run.unwrap_or(now) - jobs[job].release);
+                responses.entry(task).or_default().push(finished - jobs[job].release);
+                state.current = None;
+                state.slice_used = 0;
+            }
+        }
+        
+        now += config.tick_us;
+    }
+    
+    // Jobs still pending past their deadline have missed it
+    for state in &cpus {
+        for &job in state.ready.iter().chain(state.current.iter()) {
+            if jobs[job].deadline < config.horizon_us {
+                metrics[jobs[job].task].deadline_misses += 1;
+            }
+        }
+    }
+    
+    let average = |values: &[u64]| if values.is_empty() { 0.0 } else { values.iter().sum::<u64>() as f64 / values.len() as f64 };
+    for (index, task) in metrics.iter_mut().enumerate() {
+        let task_latencies = latencies.get(&index).map(Vec::as_slice).unwrap_or(&[]);
+        let task_responses = responses.get(&index).map(Vec::as_slice).unwrap_or(&[]);
+        task.avg_latency_us = average(task_latencies);
+        task.max_latency_us = task_latencies.iter().copied().max().unwrap_or(0);
+        task.avg_response_us = average(task_responses);
+        task.max_response_us = task_responses.iter().copied().max().unwrap_or(0);
+    }
+    
+    let all_latencies: Vec<u64> = latencies.values().flatten().copied().collect();
+    let released: u64 = metrics.iter().map(|task| task.jobs).sum();
+    let missed: u64 = metrics.iter().map(|task| task.deadline_misses).sum();
+    let shares: Vec<f64> = metrics.iter()
+        .filter(|task| task.demand_us > 0)
+        .map(|task| task.cpu_time_us as f64 / task.demand_us as f64)
+        .collect();
+    let fairness_index = match shares.iter().map(|share| share * share).sum::<f64>() {
+        squares if squares > 0.0 => shares.iter().sum::<f64>().powi(2) / (shares.len() as f64 * squares),
+        _ => 1.0,
+    };
+    
+    Ok(SchedulerMetrics {
+        cpu_utilization: cpus.iter().map(|state| state.busy_us as f64 / config.horizon_us as f64).collect(),
+        avg_latency_us: average(&all_latencies),
+        max_latency_us: all_latencies.iter().copied().max().unwrap_or(0),
+        deadline_miss_ratio: if released == 0 { 0.0 } else { missed as f64 / released as f64 },
+        fairness_index,
+        tasks: metrics,
+    })
+}
+
+/// Choose the job a CPU runs in the next tick
+fn pick(policy: &SchedulingPolicy, state: &mut CpuState, jobs: &[Job], vruntime: &[u64]) -> Option<usize> {
+    let keep = match policy {
+        SchedulingPolicy::RoundRobin { quantum_us } => state.slice_used < *quantum_us,
+        SchedulingPolicy::Fair { min_granularity_us } => state.slice_used < *min_granularity_us,
+        SchedulingPolicy::Edf => false,
+    };
+    if keep && state.current.is_some() {
+        return state.current;
+    }
+    if let Some(current) = state.current.take() {
+        state.ready.push_back(current);
+    }
+    state.slice_used = 0;
+    
+    let position = match policy {
+        SchedulingPolicy::RoundRobin { .. } => (!state.ready.is_empty()).then_some(0),
+        SchedulingPolicy::Fair { .. } => (0..state.ready.len()).min_by_key(|&i| (vruntime[jobs[state.ready[i]].task], i)),
+        SchedulingPolicy::Edf => (0..state.ready.len()).min_by_key(|&i| (jobs[state.ready[i]].deadline, jobs[state.ready[i]].release, i)),
+    }?;
+    state.current = state.ready.remove(position);
+    state.current
+}
+
+/// CPU of every task under partitioned placement
+fn partition(tasks: &[SyntheticTask], cpu_count: usize) -> Vec<usize> {
+    let mut load = vec![0.0; cpu_count];
+    let mut assignment = vec![0; tasks.len()];
+    for (index, task) in tasks.iter().enumerate() {
+        if let Some(cpu) = task.cpu {
+            assignment[index] = cpu % cpu_count;
+            load[cpu % cpu_count] += task.utilization();
+        }
+    }
+    
+    // Worst-fit decreasing for the unbound tasks
+    let mut unbound: Vec<usize> = (0..tasks.len()).filter(|&index| tasks[index].cpu.is_none()).collect();
+    unbound.sort_by(|&a, &b| tasks[b].utilization().total_cmp(&tasks[a].utilization()).then(a.cmp(&b)));
+    for index in unbound {
+        let cpu = (0..cpu_count).min_by(|&a, &b| load[a].total_cmp(&load[b]).then(a.cmp(&b))).unwrap_or(0);
+        assignment[index] = cpu;
+        load[cpu] += tasks[index].utilization();
+    }
+    assignment
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_simulate_policies() {
+        let tasks = vec![SyntheticTask::new("fast", 1_000, 300), SyntheticTask::new("slow", 4_000, 2_000)];
+        let config = SchedulerSimulationConfig { tick_us: 100, horizon_us: 40_000 };
+        
+        let edf = SchedulerDesign::uniform("edf", 1, SchedulingPolicy::Edf, TaskPlacement::Partitioned);
+        let metrics = simulate(&edf, &tasks, &config).unwrap();
+        assert_eq!(metrics.deadline_miss_ratio, 0.0);
+        assert_eq!(metrics.tasks[0].jobs, 40);
+        assert!((metrics.cpu_utilization[0] - 0.8).abs() < 1e-9);
+        assert!(metrics.fairness_index > 0.99);
+        
+        let rr = SchedulerDesign::uniform("rr", 1, SchedulingPolicy::RoundRobin { quantum_us: 2_000 }, TaskPlacement::Partitioned);
+        let metrics = simulate(&rr, &tasks, &config).unwrap();
+        assert!(metrics.tasks[0].deadline_misses > 0);
+        
+        let tasks = generate_task_set(6, 1.5, (1_000, 10_000), 7);
+        assert!((tasks.iter().map(SyntheticTask::utilization).sum::<f64>() - 1.5).abs() < 0.05);
+        let fair = SchedulerDesign::uniform("fair", 2, SchedulingPolicy::Fair { min_granularity_us: 500 }, TaskPlacement::Global);
+        assert_eq!(simulate(&fair, &tasks, &config).unwrap().cpu_utilization.len(), 2);
+        
+        let artifacts = fair.generate_config().unwrap();
+        assert!(artifacts.kconfig.contains("CONFIG_NR_CPUS=2\nCONFIG_OSL_SCHED_FAIR=y\nCONFIG_OSL_SCHED_GLOBAL=y\n"));
+        assert!(artifacts.header.contains("{ .policy = SCHED_POLICY_FAIR, .quantum_us = 0, .min_granularity_us = 500 }, /* cpu1 */"));
+    }
+}
@@ -0,0 +1,653 @@
+// Security policy designer for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! Capability sets and mandatory access control rules.
+//!
+//! Security policies are written on the canvas as `security_policy` nodes whose
+//! `policy` property holds task types with their capability sets and SELinux/AppArmor
+//! style allow/deny rules:
+//!
+//! ```text
+//! task netd caps CAP_NET_BIND_SERVICE CAP_NET_RAW
+//! allow netd network tcp:80 bind,listen
+//! deny netd file /etc/shadow read
+//! ```
+//!
+//! Policies are checked against the components on the canvas (a network rule without
+//! a network stack is an error) and emitted as AppArmor profiles or an SELinux CIL
+//! module, plus the capability bounding sets, laid out as a rootfs overlay.
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use crate::component_manager::component::{Component, ComponentCategory, ComponentProperty, ComponentType};
+use crate::component_manager::ipc_components::{IpcKind, CHANNEL_NAME_PROPERTY};
+use crate::component_manager::visual_node::NodeCanvas;
+use crate::component_manager::ComponentLibrary;
+use super::syscall_designer::GeneratedFile;
+
+/// Component ID of security policy nodes
+pub const SECURITY_POLICY_ID: &str = "security_policy";
+
+/// Node property holding the policy text
+pub const POLICY_PROPERTY: &str = "policy";
+
+/// Linux capabilities, indexed by capability number
+const CAPABILITIES: [&str; 41] = [
+    "chown", "dac_override", "dac_read_search", "fowner", "fsetid", "kill", "setgid", "setuid",
+    "setpcap", "linux_immutable", "net_bind_service", "net_broadcast", "net_admin", "net_raw",
+    "ipc_lock", "ipc_owner", "sys_module", "sys_rawio", "sys_chroot", "sys_ptrace", "sys_pacct",
+    "sys_admin", "sys_boot", "sys_nice", "sys_resource", "sys_time", "sys_tty_config", "mknod",
+    "lease", "audit_write", "audit_control", "setfcap", "mac_override", "mac_admin", "syslog",
+    "wake_alarm", "block_suspend", "audit_read", "perfmon", "bpf", "checkpoint_restore",
+];
+
+/// Object class of an access rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum PolicyClass {
+    /// Files by path (globs allowed)
+    File,
+    
+    /// Sockets (`tcp:<port>`, `udp:<port>` or `unix`)
+    Network,
+    
+    /// IPC channels by channel name
+    Ipc,
+    
+    /// Device nodes by path
+    Device,
+}
+
+impl PolicyClass {
+    /// Name used in policy text
+    pub fn name(&self) -> &'static str {
+        match self {
+            PolicyClass::File => "file",
+            PolicyClass::Network => "network",
+            PolicyClass::Ipc => "ipc",
+            PolicyClass::Device => "device",
+        }
+    }
+    
+    /// Parse a class name
+    pub fn from_name(name: &str) -> Option<PolicyClass> {
+        [PolicyClass::File, PolicyClass::Network, PolicyClass::Ipc, PolicyClass::Device].into_iter().find(|class| class.name() == name)
+    }
+    
+    /// Permissions valid for the class
+    pub fn permissions(&self) -> &'static [&'static str] {
+        match self {
+            PolicyClass::File => &["read", "write", "execute", "create", "unlink"],
+            PolicyClass::Network => &["bind", "connect", "listen", "send", "receive"],
+            PolicyClass::Ipc => &["send", "receive"],
+            PolicyClass::Device => &["read", "write", "ioctl", "mmap"],
+        }
+    }
+}
+
+/// Capability set of a task type
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaskType {
+    /// Task type name
+    pub name: String,
+    
+    /// Capabilities (lowercase, without the `cap_` prefix)
+    pub capabilities: BTreeSet<String>,
+}
+
+/// Allow or deny rule
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AccessRule {
+    /// Whether the rule denies access
+    pub deny: bool,
+    
+    /// Task type the rule applies to
+    pub task: String,
+    
+    /// Object class
+    pub class: PolicyClass,
+    
+    /// Object (path, socket or channel name)
+    pub object: String,
+    
+    /// Permissions
+    pub permissions: BTreeSet<String>,
+}
+
+/// Policy output format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PolicyFormat {
+    AppArmor,
+    SeLinux,
+}
+
+impl PolicyFormat {
+    /// Parse a format name (`apparmor` or `selinux`)
+    pub fn from_name(name: &str) -> Option<PolicyFormat> {
+        match name.to_lowercase().as_str() {
+            "apparmor" => Some(PolicyFormat::AppArmor),
+            "selinux" | "cil" => Some(PolicyFormat::SeLinux),
+            _ => None,
+        }
+    }
+}
+
+/// Severity of a policy issue
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum PolicyIssueSeverity {
+    Warning,
+    Error,
+}
+
+/// Problem found in a policy
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PolicyIssue {
+    /// Severity
+    pub severity: PolicyIssueSeverity,
+    
+    /// Description
+    pub message: String,
+}
+
+impl fmt::Display for PolicyIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}: {}", self.severity, self.message)
+    }
+}
+
+/// Components present on the canvas, as far as policies care
+#[derive(Debug, Clone, Default)]
+pub struct PolicyContext {
+    /// Component types of the nodes
+    pub component_types: HashSet<ComponentType>,
+    
+    /// Names of the IPC channels
+    pub ipc_channels: HashSet<String>,
+}
+
+impl PolicyContext {
+    /// Collect the components of a canvas
+    pub fn from_canvas(canvas: &NodeCanvas) -> Self {
+        let mut context = Self::default();
+        for node in canvas.nodes.values() {
+            context.component_types.insert(node.component.component_type.clone());
+            if let Some(kind) = IpcKind::of_component(&node.component_id) {
+                let name = node.properties.get(CHANNEL_NAME_PROPERTY).cloned().unwrap_or_else(|| kind.component_id().to_string());
+                context.ipc_channels.insert(name);
+            }
+        }
+        context
+    }
+    
+    fn has_any(&self, types: &[ComponentType]) -> bool {
+        types.iter().any(|component_type| self.component_types.contains(component_type))
+    }
+}
+
+/// Security policy: task types and access rules
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SecurityPolicy {
+    /// Task types with their capability sets
+    pub tasks: Vec<TaskType>,
+    
+    /// Access rules in declaration order
+    pub rules: Vec<AccessRule>,
+}
+
+impl SecurityPolicy {
+    /// Parse policy text
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut policy = Self::default();
+        for (number, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+            let error = |message: String| format!("Line {}: {}", number + 1, message);
+            let words: Vec<&str> = line.split_whitespace().collect();
+            
+            match words[0] {
+                "task" => {
+                    let name = words.get(1).ok_or_else(|| error("missing task type name".to_string()))?;
+                    let capabilities = match words.get(2) {
+                        None => BTreeSet::new(),
+                        Some(&"caps") => words[3..].iter().map(|cap| normalize_capability(cap).ok_or_else(|| error(format!("unknown capability '{}'", cap)))).collect::<Result<_, _>>()?,
+                        Some(other) => return Err(error(format!("expected 'caps', got '{}'", other))),
+                    };
+                    policy.tasks.push(TaskType { name: name.to_string(), capabilities });
+                }
+                "allow" | "deny" => {
+                    if words.len() != 5 {
+                        return Err(error(format!("expected '{} <task> <class> <object> <permissions>'", words[0])));
+                    }
+                    let class = PolicyClass::from_name(words[2]).ok_or_else(|| error(format!("unknown class '{}'", words[2])))?;
+                    let permissions: BTreeSet<String> = words[4].split(',').map(|permission| permission.trim().to_string()).collect();
+                    if let Some(invalid) = permissions.iter().find(|permission| !class.permissions().contains(&permission.as_str())) {
+                        return Err(error(format!("'{}' is not a {} permission (expected one of {})", invalid, class.name(), class.permissions().join(", "))));
+                    }
+                    policy.rules.push(AccessRule {
+                        deny: words[0] == "deny",
+                        task: words[1].to_string(),
+                        class,
+                        object: words[3].to_string(),
+                        permissions,
+                    });
+                }
+                other => return Err(error(format!("unknown statement '{}'", other))),
+            }
+        }
+        Ok(policy)
+    }
+    
+    /// Parse and merge the policies of all policy nodes on a canvas
+    pub fn from_canvas(canvas: &NodeCanvas) -> Result<Self, String> {
+        let mut policy = Self::default();
+        for (id, text) in policy_nodes(canvas) {
+            let node_policy = Self::parse(text).map_err(|e| format!("Policy node '{}': {}", id, e))?;
+            policy.tasks.extend(node_policy.tasks);
+            policy.rules.extend(node_policy.rules);
+        }
+        Ok(policy)
+    }
+    
+    /// Check the policy for consistency and against the components present
+    pub fn validate(&self, context: &PolicyContext) -> Vec<PolicyIssue> {
+        let mut issues = Vec::new();
+        let mut push = |severity, message: String| issues.push(PolicyIssue { severity, message });
+        
+        let mut names = HashSet::new();
+        for task in &self.tasks {
+            if !is_identifier(&task.name) {
+                push(PolicyIssueSeverity::Error, format!("Task type '{}' is not a valid identifier", task.name));
+            }
+            if !names.insert(task.name.as_str()) {
+                push(PolicyIssueSeverity::Error, format!("Task type '{}' is declared more than once", task.name));
+            }
+            let network_caps: Vec<&String> = task.capabilities.iter().filter(|cap| cap.starts_with("net_")).collect();
+            if !network_caps.is_empty() && !context.has_any(&[ComponentType::NetworkStack]) {
+                push(PolicyIssueSeverity::Error, format!("Task type '{}' holds cap_{} but the canvas has no network stack", task.name, network_caps[0]));
+            }
+        }
+        
+        let mut allowed: HashSet<(&str, PolicyClass, &str, &str)> = HashSet::new();
+        let mut denied = Vec::new();
+        for rule in &self.rules {
+            if !names.contains(rule.task.as_str()) {
+                push(PolicyIssueSeverity::Error, format!("Rule for undeclared task type '{}'", rule.task));
+            }
+            let missing = match rule.class {
+                PolicyClass::Network if !context.has_any(&[ComponentType::NetworkStack]) => Some("network stack"),
+                PolicyClass::File if !context.has_any(&[ComponentType::FileSystem, ComponentType::VirtualFileSystem]) => Some("filesystem"),
+                PolicyClass::Device if !context.has_any(&[ComponentType::DeviceDriver, ComponentType::DeviceManager]) => Some("device driver"),
+                PolicyClass::Ipc if context.ipc_channels.is_empty() => Some("IPC channel"),
+                _ => None,
+            };
+            if let Some(missing) = missing {
+                push(PolicyIssueSeverity::Error, format!("{} rule for '{}' on '{}' but the canvas has no {}", rule.class.name(), rule.task, rule.object, missing));
+            } else if rule.class == PolicyClass::Ipc && !context.ipc_channels.contains(&rule.object) {
+                push(PolicyIssueSeverity::Warning, format!("IPC rule for '{}' names unknown channel '{}'", rule.task, rule.object));
+            }
+            if rule.class == PolicyClass::Network && parse_socket(&rule.object).is_none() {
+                push(PolicyIssueSeverity::Error, format!("Network object '{}' must be 'tcp:<port>', 'udp:<port>' or 'unix'", rule.object));
+            }
+            
+            for permission in &rule.permissions {
+                let key = (rule.task.as_str(), rule.class, rule.object.as_str(), permission.as_str());
+                if rule.deny { denied.push(key) } else { allowed.insert(key); }
+            }
+        }
+        for key in denied {
+            if allowed.contains(&key) {
+                push(PolicyIssueSeverity::Warning, format!("'{}' is both allowed and denied {} on '{}'; the deny rule wins", key.0, key.3, key.2));
+            }
+        }
+        
+        issues
+    }
+    
+    /// Rules of a task type
+    fn rules_of<'a>(&'a self, task: &'a str) -> impl Iterator<Item = &'a AccessRule> + 'a {
+        self.rules.iter().filter(move |rule| rule.task == task)
+    }
+    
+    /// Generate the policy files, with paths relative to the rootfs root
+    pub fn emit(&self, format: PolicyFormat) -> Vec<GeneratedFile> {
+        let mut files = match format {
+            PolicyFormat::AppArmor => self.tasks.iter().map(|task| GeneratedFile {
+                path: PathBuf::from(format!("etc/apparmor.d/osland.{}", task.name)),
+                content: self.apparmor_profile(task),
+            }).collect(),
+            PolicyFormat::SeLinux => self.selinux_module(),
+        };
+        
+        let mut capabilities = String::from("# Capability bounding sets generated by OSland\n# <task type> <capabilities>\n");
+        for task in &self.tasks {
+            let caps: Vec<String> = task.capabilities.iter().map(|cap| format!("cap_{}", cap)).collect();
+            capabilities.push_str(&format!("{} {}\n", task.name, if caps.is_empty() { "none".to_string() } else { caps.join(",") }));
+        }
+        files.push(GeneratedFile { path: PathBuf::from("etc/security/osland/capabilities.conf"), content: capabilities });
+        
+        // Neither AppArmor nor SELinux know OSland IPC channels; the IPC layer enforces these
+        let mut ipc = String::from("# IPC channel rules generated by OSland\n# <allow|deny> <task type> <channel> <permissions>\n");
+        for rule in self.rules.iter().filter(|rule| rule.class == PolicyClass::Ipc) {
+            let permissions: Vec<&str> = rule.permissions.iter().map(|p| p.as_str()).collect();
+            ipc.push_str(&format!("{} {} {} {}\n", if rule.deny { "deny" } else { "allow" }, rule.task, rule.object, permissions.join(",")));
+        }
+        files.push(GeneratedFile { path: PathBuf::from("etc/security/osland/ipc.rules"), content: ipc });
+        files
+    }
+    
+    fn apparmor_profile(&self, task: &TaskType) -> String {
+        let mut out = format!("# Generated by OSland\nabi <abi/3.0>,\n\nprofile osland.{} flags=(enforce) {{\n", task.name);
+        for cap in &task.capabilities {
+            out.push_str(&format!("  capability {},\n", cap));
+        }
+        for rule in self.rules_of(&task.name) {
+            let prefix = if rule.deny { "deny " } else { "" };
+            match rule.class {
+                PolicyClass::File | PolicyClass::Device => {
+                    let mut modes = String::new();
+                    for (permission, mode) in [("read", 'r'), ("write", 'w'), ("create", 'w'), ("unlink", 'w'), ("ioctl", 'w'), ("mmap", 'm'), ("execute", 'x')] {
+                        if rule.permissions.contains(permission) && !modes.contains(mode) {
+                            modes.push(mode);
+                        }
+                    }
+                    // Allowed execution stays in the current profile
+                    if !rule.deny {
+                        modes = modes.replace('x', "ix");
+                    }
+                    out.push_str(&format!("  {}{} {},\n", prefix, rule.object, modes));
+                }
+                PolicyClass::Network => match parse_socket(&rule.object) {
+                    Some(("unix", _)) => out.push_str(&format!("  {}unix,\n", prefix)),
+                    Some((protocol, _)) => out.push_str(&format!("  {}network inet {},\n  {}network inet6 {},\n", prefix, socket_type(protocol), prefix, socket_type(protocol))),
+                    None => {}
+                },
+                PolicyClass::Ipc => {}
+            }
+        }
+        out.push_str("}\n");
+        out
+    }
+    
+    /// SELinux CIL module and file contexts
+    ///
+    /// SELinux denies by default, so deny rules become `neverallow` assertions. Denied
+    /// permissions are subtracted from the allow statements first: an allow overlapping
+    /// a neverallow would make the module fail to load.
+    fn selinux_module(&self) -> Vec<GeneratedFile> {
+        let mut cil = String::from("; SELinux CIL module generated by OSland\n");
+        let mut object_types: BTreeMap<String, String> = BTreeMap::new();
+        let mut used_names: BTreeSet<String> = BTreeSet::new();
+        let mut contexts = String::new();
+        
+        for task in &self.tasks {
+            cil.push_str(&format!("\n(type {}_t)\n(roletype object_r {}_t)\n", task.name, task.name));
+            let (low, high): (Vec<&String>, Vec<&String>) = task.capabilities.iter()
+                .partition(|cap| CAPABILITIES.iter().position(|known| known == cap).unwrap_or(0) < 32);
+            if !low.is_empty() {
+                cil.push_str(&format!("(allow {}_t self (capability ({})))\n", task.name, join(&low)));
+            }
+            if !high.is_empty() {
+                cil.push_str(&format!("(allow {}_t self (capability2 ({})))\n", task.name, join(&high)));
+            }
+            
+            // (deny, target type, class, permissions) in declaration order
+            let mut statements: Vec<(bool, String, &str, Vec<&str>)> = Vec::new();
+            for rule in self.rules_of(&task.name) {
+                match rule.class {
+                    PolicyClass::File | PolicyClass::Device => {
+                        let object_type = object_types.entry(rule.object.clone()).or_insert_with(|| {
+                            let base = format!("osland_{}", sanitize(&rule.object));
+                            let mut name = format!("{}_t", base);
+                            let mut suffix = 2;
+                            // Paths differing only in punctuation sanitize to the same name
+                            while !used_names.insert(name.clone()) {
+                                name = format!("{}_{}_t", base, suffix);
+                                suffix += 1;
+                            }
+                            cil.push_str(&format!("(type {})\n(roletype object_r {})\n", name, name));
+                            contexts.push_str(&format!("{} system_u:object_r:{}:s0\n", glob_to_regex(&rule.object), name));
+                            name
+                        });
+                        let class = if rule.class == PolicyClass::File { "file" } else { "chr_file" };
+                        let permissions = rule.permissions.iter().map(|p| if p == "mmap" { "map" } else { p.as_str() }).collect();
+                        statements.push((rule.deny, object_type.clone(), class, permissions));
+                    }
+                    PolicyClass::Network => {
+                        let class = match parse_socket(&rule.object) {
+                            Some(("tcp", _)) => "tcp_socket",
+                            Some(("udp", _)) => "udp_socket",
+                            Some(_) => "unix_stream_socket",
+                            None => continue,
+                        };
+                        let permissions = rule.permissions.iter().map(|p| match p.as_str() {
+                            "send" => "write",
+                            "receive" => "read",
+                            other => other,
+                        }).collect();
+                        statements.push((rule.deny, "self".to_string(), class, permissions));
+                    }
+                    PolicyClass::Ipc => {}
+                }
+            }
+            
+            let denied: HashSet<(&str, &str, &str)> = statements.iter()
+                .filter(|(deny, ..)| *deny)
+                .flat_map(|(_, target, class, permissions)| permissions.iter().map(move |p| (target.as_str(), *class, *p)))
+                .collect();
+            for (deny, target, class, permissions) in &statements {
+                let permissions: Vec<&str> = permissions.iter().copied()
+                    .filter(|p| *deny || !denied.contains(&(target.as_str(), *class, *p)))
+                    .collect();
+                if permissions.is_empty() {
+                    continue;
+                }
+                let statement = if *deny { "neverallow" } else { "allow" };
+                cil.push_str(&format!("({} {}_t {} ({} ({})))\n", statement, task.name, target, class, permissions.join(" ")));
+            }
+        }
+        
+        vec![
+            GeneratedFile { path: PathBuf::from("etc/selinux/osland/osland.cil"), content: cil },
+            GeneratedFile { path: PathBuf::from("etc/selinux/osland/file_contexts"), content: contexts },
+        ]
+    }
+    
+    /// Write the policy files as a rootfs overlay under a directory
+    pub fn write_overlay<P: AsRef<Path>>(&self, dir: P, format: PolicyFormat) -> Result<Vec<PathBuf>, String> {
+        let dir = dir.as_ref();
+        self.emit(format).into_iter().map(|file| {
+            let path = dir.join(&file.path);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+            }
+            fs::write(&path, &file.content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+            Ok(path)
+        }).collect()
+    }
+}
+
+/// Policy issue attributed to a canvas node
+#[derive(Debug, Clone, PartialEq)]
+pub struct PolicyFinding {
+    /// Policy node
+    pub node_id: String,
+    
+    /// Issue
+    pub issue: PolicyIssue,
+}
+
+/// Check every policy node of a canvas against the components present
+pub fn check_security_policies(canvas: &NodeCanvas) -> Vec<PolicyFinding> {
+    let context = PolicyContext::from_canvas(canvas);
+    let mut findings = Vec::new();
+    for (id, text) in policy_nodes(canvas) {
+        let issues = match SecurityPolicy::parse(text) {
+            Ok(policy) => policy.validate(&context),
+            Err(message) => vec![PolicyIssue { severity: PolicyIssueSeverity::Error, message }],
+        };
+        findings.extend(issues.into_iter().map(|issue| PolicyFinding { node_id: id.to_string(), issue }));
+    }
+    findings
+}
+
+/// Create the security policy component
+pub fn create_security_policy_component() -> Component {
+    Component {
+        id: SECURITY_POLICY_ID.to_string(),
+        name: SECURITY_POLICY_ID.to_string(),
+        display_name: "Security Policy".to_string(),
+        component_type: ComponentType::SecurityManager,
+        category: ComponentCategory::KernelCore,
+        version: "1.0.0".to_string(),
+        description: "Capability sets and access rules per task type".to_string(),
+        author: "OSland Team".to_string(),
+        source_url: None,
+        license: "MulanPSL-2.0".to_string(),
+        properties: vec![ComponentProperty {
+            name: POLICY_PROPERTY.to_string(),
+            value: String::new(),
+            property_type: "text".to_string(),
+            description: "Task types ('task <name> caps ...') and rules ('allow|deny <task> <class> <object> <permissions>')".to_string(),
+            required: true,
+            default_value: Some(String::new()),
+            valid_values: None,
+        }],
+        ports: Vec::new(),
+        dependencies: Vec::new(),
+        supported_architectures: Default::default(),
+        supported_languages: Vec::new(),
+        implementation_files: Vec::new(),
+        build_commands: Vec::new(),
+        initialization_code: String::new(),
+    }
+}
+
+/// Add the security policy component to an existing library
+pub fn extend_with_security_policy_component(library: &mut ComponentLibrary) {
+    // Components already present are kept
+    let _ = library.add_component(create_security_policy_component());
+}
+
+/// Policy texts of the policy nodes, ordered by node ID
+fn policy_nodes(canvas: &NodeCanvas) -> Vec<(&str, &str)> {
+    let mut nodes: Vec<(&str, &str)> = canvas.nodes.values()
+        .filter(|node| node.component_id == SECURITY_POLICY_ID)
+        .map(|node| (node.id.as_str(), node.properties.get(POLICY_PROPERTY).map_or("", |text| text.as_str())))
+        .collect();
+    nodes.sort();
+    nodes
+}
+
+/// Lowercase capability name without the `cap_` prefix, if known
+fn normalize_capability(name: &str) -> Option<String> {
+    let name = name.to_lowercase();
+    let name = name.strip_prefix("cap_").unwrap_or(&name);
+    CAPABILITIES.contains(&name).then(|| name.to_string())
+}
+
+/// Protocol and port of a network object
+fn parse_socket(object: &str) -> Option<(&str, Option<u16>)> {
+    if object == "unix" {
+        return Some(("unix", None));
+    }
+    let (protocol, port) = object.split_once(':')?;
+    if protocol != "tcp" && protocol != "udp" {
+        return None;
+    }
+    match port {
+        "*" => Some((protocol, None)),
+        port => port.parse().ok().map(|port| (protocol, Some(port))),
+    }
+}
+
+fn socket_type(protocol: &str) -> &'static str {
+    if protocol == "tcp" { "stream" } else { "dgram" }
+}
+
+fn join(items: &[&String]) -> String {
+    items.iter().map(|item| item.as_str()).collect::<Vec<_>>().join(" ")
+}
+
+fn is_identifier(name: &str) -> bool {
+    name.chars().next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_') && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Type name fragment for an object path
+fn sanitize(object: &str) -> String {
+    let name: String = object.chars().map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' }).collect();
+    name.trim_matches('_').split('_').filter(|part| !part.is_empty()).collect::<Vec<_>>().join("_")
+}
+
+/// File context regex for a path glob (`**` matches across directories)
+fn glob_to_regex(glob: &str) -> String {
+    if let Some(prefix) = glob.strip_suffix("/**") {
+        return format!("{}(/.*)?", prefix);
+    }
+    glob.replace('.', "\\.").replace("**", ".*").replace('*', "[^/]*")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_policy_validation_and_emission() {
+        let policy = SecurityPolicy::parse(
+            "task netd caps CAP_NET_BIND_SERVICE cap_bpf\n\
+             allow netd network tcp:80 bind,listen\n\
+             allow netd file /etc/netd/** read\n\
+             deny netd file /etc/netd/** read # overridden\n\
+             allow logger ipc log_queue send\n",
+        ).unwrap();
+        assert_eq!(policy.tasks[0].capabilities.iter().cloned().collect::<Vec<_>>(), vec!["bpf", "net_bind_service"]);
+        assert!(SecurityPolicy::parse("allow netd file /etc read,fly").unwrap_err().contains("'fly' is not a file permission"));
+        
+        // Only a filesystem is present: the network rule and capability are flagged
+        let mut context = PolicyContext::default();
+        context.component_types.insert(ComponentType::FileSystem);
+        let messages: Vec<String> = policy.validate(&context).iter().map(|issue| issue.to_string()).collect();
+        assert_eq!(messages, vec![
+            "Error: Task type 'netd' holds cap_net_bind_service but the canvas has no network stack",
+            "Error: network rule for 'netd' on 'tcp:80' but the canvas has no network stack",
+            "Error: Rule for undeclared task type 'logger'",
+            "Error: ipc rule for 'logger' on 'log_queue' but the canvas has no IPC channel",
+            "Warning: 'netd' is both allowed and denied read on '/etc/netd/**'; the deny rule wins",
+        ]);
+        
+        let apparmor = policy.emit(PolicyFormat::AppArmor);
+        assert_eq!(apparmor[0].path, Path::new("etc/apparmor.d/osland.netd"));
+        assert!(apparmor[0].content.contains("  capability bpf,\n  capability net_bind_service,\n  network inet stream,\n"));
+        assert!(apparmor[0].content.contains("  deny /etc/netd/** r,\n"));
+        assert_eq!(apparmor[1].content.lines().last(), Some("netd cap_bpf,cap_net_bind_service"));
+        
+        let selinux = policy.emit(PolicyFormat::SeLinux);
+        assert!(selinux[0].content.contains("(allow netd_t self (capability (net_bind_service)))\n(allow netd_t self (capability2 (bpf)))\n"));
+        assert!(selinux[0].content.contains("(neverallow netd_t osland_etc_netd_t (file (read)))"));
+        assert!(!selinux[0].content.contains("(allow netd_t osland_etc_netd_t"));
+        assert_eq!(selinux[1].content, "/etc/netd(/.*)? system_u:object_r:osland_etc_netd_t:s0\n");
+    }
+    
+    #[test]
+    fn test_selinux_denies_are_subtracted_and_types_unique() {
+        let policy = SecurityPolicy::parse(
+            "task netd\n\
+             allow netd file /var/a-b read,write\n\
+             allow netd file /var/a_b read\n\
+             deny netd file /var/a-b write\n\
+             allow netd network tcp:80 bind,listen\n\
+             deny netd network tcp:23 bind\n",
+        ).unwrap();
+        let cil = &policy.emit(PolicyFormat::SeLinux)[0].content;
+        assert!(cil.contains("(allow netd_t osland_var_a_b_t (file (read)))\n"));
+        assert!(cil.contains("(allow netd_t osland_var_a_b_2_t (file (read)))\n"));
+        assert!(cil.contains("(neverallow netd_t osland_var_a_b_t (file (write)))\n"));
+        // SELinux sockets carry no port, so the denied bind wins for every port
+        assert!(cil.contains("(allow netd_t self (tcp_socket (listen)))\n"));
+        assert!(cil.contains("(neverallow netd_t self (tcp_socket (bind)))\n"));
+    }
+}
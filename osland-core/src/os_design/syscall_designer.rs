@@ -0,0 +1,646 @@
+// Syscall interface designer for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! Syscall table design and code generation.
+//!
+//! A syscall table lists numbered syscalls with typed arguments. From it the designer
+//! generates the kernel dispatch table (C or Rust) and userland stub libraries that
+//! issue the raw syscall instruction of the target architecture. Tables are stored as
+//! JSON so they can be referenced from a `GenerateSyscalls` build step.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use crate::tile_engine::tile_compiler::TargetLanguage;
+
+/// Maximum number of arguments passed in registers on every supported architecture
+pub const MAX_SYSCALL_ARGS: usize = 6;
+
+/// Error returned by the generated dispatchers for unknown syscall numbers
+const ENOSYS: i32 = 38;
+
+/// Type of a syscall argument
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SyscallArgType {
+    I32,
+    I64,
+    U32,
+    U64,
+    Usize,
+    Isize,
+    
+    /// File descriptor
+    Fd,
+    
+    /// Pointer to memory read by the kernel
+    ConstPtr,
+    
+    /// Pointer to memory written by the kernel
+    MutPtr,
+    
+    /// NUL-terminated string
+    Str,
+}
+
+impl SyscallArgType {
+    /// C type of the argument
+    pub fn c_type(&self) -> &'static str {
+        match self {
+            SyscallArgType::I32 => "int32_t",
+            SyscallArgType::I64 => "int64_t",
+            SyscallArgType::U32 => "uint32_t",
+            SyscallArgType::U64 => "uint64_t",
+            SyscallArgType::Usize => "size_t",
+            SyscallArgType::Isize => "intptr_t",
+            SyscallArgType::Fd => "int",
+            SyscallArgType::ConstPtr => "const void *",
+            SyscallArgType::MutPtr => "void *",
+            SyscallArgType::Str => "const char *",
+        }
+    }
+    
+    /// Rust type of the argument
+    pub fn rust_type(&self) -> &'static str {
+        match self {
+            SyscallArgType::I32 | SyscallArgType::Fd => "i32",
+            SyscallArgType::I64 => "i64",
+            SyscallArgType::U32 => "u32",
+            SyscallArgType::U64 => "u64",
+            SyscallArgType::Usize => "usize",
+            SyscallArgType::Isize => "isize",
+            SyscallArgType::ConstPtr => "*const u8",
+            SyscallArgType::MutPtr => "*mut u8",
+            SyscallArgType::Str => "*const core::ffi::c_char",
+        }
+    }
+    
+    /// Python ctypes type of the argument
+    pub fn ctypes_type(&self) -> &'static str {
+        match self {
+            SyscallArgType::I32 | SyscallArgType::Fd => "ctypes.c_int32",
+            SyscallArgType::I64 => "ctypes.c_int64",
+            SyscallArgType::U32 => "ctypes.c_uint32",
+            SyscallArgType::U64 => "ctypes.c_uint64",
+            SyscallArgType::Usize => "ctypes.c_size_t",
+            SyscallArgType::Isize => "ctypes.c_ssize_t",
+            SyscallArgType::ConstPtr | SyscallArgType::MutPtr => "ctypes.c_void_p",
+            SyscallArgType::Str => "ctypes.c_char_p",
+        }
+    }
+    
+    fn is_pointer(&self) -> bool {
+        matches!(self, SyscallArgType::ConstPtr | SyscallArgType::MutPtr | SyscallArgType::Str)
+    }
+}
+
+/// Syscall argument
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyscallArg {
+    /// Argument name
+    pub name: String,
+    
+    /// Argument type
+    pub arg_type: SyscallArgType,
+}
+
+/// Syscall definition
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Syscall {
+    /// Syscall number
+    pub number: u32,
+    
+    /// Syscall name
+    pub name: String,
+    
+    /// Arguments in register order
+    #[serde(default)]
+    pub args: Vec<SyscallArg>,
+    
+    /// Description shown in the designer and generated comments
+    #[serde(default)]
+    pub description: String,
+    
+    /// Kernel handler function (defaults to `sys_<name>`)
+    #[serde(default)]
+    pub handler: Option<String>,
+}
+
+impl Syscall {
+    /// Create a syscall without arguments
+    pub fn new(number: u32, name: impl Into<String>) -> Self {
+        Self { number, name: name.into(), args: Vec::new(), description: String::new(), handler: None }
+    }
+    
+    /// Add an argument
+    pub fn arg(mut self, name: impl Into<String>, arg_type: SyscallArgType) -> Self {
+        self.args.push(SyscallArg { name: name.into(), arg_type });
+        self
+    }
+    
+    /// Kernel handler function
+    pub fn handler_name(&self) -> String {
+        self.handler.clone().unwrap_or_else(|| format!("sys_{}", self.name))
+    }
+}
+
+/// Severity of a table issue
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SyscallIssueSeverity {
+    /// Generation is refused
+    Error,
+    
+    /// Generation proceeds
+    Warning,
+}
+
+/// Problem found in a syscall table
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyscallIssue {
+    /// Issue severity
+    pub severity: SyscallIssueSeverity,
+    
+    /// Syscall the issue refers to
+    pub syscall: Option<String>,
+    
+    /// Issue message
+    pub message: String,
+}
+
+impl std::fmt::Display for SyscallIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let severity = match self.severity {
+            SyscallIssueSeverity::Error => "error",
+            SyscallIssueSeverity::Warning => "warning",
+        };
+        match &self.syscall {
+            Some(syscall) => write!(f, "{}: {}: {}", severity, syscall, self.message),
+            None => write!(f, "{}: {}", severity, self.message),
+        }
+    }
+}
+
+/// File produced by the generator, relative to the output directory
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeneratedFile {
+    /// Relative path
+    pub path: PathBuf,
+    
+    /// File content
+    pub content: String,
+}
+
+/// Syscall table of a kernel design
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyscallTable {
+    /// Table name, used for generated include guards and module names
+    pub name: String,
+    
+    /// Target hardware architecture (`x86_64`, `aarch64` or `riscv64`)
+    pub arch: String,
+    
+    /// Prefix of the userland stub functions
+    #[serde(default = "default_stub_prefix")]
+    pub stub_prefix: String,
+    
+    /// Syscalls
+    #[serde(default)]
+    pub syscalls: Vec<Syscall>,
+}
+
+fn default_stub_prefix() -> String {
+    "osl_".to_string()
+}
+
+impl SyscallTable {
+    /// Create an empty table
+    pub fn new(name: impl Into<String>, arch: impl Into<String>) -> Self {
+        Self { name: name.into(), arch: arch.into(), stub_prefix: default_stub_prefix(), syscalls: Vec::new() }
+    }
+    
+    /// Load a table from a JSON file
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        serde_json::from_str(&content).map_err(|e| format!("Failed to parse {}: {}", path.display(), e))
+    }
+    
+    /// Save the table as JSON
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), String> {
+        let content = serde_json::to_string_pretty(self).map_err(|e| format!("Failed to serialize syscall table: {}", e))?;
+        fs::write(path, content).map_err(|e| format!("Failed to write syscall table: {}", e))
+    }
+    
+    /// Add a syscall, using the next free number when `number` is `None`
+    pub fn add(&mut self, number: Option<u32>, syscall: Syscall) -> Result<u32, String> {
+        let number = match number {
+            Some(number) => number,
+            None => match self.syscalls.iter().map(|s| s.number).max() {
+                Some(last) => last.checked_add(1).ok_or_else(|| format!("No syscall number is free after {}", last))?,
+                None => 0,
+            },
+        };
+        self.syscalls.push(Syscall { number, ..syscall });
+        Ok(number)
+    }
+    
+    /// Remove a syscall by name
+    pub fn remove(&mut self, name: &str) -> Option<Syscall> {
+        let index = self.syscalls.iter().position(|syscall| syscall.name == name)?;
+        Some(self.syscalls.remove(index))
+    }
+    
+    /// Syscalls ordered by number
+    pub fn sorted(&self) -> Vec<&Syscall> {
+        let mut syscalls: Vec<&Syscall> = self.syscalls.iter().collect();
+        syscalls.sort_by_key(|syscall| syscall.number);
+        syscalls
+    }
+    
+    /// Number of dispatch table slots
+    pub fn slot_count(&self) -> u64 {
+        self.syscalls.iter().map(|syscall| u64::from(syscall.number) + 1).max().unwrap_or(0)
+    }
+    
+    /// Check numbering, names and argument counts
+    pub fn validate(&self) -> Vec<SyscallIssue> {
+        let mut issues = Vec::new();
+        let error = |syscall: &Syscall, message: String| SyscallIssue {
+            severity: SyscallIssueSeverity::Error,
+            syscall: Some(syscall.name.clone()),
+            message,
+        };
+        
+        let mut numbers: HashMap<u32, &str> = HashMap::new();
+        let mut names = HashSet::new();
+        for syscall in &self.syscalls {
+            if let Some(other) = numbers.insert(syscall.number, &syscall.name) {
+                issues.push(error(syscall, format!("number {} is already used by '{}'", syscall.number, other)));
+            }
+            if !names.insert(syscall.name.as_str()) {
+                issues.push(error(syscall, "duplicate syscall name".to_string()));
+            }
+            if !is_identifier(&syscall.name) {
+                issues.push(error(syscall, "name is not a valid identifier".to_string()));
+            }
+            if syscall.handler.as_deref().is_some_and(|handler| !is_identifier(handler)) {
+                issues.push(error(syscall, "handler is not a valid identifier".to_string()));
+            }
+            if syscall.args.len() > MAX_SYSCALL_ARGS {
+                issues.push(error(syscall, format!("{} arguments exceed the {} argument registers", syscall.args.len(), MAX_SYSCALL_ARGS)));
+            }
+            let mut arg_names = HashSet::new();
+            for arg in &syscall.args {
+                if !is_identifier(&arg.name) {
+                    issues.push(error(syscall, format!("argument '{}' is not a valid identifier", arg.name)));
+                } else if !arg_names.insert(arg.name.as_str()) {
+                    issues.push(error(syscall, format!("duplicate argument '{}'", arg.name)));
+                }
+            }
+        }
+        
+        let gaps = self.slot_count() - numbers.len() as u64;
+        if gaps > 0 {
+            issues.push(SyscallIssue {
+                severity: SyscallIssueSeverity::Warning,
+                syscall: None,
+                message: format!("{} unused number(s) below {} return ENOSYS", gaps, self.slot_count()),
+            });
+        }
+        
+        issues
+    }
+    
+    /// Generate the kernel dispatch table and the userland stub libraries
+    pub fn generate(&self, kernel_language: &TargetLanguage, stub_languages: &[TargetLanguage]) -> Result<Vec<GeneratedFile>, String> {
+        let errors: Vec<String> = self.validate().iter()
+            .filter(|issue| issue.severity == SyscallIssueSeverity::Error)
+            .map(|issue| issue.to_string())
+            .collect();
+        if !errors.is_empty() {
+            return Err(format!("Syscall table '{}' is invalid: {}", self.name, errors.join("; ")));
+        }
+        
+        let mut files = match kernel_language {
+            TargetLanguage::C => self.c_dispatch(),
+            TargetLanguage::Rust => vec![self.rust_dispatch()],
+            other => return Err(format!("Dispatch tables cannot be generated in {}", other.name())),
+        };
+        for language in stub_languages {
+            files.push(match language {
+                TargetLanguage::C => self.c_stubs(false)?,
+                TargetLanguage::Cpp => self.c_stubs(true)?,
+                TargetLanguage::Rust => self.rust_stubs()?,
+                TargetLanguage::Python => self.python_stubs(),
+                other => return Err(format!("Userland stubs cannot be generated in {}", other.name())),
+            });
+        }
+        Ok(files)
+    }
+    
+    /// Generate the artifacts and write them below a directory
+    pub fn write_artifacts<P: AsRef<Path>>(&self, dir: P, kernel_language: &TargetLanguage, stub_languages: &[TargetLanguage]) -> Result<Vec<PathBuf>, String> {
+        let mut written = Vec::new();
+        for file in self.generate(kernel_language, stub_languages)? {
+            let path = dir.as_ref().join(&file.path);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+            }
+            fs::write(&path, file.content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+            written.push(path);
+        }
+        Ok(written)
+    }
+    
+    fn guard(&self, suffix: &str) -> String {
+        format!("{}_{}", identifier(&self.name).to_uppercase(), suffix)
+    }
+    
+    fn c_dispatch(&self) -> Vec<GeneratedFile> {
+        let guard = self.guard("SYSCALL_TABLE_H");
+        let mut header = format!("/* Syscall table '{}' generated by OSland */\n#ifndef {}\n#define {}\n\n#include <stddef.h>\n#include <stdint.h>\n\n", self.name, guard, guard);
+        for syscall in self.sorted() {
+            header.push_str(&format!("#define __NR_{} {}\n", syscall.name, syscall.number));
+        }
+        header.push_str(&format!("#define NR_SYSCALLS {}\n\n", self.slot_count()));
+        for syscall in self.sorted() {
+            if !syscall.description.is_empty() {
+                header.push_str(&format!("/* {} */\n", syscall.description));
+            }
+            header.push_str(&format!("long {}({});\n", syscall.handler_name(), c_params(syscall)));
+        }
+        header.push_str("\nlong dispatch_syscall(long nr, long a0, long a1, long a2, long a3, long a4, long a5);\n\n#endif\n");
+        
+        let mut source = "/* Syscall dispatch generated by OSland */\n#include \"syscall_table.h\"\n\n".to_string();
+        source.push_str("typedef long (*syscall_fn_t)(long, long, long, long, long, long);\n\n");
+        for syscall in self.sorted() {
+            let args: Vec<String> = syscall.args.iter().enumerate()
+                .map(|(index, arg)| match arg.arg_type.is_pointer() {
+                    true => format!("({})(uintptr_t)a{}", arg.arg_type.c_type(), index),
+                    false => format!("({})a{}", arg.arg_type.c_type(), index),
+                })
+                .collect();
+            source.push_str(&format!(
+                "static long __dispatch_{}(long a0, long a1, long a2, long a3, long a4, long a5)\n{{\n{}    return {}({});\n}}\n\n",
+                syscall.name, unused_c_args(syscall.args.len()), syscall.handler_name(), args.join(", ")));
+        }
+        source.push_str("static const syscall_fn_t syscall_table[NR_SYSCALLS] = {\n");
+        for syscall in self.sorted() {
+            source.push_str(&format!("    [__NR_{}] = __dispatch_{},\n", syscall.name, syscall.name));
+        }
+        source.push_str(&format!(
+            "}};\n\nlong dispatch_syscall(long nr, long a0, long a1, long a2, long a3, long a4, long a5)\n{{\n    if (nr < 0 || nr >= NR_SYSCALLS || !syscall_table[nr])\n        return -{};\n    return syscall_table[nr](a0, a1, a2, a3, a4, a5);\n}}\n",
+            ENOSYS));
+        
+        vec![
+            GeneratedFile { path: PathBuf::from("kernel/syscall_table.h"), content: header },
+            GeneratedFile { path: PathBuf::from("kernel/syscall_table.c"), content: source },
+        ]
+    }
+    
+    fn rust_dispatch(&self) -> GeneratedFile {
+        let mut content = format!("// Syscall dispatch for '{}' generated by OSland\n\n", self.name);
+        content.push_str(&rust_numbers(self));
+        content.push_str(&format!("pub const NR_SYSCALLS: usize = {};\npub const ENOSYS: isize = {};\n\n", self.slot_count(), ENOSYS));
+        content.push_str("/// Kernel implementation of the syscalls\npub trait SyscallHandlers {\n");
+        for syscall in self.sorted() {
+            if !syscall.description.is_empty() {
+                content.push_str(&format!("    /// {}\n", syscall.description));
+            }
+            content.push_str(&format!("    fn {}(&mut self{}) -> isize;\n", syscall.handler_name(), rust_params(syscall, true)));
+        }
+        content.push_str("}\n\n/// Dispatch a syscall to its handler\npub fn dispatch<H: SyscallHandlers>(handlers: &mut H, nr: usize, args: [usize; 6]) -> isize {\n    match nr {\n");
+        for syscall in self.sorted() {
+            let args: Vec<String> = syscall.args.iter().enumerate()
+                .map(|(index, arg)| format!("args[{}] as {}", index, arg.arg_type.rust_type()))
+                .collect();
+            content.push_str(&format!("        SYS_{} => handlers.{}({}),\n", syscall.name.to_uppercase(), syscall.handler_name(), args.join(", ")));
+        }
+        content.push_str("        _ => -ENOSYS,\n    }\n}\n");
+        GeneratedFile { path: PathBuf::from("kernel/syscall_dispatch.rs"), content }
+    }
+    
+    fn c_stubs(&self, cpp: bool) -> Result<GeneratedFile, String> {
+        let raw = c_raw_syscall(&self.arch)?;
+        let guard = self.guard(if cpp { "USYSCALL_HPP" } else { "USYSCALL_H" });
+        let mut content = format!("/* Userland syscall stubs for '{}' generated by OSland */\n#ifndef {}\n#define {}\n\n#include <stddef.h>\n#include <stdint.h>\n\n", self.name, guard, guard);
+        for syscall in self.sorted() {
+            content.push_str(&format!("#define __NR_{} {}\n", syscall.name, syscall.number));
+        }
+        content.push('\n');
+        if cpp {
+            content.push_str(&format!("namespace {} {{\n\n", identifier(&self.name)));
+        }
+        content.push_str(raw);
+        for syscall in self.sorted() {
+            let mut args: Vec<String> = syscall.args.iter()
+                .map(|arg| match arg.arg_type.is_pointer() {
+                    true => format!("(long)(uintptr_t){}", arg.name),
+                    false => format!("(long){}", arg.name),
+                })
+                .collect();
+            args.resize(MAX_SYSCALL_ARGS, "0".to_string());
+            content.push_str(&format!(
+                "\nstatic inline long {}{}({})\n{{\n    return __osl_syscall(__NR_{}, {});\n}}\n",
+                self.stub_prefix, syscall.name, c_params(syscall), syscall.name, args.join(", ")));
+        }
+        if cpp {
+            content.push_str(&format!("\n}} // namespace {}\n", identifier(&self.name)));
+        }
+        content.push_str("\n#endif\n");
+        let path = if cpp { "user/cpp/usyscall.hpp" } else { "user/c/usyscall.h" };
+        Ok(GeneratedFile { path: PathBuf::from(path), content })
+    }
+    
+    fn rust_stubs(&self) -> Result<GeneratedFile, String> {
+        let mut content = format!("// Userland syscall stubs for '{}' generated by OSland\n\n", self.name);
+        content.push_str(&rust_numbers(self));
+        content.push('\n');
+        content.push_str(rust_raw_syscall(&self.arch)?);
+        for syscall in self.sorted() {
+            let mut args: Vec<String> = syscall.args.iter().map(|arg| format!("{} as usize", arg.name)).collect();
+            args.resize(MAX_SYSCALL_ARGS, "0".to_string());
+            if !syscall.description.is_empty() {
+                content.push_str(&format!("\n/// {}", syscall.description));
+            }
+            content.push_str(&format!(
+                "\n#[inline(always)]\npub unsafe fn {}{}({}) -> isize {{\n    raw_syscall(SYS_{}, [{}])\n}}\n",
+                self.stub_prefix, syscall.name, rust_params(syscall, false), syscall.name.to_uppercase(), args.join(", ")));
+        }
+        Ok(GeneratedFile { path: PathBuf::from("user/rust/usyscall.rs"), content })
+    }
+    
+    fn python_stubs(&self) -> GeneratedFile {
+        let mut content = format!("# Userland syscall stubs for '{}' generated by OSland\nimport ctypes\n\n_libc = ctypes.CDLL(None, use_errno=True)\n_libc.syscall.restype = ctypes.c_long\n\n", self.name);
+        for syscall in self.sorted() {
+            content.push_str(&format!("NR_{} = {}\n", syscall.name.to_uppercase(), syscall.number));
+        }
+        for syscall in self.sorted() {
+            let params: Vec<&str> = syscall.args.iter().map(|arg| arg.name.as_str()).collect();
+            let mut args = vec![format!("ctypes.c_long(NR_{})", syscall.name.to_uppercase())];
+            args.extend(syscall.args.iter().map(|arg| format!("{}({})", arg.arg_type.ctypes_type(), arg.name)));
+            content.push_str(&format!("\n\ndef {}{}({}):\n", self.stub_prefix, syscall.name, params.join(", ")));
+            if !syscall.description.is_empty() {
+                content.push_str(&format!("    \"\"\"{}\"\"\"\n", syscall.description));
+            }
+            content.push_str(&format!("    return _libc.syscall({})\n", args.join(", ")));
+        }
+        GeneratedFile { path: PathBuf::from("user/python/usyscall.py"), content }
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_') && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Identifier derived from a free-form name
+fn identifier(name: &str) -> String {
+    let id: String = name.chars().map(|c| if c.is_ascii_alphanumeric() { c } else { '_' }).collect();
+    if id.starts_with(|c: char| c.is_ascii_digit()) { format!("_{}", id) } else { id }
+}
+
+fn c_params(syscall: &Syscall) -> String {
+    if syscall.args.is_empty() {
+        return "void".to_string();
+    }
+    syscall.args.iter()
+        .map(|arg| match arg.arg_type.is_pointer() {
+            true => format!("{}{}", arg.arg_type.c_type(), arg.name),
+            false => format!("{} {}", arg.arg_type.c_type(), arg.name),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn unused_c_args(used: usize) -> String {
+    (used..MAX_SYSCALL_ARGS).map(|index| format!("    (void)a{};\n", index)).collect()
+}
+
+fn rust_params(syscall: &Syscall, leading_comma: bool) -> String {
+    let params: String = syscall.args.iter().map(|arg| format!(", {}: {}", arg.name, arg.arg_type.rust_type())).collect();
+    if leading_comma { params } else { params.trim_start_matches(", ").to_string() }
+}
+
+fn rust_numbers(table: &SyscallTable) -> String {
+    table.sorted().iter().map(|syscall| format!("pub const SYS_{}: usize = {};\n", syscall.name.to_uppercase(), syscall.number)).collect()
+}
+
+fn c_raw_syscall(arch: &str) -> Result<&'static str, String> {
+    match arch {
+        "x86_64" | "amd64" => Ok(r#"static inline long __osl_syscall(long n, long a0, long a1, long a2, long a3, long a4, long a5)
+{
+    long ret;
+    register long r10 __asm__("r10") = a3;
+    register long r8 __asm__("r8") = a4;
+    register long r9 __asm__("r9") = a5;
+    __asm__ volatile ("syscall"
+                      : "=a"(ret)
+                      : "a"(n), "D"(a0), "S"(a1), "d"(a2), "r"(r10), "r"(r8), "r"(r9)
+                      : "rcx", "r11", "memory");
+    return ret;
+}
+"#),
+        "aarch64" | "arm64" => Ok(r#"static inline long __osl_syscall(long n, long a0, long a1, long a2, long a3, long a4, long a5)
+{
+    register long x8 __asm__("x8") = n;
+    register long x0 __asm__("x0") = a0;
+    register long x1 __asm__("x1") = a1;
+    register long x2 __asm__("x2") = a2;
+    register long x3 __asm__("x3") = a3;
+    register long x4 __asm__("x4") = a4;
+    register long x5 __asm__("x5") = a5;
+    __asm__ volatile ("svc #0"
+                      : "+r"(x0)
+                      : "r"(x8), "r"(x1), "r"(x2), "r"(x3), "r"(x4), "r"(x5)
+                      : "memory");
+    return x0;
+}
+"#),
+        "riscv64" => Ok(r#"static inline long __osl_syscall(long n, long a0, long a1, long a2, long a3, long a4, long a5)
+{
+    register long r7 __asm__("a7") = n;
+    register long r0 __asm__("a0") = a0;
+    register long r1 __asm__("a1") = a1;
+    register long r2 __asm__("a2") = a2;
+    register long r3 __asm__("a3") = a3;
+    register long r4 __asm__("a4") = a4;
+    register long r5 __asm__("a5") = a5;
+    __asm__ volatile ("ecall"
+                      : "+r"(r0)
+                      : "r"(r7), "r"(r1), "r"(r2), "r"(r3), "r"(r4), "r"(r5)
+                      : "memory");
+    return r0;
+}
+"#),
+        other => Err(format!("Userland stubs are not supported for '{}'", other)),
+    }
+}
+
+fn rust_raw_syscall(arch: &str) -> Result<&'static str, String> {
+    match arch {
+        "x86_64" | "amd64" => Ok(r#"#[inline(always)]
+unsafe fn raw_syscall(n: usize, a: [usize; 6]) -> isize {
+    let ret: isize;
+    core::arch::asm!("syscall", inlateout("rax") n as isize => ret,
+        in("rdi") a[0], in("rsi") a[1], in("rdx") a[2], in("r10") a[3], in("r8") a[4], in("r9") a[5],
+        lateout("rcx") _, lateout("r11") _, options(nostack));
+    ret
+}
+"#),
+        "aarch64" | "arm64" => Ok(r#"#[inline(always)]
+unsafe fn raw_syscall(n: usize, a: [usize; 6]) -> isize {
+    let ret: isize;
+    core::arch::asm!("svc 0", in("x8") n, inlateout("x0") a[0] as isize => ret,
+        in("x1") a[1], in("x2") a[2], in("x3") a[3], in("x4") a[4], in("x5") a[5], options(nostack));
+    ret
+}
+"#),
+        "riscv64" => Ok(r#"#[inline(always)]
+unsafe fn raw_syscall(n: usize, a: [usize; 6]) -> isize {
+    let ret: isize;
+    core::arch::asm!("ecall", in("a7") n, inlateout("a0") a[0] as isize => ret,
+        in("a1") a[1], in("a2") a[2], in("a3") a[3], in("a4") a[4], in("a5") a[5], options(nostack));
+    ret
+}
+"#),
+        other => Err(format!("Userland stubs are not supported for '{}'", other)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_validate_and_generate() {
+        let mut table = SyscallTable::new("demo", "x86_64");
+        table.add(Some(0), Syscall::new(0, "read").arg("fd", SyscallArgType::Fd).arg("buf", SyscallArgType::MutPtr).arg("count", SyscallArgType::Usize)).unwrap();
+        assert_eq!(table.add(None, Syscall::new(0, "write").arg("fd", SyscallArgType::Fd).arg("buf", SyscallArgType::ConstPtr).arg("count", SyscallArgType::Usize)), Ok(1));
+        table.add(Some(3), Syscall::new(0, "exit").arg("code", SyscallArgType::I32)).unwrap();
+        
+        let issues = table.validate();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].severity, SyscallIssueSeverity::Warning);
+        
+        let files = table.generate(&TargetLanguage::C, &[TargetLanguage::Rust, TargetLanguage::Python]).unwrap();
+        let paths: Vec<&str> = files.iter().map(|file| file.path.to_str().unwrap()).collect();
+        assert_eq!(paths, vec!["kernel/syscall_table.h", "kernel/syscall_table.c", "user/rust/usyscall.rs", "user/python/usyscall.py"]);
+        assert!(files[0].content.contains("long sys_read(int fd, void *buf, size_t count);"));
+        assert!(files[1].content.contains("[__NR_exit] = __dispatch_exit,"));
+        assert!(files[2].content.contains("raw_syscall(SYS_WRITE, [fd as usize, buf as usize, count as usize, 0, 0, 0])"));
+        
+        table.add(Some(1), Syscall::new(0, "open")).unwrap();
+        assert!(table.validate().iter().any(|issue| issue.severity == SyscallIssueSeverity::Error));
+        assert!(table.generate(&TargetLanguage::Rust, &[]).is_err());
+    }
+    
+    #[test]
+    fn test_add_after_last_number_fails() {
+        let mut table = SyscallTable::new("demo", "x86_64");
+        table.add(Some(u32::MAX), Syscall::new(0, "last")).unwrap();
+        assert_eq!(table.slot_count(), u64::from(u32::MAX) + 1);
+        assert!(table.add(None, Syscall::new(0, "overflow")).is_err());
+    }
+}
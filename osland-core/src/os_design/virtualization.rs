@@ -0,0 +1,509 @@
+// Virtualization target support for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! Hypervisor and guest projects.
+//!
+//! A project either builds a minimal KVM-based hypervisor or runs the OS as a guest.
+//! VMX and SVM are offered as components of the CPU virtualization extension, virtio
+//! devices as tiles. The virtualization profile turns the design into a kernel config
+//! fragment and the layout of a bootable firmware plus hypervisor image, and provides
+//! the QEMU configuration and test suite that boot the image under nested virtualization.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use crate::component_manager::component::{Component, ComponentCategory, ComponentPort, ComponentProperty, ComponentType, PortDirection};
+use crate::component_manager::ComponentLibrary;
+use crate::tile_engine::tile_core::{PortType, Tile, TileGraph, TilePort, TileType};
+use crate::build_engine::test_orchestrator::{QemuConfig, TestCase, TestKind, TestSuite};
+
+/// Tile property naming the virtio device a tile stands for
+pub const VIRTIO_DEVICE_PROPERTY: &str = "virtio_device";
+
+/// Path of the EFI stub kernel in the system partition
+///
+/// Deliberately not the removable-media path `EFI/BOOT/BOOTX64.EFI`: the firmware would
+/// start the kernel from there directly, without the command line of `startup.nsh`.
+/// With no boot loader on the partition OVMF falls back to its UEFI shell, which runs
+/// the script.
+const ESP_KERNEL_PATH: &str = "EFI/osland/vmlinuz.efi";
+
+/// Marker printed by the nested test once the guest sees a usable KVM
+const NESTED_OK_MARKER: &str = "OSLAND_NESTED_OK";
+
+/// Marker printed by the guest test once it detects the hypervisor
+const GUEST_OK_MARKER: &str = "OSLAND_GUEST_OK";
+
+/// Role of the OS in a virtualized setup
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VirtualizationMode {
+    /// The OS is a minimal hypervisor hosting guests through KVM
+    Hypervisor,
+    
+    /// The OS runs as a paravirtualized guest on virtio devices
+    Guest,
+}
+
+impl VirtualizationMode {
+    /// Lowercase mode name
+    pub fn name(&self) -> &'static str {
+        match self {
+            VirtualizationMode::Hypervisor => "hypervisor",
+            VirtualizationMode::Guest => "guest",
+        }
+    }
+    
+    /// Look a mode up by its name
+    pub fn from_name(name: &str) -> Option<Self> {
+        [VirtualizationMode::Hypervisor, VirtualizationMode::Guest].into_iter().find(|mode| mode.name() == name.to_ascii_lowercase())
+    }
+}
+
+/// Hardware virtualization extension of the CPU
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CpuVirtExtension {
+    /// Intel VT-x
+    Vmx,
+    
+    /// AMD-V
+    Svm,
+}
+
+impl CpuVirtExtension {
+    /// Every extension
+    pub fn all() -> [CpuVirtExtension; 2] {
+        [CpuVirtExtension::Vmx, CpuVirtExtension::Svm]
+    }
+    
+    /// Look an extension up by its CPU flag (`vmx` or `svm`)
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::all().into_iter().find(|extension| extension.cpu_flag() == name.to_ascii_lowercase())
+    }
+    
+    /// Flag of the extension in /proc/cpuinfo
+    pub fn cpu_flag(&self) -> &'static str {
+        match self {
+            CpuVirtExtension::Vmx => "vmx",
+            CpuVirtExtension::Svm => "svm",
+        }
+    }
+    
+    /// KVM module driving the extension
+    pub fn kvm_module(&self) -> &'static str {
+        match self {
+            CpuVirtExtension::Vmx => "kvm_intel",
+            CpuVirtExtension::Svm => "kvm_amd",
+        }
+    }
+    
+    /// Kernel config symbol of the KVM backend
+    pub fn kconfig_symbol(&self) -> &'static str {
+        match self {
+            CpuVirtExtension::Vmx => "CONFIG_KVM_INTEL",
+            CpuVirtExtension::Svm => "CONFIG_KVM_AMD",
+        }
+    }
+    
+    /// ID of the extension's component
+    pub fn component_id(&self) -> &'static str {
+        match self {
+            CpuVirtExtension::Vmx => "virt_vmx",
+            CpuVirtExtension::Svm => "virt_svm",
+        }
+    }
+    
+    /// Extension a component stands for
+    pub fn of_component(component: &Component) -> Option<Self> {
+        Self::all().into_iter().find(|extension| extension.component_id() == component.id)
+    }
+    
+    /// Whether the host's KVM module exposes the extension to its guests
+    pub fn host_nested_enabled(&self) -> bool {
+        fs::read_to_string(format!("/sys/module/{}/parameters/nested", self.kvm_module()))
+            .map_or(false, |value| matches!(value.trim(), "Y" | "1"))
+    }
+}
+
+/// Create the component of a CPU virtualization extension
+pub fn create_virt_extension_component(extension: CpuVirtExtension) -> Component {
+    let property = |name: &str, description: &str| ComponentProperty {
+        name: name.to_string(),
+        value: "true".to_string(),
+        property_type: "bool".to_string(),
+        description: description.to_string(),
+        required: false,
+        default_value: Some("true".to_string()),
+        valid_values: None,
+    };
+    
+    let (display_name, description, properties) = match extension {
+        CpuVirtExtension::Vmx => ("Intel VT-x (VMX)", "VMX root and non-root operation with VMCS-managed guests", vec![
+            property("ept", "Extended page tables for guest memory"),
+            property("vpid", "Tagged TLB entries per virtual CPU"),
+            property("unrestricted_guest", "Run guests in real mode without emulation"),
+            property("nested", "Expose VMX to guests"),
+        ]),
+        CpuVirtExtension::Svm => ("AMD-V (SVM)", "Secure virtual machine extension with VMCB-managed guests", vec![
+            property("npt", "Nested page tables for guest memory"),
+            property("asid", "Tagged TLB entries per address space"),
+            property("nrips", "Next RIP saved on intercepts"),
+            property("nested", "Expose SVM to guests"),
+        ]),
+    };
+    
+    Component {
+        id: extension.component_id().to_string(),
+        name: extension.component_id().to_string(),
+        display_name: display_name.to_string(),
+        component_type: ComponentType::Custom("CpuFeature".to_string()),
+        category: ComponentCategory::HardwareAbstraction,
+        version: "1.0.0".to_string(),
+        description: description.to_string(),
+        author: "OSland Team".to_string(),
+        source_url: None,
+        license: "MulanPSL-2.0".to_string(),
+        properties,
+        ports: vec![ComponentPort {
+            name: "virt".to_string(),
+            port_type: "VirtExtension".to_string(),
+            direction: PortDirection::Output,
+            description: "Hardware virtualization used by the hypervisor".to_string(),
+        }],
+        dependencies: Vec::new(),
+        supported_architectures: Default::default(),
+        supported_languages: vec!["c".to_string(), "rust".to_string()],
+        implementation_files: Vec::new(),
+        build_commands: Vec::new(),
+        initialization_code: String::new(),
+    }
+}
+
+/// Add the VMX and SVM components to a library
+pub fn extend_with_virtualization_components(library: &mut ComponentLibrary) {
+    for extension in CpuVirtExtension::all() {
+        // Components already present are kept
+        let _ = library.add_component(create_virt_extension_component(extension));
+    }
+}
+
+/// Virtio device
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum VirtioDevice {
+    Net,
+    Block,
+    Console,
+    Rng,
+    Balloon,
+    Gpu,
+    Vsock,
+}
+
+impl VirtioDevice {
+    /// Every device
+    pub fn all() -> [VirtioDevice; 7] {
+        [VirtioDevice::Net, VirtioDevice::Block, VirtioDevice::Console, VirtioDevice::Rng, VirtioDevice::Balloon, VirtioDevice::Gpu, VirtioDevice::Vsock]
+    }
+    
+    /// Lowercase device name
+    pub fn name(&self) -> &'static str {
+        match self {
+            VirtioDevice::Net => "net",
+            VirtioDevice::Block => "blk",
+            VirtioDevice::Console => "console",
+            VirtioDevice::Rng => "rng",
+            VirtioDevice::Balloon => "balloon",
+            VirtioDevice::Gpu => "gpu",
+            VirtioDevice::Vsock => "vsock",
+        }
+    }
+    
+    /// Look a device up by its name
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::all().into_iter().find(|device| device.name() == name.to_ascii_lowercase())
+    }
+    
+    /// Device ID from the virtio specification
+    pub fn device_id(&self) -> u32 {
+        match self {
+            VirtioDevice::Net => 1,
+            VirtioDevice::Block => 2,
+            VirtioDevice::Console => 3,
+            VirtioDevice::Rng => 4,
+            VirtioDevice::Balloon => 5,
+            VirtioDevice::Gpu => 16,
+            VirtioDevice::Vsock => 19,
+        }
+    }
+    
+    /// Kernel config symbol of the guest driver
+    pub fn kconfig_symbol(&self) -> &'static str {
+        match self {
+            VirtioDevice::Net => "CONFIG_VIRTIO_NET",
+            VirtioDevice::Block => "CONFIG_VIRTIO_BLK",
+            VirtioDevice::Console => "CONFIG_VIRTIO_CONSOLE",
+            VirtioDevice::Rng => "CONFIG_HW_RANDOM_VIRTIO",
+            VirtioDevice::Balloon => "CONFIG_VIRTIO_BALLOON",
+            VirtioDevice::Gpu => "CONFIG_DRM_VIRTIO_GPU",
+            VirtioDevice::Vsock => "CONFIG_VIRTIO_VSOCKETS",
+        }
+    }
+    
+    /// Kernel config symbol of the in-kernel host backend, if there is one
+    pub fn vhost_symbol(&self) -> Option<&'static str> {
+        match self {
+            VirtioDevice::Net => Some("CONFIG_VHOST_NET"),
+            VirtioDevice::Vsock => Some("CONFIG_VHOST_VSOCK"),
+            _ => None,
+        }
+    }
+    
+    /// QEMU device model (PCI transport)
+    pub fn qemu_device(&self) -> &'static str {
+        match self {
+            VirtioDevice::Net => "virtio-net-pci",
+            VirtioDevice::Block => "virtio-blk-pci",
+            VirtioDevice::Console => "virtio-serial-pci",
+            VirtioDevice::Rng => "virtio-rng-pci",
+            VirtioDevice::Balloon => "virtio-balloon-pci",
+            VirtioDevice::Gpu => "virtio-gpu-pci",
+            VirtioDevice::Vsock => "vhost-vsock-pci",
+        }
+    }
+    
+    /// Device a tile stands for
+    pub fn of_tile(tile: &Tile) -> Option<Self> {
+        tile.get_property(VIRTIO_DEVICE_PROPERTY).and_then(|name| Self::from_name(name))
+    }
+    
+    /// Create the tile of the device
+    pub fn tile(&self) -> Tile {
+        let (display_name, tile_type, data_type, description) = match self {
+            VirtioDevice::Net => ("Virtio Net", TileType::Network, "NetworkPacket", "Paravirtualized network interface"),
+            VirtioDevice::Block => ("Virtio Block", TileType::Storage, "DataBlock", "Paravirtualized block device"),
+            VirtioDevice::Console => ("Virtio Console", TileType::IO, "Data", "Paravirtualized serial console"),
+            VirtioDevice::Rng => ("Virtio RNG", TileType::IO, "Data", "Entropy source fed by the host"),
+            VirtioDevice::Balloon => ("Virtio Balloon", TileType::Memory, "Data", "Guest memory returned to the host on demand"),
+            VirtioDevice::Gpu => ("Virtio GPU", TileType::IO, "VideoFrame", "Paravirtualized display adapter"),
+            VirtioDevice::Vsock => ("Virtio Vsock", TileType::Network, "Data", "Host-guest socket channel"),
+        };
+        
+        let mut tile = Tile::new(display_name.to_string(), tile_type, description.to_string());
+        tile.author = "OSland Team".to_string();
+        tile.set_property(VIRTIO_DEVICE_PROPERTY.to_string(), self.name().to_string());
+        tile.set_property("device_id".to_string(), self.device_id().to_string());
+        tile.set_property("transport".to_string(), "pci".to_string());
+        tile.add_port(TilePort {
+            id: "virtqueue".to_string(),
+            name: "Virtqueue".to_string(),
+            port_type: PortType::Bidirectional,
+            data_type: "VirtioTransport".to_string(),
+            description: "Virtqueues shared with the host".to_string(),
+        });
+        tile.add_port(TilePort {
+            id: "guest".to_string(),
+            name: "Guest".to_string(),
+            port_type: PortType::Output,
+            data_type: data_type.to_string(),
+            description: "Device as seen by the guest kernel".to_string(),
+        });
+        tile
+    }
+}
+
+/// Virtualization design of a project
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VirtualizationProfile {
+    /// Role of the OS
+    pub mode: VirtualizationMode,
+    
+    /// Hardware virtualization extension of the target CPU
+    pub extension: CpuVirtExtension,
+    
+    /// Virtio devices offered to (or used by) the guest
+    pub devices: Vec<VirtioDevice>,
+    
+    /// Whether the hypervisor exposes the extension to its own guests
+    pub nested: bool,
+}
+
+impl VirtualizationProfile {
+    /// Create a profile with the console, block and network devices
+    pub fn new(mode: VirtualizationMode, extension: CpuVirtExtension) -> Self {
+        Self {
+            mode,
+            extension,
+            devices: vec![VirtioDevice::Console, VirtioDevice::Block, VirtioDevice::Net],
+            nested: false,
+        }
+    }
+    
+    /// Take the devices from the virtio tiles of a tile graph
+    pub fn with_devices_from(mut self, graph: &TileGraph) -> Self {
+        let mut devices: Vec<VirtioDevice> = graph.tiles.values().filter_map(VirtioDevice::of_tile).collect();
+        devices.sort_by_key(|device| device.device_id());
+        devices.dedup();
+        self.devices = devices;
+        self
+    }
+    
+    /// Kernel config fragment enabling the profile
+    pub fn kconfig(&self) -> String {
+        let mut lines = vec![
+            "CONFIG_VIRTUALIZATION=y".to_string(),
+            "CONFIG_VIRTIO=y".to_string(),
+            "CONFIG_VIRTIO_PCI=y".to_string(),
+        ];
+        match self.mode {
+            VirtualizationMode::Hypervisor => {
+                lines.push("CONFIG_KVM=y".to_string());
+                lines.push(format!("{}=y", self.extension.kconfig_symbol()));
+                lines.push("CONFIG_VHOST=y".to_string());
+                lines.extend(self.devices.iter().filter_map(|device| device.vhost_symbol()).map(|symbol| format!("{}=y", symbol)));
+            }
+            VirtualizationMode::Guest => {
+                lines.push("CONFIG_HYPERVISOR_GUEST=y".to_string());
+                lines.push("CONFIG_PARAVIRT=y".to_string());
+                lines.push("CONFIG_KVM_GUEST=y".to_string());
+                lines.extend(self.devices.iter().map(|device| format!("{}=y", device.kconfig_symbol())));
+            }
+        }
+        lines.push(String::new());
+        lines.join("\n")
+    }
+    
+    /// Kernel command line of the hypervisor
+    pub fn kernel_cmdline(&self) -> String {
+        let mut cmdline = "console=ttyS0 panic=-1".to_string();
+        if self.mode == VirtualizationMode::Hypervisor && self.nested {
+            cmdline.push_str(&format!(" {}.nested=1", self.extension.kvm_module()));
+        }
+        cmdline
+    }
+    
+    /// Files of the EFI system partition as (source, path in the partition)
+    ///
+    /// The kernel boots through its EFI stub, started by the UEFI shell from `startup.nsh`
+    /// (see [`Self::startup_script`]); a guest kernel and initramfs are shipped next to it
+    /// for the hypervisor to launch.
+    pub fn esp_layout(&self, kernel: &Path, initrd: Option<&Path>, guest_kernel: Option<&Path>, guest_initrd: Option<&Path>) -> Vec<(PathBuf, String)> {
+        let mut files = vec![(kernel.to_path_buf(), ESP_KERNEL_PATH.to_string())];
+        if let Some(initrd) = initrd {
+            files.push((initrd.to_path_buf(), "initrd.img".to_string()));
+        }
+        if let Some(guest_kernel) = guest_kernel {
+            files.push((guest_kernel.to_path_buf(), "guest/vmlinuz".to_string()));
+        }
+        if let Some(guest_initrd) = guest_initrd {
+            files.push((guest_initrd.to_path_buf(), "guest/initrd.img".to_string()));
+        }
+        files
+    }
+    
+    /// UEFI shell script booting the kernel with its command line
+    ///
+    /// Needs firmware that ships the UEFI shell, as stock OVMF builds do.
+    pub fn startup_script(&self, with_initrd: bool) -> String {
+        let initrd = if with_initrd { " initrd=\\initrd.img" } else { "" };
+        format!("\\{} {}{}\r\n", ESP_KERNEL_PATH.replace('/', "\\"), self.kernel_cmdline(), initrd)
+    }
+    
+    /// QEMU configuration booting the image under the firmware with KVM
+    ///
+    /// In hypervisor mode the extension is passed through so that the OS can run its own guests.
+    pub fn qemu_config(&self, firmware: &Path, image: &Path) -> QemuConfig {
+        let cpu = match self.mode {
+            VirtualizationMode::Hypervisor => format!("host,+{}", self.extension.cpu_flag()),
+            VirtualizationMode::Guest => "host".to_string(),
+        };
+        let mut extra_args = vec!["-accel".to_string(), "kvm".to_string()];
+        for device in &self.devices {
+            // The disk image and user networking are attached by QemuConfig itself
+            match device {
+                VirtioDevice::Block | VirtioDevice::Net => {}
+                VirtioDevice::Vsock => extra_args.extend(["-device".to_string(), "vhost-vsock-pci,guest-cid=3".to_string()]),
+                _ => extra_args.extend(["-device".to_string(), device.qemu_device().to_string()]),
+            }
+        }
+        
+        QemuConfig {
+            arch: "x86_64".to_string(),
+            binary: None,
+            machine: None,
+            cpu: Some(cpu),
+            memory_mb: 2048,
+            kernel: None,
+            initrd: None,
+            disk_image: Some(image.to_path_buf()),
+            append: None,
+            network: self.devices.contains(&VirtioDevice::Net),
+            firmware: Some(firmware.to_path_buf()),
+            extra_args,
+        }
+    }
+    
+    /// Test suite checking the virtualization environment from inside the OS
+    pub fn test_suite(&self) -> TestSuite {
+        let prompt = "# ".to_string();
+        let (name, command, expect) = match self.mode {
+            VirtualizationMode::Hypervisor => (
+                "nested_kvm",
+                format!("grep -q -w {} /proc/cpuinfo && test -c /dev/kvm && echo {}", self.extension.cpu_flag(), NESTED_OK_MARKER),
+                NESTED_OK_MARKER,
+            ),
+            VirtualizationMode::Guest => (
+                "guest_detect",
+                format!("grep -q -w hypervisor /proc/cpuinfo && echo {}", GUEST_OK_MARKER),
+                GUEST_OK_MARKER,
+            ),
+        };
+        
+        let mut tests = TestSuite::smoke().tests;
+        tests.retain(|test| test.name == "boot");
+        tests.push(TestCase {
+            name: name.to_string(),
+            kind: TestKind::SyscallSmoke { prompt, command, expect: expect.to_string() },
+            timeout_secs: None,
+        });
+        TestSuite { name: "virtualization".to_string(), tests }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_hypervisor_profile() {
+        let mut graph = TileGraph::new("guest devices".to_string());
+        for device in [VirtioDevice::Vsock, VirtioDevice::Net, VirtioDevice::Net] {
+            graph.add_tile(device.tile()).unwrap();
+        }
+        let mut profile = VirtualizationProfile::new(VirtualizationMode::Hypervisor, CpuVirtExtension::Svm).with_devices_from(&graph);
+        profile.nested = true;
+        assert_eq!(profile.devices, vec![VirtioDevice::Net, VirtioDevice::Vsock]);
+        assert_eq!(
+            profile.kconfig(),
+            "CONFIG_VIRTUALIZATION=y\nCONFIG_VIRTIO=y\nCONFIG_VIRTIO_PCI=y\nCONFIG_KVM=y\nCONFIG_KVM_AMD=y\nCONFIG_VHOST=y\nCONFIG_VHOST_NET=y\nCONFIG_VHOST_VSOCK=y\n"
+        );
+        assert_eq!(profile.startup_script(true), "\\EFI\\osland\\vmlinuz.efi console=ttyS0 panic=-1 kvm_amd.nested=1 initrd=\\initrd.img\r\n");
+        
+        // Nothing on the removable-media path, so OVMF falls back to the shell running startup.nsh
+        let layout = profile.esp_layout(Path::new("bzImage"), None, None, None);
+        assert_eq!(layout, vec![(PathBuf::from("bzImage"), "EFI/osland/vmlinuz.efi".to_string())]);
+        
+        let qemu = profile.qemu_config(Path::new("OVMF.fd"), Path::new("hypervisor.img"));
+        assert_eq!(qemu.cpu.as_deref(), Some("host,+svm"));
+        assert!(qemu.network);
+        let args: Vec<String> = qemu.command().get_args().map(|arg| arg.to_string_lossy().into_owned()).collect();
+        assert!(args.windows(2).any(|pair| pair == ["-bios", "OVMF.fd"]));
+        assert!(args.windows(2).any(|pair| pair == ["-device", "vhost-vsock-pci,guest-cid=3"]));
+        
+        let suite = profile.test_suite();
+        assert_eq!(suite.tests.iter().map(|test| test.name.as_str()).collect::<Vec<_>>(), vec!["boot", "nested_kvm"]);
+        
+        let guest = VirtualizationProfile::new(VirtualizationMode::Guest, CpuVirtExtension::Vmx);
+        assert!(guest.kconfig().contains("CONFIG_KVM_GUEST=y\nCONFIG_VIRTIO_CONSOLE=y\nCONFIG_VIRTIO_BLK=y\nCONFIG_VIRTIO_NET=y\n"));
+        assert_eq!(CpuVirtExtension::of_component(&create_virt_extension_component(CpuVirtExtension::Vmx)), Some(CpuVirtExtension::Vmx));
+    }
+}
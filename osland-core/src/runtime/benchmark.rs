@@ -0,0 +1,402 @@
+// Benchmark harness for OSland runtime
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use serde::{Deserialize, Serialize};
+use crate::runtime::{ProgrammingLanguage, RuntimeError, RuntimeManager};
+
+/// Benchmark run settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchmarkConfig {
+    /// Unmeasured runs before sampling
+    pub warmup_iterations: usize,
+    
+    /// Measured runs
+    pub iterations: usize,
+    
+    /// Stop sampling after this long, keeping the samples taken so far
+    pub max_duration: Duration,
+}
+
+impl Default for BenchmarkConfig {
+    fn default() -> Self {
+        Self {
+            warmup_iterations: 3,
+            iterations: 30,
+            max_duration: Duration::from_secs(60),
+        }
+    }
+}
+
+/// Latency and throughput of a benchmark
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BenchmarkResult {
+    /// Benchmark name
+    pub name: String,
+    
+    /// Measured runs
+    pub iterations: usize,
+    
+    /// Mean latency (nanoseconds)
+    pub mean_ns: u64,
+    
+    /// Median latency (nanoseconds)
+    pub p50_ns: u64,
+    
+    /// 95th percentile latency (nanoseconds)
+    pub p95_ns: u64,
+    
+    /// 99th percentile latency (nanoseconds)
+    pub p99_ns: u64,
+    
+    /// Runs per second
+    pub throughput_per_sec: f64,
+    
+    /// Largest memory usage reported by the runtime (bytes)
+    pub peak_memory_bytes: Option<usize>,
+}
+
+impl BenchmarkResult {
+    /// Compute statistics from latency samples (nanoseconds)
+    pub fn from_samples(name: &str, samples: &[u64], peak_memory_bytes: Option<usize>) -> Self {
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+        let total: u64 = sorted.iter().sum();
+        let mean_ns = if sorted.is_empty() { 0 } else { total / sorted.len() as u64 };
+        
+        Self {
+            name: name.to_string(),
+            iterations: sorted.len(),
+            mean_ns,
+            p50_ns: percentile(&sorted, 50.0),
+            p95_ns: percentile(&sorted, 95.0),
+            p99_ns: percentile(&sorted, 99.0),
+            throughput_per_sec: if total == 0 { 0.0 } else { sorted.len() as f64 * 1.0e9 / total as f64 },
+            peak_memory_bytes,
+        }
+    }
+    
+    /// Compare a later run against this one
+    pub fn compare(&self, candidate: &BenchmarkResult) -> BenchmarkDelta {
+        BenchmarkDelta {
+            baseline: self.clone(),
+            candidate: candidate.clone(),
+            p95_change_percent: change_percent(self.p95_ns as f64, candidate.p95_ns as f64),
+            mean_change_percent: change_percent(self.mean_ns as f64, candidate.mean_ns as f64),
+            throughput_change_percent: change_percent(self.throughput_per_sec, candidate.throughput_per_sec),
+        }
+    }
+}
+
+/// Change between two benchmark runs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BenchmarkDelta {
+    pub baseline: BenchmarkResult,
+    pub candidate: BenchmarkResult,
+    
+    /// Change of the p95 latency (%; negative is faster)
+    pub p95_change_percent: f64,
+    
+    /// Change of the mean latency (%; negative is faster)
+    pub mean_change_percent: f64,
+    
+    /// Change of the throughput (%; positive is faster)
+    pub throughput_change_percent: f64,
+}
+
+impl BenchmarkDelta {
+    /// Check whether the candidate is faster by more than `threshold_percent` at p95
+    pub fn is_improvement(&self, threshold_percent: f64) -> bool {
+        self.p95_change_percent < -threshold_percent
+    }
+    
+    /// One-line description of the change
+    pub fn summary(&self) -> String {
+        format!(
+            "p95 {} -> {} ns ({:+.1}%), throughput {:.1} -> {:.1}/s ({:+.1}%)",
+            self.baseline.p95_ns,
+            self.candidate.p95_ns,
+            self.p95_change_percent,
+            self.baseline.throughput_per_sec,
+            self.candidate.throughput_per_sec,
+            self.throughput_change_percent,
+        )
+    }
+}
+
+/// Function share of a flame graph
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FlamegraphHotspot {
+    /// Function name
+    pub function: String,
+    
+    /// Share of samples with the function on top of the stack (%)
+    pub self_percent: f64,
+    
+    /// Share of samples with the function anywhere on the stack (%)
+    pub total_percent: f64,
+}
+
+/// Summary of a flame graph, ordered by self time
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FlamegraphSummary {
+    /// Number of stack samples
+    pub total_samples: u64,
+    
+    /// Hottest functions
+    pub hotspots: Vec<FlamegraphHotspot>,
+}
+
+impl FlamegraphSummary {
+    /// Summarize folded stacks (`frame;frame;frame count` per line, as written by
+    /// `perf script | stackcollapse-perf`), keeping the `limit` hottest functions
+    pub fn from_folded(folded: &str, limit: usize) -> Self {
+        let mut total_samples = 0u64;
+        let mut self_samples: HashMap<&str, u64> = HashMap::new();
+        let mut total_by_function: HashMap<&str, u64> = HashMap::new();
+        
+        for line in folded.lines() {
+            let Some((stack, count)) = line.trim().rsplit_once(' ') else {
+                continue;
+            };
+            let Ok(count) = count.parse::<u64>() else {
+                continue;
+            };
+            total_samples += count;
+            
+            let frames: Vec<&str> = stack.split(';').filter(|frame| !frame.is_empty()).collect();
+            if let Some(leaf) = frames.last() {
+                *self_samples.entry(*leaf).or_default() += count;
+            }
+            // Recursive frames count once per sample
+            let mut seen: Vec<&str> = Vec::with_capacity(frames.len());
+            for frame in frames {
+                if !seen.contains(&frame) {
+                    seen.push(frame);
+                    *total_by_function.entry(frame).or_default() += count;
+                }
+            }
+        }
+        
+        let percent = |samples: u64| if total_samples == 0 { 0.0 } else { samples as f64 * 100.0 / total_samples as f64 };
+        let mut hotspots: Vec<FlamegraphHotspot> = total_by_function.iter()
+            .map(|(function, total)| FlamegraphHotspot {
+                function: function.to_string(),
+                self_percent: percent(self_samples.get(function).copied().unwrap_or(0)),
+                total_percent: percent(*total),
+            })
+            .collect();
+        hotspots.sort_by(|a, b| b.self_percent.total_cmp(&a.self_percent)
+            .then_with(|| b.total_percent.total_cmp(&a.total_percent))
+            .then_with(|| a.function.cmp(&b.function)));
+        hotspots.truncate(limit);
+        
+        Self { total_samples, hotspots }
+    }
+    
+    /// Render the summary as text for prompts and reports
+    pub fn to_text(&self) -> String {
+        let mut text = format!("{} samples\n", self.total_samples);
+        for hotspot in &self.hotspots {
+            text.push_str(&format!("{:>6.2}% self {:>6.2}% total  {}\n", hotspot.self_percent, hotspot.total_percent, hotspot.function));
+        }
+        text
+    }
+}
+
+/// Runs code repeatedly and measures its latency
+pub struct BenchmarkHarness {
+    /// Runtimes executing benchmarked code
+    runtime_manager: Arc<RuntimeManager>,
+    
+    /// Run settings
+    config: BenchmarkConfig,
+}
+
+impl BenchmarkHarness {
+    /// Create a harness
+    pub fn new(runtime_manager: Arc<RuntimeManager>, config: BenchmarkConfig) -> Self {
+        Self { runtime_manager, config }
+    }
+    
+    /// Get the run settings
+    pub fn config(&self) -> &BenchmarkConfig {
+        &self.config
+    }
+    
+    /// Benchmark code in one of the registered runtimes
+    ///
+    /// Samples are the execution times reported by the runtime, so compiling the
+    /// code and dispatching it to the runtime are not measured.
+    pub fn run_code(&self, name: &str, language: ProgrammingLanguage, code: &str) -> Result<BenchmarkResult, RuntimeError> {
+        let mut peak_memory: Option<usize> = None;
+        let result = run_timed_benchmark(&self.config, name, || {
+            let started = Instant::now();
+            let result = self.runtime_manager.execute(language, code)?;
+            let elapsed = started.elapsed();
+            if result.exit_code != 0 {
+                return Err(RuntimeError::ExecutionError(format!(
+                    "Benchmark '{}' exited with code {}: {}",
+                    name,
+                    result.exit_code,
+                    result.stderr.trim(),
+                )));
+            }
+            if let Some(memory) = result.memory_usage_bytes {
+                peak_memory = Some(peak_memory.map_or(memory, |peak| peak.max(memory)));
+            }
+            Ok(Duration::from_millis(result.execution_time_ms).min(elapsed))
+        })?;
+        Ok(BenchmarkResult { peak_memory_bytes: peak_memory, ..result })
+    }
+}
+
+/// Benchmark a closure
+pub fn run_benchmark<F>(config: &BenchmarkConfig, name: &str, mut body: F) -> Result<BenchmarkResult, RuntimeError>
+where
+    F: FnMut() -> Result<(), RuntimeError>,
+{
+    run_timed_benchmark(config, name, || {
+        let started = Instant::now();
+        body()?;
+        Ok(started.elapsed())
+    })
+}
+
+/// Benchmark a closure that returns how long the measured work took
+pub fn run_timed_benchmark<F>(config: &BenchmarkConfig, name: &str, mut body: F) -> Result<BenchmarkResult, RuntimeError>
+where
+    F: FnMut() -> Result<Duration, RuntimeError>,
+{
+    for _ in 0..config.warmup_iterations {
+        body()?;
+    }
+    
+    let started = Instant::now();
+    let mut samples = Vec::with_capacity(config.iterations);
+    for _ in 0..config.iterations.max(1) {
+        samples.push(body()?.as_nanos() as u64);
+        if started.elapsed() >= config.max_duration {
+            break;
+        }
+    }
+    Ok(BenchmarkResult::from_samples(name, &samples, None))
+}
+
+/// Nearest-rank percentile of sorted samples
+fn percentile(sorted: &[u64], percent: f64) -> u64 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let rank = (percent / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+fn change_percent(before: f64, after: f64) -> f64 {
+    if before == 0.0 {
+        return 0.0;
+    }
+    (after - before) * 100.0 / before
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::{Runtime, RuntimeConfig, RuntimeResult};
+    
+    /// Runtime taking 5 ms per run but reporting 1 ms of execution
+    struct CompilingRuntime {
+        config: RuntimeConfig,
+    }
+    
+    impl Runtime for CompilingRuntime {
+        fn initialize(&mut self) -> Result<(), RuntimeError> {
+            Ok(())
+        }
+        
+        fn execute(&mut self, code: &str) -> Result<RuntimeResult, RuntimeError> {
+            std::thread::sleep(Duration::from_millis(5));
+            Ok(RuntimeResult {
+                exit_code: if code == "fail" { 1 } else { 0 },
+                execution_time_ms: 1,
+                memory_usage_bytes: Some(code.len()),
+                ..Default::default()
+            })
+        }
+        
+        fn execute_file(&mut self, _path: &std::path::Path) -> Result<RuntimeResult, RuntimeError> {
+            Err(RuntimeError::ExecutionError("not supported".to_string()))
+        }
+        
+        fn get_language(&self) -> ProgrammingLanguage {
+            ProgrammingLanguage::C
+        }
+        
+        fn is_initialized(&self) -> bool {
+            true
+        }
+        
+        fn get_config(&self) -> &RuntimeConfig {
+            &self.config
+        }
+        
+        fn set_config(&mut self, config: RuntimeConfig) -> Result<(), RuntimeError> {
+            self.config = config;
+            Ok(())
+        }
+    }
+    
+    #[test]
+    #[allow(clippy::arc_with_non_send_sync)]
+    fn test_run_code_measures_reported_execution() {
+        let mut manager = RuntimeManager::default();
+        manager.register_runtime(Box::new(CompilingRuntime { config: RuntimeConfig::default() })).unwrap();
+        let config = BenchmarkConfig { warmup_iterations: 1, iterations: 3, ..Default::default() };
+        let harness = BenchmarkHarness::new(Arc::new(manager), config);
+        
+        let result = harness.run_code("main", ProgrammingLanguage::C, "int main(void) { return 0; }").unwrap();
+        assert_eq!(result.iterations, 3);
+        assert_eq!((result.p50_ns, result.p95_ns), (1_000_000, 1_000_000));
+        assert_eq!(result.peak_memory_bytes, Some(28));
+        assert!(harness.run_code("fail", ProgrammingLanguage::C, "fail").is_err());
+    }
+    
+    #[test]
+    fn test_statistics_and_delta() {
+        let samples: Vec<u64> = (1..=100).map(|i| i * 1000).collect();
+        let baseline = BenchmarkResult::from_samples("sched", &samples, None);
+        assert_eq!((baseline.p50_ns, baseline.p95_ns, baseline.p99_ns), (50_000, 95_000, 99_000));
+        assert_eq!(baseline.mean_ns, 50_500);
+        assert!((baseline.throughput_per_sec - 1.0e9 / 50_500.0).abs() < 1e-6);
+        
+        let faster: Vec<u64> = samples.iter().map(|sample| sample / 2).collect();
+        let delta = baseline.compare(&BenchmarkResult::from_samples("sched", &faster, None));
+        assert!((delta.p95_change_percent + 50.0).abs() < 1e-9);
+        assert!((delta.throughput_change_percent - 100.0).abs() < 1e-6);
+        assert!(delta.is_improvement(5.0));
+        assert!(delta.summary().starts_with("p95 95000 -> 47500 ns (-50.0%)"));
+        
+        let config = BenchmarkConfig { warmup_iterations: 2, iterations: 5, ..Default::default() };
+        let mut calls = 0;
+        let result = run_benchmark(&config, "count", || {
+            calls += 1;
+            Ok(())
+        }).unwrap();
+        assert_eq!((calls, result.iterations), (7, 5));
+    }
+    
+    #[test]
+    fn test_flamegraph_summary() {
+        let folded = "main;schedule;pick_next 60\nmain;schedule 10\nmain;alloc;alloc 30\nbroken line\n";
+        let summary = FlamegraphSummary::from_folded(folded, 3);
+        assert_eq!(summary.total_samples, 100);
+        let hotspots: Vec<(&str, f64, f64)> = summary.hotspots.iter()
+            .map(|hotspot| (hotspot.function.as_str(), hotspot.self_percent, hotspot.total_percent))
+            .collect();
+        assert_eq!(hotspots, vec![("pick_next", 60.0, 60.0), ("alloc", 30.0, 30.0), ("schedule", 10.0, 70.0)]);
+        assert!(summary.to_text().contains(" 60.00% self  60.00% total  pick_next"));
+    }
+}
@@ -272,12 +272,13 @@ impl Runtime for CppRuntime {
             });
         }
         
-        // Run the executable
+        // Run the executable (the reported time excludes compilation)
+        let run_started = std::time::Instant::now();
         let run_output = std::process::Command::new(exe_path)
             .output()
             .map_err(|e| RuntimeError::ExecutionError(format!("Failed to execute compiled code: {}", e)))?;
         
-        let execution_time = start_time.elapsed().as_millis() as u64;
+        let execution_time = run_started.elapsed().as_millis() as u64;
         
         Ok(RuntimeResult {
             stdout: String::from_utf8_lossy(&run_output.stdout).to_string(),
@@ -0,0 +1,321 @@
+// Cross-language call tracing for OSland runtime
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! Latency breakdown of cross-language calls.
+//!
+//! Every `CrossLanguageCall` handled by `InteropService` produces a `CallSpan` with the
+//! time spent in each phase. The tracer keeps the most recent spans and per-function
+//! aggregates, which the dashboard queries to find interop bottlenecks.
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use serde::{Deserialize, Serialize};
+use super::interop::{CrossLanguageCall, ProgrammingLanguage};
+
+/// Default number of spans kept by the tracer
+pub const DEFAULT_SPAN_CAPACITY: usize = 1000;
+
+/// Tracer installed for the process
+static GLOBAL_TRACER: OnceLock<Arc<CallTracer>> = OnceLock::new();
+
+/// Tracer shared by every `InteropService` of the process and shown on the dashboard
+pub fn global_tracer() -> Arc<CallTracer> {
+    GLOBAL_TRACER.get_or_init(|| Arc::new(CallTracer::default())).clone()
+}
+
+/// Phase of a cross-language call
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum CallPhase {
+    /// Encoding the arguments for the callee
+    Serialization,
+    /// Starting the callee runtime (process spawn, build, runtime overhead)
+    ProcessSpawn,
+    /// Running the callee function
+    Execution,
+    /// Decoding the callee output
+    Deserialization,
+}
+
+impl CallPhase {
+    /// All phases in call order
+    pub fn all() -> [CallPhase; 4] {
+        [CallPhase::Serialization, CallPhase::ProcessSpawn, CallPhase::Execution, CallPhase::Deserialization]
+    }
+    
+    /// Display name
+    pub fn name(&self) -> &'static str {
+        match self {
+            CallPhase::Serialization => "serialization",
+            CallPhase::ProcessSpawn => "spawn",
+            CallPhase::Execution => "execution",
+            CallPhase::Deserialization => "deserialization",
+        }
+    }
+}
+
+/// Trace of a single call
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CallSpan {
+    /// Calling language
+    pub caller_language: ProgrammingLanguage,
+    
+    /// Called language
+    pub callee_language: ProgrammingLanguage,
+    
+    /// Called function
+    pub function_name: String,
+    
+    /// Start time (Unix milliseconds)
+    pub started_at: u64,
+    
+    /// Time per phase in microseconds
+    pub phases: BTreeMap<CallPhase, u64>,
+    
+    /// Whether the call succeeded
+    pub success: bool,
+}
+
+impl CallSpan {
+    /// Total time of the call in microseconds
+    pub fn total_us(&self) -> u64 {
+        self.phases.values().sum()
+    }
+}
+
+/// Records the phases of a call in progress
+pub struct SpanRecorder {
+    span: CallSpan,
+}
+
+impl SpanRecorder {
+    /// Start tracing a call
+    pub fn start(call: &CrossLanguageCall) -> Self {
+        Self {
+            span: CallSpan {
+                caller_language: call.caller_language,
+                callee_language: call.callee_language,
+                function_name: call.function_name.clone(),
+                started_at: chrono::Utc::now().timestamp_millis() as u64,
+                phases: BTreeMap::new(),
+                success: false,
+            },
+        }
+    }
+    
+    /// Run a closure, recording its duration under a phase
+    pub fn measure<T>(&mut self, phase: CallPhase, f: impl FnOnce() -> T) -> T {
+        let start_time = Instant::now();
+        let value = f();
+        self.record(phase, start_time.elapsed());
+        value
+    }
+    
+    /// Add a duration to a phase
+    pub fn record(&mut self, phase: CallPhase, duration: Duration) {
+        *self.span.phases.entry(phase).or_insert(0) += duration.as_micros() as u64;
+    }
+    
+    /// Finish the span
+    pub fn finish(mut self, success: bool) -> CallSpan {
+        self.span.success = success;
+        self.span
+    }
+}
+
+/// Latency statistics of a phase
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PhaseStats {
+    /// Total time in microseconds
+    pub total_us: u64,
+    
+    /// Shortest time in microseconds
+    pub min_us: u64,
+    
+    /// Longest time in microseconds
+    pub max_us: u64,
+}
+
+impl PhaseStats {
+    fn add(&mut self, duration_us: u64, first: bool) {
+        self.total_us += duration_us;
+        self.min_us = if first { duration_us } else { self.min_us.min(duration_us) };
+        self.max_us = self.max_us.max(duration_us);
+    }
+}
+
+/// Aggregated statistics of a function
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CallStats {
+    /// Called language
+    pub callee_language: ProgrammingLanguage,
+    
+    /// Called function
+    pub function_name: String,
+    
+    /// Number of calls
+    pub calls: u64,
+    
+    /// Number of failed calls
+    pub failures: u64,
+    
+    /// Statistics per phase
+    pub phases: BTreeMap<CallPhase, PhaseStats>,
+}
+
+impl CallStats {
+    /// Mean time of a call in microseconds
+    pub fn mean_total_us(&self) -> u64 {
+        if self.calls == 0 {
+            return 0;
+        }
+        self.phases.values().map(|stats| stats.total_us).sum::<u64>() / self.calls
+    }
+    
+    /// Mean time of a phase in microseconds
+    pub fn mean_phase_us(&self, phase: CallPhase) -> u64 {
+        match self.phases.get(&phase) {
+            Some(stats) if self.calls > 0 => stats.total_us / self.calls,
+            _ => 0,
+        }
+    }
+    
+    /// Phase taking the most time
+    pub fn dominant_phase(&self) -> Option<CallPhase> {
+        self.phases.iter().max_by_key(|(_, stats)| stats.total_us).map(|(phase, _)| *phase)
+    }
+}
+
+/// Cross-language call tracer
+pub struct CallTracer {
+    capacity: usize,
+    spans: Mutex<VecDeque<CallSpan>>,
+    stats: Mutex<HashMap<(ProgrammingLanguage, String), CallStats>>,
+}
+
+impl Default for CallTracer {
+    fn default() -> Self {
+        Self::new(DEFAULT_SPAN_CAPACITY)
+    }
+}
+
+impl CallTracer {
+    /// Create a tracer keeping the given number of recent spans
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            spans: Mutex::new(VecDeque::new()),
+            stats: Mutex::new(HashMap::new()),
+        }
+    }
+    
+    /// Record a finished span
+    pub fn record(&self, span: CallSpan) {
+        {
+            let mut stats = self.stats.lock().unwrap();
+            let entry = stats.entry((span.callee_language, span.function_name.clone())).or_insert_with(|| CallStats {
+                callee_language: span.callee_language,
+                function_name: span.function_name.clone(),
+                calls: 0,
+                failures: 0,
+                phases: BTreeMap::new(),
+            });
+            let first = entry.calls == 0;
+            entry.calls += 1;
+            if !span.success {
+                entry.failures += 1;
+            }
+            for phase in CallPhase::all() {
+                let duration_us = span.phases.get(&phase).copied().unwrap_or(0);
+                entry.phases.entry(phase).or_default().add(duration_us, first);
+            }
+        }
+        
+        let mut spans = self.spans.lock().unwrap();
+        spans.push_back(span);
+        while spans.len() > self.capacity {
+            spans.pop_front();
+        }
+    }
+    
+    /// Most recent spans, oldest first
+    pub fn recent_spans(&self) -> Vec<CallSpan> {
+        self.spans.lock().unwrap().iter().cloned().collect()
+    }
+    
+    /// Statistics of every traced function
+    pub fn stats(&self) -> Vec<CallStats> {
+        let mut stats: Vec<CallStats> = self.stats.lock().unwrap().values().cloned().collect();
+        stats.sort_by(|a, b| a.callee_language.as_str().cmp(b.callee_language.as_str()).then_with(|| a.function_name.cmp(&b.function_name)));
+        stats
+    }
+    
+    /// Functions with the highest mean latency
+    pub fn bottlenecks(&self, limit: usize) -> Vec<CallStats> {
+        let mut stats = self.stats();
+        stats.sort_by_key(|stats| std::cmp::Reverse(stats.mean_total_us()));
+        stats.truncate(limit);
+        stats
+    }
+    
+    /// Total time per phase over all calls, in microseconds
+    pub fn phase_totals(&self) -> BTreeMap<CallPhase, u64> {
+        let mut totals = BTreeMap::new();
+        for stats in self.stats.lock().unwrap().values() {
+            for (phase, phase_stats) in &stats.phases {
+                *totals.entry(*phase).or_insert(0) += phase_stats.total_us;
+            }
+        }
+        totals
+    }
+    
+    /// Forget all spans and statistics
+    pub fn clear(&self) {
+        self.spans.lock().unwrap().clear();
+        self.stats.lock().unwrap().clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn span(function_name: &str, phases: [u64; 4], success: bool) -> CallSpan {
+        let call = CrossLanguageCall {
+            caller_language: ProgrammingLanguage::Rust,
+            callee_language: ProgrammingLanguage::Go,
+            function_name: function_name.to_string(),
+            arguments: vec![serde_json::json!(1)],
+            return_type: serde_json::Value::Null,
+        };
+        let mut recorder = SpanRecorder::start(&call);
+        for (phase, duration_us) in CallPhase::all().into_iter().zip(phases) {
+            recorder.record(phase, Duration::from_micros(duration_us));
+        }
+        recorder.finish(success)
+    }
+    
+    #[test]
+    fn test_call_stats() {
+        let tracer = CallTracer::new(2);
+        tracer.record(span("hash", [10, 1000, 200, 5], true));
+        tracer.record(span("hash", [20, 3000, 400, 15], false));
+        tracer.record(span("add", [1, 0, 2, 1], true));
+        
+        assert_eq!(tracer.recent_spans().len(), 2);
+        let bottlenecks = tracer.bottlenecks(1);
+        let hash = &bottlenecks[0];
+        assert_eq!(hash.function_name, "hash");
+        assert_eq!((hash.calls, hash.failures), (2, 1));
+        assert_eq!(hash.mean_total_us(), 2325);
+        assert_eq!(hash.mean_phase_us(CallPhase::ProcessSpawn), 2000);
+        assert_eq!(hash.phases[&CallPhase::Execution].min_us, 200);
+        assert_eq!(hash.phases[&CallPhase::Execution].max_us, 400);
+        assert_eq!(hash.dominant_phase(), Some(CallPhase::ProcessSpawn));
+        assert_eq!(tracer.phase_totals()[&CallPhase::Serialization], 31);
+        
+        tracer.clear();
+        assert!(tracer.stats().is_empty());
+    }
+}
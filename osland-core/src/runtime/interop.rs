@@ -6,6 +6,9 @@ use std::sync::Arc;
 use std::sync::Mutex;
 use serde::{Deserialize, Serialize};
 use crate::runtime::RuntimeError;
+use crate::runtime::repl::{ReplOutput, ReplSandbox, ReplSession};
+use crate::runtime::call_tracing::{global_tracer, CallPhase, CallTracer, SpanRecorder};
+use crate::runtime::plugin::{PluginRuntime, RuntimeManifest};
 
 /// Supported programming languages
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -84,6 +87,11 @@ pub trait Runtime {
     
     /// Set runtime-specific configuration
     fn set_config(&mut self, config: RuntimeConfig) -> Result<(), RuntimeError>;
+    
+    /// Build the program calling a function, for runtimes with their own marshaling
+    fn encode_call(&self, _function_name: &str, _arguments: &[serde_json::Value]) -> Option<String> {
+        None
+    }
 }
 
 /// Runtime configuration
@@ -205,6 +213,29 @@ impl RuntimeManager {
         runtime_guard.execute_file(path)
     }
     
+    /// Register the runtime plugins described by the manifests of a directory
+    ///
+    /// Plugins whose toolchain is missing are skipped with a warning.
+    pub fn load_plugins(&mut self, dir: &std::path::Path) -> Result<Vec<ProgrammingLanguage>, RuntimeError> {
+        let mut languages = Vec::new();
+        for manifest in RuntimeManifest::discover(dir)? {
+            let runtime = PluginRuntime::new(manifest, self.config.clone());
+            let language = runtime.get_language();
+            match self.register_runtime(Box::new(runtime)) {
+                Ok(()) => languages.push(language),
+                Err(e) => log::warn!("Skipping runtime plugin '{}': {}", language.as_str(), e),
+            }
+        }
+        Ok(languages)
+    }
+    
+    /// Build the program calling a function with the marshaling of a runtime
+    pub fn encode_call(&self, language: ProgrammingLanguage, function_name: &str, arguments: &[serde_json::Value]) -> Option<String> {
+        let runtime = self.runtimes.get(&language)?;
+        let runtime_guard = runtime.lock().ok()?;
+        runtime_guard.encode_call(function_name, arguments)
+    }
+    
     /// Check if a language is supported
     pub fn is_language_supported(&self, language: ProgrammingLanguage) -> bool {
         self.runtimes.contains_key(&language)
@@ -249,6 +280,8 @@ pub struct CrossLanguageResult {
 pub struct InteropService {
     runtime_manager: Arc<Mutex<RuntimeManager>>,
     function_registry: std::collections::HashMap<(ProgrammingLanguage, String), Box<dyn Fn(&[serde_json::Value]) -> Result<serde_json::Value, RuntimeError> + Send + Sync>>,
+    repl_sessions: std::collections::HashMap<String, ReplSession>,
+    tracer: Arc<CallTracer>,
 }
 
 impl InteropService {
@@ -257,6 +290,8 @@ impl InteropService {
         Self {
             runtime_manager,
             function_registry: std::collections::HashMap::new(),
+            repl_sessions: std::collections::HashMap::new(),
+            tracer: global_tracer(),
         }
     }
     
@@ -268,14 +303,32 @@ impl InteropService {
         self.function_registry.insert((language, name.to_string()), Box::new(func));
     }
     
+    /// Get the tracer recording the latency of cross-language calls
+    pub fn call_tracer(&self) -> Arc<CallTracer> {
+        self.tracer.clone()
+    }
+    
+    /// Record calls into another tracer instead of the global one
+    pub fn set_call_tracer(&mut self, tracer: Arc<CallTracer>) {
+        self.tracer = tracer;
+    }
+    
     /// Call a cross-language function
     pub fn call_function(&self, call: CrossLanguageCall) -> Result<CrossLanguageResult, RuntimeError> {
+        let mut recorder = SpanRecorder::start(&call);
+        let result = self.dispatch_call(&call, &mut recorder);
+        let success = matches!(&result, Ok(call_result) if call_result.success);
+        self.tracer.record(recorder.finish(success));
+        result
+    }
+    
+    fn dispatch_call(&self, call: &CrossLanguageCall, recorder: &mut SpanRecorder) -> Result<CrossLanguageResult, RuntimeError> {
         let start_time = std::time::Instant::now();
         
         // Check if the function is registered
         if let Some(func) = self.function_registry.get(&(call.callee_language, call.function_name.clone())) {
             // Call the registered function
-            match func(&call.arguments) {
+            match recorder.measure(CallPhase::Execution, || func(&call.arguments)) {
                 Ok(result) => {
                     let execution_time = start_time.elapsed().as_millis() as u64;
                     Ok(CrossLanguageResult {
@@ -300,19 +353,38 @@ impl InteropService {
             let runtime_manager = self.runtime_manager.lock().map_err(|e| RuntimeError::InteropError(format!("Failed to lock runtime manager: {}", e)))?;
             
             // Construct code to call the function
-            let code = format!("{}(...{:?})
-", call.function_name, call.arguments);
+            let encoded = recorder.measure(CallPhase::Serialization, || {
+                runtime_manager.encode_call(call.callee_language, &call.function_name, &call.arguments)
+            });
+            let marshaled = encoded.is_some();
+            let code = encoded.unwrap_or_else(|| format!("{}(...{:?})
+", call.function_name, call.arguments));
             
-            match runtime_manager.execute(call.callee_language, &code) {
+            let execute_start = std::time::Instant::now();
+            let outcome = runtime_manager.execute(call.callee_language, &code);
+            
+            // Runtimes report their own run time; the rest is spawn and toolchain overhead
+            let elapsed = execute_start.elapsed();
+            let reported = outcome.as_ref()
+                .map(|runtime_result| std::time::Duration::from_millis(runtime_result.execution_time_ms).min(elapsed))
+                .unwrap_or_default();
+            recorder.record(CallPhase::Execution, reported);
+            recorder.record(CallPhase::ProcessSpawn, elapsed - reported);
+            
+            match outcome {
                 Ok(runtime_result) => {
-                    let execution_time = start_time.elapsed().as_millis() as u64;
-                    
                     // Parse the result
-                    let result: Option<serde_json::Value> = if runtime_result.stdout.is_empty() {
-                        None
-                    } else {
-                        serde_json::from_str(&runtime_result.stdout).ok()
-                    };
+                    let result: Option<serde_json::Value> = recorder.measure(CallPhase::Deserialization, || {
+                        // Runtimes with their own marshaling have already decoded the output
+                        if marshaled {
+                            Some(runtime_result.result_data.clone())
+                        } else if runtime_result.stdout.is_empty() {
+                            None
+                        } else {
+                            serde_json::from_str(&runtime_result.stdout).ok()
+                        }
+                    });
+                    let execution_time = start_time.elapsed().as_millis() as u64;
                     
                     Ok(CrossLanguageResult {
                         success: runtime_result.exit_code == 0,
@@ -342,6 +414,46 @@ impl InteropService {
     pub fn get_runtime_manager(&self) -> Arc<Mutex<RuntimeManager>> {
         self.runtime_manager.clone()
     }
+    
+    /// Open an interactive session, returning its id
+    pub fn open_session(&mut self, language: ProgrammingLanguage, sandbox: ReplSandbox) -> Result<String, RuntimeError> {
+        let session = ReplSession::new(language, sandbox)?;
+        let session_id = uuid::Uuid::new_v4().to_string();
+        self.repl_sessions.insert(session_id.clone(), session);
+        Ok(session_id)
+    }
+    
+    /// Send a snippet to a session
+    pub fn send_snippet(&mut self, session_id: &str, snippet: &str) -> Result<ReplOutput, RuntimeError> {
+        self.session_mut(session_id)?.eval(snippet)
+    }
+    
+    /// Print the value of an expression in a session
+    pub fn inspect_session(&mut self, session_id: &str, expression: &str) -> Result<ReplOutput, RuntimeError> {
+        self.session_mut(session_id)?.inspect(expression)
+    }
+    
+    /// Get the program built so far in a session
+    pub fn session_source(&self, session_id: &str) -> Result<String, RuntimeError> {
+        self.repl_sessions.get(session_id)
+            .map(|session| session.source())
+            .ok_or_else(|| RuntimeError::InteropError(format!("Unknown session: {}", session_id)))
+    }
+    
+    /// Get the ids of the open sessions
+    pub fn sessions(&self) -> Vec<(String, ProgrammingLanguage)> {
+        self.repl_sessions.iter().map(|(id, session)| (id.clone(), session.language())).collect()
+    }
+    
+    /// Close a session and remove its workspace
+    pub fn close_session(&mut self, session_id: &str) -> bool {
+        self.repl_sessions.remove(session_id).is_some()
+    }
+    
+    fn session_mut(&mut self, session_id: &str) -> Result<&mut ReplSession, RuntimeError> {
+        self.repl_sessions.get_mut(session_id)
+            .ok_or_else(|| RuntimeError::InteropError(format!("Unknown session: {}", session_id)))
+    }
 }
 
 /// Optimization level enum
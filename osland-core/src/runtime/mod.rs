@@ -11,10 +11,20 @@ pub mod rust;
 pub mod v;
 pub mod go;
 pub mod interop;
+pub mod benchmark;
+pub mod remote;
+pub mod repl;
+pub mod call_tracing;
+pub mod plugin;
 
 // Export runtime components
 pub use interop::{ProgrammingLanguage, Runtime, RuntimeConfig, RuntimeResult, OptimizationLevel};
 pub use interop::{RuntimeManager, CrossLanguageCall, CrossLanguageResult, InteropService};
+pub use plugin::{PluginRuntime, RuntimeManifest, MarshalingAdapter, ResultFormat, RUNTIME_PLUGIN_DIR};
+pub use call_tracing::{CallTracer, CallSpan, CallStats, CallPhase, PhaseStats};
+pub use repl::{ReplSession, ReplSandbox, ReplOutput, SnippetKind};
+pub use remote::{RemoteRuntime, RemoteShell, SshShell, SshTarget, OutputStream, OutputSink};
+pub use benchmark::{BenchmarkConfig, BenchmarkResult, BenchmarkDelta, BenchmarkHarness, FlamegraphSummary, FlamegraphHotspot};
 
 // Runtime error types
 #[derive(thiserror::Error, Debug)]
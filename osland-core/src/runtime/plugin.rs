@@ -0,0 +1,394 @@
+// Runtime plugins for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! Language runtimes described by manifests instead of code.
+//!
+//! A manifest (`.osland/runtimes/<name>.json`) names the language, its source file
+//! extensions, the commands that build and run a program and how call arguments and
+//! results are marshaled:
+//!
+//! ```json
+//! {
+//!   "name": "nim",
+//!   "extensions": ["nim"],
+//!   "check": ["nim", "--version"],
+//!   "build": ["nim", "c", "--out:{binary}", "{source}"],
+//!   "run": ["{binary}"],
+//!   "marshaling": { "format": "json", "call_template": "import json\necho %*{function}({arguments})" }
+//! }
+//! ```
+//!
+//! Commands are argument vectors; `{source}`, `{binary}` and `{workdir}` are replaced
+//! in every argument. `RuntimeManager::load_plugins` registers every manifest of a directory.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Mutex;
+use serde::{Deserialize, Serialize};
+use super::{ProgrammingLanguage, Runtime, RuntimeConfig, RuntimeError, RuntimeResult};
+
+/// Directory of runtime manifests, relative to the project root
+pub const RUNTIME_PLUGIN_DIR: &str = ".osland/runtimes";
+
+/// How program output is turned into a call result
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ResultFormat {
+    /// Standard output is a JSON value
+    #[default]
+    Json,
+    /// Every line of standard output is a string in an array
+    Lines,
+    /// Standard output is a single string
+    Raw,
+}
+
+/// Marshaling adapter of a plugin
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MarshalingAdapter {
+    /// Format of the program output
+    #[serde(default)]
+    pub format: ResultFormat,
+    
+    /// Program calling a function: `{function}`, `{arguments}` (comma-separated JSON
+    /// literals) and `{arguments_json}` (JSON array) are replaced
+    #[serde(default)]
+    pub call_template: Option<String>,
+}
+
+impl MarshalingAdapter {
+    /// Build the program calling a function, if the plugin supports calls
+    pub fn encode_call(&self, function_name: &str, arguments: &[serde_json::Value]) -> Option<String> {
+        let template = self.call_template.as_ref()?;
+        let literals: Vec<String> = arguments.iter().map(|argument| argument.to_string()).collect();
+        Some(template
+            .replace("{function}", function_name)
+            .replace("{arguments_json}", &serde_json::Value::Array(arguments.to_vec()).to_string())
+            .replace("{arguments}", &literals.join(", ")))
+    }
+    
+    /// Turn program output into a value
+    pub fn decode(&self, stdout: &str) -> serde_json::Value {
+        match self.format {
+            ResultFormat::Json => serde_json::from_str(stdout.trim()).unwrap_or(serde_json::Value::Null),
+            ResultFormat::Lines => serde_json::Value::Array(stdout.lines().map(|line| serde_json::Value::String(line.to_string())).collect()),
+            ResultFormat::Raw => serde_json::Value::String(stdout.to_string()),
+        }
+    }
+}
+
+/// Runtime plugin manifest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuntimeManifest {
+    /// Language name, used as `ProgrammingLanguage::Other`
+    pub name: String,
+    
+    /// Source file extensions, without the dot; the first is used for snippets
+    pub extensions: Vec<String>,
+    
+    /// Command checking that the toolchain is installed
+    #[serde(default)]
+    pub check: Option<Vec<String>>,
+    
+    /// Command building `{source}` into `{binary}` (interpreted languages have none)
+    #[serde(default)]
+    pub build: Option<Vec<String>>,
+    
+    /// Command running the program
+    pub run: Vec<String>,
+    
+    /// Extra environment variables
+    #[serde(default)]
+    pub environment: HashMap<String, String>,
+    
+    /// Marshaling of calls and results
+    #[serde(default)]
+    pub marshaling: MarshalingAdapter,
+}
+
+impl RuntimeManifest {
+    /// Load a manifest file
+    pub fn load(path: &Path) -> Result<Self, RuntimeError> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| RuntimeError::InitError(format!("Failed to read runtime manifest {:?}: {}", path, e)))?;
+        let manifest: Self = serde_json::from_str(&content)
+            .map_err(|e| RuntimeError::InitError(format!("Invalid runtime manifest {:?}: {}", path, e)))?;
+        manifest.validate()?;
+        Ok(manifest)
+    }
+    
+    /// Load every manifest of a directory, sorted by name
+    pub fn discover(dir: &Path) -> Result<Vec<Self>, RuntimeError> {
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+        let entries = std::fs::read_dir(dir)
+            .map_err(|e| RuntimeError::InitError(format!("Failed to read {:?}: {}", dir, e)))?;
+        let mut manifests = Vec::new();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().map(|extension| extension == "json").unwrap_or(false) {
+                manifests.push(Self::load(&path)?);
+            }
+        }
+        manifests.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(manifests)
+    }
+    
+    /// Check that the manifest can describe a runtime
+    pub fn validate(&self) -> Result<(), RuntimeError> {
+        if self.name.trim().is_empty() {
+            return Err(RuntimeError::InitError("Runtime manifest has no name".to_string()));
+        }
+        if self.extensions.is_empty() {
+            return Err(RuntimeError::InitError(format!("Runtime '{}' has no file extensions", self.name)));
+        }
+        if self.run.is_empty() || self.build.as_ref().map(|build| build.is_empty()).unwrap_or(false) {
+            return Err(RuntimeError::InitError(format!("Runtime '{}' has an empty command", self.name)));
+        }
+        Ok(())
+    }
+    
+    /// Language of the plugin
+    pub fn language(&self) -> ProgrammingLanguage {
+        ProgrammingLanguage::Other(intern(&self.name.to_lowercase()))
+    }
+}
+
+/// Give plugin language names the `'static` lifetime `ProgrammingLanguage::Other` needs
+fn intern(name: &str) -> &'static str {
+    static NAMES: Mutex<Option<HashSet<&'static str>>> = Mutex::new(None);
+    let mut names = NAMES.lock().unwrap();
+    let names = names.get_or_insert_with(HashSet::new);
+    if let Some(known) = names.get(name) {
+        return known;
+    }
+    let leaked: &'static str = Box::leak(name.to_string().into_boxed_str());
+    names.insert(leaked);
+    leaked
+}
+
+/// Runtime driven by a manifest
+pub struct PluginRuntime {
+    manifest: RuntimeManifest,
+    initialized: bool,
+    config: RuntimeConfig,
+    workspace: Option<PathBuf>,
+}
+
+impl PluginRuntime {
+    /// Create a runtime from a manifest
+    pub fn new(manifest: RuntimeManifest, config: RuntimeConfig) -> Self {
+        let mut runtime = Self {
+            manifest,
+            initialized: false,
+            config,
+            workspace: None,
+        };
+        runtime.config.language = runtime.manifest.language();
+        runtime
+    }
+    
+    /// Get the manifest
+    pub fn manifest(&self) -> &RuntimeManifest {
+        &self.manifest
+    }
+    
+    /// Run a manifest command with placeholders replaced
+    fn run_command(&self, template: &[String], source: &Path, binary: &Path, extra_args: &[String]) -> Result<std::process::Output, RuntimeError> {
+        let workdir = source.parent().unwrap_or_else(|| Path::new("."));
+        let args: Vec<String> = template.iter()
+            .map(|arg| arg
+                .replace("{source}", &source.to_string_lossy())
+                .replace("{binary}", &binary.to_string_lossy())
+                .replace("{workdir}", &workdir.to_string_lossy()))
+            .collect();
+        Command::new(&args[0])
+            .args(&args[1..])
+            .args(extra_args)
+            .current_dir(workdir)
+            .envs(&self.manifest.environment)
+            .envs(&self.config.environment_variables)
+            .output()
+            .map_err(|e| RuntimeError::ExecutionError(format!("Failed to run '{}': {}", args[0], e)))
+    }
+    
+    fn build_and_run(&self, source: &Path) -> Result<RuntimeResult, RuntimeError> {
+        let start_time = std::time::Instant::now();
+        let binary = source.with_extension(if cfg!(windows) { "exe" } else { "bin" });
+        
+        if let Some(build) = &self.manifest.build {
+            let output = self.run_command(build, source, &binary, &[])?;
+            if !output.status.success() {
+                return Ok(RuntimeResult {
+                    stdout: String::from_utf8_lossy(&output.stdout).to_string(),
+                    stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+                    exit_code: output.status.code().unwrap_or(-1),
+                    execution_time_ms: start_time.elapsed().as_millis() as u64,
+                    memory_usage_bytes: None,
+                    result_data: serde_json::json!({ "stage": "build" }),
+                });
+            }
+        }
+        
+        let run_start = std::time::Instant::now();
+        let output = self.run_command(&self.manifest.run, source, &binary, &self.config.runtime_args)?;
+        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+        let result_data = if output.status.success() {
+            self.manifest.marshaling.decode(&stdout)
+        } else {
+            serde_json::Value::Null
+        };
+        
+        Ok(RuntimeResult {
+            stdout,
+            stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+            exit_code: output.status.code().unwrap_or(-1),
+            execution_time_ms: run_start.elapsed().as_millis() as u64,
+            memory_usage_bytes: None,
+            result_data,
+        })
+    }
+}
+
+impl Runtime for PluginRuntime {
+    fn initialize(&mut self) -> Result<(), RuntimeError> {
+        if self.initialized {
+            return Ok(());
+        }
+        
+        if let Some(check) = self.manifest.check.as_ref().filter(|check| !check.is_empty()) {
+            let output = Command::new(&check[0])
+                .args(&check[1..])
+                .output()
+                .map_err(|e| RuntimeError::InitError(format!("{} toolchain not found: {}", self.manifest.name, e)))?;
+            if !output.status.success() {
+                return Err(RuntimeError::InitError(format!("{} toolchain not available", self.manifest.name)));
+            }
+        }
+        
+        if self.workspace.is_none() {
+            let temp_dir = tempfile::tempdir()
+                .map_err(|e| RuntimeError::InitError(format!("Failed to create temp directory: {}", e)))?;
+            self.workspace = Some(temp_dir.into_path());
+        }
+        
+        self.initialized = true;
+        Ok(())
+    }
+    
+    fn execute(&mut self, code: &str) -> Result<RuntimeResult, RuntimeError> {
+        if !self.initialized {
+            self.initialize()?;
+        }
+        
+        let workspace = self.workspace.clone().unwrap_or_else(std::env::temp_dir);
+        let source = tempfile::Builder::new()
+            .prefix("snippet-")
+            .suffix(&format!(".{}", self.manifest.extensions[0]))
+            .tempfile_in(&workspace)
+            .map_err(|e| RuntimeError::ExecutionError(format!("Failed to create temp file: {}", e)))?;
+        std::fs::write(source.path(), code)
+            .map_err(|e| RuntimeError::ExecutionError(format!("Failed to write to temp file: {}", e)))?;
+        
+        let result = self.build_and_run(source.path());
+        let _ = std::fs::remove_file(source.path().with_extension(if cfg!(windows) { "exe" } else { "bin" }));
+        result
+    }
+    
+    fn execute_file(&mut self, path: &Path) -> Result<RuntimeResult, RuntimeError> {
+        if !self.initialized {
+            self.initialize()?;
+        }
+        
+        if !path.exists() {
+            return Err(RuntimeError::ExecutionError(format!("File not found: {:?}", path)));
+        }
+        let extension = path.extension().map(|extension| extension.to_string_lossy().to_string()).unwrap_or_default();
+        if !self.manifest.extensions.contains(&extension) {
+            return Err(RuntimeError::ExecutionError(format!("Not a {} file: {:?}", self.manifest.name, path)));
+        }
+        
+        self.build_and_run(path)
+    }
+    
+    fn get_language(&self) -> ProgrammingLanguage {
+        self.manifest.language()
+    }
+    
+    fn is_initialized(&self) -> bool {
+        self.initialized
+    }
+    
+    fn get_config(&self) -> &RuntimeConfig {
+        &self.config
+    }
+    
+    fn set_config(&mut self, config: RuntimeConfig) -> Result<(), RuntimeError> {
+        self.config = config;
+        self.config.language = self.manifest.language();
+        Ok(())
+    }
+    
+    fn encode_call(&self, function_name: &str, arguments: &[serde_json::Value]) -> Option<String> {
+        self.manifest.marshaling.encode_call(function_name, arguments)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn manifest(json: &str) -> RuntimeManifest {
+        serde_json::from_str(json).unwrap()
+    }
+    
+    #[test]
+    fn test_manifest_and_marshaling() {
+        let nim = manifest(r#"{
+            "name": "Nim",
+            "extensions": ["nim"],
+            "build": ["nim", "c", "--out:{binary}", "{source}"],
+            "run": ["{binary}"],
+            "marshaling": {"format": "json", "call_template": "import json\necho %*{function}({arguments})"}
+        }"#);
+        assert!(nim.validate().is_ok());
+        assert_eq!(nim.language(), ProgrammingLanguage::Other("nim"));
+        assert_eq!(nim.language(), manifest(r#"{"name": "nim", "extensions": ["nim"], "run": ["x"]}"#).language());
+        
+        let code = nim.marshaling.encode_call("add", &[serde_json::json!(1), serde_json::json!("two")]).unwrap();
+        assert_eq!(code, "import json\necho %*add(1, \"two\")");
+        assert_eq!(nim.marshaling.decode("{\"sum\": 3}\n"), serde_json::json!({"sum": 3}));
+        
+        let lines = MarshalingAdapter { format: ResultFormat::Lines, call_template: None };
+        assert_eq!(lines.decode("a\nb\n"), serde_json::json!(["a", "b"]));
+        assert!(lines.encode_call("f", &[]).is_none());
+        
+        assert!(manifest(r#"{"name": "x", "extensions": [], "run": ["x"]}"#).validate().is_err());
+        assert!(manifest(r#"{"name": "x", "extensions": ["x"], "run": []}"#).validate().is_err());
+    }
+    
+    #[test]
+    #[cfg(unix)]
+    fn test_plugin_runtime_executes_snippet() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("shell.json"), r#"{
+            "name": "shell",
+            "extensions": ["sh"],
+            "check": ["sh", "-c", "true"],
+            "run": ["sh", "{source}"],
+            "marshaling": {"format": "json", "call_template": "echo '{arguments_json}'"}
+        }"#).unwrap();
+        
+        let manifests = RuntimeManifest::discover(dir.path()).unwrap();
+        assert_eq!(manifests.len(), 1);
+        let mut runtime = PluginRuntime::new(manifests[0].clone(), RuntimeConfig::default());
+        let code = runtime.encode_call("echo", &[serde_json::json!([1, 2])]).unwrap();
+        let result = runtime.execute(&code).unwrap();
+        assert_eq!(result.exit_code, 0);
+        assert_eq!(result.result_data, serde_json::json!([[1, 2]]));
+        assert_eq!(runtime.get_config().language, ProgrammingLanguage::Other("shell"));
+    }
+}
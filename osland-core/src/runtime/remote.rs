@@ -0,0 +1,499 @@
+// Remote runtime over SSH for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! Runtime that builds and runs code on another machine over SSH.
+//!
+//! Generated components that target a different board (for example an ARM64
+//! development board) are copied to a work directory on the target, built there
+//! with the target's toolchain and executed. Output is streamed line by line while
+//! the program runs and collected into the `RuntimeResult`.
+
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use serde::{Deserialize, Serialize};
+use super::{Runtime, RuntimeResult, RuntimeConfig, RuntimeError, ProgrammingLanguage};
+
+/// Output stream of a remote command
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OutputStream {
+    Stdout,
+    Stderr,
+}
+
+/// Callback receiving remote output lines as they arrive
+pub type OutputSink = Arc<dyn Fn(OutputStream, &str) + Send + Sync>;
+
+/// SSH connection and remote toolchain settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SshTarget {
+    /// Host name or address
+    pub host: String,
+    
+    /// Login user (the SSH default when unset)
+    #[serde(default)]
+    pub user: Option<String>,
+    
+    /// SSH port
+    #[serde(default = "default_ssh_port")]
+    pub port: u16,
+    
+    /// Private key file
+    #[serde(default)]
+    pub identity_file: Option<PathBuf>,
+    
+    /// Remote work directory for sources and binaries
+    #[serde(default = "default_remote_dir")]
+    pub remote_dir: String,
+    
+    /// Build command template (`{source}` and `{binary}` are replaced); none for interpreted code
+    #[serde(default)]
+    pub build_command: Option<String>,
+    
+    /// Run command template
+    pub run_command: String,
+    
+    /// Extra `ssh`/`scp` options (e.g. `-o`, `StrictHostKeyChecking=accept-new`)
+    #[serde(default)]
+    pub ssh_options: Vec<String>,
+}
+
+fn default_ssh_port() -> u16 {
+    22
+}
+
+fn default_remote_dir() -> String {
+    "/tmp/osland".to_string()
+}
+
+impl SshTarget {
+    /// Create a target using the usual toolchain for a language on the remote machine
+    pub fn for_language(host: &str, language: ProgrammingLanguage) -> Result<Self, RuntimeError> {
+        let (build_command, run_command) = match language {
+            ProgrammingLanguage::C => (Some("cc -O2 -o {binary} {source}"), "{binary}"),
+            ProgrammingLanguage::Cpp => (Some("c++ -O2 -o {binary} {source}"), "{binary}"),
+            ProgrammingLanguage::Rust => (Some("rustc -O -o {binary} {source}"), "{binary}"),
+            ProgrammingLanguage::Go => (Some("go build -o {binary} {source}"), "{binary}"),
+            ProgrammingLanguage::Zig => (Some("zig build-exe -O ReleaseSafe -femit-bin={binary} {source}"), "{binary}"),
+            ProgrammingLanguage::Python => (None, "python3 {source}"),
+            ProgrammingLanguage::JavaScript => (None, "node {source}"),
+            _ => return Err(RuntimeError::UnsupportedLanguageError(format!("No remote toolchain for {}", language.as_str()))),
+        };
+        
+        Ok(Self {
+            host: host.to_string(),
+            user: None,
+            port: default_ssh_port(),
+            identity_file: None,
+            remote_dir: default_remote_dir(),
+            build_command: build_command.map(str::to_string),
+            run_command: run_command.to_string(),
+            ssh_options: Vec::new(),
+        })
+    }
+    
+    /// `user@host` destination
+    pub fn destination(&self) -> String {
+        match &self.user {
+            Some(user) => format!("{}@{}", user, self.host),
+            None => self.host.clone(),
+        }
+    }
+}
+
+/// Transport to the remote machine
+pub trait RemoteShell: Send {
+    /// Copy a local file to a remote path
+    fn upload(&mut self, local: &Path, remote: &str) -> Result<(), RuntimeError>;
+    
+    /// Run a shell command remotely, passing each output line to `on_line`, and return its exit code
+    fn run(&mut self, command: &str, on_line: &mut dyn FnMut(OutputStream, &str)) -> Result<i32, RuntimeError>;
+}
+
+/// Remote shell using the system `ssh` and `scp` clients
+pub struct SshShell {
+    target: SshTarget,
+}
+
+impl SshShell {
+    /// Create a shell for a target
+    pub fn new(target: SshTarget) -> Self {
+        Self { target }
+    }
+    
+    fn common_args(&self, command: &mut Command, port_flag: &str) {
+        // Never prompt: a missing key should fail instead of blocking the IDE
+        command.arg("-o").arg("BatchMode=yes");
+        command.arg(port_flag).arg(self.target.port.to_string());
+        if let Some(identity) = &self.target.identity_file {
+            command.arg("-i").arg(identity);
+        }
+        command.args(&self.target.ssh_options);
+    }
+}
+
+impl RemoteShell for SshShell {
+    fn upload(&mut self, local: &Path, remote: &str) -> Result<(), RuntimeError> {
+        let mut command = Command::new("scp");
+        self.common_args(&mut command, "-P");
+        let output = command
+            .arg("-q")
+            .arg(local)
+            .arg(format!("{}:{}", self.target.destination(), remote))
+            .output()
+            .map_err(|e| RuntimeError::ExecutionError(format!("Failed to run scp: {}", e)))?;
+        
+        if !output.status.success() {
+            return Err(RuntimeError::ExecutionError(format!(
+                "Failed to copy {} to {}: {}",
+                local.display(),
+                self.target.host,
+                String::from_utf8_lossy(&output.stderr).trim(),
+            )));
+        }
+        Ok(())
+    }
+    
+    fn run(&mut self, remote_command: &str, on_line: &mut dyn FnMut(OutputStream, &str)) -> Result<i32, RuntimeError> {
+        let mut command = Command::new("ssh");
+        self.common_args(&mut command, "-p");
+        let mut child = command
+            .arg(self.target.destination())
+            .arg(remote_command)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| RuntimeError::ExecutionError(format!("Failed to run ssh: {}", e)))?;
+        
+        // Read both pipes on their own threads so neither can fill up and block the other
+        let (sender, receiver) = mpsc::channel();
+        let mut readers = Vec::new();
+        if let Some(stdout) = child.stdout.take() {
+            readers.push(spawn_reader(stdout, OutputStream::Stdout, sender.clone()));
+        }
+        if let Some(stderr) = child.stderr.take() {
+            readers.push(spawn_reader(stderr, OutputStream::Stderr, sender.clone()));
+        }
+        drop(sender);
+        
+        for (stream, line) in receiver {
+            on_line(stream, &line);
+        }
+        for reader in readers {
+            let _ = reader.join();
+        }
+        
+        let status = child.wait()
+            .map_err(|e| RuntimeError::ExecutionError(format!("Failed to wait for ssh: {}", e)))?;
+        // ssh exits with 255 for its own errors
+        match status.code() {
+            Some(255) => Err(RuntimeError::ExecutionError(format!("SSH connection to {} failed", self.target.host))),
+            code => Ok(code.unwrap_or(-1)),
+        }
+    }
+}
+
+fn spawn_reader<R: std::io::Read + Send + 'static>(
+    pipe: R,
+    stream: OutputStream,
+    sender: mpsc::Sender<(OutputStream, String)>,
+) -> thread::JoinHandle<()> {
+    thread::spawn(move || {
+        for line in BufReader::new(pipe).lines() {
+            let Ok(line) = line else {
+                break;
+            };
+            if sender.send((stream, line)).is_err() {
+                break;
+            }
+        }
+    })
+}
+
+/// Runtime that builds and runs code on a remote machine
+pub struct RemoteRuntime {
+    initialized: bool,
+    config: RuntimeConfig,
+    target: SshTarget,
+    shell: Box<dyn RemoteShell>,
+    output_sink: Option<OutputSink>,
+}
+
+impl RemoteRuntime {
+    /// Create a remote runtime for a language over SSH
+    pub fn new(config: RuntimeConfig, target: SshTarget) -> Self {
+        let shell = Box::new(SshShell::new(target.clone()));
+        Self::with_shell(config, target, shell)
+    }
+    
+    /// Create a remote runtime using a custom transport
+    pub fn with_shell(config: RuntimeConfig, target: SshTarget, shell: Box<dyn RemoteShell>) -> Self {
+        Self {
+            initialized: false,
+            config,
+            target,
+            shell,
+            output_sink: None,
+        }
+    }
+    
+    /// Receive output lines while remote programs run
+    pub fn set_output_sink(&mut self, sink: OutputSink) {
+        self.output_sink = Some(sink);
+    }
+    
+    /// Get the target
+    pub fn target(&self) -> &SshTarget {
+        &self.target
+    }
+    
+    /// Run a remote command, collecting its output into `result`
+    fn run_remote(&mut self, command: &str, result: &mut RuntimeResult) -> Result<i32, RuntimeError> {
+        let sink = self.output_sink.clone();
+        self.shell.run(command, &mut |stream, line| {
+            let buffer = match stream {
+                OutputStream::Stdout => &mut result.stdout,
+                OutputStream::Stderr => &mut result.stderr,
+            };
+            buffer.push_str(line);
+            buffer.push('\n');
+            if let Some(sink) = &sink {
+                sink(stream, line);
+            }
+        })
+    }
+    
+    /// Copy a source file to the target, build it and run it
+    fn build_and_run(&mut self, source: &Path) -> Result<RuntimeResult, RuntimeError> {
+        if !self.initialized {
+            self.initialize()?;
+        }
+        let start_time = std::time::Instant::now();
+        
+        let file_name = source.file_name()
+            .and_then(|name| name.to_str())
+            .ok_or_else(|| RuntimeError::ExecutionError(format!("Invalid source file name: {:?}", source)))?;
+        let remote_source = format!("{}/{}", self.target.remote_dir.trim_end_matches('/'), file_name);
+        let remote_binary = format!("{}/{}", self.target.remote_dir.trim_end_matches('/'), file_stem(file_name));
+        self.shell.upload(source, &remote_source)?;
+        
+        let mut result = RuntimeResult::default();
+        let environment = self.environment_prefix();
+        if let Some(build) = self.target.build_command.clone() {
+            let build = expand(&build, &remote_source, &remote_binary);
+            let exit_code = self.run_remote(&format!("{}{}", environment, build), &mut result)?;
+            if exit_code != 0 {
+                result.exit_code = exit_code;
+                result.execution_time_ms = start_time.elapsed().as_millis() as u64;
+                result.result_data = serde_json::json!({ "host": self.target.host, "stage": "build" });
+                return Ok(result);
+            }
+        }
+        
+        let mut run = expand(&self.target.run_command, &remote_source, &remote_binary);
+        for arg in &self.config.runtime_args {
+            run.push(' ');
+            run.push_str(&shell_quote(arg));
+        }
+        result.exit_code = self.run_remote(&format!("{}{}", environment, run), &mut result)?;
+        result.execution_time_ms = start_time.elapsed().as_millis() as u64;
+        result.result_data = serde_json::json!({ "host": self.target.host, "stage": "run" });
+        Ok(result)
+    }
+    
+    /// `cd` into the work directory and export the configured environment
+    fn environment_prefix(&self) -> String {
+        let mut prefix = format!("cd {} && ", shell_quote(&self.target.remote_dir));
+        let mut variables: Vec<_> = self.config.environment_variables.iter().collect();
+        variables.sort();
+        for (name, value) in variables {
+            prefix.push_str(&format!("{}={} ", name, shell_quote(value)));
+        }
+        prefix
+    }
+}
+
+impl Runtime for RemoteRuntime {
+    fn initialize(&mut self) -> Result<(), RuntimeError> {
+        if self.initialized {
+            return Ok(());
+        }
+        
+        let command = format!("mkdir -p {}", shell_quote(&self.target.remote_dir));
+        let mut errors = String::new();
+        let exit_code = self.shell.run(&command, &mut |_, line| {
+            errors.push_str(line);
+            errors.push('\n');
+        }).map_err(|e| RuntimeError::InitError(e.to_string()))?;
+        if exit_code != 0 {
+            return Err(RuntimeError::InitError(format!(
+                "Failed to create {} on {}: {}",
+                self.target.remote_dir,
+                self.target.host,
+                errors.trim(),
+            )));
+        }
+        
+        self.initialized = true;
+        Ok(())
+    }
+    
+    fn execute(&mut self, code: &str) -> Result<RuntimeResult, RuntimeError> {
+        let source = tempfile::Builder::new()
+            .prefix("osland-")
+            .suffix(&format!(".{}", source_extension(self.config.language)))
+            .tempfile()
+            .map_err(|e| RuntimeError::ExecutionError(format!("Failed to create temp file: {}", e)))?;
+        std::fs::write(source.path(), code)
+            .map_err(|e| RuntimeError::ExecutionError(format!("Failed to write to temp file: {}", e)))?;
+        
+        self.build_and_run(source.path())
+    }
+    
+    fn execute_file(&mut self, path: &Path) -> Result<RuntimeResult, RuntimeError> {
+        if !path.exists() {
+            return Err(RuntimeError::ExecutionError(format!("File not found: {:?}", path)));
+        }
+        self.build_and_run(path)
+    }
+    
+    fn get_language(&self) -> ProgrammingLanguage {
+        self.config.language
+    }
+    
+    fn is_initialized(&self) -> bool {
+        self.initialized
+    }
+    
+    fn get_config(&self) -> &RuntimeConfig {
+        &self.config
+    }
+    
+    fn set_config(&mut self, config: RuntimeConfig) -> Result<(), RuntimeError> {
+        if config.language != self.config.language {
+            return Err(RuntimeError::InitError(format!(
+                "Invalid language for remote {} runtime: {:?}",
+                self.config.language.as_str(),
+                config.language,
+            )));
+        }
+        
+        self.config = config;
+        Ok(())
+    }
+}
+
+/// Source file extension of a language
+fn source_extension(language: ProgrammingLanguage) -> &'static str {
+    match language {
+        ProgrammingLanguage::Rust => "rs",
+        ProgrammingLanguage::C => "c",
+        ProgrammingLanguage::Cpp => "cpp",
+        ProgrammingLanguage::Zig => "zig",
+        ProgrammingLanguage::Go => "go",
+        ProgrammingLanguage::JavaScript => "js",
+        ProgrammingLanguage::Python => "py",
+        ProgrammingLanguage::V => "v",
+        ProgrammingLanguage::Mojo => "mojo",
+        ProgrammingLanguage::Moonbit => "mbt",
+        ProgrammingLanguage::Chim => "chim",
+        ProgrammingLanguage::Other(_) => "txt",
+    }
+}
+
+fn file_stem(file_name: &str) -> &str {
+    file_name.rsplit_once('.').map_or(file_name, |(stem, _)| stem)
+}
+
+fn expand(template: &str, source: &str, binary: &str) -> String {
+    template
+        .replace("{source}", &shell_quote(source))
+        .replace("{binary}", &shell_quote(binary))
+}
+
+/// Quote a word for a POSIX shell
+fn shell_quote(word: &str) -> String {
+    if !word.is_empty() && word.chars().all(|c| c.is_ascii_alphanumeric() || "-_./=:@".contains(c)) {
+        return word.to_string();
+    }
+    format!("'{}'", word.replace('\'', "'\\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    
+    /// Records uploads and commands, answering commands with canned output
+    struct FakeShell {
+        log: Arc<Mutex<Vec<String>>>,
+        build_exit_code: i32,
+    }
+    
+    impl RemoteShell for FakeShell {
+        fn upload(&mut self, _local: &Path, remote: &str) -> Result<(), RuntimeError> {
+            self.log.lock().unwrap().push(format!("upload {}", remote));
+            Ok(())
+        }
+        
+        fn run(&mut self, command: &str, on_line: &mut dyn FnMut(OutputStream, &str)) -> Result<i32, RuntimeError> {
+            self.log.lock().unwrap().push(command.to_string());
+            if command.starts_with("mkdir") {
+                return Ok(0);
+            }
+            if command.contains("&& cc ") {
+                on_line(OutputStream::Stderr, "warning: unused variable");
+                return Ok(self.build_exit_code);
+            }
+            on_line(OutputStream::Stdout, "hello from arm64");
+            on_line(OutputStream::Stdout, "done");
+            Ok(3)
+        }
+    }
+    
+    fn runtime(build_exit_code: i32) -> (RemoteRuntime, Arc<Mutex<Vec<String>>>) {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let config = RuntimeConfig { language: ProgrammingLanguage::C, ..Default::default() };
+        let target = SshTarget::for_language("board.local", ProgrammingLanguage::C).unwrap();
+        let shell = Box::new(FakeShell { log: log.clone(), build_exit_code });
+        (RemoteRuntime::with_shell(config, target, shell), log)
+    }
+    
+    #[test]
+    fn test_remote_build_and_run() {
+        let (mut runtime, log) = runtime(0);
+        let streamed = Arc::new(Mutex::new(Vec::new()));
+        let sink = streamed.clone();
+        runtime.set_output_sink(Arc::new(move |stream, line| sink.lock().unwrap().push((stream, line.to_string()))));
+        
+        let result = runtime.execute("int main(void) { return 3; }").unwrap();
+        assert_eq!(result.exit_code, 3);
+        assert_eq!(result.stdout, "hello from arm64\ndone\n");
+        assert_eq!(result.stderr, "warning: unused variable\n");
+        assert_eq!(result.result_data["stage"], "run");
+        let log = log.lock().unwrap();
+        assert_eq!(log.len(), 4);
+        assert_eq!(log[0], "mkdir -p /tmp/osland");
+        assert!(log[1].starts_with("upload /tmp/osland/osland-") && log[1].ends_with(".c"));
+        assert!(log[2].starts_with("cd /tmp/osland && cc -O2 -o /tmp/osland/osland-"));
+        assert!(log[3].starts_with("cd /tmp/osland && /tmp/osland/osland-") && !log[3].ends_with(".c"));
+        assert_eq!(streamed.lock().unwrap().len(), 3);
+        
+        let (mut failing, _) = runtime(1);
+        let result = failing.execute("int main(void) {").unwrap();
+        assert_eq!(result.exit_code, 1);
+        assert_eq!(result.result_data["stage"], "build");
+        assert!(result.stdout.is_empty());
+    }
+    
+    #[test]
+    fn test_shell_quote() {
+        assert_eq!(shell_quote("/tmp/osland/a.c"), "/tmp/osland/a.c");
+        assert_eq!(shell_quote("it's here"), "'it'\\''s here'");
+        assert_eq!(expand("cc -o {binary} {source}", "/w/a b.c", "/w/a b"), "cc -o '/w/a b' '/w/a b.c'");
+    }
+}
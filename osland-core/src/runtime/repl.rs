@@ -0,0 +1,1054 @@
+// Interactive sessions for OSland runtimes
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! REPL sessions for compiled languages (C, C++ and Go).
+//!
+//! C and C++ sessions keep a host process alive that holds the program state. Each
+//! snippet is compiled into a shared library and run in a child forked from the host:
+//! when the snippet returns, the child becomes the new host, so only the new snippet
+//! runs and the state built by earlier ones is kept. When it crashes, exits or times
+//! out, the host is left as it was and the snippet is dropped. Variables declared by a
+//! statement snippet of their own (`int x = f();`) become globals so later snippets can
+//! use them; other locals end with their snippet.
+//!
+//! Go cannot load code into a running program, so Go sessions compile the accepted
+//! snippets together with the new one, replay the program and only report the output
+//! added by the new snippet.
+//!
+//! Programs run in the session workspace in their own process group, with a cleared
+//! environment, a time limit per snippet, address space and file size limits and,
+//! where unprivileged namespaces are available, without network access.
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::Path;
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::OnceLock;
+use std::thread;
+use std::time::{Duration, Instant};
+use log::warn;
+use serde::{Deserialize, Serialize};
+use super::{ProgrammingLanguage, RuntimeError};
+
+/// Line printed before the value of an inspected Go expression
+const INSPECT_MARKER: &str = "@@osland-inspect@@";
+
+/// Line the C/C++ host prints on both streams after each snippet
+const DONE_MARKER: &str = "@@osland-done@@";
+
+/// Time the host gets on top of the snippet timeout to report back
+const HOST_GRACE: Duration = Duration::from_secs(5);
+
+/// Host of C/C++ sessions
+///
+/// Commands are read from the original standard input, one per line:
+/// `run|peek <timeout-ms> <symbol> <library>`. The snippet runs in a forked child; after
+/// `run`, a child whose snippet returned kills its parent and serves the next commands.
+const HOST_SOURCE: &str = r#"#include <dlfcn.h>
+#include <fcntl.h>
+#include <signal.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+#include <sys/wait.h>
+#include <unistd.h>
+
+#define DONE_MARKER "@@osland-done@@"
+
+static void done(const char *status) {
+    fflush(stdout);
+    fflush(stderr);
+    printf("\n" DONE_MARKER " %s\n", status);
+    fprintf(stderr, "\n" DONE_MARKER "\n");
+    fflush(stdout);
+    fflush(stderr);
+}
+
+int main(void) {
+    FILE *commands = fdopen(dup(0), "r");
+    int null = open("/dev/null", O_RDONLY);
+    char line[8192];
+    if (!commands || null < 0 || dup2(null, 0) < 0) {
+        return 1;
+    }
+    while (fgets(line, sizeof line, commands)) {
+        char mode[8], symbol[64], status_text[32];
+        long timeout_ms = 0;
+        int offset = 0;
+        line[strcspn(line, "\n")] = '\0';
+        if (sscanf(line, "%7s %ld %63s %n", mode, &timeout_ms, symbol, &offset) != 3 || offset == 0) {
+            done("error");
+            continue;
+        }
+        fflush(stdout);
+        fflush(stderr);
+        pid_t child = fork();
+        if (child < 0) {
+            perror("fork");
+            done("error");
+            continue;
+        }
+        if (child == 0) {
+            void *library = dlopen(line + offset, RTLD_NOW | RTLD_GLOBAL);
+            void (*snippet)(void) = library ? (void (*)(void))dlsym(library, symbol) : NULL;
+            if (!snippet) {
+                fprintf(stderr, "%s\n", dlerror());
+                fflush(stderr);
+                _exit(127);
+            }
+            snippet();
+            if (strcmp(mode, "run") != 0) {
+                fflush(stdout);
+                fflush(stderr);
+                _exit(0);
+            }
+            kill(getppid(), SIGKILL);
+            done("ok");
+            continue;
+        }
+        int status = 0;
+        long waited = 0;
+        pid_t finished;
+        while ((finished = waitpid(child, &status, WNOHANG)) == 0 && waited < timeout_ms) {
+            usleep(10000);
+            waited += 10;
+        }
+        if (finished == 0) {
+            kill(child, SIGKILL);
+            waitpid(child, &status, 0);
+            done("timeout");
+        } else if (WIFEXITED(status)) {
+            snprintf(status_text, sizeof status_text, "exit %d", WEXITSTATUS(status));
+            done(status_text);
+        } else {
+            snprintf(status_text, sizeof status_text, "signal %d", WIFSIGNALED(status) ? WTERMSIG(status) : 0);
+            done(status_text);
+        }
+    }
+    return 0;
+}
+"#;
+
+/// Limits for programs run by a session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplSandbox {
+    /// Wall-clock limit per snippet
+    pub timeout: Duration,
+    
+    /// Address space limit (MiB, Unix only)
+    pub memory_limit_mb: Option<u64>,
+    
+    /// Size limit of files written by snippets (MiB, Unix only)
+    #[serde(default)]
+    pub file_size_limit_mb: Option<u64>,
+    
+    /// Let snippets use the network instead of running them in an empty network namespace
+    #[serde(default)]
+    pub allow_network: bool,
+}
+
+impl Default for ReplSandbox {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(10),
+            memory_limit_mb: Some(1024),
+            file_size_limit_mb: Some(64),
+            allow_network: false,
+        }
+    }
+}
+
+/// Kind of snippet, deciding where it goes in the program
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SnippetKind {
+    /// `#include`/`#define` lines or Go imports
+    Include,
+    /// Functions, types and other top-level declarations
+    Declaration,
+    /// Statements run in `main`
+    Statement,
+}
+
+/// Result of evaluating a snippet
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReplOutput {
+    /// Output added by the snippet
+    pub stdout: String,
+    
+    /// Compiler or program errors
+    pub stderr: String,
+    
+    /// Exit code of the program (-1 when it did not run or was killed)
+    pub exit_code: i32,
+    
+    /// Whether the snippet was kept in the session
+    pub accepted: bool,
+    
+    /// Compile and run time (milliseconds)
+    pub elapsed_ms: u64,
+}
+
+/// C/C++ code of a snippet: what it defines, what it runs and what later snippets see
+#[derive(Debug, Clone, Default, PartialEq)]
+struct SnippetCode {
+    definition: String,
+    body: String,
+    interface: String,
+}
+
+/// Interactive session of a compiled language
+pub struct ReplSession {
+    language: ProgrammingLanguage,
+    sandbox: ReplSandbox,
+    workspace: tempfile::TempDir,
+    includes: Vec<String>,
+    declarations: Vec<String>,
+    statements: Vec<String>,
+    /// Declarations of the accepted C/C++ snippets, compiled into every new snippet
+    interfaces: Vec<String>,
+    /// Number of C/C++ snippets compiled so far, naming their libraries and entry points
+    compiled: usize,
+    host: Option<ReplHost>,
+    /// Program output after the accepted Go snippets
+    last_stdout: String,
+}
+
+impl ReplSession {
+    /// Start a session
+    pub fn new(language: ProgrammingLanguage, sandbox: ReplSandbox) -> Result<Self, RuntimeError> {
+        match language {
+            ProgrammingLanguage::Go => {}
+            ProgrammingLanguage::C | ProgrammingLanguage::Cpp if cfg!(unix) => {}
+            _ => return Err(RuntimeError::UnsupportedLanguageError(format!("No REPL for {}", language.as_str()))),
+        }
+        let workspace = tempfile::Builder::new()
+            .prefix("osland-repl-")
+            .tempdir()
+            .map_err(|e| RuntimeError::InitError(format!("Failed to create REPL workspace: {}", e)))?;
+        
+        Ok(Self {
+            language,
+            sandbox,
+            workspace,
+            includes: Vec::new(),
+            declarations: Vec::new(),
+            statements: Vec::new(),
+            interfaces: Vec::new(),
+            compiled: 0,
+            host: None,
+            last_stdout: String::new(),
+        })
+    }
+    
+    /// Get the session language
+    pub fn language(&self) -> ProgrammingLanguage {
+        self.language
+    }
+    
+    /// Get the session workspace
+    pub fn workspace(&self) -> &Path {
+        self.workspace.path()
+    }
+    
+    /// Guess where a snippet belongs
+    pub fn classify(&self, snippet: &str) -> SnippetKind {
+        let first_word = snippet.split_whitespace().next().unwrap_or("");
+        match self.language {
+            ProgrammingLanguage::Go => match first_word {
+                "import" => SnippetKind::Include,
+                "func" | "type" => SnippetKind::Declaration,
+                _ => SnippetKind::Statement,
+            },
+            _ => {
+                if first_word.starts_with('#') {
+                    return SnippetKind::Include;
+                }
+                let declaration_keywords = [
+                    "struct", "union", "enum", "typedef", "static", "extern", "inline",
+                    "class", "template", "namespace", "using",
+                ];
+                if declaration_keywords.contains(&first_word) || looks_like_function_definition(snippet) {
+                    SnippetKind::Declaration
+                } else {
+                    SnippetKind::Statement
+                }
+            }
+        }
+    }
+    
+    /// Evaluate a snippet, keeping it if it compiles and runs
+    pub fn eval(&mut self, snippet: &str) -> Result<ReplOutput, RuntimeError> {
+        let kind = self.classify(snippet);
+        self.eval_as(snippet, kind)
+    }
+    
+    /// Evaluate a snippet of a given kind, keeping it if it compiles and runs
+    pub fn eval_as(&mut self, snippet: &str, kind: SnippetKind) -> Result<ReplOutput, RuntimeError> {
+        let snippet = snippet.trim().to_string();
+        if self.language == ProgrammingLanguage::Go {
+            return self.eval_replayed(snippet, kind);
+        }
+        
+        let mut includes = self.includes.clone();
+        let code = match kind {
+            SnippetKind::Include => {
+                includes.push(snippet.clone());
+                SnippetCode::default()
+            }
+            _ => snippet_code(self.language, &snippet, kind),
+        };
+        let output = self.run_in_host(&includes, &code, "run")?;
+        if output.accepted {
+            self.includes = includes;
+            if !code.interface.is_empty() {
+                self.interfaces.push(code.interface);
+            }
+            match kind {
+                SnippetKind::Include => {}
+                SnippetKind::Declaration => self.declarations.push(snippet),
+                SnippetKind::Statement => self.statements.push(snippet),
+            }
+        }
+        Ok(output)
+    }
+    
+    /// Print the value of an expression in the current state, without changing the session
+    pub fn inspect(&mut self, expression: &str) -> Result<ReplOutput, RuntimeError> {
+        let expression = expression.trim();
+        let mut output = match self.language {
+            ProgrammingLanguage::Go => {
+                let source = self.render(&self.includes, &self.declarations, &self.statements, Some(expression));
+                let mut output = self.build_and_run(&source)?;
+                output.stdout = match output.stdout.split_once(&format!("{}\n", INSPECT_MARKER)) {
+                    Some((_, value)) => value.to_string(),
+                    None => String::new(),
+                };
+                output
+            }
+            _ => {
+                let body = match self.language {
+                    ProgrammingLanguage::Cpp => format!("std::cout << ({}) << std::endl;", expression),
+                    _ => format!("OSLAND_INSPECT({});", expression),
+                };
+                let includes = self.includes.clone();
+                self.run_in_host(&includes, &SnippetCode { body, ..Default::default() }, "peek")?
+            }
+        };
+        output.stdout = output.stdout.trim_end().to_string();
+        Ok(output)
+    }
+    
+    /// Get the program built from the accepted snippets, e.g. to commit it to a tile
+    pub fn source(&self) -> String {
+        self.render(&self.includes, &self.declarations, &self.statements, None)
+    }
+    
+    /// Forget all snippets
+    pub fn reset(&mut self) {
+        self.includes.clear();
+        self.declarations.clear();
+        self.statements.clear();
+        self.interfaces.clear();
+        self.last_stdout.clear();
+        self.host = None;
+    }
+    
+    fn render(&self, includes: &[String], declarations: &[String], statements: &[String], inspect: Option<&str>) -> String {
+        match self.language {
+            ProgrammingLanguage::Go => render_go(includes, declarations, statements, inspect),
+            ProgrammingLanguage::Cpp => render_cpp(includes, declarations, statements, inspect),
+            _ => render_c(includes, declarations, statements, inspect),
+        }
+    }
+    
+    /// Compile a C/C++ snippet into a library and run it in the host
+    fn run_in_host(&mut self, includes: &[String], code: &SnippetCode, mode: &str) -> Result<ReplOutput, RuntimeError> {
+        let start_time = Instant::now();
+        self.compiled += 1;
+        let symbol = format!("osland_snippet_{}", self.compiled);
+        let (extension, compiler, standard) = match self.language {
+            ProgrammingLanguage::Cpp => ("cpp", "c++", "-std=c++17"),
+            _ => ("c", "cc", "-std=c11"),
+        };
+        let source_path = self.workspace.path().join(format!("snippet_{}.{}", self.compiled, extension));
+        let library = self.workspace.path().join(format!("snippet_{}.so", self.compiled));
+        std::fs::write(&source_path, render_snippet(self.language, includes, &self.interfaces, code, &symbol))
+            .map_err(|e| RuntimeError::ExecutionError(format!("Failed to write snippet: {}", e)))?;
+        
+        let build = Command::new(compiler)
+            .args([standard, "-O0", "-shared", "-fPIC", "-o"])
+            .arg(&library)
+            .arg(&source_path)
+            .current_dir(self.workspace.path())
+            .output()
+            .map_err(|e| RuntimeError::ExecutionError(format!("Failed to run {}: {}", compiler, e)))?;
+        if !build.status.success() {
+            return Ok(ReplOutput {
+                stderr: String::from_utf8_lossy(&build.stderr).to_string(),
+                exit_code: -1,
+                elapsed_ms: start_time.elapsed().as_millis() as u64,
+                ..Default::default()
+            });
+        }
+        
+        if self.host.is_none() {
+            self.host = Some(ReplHost::start(self.workspace.path(), &self.sandbox)?);
+        }
+        let result = self.host.as_mut().map(|host| host.run(mode, &symbol, &library, self.sandbox.timeout));
+        let (stdout, mut stderr, status) = match result {
+            Some(Ok(result)) => result,
+            Some(Err(e)) => {
+                // The state lived in the host, so the snippets are gone with it
+                self.reset();
+                return Err(e);
+            }
+            None => unreachable!(),
+        };
+        
+        let exit_code = match status.as_str() {
+            "ok" => 0,
+            "timeout" => {
+                stderr.push_str(&format!("Snippet killed after {:?}\n", self.sandbox.timeout));
+                -1
+            }
+            _ => match status.strip_prefix("exit ").and_then(|code| code.parse().ok()) {
+                Some(0) if mode == "run" => {
+                    stderr.push_str("The snippet exited instead of returning and was not kept\n");
+                    0
+                }
+                Some(code) => code,
+                None => {
+                    stderr.push_str(&format!("Snippet stopped ({})\n", status));
+                    -1
+                }
+            },
+        };
+        Ok(ReplOutput {
+            stdout,
+            stderr,
+            exit_code,
+            accepted: status == "ok",
+            elapsed_ms: start_time.elapsed().as_millis() as u64,
+        })
+    }
+    
+    /// Evaluate a Go snippet by replaying the program with it
+    fn eval_replayed(&mut self, snippet: String, kind: SnippetKind) -> Result<ReplOutput, RuntimeError> {
+        let mut candidate = (self.includes.clone(), self.declarations.clone(), self.statements.clone());
+        match kind {
+            SnippetKind::Include => candidate.0.push(snippet),
+            SnippetKind::Declaration => candidate.1.push(snippet),
+            SnippetKind::Statement => candidate.2.push(snippet),
+        }
+        
+        let source = self.render(&candidate.0, &candidate.1, &candidate.2, None);
+        let mut output = self.build_and_run(&source)?;
+        if output.exit_code == 0 {
+            output.accepted = true;
+            (self.includes, self.declarations, self.statements) = candidate;
+            let stdout = std::mem::take(&mut output.stdout);
+            output.stdout = stdout.strip_prefix(self.last_stdout.as_str()).unwrap_or(&stdout).to_string();
+            self.last_stdout = stdout;
+        }
+        Ok(output)
+    }
+    
+    fn build_and_run(&self, source: &str) -> Result<ReplOutput, RuntimeError> {
+        let start_time = Instant::now();
+        std::fs::write(self.workspace.path().join("main.go"), source)
+            .map_err(|e| RuntimeError::ExecutionError(format!("Failed to write snippet: {}", e)))?;
+        
+        let build = Command::new("go")
+            .args(["build", "-o", "snippet", "main.go"])
+            .current_dir(self.workspace.path())
+            .output()
+            .map_err(|e| RuntimeError::ExecutionError(format!("Failed to run go: {}", e)))?;
+        if !build.status.success() {
+            return Ok(ReplOutput {
+                stderr: String::from_utf8_lossy(&build.stderr).to_string(),
+                exit_code: -1,
+                elapsed_ms: start_time.elapsed().as_millis() as u64,
+                ..Default::default()
+            });
+        }
+        
+        let (stdout, stderr, exit_code) = run_sandboxed(&self.workspace.path().join("snippet"), self.workspace.path(), &self.sandbox)?;
+        Ok(ReplOutput {
+            stdout,
+            stderr,
+            exit_code,
+            accepted: false,
+            elapsed_ms: start_time.elapsed().as_millis() as u64,
+        })
+    }
+}
+
+/// Process holding the state of a C/C++ session
+struct ReplHost {
+    process: Child,
+    commands: ChildStdin,
+    /// Output lines of the host and its children, `true` for standard error
+    output: Receiver<(bool, String)>,
+}
+
+impl ReplHost {
+    /// Build the host in the workspace and start it in the sandbox
+    fn start(workspace: &Path, sandbox: &ReplSandbox) -> Result<Self, RuntimeError> {
+        let binary = workspace.join("osland-repl-host");
+        if !binary.exists() {
+            std::fs::write(workspace.join("osland-repl-host.c"), HOST_SOURCE)
+                .map_err(|e| RuntimeError::InitError(format!("Failed to write REPL host: {}", e)))?;
+            let build = Command::new("cc")
+                .args(["-O1", "-o", "osland-repl-host", "osland-repl-host.c", "-ldl"])
+                .current_dir(workspace)
+                .output()
+                .map_err(|e| RuntimeError::InitError(format!("Failed to run cc: {}", e)))?;
+            if !build.status.success() {
+                return Err(RuntimeError::InitError(format!("Failed to build REPL host: {}", String::from_utf8_lossy(&build.stderr))));
+            }
+        }
+        
+        let mut process = sandboxed_command(&binary, workspace, sandbox)
+            .stdin(Stdio::piped())
+            .spawn()
+            .map_err(|e| RuntimeError::InitError(format!("Failed to start REPL host: {}", e)))?;
+        let (sender, output) = mpsc::channel();
+        for (is_stderr, pipe) in [
+            (false, process.stdout.take().map(|pipe| Box::new(pipe) as Box<dyn Read + Send>)),
+            (true, process.stderr.take().map(|pipe| Box::new(pipe) as Box<dyn Read + Send>)),
+        ] {
+            let (Some(pipe), sender) = (pipe, sender.clone()) else {
+                continue;
+            };
+            thread::spawn(move || {
+                let mut reader = BufReader::new(pipe);
+                let mut line = Vec::new();
+                while reader.read_until(b'\n', &mut line).unwrap_or(0) > 0 {
+                    if sender.send((is_stderr, String::from_utf8_lossy(&line).to_string())).is_err() {
+                        break;
+                    }
+                    line.clear();
+                }
+            });
+        }
+        let commands = process.stdin.take()
+            .ok_or_else(|| RuntimeError::InitError("REPL host has no standard input".to_string()))?;
+        Ok(Self { process, commands, output })
+    }
+    
+    /// Run the entry point of a library, returning the output and status reported by the host
+    fn run(&mut self, mode: &str, symbol: &str, library: &Path, timeout: Duration) -> Result<(String, String, String), RuntimeError> {
+        let lost = |reason: String| RuntimeError::ExecutionError(format!("The REPL host {}; the session was reset", reason));
+        writeln!(self.commands, "{} {} {} {}", mode, timeout.as_millis(), symbol, library.display())
+            .and_then(|_| self.commands.flush())
+            .map_err(|e| lost(format!("cannot be reached ({})", e)))?;
+        
+        let deadline = Instant::now() + timeout + HOST_GRACE;
+        let (mut stdout, mut stderr) = (String::new(), String::new());
+        let mut status = None;
+        let mut stderr_done = false;
+        while status.is_none() || !stderr_done {
+            match self.output.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                Ok((false, line)) => match line.strip_prefix(DONE_MARKER) {
+                    Some(rest) => status = Some(rest.trim().to_string()),
+                    None => stdout.push_str(&line),
+                },
+                Ok((true, line)) if line.trim_end() == DONE_MARKER => stderr_done = true,
+                Ok((true, line)) => stderr.push_str(&line),
+                Err(mpsc::RecvTimeoutError::Timeout) => return Err(lost("stopped responding".to_string())),
+                Err(mpsc::RecvTimeoutError::Disconnected) => return Err(lost("exited".to_string())),
+            }
+        }
+        // The host starts its report on a new line
+        stdout.pop();
+        stderr.pop();
+        Ok((stdout, stderr, status.unwrap_or_default()))
+    }
+}
+
+impl Drop for ReplHost {
+    fn drop(&mut self) {
+        // Earlier hosts are gone, the current one is a descendant in the same process group
+        kill_process_group(&mut self.process);
+    }
+}
+
+/// Heuristic for `type name(args) {` at the start of a C/C++ snippet
+fn looks_like_function_definition(snippet: &str) -> bool {
+    let Some(header) = snippet.split('{').next() else {
+        return false;
+    };
+    let header = header.trim();
+    let Some(open) = header.find('(') else {
+        return false;
+    };
+    header.ends_with(')')
+        && snippet.contains('{')
+        && !header.contains([';', '='])
+        && header[..open].split_whitespace().count() >= 2
+        && !header.starts_with("if")
+        && !header.starts_with("for")
+        && !header.starts_with("while")
+        && !header.starts_with("switch")
+}
+
+/// Split a snippet declaring one variable into the declarator and the initializer
+///
+/// `static int *p = &x;` gives `("int *p", Some("&x"))`; snippets with other statements,
+/// several declarators or no type give `None`.
+fn split_variable(snippet: &str) -> Option<(String, Option<String>)> {
+    let body = snippet.trim().strip_suffix(';')?;
+    if body.contains(';') {
+        return None;
+    }
+    let bytes = body.as_bytes();
+    let mut depth = 0i32;
+    let mut equals = None;
+    for (index, &byte) in bytes.iter().enumerate() {
+        match byte {
+            b'(' | b'[' | b'{' | b'<' => depth += 1,
+            b')' | b']' | b'}' | b'>' => depth -= 1,
+            b'=' if depth == 0
+                && bytes.get(index + 1) != Some(&b'=')
+                && !index.checked_sub(1).is_some_and(|previous| b"=!<>+-*/%&|^".contains(&bytes[previous])) => {
+                equals = Some(index);
+                break;
+            }
+            _ => {}
+        }
+    }
+    let (declarator, initializer) = match equals {
+        Some(index) => (body[..index].trim(), Some(body[index + 1..].trim().to_string()).filter(|init| !init.is_empty())),
+        None => (body.trim(), None),
+    };
+    let declarator = declarator.strip_prefix("static ").unwrap_or(declarator).trim();
+    let mut depth = 0i32;
+    let several = initializer.iter().flat_map(|init| init.chars()).any(|c| {
+        match c {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth -= 1,
+            _ => {}
+        }
+        c == ',' && depth == 0
+    });
+    if several {
+        return None;
+    }
+    
+    let words: Vec<&str> = declarator.split_whitespace().collect();
+    let keywords = ["return", "goto", "break", "continue", "case", "default", "else", "do", "delete", "throw", "typedef", "extern", "using"];
+    let name = words.last()?.trim_start_matches(['*', '&']);
+    let name = name.split('[').next().unwrap_or(name);
+    let valid = words.len() >= 2
+        && !keywords.contains(&words[0])
+        && declarator.chars().all(|c| c.is_ascii_alphanumeric() || " \t\n_*&:<>,[]".contains(c))
+        && (!declarator.contains(',') || declarator.contains('<'))
+        && name.chars().next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    valid.then(|| (declarator.to_string(), initializer))
+}
+
+/// How a C/C++ declaration or statement snippet is compiled
+fn snippet_code(language: ProgrammingLanguage, snippet: &str, kind: SnippetKind) -> SnippetCode {
+    let words: Vec<&str> = snippet.split_whitespace().collect();
+    let cpp = language == ProgrammingLanguage::Cpp;
+    if let Some((declarator, initializer)) = split_variable(snippet) {
+        let constant = declarator.split_whitespace().any(|word| word == "const" || word == "constexpr");
+        let interface = format!("extern {};", declarator.replace("constexpr", "const"));
+        match (cpp, initializer) {
+            // Type deduction needs the initializer, which later snippets do not see
+            (true, _) if declarator.split_whitespace().any(|word| word == "auto") => {}
+            (true, Some(init)) => {
+                // `extern` keeps the external linkage C++ takes away from constants
+                let definition = format!("{}{} = {};", if constant { "extern " } else { "" }, declarator, init);
+                return SnippetCode { definition, interface, ..Default::default() };
+            }
+            (false, Some(init)) if init.starts_with(['{', '"']) => {
+                return SnippetCode { definition: format!("{} = {};", declarator, init), interface, ..Default::default() };
+            }
+            // C requires constant initializers, so the value is assigned when the snippet runs
+            (false, Some(init)) if !constant => {
+                let name = declarator.split_whitespace().last().unwrap_or_default().trim_start_matches(['*', '&']);
+                return SnippetCode { definition: format!("{};", declarator), body: format!("{} = {};", name, init), interface };
+            }
+            (_, None) if !constant => {
+                return SnippetCode { definition: format!("{};", declarator), interface, ..Default::default() };
+            }
+            _ => {}
+        }
+    }
+    
+    match kind {
+        SnippetKind::Declaration if words.first() != Some(&"template") && looks_like_function_definition(snippet) => {
+            // Later snippets link against the function, so it cannot be internal
+            let definition = snippet.trim_start()
+                .split_once(|c: char| c.is_whitespace())
+                .filter(|(first, _)| *first == "static" || *first == "inline")
+                .map_or(snippet, |(_, rest)| rest)
+                .to_string();
+            let header = definition.split('{').next().unwrap_or_default().trim();
+            SnippetCode { interface: format!("{};", header), definition, ..Default::default() }
+        }
+        SnippetKind::Declaration => SnippetCode { definition: snippet.to_string(), interface: snippet.to_string(), ..Default::default() },
+        _ => SnippetCode { body: snippet.to_string(), ..Default::default() },
+    }
+}
+
+/// Translation unit of a C/C++ snippet, exporting its body as `symbol`
+fn render_snippet(language: ProgrammingLanguage, includes: &[String], interfaces: &[String], code: &SnippetCode, symbol: &str) -> String {
+    let cpp = language == ProgrammingLanguage::Cpp;
+    let mut source = if cpp {
+        String::from("#include <iostream>\n")
+    } else {
+        String::from("#include <stdio.h>\n#include <stdlib.h>\n")
+    };
+    for include in includes {
+        source.push_str(&format!("{}\n", include));
+    }
+    if !cpp {
+        source.push_str(C_INSPECT_MACRO);
+    }
+    for interface in interfaces {
+        source.push_str(&format!("\n{}\n", interface));
+    }
+    if !code.definition.is_empty() {
+        source.push_str(&format!("\n{}\n", code.definition));
+    }
+    source.push_str(&format!("\n{}void {}(void) {{\n", if cpp { "extern \"C\" " } else { "" }, symbol));
+    if !code.body.is_empty() {
+        source.push_str(&format!("{}\n", indent(&code.body)));
+    }
+    source.push_str("}\n");
+    source
+}
+
+/// Prints a C value according to its type
+const C_INSPECT_MACRO: &str = concat!(
+    "#define OSLAND_INSPECT(x) _Generic((x), \\\n",
+    "    _Bool: printf(\"%d\\n\", (x)), char: printf(\"'%c'\\n\", (x)), \\\n",
+    "    int: printf(\"%d\\n\", (x)), unsigned int: printf(\"%u\\n\", (x)), \\\n",
+    "    long: printf(\"%ld\\n\", (x)), unsigned long: printf(\"%lu\\n\", (x)), \\\n",
+    "    long long: printf(\"%lld\\n\", (x)), unsigned long long: printf(\"%llu\\n\", (x)), \\\n",
+    "    float: printf(\"%g\\n\", (x)), double: printf(\"%g\\n\", (x)), \\\n",
+    "    char *: printf(\"\\\"%s\\\"\\n\", (x)), const char *: printf(\"\\\"%s\\\"\\n\", (x)), \\\n",
+    "    default: printf(\"<%zu-byte value>\\n\", sizeof(x)))\n",
+);
+
+fn render_c(includes: &[String], declarations: &[String], statements: &[String], inspect: Option<&str>) -> String {
+    let mut source = String::from("#include <stdio.h>\n#include <stdlib.h>\n");
+    for include in includes {
+        source.push_str(&format!("{}\n", include));
+    }
+    source.push_str(C_INSPECT_MACRO);
+    for declaration in declarations {
+        source.push_str(&format!("\n{}\n", declaration));
+    }
+    source.push_str("\nint main(void) {\n");
+    for statement in statements {
+        source.push_str(&format!("{}\n", indent(statement)));
+    }
+    if let Some(expression) = inspect {
+        source.push_str(&format!("    printf(\"\\n{}\\n\");\n    OSLAND_INSPECT({});\n", INSPECT_MARKER, expression));
+    }
+    source.push_str("    return 0;\n}\n");
+    source
+}
+
+fn render_cpp(includes: &[String], declarations: &[String], statements: &[String], inspect: Option<&str>) -> String {
+    let mut source = String::from("#include <iostream>\n");
+    for include in includes {
+        source.push_str(&format!("{}\n", include));
+    }
+    for declaration in declarations {
+        source.push_str(&format!("\n{}\n", declaration));
+    }
+    source.push_str("\nint main() {\n");
+    for statement in statements {
+        source.push_str(&format!("{}\n", indent(statement)));
+    }
+    if let Some(expression) = inspect {
+        source.push_str(&format!("    std::cout << \"\\n{}\\n\" << ({}) << std::endl;\n", INSPECT_MARKER, expression));
+    }
+    source.push_str("    return 0;\n}\n");
+    source
+}
+
+fn render_go(imports: &[String], declarations: &[String], statements: &[String], inspect: Option<&str>) -> String {
+    let mut packages = vec!["\"fmt\"".to_string()];
+    for import in imports {
+        let body = import.trim_start_matches("import").trim().trim_start_matches('(').trim_end_matches(')');
+        for package in body.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            if !packages.iter().any(|known| known == package) {
+                packages.push(package.to_string());
+            }
+        }
+    }
+    
+    let mut source = String::from("package main\n\nimport (\n");
+    for package in &packages {
+        source.push_str(&format!("\t{}\n", package));
+    }
+    source.push_str(")\n\nvar _ = fmt.Sprint\n");
+    for declaration in declarations {
+        source.push_str(&format!("\n{}\n", declaration));
+    }
+    source.push_str("\nfunc main() {\n");
+    for statement in statements {
+        source.push_str(&format!("{}\n", indent(statement)));
+    }
+    // Go rejects unused variables, which every snippet but the last would otherwise hit
+    for name in go_declared_names(statements) {
+        source.push_str(&format!("\t_ = {}\n", name));
+    }
+    if let Some(expression) = inspect {
+        source.push_str(&format!("\tfmt.Printf(\"\\n{}\\n%#v\\n\", {})\n", INSPECT_MARKER, expression));
+    }
+    source.push_str("}\n");
+    source
+}
+
+/// Names declared at the top level of Go statements (`a, b := ...`, `var a ...`)
+fn go_declared_names(statements: &[String]) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for line in statements.iter().flat_map(|statement| statement.lines()) {
+        let line = line.trim();
+        let declared = if let Some(rest) = line.strip_prefix("var ") {
+            rest.split(|c: char| c == '=' || c.is_whitespace()).next().map(|name| vec![name])
+        } else {
+            line.split_once(":=").map(|(left, _)| left.split(',').map(str::trim).collect())
+        };
+        for name in declared.unwrap_or_default() {
+            let valid = !name.is_empty() && name != "_" && name.chars().all(|c| c.is_alphanumeric() || c == '_');
+            if valid && !names.iter().any(|known| known == name) {
+                names.push(name.to_string());
+            }
+        }
+    }
+    names
+}
+
+fn indent(code: &str) -> String {
+    code.lines().map(|line| format!("    {}", line)).collect::<Vec<_>>().join("\n")
+}
+
+/// Whether programs can be started in a new network namespace without privileges
+fn network_namespaces_available() -> bool {
+    static AVAILABLE: OnceLock<bool> = OnceLock::new();
+    *AVAILABLE.get_or_init(|| {
+        let available = cfg!(target_os = "linux") && Command::new("unshare")
+            .args(["--user", "--net", "true"])
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .is_ok_and(|status| status.success());
+        if !available {
+            warn!("Network namespaces are not available; REPL snippets can use the network");
+        }
+        available
+    })
+}
+
+/// Command starting a program with the sandbox limits, in its own process group
+fn sandboxed_command(program: &Path, workdir: &Path, sandbox: &ReplSandbox) -> Command {
+    let mut command = if cfg!(unix) {
+        let mut limits = String::new();
+        if let Some(limit) = sandbox.memory_limit_mb {
+            limits.push_str(&format!("ulimit -v {} && ", limit * 1024));
+        }
+        if let Some(limit) = sandbox.file_size_limit_mb {
+            // 512-byte blocks
+            limits.push_str(&format!("ulimit -f {} && ", limit * 2048));
+        }
+        let mut command = if !sandbox.allow_network && network_namespaces_available() {
+            let mut command = Command::new("unshare");
+            command.args(["--user", "--net", "sh"]);
+            command
+        } else {
+            Command::new("sh")
+        };
+        command.arg("-c").arg(format!("{}exec \"$0\"", limits)).arg(program);
+        command
+    } else {
+        Command::new(program)
+    };
+    command
+        .current_dir(workdir)
+        .env_clear()
+        .env("PATH", std::env::var("PATH").unwrap_or_default())
+        .env("HOME", workdir)
+        .env("TMPDIR", workdir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        command.process_group(0);
+    }
+    command
+}
+
+/// Kill a sandboxed program together with everything it started
+fn kill_process_group(child: &mut Child) {
+    if cfg!(unix) {
+        let _ = Command::new("kill")
+            .args(["-KILL", "--", &format!("-{}", child.id())])
+            .stderr(Stdio::null())
+            .status();
+    }
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+/// Run a program with the sandbox limits, returning its output and exit code
+fn run_sandboxed(binary: &Path, workdir: &Path, sandbox: &ReplSandbox) -> Result<(String, String, i32), RuntimeError> {
+    let mut child = sandboxed_command(binary, workdir, sandbox).spawn()
+        .map_err(|e| RuntimeError::ExecutionError(format!("Failed to run snippet: {}", e)))?;
+    let stdout = child.stdout.take().map(read_in_background);
+    let stderr = child.stderr.take().map(read_in_background);
+    
+    let started = Instant::now();
+    let mut timed_out = false;
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break Some(status),
+            Ok(None) if started.elapsed() >= sandbox.timeout => {
+                kill_process_group(&mut child);
+                timed_out = true;
+                break None;
+            }
+            Ok(None) => thread::sleep(Duration::from_millis(10)),
+            Err(e) => return Err(RuntimeError::ExecutionError(format!("Failed to wait for snippet: {}", e))),
+        }
+    };
+    
+    let stdout = stdout.and_then(|reader| reader.join().ok()).unwrap_or_default();
+    let mut stderr = stderr.and_then(|reader| reader.join().ok()).unwrap_or_default();
+    if timed_out {
+        stderr.push_str(&format!("Snippet killed after {:?}\n", sandbox.timeout));
+    }
+    Ok((stdout, stderr, status.and_then(|status| status.code()).unwrap_or(-1)))
+}
+
+fn read_in_background<R: Read + Send + 'static>(mut pipe: R) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut buffer = Vec::new();
+        let _ = pipe.read_to_end(&mut buffer);
+        String::from_utf8_lossy(&buffer).to_string()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn compiler_available(compiler: &str) -> bool {
+        Command::new(compiler).arg("--version").output().is_ok_and(|output| output.status.success())
+    }
+    
+    #[test]
+    fn test_classify_and_render() {
+        let c = ReplSession::new(ProgrammingLanguage::C, ReplSandbox::default()).unwrap();
+        assert_eq!(c.classify("#include <string.h>"), SnippetKind::Include);
+        assert_eq!(c.classify("struct task { int pid; };"), SnippetKind::Declaration);
+        assert_eq!(c.classify("static int add(int a, int b) { return a + b; }"), SnippetKind::Declaration);
+        assert_eq!(c.classify("int square(int x) {\n    return x * x;\n}"), SnippetKind::Declaration);
+        assert_eq!(c.classify("int x = square(4);"), SnippetKind::Statement);
+        assert_eq!(c.classify("for (int i = 0; i < 3; i++) { x++; }"), SnippetKind::Statement);
+        
+        let source = render_c(&[], &["int square(int x) { return x * x; }".to_string()], &["int x = square(4);".to_string()], Some("x"));
+        assert!(source.contains("    int x = square(4);\n    printf(\"\\n@@osland-inspect@@\\n\");\n    OSLAND_INSPECT(x);"));
+        
+        let go = ReplSession::new(ProgrammingLanguage::Go, ReplSandbox::default()).unwrap();
+        assert_eq!(go.classify("import \"strings\""), SnippetKind::Include);
+        assert_eq!(go.classify("func double(x int) int { return 2 * x }"), SnippetKind::Declaration);
+        assert_eq!(go.classify("x := double(21)"), SnippetKind::Statement);
+        
+        let statements = vec!["x := 1".to_string(), "var name = \"osland\"".to_string(), "a, _ := 1, 2".to_string()];
+        assert_eq!(go_declared_names(&statements), vec!["x", "name", "a"]);
+        let source = render_go(&["import (\n\t\"strings\"\n\t\"fmt\"\n)".to_string()], &[], &statements, None);
+        assert!(source.starts_with("package main\n\nimport (\n\t\"fmt\"\n\t\"strings\"\n)\n"));
+        assert!(source.contains("\t_ = x\n\t_ = name\n\t_ = a\n}"));
+        
+        assert!(ReplSession::new(ProgrammingLanguage::Python, ReplSandbox::default()).is_err());
+    }
+    
+    #[test]
+    fn test_snippet_code() {
+        assert_eq!(split_variable("static int *p = &x;"), Some(("int *p".to_string(), Some("&x".to_string()))));
+        assert_eq!(split_variable("std::map<int, int> seen;"), Some(("std::map<int, int> seen".to_string(), None)));
+        assert_eq!(split_variable("int buffer[4] = {1, 2};"), Some(("int buffer[4]".to_string(), Some("{1, 2}".to_string()))));
+        for statement in ["x = 3;", "x += 1;", "int a = 1, b = 2;", "return x;", "printf(\"a = %d\", a);", "int x = 1; x++;", "a == b;"] {
+            assert_eq!(split_variable(statement), None, "{}", statement);
+        }
+        
+        let c = snippet_code(ProgrammingLanguage::C, "int total = sum(2, 3);", SnippetKind::Statement);
+        assert_eq!(c, SnippetCode { definition: "int total;".to_string(), body: "total = sum(2, 3);".to_string(), interface: "extern int total;".to_string() });
+        let cpp = snippet_code(ProgrammingLanguage::Cpp, "const int limit = compute();", SnippetKind::Statement);
+        assert_eq!(cpp.definition, "extern const int limit = compute();");
+        assert_eq!(snippet_code(ProgrammingLanguage::Cpp, "auto n = 3;", SnippetKind::Statement).body, "auto n = 3;");
+        
+        let function = snippet_code(ProgrammingLanguage::C, "static int add(int a, int b) { return a + b; }", SnippetKind::Declaration);
+        assert_eq!(function.definition, "int add(int a, int b) { return a + b; }");
+        assert_eq!(function.interface, "int add(int a, int b);");
+        let structure = snippet_code(ProgrammingLanguage::C, "struct task { int pid; };", SnippetKind::Declaration);
+        assert_eq!(structure.interface, "struct task { int pid; };");
+    }
+    
+    #[test]
+    fn test_c_session_runs_each_snippet_once() {
+        if !cfg!(unix) || !compiler_available("cc") {
+            return;
+        }
+        let sandbox = ReplSandbox { timeout: Duration::from_millis(500), ..Default::default() };
+        let mut session = ReplSession::new(ProgrammingLanguage::C, sandbox).unwrap();
+        assert!(session.eval("#include <string.h>").unwrap().accepted);
+        assert!(session.eval("int counter = strlen(\"ab\");").unwrap().accepted);
+        assert!(session.eval("static int bump(void) { return ++counter; }").unwrap().accepted);
+        
+        // Earlier snippets are not run again, so the log gets one line per snippet
+        for expected in ["3\n", "4\n"] {
+            let output = session.eval("FILE *log = fopen(\"log.txt\", \"a\");\nfprintf(log, \"%d\\n\", bump());\nfclose(log);\nprintf(\"%d\\n\", counter);").unwrap();
+            assert!(output.accepted, "{}", output.stderr);
+            assert_eq!(output.stdout, expected);
+        }
+        assert_eq!(std::fs::read_to_string(session.workspace().join("log.txt")).unwrap(), "3\n4\n");
+        
+        // Failing snippets leave the state alone
+        let compile_error = session.eval("counter = ;").unwrap();
+        assert!(!compile_error.accepted && compile_error.exit_code == -1 && !compile_error.stderr.is_empty());
+        let crash = session.eval("counter = 100;\nint *p = NULL;\n*p = 1;").unwrap();
+        assert!(!crash.accepted && crash.exit_code == -1);
+        let exit = session.eval("counter = 100;\nexit(3);").unwrap();
+        assert!(!exit.accepted && exit.exit_code == 3);
+        let timeout = session.eval("counter = 100;\nfor (;;) {}").unwrap();
+        assert!(!timeout.accepted && timeout.stderr.contains("killed after"), "{:?}", timeout);
+        assert_eq!(session.inspect("counter").unwrap().stdout, "4");
+        assert_eq!(session.inspect("counter + 1").unwrap().stdout, "5");
+        assert_eq!(session.inspect("counter").unwrap().stdout, "4");
+        
+        // Snippets only see the sandbox environment
+        let home = session.eval("printf(\"%s\\n\", getenv(\"HOME\"));").unwrap();
+        assert_eq!(home.stdout.trim_end(), session.workspace().to_string_lossy());
+        
+        assert!(session.source().contains("    int counter = strlen(\"ab\");\n"));
+        session.reset();
+        assert!(!session.eval("counter++;").unwrap().accepted);
+    }
+    
+    #[test]
+    fn test_cpp_session_keeps_globals() {
+        if !cfg!(unix) || !compiler_available("c++") {
+            return;
+        }
+        let mut session = ReplSession::new(ProgrammingLanguage::Cpp, ReplSandbox::default()).unwrap();
+        assert!(session.eval("#include <string>").unwrap().accepted);
+        assert!(session.eval("std::string name = \"osland\";").unwrap().accepted);
+        let output = session.eval("name += \"!\";\nstd::cout << name.size() << std::endl;").unwrap();
+        assert!(output.accepted, "{}", output.stderr);
+        assert_eq!(output.stdout, "7\n");
+        assert_eq!(session.inspect("name").unwrap().stdout, "osland!");
+    }
+}
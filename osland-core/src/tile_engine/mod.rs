@@ -63,10 +63,18 @@ pub mod tile_designer;
 pub mod tile_compiler;
 pub mod tile_library;
 pub mod tile_optimizer;
+pub mod tile_constraints;
+pub mod tile_tracer;
+pub mod tile_test_generator;
+pub mod tile_validator;
+pub mod tile_placement;
 
 // Re-export core components
 pub use tile_core::{Tile, TileType, TilePort, TileConnection};
 pub use tile_designer::TileDesigner;
 pub use tile_compiler::TileCompiler;
 pub use tile_library::TileLibrary;
-pub use tile_optimizer::TileOptimizer;
\ No newline at end of file
+pub use tile_optimizer::TileOptimizer;
+pub use tile_constraints::ConnectivityPolicy;
+pub use tile_tracer::TileTracer;
+pub use tile_placement::{TilePlacer, PlacementPlan, PlacementHint};
\ No newline at end of file
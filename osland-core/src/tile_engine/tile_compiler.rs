@@ -3,8 +3,13 @@
 // SPDX-License-Identifier: MulanPSL-2.0
 
 use crate::tile_engine::tile_core::{TileGraph, Tile, TileType, TilePort, PortType, TileConnection, ConnectionType};
-use crate::component_manager::component::{Component, ComponentType, ComponentCategory, ComponentProperty, ComponentPort, ComponentDependency};
+use crate::tile_engine::tile_constraints::ConnectivityPolicy;
+use crate::tile_engine::tile_test_generator::{TestGenerator, GeneratedTestFile};
+use crate::tile_engine::tile_validator::{GeneratedCodeValidator, ValidationReport};
+use crate::component_manager::component::{Component, ComponentType, ComponentCategory, ComponentProperty, ComponentPort, ComponentDependency, PortDirection};
+use crate::component_manager::component::KernelArchitecture as ComponentArchitecture;
 use crate::core::architecture::KernelArchitecture;
+use crate::architecture_adapter::gpu_probe::{GpuBackend, GpuProbeReport};
 use std::collections::HashMap;
 
 /// Tile Compiler
@@ -30,6 +35,12 @@ pub struct CompilationOptions {
     
     /// Target language for generated code
     pub target_language: TargetLanguage,
+    
+    /// Connectivity policy the graph must satisfy before compilation
+    pub connectivity_policy: Option<ConnectivityPolicy>,
+    
+    /// Detected GPUs; when set, GPU target languages require a matching device
+    pub gpu_capabilities: Option<GpuProbeReport>,
 }
 
 /// Target Language Enumeration
@@ -55,6 +66,70 @@ pub enum TargetLanguage {
     Custom(String),
 }
 
+impl TargetLanguage {
+    /// All built-in target languages (excludes `Custom`)
+    pub fn all() -> Vec<TargetLanguage> {
+        vec![
+            TargetLanguage::Rust,
+            TargetLanguage::C,
+            TargetLanguage::Cpp,
+            TargetLanguage::Python,
+            TargetLanguage::JavaScript,
+            TargetLanguage::Moonbit,
+            TargetLanguage::Java,
+            TargetLanguage::CSharp,
+            TargetLanguage::C3,
+            TargetLanguage::TypeScript,
+            TargetLanguage::Mojo,
+            TargetLanguage::Cuda,
+            TargetLanguage::Zig,
+            TargetLanguage::Triton,
+            TargetLanguage::CuTile,
+            TargetLanguage::TVM,
+            TargetLanguage::Helion,
+        ]
+    }
+    
+    /// Lowercase name used for file names and CLI flags
+    pub fn name(&self) -> String {
+        match self {
+            TargetLanguage::Rust => "rust".to_string(),
+            TargetLanguage::C => "c".to_string(),
+            TargetLanguage::Cpp => "cpp".to_string(),
+            TargetLanguage::Python => "python".to_string(),
+            TargetLanguage::JavaScript => "javascript".to_string(),
+            TargetLanguage::Moonbit => "moonbit".to_string(),
+            TargetLanguage::Java => "java".to_string(),
+            TargetLanguage::CSharp => "csharp".to_string(),
+            TargetLanguage::C3 => "c3".to_string(),
+            TargetLanguage::TypeScript => "typescript".to_string(),
+            TargetLanguage::Mojo => "mojo".to_string(),
+            TargetLanguage::Cuda => "cuda".to_string(),
+            TargetLanguage::Zig => "zig".to_string(),
+            TargetLanguage::Triton => "triton".to_string(),
+            TargetLanguage::CuTile => "cutile".to_string(),
+            TargetLanguage::TVM => "tvm".to_string(),
+            TargetLanguage::Helion => "helion".to_string(),
+            TargetLanguage::Custom(name) => name.to_lowercase(),
+        }
+    }
+    
+    /// Look up a built-in target language by its `name`, ignoring case
+    pub fn from_name(name: &str) -> Option<TargetLanguage> {
+        let name = name.to_lowercase();
+        TargetLanguage::all().into_iter().find(|language| language.name() == name)
+    }
+    
+    /// GPU backends the generated code can run on (empty when no GPU is needed)
+    pub fn gpu_backends(&self) -> &'static [GpuBackend] {
+        match self {
+            TargetLanguage::Cuda | TargetLanguage::CuTile | TargetLanguage::Helion => &[GpuBackend::Cuda],
+            TargetLanguage::Triton => &[GpuBackend::Cuda, GpuBackend::Rocm],
+            _ => &[],
+        }
+    }
+}
+
 impl Default for CompilationOptions {
     fn default() -> Self {
         Self {
@@ -62,6 +137,8 @@ impl Default for CompilationOptions {
             optimize_memory: false,
             generate_debug_info: false,
             target_language: TargetLanguage::Rust,
+            connectivity_policy: None,
+            gpu_capabilities: None,
         }
     }
 }
@@ -77,6 +154,9 @@ impl TileCompiler {
     
     /// Compile a tile graph to components
     pub fn compile_to_components(&self, graph: &TileGraph) -> Result<Vec<Component>, String> {
+        self.check_gpu_support()?;
+        self.check_connectivity(graph)?;
+        
         let mut components = Vec::new();
         
         // Convert each tile to a component
@@ -88,6 +168,37 @@ impl TileCompiler {
         Ok(components)
     }
     
+    /// Generate unit test skeletons for every component compiled from the graph
+    ///
+    /// Each port and property gets a test exercising boundary values derived from
+    /// its type, written for the target language's native test framework.
+    pub fn generate_tests(&self, graph: &TileGraph) -> Result<Vec<GeneratedTestFile>, String> {
+        let components = self.compile_to_components(graph)?;
+        let generator = TestGenerator::new(self.options.target_language.clone());
+        generator.generate(&components)
+    }
+    
+    /// Reject GPU target languages when the probe found no usable device
+    pub fn check_gpu_support(&self) -> Result<(), String> {
+        let Some(report) = &self.options.gpu_capabilities else {
+            return Ok(());
+        };
+        report.require_any(self.options.target_language.gpu_backends())
+            .map_err(|e| format!("Cannot target {}: {}", self.options.target_language.name(), e))
+    }
+    
+    /// Reject graphs that violate the configured connectivity policy
+    fn check_connectivity(&self, graph: &TileGraph) -> Result<(), String> {
+        if let Some(policy) = &self.options.connectivity_policy {
+            let violations = policy.validate(graph);
+            if !violations.is_empty() {
+                let messages: Vec<String> = violations.into_iter().map(|v| v.message).collect();
+                return Err(format!("Connectivity policy violations:\n{}", messages.join("\n")));
+            }
+        }
+        Ok(())
+    }
+    
     /// Convert a tile to a component
     fn convert_tile_to_component(&self, tile: &Tile, graph: &TileGraph) -> Result<Component, String> {
         // Determine component type based on tile type
@@ -98,7 +209,7 @@ impl TileCompiler {
             TileType::Network => ComponentType::NetworkStack,
             TileType::Storage => ComponentType::FileSystem,
             TileType::Security => ComponentType::SecurityManager,
-            TileType::Custom(_) => ComponentType::Custom("CustomTileComponent".to_string()),
+            TileType::Custom(ref name) => ComponentType::Custom(name.clone()),
         };
         
         // Determine component category
@@ -261,8 +372,93 @@ impl TileCompiler {
         Ok(component)
     }
     
+    /// Wrap a component as a tile (the reverse of `convert_tile_to_component`)
+    ///
+    /// Ports, properties, dependencies and architectures are mapped so that
+    /// extracted kernel components can be placed directly into tile graphs.
+    pub fn convert_component_to_tile(component: &Component) -> Tile {
+        let tile_type = match component.component_type {
+            ComponentType::ProcessManager
+            | ComponentType::Scheduler
+            | ComponentType::InterruptController
+            | ComponentType::SystemCallHandler
+            | ComponentType::CudaTile
+            | ComponentType::CudaTensor
+            | ComponentType::CudaPerformance => TileType::Processing,
+            ComponentType::MemoryManager
+            | ComponentType::UnitCache
+            | ComponentType::UnitBuffer => TileType::Memory,
+            ComponentType::DeviceDriver
+            | ComponentType::DeviceManager
+            | ComponentType::PlatformAbstraction
+            | ComponentType::BoardSupport => TileType::IO,
+            ComponentType::NetworkStack => TileType::Network,
+            ComponentType::FileSystem | ComponentType::VirtualFileSystem => TileType::Storage,
+            ComponentType::SecurityManager => TileType::Security,
+            ComponentType::Custom(ref name) => TileType::Custom(name.clone()),
+            // Unit.land data flow units are processing stages
+            _ => TileType::Processing,
+        };
+        
+        let mut tile = Tile::new(component.name.clone(), tile_type, component.description.clone());
+        tile.id = component.id.clone();
+        tile.version = component.version.clone();
+        tile.author = component.author.clone();
+        tile.initialization_code = component.initialization_code.clone();
+        
+        for component_port in &component.ports {
+            tile.add_port(TilePort {
+                id: component_port.name.clone(),
+                name: component_port.name.clone(),
+                port_type: match component_port.direction {
+                    PortDirection::Input => PortType::Input,
+                    PortDirection::Output => PortType::Output,
+                    PortDirection::Bidirectional => PortType::Bidirectional,
+                },
+                data_type: component_port.port_type.clone(),
+                description: component_port.description.clone(),
+            });
+        }
+        
+        for property in &component.properties {
+            tile.set_property(property.name.clone(), property.value.clone());
+        }
+        
+        for dependency in &component.dependencies {
+            let name = match &dependency.component_type {
+                ComponentType::Custom(name) => name.clone(),
+                other => format!("{:?}", other),
+            };
+            tile.add_dependency(name);
+        }
+        
+        let mut architectures: Vec<String> = component.supported_architectures.iter()
+            .map(|arch| match arch {
+                ComponentArchitecture::Monolithic => "monolithic".to_string(),
+                ComponentArchitecture::Microkernel => "microkernel".to_string(),
+                ComponentArchitecture::Hybrid => "hybrid".to_string(),
+                ComponentArchitecture::Exokernel => "exokernel".to_string(),
+                ComponentArchitecture::Framekernel => "frame".to_string(),
+                ComponentArchitecture::Custom(name) => name.clone(),
+            })
+            .collect();
+        architectures.sort();
+        for architecture in architectures {
+            tile.add_supported_architecture(architecture);
+        }
+        
+        tile
+    }
+    
+    /// Wrap every component of a list as a tile
+    pub fn convert_components_to_tiles(components: &[Component]) -> Vec<Tile> {
+        components.iter().map(Self::convert_component_to_tile).collect()
+    }
+    
     /// Generate execution code from tile graph
     pub fn generate_execution_code(&self, graph: &TileGraph) -> Result<String, String> {
+        self.check_gpu_support()?;
+        
         let mut code = String::new();
         
         match &self.options.target_language {
@@ -276,13 +472,13 @@ impl TileCompiler {
                 code.push_str("import torch\n\n");
                 
                 // Generate Triton kernels for each tile
-                for tile in graph.tiles.values() {
+                for tile in sorted_tiles(graph) {
                     let tile_name = sanitize_identifier(&tile.name);
                     code.push_str(&format!("@triton.jit\n"));
                     code.push_str(&format!("def {}_kernel({}, **kwargs):\n", tile_name, "*args"));
                     code.push_str(&format!("    \"\"\"Triton kernel for tile: {}\"\"\"\n", tile.name));
                     code.push_str(&format!("    # Tile properties\n"));
-                    for (key, value) in &tile.properties {
+                    for (key, value) in sorted_properties(tile) {
                         code.push_str(&format!("    {} = {}\n", sanitize_identifier(key), value));
                     }
                     code.push_str(&format!("    \n"));
@@ -300,7 +496,7 @@ impl TileCompiler {
                 code.push_str(&format!("    print(\"Executing tile graph: {}\")\n", graph.name));
                 
                 // Execute Triton kernels
-                for tile in graph.tiles.values() {
+                for tile in sorted_tiles(graph) {
                     let tile_name = sanitize_identifier(&tile.name);
                     code.push_str(&format!("    # Execute {}_kernel\n", tile_name));
                     code.push_str(&format!("    {}_kernel({}, **{{}})\n", tile_name, "*args"));
@@ -320,12 +516,12 @@ impl TileCompiler {
                 code.push_str("#include <cudatile/cudatile.h>\n\n");
                 
                 // Generate CuTile kernels for each tile
-                for tile in graph.tiles.values() {
+                for tile in sorted_tiles(graph) {
                     let tile_name = sanitize_identifier(&tile.name);
                     code.push_str(&format!("__tile__ void {}_kernel({}) {{
 ", tile_name, "...args"));
                     code.push_str(&format!("    // Tile properties\n"));
-                    for (key, value) in &tile.properties {
+                    for (key, value) in sorted_properties(tile) {
                         code.push_str(&format!("    constexpr auto {} = {};\n", sanitize_identifier(key), value));
                     }
                     code.push_str(&format!("    \n"));
@@ -343,7 +539,7 @@ impl TileCompiler {
                 code.push_str(&format!("    printf(\"Executing tile graph: %s\n\", \"{}\");\n", graph.name));
                 
                 // Execute CuTile kernels
-                for tile in graph.tiles.values() {
+                for tile in sorted_tiles(graph) {
                     let tile_name = sanitize_identifier(&tile.name);
                     code.push_str(&format!("    // Execute {}_kernel\n", tile_name));
                     code.push_str(&format!("    {}_kernel({});\n", tile_name, "...args"));
@@ -362,12 +558,12 @@ impl TileCompiler {
                 code.push_str("import tvm.runtime\n\n");
                 
                 // Generate TVM computations for each tile
-                for tile in graph.tiles.values() {
+                for tile in sorted_tiles(graph) {
                     let tile_name = sanitize_identifier(&tile.name);
                     code.push_str(&format!("# TVM computation for tile: {}\n", tile.name));
                     code.push_str(&format!("def create_{}_computation():\n", tile_name));
                     code.push_str(&format!("    # Tile properties\n"));
-                    for (key, value) in &tile.properties {
+                    for (key, value) in sorted_properties(tile) {
                         code.push_str(&format!("    {} = {}\n", sanitize_identifier(key), value));
                     }
                     code.push_str(&format!("    \n"));
@@ -385,7 +581,7 @@ impl TileCompiler {
                 code.push_str(&format!("    print(\"Executing tile graph: {}\")\n", graph.name));
                 
                 // Execute TVM computations
-                for tile in graph.tiles.values() {
+                for tile in sorted_tiles(graph) {
                     let tile_name = sanitize_identifier(&tile.name);
                     code.push_str(&format!("    # Execute {} computation\n", tile_name));
                     code.push_str(&format!("    {} = create_{}_computation()\n", tile_name, tile_name));
@@ -404,13 +600,13 @@ impl TileCompiler {
                 code.push_str("import torch.helion as helion\n\n");
                 
                 // Generate Helion functions for each tile
-                for tile in graph.tiles.values() {
+                for tile in sorted_tiles(graph) {
                     let tile_name = sanitize_identifier(&tile.name);
                     code.push_str(&format!("@helion.jit\n"));
                     code.push_str(&format!("def {}_helion({}, **kwargs):\n", tile_name, "*args"));
                     code.push_str(&format!("    \"\"\"PyTorch Helion function for tile: {}\"\"\"\n", tile.name));
                     code.push_str(&format!("    # Tile properties\n"));
-                    for (key, value) in &tile.properties {
+                    for (key, value) in sorted_properties(tile) {
                         code.push_str(&format!("    {} = {}\n", sanitize_identifier(key), value));
                     }
                     code.push_str(&format!("    \n"));
@@ -428,7 +624,7 @@ impl TileCompiler {
                 code.push_str(&format!("    print(\"Executing tile graph: {}\")\n", graph.name));
                 
                 // Execute Helion functions
-                for tile in graph.tiles.values() {
+                for tile in sorted_tiles(graph) {
                     let tile_name = sanitize_identifier(&tile.name);
                     code.push_str(&format!("    # Execute {}_helion\n", tile_name));
                     code.push_str(&format!("    {}_helion({}, **{{}})\n", tile_name, "*args"));
@@ -453,13 +649,13 @@ impl TileCompiler {
                 code.push_str("    {\n");
                 
                 // Generate methods for each tile
-                for tile in graph.tiles.values() {
+                for tile in sorted_tiles(graph) {
                     let tile_name = sanitize_identifier(&tile.name);
                     code.push_str(&format!("        /// <summary>Method for tile: {}</summary>\n", tile.name));
                     code.push_str(&format!("        public void {}Tile({})\n", tile_name, "params object[] args"));
                     code.push_str("        {\n");
                     code.push_str(&format!("            // Tile properties\n"));
-                    for (key, value) in &tile.properties {
+                    for (key, value) in sorted_properties(tile) {
                         code.push_str(&format!("            var {} = {};\n", sanitize_identifier(key), value));
                     }
                     code.push_str(&format!("            \n"));
@@ -479,7 +675,7 @@ impl TileCompiler {
                 code.push_str(&format!("            Console.WriteLine(\"Executing tile graph: {}\");\n", graph.name));
                 
                 // Execute all tiles
-                for tile in graph.tiles.values() {
+                for tile in sorted_tiles(graph) {
                     let tile_name = sanitize_identifier(&tile.name);
                     code.push_str(&format!("            // Execute {}Tile\n", tile_name));
                     code.push_str(&format!("            {}Tile({});\n", tile_name, "args"));
@@ -507,13 +703,13 @@ impl TileCompiler {
                 code.push_str("use std::io;\n\n");
                 
                 // Generate functions for each tile
-                for tile in graph.tiles.values() {
+                for tile in sorted_tiles(graph) {
                     let tile_name = sanitize_identifier(&tile.name);
                     code.push_str(&format!("// Function for tile: {}\n", tile.name));
                     code.push_str(&format!("fn {}_tile({}) -> void\n", tile_name, "*args"));
                     code.push_str("{\n");
                     code.push_str(&format!("    // Tile properties\n"));
-                    for (key, value) in &tile.properties {
+                    for (key, value) in sorted_properties(tile) {
                         code.push_str(&format!("    let {} = {};\n", sanitize_identifier(key), value));
                     }
                     code.push_str(&format!("    \n"));
@@ -532,7 +728,7 @@ impl TileCompiler {
                 code.push_str(&format!("    io::printf(\"Executing tile graph: %s\\n\", \"{}\");\n", graph.name));
                 
                 // Execute all tiles
-                for tile in graph.tiles.values() {
+                for tile in sorted_tiles(graph) {
                     let tile_name = sanitize_identifier(&tile.name);
                     code.push_str(&format!("    // Execute {}_tile\n", tile_name));
                     code.push_str(&format!("    {}_tile({});\n", tile_name, "*args"));
@@ -548,13 +744,13 @@ impl TileCompiler {
                 code.push_str("// SPDX-License-Identifier: MulanPSL-2.0\n\n");
                 
                 // Generate functions for each tile
-                for tile in graph.tiles.values() {
+                for tile in sorted_tiles(graph) {
                     let tile_name = sanitize_identifier(&tile.name);
-                    code.push_str(&format!("/** Function for tile: {}\ */\n", tile.name));
+                    code.push_str(&format!("/** Function for tile: {} */\n", tile.name));
                     code.push_str(&format!("function {}Tile({}): void\n", tile_name, "...args: any[]"));
                     code.push_str("{\n");
                     code.push_str(&format!("    // Tile properties\n"));
-                    for (key, value) in &tile.properties {
+                    for (key, value) in sorted_properties(tile) {
                         code.push_str(&format!("    const {} = {};\n", sanitize_identifier(key), value));
                     }
                     code.push_str(&format!("    \n"));
@@ -574,7 +770,7 @@ impl TileCompiler {
                 code.push_str(&format!("    console.log(`Executing tile graph: {}`);\n", graph.name));
                 
                 // Execute all tiles
-                for tile in graph.tiles.values() {
+                for tile in sorted_tiles(graph) {
                     let tile_name = sanitize_identifier(&tile.name);
                     code.push_str(&format!("    // Execute {}Tile\n", tile_name));
                     code.push_str(&format!("    {}Tile({});\n", tile_name, "...args"));
@@ -594,13 +790,13 @@ impl TileCompiler {
                 code.push_str("let sys = Python.import_module('sys')\n\n");
                 
                 // Generate functions for each tile
-                for tile in graph.tiles.values() {
+                for tile in sorted_tiles(graph) {
                     let tile_name = sanitize_identifier(&tile.name);
                     code.push_str(&format!("# Function for tile: {}\n", tile.name));
                     code.push_str(&format!("fn {}_tile({}) -> None\n", tile_name, "*args"));
                     code.push_str("{\n");
                     code.push_str(&format!("    # Tile properties\n"));
-                    for (key, value) in &tile.properties {
+                    for (key, value) in sorted_properties(tile) {
                         code.push_str(&format!("    let {} = {};\n", sanitize_identifier(key), value));
                     }
                     code.push_str(&format!("    \n"));
@@ -619,7 +815,7 @@ impl TileCompiler {
                 code.push_str(&format!("    print('Executing tile graph: {}')\n", graph.name));
                 
                 // Execute all tiles
-                for tile in graph.tiles.values() {
+                for tile in sorted_tiles(graph) {
                     let tile_name = sanitize_identifier(&tile.name);
                     code.push_str(&format!("    # Execute {}_tile\n", tile_name));
                     code.push_str(&format!("    {}_tile({});\n", tile_name, "*args"));
@@ -638,12 +834,12 @@ impl TileCompiler {
                 code.push_str("use std::sync::{Arc, RwLock};\n\n");
                 
                 // Generate structs for each tile
-                for tile in graph.tiles.values() {
-                    code.push_str(&format!("/// Tile: {}\n", tile.name));
+                for tile in sorted_tiles(graph) {
+                    code.push_str(&format!("/// Tile: {}\n", tile.name.replace(['\r', '\n'], " ")));
                     code.push_str(&format!("pub struct {} {{\n", sanitize_identifier(&tile.name)));
                     
                     // Add fields for properties
-                    for (key, value) in &tile.properties {
+                    for (key, value) in sorted_properties(tile) {
                         code.push_str(&format!("    pub {}: String,\n", sanitize_identifier(key)));
                     }
                     
@@ -662,14 +858,14 @@ impl TileCompiler {
                 }
                 
                 // Generate implementation blocks
-                for tile in graph.tiles.values() {
+                for tile in sorted_tiles(graph) {
                     code.push_str(&format!("impl {} {{\n", sanitize_identifier(&tile.name)));
                     code.push_str("    /// Create a new instance\n");
                     code.push_str(&format!("    pub fn new() -> Self {{\n"));
                     code.push_str(&format!("        Self {{\n"));
                     
                     // Initialize properties
-                    for (key, _) in &tile.properties {
+                    for (key, _) in sorted_properties(tile) {
                         code.push_str(&format!("            {}: String::new(),\n", sanitize_identifier(key)));
                     }
                     
@@ -713,22 +909,24 @@ impl TileCompiler {
                 // Generate main execution function
                 code.push_str("/// Execute the entire tile graph\n");
                 code.push_str("pub fn execute_tile_graph() {\n");
-                code.push_str(&format!("    println!(\"Executing tile graph: {}\");\n", graph.name));
+                // Escape the name so quotes and braces survive the string literal and format string
+                let graph_name = graph.name.replace('{', "{{").replace('}', "}}").escape_default().to_string();
+                code.push_str(&format!("    println!(\"Executing tile graph: {}\");\n", graph_name));
                 
                 // Create instances of all tiles
-                for tile in graph.tiles.values() {
+                for tile in sorted_tiles(graph) {
                     code.push_str(&format!("    let mut {} = {}::new();\n", 
                         sanitize_identifier(&format!("{}_instance", tile.name)), 
                         sanitize_identifier(&tile.name)));
                 }
                 
                 code.push_str("\n    // Initialize all tiles\n");
-                for tile in graph.tiles.values() {
+                for tile in sorted_tiles(graph) {
                     code.push_str(&format!("    {}_instance.initialize();\n", sanitize_identifier(&tile.name)));
                 }
                 
                 code.push_str("\n    // Execute all tiles\n");
-                for tile in graph.tiles.values() {
+                for tile in sorted_tiles(graph) {
                     code.push_str(&format!("    {}_instance.execute();\n", sanitize_identifier(&tile.name)));
                 }
                 
@@ -768,6 +966,15 @@ impl TileCompiler {
         Ok(code)
     }
     
+    /// Generate execution code and check it with the target toolchain
+    ///
+    /// The toolchain runs in check-only mode; diagnostics are mapped back to the
+    /// tile that produced the offending line. Missing toolchains yield a skipped report.
+    pub fn validate_generated_code(&self, graph: &TileGraph) -> Result<ValidationReport, String> {
+        let code = self.generate_execution_code(graph)?;
+        GeneratedCodeValidator::new(self.options.target_language.clone()).validate(graph, &code)
+    }
+    
     /// Optimize the tile graph
     pub fn optimize_graph(&self, graph: &mut TileGraph) -> Result<(), String> {
         // Apply performance optimizations if requested
@@ -808,8 +1015,22 @@ impl TileCompiler {
     }
 }
 
+/// Tiles in a stable order (by name, then ID) so generated code is reproducible
+fn sorted_tiles(graph: &TileGraph) -> Vec<&Tile> {
+    let mut tiles: Vec<&Tile> = graph.tiles.values().collect();
+    tiles.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.id.cmp(&b.id)));
+    tiles
+}
+
+/// Tile properties sorted by key
+fn sorted_properties(tile: &Tile) -> Vec<(&String, &String)> {
+    let mut properties: Vec<(&String, &String)> = tile.properties.iter().collect();
+    properties.sort_by(|a, b| a.0.cmp(b.0));
+    properties
+}
+
 /// Sanitize identifier to make it a valid Rust identifier
-fn sanitize_identifier(name: &str) -> String {
+pub(crate) fn sanitize_identifier(name: &str) -> String {
     // Replace invalid characters with underscores
     let mut sanitized = String::new();
     for (i, ch) in name.chars().enumerate() {
@@ -0,0 +1,300 @@
+// Tile Constraints Module for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+use crate::tile_engine::tile_core::{TileGraph, TileType};
+use serde::{Serialize, Deserialize};
+use std::collections::{HashSet, VecDeque};
+use std::fs;
+use std::path::Path;
+
+/// Graph property used to select the active policy profile
+pub const PROFILE_PROPERTY: &str = "profile";
+
+/// Connectivity Rule Kind
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum RuleKind {
+    /// Connections from source to destination type are allowed
+    Allow,
+    
+    /// Connections from source to destination type are forbidden
+    Deny,
+    
+    /// Every data path from source to destination type must pass through a tile of this type
+    RequireVia(TileType),
+}
+
+/// Connectivity Rule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectivityRule {
+    /// Source tile type
+    pub source: TileType,
+    
+    /// Destination tile type
+    pub dest: TileType,
+    
+    /// Rule kind
+    pub kind: RuleKind,
+    
+    /// Profiles this rule applies to (empty means all profiles)
+    #[serde(default)]
+    pub profiles: Vec<String>,
+    
+    /// Rule description shown in violations
+    #[serde(default)]
+    pub description: String,
+}
+
+impl ConnectivityRule {
+    /// Check whether the rule applies to the given profile
+    pub fn applies_to(&self, profile: Option<&str>) -> bool {
+        self.profiles.is_empty() || profile.map_or(false, |p| self.profiles.iter().any(|rp| rp == p))
+    }
+}
+
+/// Connectivity Violation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectivityViolation {
+    /// Connection ID (None for path-level violations)
+    pub connection_id: Option<String>,
+    
+    /// Source tile ID
+    pub source_tile_id: String,
+    
+    /// Destination tile ID
+    pub dest_tile_id: String,
+    
+    /// Violation message
+    pub message: String,
+}
+
+/// Connectivity Policy
+///
+/// Declares which tile types may be connected. Policies are usually stored in a
+/// project-level JSON file and selected per graph through the `profile` property.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectivityPolicy {
+    /// Policy name
+    pub name: String,
+    
+    /// Whether connections not matched by any Allow rule are permitted
+    #[serde(default = "default_allow")]
+    pub default_allow: bool,
+    
+    /// Policy rules
+    #[serde(default)]
+    pub rules: Vec<ConnectivityRule>,
+}
+
+fn default_allow() -> bool {
+    true
+}
+
+impl ConnectivityPolicy {
+    /// Create a new, permissive policy
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            default_allow: true,
+            rules: Vec::new(),
+        }
+    }
+    
+    /// Load a policy from a JSON file
+    pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let content = fs::read_to_string(path).map_err(|e| format!("Failed to read policy file: {}", e))?;
+        let policy: Self = serde_json::from_str(&content).map_err(|e| format!("Failed to parse policy JSON: {}", e))?;
+        Ok(policy)
+    }
+    
+    /// Save the policy to a JSON file
+    pub fn save_to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self).map_err(|e| format!("Failed to serialize policy: {}", e))?;
+        fs::write(path, json).map_err(|e| format!("Failed to write policy file: {}", e))?;
+        Ok(())
+    }
+    
+    /// Add a rule to the policy
+    pub fn add_rule(&mut self, rule: ConnectivityRule) {
+        self.rules.push(rule);
+    }
+    
+    /// Built-in policy requiring a Security tile between Network and Processing tiles
+    /// for the `hardened` profile
+    pub fn hardened() -> Self {
+        let mut policy = Self::new("hardened".to_string());
+        policy.add_rule(ConnectivityRule {
+            source: TileType::Network,
+            dest: TileType::Processing,
+            kind: RuleKind::RequireVia(TileType::Security),
+            profiles: vec!["hardened".to_string()],
+            description: "Network traffic must be filtered by a Security tile before reaching Processing tiles".to_string(),
+        });
+        policy
+    }
+    
+    /// Validate a tile graph against the policy
+    pub fn validate(&self, graph: &TileGraph) -> Vec<ConnectivityViolation> {
+        let profile = graph.get_property(PROFILE_PROPERTY).map(|p| p.as_str());
+        let rules: Vec<&ConnectivityRule> = self.rules.iter()
+            .filter(|rule| rule.applies_to(profile))
+            .collect();
+        let mut violations = Vec::new();
+        
+        // Per-connection checks
+        for conn in &graph.connections {
+            let (source, dest) = match (graph.get_tile(&conn.source_tile_id), graph.get_tile(&conn.dest_tile_id)) {
+                (Some(source), Some(dest)) => (source, dest),
+                _ => continue,
+            };
+            
+            let matching: Vec<&&ConnectivityRule> = rules.iter()
+                .filter(|rule| rule.source == source.tile_type && rule.dest == dest.tile_type)
+                .collect();
+            
+            if let Some(rule) = matching.iter().find(|rule| rule.kind == RuleKind::Deny) {
+                violations.push(ConnectivityViolation {
+                    connection_id: Some(conn.id.clone()),
+                    source_tile_id: source.id.clone(),
+                    dest_tile_id: dest.id.clone(),
+                    message: format!(
+                        "Connection from '{}' ({:?}) to '{}' ({:?}) is denied by policy '{}': {}",
+                        source.name, source.tile_type, dest.name, dest.tile_type, self.name, rule.description
+                    ),
+                });
+                continue;
+            }
+            
+            if !self.default_allow && !matching.iter().any(|rule| rule.kind == RuleKind::Allow) {
+                violations.push(ConnectivityViolation {
+                    connection_id: Some(conn.id.clone()),
+                    source_tile_id: source.id.clone(),
+                    dest_tile_id: dest.id.clone(),
+                    message: format!(
+                        "Connection from '{}' ({:?}) to '{}' ({:?}) is not allowed by policy '{}'",
+                        source.name, source.tile_type, dest.name, dest.tile_type, self.name
+                    ),
+                });
+            }
+        }
+        
+        // Path-level checks for RequireVia rules
+        for rule in &rules {
+            if let RuleKind::RequireVia(via) = &rule.kind {
+                violations.extend(self.check_required_intermediary(graph, rule, via));
+            }
+        }
+        
+        violations
+    }
+    
+    /// Find source tiles that can reach a destination tile without passing through `via`
+    fn check_required_intermediary(
+        &self,
+        graph: &TileGraph,
+        rule: &ConnectivityRule,
+        via: &TileType,
+    ) -> Vec<ConnectivityViolation> {
+        let mut violations = Vec::new();
+        
+        for start in graph.tiles.values().filter(|tile| tile.tile_type == rule.source) {
+            let mut visited: HashSet<&str> = HashSet::new();
+            let mut queue: VecDeque<&str> = VecDeque::new();
+            visited.insert(start.id.as_str());
+            queue.push_back(start.id.as_str());
+            
+            while let Some(current) = queue.pop_front() {
+                for conn in graph.connections.iter().filter(|conn| conn.source_tile_id == current) {
+                    let next = match graph.get_tile(&conn.dest_tile_id) {
+                        Some(tile) => tile,
+                        None => continue,
+                    };
+                    
+                    // Paths through the required intermediary are compliant
+                    if &next.tile_type == via || !visited.insert(next.id.as_str()) {
+                        continue;
+                    }
+                    
+                    if next.tile_type == rule.dest {
+                        violations.push(ConnectivityViolation {
+                            connection_id: None,
+                            source_tile_id: start.id.clone(),
+                            dest_tile_id: next.id.clone(),
+                            message: format!(
+                                "Path from '{}' to '{}' does not pass through a {:?} tile (policy '{}'): {}",
+                                start.name, next.name, via, self.name, rule.description
+                            ),
+                        });
+                    }
+                    
+                    queue.push_back(next.id.as_str());
+                }
+            }
+        }
+        
+        violations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tile_engine::tile_core::{Tile, TilePort, PortType, TileConnection, ConnectionType};
+    
+    fn tile_with_ports(name: &str, tile_type: TileType) -> Tile {
+        let mut tile = Tile::new(name.to_string(), tile_type, name.to_string());
+        tile.add_port(TilePort {
+            id: "in".to_string(),
+            name: "in".to_string(),
+            port_type: PortType::Input,
+            data_type: "Packet".to_string(),
+            description: String::new(),
+        });
+        tile.add_port(TilePort {
+            id: "out".to_string(),
+            name: "out".to_string(),
+            port_type: PortType::Output,
+            data_type: "Packet".to_string(),
+            description: String::new(),
+        });
+        tile
+    }
+    
+    fn connect(graph: &mut TileGraph, source: &str, dest: &str) {
+        graph.add_connection(TileConnection {
+            id: format!("{}->{}", source, dest),
+            source_tile_id: source.to_string(),
+            source_port_id: "out".to_string(),
+            dest_tile_id: dest.to_string(),
+            dest_port_id: "in".to_string(),
+            connection_type: ConnectionType::DataFlow,
+        }).unwrap();
+    }
+    
+    #[test]
+    fn test_require_via_security() {
+        let policy = ConnectivityPolicy::hardened();
+        let mut graph = TileGraph::new("net".to_string());
+        graph.set_property(PROFILE_PROPERTY.to_string(), "hardened".to_string());
+        
+        let nic = tile_with_ports("nic", TileType::Network);
+        let fw = tile_with_ports("fw", TileType::Security);
+        let cpu = tile_with_ports("cpu", TileType::Processing);
+        let (nic_id, fw_id, cpu_id) = (nic.id.clone(), fw.id.clone(), cpu.id.clone());
+        graph.add_tile(nic).unwrap();
+        graph.add_tile(fw).unwrap();
+        graph.add_tile(cpu).unwrap();
+        
+        connect(&mut graph, &nic_id, &fw_id);
+        connect(&mut graph, &fw_id, &cpu_id);
+        assert!(policy.validate(&graph).is_empty());
+        
+        // A direct bypass violates the rule
+        connect(&mut graph, &nic_id, &cpu_id);
+        assert_eq!(policy.validate(&graph).len(), 1);
+        
+        // The rule is profile-specific
+        graph.set_property(PROFILE_PROPERTY.to_string(), "desktop".to_string());
+        assert!(policy.validate(&graph).is_empty());
+    }
+}
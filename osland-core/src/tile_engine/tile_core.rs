@@ -5,6 +5,7 @@
 use serde::{Serialize, Deserialize};
 use std::collections::HashMap;
 use uuid::Uuid;
+use crate::core::stable_ids::{self, IdStrategy};
 
 /// Tile Type Enumeration
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -219,20 +220,54 @@ pub struct TileGraph {
     
     /// Graph properties
     pub properties: HashMap<String, String>,
+    
+    /// How new tile and connection IDs are generated
+    #[serde(default)]
+    pub id_strategy: IdStrategy,
 }
 
 impl TileGraph {
     /// Create a new tile graph
     pub fn new(name: String) -> Self {
+        Self::with_id_strategy(name, IdStrategy::Random)
+    }
+    
+    /// Create a new tile graph whose IDs are generated with the given strategy
+    ///
+    /// With `IdStrategy::Deterministic` the graph ID is derived from its name.
+    pub fn with_id_strategy(name: String, id_strategy: IdStrategy) -> Self {
         Self {
-            id: Uuid::new_v4().to_string(),
+            id: id_strategy.uuid(&["graph", &name]).to_string(),
             name,
             tiles: HashMap::new(),
             connections: Vec::new(),
             properties: HashMap::new(),
+            id_strategy,
         }
     }
     
+    /// Generate an ID for a new tile instantiated from a template
+    ///
+    /// Deterministic IDs number the instances of each template in creation order.
+    pub fn new_tile_id(&self, template: &str) -> String {
+        (0u64..)
+            .map(|sequence| self.id_strategy.uuid(&["tile", &self.id, template, &sequence.to_string()]).to_string())
+            .find(|id| !self.tiles.contains_key(id))
+            .unwrap_or_default()
+    }
+    
+    /// Generate an ID for a connection between two tile ports
+    pub fn connection_id(&self, source_tile_id: &str, source_port_id: &str, dest_tile_id: &str, dest_port_id: &str) -> String {
+        self.id_strategy.uuid(&["connection", source_tile_id, source_port_id, dest_tile_id, dest_port_id]).to_string()
+    }
+    
+    /// Serialize the graph with sorted keys and connections, so that equal graphs produce equal files
+    pub fn to_canonical_json(&self) -> Result<String, String> {
+        let mut graph = self.clone();
+        graph.connections.sort_by(|a, b| a.id.cmp(&b.id));
+        stable_ids::to_canonical_json(&graph).map_err(|e| format!("Failed to serialize tile graph: {}", e))
+    }
+    
     /// Add a tile to the graph
     pub fn add_tile(&mut self, tile: Tile) -> Result<(), String> {
         if self.tiles.contains_key(&tile.id) {
@@ -3,6 +3,9 @@
 // SPDX-License-Identifier: MulanPSL-2.0
 
 use crate::tile_engine::tile_core::{Tile, TileGraph, TileType, TilePort, PortType, TileConnection, ConnectionType};
+use crate::tile_engine::tile_constraints::ConnectivityPolicy;
+use crate::tile_engine::tile_placement::{PlacementPlan, TilePlacer};
+use crate::core::stable_ids::IdStrategy;
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
 
@@ -19,18 +22,27 @@ pub struct TileDesigner {
     
     /// Current history position
     history_position: Arc<RwLock<usize>>,
+    
+    /// Connectivity policy enforced during validation
+    connectivity_policy: Arc<RwLock<Option<ConnectivityPolicy>>>,
 }
 
 impl TileDesigner {
     /// Create a new tile designer
     pub fn new(graph_name: String) -> Self {
-        let graph = TileGraph::new(graph_name);
+        Self::with_id_strategy(graph_name, IdStrategy::Random)
+    }
+    
+    /// Create a new tile designer whose graph generates IDs with the given strategy
+    pub fn with_id_strategy(graph_name: String, id_strategy: IdStrategy) -> Self {
+        let graph = TileGraph::with_id_strategy(graph_name, id_strategy);
         
         Self {
             current_graph: Arc::new(RwLock::new(graph)),
             tile_library: Arc::new(RwLock::new(HashMap::new())),
             design_history: Arc::new(RwLock::new(Vec::new())),
             history_position: Arc::new(RwLock::new(0)),
+            connectivity_policy: Arc::new(RwLock::new(None)),
         }
     }
     
@@ -41,6 +53,13 @@ impl TileDesigner {
         Ok(())
     }
     
+    /// Set the connectivity policy enforced by `validate_graph`
+    pub fn set_connectivity_policy(&self, policy: Option<ConnectivityPolicy>) -> Result<(), String> {
+        let mut connectivity_policy = self.connectivity_policy.write().map_err(|_| "Failed to acquire write lock on connectivity policy")?;
+        *connectivity_policy = policy;
+        Ok(())
+    }
+    
     /// Get available tiles from library
     pub fn get_available_tiles(&self) -> Result<Vec<Tile>, String> {
         let tile_library = self.tile_library.read().map_err(|_| "Failed to acquire read lock on tile library")?;
@@ -78,8 +97,9 @@ impl TileDesigner {
         // Save current state to history
         self.save_to_history()?;
         
+        let mut graph = self.current_graph.write().map_err(|_| "Failed to acquire write lock on graph")?;
         let connection = TileConnection {
-            id: uuid::Uuid::new_v4().to_string(),
+            id: graph.connection_id(&source_tile_id, &source_port_id, &dest_tile_id, &dest_port_id),
             source_tile_id,
             source_port_id,
             dest_tile_id,
@@ -87,7 +107,6 @@ impl TileDesigner {
             connection_type,
         };
         
-        let mut graph = self.current_graph.write().map_err(|_| "Failed to acquire write lock on graph")?;
         graph.add_connection(connection.clone())?;
         Ok(connection.id)
     }
@@ -102,6 +121,14 @@ impl TileDesigner {
         Ok(())
     }
     
+    /// Store topology-aware placement hints on the tiles of the current graph
+    pub fn annotate_placement(&self, placer: &TilePlacer) -> Result<PlacementPlan, String> {
+        self.save_to_history()?;
+        
+        let mut graph = self.current_graph.write().map_err(|_| "Failed to acquire write lock on graph")?;
+        Ok(placer.annotate(&mut graph))
+    }
+    
     /// Get the current graph
     pub fn get_current_graph(&self) -> Result<TileGraph, String> {
         let graph = self.current_graph.read().map_err(|_| "Failed to acquire read lock on graph")?;
@@ -194,8 +221,9 @@ impl TileDesigner {
         let tile_library = self.tile_library.read().map_err(|_| "Failed to acquire read lock on tile library")?;
         
         if let Some(template) = tile_library.get(template_name) {
+            let graph = self.current_graph.read().map_err(|_| "Failed to acquire read lock on graph")?;
             let mut new_tile = template.clone();
-            new_tile.id = uuid::Uuid::new_v4().to_string(); // Generate new ID
+            new_tile.id = graph.new_tile_id(template_name); // Generate new ID
             Ok(new_tile)
         } else {
             Err(format!("Template '{}' not found in tile library", template_name))
@@ -234,6 +262,12 @@ impl TileDesigner {
             }
         }
         
+        // Check connectivity constraints
+        let connectivity_policy = self.connectivity_policy.read().map_err(|_| "Failed to acquire read lock on connectivity policy")?;
+        if let Some(policy) = connectivity_policy.as_ref() {
+            errors.extend(policy.validate(&graph).into_iter().map(|violation| violation.message));
+        }
+        
         Ok(errors)
     }
 }
\ No newline at end of file
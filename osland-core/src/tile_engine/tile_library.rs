@@ -3,13 +3,18 @@
 // SPDX-License-Identifier: MulanPSL-2.0
 
 use crate::tile_engine::tile_core::{Tile, TileType, TilePort, PortType};
+use crate::tile_engine::tile_compiler::TileCompiler;
+use crate::component_manager::component::ComponentLibrary;
+use crate::os_design::virtualization::VirtioDevice;
 use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
 use serde::{Serialize, Deserialize};
-use uuid::Uuid;
+use crate::core::stable_ids::{self, IdStrategy};
+use crate::core::event_bus::{self, ProjectEvent, SystemEvent};
 
 /// Tile Library Manager
+#[derive(Debug, Clone)]
 pub struct TileLibrary {
     /// Collection of tiles organized by category
     tiles: HashMap<String, HashMap<String, Tile>>,
@@ -19,6 +24,9 @@ pub struct TileLibrary {
     
     /// Version history for tiles
     version_history: HashMap<String, Vec<TileVersion>>,
+    
+    /// How IDs of new tile versions are generated
+    id_strategy: IdStrategy,
 }
 
 /// Library Metadata
@@ -36,6 +44,10 @@ pub struct LibraryMetadata {
     /// Library author
     pub author: String,
     
+    /// License of the library's tiles (SPDX identifier), unless a tile sets its own
+    #[serde(default)]
+    pub license: Option<String>,
+    
     /// Creation date
     pub created_date: String,
     
@@ -83,6 +95,7 @@ impl TileLibrary {
             version: "1.0.0".to_string(),
             description,
             author: "OSland Team".to_string(),
+            license: None,
             created_date: chrono::Utc::now().to_rfc3339(),
             modified_date: chrono::Utc::now().to_rfc3339(),
         };
@@ -91,9 +104,17 @@ impl TileLibrary {
             tiles: HashMap::new(),
             metadata,
             version_history: HashMap::new(),
+            id_strategy: IdStrategy::default(),
         }
     }
     
+    /// Set how IDs of new tile versions are generated
+    ///
+    /// With `IdStrategy::Deterministic` a version's ID is derived from the tile ID and the version number.
+    pub fn set_id_strategy(&mut self, id_strategy: IdStrategy) {
+        self.id_strategy = id_strategy;
+    }
+    
     /// Create a tile library from an existing set of tiles
     pub fn from_tiles(name: String, description: String, tiles: Vec<(String, Tile)>) -> Self {
         let mut library = Self::new(name, description);
@@ -118,6 +139,28 @@ impl TileLibrary {
         library
     }
     
+    /// Import every component of a component library as a tile
+    ///
+    /// Tiles are grouped by component category. Components already present in the
+    /// library are skipped. Returns the number of imported tiles.
+    pub fn import_component_library(&mut self, library: &ComponentLibrary) -> Result<usize, String> {
+        let mut imported = 0;
+        
+        for component in library.get_all_components() {
+            let category = format!("{:?}", component.category);
+            let already_present = self.tiles.get(&category)
+                .map_or(false, |tiles| tiles.contains_key(&component.id));
+            if already_present {
+                continue;
+            }
+            
+            self.add_tile(category, TileCompiler::convert_component_to_tile(component))?;
+            imported += 1;
+        }
+        
+        Ok(imported)
+    }
+    
     /// Load a tile library from a JSON file
     pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<Self, String> {
         let content = fs::read_to_string(path).map_err(|e| format!("Failed to read library file: {}", e))?;
@@ -127,7 +170,7 @@ impl TileLibrary {
     
     /// Save the tile library to a JSON file
     pub fn save_to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), String> {
-        let json = serde_json::to_string_pretty(self).map_err(|e| format!("Failed to serialize library: {}", e))?;
+        let json = stable_ids::to_canonical_json(self).map_err(|e| format!("Failed to serialize library: {}", e))?;
         fs::write(path, json).map_err(|e| format!("Failed to write library file: {}", e))?;
         Ok(())
     }
@@ -231,6 +274,7 @@ impl TileLibrary {
             "Standard Tile Library".to_string(),
             "A collection of standard tiles for OSland".to_string()
         );
+        library.metadata.license = Some("MulanPSL-2.0".to_string());
         
         // Add standard processing tiles
         library.add_standard_processing_tiles().unwrap();
@@ -250,6 +294,9 @@ impl TileLibrary {
         // Add standard security tiles
         library.add_standard_security_tiles().unwrap();
         
+        // Add virtio device tiles
+        library.add_standard_virtio_tiles().unwrap();
+        
         library
     }
     
@@ -713,6 +760,15 @@ impl TileLibrary {
         
         Ok(())
     }
+    
+    /// Add virtio device tiles for hypervisor and guest designs
+    fn add_standard_virtio_tiles(&mut self) -> Result<(), String> {
+        for device in VirtioDevice::all() {
+            self.add_tile("Virtio".to_string(), device.tile())?;
+        }
+        
+        Ok(())
+    }
 
     /// Get version history for a tile
     pub fn get_tile_version_history(&self, tile_id: &str) -> Result<&Vec<TileVersion>, String> {
@@ -770,7 +826,7 @@ impl TileLibrary {
         let mut new_tile = tile.clone();
         let new_version = self.increment_version(&new_tile.version);
         new_tile.version = new_version.clone();
-        new_tile.id = Uuid::new_v4().to_string(); // Generate new ID for the version
+        new_tile.id = self.id_strategy.uuid(&["tile", tile_id, &new_version]).to_string(); // Generate new ID for the version
         
         // Add to version history
         let version = TileVersion {
@@ -783,6 +839,12 @@ impl TileLibrary {
         
         self.version_history.entry(tile_id.to_string()).or_insert_with(Vec::new).push(version);
         
+        event_bus::publish(SystemEvent::Project(ProjectEvent::VersionPublished {
+            project: self.metadata.name.clone(),
+            component: tile_id.to_string(),
+            version: new_version.clone(),
+        }));
+        
         Ok(new_version)
     }
     
@@ -825,7 +887,7 @@ impl TileLibrary {
 
     /// Export tile library to a file
     pub fn export_library<P: AsRef<Path>>(&self, path: P) -> Result<(), String> {
-        let json = serde_json::to_string_pretty(self).map_err(|e| format!("Failed to serialize library: {}", e))?;
+        let json = stable_ids::to_canonical_json(self).map_err(|e| format!("Failed to serialize library: {}", e))?;
         fs::write(path, json).map_err(|e| format!("Failed to write library file: {}", e))?;
         Ok(())
     }
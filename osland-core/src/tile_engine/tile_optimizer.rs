@@ -4,6 +4,7 @@
 
 use crate::tile_engine::tile_core::{TileGraph, Tile, TileType, TilePort, PortType, TileConnection, ConnectionType};
 use std::collections::{HashMap, HashSet};
+use serde::{Serialize, Deserialize};
 
 /// Tile Optimizer
 pub struct TileOptimizer {
@@ -68,6 +69,79 @@ pub struct OptimizationReport {
     pub details: Vec<String>,
 }
 
+/// Per-tile power estimate
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TilePowerEstimate {
+    /// Tile ID
+    pub tile_id: String,
+    
+    /// Tile name
+    pub tile_name: String,
+    
+    /// Static (leakage) power in watts
+    pub static_power_w: f64,
+    
+    /// Dynamic (switching) power in watts
+    pub dynamic_power_w: f64,
+}
+
+impl TilePowerEstimate {
+    /// Total power in watts
+    pub fn total_power_w(&self) -> f64 {
+        self.static_power_w + self.dynamic_power_w
+    }
+}
+
+/// Power estimate for a whole tile graph
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphPowerEstimate {
+    /// Graph ID
+    pub graph_id: String,
+    
+    /// Graph name
+    pub graph_name: String,
+    
+    /// Per-tile estimates, sorted by tile ID
+    pub tiles: Vec<TilePowerEstimate>,
+    
+    /// Total static power in watts
+    pub total_static_power_w: f64,
+    
+    /// Total dynamic power in watts
+    pub total_dynamic_power_w: f64,
+}
+
+impl GraphPowerEstimate {
+    /// Total power in watts
+    pub fn total_power_w(&self) -> f64 {
+        self.total_static_power_w + self.total_dynamic_power_w
+    }
+    
+    /// Get the estimate for a single tile
+    pub fn get_tile(&self, tile_id: &str) -> Option<&TilePowerEstimate> {
+        self.tiles.iter().find(|estimate| estimate.tile_id == tile_id)
+    }
+}
+
+/// What-if power comparison between two designs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PowerComparison {
+    /// Baseline design estimate
+    pub baseline: GraphPowerEstimate,
+    
+    /// Candidate design estimate
+    pub candidate: GraphPowerEstimate,
+    
+    /// Candidate total minus baseline total, in watts
+    pub delta_power_w: f64,
+    
+    /// Relative change in percent (negative means the candidate uses less power)
+    pub delta_percent: f64,
+    
+    /// Human-readable notes about the largest per-tile differences
+    pub details: Vec<String>,
+}
+
 impl TileOptimizer {
     /// Create a new tile optimizer
     pub fn new(settings: Option<OptimizationSettings>) -> Self {
@@ -488,4 +562,151 @@ impl TileOptimizer {
         
         Ok(optimized)
     }
-}
\ No newline at end of file
+    
+    /// Estimate static and dynamic power for a single tile
+    ///
+    /// The model is intentionally coarse: a per-type static baseline scaled by the
+    /// number of cores and the memory size, and a dynamic component proportional to
+    /// cores x frequency x activity. Recognised properties are `cores`,
+    /// `compute_units`, `frequency_mhz`/`speed_mhz`, `size_kb`/`size_mb`/`size_gb`,
+    /// `speed_mbps` and `activity` (0.0-1.0). An explicit `power_w` property
+    /// overrides the model entirely.
+    pub fn estimate_tile_power(&self, tile: &Tile) -> TilePowerEstimate {
+        let property_f64 = |key: &str| tile.get_property(key).and_then(|v| v.trim().parse::<f64>().ok());
+        
+        if let Some(power) = property_f64("power_w") {
+            let static_share = property_f64("static_power_ratio").unwrap_or(0.3).clamp(0.0, 1.0);
+            return TilePowerEstimate {
+                tile_id: tile.id.clone(),
+                tile_name: tile.name.clone(),
+                static_power_w: power * static_share,
+                dynamic_power_w: power * (1.0 - static_share),
+            };
+        }
+        
+        let cores = property_f64("cores")
+            .or_else(|| property_f64("compute_units"))
+            .unwrap_or(1.0)
+            .max(1.0);
+        let frequency_ghz = property_f64("frequency_mhz")
+            .or_else(|| property_f64("speed_mhz"))
+            .map(|mhz| mhz / 1000.0)
+            .unwrap_or(1.0);
+        let memory_mb = property_f64("size_mb")
+            .or_else(|| property_f64("size_kb").map(|kb| kb / 1024.0))
+            .or_else(|| property_f64("size_gb").map(|gb| gb * 1024.0))
+            .unwrap_or(0.0);
+        let activity = property_f64("activity").unwrap_or(0.5).clamp(0.0, 1.0);
+        
+        // (static baseline W, dynamic W per core per GHz at full activity)
+        let (base_static, dynamic_coefficient) = match tile.tile_type {
+            TileType::Processing => (0.5, 2.0),
+            TileType::Memory => (0.1, 0.3),
+            TileType::IO => (0.2, 0.5),
+            TileType::Network => (0.3, 0.8),
+            TileType::Storage => (1.0, 0.6),
+            TileType::Security => (0.2, 0.7),
+            TileType::Custom(_) => (0.3, 1.0),
+        };
+        
+        // Memory leakage: roughly 0.35 W per GiB of DRAM
+        let static_power_w = base_static * cores + memory_mb / 1024.0 * 0.35;
+        
+        let mut dynamic_power_w = dynamic_coefficient * cores * frequency_ghz * activity;
+        if let Some(mbps) = property_f64("speed_mbps") {
+            // Link power scales with line rate
+            dynamic_power_w += mbps / 1000.0 * 0.5 * activity;
+        }
+        
+        TilePowerEstimate {
+            tile_id: tile.id.clone(),
+            tile_name: tile.name.clone(),
+            static_power_w,
+            dynamic_power_w,
+        }
+    }
+    
+    /// Estimate power for every tile in a graph and aggregate the totals
+    pub fn estimate_power(&self, graph: &TileGraph) -> GraphPowerEstimate {
+        let mut tiles: Vec<TilePowerEstimate> = graph.tiles.values()
+            .map(|tile| self.estimate_tile_power(tile))
+            .collect();
+        tiles.sort_by(|a, b| a.tile_id.cmp(&b.tile_id));
+        
+        let total_static_power_w = tiles.iter().map(|t| t.static_power_w).sum();
+        let total_dynamic_power_w = tiles.iter().map(|t| t.dynamic_power_w).sum();
+        
+        GraphPowerEstimate {
+            graph_id: graph.id.clone(),
+            graph_name: graph.name.clone(),
+            tiles,
+            total_static_power_w,
+            total_dynamic_power_w,
+        }
+    }
+    
+    /// Annotate each tile with its power estimate (`estimated_static_power_w`,
+    /// `estimated_dynamic_power_w`) and the graph with `estimated_power_w`
+    pub fn annotate_power(&self, graph: &mut TileGraph) -> GraphPowerEstimate {
+        let estimate = self.estimate_power(graph);
+        
+        for tile_estimate in &estimate.tiles {
+            if let Some(tile) = graph.tiles.get_mut(&tile_estimate.tile_id) {
+                tile.set_property("estimated_static_power_w".to_string(), format!("{:.3}", tile_estimate.static_power_w));
+                tile.set_property("estimated_dynamic_power_w".to_string(), format!("{:.3}", tile_estimate.dynamic_power_w));
+            }
+        }
+        graph.set_property("estimated_power_w".to_string(), format!("{:.3}", estimate.total_power_w()));
+        
+        estimate
+    }
+    
+    /// Compare the estimated power of two designs (what-if analysis)
+    pub fn compare_power(&self, baseline: &TileGraph, candidate: &TileGraph) -> PowerComparison {
+        let baseline_estimate = self.estimate_power(baseline);
+        let candidate_estimate = self.estimate_power(candidate);
+        
+        let baseline_total = baseline_estimate.total_power_w();
+        let candidate_total = candidate_estimate.total_power_w();
+        let delta_power_w = candidate_total - baseline_total;
+        let delta_percent = if baseline_total > 0.0 {
+            delta_power_w / baseline_total * 100.0
+        } else {
+            0.0
+        };
+        
+        // Match tiles by name so that re-instantiated tiles (new IDs) still compare
+        let baseline_by_name: HashMap<&str, f64> = baseline_estimate.tiles.iter()
+            .map(|t| (t.tile_name.as_str(), t.total_power_w()))
+            .collect();
+        let candidate_names: HashSet<&str> = candidate_estimate.tiles.iter()
+            .map(|t| t.tile_name.as_str())
+            .collect();
+        
+        let mut details = Vec::new();
+        for tile in &candidate_estimate.tiles {
+            match baseline_by_name.get(tile.tile_name.as_str()) {
+                Some(before) => {
+                    let diff = tile.total_power_w() - before;
+                    if diff.abs() >= 0.001 {
+                        details.push(format!("Tile '{}' changed by {:+.3} W", tile.tile_name, diff));
+                    }
+                }
+                None => details.push(format!("Tile '{}' added ({:.3} W)", tile.tile_name, tile.total_power_w())),
+            }
+        }
+        for tile in &baseline_estimate.tiles {
+            if !candidate_names.contains(tile.tile_name.as_str()) {
+                details.push(format!("Tile '{}' removed ({:.3} W)", tile.tile_name, tile.total_power_w()));
+            }
+        }
+        
+        PowerComparison {
+            baseline: baseline_estimate,
+            candidate: candidate_estimate,
+            delta_power_w,
+            delta_percent,
+            details,
+        }
+    }
+}
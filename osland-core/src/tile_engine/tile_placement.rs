@@ -0,0 +1,368 @@
+// Tile Placement Module for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! Topology-aware placement hints for the tiles of a graph.
+//!
+//! Tiles joined by connections form a group that is kept on one NUMA node and, as
+//! far as it fits, within one last-level cache, so the data they exchange stays in
+//! cache instead of crossing sockets. Groups are placed largest first on the node
+//! with the most free CPUs, and the first hardware thread of every core is handed
+//! out before any sibling thread. A tile asks for more than one CPU with the
+//! `threads` property (at most the number of CPUs) and can be pinned to a node with
+//! `numa_node`.
+//!
+//! The hints are advisory: `TilePlacer::annotate` stores them as tile properties,
+//! which the tile designer shows on the tiles and the tile compiler carries into the
+//! compiled components. Nothing pins threads with them yet.
+
+use std::collections::{BTreeMap, HashMap};
+use serde::{Deserialize, Serialize};
+use crate::architecture_adapter::topology_probe::{parse_cpu_list, CpuTopology};
+use super::tile_core::{Tile, TileGraph};
+
+/// Tile property with the number of CPUs the tile runs on
+pub const THREADS_PROPERTY: &str = "threads";
+
+/// Tile property pinning the tile to a NUMA node
+pub const NUMA_NODE_PROPERTY: &str = "numa_node";
+
+/// Tile property set by `annotate` with the suggested NUMA node
+pub const PLACEMENT_NODE_PROPERTY: &str = "placement_numa_node";
+
+/// Tile property set by `annotate` with the suggested CPUs (a kernel CPU list)
+pub const PLACEMENT_CPUS_PROPERTY: &str = "placement_cpus";
+
+/// Suggested placement of one tile
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlacementHint {
+    /// Tile ID
+    pub tile_id: String,
+    
+    /// Group of connected tiles the tile belongs to
+    pub group: usize,
+    
+    /// NUMA node the tile should run and allocate memory on
+    pub numa_node: u32,
+    
+    /// CPUs the tile's threads should be pinned to
+    pub cpus: Vec<u32>,
+    
+    /// Whether the tile had to leave its group's node or share CPUs with other tiles
+    pub spilled: bool,
+}
+
+/// Placement hints for a whole graph
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PlacementPlan {
+    /// Hints ordered by group, then tile ID
+    pub hints: Vec<PlacementHint>,
+}
+
+impl PlacementPlan {
+    /// Get the hint of a tile
+    pub fn get(&self, tile_id: &str) -> Option<&PlacementHint> {
+        self.hints.iter().find(|hint| hint.tile_id == tile_id)
+    }
+    
+    /// Count the connections whose ends were placed on different NUMA nodes
+    pub fn cross_node_connections(&self, graph: &TileGraph) -> usize {
+        graph.connections.iter()
+            .filter(|connection| {
+                match (self.get(&connection.source_tile_id), self.get(&connection.dest_tile_id)) {
+                    (Some(source), Some(dest)) => source.numa_node != dest.numa_node,
+                    _ => false,
+                }
+            })
+            .count()
+    }
+}
+
+/// Get the placement `annotate` stored on a tile: its NUMA node and CPUs
+pub fn placement_of(tile: &Tile) -> Option<(u32, Vec<u32>)> {
+    let numa_node = tile.get_property(PLACEMENT_NODE_PROPERTY)?.parse().ok()?;
+    let cpus = parse_cpu_list(tile.get_property(PLACEMENT_CPUS_PROPERTY)?).ok()?;
+    Some((numa_node, cpus))
+}
+
+/// Format CPUs as a kernel CPU list such as `0-3,8`
+pub fn format_cpu_list(cpus: &[u32]) -> String {
+    let mut sorted = cpus.to_vec();
+    sorted.sort_unstable();
+    sorted.dedup();
+    let mut ranges: Vec<String> = Vec::new();
+    let mut index = 0;
+    while index < sorted.len() {
+        let start = sorted[index];
+        let mut end = start;
+        while index + 1 < sorted.len() && sorted[index + 1] == end + 1 {
+            index += 1;
+            end = sorted[index];
+        }
+        ranges.push(if start == end { start.to_string() } else { format!("{}-{}", start, end) });
+        index += 1;
+    }
+    ranges.join(",")
+}
+
+/// Free CPUs of one NUMA node, in the order they are handed out
+struct NodeCpus {
+    /// Free CPUs ordered by hardware thread rank, last-level cache, then CPU number
+    free: Vec<u32>,
+    
+    /// Every CPU of the node in the same order, reused once the node is full
+    all: Vec<u32>,
+    
+    /// Position in `all` of the next CPU to share once the node is full
+    shared: usize,
+}
+
+/// Topology-aware tile placer
+pub struct TilePlacer<'a> {
+    topology: &'a CpuTopology,
+}
+
+impl<'a> TilePlacer<'a> {
+    /// Create a placer for a topology
+    pub fn new(topology: &'a CpuTopology) -> Self {
+        Self { topology }
+    }
+    
+    /// Get the last-level cache domain of a CPU (its index among the caches)
+    fn cache_domain(&self, cpu: u32) -> usize {
+        self.topology.last_level_cache(cpu)
+            .and_then(|cache| self.topology.caches.iter().position(|candidate| candidate == cache))
+            .unwrap_or(usize::MAX)
+    }
+    
+    /// Split the graph into groups of connected tiles, largest demand first
+    fn groups(&self, graph: &TileGraph, demand: &HashMap<&str, usize>) -> Vec<Vec<String>> {
+        let mut tile_ids: Vec<&str> = graph.tiles.keys().map(String::as_str).collect();
+        tile_ids.sort_unstable();
+        let index: HashMap<&str, usize> = tile_ids.iter().enumerate().map(|(position, id)| (*id, position)).collect();
+        
+        // Union-find over the connections
+        let mut parent: Vec<usize> = (0..tile_ids.len()).collect();
+        fn find(parent: &mut [usize], mut node: usize) -> usize {
+            while parent[node] != node {
+                parent[node] = parent[parent[node]];
+                node = parent[node];
+            }
+            node
+        }
+        for connection in &graph.connections {
+            if let (Some(&source), Some(&dest)) = (index.get(connection.source_tile_id.as_str()), index.get(connection.dest_tile_id.as_str())) {
+                let (source, dest) = (find(&mut parent, source), find(&mut parent, dest));
+                parent[source.max(dest)] = source.min(dest);
+            }
+        }
+        
+        let mut groups: BTreeMap<usize, Vec<String>> = BTreeMap::new();
+        for (position, id) in tile_ids.iter().enumerate() {
+            let root = find(&mut parent, position);
+            groups.entry(root).or_default().push(id.to_string());
+        }
+        let mut groups: Vec<Vec<String>> = groups.into_values().collect();
+        let total = |group: &Vec<String>| group.iter().map(|id| demand[id.as_str()]).sum::<usize>();
+        groups.sort_by(|a, b| total(b).cmp(&total(a)).then_with(|| a[0].cmp(&b[0])));
+        groups
+    }
+    
+    /// Suggest a NUMA node and CPUs for every tile of the graph
+    pub fn place(&self, graph: &TileGraph) -> PlacementPlan {
+        let mut plan = PlacementPlan::default();
+        if self.topology.numa_nodes.is_empty() {
+            return plan;
+        }
+        
+        // A tile cannot use more CPUs than the machine has
+        let cpu_count: usize = self.topology.numa_nodes.iter().map(|node| node.cpus.len()).sum();
+        let demand: HashMap<&str, usize> = graph.tiles.iter()
+            .map(|(id, tile)| {
+                let threads = tile.get_property(THREADS_PROPERTY).and_then(|value| value.parse::<usize>().ok()).unwrap_or(1);
+                (id.as_str(), threads.clamp(1, cpu_count.max(1)))
+            })
+            .collect();
+        
+        let mut nodes: BTreeMap<u32, NodeCpus> = self.topology.numa_nodes.iter()
+            .map(|node| {
+                let mut cpus = node.cpus.clone();
+                cpus.sort_by_key(|&cpu| (self.topology.smt_rank(cpu), self.cache_domain(cpu), cpu));
+                (node.id, NodeCpus { free: cpus.clone(), all: cpus, shared: 0 })
+            })
+            .collect();
+        
+        for (group_index, group) in self.groups(graph, &demand).into_iter().enumerate() {
+            let group_demand: usize = group.iter().map(|id| demand[id.as_str()]).sum();
+            let pinned = group.iter()
+                .filter_map(|id| graph.tiles[id].get_property(NUMA_NODE_PROPERTY))
+                .filter_map(|value| value.parse::<u32>().ok())
+                .find(|node| nodes.contains_key(node));
+            let home = pinned.unwrap_or_else(|| {
+                nodes.iter()
+                    .max_by(|(a_id, a), (b_id, b)| a.free.len().cmp(&b.free.len()).then_with(|| b_id.cmp(a_id)))
+                    .map(|(id, _)| *id)
+                    .unwrap_or_default()
+            });
+            
+            // Keep the group in the cache domain that fits it most tightly, or else the one with most room
+            let preferred_domain = {
+                let mut free_per_domain: BTreeMap<usize, usize> = BTreeMap::new();
+                for &cpu in &nodes[&home].free {
+                    *free_per_domain.entry(self.cache_domain(cpu)).or_default() += 1;
+                }
+                free_per_domain.iter()
+                    .filter(|(_, free)| **free >= group_demand)
+                    .min_by_key(|(domain, free)| (**free, **domain))
+                    .or_else(|| free_per_domain.iter().max_by_key(|(domain, free)| (**free, usize::MAX - **domain)))
+                    .map(|(domain, _)| *domain)
+            };
+            
+            for tile_id in group {
+                let mut cpus = Vec::new();
+                let mut spilled = false;
+                let mut numa_node = home;
+                while cpus.len() < demand[tile_id.as_str()] {
+                    let wanted = demand[tile_id.as_str()] - cpus.len();
+                    let node = if nodes[&home].free.is_empty() {
+                        // Spill to the node with most free CPUs, sharing CPUs only when every node is full
+                        spilled = true;
+                        nodes.iter()
+                            .filter(|(_, node)| !node.free.is_empty())
+                            .max_by(|(a_id, a), (b_id, b)| a.free.len().cmp(&b.free.len()).then_with(|| b_id.cmp(a_id)))
+                            .map(|(id, _)| *id)
+                    } else {
+                        Some(home)
+                    };
+                    match node {
+                        Some(node_id) => {
+                            if cpus.is_empty() {
+                                numa_node = node_id;
+                            }
+                            let node = nodes.get_mut(&node_id).unwrap();
+                            let domain = preferred_domain.filter(|_| node_id == home);
+                            let mut taken: Vec<u32> = node.free.iter()
+                                .copied()
+                                .filter(|&cpu| match domain {
+                                    Some(domain) => self.cache_domain(cpu) == domain,
+                                    None => true,
+                                })
+                                .take(wanted)
+                                .collect();
+                            if taken.len() < wanted {
+                                let more: Vec<u32> = node.free.iter()
+                                    .copied()
+                                    .filter(|cpu| !taken.contains(cpu))
+                                    .take(wanted - taken.len())
+                                    .collect();
+                                taken.extend(more);
+                            }
+                            node.free.retain(|cpu| !taken.contains(cpu));
+                            cpus.extend(taken);
+                        }
+                        None => {
+                            let node = nodes.get_mut(&numa_node).unwrap();
+                            if node.all.is_empty() {
+                                break;
+                            }
+                            cpus.push(node.all[node.shared % node.all.len()]);
+                            node.shared += 1;
+                        }
+                    }
+                }
+                cpus.sort_unstable();
+                plan.hints.push(PlacementHint { tile_id, group: group_index, numa_node, cpus, spilled });
+            }
+        }
+        plan
+    }
+    
+    /// Place the graph and store the hints as `placement_numa_node` and `placement_cpus` tile properties
+    pub fn annotate(&self, graph: &mut TileGraph) -> PlacementPlan {
+        let plan = self.place(graph);
+        for hint in &plan.hints {
+            if let Some(tile) = graph.tiles.get_mut(&hint.tile_id) {
+                tile.set_property(PLACEMENT_NODE_PROPERTY.to_string(), hint.numa_node.to_string());
+                tile.set_property(PLACEMENT_CPUS_PROPERTY.to_string(), format_cpu_list(&hint.cpus));
+            }
+        }
+        plan
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tile_engine::tile_core::{ConnectionType, Tile, TileConnection, TileType};
+    
+    fn graph(tiles: &[(&str, usize)], connections: &[(&str, &str)]) -> TileGraph {
+        let mut graph = TileGraph::new("placement".to_string());
+        for (id, threads) in tiles {
+            let mut tile = Tile::new(id.to_string(), TileType::Processing, String::new());
+            tile.id = id.to_string();
+            tile.set_property(THREADS_PROPERTY.to_string(), threads.to_string());
+            graph.add_tile(tile).unwrap();
+        }
+        for (index, (source, dest)) in connections.iter().enumerate() {
+            graph.connections.push(TileConnection {
+                id: format!("conn-{}", index),
+                source_tile_id: source.to_string(),
+                source_port_id: "out".to_string(),
+                dest_tile_id: dest.to_string(),
+                dest_port_id: "in".to_string(),
+                connection_type: ConnectionType::DataFlow,
+            });
+        }
+        graph
+    }
+    
+    #[test]
+    fn test_connected_tiles_share_a_node_and_cache() {
+        // Two packages of four cores with two threads each, one NUMA node per package
+        let topology = CpuTopology::uniform(2, 4, 2);
+        let mut graph = graph(
+            &[("net", 2), ("filter", 1), ("log", 1), ("fs", 2), ("cache", 2)],
+            &[("net", "filter"), ("filter", "log"), ("fs", "cache")],
+        );
+        let placer = TilePlacer::new(&topology);
+        let plan = placer.annotate(&mut graph);
+        
+        assert_eq!(plan.hints.len(), 5);
+        assert_eq!(plan.cross_node_connections(&graph), 0);
+        let net = plan.get("net").unwrap();
+        let fs = plan.get("fs").unwrap();
+        assert_ne!(net.numa_node, fs.numa_node);
+        assert_eq!(net.numa_node, plan.get("log").unwrap().numa_node);
+        
+        // One thread per core before any sibling thread
+        let group: Vec<u32> = ["net", "filter", "log"].iter().flat_map(|id| plan.get(id).unwrap().cpus.clone()).collect();
+        assert!(group.iter().all(|&cpu| topology.smt_rank(cpu) == 0));
+        assert!(group.windows(2).all(|pair| topology.shared_cache_level(pair[0], pair[1]) == Some(3)));
+        assert!(plan.hints.iter().all(|hint| !hint.spilled));
+        assert_eq!(graph.tiles["net"].get_property(PLACEMENT_CPUS_PROPERTY), Some(&format_cpu_list(&net.cpus)));
+    }
+    
+    #[test]
+    fn test_pinned_and_oversubscribed_tiles() {
+        let topology = CpuTopology::uniform(2, 1, 2);
+        let mut pinned = graph(&[("a", 1), ("b", 1)], &[]);
+        pinned.tiles.get_mut("b").unwrap().set_property(NUMA_NODE_PROPERTY.to_string(), "1".to_string());
+        pinned.tiles.get_mut("a").unwrap().set_property(NUMA_NODE_PROPERTY.to_string(), "1".to_string());
+        let plan = TilePlacer::new(&topology).place(&pinned);
+        assert!(plan.hints.iter().all(|hint| hint.numa_node == 1 && !hint.spilled));
+        
+        // Six CPUs asked of a machine with four
+        let plan = TilePlacer::new(&topology).place(&graph(&[("big", 3), ("other", 3)], &[("big", "other")]));
+        let cpus: usize = plan.hints.iter().map(|hint| hint.cpus.len()).sum();
+        assert_eq!(cpus, 6);
+        assert!(plan.get("other").unwrap().spilled);
+        
+        // Demand beyond the machine is clamped to its CPU count
+        let mut huge = graph(&[("huge", 1_000_000_000)], &[]);
+        let plan = TilePlacer::new(&topology).annotate(&mut huge);
+        assert_eq!(plan.get("huge").unwrap().cpus, vec![0, 1, 2, 3]);
+        assert_eq!(placement_of(&huge.tiles["huge"]), Some((plan.get("huge").unwrap().numa_node, vec![0, 1, 2, 3])));
+        
+        assert_eq!(format_cpu_list(&[5, 0, 1, 2, 8, 9]), "0-2,5,8-9");
+    }
+}
@@ -0,0 +1,471 @@
+// Tile Test Generator Module for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+use crate::component_manager::component::{Component, ComponentProperty, ComponentPort, PortDirection};
+use crate::tile_engine::tile_compiler::{TargetLanguage, sanitize_identifier};
+
+/// Generated test file
+#[derive(Debug, Clone)]
+pub struct GeneratedTestFile {
+    /// Component ID the tests belong to
+    pub component_id: String,
+    
+    /// Suggested file name
+    pub file_name: String,
+    
+    /// Test framework the file is written for
+    pub framework: String,
+    
+    /// File content
+    pub content: String,
+}
+
+/// Integer type of a boundary value
+///
+/// Every value derived for one port or property has the same integer type, so
+/// the generated arrays and initializer lists have a single element type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntType {
+    U8,
+    U16,
+    U32,
+    U64,
+    I8,
+    I16,
+    I32,
+    I64,
+}
+
+impl IntType {
+    /// Width in bits
+    fn bits(&self) -> u32 {
+        match self {
+            IntType::U8 | IntType::I8 => 8,
+            IntType::U16 | IntType::I16 => 16,
+            IntType::U32 | IntType::I32 => 32,
+            IntType::U64 | IntType::I64 => 64,
+        }
+    }
+    
+    /// Whether the type is signed
+    fn signed(&self) -> bool {
+        matches!(self, IntType::I8 | IntType::I16 | IntType::I32 | IntType::I64)
+    }
+    
+    /// Smallest and largest value of the type
+    fn range(&self) -> (i128, i128) {
+        if self.signed() {
+            (-(1i128 << (self.bits() - 1)), (1i128 << (self.bits() - 1)) - 1)
+        } else {
+            (0, (1i128 << self.bits()) - 1)
+        }
+    }
+    
+    /// Rust type name, used as the literal suffix
+    fn rust_name(&self) -> &'static str {
+        match self {
+            IntType::U8 => "u8",
+            IntType::U16 => "u16",
+            IntType::U32 => "u32",
+            IntType::U64 => "u64",
+            IntType::I8 => "i8",
+            IntType::I16 => "i16",
+            IntType::I32 => "i32",
+            IntType::I64 => "i64",
+        }
+    }
+}
+
+/// Boundary value used as a test input
+#[derive(Debug, Clone, PartialEq)]
+pub enum BoundaryValue {
+    /// Integer value of the given type
+    Int(i128, IntType),
+    
+    /// Floating point value
+    Float(f64),
+    
+    /// Boolean value
+    Bool(bool),
+    
+    /// String value
+    Str(String),
+}
+
+impl BoundaryValue {
+    /// Render the value as a literal in the given language
+    fn render(&self, language: &TargetLanguage) -> String {
+        match self {
+            BoundaryValue::Int(v, int_type) => render_int(*v, *int_type, language),
+            BoundaryValue::Float(v) => {
+                let literal = format!("{:?}", v);
+                match language {
+                    TargetLanguage::Rust => format!("{}_f64", literal),
+                    _ => literal,
+                }
+            }
+            BoundaryValue::Bool(v) => match language {
+                TargetLanguage::Python | TargetLanguage::Triton | TargetLanguage::TVM
+                | TargetLanguage::Helion | TargetLanguage::Mojo => if *v { "True" } else { "False" }.to_string(),
+                _ => v.to_string(),
+            },
+            BoundaryValue::Str(v) => format!("{:?}", v),
+        }
+    }
+}
+
+/// Render an integer literal of the given type
+fn render_int(value: i128, int_type: IntType, language: &TargetLanguage) -> String {
+    match language {
+        TargetLanguage::Rust => format!("{}_{}", value, int_type.rust_name()),
+        TargetLanguage::C | TargetLanguage::Cpp | TargetLanguage::Cuda | TargetLanguage::CuTile => {
+            let suffix = match (int_type.signed(), int_type.bits()) {
+                (true, 64) => "ll",
+                (false, 64) => "ull",
+                (false, _) => "u",
+                (true, _) => "",
+            };
+            // The most negative value has no literal of its own type
+            if int_type.signed() && int_type.bits() >= 32 && value == int_type.range().0 {
+                format!("({}{} - 1)", value + 1, suffix)
+            } else {
+                format!("{}{}", value, suffix)
+            }
+        }
+        TargetLanguage::Java => match int_type {
+            IntType::U64 if value > i64::MAX as i128 => format!("0x{:X}L", value as u64),
+            IntType::U32 | IntType::U64 | IntType::I64 => format!("{}L", value),
+            _ => value.to_string(),
+        },
+        TargetLanguage::Moonbit => match int_type {
+            IntType::U32 => format!("{}U", value),
+            IntType::U64 => format!("{}UL", value),
+            IntType::I64 => format!("{}L", value),
+            _ => value.to_string(),
+        },
+        // Numbers beyond 2^53 lose precision, so they become BigInts
+        TargetLanguage::JavaScript | TargetLanguage::TypeScript if value.unsigned_abs() > (1u128 << 53) - 1 => format!("{}n", value),
+        _ => value.to_string(),
+    }
+}
+
+/// Boundary values of an integer type: its minimum, -1, 0, 1 and maximum
+fn int_boundaries(int_type: IntType) -> Vec<BoundaryValue> {
+    let (min, max) = int_type.range();
+    let mut values: Vec<i128> = vec![min, -1, 0, 1, max];
+    values.retain(|value| (min..=max).contains(value));
+    values.dedup();
+    values.into_iter().map(|value| BoundaryValue::Int(value, int_type)).collect()
+}
+
+/// Derive boundary values for a data or property type name
+pub fn boundary_values_for_type(type_name: &str) -> Vec<BoundaryValue> {
+    let normalized = type_name.trim().to_lowercase();
+    match normalized.as_str() {
+        "u8" => int_boundaries(IntType::U8),
+        "u16" => int_boundaries(IntType::U16),
+        "u32" | "uint" | "unsigned" => int_boundaries(IntType::U32),
+        "u64" | "usize" | "size" => int_boundaries(IntType::U64),
+        "i8" => int_boundaries(IntType::I8),
+        "i16" => int_boundaries(IntType::I16),
+        "i32" | "int" | "integer" | "number" => int_boundaries(IntType::I32),
+        "i64" | "isize" | "long" => int_boundaries(IntType::I64),
+        "f32" | "f64" | "float" | "double" | "float32" | "float64" => vec![
+            BoundaryValue::Float(0.0), BoundaryValue::Float(-1.0), BoundaryValue::Float(1.0e-9), BoundaryValue::Float(1.0e9),
+        ],
+        "bool" | "boolean" => vec![BoundaryValue::Bool(false), BoundaryValue::Bool(true)],
+        _ => vec![
+            BoundaryValue::Str(String::new()),
+            BoundaryValue::Str("x".to_string()),
+            BoundaryValue::Str("x".repeat(4096)),
+        ],
+    }
+}
+
+/// Derive boundary values for a property from its schema
+pub fn boundary_values_for_property(property: &ComponentProperty) -> Vec<BoundaryValue> {
+    // Enumerated properties: every valid value is a boundary
+    if let Some(valid_values) = &property.valid_values {
+        if !valid_values.is_empty() {
+            return valid_values.iter().map(|v| BoundaryValue::Str(v.clone())).collect();
+        }
+    }
+    
+    let mut values = boundary_values_for_type(&property.property_type);
+    
+    // Untyped properties still get their current and default values when they parse as
+    // numbers; they stay strings so the array keeps the property's type
+    if property.property_type == "string" {
+        for candidate in [Some(&property.value), property.default_value.as_ref()].into_iter().flatten() {
+            if let Ok(number) = candidate.parse::<i128>() {
+                for number in [0, number, number.saturating_mul(2)] {
+                    let value = BoundaryValue::Str(number.to_string());
+                    if !values.contains(&value) {
+                        values.push(value);
+                    }
+                }
+            }
+        }
+    }
+    
+    values
+}
+
+/// Test skeleton generator for compiled components
+pub struct TestGenerator {
+    /// Target language
+    language: TargetLanguage,
+}
+
+impl TestGenerator {
+    /// Create a new test generator
+    pub fn new(language: TargetLanguage) -> Self {
+        Self { language }
+    }
+    
+    /// Name of the native test framework for the target language
+    pub fn framework(&self) -> Option<&'static str> {
+        match self.language {
+            TargetLanguage::Rust => Some("cargo test"),
+            TargetLanguage::Python | TargetLanguage::Triton | TargetLanguage::TVM | TargetLanguage::Helion => Some("pytest"),
+            TargetLanguage::JavaScript | TargetLanguage::TypeScript => Some("jest"),
+            TargetLanguage::C | TargetLanguage::Cpp | TargetLanguage::Cuda | TargetLanguage::CuTile => Some("googletest"),
+            TargetLanguage::Java => Some("junit5"),
+            TargetLanguage::CSharp => Some("xunit"),
+            TargetLanguage::Zig => Some("zig test"),
+            TargetLanguage::Moonbit => Some("moon test"),
+            TargetLanguage::Mojo => Some("mojo test"),
+            TargetLanguage::C3 => Some("c3c test"),
+            TargetLanguage::Custom(_) => None,
+        }
+    }
+    
+    /// Generate test skeletons for a list of components
+    pub fn generate(&self, components: &[Component]) -> Result<Vec<GeneratedTestFile>, String> {
+        components.iter().map(|component| self.generate_for_component(component)).collect()
+    }
+    
+    /// Generate a test skeleton file for a single component
+    pub fn generate_for_component(&self, component: &Component) -> Result<GeneratedTestFile, String> {
+        let framework = self.framework()
+            .ok_or_else(|| format!("No native test framework known for {:?}", self.language))?;
+        let name = sanitize_identifier(&component.name);
+        
+        // (test name, description, literals)
+        let mut cases: Vec<(String, String, Vec<String>)> = Vec::new();
+        for port in &component.ports {
+            cases.push((
+                format!("port_{}", sanitize_identifier(&port.name).to_lowercase()),
+                describe_port(port),
+                self.render_all(&boundary_values_for_type(&port.port_type)),
+            ));
+        }
+        for property in &component.properties {
+            cases.push((
+                format!("property_{}", sanitize_identifier(&property.name).to_lowercase()),
+                format!("property '{}' ({})", property.name, property.property_type),
+                self.render_all(&boundary_values_for_property(property)),
+            ));
+        }
+        
+        let (file_name, content) = match self.language {
+            TargetLanguage::Rust => (format!("{}_test.rs", name), self.render_rust(&name, &cases)),
+            TargetLanguage::Python | TargetLanguage::Triton | TargetLanguage::TVM | TargetLanguage::Helion =>
+                (format!("test_{}.py", name.to_lowercase()), self.render_pytest(&name, &cases)),
+            TargetLanguage::JavaScript => (format!("{}.test.js", name), self.render_jest(&name, &cases)),
+            TargetLanguage::TypeScript => (format!("{}.test.ts", name), self.render_jest(&name, &cases)),
+            TargetLanguage::C | TargetLanguage::Cpp | TargetLanguage::Cuda | TargetLanguage::CuTile =>
+                (format!("{}_test.cpp", name), self.render_gtest(&name, &cases)),
+            TargetLanguage::Java => (format!("{}Test.java", name), self.render_junit(&name, &cases)),
+            TargetLanguage::CSharp => (format!("{}Tests.cs", name), self.render_xunit(&name, &cases)),
+            TargetLanguage::Zig => (format!("{}_test.zig", name), self.render_block_tests(&name, &cases, "const", ";")),
+            TargetLanguage::Moonbit => (format!("{}_test.mbt", name), self.render_block_tests(&name, &cases, "let", "")),
+            TargetLanguage::Mojo => (format!("test_{}.mojo", name.to_lowercase()), self.render_pytest(&name, &cases)),
+            TargetLanguage::C3 => (format!("{}_test.c3", name), self.render_c3(&name, &cases)),
+            TargetLanguage::Custom(_) => unreachable!("custom languages have no framework"),
+        };
+        
+        Ok(GeneratedTestFile {
+            component_id: component.id.clone(),
+            file_name,
+            framework: framework.to_string(),
+            content,
+        })
+    }
+    
+    /// Render boundary values as literals
+    fn render_all(&self, values: &[BoundaryValue]) -> Vec<String> {
+        values.iter().map(|v| v.render(&self.language)).collect()
+    }
+    
+    fn render_rust(&self, name: &str, cases: &[(String, String, Vec<String>)]) -> String {
+        let mut code = header("//", name);
+        code.push_str("#[cfg(test)]\nmod tests {\n    use super::*;\n");
+        for (test_name, description, values) in cases {
+            code.push_str(&format!("\n    /// Boundary values for {}\n", description));
+            code.push_str(&format!("    #[test]\n    fn test_{}() {{\n", test_name));
+            code.push_str(&format!("        let mut component = {}::new();\n", name));
+            code.push_str(&format!("        for value in [{}] {{\n", values.join(", ")));
+            code.push_str("            // TODO: drive the component with `value` and assert the expected behavior\n");
+            code.push_str("            let _ = (&mut component, value);\n");
+            code.push_str("        }\n    }\n");
+        }
+        code.push_str("}\n");
+        code
+    }
+    
+    fn render_pytest(&self, name: &str, cases: &[(String, String, Vec<String>)]) -> String {
+        let mut code = header("#", name);
+        code.push_str("import pytest\n\n");
+        for (test_name, description, values) in cases {
+            code.push_str(&format!("\n@pytest.mark.parametrize(\"value\", [{}])\n", values.join(", ")));
+            code.push_str(&format!("def test_{}(value):\n", test_name));
+            code.push_str(&format!("    \"\"\"Boundary values for {}\"\"\"\n", description));
+            code.push_str("    # TODO: drive the component with `value` and assert the expected behavior\n");
+            code.push_str("    assert value is not None\n");
+        }
+        code
+    }
+    
+    fn render_jest(&self, name: &str, cases: &[(String, String, Vec<String>)]) -> String {
+        let mut code = header("//", name);
+        code.push_str(&format!("describe('{}', () => {{\n", name));
+        for (test_name, description, values) in cases {
+            code.push_str(&format!("  // Boundary values for {}\n", description));
+            code.push_str(&format!("  test.each([{}])('{} %p', (value) => {{\n", values.join(", "), test_name));
+            code.push_str("    // TODO: drive the component with `value` and assert the expected behavior\n");
+            code.push_str("    expect(value).toBeDefined();\n");
+            code.push_str("  });\n");
+        }
+        code.push_str("});\n");
+        code
+    }
+    
+    fn render_gtest(&self, name: &str, cases: &[(String, String, Vec<String>)]) -> String {
+        let mut code = header("//", name);
+        code.push_str("#include <gtest/gtest.h>\n\n");
+        for (test_name, description, values) in cases {
+            code.push_str(&format!("// Boundary values for {}\n", description));
+            code.push_str(&format!("TEST({}Test, {}) {{\n", name, test_name));
+            code.push_str(&format!("    for (auto value : {{{}}}) {{\n", values.join(", ")));
+            code.push_str("        // TODO: drive the component with `value` and assert the expected behavior\n");
+            code.push_str("        (void)value;\n");
+            code.push_str("    }\n}\n\n");
+        }
+        code
+    }
+    
+    fn render_junit(&self, name: &str, cases: &[(String, String, Vec<String>)]) -> String {
+        let mut code = header("//", name);
+        code.push_str("import org.junit.jupiter.api.Test;\n\n");
+        code.push_str(&format!("class {}Test {{\n", name));
+        for (test_name, description, values) in cases {
+            code.push_str(&format!("    // Boundary values for {}\n", description));
+            code.push_str(&format!("    @Test\n    void {}() {{\n", test_name));
+            code.push_str(&format!("        Object[] values = {{{}}};\n", values.join(", ")));
+            code.push_str("        for (Object value : values) {\n");
+            code.push_str("            // TODO: drive the component with `value` and assert the expected behavior\n");
+            code.push_str("        }\n    }\n\n");
+        }
+        code.push_str("}\n");
+        code
+    }
+    
+    fn render_xunit(&self, name: &str, cases: &[(String, String, Vec<String>)]) -> String {
+        let mut code = header("//", name);
+        code.push_str("using Xunit;\n\n");
+        code.push_str(&format!("public class {}Tests\n{{\n", name));
+        for (test_name, description, values) in cases {
+            code.push_str(&format!("    // Boundary values for {}\n", description));
+            code.push_str("    [Fact]\n");
+            code.push_str(&format!("    public void {}()\n    {{\n", test_name));
+            code.push_str(&format!("        object[] values = {{ {} }};\n", values.join(", ")));
+            code.push_str("        foreach (var value in values)\n        {\n");
+            code.push_str("            // TODO: drive the component with `value` and assert the expected behavior\n");
+            code.push_str("        }\n    }\n\n");
+        }
+        code.push_str("}\n");
+        code
+    }
+    
+    /// Render `test "name" { ... }` blocks (Zig, MoonBit)
+    fn render_block_tests(&self, name: &str, cases: &[(String, String, Vec<String>)], binding: &str, terminator: &str) -> String {
+        let mut code = header("//", name);
+        for (test_name, description, values) in cases {
+            code.push_str(&format!("// Boundary values for {}\n", description));
+            code.push_str(&format!("test \"{} {}\" {{\n", name, test_name));
+            for (index, value) in values.iter().enumerate() {
+                code.push_str(&format!("    {} value_{} = {}{}\n", binding, index, value, terminator));
+            }
+            code.push_str("    // TODO: drive the component with each value and assert the expected behavior\n");
+            code.push_str("}\n\n");
+        }
+        code
+    }
+    
+    fn render_c3(&self, name: &str, cases: &[(String, String, Vec<String>)]) -> String {
+        let mut code = header("//", name);
+        code.push_str(&format!("module {}_test;\n\n", name.to_lowercase()));
+        for (test_name, description, values) in cases {
+            code.push_str(&format!("// Boundary values for {}\n", description));
+            code.push_str(&format!("fn void test_{}() @test\n{{\n", test_name));
+            code.push_str(&format!("    // Values: {}\n", values.join(", ")));
+            code.push_str("    // TODO: drive the component with each value and assert the expected behavior\n");
+            code.push_str("}\n\n");
+        }
+        code
+    }
+}
+
+/// Describe a port for generated comments
+fn describe_port(port: &ComponentPort) -> String {
+    let direction = match port.direction {
+        PortDirection::Input => "input",
+        PortDirection::Output => "output",
+        PortDirection::Bidirectional => "bidirectional",
+    };
+    format!("{} port '{}' ({})", direction, port.name, port.port_type)
+}
+
+/// Standard generated-file header
+fn header(comment: &str, name: &str) -> String {
+    format!(
+        "{c} Auto-generated test skeleton for component: {}\n{c} Copyright (c) 2025 OSland Project Team\n{c} SPDX-License-Identifier: MulanPSL-2.0\n\n",
+        name,
+        c = comment
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn render(values: &[BoundaryValue], language: TargetLanguage) -> Vec<String> {
+        values.iter().map(|value| value.render(&language)).collect()
+    }
+    
+    #[test]
+    fn test_boundary_literals_share_one_type() {
+        assert_eq!(render(&boundary_values_for_type("u32"), TargetLanguage::Rust), ["0_u32", "1_u32", "4294967295_u32"]);
+        assert_eq!(render(&boundary_values_for_type("u64"), TargetLanguage::Rust)[2], "18446744073709551615_u64");
+        assert_eq!(render(&boundary_values_for_type("i32"), TargetLanguage::Cpp), ["(-2147483647 - 1)", "-1", "0", "1", "2147483647"]);
+        assert!(render(&boundary_values_for_type("u64"), TargetLanguage::C).iter().all(|literal| literal.ends_with("ull")));
+        assert_eq!(render(&boundary_values_for_type("u64"), TargetLanguage::Java)[2], "0xFFFFFFFFFFFFFFFFL");
+        assert_eq!(render(&boundary_values_for_type("i64"), TargetLanguage::JavaScript)[0], "-9223372036854775808n");
+        
+        // Numeric values of string properties stay strings
+        let property = ComponentProperty {
+            name: "queue_depth".to_string(),
+            value: "16".to_string(),
+            property_type: "string".to_string(),
+            description: String::new(),
+            required: false,
+            default_value: None,
+            valid_values: None,
+        };
+        let values = boundary_values_for_property(&property);
+        assert!(values.iter().all(|value| matches!(value, BoundaryValue::Str(_))));
+        assert!(values.contains(&BoundaryValue::Str("32".to_string())));
+    }
+}
@@ -0,0 +1,457 @@
+// Tile Tracer Module for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+use crate::core::profiling::{Profile, ProfileSpan};
+use crate::tile_engine::tile_core::{TileGraph, TileConnection};
+use serde::{Serialize, Deserialize};
+use serde_json::{json, Value};
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::path::Path;
+use std::time::Instant;
+
+/// Default simulated tile latency in microseconds
+const DEFAULT_TILE_LATENCY_US: u64 = 100;
+
+/// Default simulated transfer latency in microseconds
+const DEFAULT_TRANSFER_LATENCY_US: u64 = 10;
+
+/// Tile execution span
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TileSpan {
+    /// Tile ID
+    pub tile_id: String,
+    
+    /// Tile name
+    pub tile_name: String,
+    
+    /// Start timestamp in microseconds
+    pub start_us: u64,
+    
+    /// Duration in microseconds
+    pub duration_us: u64,
+    
+    /// Execution lane (rendered as a thread in the trace viewer)
+    pub lane: u32,
+}
+
+/// Data transfer over a connection
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataTransfer {
+    /// Connection ID
+    pub connection_id: String,
+    
+    /// Source tile ID
+    pub source_tile_id: String,
+    
+    /// Destination tile ID
+    pub dest_tile_id: String,
+    
+    /// Timestamp the transfer left the source, in microseconds
+    pub start_us: u64,
+    
+    /// Timestamp the transfer arrived at the destination, in microseconds
+    pub end_us: u64,
+    
+    /// Number of bytes transferred (if known)
+    pub bytes: Option<u64>,
+}
+
+/// Tile Execution Tracer
+///
+/// Records tile spans and connection transfers, and exports them in the Chrome
+/// `trace_event` format for Perfetto or chrome://tracing.
+pub struct TileTracer {
+    /// Traced graph name
+    graph_name: String,
+    
+    /// Recording start time (for live recording)
+    epoch: Instant,
+    
+    /// Open spans keyed by tile ID: (tile name, start timestamp, lane)
+    open_spans: HashMap<String, (String, u64, u32)>,
+    
+    /// Completed spans
+    spans: Vec<TileSpan>,
+    
+    /// Recorded transfers
+    transfers: Vec<DataTransfer>,
+}
+
+impl TileTracer {
+    /// Create a new tracer
+    pub fn new(graph_name: String) -> Self {
+        Self {
+            graph_name,
+            epoch: Instant::now(),
+            open_spans: HashMap::new(),
+            spans: Vec::new(),
+            transfers: Vec::new(),
+        }
+    }
+    
+    /// Microseconds elapsed since the tracer was created
+    fn now_us(&self) -> u64 {
+        self.epoch.elapsed().as_micros() as u64
+    }
+    
+    /// Mark the start of a tile's execution (live recording)
+    pub fn begin_tile(&mut self, tile_id: &str, tile_name: &str, lane: u32) {
+        let now = self.now_us();
+        self.open_spans.insert(tile_id.to_string(), (tile_name.to_string(), now, lane));
+    }
+    
+    /// Mark the end of a tile's execution (live recording)
+    pub fn end_tile(&mut self, tile_id: &str) -> Result<(), String> {
+        let now = self.now_us();
+        let (tile_name, start_us, lane) = self.open_spans.remove(tile_id)
+            .ok_or_else(|| format!("No open span for tile '{}'", tile_id))?;
+        
+        self.spans.push(TileSpan {
+            tile_id: tile_id.to_string(),
+            tile_name,
+            start_us,
+            duration_us: now.saturating_sub(start_us),
+            lane,
+        });
+        Ok(())
+    }
+    
+    /// Record a completed span with explicit timestamps
+    pub fn record_span(&mut self, span: TileSpan) {
+        self.spans.push(span);
+    }
+    
+    /// Record a data transfer over a connection
+    pub fn record_transfer(&mut self, transfer: DataTransfer) {
+        self.transfers.push(transfer);
+    }
+    
+    /// Get the recorded spans
+    pub fn spans(&self) -> &[TileSpan] {
+        &self.spans
+    }
+    
+    /// Get the recorded transfers
+    pub fn transfers(&self) -> &[DataTransfer] {
+        &self.transfers
+    }
+    
+    /// Simulate the execution of a tile graph and record the resulting trace
+    ///
+    /// Tiles run in dependency order as soon as all their inputs have arrived.
+    /// Durations come from the `latency_us` tile property, every transfer takes a
+    /// fixed latency, and tiles that overlap in time are placed on separate lanes.
+    pub fn simulate(graph: &TileGraph) -> Result<Self, String> {
+        let mut tracer = Self::new(graph.name.clone());
+        
+        // Kahn's algorithm over all connections
+        let mut in_degree: HashMap<&str, usize> = graph.tiles.keys().map(|id| (id.as_str(), 0)).collect();
+        let mut outgoing: HashMap<&str, Vec<&TileConnection>> = HashMap::new();
+        for conn in &graph.connections {
+            *in_degree.entry(conn.dest_tile_id.as_str()).or_insert(0) += 1;
+            outgoing.entry(conn.source_tile_id.as_str()).or_default().push(conn);
+        }
+        
+        let mut ready: Vec<&str> = in_degree.iter()
+            .filter(|(_, degree)| **degree == 0)
+            .map(|(id, _)| *id)
+            .collect();
+        ready.sort();
+        let mut queue: VecDeque<&str> = ready.into_iter().collect();
+        
+        let mut earliest_start: HashMap<&str, u64> = HashMap::new();
+        let mut lane_free_at: Vec<u64> = Vec::new();
+        let mut processed = 0;
+        
+        while let Some(tile_id) = queue.pop_front() {
+            let tile = graph.get_tile(tile_id)
+                .ok_or_else(|| format!("Connection references unknown tile '{}'", tile_id))?;
+            processed += 1;
+            
+            let start_us = earliest_start.get(tile_id).copied().unwrap_or(0);
+            let duration_us = tile.get_property("latency_us")
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(DEFAULT_TILE_LATENCY_US);
+            
+            // Pick the first lane that is idle by the start time
+            let lane = match lane_free_at.iter().position(|free_at| *free_at <= start_us) {
+                Some(lane) => lane,
+                None => {
+                    lane_free_at.push(0);
+                    lane_free_at.len() - 1
+                }
+            };
+            lane_free_at[lane] = start_us + duration_us;
+            
+            tracer.record_span(TileSpan {
+                tile_id: tile.id.clone(),
+                tile_name: tile.name.clone(),
+                start_us,
+                duration_us,
+                lane: lane as u32,
+            });
+            
+            let end_us = start_us + duration_us;
+            let mut next_ready = Vec::new();
+            for conn in outgoing.get(tile_id).cloned().unwrap_or_default() {
+                let arrival = end_us + DEFAULT_TRANSFER_LATENCY_US;
+                tracer.record_transfer(DataTransfer {
+                    connection_id: conn.id.clone(),
+                    source_tile_id: conn.source_tile_id.clone(),
+                    dest_tile_id: conn.dest_tile_id.clone(),
+                    start_us: end_us,
+                    end_us: arrival,
+                    bytes: tile.get_property("output_bytes").and_then(|v| v.parse::<u64>().ok()),
+                });
+                
+                let dest = conn.dest_tile_id.as_str();
+                let entry = earliest_start.entry(dest).or_insert(0);
+                *entry = (*entry).max(arrival);
+                
+                if let Some(degree) = in_degree.get_mut(dest) {
+                    *degree -= 1;
+                    if *degree == 0 {
+                        next_ready.push(dest);
+                    }
+                }
+            }
+            next_ready.sort();
+            queue.extend(next_ready);
+        }
+        
+        if processed < graph.tiles.len() {
+            return Err("Tile graph contains a cycle; execution cannot be simulated".to_string());
+        }
+        
+        Ok(tracer)
+    }
+    
+    /// Build the Chrome `trace_event` JSON document
+    pub fn to_chrome_trace(&self) -> Value {
+        let pid = 1;
+        let mut events = vec![json!({
+            "name": "process_name",
+            "ph": "M",
+            "pid": pid,
+            "args": { "name": format!("Tile graph: {}", self.graph_name) },
+        })];
+        
+        let mut lanes: Vec<u32> = self.spans.iter().map(|span| span.lane).collect();
+        lanes.sort();
+        lanes.dedup();
+        for lane in &lanes {
+            events.push(json!({
+                "name": "thread_name",
+                "ph": "M",
+                "pid": pid,
+                "tid": lane,
+                "args": { "name": format!("Lane {}", lane) },
+            }));
+        }
+        
+        let mut lane_of_tile: HashMap<&str, u32> = HashMap::new();
+        for span in &self.spans {
+            lane_of_tile.insert(span.tile_id.as_str(), span.lane);
+            events.push(json!({
+                "name": span.tile_name,
+                "cat": "tile",
+                "ph": "X",
+                "ts": span.start_us,
+                "dur": span.duration_us,
+                "pid": pid,
+                "tid": span.lane,
+                "args": { "tile_id": span.tile_id },
+            }));
+        }
+        
+        // Transfers are rendered as flow arrows between the two tile spans
+        for (index, transfer) in self.transfers.iter().enumerate() {
+            let source_lane = lane_of_tile.get(transfer.source_tile_id.as_str()).copied().unwrap_or(0);
+            let dest_lane = lane_of_tile.get(transfer.dest_tile_id.as_str()).copied().unwrap_or(0);
+            let mut args = json!({ "connection_id": transfer.connection_id });
+            if let Some(bytes) = transfer.bytes {
+                args["bytes"] = json!(bytes);
+            }
+            
+            events.push(json!({
+                "name": "transfer",
+                "cat": "connection",
+                "ph": "s",
+                "id": index,
+                "ts": transfer.start_us,
+                "pid": pid,
+                "tid": source_lane,
+                "args": args,
+            }));
+            events.push(json!({
+                "name": "transfer",
+                "cat": "connection",
+                "ph": "f",
+                "bp": "e",
+                "id": index,
+                "ts": transfer.end_us,
+                "pid": pid,
+                "tid": dest_lane,
+            }));
+        }
+        
+        json!({
+            "traceEvents": events,
+            "displayTimeUnit": "ms",
+        })
+    }
+    
+    /// Build a profile with one span per lane and the tile spans under it
+    ///
+    /// Time a lane spends waiting for inputs shows up as the lane's own time.
+    pub fn to_profile(&self) -> Profile {
+        let mut profile = Profile::new(self.graph_name.clone());
+        let mut lanes: Vec<u32> = self.spans.iter().map(|span| span.lane).collect();
+        lanes.sort();
+        lanes.dedup();
+        
+        for lane in lanes {
+            let mut spans: Vec<&TileSpan> = self.spans.iter().filter(|span| span.lane == lane).collect();
+            spans.sort_by_key(|span| span.start_us);
+            let start_us = spans.first().map(|span| span.start_us).unwrap_or(0);
+            let end_us = spans.iter().map(|span| span.start_us + span.duration_us).max().unwrap_or(start_us);
+            
+            let mut lane_span = ProfileSpan::new(format!("Lane {}", lane), start_us, end_us - start_us);
+            lane_span.children = spans.iter()
+                .map(|span| ProfileSpan::new(span.tile_name.clone(), span.start_us, span.duration_us))
+                .collect();
+            profile.roots.push(lane_span);
+        }
+        profile
+    }
+    
+    /// Write the trace to a JSON file
+    pub fn save_chrome_trace<P: AsRef<Path>>(&self, path: P) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(&self.to_chrome_trace()).map_err(|e| format!("Failed to serialize trace: {}", e))?;
+        fs::write(path, json).map_err(|e| format!("Failed to write trace file: {}", e))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tile_engine::tile_core::{ConnectionType, Tile, TileType};
+    
+    fn graph(tiles: &[(&str, u64)], connections: &[(&str, &str)]) -> TileGraph {
+        let mut graph = TileGraph::new("pipeline".to_string());
+        for (id, latency_us) in tiles {
+            let mut tile = Tile::new(id.to_string(), TileType::Processing, String::new());
+            tile.id = id.to_string();
+            tile.set_property("latency_us".to_string(), latency_us.to_string());
+            graph.add_tile(tile).unwrap();
+        }
+        for (index, (source, dest)) in connections.iter().enumerate() {
+            graph.connections.push(TileConnection {
+                id: format!("conn-{}", index),
+                source_tile_id: source.to_string(),
+                source_port_id: "out".to_string(),
+                dest_tile_id: dest.to_string(),
+                dest_port_id: "in".to_string(),
+                connection_type: ConnectionType::DataFlow,
+            });
+        }
+        graph
+    }
+    
+    /// A source feeding a fast and a slow tile, both feeding a sink
+    fn diamond() -> TileGraph {
+        let mut graph = graph(
+            &[("src", 50), ("fast", 100), ("slow", 200), ("sink", 30)],
+            &[("src", "fast"), ("src", "slow"), ("fast", "sink"), ("slow", "sink")],
+        );
+        graph.tiles.get_mut("src").unwrap().set_property("output_bytes".to_string(), "4096".to_string());
+        graph
+    }
+    
+    fn span<'a>(tracer: &'a TileTracer, tile_id: &str) -> &'a TileSpan {
+        tracer.spans().iter().find(|span| span.tile_id == tile_id).unwrap()
+    }
+    
+    #[test]
+    fn test_simulated_timing_and_lanes() {
+        let tracer = TileTracer::simulate(&diamond()).unwrap();
+        
+        // Tiles start once their last input has arrived
+        let timings: Vec<(u64, u64, u32)> = ["src", "fast", "slow", "sink"].iter()
+            .map(|id| span(&tracer, id))
+            .map(|span| (span.start_us, span.duration_us, span.lane))
+            .collect();
+        assert_eq!(timings, vec![(0, 50, 0), (60, 100, 0), (60, 200, 1), (270, 30, 0)]);
+        
+        let transfers: Vec<(&str, u64, u64, Option<u64>)> = tracer.transfers().iter()
+            .map(|t| (t.connection_id.as_str(), t.start_us, t.end_us, t.bytes))
+            .collect();
+        assert_eq!(transfers, vec![
+            ("conn-0", 50, 60, Some(4096)),
+            ("conn-1", 50, 60, Some(4096)),
+            ("conn-2", 160, 170, None),
+            ("conn-3", 260, 270, None),
+        ]);
+        
+        // Independent tiles without a latency take the default one and overlap on separate lanes
+        let mut unset = graph(&[("a", 0), ("b", 0)], &[]);
+        for tile in unset.tiles.values_mut() {
+            tile.properties.clear();
+        }
+        let unset = TileTracer::simulate(&unset).unwrap();
+        assert!(unset.spans().iter().all(|span| span.start_us == 0 && span.duration_us == DEFAULT_TILE_LATENCY_US));
+        let mut lanes: Vec<u32> = unset.spans().iter().map(|span| span.lane).collect();
+        lanes.sort();
+        assert_eq!(lanes, vec![0, 1]);
+    }
+    
+    #[test]
+    fn test_cyclic_graph_is_rejected() {
+        let cyclic = graph(&[("a", 10), ("b", 10), ("c", 10)], &[("a", "b"), ("b", "a"), ("c", "a")]);
+        let error = TileTracer::simulate(&cyclic).err().unwrap();
+        assert!(error.contains("cycle"), "{}", error);
+    }
+    
+    #[test]
+    fn test_chrome_trace_events() {
+        let tracer = TileTracer::simulate(&diamond()).unwrap();
+        let trace = tracer.to_chrome_trace();
+        assert_eq!(trace["displayTimeUnit"], "ms");
+        let events = trace["traceEvents"].as_array().unwrap();
+        
+        // Process and lane names, one complete event per tile, a flow start and end per transfer
+        let phases: Vec<&str> = events.iter().map(|event| event["ph"].as_str().unwrap()).collect();
+        assert_eq!(phases.iter().filter(|ph| **ph == "M").count(), 3);
+        assert_eq!(phases.iter().filter(|ph| **ph == "X").count(), 4);
+        assert_eq!(phases.iter().filter(|ph| **ph == "s").count(), 4);
+        assert_eq!(phases.iter().filter(|ph| **ph == "f").count(), 4);
+        assert_eq!(events[0]["args"]["name"], "Tile graph: pipeline");
+        assert_eq!(events[2], json!({"name": "thread_name", "ph": "M", "pid": 1, "tid": 1, "args": {"name": "Lane 1"}}));
+        
+        let slow = events.iter().find(|event| event["ph"] == "X" && event["args"]["tile_id"] == "slow").unwrap();
+        assert_eq!(slow, &json!({
+            "name": "slow", "cat": "tile", "ph": "X", "ts": 60, "dur": 200, "pid": 1, "tid": 1,
+            "args": {"tile_id": "slow"},
+        }));
+        
+        // The flow to the slow tile leaves lane 0 and ends on lane 1
+        let flow: Vec<&Value> = events.iter().filter(|event| event["cat"] == "connection" && event["id"] == 1).collect();
+        assert_eq!(flow[0], &json!({
+            "name": "transfer", "cat": "connection", "ph": "s", "id": 1, "ts": 50, "pid": 1, "tid": 0,
+            "args": {"connection_id": "conn-1", "bytes": 4096},
+        }));
+        assert_eq!(flow[1], &json!({
+            "name": "transfer", "cat": "connection", "ph": "f", "bp": "e", "id": 1, "ts": 60, "pid": 1, "tid": 1,
+        }));
+        let unsized_flow = events.iter().find(|event| event["ph"] == "s" && event["id"] == 2).unwrap();
+        assert!(unsized_flow["args"].get("bytes").is_none());
+        
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("trace.json");
+        tracer.save_chrome_trace(&path).unwrap();
+        assert_eq!(serde_json::from_str::<Value>(&fs::read_to_string(&path).unwrap()).unwrap(), trace);
+    }
+}
@@ -0,0 +1,376 @@
+// Tile Validator Module for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+use crate::tile_engine::tile_core::TileGraph;
+use crate::tile_engine::tile_compiler::{TargetLanguage, sanitize_identifier};
+use regex::Regex;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Validation Status
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidationStatus {
+    /// The toolchain accepted the generated code
+    Passed,
+    
+    /// The toolchain reported errors
+    Failed,
+    
+    /// Validation was not performed (toolchain missing or language unsupported)
+    Skipped(String),
+}
+
+/// Compiler diagnostic mapped back to a tile
+#[derive(Debug, Clone)]
+pub struct CompileDiagnostic {
+    /// Line in the generated source (1-based)
+    pub line: Option<usize>,
+    
+    /// Column in the generated source (1-based)
+    pub column: Option<usize>,
+    
+    /// Diagnostic message
+    pub message: String,
+    
+    /// ID of the tile the line was generated from
+    pub tile_id: Option<String>,
+    
+    /// Name of the tile the line was generated from
+    pub tile_name: Option<String>,
+}
+
+/// Validation Report
+#[derive(Debug, Clone)]
+pub struct ValidationReport {
+    /// Target language
+    pub language: TargetLanguage,
+    
+    /// Tool invocation used for validation
+    pub command: String,
+    
+    /// Validation status
+    pub status: ValidationStatus,
+    
+    /// Diagnostics reported by the toolchain
+    pub diagnostics: Vec<CompileDiagnostic>,
+    
+    /// Raw toolchain output
+    pub output: String,
+}
+
+/// Generated Code Validator
+///
+/// Writes generated sources to a scratch directory and runs the target
+/// toolchain in check-only mode (`cargo check`, `tsc --noEmit`,
+/// `python -m py_compile`, `zig ast-check`, ...).
+pub struct GeneratedCodeValidator {
+    /// Target language
+    language: TargetLanguage,
+    
+    /// Program replacing the language's default toolchain program
+    program: Option<String>,
+}
+
+impl GeneratedCodeValidator {
+    /// Create a new validator
+    pub fn new(language: TargetLanguage) -> Self {
+        Self { language, program: None }
+    }
+    
+    /// Run another program with the same arguments (e.g. `clang` instead of `cc`)
+    pub fn with_program(mut self, program: impl Into<String>) -> Self {
+        self.program = Some(program.into());
+        self
+    }
+    
+    /// Source file name for the target language
+    fn source_file_name(&self) -> Option<&'static str> {
+        match self.language {
+            TargetLanguage::Rust => Some("src/lib.rs"),
+            TargetLanguage::C => Some("tile_graph.c"),
+            TargetLanguage::Cpp => Some("tile_graph.cpp"),
+            TargetLanguage::Cuda | TargetLanguage::CuTile => Some("tile_graph.cu"),
+            TargetLanguage::Python | TargetLanguage::Triton | TargetLanguage::TVM | TargetLanguage::Helion => Some("tile_graph.py"),
+            TargetLanguage::JavaScript => Some("tile_graph.js"),
+            TargetLanguage::TypeScript => Some("tile_graph.ts"),
+            TargetLanguage::Java => Some("TileGraph.java"),
+            TargetLanguage::Zig => Some("tile_graph.zig"),
+            _ => None,
+        }
+    }
+    
+    /// Check-only command for the target language: (program, args)
+    fn check_command(&self, source: &Path, work_dir: &Path) -> Option<(String, Vec<String>)> {
+        let (program, args) = self.default_check_command(source, work_dir)?;
+        Some((self.program.clone().unwrap_or(program), args))
+    }
+    
+    /// Check-only command of the language's usual toolchain
+    fn default_check_command(&self, source: &Path, work_dir: &Path) -> Option<(String, Vec<String>)> {
+        let source = source.to_string_lossy().to_string();
+        let args = |items: &[&str]| items.iter().map(|s| s.to_string()).collect::<Vec<String>>();
+        
+        match self.language {
+            TargetLanguage::Rust => Some(("cargo".to_string(), vec![
+                "check".to_string(),
+                "--quiet".to_string(),
+                "--message-format=short".to_string(),
+                "--manifest-path".to_string(),
+                work_dir.join("Cargo.toml").to_string_lossy().to_string(),
+            ])),
+            TargetLanguage::C => Some(("cc".to_string(), [args(&["-fsyntax-only", "-x", "c"]), vec![source]].concat())),
+            TargetLanguage::Cpp => Some(("c++".to_string(), [args(&["-fsyntax-only", "-x", "c++"]), vec![source]].concat())),
+            TargetLanguage::Cuda | TargetLanguage::CuTile => Some(("nvcc".to_string(), [args(&["-x", "cu", "-c", "-o", "/dev/null"]), vec![source]].concat())),
+            TargetLanguage::Python | TargetLanguage::Triton | TargetLanguage::TVM | TargetLanguage::Helion =>
+                Some(("python3".to_string(), [args(&["-m", "py_compile"]), vec![source]].concat())),
+            TargetLanguage::JavaScript => Some(("node".to_string(), [args(&["--check"]), vec![source]].concat())),
+            TargetLanguage::TypeScript => Some(("tsc".to_string(), [args(&["--noEmit", "--pretty", "false"]), vec![source]].concat())),
+            TargetLanguage::Java => Some(("javac".to_string(), vec![
+                "-d".to_string(),
+                work_dir.join("classes").to_string_lossy().to_string(),
+                source,
+            ])),
+            TargetLanguage::Zig => Some(("zig".to_string(), [args(&["ast-check"]), vec![source]].concat())),
+            _ => None,
+        }
+    }
+    
+    /// Validate generated code for a graph
+    pub fn validate(&self, graph: &TileGraph, code: &str) -> Result<ValidationReport, String> {
+        let skipped = |reason: String, command: String| ValidationReport {
+            language: self.language.clone(),
+            command,
+            status: ValidationStatus::Skipped(reason),
+            diagnostics: Vec::new(),
+            output: String::new(),
+        };
+        
+        let file_name = match self.source_file_name() {
+            Some(name) => name,
+            None => return Ok(skipped(format!("No check-only toolchain configured for {:?}", self.language), String::new())),
+        };
+        
+        let work_dir = tempfile::tempdir().map_err(|e| format!("Failed to create validation directory: {}", e))?;
+        let source_path: PathBuf = work_dir.path().join(file_name);
+        if let Some(parent) = source_path.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("Failed to create validation directory: {}", e))?;
+        }
+        fs::write(&source_path, code).map_err(|e| format!("Failed to write generated source: {}", e))?;
+        
+        if let TargetLanguage::Rust = self.language {
+            let manifest = "[package]\nname = \"osland_generated\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[lib]\npath = \"src/lib.rs\"\n";
+            fs::write(work_dir.path().join("Cargo.toml"), manifest).map_err(|e| format!("Failed to write Cargo.toml: {}", e))?;
+        }
+        
+        let (program, args) = self.check_command(&source_path, work_dir.path())
+            .ok_or_else(|| format!("No check command for {:?}", self.language))?;
+        let command_line = format!("{} {}", program, args.join(" "));
+        
+        let output = match Command::new(&program).args(&args).current_dir(work_dir.path()).output() {
+            Ok(output) => output,
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                return Ok(skipped(format!("'{}' not found in PATH", program), command_line));
+            }
+            Err(e) => return Err(format!("Failed to run '{}': {}", program, e)),
+        };
+        
+        let combined = format!("{}{}", String::from_utf8_lossy(&output.stdout), String::from_utf8_lossy(&output.stderr));
+        Ok(ValidationReport {
+            language: self.language.clone(),
+            command: command_line,
+            status: if output.status.success() { ValidationStatus::Passed } else { ValidationStatus::Failed },
+            diagnostics: map_diagnostics(graph, code, &combined),
+            output: combined,
+        })
+    }
+}
+
+/// Parse toolchain output and attribute each diagnostic to the tile of its line
+fn map_diagnostics(graph: &TileGraph, code: &str, output: &str) -> Vec<CompileDiagnostic> {
+    let line_map = build_line_map(graph, code);
+    parse_diagnostics(output)
+        .into_iter()
+        .map(|(line, column, message)| {
+            let tile = line.and_then(|l| line_map.get(l.saturating_sub(1)).cloned().flatten());
+            CompileDiagnostic {
+                line,
+                column,
+                message,
+                tile_id: tile.as_ref().map(|(id, _)| id.clone()),
+                tile_name: tile.map(|(_, name)| name),
+            }
+        })
+        .collect()
+}
+
+/// Map every generated line to the tile whose section it belongs to
+///
+/// Generators emit each tile under its sanitized name, so a line mentioning a
+/// tile identifier starts that tile's section until another tile is mentioned.
+fn build_line_map(graph: &TileGraph, code: &str) -> Vec<Option<(String, String)>> {
+    let identifiers: Vec<(String, String, String)> = graph.tiles.values()
+        .map(|tile| (sanitize_identifier(&tile.name), tile.id.clone(), tile.name.clone()))
+        .filter(|(identifier, _, _)| !identifier.is_empty())
+        .collect();
+    
+    let mut current: Option<(String, String)> = None;
+    code.lines()
+        .map(|line| {
+            // Prefer the longest identifier so "cpu_core" wins over "cpu"
+            if let Some((_, id, name)) = identifiers.iter()
+                .filter(|(identifier, _, _)| line.contains(identifier.as_str()))
+                .max_by_key(|(identifier, _, _)| identifier.len())
+            {
+                current = Some((id.clone(), name.clone()));
+            }
+            current.clone()
+        })
+        .collect()
+}
+
+/// Extract (line, column, message) triples from toolchain output
+fn parse_diagnostics(output: &str) -> Vec<(Option<usize>, Option<usize>, String)> {
+    // file:line:col: message (gcc, clang, nvcc, zig, cargo --message-format=short, javac without column)
+    let colon_style = Regex::new(r"^[^:\s]+:(\d+):(?:(\d+):)?\s*(.+)$").unwrap();
+    // file(line,col): message (tsc)
+    let paren_style = Regex::new(r"^[^(\s]+\((\d+),(\d+)\):\s*(.+)$").unwrap();
+    // File "...", line N (python)
+    let python_style = Regex::new(r#"File "[^"]+", line (\d+)"#).unwrap();
+    
+    let mut diagnostics = Vec::new();
+    let lines: Vec<&str> = output.lines().collect();
+    
+    for (index, line) in lines.iter().enumerate() {
+        let line = line.trim();
+        if let Some(caps) = paren_style.captures(line).or_else(|| colon_style.captures(line)) {
+            diagnostics.push((
+                caps.get(1).and_then(|m| m.as_str().parse().ok()),
+                caps.get(2).and_then(|m| m.as_str().parse().ok()),
+                caps[3].to_string(),
+            ));
+        } else if let Some(caps) = python_style.captures(line) {
+            // The error message follows the source excerpt
+            let message = lines[index + 1..].iter()
+                .find(|l| l.contains("Error"))
+                .map(|l| l.trim().to_string())
+                .unwrap_or_else(|| "Syntax error".to_string());
+            diagnostics.push((caps[1].parse().ok(), None, message));
+        }
+    }
+    
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tile_engine::tile_core::{Tile, TileType};
+    
+    const CODE: &str = "// Auto-generated code from Tile Graph
+void cpu_core_kernel(void) {
+    int ready = ;
+}
+void cpu_kernel(void) {
+    undefined_call();
+}
+void net_rx_kernel(void) {}
+";
+    
+    fn graph() -> TileGraph {
+        let mut graph = TileGraph::new("kernel".to_string());
+        for (id, name) in [("t-core", "cpu core"), ("t-cpu", "cpu"), ("t-rx", "net rx")] {
+            let mut tile = Tile::new(name.to_string(), TileType::Processing, String::new());
+            tile.id = id.to_string();
+            graph.add_tile(tile).unwrap();
+        }
+        graph
+    }
+    
+    /// Line, column, tile ID and message of a diagnostic
+    type Summary<'a> = (Option<usize>, Option<usize>, Option<&'a str>, &'a str);
+    
+    fn summary(diagnostics: &[CompileDiagnostic]) -> Vec<Summary<'_>> {
+        diagnostics.iter()
+            .map(|d| (d.line, d.column, d.tile_id.as_deref(), d.message.as_str()))
+            .collect()
+    }
+    
+    #[test]
+    fn test_diagnostics_map_to_tiles() {
+        let gcc = "/tmp/check/tile_graph.c: In function 'cpu_core_kernel':
+/tmp/check/tile_graph.c:3:17: error: expected expression before ';' token
+/tmp/check/tile_graph.c:6:5: warning: implicit declaration of function 'undefined_call'
+/tmp/check/tile_graph.c:1:1: note: in generated header
+";
+        assert_eq!(summary(&map_diagnostics(&graph(), CODE, gcc)), vec![
+            (Some(3), Some(17), Some("t-core"), "error: expected expression before ';' token"),
+            (Some(6), Some(5), Some("t-cpu"), "warning: implicit declaration of function 'undefined_call'"),
+            (Some(1), Some(1), None, "note: in generated header"),
+        ]);
+        let diagnostic = &map_diagnostics(&graph(), CODE, gcc)[0];
+        assert_eq!(diagnostic.tile_name.as_deref(), Some("cpu core"));
+        
+        let tsc = "tile_graph.ts(8,26): error TS2304: Cannot find name 'rx_queue'.\n";
+        assert_eq!(summary(&map_diagnostics(&graph(), CODE, tsc)), vec![
+            (Some(8), Some(26), Some("t-rx"), "error TS2304: Cannot find name 'rx_queue'."),
+        ]);
+        
+        let javac = "/tmp/check/TileGraph.java:5: error: ';' expected\n1 error\n";
+        assert_eq!(summary(&map_diagnostics(&graph(), CODE, javac)), vec![
+            (Some(5), None, Some("t-cpu"), "error: ';' expected"),
+        ]);
+        
+        let python = "  File \"/tmp/check/tile_graph.py\", line 6
+    undefined_call(
+                  ^
+SyntaxError: '(' was never closed
+";
+        assert_eq!(summary(&map_diagnostics(&graph(), CODE, python)), vec![
+            (Some(6), None, Some("t-cpu"), "SyntaxError: '(' was never closed"),
+        ]);
+        
+        // Lines past the end of the generated code belong to no tile
+        let past_end = "src/lib.rs:40:1: error: unexpected closing delimiter\n";
+        assert_eq!(summary(&map_diagnostics(&graph(), CODE, past_end)), vec![
+            (Some(40), Some(1), None, "error: unexpected closing delimiter"),
+        ]);
+    }
+    
+    #[test]
+    fn test_missing_toolchain_is_skipped() {
+        let report = GeneratedCodeValidator::new(TargetLanguage::C)
+            .with_program("osland-missing-cc")
+            .validate(&graph(), CODE)
+            .unwrap();
+        assert_eq!(report.status, ValidationStatus::Skipped("'osland-missing-cc' not found in PATH".to_string()));
+        assert!(report.command.starts_with("osland-missing-cc -fsyntax-only -x c "));
+        assert!(report.diagnostics.is_empty());
+        
+        let report = GeneratedCodeValidator::new(TargetLanguage::Moonbit).validate(&graph(), CODE).unwrap();
+        assert_eq!(report.status, ValidationStatus::Skipped("No check-only toolchain configured for Moonbit".to_string()));
+        assert!(report.command.is_empty());
+    }
+    
+    #[cfg(unix)]
+    #[test]
+    fn test_failed_check_reports_tile_diagnostics() {
+        use std::os::unix::fs::PermissionsExt;
+        
+        // Stand-in compiler reporting an error on line 6 of the source it is given
+        let dir = tempfile::tempdir().unwrap();
+        let compiler = dir.path().join("fake-cc");
+        fs::write(&compiler, "#!/bin/sh\nfor source; do :; done\necho \"$source:6:5: error: 'undefined_call' undeclared\" >&2\nexit 1\n").unwrap();
+        fs::set_permissions(&compiler, fs::Permissions::from_mode(0o755)).unwrap();
+        
+        let report = GeneratedCodeValidator::new(TargetLanguage::C)
+            .with_program(compiler.to_string_lossy())
+            .validate(&graph(), CODE)
+            .unwrap();
+        assert_eq!(report.status, ValidationStatus::Failed);
+        assert_eq!(summary(&report.diagnostics), vec![(Some(6), Some(5), Some("t-cpu"), "error: 'undefined_call' undeclared")]);
+        assert!(report.output.contains("tile_graph.c:6:5"));
+    }
+}
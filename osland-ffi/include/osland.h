@@ -0,0 +1,48 @@
+/*
+ * OSland C API
+ * Copyright (c) 2025 OSland Project Team
+ * SPDX-License-Identifier: MulanPSL-2.0
+ *
+ * Strings are NUL-terminated UTF-8; structured values are JSON in the serde
+ * format of the osland-core types. Strings returned by the library must be
+ * released with osland_string_free. Functions returning int return 0 on
+ * success and -1 on failure, functions returning a pointer return NULL on
+ * failure; osland_last_error describes the last failure on the calling thread.
+ */
+
+#ifndef OSLAND_H
+#define OSLAND_H
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct OslandTables OslandTables;
+
+/* Message of the last failure on this thread, or NULL. Valid until the next failing call. */
+const char *osland_last_error(void);
+
+/* Release a string returned by the library. */
+void osland_string_free(char *value);
+
+/* Tables manager */
+OslandTables *osland_tables_new(void);
+void osland_tables_free(OslandTables *tables);
+int osland_tables_create_table(const OslandTables *tables, const char *definition_json);
+char *osland_tables_insert_row(const OslandTables *tables, const char *table, const char *values_json);
+int osland_tables_update_row(const OslandTables *tables, const char *table, const char *row_id, const char *values_json);
+int osland_tables_delete_row(const OslandTables *tables, const char *table, const char *row_id);
+/* conditions_json may be NULL to return every row; the result is a JSON array of rows. */
+char *osland_tables_query(const OslandTables *tables, const char *table, const char *conditions_json);
+
+/* Tile engine: compile a JSON tile graph to code in a target language such as "rust" or "c". */
+char *osland_compile_tile_graph(const char *graph_json, const char *language);
+
+/* Build engine: build an image from a build configuration file. Blocks until done. */
+int osland_build_image(const char *config_path, const char *output_path);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* OSLAND_H */
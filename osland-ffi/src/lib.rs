@@ -0,0 +1,307 @@
+// OSland C ABI
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! C ABI for the OSland engines, declared in `include/osland.h`.
+//!
+//! Conventions shared by every function:
+//!
+//! - Strings are NUL-terminated UTF-8. Structured values (table definitions, row
+//!   values, query results, tile graphs) are passed as JSON in the serde format of
+//!   the `osland-core` types.
+//! - Strings returned by the library are owned by the caller and must be released
+//!   with `osland_string_free`.
+//! - Functions returning `int` return 0 on success and -1 on failure; functions
+//!   returning a pointer return NULL on failure. The message of the last failure
+//!   on the calling thread is available from `osland_last_error`.
+//! - Panics never cross the ABI; they are reported as failures.
+//!
+//! With the `python` feature the same operations are exported as the `osland`
+//! Python extension module (see `python.rs`).
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ffi::{CStr, CString};
+use std::os::raw::{c_char, c_int};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+use osland_core::core::architecture::KernelArchitecture;
+use osland_core::{CompilationOptions, TableDefinition, TablesManager, TargetLanguage, TileCompiler, TileGraph};
+
+#[cfg(feature = "python")]
+mod python;
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = RefCell::new(None);
+}
+
+/// Opaque handle to a tables manager
+pub struct OslandTables {
+    manager: TablesManager,
+}
+
+/// Remember the message of a failure for `osland_last_error`
+fn set_last_error(message: String) {
+    let message = CString::new(message.replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(message));
+}
+
+/// Run `f`, turning errors and panics into `failure` and a last error message
+fn guard<T>(failure: T, f: impl FnOnce() -> Result<T, String>) -> T {
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(value)) => value,
+        Ok(Err(message)) => {
+            set_last_error(message);
+            failure
+        }
+        Err(payload) => {
+            let message = payload.downcast_ref::<&str>().map(|s| s.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown panic".to_string());
+            set_last_error(format!("Panic in OSland: {}", message));
+            failure
+        }
+    }
+}
+
+/// Borrow a string argument
+///
+/// # Safety
+/// `value` must be NULL or point to a NUL-terminated string that outlives the call.
+unsafe fn str_arg<'a>(value: *const c_char, name: &str) -> Result<&'a str, String> {
+    if value.is_null() {
+        return Err(format!("Argument '{}' is NULL", name));
+    }
+    CStr::from_ptr(value).to_str().map_err(|e| format!("Argument '{}' is not valid UTF-8: {}", name, e))
+}
+
+/// Parse a JSON argument
+///
+/// # Safety
+/// Same as `str_arg`.
+unsafe fn json_arg<T: serde::de::DeserializeOwned>(value: *const c_char, name: &str) -> Result<T, String> {
+    serde_json::from_str(str_arg(value, name)?).map_err(|e| format!("Argument '{}' is not valid JSON: {}", name, e))
+}
+
+/// Borrow the manager behind a handle
+///
+/// # Safety
+/// `tables` must be NULL or a handle returned by `osland_tables_new` that was not freed.
+unsafe fn tables_arg<'a>(tables: *const OslandTables) -> Result<&'a TablesManager, String> {
+    tables.as_ref().map(|tables| &tables.manager).ok_or_else(|| "Argument 'tables' is NULL".to_string())
+}
+
+/// Hand a string over to the caller
+fn into_c_string(value: String) -> Result<*mut c_char, String> {
+    CString::new(value).map(CString::into_raw).map_err(|e| format!("Result contains a NUL byte: {}", e))
+}
+
+/// Get the message of the last failure on this thread, or NULL
+///
+/// The pointer stays valid until the next failing call on the same thread.
+#[no_mangle]
+pub extern "C" fn osland_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| last.borrow().as_ref().map_or(ptr::null(), |message| message.as_ptr()))
+}
+
+/// Release a string returned by the library
+///
+/// # Safety
+/// `value` must be NULL or a string returned by this library that was not freed.
+#[no_mangle]
+pub unsafe extern "C" fn osland_string_free(value: *mut c_char) {
+    if !value.is_null() {
+        drop(CString::from_raw(value));
+    }
+}
+
+/// Create a running tables manager
+#[no_mangle]
+pub extern "C" fn osland_tables_new() -> *mut OslandTables {
+    guard(ptr::null_mut(), || {
+        let manager = TablesManager::new();
+        manager.start();
+        Ok(Box::into_raw(Box::new(OslandTables { manager })))
+    })
+}
+
+/// Stop and release a tables manager
+///
+/// # Safety
+/// `tables` must be NULL or a handle returned by `osland_tables_new` that was not freed.
+#[no_mangle]
+pub unsafe extern "C" fn osland_tables_free(tables: *mut OslandTables) {
+    if !tables.is_null() {
+        let tables = Box::from_raw(tables);
+        tables.manager.stop();
+    }
+}
+
+/// Create a table from a JSON `TableDefinition`
+///
+/// # Safety
+/// Pointer arguments must be valid as described in the module documentation.
+#[no_mangle]
+pub unsafe extern "C" fn osland_tables_create_table(tables: *const OslandTables, definition_json: *const c_char) -> c_int {
+    guard(-1, || {
+        let definition: TableDefinition = json_arg(definition_json, "definition_json")?;
+        tables_arg(tables)?.create_table(definition)?;
+        Ok(0)
+    })
+}
+
+/// Insert a row from a JSON object of column values, returning the new row ID
+///
+/// # Safety
+/// Pointer arguments must be valid as described in the module documentation.
+#[no_mangle]
+pub unsafe extern "C" fn osland_tables_insert_row(tables: *const OslandTables, table: *const c_char, values_json: *const c_char) -> *mut c_char {
+    guard(ptr::null_mut(), || {
+        let values: HashMap<String, String> = json_arg(values_json, "values_json")?;
+        let row_id = tables_arg(tables)?.insert_row(str_arg(table, "table")?, values)?;
+        into_c_string(row_id)
+    })
+}
+
+/// Update a row with a JSON object of column values
+///
+/// # Safety
+/// Pointer arguments must be valid as described in the module documentation.
+#[no_mangle]
+pub unsafe extern "C" fn osland_tables_update_row(tables: *const OslandTables, table: *const c_char, row_id: *const c_char, values_json: *const c_char) -> c_int {
+    guard(-1, || {
+        let values: HashMap<String, String> = json_arg(values_json, "values_json")?;
+        tables_arg(tables)?.update_row(str_arg(table, "table")?, str_arg(row_id, "row_id")?, values)?;
+        Ok(0)
+    })
+}
+
+/// Delete a row
+///
+/// # Safety
+/// Pointer arguments must be valid as described in the module documentation.
+#[no_mangle]
+pub unsafe extern "C" fn osland_tables_delete_row(tables: *const OslandTables, table: *const c_char, row_id: *const c_char) -> c_int {
+    guard(-1, || {
+        tables_arg(tables)?.delete_row(str_arg(table, "table")?, str_arg(row_id, "row_id")?)?;
+        Ok(0)
+    })
+}
+
+/// Query the rows matching a JSON object of column values, returned as a JSON array of rows
+///
+/// `conditions_json` may be NULL to return every row.
+///
+/// # Safety
+/// Pointer arguments must be valid as described in the module documentation.
+#[no_mangle]
+pub unsafe extern "C" fn osland_tables_query(tables: *const OslandTables, table: *const c_char, conditions_json: *const c_char) -> *mut c_char {
+    guard(ptr::null_mut(), || {
+        let conditions: HashMap<String, String> = if conditions_json.is_null() {
+            HashMap::new()
+        } else {
+            json_arg(conditions_json, "conditions_json")?
+        };
+        let rows = tables_arg(tables)?.query_rows(str_arg(table, "table")?, conditions)?;
+        into_c_string(serde_json::to_string(&rows).map_err(|e| e.to_string())?)
+    })
+}
+
+/// Compile a JSON `TileGraph` to source code in a target language (for example "rust" or "c")
+///
+/// # Safety
+/// Pointer arguments must be valid as described in the module documentation.
+#[no_mangle]
+pub unsafe extern "C" fn osland_compile_tile_graph(graph_json: *const c_char, language: *const c_char) -> *mut c_char {
+    guard(ptr::null_mut(), || {
+        let graph: TileGraph = json_arg(graph_json, "graph_json")?;
+        let language = str_arg(language, "language")?;
+        let options = CompilationOptions {
+            target_language: TargetLanguage::from_name(language).ok_or_else(|| format!("Unknown target language '{}'", language))?,
+            ..CompilationOptions::default()
+        };
+        let code = TileCompiler::new(KernelArchitecture::default(), Some(options)).generate_execution_code(&graph)?;
+        into_c_string(code)
+    })
+}
+
+/// Build an operating system image from a build configuration file
+///
+/// Blocks until the build finishes.
+///
+/// # Safety
+/// Pointer arguments must be valid as described in the module documentation.
+#[no_mangle]
+pub unsafe extern "C" fn osland_build_image(config_path: *const c_char, output_path: *const c_char) -> c_int {
+    guard(-1, || {
+        let config_path = str_arg(config_path, "config_path")?.to_string();
+        let output_path = str_arg(output_path, "output_path")?.to_string();
+        osland_core::build_engine::build_image(config_path, output_path).map_err(|e| e.to_string())?;
+        Ok(0)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn c(value: &str) -> CString {
+        CString::new(value).unwrap()
+    }
+    
+    unsafe fn take(value: *mut c_char) -> String {
+        assert!(!value.is_null(), "call failed: {:?}", CStr::from_ptr(osland_last_error()));
+        let result = CStr::from_ptr(value).to_str().unwrap().to_string();
+        osland_string_free(value);
+        result
+    }
+    
+    #[test]
+    fn test_table_round_trip() {
+        let definition = r#"{"name":"tasks","columns":[
+            {"name":"title","column_type":"String","nullable":false,"default_value":null,"description":""},
+            {"name":"state","column_type":"String","nullable":true,"default_value":"open","description":""}],
+            "primary_key":[],"indexes":[],"description":"","created_at":0,"updated_at":0}"#;
+        unsafe {
+            let tables = osland_tables_new();
+            assert_eq!(osland_tables_create_table(tables, c(definition).as_ptr()), 0);
+            let row_id = take(osland_tables_insert_row(tables, c("tasks").as_ptr(), c(r#"{"title":"boot"}"#).as_ptr()));
+            take(osland_tables_insert_row(tables, c("tasks").as_ptr(), c(r#"{"title":"mm","state":"done"}"#).as_ptr()));
+            assert_eq!(osland_tables_update_row(tables, c("tasks").as_ptr(), c(&row_id).as_ptr(), c(r#"{"state":"done"}"#).as_ptr()), 0);
+            
+            let rows: serde_json::Value = serde_json::from_str(&take(osland_tables_query(tables, c("tasks").as_ptr(), c(r#"{"state":"done"}"#).as_ptr()))).unwrap();
+            assert_eq!(rows.as_array().unwrap().len(), 2);
+            
+            assert_eq!(osland_tables_delete_row(tables, c("tasks").as_ptr(), c(&row_id).as_ptr()), 0);
+            let rows: serde_json::Value = serde_json::from_str(&take(osland_tables_query(tables, c("tasks").as_ptr(), ptr::null()))).unwrap();
+            assert_eq!(rows.as_array().unwrap().len(), 1);
+            osland_tables_free(tables);
+        }
+    }
+    
+    #[test]
+    fn test_errors_are_reported() {
+        unsafe {
+            let tables = osland_tables_new();
+            assert!(osland_tables_insert_row(tables, c("missing").as_ptr(), c("{}").as_ptr()).is_null());
+            assert!(CStr::from_ptr(osland_last_error()).to_str().unwrap().contains("missing"));
+            
+            assert_eq!(osland_tables_create_table(tables, c("not json").as_ptr()), -1);
+            assert!(CStr::from_ptr(osland_last_error()).to_str().unwrap().contains("definition_json"));
+            assert_eq!(osland_tables_delete_row(ptr::null(), c("t").as_ptr(), c("r").as_ptr()), -1);
+            osland_tables_free(tables);
+        }
+    }
+    
+    #[test]
+    fn test_compile_tile_graph() {
+        let graph = serde_json::to_string(&TileGraph::new("boot".to_string())).unwrap();
+        unsafe {
+            let code = take(osland_compile_tile_graph(c(&graph).as_ptr(), c("Rust").as_ptr()));
+            assert!(!code.is_empty());
+            assert!(osland_compile_tile_graph(c(&graph).as_ptr(), c("cobol").as_ptr()).is_null());
+            assert!(CStr::from_ptr(osland_last_error()).to_str().unwrap().contains("cobol"));
+        }
+    }
+}
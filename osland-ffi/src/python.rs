@@ -0,0 +1,113 @@
+// OSland Python bindings
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! The `osland` Python extension module.
+//!
+//! ```python
+//! import json, osland
+//!
+//! tables = osland.Tables()
+//! tables.create_table(json.dumps(definition))
+//! row_id = tables.insert_row("tasks", {"title": "boot"})
+//! rows = tables.query("tasks", {"state": "open"})
+//!
+//! code = osland.compile_tile_graph(json.dumps(graph), "c")
+//! osland.build_image("build.json", "out/")
+//! ```
+//!
+//! Table definitions and tile graphs are JSON strings in the same format as for
+//! the C ABI; row values are dictionaries of strings.
+
+use std::collections::HashMap;
+
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use osland_core::core::architecture::KernelArchitecture;
+use osland_core::{CompilationOptions, TableDefinition, TablesManager, TargetLanguage, TileCompiler, TileGraph};
+
+/// A running DBOS tables manager
+#[pyclass(name = "Tables")]
+struct PyTables {
+    manager: TablesManager,
+}
+
+#[pymethods]
+impl PyTables {
+    #[new]
+    fn new() -> Self {
+        let manager = TablesManager::new();
+        manager.start();
+        Self { manager }
+    }
+    
+    /// Create a table from a JSON table definition
+    fn create_table(&self, definition: &str) -> PyResult<()> {
+        let definition: TableDefinition = serde_json::from_str(definition)
+            .map_err(|e| PyValueError::new_err(format!("Invalid table definition: {}", e)))?;
+        self.manager.create_table(definition).map_err(PyRuntimeError::new_err)
+    }
+    
+    /// Insert a row, returning its ID
+    fn insert_row(&self, table: &str, values: HashMap<String, String>) -> PyResult<String> {
+        self.manager.insert_row(table, values).map_err(PyRuntimeError::new_err)
+    }
+    
+    /// Update the given columns of a row
+    fn update_row(&self, table: &str, row_id: &str, values: HashMap<String, String>) -> PyResult<()> {
+        self.manager.update_row(table, row_id, values).map_err(PyRuntimeError::new_err)
+    }
+    
+    /// Delete a row
+    fn delete_row(&self, table: &str, row_id: &str) -> PyResult<()> {
+        self.manager.delete_row(table, row_id).map_err(PyRuntimeError::new_err)
+    }
+    
+    /// Get the rows whose columns equal the given values, as dictionaries
+    #[pyo3(signature = (table, conditions = None))]
+    fn query(&self, py: Python<'_>, table: &str, conditions: Option<HashMap<String, String>>) -> PyResult<Vec<PyObject>> {
+        let rows = self.manager.query_rows(table, conditions.unwrap_or_default()).map_err(PyRuntimeError::new_err)?;
+        rows.into_iter().map(|row| {
+            let dict = PyDict::new(py);
+            dict.set_item("row_id", row.row_id)?;
+            dict.set_item("values", row.values)?;
+            dict.set_item("created_at", row.created_at)?;
+            dict.set_item("updated_at", row.updated_at)?;
+            Ok(dict.to_object(py))
+        }).collect()
+    }
+}
+
+/// Compile a JSON tile graph to source code in a target language
+#[pyfunction]
+#[pyo3(signature = (graph, language = "rust"))]
+fn compile_tile_graph(graph: &str, language: &str) -> PyResult<String> {
+    let graph: TileGraph = serde_json::from_str(graph)
+        .map_err(|e| PyValueError::new_err(format!("Invalid tile graph: {}", e)))?;
+    let options = CompilationOptions {
+        target_language: TargetLanguage::from_name(language)
+            .ok_or_else(|| PyValueError::new_err(format!("Unknown target language '{}'", language)))?,
+        ..CompilationOptions::default()
+    };
+    TileCompiler::new(KernelArchitecture::default(), Some(options))
+        .generate_execution_code(&graph)
+        .map_err(PyRuntimeError::new_err)
+}
+
+/// Build an operating system image, releasing the GIL while the build runs
+#[pyfunction]
+fn build_image(py: Python<'_>, config_path: String, output_path: String) -> PyResult<()> {
+    py.allow_threads(|| osland_core::build_engine::build_image(config_path, output_path))
+        .map_err(|e| PyRuntimeError::new_err(e.to_string()))
+}
+
+#[pymodule]
+#[pyo3(name = "osland")]
+fn osland_module(_py: Python<'_>, module: &PyModule) -> PyResult<()> {
+    module.add_class::<PyTables>()?;
+    module.add_function(wrap_pyfunction!(compile_tile_graph, module)?)?;
+    module.add_function(wrap_pyfunction!(build_image, module)?)?;
+    Ok(())
+}
@@ -0,0 +1,484 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::collaboration::{Operation, OperationType};
+use crate::core::simulation;
+
+/// Default interval between two flushes of the broadcast queue
+pub const DEFAULT_FLUSH_INTERVAL: Duration = Duration::from_millis(33);
+
+/// Settings of operation batching
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BatchConfig {
+    /// Maximum time an operation waits before it is sent
+    pub flush_interval: Duration,
+    
+    /// Number of queued operations that forces a flush
+    pub max_batch_size: usize,
+    
+    /// Send changed fields instead of whole payloads
+    pub delta_compression: bool,
+    
+    /// Number of deltas after which a full payload is sent again
+    pub keyframe_interval: u32,
+}
+
+impl Default for BatchConfig {
+    fn default() -> Self {
+        Self {
+            flush_interval: DEFAULT_FLUSH_INTERVAL,
+            max_batch_size: 64,
+            delta_compression: true,
+            keyframe_interval: 50,
+        }
+    }
+}
+
+/// Key of the object a high-frequency operation changes
+///
+/// Operations with the same key supersede each other, so only the last one of a
+/// drag needs to be sent.
+pub fn coalescing_key(operation: &Operation) -> Option<String> {
+    let target = match operation.operation_type {
+        OperationType::CursorMove | OperationType::SelectionChange => String::new(),
+        OperationType::UpdateNode => operation.data.get(0)?.as_str()?.to_string(),
+        OperationType::UpdateTileProperty => format!("{}/{}",
+            operation.data.get("tile_id")?.as_str()?, operation.data.get("key")?.as_str()?),
+        _ => return None,
+    };
+    Some(format!("{:?}:{}:{}", operation.operation_type, operation.user_id, target))
+}
+
+/// Merges operations that supersede each other before they are sent
+///
+/// Used on the client while dragging: a pointer move replaces the queued update of
+/// the same node when nothing was queued after it, so every operation keeps its
+/// place relative to the others.
+#[derive(Debug, Default)]
+pub struct MoveCoalescer {
+    /// Queued operations
+    queue: Vec<Operation>,
+    
+    /// Coalescing key of the last queued operation
+    last_key: Option<String>,
+    
+    /// Number of operations replaced by a newer one
+    coalesced: u64,
+}
+
+impl MoveCoalescer {
+    /// Create an empty coalescer
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Queue an operation, replacing the last queued one if it has the same key
+    pub fn push(&mut self, operation: Operation) {
+        let key = coalescing_key(&operation);
+        if key.is_some() && key == self.last_key {
+            *self.queue.last_mut().unwrap() = operation;
+            self.coalesced += 1;
+        } else {
+            self.queue.push(operation);
+            self.last_key = key;
+        }
+    }
+    
+    /// Take the queued operations
+    pub fn drain(&mut self) -> Vec<Operation> {
+        self.last_key = None;
+        std::mem::take(&mut self.queue)
+    }
+    
+    /// Number of queued operations
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+    
+    /// Check if nothing is queued
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+    
+    /// Number of operations dropped because a newer one replaced them
+    pub fn coalesced(&self) -> u64 {
+        self.coalesced
+    }
+}
+
+/// Operation as sent in a batch
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeltaFrame {
+    /// Operation; with `delta` set its data is a merge patch of the previous payload
+    pub operation: Operation,
+    
+    /// Whether the data is a patch
+    #[serde(default)]
+    pub delta: bool,
+    
+    /// Coalescing key of a patch, which the patched data may no longer contain
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+}
+
+/// Batch of operations sent in one WebSocket message
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OperationBatch {
+    /// Operations in order
+    pub frames: Vec<DeltaFrame>,
+}
+
+/// Bandwidth used by the broadcast stream
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BandwidthMetrics {
+    /// Operations submitted for broadcast
+    pub operations_in: u64,
+    
+    /// Operations actually sent
+    pub operations_sent: u64,
+    
+    /// Operations sent as deltas
+    pub deltas_sent: u64,
+    
+    /// Messages sent
+    pub batches_sent: u64,
+    
+    /// Bytes one message per operation would have taken
+    pub raw_bytes: u64,
+    
+    /// Bytes actually sent
+    pub sent_bytes: u64,
+    
+    /// Total time operations waited in the queue (milliseconds)
+    pub total_delay_ms: u64,
+    
+    /// Longest time an operation waited in the queue (milliseconds)
+    pub max_delay_ms: u64,
+}
+
+impl BandwidthMetrics {
+    /// Sent bytes relative to unbatched bytes
+    pub fn compression_ratio(&self) -> f64 {
+        if self.raw_bytes == 0 {
+            return 1.0;
+        }
+        self.sent_bytes as f64 / self.raw_bytes as f64
+    }
+    
+    /// Mean latency added by batching (milliseconds)
+    pub fn mean_delay_ms(&self) -> f64 {
+        if self.operations_in == 0 {
+            return 0.0;
+        }
+        self.total_delay_ms as f64 / self.operations_in as f64
+    }
+}
+
+/// Server-side queue batching broadcast operations
+#[derive(Debug)]
+pub struct OperationBatcher {
+    /// Settings
+    config: BatchConfig,
+    
+    /// Queued operations
+    queue: MoveCoalescer,
+    
+    /// Enqueue time of each submitted operation not yet flushed, on the simulation clock
+    enqueued: Vec<Duration>,
+    
+    /// Last flush time, on the simulation clock
+    last_flush: Duration,
+    
+    /// Last payload sent per coalescing key and deltas since it was sent in full
+    baselines: HashMap<String, (Value, u32)>,
+    
+    /// Bandwidth metrics
+    metrics: BandwidthMetrics,
+}
+
+impl OperationBatcher {
+    /// Create a batcher
+    pub fn new(config: BatchConfig) -> Self {
+        Self {
+            config,
+            queue: MoveCoalescer::new(),
+            enqueued: Vec::new(),
+            last_flush: simulation::now(),
+            baselines: HashMap::new(),
+            metrics: BandwidthMetrics::default(),
+        }
+    }
+    
+    /// Get the settings
+    pub fn config(&self) -> &BatchConfig {
+        &self.config
+    }
+    
+    /// Change the settings
+    pub fn set_config(&mut self, config: BatchConfig) {
+        self.config = config;
+        self.baselines.clear();
+    }
+    
+    /// Queue an operation, returning a message to send if the batch is due
+    pub fn push(&mut self, operation: Operation) -> Option<String> {
+        self.metrics.operations_in += 1;
+        self.metrics.raw_bytes += serde_json::to_string(&operation).map(|text| text.len() as u64).unwrap_or(0);
+        self.enqueued.push(simulation::now());
+        self.queue.push(operation);
+        
+        if self.queue.len() >= self.config.max_batch_size || self.is_due() {
+            self.flush()
+        } else {
+            None
+        }
+    }
+    
+    /// Check if the flush interval has passed
+    pub fn is_due(&self) -> bool {
+        !self.queue.is_empty() && simulation::now().saturating_sub(self.last_flush) >= self.config.flush_interval
+    }
+    
+    /// Build the message of the queued operations
+    pub fn flush(&mut self) -> Option<String> {
+        let now = simulation::now();
+        self.last_flush = now;
+        if self.queue.is_empty() {
+            return None;
+        }
+        
+        for enqueued in self.enqueued.drain(..) {
+            let delay_ms = now.saturating_sub(enqueued).as_millis() as u64;
+            self.metrics.total_delay_ms += delay_ms;
+            self.metrics.max_delay_ms = self.metrics.max_delay_ms.max(delay_ms);
+        }
+        
+        let frames: Vec<DeltaFrame> = self.queue.drain().into_iter().map(|operation| self.encode(operation)).collect();
+        self.metrics.operations_sent += frames.len() as u64;
+        self.metrics.deltas_sent += frames.iter().filter(|frame| frame.delta).count() as u64;
+        self.metrics.batches_sent += 1;
+        
+        let message = serde_json::to_string(&OperationBatch { frames }).ok()?;
+        self.metrics.sent_bytes += message.len() as u64;
+        Some(message)
+    }
+    
+    /// Forget the payloads deltas are based on, e.g. when a client connects
+    ///
+    /// The next operation of each key is sent in full.
+    pub fn reset_deltas(&mut self) {
+        self.baselines.clear();
+    }
+    
+    /// Get the bandwidth metrics
+    pub fn metrics(&self) -> &BandwidthMetrics {
+        &self.metrics
+    }
+    
+    /// Replace the data of an operation by a patch of the previous payload of its key
+    fn encode(&mut self, mut operation: Operation) -> DeltaFrame {
+        let key = match coalescing_key(&operation) {
+            Some(key) if self.config.delta_compression => key,
+            _ => return DeltaFrame { operation, delta: false, key: None },
+        };
+        
+        let patch = match self.baselines.get(&key) {
+            Some((base, count)) if *count < self.config.keyframe_interval => merge_patch(base, &operation.data),
+            _ => None,
+        };
+        match patch {
+            Some(patch) => {
+                let (base, count) = self.baselines.get_mut(&key).unwrap();
+                *base = std::mem::replace(&mut operation.data, patch);
+                *count += 1;
+                DeltaFrame { operation, delta: true, key: Some(key) }
+            }
+            None => {
+                self.baselines.insert(key, (operation.data.clone(), 0));
+                DeltaFrame { operation, delta: false, key: None }
+            }
+        }
+    }
+}
+
+/// Rebuilds operations from batches on the receiving side
+#[derive(Debug, Default)]
+pub struct BatchDecoder {
+    /// Last full payload per coalescing key
+    baselines: HashMap<String, Value>,
+}
+
+impl BatchDecoder {
+    /// Create a decoder
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Decode a batch message
+    pub fn decode(&mut self, message: &str) -> Result<Vec<Operation>, String> {
+        let batch: OperationBatch = serde_json::from_str(message)
+            .map_err(|e| format!("Failed to deserialize operation batch: {}", e))?;
+        
+        let mut operations = Vec::with_capacity(batch.frames.len());
+        for frame in batch.frames {
+            let mut operation = frame.operation;
+            let key = if frame.delta { frame.key } else { coalescing_key(&operation) };
+            if frame.delta {
+                let base = key.as_ref().and_then(|key| self.baselines.get(key))
+                    .ok_or_else(|| format!("Missing base payload for delta of operation {}", operation.operation_id))?;
+                operation.data = apply_merge_patch(base, &operation.data);
+            }
+            if let Some(key) = key {
+                self.baselines.insert(key, operation.data.clone());
+            }
+            operations.push(operation);
+        }
+        Ok(operations)
+    }
+}
+
+/// JSON merge patch (RFC 7386) turning `base` into `target`
+///
+/// Returns `None` when a patch cannot express the change (the target contains
+/// nulls, which a merge patch reads as removals) or is not smaller than the target.
+pub fn merge_patch(base: &Value, target: &Value) -> Option<Value> {
+    fn diff(base: &Value, target: &Value) -> Option<Value> {
+        match (base, target) {
+            (Value::Object(base), Value::Object(target)) => {
+                let mut patch = Map::new();
+                for (key, value) in target {
+                    match base.get(key) {
+                        Some(old) if old == value => {}
+                        Some(old) => {
+                            patch.insert(key.clone(), diff(old, value)?);
+                        }
+                        None => {
+                            patch.insert(key.clone(), plain(value)?);
+                        }
+                    }
+                }
+                for key in base.keys().filter(|key| !target.contains_key(*key)) {
+                    patch.insert(key.clone(), Value::Null);
+                }
+                Some(Value::Object(patch))
+            }
+            _ => plain(target),
+        }
+    }
+    
+    // Values that are copied whole must not contain nulls nor be objects merged
+    // into a non-object base
+    fn plain(value: &Value) -> Option<Value> {
+        match value {
+            Value::Null => None,
+            Value::Array(items) if items.iter().any(contains_null) => None,
+            Value::Object(map) if map.values().any(contains_null) => None,
+            _ => Some(value.clone()),
+        }
+    }
+    
+    fn contains_null(value: &Value) -> bool {
+        match value {
+            Value::Null => true,
+            Value::Array(items) => items.iter().any(contains_null),
+            Value::Object(map) => map.values().any(contains_null),
+            _ => false,
+        }
+    }
+    
+    if !base.is_object() || !target.is_object() {
+        return None;
+    }
+    let patch = diff(base, target)?;
+    let smaller = serde_json::to_string(&patch).ok()?.len() < serde_json::to_string(target).ok()?.len();
+    smaller.then_some(patch)
+}
+
+/// Apply a JSON merge patch
+pub fn apply_merge_patch(base: &Value, patch: &Value) -> Value {
+    match (base, patch) {
+        (Value::Object(base), Value::Object(patch)) => {
+            let mut merged = base.clone();
+            for (key, value) in patch {
+                if value.is_null() {
+                    merged.remove(key);
+                } else {
+                    let old = merged.get(key).cloned().unwrap_or(Value::Null);
+                    merged.insert(key.clone(), apply_merge_patch(&old, value));
+                }
+            }
+            Value::Object(merged)
+        }
+        _ => patch.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn drag(x: u32) -> Operation {
+        Operation::new("alice".to_string(), format!("move-{}", x), OperationType::UpdateTileProperty, serde_json::json!({
+            "tile_id": "sched",
+            "key": "layout.position",
+            "value": format!("{},40", x),
+            "base_value": null,
+            "description": "Round robin scheduler with per-CPU run queues",
+        }))
+    }
+    
+    #[test]
+    fn test_batching_reduces_bandwidth() {
+        let mut coalescer = MoveCoalescer::new();
+        for x in 0..5 {
+            coalescer.push(drag(x));
+        }
+        coalescer.push(Operation::new("alice".to_string(), "add".to_string(), OperationType::AddNode, serde_json::json!({})));
+        let drained = coalescer.drain();
+        assert_eq!(drained.len(), 2);
+        assert_eq!(drained[0].operation_id, "move-4");
+        assert_eq!(coalescer.coalesced(), 4);
+        
+        // Moves separated by another operation are not merged across it
+        coalescer.push(drag(0));
+        coalescer.push(Operation::new("alice".to_string(), "remove".to_string(), OperationType::RemoveNode, serde_json::json!({})));
+        coalescer.push(drag(1));
+        let ids: Vec<String> = coalescer.drain().into_iter().map(|operation| operation.operation_id).collect();
+        assert_eq!(ids, vec!["move-0", "remove", "move-1"]);
+        
+        // Each flush carries one position of the drag, as a patch after the first
+        let mut batcher = OperationBatcher::new(BatchConfig { flush_interval: Duration::from_secs(60), ..BatchConfig::default() });
+        let mut decoder = BatchDecoder::new();
+        let mut received = Vec::new();
+        for x in 0..20 {
+            assert!(batcher.push(drag(x)).is_none());
+            let message = batcher.flush().unwrap();
+            received.extend(decoder.decode(&message).unwrap());
+        }
+        assert_eq!(received.len(), 20);
+        assert_eq!(received[19].data["value"], "19,40");
+        assert_eq!(received[19].data["description"], drag(0).data["description"]);
+        
+        let metrics = batcher.metrics();
+        assert_eq!(metrics.deltas_sent, 19);
+        assert!(metrics.sent_bytes < metrics.raw_bytes);
+        assert!(metrics.max_delay_ms <= DEFAULT_FLUSH_INTERVAL.as_millis() as u64);
+        
+        // A full batch is flushed without waiting for the interval
+        let mut batcher = OperationBatcher::new(BatchConfig { max_batch_size: 2, flush_interval: Duration::from_secs(60), ..BatchConfig::default() });
+        assert!(batcher.push(Operation::new("alice".to_string(), "a".to_string(), OperationType::AddNode, serde_json::json!({}))).is_none());
+        assert!(batcher.push(Operation::new("alice".to_string(), "b".to_string(), OperationType::AddNode, serde_json::json!({}))).is_some());
+    }
+    
+    #[test]
+    fn test_merge_patch_round_trip() {
+        let base = serde_json::json!({"a": 1, "b": {"c": "long value", "d": [1, 2]}, "e": "removed soon"});
+        let target = serde_json::json!({"a": 1, "b": {"c": "long value", "d": [1, 3]}});
+        let patch = merge_patch(&base, &target).unwrap();
+        assert_eq!(apply_merge_patch(&base, &patch), target);
+        assert!(merge_patch(&base, &serde_json::json!({"a": null})).is_none());
+    }
+}
@@ -0,0 +1,326 @@
+//! Review and approval of design changes
+//!
+//! Instead of editing the shared canvas directly, a collaborator can propose a set of
+//! canvas and tile operations as a change request. Owners review it and either approve
+//! it or request changes; the author may revise the operations, which starts a new
+//! review round. Once approved, the whole set is applied atomically by
+//! `CollaborationManager::apply_change_request`: if one operation fails, none is applied.
+//!
+//! Change requests are kept in the `CollaborationStore`, so every server instance of a
+//! project sees the same requests and reviews.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::collaboration::{Operation, Subsystem, UserRole, UserSession};
+use crate::core::simulation;
+
+/// Owner approvals needed before a change request can be applied
+pub const DEFAULT_REQUIRED_APPROVALS: usize = 1;
+
+/// State of a change request
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ChangeRequestStatus {
+    /// Waiting for reviews
+    Open,
+    
+    /// A reviewer asked for changes to the current revision
+    ChangesRequested,
+    
+    /// Enough owners approved the current revision
+    Approved,
+    
+    /// The operations were applied to the project
+    Applied,
+    
+    /// The author withdrew the request
+    Withdrawn,
+}
+
+impl ChangeRequestStatus {
+    /// Check if the request can no longer change
+    pub fn is_closed(&self) -> bool {
+        matches!(self, ChangeRequestStatus::Applied | ChangeRequestStatus::Withdrawn)
+    }
+}
+
+/// Decision of a reviewer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ReviewVerdict {
+    /// The changes may be applied
+    Approve,
+    
+    /// The author has to revise the changes
+    RequestChanges,
+}
+
+/// Review of one revision of a change request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Review {
+    /// Reviewer user ID
+    pub reviewer_id: String,
+    
+    /// Reviewer display name
+    pub reviewer_name: String,
+    
+    /// Decision
+    pub verdict: ReviewVerdict,
+    
+    /// Comment for the author
+    pub comment: String,
+    
+    /// Revision the review applies to
+    pub revision: u32,
+    
+    /// Review time (milliseconds)
+    pub reviewed_at: u64,
+}
+
+/// Proposed set of canvas and tile changes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChangeRequest {
+    /// Change request ID
+    pub request_id: String,
+    
+    /// Project ID
+    pub project_id: String,
+    
+    /// Short summary
+    pub title: String,
+    
+    /// Motivation and details
+    pub description: String,
+    
+    /// Author user ID
+    pub author_id: String,
+    
+    /// Author display name
+    pub author_name: String,
+    
+    /// Operations applied together once approved
+    pub operations: Vec<Operation>,
+    
+    /// Revision of the operations, incremented by every revise
+    pub revision: u32,
+    
+    /// Reviews of all revisions, oldest first
+    pub reviews: Vec<Review>,
+    
+    /// Owner approvals of the current revision needed to apply it
+    pub required_approvals: usize,
+    
+    /// Current state
+    pub status: ChangeRequestStatus,
+    
+    /// Creation time (milliseconds)
+    pub created_at: u64,
+    
+    /// Last change time (milliseconds)
+    pub updated_at: u64,
+    
+    /// User who applied the changes
+    #[serde(default)]
+    pub applied_by: Option<String>,
+}
+
+impl ChangeRequest {
+    /// Propose changes; the author needs write permission
+    pub fn new(project_id: &str, author: &UserSession, title: String, description: String, operations: Vec<Operation>, required_approvals: usize) -> Result<Self, String> {
+        if !author.has_write_permission() {
+            return Err(format!("{} has no write permission", author.username));
+        }
+        validate_operations(&author.user_id, &operations)?;
+        let now = current_timestamp();
+        Ok(Self {
+            request_id: uuid::Uuid::new_v4().to_string(),
+            project_id: project_id.to_string(),
+            title,
+            description,
+            author_id: author.user_id.clone(),
+            author_name: author.username.clone(),
+            operations,
+            revision: 1,
+            reviews: Vec::new(),
+            required_approvals: required_approvals.max(1),
+            status: ChangeRequestStatus::Open,
+            created_at: now,
+            updated_at: now,
+            applied_by: None,
+        })
+    }
+    
+    /// Record the review of an owner other than the author
+    ///
+    /// A reviewer's latest review of the current revision replaces their earlier ones.
+    pub fn review(&mut self, reviewer: &UserSession, verdict: ReviewVerdict, comment: String) -> Result<(), String> {
+        self.check_open()?;
+        if reviewer.role != UserRole::Owner {
+            return Err(format!("Only owners can review change requests; {} is {:?}", reviewer.username, reviewer.role));
+        }
+        if reviewer.user_id == self.author_id {
+            return Err("Authors cannot review their own change requests".to_string());
+        }
+        self.reviews.push(Review {
+            reviewer_id: reviewer.user_id.clone(),
+            reviewer_name: reviewer.username.clone(),
+            verdict,
+            comment,
+            revision: self.revision,
+            reviewed_at: current_timestamp(),
+        });
+        self.refresh_status();
+        Ok(())
+    }
+    
+    /// Replace the proposed operations; only the author may revise and earlier reviews no longer count
+    pub fn revise(&mut self, user_id: &str, operations: Vec<Operation>) -> Result<(), String> {
+        self.check_open()?;
+        if user_id != self.author_id {
+            return Err(format!("Only the author can revise change request {}", self.request_id));
+        }
+        validate_operations(&self.author_id, &operations)?;
+        self.operations = operations;
+        self.revision += 1;
+        self.refresh_status();
+        Ok(())
+    }
+    
+    /// Withdraw the request; only the author may withdraw
+    pub fn withdraw(&mut self, user_id: &str) -> Result<(), String> {
+        self.check_open()?;
+        if user_id != self.author_id {
+            return Err(format!("Only the author can withdraw change request {}", self.request_id));
+        }
+        self.status = ChangeRequestStatus::Withdrawn;
+        self.updated_at = current_timestamp();
+        Ok(())
+    }
+    
+    /// Check that the request is approved and may be applied
+    pub fn check_applicable(&self) -> Result<(), String> {
+        match self.status {
+            ChangeRequestStatus::Approved => Ok(()),
+            status => Err(format!("Change request {} is {:?}, not approved", self.request_id, status)),
+        }
+    }
+    
+    /// Mark the request as applied by a user
+    pub fn mark_applied(&mut self, user_id: &str) {
+        self.status = ChangeRequestStatus::Applied;
+        self.applied_by = Some(user_id.to_string());
+        self.updated_at = current_timestamp();
+    }
+    
+    /// Latest verdict of each reviewer on the current revision
+    pub fn current_verdicts(&self) -> HashMap<&str, ReviewVerdict> {
+        self.reviews.iter()
+            .filter(|review| review.revision == self.revision)
+            .map(|review| (review.reviewer_id.as_str(), review.verdict))
+            .collect()
+    }
+    
+    /// Fail if the request was applied or withdrawn
+    fn check_open(&self) -> Result<(), String> {
+        if self.status.is_closed() {
+            return Err(format!("Change request {} is {:?}", self.request_id, self.status));
+        }
+        Ok(())
+    }
+    
+    /// Derive the status from the verdicts on the current revision
+    fn refresh_status(&mut self) {
+        let verdicts = self.current_verdicts();
+        let approvals = verdicts.values().filter(|verdict| **verdict == ReviewVerdict::Approve).count();
+        self.status = if verdicts.values().any(|verdict| *verdict == ReviewVerdict::RequestChanges) {
+            ChangeRequestStatus::ChangesRequested
+        } else if approvals >= self.required_approvals {
+            ChangeRequestStatus::Approved
+        } else {
+            ChangeRequestStatus::Open
+        };
+        self.updated_at = current_timestamp();
+    }
+}
+
+/// Check that a change request only edits the design and only on behalf of its author
+fn validate_operations(author_id: &str, operations: &[Operation]) -> Result<(), String> {
+    if operations.is_empty() {
+        return Err("A change request needs at least one operation".to_string());
+    }
+    for operation in operations {
+        if operation.operation_type.subsystem() == Subsystem::Presence {
+            return Err(format!("{:?} cannot be part of a change request", operation.operation_type));
+        }
+        if operation.user_id != author_id {
+            return Err(format!("Operation {} was not made by the author", operation.operation_id));
+        }
+    }
+    Ok(())
+}
+
+/// Get current timestamp in milliseconds
+fn current_timestamp() -> u64 {
+    simulation::unix_millis()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collaboration::OperationType;
+    
+    fn remove_node(user_id: &str, node_id: &str) -> Operation {
+        Operation::new(user_id.to_string(), uuid::Uuid::new_v4().to_string(), OperationType::RemoveNode, serde_json::json!(node_id))
+    }
+    
+    #[test]
+    fn test_review_rounds() {
+        let alice = UserSession::new("alice".to_string(), "Alice".to_string(), UserRole::Editor);
+        let owner = UserSession::new("carol".to_string(), "Carol".to_string(), UserRole::Owner);
+        let admin = UserSession::new("dave".to_string(), "Dave".to_string(), UserRole::Admin);
+        let viewer = UserSession::new("erin".to_string(), "Erin".to_string(), UserRole::Viewer);
+        
+        assert!(ChangeRequest::new("kernel", &viewer, "Drop".to_string(), String::new(), vec![remove_node("erin", "n1")], 1).is_err());
+        assert!(ChangeRequest::new("kernel", &alice, "Drop".to_string(), String::new(), vec![remove_node("bob", "n1")], 1).is_err());
+        let cursor = Operation::new("alice".to_string(), "c".to_string(), OperationType::CursorMove, serde_json::json!({"x": 1, "y": 2}));
+        assert!(ChangeRequest::new("kernel", &alice, "Drop".to_string(), String::new(), vec![cursor], 1).is_err());
+        
+        let mut request = ChangeRequest::new("kernel", &alice, "Drop n1".to_string(), String::new(), vec![remove_node("alice", "n1")], 1).unwrap();
+        assert_eq!(request.status, ChangeRequestStatus::Open);
+        assert!(request.check_applicable().is_err());
+        assert!(request.review(&admin, ReviewVerdict::Approve, String::new()).is_err());
+        
+        request.review(&owner, ReviewVerdict::RequestChanges, "Keep n1, drop n2".to_string()).unwrap();
+        assert_eq!(request.status, ChangeRequestStatus::ChangesRequested);
+        assert!(request.revise("carol", vec![remove_node("carol", "n2")]).is_err());
+        
+        // A new revision resets the round
+        request.revise("alice", vec![remove_node("alice", "n2")]).unwrap();
+        assert_eq!((request.revision, request.status), (2, ChangeRequestStatus::Open));
+        request.review(&owner, ReviewVerdict::Approve, String::new()).unwrap();
+        assert_eq!(request.status, ChangeRequestStatus::Approved);
+        assert!(request.check_applicable().is_ok());
+        
+        request.mark_applied("alice");
+        assert!(request.withdraw("alice").is_err());
+        assert!(request.review(&owner, ReviewVerdict::RequestChanges, String::new()).is_err());
+    }
+    
+    #[test]
+    fn test_authors_cannot_approve_their_own_changes() {
+        let owner = UserSession::new("carol".to_string(), "Carol".to_string(), UserRole::Owner);
+        let other = UserSession::new("frank".to_string(), "Frank".to_string(), UserRole::Owner);
+        let third = UserSession::new("grace".to_string(), "Grace".to_string(), UserRole::Owner);
+        let mut request = ChangeRequest::new("kernel", &owner, "Drop n1".to_string(), String::new(), vec![remove_node("carol", "n1")], 2).unwrap();
+        assert!(request.review(&owner, ReviewVerdict::Approve, String::new()).is_err());
+        
+        request.review(&other, ReviewVerdict::Approve, String::new()).unwrap();
+        assert_eq!(request.status, ChangeRequestStatus::Open);
+        request.review(&third, ReviewVerdict::RequestChanges, String::new()).unwrap();
+        assert_eq!(request.status, ChangeRequestStatus::ChangesRequested);
+        
+        // Only the latest verdict of a reviewer counts
+        request.review(&third, ReviewVerdict::Approve, String::new()).unwrap();
+        assert_eq!(request.status, ChangeRequestStatus::Approved);
+    }
+}
@@ -0,0 +1,115 @@
+use std::net::TcpStream;
+use std::time::Duration;
+
+use tokio_tungstenite::tungstenite::stream::MaybeTlsStream;
+use tokio_tungstenite::tungstenite::{self, connect, Message, WebSocket};
+
+use crate::collaboration::{BatchDecoder, MoveCoalescer, Operation, OperationBatch};
+
+/// Client of a collaboration server
+///
+/// Announces the user on connect, coalesces queued drags before sending them and
+/// rebuilds the delta-compressed batches broadcast by the server.
+pub struct CollaborationClient {
+    /// WebSocket connection
+    socket: WebSocket<MaybeTlsStream<TcpStream>>,
+    
+    /// Operations waiting to be sent
+    outgoing: MoveCoalescer,
+    
+    /// Decoder of the received batches
+    decoder: BatchDecoder,
+}
+
+impl CollaborationClient {
+    /// Connect to a server (`ws://host:port`) as a user
+    pub fn connect(url: &str, user_id: &str) -> Result<Self, String> {
+        let (mut socket, _) = connect(url).map_err(|e| format!("Failed to connect to {}: {}", url, e))?;
+        let hello = serde_json::json!({"type": "hello", "user_id": user_id});
+        socket.send(Message::Text(hello.to_string()))
+            .map_err(|e| format!("Failed to identify to {}: {}", url, e))?;
+        Ok(Self {
+            socket,
+            outgoing: MoveCoalescer::new(),
+            decoder: BatchDecoder::new(),
+        })
+    }
+    
+    /// Queue an operation; consecutive moves of the same node are merged
+    pub fn queue(&mut self, operation: Operation) {
+        self.outgoing.push(operation);
+    }
+    
+    /// Send the queued operations, returning how many were sent
+    pub fn flush(&mut self) -> Result<usize, String> {
+        let operations = self.outgoing.drain();
+        for operation in &operations {
+            let text = serde_json::to_string(operation)
+                .map_err(|e| format!("Failed to serialize operation: {}", e))?;
+            self.socket.write(Message::Text(text)).map_err(|e| format!("Failed to send operation: {}", e))?;
+        }
+        self.socket.flush().map_err(|e| format!("Failed to send operations: {}", e))?;
+        Ok(operations.len())
+    }
+    
+    /// Wait up to `timeout` for a message, returning the operations it carries
+    pub fn receive(&mut self, timeout: Duration) -> Result<Vec<Operation>, String> {
+        if let MaybeTlsStream::Plain(stream) = self.socket.get_mut() {
+            stream.set_read_timeout(Some(timeout.max(Duration::from_millis(1))))
+                .map_err(|e| format!("Failed to set read timeout: {}", e))?;
+        }
+        match self.socket.read() {
+            Ok(Message::Text(text)) => decode_message(&mut self.decoder, &text),
+            Ok(_) => Ok(Vec::new()),
+            Err(tungstenite::Error::Io(e)) if matches!(e.kind(), std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut) => Ok(Vec::new()),
+            Err(e) => Err(format!("Failed to receive operations: {}", e)),
+        }
+    }
+    
+    /// Close the connection
+    pub fn close(mut self) -> Result<(), String> {
+        self.socket.close(None).map_err(|e| format!("Failed to close connection: {}", e))
+    }
+}
+
+/// Operations of a server message: a batch, or a single operation relayed from a client
+fn decode_message(decoder: &mut BatchDecoder, text: &str) -> Result<Vec<Operation>, String> {
+    if serde_json::from_str::<OperationBatch>(text).is_ok() {
+        return decoder.decode(text);
+    }
+    match serde_json::from_str::<Operation>(text) {
+        Ok(operation) => Ok(vec![operation]),
+        // Other messages, such as hellos of other clients, carry no operations
+        Err(_) => Ok(Vec::new()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collaboration::{BatchConfig, OperationBatcher, OperationType};
+    
+    #[test]
+    fn test_decode_server_messages() {
+        let drag = |x: u32| Operation::new("bob".to_string(), format!("move-{}", x), OperationType::UpdateTileProperty, serde_json::json!({
+            "tile_id": "sched",
+            "key": "layout.position",
+            "value": format!("{},40", x),
+            "description": "Round robin scheduler with per-CPU run queues",
+        }));
+        let mut batcher = OperationBatcher::new(BatchConfig::default());
+        let mut decoder = BatchDecoder::new();
+        for x in 0..3 {
+            batcher.push(drag(x));
+            let message = batcher.flush().unwrap();
+            let operations = decode_message(&mut decoder, &message).unwrap();
+            assert_eq!(operations.len(), 1);
+            assert_eq!(operations[0].operation_id, format!("move-{}", x));
+            assert_eq!(operations[0].data, drag(x).data);
+        }
+        
+        let relayed = serde_json::to_string(&drag(7)).unwrap();
+        assert_eq!(decode_message(&mut decoder, &relayed).unwrap()[0].operation_id, "move-7");
+        assert!(decode_message(&mut decoder, r#"{"type":"hello","user_id":"carol"}"#).unwrap().is_empty());
+    }
+}
@@ -1,14 +1,23 @@
 use std::collections::{HashMap, HashSet, VecDeque};
-use std::sync::{Arc, RwLock};
-use std::time::SystemTime;
+use std::sync::{Arc, Mutex, RwLock};
+use std::thread;
+use std::time::{Duration, Instant};
 
 use serde::{Deserialize, Serialize};
 
 use crate::component_manager::visual_node::{NodeCanvas, VisualNode};
+use crate::tile_engine::tile_core::TileGraph;
+use crate::tile_engine::tile_library::TileLibrary;
+use crate::core::event_bus::{self, CollaborationEvent, SystemEvent};
+use crate::core::simulation;
 use crate::collaboration::{
-    ConflictResolutionStrategy, ConflictResult, Operation, OperationType, UserRole,
-    UserSession, WebSocketServer,
+    BandwidthMetrics, BatchConfig, ChangeRequest, CollaborationStore, ConflictResolutionStrategy, ConflictResult, EditLock, EditLockTable,
+    InMemoryStore, Invite, InviteRegistry, JoinOutcome, JoinRequest, LockTarget, Operation,
+    OperationRecord, OperationType, SessionRecord, SessionSummary, StoreCursor, Subsystem,
+    OperationBatcher, ReviewVerdict, UserRole, UserSession, WebSocketServer, DEFAULT_IDLE_TIMEOUT,
+    DEFAULT_REQUIRED_APPROVALS, DEFAULT_STALE_SESSION_TIMEOUT,
 };
+use crate::collaboration::tile_operations;
 
 /// Collaboration manager that handles real-time collaborative editing
 #[derive(Debug)]
@@ -28,9 +37,51 @@ pub struct CollaborationManager {
     /// WebSocket server for real-time communication
     websocket_server: Arc<WebSocketServer>,
     
+    /// Queue batching and delta-compressing broadcast operations
+    batcher: Mutex<OperationBatcher>,
+    
     /// Conflict resolution strategy
     conflict_strategy: ConflictResolutionStrategy,
     
+    /// Tile graph designed in the session
+    tile_graph: Arc<RwLock<TileGraph>>,
+    
+    /// Tile library shared by the session
+    tile_library: Arc<RwLock<TileLibrary>>,
+    
+    /// Conflict strategy per subsystem, overriding `conflict_strategy`
+    subsystem_strategies: HashMap<Subsystem, ConflictResolutionStrategy>,
+    
+    /// Edit locks on nodes and property groups
+    edit_locks: Arc<RwLock<EditLockTable>>,
+    
+    /// Invite tokens and the lobby of users waiting for approval
+    invites: RwLock<InviteRegistry>,
+    
+    /// Time after which inactive sessions are closed
+    idle_timeout: Duration,
+    
+    /// Owner approvals needed by new change requests
+    required_approvals: usize,
+    
+    /// State shared with the other server instances
+    store: Arc<dyn CollaborationStore>,
+    
+    /// ID of this server instance
+    instance_id: String,
+    
+    /// Sequence of the last operation this instance wrote to the store
+    ///
+    /// Held while the operation is written, so the store receives the sequences of
+    /// this instance in order and a cursor never skips a slower write.
+    instance_sequence: Mutex<u64>,
+    
+    /// Time after which sessions of instances without heartbeat are removed
+    stale_session_timeout: Duration,
+    
+    /// Operations of other instances already applied
+    store_cursor: RwLock<StoreCursor>,
+    
     /// Project ID
     project_id: String,
 }
@@ -38,6 +89,14 @@ pub struct CollaborationManager {
 impl CollaborationManager {
     /// Create a new collaboration manager
     pub fn new(project_id: String, initial_canvas: NodeCanvas) -> Self {
+        Self::with_store(project_id, initial_canvas, Arc::new(InMemoryStore::new()), uuid::Uuid::new_v4().to_string())
+    }
+    
+    /// Create a collaboration manager sharing its state with other instances through a store
+    ///
+    /// Operations already in the store are replayed onto the initial canvas, so a new
+    /// instance (e.g. after a failover) starts from the current project state.
+    pub fn with_store(project_id: String, initial_canvas: NodeCanvas, store: Arc<dyn CollaborationStore>, instance_id: String) -> Self {
         let sessions = Arc::new(RwLock::new(HashMap::new()));
         let canvas_state = Arc::new(RwLock::new(initial_canvas));
         let operation_history = Arc::new(RwLock::new(VecDeque::new()));
@@ -49,10 +108,30 @@ impl CollaborationManager {
             operation_history,
             max_history_size: 1000,
             websocket_server,
+            batcher: Mutex::new(OperationBatcher::new(BatchConfig::default())),
             conflict_strategy: ConflictResolutionStrategy::OperationalTransformation,
+            tile_graph: Arc::new(RwLock::new(TileGraph::new(project_id.clone()))),
+            tile_library: Arc::new(RwLock::new(TileLibrary::new(project_id.clone(), "Shared tile library".to_string()))),
+            subsystem_strategies: [Subsystem::TileGraph, Subsystem::TileLibrary].into_iter()
+                .map(|subsystem| (subsystem, subsystem.default_strategy()))
+                .collect(),
+            edit_locks: Arc::new(RwLock::new(EditLockTable::default())),
+            invites: RwLock::new(InviteRegistry::default()),
+            idle_timeout: DEFAULT_IDLE_TIMEOUT,
+            required_approvals: DEFAULT_REQUIRED_APPROVALS,
+            store,
+            instance_id,
+            instance_sequence: Mutex::new(0),
+            stale_session_timeout: DEFAULT_STALE_SESSION_TIMEOUT,
+            store_cursor: RwLock::new(StoreCursor::default()),
             project_id,
         };
         
+        // Catch up with the operations of the other instances
+        if let Err(e) = manager.sync_from_store() {
+            eprintln!("Failed to replay shared collaboration state: {}", e);
+        }
+        
         // Start WebSocket server
         manager.websocket_server.start();
         
@@ -63,8 +142,17 @@ impl CollaborationManager {
     pub fn add_session(&self, user_id: String, username: String, role: UserRole) -> UserSession {
         let session = UserSession::new(user_id.clone(), username, role);
         
-        let mut sessions = self.sessions.write().unwrap();
+        let mut sessions = simulation::write(&self.sessions);
         sessions.insert(user_id.clone(), session.clone());
+        drop(sessions);
+        
+        // The new client has no payloads to apply deltas to
+        self.flush_broadcasts();
+        simulation::lock(&self.batcher).reset_deltas();
+        
+        if let Err(e) = self.store.put_session(&self.session_record(&session)) {
+            eprintln!("Failed to share session {}: {}", user_id, e);
+        }
         
         // Broadcast user joined event
         let operation = Operation::new(
@@ -80,8 +168,16 @@ impl CollaborationManager {
     
     /// Remove a user session
     pub fn remove_session(&self, user_id: &str) {
-        let mut sessions = self.sessions.write().unwrap();
+        let mut sessions = simulation::write(&self.sessions);
         if let Some(session) = sessions.remove(user_id) {
+            if let Err(e) = self.store.remove_session(&self.project_id, user_id) {
+                eprintln!("Failed to remove shared session {}: {}", user_id, e);
+            }
+            
+            // Free the nodes the user was editing
+            let released = simulation::write(&self.edit_locks).release_all(user_id);
+            self.broadcast_lock_changes(user_id, OperationType::LockReleased, &released);
+            
             // Broadcast user left event
             let operation = Operation::new(
                 user_id.to_string(),
@@ -100,26 +196,449 @@ impl CollaborationManager {
             return Err("Invalid operation".to_string());
         }
         
+        if let Some(session) = simulation::read(&self.sessions).get(&operation.user_id) {
+            session.update_last_active();
+        }
+        
+        // Reject edits of nodes locked by someone else
+        if let Some(target) = Self::edited_target(&operation) {
+            simulation::read(&self.edit_locks).check_edit(&target, &operation.user_id)?;
+        }
+        
+        // Reject tile edits that lost against a concurrent edit
+        if matches!(operation.operation_type.subsystem(), Subsystem::TileGraph | Subsystem::TileLibrary) {
+            let strategy = self.strategy_for(operation.operation_type.subsystem());
+            let graph = simulation::read(&self.tile_graph);
+            let library = simulation::read(&self.tile_library);
+            tile_operations::check_tile_conflict(&graph, &library, &operation, &strategy)?;
+        }
+        
         // Resolve conflicts
         let resolved_operation = self.resolve_conflicts(operation);
         
         // Apply operation to canvas
         self.apply_operation(&resolved_operation)?;
         
+        self.share_operation(resolved_operation)
+    }
+    
+    /// Publish, store, record and broadcast an applied operation
+    fn share_operation(&self, operation: Operation) -> Result<(), String> {
+        // Let panels and plugins observe the applied operation
+        event_bus::publish(SystemEvent::Collaboration(CollaborationEvent {
+            operation_id: operation.operation_id.clone(),
+            user_id: operation.user_id.clone(),
+            operation: format!("{:?}", operation.operation_type),
+        }));
+        
+        // Share with the other instances
+        {
+            let mut sequence = simulation::lock(&self.instance_sequence);
+            let record = OperationRecord {
+                project_id: self.project_id.clone(),
+                instance_id: self.instance_id.clone(),
+                instance_sequence: *sequence + 1,
+                operation: operation.clone(),
+            };
+            self.store.append_operation(&record)?;
+            *sequence += 1;
+            simulation::write(&self.store_cursor).advance(&record);
+        }
+        
         // Add to history
-        self.add_to_history(resolved_operation.clone());
+        self.add_to_history(operation.clone());
         
         // Broadcast operation to all users
-        self.broadcast_operation(resolved_operation);
+        self.broadcast_operation(operation);
         
         Ok(())
     }
     
+    /// Propose canvas and tile operations for review instead of applying them
+    pub fn propose_changes(&self, user_id: &str, title: String, description: String, operations: Vec<Operation>) -> Result<ChangeRequest, String> {
+        let session = simulation::read(&self.sessions).get(user_id).cloned()
+            .ok_or_else(|| format!("Unknown user: {}", user_id))?;
+        let request = ChangeRequest::new(&self.project_id, &session, title, description, operations, self.required_approvals)?;
+        self.save_change_request(user_id, &request)?;
+        Ok(request)
+    }
+    
+    /// Approve a change request or ask for changes; requires owner permission
+    pub fn review_change_request(&self, user_id: &str, request_id: &str, verdict: ReviewVerdict, comment: String) -> Result<ChangeRequest, String> {
+        let session = simulation::read(&self.sessions).get(user_id).cloned()
+            .ok_or_else(|| format!("Unknown user: {}", user_id))?;
+        let mut request = self.get_change_request(request_id)?;
+        request.review(&session, verdict, comment)?;
+        self.save_change_request(user_id, &request)?;
+        Ok(request)
+    }
+    
+    /// Replace the operations of a change request, starting a new review round
+    pub fn revise_change_request(&self, user_id: &str, request_id: &str, operations: Vec<Operation>) -> Result<ChangeRequest, String> {
+        let mut request = self.get_change_request(request_id)?;
+        request.revise(user_id, operations)?;
+        self.save_change_request(user_id, &request)?;
+        Ok(request)
+    }
+    
+    /// Withdraw a change request
+    pub fn withdraw_change_request(&self, user_id: &str, request_id: &str) -> Result<ChangeRequest, String> {
+        let mut request = self.get_change_request(request_id)?;
+        request.withdraw(user_id)?;
+        self.save_change_request(user_id, &request)?;
+        Ok(request)
+    }
+    
+    /// Apply an approved change request atomically; requires write permission
+    ///
+    /// The operations are applied to copies of the canvas, tile graph and tile library,
+    /// which replace the shared state only if every operation succeeded. Applied
+    /// operations are then shared like directly made edits.
+    pub fn apply_change_request(&self, user_id: &str, request_id: &str) -> Result<ChangeRequest, String> {
+        let session = simulation::read(&self.sessions).get(user_id).cloned()
+            .ok_or_else(|| format!("Unknown user: {}", user_id))?;
+        if !session.has_write_permission() {
+            return Err(format!("{} has no write permission", session.username));
+        }
+        let mut request = self.get_change_request(request_id)?;
+        request.check_applicable()?;
+        
+        let operations: Vec<Operation> = request.operations.iter().cloned()
+            .map(|mut operation| {
+                operation.timestamp = current_timestamp();
+                operation
+            })
+            .collect();
+        {
+            let locks = simulation::read(&self.edit_locks);
+            for target in operations.iter().filter_map(Self::edited_target) {
+                locks.check_edit(&target, user_id)?;
+            }
+        }
+        
+        {
+            let mut graph = simulation::write(&self.tile_graph);
+            let mut library = simulation::write(&self.tile_library);
+            let mut canvas = simulation::write(&self.canvas_state);
+            let (mut next_graph, mut next_library, mut next_canvas) = (graph.clone(), library.clone(), canvas.clone());
+            for operation in &operations {
+                let result = if matches!(operation.operation_type.subsystem(), Subsystem::TileGraph | Subsystem::TileLibrary) {
+                    let strategy = self.strategy_for(operation.operation_type.subsystem());
+                    tile_operations::check_tile_conflict(&next_graph, &next_library, operation, &strategy)
+                        .and_then(|_| tile_operations::apply_tile_operation(&mut next_graph, &mut next_library, operation))
+                } else {
+                    apply_canvas_operation(&mut next_canvas, operation)
+                };
+                result.map_err(|e| format!("Change request {} not applied: operation {} failed: {}", request_id, operation.operation_id, e))?;
+            }
+            *graph = next_graph;
+            *library = next_library;
+            *canvas = next_canvas;
+        }
+        
+        for operation in operations {
+            self.share_operation(operation)?;
+        }
+        request.mark_applied(user_id);
+        self.save_change_request(user_id, &request)?;
+        Ok(request)
+    }
+    
+    /// Get the change requests of the project, oldest first
+    pub fn list_change_requests(&self) -> Result<Vec<ChangeRequest>, String> {
+        self.store.change_requests(&self.project_id)
+    }
+    
+    /// Get a change request by ID
+    pub fn get_change_request(&self, request_id: &str) -> Result<ChangeRequest, String> {
+        self.store.change_requests(&self.project_id)?.into_iter()
+            .find(|request| request.request_id == request_id)
+            .ok_or_else(|| format!("Unknown change request: {}", request_id))
+    }
+    
+    /// Set the owner approvals needed by new change requests
+    pub fn set_required_approvals(&mut self, required_approvals: usize) {
+        self.required_approvals = required_approvals.max(1);
+    }
+    
+    /// Store a change request and tell the clients about it
+    fn save_change_request(&self, user_id: &str, request: &ChangeRequest) -> Result<(), String> {
+        self.store.put_change_request(request)?;
+        let operation = Operation::new(
+            user_id.to_string(),
+            uuid::Uuid::new_v4().to_string(),
+            OperationType::ChangeRequestUpdated,
+            serde_json::to_value(request).unwrap(),
+        );
+        self.broadcast_operation(operation);
+        Ok(())
+    }
+    
+    /// Create an invite link granting a role; requires admin permission
+    pub fn create_invite(&self, user_id: &str, role: UserRole, ttl: Duration, requires_approval: bool) -> Result<String, String> {
+        let session = self.admin_session(user_id)?;
+        if role == UserRole::Owner && !session.has_owner_permission() {
+            return Err("Only owners can invite owners".to_string());
+        }
+        let (_, token) = simulation::read(&self.invites).create(&self.project_id, role, user_id, ttl, requires_approval);
+        Ok(Invite::link(&self.project_id, &token))
+    }
+    
+    /// Revoke an invite token; requires admin permission
+    pub fn revoke_invite(&self, user_id: &str, token: &str) -> Result<(), String> {
+        self.admin_session(user_id)?;
+        let mut invites = simulation::write(&self.invites);
+        let invite = invites.verify(&self.project_id, token)?;
+        invites.revoke(&invite);
+        Ok(())
+    }
+    
+    /// Join the session with an invite token
+    ///
+    /// Users invited without approval join immediately; the others wait in the lobby
+    /// until an admin approves them.
+    pub fn join_with_invite(&self, token: &str, user_id: String, username: String) -> Result<JoinOutcome, String> {
+        let outcome = simulation::write(&self.invites).redeem(&self.project_id, token, &user_id, &username)?;
+        if let JoinOutcome::Admitted(role) = &outcome {
+            self.add_session(user_id, username, role.clone());
+        }
+        Ok(outcome)
+    }
+    
+    /// Let a user waiting in the lobby join; requires admin permission
+    pub fn approve_join(&self, user_id: &str, request_id: &str) -> Result<UserSession, String> {
+        self.admin_session(user_id)?;
+        let request = simulation::write(&self.invites).take_request(request_id)?;
+        Ok(self.add_session(request.user_id, request.username, request.role))
+    }
+    
+    /// Turn away a user waiting in the lobby; requires admin permission
+    pub fn reject_join(&self, user_id: &str, request_id: &str) -> Result<JoinRequest, String> {
+        self.admin_session(user_id)?;
+        simulation::write(&self.invites).take_request(request_id)
+    }
+    
+    /// Get the users waiting in the lobby
+    pub fn get_pending_joins(&self) -> Vec<JoinRequest> {
+        simulation::read(&self.invites).pending_requests()
+    }
+    
+    /// Use the invite signing secret stored in a file, creating it if needed
+    ///
+    /// Invites issued before a restart stay valid. Open invites of the previous
+    /// registry and users waiting in its lobby are dropped.
+    pub fn use_invite_secret_file<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), String> {
+        *simulation::write(&self.invites) = InviteRegistry::from_secret_file(path)?;
+        Ok(())
+    }
+    
+    /// Set the time after which inactive sessions are closed
+    pub fn set_idle_timeout(&mut self, idle_timeout: Duration) {
+        self.idle_timeout = idle_timeout;
+    }
+    
+    /// Close the sessions inactive for longer than the idle timeout, returning their user IDs
+    pub fn expire_idle_sessions(&self) -> Vec<String> {
+        let idle: Vec<String> = simulation::read(&self.sessions).values()
+            .filter(|session| session.last_active.read().unwrap().elapsed().map_or(false, |idle| idle > self.idle_timeout))
+            .map(|session| session.user_id.clone())
+            .collect();
+        for user_id in &idle {
+            self.close_session(user_id);
+        }
+        idle
+    }
+    
+    /// Record the WebSocket connections of users who identified themselves
+    ///
+    /// The connection is dropped when the user's session is closed.
+    pub fn attach_connections(&self) {
+        let connections = self.websocket_server.identified_clients();
+        let mut sessions = simulation::write(&self.sessions);
+        for (user_id, connection_id) in connections {
+            if let Some(session) = sessions.get_mut(&user_id) {
+                if session.connection_id.as_deref() != Some(connection_id.as_str()) {
+                    session.set_connection_id(connection_id);
+                }
+            }
+        }
+    }
+    
+    /// Set the time after which sessions of instances without heartbeat are removed
+    ///
+    /// Must exceed the maintenance interval, which is how often heartbeats are sent.
+    pub fn set_stale_session_timeout(&mut self, timeout: Duration) {
+        self.stale_session_timeout = timeout;
+    }
+    
+    /// Periodic upkeep: send due broadcasts, attach new connections, close idle sessions
+    /// and keep in step with the other instances sharing the store
+    pub fn tick(&self) {
+        self.flush_due_broadcasts();
+        self.attach_connections();
+        let expired = self.expire_idle_sessions();
+        if !expired.is_empty() {
+            log::info!("Closed {} idle collaboration session(s)", expired.len());
+        }
+        
+        if let Err(e) = self.sync_from_store() {
+            log::warn!("Failed to sync collaboration operations from the store: {}", e);
+        }
+        if let Err(e) = self.heartbeat() {
+            log::warn!("Failed to refresh collaboration session heartbeats: {}", e);
+        }
+        match self.expire_stale_sessions(self.stale_session_timeout) {
+            Ok(stale) if !stale.is_empty() => log::info!("Removed {} stale collaboration session(s)", stale.len()),
+            Ok(_) => {}
+            Err(e) => log::warn!("Failed to expire stale collaboration sessions: {}", e),
+        }
+    }
+    
+    /// Run `tick` every `interval` on a background thread until the manager is dropped
+    ///
+    /// Between ticks the thread wakes up every batch flush interval to send the
+    /// queued broadcasts, so quiet periods do not delay them.
+    pub fn start_maintenance(manager: &Arc<Self>, interval: Duration) -> thread::JoinHandle<()> {
+        let manager = Arc::downgrade(manager);
+        thread::spawn(move || {
+            let mut last_tick = Instant::now();
+            loop {
+                let Some(flush_interval) = manager.upgrade()
+                    .map(|manager| simulation::lock(&manager.batcher).config().flush_interval) else { break };
+                thread::sleep(flush_interval.min(interval));
+                let Some(manager) = manager.upgrade() else { break };
+                if last_tick.elapsed() >= interval {
+                    manager.tick();
+                    last_tick = Instant::now();
+                } else {
+                    manager.flush_due_broadcasts();
+                }
+            }
+        })
+    }
+    
+    /// List the active sessions of this instance for the dashboard
+    pub fn list_sessions(&self) -> Vec<SessionSummary> {
+        let mut summaries: Vec<SessionSummary> = simulation::read(&self.sessions).values()
+            .map(|session| SessionSummary {
+                user_id: session.user_id.clone(),
+                username: session.username.clone(),
+                role: session.role.clone(),
+                instance_id: self.instance_id.clone(),
+                connected_secs: session.session_start.elapsed().map(|elapsed| elapsed.as_secs()).unwrap_or(0),
+                idle_secs: session.last_active.read().unwrap().elapsed().map(|elapsed| elapsed.as_secs()).unwrap_or(0),
+            })
+            .collect();
+        summaries.sort_by(|a, b| a.username.cmp(&b.username));
+        summaries
+    }
+    
+    /// Terminate another user's session; requires admin permission and owners can only be removed by owners
+    pub fn terminate_session(&self, user_id: &str, target_user_id: &str) -> Result<(), String> {
+        let session = self.admin_session(user_id)?;
+        let target_role = simulation::read(&self.sessions).get(target_user_id).map(|target| target.role.clone())
+            .ok_or_else(|| format!("Unknown user: {}", target_user_id))?;
+        if target_role == UserRole::Owner && !session.has_owner_permission() {
+            return Err("Only owners can remove owners".to_string());
+        }
+        self.close_session(target_user_id);
+        Ok(())
+    }
+    
+    /// Remove a session and drop its connection
+    fn close_session(&self, user_id: &str) {
+        let connection_id = simulation::read(&self.sessions).get(user_id).and_then(|session| session.connection_id.clone());
+        self.remove_session(user_id);
+        if let Some(connection_id) = connection_id {
+            self.websocket_server.disconnect(&connection_id);
+        }
+    }
+    
+    /// Session of a user with admin permission
+    fn admin_session(&self, user_id: &str) -> Result<UserSession, String> {
+        let session = simulation::read(&self.sessions).get(user_id).cloned()
+            .ok_or_else(|| format!("Unknown user: {}", user_id))?;
+        if !session.has_admin_permission() {
+            return Err(format!("{} has no admin permission", session.username));
+        }
+        Ok(session)
+    }
+    
+    /// Apply the operations other instances wrote to the store since the last sync
+    ///
+    /// Call periodically; returns the number of operations applied.
+    pub fn sync_from_store(&self) -> Result<usize, String> {
+        let cursor = simulation::read(&self.store_cursor).clone();
+        let records = self.store.operations_since(&self.project_id, &cursor)?;
+        
+        let mut applied = 0;
+        for record in records {
+            simulation::write(&self.store_cursor).advance(&record);
+            if record.instance_id == self.instance_id {
+                continue;
+            }
+            if let Err(e) = self.apply_operation(&record.operation) {
+                eprintln!("Failed to apply operation {} from instance {}: {}", record.operation.operation_id, record.instance_id, e);
+                continue;
+            }
+            self.add_to_history(record.operation.clone());
+            self.broadcast_operation(record.operation);
+            applied += 1;
+        }
+        Ok(applied)
+    }
+    
+    /// Refresh the heartbeat of the sessions hosted by this instance
+    pub fn heartbeat(&self) -> Result<(), String> {
+        for session in simulation::read(&self.sessions).values() {
+            self.store.put_session(&self.session_record(session))?;
+        }
+        Ok(())
+    }
+    
+    /// Remove sessions whose instance stopped sending heartbeats, returning their user IDs
+    pub fn expire_stale_sessions(&self, timeout: Duration) -> Result<Vec<String>, String> {
+        let cutoff = current_timestamp().saturating_sub(timeout.as_millis() as u64);
+        let mut expired = Vec::new();
+        for record in self.store.sessions(&self.project_id)? {
+            if record.heartbeat < cutoff {
+                self.store.remove_session(&self.project_id, &record.user_id)?;
+                expired.push(record.user_id);
+            }
+        }
+        Ok(expired)
+    }
+    
+    /// Get the sessions of all instances
+    pub fn get_cluster_sessions(&self) -> Result<Vec<SessionRecord>, String> {
+        self.store.sessions(&self.project_id)
+    }
+    
+    /// Get the ID of this server instance
+    pub fn instance_id(&self) -> &str {
+        &self.instance_id
+    }
+    
+    /// Shared record of a local session
+    fn session_record(&self, session: &UserSession) -> SessionRecord {
+        SessionRecord {
+            project_id: self.project_id.clone(),
+            user_id: session.user_id.clone(),
+            username: session.username.clone(),
+            role: session.role.clone(),
+            instance_id: self.instance_id.clone(),
+            heartbeat: current_timestamp(),
+        }
+    }
+    
     /// Validate an operation
     fn validate_operation(&self, operation: &Operation) -> bool {
-        // Check if user exists
-        let sessions = self.sessions.read().unwrap();
-        if !sessions.contains_key(&operation.user_id) {
+        // Check if user exists, possibly connected to another instance
+        let known_locally = simulation::read(&self.sessions).contains_key(&operation.user_id);
+        let known = known_locally || self.store.sessions(&self.project_id)
+            .map(|records| records.iter().any(|record| record.user_id == operation.user_id))
+            .unwrap_or(false);
+        if !known {
             return false;
         }
         
@@ -134,7 +653,80 @@ impl CollaborationManager {
             OperationType::UserJoined | 
             OperationType::UserLeft | 
             OperationType::CursorMove | 
-            OperationType::SelectionChange)
+            OperationType::SelectionChange |
+            OperationType::LockAcquired |
+            OperationType::LockReleased |
+            OperationType::AddTile |
+            OperationType::RemoveTile |
+            OperationType::UpdateTileProperty |
+            OperationType::AddTileConnection |
+            OperationType::RemoveTileConnection |
+            OperationType::AddLibraryTile |
+            OperationType::UpdateLibraryTile |
+            OperationType::PublishTileVersion)
+    }
+    
+    /// Node or tile modified by an operation
+    fn edited_target(operation: &Operation) -> Option<LockTarget> {
+        let node_id = match operation.operation_type {
+            OperationType::RemoveNode => operation.data.as_str(),
+            OperationType::UpdateNode => operation.data.get(0).and_then(|value| value.as_str()),
+            _ => return tile_operations::edited_tile_target(operation),
+        }?;
+        Some(LockTarget::Node(node_id.to_string()))
+    }
+    
+    /// Lock a node or property group for a user
+    pub fn acquire_lock(&self, user_id: &str, target: LockTarget) -> Result<EditLock, String> {
+        let session = simulation::read(&self.sessions).get(user_id).cloned()
+            .ok_or_else(|| format!("Unknown user: {}", user_id))?;
+        let lock = simulation::write(&self.edit_locks).acquire(target, &session)?;
+        self.broadcast_lock_changes(user_id, OperationType::LockAcquired, std::slice::from_ref(&lock));
+        Ok(lock)
+    }
+    
+    /// Take over a lock held by someone else (owners only)
+    pub fn steal_lock(&self, user_id: &str, target: LockTarget) -> Result<EditLock, String> {
+        let session = simulation::read(&self.sessions).get(user_id).cloned()
+            .ok_or_else(|| format!("Unknown user: {}", user_id))?;
+        let (lock, stolen) = simulation::write(&self.edit_locks).steal(target, &session)?;
+        self.broadcast_lock_changes(user_id, OperationType::LockReleased, &stolen);
+        self.broadcast_lock_changes(user_id, OperationType::LockAcquired, std::slice::from_ref(&lock));
+        Ok(lock)
+    }
+    
+    /// Release a lock held by a user
+    pub fn release_lock(&self, user_id: &str, target: &LockTarget) -> Option<EditLock> {
+        let lock = simulation::write(&self.edit_locks).release(target, user_id)?;
+        self.broadcast_lock_changes(user_id, OperationType::LockReleased, std::slice::from_ref(&lock));
+        Some(lock)
+    }
+    
+    /// Drop expired locks and tell the clients
+    pub fn expire_locks(&self) -> Vec<EditLock> {
+        let expired = simulation::write(&self.edit_locks).expire();
+        for lock in &expired {
+            self.broadcast_lock_changes(&lock.holder, OperationType::LockReleased, std::slice::from_ref(lock));
+        }
+        expired
+    }
+    
+    /// Get the active edit locks
+    pub fn get_edit_locks(&self) -> Vec<EditLock> {
+        simulation::read(&self.edit_locks).locks()
+    }
+    
+    /// Broadcast lock changes through the sync protocol
+    fn broadcast_lock_changes(&self, user_id: &str, operation_type: OperationType, locks: &[EditLock]) {
+        for lock in locks {
+            let operation = Operation::new(
+                user_id.to_string(),
+                uuid::Uuid::new_v4().to_string(),
+                operation_type.clone(),
+                serde_json::to_value(lock).unwrap(),
+            );
+            self.broadcast_operation(operation);
+        }
     }
     
     /// Resolve conflicts using the configured strategy
@@ -159,61 +751,26 @@ impl CollaborationManager {
         }
     }
     
-    /// Apply an operation to the canvas
+    /// Conflict strategy of a subsystem
+    fn strategy_for(&self, subsystem: Subsystem) -> ConflictResolutionStrategy {
+        self.subsystem_strategies.get(&subsystem).cloned().unwrap_or_else(|| self.conflict_strategy.clone())
+    }
+    
+    /// Apply an operation to the canvas, tile graph or tile library
     fn apply_operation(&self, operation: &Operation) -> Result<(), String> {
-        let mut canvas = self.canvas_state.write().unwrap();
-        
-        match operation.operation_type {
-            OperationType::AddNode => {
-                let node: VisualNode = serde_json::from_value(operation.data.clone())
-                    .map_err(|e| format!("Failed to deserialize node: {}", e))?;
-                canvas.add_node(node);
-            }
-            OperationType::RemoveNode => {
-                let node_id: String = serde_json::from_value(operation.data.clone())
-                    .map_err(|e| format!("Failed to deserialize node ID: {}", e))?;
-                canvas.remove_node(&node_id);
-            }
-            OperationType::UpdateNode => {
-                let update_data: (String, VisualNode) = 
-                    serde_json::from_value(operation.data.clone())
-                    .map_err(|e| format!("Failed to deserialize update data: {}", e))?;
-                let (node_id, updated_node) = update_data;
-                
-                // Find and update the node
-                if let Some(node) = canvas.nodes.get_mut(&node_id) {
-                    *node = updated_node;
-                    canvas.update_dag_properties();
-                }
-            }
-            OperationType::AddConnection => {
-                let connection: crate::component_manager::visual_node::NodeConnection = 
-                    serde_json::from_value(operation.data.clone())
-                    .map_err(|e| format!("Failed to deserialize connection: {}", e))?;
-                canvas.add_connection(connection)?;
-            }
-            OperationType::RemoveConnection => {
-                let connection_id: String = serde_json::from_value(operation.data.clone())
-                    .map_err(|e| format!("Failed to deserialize connection ID: {}", e))?;
-                canvas.remove_connection(&connection_id);
-            }
-            OperationType::UpdateCanvas => {
-                let canvas_update: NodeCanvas = serde_json::from_value(operation.data.clone())
-                    .map_err(|e| format!("Failed to deserialize canvas update: {}", e))?;
-                *canvas = canvas_update;
-            }
-            _ => {
-                // User events don't modify the canvas
-                return Ok(());
-            }
+        if matches!(operation.operation_type.subsystem(), Subsystem::TileGraph | Subsystem::TileLibrary) {
+            let mut graph = simulation::write(&self.tile_graph);
+            let mut library = simulation::write(&self.tile_library);
+            return tile_operations::apply_tile_operation(&mut graph, &mut library, operation);
         }
         
-        Ok(())
+        let mut canvas = simulation::write(&self.canvas_state);
+        apply_canvas_operation(&mut canvas, operation)
     }
     
     /// Add operation to history
     fn add_to_history(&self, operation: Operation) {
-        let mut history = self.operation_history.write().unwrap();
+        let mut history = simulation::write(&self.operation_history);
         
         // Add to history
         history.push_back(operation);
@@ -226,26 +783,76 @@ impl CollaborationManager {
     
     /// Broadcast operation to all users
     fn broadcast_operation(&self, operation: Operation) {
-        // Serialize operation
-        let serialized = serde_json::to_string(&operation).unwrap();
+        // Queue the operation; the batch is sent once full or the flush interval passed
+        let batch = simulation::lock(&self.batcher).push(operation);
         
         // Broadcast to all connected clients
-        self.websocket_server.broadcast(serialized);
+        if let Some(batch) = batch {
+            self.websocket_server.broadcast(batch);
+        }
+    }
+    
+    /// Send the queued operations if the flush interval has passed
+    ///
+    /// Called by the maintenance thread once per flush interval, so queued
+    /// operations are not delayed by quiet periods.
+    pub fn flush_due_broadcasts(&self) {
+        let mut batcher = simulation::lock(&self.batcher);
+        if batcher.is_due() {
+            if let Some(batch) = batcher.flush() {
+                self.websocket_server.broadcast(batch);
+            }
+        }
+    }
+    
+    /// Send the queued operations now
+    pub fn flush_broadcasts(&self) {
+        let batch = simulation::lock(&self.batcher).flush();
+        if let Some(batch) = batch {
+            self.websocket_server.broadcast(batch);
+        }
+    }
+    
+    /// Set the batching of broadcast operations
+    pub fn set_batch_config(&self, config: BatchConfig) {
+        self.flush_broadcasts();
+        simulation::lock(&self.batcher).set_config(config);
+    }
+    
+    /// Get the bandwidth used by broadcasts
+    pub fn get_bandwidth_metrics(&self) -> BandwidthMetrics {
+        simulation::lock(&self.batcher).metrics().clone()
     }
     
     /// Get current canvas state
     pub fn get_canvas_state(&self) -> NodeCanvas {
-        self.canvas_state.read().unwrap().clone()
+        simulation::read(&self.canvas_state).clone()
+    }
+    
+    /// Get current tile graph
+    pub fn get_tile_graph(&self) -> TileGraph {
+        simulation::read(&self.tile_graph).clone()
+    }
+    
+    /// Get the shared tile library
+    pub fn get_tile_library(&self) -> Arc<RwLock<TileLibrary>> {
+        self.tile_library.clone()
+    }
+    
+    /// Replace the tile graph and library edited in the session
+    pub fn set_tile_design(&self, graph: TileGraph, library: TileLibrary) {
+        *simulation::write(&self.tile_graph) = graph;
+        *simulation::write(&self.tile_library) = library;
     }
     
     /// Get active user sessions
     pub fn get_active_sessions(&self) -> HashMap<String, UserSession> {
-        self.sessions.read().unwrap().clone()
+        simulation::read(&self.sessions).clone()
     }
     
     /// Get operation history
     pub fn get_operation_history(&self) -> VecDeque<Operation> {
-        self.operation_history.read().unwrap().clone()
+        simulation::read(&self.operation_history).clone()
     }
     
     /// Set conflict resolution strategy
@@ -253,8 +860,71 @@ impl CollaborationManager {
         self.conflict_strategy = strategy;
     }
     
+    /// Set the conflict resolution strategy of a subsystem
+    pub fn set_subsystem_strategy(&mut self, subsystem: Subsystem, strategy: ConflictResolutionStrategy) {
+        self.subsystem_strategies.insert(subsystem, strategy);
+    }
+    
     /// Shutdown the collaboration manager
     pub fn shutdown(&self) {
+        self.flush_broadcasts();
         self.websocket_server.stop();
     }
 }
+
+/// Apply a canvas operation; operations of other subsystems leave the canvas unchanged
+fn apply_canvas_operation(canvas: &mut NodeCanvas, operation: &Operation) -> Result<(), String> {
+    match operation.operation_type {
+        OperationType::AddNode => {
+            let node: VisualNode = serde_json::from_value(operation.data.clone())
+                .map_err(|e| format!("Failed to deserialize node: {}", e))?;
+            canvas.add_node(node).map_err(|e| e.to_string())?;
+        }
+        OperationType::RemoveNode => {
+            let node_id: String = serde_json::from_value(operation.data.clone())
+                .map_err(|e| format!("Failed to deserialize node ID: {}", e))?;
+            canvas.remove_node(&node_id).map_err(|e| e.to_string())?;
+        }
+        OperationType::UpdateNode => {
+            let update_data: (String, VisualNode) = 
+                serde_json::from_value(operation.data.clone())
+                .map_err(|e| format!("Failed to deserialize update data: {}", e))?;
+            let (node_id, updated_node) = update_data;
+            
+            // Find and update the node
+            if let Some(node) = canvas.nodes.get_mut(&node_id) {
+                *node = updated_node;
+                canvas.refresh_node_bounds(&node_id);
+                canvas.update_dag_properties();
+            }
+        }
+        OperationType::AddConnection => {
+            let connection: crate::component_manager::visual_node::NodeConnection = 
+                serde_json::from_value(operation.data.clone())
+                .map_err(|e| format!("Failed to deserialize connection: {}", e))?;
+            canvas.add_connection(connection).map_err(|e| e.to_string())?;
+        }
+        OperationType::RemoveConnection => {
+            let connection_id: String = serde_json::from_value(operation.data.clone())
+                .map_err(|e| format!("Failed to deserialize connection ID: {}", e))?;
+            canvas.remove_connection(&connection_id).map_err(|e| e.to_string())?;
+        }
+        OperationType::UpdateCanvas => {
+            let canvas_update: NodeCanvas = serde_json::from_value(operation.data.clone())
+                .map_err(|e| format!("Failed to deserialize canvas update: {}", e))?;
+            *canvas = canvas_update;
+            canvas.rebuild_spatial_index();
+        }
+        _ => {
+            // User events don't modify the canvas
+            return Ok(());
+        }
+    }
+    
+    Ok(())
+}
+
+/// Get current timestamp in milliseconds
+fn current_timestamp() -> u64 {
+    simulation::unix_millis()
+}
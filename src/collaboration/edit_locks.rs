@@ -0,0 +1,232 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::collaboration::{UserRole, UserSession};
+use crate::core::simulation;
+
+/// Default lifetime of an edit lock
+pub const DEFAULT_LOCK_TTL: Duration = Duration::from_secs(120);
+
+/// Part of the design an edit lock protects
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum LockTarget {
+    /// A whole node or tile
+    Node(String),
+    
+    /// A group of properties of a node or tile
+    PropertyGroup {
+        /// Node or tile ID
+        node_id: String,
+        
+        /// Property group name
+        group: String,
+    },
+}
+
+impl LockTarget {
+    /// ID of the locked node
+    pub fn node_id(&self) -> &str {
+        match self {
+            LockTarget::Node(node_id) => node_id,
+            LockTarget::PropertyGroup { node_id, .. } => node_id,
+        }
+    }
+    
+    /// Check if two targets cover the same properties
+    pub fn overlaps(&self, other: &LockTarget) -> bool {
+        match (self, other) {
+            (LockTarget::PropertyGroup { node_id, group }, LockTarget::PropertyGroup { node_id: other_node, group: other_group }) => {
+                node_id == other_node && group == other_group
+            }
+            _ => self.node_id() == other.node_id(),
+        }
+    }
+}
+
+/// Soft lock held by a user while editing
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EditLock {
+    /// Locked target
+    pub target: LockTarget,
+    
+    /// ID of the user holding the lock
+    pub holder: String,
+    
+    /// Display name of the holder
+    pub holder_name: String,
+    
+    /// Role of the holder when the lock was taken
+    pub role: UserRole,
+    
+    /// Time the lock was acquired (milliseconds)
+    pub acquired_at: u64,
+    
+    /// Time the lock expires (milliseconds)
+    pub expires_at: u64,
+}
+
+impl EditLock {
+    /// Check if the lock has expired
+    pub fn is_expired(&self, now: u64) -> bool {
+        now >= self.expires_at
+    }
+}
+
+/// Table of edit locks of a collaboration session
+#[derive(Debug, Clone)]
+pub struct EditLockTable {
+    /// Locks by target
+    locks: HashMap<LockTarget, EditLock>,
+    
+    /// Lifetime of new locks
+    ttl: Duration,
+}
+
+impl Default for EditLockTable {
+    fn default() -> Self {
+        Self::new(DEFAULT_LOCK_TTL)
+    }
+}
+
+impl EditLockTable {
+    /// Create a lock table with the given lock lifetime
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            locks: HashMap::new(),
+            ttl,
+        }
+    }
+    
+    /// Acquire or renew a lock
+    ///
+    /// Fails if another user holds an unexpired lock overlapping the target.
+    pub fn acquire(&mut self, target: LockTarget, session: &UserSession) -> Result<EditLock, String> {
+        let now = current_timestamp();
+        if !session.has_write_permission() {
+            return Err(format!("{} has no write permission", session.username));
+        }
+        if let Some(lock) = self.conflicting_lock(&target, &session.user_id, now) {
+            return Err(format!("{} is locked by {} for {}s",
+                target.node_id(), lock.holder_name, (lock.expires_at - now) / 1000));
+        }
+        
+        let lock = EditLock {
+            target: target.clone(),
+            holder: session.user_id.clone(),
+            holder_name: session.username.clone(),
+            role: session.role.clone(),
+            acquired_at: now,
+            expires_at: now + self.ttl.as_millis() as u64,
+        };
+        self.locks.insert(target, lock.clone());
+        Ok(lock)
+    }
+    
+    /// Take over the locks overlapping a target; only owners may steal
+    ///
+    /// Returns the new lock and the locks taken from other users.
+    pub fn steal(&mut self, target: LockTarget, session: &UserSession) -> Result<(EditLock, Vec<EditLock>), String> {
+        if !session.has_owner_permission() {
+            return Err(format!("{} cannot steal edit locks", session.username));
+        }
+        let stolen_targets: Vec<LockTarget> = self.locks.values()
+            .filter(|lock| lock.target.overlaps(&target) && lock.holder != session.user_id)
+            .map(|lock| lock.target.clone())
+            .collect();
+        let stolen = stolen_targets.iter().filter_map(|target| self.locks.remove(target)).collect();
+        let lock = self.acquire(target, session)?;
+        Ok((lock, stolen))
+    }
+    
+    /// Release a lock held by a user
+    pub fn release(&mut self, target: &LockTarget, user_id: &str) -> Option<EditLock> {
+        match self.locks.get(target) {
+            Some(lock) if lock.holder == user_id => self.locks.remove(target),
+            _ => None,
+        }
+    }
+    
+    /// Release every lock of a user (e.g. when they leave)
+    pub fn release_all(&mut self, user_id: &str) -> Vec<EditLock> {
+        let targets: Vec<LockTarget> = self.locks.values()
+            .filter(|lock| lock.holder == user_id)
+            .map(|lock| lock.target.clone())
+            .collect();
+        targets.iter().filter_map(|target| self.locks.remove(target)).collect()
+    }
+    
+    /// Remove expired locks, returning them
+    pub fn expire(&mut self) -> Vec<EditLock> {
+        let now = current_timestamp();
+        let expired: Vec<LockTarget> = self.locks.values()
+            .filter(|lock| lock.is_expired(now))
+            .map(|lock| lock.target.clone())
+            .collect();
+        expired.iter().filter_map(|target| self.locks.remove(target)).collect()
+    }
+    
+    /// Check that a user may edit a target
+    pub fn check_edit(&self, target: &LockTarget, user_id: &str) -> Result<(), String> {
+        match self.conflicting_lock(target, user_id, current_timestamp()) {
+            Some(lock) => Err(format!("{} is being edited by {}", target.node_id(), lock.holder_name)),
+            None => Ok(()),
+        }
+    }
+    
+    /// Get the active locks
+    pub fn locks(&self) -> Vec<EditLock> {
+        let now = current_timestamp();
+        self.locks.values().filter(|lock| !lock.is_expired(now)).cloned().collect()
+    }
+    
+    /// Unexpired lock of another user overlapping a target
+    fn conflicting_lock(&self, target: &LockTarget, user_id: &str, now: u64) -> Option<&EditLock> {
+        self.locks.values().find(|lock| lock.holder != user_id && !lock.is_expired(now) && lock.target.overlaps(target))
+    }
+}
+
+/// Get current timestamp in milliseconds
+fn current_timestamp() -> u64 {
+    simulation::unix_millis()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_edit_locks() {
+        let alice = UserSession::new("alice".to_string(), "Alice".to_string(), UserRole::Editor);
+        let bob = UserSession::new("bob".to_string(), "Bob".to_string(), UserRole::Editor);
+        let owner = UserSession::new("carol".to_string(), "Carol".to_string(), UserRole::Owner);
+        let viewer = UserSession::new("dave".to_string(), "Dave".to_string(), UserRole::Viewer);
+        let mut table = EditLockTable::default();
+        
+        let timing = LockTarget::PropertyGroup { node_id: "sched".to_string(), group: "timing".to_string() };
+        let memory = LockTarget::PropertyGroup { node_id: "sched".to_string(), group: "memory".to_string() };
+        table.acquire(timing.clone(), &alice).unwrap();
+        table.acquire(memory.clone(), &bob).unwrap();
+        assert!(table.acquire(timing.clone(), &alice).is_ok());
+        assert!(table.acquire(timing.clone(), &bob).is_err());
+        assert!(table.acquire(LockTarget::Node("sched".to_string()), &bob).is_err());
+        assert!(table.check_edit(&LockTarget::Node("sched".to_string()), &alice).is_err());
+        assert!(table.acquire(LockTarget::Node("alloc".to_string()), &viewer).is_err());
+        
+        assert!(table.steal(timing.clone(), &bob).is_err());
+        let (lock, stolen) = table.steal(LockTarget::Node("sched".to_string()), &owner).unwrap();
+        assert_eq!(lock.holder, "carol");
+        assert_eq!(stolen.len(), 2);
+        assert!(table.check_edit(&memory, &bob).is_err());
+        
+        assert!(table.release(&LockTarget::Node("sched".to_string()), "bob").is_none());
+        assert_eq!(table.release_all("carol").len(), 1);
+        assert!(table.check_edit(&memory, &bob).is_ok());
+        
+        let mut short = EditLockTable::new(Duration::ZERO);
+        short.acquire(timing.clone(), &alice).unwrap();
+        assert!(short.check_edit(&timing, &bob).is_ok());
+        assert_eq!(short.expire().len(), 1);
+    }
+}
@@ -6,9 +6,28 @@ mod user_session;
 mod operation_sync;
 mod conflict_resolution;
 mod websocket_server;
+mod edit_locks;
+mod state_store;
+mod tile_operations;
+mod session_lifecycle;
+mod batching;
+mod change_requests;
+mod client;
 
 pub use collaboration_manager::CollaborationManager;
 pub use user_session::{UserSession, UserRole};
 pub use operation_sync::{Operation, OperationType};
 pub use conflict_resolution::{ConflictResolutionStrategy, ConflictResult};
 pub use websocket_server::WebSocketServer;
+pub use state_store::{CollaborationStore, InMemoryStore, TablesStore, SessionRecord, OperationRecord, StoreCursor, DEFAULT_STALE_SESSION_TIMEOUT};
+pub use edit_locks::{EditLock, EditLockTable, LockTarget, DEFAULT_LOCK_TTL};
+pub use tile_operations::{Subsystem, TilePropertyEdit, LibraryTileEdit, TileVersionPublish};
+pub use session_lifecycle::{
+    Invite, InviteRegistry, JoinOutcome, JoinRequest, SessionSummary, DEFAULT_IDLE_TIMEOUT, DEFAULT_INVITE_TTL,
+};
+pub use batching::{
+    BandwidthMetrics, BatchConfig, BatchDecoder, DeltaFrame, MoveCoalescer, OperationBatch, OperationBatcher,
+    DEFAULT_FLUSH_INTERVAL,
+};
+pub use client::CollaborationClient;
+pub use change_requests::{ChangeRequest, ChangeRequestStatus, Review, ReviewVerdict, DEFAULT_REQUIRED_APPROVALS};
@@ -1,9 +1,10 @@
 use std::sync::{Arc, RwLock};
-use std::time::{SystemTime, UNIX_EPOCH};
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use crate::core::simulation;
+
 /// Operation type for collaborative editing
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum OperationType {
@@ -36,6 +37,39 @@ pub enum OperationType {
     
     /// User changed selection
     SelectionChange,
+    
+    /// User locked a node or property group for editing
+    LockAcquired,
+    
+    /// Edit lock was released, expired or stolen
+    LockReleased,
+    
+    /// Add a tile to the tile graph
+    AddTile,
+    
+    /// Remove a tile from the tile graph
+    RemoveTile,
+    
+    /// Set or remove a property of a tile in the tile graph
+    UpdateTileProperty,
+    
+    /// Connect two tiles in the tile graph
+    AddTileConnection,
+    
+    /// Remove a connection from the tile graph
+    RemoveTileConnection,
+    
+    /// Add a tile to the shared tile library
+    AddLibraryTile,
+    
+    /// Update a tile of the shared tile library
+    UpdateLibraryTile,
+    
+    /// Publish a new version of a library tile
+    PublishTileVersion,
+    
+    /// A change request was proposed, reviewed, revised, withdrawn or applied
+    ChangeRequestUpdated,
 }
 
 /// Operation that represents a change to the canvas state
@@ -99,10 +133,7 @@ impl Operation {
     
     /// Get current timestamp in milliseconds
     fn get_current_timestamp() -> u64 {
-        SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_millis() as u64
+        simulation::unix_millis()
     }
     
     /// Check if this operation depends on another operation
@@ -0,0 +1,323 @@
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::time::Duration;
+
+use hmac::{Hmac, Mac};
+use rand_core::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use subtle::ConstantTimeEq;
+
+use crate::collaboration::UserRole;
+use crate::core::simulation;
+
+/// Default lifetime of an invite link
+pub const DEFAULT_INVITE_TTL: Duration = Duration::from_secs(7 * 24 * 3600);
+
+/// Default time after which an inactive session is closed
+pub const DEFAULT_IDLE_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// Claims carried by an invite token
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Invite {
+    /// Project the invite is for
+    pub project_id: String,
+    
+    /// Role granted to users joining with the invite
+    pub role: UserRole,
+    
+    /// ID of the user who created the invite
+    pub created_by: String,
+    
+    /// Expiry time (milliseconds)
+    pub expires_at: u64,
+    
+    /// Whether joins wait in the lobby until approved
+    pub requires_approval: bool,
+    
+    /// Random nonce identifying the invite
+    pub nonce: String,
+}
+
+impl Invite {
+    /// Build the shareable link of a token
+    pub fn link(project_id: &str, token: &str) -> String {
+        format!("osland://join/{}?token={}", project_id, token)
+    }
+}
+
+/// User waiting in the lobby for approval
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JoinRequest {
+    /// Request ID
+    pub request_id: String,
+    
+    /// ID of the joining user
+    pub user_id: String,
+    
+    /// Display name of the joining user
+    pub username: String,
+    
+    /// Role granted by the invite
+    pub role: UserRole,
+    
+    /// Time of the request (milliseconds)
+    pub requested_at: u64,
+}
+
+/// Result of joining with an invite
+#[derive(Debug, Clone, PartialEq)]
+pub enum JoinOutcome {
+    /// The user joined with the given role
+    Admitted(UserRole),
+    
+    /// The user waits in the lobby
+    Pending(JoinRequest),
+}
+
+/// Active session as listed on the dashboard
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionSummary {
+    /// User ID
+    pub user_id: String,
+    
+    /// Display name
+    pub username: String,
+    
+    /// Role in the session
+    pub role: UserRole,
+    
+    /// Server instance hosting the session
+    pub instance_id: String,
+    
+    /// Time since the session started (seconds)
+    pub connected_secs: u64,
+    
+    /// Time since the last activity (seconds)
+    pub idle_secs: u64,
+}
+
+/// Issues and verifies invite tokens and holds the lobby of a project
+///
+/// Tokens are `<claims>.<signature>` where the claims are the hex-encoded JSON of an
+/// `Invite` and the signature is an HMAC-SHA256 of the claims keyed with the secret,
+/// so the role cannot be changed without invalidating the token. Tokens outlive a
+/// restart only if the secret does, see `from_secret_file`.
+#[derive(Debug, Clone)]
+pub struct InviteRegistry {
+    /// Signing secret
+    secret: Vec<u8>,
+    
+    /// Nonces of revoked invites
+    revoked: HashSet<String>,
+    
+    /// Join requests waiting for approval, by request ID
+    lobby: HashMap<String, JoinRequest>,
+}
+
+impl Default for InviteRegistry {
+    fn default() -> Self {
+        Self::with_secret(random_secret())
+    }
+}
+
+impl InviteRegistry {
+    /// Create a registry with a signing secret shared by all server instances
+    pub fn with_secret(secret: Vec<u8>) -> Self {
+        Self {
+            secret,
+            revoked: HashSet::new(),
+            lobby: HashMap::new(),
+        }
+    }
+    
+    /// Create a registry with the hex-encoded secret stored in a file
+    ///
+    /// A new random secret is written to the file (readable by the owner only) if it
+    /// does not exist yet, so invites stay valid across restarts.
+    pub fn from_secret_file<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let path = path.as_ref();
+        match std::fs::read_to_string(path) {
+            Ok(content) => {
+                let secret = unhex(content.trim())
+                    .filter(|secret| !secret.is_empty())
+                    .ok_or_else(|| format!("Invalid invite secret in {}", path.display()))?;
+                Ok(Self::with_secret(secret))
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                let secret = random_secret();
+                write_secret_file(path, &hex(&secret))
+                    .map_err(|e| format!("Failed to write invite secret {}: {}", path.display(), e))?;
+                Ok(Self::with_secret(secret))
+            }
+            Err(e) => Err(format!("Failed to read invite secret {}: {}", path.display(), e)),
+        }
+    }
+    
+    /// Create an invite token
+    pub fn create(&self, project_id: &str, role: UserRole, created_by: &str, ttl: Duration, requires_approval: bool) -> (Invite, String) {
+        let invite = Invite {
+            project_id: project_id.to_string(),
+            role,
+            created_by: created_by.to_string(),
+            expires_at: current_timestamp() + ttl.as_millis() as u64,
+            requires_approval,
+            nonce: uuid::Uuid::new_v4().to_string(),
+        };
+        let claims = hex(&serde_json::to_vec(&invite).unwrap());
+        let token = format!("{}.{}", claims, self.sign(&claims));
+        (invite, token)
+    }
+    
+    /// Verify a token for a project, returning its invite
+    pub fn verify(&self, project_id: &str, token: &str) -> Result<Invite, String> {
+        let (claims, signature) = token.split_once('.').ok_or("Malformed invite token")?;
+        if !bool::from(self.sign(claims).as_bytes().ct_eq(signature.as_bytes())) {
+            return Err("Invalid invite token signature".to_string());
+        }
+        let bytes = unhex(claims).ok_or("Malformed invite token")?;
+        let invite: Invite = serde_json::from_slice(&bytes)
+            .map_err(|e| format!("Malformed invite token: {}", e))?;
+        
+        if invite.project_id != project_id {
+            return Err("Invite is for another project".to_string());
+        }
+        if current_timestamp() >= invite.expires_at {
+            return Err("Invite has expired".to_string());
+        }
+        if self.revoked.contains(&invite.nonce) {
+            return Err("Invite has been revoked".to_string());
+        }
+        Ok(invite)
+    }
+    
+    /// Revoke an invite
+    pub fn revoke(&mut self, invite: &Invite) {
+        self.revoked.insert(invite.nonce.clone());
+    }
+    
+    /// Redeem a token, putting the user in the lobby if the invite requires approval
+    pub fn redeem(&mut self, project_id: &str, token: &str, user_id: &str, username: &str) -> Result<JoinOutcome, String> {
+        let invite = self.verify(project_id, token)?;
+        if !invite.requires_approval {
+            return Ok(JoinOutcome::Admitted(invite.role));
+        }
+        
+        // A user waits in the lobby only once
+        if let Some(request) = self.lobby.values().find(|request| request.user_id == user_id) {
+            return Ok(JoinOutcome::Pending(request.clone()));
+        }
+        let request = JoinRequest {
+            request_id: uuid::Uuid::new_v4().to_string(),
+            user_id: user_id.to_string(),
+            username: username.to_string(),
+            role: invite.role,
+            requested_at: current_timestamp(),
+        };
+        self.lobby.insert(request.request_id.clone(), request.clone());
+        Ok(JoinOutcome::Pending(request))
+    }
+    
+    /// Remove a request from the lobby (on approval or rejection)
+    pub fn take_request(&mut self, request_id: &str) -> Result<JoinRequest, String> {
+        self.lobby.remove(request_id).ok_or_else(|| format!("Unknown join request: {}", request_id))
+    }
+    
+    /// Requests waiting for approval, oldest first
+    pub fn pending_requests(&self) -> Vec<JoinRequest> {
+        let mut requests: Vec<JoinRequest> = self.lobby.values().cloned().collect();
+        requests.sort_by_key(|request| request.requested_at);
+        requests
+    }
+    
+    /// Signature of token claims
+    fn sign(&self, claims: &str) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(&self.secret).expect("HMAC accepts keys of any length");
+        mac.update(claims.as_bytes());
+        hex(&mac.finalize().into_bytes())
+    }
+}
+
+/// Generate a random signing secret
+fn random_secret() -> Vec<u8> {
+    let mut secret = vec![0u8; 32];
+    rand_core::OsRng.fill_bytes(&mut secret);
+    secret
+}
+
+/// Create a secret file only its owner can read
+fn write_secret_file(path: &Path, content: &str) -> std::io::Result<()> {
+    use std::io::Write;
+    
+    if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options.open(path)?.write_all(content.as_bytes())
+}
+
+/// Encode bytes as lowercase hex
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Decode lowercase hex
+fn unhex(text: &str) -> Option<Vec<u8>> {
+    if !text.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..text.len()).step_by(2).map(|i| u8::from_str_radix(text.get(i..i + 2)?, 16).ok()).collect()
+}
+
+/// Get current timestamp in milliseconds
+fn current_timestamp() -> u64 {
+    simulation::unix_millis()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_invites_and_lobby() {
+        let mut registry = InviteRegistry::with_secret(b"secret".to_vec());
+        let (invite, token) = registry.create("kernel", UserRole::Editor, "alice", DEFAULT_INVITE_TTL, false);
+        assert_eq!(registry.verify("kernel", &token).unwrap(), invite);
+        assert!(registry.verify("other", &token).is_err());
+        assert_eq!(registry.redeem("kernel", &token, "bob", "Bob").unwrap(), JoinOutcome::Admitted(UserRole::Editor));
+        
+        // Upgrading the embedded role breaks the signature
+        let (claims, signature) = token.split_once('.').unwrap();
+        let forged = String::from_utf8(unhex(claims).unwrap()).unwrap().replace("Editor", "Admin");
+        assert!(registry.verify("kernel", &format!("{}.{}", hex(forged.as_bytes()), signature)).is_err());
+        assert!(InviteRegistry::with_secret(b"other".to_vec()).verify("kernel", &token).is_err());
+        
+        // The secret file keeps tokens valid across restarts
+        let dir = tempfile::tempdir().unwrap();
+        let secret_file = dir.path().join("invite.secret");
+        let (_, persisted) = InviteRegistry::from_secret_file(&secret_file).unwrap().create("kernel", UserRole::Viewer, "alice", DEFAULT_INVITE_TTL, false);
+        assert!(InviteRegistry::from_secret_file(&secret_file).unwrap().verify("kernel", &persisted).is_ok());
+        assert!(registry.verify("kernel", &persisted).is_err());
+        
+        let (_, expired) = registry.create("kernel", UserRole::Viewer, "alice", Duration::ZERO, false);
+        assert!(registry.verify("kernel", &expired).is_err());
+        registry.revoke(&invite);
+        assert!(registry.verify("kernel", &token).is_err());
+        
+        let (_, gated) = registry.create("kernel", UserRole::Viewer, "alice", DEFAULT_INVITE_TTL, true);
+        let request = match registry.redeem("kernel", &gated, "carol", "Carol").unwrap() {
+            JoinOutcome::Pending(request) => request,
+            outcome => panic!("unexpected outcome {:?}", outcome),
+        };
+        assert_eq!(registry.redeem("kernel", &gated, "carol", "Carol").unwrap(), JoinOutcome::Pending(request.clone()));
+        assert_eq!(registry.pending_requests().len(), 1);
+        assert_eq!(registry.take_request(&request.request_id).unwrap().role, UserRole::Viewer);
+        assert!(registry.pending_requests().is_empty());
+    }
+}
@@ -0,0 +1,438 @@
+//! Shared state of collaboration servers
+//!
+//! Several `CollaborationManager`/`WebSocketServer` instances can serve one project
+//! when they share a `CollaborationStore`. Each instance writes the sessions it hosts
+//! and the operations it accepts to the store and, on every maintenance tick, pulls the
+//! operations of the other instances (`CollaborationManager::sync_from_store`), applying
+//! them to its canvas and forwarding them to its own clients.
+//!
+//! Operations are numbered per instance, so no cross-instance counter is needed. A
+//! cursor keeps the last sequence seen from each instance. Ticks also refresh the
+//! heartbeat of the local sessions and remove the sessions whose instance stopped
+//! sending heartbeats.
+//!
+//! The stores provided here live in one process: `TablesStore` keeps the state in the
+//! DBOS tables of a `TablesManager` the instances share. Instances in separate processes
+//! need a `CollaborationStore` backed by storage they can all reach.
+//!
+//! Change requests under review are kept in the store as well, so an owner connected to
+//! one instance can approve a request proposed on another.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::collaboration::{ChangeRequest, Operation, UserRole};
+use crate::dbos_integration::dbos_core::{ColumnDefinition, ColumnType, IndexDefinition, TableDefinition, TablesManager};
+
+/// Time after which the sessions of an instance without heartbeat are removed
+pub const DEFAULT_STALE_SESSION_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Table holding the sessions of all instances
+pub const SESSIONS_TABLE: &str = "collaboration_sessions";
+
+/// Table holding the operations of all instances
+pub const OPERATIONS_TABLE: &str = "collaboration_operations";
+
+/// Table holding the change requests of all projects
+pub const CHANGE_REQUESTS_TABLE: &str = "collaboration_change_requests";
+
+/// Session as seen by every instance
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionRecord {
+    /// Project ID
+    pub project_id: String,
+    
+    /// User ID
+    pub user_id: String,
+    
+    /// User display name
+    pub username: String,
+    
+    /// User role
+    pub role: UserRole,
+    
+    /// Instance the user is connected to
+    pub instance_id: String,
+    
+    /// Last heartbeat of the instance for this session (milliseconds)
+    pub heartbeat: u64,
+}
+
+/// Operation accepted by an instance
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OperationRecord {
+    /// Project ID
+    pub project_id: String,
+    
+    /// Instance that accepted the operation
+    pub instance_id: String,
+    
+    /// Sequence number within the instance
+    pub instance_sequence: u64,
+    
+    /// The operation
+    pub operation: Operation,
+}
+
+/// Last operation sequence seen from each instance
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StoreCursor {
+    /// Sequence by instance ID
+    pub positions: HashMap<String, u64>,
+}
+
+impl StoreCursor {
+    /// Check if a record is newer than the cursor
+    pub fn is_new(&self, record: &OperationRecord) -> bool {
+        self.positions.get(&record.instance_id).map(|seen| record.instance_sequence > *seen).unwrap_or(true)
+    }
+    
+    /// Move the cursor past a record
+    pub fn advance(&mut self, record: &OperationRecord) {
+        let position = self.positions.entry(record.instance_id.clone()).or_insert(0);
+        *position = (*position).max(record.instance_sequence);
+    }
+}
+
+/// Storage shared by collaboration instances
+pub trait CollaborationStore: Send + Sync + std::fmt::Debug {
+    /// Insert or refresh a session
+    fn put_session(&self, record: &SessionRecord) -> Result<(), String>;
+    
+    /// Remove a session
+    fn remove_session(&self, project_id: &str, user_id: &str) -> Result<(), String>;
+    
+    /// Get the sessions of a project
+    fn sessions(&self, project_id: &str) -> Result<Vec<SessionRecord>, String>;
+    
+    /// Store an accepted operation
+    fn append_operation(&self, record: &OperationRecord) -> Result<(), String>;
+    
+    /// Get the operations newer than a cursor, in timestamp order
+    fn operations_since(&self, project_id: &str, cursor: &StoreCursor) -> Result<Vec<OperationRecord>, String>;
+    
+    /// Insert or update a change request
+    fn put_change_request(&self, request: &ChangeRequest) -> Result<(), String>;
+    
+    /// Get the change requests of a project, oldest first
+    fn change_requests(&self, project_id: &str) -> Result<Vec<ChangeRequest>, String>;
+}
+
+/// Sort operations by time, breaking ties deterministically
+fn sort_records(records: &mut [OperationRecord]) {
+    records.sort_by(|a, b| a.operation.timestamp.cmp(&b.operation.timestamp)
+        .then_with(|| a.instance_id.cmp(&b.instance_id))
+        .then_with(|| a.instance_sequence.cmp(&b.instance_sequence)));
+}
+
+/// Store for a single instance (or instances within one process)
+#[derive(Debug, Default)]
+pub struct InMemoryStore {
+    sessions: Mutex<HashMap<(String, String), SessionRecord>>,
+    operations: Mutex<Vec<OperationRecord>>,
+    change_requests: Mutex<HashMap<String, ChangeRequest>>,
+}
+
+impl InMemoryStore {
+    /// Create an empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl CollaborationStore for InMemoryStore {
+    fn put_session(&self, record: &SessionRecord) -> Result<(), String> {
+        self.sessions.lock().unwrap().insert((record.project_id.clone(), record.user_id.clone()), record.clone());
+        Ok(())
+    }
+    
+    fn remove_session(&self, project_id: &str, user_id: &str) -> Result<(), String> {
+        self.sessions.lock().unwrap().remove(&(project_id.to_string(), user_id.to_string()));
+        Ok(())
+    }
+    
+    fn sessions(&self, project_id: &str) -> Result<Vec<SessionRecord>, String> {
+        Ok(self.sessions.lock().unwrap().values().filter(|record| record.project_id == project_id).cloned().collect())
+    }
+    
+    fn append_operation(&self, record: &OperationRecord) -> Result<(), String> {
+        self.operations.lock().unwrap().push(record.clone());
+        Ok(())
+    }
+    
+    fn operations_since(&self, project_id: &str, cursor: &StoreCursor) -> Result<Vec<OperationRecord>, String> {
+        let mut records: Vec<OperationRecord> = self.operations.lock().unwrap().iter()
+            .filter(|record| record.project_id == project_id && cursor.is_new(record))
+            .cloned()
+            .collect();
+        sort_records(&mut records);
+        Ok(records)
+    }
+    
+    fn put_change_request(&self, request: &ChangeRequest) -> Result<(), String> {
+        self.change_requests.lock().unwrap().insert(request.request_id.clone(), request.clone());
+        Ok(())
+    }
+    
+    fn change_requests(&self, project_id: &str) -> Result<Vec<ChangeRequest>, String> {
+        let mut requests: Vec<ChangeRequest> = self.change_requests.lock().unwrap().values()
+            .filter(|request| request.project_id == project_id)
+            .cloned()
+            .collect();
+        sort_change_requests(&mut requests);
+        Ok(requests)
+    }
+}
+
+/// Store in the DBOS tables of a tables manager shared by the instances
+#[derive(Debug)]
+pub struct TablesStore {
+    tables: Arc<TablesManager>,
+}
+
+impl TablesStore {
+    /// Create a store on top of a running tables manager, creating its tables if needed
+    pub fn new(tables: Arc<TablesManager>) -> Result<Self, String> {
+        let definitions = vec![
+            table(SESSIONS_TABLE, "Collaboration sessions of all server instances", vec![
+                column("project_id", ColumnType::String, "Project ID"),
+                column("user_id", ColumnType::String, "User ID"),
+                column("instance_id", ColumnType::String, "Server instance hosting the session"),
+                column("heartbeat", ColumnType::Long, "Last heartbeat (milliseconds)"),
+                column("session", ColumnType::Json, "Session record"),
+            ], vec!["project_id", "user_id"], "idx_collaboration_sessions_project"),
+            table(OPERATIONS_TABLE, "Collaboration operations of all server instances", vec![
+                column("project_id", ColumnType::String, "Project ID"),
+                column("instance_id", ColumnType::String, "Server instance that accepted the operation"),
+                column("instance_sequence", ColumnType::Long, "Sequence number within the instance"),
+                column("operation", ColumnType::Json, "Operation record"),
+            ], vec!["project_id", "instance_id", "instance_sequence"], "idx_collaboration_operations_project"),
+            table(CHANGE_REQUESTS_TABLE, "Change requests awaiting or past review", vec![
+                column("project_id", ColumnType::String, "Project ID"),
+                column("request_id", ColumnType::String, "Change request ID"),
+                column("status", ColumnType::String, "Review status"),
+                column("change_request", ColumnType::Json, "Change request with its operations and reviews"),
+            ], vec!["project_id", "request_id"], "idx_collaboration_change_requests_project"),
+        ];
+        for definition in definitions {
+            if tables.get_table(&definition.name)?.is_none() {
+                tables.create_table(definition)?;
+            }
+        }
+        Ok(Self { tables })
+    }
+    
+    /// Get the backing tables manager
+    pub fn tables_manager(&self) -> Arc<TablesManager> {
+        self.tables.clone()
+    }
+}
+
+impl CollaborationStore for TablesStore {
+    fn put_session(&self, record: &SessionRecord) -> Result<(), String> {
+        let values = HashMap::from([
+            ("project_id".to_string(), record.project_id.clone()),
+            ("user_id".to_string(), record.user_id.clone()),
+            ("instance_id".to_string(), record.instance_id.clone()),
+            ("heartbeat".to_string(), record.heartbeat.to_string()),
+            ("session".to_string(), serde_json::to_string(record).map_err(|e| e.to_string())?),
+        ]);
+        let conditions = HashMap::from([
+            ("project_id".to_string(), record.project_id.clone()),
+            ("user_id".to_string(), record.user_id.clone()),
+        ]);
+        match self.tables.query_rows(SESSIONS_TABLE, conditions)?.first() {
+            Some(row) => self.tables.update_row(SESSIONS_TABLE, &row.row_id, values),
+            None => self.tables.insert_row(SESSIONS_TABLE, values).map(|_| ()),
+        }
+    }
+    
+    fn remove_session(&self, project_id: &str, user_id: &str) -> Result<(), String> {
+        let conditions = HashMap::from([
+            ("project_id".to_string(), project_id.to_string()),
+            ("user_id".to_string(), user_id.to_string()),
+        ]);
+        for row in self.tables.query_rows(SESSIONS_TABLE, conditions)? {
+            self.tables.delete_row(SESSIONS_TABLE, &row.row_id)?;
+        }
+        Ok(())
+    }
+    
+    fn sessions(&self, project_id: &str) -> Result<Vec<SessionRecord>, String> {
+        let conditions = HashMap::from([("project_id".to_string(), project_id.to_string())]);
+        self.tables.query_rows(SESSIONS_TABLE, conditions)?.iter()
+            .map(|row| serde_json::from_str(row.values.get("session").map(String::as_str).unwrap_or("null"))
+                .map_err(|e| format!("Corrupt session row {}: {}", row.row_id, e)))
+            .collect()
+    }
+    
+    fn append_operation(&self, record: &OperationRecord) -> Result<(), String> {
+        let values = HashMap::from([
+            ("project_id".to_string(), record.project_id.clone()),
+            ("instance_id".to_string(), record.instance_id.clone()),
+            ("instance_sequence".to_string(), record.instance_sequence.to_string()),
+            ("operation".to_string(), serde_json::to_string(record).map_err(|e| e.to_string())?),
+        ]);
+        self.tables.insert_row(OPERATIONS_TABLE, values).map(|_| ())
+    }
+    
+    fn operations_since(&self, project_id: &str, cursor: &StoreCursor) -> Result<Vec<OperationRecord>, String> {
+        let conditions = HashMap::from([("project_id".to_string(), project_id.to_string())]);
+        let mut records = Vec::new();
+        let mut corrupt = None;
+        self.tables.for_each_row(OPERATIONS_TABLE, &conditions, |row| {
+            let new = row.values.get("instance_id")
+                .zip(row.values.get("instance_sequence").and_then(|sequence| sequence.parse::<u64>().ok()))
+                .map(|(instance_id, sequence)| cursor.positions.get(instance_id).map(|seen| sequence > *seen).unwrap_or(true))
+                .unwrap_or(true);
+            if !new {
+                return;
+            }
+            match serde_json::from_str::<OperationRecord>(row.values.get("operation").map(String::as_str).unwrap_or("null")) {
+                Ok(record) => records.push(record),
+                Err(e) => corrupt = Some(format!("Corrupt operation row {}: {}", row.row_id, e)),
+            }
+        })?;
+        if let Some(error) = corrupt {
+            return Err(error);
+        }
+        sort_records(&mut records);
+        Ok(records)
+    }
+    
+    fn put_change_request(&self, request: &ChangeRequest) -> Result<(), String> {
+        let values = HashMap::from([
+            ("project_id".to_string(), request.project_id.clone()),
+            ("request_id".to_string(), request.request_id.clone()),
+            ("status".to_string(), format!("{:?}", request.status)),
+            ("change_request".to_string(), serde_json::to_string(request).map_err(|e| e.to_string())?),
+        ]);
+        let conditions = HashMap::from([
+            ("project_id".to_string(), request.project_id.clone()),
+            ("request_id".to_string(), request.request_id.clone()),
+        ]);
+        match self.tables.query_rows(CHANGE_REQUESTS_TABLE, conditions)?.first() {
+            Some(row) => self.tables.update_row(CHANGE_REQUESTS_TABLE, &row.row_id, values),
+            None => self.tables.insert_row(CHANGE_REQUESTS_TABLE, values).map(|_| ()),
+        }
+    }
+    
+    fn change_requests(&self, project_id: &str) -> Result<Vec<ChangeRequest>, String> {
+        let conditions = HashMap::from([("project_id".to_string(), project_id.to_string())]);
+        let mut requests = self.tables.query_rows(CHANGE_REQUESTS_TABLE, conditions)?.iter()
+            .map(|row| serde_json::from_str(row.values.get("change_request").map(String::as_str).unwrap_or("null"))
+                .map_err(|e| format!("Corrupt change request row {}: {}", row.row_id, e)))
+            .collect::<Result<Vec<ChangeRequest>, String>>()?;
+        sort_change_requests(&mut requests);
+        Ok(requests)
+    }
+}
+
+/// Sort change requests by creation time
+fn sort_change_requests(requests: &mut [ChangeRequest]) {
+    requests.sort_by(|a, b| a.created_at.cmp(&b.created_at).then_with(|| a.request_id.cmp(&b.request_id)));
+}
+
+/// Build a table definition with one non-unique index on the project
+fn table(name: &str, description: &str, columns: Vec<ColumnDefinition>, primary_key: Vec<&str>, index: &str) -> TableDefinition {
+    let now = chrono::Utc::now().timestamp() as u64;
+    TableDefinition {
+        name: name.to_string(),
+        columns,
+        primary_key: primary_key.into_iter().map(String::from).collect(),
+        indexes: vec![IndexDefinition {
+            name: index.to_string(),
+            columns: vec!["project_id".to_string()],
+            unique: false,
+        }],
+        description: description.to_string(),
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+/// Build a required column definition
+fn column(name: &str, column_type: ColumnType, description: &str) -> ColumnDefinition {
+    ColumnDefinition {
+        name: name.to_string(),
+        column_type,
+        nullable: false,
+        default_value: None,
+        description: description.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collaboration::OperationType;
+    
+    fn record(instance_id: &str, instance_sequence: u64, timestamp: u64) -> OperationRecord {
+        let mut operation = Operation::new(
+            "alice".to_string(),
+            format!("{}-{}", instance_id, instance_sequence),
+            OperationType::CursorMove,
+            serde_json::json!({"x": 1, "y": 2}),
+        );
+        operation.timestamp = timestamp;
+        OperationRecord {
+            project_id: "kernel".to_string(),
+            instance_id: instance_id.to_string(),
+            instance_sequence,
+            operation,
+        }
+    }
+    
+    #[test]
+    fn test_stores_share_operations_and_sessions() {
+        let tables = Arc::new(TablesManager::new());
+        tables.start();
+        let stores: Vec<Box<dyn CollaborationStore>> = vec![
+            Box::new(InMemoryStore::new()),
+            Box::new(TablesStore::new(tables).unwrap()),
+        ];
+        
+        for store in stores {
+            store.append_operation(&record("a", 1, 100)).unwrap();
+            store.append_operation(&record("b", 1, 50)).unwrap();
+            store.append_operation(&record("a", 2, 200)).unwrap();
+            
+            let mut cursor = StoreCursor::default();
+            let records = store.operations_since("kernel", &cursor).unwrap();
+            let ids: Vec<&str> = records.iter().map(|record| record.operation.operation_id.as_str()).collect();
+            assert_eq!(ids, vec!["b-1", "a-1", "a-2"]);
+            
+            cursor.advance(&records[1]);
+            cursor.advance(&records[0]);
+            assert_eq!(store.operations_since("kernel", &cursor).unwrap().len(), 1);
+            assert!(store.operations_since("other", &StoreCursor::default()).unwrap().is_empty());
+            
+            let mut session = SessionRecord {
+                project_id: "kernel".to_string(),
+                user_id: "alice".to_string(),
+                username: "Alice".to_string(),
+                role: UserRole::Editor,
+                instance_id: "a".to_string(),
+                heartbeat: 1,
+            };
+            store.put_session(&session).unwrap();
+            session.heartbeat = 2;
+            store.put_session(&session).unwrap();
+            assert_eq!(store.sessions("kernel").unwrap(), vec![session]);
+            store.remove_session("kernel", "alice").unwrap();
+            assert!(store.sessions("kernel").unwrap().is_empty());
+            
+            let author = crate::collaboration::UserSession::new("alice".to_string(), "Alice".to_string(), UserRole::Editor);
+            let mut request = ChangeRequest::new("kernel", &author, "Drop n1".to_string(), String::new(),
+                vec![Operation::new("alice".to_string(), "op".to_string(), OperationType::RemoveNode, serde_json::json!("n1"))], 1).unwrap();
+            store.put_change_request(&request).unwrap();
+            request.withdraw("alice").unwrap();
+            store.put_change_request(&request).unwrap();
+            assert_eq!(store.change_requests("kernel").unwrap(), vec![request]);
+            assert!(store.change_requests("other").unwrap().is_empty());
+        }
+    }
+}
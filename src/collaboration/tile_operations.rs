@@ -0,0 +1,268 @@
+use serde::{Deserialize, Serialize};
+
+use crate::collaboration::{ConflictResolutionStrategy, LockTarget, Operation, OperationType};
+use crate::tile_engine::tile_core::{Tile, TileConnection, TileGraph};
+use crate::tile_engine::tile_library::TileLibrary;
+
+/// Part of the project an operation changes; each has its own conflict strategy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Subsystem {
+    /// Node canvas
+    Canvas,
+    
+    /// Tile graph being designed
+    TileGraph,
+    
+    /// Shared tile library
+    TileLibrary,
+    
+    /// Users, cursors, selections, locks and change request notices
+    Presence,
+}
+
+impl Subsystem {
+    /// Default conflict strategy of the subsystem
+    ///
+    /// Tile properties are independent values, so the latest edit wins; library
+    /// versions are published once, so the first publish of a version wins.
+    pub fn default_strategy(&self) -> ConflictResolutionStrategy {
+        match self {
+            Subsystem::TileGraph => ConflictResolutionStrategy::LastWriteWins,
+            Subsystem::TileLibrary => ConflictResolutionStrategy::FirstWriteWins,
+            Subsystem::Canvas | Subsystem::Presence => ConflictResolutionStrategy::OperationalTransformation,
+        }
+    }
+}
+
+impl OperationType {
+    /// Subsystem changed by operations of this type
+    pub fn subsystem(&self) -> Subsystem {
+        match self {
+            OperationType::AddNode | OperationType::RemoveNode | OperationType::UpdateNode |
+            OperationType::AddConnection | OperationType::RemoveConnection | OperationType::UpdateCanvas => Subsystem::Canvas,
+            OperationType::AddTile | OperationType::RemoveTile | OperationType::UpdateTileProperty |
+            OperationType::AddTileConnection | OperationType::RemoveTileConnection => Subsystem::TileGraph,
+            OperationType::AddLibraryTile | OperationType::UpdateLibraryTile | OperationType::PublishTileVersion => Subsystem::TileLibrary,
+            OperationType::UserJoined | OperationType::UserLeft | OperationType::CursorMove |
+            OperationType::SelectionChange | OperationType::LockAcquired | OperationType::LockReleased |
+            OperationType::ChangeRequestUpdated => Subsystem::Presence,
+        }
+    }
+}
+
+/// Change of a tile property
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TilePropertyEdit {
+    /// Tile ID
+    pub tile_id: String,
+    
+    /// Property key; the part before the first '.' is its lock group
+    pub key: String,
+    
+    /// New value (`None` removes the property)
+    pub value: Option<String>,
+    
+    /// Value the editing user saw, used to detect concurrent edits
+    #[serde(default)]
+    pub base_value: Option<String>,
+}
+
+impl TilePropertyEdit {
+    /// Lock group of the edited property
+    pub fn group(&self) -> &str {
+        self.key.split('.').next().unwrap_or(&self.key)
+    }
+}
+
+/// Tile added to or updated in the library
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LibraryTileEdit {
+    /// Library category
+    pub category: String,
+    
+    /// Tile
+    pub tile: Tile,
+    
+    /// Change notes for updates
+    #[serde(default)]
+    pub notes: String,
+}
+
+/// Publication of a new version of a library tile
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TileVersionPublish {
+    /// Library category
+    pub category: String,
+    
+    /// Tile ID
+    pub tile_id: String,
+    
+    /// Release notes
+    pub notes: String,
+    
+    /// Latest version the publishing user saw
+    #[serde(default)]
+    pub base_version: Option<String>,
+}
+
+/// Target to check for edit locks before applying a tile operation
+pub fn edited_tile_target(operation: &Operation) -> Option<LockTarget> {
+    match operation.operation_type {
+        OperationType::RemoveTile => operation.data.as_str().map(|tile_id| LockTarget::Node(tile_id.to_string())),
+        OperationType::UpdateTileProperty => {
+            let edit: TilePropertyEdit = serde_json::from_value(operation.data.clone()).ok()?;
+            Some(LockTarget::PropertyGroup { node_id: edit.tile_id.clone(), group: edit.group().to_string() })
+        }
+        OperationType::UpdateLibraryTile | OperationType::PublishTileVersion => {
+            operation.data.get("tile_id")
+                .or_else(|| operation.data.get("tile").and_then(|tile| tile.get("id")))
+                .and_then(|tile_id| tile_id.as_str())
+                .map(|tile_id| LockTarget::Node(tile_id.to_string()))
+        }
+        _ => None,
+    }
+}
+
+/// Reject a tile operation that conflicts with a concurrent edit under the given strategy
+pub fn check_tile_conflict(graph: &TileGraph, library: &TileLibrary, operation: &Operation, strategy: &ConflictResolutionStrategy) -> Result<(), String> {
+    let conflict = match operation.operation_type {
+        OperationType::UpdateTileProperty => {
+            let edit: TilePropertyEdit = decode(operation)?;
+            let current = graph.get_tile(&edit.tile_id).and_then(|tile| tile.get_property(&edit.key)).cloned();
+            match &edit.base_value {
+                Some(base_value) if current.as_ref() != Some(base_value) => Some(format!(
+                    "Property '{}' of tile {} was changed to {:?} while editing",
+                    edit.key, edit.tile_id, current)),
+                _ => None,
+            }
+        }
+        OperationType::PublishTileVersion => {
+            let publish: TileVersionPublish = decode(operation)?;
+            let latest = library.get_latest_tile_version(&publish.tile_id)
+                .or_else(|_| library.get_tile(&publish.category, &publish.tile_id))
+                .map(|tile| tile.version.clone())?;
+            match &publish.base_version {
+                Some(base_version) if *base_version != latest => Some(format!(
+                    "Tile {} was already published as version {}", publish.tile_id, latest)),
+                _ => None,
+            }
+        }
+        _ => None,
+    };
+    
+    match (conflict, strategy) {
+        (None, _) => Ok(()),
+        (Some(_), ConflictResolutionStrategy::LastWriteWins | ConflictResolutionStrategy::OperationalTransformation) => Ok(()),
+        (Some(conflict), ConflictResolutionStrategy::FirstWriteWins) => Err(conflict),
+        (Some(conflict), ConflictResolutionStrategy::ManualResolution) => Err(format!("{}; manual resolution required", conflict)),
+    }
+}
+
+/// Apply a tile graph or tile library operation
+pub fn apply_tile_operation(graph: &mut TileGraph, library: &mut TileLibrary, operation: &Operation) -> Result<(), String> {
+    match operation.operation_type {
+        OperationType::AddTile => {
+            let tile: Tile = decode(operation)?;
+            graph.add_tile(tile)
+        }
+        OperationType::RemoveTile => {
+            let tile_id: String = decode(operation)?;
+            graph.remove_tile(&tile_id)
+        }
+        OperationType::UpdateTileProperty => {
+            let edit: TilePropertyEdit = decode(operation)?;
+            let tile = graph.tiles.get_mut(&edit.tile_id).ok_or("Tile not found in the graph")?;
+            match edit.value {
+                Some(value) => tile.set_property(edit.key, value),
+                None => {
+                    tile.properties.remove(&edit.key);
+                }
+            }
+            Ok(())
+        }
+        OperationType::AddTileConnection => {
+            let connection: TileConnection = decode(operation)?;
+            graph.add_connection(connection)
+        }
+        OperationType::RemoveTileConnection => {
+            let connection_id: String = decode(operation)?;
+            let count = graph.connections.len();
+            graph.connections.retain(|connection| connection.id != connection_id);
+            if graph.connections.len() == count {
+                return Err("Connection not found in the graph".to_string());
+            }
+            Ok(())
+        }
+        OperationType::AddLibraryTile => {
+            let edit: LibraryTileEdit = decode(operation)?;
+            library.add_tile(edit.category, edit.tile)
+        }
+        OperationType::UpdateLibraryTile => {
+            let edit: LibraryTileEdit = decode(operation)?;
+            library.update_tile(edit.category, edit.tile, edit.notes)
+        }
+        OperationType::PublishTileVersion => {
+            let publish: TileVersionPublish = decode(operation)?;
+            library.create_new_version(publish.category, &publish.tile_id, publish.notes).map(|_| ())
+        }
+        _ => Err(format!("{:?} is not a tile operation", operation.operation_type)),
+    }
+}
+
+/// Deserialize the data of an operation
+fn decode<T: serde::de::DeserializeOwned>(operation: &Operation) -> Result<T, String> {
+    serde_json::from_value(operation.data.clone())
+        .map_err(|e| format!("Failed to deserialize {:?} data: {}", operation.operation_type, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tile_engine::tile_core::TileType;
+    
+    fn tile_with_id(id: &str) -> Tile {
+        let mut tile = Tile::new("Scheduler".to_string(), TileType::Processing, "Round robin".to_string());
+        tile.id = id.to_string();
+        tile
+    }
+    
+    fn operation(operation_type: OperationType, data: serde_json::Value) -> Operation {
+        Operation::new("alice".to_string(), uuid::Uuid::new_v4().to_string(), operation_type, data)
+    }
+    
+    #[test]
+    fn test_tile_operations_and_conflicts() {
+        let mut graph = TileGraph::new("kernel".to_string());
+        let mut library = TileLibrary::new("shared".to_string(), String::new());
+        let tile = tile_with_id("sched");
+        
+        apply_tile_operation(&mut graph, &mut library, &operation(OperationType::AddTile, serde_json::to_value(&tile).unwrap())).unwrap();
+        let edit = |value: &str, base: Option<&str>| operation(OperationType::UpdateTileProperty, serde_json::json!({
+            "tile_id": "sched", "key": "timing.quantum_ms", "value": value, "base_value": base,
+        }));
+        apply_tile_operation(&mut graph, &mut library, &edit("10", None)).unwrap();
+        assert_eq!(graph.get_tile("sched").unwrap().get_property("timing.quantum_ms").map(String::as_str), Some("10"));
+        
+        // A stale edit loses under first-write-wins and is applied under last-write-wins
+        let stale = edit("20", Some("5"));
+        assert!(check_tile_conflict(&graph, &library, &stale, &ConflictResolutionStrategy::FirstWriteWins).is_err());
+        assert!(check_tile_conflict(&graph, &library, &stale, &ConflictResolutionStrategy::LastWriteWins).is_ok());
+        assert!(check_tile_conflict(&graph, &library, &edit("20", Some("10")), &ConflictResolutionStrategy::FirstWriteWins).is_ok());
+        assert_eq!(edited_tile_target(&stale), Some(LockTarget::PropertyGroup { node_id: "sched".to_string(), group: "timing".to_string() }));
+        
+        let add = operation(OperationType::AddLibraryTile, serde_json::json!({"category": "schedulers", "tile": tile}));
+        library.add_tile("schedulers".to_string(), tile_with_id("other")).unwrap();
+        apply_tile_operation(&mut graph, &mut library, &add).unwrap();
+        let publish = |base: &str| operation(OperationType::PublishTileVersion, serde_json::json!({
+            "category": "schedulers", "tile_id": "sched", "notes": "Faster", "base_version": base,
+        }));
+        let first = publish("1.0.0");
+        check_tile_conflict(&graph, &library, &first, &Subsystem::TileLibrary.default_strategy()).unwrap();
+        apply_tile_operation(&mut graph, &mut library, &first).unwrap();
+        assert_eq!(library.get_latest_tile_version("sched").unwrap().version, "1.0.1");
+        assert!(check_tile_conflict(&graph, &library, &publish("1.0.0"), &Subsystem::TileLibrary.default_strategy()).is_err());
+        
+        apply_tile_operation(&mut graph, &mut library, &operation(OperationType::RemoveTile, serde_json::json!("sched"))).unwrap();
+        assert!(graph.tiles.is_empty());
+    }
+}
@@ -6,6 +6,9 @@ use serde::{Deserialize, Serialize};
 /// User role in the collaboration session
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum UserRole {
+    /// Project owner; may also take over other users' edit locks
+    Owner,
+    
     /// Admin with full permissions
     Admin,
     
@@ -127,12 +130,17 @@ impl UserSession {
     
     /// Check if the user has write permissions
     pub fn has_write_permission(&self) -> bool {
-        matches!(self.role, UserRole::Admin | UserRole::Editor)
+        matches!(self.role, UserRole::Owner | UserRole::Admin | UserRole::Editor)
     }
     
     /// Check if the user has admin permissions
     pub fn has_admin_permission(&self) -> bool {
-        matches!(self.role, UserRole::Admin)
+        matches!(self.role, UserRole::Owner | UserRole::Admin)
+    }
+    
+    /// Check if the user owns the project
+    pub fn has_owner_permission(&self) -> bool {
+        matches!(self.role, UserRole::Owner)
     }
     
     /// Generate a unique color for the user based on their ID
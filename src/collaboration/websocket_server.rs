@@ -18,6 +18,9 @@ pub struct WebSocketServer {
     /// Connected clients
     clients: Arc<RwLock<HashMap<String, UnboundedSender<Message>>>>,
     
+    /// User IDs announced by connected clients, by client ID
+    identities: Arc<RwLock<HashMap<String, String>>>,
+    
     /// Server running state
     running: Arc<RwLock<bool>>,
     
@@ -31,6 +34,7 @@ impl WebSocketServer {
         Self {
             port,
             clients: Arc::new(RwLock::new(HashMap::new())),
+            identities: Arc::new(RwLock::new(HashMap::new())),
             running: Arc::new(RwLock::new(false)),
             server_thread: None,
         }
@@ -47,6 +51,7 @@ impl WebSocketServer {
         // Create a new thread to run the server
         let port = self.port;
         let clients = self.clients.clone();
+        let identities = self.identities.clone();
         let running = self.running.clone();
         
         thread::spawn(move || {
@@ -74,8 +79,9 @@ impl WebSocketServer {
                         Ok((stream, _)) => {
                             // Handle the connection in a new task
                             let clients = clients.clone();
+                            let identities = identities.clone();
                             tokio::spawn(async move {
-                                if let Err(e) = handle_connection(stream, clients.clone()).await {
+                                if let Err(e) = handle_connection(stream, clients.clone(), identities).await {
                                     eprintln!("Error handling connection: {}", e);
                                 }
                             });
@@ -138,6 +144,24 @@ impl WebSocketServer {
         }
     }
     
+    /// Close the connection of a client
+    pub fn disconnect(&self, client_id: &str) -> bool {
+        self.identities.write().unwrap().remove(client_id);
+        // Dropping the sender ends the client's write loop
+        self.clients.write().unwrap().remove(client_id).is_some()
+    }
+    
+    /// Get the connected clients that announced a user, as (user ID, client ID) pairs
+    ///
+    /// Clients announce their user by sending `{"type":"hello","user_id":"..."}`.
+    pub fn identified_clients(&self) -> Vec<(String, String)> {
+        let clients = self.clients.read().unwrap();
+        self.identities.read().unwrap().iter()
+            .filter(|(client_id, _)| clients.contains_key(*client_id))
+            .map(|(client_id, user_id)| (user_id.clone(), client_id.clone()))
+            .collect()
+    }
+    
     /// Get the number of connected clients
     pub fn get_connected_clients(&self) -> usize {
         self.clients.read().unwrap().len()
@@ -173,6 +197,7 @@ impl Drop for WebSocketServer {
 async fn handle_connection(
     raw_stream: TcpStream,
     clients: Arc<RwLock<HashMap<String, UnboundedSender<Message>>>>,
+    identities: Arc<RwLock<HashMap<String, String>>>,
 ) -> Result<(), std::io::Error> {
     let addr = raw_stream
         .peer_addr()?
@@ -207,12 +232,23 @@ async fn handle_connection(
     };
     
     // Handle incoming messages from the WebSocket stream
+    let stream_clients = clients.clone();
+    let stream_identities = identities.clone();
+    let stream_client_id = client_id.clone();
     let stream_task = async move {
+        let clients = stream_clients;
+        let client_id = stream_client_id;
         while let Some(msg) = ws_stream.try_next().await? {
             match msg {
                 Message::Text(text) => {
                     println!("Received message from {}: {}", client_id, text);
                     
+                    // Remember which user the client belongs to
+                    if let Some(user_id) = hello_user_id(&text) {
+                        stream_identities.write().unwrap().insert(client_id.clone(), user_id);
+                        continue;
+                    }
+                    
                     // Broadcast the message to all clients (including sender)
                     let clients = clients.read().unwrap();
                     for (id, sender) in clients.iter() {
@@ -249,6 +285,16 @@ async fn handle_connection(
     // Client disconnected, remove from list
     println!("Client {} disconnected", client_id);
     clients.write().unwrap().remove(&client_id);
+    identities.write().unwrap().remove(&client_id);
     
     Ok(())
 }
+
+/// User ID announced by a `{"type":"hello","user_id":"..."}` message
+fn hello_user_id(text: &str) -> Option<String> {
+    let message: serde_json::Value = serde_json::from_str(text).ok()?;
+    if message.get("type")?.as_str()? != "hello" {
+        return None;
+    }
+    message.get("user_id")?.as_str().map(str::to_string)
+}
@@ -0,0 +1,196 @@
+// Daemon client authentication for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! Clients of the daemon authenticate with a bearer token.
+//!
+//! The tokens file names every client and the namespace it is confined to. The
+//! namespace of a table request is derived from the authenticated client, so a
+//! confined client can neither name another namespace nor act on every table.
+//! Clients without a namespace are administrators. Without a tokens file every
+//! client is the local administrator, which the daemon only allows on loopback.
+
+use std::collections::HashMap;
+use std::path::Path;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use tonic::{Request, Status};
+
+use crate::dbos_integration::dbos_core::validate_namespace;
+
+/// Request header carrying the client token as `Bearer <token>`
+pub const AUTHORIZATION_HEADER: &str = "authorization";
+
+/// Client entry of the tokens file
+#[derive(Debug, Clone, Deserialize)]
+pub struct ClientEntry {
+    /// Client name, recorded as the actor in the audit log
+    pub name: String,
+    
+    /// Secret bearer token
+    pub token: String,
+    
+    /// Namespace the client is confined to (administrators have none)
+    #[serde(default)]
+    pub namespace: Option<String>,
+}
+
+/// Tokens file
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TokensFile {
+    pub clients: Vec<ClientEntry>,
+}
+
+/// Authenticated client of the daemon
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Caller {
+    /// Client name
+    pub name: String,
+    
+    /// Namespace the client is confined to (`None` for administrators)
+    pub namespace: Option<String>,
+}
+
+impl Caller {
+    /// Whether the client may act on every namespace
+    pub fn is_admin(&self) -> bool {
+        self.namespace.is_none()
+    }
+    
+    /// Namespace a table request of this client acts on
+    ///
+    /// Confined clients always act on their own namespace and may not name another
+    /// one. Administrators act on the requested namespace, or on every table when
+    /// the request names none.
+    #[allow(clippy::result_large_err)]
+    pub fn namespace_for(&self, requested: &str) -> Result<String, Status> {
+        match &self.namespace {
+            Some(namespace) if requested.is_empty() || requested == namespace => Ok(namespace.clone()),
+            Some(namespace) => Err(Status::permission_denied(format!(
+                "Client '{}' is confined to namespace '{}'", self.name, namespace
+            ))),
+            None => Ok(requested.to_string()),
+        }
+    }
+}
+
+/// Token authentication of daemon clients
+#[derive(Debug, Default)]
+pub struct DaemonAuth {
+    /// Clients by SHA-256 digest of their token
+    clients: HashMap<[u8; 32], Caller>,
+}
+
+impl DaemonAuth {
+    /// Authentication without tokens: every client is the local administrator
+    pub fn local() -> Self {
+        Self::default()
+    }
+    
+    /// Authentication accepting the tokens of the given clients
+    pub fn from_clients(clients: Vec<ClientEntry>) -> Result<Self, String> {
+        let mut auth = Self::default();
+        for client in clients {
+            if client.token.is_empty() {
+                return Err(format!("Client '{}' has an empty token", client.name));
+            }
+            if let Some(namespace) = &client.namespace {
+                validate_namespace(namespace)?;
+            }
+            let caller = Caller { name: client.name, namespace: client.namespace };
+            if auth.clients.insert(token_digest(&client.token), caller.clone()).is_some() {
+                return Err(format!("Client '{}' reuses the token of another client", caller.name));
+            }
+        }
+        Ok(auth)
+    }
+    
+    /// Load the tokens file (JSON)
+    pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let file: TokensFile = serde_json::from_str(&content)
+            .map_err(|e| format!("Invalid tokens file {}: {}", path.display(), e))?;
+        if file.clients.is_empty() {
+            return Err(format!("Tokens file {} lists no clients", path.display()));
+        }
+        Self::from_clients(file.clients)
+    }
+    
+    /// Whether clients must present a token
+    pub fn requires_tokens(&self) -> bool {
+        !self.clients.is_empty()
+    }
+    
+    /// Identify the client of a request
+    #[allow(clippy::result_large_err)]
+    pub fn authenticate<T>(&self, request: &Request<T>) -> Result<Caller, Status> {
+        if !self.requires_tokens() {
+            let name = request.remote_addr().map(|addr| addr.to_string()).unwrap_or_else(|| "local".to_string());
+            return Ok(Caller { name, namespace: None });
+        }
+        let token = request.metadata().get(AUTHORIZATION_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .ok_or_else(|| Status::unauthenticated("Missing bearer token"))?;
+        self.clients.get(&token_digest(token.trim()))
+            .cloned()
+            .ok_or_else(|| Status::unauthenticated("Unknown bearer token"))
+    }
+}
+
+fn token_digest(token: &str) -> [u8; 32] {
+    Sha256::digest(token.as_bytes()).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn request(token: Option<&str>) -> Request<()> {
+        let mut request = Request::new(());
+        if let Some(token) = token {
+            request.metadata_mut().insert(AUTHORIZATION_HEADER, format!("Bearer {}", token).parse().unwrap());
+        }
+        request
+    }
+    
+    #[test]
+    fn test_namespace_comes_from_the_token() {
+        let auth = DaemonAuth::from_clients(vec![
+            ClientEntry { name: "alice".to_string(), token: "alice-secret".to_string(), namespace: Some("alice".to_string()) },
+            ClientEntry { name: "ops".to_string(), token: "ops-secret".to_string(), namespace: None },
+        ]).unwrap();
+        assert!(auth.requires_tokens());
+        assert_eq!(auth.authenticate(&request(None)).unwrap_err().code(), tonic::Code::Unauthenticated);
+        assert_eq!(auth.authenticate(&request(Some("guess"))).unwrap_err().code(), tonic::Code::Unauthenticated);
+        
+        let alice = auth.authenticate(&request(Some("alice-secret"))).unwrap();
+        assert!(!alice.is_admin());
+        assert_eq!(alice.namespace_for("").unwrap(), "alice");
+        assert_eq!(alice.namespace_for("alice").unwrap(), "alice");
+        assert_eq!(alice.namespace_for("bob").unwrap_err().code(), tonic::Code::PermissionDenied);
+        
+        let ops = auth.authenticate(&request(Some("ops-secret"))).unwrap();
+        assert!(ops.is_admin());
+        assert_eq!(ops.namespace_for("").unwrap(), "");
+        assert_eq!(ops.namespace_for("bob").unwrap(), "bob");
+    }
+    
+    #[test]
+    fn test_invalid_clients_are_rejected() {
+        let client = |token: &str, namespace: Option<&str>| ClientEntry {
+            name: "client".to_string(),
+            token: token.to_string(),
+            namespace: namespace.map(str::to_string),
+        };
+        assert!(DaemonAuth::from_clients(vec![client("", None)]).is_err());
+        assert!(DaemonAuth::from_clients(vec![client("secret", Some("../etc"))]).is_err());
+        assert!(DaemonAuth::from_clients(vec![client("secret", None), client("secret", Some("alice"))]).is_err());
+        
+        let local = DaemonAuth::local();
+        assert!(!local.requires_tokens());
+        assert!(local.authenticate(&request(None)).unwrap().is_admin());
+    }
+}
@@ -0,0 +1,79 @@
+// Daemon module for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! Long-running engine process serving the API in `proto/osland.proto` over gRPC.
+//!
+//! `osland serve` holds one tables manager and one set of open projects, so every
+//! client of the daemon shares the same engine state instead of creating its own.
+//! Builds, extractions and table changes are streamed to the client from the
+//! global event bus. Remote tools use the generated `OslandClient`; the IDE and
+//! the other CLI commands run the engine in-process and do not go through the
+//! daemon.
+//!
+//! Without a tokens file nothing authenticates the clients, so the daemon then
+//! only listens on loopback addresses. Whatever the client, the paths it names
+//! are resolved inside the workspace directory and may not leave it; a client
+//! confined to a namespace is further kept to `namespaces/<namespace>` there.
+
+pub mod auth;
+pub mod service;
+
+/// Generated gRPC types, server and client
+pub mod proto {
+    tonic::include_proto!("osland.v1");
+}
+
+pub use proto::osland_client::OslandClient;
+pub use auth::{Caller, DaemonAuth};
+pub use service::{DaemonState, OslandService};
+
+use std::net::SocketAddr;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Default address of the daemon
+pub const DEFAULT_ADDR: &str = "127.0.0.1:50451";
+
+// Daemon error types
+#[derive(thiserror::Error, Debug)]
+pub enum DaemonError {
+    #[error("Invalid address: {0}")]
+    AddressError(String),
+    
+    #[error("Server error: {0}")]
+    ServerError(String),
+    
+    #[error("Authentication required: {0}")]
+    AuthRequired(String),
+    
+    #[error("Workspace error: {0}")]
+    WorkspaceError(String),
+}
+
+/// Serve the daemon API on `addr` until the process receives Ctrl-C
+///
+/// Clients can only name paths inside `workspace`.
+pub fn serve(addr: &str, auth: DaemonAuth, workspace: &Path) -> Result<(), DaemonError> {
+    let addr: SocketAddr = addr.parse().map_err(|e| DaemonError::AddressError(format!("{}: {}", addr, e)))?;
+    if !addr.ip().is_loopback() && !auth.requires_tokens() {
+        return Err(DaemonError::AuthRequired(format!("{} is not a loopback address; pass a tokens file to serve remote clients", addr)));
+    }
+    let state = DaemonState::new(workspace).map_err(DaemonError::WorkspaceError)?;
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| DaemonError::ServerError(format!("Failed to start runtime: {}", e)))?;
+    
+    runtime.block_on(async move {
+        let service = OslandService::with_auth(Arc::new(state), auth);
+        log::info!("OSland daemon listening on {} for the workspace {}", addr, workspace.display());
+        tonic::transport::Server::builder()
+            .add_service(proto::osland_server::OslandServer::new(service))
+            .serve_with_shutdown(addr, async {
+                let _ = tokio::signal::ctrl_c().await;
+            })
+            .await
+            .map_err(|e| DaemonError::ServerError(e.to_string()))
+    })
+}
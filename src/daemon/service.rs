@@ -0,0 +1,824 @@
+// Daemon gRPC service for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
+use std::sync::{Arc, RwLock};
+use std::thread;
+use std::time::Duration;
+
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::{Request, Response, Status};
+
+use crate::agfs_integration::FileManager;
+use crate::build_engine::{self, BuildConfig, Severity};
+use crate::core::audit;
+use crate::core::cancellation::CancellationToken;
+use crate::core::event_bus::{BuildEvent, EventBus, EventFilter, EventTopic, SystemEvent, TableChangeKind};
+use crate::dbos_integration::dbos_core::{SharedTables, TableDefinition, TablesManager, NAMESPACE_SEPARATOR};
+use crate::kernel_extractor::{AuditConfig, DeadCodeConfig, ExtractionConfig, KernelExtractor};
+
+use super::auth::{Caller, DaemonAuth};
+use super::proto::{self, osland_server::Osland};
+
+/// Build configuration file looked up in project directories
+const PROJECT_CONFIG: &str = "build.json";
+
+/// Interval at which streaming workers check whether the client is still there
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Messages buffered per stream before the worker waits for the client
+const STREAM_BUFFER: usize = 64;
+
+/// Engine state shared by every client of the daemon
+pub struct DaemonState {
+    /// Tables manager serving all table requests
+    tables: Arc<TablesManager>,
+    
+    /// Directory (canonical) every path named by a client must stay in
+    workspace: PathBuf,
+    
+    /// File manager rooted at the workspace, resolving the paths of confined clients
+    files: FileManager,
+    
+    /// Open projects by ID
+    projects: RwLock<HashMap<String, OpenProject>>,
+}
+
+/// Project opened by a client
+struct OpenProject {
+    project: proto::Project,
+    
+    /// Namespace of the client that opened it (`None` for administrators)
+    namespace: Option<String>,
+}
+
+impl OpenProject {
+    /// Whether a client may see and use the project
+    fn visible_to(&self, caller: &Caller) -> bool {
+        caller.is_admin() || self.namespace == caller.namespace
+    }
+}
+
+impl DaemonState {
+    /// Create the state with a running tables manager and no open projects
+    ///
+    /// Clients can only name paths inside `workspace`.
+    pub fn new(workspace: &Path) -> Result<Self, String> {
+        let workspace = workspace.canonicalize()
+            .map_err(|e| format!("Cannot use workspace {}: {}", workspace.display(), e))?;
+        if !workspace.is_dir() {
+            return Err(format!("Workspace {} is not a directory", workspace.display()));
+        }
+        let tables = TablesManager::new();
+        tables.start();
+        let mut files = FileManager::new();
+        files.set_root(workspace.clone());
+        Ok(Self {
+            tables: Arc::new(tables),
+            workspace,
+            files,
+            projects: RwLock::new(HashMap::new()),
+        })
+    }
+    
+    /// Get the tables manager of the daemon
+    pub fn tables(&self) -> Arc<TablesManager> {
+        self.tables.clone()
+    }
+    
+    /// Get the workspace directory
+    pub fn workspace(&self) -> &Path {
+        &self.workspace
+    }
+    
+    /// Get the tables a client's builds are recorded in
+    #[allow(clippy::result_large_err)]
+    pub fn tables_for(&self, caller: &Caller) -> Result<SharedTables, Status> {
+        match &caller.namespace {
+            Some(namespace) => SharedTables::scoped(self.tables(), namespace).map_err(Status::invalid_argument),
+            None => Ok(self.tables().into()),
+        }
+    }
+    
+    /// Resolve a path named by a client
+    ///
+    /// Administrators name paths of the workspace: relative paths are taken from it
+    /// and absolute paths must lie in it. Confined clients name paths of their
+    /// namespace directory, `namespaces/<namespace>` in the workspace, through the
+    /// scoped file manager. Neither `..` nor a symbolic link may lead out of the
+    /// directory of the client.
+    #[allow(clippy::result_large_err)]
+    pub fn resolve_path(&self, caller: &Caller, path: &str) -> Result<PathBuf, Status> {
+        let (root, resolved) = match &caller.namespace {
+            None => (self.workspace.clone(), confine_path(&self.workspace, path)),
+            Some(namespace) => {
+                let files = self.files.scoped(namespace).map_err(Status::permission_denied)?;
+                let root = files.resolve_path("").map_err(Status::permission_denied)?;
+                // Paths the daemon handed out, such as project paths, are absolute
+                let relative = Path::new(path).strip_prefix(&root).ok().and_then(Path::to_str).unwrap_or(path);
+                let resolved = files.resolve_path(relative);
+                (root, resolved)
+            }
+        };
+        let resolved = resolved.map_err(Status::permission_denied)?;
+        check_links(&root, &resolved, path).map_err(Status::permission_denied)?;
+        Ok(resolved)
+    }
+    
+    /// Open a project directory of a client, returning the existing entry if the client already opened it
+    #[allow(clippy::result_large_err)]
+    pub fn open_project(&self, caller: &Caller, path: &str) -> Result<proto::Project, Status> {
+        let path = self.resolve_path(caller, path)?;
+        let path = path.canonicalize()
+            .map_err(|e| Status::not_found(format!("Cannot open project {}: {}", path.display(), e)))?;
+        if !path.is_dir() {
+            return Err(Status::not_found(format!("Project path {} is not a directory", path.display())));
+        }
+        let path_str = path.display().to_string();
+        let mut projects = self.projects.write().unwrap();
+        let existing = projects.values().find(|open| open.project.path == path_str && open.namespace == caller.namespace);
+        if let Some(open) = existing {
+            return Ok(open.project.clone());
+        }
+        
+        let config = path.join(PROJECT_CONFIG);
+        let project = proto::Project {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_else(|| path_str.clone()),
+            path: path_str,
+            config_path: if config.is_file() { config.display().to_string() } else { String::new() },
+        };
+        projects.insert(project.id.clone(), OpenProject { project: project.clone(), namespace: caller.namespace.clone() });
+        Ok(project)
+    }
+    
+    /// Get the open projects a client can see, sorted by name
+    pub fn projects(&self, caller: &Caller) -> Vec<proto::Project> {
+        let mut projects: Vec<proto::Project> = self.projects.read().unwrap().values()
+            .filter(|open| open.visible_to(caller))
+            .map(|open| open.project.clone())
+            .collect();
+        projects.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.path.cmp(&b.path)));
+        projects
+    }
+    
+    /// Close a project the client can see, returning whether it was open
+    pub fn close_project(&self, caller: &Caller, id: &str) -> bool {
+        let mut projects = self.projects.write().unwrap();
+        if !projects.get(id).is_some_and(|open| open.visible_to(caller)) {
+            return false;
+        }
+        projects.remove(id).is_some()
+    }
+    
+    /// Resolve the build configuration of a client's build request
+    #[allow(clippy::result_large_err)]
+    fn config_path(&self, caller: &Caller, request: &proto::BuildRequest) -> Result<String, Status> {
+        if !request.config_path.is_empty() {
+            return Ok(self.resolve_path(caller, &request.config_path)?.display().to_string());
+        }
+        let projects = self.projects.read().unwrap();
+        let project = projects.get(&request.project_id)
+            .filter(|open| open.visible_to(caller))
+            .map(|open| &open.project)
+            .ok_or_else(|| Status::not_found(format!("Project '{}' is not open", request.project_id)))?;
+        if project.config_path.is_empty() {
+            return Err(Status::failed_precondition(format!("Project '{}' has no {}", project.name, PROJECT_CONFIG)));
+        }
+        Ok(project.config_path.clone())
+    }
+}
+
+/// Resolve `path` below the canonical directory `root` without following links
+fn confine_path(root: &Path, path: &str) -> Result<PathBuf, String> {
+    let outside = || format!("Path '{}' is outside the workspace", path);
+    let requested = Path::new(path);
+    let relative = if requested.is_absolute() {
+        requested.strip_prefix(root).map_err(|_| outside())?
+    } else {
+        requested
+    };
+    let mut resolved = root.to_path_buf();
+    for component in relative.components() {
+        match component {
+            Component::Normal(part) => resolved.push(part),
+            Component::CurDir => {}
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => return Err(outside()),
+        }
+    }
+    Ok(resolved)
+}
+
+/// Check that no symbolic link along `resolved`, a path below `root`, leads out of `root`
+fn check_links(root: &Path, resolved: &Path, path: &str) -> Result<(), String> {
+    // The deepest entry that exists, counting dangling links, which cannot be canonicalized
+    let existing = resolved.ancestors()
+        .find(|ancestor| ancestor.symlink_metadata().is_ok())
+        .unwrap_or(root);
+    let canonical = existing.canonicalize()
+        .map_err(|e| format!("Cannot resolve '{}': {}", path, e))?;
+    // Above a root that does not exist yet, the path must not go through links at all
+    let confined = if existing.starts_with(root) {
+        canonical.starts_with(root)
+    } else {
+        canonical == existing
+    };
+    if !confined {
+        return Err(format!("Path '{}' links outside the workspace", path));
+    }
+    Ok(())
+}
+
+/// gRPC service backed by the daemon state
+pub struct OslandService {
+    state: Arc<DaemonState>,
+    auth: DaemonAuth,
+}
+
+impl OslandService {
+    /// Create a service for the given state where every client is the local administrator
+    pub fn new(state: Arc<DaemonState>) -> Self {
+        Self::with_auth(state, DaemonAuth::local())
+    }
+    
+    /// Create a service authenticating its clients
+    pub fn with_auth(state: Arc<DaemonState>, auth: DaemonAuth) -> Self {
+        Self { state, auth }
+    }
+}
+
+/// Forward events from an event bus subscription to a stream until `done` returns true
+///
+/// Returns false if the client went away. Events still queued when `done` becomes
+/// true are forwarded before returning.
+fn forward_events<T>(
+    events: &Receiver<SystemEvent>,
+    sender: &mpsc::Sender<Result<T, Status>>,
+    convert: impl Fn(SystemEvent) -> Option<T>,
+    done: impl Fn() -> bool,
+) -> bool {
+    loop {
+        let event = match events.recv_timeout(POLL_INTERVAL) {
+            Ok(event) => event,
+            Err(RecvTimeoutError::Timeout) if done() => break,
+            Err(RecvTimeoutError::Timeout) => {
+                if sender.is_closed() {
+                    return false;
+                }
+                continue;
+            }
+            Err(RecvTimeoutError::Disconnected) => break,
+        };
+        if let Some(message) = convert(event) {
+            if sender.blocking_send(Ok(message)).is_err() {
+                return false;
+            }
+        }
+    }
+    events.try_iter()
+        .filter_map(convert)
+        .all(|message| sender.blocking_send(Ok(message)).is_ok())
+}
+
+fn build_update(event: SystemEvent) -> Option<proto::BuildUpdate> {
+    use proto::build_update::Update;
+    
+    let update = match event {
+        SystemEvent::Build(BuildEvent::Progress { status, percentage, .. }) => {
+            Update::Progress(proto::BuildProgress { status, percentage: percentage as u32 })
+        }
+        SystemEvent::Build(BuildEvent::Log { message, .. }) => Update::Log(proto::BuildLog { message }),
+        _ => return None,
+    };
+    Some(proto::BuildUpdate { update: Some(update) })
+}
+
+fn extraction_update(event: SystemEvent) -> Option<proto::ExtractionUpdate> {
+    match event {
+        SystemEvent::Extraction(event) => Some(proto::ExtractionUpdate {
+            update: Some(proto::extraction_update::Update::Progress(proto::ExtractionProgress {
+                stage: event.stage,
+                completed: event.completed as u32,
+                total: event.total as u32,
+            })),
+        }),
+        _ => None,
+    }
+}
+
+fn table_change(event: SystemEvent) -> Option<proto::TableChange> {
+    match event {
+        SystemEvent::Table(event) => {
+            let kind = match event.kind {
+                TableChangeKind::Insert => proto::table_change::Kind::Insert,
+                TableChangeKind::Update => proto::table_change::Kind::Update,
+                TableChangeKind::Delete => proto::table_change::Kind::Delete,
+            };
+            Some(proto::TableChange { table: event.table, row_id: event.row_id, kind: kind as i32 })
+        }
+        _ => None,
+    }
+}
+
+/// Convert a table change visible in a namespace, naming private tables as the namespace sees them
+fn namespaced_table_change(event: SystemEvent, namespace: &str) -> Option<proto::TableChange> {
+    let SystemEvent::Table(table_event) = &event else {
+        return None;
+    };
+    let private = table_event.table.strip_prefix(namespace)
+        .and_then(|name| name.strip_prefix(NAMESPACE_SEPARATOR))
+        .map(str::to_string);
+    if private.is_none() && table_event.namespace.as_deref() != Some(namespace) {
+        return None;
+    }
+    let mut change = table_change(event)?;
+    if let Some(name) = private {
+        change.table = name;
+    }
+    Some(change)
+}
+
+/// Call a table operation on every table, or only on a namespace when it is not empty
+macro_rules! scoped {
+    ($tables:expr, $namespace:expr, $method:ident($($arg:expr),*)) => {
+        if $namespace.is_empty() {
+            $tables.$method($($arg),*)
+        } else {
+            $tables.namespace(&$namespace)?.$method($($arg),*)
+        }
+    };
+}
+
+/// Run a blocking table operation of `actor` off the async runtime
+async fn with_tables<T: Send + 'static>(
+    state: &Arc<DaemonState>,
+    actor: String,
+    operation: impl FnOnce(&TablesManager) -> Result<T, String> + Send + 'static,
+) -> Result<T, Status> {
+    let tables = state.tables();
+    tokio::task::spawn_blocking(move || {
+        let _actor = audit::act_as(actor);
+        operation(&tables)
+    })
+        .await
+        .map_err(|e| Status::internal(e.to_string()))?
+        .map_err(Status::invalid_argument)
+}
+
+#[tonic::async_trait]
+impl Osland for OslandService {
+    type BuildStream = ReceiverStream<Result<proto::BuildUpdate, Status>>;
+    type ExtractStream = ReceiverStream<Result<proto::ExtractionUpdate, Status>>;
+    type WatchTablesStream = ReceiverStream<Result<proto::TableChange, Status>>;
+    
+    async fn open_project(&self, request: Request<proto::OpenProjectRequest>) -> Result<Response<proto::Project>, Status> {
+        let caller = self.auth.authenticate(&request)?;
+        self.state.open_project(&caller, &request.into_inner().path).map(Response::new)
+    }
+    
+    async fn list_projects(&self, request: Request<proto::ListProjectsRequest>) -> Result<Response<proto::ListProjectsResponse>, Status> {
+        let caller = self.auth.authenticate(&request)?;
+        Ok(Response::new(proto::ListProjectsResponse { projects: self.state.projects(&caller) }))
+    }
+    
+    async fn close_project(&self, request: Request<proto::CloseProjectRequest>) -> Result<Response<proto::CloseProjectResponse>, Status> {
+        let caller = self.auth.authenticate(&request)?;
+        let id = request.into_inner().id;
+        if !self.state.close_project(&caller, &id) {
+            return Err(Status::not_found(format!("Project '{}' is not open", id)));
+        }
+        Ok(Response::new(proto::CloseProjectResponse {}))
+    }
+    
+    async fn check_config(&self, request: Request<proto::CheckConfigRequest>) -> Result<Response<proto::CheckConfigResponse>, Status> {
+        let caller = self.auth.authenticate(&request)?;
+        let config_path = self.state.resolve_path(&caller, &request.into_inner().config_path)?.display().to_string();
+        let diagnostics = tokio::task::spawn_blocking(move || build_engine::check_config(config_path))
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+        let valid = !diagnostics.iter().any(|diagnostic| diagnostic.severity == Severity::Error);
+        let diagnostics = diagnostics.iter()
+            .map(|diagnostic| proto::Diagnostic {
+                severity: format!("{:?}", diagnostic.severity).to_lowercase(),
+                message: diagnostic.to_string(),
+            })
+            .collect();
+        Ok(Response::new(proto::CheckConfigResponse { diagnostics, valid }))
+    }
+    
+    async fn build(&self, request: Request<proto::BuildRequest>) -> Result<Response<Self::BuildStream>, Status> {
+        // The audit log records the authenticated client, never a name the client claims
+        let caller = self.auth.authenticate(&request)?;
+        let actor = caller.name.clone();
+        let request = request.into_inner();
+        let config_path = self.state.config_path(&caller, &request)?;
+        let project = BuildConfig::from_file(&PathBuf::from(&config_path))
+            .map_err(|e| Status::invalid_argument(format!("Failed to load {}: {}", config_path, e)))?
+            .project_name;
+        // A unique event source tells this build's progress apart from concurrent builds of the project
+        let event_source = format!("{}#{}", project, uuid::Uuid::new_v4());
+        if request.output_path.is_empty() {
+            return Err(Status::invalid_argument("Build request has no output path"));
+        }
+        let output_path = self.state.resolve_path(&caller, &request.output_path)?.display().to_string();
+        // Builds of confined clients record their history in the client's namespace
+        let tables = self.state.tables_for(&caller)?;
+        
+        let (sender, receiver) = mpsc::channel(STREAM_BUFFER);
+        tokio::task::spawn_blocking(move || {
+            // Subscribe before starting so no event of this build is missed
+            let (subscription, events) = EventBus::global()
+                .subscribe_channel(EventFilter::topic(EventTopic::Build).with_source(event_source.clone()));
+            let token = CancellationToken::new();
+            let build_token = token.clone();
+            let build = thread::spawn(move || {
+                let _actor = audit::act_as(actor);
+                let services = build_engine::BuildServices { tables: Some(tables), ..Default::default() };
+                build_engine::build_image_with_cancellation(config_path, output_path, Some(event_source), build_token, services)
+            });
+            let connected = forward_events(&events, &sender, build_update, || build.is_finished());
+            EventBus::global().unsubscribe(subscription);
+            
+            // Nobody is waiting for the result any more
+            if !connected {
+                token.cancel();
+            }
+            
+            let (success, message) = match build.join() {
+                Ok(Ok(())) => (true, "Build finished".to_string()),
+                Ok(Err(e)) => (false, e.to_string()),
+                Err(_) => (false, "Build panicked".to_string()),
+            };
+            if connected {
+                let finished = proto::build_update::Update::Finished(proto::BuildFinished { success, message });
+                let _ = sender.blocking_send(Ok(proto::BuildUpdate { update: Some(finished) }));
+            }
+        });
+        Ok(Response::new(ReceiverStream::new(receiver)))
+    }
+    
+    async fn extract(&self, request: Request<proto::ExtractRequest>) -> Result<Response<Self::ExtractStream>, Status> {
+        let caller = self.auth.authenticate(&request)?;
+        let request = request.into_inner();
+        let source_dir = self.state.resolve_path(&caller, &request.source_dir)?;
+        let output_dir = self.state.resolve_path(&caller, &request.output_dir)?;
+        // A unique kernel name tells this extraction's progress apart from concurrent ones
+        let kernel_name = format!("{}#{}", request.source_dir, uuid::Uuid::new_v4());
+        let config = ExtractionConfig {
+            source_dir,
+            output_dir,
+            export_directory_layout: request.export_dirs,
+            dead_code_elimination: (!request.features.is_empty())
+                .then(|| DeadCodeConfig { features: request.features, ..Default::default() }),
+            security_audit: request.audit.then(AuditConfig::default),
+            kernel_name: Some(kernel_name.clone()),
+            ..Default::default()
+        };
+        
+        let (sender, receiver) = mpsc::channel(STREAM_BUFFER);
+        tokio::task::spawn_blocking(move || {
+            let (subscription, events) = EventBus::global()
+                .subscribe_channel(EventFilter::topic(EventTopic::Extraction).with_source(kernel_name));
+            let token = CancellationToken::new();
+            let extraction_token = token.clone();
+            let extraction = thread::spawn(move || {
+                KernelExtractor::with_config(config).and_then(|mut extractor| extractor.extract_with_cancellation(extraction_token))
+            });
+            let connected = forward_events(&events, &sender, extraction_update, || extraction.is_finished());
+            EventBus::global().unsubscribe(subscription);
+            
+            // Nobody is waiting for the result any more
+            if !connected {
+                token.cancel();
+            }
+            let (success, message) = match extraction.join() {
+                Ok(Ok(())) => (true, "Extraction finished".to_string()),
+                Ok(Err(e)) => (false, e.to_string()),
+                Err(_) => (false, "Extraction panicked".to_string()),
+            };
+            if connected {
+                let finished = proto::extraction_update::Update::Finished(proto::ExtractionFinished { success, message });
+                let _ = sender.blocking_send(Ok(proto::ExtractionUpdate { update: Some(finished) }));
+            }
+        });
+        Ok(Response::new(ReceiverStream::new(receiver)))
+    }
+    
+    async fn create_table(&self, request: Request<proto::CreateTableRequest>) -> Result<Response<proto::CreateTableResponse>, Status> {
+        let caller = self.auth.authenticate(&request)?;
+        let actor = caller.name.clone();
+        let request = request.into_inner();
+        let namespace = caller.namespace_for(&request.namespace)?;
+        let definition: TableDefinition = serde_json::from_str(&request.definition_json)
+            .map_err(|e| Status::invalid_argument(format!("Invalid table definition: {}", e)))?;
+        with_tables(&self.state, actor, move |tables| scoped!(tables, namespace, create_table(definition))).await?;
+        Ok(Response::new(proto::CreateTableResponse {}))
+    }
+    
+    async fn list_tables(&self, request: Request<proto::ListTablesRequest>) -> Result<Response<proto::ListTablesResponse>, Status> {
+        let caller = self.auth.authenticate(&request)?;
+        let actor = caller.name.clone();
+        let namespace = caller.namespace_for(&request.into_inner().namespace)?;
+        let mut tables: Vec<String> = with_tables(&self.state, actor, move |tables| scoped!(tables, namespace, get_all_tables())).await?
+            .into_iter()
+            .map(|table| table.name)
+            .collect();
+        tables.sort();
+        Ok(Response::new(proto::ListTablesResponse { tables }))
+    }
+    
+    async fn insert_row(&self, request: Request<proto::InsertRowRequest>) -> Result<Response<proto::InsertRowResponse>, Status> {
+        let caller = self.auth.authenticate(&request)?;
+        let actor = caller.name.clone();
+        let request = request.into_inner();
+        let namespace = caller.namespace_for(&request.namespace)?;
+        let row_id = with_tables(&self.state, actor, move |tables| scoped!(tables, namespace, insert_row(&request.table, request.values))).await?;
+        Ok(Response::new(proto::InsertRowResponse { row_id }))
+    }
+    
+    async fn update_row(&self, request: Request<proto::UpdateRowRequest>) -> Result<Response<proto::UpdateRowResponse>, Status> {
+        let caller = self.auth.authenticate(&request)?;
+        let actor = caller.name.clone();
+        let request = request.into_inner();
+        let namespace = caller.namespace_for(&request.namespace)?;
+        with_tables(&self.state, actor, move |tables| scoped!(tables, namespace, update_row(&request.table, &request.row_id, request.values))).await?;
+        Ok(Response::new(proto::UpdateRowResponse {}))
+    }
+    
+    async fn delete_row(&self, request: Request<proto::DeleteRowRequest>) -> Result<Response<proto::DeleteRowResponse>, Status> {
+        let caller = self.auth.authenticate(&request)?;
+        let actor = caller.name.clone();
+        let request = request.into_inner();
+        let namespace = caller.namespace_for(&request.namespace)?;
+        with_tables(&self.state, actor, move |tables| scoped!(tables, namespace, delete_row(&request.table, &request.row_id))).await?;
+        Ok(Response::new(proto::DeleteRowResponse {}))
+    }
+    
+    async fn query_rows(&self, request: Request<proto::QueryRowsRequest>) -> Result<Response<proto::QueryRowsResponse>, Status> {
+        let caller = self.auth.authenticate(&request)?;
+        let actor = caller.name.clone();
+        let request = request.into_inner();
+        let namespace = caller.namespace_for(&request.namespace)?;
+        let rows = with_tables(&self.state, actor, move |tables| scoped!(tables, namespace, query_rows(&request.table, request.conditions))).await?
+            .into_iter()
+            .map(|row| proto::Row { row_id: row.row_id, values: row.values, created_at: row.created_at, updated_at: row.updated_at })
+            .collect();
+        Ok(Response::new(proto::QueryRowsResponse { rows }))
+    }
+    
+    async fn watch_tables(&self, request: Request<proto::WatchTablesRequest>) -> Result<Response<Self::WatchTablesStream>, Status> {
+        let caller = self.auth.authenticate(&request)?;
+        let proto::WatchTablesRequest { table, namespace } = request.into_inner();
+        let namespace = caller.namespace_for(&namespace)?;
+        if !namespace.is_empty() {
+            self.state.tables().namespace(&namespace).map_err(Status::invalid_argument)?;
+        }
+        // Private tables are published under their storage name, so namespaced watches filter after conversion
+        let mut filter = EventFilter::topic(EventTopic::Table);
+        if !table.is_empty() && namespace.is_empty() {
+            filter = filter.with_source(table.clone());
+        }
+        
+        let (sender, receiver) = mpsc::channel(STREAM_BUFFER);
+        tokio::task::spawn_blocking(move || {
+            let (subscription, events) = EventBus::global().subscribe_channel(filter);
+            let convert = |event| {
+                if namespace.is_empty() {
+                    return table_change(event);
+                }
+                namespaced_table_change(event, &namespace).filter(|change| table.is_empty() || change.table == table)
+            };
+            forward_events(&events, &sender, convert, || false);
+            EventBus::global().unsubscribe(subscription);
+        });
+        Ok(Response::new(ReceiverStream::new(receiver)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use tokio_stream::StreamExt;
+    
+    fn service(workspace: &Path) -> OslandService {
+        OslandService::new(Arc::new(DaemonState::new(workspace).unwrap()))
+    }
+    
+    #[tokio::test]
+    async fn test_projects_are_shared_between_requests() {
+        let workspace = tempfile::tempdir().unwrap();
+        let dir = workspace.path().join("kernel");
+        std::fs::create_dir(&dir).unwrap();
+        std::fs::write(dir.join(PROJECT_CONFIG), "{}").unwrap();
+        let service = service(workspace.path());
+        
+        let path = dir.display().to_string();
+        let project = service.open_project(Request::new(proto::OpenProjectRequest { path })).await.unwrap().into_inner();
+        assert!(project.config_path.ends_with(PROJECT_CONFIG));
+        let again = service.open_project(Request::new(proto::OpenProjectRequest { path: "kernel".to_string() })).await.unwrap().into_inner();
+        assert_eq!(project.id, again.id);
+        
+        let projects = service.list_projects(Request::new(proto::ListProjectsRequest {})).await.unwrap().into_inner().projects;
+        assert_eq!(projects.len(), 1);
+        service.close_project(Request::new(proto::CloseProjectRequest { id: project.id.clone() })).await.unwrap();
+        assert!(service.close_project(Request::new(proto::CloseProjectRequest { id: project.id })).await.is_err());
+    }
+    
+    #[tokio::test]
+    async fn test_table_requests_and_changes() {
+        let workspace = tempfile::tempdir().unwrap();
+        let service = service(workspace.path());
+        let definition = r#"{"name":"daemon_tasks","columns":[
+            {"name":"title","column_type":"String","nullable":false,"default_value":null,"description":""}],
+            "primary_key":[],"indexes":[],"description":"","created_at":0,"updated_at":0}"#;
+        service.create_table(Request::new(proto::CreateTableRequest { definition_json: definition.to_string(), namespace: String::new() })).await.unwrap();
+        let mut changes = service.watch_tables(Request::new(proto::WatchTablesRequest { table: "daemon_tasks".to_string(), namespace: String::new() }))
+            .await.unwrap().into_inner();
+        // Give the watcher time to subscribe
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        
+        let values = HashMap::from([("title".to_string(), "boot".to_string())]);
+        let row_id = service.insert_row(Request::new(proto::InsertRowRequest { table: "daemon_tasks".to_string(), values: values.clone(), namespace: String::new() }))
+            .await.unwrap().into_inner().row_id;
+        let rows = service.query_rows(Request::new(proto::QueryRowsRequest { table: "daemon_tasks".to_string(), conditions: values, namespace: String::new() }))
+            .await.unwrap().into_inner().rows;
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].row_id, row_id);
+        
+        let change = tokio::time::timeout(Duration::from_secs(5), changes.next()).await.unwrap().unwrap().unwrap();
+        assert_eq!(change.row_id, row_id);
+        assert_eq!(change.kind, proto::table_change::Kind::Insert as i32);
+        
+        let missing = service.insert_row(Request::new(proto::InsertRowRequest { table: "missing".to_string(), values: HashMap::new(), namespace: String::new() })).await;
+        assert_eq!(missing.unwrap_err().code(), tonic::Code::InvalidArgument);
+    }
+    
+    #[tokio::test]
+    async fn test_confined_clients_only_see_their_namespace() {
+        use crate::daemon::auth::{ClientEntry, AUTHORIZATION_HEADER};
+        
+        let client = |name: &str, namespace: Option<&str>| ClientEntry {
+            name: name.to_string(),
+            token: format!("{}-token", name),
+            namespace: namespace.map(str::to_string),
+        };
+        let auth = DaemonAuth::from_clients(vec![client("alice", Some("alice")), client("bob", Some("bob")), client("ops", None)]).unwrap();
+        let workspace = tempfile::tempdir().unwrap();
+        let service = OslandService::with_auth(Arc::new(DaemonState::new(workspace.path()).unwrap()), auth);
+        fn as_client<T>(name: &str, message: T) -> Request<T> {
+            let mut request = Request::new(message);
+            request.metadata_mut().insert(AUTHORIZATION_HEADER, format!("Bearer {}-token", name).parse().unwrap());
+            request
+        }
+        
+        let definition = r#"{"name":"notes","columns":[
+            {"name":"text","column_type":"String","nullable":false,"default_value":null,"description":""}],
+            "primary_key":[],"indexes":[],"description":"","created_at":0,"updated_at":0}"#;
+        let create = proto::CreateTableRequest { definition_json: definition.to_string(), namespace: String::new() };
+        assert_eq!(service.create_table(Request::new(create.clone())).await.unwrap_err().code(), tonic::Code::Unauthenticated);
+        service.create_table(as_client("alice", create.clone())).await.unwrap();
+        let foreign = proto::CreateTableRequest { namespace: "alice".to_string(), ..create };
+        assert_eq!(service.create_table(as_client("bob", foreign)).await.unwrap_err().code(), tonic::Code::PermissionDenied);
+        
+        let list = |name: &str| as_client(name, proto::ListTablesRequest { namespace: String::new() });
+        assert!(service.list_tables(list("alice")).await.unwrap().into_inner().tables.contains(&"notes".to_string()));
+        assert!(!service.list_tables(list("bob")).await.unwrap().into_inner().tables.contains(&"notes".to_string()));
+        let everything = service.list_tables(list("ops")).await.unwrap().into_inner().tables;
+        assert!(everything.iter().any(|table| table.starts_with("alice") && table.ends_with("notes")));
+        
+        // Projects belong to the namespace of the client that opened them
+        let alice_dir = workspace.path().join("namespaces/alice/kernel");
+        std::fs::create_dir_all(&alice_dir).unwrap();
+        std::fs::write(alice_dir.join(PROJECT_CONFIG), "{}").unwrap();
+        let open = proto::OpenProjectRequest { path: "kernel".to_string() };
+        let project = service.open_project(as_client("alice", open.clone())).await.unwrap().into_inner();
+        assert!(Path::new(&project.path).ends_with("namespaces/alice/kernel"));
+        assert_eq!(service.open_project(as_client("bob", open)).await.unwrap_err().code(), tonic::Code::NotFound);
+        
+        let projects = |name: &str| as_client(name, proto::ListProjectsRequest {});
+        assert_eq!(service.list_projects(projects("alice")).await.unwrap().into_inner().projects.len(), 1);
+        assert!(service.list_projects(projects("bob")).await.unwrap().into_inner().projects.is_empty());
+        assert_eq!(service.list_projects(projects("ops")).await.unwrap().into_inner().projects.len(), 1);
+        let close = proto::CloseProjectRequest { id: project.id.clone() };
+        assert_eq!(service.close_project(as_client("bob", close)).await.unwrap_err().code(), tonic::Code::NotFound);
+        assert_eq!(service.list_projects(projects("alice")).await.unwrap().into_inner().projects.len(), 1);
+        
+        // Bob can neither build alice's project nor read or write her directory
+        let bob_dir = workspace.path().join("namespaces/bob");
+        std::fs::create_dir_all(&bob_dir).unwrap();
+        let alice_project = proto::BuildRequest { config_path: String::new(), project_id: project.id, output_path: "image".to_string() };
+        assert_eq!(service.build(as_client("bob", alice_project)).await.unwrap_err().code(), tonic::Code::NotFound);
+        let into_alice = proto::BuildRequest { output_path: "../alice/kernel/image".to_string(), ..build_request(&bob_dir, "bob") };
+        assert_eq!(service.build(as_client("bob", into_alice)).await.unwrap_err().code(), tonic::Code::PermissionDenied);
+        let from_alice = proto::CheckConfigRequest { config_path: format!("../alice/kernel/{}", PROJECT_CONFIG) };
+        assert_eq!(service.check_config(as_client("bob", from_alice)).await.unwrap_err().code(), tonic::Code::PermissionDenied);
+        let bob = Caller { name: "bob".to_string(), namespace: Some("bob".to_string()) };
+        let alice_image = alice_dir.join("image").display().to_string();
+        assert!(service.state.resolve_path(&bob, &alice_image).unwrap().starts_with(service.state.workspace().join("namespaces/bob")));
+        
+        // and his builds record their history in his namespace
+        assert_eq!(service.state.tables_for(&bob).unwrap().namespace(), Some("bob"));
+    }
+    
+    /// Collect the updates of a stream until it ends
+    async fn collect<T>(stream: impl tokio_stream::Stream<Item = Result<T, Status>>) -> Vec<T> {
+        tokio::time::timeout(Duration::from_secs(60), stream.map(Result::unwrap).collect::<Vec<T>>()).await.unwrap()
+    }
+    
+    fn build_request(dir: &Path, name: &str) -> proto::BuildRequest {
+        let mut config = BuildConfig::default(crate::core::architecture::KernelArchitecture::Monolithic);
+        config.project_name = "daemon-build".to_string();
+        config.output_dir = dir.join(name);
+        config.kernel_config.source_path = dir.join("missing-kernel");
+        let config_path = dir.join(format!("{}.json", name));
+        config.to_file(&config_path).unwrap();
+        proto::BuildRequest { config_path: config_path.display().to_string(), project_id: String::new(), output_path: dir.join(name).display().to_string() }
+    }
+    
+    #[tokio::test]
+    async fn test_build_streams_its_own_progress() {
+        use proto::build_update::Update;
+        
+        let dir = tempfile::tempdir().unwrap();
+        let service = service(dir.path());
+        let alone = collect(service.build(Request::new(build_request(dir.path(), "alone"))).await.unwrap().into_inner()).await;
+        assert!(matches!(alone.last().and_then(|update| update.update.as_ref()), Some(Update::Finished(_))));
+        assert!(alone.iter().any(|update| matches!(update.update, Some(Update::Progress(_)))));
+        
+        // Concurrent builds of one project must not see each other's events
+        let first = service.build(Request::new(build_request(dir.path(), "first"))).await.unwrap().into_inner();
+        let second = service.build(Request::new(build_request(dir.path(), "second"))).await.unwrap().into_inner();
+        let (first, second) = tokio::join!(collect(first), collect(second));
+        assert_eq!(first.len(), alone.len());
+        assert_eq!(second.len(), alone.len());
+    }
+    
+    #[tokio::test]
+    async fn test_extract_streams_progress_and_result() {
+        use proto::extraction_update::Update;
+        
+        let workspace = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(workspace.path().join("linux/kernel")).unwrap();
+        std::fs::write(workspace.path().join("linux/kernel/sched.c"), "int schedule(void) { return 0; }\n").unwrap();
+        let request = proto::ExtractRequest {
+            source_dir: "linux".to_string(),
+            output_dir: "extracted".to_string(),
+            ..Default::default()
+        };
+        
+        let updates = collect(service(workspace.path()).extract(Request::new(request)).await.unwrap().into_inner()).await;
+        assert!(updates.iter().any(|update| matches!(update.update, Some(Update::Progress(_)))));
+        match updates.last().and_then(|update| update.update.as_ref()) {
+            Some(Update::Finished(finished)) => assert!(finished.success, "{}", finished.message),
+            other => panic!("Extraction did not finish: {:?}", other),
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_build_needs_a_configuration() {
+        let workspace = tempfile::tempdir().unwrap();
+        let service = service(workspace.path());
+        let request = proto::BuildRequest { config_path: String::new(), project_id: "unknown".to_string(), output_path: String::new() };
+        assert_eq!(service.build(Request::new(request)).await.unwrap_err().code(), tonic::Code::NotFound);
+    }
+    
+    #[tokio::test]
+    async fn test_paths_stay_in_the_workspace() {
+        let workspace = tempfile::tempdir().unwrap();
+        let outside = tempfile::tempdir().unwrap();
+        let service = service(workspace.path());
+        let admin = Caller { name: "local".to_string(), namespace: None };
+        let denied = tonic::Code::PermissionDenied;
+        
+        for path in [outside.path().display().to_string(), "../etc".to_string(), "kernel/../../etc".to_string()] {
+            let open = service.open_project(Request::new(proto::OpenProjectRequest { path: path.clone() })).await;
+            assert_eq!(open.unwrap_err().code(), denied, "{}", path);
+            let check = service.check_config(Request::new(proto::CheckConfigRequest { config_path: path })).await;
+            assert_eq!(check.unwrap_err().code(), denied);
+        }
+        
+        // Configurations and images outside the workspace are refused before anything runs
+        let foreign = build_request(outside.path(), "foreign");
+        assert_eq!(service.build(Request::new(foreign)).await.unwrap_err().code(), denied);
+        let escaping = proto::BuildRequest { output_path: outside.path().join("image").display().to_string(), ..build_request(workspace.path(), "local") };
+        assert_eq!(service.build(Request::new(escaping)).await.unwrap_err().code(), denied);
+        let extract = proto::ExtractRequest { source_dir: outside.path().display().to_string(), output_dir: "extracted".to_string(), ..Default::default() };
+        assert_eq!(service.extract(Request::new(extract)).await.unwrap_err().code(), denied);
+        
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(outside.path(), workspace.path().join("link")).unwrap();
+            std::os::unix::fs::symlink(outside.path().join("missing"), workspace.path().join("dangling")).unwrap();
+            for path in ["link", "link/image", "dangling"] {
+                assert_eq!(service.state.resolve_path(&admin, path).unwrap_err().code(), denied, "{}", path);
+            }
+        }
+        assert!(service.state.resolve_path(&admin, "images/kernel.img").unwrap().starts_with(service.state.workspace()));
+    }
+}
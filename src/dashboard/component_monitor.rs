@@ -4,14 +4,37 @@
 
 use gpui::{Widget, View, ViewContext, RenderContext, LayoutContext, EventContext, Color, Rect, Point, BoxConstraints, Label, ScrollView, Panel};
 use crate::component_manager::component::Component;
+use crate::dbos_integration::dbos_core::TablesManager;
+use crate::tile_engine::tile_core::Tile;
+use super::health_checks::{spawn_health_checks, Alert, AlertRule, HealthChecker, HealthProbe, HealthStatus, SystemProbeRunner};
 use std::collections::HashMap;
-use std::time::SystemTime;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, SystemTime};
+
+/// Number of alerts kept for display
+const MAX_ALERTS: usize = 50;
+
+/// How often the health check thread looks for due probes
+const HEALTH_POLL_INTERVAL: Duration = Duration::from_secs(1);
 
 /// Component monitor widget
 pub struct ComponentMonitor {
     /// Component statuses
     component_statuses: HashMap<String, ComponentStatus>,
     
+    /// Health probes of components and tiles, shared with the health check thread
+    health: Arc<Mutex<HealthChecker>>,
+    
+    /// Alerts fired by the health check thread
+    alert_sender: Sender<Alert>,
+    alert_receiver: Receiver<Alert>,
+    health_thread: Option<JoinHandle<()>>,
+    
+    /// Most recent health alerts, newest last
+    alerts: Vec<Alert>,
+    
     /// UI components
     main_panel: Panel,
     scroll_view: ScrollView,
@@ -42,8 +65,14 @@ pub enum ComponentRuntimeStatus {
 impl ComponentMonitor {
     /// Create a new component monitor
     pub fn new() -> Self {
+        let (alert_sender, alert_receiver) = channel();
         Self {
             component_statuses: HashMap::new(),
+            health: Arc::new(Mutex::new(HealthChecker::from_env())),
+            alert_sender,
+            alert_receiver,
+            health_thread: None,
+            alerts: Vec::new(),
             main_panel: Panel::new(),
             scroll_view: ScrollView::new(),
         }
@@ -69,6 +98,73 @@ impl ComponentMonitor {
         self.component_statuses.values().collect()
     }
     
+    /// Register the health probes declared by a component
+    pub fn register_component_probes(&mut self, project: &str, component: &Component) -> Result<usize, String> {
+        let probes = HealthProbe::from_component(component)?;
+        let count = probes.len();
+        self.health.lock().unwrap().register(project, &component.id, probes);
+        Ok(count)
+    }
+    
+    /// Register the health probes declared by a tile
+    pub fn register_tile_probes(&mut self, project: &str, tile: &Tile) -> Result<usize, String> {
+        let probes = HealthProbe::from_tile(tile)?;
+        let count = probes.len();
+        self.health.lock().unwrap().register(project, &tile.id, probes);
+        Ok(count)
+    }
+    
+    /// Add a health alert rule
+    pub fn add_alert_rule(&mut self, rule: AlertRule) {
+        self.health.lock().unwrap().add_rule(rule);
+    }
+    
+    /// Start running the registered probes on a background thread
+    pub fn start_health_checks(&mut self, tables: Arc<TablesManager>) {
+        if self.health_thread.is_none() {
+            self.health_thread = Some(spawn_health_checks(
+                &self.health, Arc::new(SystemProbeRunner), tables, HEALTH_POLL_INTERVAL, self.alert_sender.clone(),
+            ));
+        }
+    }
+    
+    /// Take the results of the health check thread, returning the alerts that fired
+    ///
+    /// Called from the UI tick; never waits for probes.
+    pub fn poll_health(&mut self) -> Vec<Alert> {
+        let alerts: Vec<Alert> = self.alert_receiver.try_iter().collect();
+        
+        // Failing probes mark running components as errored
+        let health = self.health.lock().unwrap();
+        for status in self.component_statuses.values_mut() {
+            if health.target_status(&status.component_id) == HealthStatus::Unhealthy {
+                status.status = ComponentRuntimeStatus::Error;
+                status.last_updated = SystemTime::now();
+            }
+        }
+        drop(health);
+        
+        self.alerts.extend(alerts.iter().cloned());
+        let excess = self.alerts.len().saturating_sub(MAX_ALERTS);
+        self.alerts.drain(..excess);
+        alerts
+    }
+    
+    /// Get the health checker
+    pub fn health(&self) -> Arc<Mutex<HealthChecker>> {
+        self.health.clone()
+    }
+    
+    /// Get the health of a project
+    pub fn project_health(&self, project: &str) -> HealthStatus {
+        self.health.lock().unwrap().project_status(project)
+    }
+    
+    /// Get the most recent health alerts
+    pub fn get_alerts(&self) -> &[Alert] {
+        &self.alerts
+    }
+    
     /// Initialize UI components
     fn init_ui_components(&mut self, cx: &mut ViewContext) {
         self.scroll_view = ScrollView::new();
@@ -96,6 +192,19 @@ impl ComponentMonitor {
             
             let errors_label = Label::new(&format!("  Errors: {}", status.error_count));
             self.scroll_view.add(errors_label);
+            
+            for result in self.health.lock().unwrap().results(&status.component_id) {
+                let probe_label = Label::new(&format!("  Probe {}: {:?} {}", result.probe, result.status, result.message));
+                self.scroll_view.add(probe_label);
+            }
+        }
+        
+        if !self.alerts.is_empty() {
+            self.scroll_view.add(Label::new("Alerts"));
+            for alert in self.alerts.iter().rev() {
+                let alert_label = Label::new(&format!("  [{}] {} probe {} is {:?}: {}", alert.rule, alert.target_id, alert.probe, alert.status, alert.message));
+                self.scroll_view.add(alert_label);
+            }
         }
     }
     
@@ -5,6 +5,16 @@
 use gpui::{Widget, View, ViewContext, RenderContext, LayoutContext, EventContext, Color, Rect, Point, BoxConstraints, Label, ScrollView, Panel};
 use crate::component_manager::component::Component;
 use crate::core::architecture::{KernelArchitecture, HardwareArchitecture};
+use crate::core::event_bus::EventTopic;
+use crate::dbos_integration::dbos_core::TablesManager;
+use crate::build_engine::build_history::BUILDS_TABLE;
+use crate::kernel_visualization::trace_ingest::{TraceEvent, TraceTimeline, TRACE_EVENTS_TABLE};
+use super::widgets::{
+    DashboardLayout, DataQuery, RefreshPolicy, RefreshScheduler, RenderHint, WidgetData, WidgetDescriptor,
+    WidgetRegistry, BUILTIN_WIDGET_SOURCE,
+};
+use std::collections::HashMap;
+use std::path::Path;
 use std::sync::Arc;
 
 /// Dashboard panel widget
@@ -18,6 +28,18 @@ pub struct DashboardPanel {
     /// Available components summary
     component_summary: ComponentSummary,
     
+    /// Widget kinds that can be placed on the dashboard
+    widget_registry: WidgetRegistry,
+    
+    /// Widgets placed by the user
+    layout: DashboardLayout,
+    
+    /// Last fetched data by widget instance
+    widget_data: HashMap<String, WidgetData>,
+    
+    /// Refresh state of the widgets
+    refresh_scheduler: RefreshScheduler,
+    
     /// UI components
     main_panel: Panel,
     scroll_view: ScrollView,
@@ -60,8 +82,10 @@ pub struct ComponentSummary {
 
 impl DashboardPanel {
     /// Create a new dashboard panel
+    ///
+    /// Starts with the layout saved in the project in the working directory, if any.
     pub fn new() -> Self {
-        Self {
+        let mut panel = Self {
             system_info: SystemInfo {
                 kernel_architecture: KernelArchitecture::Framekernel,
                 hardware_architecture: HardwareArchitecture::X86_64,
@@ -75,9 +99,22 @@ impl DashboardPanel {
                 by_category: Vec::new(),
                 by_architecture: Vec::new(),
             },
+            widget_registry: Self::builtin_widgets(),
+            layout: DashboardLayout::default(),
+            widget_data: HashMap::new(),
+            refresh_scheduler: RefreshScheduler::new(),
             main_panel: Panel::new(),
             scroll_view: ScrollView::new(),
+        };
+        let loaded = std::env::current_dir()
+            .map_err(|e| format!("Failed to get the working directory: {}", e))
+            .and_then(|dir| panel.load_layout(&dir));
+        if let Err(e) = loaded {
+            log::warn!("Failed to load the dashboard layout: {}", e);
+            panel.layout = DashboardLayout::default();
+            panel.add_builtin_widgets();
         }
+        panel
     }
     
     /// Update system information
@@ -95,22 +132,217 @@ impl DashboardPanel {
         self.component_summary = summary;
     }
     
+    /// Built-in widget kinds
+    fn builtin_widgets() -> WidgetRegistry {
+        let mut registry = WidgetRegistry::new();
+        let builtins = [
+            ("system-overview", "System Overview", (6, 3)),
+            ("recent-projects", "Recent Projects", (6, 3)),
+            ("component-summary", "Component Summary", (12, 4)),
+        ];
+        for (kind, title, default_size) in builtins {
+            let descriptor = WidgetDescriptor {
+                kind: kind.to_string(),
+                title: title.to_string(),
+                query: DataQuery::None,
+                refresh: RefreshPolicy::Manual,
+                render: RenderHint::Text,
+                default_size,
+            };
+            registry.register(descriptor, BUILTIN_WIDGET_SOURCE).unwrap();
+        }
+        
+        // Build history analytics
+        let build_history = [
+            ("build-duration", "Build Time (ms)", RenderHint::LineChart {
+                value_column: "duration_ms".to_string(),
+                time_column: "started_at".to_string(),
+            }),
+            ("build-results", "Recent Builds", RenderHint::Table {
+                columns: ["started_at", "outcome", "duration_ms", "cache_hits", "cache_misses", "image_size"]
+                    .iter().map(|column| column.to_string()).collect(),
+            }),
+        ];
+        for (kind, title, render) in build_history {
+            let descriptor = WidgetDescriptor {
+                kind: kind.to_string(),
+                title: title.to_string(),
+                query: DataQuery::Table { table: BUILDS_TABLE.to_string(), conditions: HashMap::new() },
+                refresh: RefreshPolicy::OnEvent(EventTopic::Build),
+                render,
+                default_size: (6, 3),
+            };
+            registry.register(descriptor, BUILTIN_WIDGET_SOURCE).unwrap();
+        }
+        
+        // Kernel traces of the test runs
+        let descriptor = WidgetDescriptor {
+            kind: "trace-timeline".to_string(),
+            title: "Kernel Traces".to_string(),
+            query: DataQuery::Table { table: TRACE_EVENTS_TABLE.to_string(), conditions: HashMap::new() },
+            refresh: RefreshPolicy::OnEvent(EventTopic::Build),
+            render: RenderHint::TraceTimeline { columns: 60 },
+            default_size: (12, 4),
+        };
+        registry.register(descriptor, BUILTIN_WIDGET_SOURCE).unwrap();
+        registry
+    }
+    
+    /// Get the widget registry, e.g. for plugins to contribute widgets
+    pub fn widget_registry_mut(&mut self) -> &mut WidgetRegistry {
+        &mut self.widget_registry
+    }
+    
+    /// Get the current layout
+    pub fn layout(&self) -> &DashboardLayout {
+        &self.layout
+    }
+    
+    /// Add a widget of a registered kind, returning its instance ID
+    pub fn add_widget(&mut self, kind: &str) -> Result<String, String> {
+        let descriptor = self.widget_registry.descriptor(kind)
+            .ok_or_else(|| format!("Unknown widget kind: {}", kind))?;
+        Ok(self.layout.add_widget(descriptor))
+    }
+    
+    /// Remove a widget
+    pub fn remove_widget(&mut self, id: &str) -> Result<(), String> {
+        self.layout.remove_widget(id).ok_or_else(|| format!("Unknown widget: {}", id))?;
+        self.widget_data.remove(id);
+        self.refresh_scheduler.invalidate(id);
+        Ok(())
+    }
+    
+    /// Move and resize a widget
+    pub fn place_widget(&mut self, id: &str, position: (u32, u32), size: (u32, u32)) -> Result<(), String> {
+        self.layout.place_widget(id, position, size)
+    }
+    
+    /// Load the layout saved in a project, falling back to the built-in widgets
+    pub fn load_layout(&mut self, project_dir: &Path) -> Result<(), String> {
+        self.layout = DashboardLayout::load(project_dir)?;
+        if self.layout.widgets.is_empty() {
+            self.add_builtin_widgets();
+        }
+        self.widget_data.clear();
+        self.refresh_scheduler = RefreshScheduler::new();
+        Ok(())
+    }
+    
+    /// Place the built-in overview widgets
+    fn add_builtin_widgets(&mut self) {
+        for kind in ["system-overview", "recent-projects", "component-summary"] {
+            if let Some(descriptor) = self.widget_registry.descriptor(kind) {
+                self.layout.add_widget(descriptor);
+            }
+        }
+    }
+    
+    /// Save the layout in a project
+    pub fn save_layout(&self, project_dir: &Path) -> Result<(), String> {
+        self.layout.save(project_dir)
+    }
+    
+    /// Fetch the data of the widgets due for a refresh
+    ///
+    /// `events` lists the topics published since the last call.
+    pub fn refresh_widgets(&mut self, tables: &TablesManager, events: &[EventTopic]) {
+        for widget in &self.layout.widgets {
+            let descriptor = match self.widget_registry.descriptor(&widget.kind) {
+                Some(descriptor) => descriptor,
+                None => continue,
+            };
+            if !self.refresh_scheduler.is_due(&widget.id, &descriptor.refresh, events) {
+                continue;
+            }
+            match self.widget_registry.fetch(&descriptor.query, tables) {
+                Ok(data) => {
+                    self.widget_data.insert(widget.id.clone(), data);
+                }
+                Err(e) => log::warn!("Failed to refresh widget {}: {}", descriptor.title, e),
+            }
+            self.refresh_scheduler.mark_refreshed(&widget.id);
+        }
+    }
+    
     /// Initialize UI components
     fn init_ui_components(&mut self, cx: &mut ViewContext) {
         self.scroll_view = ScrollView::new();
         
-        // Add system overview section
-        self.add_system_overview_section(cx);
-        
-        // Add recent projects section
-        self.add_recent_projects_section(cx);
-        
-        // Add component summary section
-        self.add_component_summary_section(cx);
+        // Add the widgets in layout order
+        let widgets: Vec<(String, String, Option<String>)> = self.layout.ordered_widgets().iter()
+            .map(|widget| (widget.id.clone(), widget.kind.clone(), widget.title.clone()))
+            .collect();
+        for (id, kind, title) in widgets {
+            match kind.as_str() {
+                "system-overview" => self.add_system_overview_section(cx),
+                "recent-projects" => self.add_recent_projects_section(cx),
+                "component-summary" => self.add_component_summary_section(cx),
+                _ => self.add_widget_section(&id, &kind, title, cx),
+            }
+        }
         
         self.main_panel.set_content(self.scroll_view.clone());
     }
     
+    /// Add a registered widget section
+    fn add_widget_section(&mut self, id: &str, kind: &str, title: Option<String>, cx: &mut ViewContext) {
+        let descriptor = match self.widget_registry.descriptor(kind) {
+            Some(descriptor) => descriptor.clone(),
+            None => return,
+        };
+        self.scroll_view.add(Label::new(&title.unwrap_or(descriptor.title)));
+        
+        match (self.widget_data.get(id).unwrap_or(&WidgetData::Empty), &descriptor.render) {
+            (WidgetData::Value(value), RenderHint::Gauge { min, max, unit }) => {
+                let share = (value - min) * 100.0 / (max - min).max(f64::EPSILON);
+                self.scroll_view.add(Label::new(&format!("{:.1}{} ({:.0}%)", value, unit, share)));
+            }
+            (WidgetData::Value(value), _) => self.scroll_view.add(Label::new(&format!("{:.2}", value))),
+            (WidgetData::Rows(rows), RenderHint::Table { columns }) => {
+                self.scroll_view.add(Label::new(&columns.join(" | ")));
+                for row in rows {
+                    let cells: Vec<&str> = columns.iter().map(|column| row.get(column).map(String::as_str).unwrap_or("")).collect();
+                    self.scroll_view.add(Label::new(&cells.join(" | ")));
+                }
+            }
+            (WidgetData::Rows(rows), RenderHint::BarChart { label_column, value_column }) => {
+                for row in rows {
+                    let value: f64 = row.get(value_column).and_then(|value| value.parse().ok()).unwrap_or(0.0);
+                    let label = row.get(label_column).map(String::as_str).unwrap_or("");
+                    self.scroll_view.add(Label::new(&format!("{} {} {}", label, "#".repeat(value.clamp(0.0, 40.0) as usize), value)));
+                }
+            }
+            (WidgetData::Rows(rows), RenderHint::TraceTimeline { columns }) => {
+                let events: Vec<TraceEvent> = rows.iter().map(TraceEvent::from_row).collect();
+                for (run_id, timeline) in TraceTimeline::by_run(&events) {
+                    self.scroll_view.add(Label::new(&format!("{} ({} us)", run_id, timeline.end_us - timeline.start_us)));
+                    for bar in timeline.lane_bars(*columns) {
+                        self.scroll_view.add(Label::new(&format!("  {}", bar)));
+                    }
+                }
+            }
+            (WidgetData::Rows(rows), RenderHint::LineChart { value_column, time_column }) => {
+                // Table rows come in no particular order
+                let mut rows: Vec<_> = rows.iter().collect();
+                if !time_column.is_empty() {
+                    let time = |row: &HashMap<String, String>| row.get(time_column).and_then(|value| value.parse::<f64>().ok()).unwrap_or(f64::NEG_INFINITY);
+                    rows.sort_by(|a, b| time(a).total_cmp(&time(b)));
+                }
+                let values: Vec<&str> = rows.iter().filter_map(|row| row.get(value_column)).map(String::as_str).collect();
+                self.scroll_view.add(Label::new(&values.join(" → ")));
+            }
+            (WidgetData::Rows(rows), _) => {
+                for row in rows {
+                    let mut cells: Vec<String> = row.iter().map(|(key, value)| format!("{}={}", key, value)).collect();
+                    cells.sort();
+                    self.scroll_view.add(Label::new(&cells.join(", ")));
+                }
+            }
+            (WidgetData::Empty, _) => self.scroll_view.add(Label::new("No data")),
+        }
+    }
+    
     /// Add system overview section
     fn add_system_overview_section(&mut self, cx: &mut ViewContext) {
         let title = Label::new("System Overview");
@@ -0,0 +1,617 @@
+// Component health checks for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! User-defined health probes of components and tiles.
+//!
+//! A component declares its probes as JSON in its `health_probes` property, a tile in
+//! its `health.probes` property. Probes run on their own interval; their results roll
+//! up into a status per component and per project, and alert rules fire when a probe
+//! of a target fails a number of consecutive checks.
+//!
+//! Probes come from project files, so command probes only run programs on the
+//! allowlist set with `allow_commands` or `OSLAND_HEALTH_COMMANDS`. `spawn_health_checks`
+//! runs the probes on a background thread without holding the checker while they run.
+
+use std::collections::{HashMap, HashSet};
+use std::process::{Command, Stdio};
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex, Weak};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant, SystemTime};
+use serde::{Deserialize, Serialize};
+use crate::component_manager::component::Component;
+use crate::core::network_policy::{check_egress, redirect_policy, EgressPurpose};
+use crate::dbos_integration::dbos_core::TablesManager;
+use crate::tile_engine::tile_core::Tile;
+
+/// Component property holding the probes
+pub const COMPONENT_PROBES_PROPERTY: &str = "health_probes";
+
+/// Tile property holding the probes
+pub const TILE_PROBES_PROPERTY: &str = "health.probes";
+
+/// Environment variable listing the programs command probes may run, comma separated
+pub const PROBE_COMMANDS_ENV: &str = "OSLAND_HEALTH_COMMANDS";
+
+/// Health of a probe, component or project; later variants are worse
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum HealthStatus {
+    Healthy,
+    Unknown,
+    Degraded,
+    Unhealthy,
+}
+
+/// Condition on the rows returned by a table query
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum RowPredicate {
+    /// At least this many rows
+    MinRows(usize),
+    
+    /// At most this many rows
+    MaxRows(usize),
+    
+    /// Every row has the value in the column
+    AllEqual { column: String, value: String },
+}
+
+/// What a probe checks
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ProbeCheck {
+    /// A command that must exit successfully
+    Command { program: String, #[serde(default)] args: Vec<String> },
+    
+    /// An HTTP endpoint that must answer with the status
+    Http { url: String, #[serde(default = "default_http_status")] expected_status: u16 },
+    
+    /// A DBOS table query whose rows must satisfy a predicate
+    TableQuery {
+        table: String,
+        #[serde(default)]
+        conditions: HashMap<String, String>,
+        predicate: RowPredicate,
+    },
+}
+
+fn default_http_status() -> u16 {
+    200
+}
+
+/// Health probe declared by a component or tile
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HealthProbe {
+    /// Probe name
+    pub name: String,
+    
+    /// Check to run
+    pub check: ProbeCheck,
+    
+    /// Seconds between two runs
+    #[serde(default = "default_interval_secs")]
+    pub interval_secs: u64,
+    
+    /// Seconds before the check is considered failed
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+    
+    /// Status reported when the check fails
+    #[serde(default = "default_failure_status")]
+    pub failure_status: HealthStatus,
+}
+
+fn default_interval_secs() -> u64 {
+    30
+}
+
+fn default_timeout_secs() -> u64 {
+    5
+}
+
+fn default_failure_status() -> HealthStatus {
+    HealthStatus::Unhealthy
+}
+
+impl HealthProbe {
+    /// Probes declared in a component's properties
+    pub fn from_component(component: &Component) -> Result<Vec<HealthProbe>, String> {
+        match component.properties.iter().find(|property| property.name == COMPONENT_PROBES_PROPERTY) {
+            Some(property) => parse_probes(&property.value, &component.name),
+            None => Ok(Vec::new()),
+        }
+    }
+    
+    /// Probes declared in a tile's properties
+    pub fn from_tile(tile: &Tile) -> Result<Vec<HealthProbe>, String> {
+        match tile.get_property(TILE_PROBES_PROPERTY) {
+            Some(value) => parse_probes(value, &tile.name),
+            None => Ok(Vec::new()),
+        }
+    }
+}
+
+fn parse_probes(value: &str, owner: &str) -> Result<Vec<HealthProbe>, String> {
+    serde_json::from_str(value).map_err(|e| format!("Invalid health probes of {}: {}", owner, e))
+}
+
+/// Runs the external checks of probes; replaced by a fake in tests
+pub trait ProbeRunner: Send + Sync {
+    /// Run a command, failing if it does not exit successfully within the timeout
+    fn run_command(&self, program: &str, args: &[String], timeout: Duration) -> Result<(), String>;
+    
+    /// Get the HTTP status of a URL
+    fn http_status(&self, url: &str, timeout: Duration) -> Result<u16, String>;
+}
+
+/// Runs commands and HTTP requests on this machine
+pub struct SystemProbeRunner;
+
+impl ProbeRunner for SystemProbeRunner {
+    fn run_command(&self, program: &str, args: &[String], timeout: Duration) -> Result<(), String> {
+        let mut child = Command::new(program)
+            .args(args)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| format!("failed to run '{}': {}", program, e))?;
+        let start_time = Instant::now();
+        loop {
+            match child.try_wait().map_err(|e| e.to_string())? {
+                Some(status) if status.success() => return Ok(()),
+                Some(status) => return Err(format!("'{}' exited with {}", program, status)),
+                None if start_time.elapsed() >= timeout => {
+                    let _ = child.kill();
+                    return Err(format!("'{}' timed out after {}s", program, timeout.as_secs()));
+                }
+                None => std::thread::sleep(Duration::from_millis(50)),
+            }
+        }
+    }
+    
+    fn http_status(&self, url: &str, timeout: Duration) -> Result<u16, String> {
+        let client = reqwest::blocking::Client::builder()
+            .redirect(redirect_policy(EgressPurpose::HealthCheck))
+            .timeout(timeout)
+            .build()
+            .map_err(|e| e.to_string())?;
+        let response = client.get(url).send().map_err(|e| e.to_string())?;
+        Ok(response.status().as_u16())
+    }
+}
+
+/// Result of one probe run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProbeResult {
+    /// Component or tile ID
+    pub target_id: String,
+    
+    /// Probe name
+    pub probe: String,
+    
+    /// Resulting status
+    pub status: HealthStatus,
+    
+    /// Failure reason, empty when healthy
+    pub message: String,
+    
+    /// Time of the check
+    pub checked_at: SystemTime,
+}
+
+/// Rule raising an alert when a target stays at or above a status
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AlertRule {
+    /// Rule name
+    pub name: String,
+    
+    /// Project the rule applies to (all projects if `None`)
+    pub project: Option<String>,
+    
+    /// Target the rule applies to (all targets if `None`)
+    pub target_id: Option<String>,
+    
+    /// Status that counts as failing
+    pub min_status: HealthStatus,
+    
+    /// Consecutive failing checks before the alert fires
+    pub consecutive_failures: u32,
+}
+
+/// Alert raised by a rule
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Alert {
+    /// Rule name
+    pub rule: String,
+    
+    /// Project of the target
+    pub project: String,
+    
+    /// Failing component or tile
+    pub target_id: String,
+    
+    /// Failing probe
+    pub probe: String,
+    
+    /// Status of the probe
+    pub status: HealthStatus,
+    
+    /// Last failure reason
+    pub message: String,
+}
+
+/// Probes of one component or tile
+struct ProbeTarget {
+    project: String,
+    probes: Vec<HealthProbe>,
+    last_run: HashMap<String, Instant>,
+    results: HashMap<String, ProbeResult>,
+    /// Consecutive failing checks by rule and probe name
+    failure_streaks: HashMap<(String, String), u32>,
+}
+
+/// Schedules probes and keeps their results
+#[derive(Default)]
+pub struct HealthChecker {
+    /// Targets by ID
+    targets: HashMap<String, ProbeTarget>,
+    
+    /// Alert rules
+    rules: Vec<AlertRule>,
+    
+    /// Programs command probes may run
+    allowed_commands: HashSet<String>,
+}
+
+impl HealthChecker {
+    /// Create an empty checker
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Create a checker allowing the programs listed in `OSLAND_HEALTH_COMMANDS`
+    pub fn from_env() -> Self {
+        let mut checker = Self::new();
+        if let Ok(programs) = std::env::var(PROBE_COMMANDS_ENV) {
+            checker.allow_commands(programs.split(',').map(str::trim).filter(|program| !program.is_empty()));
+        }
+        checker
+    }
+    
+    /// Allow command probes to run the programs
+    pub fn allow_commands<I, S>(&mut self, programs: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.allowed_commands.extend(programs.into_iter().map(Into::into));
+    }
+    
+    /// Register (or replace) the probes of a component or tile
+    pub fn register(&mut self, project: &str, target_id: &str, probes: Vec<HealthProbe>) {
+        self.targets.insert(target_id.to_string(), ProbeTarget {
+            project: project.to_string(),
+            probes,
+            last_run: HashMap::new(),
+            results: HashMap::new(),
+            failure_streaks: HashMap::new(),
+        });
+    }
+    
+    /// Stop checking a target
+    pub fn unregister(&mut self, target_id: &str) {
+        self.targets.remove(target_id);
+    }
+    
+    /// Add an alert rule
+    pub fn add_rule(&mut self, rule: AlertRule) {
+        self.rules.push(rule);
+    }
+    
+    /// Run the probes whose interval has passed, returning the alerts that fired
+    pub fn run_due(&mut self, runner: &dyn ProbeRunner, tables: &TablesManager) -> Vec<Alert> {
+        let mut alerts = Vec::new();
+        for (target_id, probe) in self.take_due() {
+            let (status, message) = self.check(&probe, runner, tables);
+            alerts.extend(self.record(&target_id, &probe.name, status, message));
+        }
+        alerts
+    }
+    
+    /// Probes whose interval has passed, marked as run
+    pub fn take_due(&mut self) -> Vec<(String, HealthProbe)> {
+        let now = Instant::now();
+        let mut due = Vec::new();
+        for (target_id, target) in &mut self.targets {
+            for probe in &target.probes {
+                let is_due = target.last_run.get(&probe.name)
+                    .is_none_or(|last_run| now.duration_since(*last_run) >= Duration::from_secs(probe.interval_secs));
+                if is_due {
+                    target.last_run.insert(probe.name.clone(), now);
+                    due.push((target_id.clone(), probe.clone()));
+                }
+            }
+        }
+        due
+    }
+    
+    /// Run the check of a probe, without touching the checker's state
+    pub fn check(&self, probe: &HealthProbe, runner: &dyn ProbeRunner, tables: &TablesManager) -> (HealthStatus, String) {
+        probe_outcome(self.refusal(probe), probe, runner, tables)
+    }
+    
+    /// Why a probe may not run, if it may not
+    fn refusal(&self, probe: &HealthProbe) -> Option<String> {
+        match &probe.check {
+            ProbeCheck::Http { url, .. } => check_egress(EgressPurpose::HealthCheck, url).err().map(|denied| denied.to_string()),
+            ProbeCheck::Command { program, .. } if !self.allowed_commands.contains(program) => {
+                Some(format!("'{}' is not allowed to run as a health probe (see {})", program, PROBE_COMMANDS_ENV))
+            }
+            _ => None,
+        }
+    }
+    
+    /// Record the result of a probe, returning the alerts that fired
+    ///
+    /// A rule fires once, when one probe of the target has failed the rule's number
+    /// of consecutive checks.
+    pub fn record(&mut self, target_id: &str, probe: &str, status: HealthStatus, message: String) -> Vec<Alert> {
+        let Some(target) = self.targets.get_mut(target_id) else {
+            // Unregistered while the probe was running
+            return Vec::new();
+        };
+        target.results.insert(probe.to_string(), ProbeResult {
+            target_id: target_id.to_string(),
+            probe: probe.to_string(),
+            status,
+            message: message.clone(),
+            checked_at: SystemTime::now(),
+        });
+        
+        let mut alerts = Vec::new();
+        for rule in &self.rules {
+            let applies = rule.project.as_ref().is_none_or(|project| *project == target.project) &&
+                rule.target_id.as_ref().is_none_or(|id| id == target_id);
+            if !applies {
+                continue;
+            }
+            let streak = target.failure_streaks.entry((rule.name.clone(), probe.to_string())).or_insert(0);
+            if status >= rule.min_status {
+                *streak += 1;
+                if *streak == rule.consecutive_failures.max(1) {
+                    alerts.push(Alert {
+                        rule: rule.name.clone(),
+                        project: target.project.clone(),
+                        target_id: target_id.to_string(),
+                        probe: probe.to_string(),
+                        status,
+                        message: message.clone(),
+                    });
+                }
+            } else {
+                *streak = 0;
+            }
+        }
+        
+        for alert in &alerts {
+            log::warn!("Health alert '{}': {} probe '{}' is {:?}: {}", alert.rule, alert.target_id, alert.probe, alert.status, alert.message);
+        }
+        alerts
+    }
+    
+    /// Latest results of a target's probes
+    pub fn results(&self, target_id: &str) -> Vec<ProbeResult> {
+        let mut results: Vec<ProbeResult> = self.targets.get(target_id)
+            .map(|target| target.results.values().cloned().collect())
+            .unwrap_or_default();
+        results.sort_by(|a, b| a.probe.cmp(&b.probe));
+        results
+    }
+    
+    /// Status of a target: the worst of its probes, unknown before the first run
+    pub fn target_status(&self, target_id: &str) -> HealthStatus {
+        self.targets.get(target_id).map(target_status).unwrap_or(HealthStatus::Unknown)
+    }
+    
+    /// Status of a project: the worst of its targets
+    pub fn project_status(&self, project: &str) -> HealthStatus {
+        self.targets.values()
+            .filter(|target| target.project == project)
+            .map(target_status)
+            .max()
+            .unwrap_or(HealthStatus::Unknown)
+    }
+    
+    /// Number of targets per status in a project
+    pub fn project_summary(&self, project: &str) -> HashMap<HealthStatus, usize> {
+        let mut summary = HashMap::new();
+        for target in self.targets.values().filter(|target| target.project == project) {
+            *summary.entry(target_status(target)).or_insert(0) += 1;
+        }
+        summary
+    }
+}
+
+/// Run due probes on a background thread until the checker is dropped
+///
+/// The checker is locked only to pick the due probes and to record their results,
+/// so the UI can read statuses while probes run. Fired alerts are sent to `alerts`.
+pub fn spawn_health_checks(
+    checker: &Arc<Mutex<HealthChecker>>,
+    runner: Arc<dyn ProbeRunner>,
+    tables: Arc<TablesManager>,
+    poll_interval: Duration,
+    alerts: Sender<Alert>,
+) -> JoinHandle<()> {
+    let checker: Weak<Mutex<HealthChecker>> = Arc::downgrade(checker);
+    std::thread::spawn(move || {
+        while let Some(shared) = checker.upgrade() {
+            let due = shared.lock().unwrap().take_due();
+            for (target_id, probe) in due {
+                let refused = shared.lock().unwrap().refusal(&probe);
+                let (status, message) = probe_outcome(refused, &probe, runner.as_ref(), &tables);
+                for alert in shared.lock().unwrap().record(&target_id, &probe.name, status, message) {
+                    let _ = alerts.send(alert);
+                }
+            }
+            drop(shared);
+            std::thread::sleep(poll_interval);
+        }
+    })
+}
+
+/// Worst status of a target's probes
+fn target_status(target: &ProbeTarget) -> HealthStatus {
+    if target.probes.iter().any(|probe| !target.results.contains_key(&probe.name)) {
+        return HealthStatus::Unknown;
+    }
+    target.results.values()
+        .map(|result| result.status)
+        .max()
+        .unwrap_or(HealthStatus::Unknown)
+}
+
+/// Status of a probe, which is `Unknown` if it may not run: that says nothing about the target
+fn probe_outcome(refused: Option<String>, probe: &HealthProbe, runner: &dyn ProbeRunner, tables: &TablesManager) -> (HealthStatus, String) {
+    match refused {
+        Some(message) => (HealthStatus::Unknown, message),
+        None => match run_probe(probe, runner, tables) {
+            Ok(()) => (HealthStatus::Healthy, String::new()),
+            Err(message) => (probe.failure_status, message),
+        },
+    }
+}
+
+/// Run the check of a probe
+fn run_probe(probe: &HealthProbe, runner: &dyn ProbeRunner, tables: &TablesManager) -> Result<(), String> {
+    let timeout = Duration::from_secs(probe.timeout_secs);
+    match &probe.check {
+        ProbeCheck::Command { program, args } => runner.run_command(program, args, timeout),
+        ProbeCheck::Http { url, expected_status } => {
+            let status = runner.http_status(url, timeout)?;
+            if status != *expected_status {
+                return Err(format!("{} answered {} instead of {}", url, status, expected_status));
+            }
+            Ok(())
+        }
+        ProbeCheck::TableQuery { table, conditions, predicate } => {
+            let rows = tables.query_rows(table, conditions.clone())?;
+            match predicate {
+                RowPredicate::MinRows(min) if rows.len() < *min => Err(format!("{} has {} rows, expected at least {}", table, rows.len(), min)),
+                RowPredicate::MaxRows(max) if rows.len() > *max => Err(format!("{} has {} rows, expected at most {}", table, rows.len(), max)),
+                RowPredicate::AllEqual { column, value } => {
+                    match rows.iter().find(|row| row.values.get(column) != Some(value)) {
+                        Some(row) => Err(format!("{} row {} has {}={:?}", table, row.row_id, column, row.values.get(column))),
+                        None => Ok(()),
+                    }
+                }
+                _ => Ok(()),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    
+    struct FakeRunner {
+        healthy: AtomicBool,
+    }
+    
+    impl ProbeRunner for FakeRunner {
+        fn run_command(&self, program: &str, _args: &[String], _timeout: Duration) -> Result<(), String> {
+            if self.healthy.load(Ordering::SeqCst) { Ok(()) } else { Err(format!("'{}' exited with 1", program)) }
+        }
+        
+        fn http_status(&self, _url: &str, _timeout: Duration) -> Result<u16, String> {
+            Ok(if self.healthy.load(Ordering::SeqCst) { 200 } else { 503 })
+        }
+    }
+    
+    #[test]
+    fn test_probes_rollup_and_alerts() {
+        let probes: Vec<HealthProbe> = serde_json::from_str(r#"[
+            {"name": "alive", "check": {"type": "command", "program": "pidof", "args": ["sched"]}, "interval_secs": 0},
+            {"name": "api", "check": {"type": "http", "url": "http://localhost/health"}, "interval_secs": 0, "failure_status": "Degraded"}
+        ]"#).unwrap();
+        let tables = TablesManager::new();
+        let runner = FakeRunner { healthy: AtomicBool::new(true) };
+        let mut checker = HealthChecker::new();
+        checker.allow_commands(["pidof"]);
+        checker.register("kernel", "sched", probes);
+        checker.register("kernel", "idle", Vec::new());
+        checker.add_rule(AlertRule {
+            name: "scheduler down".to_string(),
+            project: Some("kernel".to_string()),
+            target_id: None,
+            min_status: HealthStatus::Degraded,
+            consecutive_failures: 2,
+        });
+        assert_eq!(checker.target_status("sched"), HealthStatus::Unknown);
+        
+        assert!(checker.run_due(&runner, &tables).is_empty());
+        assert_eq!(checker.target_status("sched"), HealthStatus::Healthy);
+        
+        runner.healthy.store(false, Ordering::SeqCst);
+        assert!(checker.run_due(&runner, &tables).is_empty());
+        assert_eq!(checker.project_status("kernel"), HealthStatus::Unhealthy);
+        let mut alerts = checker.run_due(&runner, &tables);
+        alerts.sort_by(|a, b| a.probe.cmp(&b.probe));
+        assert_eq!(alerts.len(), 2);
+        assert_eq!((alerts[0].target_id.as_str(), alerts[0].probe.as_str()), ("sched", "alive"));
+        assert_eq!(alerts[1].status, HealthStatus::Degraded);
+        assert!(checker.run_due(&runner, &tables).is_empty());
+        assert_eq!(checker.results("sched")[1].status, HealthStatus::Degraded);
+        
+        runner.healthy.store(true, Ordering::SeqCst);
+        checker.run_due(&runner, &tables);
+        assert_eq!(checker.project_summary("kernel")[&HealthStatus::Healthy], 1);
+        assert_eq!(checker.project_summary("kernel")[&HealthStatus::Unknown], 1);
+    }
+    
+    #[test]
+    fn test_streaks_are_counted_per_probe_and_rule() {
+        let probes: Vec<HealthProbe> = serde_json::from_str(r#"[
+            {"name": "fast", "check": {"type": "http", "url": "http://localhost/health"}, "interval_secs": 0, "failure_status": "Degraded"},
+            {"name": "slow", "check": {"type": "http", "url": "http://localhost/ready"}, "interval_secs": 3600}
+        ]"#).unwrap();
+        let tables = TablesManager::new();
+        let runner = FakeRunner { healthy: AtomicBool::new(false) };
+        let mut checker = HealthChecker::new();
+        checker.register("kernel", "net", probes);
+        for (name, min_status) in [("degraded", HealthStatus::Degraded), ("down", HealthStatus::Unhealthy)] {
+            checker.add_rule(AlertRule {
+                name: name.to_string(),
+                project: None,
+                target_id: None,
+                min_status,
+                consecutive_failures: 2,
+            });
+        }
+        
+        assert!(checker.run_due(&runner, &tables).is_empty());
+        // Only the fast probe ran again: the slow probe's single failure does not count twice,
+        // and the fast probe is not bad enough for the "down" rule
+        let alerts = checker.run_due(&runner, &tables);
+        assert_eq!(alerts.len(), 1);
+        assert_eq!((alerts[0].rule.as_str(), alerts[0].probe.as_str()), ("degraded", "fast"));
+        assert!(checker.run_due(&runner, &tables).is_empty());
+    }
+    
+    #[test]
+    fn test_command_probes_need_an_allowlist() {
+        let probes: Vec<HealthProbe> = serde_json::from_str(r#"[
+            {"name": "alive", "check": {"type": "command", "program": "sh", "args": ["-c", "true"]}}
+        ]"#).unwrap();
+        let tables = TablesManager::new();
+        let runner = FakeRunner { healthy: AtomicBool::new(true) };
+        let mut checker = HealthChecker::new();
+        checker.register("kernel", "sched", probes);
+        checker.run_due(&runner, &tables);
+        let result = &checker.results("sched")[0];
+        assert_eq!(result.status, HealthStatus::Unknown);
+        assert!(result.message.contains(PROBE_COMMANDS_ENV));
+    }
+}
@@ -0,0 +1,101 @@
+// Interop statistics panel for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+use std::sync::Arc;
+use gpui::{Widget, View, ViewContext, RenderContext, LayoutContext, EventContext, Color, Rect, Point, BoxConstraints, Label, ScrollView, Panel};
+use crate::runtime::call_tracing::{CallPhase, CallTracer};
+
+/// Number of functions listed as bottlenecks
+const BOTTLENECK_LIMIT: usize = 10;
+
+/// Cross-language call latency widget
+pub struct InteropStatsPanel {
+    /// Tracer of the interop service
+    tracer: Arc<CallTracer>,
+    
+    /// UI components
+    main_panel: Panel,
+    scroll_view: ScrollView,
+}
+
+impl InteropStatsPanel {
+    /// Create a new interop statistics panel
+    pub fn new(tracer: Arc<CallTracer>) -> Self {
+        Self {
+            tracer,
+            main_panel: Panel::new(),
+            scroll_view: ScrollView::new(),
+        }
+    }
+    
+    /// Initialize UI components
+    fn init_ui_components(&mut self, cx: &mut ViewContext) {
+        self.scroll_view = ScrollView::new();
+        
+        // Add interop statistics
+        self.add_interop_stats(cx);
+        
+        self.main_panel.set_content(self.scroll_view.clone());
+    }
+    
+    /// Add interop statistics to UI
+    fn add_interop_stats(&mut self, cx: &mut ViewContext) {
+        self.scroll_view.add(Label::new("Cross-Language Calls"));
+        
+        let bottlenecks = self.tracer.bottlenecks(BOTTLENECK_LIMIT);
+        if bottlenecks.is_empty() {
+            self.scroll_view.add(Label::new("No cross-language calls traced"));
+            return;
+        }
+        
+        // Share of each phase over all calls
+        let totals = self.tracer.phase_totals();
+        let overall: u64 = totals.values().sum();
+        let breakdown: Vec<String> = CallPhase::all().iter()
+            .map(|phase| {
+                let share = totals.get(phase).copied().unwrap_or(0) as f64 * 100.0 / overall.max(1) as f64;
+                format!("{} {:.0}%", phase.name(), share)
+            })
+            .collect();
+        self.scroll_view.add(Label::new(&format!("Time spent: {}", breakdown.join("  "))));
+        
+        for stats in bottlenecks {
+            let phases: Vec<String> = CallPhase::all().iter()
+                .map(|phase| format!("{} {:.2}ms", phase.name(), stats.mean_phase_us(*phase) as f64 / 1000.0))
+                .collect();
+            let stats_label = Label::new(&format!(
+                "{}::{}  {} calls, {} failed, mean {:.2}ms ({})",
+                stats.callee_language.as_str(),
+                stats.function_name,
+                stats.calls,
+                stats.failures,
+                stats.mean_total_us() as f64 / 1000.0,
+                phases.join(", "),
+            ));
+            self.scroll_view.add(stats_label);
+        }
+    }
+    
+    /// Refresh the UI
+    pub fn refresh(&mut self, cx: &mut ViewContext) {
+        self.init_ui_components(cx);
+        cx.request_layout();
+        cx.request_paint();
+    }
+}
+
+// GPUI Widget implementation for InteropStatsPanel
+impl Widget for InteropStatsPanel {
+    fn layout(&mut self, constraints: BoxConstraints, cx: &mut LayoutContext) -> gpui::Size {
+        self.main_panel.layout(constraints, cx)
+    }
+    
+    fn paint(&mut self, cx: &mut RenderContext) {
+        self.main_panel.paint(cx);
+    }
+    
+    fn handle_event(&mut self, event: &gpui::Event, cx: &mut EventContext) {
+        self.main_panel.handle_event(event, cx);
+    }
+}
@@ -6,9 +6,17 @@ pub mod dashboard_panel;
 pub mod component_monitor;
 pub mod project_manager;
 pub mod search_system;
+pub mod test_results;
+pub mod interop_stats;
+pub mod widgets;
+pub mod health_checks;
 
 // Re-export core components
 pub use dashboard_panel::DashboardPanel;
 pub use component_monitor::{ComponentMonitor, ComponentStatus};
 pub use project_manager::ProjectManager;
-pub use search_system::GlobalSearchSystem;
\ No newline at end of file
+pub use search_system::GlobalSearchSystem;
+pub use test_results::TestResultsPanel;
+pub use interop_stats::InteropStatsPanel;
+pub use widgets::{DashboardLayout, DataQuery, RefreshPolicy, RenderHint, WidgetDescriptor, WidgetRegistry};
+pub use health_checks::{HealthChecker, HealthProbe, HealthStatus, ProbeCheck, AlertRule, Alert};
\ No newline at end of file
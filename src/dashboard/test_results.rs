@@ -0,0 +1,127 @@
+// Test results panel for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+use gpui::{Widget, View, ViewContext, RenderContext, LayoutContext, EventContext, Color, Rect, Point, BoxConstraints, Label, ScrollView, Panel};
+use crate::build_engine::test_orchestrator::{TestOutcome, TestReport};
+
+/// Test results widget
+pub struct TestResultsPanel {
+    /// Last test report
+    report: Option<TestReport>,
+    
+    /// Show console output of failed tests
+    show_failure_console: bool,
+    
+    /// UI components
+    main_panel: Panel,
+    scroll_view: ScrollView,
+}
+
+impl TestResultsPanel {
+    /// Create a new test results panel
+    pub fn new() -> Self {
+        Self {
+            report: None,
+            show_failure_console: true,
+            main_panel: Panel::new(),
+            scroll_view: ScrollView::new(),
+        }
+    }
+    
+    /// Update the displayed test report
+    pub fn update_report(&mut self, report: TestReport) {
+        self.report = Some(report);
+    }
+    
+    /// Get the displayed test report
+    pub fn get_report(&self) -> Option<&TestReport> {
+        self.report.as_ref()
+    }
+    
+    /// Toggle console output for failed tests
+    pub fn set_show_failure_console(&mut self, show: bool) {
+        self.show_failure_console = show;
+    }
+    
+    /// Initialize UI components
+    fn init_ui_components(&mut self, cx: &mut ViewContext) {
+        self.scroll_view = ScrollView::new();
+        
+        // Add test results
+        self.add_test_results(cx);
+        
+        self.main_panel.set_content(self.scroll_view.clone());
+    }
+    
+    /// Add test results to UI
+    fn add_test_results(&mut self, cx: &mut ViewContext) {
+        let title = Label::new("Test Results");
+        self.scroll_view.add(title);
+        
+        let report = match &self.report {
+            Some(report) => report,
+            None => {
+                self.scroll_view.add(Label::new("No tests have been run"));
+                return;
+            }
+        };
+        
+        let summary_label = Label::new(&format!("Passed: {}  Failed: {}  Skipped: {}", report.passed(), report.failed(), report.skipped()));
+        self.scroll_view.add(summary_label);
+        
+        for result in &report.results {
+            let (status, message) = match &result.outcome {
+                TestOutcome::Passed => ("PASS", None),
+                TestOutcome::Failed(message) => ("FAIL", Some(message)),
+                TestOutcome::TimedOut(message) => ("TIMEOUT", Some(message)),
+                TestOutcome::Error(message) => ("ERROR", Some(message)),
+                TestOutcome::Skipped(message) => ("SKIP", Some(message)),
+            };
+            
+            let result_label = Label::new(&format!("[{}] {}::{} ({:.1}s)", status, result.suite, result.name, result.duration_secs));
+            self.scroll_view.add(result_label);
+            
+            if let Some(message) = message {
+                let message_label = Label::new(&format!("  {}", message));
+                self.scroll_view.add(message_label);
+                
+                if self.show_failure_console {
+                    // Show the tail of the console, where the failure usually is
+                    let lines: Vec<&str> = result.console.lines().collect();
+                    for line in &lines[lines.len().saturating_sub(10)..] {
+                        self.scroll_view.add(Label::new(&format!("    | {}", line)));
+                    }
+                }
+            }
+        }
+    }
+    
+    /// Refresh the UI
+    pub fn refresh(&mut self, cx: &mut ViewContext) {
+        self.init_ui_components(cx);
+        cx.request_layout();
+        cx.request_paint();
+    }
+}
+
+// GPUI Widget implementation for TestResultsPanel
+impl Widget for TestResultsPanel {
+    fn layout(&mut self, constraints: BoxConstraints, cx: &mut LayoutContext) -> gpui::Size {
+        self.main_panel.layout(constraints, cx)
+    }
+    
+    fn paint(&mut self, cx: &mut RenderContext) {
+        self.main_panel.paint(cx);
+    }
+    
+    fn handle_event(&mut self, event: &gpui::Event, cx: &mut EventContext) {
+        self.main_panel.handle_event(event, cx);
+    }
+}
+
+impl Default for TestResultsPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,420 @@
+// Dashboard widget framework for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! Configurable dashboard widgets.
+//!
+//! A widget kind is described by a `WidgetDescriptor`: the data it queries (a DBOS
+//! table or a named metric), how often the data is refreshed and how it should be
+//! rendered. Built-in panels and plugins register kinds in a `WidgetRegistry`; users
+//! place instances of them on a grid `DashboardLayout` saved per project.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use serde::{Deserialize, Serialize};
+use crate::core::event_bus::EventTopic;
+use crate::dbos_integration::dbos_core::TablesManager;
+
+/// Layout file, relative to the project directory
+pub const DASHBOARD_LAYOUT_FILE: &str = ".osland/dashboard.json";
+
+/// Default number of grid columns
+pub const DEFAULT_GRID_COLUMNS: u32 = 12;
+
+/// Owner of the built-in widget kinds
+pub const BUILTIN_WIDGET_SOURCE: &str = "builtin";
+
+/// Data a widget displays
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum DataQuery {
+    /// Rows of a DBOS table matching column values
+    Table {
+        table: String,
+        #[serde(default)]
+        conditions: HashMap<String, String>,
+    },
+    
+    /// Current value of a registered metric
+    Metric { name: String },
+    
+    /// The widget has no data (e.g. static text)
+    None,
+}
+
+/// When the data of a widget is fetched again
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum RefreshPolicy {
+    /// Only when the user asks
+    Manual,
+    
+    /// Periodically
+    Interval(Duration),
+    
+    /// Whenever an event of the topic is published
+    OnEvent(EventTopic),
+}
+
+/// How a widget wants its data rendered
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum RenderHint {
+    /// Plain text lines
+    Text,
+    
+    /// Table with the given columns
+    Table { columns: Vec<String> },
+    
+    /// Single value against a range
+    Gauge { min: f64, max: f64, unit: String },
+    
+    /// Values over time, in the order of `time_column` (row order when empty)
+    LineChart {
+        value_column: String,
+        #[serde(default)]
+        time_column: String,
+    },
+    
+    /// Values per category
+    BarChart { label_column: String, value_column: String },
+    
+    /// Per-component lanes of the kernel traces of test runs (rows of the trace events table)
+    TraceTimeline { columns: usize },
+}
+
+/// Description of a widget kind
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WidgetDescriptor {
+    /// Unique kind name, e.g. "build-history"
+    pub kind: String,
+    
+    /// Title shown on the widget
+    pub title: String,
+    
+    /// Data query
+    pub query: DataQuery,
+    
+    /// Refresh policy
+    pub refresh: RefreshPolicy,
+    
+    /// Render hint
+    pub render: RenderHint,
+    
+    /// Default size in grid cells (width, height)
+    pub default_size: (u32, u32),
+}
+
+/// Data fetched for a widget
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum WidgetData {
+    /// Table rows
+    Rows(Vec<HashMap<String, String>>),
+    
+    /// Metric value
+    Value(f64),
+    
+    /// Nothing fetched yet
+    Empty,
+}
+
+/// Provider of a metric value
+pub type MetricProvider = Arc<dyn Fn() -> f64 + Send + Sync>;
+
+/// Widget kinds and metrics available to dashboards
+#[derive(Default, Clone)]
+pub struct WidgetRegistry {
+    /// Widget kinds with the name of the plugin (or "builtin") that registered them
+    descriptors: HashMap<String, (WidgetDescriptor, String)>,
+    
+    /// Metric providers by name
+    metrics: HashMap<String, MetricProvider>,
+}
+
+impl WidgetRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Register a widget kind contributed by a plugin (or `BUILTIN_WIDGET_SOURCE`)
+    pub fn register(&mut self, descriptor: WidgetDescriptor, source: &str) -> Result<(), String> {
+        if let Some((_, owner)) = self.descriptors.get(&descriptor.kind) {
+            if owner != source {
+                return Err(format!("Widget kind '{}' is already registered by {}", descriptor.kind, owner));
+            }
+        }
+        self.descriptors.insert(descriptor.kind.clone(), (descriptor, source.to_string()));
+        Ok(())
+    }
+    
+    /// Remove the widget kinds of a plugin, e.g. when it is unloaded
+    pub fn unregister_source(&mut self, source: &str) -> Vec<String> {
+        let kinds: Vec<String> = self.descriptors.iter()
+            .filter(|(_, (_, owner))| owner == source)
+            .map(|(kind, _)| kind.clone())
+            .collect();
+        for kind in &kinds {
+            self.descriptors.remove(kind);
+        }
+        kinds
+    }
+    
+    /// Register a metric
+    pub fn register_metric(&mut self, name: &str, provider: MetricProvider) {
+        self.metrics.insert(name.to_string(), provider);
+    }
+    
+    /// Get a widget kind
+    pub fn descriptor(&self, kind: &str) -> Option<&WidgetDescriptor> {
+        self.descriptors.get(kind).map(|(descriptor, _)| descriptor)
+    }
+    
+    /// All widget kinds, sorted by title
+    pub fn descriptors(&self) -> Vec<&WidgetDescriptor> {
+        let mut descriptors: Vec<&WidgetDescriptor> = self.descriptors.values().map(|(descriptor, _)| descriptor).collect();
+        descriptors.sort_by(|a, b| a.title.cmp(&b.title));
+        descriptors
+    }
+    
+    /// Run a data query
+    pub fn fetch(&self, query: &DataQuery, tables: &TablesManager) -> Result<WidgetData, String> {
+        match query {
+            DataQuery::Table { table, conditions } => {
+                let rows = tables.query_rows(table, conditions.clone())?;
+                Ok(WidgetData::Rows(rows.into_iter().map(|row| row.values).collect()))
+            }
+            DataQuery::Metric { name } => {
+                let provider = self.metrics.get(name).ok_or_else(|| format!("Unknown metric: {}", name))?;
+                Ok(WidgetData::Value(provider()))
+            }
+            DataQuery::None => Ok(WidgetData::Empty),
+        }
+    }
+}
+
+/// Widget placed on a dashboard
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WidgetInstance {
+    /// Instance ID
+    pub id: String,
+    
+    /// Widget kind
+    pub kind: String,
+    
+    /// Top-left grid cell (column, row)
+    pub position: (u32, u32),
+    
+    /// Size in grid cells (width, height)
+    pub size: (u32, u32),
+    
+    /// Title overriding the kind's title
+    #[serde(default)]
+    pub title: Option<String>,
+}
+
+impl WidgetInstance {
+    /// Check if two widgets cover a common cell
+    fn overlaps(&self, position: (u32, u32), size: (u32, u32)) -> bool {
+        self.position.0 < position.0 + size.0 && position.0 < self.position.0 + self.size.0 &&
+            self.position.1 < position.1 + size.1 && position.1 < self.position.1 + self.size.1
+    }
+}
+
+/// Arrangement of widgets on a project's dashboard
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DashboardLayout {
+    /// Number of grid columns
+    pub columns: u32,
+    
+    /// Placed widgets
+    pub widgets: Vec<WidgetInstance>,
+}
+
+impl Default for DashboardLayout {
+    fn default() -> Self {
+        Self {
+            columns: DEFAULT_GRID_COLUMNS,
+            widgets: Vec::new(),
+        }
+    }
+}
+
+impl DashboardLayout {
+    /// Path of the layout file of a project
+    pub fn path(project_dir: &Path) -> PathBuf {
+        project_dir.join(DASHBOARD_LAYOUT_FILE)
+    }
+    
+    /// Load the layout of a project; a project without one gets an empty layout
+    pub fn load(project_dir: &Path) -> Result<Self, String> {
+        let path = Self::path(project_dir);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))
+    }
+    
+    /// Save the layout in a project
+    pub fn save(&self, project_dir: &Path) -> Result<(), String> {
+        let path = Self::path(project_dir);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize dashboard layout: {}", e))?;
+        std::fs::write(&path, content)
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+    
+    /// Add a widget of a kind at the first free place, returning its instance ID
+    pub fn add_widget(&mut self, descriptor: &WidgetDescriptor) -> String {
+        let size = (descriptor.default_size.0.clamp(1, self.columns), descriptor.default_size.1.max(1));
+        let mut row = 0;
+        let position = loop {
+            let free = (0..=self.columns - size.0).find(|column| self.is_free((*column, row), size, None));
+            if let Some(column) = free {
+                break (column, row);
+            }
+            row += 1;
+        };
+        
+        let id = uuid::Uuid::new_v4().to_string();
+        self.widgets.push(WidgetInstance {
+            id: id.clone(),
+            kind: descriptor.kind.clone(),
+            position,
+            size,
+            title: None,
+        });
+        id
+    }
+    
+    /// Remove a widget
+    pub fn remove_widget(&mut self, id: &str) -> Option<WidgetInstance> {
+        let index = self.widgets.iter().position(|widget| widget.id == id)?;
+        Some(self.widgets.remove(index))
+    }
+    
+    /// Move and resize a widget; fails if it would leave the grid or cover another widget
+    pub fn place_widget(&mut self, id: &str, position: (u32, u32), size: (u32, u32)) -> Result<(), String> {
+        if size.0 == 0 || size.1 == 0 || position.0 + size.0 > self.columns {
+            return Err(format!("Widget does not fit in {} columns", self.columns));
+        }
+        if !self.is_free(position, size, Some(id)) {
+            return Err("Widget would overlap another widget".to_string());
+        }
+        let widget = self.widgets.iter_mut().find(|widget| widget.id == id)
+            .ok_or_else(|| format!("Unknown widget: {}", id))?;
+        widget.position = position;
+        widget.size = size;
+        Ok(())
+    }
+    
+    /// Widgets in reading order (top to bottom, left to right)
+    pub fn ordered_widgets(&self) -> Vec<&WidgetInstance> {
+        let mut widgets: Vec<&WidgetInstance> = self.widgets.iter().collect();
+        widgets.sort_by_key(|widget| (widget.position.1, widget.position.0));
+        widgets
+    }
+    
+    /// Check if an area is free, ignoring one widget
+    fn is_free(&self, position: (u32, u32), size: (u32, u32), ignore: Option<&str>) -> bool {
+        self.widgets.iter()
+            .filter(|widget| Some(widget.id.as_str()) != ignore)
+            .all(|widget| !widget.overlaps(position, size))
+    }
+}
+
+/// Tracks when widget data must be fetched again
+#[derive(Debug, Default)]
+pub struct RefreshScheduler {
+    /// Last refresh time by widget instance
+    last_refresh: HashMap<String, Instant>,
+}
+
+impl RefreshScheduler {
+    /// Create a scheduler
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Check if a widget needs fresh data, given the event topics published since the last check
+    pub fn is_due(&self, instance_id: &str, policy: &RefreshPolicy, events: &[EventTopic]) -> bool {
+        let last_refresh = match self.last_refresh.get(instance_id) {
+            Some(last_refresh) => last_refresh,
+            None => return true,
+        };
+        match policy {
+            RefreshPolicy::Manual => false,
+            RefreshPolicy::Interval(interval) => last_refresh.elapsed() >= *interval,
+            RefreshPolicy::OnEvent(topic) => events.contains(topic),
+        }
+    }
+    
+    /// Record that a widget was refreshed
+    pub fn mark_refreshed(&mut self, instance_id: &str) {
+        self.last_refresh.insert(instance_id.to_string(), Instant::now());
+    }
+    
+    /// Force the next check of a widget to refresh it
+    pub fn invalidate(&mut self, instance_id: &str) {
+        self.last_refresh.remove(instance_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn descriptor(kind: &str, size: (u32, u32)) -> WidgetDescriptor {
+        WidgetDescriptor {
+            kind: kind.to_string(),
+            title: kind.to_string(),
+            query: DataQuery::Metric { name: "cpu".to_string() },
+            refresh: RefreshPolicy::OnEvent(EventTopic::Build),
+            render: RenderHint::Gauge { min: 0.0, max: 100.0, unit: "%".to_string() },
+            default_size: size,
+        }
+    }
+    
+    #[test]
+    fn test_layout_and_registry() {
+        let mut registry = WidgetRegistry::new();
+        registry.register(descriptor("cpu", (6, 2)), BUILTIN_WIDGET_SOURCE).unwrap();
+        assert!(registry.register(descriptor("cpu", (6, 2)), "perf-plugin").is_err());
+        registry.register(descriptor("flame", (12, 4)), "perf-plugin").unwrap();
+        registry.register_metric("cpu", Arc::new(|| 42.0));
+        
+        let tables = TablesManager::new();
+        assert_eq!(registry.fetch(&DataQuery::Metric { name: "cpu".to_string() }, &tables).unwrap(), WidgetData::Value(42.0));
+        
+        let mut layout = DashboardLayout::default();
+        let first = layout.add_widget(registry.descriptor("cpu").unwrap());
+        let second = layout.add_widget(registry.descriptor("cpu").unwrap());
+        let third = layout.add_widget(registry.descriptor("flame").unwrap());
+        assert_eq!(layout.widgets[1].position, (6, 0));
+        assert_eq!(layout.widgets[2].position, (0, 2));
+        assert!(layout.place_widget(&second, (3, 0), (6, 2)).is_err());
+        assert!(layout.place_widget(&second, (8, 0), (6, 2)).is_err());
+        layout.place_widget(&second, (0, 6), (4, 2)).unwrap();
+        layout.remove_widget(&first);
+        assert_eq!(layout.ordered_widgets().iter().map(|widget| widget.id.clone()).collect::<Vec<_>>(), vec![third, second]);
+        
+        let project = tempfile::tempdir().unwrap();
+        layout.save(project.path()).unwrap();
+        assert_eq!(DashboardLayout::load(project.path()).unwrap(), layout);
+        
+        assert_eq!(registry.unregister_source("perf-plugin"), vec!["flame".to_string()]);
+        
+        let mut scheduler = RefreshScheduler::new();
+        let policy = RefreshPolicy::OnEvent(EventTopic::Build);
+        assert!(scheduler.is_due("w", &policy, &[]));
+        scheduler.mark_refreshed("w");
+        assert!(!scheduler.is_due("w", &policy, &[EventTopic::Table]));
+        assert!(scheduler.is_due("w", &policy, &[EventTopic::Build]));
+    }
+}
@@ -1,342 +0,0 @@
-// Time Travel Engine for DBOS Integration in OSland
-// Copyright (c) 2025 OSland Project Team
-// SPDX-License-Identifier: MulanPSL-2.0
-
-use std::collections::HashMap;
-use std::sync::{Arc, RwLock};
-use serde::{Serialize, Deserialize};
-use std::time::SystemTime;
-
-/// Time Travel Engine
-pub struct TimeTravelEngine {
-    /// Snapshots of system states
-    snapshots: Arc<RwLock<HashMap<u64, SystemSnapshot>>>,
-    
-    /// Timeline of events
-    timeline: Arc<RwLock<Vec<SystemEvent>>>,
-    
-    /// Is the engine running
-    running: Arc<RwLock<bool>>,
-    
-    /// Current timestamp for time travel
-    current_timestamp: Arc<RwLock<u64>>,
-}
-
-/// System Snapshot
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SystemSnapshot {
-    /// Timestamp of the snapshot
-    pub timestamp: u64,
-    
-    /// System state at this time
-    pub state: HashMap<String, String>,
-    
-    /// Active transactions at this time
-    pub active_transactions: Vec<String>,
-    
-    /// Running components at this time
-    pub running_components: Vec<String>,
-    
-    /// Resource states at this time
-    pub resource_states: HashMap<String, String>,
-}
-
-/// System Event
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SystemEvent {
-    /// Timestamp of the event
-    pub timestamp: u64,
-    
-    /// Event type
-    pub event_type: SystemEventType,
-    
-    /// Event description
-    pub description: String,
-    
-    /// Associated data
-    pub data: Option<String>,
-    
-    /// Event severity
-    pub severity: EventSeverity,
-}
-
-/// System Event Type
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub enum SystemEventType {
-    ComponentStarted,
-    ComponentStopped,
-    TransactionStarted,
-    TransactionCommitted,
-    TransactionRolledBack,
-    StateChanged,
-    SystemError,
-    ResourceCreated,
-    ResourceModified,
-    ResourceDeleted,
-    Custom(String),
-}
-
-/// Event Severity
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub enum EventSeverity {
-    Info,
-    Warning,
-    Error,
-    Critical,
-}
-
-impl TimeTravelEngine {
-    /// Create a new time travel engine
-    pub fn new() -> Self {
-        Self {
-            snapshots: Arc::new(RwLock::new(HashMap::new())),
-            timeline: Arc::new(RwLock::new(Vec::new())),
-            running: Arc::new(RwLock::new(false)),
-            current_timestamp: Arc::new(RwLock::new(0)),
-        }
-    }
-    
-    /// Start the time travel engine
-    pub fn start(&self) {
-        let mut running = self.running.write().unwrap();
-        *running = true;
-        
-        // Record system start event
-        self.record_event(
-            SystemEventType::Custom("TimeTravelEngineStarted".to_string()),
-            "Time travel engine started".to_string(),
-            None,
-            EventSeverity::Info,
-        ).unwrap_or_default();
-    }
-    
-    /// Stop the time travel engine
-    pub fn stop(&self) {
-        // Record system stop event
-        self.record_event(
-            SystemEventType::Custom("TimeTravelEngineStopped".to_string()),
-            "Time travel engine stopped".to_string(),
-            None,
-            EventSeverity::Info,
-        ).unwrap_or_default();
-        
-        let mut running = self.running.write().unwrap();
-        *running = false;
-    }
-    
-    /// Create a system snapshot
-    pub fn create_snapshot(
-        &self,
-        state: HashMap<String, String>,
-        active_transactions: Vec<String>,
-        running_components: Vec<String>,
-        resource_states: HashMap<String, String>,
-    ) -> Result<u64, String> {
-        let running = self.running.read().unwrap();
-        if !*running {
-            return Err("Time travel engine is not running".to_string());
-        }
-        
-        let timestamp = SystemTime::now()
-            .duration_since(SystemTime::UNIX_EPOCH)
-            .map(|d| d.as_secs())
-            .unwrap_or(0);
-        
-        let snapshot = SystemSnapshot {
-            timestamp,
-            state,
-            active_transactions,
-            running_components,
-            resource_states,
-        };
-        
-        let mut snapshots = self.snapshots.write().unwrap();
-        snapshots.insert(timestamp, snapshot);
-        
-        // Update current timestamp
-        let mut current_timestamp = self.current_timestamp.write().unwrap();
-        *current_timestamp = timestamp;
-        
-        // Record snapshot creation event
-        self.record_event(
-            SystemEventType::Custom("SnapshotCreated".to_string()),
-            format!("System snapshot created at timestamp {}", timestamp),
-            Some(timestamp.to_string()),
-            EventSeverity::Info,
-        )?;
-        
-        Ok(timestamp)
-    }
-    
-    /// Get a snapshot by timestamp
-    pub fn get_snapshot(&self, timestamp: u64) -> Result<Option<SystemSnapshot>, String> {
-        let snapshots = self.snapshots.read().unwrap();
-        Ok(snapshots.get(&timestamp).cloned())
-    }
-    
-    /// Get all snapshots
-    pub fn get_all_snapshots(&self) -> Result<Vec<SystemSnapshot>, String> {
-        let snapshots = self.snapshots.read().unwrap();
-        let mut snapshot_vec: Vec<SystemSnapshot> = snapshots.values().cloned().collect();
-        snapshot_vec.sort_by_key(|s| s.timestamp);
-        Ok(snapshot_vec)
-    }
-    
-    /// Restore system to a specific timestamp
-    pub fn restore_to_timestamp(&self, timestamp: u64) -> Result<Option<SystemSnapshot>, String> {
-        let snapshots = self.snapshots.read().unwrap();
-        
-        // Find the latest snapshot before or at the given timestamp
-        let snapshot = snapshots
-            .iter()
-            .filter(|(_, snap)| snap.timestamp <= timestamp)
-            .max_by_key(|(_, snap)| snap.timestamp)
-            .map(|(_, snap)| snap.clone());
-        
-        if let Some(ref snapshot) = snapshot {
-            // Update current timestamp
-            let mut current_timestamp = self.current_timestamp.write().unwrap();
-            *current_timestamp = snapshot.timestamp;
-            
-            // Record restoration event
-            self.record_event(
-                SystemEventType::Custom("SystemRestored".to_string()),
-                format!("System restored to timestamp {}", timestamp),
-                Some(timestamp.to_string()),
-                EventSeverity::Info,
-            )?;
-        }
-        
-        Ok(snapshot)
-    }
-    
-    /// Get current timestamp
-    pub fn get_current_timestamp(&self) -> Result<u64, String> {
-        let current_timestamp = self.current_timestamp.read().unwrap();
-        Ok(*current_timestamp)
-    }
-    
-    /// Record a system event
-    pub fn record_event(
-        &self,
-        event_type: SystemEventType,
-        description: String,
-        data: Option<String>,
-        severity: EventSeverity,
-    ) -> Result<u64, String> {
-        let timestamp = SystemTime::now()
-            .duration_since(SystemTime::UNIX_EPOCH)
-            .map(|d| d.as_secs())
-            .unwrap_or(0);
-        
-        let event = SystemEvent {
-            timestamp,
-            event_type,
-            description,
-            data,
-            severity,
-        };
-        
-        let mut timeline = self.timeline.write().unwrap();
-        timeline.push(event);
-        
-        Ok(timestamp)
-    }
-    
-    /// Get events within a time range
-    pub fn get_events_in_range(
-        &self,
-        start_time: u64,
-        end_time: u64,
-    ) -> Result<Vec<SystemEvent>, String> {
-        let timeline = self.timeline.read().unwrap();
-        let filtered: Vec<SystemEvent> = timeline
-            .iter()
-            .filter(|event| event.timestamp >= start_time && event.timestamp <= end_time)
-            .cloned()
-            .collect();
-        Ok(filtered)
-    }
-    
-    /// Get all events
-    pub fn get_all_events(&self) -> Result<Vec<SystemEvent>, String> {
-        let timeline = self.timeline.read().unwrap();
-        Ok(timeline.clone())
-    }
-    
-    /// Find events by type
-    pub fn find_events_by_type(
-        &self,
-        event_type: SystemEventType,
-    ) -> Result<Vec<SystemEvent>, String> {
-        let timeline = self.timeline.read().unwrap();
-        let filtered: Vec<SystemEvent> = timeline
-            .iter()
-            .filter(|event| event.event_type == event_type)
-            .cloned()
-            .collect();
-        Ok(filtered)
-    }
-    
-    /// Find events by severity
-    pub fn find_events_by_severity(
-        &self,
-        severity: EventSeverity,
-    ) -> Result<Vec<SystemEvent>, String> {
-        let timeline = self.timeline.read().unwrap();
-        let filtered: Vec<SystemEvent> = timeline
-            .iter()
-            .filter(|event| event.severity == severity)
-            .cloned()
-            .collect();
-        Ok(filtered)
-    }
-    
-    /// Get snapshot count
-    pub fn get_snapshot_count(&self) -> Result<usize, String> {
-        let snapshots = self.snapshots.read().unwrap();
-        Ok(snapshots.len())
-    }
-    
-    /// Get event count
-    pub fn get_event_count(&self) -> Result<usize, String> {
-        let timeline = self.timeline.read().unwrap();
-        Ok(timeline.len())
-    }
-    
-    /// Create a checkpoint for quick restoration
-    pub fn create_checkpoint(&self, name: String) -> Result<u64, String> {
-        // This would typically save the current state to a named checkpoint
-        // For now, we'll just record the event
-        let timestamp = SystemTime::now()
-            .duration_since(SystemTime::UNIX_EPOCH)
-            .map(|d| d.as_secs())
-            .unwrap_or(0);
-        
-        self.record_event(
-            SystemEventType::Custom("CheckpointCreated".to_string()),
-            format!("Checkpoint '{}' created at timestamp {}", name, timestamp),
-            Some(name),
-            EventSeverity::Info,
-        )?;
-        
-        Ok(timestamp)
-    }
-    
-    /// Restore to a named checkpoint
-    pub fn restore_to_checkpoint(&self, name: String) -> Result<Option<SystemSnapshot>, String> {
-        // This would typically find the checkpoint and restore to it
-        // For now, we'll just record the event and return the latest snapshot
-        self.record_event(
-            SystemEventType::Custom("CheckpointRestored".to_string()),
-            format!("Restoring to checkpoint '{}'", name),
-            Some(name),
-            EventSeverity::Info,
-        )?;
-        
-        // Return the latest snapshot as a placeholder
-        let snapshots = self.snapshots.read().unwrap();
-        let latest_snapshot = snapshots.values().max_by_key(|s| s.timestamp).cloned();
-        Ok(latest_snapshot)
-    }
-}
\ No newline at end of file
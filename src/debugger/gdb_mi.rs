@@ -0,0 +1,325 @@
+// GDB machine interface parser for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! Parser for gdb/MI output records.
+//!
+//! Every line gdb prints in MI mode is a record: the result of a command (`^done`,
+//! `^error`, ...), an asynchronous notification (`*stopped`, `=breakpoint-modified`),
+//! a stream of console, target or log text, or the `(gdb)` prompt. Values are
+//! C strings, tuples (`{a="1"}`) and lists (`["a","b"]` or `[frame={...},frame={...}]`).
+//! Names of list items are dropped since they are the same for every item.
+
+use serde::{Deserialize, Serialize};
+
+/// MI value
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum MiValue {
+    /// C string
+    Const(String),
+    
+    /// Named values (`{name=value,...}` or the results of a record)
+    Tuple(Vec<(String, MiValue)>),
+    
+    /// Values (`[value,...]` or `[name=value,...]`)
+    List(Vec<MiValue>),
+}
+
+impl MiValue {
+    /// Value of a tuple field
+    pub fn get(&self, name: &str) -> Option<&MiValue> {
+        match self {
+            MiValue::Tuple(fields) => fields.iter().find(|(field, _)| field == name).map(|(_, value)| value),
+            _ => None,
+        }
+    }
+    
+    /// String value of a tuple field
+    pub fn get_str(&self, name: &str) -> Option<&str> {
+        self.get(name).and_then(MiValue::as_str)
+    }
+    
+    /// String value
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            MiValue::Const(value) => Some(value),
+            _ => None,
+        }
+    }
+    
+    /// Items of a list (an empty tuple `{}` is printed for some empty lists)
+    pub fn items(&self) -> &[MiValue] {
+        match self {
+            MiValue::List(items) => items,
+            _ => &[],
+        }
+    }
+}
+
+/// Stream record kind
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StreamKind {
+    /// CLI output (`~`)
+    Console,
+    /// Output of the target (`@`)
+    Target,
+    /// Internal log messages (`&`)
+    Log,
+}
+
+/// Asynchronous record kind
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AsyncKind {
+    /// Execution state changes (`*running`, `*stopped`)
+    Exec,
+    /// Progress of slow operations (`+`)
+    Status,
+    /// Notifications (`=breakpoint-modified`, `=thread-created`)
+    Notify,
+}
+
+/// MI output record
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum MiRecord {
+    /// Result of a command (`^done`, `^running`, `^connected`, `^error`, `^exit`)
+    Result { token: Option<u64>, class: String, results: MiValue },
+    
+    /// Asynchronous record
+    Async { token: Option<u64>, kind: AsyncKind, class: String, results: MiValue },
+    
+    /// Stream text
+    Stream { kind: StreamKind, text: String },
+    
+    /// `(gdb)` prompt ending a batch of output
+    Prompt,
+}
+
+/// Parse an MI output line
+pub fn parse_record(line: &str) -> Result<MiRecord, String> {
+    let line = line.trim_end_matches(['\r', '\n']);
+    if line.trim_end() == "(gdb)" {
+        return Ok(MiRecord::Prompt);
+    }
+    
+    let mut parser = Parser { input: line.as_bytes(), position: 0 };
+    let digits = parser.take_while(|c| c.is_ascii_digit());
+    let token = if digits.is_empty() {
+        None
+    } else {
+        Some(digits.parse().map_err(|e| format!("Invalid token '{}': {}", digits, e))?)
+    };
+    
+    let marker = parser.next().ok_or_else(|| format!("Empty MI record: '{}'", line))?;
+    let record = match marker {
+        b'~' | b'@' | b'&' => {
+            let kind = match marker {
+                b'~' => StreamKind::Console,
+                b'@' => StreamKind::Target,
+                _ => StreamKind::Log,
+            };
+            MiRecord::Stream { kind, text: parser.c_string()? }
+        }
+        b'^' => {
+            let (class, results) = parser.class_and_results()?;
+            MiRecord::Result { token, class, results }
+        }
+        b'*' | b'+' | b'=' => {
+            let kind = match marker {
+                b'*' => AsyncKind::Exec,
+                b'+' => AsyncKind::Status,
+                _ => AsyncKind::Notify,
+            };
+            let (class, results) = parser.class_and_results()?;
+            MiRecord::Async { token, kind, class, results }
+        }
+        other => return Err(format!("Unknown MI record type '{}' in '{}'", other as char, line)),
+    };
+    
+    if parser.position < parser.input.len() {
+        return Err(format!("Trailing characters in MI record '{}'", line));
+    }
+    Ok(record)
+}
+
+/// Quote an argument of an MI command when needed
+pub fn quote(argument: &str) -> String {
+    if !argument.is_empty() && argument.chars().all(|c| c.is_ascii_alphanumeric() || "_-+./:*$&<>".contains(c)) {
+        return argument.to_string();
+    }
+    let mut quoted = String::from("\"");
+    for c in argument.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+struct Parser<'a> {
+    input: &'a [u8],
+    position: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.input.get(self.position).copied()
+    }
+    
+    fn next(&mut self) -> Option<u8> {
+        let byte = self.peek()?;
+        self.position += 1;
+        Some(byte)
+    }
+    
+    fn expect(&mut self, expected: u8) -> Result<(), String> {
+        match self.next() {
+            Some(byte) if byte == expected => Ok(()),
+            Some(byte) => Err(format!("Expected '{}' at {}, found '{}'", expected as char, self.position - 1, byte as char)),
+            None => Err(format!("Expected '{}' at end of record", expected as char)),
+        }
+    }
+    
+    fn take_while(&mut self, accept: impl Fn(u8) -> bool) -> String {
+        let start = self.position;
+        while self.peek().is_some_and(&accept) {
+            self.position += 1;
+        }
+        String::from_utf8_lossy(&self.input[start..self.position]).into_owned()
+    }
+    
+    fn identifier(&mut self) -> Result<String, String> {
+        let name = self.take_while(|c| c.is_ascii_alphanumeric() || c == b'-' || c == b'_');
+        if name.is_empty() {
+            return Err(format!("Expected a name at {}", self.position));
+        }
+        Ok(name)
+    }
+    
+    /// `class[,name=value...]`
+    fn class_and_results(&mut self) -> Result<(String, MiValue), String> {
+        let class = self.identifier()?;
+        let mut results = Vec::new();
+        while self.peek() == Some(b',') {
+            self.position += 1;
+            results.push(self.result()?);
+        }
+        Ok((class, MiValue::Tuple(results)))
+    }
+    
+    fn result(&mut self) -> Result<(String, MiValue), String> {
+        let name = self.identifier()?;
+        self.expect(b'=')?;
+        Ok((name, self.value()?))
+    }
+    
+    fn value(&mut self) -> Result<MiValue, String> {
+        match self.peek() {
+            Some(b'"') => Ok(MiValue::Const(self.c_string()?)),
+            Some(b'{') => {
+                self.position += 1;
+                let mut fields = Vec::new();
+                while self.peek() != Some(b'}') {
+                    if !fields.is_empty() {
+                        self.expect(b',')?;
+                    }
+                    fields.push(self.result()?);
+                }
+                self.position += 1;
+                Ok(MiValue::Tuple(fields))
+            }
+            Some(b'[') => {
+                self.position += 1;
+                let mut items = Vec::new();
+                while self.peek() != Some(b']') {
+                    if !items.is_empty() {
+                        self.expect(b',')?;
+                    }
+                    if matches!(self.peek(), Some(b'"' | b'{' | b'[')) {
+                        items.push(self.value()?);
+                    } else {
+                        items.push(self.result()?.1);
+                    }
+                }
+                self.position += 1;
+                Ok(MiValue::List(items))
+            }
+            Some(byte) => Err(format!("Unexpected '{}' at {}", byte as char, self.position)),
+            None => Err("Unexpected end of record".to_string()),
+        }
+    }
+    
+    /// C string with backslash and octal escapes
+    fn c_string(&mut self) -> Result<String, String> {
+        self.expect(b'"')?;
+        let mut bytes = Vec::new();
+        loop {
+            match self.next() {
+                Some(b'"') => break,
+                Some(b'\\') => match self.next() {
+                    Some(b'n') => bytes.push(b'\n'),
+                    Some(b't') => bytes.push(b'\t'),
+                    Some(b'r') => bytes.push(b'\r'),
+                    Some(b'e') => bytes.push(0x1b),
+                    Some(digit @ b'0'..=b'7') => {
+                        let mut value = u32::from(digit - b'0');
+                        for _ in 0..2 {
+                            match self.peek() {
+                                Some(digit @ b'0'..=b'7') => {
+                                    value = value * 8 + u32::from(digit - b'0');
+                                    self.position += 1;
+                                }
+                                _ => break,
+                            }
+                        }
+                        bytes.push(value as u8);
+                    }
+                    Some(other) => bytes.push(other),
+                    None => return Err("Unterminated escape in C string".to_string()),
+                },
+                Some(byte) => bytes.push(byte),
+                None => return Err("Unterminated C string".to_string()),
+            }
+        }
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_parse_records() {
+        let record = parse_record(r#"12^done,bkpt={number="1",type="breakpoint",func="start_kernel",line="904",thread-groups=["i1"]}"#).unwrap();
+        let MiRecord::Result { token, class, results } = record else { panic!("not a result record") };
+        assert_eq!(token, Some(12));
+        assert_eq!(class, "done");
+        let bkpt = results.get("bkpt").unwrap();
+        assert_eq!(bkpt.get_str("func"), Some("start_kernel"));
+        assert_eq!(bkpt.get("thread-groups").unwrap().items(), &[MiValue::Const("i1".to_string())]);
+        
+        let record = parse_record(r#"^done,stack=[frame={level="0",func="ext4_readdir"},frame={level="1",func="iterate_dir"}]"#).unwrap();
+        let MiRecord::Result { results, .. } = record else { panic!("not a result record") };
+        let frames = results.get("stack").unwrap().items();
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[1].get_str("func"), Some("iterate_dir"));
+        
+        let record = parse_record(r#"*stopped,reason="breakpoint-hit",bkptno="1",frame={addr="0xffffffff82b5a1b0",args=[]}"#).unwrap();
+        let MiRecord::Async { kind, class, results, .. } = record else { panic!("not an async record") };
+        assert_eq!((kind, class.as_str()), (AsyncKind::Exec, "stopped"));
+        assert_eq!(results.get_str("reason"), Some("breakpoint-hit"));
+        
+        assert_eq!(
+            parse_record(r#"~"Breakpoint 1 at \"main.c\"\n\303\251""#).unwrap(),
+            MiRecord::Stream { kind: StreamKind::Console, text: "Breakpoint 1 at \"main.c\"\n\u{e9}".to_string() }
+        );
+        assert_eq!(parse_record("(gdb) ").unwrap(), MiRecord::Prompt);
+        assert!(parse_record(r#"^done,value="1"#).is_err());
+        assert_eq!(quote("fs/ext4/dir.c:42"), "fs/ext4/dir.c:42");
+        assert_eq!(quote("a \"b\""), "\"a \\\"b\\\"\"");
+    }
+}
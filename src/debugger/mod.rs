@@ -0,0 +1,37 @@
+// Debugger module for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! Remote debugging of QEMU guests.
+//!
+//! QEMU is started halted with its gdbstub enabled and gdb is driven through the
+//! machine interface (gdb/MI). Stack frames and breakpoints are mapped back to the
+//! canvas components owning their source files, so the debugger panel can highlight
+//! the nodes involved.
+
+pub mod gdb_mi;
+pub mod session;
+pub mod panel;
+
+pub use gdb_mi::{MiRecord, MiValue, StreamKind, AsyncKind, parse_record};
+pub use session::{DebugConfig, DebugSession, Breakpoint, StackFrame, Register, MemoryBlock, StopEvent, TargetState};
+pub use panel::{DebuggerPanelApi, DebuggerCommand, DebuggerPanelState, PanelBreakpoint, PanelFrame};
+
+// Debugger error types
+#[derive(thiserror::Error, Debug)]
+pub enum DebuggerError {
+    #[error("Launch error: {0}")]
+    LaunchError(String),
+    
+    #[error("GDB/MI protocol error: {0}")]
+    ProtocolError(String),
+    
+    #[error("GDB command failed: {0}")]
+    CommandError(String),
+    
+    #[error("Timed out: {0}")]
+    Timeout(String),
+    
+    #[error("No debug session")]
+    NoSession,
+}
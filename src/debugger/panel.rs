@@ -0,0 +1,284 @@
+// Debugger panel API for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! Headless API behind the debugger panel.
+//!
+//! The panel sends `DebuggerCommand`s and renders the returned `DebuggerPanelState`.
+//! Frames and breakpoints carry the canvas node owning their source location, and
+//! the nodes on the current call stack are listed so the canvas can highlight them.
+
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+use crate::component_manager::visual_node::NodeCanvas;
+use crate::kernel_visualization::trace_ingest::ComponentMapper;
+use super::session::{Breakpoint, DebugConfig, DebugSession, MemoryBlock, Register, StackFrame, TargetState};
+use super::DebuggerError;
+
+/// Request from the debugger panel
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum DebuggerCommand {
+    /// Set a breakpoint on a function, `file:line` or `*address`
+    SetBreakpoint { location: String, hardware: bool },
+    
+    /// Delete a breakpoint by number
+    DeleteBreakpoint { number: String },
+    
+    /// Resume the guest
+    Continue,
+    
+    /// Step into the next source line
+    Step,
+    
+    /// Step over the next source line
+    Next,
+    
+    /// Execute a single instruction
+    StepInstruction,
+    
+    /// Halt the running guest
+    Interrupt,
+    
+    /// Read guest memory
+    ReadMemory { address: u64, length: usize },
+    
+    /// Evaluate an expression in the current frame
+    Evaluate { expression: String },
+    
+    /// Refresh the state without acting on the guest
+    Refresh,
+}
+
+/// Breakpoint with the canvas node owning its location
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PanelBreakpoint {
+    pub breakpoint: Breakpoint,
+    
+    /// Canvas node ID
+    pub node_id: Option<String>,
+}
+
+/// Stack frame with the canvas node owning its location
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PanelFrame {
+    pub frame: StackFrame,
+    
+    /// Canvas node ID
+    pub node_id: Option<String>,
+}
+
+/// Everything the debugger panel shows
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DebuggerPanelState {
+    /// Whether a session is attached
+    pub attached: bool,
+    
+    /// Execution state of the guest
+    pub target: Option<TargetState>,
+    
+    /// Breakpoints
+    pub breakpoints: Vec<PanelBreakpoint>,
+    
+    /// Call stack, innermost frame first (empty while running)
+    pub frames: Vec<PanelFrame>,
+    
+    /// Canvas nodes on the call stack, innermost first
+    pub highlighted_nodes: Vec<String>,
+    
+    /// Register values (empty while running)
+    pub registers: Vec<Register>,
+    
+    /// Memory read by the last `ReadMemory`
+    pub memory: Option<MemoryBlock>,
+    
+    /// Value of the last `Evaluate`
+    pub value: Option<String>,
+    
+    /// Guest serial console output
+    pub guest_console: String,
+}
+
+/// Debug session driven by the debugger panel
+pub struct DebuggerPanelApi {
+    session: Option<DebugSession>,
+    mapper: ComponentMapper,
+    memory: Option<MemoryBlock>,
+    value: Option<String>,
+    
+    /// Breakpoints as last listed while the guest was stopped
+    breakpoints: Vec<Breakpoint>,
+}
+
+impl DebuggerPanelApi {
+    /// Create the API with a mapper from source locations to components
+    pub fn new(mapper: ComponentMapper) -> Self {
+        Self {
+            session: None,
+            mapper,
+            memory: None,
+            value: None,
+            breakpoints: Vec::new(),
+        }
+    }
+    
+    /// Create the API mapping locations to the nodes of a canvas
+    pub fn for_canvas(canvas: &NodeCanvas) -> Self {
+        let mut mapper = ComponentMapper::default();
+        mapper.add_canvas(canvas);
+        Self::new(mapper)
+    }
+    
+    /// Replace the mapper (e.g. after the canvas changed)
+    pub fn set_mapper(&mut self, mapper: ComponentMapper) {
+        self.mapper = mapper;
+    }
+    
+    /// Whether a session is attached
+    pub fn is_attached(&self) -> bool {
+        self.session.is_some()
+    }
+    
+    /// Start a guest and attach to it, replacing any previous session
+    pub fn launch(&mut self, config: &DebugConfig) -> Result<DebuggerPanelState, DebuggerError> {
+        self.detach();
+        self.session = Some(DebugSession::launch(config)?);
+        self.state()
+    }
+    
+    /// Stop the guest and the debugger
+    pub fn detach(&mut self) {
+        self.session = None;
+        self.memory = None;
+        self.value = None;
+        self.breakpoints.clear();
+    }
+    
+    /// Execute a panel command and return the new state
+    pub fn execute(&mut self, command: DebuggerCommand) -> Result<DebuggerPanelState, DebuggerError> {
+        let session = self.session.as_mut().ok_or(DebuggerError::NoSession)?;
+        match command {
+            DebuggerCommand::SetBreakpoint { location, hardware } => {
+                session.insert_breakpoint(&location, hardware)?;
+            }
+            DebuggerCommand::DeleteBreakpoint { number } => session.delete_breakpoint(&number)?,
+            DebuggerCommand::Continue => session.resume()?,
+            DebuggerCommand::Step => session.step()?,
+            DebuggerCommand::Next => session.next()?,
+            DebuggerCommand::StepInstruction => session.step_instruction()?,
+            DebuggerCommand::Interrupt => session.interrupt()?,
+            DebuggerCommand::ReadMemory { address, length } => {
+                self.memory = Some(session.read_memory(address, length)?);
+            }
+            DebuggerCommand::Evaluate { expression } => {
+                self.value = Some(session.evaluate(&expression)?);
+            }
+            DebuggerCommand::Refresh => {}
+        }
+        self.state()
+    }
+    
+    /// Current state of the session
+    ///
+    /// Breakpoints, the call stack and registers are only read while the guest is
+    /// stopped; a running guest shows the breakpoints last listed and no frames.
+    pub fn state(&mut self) -> Result<DebuggerPanelState, DebuggerError> {
+        let Some(session) = self.session.as_mut() else {
+            return Ok(DebuggerPanelState::default());
+        };
+        
+        let target = session.poll()?.clone();
+        let (frames, registers) = match target {
+            TargetState::Stopped(_) => {
+                self.breakpoints = session.breakpoints()?;
+                (session.backtrace()?, session.registers()?)
+            }
+            _ => (Vec::new(), Vec::new()),
+        };
+        let guest_console = session.guest_console();
+        
+        let frames = self.map_frames(frames);
+        let mut highlighted_nodes: Vec<String> = Vec::new();
+        for node_id in frames.iter().filter_map(|frame| frame.node_id.as_ref()) {
+            if !highlighted_nodes.contains(node_id) {
+                highlighted_nodes.push(node_id.clone());
+            }
+        }
+        
+        Ok(DebuggerPanelState {
+            attached: true,
+            target: Some(target),
+            breakpoints: self.map_breakpoints(self.breakpoints.clone()),
+            frames,
+            highlighted_nodes,
+            registers,
+            memory: self.memory.clone(),
+            value: self.value.clone(),
+            guest_console,
+        })
+    }
+    
+    /// Canvas node owning a source file, falling back to the function name
+    pub fn node_at(&self, file: Option<&Path>, function: Option<&str>) -> Option<String> {
+        file.and_then(|file| self.mapper.component_of_file(file))
+            .or_else(|| function.and_then(|function| self.mapper.component_of(function)))
+            .map(str::to_string)
+    }
+    
+    /// Attach canvas nodes to stack frames
+    pub fn map_frames(&self, frames: Vec<StackFrame>) -> Vec<PanelFrame> {
+        frames.into_iter()
+            .map(|frame| {
+                let node_id = self.node_at(frame.location.as_ref().map(|location| location.file.as_path()), frame.function.as_deref());
+                PanelFrame { frame, node_id }
+            })
+            .collect()
+    }
+    
+    /// Attach canvas nodes to breakpoints
+    pub fn map_breakpoints(&self, breakpoints: Vec<Breakpoint>) -> Vec<PanelBreakpoint> {
+        breakpoints.into_iter()
+            .map(|breakpoint| {
+                let node_id = self.node_at(breakpoint.location.as_ref().map(|location| location.file.as_path()), breakpoint.function.as_deref());
+                PanelBreakpoint { breakpoint, node_id }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::debugger::gdb_mi::{parse_record, MiRecord};
+    
+    #[test]
+    fn test_frames_map_to_canvas_nodes() {
+        let mut mapper = ComponentMapper::default();
+        mapper.add_source_file(Path::new("fs/ext4"), "node-ext4");
+        mapper.add_prefix("vfs", "node-vfs");
+        let api = DebuggerPanelApi::new(mapper);
+        assert!(!api.is_attached());
+        
+        let record = parse_record(concat!(
+            r#"^done,stack=[frame={level="0",addr="0xffffffff813a2b31",func="ext4_readdir",file="fs/ext4/dir.c","#,
+            r#"fullname="/build/linux/fs/ext4/dir.c",line="136",arch="i386:x86-64"},"#,
+            r#"frame={level="1",addr="0xffffffff8130f0a2",func="vfs_readdir"},"#,
+            r#"frame={level="2",addr="0xffffffff81000000",func="??"}]"#,
+        )).unwrap();
+        let MiRecord::Result { results, .. } = record else { panic!("not a result record") };
+        let frames: Vec<StackFrame> = results.get("stack").unwrap().items().iter().map(StackFrame::from_mi).collect();
+        assert_eq!(frames[0].location.as_ref().unwrap().line, 136);
+        assert_eq!(frames[0].address, Some(0xffffffff813a2b31));
+        assert_eq!(frames[2].function, None);
+        
+        let mapped = api.map_frames(frames);
+        let nodes: Vec<_> = mapped.iter().map(|frame| frame.node_id.as_deref()).collect();
+        assert_eq!(nodes, vec![Some("node-ext4"), Some("node-vfs"), None]);
+        
+        let record = parse_record(r#"^done,bkpt={number="2",type="hw breakpoint",enabled="y",addr="0xffffffff813a2b00",func="ext4_readdir",file="fs/ext4/dir.c",line="120",times="3"}"#).unwrap();
+        let MiRecord::Result { results, .. } = record else { panic!("not a result record") };
+        let breakpoint = Breakpoint::from_mi(results.get("bkpt").unwrap()).unwrap();
+        assert!(breakpoint.hardware && breakpoint.enabled);
+        assert_eq!(breakpoint.hits, 3);
+        assert_eq!(api.map_breakpoints(vec![breakpoint])[0].node_id.as_deref(), Some("node-ext4"));
+    }
+}
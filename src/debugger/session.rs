@@ -0,0 +1,558 @@
+// GDB remote debugging sessions for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! Debug sessions attached to QEMU guests.
+//!
+//! QEMU is started with `-S -gdb tcp:...`, so the guest waits for the debugger before
+//! executing its first instruction. gdb runs in MI mode with the kernel image as the
+//! symbol file and connects to the gdbstub. Commands carry a token and the session
+//! reads records until the matching result arrives; execution notifications met on
+//! the way update the target state.
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use serde::{Deserialize, Serialize};
+use crate::build_engine::test_orchestrator::QemuConfig;
+use crate::kernel_visualization::crash_analyzer::SourceLocation;
+use super::gdb_mi::{parse_record, quote, AsyncKind, MiRecord, MiValue};
+use super::DebuggerError;
+
+/// Default TCP port of the gdbstub
+const DEFAULT_GDB_PORT: u16 = 1234;
+
+/// Default time to wait for a command result
+const DEFAULT_TIMEOUT_SECS: u64 = 10;
+
+/// Debug session configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DebugConfig {
+    /// Guest machine
+    pub qemu: QemuConfig,
+    
+    /// Kernel image with debug information
+    pub vmlinux: PathBuf,
+    
+    /// gdb binary (`gdb-multiarch` for foreign architectures)
+    #[serde(default = "default_gdb")]
+    pub gdb: String,
+    
+    /// TCP port of the gdbstub on localhost
+    #[serde(default = "default_port")]
+    pub port: u16,
+    
+    /// Time to wait for a command result, in seconds
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+fn default_gdb() -> String {
+    "gdb".to_string()
+}
+
+fn default_port() -> u16 {
+    DEFAULT_GDB_PORT
+}
+
+fn default_timeout_secs() -> u64 {
+    DEFAULT_TIMEOUT_SECS
+}
+
+impl DebugConfig {
+    /// Debug a kernel image in the given machine with default settings
+    pub fn new(qemu: QemuConfig, vmlinux: PathBuf) -> Self {
+        Self {
+            qemu,
+            vmlinux,
+            gdb: default_gdb(),
+            port: default_port(),
+            timeout_secs: default_timeout_secs(),
+        }
+    }
+    
+    /// Load a debug configuration (JSON)
+    pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<Self, DebuggerError> {
+        let content = std::fs::read_to_string(path.as_ref())
+            .map_err(|e| DebuggerError::LaunchError(format!("Failed to read {}: {}", path.as_ref().display(), e)))?;
+        serde_json::from_str(&content)
+            .map_err(|e| DebuggerError::LaunchError(format!("Invalid debug configuration {}: {}", path.as_ref().display(), e)))
+    }
+}
+
+/// Breakpoint set in the guest
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Breakpoint {
+    /// gdb breakpoint number
+    pub number: String,
+    
+    /// Location as requested (`start_kernel`, `fs/ext4/dir.c:120`)
+    pub requested: Option<String>,
+    
+    /// Function the breakpoint resolved to
+    pub function: Option<String>,
+    
+    /// Address the breakpoint resolved to
+    pub address: Option<u64>,
+    
+    /// Source location the breakpoint resolved to
+    pub location: Option<SourceLocation>,
+    
+    /// Whether the breakpoint is enabled
+    pub enabled: bool,
+    
+    /// Hardware breakpoint
+    pub hardware: bool,
+    
+    /// Number of hits
+    pub hits: u64,
+}
+
+impl Breakpoint {
+    /// Read a `bkpt={...}` tuple
+    pub fn from_mi(value: &MiValue) -> Option<Self> {
+        Some(Self {
+            number: value.get_str("number")?.to_string(),
+            requested: value.get_str("original-location").map(str::to_string),
+            function: value.get_str("func").map(str::to_string),
+            address: value.get_str("addr").and_then(parse_address),
+            location: source_location(value),
+            enabled: value.get_str("enabled") != Some("n"),
+            hardware: value.get_str("type") == Some("hw breakpoint"),
+            hits: value.get_str("times").and_then(|times| times.parse().ok()).unwrap_or(0),
+        })
+    }
+}
+
+/// Frame of the guest call stack
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StackFrame {
+    /// Depth (0 is the innermost frame)
+    pub level: usize,
+    
+    /// Function name
+    pub function: Option<String>,
+    
+    /// Program counter
+    pub address: Option<u64>,
+    
+    /// Source location
+    pub location: Option<SourceLocation>,
+}
+
+impl StackFrame {
+    /// Read a `frame={...}` tuple
+    pub fn from_mi(value: &MiValue) -> Self {
+        Self {
+            level: value.get_str("level").and_then(|level| level.parse().ok()).unwrap_or(0),
+            function: value.get_str("func").filter(|func| *func != "??").map(str::to_string),
+            address: value.get_str("addr").and_then(parse_address),
+            location: source_location(value),
+        }
+    }
+}
+
+/// Register value
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Register {
+    /// Register name
+    pub name: String,
+    
+    /// Value as printed by gdb (hexadecimal)
+    pub value: String,
+}
+
+/// Block of guest memory
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MemoryBlock {
+    /// Start address
+    pub address: u64,
+    
+    /// Bytes read
+    pub contents: Vec<u8>,
+}
+
+/// Why the guest stopped
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StopEvent {
+    /// Reason reported by gdb (`breakpoint-hit`, `end-stepping-range`, `signal-received`)
+    pub reason: String,
+    
+    /// Number of the breakpoint hit
+    pub breakpoint: Option<String>,
+    
+    /// Frame the guest stopped in
+    pub frame: Option<StackFrame>,
+}
+
+impl StopEvent {
+    /// Read the results of a `*stopped` record
+    pub fn from_mi(results: &MiValue) -> Self {
+        Self {
+            reason: results.get_str("reason").unwrap_or("interrupted").to_string(),
+            breakpoint: results.get_str("bkptno").map(str::to_string),
+            frame: results.get("frame").map(StackFrame::from_mi),
+        }
+    }
+}
+
+/// Execution state of the guest
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TargetState {
+    /// Executing
+    Running,
+    
+    /// Halted, registers and memory can be inspected
+    Stopped(StopEvent),
+    
+    /// The guest or the connection went away
+    Exited,
+}
+
+/// gdb attached to a QEMU guest
+pub struct DebugSession {
+    qemu: Child,
+    gdb: Child,
+    stdin: ChildStdin,
+    records: Receiver<String>,
+    guest_console: Arc<Mutex<String>>,
+    gdb_console: Vec<String>,
+    register_names: Vec<String>,
+    next_token: u64,
+    state: TargetState,
+    timeout: Duration,
+}
+
+impl DebugSession {
+    /// Start QEMU halted with a gdbstub and attach gdb to it
+    pub fn launch(config: &DebugConfig) -> Result<Self, DebuggerError> {
+        let mut qemu = config.qemu.command()
+            .args(["-S", "-gdb", &format!("tcp:127.0.0.1:{}", config.port)])
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| DebuggerError::LaunchError(format!("Failed to start QEMU: {}", e)))?;
+        let guest_console = Arc::new(Mutex::new(String::new()));
+        if let Some(stdout) = qemu.stdout.take() {
+            collect_output(stdout, guest_console.clone());
+        }
+        if let Some(stderr) = qemu.stderr.take() {
+            collect_output(stderr, guest_console.clone());
+        }
+        
+        let gdb = Command::new(&config.gdb)
+            .args(["--interpreter=mi3", "-q", "-nx"])
+            .arg(&config.vmlinux)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn();
+        let mut gdb = match gdb {
+            Ok(gdb) => gdb,
+            Err(e) => {
+                let _ = qemu.kill();
+                let _ = qemu.wait();
+                return Err(DebuggerError::LaunchError(format!("Failed to start {}: {}", config.gdb, e)));
+            }
+        };
+        let stdin = gdb.stdin.take().ok_or_else(|| DebuggerError::LaunchError("gdb stdin unavailable".to_string()))?;
+        let stdout = gdb.stdout.take().ok_or_else(|| DebuggerError::LaunchError("gdb stdout unavailable".to_string()))?;
+        
+        let (sender, records) = mpsc::channel();
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+        
+        let mut session = Self {
+            qemu,
+            gdb,
+            stdin,
+            records,
+            guest_console,
+            gdb_console: Vec::new(),
+            register_names: Vec::new(),
+            next_token: 0,
+            state: TargetState::Running,
+            timeout: Duration::from_secs(config.timeout_secs),
+        };
+        session.command("-gdb-set confirm off")?;
+        session.command("-gdb-set pagination off")?;
+        // Keep accepting commands (such as `-exec-interrupt`) while the guest runs
+        session.command("-gdb-set mi-async on")?;
+        session.connect(config.port)?;
+        Ok(session)
+    }
+    
+    /// Connect to the gdbstub, retrying while QEMU is still starting
+    fn connect(&mut self, port: u16) -> Result<(), DebuggerError> {
+        let deadline = Instant::now() + self.timeout;
+        loop {
+            match self.command(&format!("-target-select remote 127.0.0.1:{}", port)) {
+                Ok(_) => break,
+                Err(DebuggerError::CommandError(message)) if Instant::now() < deadline => {
+                    if let Ok(Some(status)) = self.qemu.try_wait() {
+                        return Err(DebuggerError::LaunchError(format!("QEMU exited ({}): {}", status, message)));
+                    }
+                    thread::sleep(Duration::from_millis(200));
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        
+        // The guest is halted at its reset vector until continued
+        let frame = self.command("-stack-info-frame").ok().and_then(|results| results.get("frame").map(StackFrame::from_mi));
+        self.state = TargetState::Stopped(StopEvent { reason: "attached".to_string(), breakpoint: None, frame });
+        Ok(())
+    }
+    
+    /// Run an MI command and return its results
+    pub fn command(&mut self, command: &str) -> Result<MiValue, DebuggerError> {
+        self.next_token += 1;
+        let token = self.next_token;
+        writeln!(self.stdin, "{}{}", token, command)
+            .and_then(|_| self.stdin.flush())
+            .map_err(|e| DebuggerError::ProtocolError(format!("Failed to write to gdb: {}", e)))?;
+        
+        let deadline = Instant::now() + self.timeout;
+        loop {
+            if let MiRecord::Result { token: Some(received), class, results } = self.next_record(deadline, command)? {
+                if received != token {
+                    continue;
+                }
+                return match class.as_str() {
+                    "error" => Err(DebuggerError::CommandError(results.get_str("msg").unwrap_or(command).to_string())),
+                    "exit" => {
+                        self.state = TargetState::Exited;
+                        Err(DebuggerError::ProtocolError("gdb exited".to_string()))
+                    }
+                    _ => Ok(results),
+                };
+            }
+        }
+    }
+    
+    /// Read the next record, updating the target state and the gdb console
+    fn next_record(&mut self, deadline: Instant, waiting_for: &str) -> Result<MiRecord, DebuggerError> {
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let line = match self.records.recv_timeout(remaining) {
+                Ok(line) => line,
+                Err(RecvTimeoutError::Timeout) => return Err(DebuggerError::Timeout(format!("No reply to '{}'", waiting_for))),
+                Err(RecvTimeoutError::Disconnected) => {
+                    self.state = TargetState::Exited;
+                    return Err(DebuggerError::ProtocolError("gdb exited".to_string()));
+                }
+            };
+            
+            // Anything that is not a record is output of gdb itself
+            let record = match parse_record(&line) {
+                Ok(record) => record,
+                Err(_) => {
+                    self.gdb_console.push(line);
+                    continue;
+                }
+            };
+            match &record {
+                MiRecord::Async { kind: AsyncKind::Exec, class, results, .. } => match class.as_str() {
+                    "running" => self.state = TargetState::Running,
+                    "stopped" if results.get_str("reason").is_some_and(|reason| reason.starts_with("exited")) => {
+                        self.state = TargetState::Exited;
+                    }
+                    "stopped" => self.state = TargetState::Stopped(StopEvent::from_mi(results)),
+                    _ => {}
+                },
+                MiRecord::Stream { text, .. } => self.gdb_console.push(text.clone()),
+                _ => {}
+            }
+            return Ok(record);
+        }
+    }
+    
+    /// Current execution state
+    pub fn state(&self) -> &TargetState {
+        &self.state
+    }
+    
+    /// Process the records that arrived since the last command without blocking
+    pub fn poll(&mut self) -> Result<&TargetState, DebuggerError> {
+        loop {
+            match self.next_record(Instant::now(), "pending records") {
+                Ok(_) => {}
+                Err(DebuggerError::Timeout(_)) => return Ok(&self.state),
+                Err(e) => return Err(e),
+            }
+        }
+    }
+    
+    /// Output of the guest serial console so far
+    pub fn guest_console(&self) -> String {
+        self.guest_console.lock().map(|console| console.clone()).unwrap_or_default()
+    }
+    
+    /// Console, target and log output of gdb so far
+    pub fn gdb_console(&self) -> &[String] {
+        &self.gdb_console
+    }
+    
+    /// Set a breakpoint on a function, `file:line` or `*address`
+    ///
+    /// Hardware breakpoints survive the kernel remapping its text early in boot.
+    pub fn insert_breakpoint(&mut self, location: &str, hardware: bool) -> Result<Breakpoint, DebuggerError> {
+        let flags = if hardware { "-h " } else { "" };
+        let results = self.command(&format!("-break-insert {}{}", flags, quote(location)))?;
+        results.get("bkpt")
+            .and_then(Breakpoint::from_mi)
+            .ok_or_else(|| DebuggerError::ProtocolError(format!("No breakpoint in reply to '{}'", location)))
+    }
+    
+    /// Delete a breakpoint
+    pub fn delete_breakpoint(&mut self, number: &str) -> Result<(), DebuggerError> {
+        self.command(&format!("-break-delete {}", quote(number))).map(|_| ())
+    }
+    
+    /// All breakpoints
+    pub fn breakpoints(&mut self) -> Result<Vec<Breakpoint>, DebuggerError> {
+        let results = self.command("-break-list")?;
+        Ok(results.get("BreakpointTable")
+            .and_then(|table| table.get("body"))
+            .map(|body| body.items().iter().filter_map(Breakpoint::from_mi).collect())
+            .unwrap_or_default())
+    }
+    
+    /// Call stack of the stopped guest
+    pub fn backtrace(&mut self) -> Result<Vec<StackFrame>, DebuggerError> {
+        let results = self.command("-stack-list-frames")?;
+        Ok(results.get("stack").map(|stack| stack.items().iter().map(StackFrame::from_mi).collect()).unwrap_or_default())
+    }
+    
+    /// Register values of the stopped guest
+    pub fn registers(&mut self) -> Result<Vec<Register>, DebuggerError> {
+        if self.register_names.is_empty() {
+            let results = self.command("-data-list-register-names")?;
+            self.register_names = results.get("register-names")
+                .map(|names| names.items().iter().map(|name| name.as_str().unwrap_or_default().to_string()).collect())
+                .unwrap_or_default();
+        }
+        
+        let results = self.command("-data-list-register-values --skip-unavailable x")?;
+        let mut registers = Vec::new();
+        for value in results.get("register-values").map(MiValue::items).unwrap_or_default() {
+            let name = value.get_str("number")
+                .and_then(|number| number.parse::<usize>().ok())
+                .and_then(|number| self.register_names.get(number))
+                .filter(|name| !name.is_empty());
+            if let (Some(name), Some(value)) = (name, value.get_str("value")) {
+                registers.push(Register { name: name.clone(), value: value.to_string() });
+            }
+        }
+        Ok(registers)
+    }
+    
+    /// Read guest memory (virtual addresses as seen by the guest CPU)
+    pub fn read_memory(&mut self, address: u64, length: usize) -> Result<MemoryBlock, DebuggerError> {
+        let results = self.command(&format!("-data-read-memory-bytes 0x{:x} {}", address, length))?;
+        let mut block = MemoryBlock { address, contents: Vec::new() };
+        for chunk in results.get("memory").map(MiValue::items).unwrap_or_default() {
+            let begin = chunk.get_str("begin").and_then(parse_address).unwrap_or(address);
+            if block.contents.is_empty() {
+                block.address = begin;
+            }
+            block.contents.extend(chunk.get_str("contents").map(parse_hex_bytes).unwrap_or_default());
+        }
+        Ok(block)
+    }
+    
+    /// Evaluate an expression in the current frame
+    pub fn evaluate(&mut self, expression: &str) -> Result<String, DebuggerError> {
+        let results = self.command(&format!("-data-evaluate-expression {}", quote(expression)))?;
+        Ok(results.get_str("value").unwrap_or_default().to_string())
+    }
+    
+    /// Resume the guest
+    pub fn resume(&mut self) -> Result<(), DebuggerError> {
+        self.command("-exec-continue").map(|_| ())
+    }
+    
+    /// Step into the next source line
+    pub fn step(&mut self) -> Result<(), DebuggerError> {
+        self.command("-exec-step").map(|_| ())
+    }
+    
+    /// Step over the next source line
+    pub fn next(&mut self) -> Result<(), DebuggerError> {
+        self.command("-exec-next").map(|_| ())
+    }
+    
+    /// Execute a single instruction
+    pub fn step_instruction(&mut self) -> Result<(), DebuggerError> {
+        self.command("-exec-step-instruction").map(|_| ())
+    }
+    
+    /// Halt the running guest
+    pub fn interrupt(&mut self) -> Result<(), DebuggerError> {
+        self.command("-exec-interrupt").map(|_| ())
+    }
+    
+    /// Wait until the guest stops or exits
+    pub fn wait_for_stop(&mut self, timeout: Duration) -> Result<&TargetState, DebuggerError> {
+        let deadline = Instant::now() + timeout;
+        while self.state == TargetState::Running {
+            self.next_record(deadline, "*stopped")?;
+        }
+        Ok(&self.state)
+    }
+}
+
+impl Drop for DebugSession {
+    fn drop(&mut self) {
+        let _ = writeln!(self.stdin, "-gdb-exit");
+        let _ = self.stdin.flush();
+        let _ = self.gdb.kill();
+        let _ = self.gdb.wait();
+        let _ = self.qemu.kill();
+        let _ = self.qemu.wait();
+    }
+}
+
+/// Append the output of a pipe to a buffer in the background
+fn collect_output<R: Read + Send + 'static>(mut pipe: R, buffer: Arc<Mutex<String>>) {
+    thread::spawn(move || {
+        let mut chunk = [0u8; 4096];
+        while let Ok(read) = pipe.read(&mut chunk) {
+            if read == 0 {
+                break;
+            }
+            if let Ok(mut buffer) = buffer.lock() {
+                buffer.push_str(&String::from_utf8_lossy(&chunk[..read]));
+            }
+        }
+    });
+}
+
+/// Source location of a frame or breakpoint tuple (`fullname` preferred over `file`)
+fn source_location(value: &MiValue) -> Option<SourceLocation> {
+    let file = value.get_str("fullname").or_else(|| value.get_str("file"))?;
+    let line = value.get_str("line")?.parse().ok()?;
+    Some(SourceLocation { file: PathBuf::from(file), line })
+}
+
+fn parse_address(text: &str) -> Option<u64> {
+    let text = text.split_whitespace().next()?;
+    u64::from_str_radix(text.trim_start_matches("0x"), 16).ok()
+}
+
+fn parse_hex_bytes(text: &str) -> Vec<u8> {
+    text.as_bytes()
+        .chunks(2)
+        .filter_map(|pair| std::str::from_utf8(pair).ok().and_then(|pair| u8::from_str_radix(pair, 16).ok()))
+        .collect()
+}
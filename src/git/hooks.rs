@@ -0,0 +1,146 @@
+// Pre-commit validation for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! Validation of canvases and tile graphs before they are committed.
+//!
+//! Staged JSON files are read from the index (not the work tree), so partially
+//! staged files are checked as they will be committed. Canvases are linted with the
+//! project lint configuration; tile graphs are checked for dangling connections and
+//! against the connectivity policy.
+
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use crate::component_manager::canvas_lint::{CanvasLinter, LintConfig, LintSeverity};
+use crate::component_manager::visual_node::NodeCanvas;
+use crate::core::event_bus::{self, ProjectEvent, SystemEvent};
+use crate::tile_engine::tile_constraints::ConnectivityPolicy;
+use crate::tile_engine::tile_core::TileGraph;
+use super::repository::{ChangeKind, GitRepository};
+use super::GitError;
+
+/// Validation result of a canvas or tile graph file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidatedFile {
+    /// Path relative to the repository root
+    pub path: PathBuf,
+    
+    /// `canvas` or `tile graph`
+    pub kind: String,
+    
+    /// Errors blocking the commit
+    pub errors: Vec<String>,
+}
+
+/// Validates canvases and tile graphs before commit
+#[derive(Debug, Clone)]
+pub struct CommitValidator {
+    lint_config: LintConfig,
+    policy: ConnectivityPolicy,
+}
+
+impl Default for CommitValidator {
+    fn default() -> Self {
+        Self::new(LintConfig::default())
+    }
+}
+
+impl CommitValidator {
+    /// Create a validator with a project lint configuration and the built-in connectivity policy
+    pub fn new(lint_config: LintConfig) -> Self {
+        Self {
+            lint_config,
+            policy: ConnectivityPolicy::hardened(),
+        }
+    }
+    
+    /// Check tile graphs against a project connectivity policy
+    pub fn with_policy(mut self, policy: ConnectivityPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+    
+    /// Validate a file, or None when it is neither a canvas nor a tile graph
+    pub fn validate_content(&self, path: &Path, content: &str) -> Option<ValidatedFile> {
+        if let Ok(canvas) = serde_json::from_str::<NodeCanvas>(content) {
+            let report = CanvasLinter::with_default_rules(self.lint_config.clone()).lint(&canvas, None);
+            let errors = report.diagnostics.iter()
+                .filter(|diagnostic| diagnostic.severity == LintSeverity::Error)
+                .map(|diagnostic| diagnostic.to_string())
+                .collect();
+            return Some(ValidatedFile { path: path.to_path_buf(), kind: "canvas".to_string(), errors });
+        }
+        let graph = serde_json::from_str::<TileGraph>(content).ok()?;
+        let mut errors = Vec::new();
+        for connection in &graph.connections {
+            for (tile_id, port_id) in [(&connection.source_tile_id, &connection.source_port_id), (&connection.dest_tile_id, &connection.dest_port_id)] {
+                match graph.get_tile(tile_id) {
+                    None => errors.push(format!("Connection '{}' references missing tile '{}'", connection.id, tile_id)),
+                    Some(tile) if tile.get_port(port_id).is_none() => {
+                        errors.push(format!("Connection '{}' references missing port '{}' of tile '{}'", connection.id, port_id, tile.name));
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+        errors.extend(self.policy.validate(&graph).into_iter().map(|violation| violation.message));
+        Some(ValidatedFile { path: path.to_path_buf(), kind: "tile graph".to_string(), errors })
+    }
+    
+    /// Validate the staged canvases and tile graphs of a repository
+    pub fn validate_staged(&self, repository: &GitRepository) -> Result<Vec<ValidatedFile>, GitError> {
+        let mut files = Vec::new();
+        for status in repository.status()? {
+            if !status.is_staged() || status.index == ChangeKind::Deleted || status.path.extension().map_or(true, |ext| ext != "json") {
+                continue;
+            }
+            let content = repository.staged_content(&status.path)?;
+            files.extend(self.validate_content(&status.path, &content));
+        }
+        let errors: Vec<String> = files.iter()
+            .flat_map(|file| file.errors.iter().map(move |error| format!("{}: {}", file.path.display(), error)))
+            .collect();
+        if !errors.is_empty() {
+            event_bus::publish(SystemEvent::Project(ProjectEvent::ValidationFailed {
+                project: repository.root().display().to_string(),
+                target: "staged files".to_string(),
+                errors,
+            }));
+        }
+        Ok(files)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tile_engine::tile_core::{ConnectionType, Tile, TileConnection, TileType};
+    
+    #[test]
+    fn test_validate_tile_graph_content() {
+        let mut graph = TileGraph::new("pipeline".to_string());
+        let tile = Tile::new("source".to_string(), TileType::Processing, "Source".to_string());
+        let tile_id = tile.id.clone();
+        graph.add_tile(tile).unwrap();
+        graph.connections.push(TileConnection {
+            id: "c1".to_string(),
+            source_tile_id: tile_id,
+            source_port_id: "out".to_string(),
+            dest_tile_id: "gone".to_string(),
+            dest_port_id: "in".to_string(),
+            connection_type: ConnectionType::DataFlow,
+        });
+        
+        let validator = CommitValidator::default();
+        let file = validator.validate_content(Path::new("tiles/pipeline.json"), &serde_json::to_string(&graph).unwrap()).unwrap();
+        assert_eq!(file.kind, "tile graph");
+        assert_eq!(file.errors, vec![
+            "Connection 'c1' references missing port 'out' of tile 'source'".to_string(),
+            "Connection 'c1' references missing tile 'gone'".to_string(),
+        ]);
+        
+        let canvas = validator.validate_content(Path::new("canvas.json"), &serde_json::to_string(&NodeCanvas::new()).unwrap()).unwrap();
+        assert_eq!((canvas.kind.as_str(), canvas.errors.len()), ("canvas", 0));
+        assert!(validator.validate_content(Path::new("build.json"), "{\"project_name\": \"demo\"}").is_none());
+    }
+}
@@ -0,0 +1,114 @@
+// Canvas change markers for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! Change markers for canvas nodes whose implementation files are modified.
+
+use std::path::{Component as PathComponent, Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use crate::component_manager::visual_node::NodeCanvas;
+use super::repository::{ChangeKind, FileStatus};
+
+/// Marker shown on a canvas node
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChangeMarker {
+    /// Canvas node ID
+    pub node_id: String,
+    
+    /// Most significant change of the node's files
+    pub kind: ChangeKind,
+    
+    /// Changed implementation files
+    pub files: Vec<FileStatus>,
+}
+
+/// Markers for the nodes of a canvas
+///
+/// Relative implementation files are resolved against `project_dir`; files and
+/// directories are matched against the status paths relative to `repository_root`.
+pub fn change_markers(canvas: &NodeCanvas, statuses: &[FileStatus], repository_root: &Path, project_dir: &Path) -> Vec<ChangeMarker> {
+    let mut markers: Vec<ChangeMarker> = canvas.nodes.values()
+        .filter_map(|node| {
+            let files: Vec<PathBuf> = node.component.implementation_files.iter()
+                .filter_map(|file| {
+                    let path = Path::new(file);
+                    let path = if path.is_relative() { project_dir.join(path) } else { path.to_path_buf() };
+                    normalize(&path).strip_prefix(normalize(repository_root)).ok().map(Path::to_path_buf)
+                })
+                .collect();
+            let changed: Vec<FileStatus> = statuses.iter()
+                .filter(|status| status.kind() != ChangeKind::Ignored)
+                .filter(|status| files.iter().any(|file| {
+                    status.path.starts_with(file) || status.original_path.as_ref().is_some_and(|original| original.starts_with(file))
+                }))
+                .cloned()
+                .collect();
+            let kind = changed.iter().map(FileStatus::kind).max_by_key(significance)?;
+            Some(ChangeMarker { node_id: node.id.clone(), kind, files: changed })
+        })
+        .collect();
+    markers.sort_by(|a, b| a.node_id.cmp(&b.node_id));
+    markers
+}
+
+/// Rank of a change when a node has several changed files
+fn significance(kind: &ChangeKind) -> u8 {
+    match kind {
+        ChangeKind::Unmodified | ChangeKind::Ignored => 0,
+        ChangeKind::Untracked => 1,
+        ChangeKind::Copied => 2,
+        ChangeKind::Added => 3,
+        ChangeKind::Renamed => 4,
+        ChangeKind::Modified => 5,
+        ChangeKind::Deleted => 6,
+        ChangeKind::Conflicted => 7,
+    }
+}
+
+/// Remove `.` and `..` components without touching the file system
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            PathComponent::CurDir => {}
+            PathComponent::ParentDir => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::geometry::Point;
+    use crate::component_manager::network_components::{create_ip_component, create_tcp_component};
+    use crate::component_manager::visual_node::VisualNode;
+    
+    #[test]
+    fn test_markers_follow_implementation_files() {
+        let mut tcp = create_tcp_component();
+        tcp.implementation_files = vec!["net/tcp.c".to_string(), "/repo/kernel/net/tcp.h".to_string()];
+        let mut ip = create_ip_component();
+        ip.implementation_files = vec!["../drivers/ip".to_string()];
+        let mut canvas = NodeCanvas::new();
+        canvas.add_node(VisualNode::with_id(tcp, Point::new(0.0, 0.0), "tcp".to_string()).unwrap()).unwrap();
+        canvas.add_node(VisualNode::with_id(ip, Point::new(0.0, 0.0), "ip".to_string()).unwrap()).unwrap();
+        
+        let status = |path: &str, index: ChangeKind, worktree: ChangeKind| FileStatus { path: PathBuf::from(path), original_path: None, index, worktree };
+        let statuses = vec![
+            status("kernel/net/tcp.c", ChangeKind::Unmodified, ChangeKind::Modified),
+            status("kernel/net/tcp.h", ChangeKind::Deleted, ChangeKind::Unmodified),
+            status("drivers/ip/route.c", ChangeKind::Untracked, ChangeKind::Untracked),
+            status("kernel/net/udp.c", ChangeKind::Unmodified, ChangeKind::Modified),
+        ];
+        let markers = change_markers(&canvas, &statuses, Path::new("/repo"), Path::new("/repo/kernel"));
+        assert_eq!(markers.len(), 2);
+        assert_eq!((markers[0].node_id.as_str(), markers[0].kind, markers[0].files.len()), ("ip", ChangeKind::Untracked, 1));
+        assert_eq!((markers[1].node_id.as_str(), markers[1].kind, markers[1].files.len()), ("tcp", ChangeKind::Deleted, 2));
+        
+        assert!(change_markers(&canvas, &statuses[3..], Path::new("/repo"), Path::new("/repo/kernel")).is_empty());
+    }
+}
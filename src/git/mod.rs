@@ -0,0 +1,36 @@
+// Git integration module for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! Version control of project files.
+//!
+//! Repositories are driven through the `git` command line, so every feature of the
+//! user's git installation (credential helpers, hooks, config) keeps working. Canvas
+//! nodes whose implementation files are modified get change markers, and canvases
+//! and tile graphs are validated before they are committed.
+
+pub mod repository;
+pub mod markers;
+pub mod hooks;
+
+pub use repository::{GitRepository, FileStatus, ChangeKind, Branch, CommitInfo, FileDiff, DiffHunk, DiffLine, DiffLineKind, parse_diff};
+pub use markers::{ChangeMarker, change_markers};
+pub use hooks::{CommitValidator, ValidatedFile};
+
+use std::path::PathBuf;
+
+// Git error types
+#[derive(thiserror::Error, Debug)]
+pub enum GitError {
+    #[error("Not a git repository: {0}")]
+    NotARepository(PathBuf),
+    
+    #[error("Git command failed: {0}")]
+    CommandError(String),
+    
+    #[error("Pre-commit validation failed:\n{}", .0.join("\n"))]
+    ValidationFailed(Vec<String>),
+    
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+}
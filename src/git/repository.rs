@@ -0,0 +1,574 @@
+// Git repository access for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! Status, commit, branch and diff of a project repository.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use serde::{Deserialize, Serialize};
+use super::hooks::CommitValidator;
+use super::GitError;
+
+/// State of a file in the index or the work tree
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum ChangeKind {
+    Unmodified,
+    Modified,
+    Added,
+    Deleted,
+    Renamed,
+    Copied,
+    Untracked,
+    Ignored,
+    Conflicted,
+}
+
+impl ChangeKind {
+    /// Kind of a `git status --porcelain` status letter
+    pub fn from_code(code: char) -> Self {
+        match code {
+            'M' | 'T' => ChangeKind::Modified,
+            'A' => ChangeKind::Added,
+            'D' => ChangeKind::Deleted,
+            'R' => ChangeKind::Renamed,
+            'C' => ChangeKind::Copied,
+            '?' => ChangeKind::Untracked,
+            '!' => ChangeKind::Ignored,
+            'U' => ChangeKind::Conflicted,
+            _ => ChangeKind::Unmodified,
+        }
+    }
+    
+    /// Single letter shown next to files and nodes
+    pub fn letter(&self) -> char {
+        match self {
+            ChangeKind::Unmodified => ' ',
+            ChangeKind::Modified => 'M',
+            ChangeKind::Added => 'A',
+            ChangeKind::Deleted => 'D',
+            ChangeKind::Renamed => 'R',
+            ChangeKind::Copied => 'C',
+            ChangeKind::Untracked => '?',
+            ChangeKind::Ignored => '!',
+            ChangeKind::Conflicted => 'U',
+        }
+    }
+}
+
+/// Status of a changed file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileStatus {
+    /// Path relative to the repository root
+    pub path: PathBuf,
+    
+    /// Path before a rename or copy
+    pub original_path: Option<PathBuf>,
+    
+    /// Change staged in the index
+    pub index: ChangeKind,
+    
+    /// Change in the work tree not yet staged
+    pub worktree: ChangeKind,
+}
+
+impl FileStatus {
+    /// Whether the file has staged changes
+    pub fn is_staged(&self) -> bool {
+        !matches!(self.index, ChangeKind::Unmodified | ChangeKind::Untracked | ChangeKind::Ignored)
+    }
+    
+    /// The more significant of the index and work tree changes
+    pub fn kind(&self) -> ChangeKind {
+        if self.index == ChangeKind::Conflicted || self.worktree == ChangeKind::Conflicted {
+            ChangeKind::Conflicted
+        } else if self.worktree != ChangeKind::Unmodified {
+            self.worktree
+        } else {
+            self.index
+        }
+    }
+}
+
+/// Local branch
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Branch {
+    /// Branch name
+    pub name: String,
+    
+    /// Commit the branch points to
+    pub head: String,
+    
+    /// Whether the branch is checked out
+    pub current: bool,
+}
+
+/// Commit summary
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommitInfo {
+    /// Commit hash
+    pub hash: String,
+    
+    /// Author name
+    pub author: String,
+    
+    /// Commit time (Unix seconds)
+    pub timestamp: i64,
+    
+    /// First line of the message
+    pub summary: String,
+}
+
+/// Kind of a diff line
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DiffLineKind {
+    Context,
+    Added,
+    Removed,
+}
+
+/// Line of a diff hunk
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiffLine {
+    pub kind: DiffLineKind,
+    
+    /// Line content without the leading marker
+    pub text: String,
+}
+
+/// Hunk of a file diff
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiffHunk {
+    /// First line in the old file
+    pub old_start: usize,
+    
+    /// Number of lines in the old file
+    pub old_lines: usize,
+    
+    /// First line in the new file
+    pub new_start: usize,
+    
+    /// Number of lines in the new file
+    pub new_lines: usize,
+    
+    /// Lines of the hunk
+    pub lines: Vec<DiffLine>,
+}
+
+/// Changes of a file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileDiff {
+    /// Path relative to the repository root (the new path for renames)
+    pub path: PathBuf,
+    
+    /// Path before a rename
+    pub old_path: Option<PathBuf>,
+    
+    /// Whether git considers the file binary
+    pub binary: bool,
+    
+    /// Changed hunks
+    pub hunks: Vec<DiffHunk>,
+}
+
+impl FileDiff {
+    /// Number of added and removed lines
+    pub fn line_counts(&self) -> (usize, usize) {
+        let lines = self.hunks.iter().flat_map(|hunk| hunk.lines.iter());
+        lines.fold((0, 0), |(added, removed), line| match line.kind {
+            DiffLineKind::Added => (added + 1, removed),
+            DiffLineKind::Removed => (added, removed + 1),
+            DiffLineKind::Context => (added, removed),
+        })
+    }
+}
+
+/// Git repository holding a project
+#[derive(Debug, Clone)]
+pub struct GitRepository {
+    root: PathBuf,
+    git: String,
+}
+
+impl GitRepository {
+    /// Open the repository containing a directory
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, GitError> {
+        let path = path.as_ref();
+        let output = Command::new("git")
+            .args(["rev-parse", "--show-toplevel"])
+            .current_dir(path)
+            .output()
+            .map_err(|e| GitError::CommandError(format!("git: {}", e)))?;
+        if !output.status.success() {
+            return Err(GitError::NotARepository(path.to_path_buf()));
+        }
+        let root = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+        Ok(Self { root, git: "git".to_string() })
+    }
+    
+    /// Create a repository in a directory
+    pub fn init<P: AsRef<Path>>(path: P) -> Result<Self, GitError> {
+        let path = path.as_ref();
+        std::fs::create_dir_all(path)?;
+        let repository = Self { root: path.to_path_buf(), git: "git".to_string() };
+        repository.run(&["init", "-q"])?;
+        Self::open(path)
+    }
+    
+    /// Use another git executable
+    pub fn with_git(mut self, git: &str) -> Self {
+        self.git = git.to_string();
+        self
+    }
+    
+    /// Root of the work tree
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+    
+    /// Changed, untracked and conflicted files
+    pub fn status(&self) -> Result<Vec<FileStatus>, GitError> {
+        let output = self.run(&["status", "--porcelain=v1", "-z", "--untracked-files=all"])?;
+        let mut entries = output.split('\0').filter(|entry| !entry.is_empty());
+        let mut statuses = Vec::new();
+        while let Some(entry) = entries.next() {
+            if entry.len() < 4 {
+                continue;
+            }
+            let codes: Vec<char> = entry[..2].chars().collect();
+            let (index, worktree) = match (codes[0], codes[1]) {
+                // Unmerged paths: DD, AU, UD, UA, DU, AA, UU
+                ('U', _) | (_, 'U') | ('D', 'D') | ('A', 'A') => (ChangeKind::Conflicted, ChangeKind::Conflicted),
+                (index, worktree) => (ChangeKind::from_code(index), ChangeKind::from_code(worktree)),
+            };
+            let original_path = match index {
+                ChangeKind::Renamed | ChangeKind::Copied => entries.next().map(PathBuf::from),
+                _ => None,
+            };
+            statuses.push(FileStatus { path: PathBuf::from(&entry[3..]), original_path, index, worktree });
+        }
+        Ok(statuses)
+    }
+    
+    /// Stage files
+    pub fn stage(&self, paths: &[&Path]) -> Result<(), GitError> {
+        let mut args = vec!["add", "--"];
+        let paths: Vec<String> = paths.iter().map(|path| path.to_string_lossy().to_string()).collect();
+        args.extend(paths.iter().map(String::as_str));
+        self.run(&args).map(|_| ())
+    }
+    
+    /// Stage every change in the work tree
+    pub fn stage_all(&self) -> Result<(), GitError> {
+        self.run(&["add", "-A"]).map(|_| ())
+    }
+    
+    /// Remove files from the index, keeping the work tree changes
+    pub fn unstage(&self, paths: &[&Path]) -> Result<(), GitError> {
+        let mut args = vec!["reset", "-q", "--"];
+        let paths: Vec<String> = paths.iter().map(|path| path.to_string_lossy().to_string()).collect();
+        args.extend(paths.iter().map(String::as_str));
+        self.run(&args).map(|_| ())
+    }
+    
+    /// Commit the staged changes, returning the new commit hash
+    pub fn commit(&self, message: &str) -> Result<String, GitError> {
+        self.run(&["commit", "-q", "-m", message])?;
+        self.head()
+    }
+    
+    /// Validate the staged canvases and tile graphs, then commit
+    pub fn commit_validated(&self, message: &str, validator: &CommitValidator) -> Result<String, GitError> {
+        let errors: Vec<String> = validator.validate_staged(self)?.into_iter()
+            .flat_map(|file| file.errors.into_iter().map(move |error| format!("{}: {}", file.path.display(), error)))
+            .collect();
+        if !errors.is_empty() {
+            return Err(GitError::ValidationFailed(errors));
+        }
+        self.commit(message)
+    }
+    
+    /// Hash of the checked out commit
+    pub fn head(&self) -> Result<String, GitError> {
+        Ok(self.run(&["rev-parse", "HEAD"])?.trim().to_string())
+    }
+    
+    /// Name of the checked out branch (None when detached)
+    pub fn current_branch(&self) -> Result<Option<String>, GitError> {
+        match self.run(&["symbolic-ref", "--short", "-q", "HEAD"]) {
+            Ok(name) => Ok(Some(name.trim().to_string())),
+            Err(GitError::CommandError(_)) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+    
+    /// Local branches
+    pub fn branches(&self) -> Result<Vec<Branch>, GitError> {
+        let output = self.run(&["for-each-ref", "--format=%(refname:short)%00%(objectname)%00%(HEAD)", "refs/heads"])?;
+        Ok(output.lines()
+            .filter_map(|line| {
+                let mut fields = line.split('\0');
+                Some(Branch {
+                    name: fields.next()?.to_string(),
+                    head: fields.next()?.to_string(),
+                    current: fields.next()? == "*",
+                })
+            })
+            .collect())
+    }
+    
+    /// Create a branch at the checked out commit
+    pub fn create_branch(&self, name: &str, checkout: bool) -> Result<(), GitError> {
+        if checkout {
+            self.run(&["checkout", "-q", "-b", name]).map(|_| ())
+        } else {
+            self.run(&["branch", name]).map(|_| ())
+        }
+    }
+    
+    /// Check out a branch
+    pub fn checkout(&self, name: &str) -> Result<(), GitError> {
+        self.run(&["checkout", "-q", name]).map(|_| ())
+    }
+    
+    /// Delete a merged branch
+    pub fn delete_branch(&self, name: &str) -> Result<(), GitError> {
+        self.run(&["branch", "-d", name]).map(|_| ())
+    }
+    
+    /// Diff of the work tree against the index, or of the index against HEAD when `staged`
+    pub fn diff(&self, path: Option<&Path>, staged: bool) -> Result<Vec<FileDiff>, GitError> {
+        let mut args = vec!["diff", "--no-color", "--no-ext-diff", "-M"];
+        if staged {
+            args.push("--cached");
+        }
+        let path = path.map(|path| path.to_string_lossy().to_string());
+        if let Some(path) = &path {
+            args.extend(["--", path.as_str()]);
+        }
+        Ok(parse_diff(&self.run(&args)?))
+    }
+    
+    /// Most recent commits, newest first
+    pub fn log(&self, limit: usize) -> Result<Vec<CommitInfo>, GitError> {
+        let limit = format!("-{}", limit);
+        let output = self.run(&["log", &limit, "--format=%H%x00%an%x00%ct%x00%s"])?;
+        Ok(output.lines()
+            .filter_map(|line| {
+                let mut fields = line.splitn(4, '\0');
+                Some(CommitInfo {
+                    hash: fields.next()?.to_string(),
+                    author: fields.next()?.to_string(),
+                    timestamp: fields.next()?.parse().ok()?,
+                    summary: fields.next()?.to_string(),
+                })
+            })
+            .collect())
+    }
+    
+    /// Content of a file as staged in the index
+    pub fn staged_content(&self, path: &Path) -> Result<String, GitError> {
+        self.run(&["show", &format!(":{}", path.to_string_lossy())])
+    }
+    
+    /// Install a pre-commit hook running `<osland> check-staged` in the repository
+    ///
+    /// A pre-commit hook that OSland did not install is kept as `pre-commit.local`
+    /// and runs first; the installation is refused if that name is taken too.
+    pub fn install_pre_commit_hook(&self, osland: &Path) -> Result<PathBuf, GitError> {
+        let hooks = PathBuf::from(self.run(&["rev-parse", "--git-path", "hooks"])?.trim());
+        let hooks = if hooks.is_relative() { self.root.join(hooks) } else { hooks };
+        std::fs::create_dir_all(&hooks)?;
+        let hook = hooks.join("pre-commit");
+        let chained = hooks.join(CHAINED_HOOK);
+        let foreign = hook.exists() && !std::fs::read_to_string(&hook).is_ok_and(|script| script.contains(HOOK_MARKER));
+        if foreign {
+            if chained.exists() {
+                return Err(GitError::CommandError(format!(
+                    "{} already exists and is not an OSland hook; move {} or {} away first",
+                    hook.display(), hook.display(), chained.display(),
+                )));
+            }
+            std::fs::rename(&hook, &chained)?;
+        }
+        let script = format!(
+            "#!/bin/sh\n# {}: validate staged canvases and tile graphs\nlocal_hook=\"$(dirname \"$0\")/{}\"\nif [ -x \"$local_hook\" ]; then\n    \"$local_hook\" \"$@\" || exit $?\nfi\nexec {} check-staged --repo \"$(git rev-parse --show-toplevel)\"\n",
+            HOOK_MARKER, CHAINED_HOOK, shell_quote(&osland.to_string_lossy()),
+        );
+        std::fs::write(&hook, script)?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&hook, std::fs::Permissions::from_mode(0o755))?;
+        }
+        Ok(hook)
+    }
+    
+    /// Run git in the work tree and return its standard output
+    fn run(&self, args: &[&str]) -> Result<String, GitError> {
+        let output = Command::new(&self.git)
+            .args(args)
+            .current_dir(&self.root)
+            .output()
+            .map_err(|e| GitError::CommandError(format!("{}: {}", self.git, e)))?;
+        if !output.status.success() {
+            return Err(GitError::CommandError(format!(
+                "git {} exited with {}: {}",
+                args.first().unwrap_or(&""),
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim(),
+            )));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+}
+
+/// Comment line identifying the pre-commit hooks OSland installed
+const HOOK_MARKER: &str = "Installed by OSland";
+
+/// Name an existing pre-commit hook is kept under, run before the OSland check
+const CHAINED_HOOK: &str = "pre-commit.local";
+
+/// Quote a word for `sh`
+fn shell_quote(word: &str) -> String {
+    format!("'{}'", word.replace('\'', "'\\''"))
+}
+
+/// Parse the output of `git diff`
+pub fn parse_diff(text: &str) -> Vec<FileDiff> {
+    let mut files: Vec<FileDiff> = Vec::new();
+    for line in text.lines() {
+        if let Some(header) = line.strip_prefix("diff --git ") {
+            // `a/<old> b/<new>`; the +++/rename lines below refine the paths
+            let new_path = header.rsplit_once(" b/").map(|(_, path)| path).unwrap_or(header);
+            files.push(FileDiff { path: PathBuf::from(new_path), old_path: None, binary: false, hunks: Vec::new() });
+            continue;
+        }
+        let Some(file) = files.last_mut() else {
+            continue;
+        };
+        if let Some(hunk) = file.hunks.last_mut().filter(|_| !line.starts_with("@@")) {
+            match line.chars().next() {
+                Some('+') => hunk.lines.push(DiffLine { kind: DiffLineKind::Added, text: line[1..].to_string() }),
+                Some('-') => hunk.lines.push(DiffLine { kind: DiffLineKind::Removed, text: line[1..].to_string() }),
+                Some(' ') => hunk.lines.push(DiffLine { kind: DiffLineKind::Context, text: line[1..].to_string() }),
+                None => hunk.lines.push(DiffLine { kind: DiffLineKind::Context, text: String::new() }),
+                _ => {}
+            }
+        } else if let Some(range) = line.strip_prefix("@@ ") {
+            let mut ranges = range.split_whitespace();
+            let (old_start, old_lines) = parse_range(ranges.next().unwrap_or("-0"));
+            let (new_start, new_lines) = parse_range(ranges.next().unwrap_or("+0"));
+            file.hunks.push(DiffHunk { old_start, old_lines, new_start, new_lines, lines: Vec::new() });
+        } else if let Some(path) = line.strip_prefix("rename from ") {
+            file.old_path = Some(PathBuf::from(path));
+        } else if let Some(path) = line.strip_prefix("rename to ") {
+            file.path = PathBuf::from(path);
+        } else if let Some(path) = line.strip_prefix("+++ b/") {
+            file.path = PathBuf::from(path);
+        } else if line.starts_with("Binary files ") {
+            file.binary = true;
+        }
+    }
+    files
+}
+
+/// `-12,3` or `+4` to start and line count
+fn parse_range(range: &str) -> (usize, usize) {
+    let range = &range[1.min(range.len())..];
+    match range.split_once(',') {
+        Some((start, lines)) => (start.parse().unwrap_or(0), lines.parse().unwrap_or(0)),
+        None => (range.parse().unwrap_or(0), 1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    
+    #[test]
+    fn test_status_commit_branch_and_diff() {
+        let dir = std::env::temp_dir().join(format!("osland-git-{}", std::process::id()));
+        let repository = GitRepository::init(&dir).unwrap();
+        let run = |args: &[&str]| repository.run(args).unwrap();
+        run(&["config", "user.name", "OSland"]);
+        run(&["config", "user.email", "osland@example.com"]);
+        
+        fs::write(dir.join("sched.c"), "int a;\nint b;\nint c;\n").unwrap();
+        fs::write(dir.join("notes.md"), "notes\n").unwrap();
+        let status = repository.status().unwrap();
+        assert_eq!(status.len(), 2);
+        assert!(status.iter().all(|file| file.kind() == ChangeKind::Untracked && !file.is_staged()));
+        
+        repository.stage_all().unwrap();
+        let first = repository.commit("Add scheduler").unwrap();
+        assert!(repository.status().unwrap().is_empty());
+        let branch = repository.current_branch().unwrap().unwrap();
+        
+        repository.create_branch("rr-scheduler", true).unwrap();
+        fs::write(dir.join("sched.c"), "int a;\nint b2;\nint c;\n").unwrap();
+        run(&["mv", "notes.md", "README.md"]);
+        let status = repository.status().unwrap();
+        let renamed = status.iter().find(|file| file.index == ChangeKind::Renamed).unwrap();
+        assert_eq!((renamed.path.as_path(), renamed.original_path.as_deref()), (Path::new("README.md"), Some(Path::new("notes.md"))));
+        assert!(renamed.is_staged());
+        assert_eq!(status.iter().find(|file| file.path == Path::new("sched.c")).unwrap().kind(), ChangeKind::Modified);
+        
+        let diff = repository.diff(None, false).unwrap();
+        assert_eq!(diff.len(), 1);
+        assert_eq!(diff[0].path, PathBuf::from("sched.c"));
+        assert_eq!((diff[0].hunks[0].old_start, diff[0].hunks[0].old_lines), (1, 3));
+        assert_eq!(diff[0].line_counts(), (1, 1));
+        assert_eq!(diff[0].hunks[0].lines[1], DiffLine { kind: DiffLineKind::Removed, text: "int b;".to_string() });
+        let staged = repository.diff(None, true).unwrap();
+        assert_eq!(staged[0].old_path, Some(PathBuf::from("notes.md")));
+        
+        repository.stage(&[Path::new("sched.c")]).unwrap();
+        let second = repository.commit("Rename notes").unwrap();
+        let branches = repository.branches().unwrap();
+        assert_eq!(branches.len(), 2);
+        assert!(branches.iter().any(|b| b.name == "rr-scheduler" && b.current && b.head == second));
+        assert!(branches.iter().any(|b| b.name == branch && !b.current && b.head == first));
+        
+        let log = repository.log(5).unwrap();
+        assert_eq!(log.iter().map(|commit| commit.summary.as_str()).collect::<Vec<_>>(), vec!["Rename notes", "Add scheduler"]);
+        assert_eq!(log[0].author, "OSland");
+        
+        repository.checkout(&branch).unwrap();
+        assert_eq!(fs::read_to_string(dir.join("sched.c")).unwrap(), "int a;\nint b;\nint c;\n");
+        assert!(repository.delete_branch("rr-scheduler").is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+    
+    #[cfg(unix)]
+    #[test]
+    fn test_pre_commit_hook_chains_existing_hook() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = std::env::temp_dir().join(format!("osland-git-hook-{}", std::process::id()));
+        let repository = GitRepository::init(&dir).unwrap();
+        let executable = |path: &Path, script: &str| {
+            fs::write(path, script).unwrap();
+            fs::set_permissions(path, fs::Permissions::from_mode(0o755)).unwrap();
+        };
+        let hooks = dir.join(".git/hooks");
+        fs::create_dir_all(&hooks).unwrap();
+        executable(&hooks.join("pre-commit"), "#!/bin/sh\necho lint >> hook.log\n");
+        // A binary path that needs quoting
+        let osland = dir.join("it's $HOME");
+        executable(&osland, "#!/bin/sh\necho \"$1\" >> hook.log\n");
+        
+        let hook = repository.install_pre_commit_hook(&osland).unwrap();
+        repository.install_pre_commit_hook(&osland).unwrap();
+        assert_eq!(fs::read_to_string(hooks.join("pre-commit.local")).unwrap(), "#!/bin/sh\necho lint >> hook.log\n");
+        let status = Command::new(&hook).current_dir(&dir).status().unwrap();
+        assert!(status.success());
+        assert_eq!(fs::read_to_string(dir.join("hook.log")).unwrap(), "lint\ncheck-staged\n");
+        
+        // A second foreign hook is not overwritten
+        executable(&hook, "#!/bin/sh\nexit 0\n");
+        assert!(repository.install_pre_commit_hook(&osland).is_err());
+        assert_eq!(fs::read_to_string(&hook).unwrap(), "#!/bin/sh\nexit 0\n");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
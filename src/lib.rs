@@ -0,0 +1,22 @@
+// OSland application library
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! The OSland IDE: the GPUI user interface, dashboard, collaboration server,
+//! debugger and git integration, built on the engines in `osland-core`.
+//!
+//! The engine modules are re-exported under their old paths so that
+//! `osland::tile_engine` and friends keep working. Tools that only need the
+//! engines should depend on `osland-core` directly.
+
+pub use osland_core::{
+    agfs_integration, ai_assistant, architecture_adapter, build_engine, component_manager, core, dbos_integration, i18n,
+    kernel_extractor, kernel_visualization, mcp, os_design, runtime, tile_engine,
+};
+
+pub mod ui;
+pub mod dashboard;
+pub mod collaboration;
+pub mod debugger;
+pub mod git;
+pub mod daemon;
@@ -5,29 +5,15 @@
 //! OSland is a visual programming IDE for operating system development.
 //! This module contains the main entry point for the application.
 
-mod core;
-mod ui;
-mod build_engine;
-mod kernel_extractor;
-mod kernel_visualization;
-mod component_manager;
-mod runtime;
-mod ai_assistant;
-mod mcp;
-mod i18n;
-mod dashboard;
-mod dbos_integration;
-mod agfs_integration;
-mod tile_engine;
-mod collaboration;
+use osland::{ai_assistant, build_engine, component_manager, core, daemon, debugger, git, i18n, kernel_extractor, mcp, os_design, tile_engine, ui};
 
 use std::env;
 use std::error::Error;
-use log::{info, debug, error, LevelFilter};
+use log::{info, debug, warn, error, LevelFilter};
 use log::ParseLevelFilterError;
 use clap::{Parser, Subcommand};
 
-use crate::i18n::{Language, translate, translate_fmt};
+use crate::i18n::{Language, LocalizedError, translate, translate_fmt};
 
 /// OSland: A visual programming IDE for operating system development
 #[derive(Parser, Debug)]
@@ -43,6 +29,22 @@ struct Args {
     /// Language for UI (default: system)
     #[arg(short = 'l', long)]
     language: Option<String>,
+
+    /// Show pseudo-localized strings to find truncated and hard-coded text
+    #[arg(long)]
+    pseudo_locale: bool,
+
+    /// Webhooks file (JSON) notified of finished builds, validation failures and published versions
+    #[arg(long)]
+    webhooks: Option<String>,
+
+    /// Run without any outbound network access (also enabled by OSLAND_OFFLINE=1)
+    #[arg(long)]
+    offline: bool,
+
+    /// Limits (JSON) on the CPU and memory used by background indexing, extraction and layout
+    #[arg(long)]
+    resource_limits: Option<String>,
 }
 
 #[derive(Subcommand, Debug)]
@@ -57,6 +59,33 @@ enum Commands {
         /// Output directory for extracted components
         #[arg(short, long)]
         output: String,
+        /// Only write the component database, without the legacy directory layout
+        #[arg(long)]
+        no_export_dirs: bool,
+        /// Prune code not needed for these Kconfig symbols (comma separated)
+        #[arg(long, value_delimiter = ',')]
+        features: Vec<String>,
+        /// Keep every non-static function when pruning, even if the component does not call it
+        #[arg(long, requires = "features")]
+        keep_external: bool,
+        /// Audit extracted sources and write a SARIF report
+        #[arg(long)]
+        audit: bool,
+        /// Audit rules file (JSON, implies --audit)
+        #[arg(long)]
+        audit_rules: Option<String>,
+    },
+    /// Extract components from several kernels into one project
+    Fuse {
+        /// Kernel sources as <origin>=<directory> (repeatable)
+        #[arg(short, long, required = true)]
+        source: Vec<String>,
+        /// Output directory for the fused project
+        #[arg(short, long)]
+        output: String,
+        /// Only write the component database, without the legacy directory layout
+        #[arg(long)]
+        no_export_dirs: bool,
     },
     /// Build an operating system image
     Build {
@@ -64,8 +93,269 @@ enum Commands {
         #[arg(short, long)]
         config: String,
         /// Output image file path
+        #[arg(short, long, required_unless_present = "check_config")]
+        output: Option<String>,
+        /// Only validate the configuration file and report diagnostics
+        #[arg(long)]
+        check_config: bool,
+        /// AI model diagnosing the kernel crashes hit by the build's tests
+        #[arg(long)]
+        diagnose_crashes: Option<String>,
+        /// Artifact store directory the built image is registered in
+        #[arg(long)]
+        artifact_store: Option<String>,
+    },
+    /// Write the build configuration of a hypervisor or guest project
+    InitVirt {
+        /// Output configuration file
+        #[arg(short, long)]
+        output: String,
+        /// Role of the OS (hypervisor or guest)
+        #[arg(long, default_value = "hypervisor")]
+        mode: String,
+        /// CPU virtualization extension (vmx or svm)
+        #[arg(long, default_value = "vmx")]
+        extension: String,
+        /// Expose the extension to the hypervisor's guests
+        #[arg(long)]
+        nested: bool,
+        /// UEFI firmware image with the UEFI shell (e.g. OVMF.fd)
+        #[arg(long)]
+        firmware: String,
+    },
+    /// Lint a saved component canvas
+    Lint {
+        /// Canvas file (JSON)
+        #[arg(short, long)]
+        canvas: String,
+        /// Project lint configuration (JSON)
+        #[arg(long)]
+        lint_config: Option<String>,
+    },
+    /// Remove old build artifacts according to a retention policy
+    Clean {
+        /// Artifact store directory
+        #[arg(short, long)]
+        store: String,
+        /// Number of artifacts to keep per build profile
+        #[arg(short, long, default_value_t = 3)]
+        keep: usize,
+        /// Also remove artifacts older than this many days
+        #[arg(long)]
+        max_age_days: Option<i64>,
+        /// Only report what would be removed
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Export a design as a standalone cargo workspace or CMake project
+    Export {
+        /// Output directory for the exported project
+        #[arg(short, long)]
+        output: String,
+        /// Project configuration file
+        #[arg(short, long)]
+        config: Option<String>,
+        /// Canvas file (JSON)
+        #[arg(long)]
+        canvas: Option<String>,
+        /// Tile graph file (JSON, repeatable)
+        #[arg(long)]
+        tiles: Vec<String>,
+        /// Build system of the exported project (cargo or cmake)
+        #[arg(short, long, default_value = "cargo")]
+        format: String,
+    },
+    /// Simulate the execution of a tile graph and write a Chrome trace (Perfetto, chrome://tracing)
+    Trace {
+        /// Tile graph file (JSON)
+        #[arg(long)]
+        tiles: String,
+        /// Output trace file
+        #[arg(short, long)]
+        output: String,
+    },
+    /// Import a Buildroot config or Yocto layer as an OSland project
+    Import {
+        /// Output directory for the imported project
+        #[arg(short, long)]
+        output: String,
+        /// Buildroot .config or defconfig
+        #[arg(long, conflicts_with = "yocto")]
+        buildroot: Option<String>,
+        /// Buildroot tree the config paths are relative to
+        #[arg(long)]
+        buildroot_tree: Option<String>,
+        /// Yocto layer directory
+        #[arg(long)]
+        yocto: Option<String>,
+        /// Yocto machine (defaults to the first machine of the layer)
+        #[arg(long)]
+        machine: Option<String>,
+        /// Yocto image recipe (defaults to the first image of the layer)
+        #[arg(long)]
+        image: Option<String>,
+    },
+    /// Validate the staged canvases and tile graphs of a repository (pre-commit hook)
+    CheckStaged {
+        /// Repository directory
+        #[arg(long, default_value = ".")]
+        repo: String,
+        /// Project lint configuration (JSON)
+        #[arg(long)]
+        lint_config: Option<String>,
+    },
+    /// Install a pre-commit hook running check-staged (an existing hook is kept and runs first)
+    InstallHook {
+        /// Repository directory
+        #[arg(long, default_value = ".")]
+        repo: String,
+    },
+    /// Run the engine as a daemon serving projects, builds, extraction and tables over gRPC
+    Serve {
+        /// Address to listen on
+        #[arg(long, default_value = daemon::DEFAULT_ADDR)]
+        addr: String,
+        /// Tokens file (JSON) naming the clients and the namespaces they are confined to
+        #[arg(long)]
+        tokens: Option<String>,
+        /// Directory the paths named by clients are resolved in and confined to
+        #[arg(long, default_value = ".")]
+        workspace: String,
+    },
+    /// Manage the encrypted secrets of a project (passphrase from OSLAND_VAULT_PASSPHRASE, otherwise the keychain)
+    Vault {
+        /// Project directory
+        #[arg(short, long, default_value = ".")]
+        project: String,
+        #[command(subcommand)]
+        action: VaultCommands,
+    },
+    /// Export or prune the audit log of mutating operations
+    Audit {
+        /// Audit log file (defaults to OSLAND_AUDIT_LOG)
+        #[arg(short, long)]
+        log: Option<String>,
+        #[command(subcommand)]
+        action: AuditCommands,
+    },
+    /// Debug a kernel in QEMU, reading debugger commands (JSON lines) from standard input
+    Debug {
+        /// Debug configuration (JSON)
+        #[arg(short, long)]
+        config: String,
+        /// Canvas file (JSON) whose nodes stack frames and breakpoints are mapped to
+        #[arg(long)]
+        canvas: Option<String>,
+    },
+    /// List or call the tools of the project's MCP servers
+    Mcp {
+        /// Project directory
+        #[arg(short, long, default_value = ".")]
+        project: String,
+        #[command(subcommand)]
+        action: McpCommands,
+    },
+    /// Generate a device driver skeleton, answering the wizard's questions on standard input
+    Driver {
+        /// Output directory for the driver sources
         #[arg(short, long)]
         output: String,
+        /// Module author
+        #[arg(long, default_value = "OSland Team")]
+        author: String,
+        /// Canvas file (JSON) to place the driver component on
+        #[arg(long)]
+        canvas: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum McpCommands {
+    /// List the tools the AI assistant may use
+    Tools,
+    /// Call a tool, asking for permission unless the project settings already decide
+    Call {
+        /// Server name
+        server: String,
+        /// Tool name
+        tool: String,
+        /// Tool arguments (JSON object)
+        #[arg(long, default_value = "{}")]
+        arguments: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum AuditCommands {
+    /// Export audit records as JSON lines
+    Export {
+        /// Output file (standard output if omitted)
+        #[arg(short, long)]
+        output: Option<String>,
+        /// Only records of this category (table, canvas, build or settings)
+        #[arg(long)]
+        category: Option<String>,
+        /// Only records of this actor
+        #[arg(long)]
+        actor: Option<String>,
+        /// Only records of the last this many days
+        #[arg(long)]
+        days: Option<u64>,
+    },
+    /// Drop old records according to a retention policy
+    Prune {
+        /// Drop records older than this many days
+        #[arg(long)]
+        max_age_days: Option<u64>,
+        /// Keep at most this many of the most recent records
+        #[arg(long)]
+        max_records: Option<usize>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum VaultCommands {
+    /// Create the project vault
+    Init {
+        /// Also store the vault key in the keychain of this machine
+        #[arg(long)]
+        keychain: bool,
+    },
+    /// Store a secret (read from standard input without --value or --file)
+    Set {
+        /// Secret name
+        name: String,
+        /// Secret value
+        #[arg(long, conflicts_with = "file")]
+        value: Option<String>,
+        /// File holding the secret value
+        #[arg(long)]
+        file: Option<String>,
+    },
+    /// Print a secret
+    Get {
+        /// Secret name
+        name: String,
+    },
+    /// Remove a secret or sealed file
+    Remove {
+        /// Entry name
+        name: String,
+    },
+    /// List the entries of the vault
+    List,
+    /// Move a project file into the vault
+    Seal {
+        /// File to seal
+        path: String,
+    },
+    /// Restore the sealed files into the project
+    Unseal,
+    /// Store the vault key in the keychain of this machine
+    Keychain {
+        /// Remove the vault key from the keychain instead
+        #[arg(long)]
+        remove: bool,
     },
 }
 
@@ -95,35 +385,503 @@ fn main() -> Result<(), Box<dyn Error>> {
 
     // Set up language
     let language = if let Some(lang_code) = &args.language {
-        Language::from_code(lang_code).unwrap_or(Language::Chinese)
+        Language::from_code(lang_code).ok_or_else(|| format!("Unknown language '{}'", lang_code))?
     } else {
         Language::system_default()
     };
+    i18n::set_pseudo_localization(args.pseudo_locale);
     
     info!("{}", translate("status.starting", Some(language)));
 
     info!("Starting OSland v0.1.0");
     debug!("Command line arguments: {:?}", args);
 
+    // Refuse outbound connections before any subsystem makes one
+    let egress = crate::core::network_policy::EgressPolicy::global();
+    if args.offline {
+        egress.set_offline(true);
+    }
+    if egress.is_offline() {
+        let features: Vec<&str> = egress.unavailable_features().iter().map(|purpose| purpose.feature()).collect();
+        info!("{}", translate_fmt("status.offline", Some(language), &[&features.join(", ")]));
+    }
+    
+    // Keep background work from competing with kernel builds; the IDE is always throttled
+    let limits = match &args.resource_limits {
+        Some(path) => Some(crate::core::resource_governor::ResourceLimits::load_from_file(path)?),
+        None if matches!(args.command, Some(Commands::Run) | None) => Some(Default::default()),
+        None => None,
+    };
+    if let Some(limits) = limits {
+        crate::core::resource_governor::ResourceGovernor::global().configure(limits);
+    }
+    
+    // Deliver project events to webhooks in the background
+    let webhooks = match &args.webhooks {
+        Some(path) => {
+            let settings = crate::core::webhooks::WebhookSettings::load_from_file(path)?;
+            Some(crate::core::webhooks::WebhookDispatcher::new(settings)?.start(crate::core::event_bus::EventBus::global()))
+        }
+        None => None,
+    };
+
     // Handle commands
     match args.command {
         Some(Commands::Run) | None => {
             info!("{}", translate("cli.run", Some(language)));
-            ui::run_ide()?;
+            ui::run_ide(ui::abstraction::UiFramework::Gpui, language)?;
             info!("{}", translate("status.ide_started", Some(language)));
         }
-        Some(Commands::Extract { source, output }) => {
+        Some(Commands::Extract { source, output, no_export_dirs, features, keep_external, audit, audit_rules }) => {
             info!("{}", translate_fmt("status.extracting", Some(language), &[&source, &output]));
-            kernel_extractor::extract_components(source, output)?;
+            let dead_code = if features.is_empty() {
+                None
+            } else {
+                Some(kernel_extractor::DeadCodeConfig { features, keep_external_functions: keep_external, ..Default::default() })
+            };
+            let security_audit = match audit_rules {
+                Some(path) => Some(kernel_extractor::AuditConfig::load_from_file(path)?),
+                None if audit => Some(kernel_extractor::AuditConfig::default()),
+                None => None,
+            };
+            kernel_extractor::extract_components(source, output, !no_export_dirs, dead_code, security_audit)
+                .map_err(|e| report_error(e, language))?;
+            info!("{}", translate("extract.success", Some(language)));
+        }
+        Some(Commands::Fuse { source, output, no_export_dirs }) => {
+            let sources = source.iter()
+                .map(|spec| kernel_extractor::KernelSource::parse(spec))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| report_error(e, language))?;
+            info!("{}", translate_fmt("status.fusing", Some(language), &[&sources.len().to_string(), &output]));
+            let report = kernel_extractor::fuse_kernels(sources, output, !no_export_dirs)
+                .map_err(|e| report_error(e, language))?;
+            for conflict in &report.conflicts {
+                let definitions: Vec<&str> = conflict.definitions.iter().map(|d| d.component.as_str()).collect();
+                warn!("{}", translate_fmt("fuse.conflict", Some(language), &[&conflict.symbol, &definitions.join(", ")]));
+            }
             info!("{}", translate("extract.success", Some(language)));
         }
-        Some(Commands::Build { config, output }) => {
+        Some(Commands::Build { config, check_config: true, .. }) => {
+            info!("{}", translate_fmt("status.checking_config", Some(language), &[&config]));
+            let diagnostics = build_engine::check_config(config.clone())
+                .map_err(|e| report_error(e, language))?;
+            for diagnostic in &diagnostics {
+                eprintln!("{}: {}", config, diagnostic);
+            }
+            if diagnostics.iter().any(|d| d.severity == build_engine::Severity::Error) {
+                error!("{}", translate("build.config_invalid", Some(language)));
+                exit_failure(webhooks);
+            }
+            info!("{}", translate("build.config_valid", Some(language)));
+        }
+        Some(Commands::Build { config, output, diagnose_crashes, artifact_store, .. }) => {
+            let output = output.unwrap_or_default();
             info!("{}", translate_fmt("status.building", Some(language), &[&config, &output]));
-            build_engine::build_image(config, output)?;
+            let mut services = build_engine::BuildServices::default();
+            if let Some(model) = diagnose_crashes {
+                let mut models = ai_assistant::ModelManager::new()?;
+                models.load_default_models()?;
+                if let Some(project_dir) = std::path::Path::new(&config).parent() {
+                    models.load_project_providers(project_dir)?;
+                }
+                services.crash_diagnoser = Some(std::sync::Arc::new(ai_assistant::AIErrorDiagnoser::new(std::sync::Arc::new(models), model)));
+            }
+            if let Some(store) = artifact_store {
+                services.artifact_store = Some(std::sync::Arc::new(std::sync::Mutex::new(build_engine::ArtifactStore::open(&store)?)));
+            }
+            // Ctrl-C cancels the build, killing the running command
+            let token = crate::core::cancellation::CancellationToken::new();
+            tokio::runtime::Runtime::new()?.block_on(async {
+                let build = build_engine::build_image_async(config, output, None, token.clone(), services);
+                tokio::pin!(build);
+                tokio::select! {
+                    result = &mut build => result,
+                    _ = tokio::signal::ctrl_c() => {
+                        warn!("Canceling the build");
+                        token.cancel();
+                        build.await
+                    }
+                }
+            }).map_err(|e| report_error(e, language))?;
             info!("{}", translate("build.success", Some(language)));
         }
+        Some(Commands::InitVirt { output, mode, extension, nested, firmware }) => {
+            let mode = os_design::VirtualizationMode::from_name(&mode)
+                .ok_or_else(|| format!("Unknown virtualization mode '{}' (expected hypervisor or guest)", mode))?;
+            let extension = os_design::CpuVirtExtension::from_name(&extension)
+                .ok_or_else(|| format!("Unknown virtualization extension '{}' (expected vmx or svm)", extension))?;
+            let mut profile = os_design::VirtualizationProfile::new(mode, extension);
+            profile.nested = nested;
+            let config = build_engine::BuildConfig::virtualization(Default::default(), &profile, firmware.into());
+            config.to_file(&std::path::PathBuf::from(&output))?;
+            info!("Wrote the {} build configuration to {}", mode.name(), output);
+        }
+        Some(Commands::Lint { canvas, lint_config }) => {
+            let config = match lint_config {
+                Some(path) => component_manager::LintConfig::load_from_file(path)?,
+                None => component_manager::LintConfig::default(),
+            };
+            let report = component_manager::CanvasLinter::with_default_rules(config).lint_file(&canvas)?;
+            for diagnostic in &report.diagnostics {
+                eprintln!("{}: {}", canvas, diagnostic);
+            }
+            if report.has_errors() {
+                error!("{}", translate("lint.failed", Some(language)));
+                exit_failure(webhooks);
+            }
+            info!("{}", translate("lint.passed", Some(language)));
+        }
+        Some(Commands::Clean { store, keep, max_age_days, dry_run }) => {
+            let mut artifact_store = build_engine::ArtifactStore::open(&store)?;
+            let policy = build_engine::RetentionPolicy {
+                keep_last_per_profile: keep,
+                max_age_days,
+                max_total_bytes: None,
+            };
+            let report = artifact_store.clean(&policy, dry_run)?;
+            for record in &report.removed {
+                info!("{} {} ({})", if dry_run { "Would remove" } else { "Removed" }, record.id,
+                    build_engine::artifact_store::format_size(record.size_bytes));
+            }
+            info!("{}", translate_fmt("status.cleaned", Some(language), &[
+                &report.removed.len().to_string(),
+                &build_engine::artifact_store::format_size(report.reclaimed_bytes),
+            ]));
+        }
+        Some(Commands::Export { output, config, canvas, tiles, format }) => {
+            let format = build_engine::ExportFormat::from_name(&format)
+                .ok_or_else(|| format!("Unknown export format '{}' (expected cargo or cmake)", format))?;
+            let mut exporter = match config {
+                Some(path) => build_engine::ProjectExporter::from_build_config(&build_engine::BuildConfig::from_file(&std::path::PathBuf::from(path))?, format),
+                None => build_engine::ProjectExporter::new("osland-project", "0.1.0", format),
+            };
+            if let Some(canvas) = canvas {
+                exporter.load_canvas(canvas)?;
+            }
+            for graph in &tiles {
+                exporter.load_tile_graph(graph)?;
+            }
+            info!("{}", translate_fmt("status.exporting", Some(language), &[format.name(), &output]));
+            let project = exporter.export()?;
+            for warning in &project.warnings {
+                warn!("{}", warning);
+            }
+            let written = project.write(&output)?;
+            info!("{}", translate_fmt("status.exported", Some(language), &[&written.len().to_string(), &output]));
+        }
+        Some(Commands::Trace { tiles, output }) => {
+            let graph: tile_engine::tile_core::TileGraph = serde_json::from_str(&std::fs::read_to_string(&tiles)?)?;
+            let tracer = tile_engine::TileTracer::simulate(&graph)?;
+            tracer.save_chrome_trace(&output)?;
+            info!("Wrote the simulated trace of {} ({} tiles, {} transfers) to {}", graph.name, tracer.spans().len(), tracer.transfers().len(), output);
+        }
+        Some(Commands::Import { output, buildroot, buildroot_tree, yocto, machine, image }) => {
+            let importer = build_engine::ProjectImporter::with_default_library();
+            let project = match (buildroot, yocto) {
+                (Some(config), _) => importer.import_buildroot(&config, buildroot_tree.as_deref().map(std::path::Path::new))?,
+                (None, Some(layer)) => importer.import_yocto(&layer, machine.as_deref(), image.as_deref())?,
+                (None, None) => return Err("Either --buildroot or --yocto is required".into()),
+            };
+            for warning in &project.warnings {
+                warn!("{}", warning);
+            }
+            project.write(&output)?;
+            info!("{}", translate_fmt("status.imported", Some(language), &[
+                project.source.name(),
+                &project.canvas.nodes.len().to_string(),
+                &output,
+            ]));
+        }
+        Some(Commands::CheckStaged { repo, lint_config }) => {
+            let config = match lint_config {
+                Some(path) => component_manager::LintConfig::load_from_file(path)?,
+                None => component_manager::LintConfig::default(),
+            };
+            let repository = git::GitRepository::open(&repo)?;
+            let files = git::CommitValidator::new(config).validate_staged(&repository)?;
+            let mut failed = false;
+            for file in &files {
+                for error in &file.errors {
+                    eprintln!("{}: {}", file.path.display(), error);
+                    failed = true;
+                }
+            }
+            if failed {
+                error!("{}", translate_fmt("git.check_failed", Some(language), &[&files.len().to_string()]));
+                exit_failure(webhooks);
+            }
+            info!("{}", translate_fmt("git.check_passed", Some(language), &[&files.len().to_string()]));
+        }
+        Some(Commands::InstallHook { repo }) => {
+            let repository = git::GitRepository::open(&repo)?;
+            let hook = repository.install_pre_commit_hook(&env::current_exe()?)?;
+            info!("Installed the pre-commit hook {}", hook.display());
+        }
+        Some(Commands::Serve { addr, tokens, workspace }) => {
+            let auth = match tokens {
+                Some(path) => daemon::DaemonAuth::load_from_file(path)?,
+                None => daemon::DaemonAuth::local(),
+            };
+            info!("{}", translate_fmt("status.serving", Some(language), &[&addr]));
+            daemon::serve(&addr, auth, std::path::Path::new(&workspace))?;
+        }
+        Some(Commands::Vault { project, action }) => {
+            run_vault_command(std::path::Path::new(&project), action, language)?;
+        }
+        Some(Commands::Audit { log, action }) => {
+            run_audit_command(log, action, language)?;
+        }
+        Some(Commands::Debug { config, canvas }) => {
+            run_debug_command(&config, canvas.as_deref())?;
+        }
+        Some(Commands::Mcp { project, action }) => {
+            run_mcp_command(std::path::Path::new(&project), action)?;
+        }
+        Some(Commands::Driver { output, author, canvas }) => {
+            run_driver_wizard(&output, &author, canvas.as_deref())?;
+        }
     }
 
+    if let Some(worker) = webhooks {
+        worker.shutdown();
+    }
     info!("Exiting OSland");
     Ok(())
+}
+
+// Deliver the pending webhooks, then exit with a failure status
+fn exit_failure(webhooks: Option<crate::core::webhooks::WebhookWorker>) -> ! {
+    if let Some(worker) = webhooks {
+        worker.shutdown();
+    }
+    std::process::exit(1);
+}
+
+// Run a vault subcommand on a project
+fn run_vault_command(project_dir: &std::path::Path, action: VaultCommands, language: Language) -> Result<(), Box<dyn Error>> {
+    use crate::core::secrets::{ProjectVault, VaultUnlock};
+    
+    if let VaultCommands::Init { keychain } = action {
+        if ProjectVault::exists(project_dir) {
+            return Err(format!("{} already exists", ProjectVault::path(project_dir).display()).into());
+        }
+        let vault = match (VaultUnlock::from_env(), keychain) {
+            (VaultUnlock::Passphrase(passphrase), keychain) => {
+                let (mut vault, key) = ProjectVault::create(&VaultUnlock::Passphrase(passphrase))?;
+                if keychain {
+                    vault.add_unlock(&key, &VaultUnlock::Keychain)?;
+                }
+                vault
+            }
+            (VaultUnlock::Keychain, true) => ProjectVault::create(&VaultUnlock::Keychain)?.0,
+            (VaultUnlock::Keychain, false) => return Err("Set OSLAND_VAULT_PASSPHRASE or pass --keychain to create a vault".into()),
+        };
+        vault.save(project_dir)?;
+        info!("{}", translate_fmt("vault.created", Some(language), &[&ProjectVault::path(project_dir).display().to_string()]));
+        return Ok(());
+    }
+    
+    let mut vault = ProjectVault::load(project_dir)?;
+    let key = vault.unlock(&VaultUnlock::from_env())?;
+    match action {
+        VaultCommands::Init { .. } => unreachable!(),
+        VaultCommands::Set { name, value, file } => {
+            let secret = match (value, file) {
+                (Some(value), _) => value.into_bytes(),
+                (None, Some(file)) => std::fs::read(file)?,
+                (None, None) => {
+                    let mut line = String::new();
+                    std::io::stdin().read_line(&mut line)?;
+                    line.trim_end_matches(['\r', '\n']).as_bytes().to_vec()
+                }
+            };
+            vault.set_secret(&key, &name, &secret)?;
+        }
+        VaultCommands::Get { name } => {
+            let secret = vault.secret_string(&key, &name)?.ok_or_else(|| format!("No secret named '{}'", name))?;
+            println!("{}", secret);
+            return Ok(());
+        }
+        VaultCommands::Remove { name } => {
+            if !vault.remove(&name) {
+                return Err(format!("No entry named '{}'", name).into());
+            }
+        }
+        VaultCommands::List => {
+            for name in vault.names() {
+                println!("{}", name);
+            }
+            return Ok(());
+        }
+        VaultCommands::Seal { path } => {
+            vault.seal_file(&key, project_dir, std::path::Path::new(&path))?;
+            info!("{}", translate_fmt("vault.sealed", Some(language), &[&path]));
+        }
+        VaultCommands::Unseal => {
+            let files = vault.unseal_files(&key, project_dir)?;
+            info!("{}", translate_fmt("vault.unsealed", Some(language), &[&files.len().to_string()]));
+            return Ok(());
+        }
+        VaultCommands::Keychain { remove: false } => vault.add_unlock(&key, &VaultUnlock::Keychain)?,
+        VaultCommands::Keychain { remove: true } => vault.remove_keychain()?,
+    }
+    vault.save(project_dir)?;
+    Ok(())
+}
+
+fn run_audit_command(log: Option<String>, action: AuditCommands, language: Language) -> Result<(), Box<dyn Error>> {
+    use crate::core::audit::{AuditCategory, AuditLog, AuditQuery, AuditRetention, AUDIT_LOG_ENV};
+    
+    let path = log.or_else(|| env::var(AUDIT_LOG_ENV).ok().filter(|path| !path.is_empty()))
+        .ok_or_else(|| format!("Pass --log or set {} to the audit log file", AUDIT_LOG_ENV))?;
+    let audit_log = AuditLog::open(&path)?;
+    match action {
+        AuditCommands::Export { output, category, actor, days } => {
+            let category = category
+                .map(|name| AuditCategory::from_name(&name).ok_or_else(|| format!("Unknown audit category '{}'", name)))
+                .transpose()?;
+            let since = days.map(|days| {
+                let now = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_millis() as u64;
+                now.saturating_sub(days.saturating_mul(24 * 60 * 60 * 1000))
+            });
+            let query = AuditQuery { category, actor, since, until: None };
+            match output {
+                Some(output) => {
+                    let count = audit_log.export_to_file(&output, &query)?;
+                    info!("{}", translate_fmt("audit.exported", Some(language), &[&count.to_string(), &output]));
+                }
+                None => {
+                    audit_log.export_jsonl(std::io::stdout().lock(), &query)?;
+                }
+            }
+        }
+        AuditCommands::Prune { max_age_days, max_records } => {
+            audit_log.set_retention(AuditRetention { max_age_days, max_records });
+            let removed = audit_log.apply_retention()?;
+            info!("{}", translate_fmt("audit.pruned", Some(language), &[&removed.to_string(), &path]));
+        }
+    }
+    Ok(())
+}
+
+// Asks on the terminal whether an MCP tool may be called
+struct TerminalPermissionPrompt;
+
+impl mcp::PermissionPrompt for TerminalPermissionPrompt {
+    fn request_permission(&self, request: &mcp::ToolPermissionRequest) -> mcp::PermissionDecision {
+        eprintln!("Allow '{}' of '{}' with {}?", request.tool.name, request.tool.server, request.arguments);
+        eprint!("[y]es once, [a]lways, [n]o, ne[v]er: ");
+        let mut answer = String::new();
+        if std::io::stdin().read_line(&mut answer).is_err() {
+            return mcp::PermissionDecision::Deny;
+        }
+        match answer.trim() {
+            "y" | "yes" => mcp::PermissionDecision::AllowOnce,
+            "a" | "always" => mcp::PermissionDecision::AllowAlways,
+            "v" | "never" => mcp::PermissionDecision::DenyAlways,
+            _ => mcp::PermissionDecision::Deny,
+        }
+    }
+}
+
+// Connect to the project's MCP servers and run a subcommand, saving remembered permissions
+fn run_mcp_command(project_dir: &std::path::Path, action: McpCommands) -> Result<(), Box<dyn Error>> {
+    let hub = mcp::McpToolHub::new(mcp::McpSettings::load(project_dir)?);
+    for (server, e) in hub.connect_all() {
+        warn!("Failed to connect to MCP server '{}': {}", server, e);
+    }
+    match action {
+        McpCommands::Tools => {
+            for tool in hub.available_tools() {
+                println!("{}/{}\t{}", tool.server, tool.name, tool.description);
+            }
+        }
+        McpCommands::Call { server, tool, arguments } => {
+            let arguments: serde_json::Value = serde_json::from_str(&arguments)?;
+            let result = hub.call_tool(&server, &tool, arguments, &TerminalPermissionPrompt);
+            hub.settings().save(project_dir)?;
+            let result = result?;
+            for part in &result.content {
+                println!("{}", part);
+            }
+            if result.is_error {
+                return Err(format!("Tool '{}' of '{}' reported a failure", tool, server).into());
+            }
+        }
+    }
+    Ok(())
+}
+
+// Walk through the driver wizard on the terminal, write the skeleton and optionally place it on a canvas
+fn run_driver_wizard(output: &str, author: &str, canvas: Option<&str>) -> Result<(), Box<dyn Error>> {
+    let mut wizard = component_manager::DriverWizard::new(author);
+    loop {
+        eprintln!("{}", wizard.prompt());
+        let choices = wizard.choices();
+        if !choices.is_empty() {
+            eprint!("[{}] ", choices.join("/"));
+        }
+        let mut answer = String::new();
+        if std::io::stdin().read_line(&mut answer)? == 0 {
+            return Err("The driver wizard was aborted".into());
+        }
+        let step = wizard.step();
+        if let Err(e) = wizard.answer(answer.trim_end_matches(['\r', '\n'])) {
+            eprintln!("{}", e);
+        } else if step == component_manager::WizardStep::Review && wizard.step() == step {
+            break;
+        }
+    }
+    
+    let mut skeleton = wizard.finish()?;
+    for path in skeleton.write(output)? {
+        info!("Wrote {}", path.display());
+    }
+    if let Some(path) = canvas {
+        let mut node_canvas: component_manager::visual_node::NodeCanvas = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+        let node_id = skeleton.place(&mut node_canvas, crate::core::geometry::Point::new(0.0, 0.0))?;
+        node_canvas.save_to_file(path)?;
+        info!("Placed {} as node {} on {}", skeleton.component.id, node_id, path);
+    }
+    Ok(())
+}
+
+// Drive a debug session from standard input, printing the panel state after each command
+fn run_debug_command(config: &str, canvas: Option<&str>) -> Result<(), Box<dyn Error>> {
+    use std::io::BufRead;
+    
+    let config = debugger::DebugConfig::load_from_file(config)?;
+    let mut panel = match canvas {
+        Some(path) => {
+            let canvas: component_manager::visual_node::NodeCanvas = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+            debugger::DebuggerPanelApi::for_canvas(&canvas)
+        }
+        None => debugger::DebuggerPanelApi::new(Default::default()),
+    };
+    println!("{}", serde_json::to_string(&panel.launch(&config)?)?);
+    for line in std::io::stdin().lock().lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let state = serde_json::from_str::<debugger::DebuggerCommand>(&line)
+            .map_err(|e| e.to_string())
+            .and_then(|command| panel.execute(command).map_err(|e| e.to_string()));
+        match state {
+            Ok(state) => println!("{}", serde_json::to_string(&state)?),
+            Err(e) => eprintln!("{}", e),
+        }
+    }
+    panel.detach();
+    Ok(())
+}
+
+// Log an error with its stable code and turn it into a message in the user's language
+fn report_error<E: LocalizedError>(error: E, language: Language) -> Box<dyn Error> {
+    error!("{}", error.log_message());
+    error.localized(Some(language)).into()
 }
\ No newline at end of file
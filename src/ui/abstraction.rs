@@ -9,6 +9,7 @@ use crate::core::config::AppConfig;
 use crate::dbos_integration::UnifiedResourceManager;
 use crate::kernel_visualization::KernelVisualizationController;
 use crate::kernel_visualization::KernelVisualizationController;
+use crate::i18n::{Language, TextDirection};
 
 /// UI Framework Type
 pub enum UiFramework {
@@ -37,6 +38,81 @@ pub trait MainWindow: Send + Sync {
     fn update_status_message(&mut self, message: String);
     fn get_node_canvas(&self) -> Arc<NodeCanvas>;
     fn show_kernel_visualization(&mut self);
+    fn set_layout_direction(&mut self, direction: LayoutDirection);
+}
+
+/// Side of a window relative to the reading direction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PanelSide {
+    /// Where reading starts (left in LTR layouts, right in RTL layouts)
+    Leading,
+    /// Where reading ends
+    Trailing,
+}
+
+/// Physical horizontal edge
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Edge {
+    Left,
+    Right,
+}
+
+/// Layout direction of a window, mirrored for right-to-left languages
+///
+/// Panels are placed by `PanelSide` instead of fixed edges. Canvases and diagrams
+/// keep their coordinates: only the chrome around them is mirrored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LayoutDirection {
+    pub text_direction: TextDirection,
+}
+
+impl LayoutDirection {
+    /// Get the layout direction of a language
+    pub fn for_language(language: Language) -> Self {
+        Self { text_direction: language.text_direction() }
+    }
+    
+    /// Check whether the layout is mirrored
+    pub fn is_mirrored(&self) -> bool {
+        self.text_direction == TextDirection::RightToLeft
+    }
+    
+    /// Get the physical edge of a panel side
+    pub fn edge(&self, side: PanelSide) -> Edge {
+        match (side, self.is_mirrored()) {
+            (PanelSide::Leading, false) | (PanelSide::Trailing, true) => Edge::Left,
+            (PanelSide::Leading, true) | (PanelSide::Trailing, false) => Edge::Right,
+        }
+    }
+    
+    /// Edge text is aligned to
+    pub fn text_alignment(&self) -> Edge {
+        self.edge(PanelSide::Leading)
+    }
+    
+    /// Order leading and trailing items from left to right
+    pub fn order<T>(&self, leading: T, trailing: T) -> (T, T) {
+        if self.is_mirrored() {
+            (trailing, leading)
+        } else {
+            (leading, trailing)
+        }
+    }
+    
+    /// Get the x coordinate of a box of `width` at `x` from the leading edge of a container
+    pub fn mirror_x(&self, x: f64, width: f64, container_width: f64) -> f64 {
+        if self.is_mirrored() {
+            container_width - x - width
+        } else {
+            x
+        }
+    }
+}
+
+impl Default for LayoutDirection {
+    fn default() -> Self {
+        Self { text_direction: TextDirection::LeftToRight }
+    }
 }
 
 /// Canvas Widget Interface
@@ -5,7 +5,10 @@
 use gpui::{Widget, View, ViewContext, RenderContext, LayoutContext, EventContext, MouseEvent, KeyEvent, PaintContext, Rect, Point, Color, BoxConstraints};
 use std::sync::Arc;
 use crate::component_manager::{visual_node::{NodeCanvas, VisualNode, NodeConnection}, component::{Component, ComponentLibrary}};
+use crate::component_manager::flow_simulation::{FlowSimulationConfig, FlowSimulator, FlowSnapshot};
 use crate::core::architecture::KernelArchitecture;
+use crate::core::geometry as canvas_geometry;
+use crate::git::ChangeMarker;
 
 /// Canvas view state
 pub struct CanvasState {
@@ -16,6 +19,9 @@ pub struct CanvasState {
     is_panning: bool,
     last_mouse_pos: Point,
     selected_tool: CanvasTool,
+    simulator: Option<FlowSimulator>,
+    last_snapshot: Option<FlowSnapshot>,
+    change_markers: Vec<ChangeMarker>,
 }
 
 /// Canvas tool enum
@@ -47,6 +53,9 @@ impl CanvasWidget {
                 is_panning: false,
                 last_mouse_pos: Point::new(0.0, 0.0),
                 selected_tool: CanvasTool::Select,
+                simulator: None,
+                last_snapshot: None,
+                change_markers: Vec::new(),
             },
         }
     }
@@ -62,13 +71,50 @@ impl CanvasWidget {
     }
     
     /// Update the node canvas
-    pub fn update_node_canvas(&mut self, node_canvas: NodeCanvas) {
+    pub fn update_node_canvas(&mut self, mut node_canvas: NodeCanvas) {
+        node_canvas.rebuild_spatial_index();
         self.state.node_canvas = Arc::new(node_canvas);
     }
     
+    /// Enable or disable the data flow simulation mode
+    pub fn toggle_simulation(&mut self) {
+        let canvas = Arc::make_mut(&mut self.state.node_canvas);
+        match self.state.simulator.take() {
+            Some(mut simulator) => {
+                simulator.reset(canvas);
+                self.state.last_snapshot = None;
+            }
+            None => self.state.simulator = Some(FlowSimulator::new(FlowSimulationConfig::default())),
+        }
+    }
+    
+    /// Check whether the simulation mode is active
+    pub fn is_simulating(&self) -> bool {
+        self.state.simulator.is_some()
+    }
+    
+    /// Advance the simulation by one tick (does nothing outside the simulation mode)
+    pub fn step_simulation(&mut self, cx: &mut ViewContext) {
+        if let Some(simulator) = &mut self.state.simulator {
+            let canvas = Arc::make_mut(&mut self.state.node_canvas);
+            self.state.last_snapshot = Some(simulator.step(canvas));
+            cx.request_paint();
+        }
+    }
+    
+    /// Show git change markers on the nodes whose implementation files changed
+    pub fn set_change_markers(&mut self, markers: Vec<ChangeMarker>) {
+        self.state.change_markers = markers;
+    }
+    
+    /// Get the latest simulation snapshot
+    pub fn last_simulation_snapshot(&self) -> Option<&FlowSnapshot> {
+        self.state.last_snapshot.as_ref()
+    }
+    
     /// Add a component to the canvas at the specified position
     pub fn add_component(&mut self, component: &Component, position: Point) -> Result<(), crate::component_manager::ComponentManagerError> {
-        let node = VisualNode::new(component.clone(), position)?;
+        let node = self.state.node_canvas.create_node(component.clone(), position.into())?;
         let mut canvas = Arc::try_unwrap(self.state.node_canvas.clone())
             .map_err(|_| crate::component_manager::ComponentManagerError::VisualNodeError("Failed to unwrap node canvas"))?;
         
@@ -145,10 +191,12 @@ impl CanvasWidget {
             // Drag selected nodes
             let mut canvas = Arc::try_unwrap(self.state.node_canvas.clone()).unwrap();
             
-            for node_id in &canvas.selected_nodes {
+            let selected: Vec<String> = canvas.selected_nodes.iter().cloned().collect();
+            for node_id in &selected {
                 if let Some(node) = canvas.nodes.get_mut(node_id) {
-                    node.position += delta;
+                    node.position = canvas_geometry::Point::new(node.position.x + delta.x, node.position.y + delta.y);
                 }
+                canvas.refresh_node_bounds(node_id);
             }
             
             self.state.node_canvas = Arc::new(canvas);
@@ -183,13 +231,13 @@ impl CanvasWidget {
                 if let Some((end_node, end_port)) = self.find_port_at_point(mouse_pos) {
                     // Create connection
                     let connection = NodeConnection {
-                        id: format!("conn_{}_{}", uuid::Uuid::new_v4(), uuid::Uuid::new_v4()),
+                        id: canvas.connection_id(&start_node, &start_port, &end_node, &end_port),
                         from_node: start_node.clone(),
                         from_port: start_port.clone(),
                         to_node: end_node.clone(),
                         to_port: end_port.clone(),
                         connection_type: "default".to_string(),
-                        color: Color::from_rgba8(0, 0, 0, 255),
+                        color: canvas_geometry::Color::from_rgba8(0, 0, 0, 255),
                         line_width: 2.0,
                         description: format!("Connection from {}:{} to {}:{}", start_node, start_port, end_node, end_port),
                     };
@@ -227,22 +275,16 @@ impl CanvasWidget {
     /// Find a node at the specified point
     fn find_node_at_point(&self, point: Point) -> Option<&VisualNode> {
         // Apply zoom and pan transformation
-        let transformed_point = Point::new(
+        let transformed_point = canvas_geometry::Point::new(
             (point.x - self.state.node_canvas.pan_offset.0) / self.state.node_canvas.zoom,
             (point.y - self.state.node_canvas.pan_offset.1) / self.state.node_canvas.zoom
         );
         
-        // Check nodes in reverse z-index order
-        let mut nodes: Vec<&VisualNode> = self.state.node_canvas.nodes.values().collect();
-        nodes.sort_by_key(|node| -node.z_index);
-        
-        for node in nodes {
-            if node.contains_point(transformed_point) {
-                return Some(node);
-            }
-        }
-        
-        None
+        // Check the nodes under the point in reverse z-index order
+        self.state.node_canvas.nodes_in_viewport(canvas_geometry::Rect::new(transformed_point, (0.0, 0.0)))
+            .into_iter()
+            .rev()
+            .find(|node| node.contains_point(transformed_point))
     }
     
     /// Find a port at the specified point
@@ -308,11 +350,14 @@ impl Widget for CanvasWidget {
         // Draw grid
         self.draw_grid(bounds, cx);
         
+        // Only the part of the canvas under the widget is drawn
+        let visible = self.state.node_canvas.visible_region(bounds.into());
+        
         // Draw connections
-        self.draw_connections(cx);
+        self.draw_connections(visible, cx);
         
         // Draw nodes
-        self.draw_nodes(cx);
+        self.draw_nodes(visible, cx);
         
         // Draw temporary connection if connecting
         if let Some((start_node, start_port)) = (
@@ -374,9 +419,9 @@ impl CanvasWidget {
         }
     }
     
-    /// Draw connections on the canvas
-    fn draw_connections(&self, cx: &mut PaintContext) {
-        for connection in self.state.node_canvas.connections.values() {
+    /// Draw the connections crossing the visible canvas region
+    fn draw_connections(&self, visible: canvas_geometry::Rect, cx: &mut PaintContext) {
+        for connection in self.state.node_canvas.connections_in_viewport(visible) {
             // Get from and to nodes
             if let (Some(from_node), Some(to_node)) = (
                 self.state.node_canvas.nodes.get(&connection.from_node),
@@ -410,19 +455,41 @@ impl CanvasWidget {
                     );
                     
                     // Draw connection line
-                    cx.draw_line(from_pos, to_pos, connection.color, connection.line_width);
+                    cx.draw_line(from_pos, to_pos, connection.color.into(), connection.line_width);
+                    
+                    // Draw simulated data flow: a moving dot and the current rate
+                    let info = &connection.data_flow_info;
+                    if connection.show_data_flow && info.is_active {
+                        let tick = self.state.last_snapshot.as_ref().map_or(0, |snapshot| snapshot.tick);
+                        let phase = (tick as f64 * (0.05 + connection.animation_speed * 0.2)).fract();
+                        let dot = Point::new(
+                            from_pos.x + (to_pos.x - from_pos.x) * phase,
+                            from_pos.y + (to_pos.y - from_pos.y) * phase,
+                        );
+                        let color = if connection.is_highlighted {
+                            Color::from_rgba8(220, 40, 40, 255)
+                        } else {
+                            Color::from_rgba8(30, 120, 220, 255)
+                        };
+                        cx.fill_circle(dot, 4.0, color);
+                        
+                        let rate = info.flow_rate.map(format_rate).unwrap_or_default();
+                        cx.draw_text(
+                            &rate,
+                            Point::new((from_pos.x + to_pos.x) / 2.0 + 6.0, (from_pos.y + to_pos.y) / 2.0 - 6.0),
+                            color,
+                            10.0,
+                        );
+                    }
                 }
             }
         }
     }
     
-    /// Draw nodes on the canvas
-    fn draw_nodes(&self, cx: &mut PaintContext) {
+    /// Draw the nodes in the visible canvas region
+    fn draw_nodes(&self, visible: canvas_geometry::Rect, cx: &mut PaintContext) {
         // Draw nodes in z-index order
-        let mut nodes: Vec<&VisualNode> = self.state.node_canvas.nodes.values().collect();
-        nodes.sort_by_key(|node| node.z_index);
-        
-        for node in nodes {
+        for node in self.state.node_canvas.nodes_in_viewport(visible) {
             // Calculate absolute position and size with zoom and pan
             let x = node.position.x * self.state.node_canvas.zoom + self.state.node_canvas.pan_offset.0;
             let y = node.position.y * self.state.node_canvas.zoom + self.state.node_canvas.pan_offset.1;
@@ -432,10 +499,10 @@ impl CanvasWidget {
             let node_rect = Rect::new(Point::new(x, y), (width, height));
             
             // Draw node background
-            cx.fill(node_rect, node.style.background_color);
+            cx.fill(node_rect, node.style.background_color.into());
             
             // Draw node border
-            cx.stroke(node_rect, node.style.border_color, node.style.border_width);
+            cx.stroke(node_rect, node.style.border_color.into(), node.style.border_width);
             
             // Draw node title
             let title_y = y + 20.0; // Adjust based on node style
@@ -446,6 +513,16 @@ impl CanvasWidget {
                 14.0,
             );
             
+            // Draw the change marker in the top right corner
+            if let Some(marker) = self.state.change_markers.iter().find(|marker| marker.node_id == node.id) {
+                cx.draw_text(
+                    &marker.kind.letter().to_string(),
+                    Point::new(x + width - 16.0, title_y),
+                    Color::from_rgba8(200, 120, 0, 255),
+                    14.0,
+                );
+            }
+            
             // Draw ports
             self.draw_ports(node, x, y, cx);
         }
@@ -524,6 +601,22 @@ impl CanvasWidget {
                 cx.request_layout();
                 cx.request_paint();
             },
+            gpui::Key::Z if key_event.modifiers.contains(gpui::Modifier::Ctrl) => {
+                // Undo the last canvas operation
+                let canvas = Arc::make_mut(&mut self.state.node_canvas);
+                if canvas.undo().unwrap_or(false) {
+                    cx.request_layout();
+                    cx.request_paint();
+                }
+            },
+            gpui::Key::Y if key_event.modifiers.contains(gpui::Modifier::Ctrl) => {
+                // Redo the last undone canvas operation
+                let canvas = Arc::make_mut(&mut self.state.node_canvas);
+                if canvas.redo().unwrap_or(false) {
+                    cx.request_layout();
+                    cx.request_paint();
+                }
+            },
             gpui::Key::Escape => {
                 // Cancel current operation
                 let mut canvas = Arc::try_unwrap(self.state.node_canvas.clone()).unwrap();
@@ -536,3 +629,12 @@ impl CanvasWidget {
         }
     }
 }
+
+/// Format a byte rate for connection labels
+fn format_rate(bytes_per_second: u64) -> String {
+    match bytes_per_second {
+        rate if rate >= 1_000_000 => format!("{:.1} MB/s", rate as f64 / 1_000_000.0),
+        rate if rate >= 1_000 => format!("{:.1} KB/s", rate as f64 / 1_000.0),
+        rate => format!("{} B/s", rate),
+    }
+}
@@ -3,7 +3,12 @@
 // SPDX-License-Identifier: MulanPSL-2.0
 
 use gpui::{Widget, View, ViewContext, RenderContext, LayoutContext, EventContext, Color, Rect, Point, BoxConstraints, Label, Panel, Split};
-use crate::dashboard::{DashboardPanel, ProjectManager, GlobalSearchSystem, ComponentMonitor};
+use std::path::Path;
+use std::sync::Arc;
+use crate::core::event_bus::EventTopic;
+use crate::dashboard::{DashboardPanel, ProjectManager, GlobalSearchSystem, ComponentMonitor, InteropStatsPanel};
+use crate::dbos_integration::dbos_core::TablesManager;
+use crate::runtime::call_tracing::global_tracer;
 
 /// Dashboard integration widget
 pub struct DashboardIntegration {
@@ -19,9 +24,15 @@ pub struct DashboardIntegration {
     /// Component monitor
     component_monitor: ComponentMonitor,
     
+    /// Cross-language call latency of the interop services
+    interop_stats: InteropStatsPanel,
+    
     /// Current active view
     active_view: DashboardView,
     
+    /// Tables the dashboard widgets read their data from
+    tables: Option<Arc<TablesManager>>,
+    
     /// Main UI panel
     main_panel: Panel,
 }
@@ -33,6 +44,7 @@ pub enum DashboardView {
     ProjectManager,
     SearchSystem,
     ComponentMonitor,
+    InteropStats,
 }
 
 impl DashboardIntegration {
@@ -43,7 +55,9 @@ impl DashboardIntegration {
             project_manager: ProjectManager::new(),
             search_system: GlobalSearchSystem::new(),
             component_monitor: ComponentMonitor::new(),
+            interop_stats: InteropStatsPanel::new(global_tracer()),
             active_view: DashboardView::Dashboard,
+            tables: None,
             main_panel: Panel::new(),
         }
     }
@@ -68,6 +82,11 @@ impl DashboardIntegration {
         self.active_view = DashboardView::ComponentMonitor;
     }
     
+    /// Switch to interop statistics view
+    pub fn show_interop_stats(&mut self) {
+        self.active_view = DashboardView::InteropStats;
+    }
+    
     /// Update system information in dashboard
     pub fn update_system_info(&mut self, info: crate::dashboard::dashboard_panel::SystemInfo) {
         self.dashboard_panel.update_system_info(info);
@@ -88,6 +107,31 @@ impl DashboardIntegration {
         self.component_monitor.update_component_status(status);
     }
     
+    /// Start the health probes of the monitored components
+    pub fn start_health_checks(&mut self, tables: Arc<TablesManager>) {
+        self.tables = Some(tables.clone());
+        self.component_monitor.start_health_checks(tables);
+    }
+    
+    /// Show the dashboard layout saved in a project
+    pub fn load_dashboard_layout(&mut self, project_dir: &Path) {
+        if let Err(e) = self.dashboard_panel.load_layout(project_dir) {
+            log::warn!("Failed to load the dashboard layout of {}: {}", project_dir.display(), e);
+        }
+    }
+    
+    /// Refresh the dashboard widgets that are due, given the topics published since the last call
+    pub fn refresh_widgets(&mut self, events: &[EventTopic]) {
+        if let Some(tables) = &self.tables {
+            self.dashboard_panel.refresh_widgets(tables, events);
+        }
+    }
+    
+    /// Take finished health checks, showing fired alerts in the component monitor
+    pub fn poll_health(&mut self) -> usize {
+        self.component_monitor.poll_health().len()
+    }
+    
     /// Set search query
     pub fn set_search_query(&mut self, query: String) {
         self.search_system.set_search_query(query);
@@ -122,6 +166,10 @@ impl DashboardIntegration {
                 self.component_monitor.refresh(cx);
                 self.main_panel.set_content(self.component_monitor.clone());
             },
+            DashboardView::InteropStats => {
+                self.interop_stats.refresh(cx);
+                self.main_panel.set_content(self.interop_stats.clone());
+            },
         }
     }
     
@@ -7,7 +7,7 @@ use std::sync::Arc;
 use crate::component_manager::{component::ComponentLibrary, visual_node::NodeCanvas};
 use crate::core::architecture::KernelArchitecture;
 use crate::core::config::AppConfig;
-use super::abstraction::{UiApplication, MainWindow, CanvasWidget, CanvasTool, EventContext as AbstractionEventContext};
+use super::abstraction::{UiApplication, MainWindow, CanvasWidget, CanvasTool, LayoutDirection, EventContext as AbstractionEventContext};
 use super::canvas::CanvasWidget as GpuiCanvasWidget;
 use super::dashboard_integration::DashboardIntegration;
 use super::unified_resource_panel::UnifiedResourcePanel;
@@ -101,6 +101,10 @@ impl MainWindow for GpuiMainWindow {
     fn show_kernel_visualization(&mut self) {
         // Implement show_kernel_visualization method
     }
+    
+    fn set_layout_direction(&mut self, direction: LayoutDirection) {
+        self.inner.set_layout_direction(direction);
+    }
 }
 
 /// GPUI Canvas Widget Implementation
@@ -15,6 +15,7 @@ use druid::{Selector, Target};
 use crate::kernel_visualization::{KernelVisualizationController, KernelStructure, VisualizationSettings, LayoutAlgorithm, HierarchicalLayout, ForceDirectedLayout, RadialLayout, VisualizationEvent, VisualizationEventHandler};
 use crate::kernel_visualization::interactive_canvas::{InteractiveCanvasWidget, CanvasTool};
 use crate::kernel_visualization::architecture_viewer::{ArchitectureViewConfig, Architecture};
+use crate::kernel_visualization::boot_log::{correlate_failures, BootLogStore};
 
 /// Kernel visualization panel state
 #[derive(Clone, Data)]
@@ -202,6 +203,24 @@ impl KernelVisualizationPanel {
         controller.reset_view();
         self.state.zoom_level = 1.0;
     }
+    
+    /// Highlight the components that failed while booting the project's test runs
+    pub fn show_boot_failures(&mut self, store: &BootLogStore, project: &str) {
+        let failures = match store.failures(project) {
+            Ok(failures) => failures,
+            Err(e) => {
+                self.state.analysis_status = format!("Failed to load boot logs: {}", e);
+                return;
+            }
+        };
+        
+        let mut controller = self.controller.borrow_mut();
+        let structure = controller.get_kernel_structure();
+        let correlations = correlate_failures(&failures, structure.as_ref(), None);
+        let highlighted = correlations.iter().filter(|correlation| !correlation.components.is_empty()).count();
+        controller.show_boot_failures(correlations);
+        self.state.analysis_status = format!("{} boot failures, {} correlated with components", failures.len(), highlighted);
+    }
 }
 
 impl Widget<KernelVisualizationState> for KernelVisualizationPanel {
@@ -3,10 +3,15 @@
 // SPDX-License-Identifier: MulanPSL-2.0
 
 use gpui::{Widget, View, ViewContext, RenderContext, LayoutContext, EventContext, Color, Rect, Point, BoxConstraints, TextEdit, Split, Toolbar, MenuBar, Button, Label, ScrollView, Panel};
-use std::sync::Arc;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::sync::mpsc::Receiver;
 use crate::component_manager::{component::ComponentLibrary, visual_node::NodeCanvas};
 use crate::core::architecture::KernelArchitecture;
+use crate::core::audit::{self, AuditCategory};
 use crate::core::config::AppConfig;
+use crate::core::event_bus::{BuildEvent, EventBus, EventFilter, EventTopic, SystemEvent};
+use super::abstraction::LayoutDirection;
 use super::canvas::CanvasWidget;
 use super::dashboard_integration::DashboardIntegration;
 use super::unified_resource_panel::UnifiedResourcePanel;
@@ -16,6 +21,12 @@ use super::tile_designer_panel::TileDesignerPanel;
 use super::kernel_visualization_panel::KernelVisualizationPanel;
 use crate::dbos_integration::UnifiedResourceManager;
 use crate::kernel_visualization::KernelVisualizationController;
+use crate::kernel_visualization::boot_log::BootLogStore;
+use crate::kernel_extractor::ComponentDatabase;
+use crate::git;
+use crate::kernel_extractor::extractor::COMPONENT_DATABASE_FILE;
+use crate::runtime::{RuntimeManager, RUNTIME_PLUGIN_DIR};
+use crate::os_design::{create_boot_flow_canvas, BootFlow};
 
 /// Main window state
 pub struct MainWindowState {
@@ -24,6 +35,7 @@ pub struct MainWindowState {
     architecture: KernelArchitecture,
     current_project_path: Option<String>,
     status_message: String,
+    layout_direction: LayoutDirection,
 }
 
 /// Main window widget
@@ -47,8 +59,18 @@ pub struct MainWindow {
     tile_designer_panel: TileDesignerPanel,
     // Add kernel visualization panel
     kernel_visualization_panel: Option<KernelVisualizationPanel>,
+    // Boot flow designer canvas, created when first shown
+    boot_flow_canvas: Option<CanvasWidget>,
     // Add kernel visualization controller
     kernel_visualization_controller: Option<KernelVisualizationController>,
+    // Boot logs of the test runs, highlighted in the kernel visualization
+    boot_logs: Option<BootLogStore>,
+    // Extracted component database (security findings, provenance, ...)
+    component_database: Option<Arc<ComponentDatabase>>,
+    // Build, extraction and collaboration events shown in the status bar
+    status_events: Receiver<SystemEvent>,
+    // Language runtimes, including the plugins of the current directory and project
+    runtime_manager: Arc<Mutex<RuntimeManager>>,
 }
 
 impl MainWindow {
@@ -60,6 +82,17 @@ impl MainWindow {
         let dbos_config = crate::dbos_integration::DbosConfig::default();
         let agfs_config = crate::agfs_integration::AgfsConfig::default();
         let unified_resource_manager = Arc::new(UnifiedResourceManager::new(dbos_config, agfs_config));
+        if let Err(e) = unified_resource_manager.discover_topology() {
+            log::warn!("Failed to discover the CPU topology: {}", e);
+        }
+        match unified_resource_manager.discover_gpus() {
+            Ok(report) => {
+                for (backend, note) in &report.notes {
+                    log::debug!("No {} GPU: {}", backend.name(), note);
+                }
+            }
+            Err(e) => log::warn!("Failed to discover GPUs: {}", e),
+        }
         
         // Get time travel engine from DBOS system
         let time_travel_engine = unified_resource_manager.get_dbos_system().get_time_travel_engine();
@@ -68,10 +101,23 @@ impl MainWindow {
         let command_interface = unified_resource_manager.get_agfs_system().get_command_interface();
         
         // Create tile designer
-        let tile_designer = TileDesignerPanel::new(
+        let mut tile_designer = TileDesignerPanel::new(
             Arc::new(crate::tile_engine::tile_designer::TileDesigner::new("Main Design".to_string())),
             Arc::new(std::sync::RwLock::new(crate::tile_engine::tile_library::TileLibrary::create_standard_library()))
         );
+        tile_designer.set_resource_manager(unified_resource_manager.clone());
+        
+        let mut dashboard_integration = DashboardIntegration::new();
+        dashboard_integration.start_health_checks(unified_resource_manager.get_dbos_system().get_tables_manager());
+        
+        let boot_logs = BootLogStore::new(unified_resource_manager.get_dbos_system().get_tables_manager())
+            .map_err(|e| log::warn!("Boot failures are not shown in the kernel visualization: {}", e))
+            .ok();
+        
+        let runtime_manager = Arc::new(Mutex::new(RuntimeManager::default()));
+        if let Ok(dir) = std::env::current_dir() {
+            load_runtime_plugins(&runtime_manager, &dir);
+        }
         
         Self {
             state: MainWindowState {
@@ -80,6 +126,7 @@ impl MainWindow {
                 architecture,
                 current_project_path: None,
                 status_message: "Ready".to_string(),
+                layout_direction: LayoutDirection::default(),
             },
             canvas_widget,
             component_panel: Panel::new(),
@@ -88,7 +135,7 @@ impl MainWindow {
             menu_bar: MenuBar::new(),
             status_bar: Label::new("Ready"),
             // Add dashboard integration
-            dashboard_integration: DashboardIntegration::new(),
+            dashboard_integration,
             // Add unified resource panel
             unified_resource_panel: UnifiedResourcePanel::new(unified_resource_manager),
             // Add time travel panel
@@ -99,15 +146,110 @@ impl MainWindow {
             tile_designer_panel: tile_designer,
             // Add kernel visualization panel
             kernel_visualization_panel: None,
+            boot_flow_canvas: None,
             // Add kernel visualization controller
             kernel_visualization_controller: None,
+            boot_logs,
+            component_database: None,
+            status_events: EventBus::global().subscribe_channel(
+                EventFilter::topic(EventTopic::Build)
+                    .with_topic(EventTopic::Extraction)
+                    .with_topic(EventTopic::Collaboration)
+            ).1,
+            runtime_manager,
         }
     }
     
+    /// Get the runtime manager with the loaded runtime plugins
+    pub fn runtime_manager(&self) -> Arc<Mutex<RuntimeManager>> {
+        self.runtime_manager.clone()
+    }
+    
+    /// Show pending engine events in the status bar
+    fn drain_status_events(&mut self) {
+        let mut latest = None;
+        let mut topics = Vec::new();
+        while let Ok(event) = self.status_events.try_recv() {
+            if !topics.contains(&event.topic()) {
+                topics.push(event.topic());
+            }
+            let message = match event {
+                SystemEvent::Build(BuildEvent::Started { project }) => format!("Building {}...", project),
+                SystemEvent::Build(BuildEvent::Progress { status, percentage, .. }) => format!("{} ({}%)", status, percentage),
+                SystemEvent::Build(BuildEvent::Finished { message, .. }) => message,
+                SystemEvent::Extraction(progress) => format!(
+                    "Extracting {}: {} ({}/{})", progress.kernel, progress.stage, progress.completed, progress.total
+                ),
+                SystemEvent::Collaboration(op) => format!("{} applied {}", op.user_id, op.operation),
+                _ => continue,
+            };
+            latest = Some(message);
+        }
+        
+        if let Some(message) = latest {
+            self.update_status_message(message);
+        }
+        self.dashboard_integration.refresh_widgets(&topics);
+    }
+    
+    /// Set the extracted component database shown in the property panel
+    pub fn set_component_database(&mut self, database: Option<Arc<ComponentDatabase>>) {
+        self.component_database = database;
+    }
+    
     /// Set the current project path
+    ///
+    /// Loads the component database written by the last extraction into the project
+    /// directory, so the property panel shows findings and provenance of its components,
+    /// the dashboard layout and the runtime plugins saved in it.
     pub fn set_current_project(&mut self, path: Option<String>) {
-        self.state.current_project_path = path;
+        let database = path.as_ref()
+            .map(|path| std::path::Path::new(path).join(COMPONENT_DATABASE_FILE))
+            .filter(|file| file.exists())
+            .and_then(|file| match ComponentDatabase::load(&file) {
+                Ok(database) => Some(Arc::new(database)),
+                Err(e) => {
+                    log::warn!("Failed to load component database {}: {}", file.display(), e);
+                    None
+                }
+            });
+        self.set_component_database(database);
+        if let Some(path) = &path {
+            self.dashboard_integration.load_dashboard_layout(std::path::Path::new(path));
+            load_runtime_plugins(&self.runtime_manager, Path::new(path));
+        }
         self.update_status_message(format!("Project: {}", path.as_deref().unwrap_or("No project open")));
+        self.state.current_project_path = path;
+        self.refresh_change_markers();
+    }
+    
+    /// Mark the canvas nodes whose implementation files have uncommitted changes
+    ///
+    /// Projects outside a git repository get no markers.
+    pub fn refresh_change_markers(&mut self) {
+        let markers = self.state.current_project_path.as_ref()
+            .and_then(|path| {
+                let project_dir = std::path::Path::new(path);
+                let repository = git::GitRepository::open(project_dir).ok()?;
+                match repository.status() {
+                    Ok(statuses) => Some(git::change_markers(&self.canvas_widget.get_node_canvas(), &statuses, repository.root(), project_dir)),
+                    Err(e) => {
+                        log::warn!("Failed to read the git status of {}: {}", path, e);
+                        None
+                    }
+                }
+            })
+            .unwrap_or_default();
+        self.canvas_widget.set_change_markers(markers);
+    }
+    
+    /// Set the layout direction, mirroring the side panels for right-to-left languages
+    pub fn set_layout_direction(&mut self, direction: LayoutDirection) {
+        if self.state.layout_direction != direction {
+            audit::record(AuditCategory::Settings, "settings.layout_direction", "main window",
+                Some(format!("{:?}", self.state.layout_direction.text_direction)), Some(format!("{:?}", direction.text_direction)));
+        }
+        self.state.layout_direction = direction;
     }
     
     /// Update status message
@@ -183,10 +325,16 @@ impl MainWindow {
         dashboard_menu.add_item("Global Search", || {
             self.dashboard_integration.show_search_system();
         });
+        dashboard_menu.add_item("Interop Statistics", || {
+            self.dashboard_integration.show_interop_stats();
+        });
         dashboard_menu.add_separator();
         dashboard_menu.add_item("Kernel Structure Visualization", move |cx| {
             self.show_kernel_visualization(cx);
         });
+        dashboard_menu.add_item("Boot Flow Designer", || {
+            self.show_boot_flow();
+        });
         
         // Tools menu
         let tools_menu = self.menu_bar.add_menu("Tools");
@@ -245,6 +393,15 @@ impl MainWindow {
         self.toolbar.add_separator();
         self.toolbar.add_button("Build", || {});
         self.toolbar.add_button("Run", || {});
+        self.toolbar.add_button("Simulate Flow", move |cx| {
+            self.canvas_widget.toggle_simulation();
+            // Show the first tick right away
+            self.canvas_widget.step_simulation(cx);
+            cx.request_paint();
+        });
+        self.toolbar.add_button("Step Flow", move |cx| {
+            self.canvas_widget.step_simulation(cx);
+        });
         
         // Tile operations
         self.toolbar.add_separator();
@@ -281,12 +438,64 @@ impl MainWindow {
                 });
                 
                 scroll_view.add(button);
+                
+                let name = component.name.clone();
+                let details_button = Button::new("Details", move |cx| {
+                    self.show_component_details(&name, cx);
+                });
+                scroll_view.add(details_button);
             }
         }
         
         self.component_panel.set_content(scroll_view);
     }
     
+    /// Show the generated documentation of a component in the component panel
+    pub fn show_component_details(&mut self, component_name: &str, cx: &mut ViewContext) {
+        let scroll_view = ScrollView::new();
+        
+        let back_button = Button::new("< Components", move |cx| {
+            self.init_component_panel(cx);
+            cx.request_layout();
+        });
+        scroll_view.add(back_button);
+        
+        let markdown = self.component_database.as_ref()
+            .and_then(|database| database.get_component(component_name).ok().flatten())
+            .and_then(|component| component.metadata["docs"]["markdown"].as_str().map(String::from));
+        
+        match markdown {
+            Some(markdown) => {
+                // Render the Markdown line by line, dropping the markup
+                let mut in_code = false;
+                for line in markdown.lines() {
+                    if line.starts_with("```") {
+                        in_code = !in_code;
+                        continue;
+                    }
+                    if line.trim().is_empty() {
+                        continue;
+                    }
+                    
+                    let text = if in_code {
+                        format!("    {}", line)
+                    } else {
+                        line.trim_start_matches('#').trim().replace("**", "").replace('`', "")
+                    };
+                    scroll_view.add(Label::new(&text));
+                }
+            }
+            None => {
+                let label = Label::new(&format!("No documentation available for {}", component_name));
+                scroll_view.add(label);
+            }
+        }
+        
+        self.component_panel.set_content(scroll_view);
+        cx.request_layout();
+        cx.request_paint();
+    }
+    
     /// Initialize property panel
     fn init_property_panel(&mut self, cx: &mut ViewContext) {
         // Create property panel with scroll view
@@ -323,6 +532,30 @@ impl MainWindow {
             
             scroll_view.add(position_label);
             scroll_view.add(position_edit);
+            
+            // Add security findings of the extracted component
+            let extracted = self.component_database.as_ref()
+                .and_then(|database| database.get_component(&node.component.name).ok().flatten());
+            if let Some(findings) = extracted.as_ref().and_then(|component| component.metadata["security"]["findings"].as_array()) {
+                let findings_label = Label::new(&format!("Security findings: {}", findings.len()));
+                scroll_view.add(findings_label);
+                
+                for finding in findings {
+                    let file = std::path::Path::new(finding["file"].as_str().unwrap_or_default())
+                        .file_name()
+                        .map(|name| name.to_string_lossy().to_string())
+                        .unwrap_or_default();
+                    let finding_label = Label::new(&format!(
+                        "[{}] {}:{} {} - {}",
+                        finding["severity"].as_str().unwrap_or("Note"),
+                        file,
+                        finding["line"],
+                        finding["rule_id"].as_str().unwrap_or_default(),
+                        finding["message"].as_str().unwrap_or_default(),
+                    ));
+                    scroll_view.add(finding_label);
+                }
+            }
         } else {
             // No node selected
             let default_label = Label::new("Select a component to view properties");
@@ -361,18 +594,33 @@ impl Widget for MainWindow {
         let content_height = total_height - menu_height - toolbar_height - status_height;
         let content_rect = Rect::new(Point::new(0.0, menu_height + toolbar_height), (available_size.width, content_height));
         
-        // Create main content split (component panel | canvas | property panel)
-        let main_split = Split::new(
+        // Create main content split (component panel | canvas | property panel),
+        // mirrored to (property panel | canvas | component panel) in RTL layouts
+        let main_split = if self.state.layout_direction.is_mirrored() {
             Split::new(
-                self.component_panel,
-                self.canvas_widget,
-                200.0, // Initial size of component panel
+                self.property_panel,
+                Split::new(
+                    self.canvas_widget,
+                    self.component_panel,
+                    200.0, // Initial size of component panel
+                    false  // Vertical split
+                ),
+                300.0, // Initial size of property panel
                 false  // Vertical split
-            ),
-            self.property_panel,
-            300.0, // Initial size of property panel
-            false  // Vertical split
-        );
+            )
+        } else {
+            Split::new(
+                Split::new(
+                    self.component_panel,
+                    self.canvas_widget,
+                    200.0, // Initial size of component panel
+                    false  // Vertical split
+                ),
+                self.property_panel,
+                300.0, // Initial size of property panel
+                false  // Vertical split
+            )
+        };
         
         // Layout main content
         main_split.layout(BoxConstraints::tight(content_rect.size()), cx);
@@ -400,6 +648,11 @@ impl Widget for MainWindow {
         if let Some(panel) = &mut self.kernel_visualization_panel {
             panel.paint(cx);
         }
+        
+        // Paint boot flow designer
+        if let Some(canvas) = &mut self.boot_flow_canvas {
+            canvas.paint(cx);
+        }
     }
     
     fn handle_event(&mut self, event: &gpui::Event, cx: &mut EventContext) {
@@ -409,8 +662,17 @@ impl Widget for MainWindow {
         self.canvas_widget.handle_event(event, cx);
         self.component_panel.handle_event(event, cx);
         self.property_panel.handle_event(event, cx);
+        if let Some(canvas) = &mut self.boot_flow_canvas {
+            canvas.handle_event(event, cx);
+        }
         // Handle dashboard integration events
         self.dashboard_integration.handle_event(event, cx);
+        self.drain_status_events();
+        self.unified_resource_panel.process_events(cx);
+        let alerts = self.dashboard_integration.poll_health();
+        if alerts > 0 {
+            self.update_status_message(format!("{} health alert(s)", alerts));
+        }
         
         match event {
             gpui::Event::MouseDown(mouse_event) => {
@@ -461,13 +723,17 @@ impl MainWindow {
     
     /// Save project
     fn save_project(&mut self) {
-        // TODO: Implement save project
-        if let Some(path) = &self.state.current_project_path {
+        if let Some(path) = self.state.current_project_path.clone() {
             self.update_status_message(format!("Saving project to {}", path));
             // Save project file
             let node_canvas = self.canvas_widget.get_node_canvas();
-            // TODO: Implement project serialization and saving
-            self.update_status_message(format!("Project saved to {}", path));
+            match node_canvas.save_to_file(&path) {
+                Ok(()) => {
+                    self.update_status_message(format!("Project saved to {}", path));
+                    self.refresh_change_markers();
+                }
+                Err(e) => self.update_status_message(format!("Failed to save project: {}", e)),
+            }
         } else {
             self.update_status_message("No project open to save".to_string());
         }
@@ -483,6 +749,27 @@ impl MainWindow {
         self.update_status_message("Build completed".to_string());
     }
     
+    /// Show the boot flow designer, starting from every boot stage in boot order
+    fn show_boot_flow(&mut self) {
+        if self.boot_flow_canvas.is_none() {
+            let mut canvas = CanvasWidget::new(self.state.component_library.clone(), self.state.architecture.clone());
+            match create_boot_flow_canvas() {
+                Ok(boot_flow) => canvas.update_node_canvas(boot_flow),
+                Err(e) => {
+                    self.update_status_message(format!("Failed to create the boot flow: {}", e));
+                    return;
+                }
+            }
+            self.boot_flow_canvas = Some(canvas);
+        }
+        self.update_status_message("Boot flow designer displayed".to_string());
+    }
+    
+    /// Get the boot flow designed in the boot flow designer, if it has been shown
+    pub fn boot_flow(&self) -> Option<Result<BootFlow, String>> {
+        self.boot_flow_canvas.as_ref().map(|canvas| BootFlow::from_canvas(&canvas.get_node_canvas()))
+    }
+    
     /// Show kernel visualization panel
     fn show_kernel_visualization(&mut self, cx: &mut ViewContext) {
         // Initialize kernel visualization if not already done
@@ -506,9 +793,30 @@ impl MainWindow {
         
         // Show the panel
         if let Some(panel) = &mut self.kernel_visualization_panel {
+            // Boot logs are looked up under the project directory's name
+            let project = self.state.current_project_path.as_deref()
+                .and_then(|path| std::path::Path::new(path).file_name())
+                .map(|name| name.to_string_lossy().into_owned());
+            if let (Some(store), Some(project)) = (&self.boot_logs, project) {
+                panel.show_boot_failures(store, &project);
+            }
+            
             // This would involve showing the panel in the UI layout
             // For now, we'll just update the status message
             self.update_status_message("Kernel visualization panel displayed".to_string());
         }
     }
 }
+
+/// Register the runtime plugins of a directory's `.osland/runtimes`
+fn load_runtime_plugins(runtime_manager: &Mutex<RuntimeManager>, dir: &Path) {
+    let mut runtime_manager = runtime_manager.lock().unwrap();
+    match runtime_manager.load_plugins(&dir.join(RUNTIME_PLUGIN_DIR)) {
+        Ok(languages) if !languages.is_empty() => {
+            let names: Vec<&str> = languages.iter().map(|language| language.as_str()).collect();
+            log::info!("Loaded runtime plugins from {}: {}", dir.display(), names.join(", "));
+        }
+        Ok(_) => {}
+        Err(e) => log::warn!("Failed to load the runtime plugins of {}: {}", dir.display(), e),
+    }
+}
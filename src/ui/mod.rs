@@ -29,16 +29,17 @@ pub use command_line_panel::CommandLinePanel;
 pub use tile_designer_panel::TileDesignerPanel;
 pub use kernel_visualization_panel::KernelVisualizationPanel;
 
-// Run the OSland IDE with the specified framework
-pub fn run_ide(framework: abstraction::UiFramework) -> Result<(), abstraction::UIError> {
+// Run the OSland IDE with the specified framework, laid out for the given language
+pub fn run_ide(framework: abstraction::UiFramework, language: crate::i18n::Language) -> Result<(), abstraction::UIError> {
     // Create UI application using factory
     let mut app = abstraction::UiFactory::create_application(framework)?;
     
     // Create main window
     let config = crate::core::config::AppConfig::default();
-    let component_library = std::sync::Arc::new(crate::component_manager::component::ComponentLibrary::default());
+    let component_library = std::sync::Arc::new(create_component_library());
     let architecture = crate::core::architecture::KernelArchitecture::default();
     let mut window = app.create_main_window(config, component_library, architecture);
+    window.set_layout_direction(abstraction::LayoutDirection::for_language(language));
     
     // Show the window
     window.show();
@@ -47,9 +48,22 @@ pub fn run_ide(framework: abstraction::UiFramework) -> Result<(), abstraction::U
     app.run()
 }
 
+/// Component library of the IDE: the default components plus the OS design components
+fn create_component_library() -> crate::component_manager::component::ComponentLibrary {
+    let mut library = crate::component_manager::component::ComponentLibrary::default();
+    crate::os_design::extend_with_security_policy_component(&mut library);
+    crate::os_design::extend_with_realtime_components(&mut library);
+    crate::os_design::extend_with_filesystem_components(&mut library);
+    crate::os_design::extend_with_virtualization_components(&mut library);
+    crate::os_design::extend_with_allocator_components(&mut library);
+    crate::component_manager::extend_with_network_components(&mut library);
+    crate::os_design::extend_with_boot_stage_components(&mut library);
+    library
+}
+
 /// Run the OSland IDE with GPUI framework (default)
 pub fn run_ide_with_gpui() -> Result<(), abstraction::UIError> {
-    run_ide(abstraction::UiFramework::Gpui)
+    run_ide(abstraction::UiFramework::Gpui, crate::i18n::Language::system_default())
 }
 
 // UI error types
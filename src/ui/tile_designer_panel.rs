@@ -8,7 +8,9 @@ use crate::tile_engine::{
     tile_core::{Tile, TileGraph, TileType, TilePort, PortType, TileConnection, ConnectionType},
     tile_designer::TileDesigner,
     tile_library::TileLibrary,
+    tile_placement::{format_cpu_list, placement_of},
 };
+use crate::dbos_integration::UnifiedResourceManager;
 
 /// Tile Designer Panel
 pub struct TileDesignerPanel {
@@ -23,6 +25,9 @@ pub struct TileDesignerPanel {
     
     /// View state
     view_state: ViewState,
+    
+    /// Resource manager providing the CPU topology for tile placement
+    resources: Option<Arc<UnifiedResourceManager>>,
 }
 
 /// View State
@@ -64,9 +69,15 @@ impl TileDesignerPanel {
             library,
             selected_tile_id: None,
             view_state: ViewState::default(),
+            resources: None,
         }
     }
     
+    /// Use the CPU topology of a resource manager for the Place action
+    pub fn set_resource_manager(&mut self, resources: Arc<UnifiedResourceManager>) {
+        self.resources = Some(resources);
+    }
+    
     /// Render the tile designer panel
     pub fn render(&self, cx: &mut WindowContext) -> impl IntoElement {
         div()
@@ -103,6 +114,39 @@ impl TileDesignerPanel {
             .child(self.render_tool_button("Zoom In", cx))
             .child(self.render_tool_button("Zoom Out", cx))
             .child(self.render_tool_button("Reset View", cx))
+            .child(div().w_4())
+            .child(self.render_place_button(cx))
+    }
+    
+    /// Render the button storing placement hints on the tiles
+    fn render_place_button(&self, cx: &mut WindowContext) -> impl IntoElement {
+        button()
+            .id("place")
+            .px_3()
+            .py_1()
+            .mr_2()
+            .bg(rgb(0x3d3d3d))
+            .hover(|style| style.bg(rgb(0x4d4d4d)))
+            .active(|style| style.bg(rgb(0x5d5d5d)))
+            .text_size(14.0)
+            .text_color(rgb(0xffffff))
+            .on_click({
+                let designer = self.designer.clone();
+                let resources = self.resources.clone();
+                move |_event, _cx| {
+                    let Some(resources) = &resources else { return };
+                    match resources.annotate_placement(&designer) {
+                        Ok(plan) => {
+                            let cross_node = designer.get_current_graph()
+                                .map(|graph| plan.cross_node_connections(&graph))
+                                .unwrap_or(0);
+                            log::info!("Placed {} tiles, {} connections cross NUMA nodes", plan.hints.len(), cross_node);
+                        }
+                        Err(e) => log::warn!("Failed to place tiles: {}", e),
+                    }
+                }
+            })
+            .child(Label::new("Place"))
     }
     
     /// Render a tool button
@@ -300,6 +344,9 @@ impl TileDesignerPanel {
                     .border_color(rgb(0x3d3d3d))
                     .child(Label::new(&tile.name).font_weight(FontWeight::BOLD))
                     .child(Label::new(format!("{:?}", tile.tile_type)).text_xs().text_color(rgb(0xaaaaaa)))
+                    .children(placement_of(tile).map(|(numa_node, cpus)| {
+                        Label::new(format!("NUMA {} · CPUs {}", numa_node, format_cpu_list(&cpus))).text_xs().text_color(rgb(0x88c0d0))
+                    }))
             )
             .child(
                 div()
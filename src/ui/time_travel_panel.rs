@@ -114,6 +114,14 @@ impl TimeTravelPanel {
                 self.scroll_view.add(error_label);
             }
         }
+        
+        if let Ok(stats) = self.time_travel_engine.storage_stats() {
+            let storage_label = Label::new(&format!(
+                "Snapshot Storage: {} KiB ({} keyframes, {} deltas, {} KiB uncompressed)",
+                stats.stored_bytes / 1024, stats.keyframes, stats.deltas, stats.full_bytes / 1024
+            ));
+            self.scroll_view.add(storage_label);
+        }
     }
     
     /// Update events list display
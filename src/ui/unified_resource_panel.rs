@@ -5,6 +5,8 @@
 use gpui::{Widget, View, ViewContext, RenderContext, LayoutContext, EventContext, Color, Rect, Point, BoxConstraints, Label, ScrollView, Panel, Button};
 use crate::dbos_integration::{UnifiedResourceManager, UnifiedResourceInfo, SystemType, ResourceStatus};
 use std::sync::Arc;
+use std::sync::mpsc::Receiver;
+use crate::core::event_bus::{EventBus, EventFilter, EventTopic, SystemEvent};
 
 /// Unified Resource Management Panel
 pub struct UnifiedResourcePanel {
@@ -15,6 +17,9 @@ pub struct UnifiedResourcePanel {
     main_panel: Panel,
     scroll_view: ScrollView,
     refresh_button: Button,
+    
+    /// Table changes published by the DBOS tables manager
+    table_events: Receiver<SystemEvent>,
 }
 
 impl UnifiedResourcePanel {
@@ -27,7 +32,30 @@ impl UnifiedResourcePanel {
             refresh_button: Button::new("Refresh", || {
                 // TODO: Implement refresh functionality
             }),
+            table_events: Self::subscribe_table_events(),
+        }
+    }
+    
+    /// Subscribe to DBOS table changes
+    ///
+    /// Only whether a table changed matters, so one pending event is enough.
+    fn subscribe_table_events() -> Receiver<SystemEvent> {
+        EventBus::global().subscribe_bounded(EventFilter::topic(EventTopic::Table), 1).1
+    }
+    
+    /// Refresh the panel if any table changed since the last call
+    ///
+    /// Returns whether a refresh happened.
+    pub fn process_events(&mut self, cx: &mut ViewContext) -> bool {
+        let mut changed = false;
+        while self.table_events.try_recv().is_ok() {
+            changed = true;
+        }
+        
+        if changed {
+            self.refresh(cx);
         }
+        changed
     }
     
     /// Initialize UI components
@@ -149,6 +177,7 @@ impl Default for UnifiedResourcePanel {
             main_panel: Panel::new(),
             scroll_view: ScrollView::new(),
             refresh_button: Button::new("Refresh", || {}),
+            table_events: Self::subscribe_table_events(),
         }
     }
 }
\ No newline at end of file
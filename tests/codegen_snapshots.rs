@@ -0,0 +1,191 @@
+// Code Generation Snapshot Tests for OSland
+// Copyright (c) 2025 OSland Project Team
+// SPDX-License-Identifier: MulanPSL-2.0
+
+//! Golden-file snapshot tests for `TileCompiler::generate_execution_code`.
+//!
+//! Every representative graph is rendered through every built-in `TargetLanguage`
+//! and compared with `tests/golden/codegen/<graph>/<language>.golden`.
+//!
+//! Run with `cargo test --test codegen_snapshots`. After an intentional change to
+//! the generated code, update the golden files with
+//! `cargo test --test codegen_snapshots -- --bless` (or `OSLAND_BLESS=1`) and
+//! review the diff before committing. A missing golden file is a failure too, so a
+//! new graph or language needs a bless run and its golden files committed.
+
+use osland::core::architecture::KernelArchitecture;
+use osland::tile_engine::tile_compiler::{CompilationOptions, TargetLanguage, TileCompiler};
+use osland::tile_engine::tile_core::{ConnectionType, PortType, Tile, TileConnection, TileGraph, TilePort, TileType};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+/// Create a tile with a fixed ID so snapshots are reproducible
+fn fixed_tile(id: &str, name: &str, tile_type: TileType) -> Tile {
+    let mut tile = Tile::new(name.to_string(), tile_type, format!("{} tile", name));
+    tile.id = id.to_string();
+    tile
+}
+
+fn port(id: &str, port_type: PortType, data_type: &str) -> TilePort {
+    TilePort {
+        id: id.to_string(),
+        name: id.to_string(),
+        port_type,
+        data_type: data_type.to_string(),
+        description: format!("{} port", id),
+    }
+}
+
+/// A single processing tile with properties and execution code
+fn single_tile_graph() -> TileGraph {
+    let mut graph = TileGraph::new("single_tile".to_string());
+    graph.id = "graph-single".to_string();
+    
+    let mut cpu = fixed_tile("tile-cpu", "cpu_core", TileType::Processing);
+    cpu.set_property("cores".to_string(), "4".to_string());
+    cpu.set_property("frequency_mhz".to_string(), "2000".to_string());
+    cpu.set_initialization_code("init_cpu()".to_string());
+    cpu.set_execution_code("run_cpu()".to_string());
+    cpu.add_port(port("in", PortType::Input, "u32"));
+    cpu.add_port(port("out", PortType::Output, "u32"));
+    graph.add_tile(cpu).unwrap();
+    
+    graph
+}
+
+/// A three-stage pipeline: network -> processing -> storage
+fn pipeline_graph() -> TileGraph {
+    let mut graph = TileGraph::new("pipeline".to_string());
+    graph.id = "graph-pipeline".to_string();
+    
+    let mut nic = fixed_tile("tile-nic", "nic", TileType::Network);
+    nic.set_property("speed_mbps".to_string(), "1000".to_string());
+    nic.add_port(port("out", PortType::Output, "Packet"));
+    
+    let mut filter = fixed_tile("tile-filter", "filter", TileType::Processing);
+    filter.add_port(port("in", PortType::Input, "Packet"));
+    filter.add_port(port("out", PortType::Output, "Packet"));
+    
+    let mut disk = fixed_tile("tile-disk", "disk", TileType::Storage);
+    disk.set_property("size_gb".to_string(), "256".to_string());
+    disk.add_port(port("in", PortType::Input, "Packet"));
+    
+    graph.add_tile(nic).unwrap();
+    graph.add_tile(filter).unwrap();
+    graph.add_tile(disk).unwrap();
+    
+    for (id, source, dest) in [("conn-1", "tile-nic", "tile-filter"), ("conn-2", "tile-filter", "tile-disk")] {
+        graph.add_connection(TileConnection {
+            id: id.to_string(),
+            source_tile_id: source.to_string(),
+            source_port_id: "out".to_string(),
+            dest_tile_id: dest.to_string(),
+            dest_port_id: "in".to_string(),
+            connection_type: ConnectionType::DataFlow,
+        }).unwrap();
+    }
+    
+    graph
+}
+
+fn golden_path(graph: &TileGraph, language: &TargetLanguage) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden/codegen")
+        .join(&graph.name)
+        .join(format!("{}.golden", language.name()))
+}
+
+/// Print a short line-level diff of the first differences
+fn print_diff(expected: &str, actual: &str) {
+    let expected_lines: Vec<&str> = expected.lines().collect();
+    let actual_lines: Vec<&str> = actual.lines().collect();
+    let mut shown = 0;
+    
+    for index in 0..expected_lines.len().max(actual_lines.len()) {
+        let e = expected_lines.get(index).copied();
+        let a = actual_lines.get(index).copied();
+        if e != a {
+            println!("    line {}:", index + 1);
+            println!("      - {}", e.unwrap_or("<missing>"));
+            println!("      + {}", a.unwrap_or("<missing>"));
+            shown += 1;
+            if shown == 5 {
+                println!("    ...");
+                break;
+            }
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let bless = std::env::args().any(|arg| arg == "--bless")
+        || std::env::var("OSLAND_BLESS").map_or(false, |v| v == "1");
+    // Respect libtest's `--list` so `cargo test -- --list` keeps working
+    let list_only = std::env::args().any(|arg| arg == "--list");
+    
+    let graphs = vec![single_tile_graph(), pipeline_graph()];
+    let mut failures = 0;
+    let mut written = 0;
+    let mut checked = 0;
+    
+    for graph in &graphs {
+        for language in TargetLanguage::all() {
+            let name = format!("{}::{}", graph.name, language.name());
+            if list_only {
+                println!("{}: test", name);
+                continue;
+            }
+            
+            let options = CompilationOptions {
+                target_language: language.clone(),
+                ..CompilationOptions::default()
+            };
+            let compiler = TileCompiler::new(KernelArchitecture::Microkernel, Some(options));
+            let actual = match compiler.generate_execution_code(graph) {
+                Ok(code) => code,
+                Err(e) => {
+                    println!("FAILED {}: code generation error: {}", name, e);
+                    failures += 1;
+                    continue;
+                }
+            };
+            
+            let path = golden_path(graph, &language);
+            checked += 1;
+            
+            match fs::read_to_string(&path) {
+                Ok(expected) if expected == actual => {}
+                Ok(expected) if !bless => {
+                    println!("FAILED {}: output differs from {}", name, path.display());
+                    print_diff(&expected, &actual);
+                    failures += 1;
+                }
+                Err(_) if !bless => {
+                    println!("FAILED {}: missing golden file {}", name, path.display());
+                    failures += 1;
+                }
+                result => {
+                    if result.is_err() {
+                        println!("new snapshot {}: {}", name, path.display());
+                    }
+                    fs::create_dir_all(path.parent().unwrap()).expect("Failed to create golden directory");
+                    fs::write(&path, &actual).expect("Failed to write golden file");
+                    written += 1;
+                }
+            }
+        }
+    }
+    
+    if list_only {
+        return ExitCode::SUCCESS;
+    }
+    
+    println!("codegen snapshots: {} checked, {} written, {} failed", checked, written, failures);
+    if failures > 0 {
+        println!("Re-run with `cargo test --test codegen_snapshots -- --bless` to accept the new output and commit the golden files.");
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
@@ -6,7 +6,10 @@ use osland::tile_engine::{
     tile_core::{Tile, TileType, TilePort, PortType, TileGraph},
     tile_designer::TileDesigner,
     tile_library::TileLibrary,
+    tile_optimizer::TileOptimizer,
+    tile_compiler::TileCompiler,
 };
+use osland::core::architecture::KernelArchitecture;
 
 #[test]
 fn test_tile_creation() {
@@ -115,4 +118,66 @@ fn test_tile_library_save_load() {
     let loaded_library = TileLibrary::load_from_file(temp_path).expect("Failed to load library");
     
     assert_eq!(library.get_categories().len(), loaded_library.get_categories().len());
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_tile_power_estimation_and_comparison() {
+    let optimizer = TileOptimizer::new(None);
+    
+    let mut baseline = TileGraph::new("Baseline".to_string());
+    let mut cpu = Tile::new("CPU".to_string(), TileType::Processing, "CPU".to_string());
+    cpu.set_property("cores".to_string(), "4".to_string());
+    cpu.set_property("frequency_mhz".to_string(), "2000".to_string());
+    baseline.add_tile(cpu.clone()).unwrap();
+    
+    let estimate = optimizer.estimate_power(&baseline);
+    assert_eq!(estimate.tiles.len(), 1);
+    assert!(estimate.total_static_power_w > 0.0);
+    assert!(estimate.total_dynamic_power_w > 0.0);
+    
+    // Halving the core count must reduce the estimate
+    let mut candidate = TileGraph::new("Candidate".to_string());
+    let mut small_cpu = cpu.clone();
+    small_cpu.set_property("cores".to_string(), "2".to_string());
+    candidate.add_tile(small_cpu).unwrap();
+    
+    let comparison = optimizer.compare_power(&baseline, &candidate);
+    assert!(comparison.delta_power_w < 0.0);
+    assert!(comparison.delta_percent < 0.0);
+    assert_eq!(comparison.details.len(), 1);
+}
+
+#[test]
+fn test_tile_component_round_trip() {
+    let mut tile = Tile::new(
+        "Scheduler".to_string(),
+        TileType::Custom("Scheduler".to_string()),
+        "A scheduler tile".to_string(),
+    );
+    tile.add_port(TilePort {
+        id: "tasks".to_string(),
+        name: "tasks".to_string(),
+        port_type: PortType::Input,
+        data_type: "Task".to_string(),
+        description: "Incoming tasks".to_string(),
+    });
+    tile.set_property("policy".to_string(), "cfs".to_string());
+    tile.add_dependency("timer".to_string());
+    
+    let mut graph = TileGraph::new("Round Trip".to_string());
+    graph.add_tile(tile.clone()).unwrap();
+    
+    let compiler = TileCompiler::new(KernelArchitecture::Microkernel, None);
+    let components = compiler.compile_to_components(&graph).unwrap();
+    assert_eq!(components.len(), 1);
+    
+    let restored = TileCompiler::convert_component_to_tile(&components[0]);
+    assert_eq!(restored.id, tile.id);
+    assert_eq!(restored.name, tile.name);
+    assert_eq!(restored.tile_type, tile.tile_type);
+    assert_eq!(restored.ports.len(), 1);
+    assert_eq!(restored.ports[0].name, "tasks");
+    assert_eq!(restored.ports[0].data_type, "Task");
+    assert_eq!(restored.get_property("policy"), Some(&"cfs".to_string()));
+    assert_eq!(restored.dependencies, vec!["timer".to_string()]);
+}